@@ -244,6 +244,7 @@ fn normalize_type(input: &str) -> &str {
         "uint32" => "BigEndian<u32>",
         "int8" => "BigEndian<i8>",
         "int16" => "BigEndian<i16>",
+        "int24" => "BigEndian<Int24>",
         "int32" => "BigEndian<i32>",
         "FWORD" => "BigEndian<FWord>",
         "UFWORD" => "BigEndian<UfWord>",
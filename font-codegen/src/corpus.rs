@@ -0,0 +1,386 @@
+//! Generating a structured corpus for fuzzing.
+//!
+//! The codegen inputs tell us where the fields of each table are, and which
+//! of them are offsets or counts. We use this to mutate real fonts in ways
+//! that random byte flips rarely find: truncating a table at a field
+//! boundary, pointing an offset out of bounds or back at its parent, or
+//! claiming more items than a table contains.
+//!
+//! Positions are only known up to the first field that is not fixed-size.
+//! If that field is an array of fixed-size records, the fields of its first
+//! record are included too, since records often contain offsets.
+
+use std::collections::HashSet;
+
+use crate::{
+    error::ErrorReport,
+    parsing::{Count, CountArg, Field, FieldType, Item, Items, Phase},
+};
+
+/// The known fields of a top-level table.
+#[derive(Clone, Debug)]
+pub struct TableLayout {
+    pub tag: String,
+    pub fields: Vec<FieldLayout>,
+}
+
+/// The position of a single field, relative to the start of its table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldLayout {
+    pub name: String,
+    pub offset: usize,
+    pub len: usize,
+    pub kind: FieldKind,
+}
+
+/// How a field is used when parsing, which determines how it is mutated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    Offset,
+    Count,
+    Other,
+}
+
+/// A mutated font.
+pub struct Mutation {
+    /// A short description of the mutation, usable in a file name.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Compute the layouts of the tables with tags in a codegen input.
+pub fn table_layouts(code_str: &str) -> Result<Vec<TableLayout>, syn::Error> {
+    let mut items: Items = syn::parse_str(code_str)?;
+    items.sanity_check(Phase::Parse)?;
+    items.resolve_pending()?;
+    items.sanity_check(Phase::Analysis)?;
+
+    Ok(items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Table(table) => Some(TableLayout {
+                tag: table.attrs.tag.as_ref()?.attr.value(),
+                fields: layout_fields(&items, &table.fields.fields, ""),
+            }),
+            _ => None,
+        })
+        .collect())
+}
+
+/// The layout of the leading fixed-size fields.
+fn layout_fields(items: &Items, fields: &[Field], prefix: &str) -> Vec<FieldLayout> {
+    let counts = fields
+        .iter()
+        .filter_map(|fld| fld.attrs.count.as_ref())
+        .flat_map(|count| count.attr.iter_referenced_fields())
+        .collect::<HashSet<_>>();
+    let mut result = Vec::new();
+    let mut offset = 0;
+    for fld in fields {
+        let name = format!("{prefix}{}", fld.name);
+        let (len, kind) = match &fld.typ {
+            FieldType::Offset { typ, .. } => match scalar_len(items, typ) {
+                Some(len) => (len, FieldKind::Offset),
+                None => break,
+            },
+            FieldType::Scalar { typ } => match scalar_len(items, typ) {
+                Some(len) if counts.contains(&fld.name) || is_count_name(&name) => {
+                    (len, FieldKind::Count)
+                }
+                Some(len) => (len, FieldKind::Other),
+                None => break,
+            },
+            FieldType::Struct { typ } => match record_layout(items, typ, &format!("{name}.")) {
+                Some((record, len)) => {
+                    result.extend(record.into_iter().map(|mut fld| {
+                        fld.offset += offset;
+                        fld
+                    }));
+                    offset += len;
+                    continue;
+                }
+                None => break,
+            },
+            // the first record of an array, if its count is a field we know
+            FieldType::Array { inner_typ } => {
+                let count_is_known = matches!(
+                    fld.attrs.count.as_ref().map(|count| &count.attr),
+                    Some(Count::SingleArg(CountArg::Field(count)))
+                        if result.iter().any(|prev| prev.name == format!("{prefix}{count}"))
+                );
+                if let (FieldType::Struct { typ }, true) = (inner_typ.as_ref(), count_is_known) {
+                    if let Some((record, _)) = record_layout(items, typ, &format!("{name}[0].")) {
+                        result.extend(record.into_iter().map(|mut fld| {
+                            fld.offset += offset;
+                            fld
+                        }));
+                    }
+                }
+                break;
+            }
+            _ => break,
+        };
+        result.push(FieldLayout {
+            name,
+            offset,
+            len,
+            kind,
+        });
+        offset += len;
+    }
+    result
+}
+
+/// The layout and length of a record, if every field is fixed-size.
+fn record_layout(
+    items: &Items,
+    name: &syn::Ident,
+    prefix: &str,
+) -> Option<(Vec<FieldLayout>, usize)> {
+    let Some(Item::Record(record)) = items.get(name) else {
+        return None;
+    };
+    let layout = layout_fields(items, &record.fields.fields, prefix);
+    let is_fixed_size = layout.len() == record.fields.fields.len()
+        && record
+            .fields
+            .fields
+            .iter()
+            .all(|fld| matches!(fld.typ, FieldType::Scalar { .. } | FieldType::Offset { .. }));
+    let len = layout.last().map(|fld| fld.offset + fld.len).unwrap_or(0);
+    is_fixed_size.then_some((layout, len))
+}
+
+/// The size in bytes of a scalar type, if known.
+fn scalar_len(items: &Items, typ: &syn::Ident) -> Option<usize> {
+    match items.get(typ) {
+        Some(Item::RawEnum(item)) => return scalar_len(items, &item.typ),
+        Some(Item::Flags(item)) => return scalar_len(items, &item.typ),
+        _ => (),
+    }
+    let len = match typ.to_string().as_str() {
+        "u8" | "i8" => 1,
+        "u16" | "i16" | "FWord" | "UfWord" | "F2Dot14" | "GlyphId" | "NameId" | "Offset16" => 2,
+        "Uint24" | "Offset24" => 3,
+        "u32" | "i32" | "Fixed" | "Tag" | "MajorMinor" | "Version16Dot16" | "Offset32" => 4,
+        "LongDateTime" => 8,
+        _ => return None,
+    };
+    Some(len)
+}
+
+/// Counts used by other tables, like `numGlyphs` in maxp, can't be found from
+/// `#[count]` attributes, so we also go by name.
+fn is_count_name(name: &str) -> bool {
+    let name = name.rsplit('.').next().unwrap_or(name);
+    name.starts_with("num_") || name.starts_with("number_of_") || name.ends_with("_count")
+}
+
+/// Generate mutations of a font, using the layouts of its tables.
+///
+/// Font collections are not supported.
+pub fn mutate_font(data: &[u8], layouts: &[TableLayout]) -> Result<Vec<Mutation>, ErrorReport> {
+    if data.get(..4) == Some(b"ttcf") {
+        return Err(ErrorReport::message("font collections are not supported"));
+    }
+    let num_tables = read_u32(data, 4, 2)
+        .ok_or_else(|| ErrorReport::message("font is too short for a table directory"))?;
+    let mut mutations = Vec::new();
+    for i in 0..num_tables as usize {
+        let record = 12 + i * 16;
+        let (Some(tag), Some(offset), Some(len)) = (
+            data.get(record..record + 4),
+            read_u32(data, record + 8, 4),
+            read_u32(data, record + 12, 4),
+        ) else {
+            return Err(ErrorReport::message("table directory is truncated"));
+        };
+        let tag = String::from_utf8_lossy(tag).into_owned();
+        let (offset, len) = (offset as usize, len as usize);
+        let Some(layout) = layouts.iter().find(|layout| layout.tag == tag) else {
+            continue;
+        };
+        let file_tag = file_name_part(tag.trim_end());
+
+        // truncate the table by shortening its record in the directory
+        let mut boundaries = layout
+            .fields
+            .iter()
+            .map(|fld| fld.offset)
+            .chain([0, len / 2])
+            .filter(|boundary| *boundary < len)
+            .collect::<Vec<_>>();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+        for boundary in boundaries {
+            let mut data = data.to_owned();
+            write_uint(&mut data[record + 12..record + 16], boundary as u32);
+            mutations.push(Mutation {
+                name: format!("{file_tag}_truncated_{boundary}"),
+                data,
+            });
+        }
+
+        for fld in &layout.fields {
+            // other fields are left to random mutation
+            if fld.kind == FieldKind::Other
+                || fld.offset + fld.len > len
+                || offset + fld.offset + fld.len > data.len()
+            {
+                continue;
+            }
+            let max = u32::MAX >> (32 - 8 * fld.len);
+            let current = read_u32(data, offset + fld.offset, fld.len).unwrap_or_default();
+            let values: &[(&str, u32)] = match fld.kind {
+                FieldKind::Offset => &[
+                    // back at the parent, which may create a cycle
+                    ("zero", 0),
+                    ("misaligned", 1),
+                    ("past_end", (len as u32).min(max)),
+                    ("max", max),
+                ],
+                FieldKind::Count => &[
+                    ("zero", 0),
+                    ("plus_one", current.saturating_add(1).min(max)),
+                    ("max", max),
+                ],
+                FieldKind::Other => unreachable!(),
+            };
+            for (kind, value) in values.iter().filter(|(_, value)| *value != current) {
+                let mut data = data.to_owned();
+                let start = offset + fld.offset;
+                write_uint(&mut data[start..start + fld.len], *value);
+                mutations.push(Mutation {
+                    name: format!("{file_tag}_{}_{kind}", file_name_part(&fld.name)),
+                    data,
+                });
+            }
+        }
+    }
+    Ok(mutations)
+}
+
+/// Replace runs of characters other than letters and digits with a single '_'.
+fn file_name_part(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Read a big-endian unsigned integer of up to four bytes.
+fn read_u32(data: &[u8], pos: usize, len: usize) -> Option<u32> {
+    let bytes = data.get(pos..pos + len)?;
+    Some(bytes.iter().fold(0, |acc, b| (acc << 8) | *b as u32))
+}
+
+/// Write a big-endian unsigned integer, filling the slice.
+fn write_uint(dest: &mut [u8], value: u32) {
+    let len = dest.len();
+    for (i, byte) in dest.iter_mut().enumerate() {
+        *byte = (value >> (8 * (len - 1 - i))) as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = r#"
+#![parse_module(read_fonts::tables::test)]
+
+#[tag = "test"]
+table Test {
+    version: u16,
+    #[nullable]
+    coverage_offset: Offset16<Test>,
+    record_count: u16,
+    #[count($record_count)]
+    records: [TestRecord],
+    trailing: u16,
+}
+
+record TestRecord {
+    tag: Tag,
+    data_offset: Offset32<Test>,
+}
+
+table NoTag {
+    value: u16,
+}
+"#;
+
+    fn field(name: &str, offset: usize, len: usize, kind: FieldKind) -> FieldLayout {
+        FieldLayout {
+            name: name.into(),
+            offset,
+            len,
+            kind,
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let layouts = table_layouts(INPUT).unwrap();
+        assert_eq!(layouts.len(), 1);
+        assert_eq!(layouts[0].tag, "test");
+        assert_eq!(
+            layouts[0].fields,
+            [
+                field("version", 0, 2, FieldKind::Other),
+                field("coverage_offset", 2, 2, FieldKind::Offset),
+                field("record_count", 4, 2, FieldKind::Count),
+                field("records[0].tag", 6, 4, FieldKind::Other),
+                field("records[0].data_offset", 10, 4, FieldKind::Offset),
+            ]
+        );
+    }
+
+    #[test]
+    fn mutate() {
+        let table = [0, 1, 0, 0, 0, 1, b'a', b'b', b'c', b'd', 0, 0, 0, 14];
+        let mut font = vec![0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+        font.extend(b"test");
+        font.extend([0; 4]);
+        font.extend(28u32.to_be_bytes());
+        font.extend((table.len() as u32).to_be_bytes());
+        font.extend(table);
+
+        let layouts = table_layouts(INPUT).unwrap();
+        let mutations = mutate_font(&font, &layouts).unwrap();
+        let names = mutations
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "test_truncated_0",
+                "test_truncated_2",
+                "test_truncated_4",
+                "test_truncated_6",
+                "test_truncated_7",
+                "test_truncated_10",
+                "test_coverage_offset_misaligned",
+                "test_coverage_offset_past_end",
+                "test_coverage_offset_max",
+                "test_record_count_zero",
+                "test_record_count_plus_one",
+                "test_record_count_max",
+                "test_records_0_data_offset_zero",
+                "test_records_0_data_offset_misaligned",
+                "test_records_0_data_offset_max",
+            ]
+        );
+        // only the directory changes when truncating
+        assert_eq!(mutations[3].data[24..28], 6u32.to_be_bytes());
+        assert_eq!(mutations[3].data[28..], table);
+        let max = mutations.iter().find(|m| m.name == "test_record_count_max");
+        assert_eq!(max.unwrap().data[32..34], [0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn collections_are_rejected() {
+        assert!(mutate_font(b"ttcf\0\x01\0\0", &[]).is_err());
+    }
+}
@@ -268,6 +268,9 @@ fn traversal_arm_for_field(
     let name_str = &fld.name.to_string();
     let name = &fld.name;
     let maybe_unwrap = fld.attrs.since_version.is_some().then(|| quote!(.unwrap()));
+    // the getter for a nullable offset already flattens the version into the
+    // returned option, which the traversal constructors accept directly
+    let maybe_unwrap_target = (!fld.is_nullable()).then(|| maybe_unwrap.clone()).flatten();
     if let Some(traverse_with) = &fld.attrs.traverse_with {
         let traverse_fn = &traverse_with.attr;
         if traverse_fn == "skip" {
@@ -289,7 +292,7 @@ fn traversal_arm_for_field(
                     #name_str,
                     traversal::FieldType::offset_to_array_of_records(
                         self.#name()#maybe_unwrap,
-                        self.#getter(#pass_data)#maybe_unwrap,
+                        self.#getter(#pass_data)#maybe_unwrap_target,
                         stringify!(#typ),
                         #offset_data,
                     )
@@ -301,7 +304,7 @@ fn traversal_arm_for_field(
                 OffsetTarget::Array(_) => quote!(offset_to_array_of_scalars),
             };
             let getter = fld.offset_getter_name();
-            quote!(Field::new(#name_str, FieldType::#constructor_name(self.#name()#maybe_unwrap, self.#getter(#pass_data)#maybe_unwrap)))
+            quote!(Field::new(#name_str, FieldType::#constructor_name(self.#name()#maybe_unwrap, self.#getter(#pass_data)#maybe_unwrap_target)))
         }
         FieldType::Scalar { .. } => quote!(Field::new(#name_str, self.#name()#maybe_unwrap)),
 
@@ -391,7 +394,10 @@ fn traversal_arm_for_field(
             quote!(Field::new(#name_str, self.#name() #maybe_unwrap .traversal_type(#offset_data)))
         }
         FieldType::Struct { .. } => {
-            quote!(compile_error!(concat!("another weird type: ", #name_str)))
+            let offset_data = pass_data
+                .cloned()
+                .unwrap_or_else(|| fld.offset_getter_data_src());
+            quote!(Field::new(#name_str, traversal::FieldType::Record(self.#name() #maybe_unwrap .clone().traverse(#offset_data))))
         }
         FieldType::PendingResolution { .. } => panic!("Should have resolved {fld:#?}"),
     }
@@ -411,6 +417,23 @@ fn check_resolution(phase: Phase, field_type: &FieldType) -> syn::Result<()> {
 }
 
 impl Field {
+    /// The name of this field as it is written in the spec, in lowerCamelCase.
+    ///
+    /// This is used to name the field in errors.
+    pub(crate) fn spec_name(&self) -> String {
+        let name = self.name.to_string();
+        let mut words = name.split('_');
+        let mut result = words.next().unwrap_or_default().to_owned();
+        for word in words {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                result.extend(first.to_uppercase());
+                result.push_str(chars.as_str());
+            }
+        }
+        result
+    }
+
     pub(crate) fn type_for_record(&self) -> TokenStream {
         match &self.typ {
             FieldType::Offset { typ, .. } if self.is_nullable() => {
@@ -557,6 +580,10 @@ impl Field {
             FieldType::Offset { typ, .. } | FieldType::Scalar { typ } => {
                 quote!(#typ::RAW_BYTE_LEN)
             }
+            // a fixed-size record
+            FieldType::Struct { typ } if !self.has_computed_len() => {
+                quote!(#typ::RAW_BYTE_LEN)
+            }
             FieldType::Struct { .. }
             | FieldType::Array { .. }
             | FieldType::ComputedArray { .. }
@@ -661,7 +688,12 @@ impl Field {
         let is_var_array = self.is_var_array();
         let is_versioned = self.is_version_dependent();
 
+        let is_record = matches!(self.typ, FieldType::Struct { .. }) && !self.has_computed_len();
+
         let mut return_type = self.raw_getter_return_type();
+        if is_record {
+            return_type = quote!(&'a #return_type);
+        }
         if is_versioned {
             return_type = quote!(Option<#return_type>);
         }
@@ -670,6 +702,8 @@ impl Field {
         let mut read_stmt = if let Some(args) = &self.attrs.read_with_args {
             let get_args = args.to_tokens_for_table_getter();
             quote!( self.data.read_with_args(range, &#get_args).unwrap() )
+        } else if is_record {
+            quote!(self.data.read_ref_at(range.start).unwrap())
         } else if is_var_array {
             quote!(VarLenArray::read(self.data.split_off(range.start).unwrap()).unwrap())
         } else if is_array {
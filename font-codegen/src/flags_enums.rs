@@ -298,6 +298,13 @@ pub(crate) fn generate_flags(raw: &BitFlags) -> proc_macro2::TokenStream {
                 src.bits().into()
             }
         }
+
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for #name {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                u.arbitrary().map(Self::from_bits_truncate)
+            }
+        }
     }
 }
 
@@ -327,6 +334,9 @@ pub(crate) fn generate_raw_enum(raw: &RawEnum) -> TokenStream {
             #name = #value,
         }
     });
+    // arbitrary values are only drawn from known variants, since `Unknown`
+    // cannot be round-tripped
+    let known_variants = raw.variants.iter().map(|variant| &variant.name);
     let variant_inits = raw.variants.iter().map(|variant| {
         let name = &variant.name;
         let value = &variant.value;
@@ -374,6 +384,13 @@ pub(crate) fn generate_raw_enum(raw: &RawEnum) -> TokenStream {
                 (src as #typ).into()
             }
         }
+
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for #name {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                u.choose(&[ #( Self::#known_variants, )* ]).copied()
+            }
+        }
     }
 }
 
@@ -6,6 +6,7 @@ use log::debug;
 use proc_macro2::TokenStream;
 use quote::quote;
 
+mod corpus;
 mod error;
 mod fields;
 mod flags_enums;
@@ -16,6 +17,7 @@ mod table;
 
 use parsing::{Item, Items, Phase};
 
+pub use corpus::{mutate_font, table_layouts, FieldKind, FieldLayout, Mutation, TableLayout};
 pub use error::ErrorReport;
 
 /// Codegeneration mode.
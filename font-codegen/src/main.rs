@@ -6,6 +6,9 @@ use std::path::{Path, PathBuf};
 
 use font_codegen::{ErrorReport, Mode};
 
+/// The plan listing the codegen inputs, relative to the workspace root.
+const DEFAULT_PLAN: &str = "resources/codegen_plan.toml";
+
 use log::{debug, error};
 use miette::miette;
 use rayon::prelude::*;
@@ -16,6 +19,7 @@ fn main() -> miette::Result<()> {
     match flags::Args::from_env() {
         Ok(args) => match args.subcommand {
             flags::ArgsCmd::Plan(plan) => run_plan(&plan.path),
+            flags::ArgsCmd::Corpus(args) => run_corpus(&args),
             flags::ArgsCmd::File(args) => {
                 let generated_code = run_for_path(&args.path, args.mode)?;
                 print!("{generated_code}");
@@ -69,6 +73,57 @@ fn run_plan(path: &Path) -> miette::Result<()> {
     Ok(())
 }
 
+/// Write structured mutations of fonts, using the layouts of the tables
+/// generated for read-fonts.
+fn run_corpus(args: &flags::Corpus) -> miette::Result<()> {
+    ensure_correct_working_directory()?;
+    let plan_path = args
+        .plan
+        .as_deref()
+        .unwrap_or_else(|| Path::new(DEFAULT_PLAN));
+    let contents = read_contents(plan_path)?;
+    let plan: CodegenPlan =
+        toml::from_str(&contents).map_err(|e| miette!("failed to parse plan: '{}'", e))?;
+    let mut layouts = Vec::new();
+    for op in plan
+        .generate
+        .iter()
+        .filter(|op| matches!(op.mode, Mode::Parse))
+    {
+        let contents = read_contents(&op.source)?;
+        let layout = font_codegen::table_layouts(&contents)
+            .map_err(|e| ErrorReport::from_error_src(&e, &op.source, contents))?;
+        layouts.extend(layout);
+    }
+
+    std::fs::create_dir_all(&args.output).map_err(|e| {
+        miette!(
+            "failed to create directory '{}': {e}",
+            args.output.display()
+        )
+    })?;
+    for path in &args.fonts {
+        let data = std::fs::read(path)
+            .map_err(|e| miette!("error reading '{}': {}", path.display(), e))?;
+        let mutations = font_codegen::mutate_font(&data, &layouts)
+            .map_err(|e| miette!("failed to mutate '{}': {e}", path.display()))?;
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let ext = path.extension().unwrap_or_default().to_string_lossy();
+        for mutation in &mutations {
+            let target = args.output.join(format!("{stem}_{}.{ext}", mutation.name));
+            debug!(
+                "writing {} bytes to {}",
+                mutation.data.len(),
+                target.display()
+            );
+            std::fs::write(&target, &mutation.data)
+                .map_err(|e| miette!("error writing '{}': {}", target.display(), e))?;
+        }
+        println!("{}: {} mutations", path.display(), mutations.len());
+    }
+    Ok(())
+}
+
 fn ensure_correct_working_directory() -> miette::Result<()> {
     if !(Path::new("read-fonts").is_dir() && Path::new("resources").is_dir()) {
         return Err(miette!(
@@ -119,6 +174,17 @@ mod flags {
             default cmd plan
                 /// plan path
                 required path: PathBuf {}
+            /// Write structurally mutated fonts, for use as a fuzzing corpus
+            cmd corpus
+                /// The fonts to mutate
+                repeated fonts: PathBuf
+                {
+                    /// The directory to write the corpus to
+                    required -o, --output output: PathBuf
+                    /// The plan listing the codegen inputs to take table
+                    /// layouts from
+                    optional --plan plan: PathBuf
+                }
         }
     }
 }
@@ -239,6 +239,11 @@ pub(crate) struct FieldReadArgs {
 pub(crate) struct SinceVersion {
     major: syn::LitInt,
     minor: Option<syn::LitInt>,
+    /// The type of the version field this is compared with.
+    ///
+    /// This is set during resolution, and is used to generate a typed
+    /// version for major/minor comparisons.
+    version_type: Option<syn::Ident>,
 }
 
 /// Annotations for how to calculate the count of an array.
@@ -275,6 +280,8 @@ pub(crate) enum CountTransform {
     DeltaSetIndexData,
     /// three args: the axis count, the tuple index, and a constant on that index
     TupleLen,
+    /// three args: the item count, the word delta count, and the region index count
+    ItemVariationDataLen,
 }
 
 /// Attributes for specifying how to compile a field
@@ -695,7 +702,7 @@ impl Parse for FieldType {
 
 // https://learn.microsoft.com/en-us/typography/opentype/spec/otff#data-types
 // Offset(16,24,32) get special handling, not listed here
-// GlyphId and MajorMinor are *not* spec names for scalar but are captured here
+// GlyphId, MajorMinor and NameId are *not* spec names for scalar but are captured here
 #[derive(Debug, PartialEq)]
 enum WellKnownScalar {
     UInt8,
@@ -703,6 +710,7 @@ enum WellKnownScalar {
     UInt16,
     Int16,
     UInt24,
+    Int24,
     UInt32,
     Int32,
     Fixed,
@@ -713,7 +721,9 @@ enum WellKnownScalar {
     Tag,
     Version16Dot16,
     GlyphId,
+    GlyphId24,
     MajorMinor,
+    NameId,
 }
 
 impl FromStr for WellKnownScalar {
@@ -728,6 +738,8 @@ impl FromStr for WellKnownScalar {
             "i16" => Ok(WellKnownScalar::Int16),
             "u24" => Ok(WellKnownScalar::UInt24),
             "Uint24" => Ok(WellKnownScalar::UInt24),
+            "i24" => Ok(WellKnownScalar::Int24),
+            "Int24" => Ok(WellKnownScalar::Int24),
             "u32" => Ok(WellKnownScalar::UInt32),
             "i32" => Ok(WellKnownScalar::Int32),
             "Fixed" => Ok(WellKnownScalar::Fixed),
@@ -738,7 +750,9 @@ impl FromStr for WellKnownScalar {
             "Tag" => Ok(WellKnownScalar::Tag),
             "Version16Dot16" => Ok(WellKnownScalar::Version16Dot16),
             "GlyphId" => Ok(WellKnownScalar::GlyphId),
+            "GlyphId24" => Ok(WellKnownScalar::GlyphId24),
             "MajorMinor" => Ok(WellKnownScalar::MajorMinor),
+            "NameId" => Ok(WellKnownScalar::NameId),
             _ => Err(()),
         }
     }
@@ -1115,6 +1129,27 @@ fn resolve_field(
     Ok(())
 }
 
+/// Resolve the `#[since_version]` attributes of these fields against the
+/// type of the `#[version]` field, if there is one.
+fn resolve_since_versions(fields: &mut [Field]) -> Result<(), syn::Error> {
+    let Some(version_type) = fields
+        .iter()
+        .find(|fld| fld.attrs.version.is_some())
+        .and_then(|fld| match &fld.typ {
+            FieldType::Scalar { typ } => Some(typ.clone()),
+            _ => None,
+        })
+    else {
+        return Ok(());
+    };
+    for field in fields {
+        if let Some(since_version) = field.attrs.since_version.as_mut() {
+            since_version.attr.resolve(&version_type)?;
+        }
+    }
+    Ok(())
+}
+
 impl Items {
     pub(crate) fn sanity_check(&self, phase: Phase) -> syn::Result<()> {
         for item in self.iter() {
@@ -1149,9 +1184,10 @@ impl Items {
                 Item::Table(item) => &mut item.fields.fields,
                 _ => continue,
             };
-            for field in fields {
+            for field in fields.iter_mut() {
                 resolve_field(&known, field)?;
             }
+            resolve_since_versions(fields)?;
         }
         Ok(())
     }
@@ -1273,6 +1309,10 @@ static TRANSFORM_IDENTS: &[(CountTransform, &str)] = &[
     (CountTransform::DeltaValueCount, "delta_value_count"),
     (CountTransform::DeltaSetIndexData, "delta_set_index_data"),
     (CountTransform::TupleLen, "tuple_len"),
+    (
+        CountTransform::ItemVariationDataLen,
+        "item_variation_data_len",
+    ),
 ];
 
 impl FromStr for CountTransform {
@@ -1304,6 +1344,7 @@ impl CountTransform {
             CountTransform::DeltaValueCount => 3,
             CountTransform::DeltaSetIndexData => 2,
             CountTransform::TupleLen => 3,
+            CountTransform::ItemVariationDataLen => 3,
         }
     }
 }
@@ -1327,17 +1368,52 @@ impl Parse for SinceVersion {
                 input.parse::<syn::LitInt>()
             })
             .transpose()
-            .map(|minor| Self { major, minor })
+            .map(|minor| Self {
+                major,
+                minor,
+                version_type: None,
+            })
+    }
+}
+
+impl SinceVersion {
+    /// Check that this version can be compared with a version field of the
+    /// given type, and remember the type for codegen.
+    pub(crate) fn resolve(&mut self, version_type: &syn::Ident) -> syn::Result<()> {
+        let span = self.major.span();
+        match (version_type.to_string().as_str(), &self.minor) {
+            ("u16", Some(_)) => {
+                return Err(logged_syn_error(
+                    span,
+                    "version field is u16, expected a single version number",
+                ))
+            }
+            ("MajorMinor" | "Version16Dot16", None) => {
+                return Err(logged_syn_error(
+                    span,
+                    format!("version field is {version_type}, expected (major, minor)"),
+                ))
+            }
+            ("Version16Dot16", Some(minor)) if minor.base10_parse::<u16>()? > 9 => {
+                return Err(logged_syn_error(
+                    minor.span(),
+                    "Version16Dot16 minor version must be in the range 0..=9",
+                ))
+            }
+            _ => (),
+        }
+        self.version_type = Some(version_type.clone());
+        Ok(())
     }
 }
 
 impl ToTokens for SinceVersion {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let major = &self.major;
-        if let Some(minor) = &self.minor {
-            tokens.extend(quote!( (#major, #minor) ));
-        } else {
-            major.to_tokens(tokens);
+        match (&self.minor, &self.version_type) {
+            (Some(minor), Some(typ)) => tokens.extend(quote!( #typ::new(#major, #minor) )),
+            (Some(minor), None) => tokens.extend(quote!( (#major, #minor) )),
+            (None, _) => major.to_tokens(tokens),
         }
     }
 }
@@ -1434,6 +1510,9 @@ impl Count {
                 (CountTransform::TupleLen, [a, b, c]) => {
                     quote!(TupleIndex::tuple_len(#a, #b, #c))
                 }
+                (CountTransform::ItemVariationDataLen, [a, b, c]) => {
+                    quote!(ItemVariationData::delta_sets_len(#a, #b, #c))
+                }
                 _ => unreachable!("validated before now"),
             },
         }
@@ -1729,4 +1808,26 @@ mod tests {
         assert!(parse("1, 2, 3").is_err());
         assert!(parse("1, 'b'").is_err());
     }
+
+    #[test]
+    fn resolve_available() {
+        fn resolve(s: &str, version_type: &str) -> Result<String, syn::Error> {
+            let mut since_version: SinceVersion = syn::parse_str(s)?;
+            since_version.resolve(&syn::parse_str(version_type)?)?;
+            Ok(since_version.into_token_stream().to_string())
+        }
+
+        assert_eq!(resolve("1", "u16").unwrap(), "1");
+        assert_eq!(
+            resolve("1, 2", "MajorMinor").unwrap(),
+            "MajorMinor :: new (1 , 2)"
+        );
+        assert_eq!(
+            resolve("1, 0", "Version16Dot16").unwrap(),
+            "Version16Dot16 :: new (1 , 0)"
+        );
+        assert!(resolve("1, 1", "u16").is_err());
+        assert!(resolve("1", "MajorMinor").is_err());
+        assert!(resolve("1, 10", "Version16Dot16").is_err());
+    }
 }
@@ -200,6 +200,16 @@ pub(crate) fn generate_compile_impl(
         }
     };
 
+    // top-level tables identify themselves, so the packer can reason about
+    // their structure when resolving offset overflows
+    let table_type_fn = attrs.tag.as_ref().map(|_| {
+        quote! {
+            fn table_type(&self) -> TableType {
+                TableType::TopLevel(Self::TAG)
+            }
+        }
+    });
+
     let font_write_impl = attrs.skip_font_write.is_none().then(|| {
         quote! {
             impl #write_impl_params FontWrite for #name <#generic_param> {
@@ -207,6 +217,7 @@ pub(crate) fn generate_compile_impl(
                 fn write_into(&self, writer: &mut TableWriter) {
                     #( #write_stmts; )*
                 }
+                #table_type_fn
             }
         }
     });
@@ -283,6 +294,7 @@ pub(crate) fn generate_compile_impl(
     Ok(quote! {
         #( #docs )*
         #[derive(Clone, Debug #maybe_derive_default)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
         pub struct #name <#generic_param> {
             #( #field_decls, )*
         }
@@ -6,7 +6,7 @@ use quote::{quote, ToTokens};
 
 use crate::parsing::{Attr, GenericGroup, Item, Items, Phase};
 
-use super::parsing::{Field, ReferencedFields, Table, TableFormat, TableReadArg, TableReadArgs};
+use super::parsing::{ReferencedFields, Table, TableFormat, TableReadArg, TableReadArgs};
 
 pub(crate) fn generate(item: &Table) -> syn::Result<TokenStream> {
     let docs = &item.attrs.docs;
@@ -392,6 +392,7 @@ pub(crate) fn generate_group_compile(
     Ok(quote! {
         #( #docs)*
         #[derive(Debug, Clone)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
         pub enum #name {
             #( #variant_decls, )*
         }
@@ -467,6 +468,7 @@ pub(crate) fn generate_format_compile(
     Ok(quote! {
         #( #docs )*
         #[derive(Clone, Debug)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
         pub enum #name {
             #( #variants ),*
         }
@@ -526,8 +528,12 @@ fn generate_format_constructors(item: &TableFormat, items: &Items) -> syn::Resul
         let constructor_ident = make_snake_case_ident(var_name);
 
         let docstring = format!(" Construct a new `{}` subtable", variant.type_name());
+        // judiciously allow this lint
+        let too_many_args =
+            (constructor_args_raw.len() > 7).then(|| quote!(#[allow(clippy::too_many_arguments)]));
         constructors.push(quote! {
             #[doc = #docstring]
+            #too_many_args
             pub fn #constructor_ident ( #( #constructor_args,)*  ) -> Self {
                 Self::#var_name( #var_type::new( #( #constructor_arg_names, )* ))
             }
@@ -742,7 +748,14 @@ impl Table {
     }
 
     fn iter_field_validation_stmts(&self) -> impl Iterator<Item = TokenStream> + '_ {
-        self.fields.iter().map(Field::field_parse_validation_stmts)
+        self.fields.iter().map(|fld| {
+            let name = fld.spec_name();
+            let stmts = fld.field_parse_validation_stmts();
+            quote! {
+                cursor.set_field(#name);
+                #stmts
+            }
+        })
     }
 
     fn iter_table_ref_getters(&self) -> impl Iterator<Item = TokenStream> + '_ {
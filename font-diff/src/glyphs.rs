@@ -0,0 +1,331 @@
+//! Comparison of glyph sets, and of the tables that are indexed by glyph.
+
+use std::collections::{BTreeMap, HashMap};
+
+use font_types::{BigEndianSlice, GlyphId, Tag};
+use read_fonts::{
+    tables::{
+        cmap::CmapSubtable,
+        glyf::{CompositeGlyph, CurvePoint, Glyf, Glyph, SimpleGlyph},
+        hmtx::Hmtx,
+        loca::Loca,
+    },
+    traversal::SomeTable,
+    FontRef, ReadError, TableProvider, TopLevelTable,
+};
+
+use crate::{traverse::Differ, Change, ChangeKind};
+
+/// The names of the glyphs in a font.
+///
+/// Glyphs are matched between fonts by name. Names come from the `post`
+/// table if possible; duplicate names are made unique by adding a suffix,
+/// as ttx does.
+pub(crate) struct GlyphSet {
+    names: Vec<String>,
+    ids: HashMap<String, GlyphId>,
+}
+
+impl GlyphSet {
+    pub(crate) fn new(font: &FontRef) -> Self {
+        let num_glyphs = font.maxp().map(|maxp| maxp.num_glyphs()).unwrap_or(0);
+        let post = font.post().ok();
+        let mut names = Vec::with_capacity(num_glyphs as usize);
+        let mut ids = HashMap::with_capacity(num_glyphs as usize);
+        for gid in (0..num_glyphs).map(GlyphId::new) {
+            let base = post
+                .as_ref()
+                .and_then(|post| post.glyph_name(gid))
+                .map(str::to_string)
+                .unwrap_or_else(|| fallback_name(gid));
+            let mut name = base.clone();
+            let mut suffix = 0;
+            while ids.contains_key(&name) {
+                suffix += 1;
+                name = format!("{base}#{suffix}");
+            }
+            ids.insert(name.clone(), gid);
+            names.push(name);
+        }
+        GlyphSet { names, ids }
+    }
+
+    /// The name of a glyph.
+    pub(crate) fn name(&self, gid: GlyphId) -> String {
+        self.names
+            .get(gid.to_u16() as usize)
+            .cloned()
+            .unwrap_or_else(|| fallback_name(gid))
+    }
+
+    /// The glyphs present in both sets, as `(name, self_gid, other_gid)`,
+    /// in the glyph order of `self`.
+    fn common<'b>(
+        &'b self,
+        other: &'b GlyphSet,
+    ) -> impl Iterator<Item = (&'b str, GlyphId, GlyphId)> + 'b {
+        self.names.iter().enumerate().filter_map(|(gid, name)| {
+            let other_gid = other.ids.get(name)?;
+            Some((name.as_str(), GlyphId::new(gid as u16), *other_gid))
+        })
+    }
+}
+
+fn fallback_name(gid: GlyphId) -> String {
+    if gid == GlyphId::NOTDEF {
+        ".notdef".into()
+    } else {
+        format!("glyph{:05}", gid.to_u16())
+    }
+}
+
+/// Records the glyphs that are present in only one of the fonts.
+pub(crate) fn diff_glyph_sets(old: &GlyphSet, new: &GlyphSet, changes: &mut Vec<Change>) {
+    for name in old.names.iter().filter(|name| !new.ids.contains_key(*name)) {
+        changes.push(Change::new(
+            format!("glyphs[{name}]"),
+            ChangeKind::Removed("glyph".into()),
+        ));
+    }
+    for name in new.names.iter().filter(|name| !old.ids.contains_key(*name)) {
+        changes.push(Change::new(
+            format!("glyphs[{name}]"),
+            ChangeKind::Added("glyph".into()),
+        ));
+    }
+}
+
+/// The advance and side bearing of each glyph, from `hmtx` or `vmtx`.
+type Metrics = Vec<(Option<u16>, Option<i16>)>;
+
+fn read_metrics(font: &FontRef, tag: Tag, num_glyphs: usize) -> Result<Metrics, ReadError> {
+    let gids = (0..num_glyphs as u16).map(GlyphId::new);
+    if tag == Hmtx::TAG {
+        let hmtx = font.hmtx()?;
+        Ok(gids
+            .map(|gid| (hmtx.advance(gid), hmtx.side_bearing(gid)))
+            .collect())
+    } else {
+        let vmtx = font.vmtx()?;
+        Ok(gids
+            .map(|gid| (vmtx.advance(gid), vmtx.side_bearing(gid)))
+            .collect())
+    }
+}
+
+/// Compares the metrics of each glyph in `hmtx` or `vmtx`.
+pub(crate) fn diff_metrics(old: &FontRef, new: &FontRef, tag: Tag, differ: &mut Differ) {
+    let old_glyphs = differ.old_glyphs();
+    let new_glyphs = differ.new_glyphs();
+    let (old_metrics, new_metrics) = match (
+        read_metrics(old, tag, old_glyphs.names.len()),
+        read_metrics(new, tag, new_glyphs.names.len()),
+    ) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(err), _) | (_, Err(err)) => {
+            return differ.push(&tag.to_string(), ChangeKind::Error(err.to_string()))
+        }
+    };
+    let display = |value: Option<String>| value.unwrap_or_else(|| "missing".into());
+    for (name, old_gid, new_gid) in old_glyphs.common(new_glyphs) {
+        let (old_advance, old_bearing) = old_metrics[old_gid.to_u16() as usize];
+        let (new_advance, new_bearing) = new_metrics[new_gid.to_u16() as usize];
+        differ.modified(
+            &format!("{tag}[{name}].advance"),
+            display(old_advance.map(|v| v.to_string())),
+            display(new_advance.map(|v| v.to_string())),
+        );
+        differ.modified(
+            &format!("{tag}[{name}].side_bearing"),
+            display(old_bearing.map(|v| v.to_string())),
+            display(new_bearing.map(|v| v.to_string())),
+        );
+    }
+}
+
+/// Compares the outline of each glyph in `glyf`.
+pub(crate) fn diff_glyf(old: &FontRef, new: &FontRef, differ: &mut Differ) {
+    fn read<'a>(font: &FontRef<'a>) -> Result<(Glyf<'a>, Loca<'a>), ReadError> {
+        Ok((font.glyf()?, font.loca(None)?))
+    }
+    let ((old_glyf, old_loca), (new_glyf, new_loca)) = match (read(old), read(new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(err), _) | (_, Err(err)) => {
+            return differ.push("glyf", ChangeKind::Error(err.to_string()))
+        }
+    };
+    for (name, old_gid, new_gid) in differ.old_glyphs().common(differ.new_glyphs()) {
+        let path = format!("glyf[{name}]");
+        match (
+            old_loca.get_glyf(old_gid, &old_glyf),
+            new_loca.get_glyf(new_gid, &new_glyf),
+        ) {
+            (Ok(old), Ok(new)) => diff_glyph(differ, &path, old, new),
+            (Err(err), _) | (_, Err(err)) => differ.push(&path, ChangeKind::Error(err.to_string())),
+        }
+    }
+}
+
+fn diff_glyph(differ: &mut Differ, path: &str, old: Option<Glyph>, new: Option<Glyph>) {
+    match (old, new) {
+        (Some(Glyph::Simple(old)), Some(Glyph::Simple(new))) => {
+            diff_simple_glyphs(differ, path, &old, &new)
+        }
+        (Some(Glyph::Composite(old)), Some(Glyph::Composite(new))) => {
+            diff_composite_glyphs(differ, path, &old, &new)
+        }
+        (old, new) => differ.modified(path, describe_glyph(old), describe_glyph(new)),
+    }
+}
+
+fn describe_glyph(glyph: Option<Glyph>) -> String {
+    match glyph {
+        None => "empty".into(),
+        Some(Glyph::Simple(glyph)) => format!(
+            "{} points in {} contours",
+            glyph.num_points(),
+            glyph.end_pts_of_contours().len()
+        ),
+        Some(Glyph::Composite(glyph)) => {
+            format!("{} components", glyph.components().count())
+        }
+    }
+}
+
+fn diff_simple_glyphs(differ: &mut Differ, path: &str, old: &SimpleGlyph, new: &SimpleGlyph) {
+    let old_ends = old.end_pts_of_contours().to_native_vec();
+    let new_ends = new.end_pts_of_contours().to_native_vec();
+    if old_ends != new_ends {
+        // the points don't correspond, so there is no point comparing them
+        return differ.modified(
+            &format!("{path}.end_pts_of_contours"),
+            format!("{old_ends:?}"),
+            format!("{new_ends:?}"),
+        );
+    }
+    for (i, (old, new)) in old.points().zip(new.points()).enumerate() {
+        differ.modified(
+            &format!("{path}.points.{i}"),
+            format_point(old),
+            format_point(new),
+        );
+    }
+    diff_instructions(differ, path, old.instructions(), new.instructions());
+}
+
+fn format_point(point: CurvePoint) -> String {
+    if point.on_curve {
+        format!("({}, {})", point.x, point.y)
+    } else {
+        format!("({}, {}) off-curve", point.x, point.y)
+    }
+}
+
+fn diff_composite_glyphs(
+    differ: &mut Differ,
+    path: &str,
+    old: &CompositeGlyph,
+    new: &CompositeGlyph,
+) {
+    let old_components = old.components().collect::<Vec<_>>();
+    let new_components = new.components().collect::<Vec<_>>();
+    for i in 0..old_components.len().max(new_components.len()) {
+        let component_path = format!("{path}.components.{i}");
+        match (old_components.get(i), new_components.get(i)) {
+            (Some(old), Some(new)) => differ.diff_tables(&component_path, old, new),
+            (Some(old), None) => {
+                let item = format!("component {}", differ.old_glyphs().name(old.glyph));
+                differ.push(&component_path, ChangeKind::Removed(item));
+            }
+            (None, Some(new)) => {
+                let item = format!("component {}", differ.new_glyphs().name(new.glyph));
+                differ.push(&component_path, ChangeKind::Added(item));
+            }
+            (None, None) => (),
+        }
+    }
+    diff_instructions(
+        differ,
+        path,
+        old.instructions().unwrap_or_default(),
+        new.instructions().unwrap_or_default(),
+    );
+}
+
+fn diff_instructions(differ: &mut Differ, path: &str, old: &[u8], new: &[u8]) {
+    if old != new {
+        differ.push(
+            &format!("{path}.instructions"),
+            ChangeKind::Modified {
+                old: format!("{} bytes", old.len()),
+                new: format!("{} bytes", new.len()),
+            },
+        );
+    }
+}
+
+/// The codepoint to glyph mapping of a font, and a description of each of
+/// its cmap subtables.
+type CharMap = (BTreeMap<u32, GlyphId>, Vec<String>);
+
+fn read_cmap(font: &FontRef) -> Result<CharMap, ReadError> {
+    let cmap = font.cmap()?;
+    let mut mapping = BTreeMap::new();
+    let mut subtables = Vec::new();
+    for record in cmap.encoding_records() {
+        let subtable = record.subtable(cmap.offset_data())?;
+        subtables.push(format!(
+            "{:?}/{} {}",
+            record.platform_id(),
+            record.encoding_id(),
+            subtable.type_name()
+        ));
+        // as in `Cmap::map_codepoint`, the first subtable with a mapping wins
+        let mappings: Box<dyn Iterator<Item = (u32, GlyphId)>> = match &subtable {
+            CmapSubtable::Format4(subtable) => Box::new(subtable.iter()),
+            CmapSubtable::Format12(subtable) => Box::new(subtable.iter()),
+            _ => continue,
+        };
+        for (codepoint, gid) in mappings {
+            mapping.entry(codepoint).or_insert(gid);
+        }
+    }
+    Ok((mapping, subtables))
+}
+
+/// Compares `cmap` as a mapping from codepoints to glyph names.
+///
+/// Only the mappings of format 4 and 12 subtables are compared; for other
+/// subtables, only their presence is compared.
+pub(crate) fn diff_cmap(old: &FontRef, new: &FontRef, differ: &mut Differ) {
+    let ((old_mapping, old_subtables), (new_mapping, new_subtables)) =
+        match (read_cmap(old), read_cmap(new)) {
+            (Ok(old), Ok(new)) => (old, new),
+            (Err(err), _) | (_, Err(err)) => {
+                return differ.push("cmap", ChangeKind::Error(err.to_string()))
+            }
+        };
+    differ.modified(
+        "cmap.encoding_records",
+        old_subtables.join(", "),
+        new_subtables.join(", "),
+    );
+    let (old_glyphs, new_glyphs) = (differ.old_glyphs(), differ.new_glyphs());
+    let mut codepoints = old_mapping
+        .keys()
+        .chain(new_mapping.keys())
+        .collect::<Vec<_>>();
+    codepoints.sort_unstable();
+    codepoints.dedup();
+    for codepoint in codepoints {
+        let path = format!("cmap[U+{codepoint:04X}]");
+        match (old_mapping.get(codepoint), new_mapping.get(codepoint)) {
+            (Some(old), Some(new)) => {
+                differ.modified(&path, old_glyphs.name(*old), new_glyphs.name(*new))
+            }
+            (Some(old), None) => differ.push(&path, ChangeKind::Removed(old_glyphs.name(*old))),
+            (None, Some(new)) => differ.push(&path, ChangeKind::Added(new_glyphs.name(*new))),
+            (None, None) => (),
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Semantic comparison of fonts.
+//!
+//! This compares two fonts table by table, using the parsed representation of
+//! each table, and reports the differences in terms of what they mean rather
+//! than where the bytes differ:
+//!
+//! - glyphs are matched by name (from the `post` table), so reordering the
+//!   glyphs of a font does not produce spurious differences, and glyph ids
+//!   are reported as glyph names
+//! - `glyf`, `hmtx` and `vmtx` are compared glyph by glyph, reporting changed
+//!   points, components and metrics; `loca` is not compared, since any
+//!   meaningful change is visible in `glyf`
+//! - `cmap` is compared as a mapping from codepoints to glyphs
+//! - other supported tables are compared field by field. The path of each
+//!   difference uses the names of the fields in read-fonts, so it can be
+//!   passed to `otexplorer -q` to print the surrounding data.
+//!
+//! Tables that are not supported are compared byte for byte. The checksum
+//! adjustment in `head` is ignored, since it changes whenever anything else
+//! does.
+//!
+//! ```no_run
+//! # let (path_a, path_b) = ("", "");
+//! use read_fonts::FontRef;
+//!
+//! let old_data = std::fs::read(path_a).unwrap();
+//! let new_data = std::fs::read(path_b).unwrap();
+//! let old = FontRef::new(&old_data).unwrap();
+//! let new = FontRef::new(&new_data).unwrap();
+//! for change in font_diff::diff_fonts(&old, &new).changes {
+//!     println!("{change}");
+//! }
+//! ```
+
+mod glyphs;
+mod traverse;
+
+use std::{collections::BTreeSet, fmt};
+
+use font_types::Tag;
+use read_fonts::{traversal::SomeTable, FontRef, ReadError, TableProvider, TopLevelTable};
+
+use glyphs::GlyphSet;
+use traverse::Differ;
+
+/// The differences between two fonts.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FontDiff {
+    /// The individual differences, with glyph changes first and then each
+    /// table's changes in tag order.
+    pub changes: Vec<Change>,
+}
+
+impl FontDiff {
+    /// Returns `true` if no differences were found.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for FontDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A single difference between two fonts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    /// The location of the change, such as `hmtx[a].advance` or
+    /// `GSUB.lookup_list_offset.lookup_offsets.2`.
+    pub path: String,
+    /// What changed.
+    pub kind: ChangeKind,
+}
+
+/// The kinds of [`Change`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// A value differs between the two fonts.
+    Modified { old: String, new: String },
+    /// An item exists only in the new font.
+    Added(String),
+    /// An item exists only in the old font.
+    Removed(String),
+    /// An item could not be read in one of the fonts.
+    Error(String),
+}
+
+impl Change {
+    fn new(path: impl Into<String>, kind: ChangeKind) -> Self {
+        Change {
+            path: path.into(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = &self.path;
+        match &self.kind {
+            ChangeKind::Modified { old, new } => write!(f, "{path}: {old} -> {new}"),
+            ChangeKind::Added(item) => write!(f, "{path}: added {item}"),
+            ChangeKind::Removed(item) => write!(f, "{path}: removed {item}"),
+            ChangeKind::Error(err) => write!(f, "{path}: error: {err}"),
+        }
+    }
+}
+
+/// Compare two fonts, returning the differences between them.
+pub fn diff_fonts(old: &FontRef, new: &FontRef) -> FontDiff {
+    let old_glyphs = GlyphSet::new(old);
+    let new_glyphs = GlyphSet::new(new);
+    let mut changes = Vec::new();
+    glyphs::diff_glyph_sets(&old_glyphs, &new_glyphs, &mut changes);
+
+    let tags = old
+        .table_directory
+        .table_records()
+        .iter()
+        .chain(new.table_directory.table_records())
+        .map(|record| record.tag())
+        .collect::<BTreeSet<_>>();
+    for tag in tags {
+        let (old_data, new_data) = match (old.table_data(tag), new.table_data(tag)) {
+            (Some(old_data), Some(new_data)) => (old_data, new_data),
+            (Some(_), None) => {
+                changes.push(Change::new(
+                    tag.to_string(),
+                    ChangeKind::Removed("table".into()),
+                ));
+                continue;
+            }
+            (None, Some(_)) => {
+                changes.push(Change::new(
+                    tag.to_string(),
+                    ChangeKind::Added("table".into()),
+                ));
+                continue;
+            }
+            (None, None) => continue,
+        };
+        if old_data.as_ref() == new_data.as_ref() {
+            continue;
+        }
+        let mut differ = Differ::new(&old_glyphs, &new_glyphs, &mut changes);
+        match tag {
+            read_fonts::tables::loca::Loca::TAG => (),
+            read_fonts::tables::glyf::Glyf::TAG => glyphs::diff_glyf(old, new, &mut differ),
+            read_fonts::tables::hmtx::Hmtx::TAG | read_fonts::tables::vmtx::Vmtx::TAG => {
+                glyphs::diff_metrics(old, new, tag, &mut differ)
+            }
+            read_fonts::tables::cmap::Cmap::TAG => glyphs::diff_cmap(old, new, &mut differ),
+            _ => match (get_some_table(old, tag), get_some_table(new, tag)) {
+                (Some(Ok(old_table)), Some(Ok(new_table))) => {
+                    differ.diff_tables(&tag.to_string(), &old_table, &new_table)
+                }
+                (Some(Err(e)), _) | (_, Some(Err(e))) => {
+                    differ.push(&tag.to_string(), ChangeKind::Error(e.to_string()))
+                }
+                _ => differ.push(
+                    &tag.to_string(),
+                    ChangeKind::Modified {
+                        old: format!("{} bytes", old_data.len()),
+                        new: format!("{} bytes", new_data.len()),
+                    },
+                ),
+            },
+        }
+    }
+    FontDiff { changes }
+}
+
+/// Given a font and a tag, return the appropriate table as a [`dyn SomeTable`][SomeTable].
+///
+/// Returns `None` if the table is not supported.
+fn get_some_table<'a>(
+    font: &FontRef<'a>,
+    tag: Tag,
+) -> Option<Result<Box<dyn SomeTable<'a> + 'a>, ReadError>> {
+    use read_fonts::tables;
+    Some(match tag {
+        tables::gpos::Gpos::TAG => font.gpos().map(|x| Box::new(x) as _),
+        tables::gsub::Gsub::TAG => font.gsub().map(|x| Box::new(x) as _),
+        tables::fvar::Fvar::TAG => font.fvar().map(|x| Box::new(x) as _),
+        tables::avar::Avar::TAG => font.avar().map(|x| Box::new(x) as _),
+        tables::gdef::Gdef::TAG => font.gdef().map(|x| Box::new(x) as _),
+        tables::head::Head::TAG => font.head().map(|x| Box::new(x) as _),
+        tables::hhea::Hhea::TAG => font.hhea().map(|x| Box::new(x) as _),
+        tables::maxp::Maxp::TAG => font.maxp().map(|x| Box::new(x) as _),
+        tables::name::Name::TAG => font.name().map(|x| Box::new(x) as _),
+        tables::os2::Os2::TAG => font.os2().map(|x| Box::new(x) as _),
+        tables::post::Post::TAG => font.post().map(|x| Box::new(x) as _),
+        tables::colr::Colr::TAG => font.colr().map(|x| Box::new(x) as _),
+        tables::cpal::Cpal::TAG => font.cpal().map(|x| Box::new(x) as _),
+        tables::stat::Stat::TAG => font.stat().map(|x| Box::new(x) as _),
+        tables::vhea::Vhea::TAG => font.vhea().map(|x| Box::new(x) as _),
+        tables::pclt::Pclt::TAG => font.pclt().map(|x| Box::new(x) as _),
+        tables::ltsh::Ltsh::TAG => font.ltsh().map(|x| Box::new(x) as _),
+        tables::lcar::Lcar::TAG => font.lcar().map(|x| Box::new(x) as _),
+        tables::opbd::Opbd::TAG => font.opbd().map(|x| Box::new(x) as _),
+        tables::math::Math::TAG => font.math().map(|x| Box::new(x) as _),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use font_types::{GlyphId, Tag};
+    use read_fonts::{test_data::test_fonts, FontRef, TableProvider};
+    use write_fonts::{
+        tables::hmtx::{Hmtx, LongMetric},
+        FontBuilder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn identical() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        assert!(diff_fonts(&font, &font).is_empty());
+    }
+
+    #[test]
+    fn added_and_removed_tables() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut builder = FontBuilder::from(font.clone());
+        builder.remove_table(Tag::new(b"gvar"));
+        builder.add_table(Tag::new(b"TEST"), vec![1, 2, 3, 4]);
+        let data = builder.build();
+        let new = FontRef::new(&data).unwrap();
+        let diff = diff_fonts(&font, &new);
+        assert_eq!(
+            diff.changes,
+            [
+                Change::new("TEST", ChangeKind::Added("table".into())),
+                Change::new("gvar", ChangeKind::Removed("table".into())),
+            ]
+        );
+        // and the reverse
+        let diff = diff_fonts(&new, &font);
+        assert_eq!(diff.changes[0].kind, ChangeKind::Removed("table".into()));
+        assert_eq!(diff.changes[1].kind, ChangeKind::Added("table".into()));
+    }
+
+    #[test]
+    fn changed_advance() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let hmtx = font.hmtx().unwrap();
+        let old_advance = hmtx.advance(GlyphId::new(1)).unwrap();
+        let mut h_metrics = hmtx
+            .h_metrics()
+            .iter()
+            .map(|metric| LongMetric::new(metric.advance(), metric.side_bearing()))
+            .collect::<Vec<_>>();
+        h_metrics[1].advance += 10;
+        let lsbs = hmtx
+            .left_side_bearings()
+            .iter()
+            .map(|lsb| lsb.get())
+            .collect();
+        let mut builder = FontBuilder::from(font.clone());
+        builder
+            .add_typed_table(&Hmtx::new(h_metrics, lsbs))
+            .unwrap();
+        let data = builder.build();
+        let new = FontRef::new(&data).unwrap();
+        let diff = diff_fonts(&font, &new);
+        let name = GlyphSet::new(&font).name(GlyphId::new(1));
+        let expected = Change::new(
+            format!("hmtx[{name}].advance"),
+            ChangeKind::Modified {
+                old: old_advance.to_string(),
+                new: (old_advance + 10).to_string(),
+            },
+        );
+        assert_eq!(diff.changes, [expected]);
+        assert!(diff
+            .changes
+            .iter()
+            .all(|change| !change.path.starts_with("glyphs")));
+    }
+
+    #[test]
+    fn different_fonts() {
+        let old = FontRef::new(test_fonts::SIMPLE_GLYF).unwrap();
+        let new = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let diff = diff_fonts(&old, &new);
+        let paths = diff
+            .changes
+            .iter()
+            .map(|change| change.path.as_str())
+            .collect::<Vec<_>>();
+        assert!(paths.contains(&"gvar"));
+        assert!(paths.contains(&"head.units_per_em"), "{diff}");
+        assert!(paths.iter().any(|path| path.starts_with("glyphs[")));
+        assert!(paths.iter().any(|path| path.starts_with("cmap[U+")));
+        assert!(paths.iter().all(|path| !path.starts_with("loca")));
+    }
+}
@@ -0,0 +1,87 @@
+//! Compare two fonts, printing their semantic differences.
+//!
+//! Exits with status 1 if the fonts differ, like `diff`.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use font_types::Tag;
+use read_fonts::{FileRef, FontRef};
+
+fn main() -> Result<(), Error> {
+    let args = flags::Args::from_env().map_err(Error::new)?;
+    let old_bytes = std::fs::read(&args.old).map_err(Error::new)?;
+    let new_bytes = std::fs::read(&args.new).map_err(Error::new)?;
+    let index = args.index.unwrap_or(0) as usize;
+    let old = load_font(&old_bytes, index)?;
+    let new = load_font(&new_bytes, index)?;
+
+    let mut diff = font_diff::diff_fonts(&old, &new);
+    if let Some(tags) = &args.tables {
+        let tags = make_tag_set(tags)?;
+        diff.changes.retain(|change| {
+            let tag = change.path.split(['.', '[']).next().unwrap_or_default();
+            Tag::from_str(tag).is_ok_and(|tag| tags.contains(&tag))
+        });
+    }
+    if diff.is_empty() {
+        return Ok(());
+    }
+    print!("{diff}");
+    std::process::exit(1);
+}
+
+fn load_font(bytes: &[u8], index: usize) -> Result<FontRef<'_>, Error> {
+    FileRef::new(bytes)
+        .map_err(Error::new)?
+        .fonts()
+        .nth(index)
+        .ok_or_else(|| Error(format!("no font at index {index}")))?
+        .map_err(Error::new)
+}
+
+fn make_tag_set(inp: &str) -> Result<HashSet<Tag>, Error> {
+    inp.split(' ')
+        .map(|raw| match Tag::from_str(raw) {
+            Ok(tag) => Ok(tag),
+            Err(e) => Err(Error(format!(
+                "Invalid tag '{}': {e}",
+                raw.escape_default()
+            ))),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct Error(String);
+
+impl Error {
+    fn new(t: impl std::fmt::Display) -> Self {
+        Self(t.to_string())
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for Error {}
+
+mod flags {
+    use std::path::PathBuf;
+
+    xflags::xflags! {
+        /// Compare two fonts
+        cmd args
+            required old: PathBuf
+            required new: PathBuf
+            {
+                /// The index of the font to compare, in font collections
+                optional -i, --index index: u32
+                /// Only report changes in these (space separated) tables
+                optional -t, --tables include: String
+            }
+    }
+}
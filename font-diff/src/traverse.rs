@@ -0,0 +1,211 @@
+//! Field by field comparison of tables.
+
+use std::fmt::Display;
+
+use read_fonts::traversal::{FieldType, SomeArray, SomeTable};
+
+use crate::{glyphs::GlyphSet, Change, ChangeKind};
+
+/// Fields that are derived from the bytes of the whole font, and so differ
+/// whenever anything else does.
+const IGNORED_PATHS: &[&str] = &["head.checksum_adjustment"];
+
+/// Compares tables, collecting the differences.
+pub(crate) struct Differ<'a> {
+    old_glyphs: &'a GlyphSet,
+    new_glyphs: &'a GlyphSet,
+    changes: &'a mut Vec<Change>,
+}
+
+impl<'a> Differ<'a> {
+    pub(crate) fn new(
+        old_glyphs: &'a GlyphSet,
+        new_glyphs: &'a GlyphSet,
+        changes: &'a mut Vec<Change>,
+    ) -> Self {
+        Differ {
+            old_glyphs,
+            new_glyphs,
+            changes,
+        }
+    }
+
+    pub(crate) fn old_glyphs(&self) -> &'a GlyphSet {
+        self.old_glyphs
+    }
+
+    pub(crate) fn new_glyphs(&self) -> &'a GlyphSet {
+        self.new_glyphs
+    }
+
+    pub(crate) fn push(&mut self, path: &str, kind: ChangeKind) {
+        if !IGNORED_PATHS.contains(&path) {
+            self.changes.push(Change::new(path, kind));
+        }
+    }
+
+    /// Records a modification, if the two values are formatted differently.
+    pub(crate) fn modified(&mut self, path: &str, old: impl Display, new: impl Display) {
+        let (old, new) = (old.to_string(), new.to_string());
+        if old != new {
+            self.push(path, ChangeKind::Modified { old, new });
+        }
+    }
+
+    /// Compares two tables or records, matching their fields by name.
+    pub(crate) fn diff_tables<'o, 'n>(
+        &mut self,
+        path: &str,
+        old: &(dyn SomeTable<'o> + 'o),
+        new: &(dyn SomeTable<'n> + 'n),
+    ) {
+        if old.type_name() != new.type_name() {
+            return self.modified(path, old.type_name(), new.type_name());
+        }
+        let mut new_fields = new.iter().map(Some).collect::<Vec<_>>();
+        for old_field in old.iter() {
+            let field_path = format!("{path}.{}", old_field.name);
+            let new_field = new_fields
+                .iter_mut()
+                .find(|field| matches!(field, Some(field) if field.name == old_field.name))
+                .and_then(Option::take);
+            match new_field {
+                Some(new_field) => {
+                    self.diff_values(&field_path, &old_field.value, &new_field.value)
+                }
+                None => {
+                    let item = describe(&old_field.value, self.old_glyphs);
+                    self.push(&field_path, ChangeKind::Removed(item));
+                }
+            }
+        }
+        for new_field in new_fields.into_iter().flatten() {
+            let item = describe(&new_field.value, self.new_glyphs);
+            self.push(
+                &format!("{path}.{}", new_field.name),
+                ChangeKind::Added(item),
+            );
+        }
+    }
+
+    fn diff_values(&mut self, path: &str, old: &FieldType, new: &FieldType) {
+        let old_scalar = scalar(old, self.old_glyphs);
+        let new_scalar = scalar(new, self.new_glyphs);
+        if old_scalar.is_some() || new_scalar.is_some() {
+            let old = old_scalar.unwrap_or_else(|| describe(old, self.old_glyphs));
+            let new = new_scalar.unwrap_or_else(|| describe(new, self.new_glyphs));
+            return self.modified(path, old, new);
+        }
+        match (old, new) {
+            (FieldType::ResolvedOffset(old), FieldType::ResolvedOffset(new)) => {
+                match (&old.target, &new.target) {
+                    (Ok(old), Ok(new)) => self.diff_tables(path, old, new),
+                    (Err(old), Err(new)) if old.to_string() == new.to_string() => (),
+                    (Err(err), _) | (_, Err(err)) => {
+                        self.push(path, ChangeKind::Error(err.to_string()))
+                    }
+                }
+            }
+            (FieldType::ArrayOffset(old), FieldType::ArrayOffset(new)) => {
+                match (&old.target, &new.target) {
+                    (Ok(old), Ok(new)) => self.diff_arrays(path, old.as_ref(), new.as_ref()),
+                    (Err(old), Err(new)) if old.to_string() == new.to_string() => (),
+                    (Err(err), _) | (_, Err(err)) => {
+                        self.push(path, ChangeKind::Error(err.to_string()))
+                    }
+                }
+            }
+            (FieldType::Array(old), FieldType::Array(new)) => {
+                self.diff_arrays(path, old.as_ref(), new.as_ref())
+            }
+            (FieldType::Record(old), FieldType::Record(new)) => self.diff_tables(path, old, new),
+            // the targets of unresolved offsets are unknown, so only compare
+            // whether they are null.
+            (FieldType::BareOffset(old), FieldType::BareOffset(new))
+                if (old.to_u32() == 0) == (new.to_u32() == 0) => {}
+            _ => self.modified(
+                path,
+                describe(old, self.old_glyphs),
+                describe(new, self.new_glyphs),
+            ),
+        }
+    }
+
+    fn diff_arrays<'o, 'n>(
+        &mut self,
+        path: &str,
+        old: &(dyn SomeArray<'o> + 'o),
+        new: &(dyn SomeArray<'n> + 'n),
+    ) {
+        for i in 0..old.len().max(new.len()) {
+            let item_path = format!("{path}.{i}");
+            match (old.get(i), new.get(i)) {
+                (Some(old), Some(new)) => self.diff_values(&item_path, &old, &new),
+                (Some(old), None) => {
+                    let item = describe(&old, self.old_glyphs);
+                    self.push(&item_path, ChangeKind::Removed(item));
+                }
+                (None, Some(new)) => {
+                    let item = describe(&new, self.new_glyphs);
+                    self.push(&item_path, ChangeKind::Added(item));
+                }
+                (None, None) => (),
+            }
+        }
+    }
+}
+
+/// Formats a scalar value, or returns `None` if the field is not a scalar.
+fn scalar(field: &FieldType, glyphs: &GlyphSet) -> Option<String> {
+    Some(match field {
+        FieldType::I8(val) => val.to_string(),
+        FieldType::U8(val) => val.to_string(),
+        FieldType::I16(val) => val.to_string(),
+        FieldType::U16(val) => val.to_string(),
+        FieldType::I32(val) => val.to_string(),
+        FieldType::U32(val) => val.to_string(),
+        FieldType::I24(val) => val.to_string(),
+        FieldType::U24(val) => val.to_string(),
+        FieldType::Tag(val) => format!("'{val}'"),
+        FieldType::FWord(val) => val.to_i16().to_string(),
+        FieldType::UfWord(val) => val.to_u16().to_string(),
+        FieldType::MajorMinor(val) => val.to_string(),
+        FieldType::Version16Dot16(val) => val.to_string(),
+        FieldType::F2Dot14(val) => val.to_string(),
+        FieldType::Fixed(val) => val.to_string(),
+        FieldType::LongDateTime(val) => val.as_secs().to_string(),
+        FieldType::GlyphId(val) => glyphs.name(*val),
+        FieldType::GlyphId24(val) => match val.to_glyph_id() {
+            Some(gid) => glyphs.name(gid),
+            None => val.to_u32().to_string(),
+        },
+        FieldType::NameId(val) => val.to_string(),
+        _ => return None,
+    })
+}
+
+/// A short description of any field, used for added and removed items.
+fn describe(field: &FieldType, glyphs: &GlyphSet) -> String {
+    if let Some(scalar) = scalar(field, glyphs) {
+        return scalar;
+    }
+    match field {
+        FieldType::BareOffset(offset) if offset.to_u32() == 0 => "null".into(),
+        FieldType::BareOffset(offset) => format!("offset 0x{:04X}", offset.to_u32()),
+        FieldType::ResolvedOffset(offset) => match &offset.target {
+            Ok(table) => table.type_name().into(),
+            Err(_) => "unreadable table".into(),
+        },
+        FieldType::StringOffset(offset) => match &offset.target {
+            Ok(string) => format!("{:?}", string.iter_chars().collect::<String>()),
+            Err(_) => "unreadable string".into(),
+        },
+        FieldType::ArrayOffset(offset) => match &offset.target {
+            Ok(array) => format!("[{}; {}]", array.type_name(), array.len()),
+            Err(_) => "unreadable array".into(),
+        },
+        FieldType::Array(array) => format!("[{}; {}]", array.type_name(), array.len()),
+        FieldType::Record(record) => record.type_name().into(),
+        _ => "unknown".into(),
+    }
+}
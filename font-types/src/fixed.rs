@@ -6,6 +6,7 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssi
 macro_rules! fixed_impl {
     ($name:ident, $bits:literal, $fract_bits:literal, $ty:ty) => {
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
         #[doc = concat!(stringify!($bits), "-bit signed fixed point number with ", stringify!($fract_bits), " bits of fraction." )]
         pub struct $name($ty);
         impl $name {
@@ -59,8 +60,16 @@ macro_rules! fixed_impl {
                 Self(self.0 - self.floor().0)
             }
 
+            /// Checked addition. Returns `None` if overflow occurred.
+            pub const fn checked_add(self, other: Self) -> Option<Self> {
+                match self.0.checked_add(other.0) {
+                    Some(bits) => Some(Self(bits)),
+                    None => None,
+                }
+            }
+
             /// Wrapping addition.
-            pub fn wrapping_add(self, other: Self) -> Self {
+            pub const fn wrapping_add(self, other: Self) -> Self {
                 Self(self.0.wrapping_add(other.0))
             }
 
@@ -69,16 +78,42 @@ macro_rules! fixed_impl {
                 Self(self.0.saturating_add(other.0))
             }
 
-            /// Wrapping substitution.
+            /// Checked subtraction. Returns `None` if overflow occurred.
+            pub const fn checked_sub(self, other: Self) -> Option<Self> {
+                match self.0.checked_sub(other.0) {
+                    Some(bits) => Some(Self(bits)),
+                    None => None,
+                }
+            }
+
+            /// Wrapping subtraction.
             pub const fn wrapping_sub(self, other: Self) -> Self {
                 Self(self.0.wrapping_sub(other.0))
             }
 
-            /// Saturating substitution.
+            /// Saturating subtraction.
             pub const fn saturating_sub(self, other: Self) -> Self {
                 Self(self.0.saturating_sub(other.0))
             }
 
+            /// Checked negation. Returns `None` if `self` is the minimum value.
+            pub const fn checked_neg(self) -> Option<Self> {
+                match self.0.checked_neg() {
+                    Some(bits) => Some(Self(bits)),
+                    None => None,
+                }
+            }
+
+            /// Wrapping negation.
+            pub const fn wrapping_neg(self) -> Self {
+                Self(self.0.wrapping_neg())
+            }
+
+            /// Saturating negation.
+            pub const fn saturating_neg(self) -> Self {
+                Self(self.0.saturating_neg())
+            }
+
             /// The representation of this number as a big-endian byte array.
             pub const fn to_be_bytes(self) -> [u8; $bits / 8] {
                 self.0.to_be_bytes()
@@ -149,14 +184,90 @@ macro_rules! fixed_mul_div {
                     result as i32
                 })
             }
+
+            /// Returns the rounded product, which may not fit in 32 bits.
+            const fn mul_wide(self, other: Self) -> i64 {
+                let ab = self.0 as i64 * other.0 as i64;
+                (ab + 0x8000 - (ab < 0) as i64) >> 16
+            }
+
+            /// Returns the rounded quotient, which may not fit in 32 bits, or
+            /// `None` if the divisor is zero.
+            const fn div_wide(self, other: Self) -> Option<i64> {
+                if other.0 == 0 {
+                    return None;
+                }
+                let a = self.0.unsigned_abs() as u64;
+                let b = other.0.unsigned_abs() as u64;
+                let q = ((a << 16) + (b >> 1)) / b;
+                Some(if (self.0 < 0) != (other.0 < 0) {
+                    -(q as i64)
+                } else {
+                    q as i64
+                })
+            }
+
+            /// Checked multiplication. Returns `None` if overflow occurred.
+            pub const fn checked_mul(self, other: Self) -> Option<Self> {
+                let product = self.mul_wide(other);
+                if product < i32::MIN as i64 || product > i32::MAX as i64 {
+                    None
+                } else {
+                    Some(Self(product as i32))
+                }
+            }
+
+            /// Wrapping multiplication.
+            pub const fn wrapping_mul(self, other: Self) -> Self {
+                Self(self.mul_wide(other) as i32)
+            }
+
+            /// Saturating multiplication.
+            pub const fn saturating_mul(self, other: Self) -> Self {
+                Self(clamp_i64(self.mul_wide(other)))
+            }
+
+            /// Checked division. Returns `None` if the divisor is zero or
+            /// overflow occurred.
+            pub const fn checked_div(self, other: Self) -> Option<Self> {
+                match self.div_wide(other) {
+                    Some(q) if q >= i32::MIN as i64 && q <= i32::MAX as i64 => Some(Self(q as i32)),
+                    _ => None,
+                }
+            }
+
+            /// Wrapping division.
+            ///
+            /// Division by zero produces the maximum value with the sign of
+            /// `self`, matching the division operator.
+            pub const fn wrapping_div(self, other: Self) -> Self {
+                match self.div_wide(other) {
+                    Some(q) => Self(q as i32),
+                    None => Self::div_by_zero(self),
+                }
+            }
+
+            /// Saturating division.
+            ///
+            /// Division by zero produces the maximum value with the sign of
+            /// `self`, matching the division operator.
+            pub const fn saturating_div(self, other: Self) -> Self {
+                match self.div_wide(other) {
+                    Some(q) => Self(clamp_i64(q)),
+                    None => Self::div_by_zero(self),
+                }
+            }
+
+            const fn div_by_zero(self) -> Self {
+                Self(if self.0 < 0 { -i32::MAX } else { i32::MAX })
+            }
         }
 
         impl Mul for $ty {
             type Output = Self;
             #[inline(always)]
             fn mul(self, other: Self) -> Self::Output {
-                let ab = self.0 as i64 * other.0 as i64;
-                Self(((ab + 0x8000 - i64::from(ab < 0)) >> 16) as i32)
+                self.wrapping_mul(other)
             }
         }
 
@@ -206,6 +317,17 @@ macro_rules! fixed_mul_div {
     };
 }
 
+/// Clamps a 64-bit value to the range of a 32-bit value.
+const fn clamp_i64(value: i64) -> i32 {
+    if value < i32::MIN as i64 {
+        i32::MIN
+    } else if value > i32::MAX as i64 {
+        i32::MAX
+    } else {
+        value as i32
+    }
+}
+
 /// impl float conversion methods.
 ///
 /// We convert to different float types in order to ensure we can roundtrip
@@ -306,13 +428,137 @@ impl F26Dot6 {
     pub const fn to_i32(self) -> i32 {
         self.0.wrapping_add(32) >> 6
     }
+
+    // The following rounding operations are those of the TrueType
+    // interpreter. They round the magnitude of the value and keep its sign,
+    // so a value never rounds across zero.
+    // See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_graphics_state#round-state>
+
+    /// Rounds to the nearest integer, with halfway values rounded away from
+    /// zero.
+    pub const fn round_to_grid(self) -> Self {
+        self.with_sign_of(self.magnitude().wrapping_add(32) & !63, 0)
+    }
+
+    /// Rounds to the nearest value halfway between two integers.
+    pub const fn round_to_half_grid(self) -> Self {
+        self.with_sign_of((self.magnitude() & !63).wrapping_add(32), 0)
+    }
+
+    /// Rounds to the nearest multiple of one half, with halfway values
+    /// rounded away from zero.
+    pub const fn round_to_double_grid(self) -> Self {
+        self.with_sign_of(self.magnitude().wrapping_add(16) & !31, 0)
+    }
+
+    /// Rounds toward zero to an integer.
+    pub const fn round_down_to_grid(self) -> Self {
+        self.with_sign_of(self.magnitude() & !63, 0)
+    }
+
+    /// Rounds away from zero to an integer.
+    pub const fn round_up_to_grid(self) -> Self {
+        self.with_sign_of(self.magnitude().wrapping_add(63) & !63, 0)
+    }
+
+    /// Rounds to the nearest value of the form `phase + n * period`, as
+    /// set by the `SROUND` instruction.
+    ///
+    /// The threshold is added to the magnitude before rounding down, and
+    /// the period must be a power of two. Values that would round across
+    /// zero are set to the phase instead.
+    pub const fn super_round(self, period: Self, phase: Self, threshold: Self) -> Self {
+        let rounded = self
+            .magnitude()
+            .wrapping_sub(phase.0)
+            .wrapping_add(threshold.0)
+            & period.0.wrapping_neg();
+        self.with_sign_of(rounded.wrapping_add(phase.0), phase.0)
+    }
+
+    /// Rounds to the nearest value of the form `phase + n * period`, as
+    /// set by the `S45ROUND` instruction.
+    ///
+    /// This is the same as [`super_round`](Self::super_round) but allows
+    /// any period. A zero period leaves the value unchanged.
+    pub const fn super_round_45(self, period: Self, phase: Self, threshold: Self) -> Self {
+        if period.0 == 0 {
+            return self;
+        }
+        let rounded = (self
+            .magnitude()
+            .wrapping_sub(phase.0)
+            .wrapping_add(threshold.0)
+            / period.0)
+            .wrapping_mul(period.0);
+        self.with_sign_of(rounded.wrapping_add(phase.0), phase.0)
+    }
+
+    /// Returns the absolute value, wrapping for the minimum value.
+    const fn magnitude(self) -> i32 {
+        if self.0 >= 0 {
+            self.0
+        } else {
+            self.0.wrapping_neg()
+        }
+    }
+
+    /// Gives a rounded magnitude the sign of `self`. Results that would have
+    /// the opposite sign are clamped to `min`.
+    const fn with_sign_of(self, rounded: i32, min: i32) -> Self {
+        if self.0 >= 0 {
+            Self(if rounded < 0 { min } else { rounded })
+        } else {
+            let value = rounded.wrapping_neg();
+            Self(if value > 0 { -min } else { value })
+        }
+    }
 }
 
 impl F2Dot14 {
     /// Converts a 2.14 to 16.16 fixed point value.
+    ///
+    /// This conversion is lossless: every 2.14 value is exactly representable
+    /// in 16.16 and `F2Dot14::from_fixed(x.to_fixed()) == x` for all `x`.
     pub const fn to_fixed(self) -> Fixed {
         Fixed(self.0 as i32 * 4)
     }
+
+    /// Converts a 16.16 to 2.14 fixed point value.
+    ///
+    /// This is the same as [`Fixed::to_f2dot14`], and rounds as defined by
+    /// the spec for normalized coordinates.
+    pub const fn from_fixed(value: Fixed) -> Self {
+        value.to_f2dot14()
+    }
+}
+
+impl From<F2Dot14> for Fixed {
+    fn from(value: F2Dot14) -> Self {
+        value.to_fixed()
+    }
+}
+
+impl Mul<F2Dot14> for Fixed {
+    type Output = Fixed;
+    #[inline(always)]
+    fn mul(self, other: F2Dot14) -> Self::Output {
+        self * other.to_fixed()
+    }
+}
+
+impl MulAssign<F2Dot14> for Fixed {
+    fn mul_assign(&mut self, rhs: F2Dot14) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Fixed> for F2Dot14 {
+    type Output = Fixed;
+    #[inline(always)]
+    fn mul(self, other: Fixed) -> Self::Output {
+        self.to_fixed() * other
+    }
 }
 
 #[cfg(test)]
@@ -339,6 +585,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn roundtrip_f2dot14_fixed() {
+        for i in i16::MIN..=i16::MAX {
+            let val = F2Dot14::from_bits(i);
+            assert_eq!(val.to_bits(), i);
+            let fixed = val.to_fixed();
+            assert_eq!(fixed, Fixed::from(val));
+            assert_eq!(fixed.to_bits(), i as i32 * 4);
+            assert_eq!(F2Dot14::from_fixed(fixed), val);
+            assert_eq!(fixed.to_f2dot14(), val);
+        }
+    }
+
+    #[test]
+    fn roundtrip_bits() {
+        for bits in [i32::MIN, -0x10000, -1, 0, 1, 0x10000, 0x12345678, i32::MAX] {
+            assert_eq!(Fixed::from_bits(bits).to_bits(), bits);
+            assert_eq!(F26Dot6::from_bits(bits).to_bits(), bits);
+        }
+    }
+
+    #[test]
+    fn fixed_to_f2dot14_rounding() {
+        // values between two 2.14 steps round to nearest, ties toward +inf
+        assert_eq!(F2Dot14::from_fixed(Fixed::from_bits(1)).to_bits(), 0);
+        assert_eq!(F2Dot14::from_fixed(Fixed::from_bits(2)).to_bits(), 1);
+        assert_eq!(F2Dot14::from_fixed(Fixed::from_bits(-2)).to_bits(), 0);
+        assert_eq!(F2Dot14::from_fixed(Fixed::from_bits(-3)).to_bits(), -1);
+        assert_eq!(F2Dot14::from_fixed(Fixed::ONE), F2Dot14::ONE);
+        assert_eq!(F2Dot14::from_fixed(-Fixed::ONE), F2Dot14::from_f32(-1.0));
+    }
+
+    #[test]
+    fn mul_f2dot14_fixed() {
+        let half = F2Dot14::from_f32(0.5);
+        let three = Fixed::from_i32(3);
+        assert_eq!(three * half, Fixed::from_f64(1.5));
+        assert_eq!(half * three, Fixed::from_f64(1.5));
+        let mut val = Fixed::from_i32(-5);
+        val *= F2Dot14::from_f32(-0.25);
+        assert_eq!(val, Fixed::from_f64(1.25));
+        // matches multiplying after a lossless conversion for all 2.14 values
+        let scale = Fixed::from_bits(0x0001_2345);
+        for i in (i16::MIN..=i16::MAX).step_by(7) {
+            let coord = F2Dot14::from_bits(i);
+            assert_eq!(scale * coord, scale * coord.to_fixed());
+            assert_eq!(coord * scale, scale * coord);
+        }
+    }
+
     #[test]
     fn round_f2dot14() {
         assert_eq!(F2Dot14(0x7000).round(), F2Dot14::from_f32(-2.0));
@@ -406,4 +702,79 @@ mod tests {
             Fixed::from_f64(0.25)
         );
     }
+
+    #[test]
+    fn checked_add_sub() {
+        let one = F2Dot14::ONE;
+        assert_eq!(one.checked_add(one), None);
+        assert_eq!(
+            one.checked_add(F2Dot14::from_f32(0.5)),
+            Some(F2Dot14::from_f32(1.5))
+        );
+        assert_eq!(F2Dot14::MAX.checked_add(F2Dot14::EPSILON), None);
+        assert_eq!(F2Dot14::MAX.saturating_add(one), F2Dot14::MAX);
+        assert_eq!(F2Dot14::MAX.wrapping_add(F2Dot14::EPSILON), F2Dot14::MIN);
+        assert_eq!(Fixed::MIN.checked_sub(Fixed::EPSILON), None);
+        assert_eq!(Fixed::MIN.saturating_sub(Fixed::ONE), Fixed::MIN);
+        assert_eq!(F26Dot6::MIN.checked_neg(), None);
+        assert_eq!(F26Dot6::MIN.saturating_neg(), F26Dot6::MAX);
+        assert_eq!(F26Dot6::MIN.wrapping_neg(), F26Dot6::MIN);
+    }
+
+    #[test]
+    fn checked_mul_div() {
+        let big = Fixed::from_i32(30000);
+        assert_eq!(
+            Fixed::from_f64(1.5).checked_mul(Fixed::from_f64(-2.0)),
+            Some(Fixed::from_f64(-3.0))
+        );
+        assert_eq!(big.checked_mul(big), None);
+        assert_eq!(big.saturating_mul(big), Fixed::MAX);
+        assert_eq!(big.saturating_mul(-big), Fixed::MIN);
+        assert_eq!(big.wrapping_mul(big), big * big);
+        assert_eq!(
+            Fixed::from_i32(3).checked_div(Fixed::from_i32(-2)),
+            Some(Fixed::from_f64(-1.5))
+        );
+        assert_eq!(big.checked_div(Fixed::ZERO), None);
+        assert_eq!(big.checked_div(Fixed::from_f64(0.25)), None);
+        assert_eq!(big.saturating_div(Fixed::from_f64(-0.25)), Fixed::MIN);
+        assert_eq!(-big.wrapping_div(Fixed::ZERO), -(big / Fixed::ZERO));
+        assert_eq!(Fixed::MIN.saturating_div(Fixed::ZERO), -Fixed::MAX);
+    }
+
+    #[test]
+    fn f26dot6_rounding() {
+        let values = [0, 20, 32, 100, -20, -100].map(F26Dot6::from_bits);
+        let round = |f: fn(F26Dot6) -> F26Dot6| values.map(|value| f(value).to_bits());
+        assert_eq!(round(F26Dot6::round_to_grid), [0, 0, 64, 128, 0, -128]);
+        assert_eq!(
+            round(F26Dot6::round_to_half_grid),
+            [32, 32, 32, 96, -32, -96]
+        );
+        assert_eq!(
+            round(F26Dot6::round_to_double_grid),
+            [0, 32, 32, 96, -32, -96]
+        );
+        assert_eq!(round(F26Dot6::round_down_to_grid), [0, 0, 0, 64, 0, -64]);
+        assert_eq!(
+            round(F26Dot6::round_up_to_grid),
+            [0, 64, 64, 128, -64, -128]
+        );
+    }
+
+    #[test]
+    fn f26dot6_super_rounding() {
+        let [period, half, zero] = [64, 32, 0].map(F26Dot6::from_bits);
+        // phase 1/2 and threshold 1/2 round to the half grid
+        let value = F26Dot6::from_bits(100);
+        assert_eq!(value.super_round(period, half, half).to_bits(), 96);
+        assert_eq!((-value).super_round(period, half, half).to_bits(), -96);
+        // rounding across zero produces the phase
+        let small = F26Dot6::from_bits(10);
+        assert_eq!(small.super_round(period, half, zero).to_bits(), 32);
+        let period_45 = F26Dot6::from_bits(45);
+        assert_eq!(value.super_round_45(period_45, zero, zero).to_bits(), 90);
+        assert_eq!(value.super_round_45(zero, zero, zero), value);
+    }
 }
@@ -2,9 +2,17 @@
 //!
 //! Although these are treated as u16s in the spec, we choose to represent them
 //! as a distinct type.
+//!
+//! Fonts with more than 65535 glyphs need wider identifiers, which are
+//! represented by [`GlyphId24`]. Converting a [`GlyphId24`] to a [`GlyphId`]
+//! is fallible, so code that supports large glyph sets can use the wider type
+//! and narrow it only where a table requires 16 bits.
+
+use crate::Uint24;
 
 /// A 16-bit glyph identifier.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct GlyphId(u16);
 
 impl GlyphId {
@@ -21,6 +29,11 @@ impl GlyphId {
         self.0
     }
 
+    /// The identifier as a u32.
+    pub const fn to_u32(self) -> u32 {
+        self.0 as u32
+    }
+
     pub const fn to_be_bytes(self) -> [u8; 2] {
         self.0.to_be_bytes()
     }
@@ -39,3 +52,92 @@ impl std::fmt::Display for GlyphId {
 }
 
 crate::newtype_scalar!(GlyphId, [u8; 2]);
+
+/// A 24-bit glyph identifier.
+///
+/// This is used by tables that support more than 65535 glyphs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct GlyphId24(Uint24);
+
+impl GlyphId24 {
+    /// The identifier reserved for unknown glyphs
+    pub const NOTDEF: GlyphId24 = GlyphId24(Uint24::MIN);
+
+    /// Construct a new `GlyphId24`.
+    pub const fn new(raw: Uint24) -> Self {
+        GlyphId24(raw)
+    }
+
+    /// Construct a new `GlyphId24` from a u32, returning `None` if the value
+    /// does not fit in 24 bits.
+    pub const fn checked_new(raw: u32) -> Option<Self> {
+        match Uint24::checked_new(raw) {
+            Some(raw) => Some(GlyphId24(raw)),
+            None => None,
+        }
+    }
+
+    /// The identifier as a u32.
+    pub const fn to_u32(self) -> u32 {
+        self.0.to_u32()
+    }
+
+    /// The identifier as a 16-bit [`GlyphId`], or `None` if it is out of
+    /// range.
+    pub const fn to_glyph_id(self) -> Option<GlyphId> {
+        let raw = self.0.to_u32();
+        if raw > u16::MAX as u32 {
+            None
+        } else {
+            Some(GlyphId(raw as u16))
+        }
+    }
+
+    pub const fn to_be_bytes(self) -> [u8; 3] {
+        self.0.to_be_bytes()
+    }
+}
+
+impl From<GlyphId> for GlyphId24 {
+    fn from(src: GlyphId) -> GlyphId24 {
+        GlyphId24(Uint24::from(src.0))
+    }
+}
+
+impl From<GlyphId> for u32 {
+    fn from(src: GlyphId) -> u32 {
+        src.to_u32()
+    }
+}
+
+impl From<GlyphId24> for u32 {
+    fn from(src: GlyphId24) -> u32 {
+        src.to_u32()
+    }
+}
+
+impl std::fmt::Display for GlyphId24 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "GID_{}", self.0)
+    }
+}
+
+crate::newtype_scalar!(GlyphId24, [u8; 3]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyph_id_24() {
+        let gid = GlyphId24::checked_new(0x12345).unwrap();
+        assert_eq!(gid.to_u32(), 0x12345);
+        assert_eq!(gid.to_glyph_id(), None);
+        assert_eq!(gid.to_be_bytes(), [0x01, 0x23, 0x45]);
+        assert_eq!(GlyphId24::checked_new(0x1000000), None);
+        let small = GlyphId24::from(GlyphId::new(42));
+        assert_eq!(small.to_glyph_id(), Some(GlyphId::new(42)));
+        assert_eq!(small.to_string(), GlyphId::new(42).to_string());
+    }
+}
@@ -0,0 +1,163 @@
+/// 24-bit signed integer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Int24(i32);
+
+impl Int24 {
+    /// The smallest value that can be represented by this integer type.
+    pub const MIN: Self = Int24(-0x800000);
+
+    /// The largest value that can be represented by this integer type.
+    pub const MAX: Self = Int24(0x7fffff);
+
+    /// Create from an i32. Saturates on overflow.
+    pub const fn new(raw: i32) -> Int24 {
+        if raw < Self::MIN.0 {
+            Self::MIN
+        } else if raw > Self::MAX.0 {
+            Self::MAX
+        } else {
+            Int24(raw)
+        }
+    }
+
+    /// Create from an i32, returning `None` if the value overflows.
+    pub const fn checked_new(raw: i32) -> Option<Int24> {
+        if raw < Self::MIN.0 || raw > Self::MAX.0 {
+            None
+        } else {
+            Some(Int24(raw))
+        }
+    }
+
+    /// Returns the value as an i32.
+    pub const fn to_i32(self) -> i32 {
+        self.0
+    }
+
+    /// Checked addition. Returns `None` if the result overflows.
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        Self::checked_new(self.0 + other.0)
+    }
+
+    /// Checked subtraction. Returns `None` if the result overflows.
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        Self::checked_new(self.0 - other.0)
+    }
+
+    /// Saturating addition.
+    pub const fn saturating_add(self, other: Self) -> Self {
+        Self::new(self.0 + other.0)
+    }
+
+    /// Saturating subtraction.
+    pub const fn saturating_sub(self, other: Self) -> Self {
+        Self::new(self.0 - other.0)
+    }
+
+    /// Wrapping addition, modulo 2^24.
+    pub const fn wrapping_add(self, other: Self) -> Self {
+        Self::wrap(self.0 + other.0)
+    }
+
+    /// Wrapping subtraction, modulo 2^24.
+    pub const fn wrapping_sub(self, other: Self) -> Self {
+        Self::wrap(self.0 - other.0)
+    }
+
+    /// Truncates to 24 bits, sign extending the result.
+    const fn wrap(raw: i32) -> Self {
+        Int24((raw << 8) >> 8)
+    }
+
+    /// The representation of this number as a big-endian byte array.
+    pub const fn to_be_bytes(self) -> [u8; 3] {
+        let bytes = self.0.to_be_bytes();
+        [bytes[1], bytes[2], bytes[3]]
+    }
+
+    /// Create from a big-endian byte array.
+    pub const fn from_be_bytes(bytes: [u8; 3]) -> Self {
+        // place the bytes in the high 24 bits and shift back to sign extend
+        Int24(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], 0]) >> 8)
+    }
+}
+
+impl From<Int24> for i32 {
+    fn from(src: Int24) -> i32 {
+        src.0
+    }
+}
+
+impl From<i8> for Int24 {
+    fn from(src: i8) -> Int24 {
+        Int24(src as i32)
+    }
+}
+
+impl From<i16> for Int24 {
+    fn from(src: i16) -> Int24 {
+        Int24(src as i32)
+    }
+}
+
+impl From<u16> for Int24 {
+    fn from(src: u16) -> Int24 {
+        Int24(src as i32)
+    }
+}
+
+impl std::fmt::Display for Int24 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Int24 {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Int24::from_be_bytes)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[u8; 3] as arbitrary::Arbitrary>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constructor() {
+        assert_eq!(Int24::MAX, Int24::new(i32::MAX));
+        assert_eq!(Int24::MIN, Int24::new(i32::MIN));
+        assert!(Int24::checked_new(0x800000).is_none());
+        assert!(Int24::checked_new(-0x800001).is_none());
+    }
+
+    #[test]
+    fn be_bytes() {
+        for (bytes, value) in [
+            ([0xff, 0xff, 0xfe], -2),
+            ([0x80, 0x00, 0x00], -0x800000),
+            ([0x7f, 0xff, 0xff], 0x7fffff),
+            ([0x00, 0x01, 0x02], 0x0102),
+        ] {
+            let val = Int24::from_be_bytes(bytes);
+            assert_eq!(val.to_i32(), value);
+            assert_eq!(val.to_be_bytes(), bytes);
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let one = Int24::new(1);
+        assert_eq!(Int24::MAX.checked_add(one), None);
+        assert_eq!(Int24::MAX.saturating_add(one), Int24::MAX);
+        assert_eq!(Int24::MAX.wrapping_add(one), Int24::MIN);
+        assert_eq!(Int24::MIN.checked_sub(one), None);
+        assert_eq!(Int24::MIN.saturating_sub(one), Int24::MIN);
+        assert_eq!(Int24::MIN.wrapping_sub(one), Int24::MAX);
+        assert_eq!(Int24::new(-5).checked_add(one), Some(Int24::new(-4)));
+    }
+}
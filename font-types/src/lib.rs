@@ -16,23 +16,31 @@ extern crate core as std;
 mod fixed;
 mod fword;
 mod glyph_id;
+mod int24;
 mod longdatetime;
+mod name_id;
 mod offset;
 mod pen;
 mod point;
 mod raw;
+mod rect;
+#[cfg(feature = "serde")]
+mod serde_impls;
 mod tag;
 mod uint24;
 mod version;
 
 pub use fixed::{F26Dot6, F2Dot14, Fixed};
 pub use fword::{FWord, UfWord};
-pub use glyph_id::GlyphId;
+pub use glyph_id::{GlyphId, GlyphId24};
+pub use int24::Int24;
 pub use longdatetime::LongDateTime;
+pub use name_id::NameId;
 pub use offset::{Nullable, Offset16, Offset24, Offset32};
 pub use pen::Pen;
 pub use point::Point;
-pub use raw::{BigEndian, FixedSize, ReadScalar, Scalar};
+pub use raw::{BigEndian, BigEndianSlice, FixedSize, ReadScalar, Scalar};
+pub use rect::Rect;
 pub use tag::{InvalidTag, Tag};
 pub use uint24::Uint24;
 pub use version::{Compatible, MajorMinor, Version16Dot16};
@@ -4,6 +4,7 @@
 ///
 /// This represented as a number of seconds since 12:00 midnight, January 1, 1904, UTC.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LongDateTime(i64);
 
 impl LongDateTime {
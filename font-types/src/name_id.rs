@@ -0,0 +1,160 @@
+//! Name Identifiers
+//!
+//! Although these are treated as u16s in the spec, we choose to represent them
+//! as a distinct type.
+
+/// Identifier for an entry in the [name table].
+///
+/// Ids below 256 have meanings defined by the spec, while ids from 256 up
+/// are font-specific and are referenced from tables such as `fvar` and
+/// `STAT`.
+///
+/// [name table]: https://learn.microsoft.com/en-us/typography/opentype/spec/name#name-ids
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct NameId(u16);
+
+impl NameId {
+    /// Copyright notice.
+    pub const COPYRIGHT_NOTICE: Self = Self(0);
+    /// Font family name.
+    pub const FAMILY_NAME: Self = Self(1);
+    /// Font subfamily name.
+    pub const SUBFAMILY_NAME: Self = Self(2);
+    /// Unique font identifier.
+    pub const UNIQUE_ID: Self = Self(3);
+    /// Full font name.
+    pub const FULL_NAME: Self = Self(4);
+    /// Version string.
+    pub const VERSION_STRING: Self = Self(5);
+    /// PostScript name.
+    pub const POSTSCRIPT_NAME: Self = Self(6);
+    /// Trademark notice.
+    pub const TRADEMARK: Self = Self(7);
+    /// Manufacturer name.
+    pub const MANUFACTURER: Self = Self(8);
+    /// Name of the designer.
+    pub const DESIGNER: Self = Self(9);
+    /// Description of the typeface.
+    pub const DESCRIPTION: Self = Self(10);
+    /// URL of the font vendor.
+    pub const VENDOR_URL: Self = Self(11);
+    /// URL of the typeface designer.
+    pub const DESIGNER_URL: Self = Self(12);
+    /// License description.
+    pub const LICENSE_DESCRIPTION: Self = Self(13);
+    /// URL where additional licensing information can be found.
+    pub const LICENSE_URL: Self = Self(14);
+    /// Typographic family name.
+    pub const TYPOGRAPHIC_FAMILY_NAME: Self = Self(16);
+    /// Typographic subfamily name.
+    pub const TYPOGRAPHIC_SUBFAMILY_NAME: Self = Self(17);
+    /// Compatible full name, on the Macintosh platform only.
+    pub const COMPATIBLE_FULL_NAME: Self = Self(18);
+    /// Sample text.
+    pub const SAMPLE_TEXT: Self = Self(19);
+    /// PostScript CID findfont name.
+    pub const POSTSCRIPT_CID_NAME: Self = Self(20);
+    /// WWS family name.
+    pub const WWS_FAMILY_NAME: Self = Self(21);
+    /// WWS subfamily name.
+    pub const WWS_SUBFAMILY_NAME: Self = Self(22);
+    /// Light background palette name.
+    pub const LIGHT_BACKGROUND_PALETTE: Self = Self(23);
+    /// Dark background palette name.
+    pub const DARK_BACKGROUND_PALETTE: Self = Self(24);
+    /// Variations PostScript name prefix.
+    pub const VARIATIONS_POSTSCRIPT_NAME_PREFIX: Self = Self(25);
+
+    /// The last id with a meaning defined by the spec.
+    pub const LAST_PREDEFINED: Self = Self(25);
+    /// The first id available for font-specific names.
+    pub const FIRST_FONT_SPECIFIC: Self = Self(256);
+    /// The last id available for font-specific names.
+    ///
+    /// The spec reserves font-specific ids up to 32767.
+    pub const LAST_FONT_SPECIFIC: Self = Self(32767);
+
+    /// Construct a new `NameId`.
+    pub const fn new(raw: u16) -> Self {
+        Self(raw)
+    }
+
+    /// The identifier as a u16.
+    pub const fn to_u16(self) -> u16 {
+        self.0
+    }
+
+    /// Returns true if this id is in the range with meanings defined by the
+    /// spec.
+    pub const fn is_predefined(self) -> bool {
+        self.0 <= Self::LAST_PREDEFINED.0
+    }
+
+    /// Returns true if this id is reserved by the spec for future use.
+    pub const fn is_reserved(self) -> bool {
+        (self.0 > Self::LAST_PREDEFINED.0 && self.0 < Self::FIRST_FONT_SPECIFIC.0)
+            || self.0 > Self::LAST_FONT_SPECIFIC.0
+    }
+
+    /// Returns true if this id is in the range used for font-specific names,
+    /// such as those referenced by `fvar` and `STAT`.
+    pub const fn is_font_specific(self) -> bool {
+        self.0 >= Self::FIRST_FONT_SPECIFIC.0 && self.0 <= Self::LAST_FONT_SPECIFIC.0
+    }
+
+    /// The next id, or `None` if this is the last id.
+    pub const fn checked_next(self) -> Option<Self> {
+        match self.0.checked_add(1) {
+            Some(raw) => Some(Self(raw)),
+            None => None,
+        }
+    }
+
+    pub const fn to_be_bytes(self) -> [u8; 2] {
+        self.0.to_be_bytes()
+    }
+}
+
+impl From<u16> for NameId {
+    fn from(src: u16) -> Self {
+        Self(src)
+    }
+}
+
+impl From<NameId> for u16 {
+    fn from(src: NameId) -> u16 {
+        src.0
+    }
+}
+
+impl PartialEq<u16> for NameId {
+    fn eq(&self, other: &u16) -> bool {
+        self.0 == *other
+    }
+}
+
+impl std::fmt::Display for NameId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+crate::newtype_scalar!(NameId, [u8; 2]);
+
+#[cfg(test)]
+mod tests {
+    use super::NameId;
+
+    #[test]
+    fn ranges() {
+        assert!(NameId::FAMILY_NAME.is_predefined());
+        assert!(!NameId::FAMILY_NAME.is_font_specific());
+        assert!(NameId::new(26).is_reserved());
+        assert!(NameId::new(255).is_reserved());
+        assert!(NameId::new(256).is_font_specific());
+        assert!(NameId::new(32767).is_font_specific());
+        assert!(NameId::new(32768).is_reserved());
+        assert_eq!(NameId::new(u16::MAX).checked_next(), None);
+    }
+}
@@ -2,6 +2,7 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssi
 
 /// Two dimensional point with a generic coordinate type.
 #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Point<T> {
     /// X coordinate.
     pub x: T,
@@ -32,6 +33,70 @@ impl<T> Point<T> {
     }
 }
 
+macro_rules! float_point_impl {
+    ($ty:ty) => {
+        impl Point<$ty> {
+            /// Returns the dot product of this point and `other`, treating
+            /// both as vectors.
+            #[inline]
+            pub fn dot(self, other: Self) -> $ty {
+                self.x * other.x + self.y * other.y
+            }
+
+            /// Returns the z component of the cross product of this point
+            /// and `other`, treating both as vectors.
+            ///
+            /// The result is positive when `other` is counter-clockwise from
+            /// `self` in a y-up coordinate system.
+            #[inline]
+            pub fn cross(self, other: Self) -> $ty {
+                self.x * other.y - self.y * other.x
+            }
+
+            /// Returns the squared length of the vector from the origin to
+            /// this point.
+            #[inline]
+            pub fn length_squared(self) -> $ty {
+                self.dot(self)
+            }
+
+            /// Returns the length of the vector from the origin to this point.
+            #[cfg(any(feature = "std", test))]
+            #[inline]
+            pub fn length(self) -> $ty {
+                self.x.hypot(self.y)
+            }
+
+            /// Returns a vector with the same direction and a length of one.
+            ///
+            /// Returns `None` if the length is zero or not finite.
+            #[cfg(any(feature = "std", test))]
+            #[inline]
+            pub fn normalize(self) -> Option<Self> {
+                let length = self.length();
+                (length != 0.0 && length.is_finite()).then(|| self / length)
+            }
+
+            /// Linearly interpolates between this point and `other`, where
+            /// a `t` of 0 produces `self` and 1 produces `other`.
+            #[inline]
+            pub fn lerp(self, other: Self, t: $ty) -> Self {
+                self + (other - self) * t
+            }
+
+            /// Returns the euclidean distance between this point and `other`.
+            #[cfg(any(feature = "std", test))]
+            #[inline]
+            pub fn distance(self, other: Self) -> $ty {
+                (other - self).length()
+            }
+        }
+    };
+}
+
+float_point_impl!(f32);
+float_point_impl!(f64);
+
 impl<T> Add for Point<T>
 where
     T: Add<Output = T>,
@@ -241,4 +306,39 @@ mod tests {
     fn neg() {
         assert_eq!(-Point::new(1, -2), Point::new(-1, 2));
     }
+
+    #[test]
+    fn products() {
+        let (a, b) = (Point::new(1.0f32, 2.0), Point::new(3.0, -4.0));
+        assert_eq!(a.dot(b), -5.0);
+        assert_eq!(a.cross(b), -10.0);
+        assert_eq!(b.cross(a), 10.0);
+        assert_eq!(Point::new(1.0f64, 0.0).cross(Point::new(0.0, 1.0)), 1.0);
+    }
+
+    #[test]
+    fn length_distance() {
+        let point = Point::new(3.0f32, -4.0);
+        assert_eq!(point.length_squared(), 25.0);
+        assert_eq!(point.length(), 5.0);
+        assert_eq!(Point::new(1.0f64, 1.0).distance(Point::new(4.0, 5.0)), 5.0);
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(
+            Point::new(3.0f32, -4.0).normalize(),
+            Some(Point::new(0.6, -0.8))
+        );
+        assert_eq!(Point::new(0.0f32, 0.0).normalize(), None);
+        assert_eq!(Point::new(f64::INFINITY, 0.0).normalize(), None);
+    }
+
+    #[test]
+    fn lerp() {
+        let (a, b) = (Point::new(0.0f32, 10.0), Point::new(10.0, 20.0));
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.25), Point::new(2.5, 12.5));
+    }
 }
@@ -72,6 +72,69 @@ impl<T: Scalar> BigEndian<T> {
     }
 }
 
+/// Bulk conversion of big-endian arrays to native values.
+///
+/// This is implemented for slices of [`BigEndian`] values, such as those
+/// returned when reading arrays from font data. Converting a whole array at
+/// once is considerably faster than calling [`BigEndian::get`] on each
+/// element, since the loop is simple enough for the compiler to vectorize.
+pub trait BigEndianSlice<T> {
+    /// Converts each element, writing the results to `dst`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` has a different length than `self`.
+    fn copy_to_native(&self, dst: &mut [T]);
+
+    /// Converts each element, appending the results to `dst`.
+    #[cfg(feature = "std")]
+    fn extend_native(&self, dst: &mut Vec<T>);
+
+    /// Converts each element into a new vector.
+    #[cfg(feature = "std")]
+    fn to_native_vec(&self) -> Vec<T> {
+        let mut result = Vec::new();
+        self.extend_native(&mut result);
+        result
+    }
+}
+
+/// The number of elements converted per iteration of the bulk conversion
+/// loop.
+const CHUNK_LEN: usize = 16;
+
+impl<T: Scalar> BigEndianSlice<T> for [BigEndian<T>] {
+    fn copy_to_native(&self, dst: &mut [T]) {
+        assert_eq!(
+            self.len(),
+            dst.len(),
+            "source and destination slices have different lengths"
+        );
+        let mut src_chunks = self.chunks_exact(CHUNK_LEN);
+        let mut dst_chunks = dst.chunks_exact_mut(CHUNK_LEN);
+        for (src, dst) in src_chunks.by_ref().zip(dst_chunks.by_ref()) {
+            // fixed length chunks allow the bounds checks to be elided
+            for i in 0..CHUNK_LEN {
+                dst[i] = T::from_raw(src[i].0);
+            }
+        }
+        for (src, dst) in src_chunks
+            .remainder()
+            .iter()
+            .zip(dst_chunks.into_remainder())
+        {
+            *dst = T::from_raw(src.0);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn extend_native(&self, dst: &mut Vec<T>) {
+        // slice iterators have an exact length, so this writes directly
+        // into the reserved capacity without per-element capacity checks
+        dst.extend(self.iter().map(|val| T::from_raw(val.0)));
+    }
+}
+
 impl<T: Scalar> From<T> for BigEndian<T> {
     #[inline]
     fn from(val: T) -> Self {
@@ -180,6 +243,7 @@ int_scalar!(u32, [u8; 4]);
 int_scalar!(i32, [u8; 4]);
 int_scalar!(i64, [u8; 8]);
 int_scalar!(crate::Uint24, [u8; 3]);
+int_scalar!(crate::Int24, [u8; 3]);
 
 impl<T: std::fmt::Debug + Scalar + Copy> std::fmt::Debug for BigEndian<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -192,3 +256,31 @@ impl<T: std::fmt::Display + Scalar + Copy> std::fmt::Display for BigEndian<T> {
         self.get().fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bulk_conversion() {
+        let values: Vec<i16> = (-100..100).map(|i| i * 311).collect();
+        let raw: Vec<BigEndian<i16>> = values.iter().copied().map(BigEndian::from).collect();
+        // exercise both the chunked loop and the remainder
+        for len in [0, 1, 15, 16, 17, 33, raw.len()] {
+            let mut native = vec![0i16; len];
+            raw[..len].copy_to_native(&mut native);
+            assert_eq!(native, values[..len]);
+            assert_eq!(raw[..len].to_native_vec(), values[..len]);
+        }
+        let mut extended = vec![1i16];
+        raw[..3].extend_native(&mut extended);
+        assert_eq!(extended, [1, values[0], values[1], values[2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "different lengths")]
+    fn bulk_conversion_len_mismatch() {
+        let raw = [BigEndian::from(1u32); 4];
+        raw.copy_to_native(&mut [0u32; 3]);
+    }
+}
@@ -0,0 +1,173 @@
+use std::{cmp::Ordering, ops::Sub};
+
+use crate::Point;
+
+/// Axis-aligned rectangle with a generic coordinate type.
+///
+/// The rectangle is defined by its minimum and maximum coordinates. It is
+/// empty if a maximum coordinate is not greater than the corresponding
+/// minimum.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Rect<T> {
+    /// Minimum x coordinate.
+    pub x_min: T,
+    /// Minimum y coordinate.
+    pub y_min: T,
+    /// Maximum x coordinate.
+    pub x_max: T,
+    /// Maximum y coordinate.
+    pub y_max: T,
+}
+
+impl<T> Rect<T> {
+    /// Creates a new rectangle with the given minimum and maximum
+    /// coordinates.
+    pub const fn new(x_min: T, y_min: T, x_max: T, y_max: T) -> Self {
+        Self {
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+        }
+    }
+
+    /// Maps `Rect<T>` to `Rect<U>` by applying a function to each coordinate.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Rect<U> {
+        Rect {
+            x_min: f(self.x_min),
+            y_min: f(self.y_min),
+            x_max: f(self.x_max),
+            y_max: f(self.y_max),
+        }
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + PartialOrd,
+{
+    /// Returns the smallest rectangle containing all of the given points, or
+    /// `None` if there are no points.
+    pub fn from_points(points: impl IntoIterator<Item = Point<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut rect = Self::new(first.x, first.y, first.x, first.y);
+        for point in points {
+            rect.include_point(point);
+        }
+        Some(rect)
+    }
+
+    /// Returns true if the rectangle encloses no area.
+    pub fn is_empty(&self) -> bool {
+        // NaN coordinates produce an empty rectangle.
+        self.x_min.partial_cmp(&self.x_max) != Some(Ordering::Less)
+            || self.y_min.partial_cmp(&self.y_max) != Some(Ordering::Less)
+    }
+
+    /// Returns true if the point is inside the rectangle or on its edges.
+    pub fn contains(&self, point: Point<T>) -> bool {
+        point.x >= self.x_min
+            && point.x <= self.x_max
+            && point.y >= self.y_min
+            && point.y <= self.y_max
+    }
+
+    /// Extends the rectangle to contain the given point.
+    pub fn include_point(&mut self, point: Point<T>) {
+        self.x_min = min(self.x_min, point.x);
+        self.y_min = min(self.y_min, point.y);
+        self.x_max = max(self.x_max, point.x);
+        self.y_max = max(self.y_max, point.y);
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            x_min: min(self.x_min, other.x_min),
+            y_min: min(self.y_min, other.y_min),
+            x_max: max(self.x_max, other.x_max),
+            y_max: max(self.y_max, other.y_max),
+        }
+    }
+
+    /// Returns the area shared by both rectangles, or `None` if they do not
+    /// overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let rect = Self {
+            x_min: max(self.x_min, other.x_min),
+            y_min: max(self.y_min, other.y_min),
+            x_max: min(self.x_max, other.x_max),
+            y_max: min(self.y_max, other.y_max),
+        };
+        (!rect.is_empty()).then_some(rect)
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + Sub<Output = T>,
+{
+    /// Returns the width of the rectangle.
+    pub fn width(&self) -> T {
+        self.x_max - self.x_min
+    }
+
+    /// Returns the height of the rectangle.
+    pub fn height(&self) -> T {
+        self.y_max - self.y_min
+    }
+}
+
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Point, Rect};
+
+    #[test]
+    fn from_points() {
+        let points = [Point::new(1, 5), Point::new(-2, 3), Point::new(4, -1)];
+        assert_eq!(Rect::from_points(points), Some(Rect::new(-2, -1, 4, 5)));
+        assert_eq!(Rect::<f32>::from_points([]), None);
+        let single = Rect::from_points([Point::new(1.0, 2.0)]).unwrap();
+        assert_eq!(single, Rect::new(1.0, 2.0, 1.0, 2.0));
+        assert!(single.is_empty());
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let a = Rect::new(0, 0, 10, 10);
+        let b = Rect::new(5, -5, 15, 5);
+        assert_eq!(a.union(&b), Rect::new(0, -5, 15, 10));
+        assert_eq!(a.intersection(&b), Some(Rect::new(5, 0, 10, 5)));
+        // Rectangles that only share an edge do not overlap.
+        let c = Rect::new(10, 0, 20, 10);
+        assert_eq!(a.intersection(&c), None);
+    }
+
+    #[test]
+    fn contains() {
+        let rect = Rect::new(0.0, 0.0, 2.0, 1.0);
+        assert!(rect.contains(Point::new(0.0, 0.0)));
+        assert!(rect.contains(Point::new(2.0, 1.0)));
+        assert!(!rect.contains(Point::new(2.5, 0.5)));
+        assert_eq!((rect.width(), rect.height()), (2.0, 1.0));
+        assert!(Rect::new(0.0, 0.0, f32::NAN, 1.0).is_empty());
+    }
+}
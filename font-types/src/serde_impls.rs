@@ -0,0 +1,206 @@
+//! serde support for scalar types.
+//!
+//! For human-readable formats, types are represented in the form they are
+//! usually written by hand: tags as strings (`"wght"`), fixed point values as
+//! numbers (`1.5`) and versions as `"major.minor"` strings. Other formats use
+//! the raw value.
+
+use std::fmt::{self, Formatter};
+
+use serde::{
+    de::{self, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{F26Dot6, F2Dot14, Fixed, GlyphId, MajorMinor, Scalar, Tag, Version16Dot16};
+
+impl Serialize for Tag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.to_be_bytes().serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Tag {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TagVisitor;
+
+        impl Visitor<'_> for TagVisitor {
+            type Value = Tag;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("a tag of one to four printable ascii characters")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Tag, E> {
+                Tag::new_checked(v.as_bytes()).map_err(E::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(TagVisitor)
+        } else {
+            <[u8; 4]>::deserialize(deserializer).map(Tag::from_be_bytes)
+        }
+    }
+}
+
+macro_rules! fixed_serde {
+    ($ty:ident, $bits:ty, $float:ty, $to:ident, $from:ident) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    self.$to().serialize(serializer)
+                } else {
+                    self.to_bits().serialize(serializer)
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if !deserializer.is_human_readable() {
+                    return <$bits>::deserialize(deserializer).map(Self::from_bits);
+                }
+                let value = <$float>::deserialize(deserializer)?;
+                if !(Self::MIN.$to()..=Self::MAX.$to()).contains(&value) {
+                    return Err(de::Error::invalid_value(
+                        Unexpected::Float(value as f64),
+                        &concat!("a value in the range of ", stringify!($ty)),
+                    ));
+                }
+                Ok(Self::$from(value))
+            }
+        }
+    };
+}
+
+fixed_serde!(F2Dot14, i16, f32, to_f32, from_f32);
+fixed_serde!(Fixed, i32, f64, to_f64, from_f64);
+fixed_serde!(F26Dot6, i32, f64, to_f64, from_f64);
+
+impl Serialize for GlyphId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_u16().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GlyphId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u16::deserialize(deserializer).map(GlyphId::new)
+    }
+}
+
+/// Parses a `"major.minor"` version string.
+fn parse_version<E: de::Error>(v: &str) -> Result<(u16, u16), E> {
+    v.split_once('.')
+        .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)))
+        .ok_or_else(|| E::invalid_value(Unexpected::Str(v), &"a version like \"1.0\""))
+}
+
+struct VersionVisitor;
+
+impl Visitor<'_> for VersionVisitor {
+    type Value = (u16, u16);
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a version string like \"1.0\"")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        parse_version(v)
+    }
+}
+
+impl Serialize for Version16Dot16 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            u32::from_be_bytes(self.to_be_bytes()).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Version16Dot16 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return u32::deserialize(deserializer)
+                .map(|bits| Version16Dot16::from_raw(bits.to_be_bytes()));
+        }
+        let (major, minor) = deserializer.deserialize_str(VersionVisitor)?;
+        if minor > 9 {
+            return Err(de::Error::invalid_value(
+                Unexpected::Unsigned(minor as u64),
+                &"a minor version in the range 0..=9",
+            ));
+        }
+        Ok(Version16Dot16::new(major, minor))
+    }
+}
+
+impl Serialize for MajorMinor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.major, self.minor).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MajorMinor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (major, minor) = if deserializer.is_human_readable() {
+            deserializer.deserialize_str(VersionVisitor)?
+        } else {
+            <(u16, u16)>::deserialize(deserializer)?
+        };
+        Ok(MajorMinor::new(major, minor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip<T>(value: T, json: &str)
+    where
+        T: Serialize + for<'de> Deserialize<'de> + PartialEq + std::fmt::Debug,
+    {
+        assert_eq!(serde_json::to_string(&value).unwrap(), json);
+        assert_eq!(serde_json::from_str::<T>(json).unwrap(), value);
+    }
+
+    #[test]
+    fn human_readable() {
+        roundtrip(Tag::new(b"wght"), "\"wght\"");
+        roundtrip(Tag::CFF, "\"CFF \"");
+        roundtrip(Fixed::from_f64(1.5), "1.5");
+        roundtrip(F2Dot14::from_f32(-0.25), "-0.25");
+        roundtrip(F26Dot6::from_f64(10.0), "10.0");
+        roundtrip(GlyphId::new(42), "42");
+        roundtrip(Version16Dot16::VERSION_0_5, "\"0.5\"");
+        roundtrip(MajorMinor::VERSION_1_2, "\"1.2\"");
+        assert_eq!(
+            serde_json::from_str::<Fixed>("-3").unwrap(),
+            Fixed::from_i32(-3)
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(serde_json::from_str::<Tag>("\"\"").is_err());
+        assert!(serde_json::from_str::<Tag>("\"toolong\"").is_err());
+        assert!(serde_json::from_str::<Tag>("\" abc\"").is_err());
+        assert!(serde_json::from_str::<F2Dot14>("2.5").is_err());
+        assert!(serde_json::from_str::<Fixed>("40000").is_err());
+        assert!(serde_json::from_str::<GlyphId>("70000").is_err());
+        assert!(serde_json::from_str::<Version16Dot16>("\"1.10\"").is_err());
+        assert!(serde_json::from_str::<MajorMinor>("\"1\"").is_err());
+        assert!(serde_json::from_str::<MajorMinor>("\"1.x\"").is_err());
+    }
+}
@@ -9,6 +9,7 @@ use std::{
 /// A tag is a 4-byte array where each byte is in the printable ascii range
 /// (0x20..=0x7E).
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Tag([u8; 4]);
 
 impl Tag {
@@ -65,6 +66,18 @@ impl Tag {
         Ok(Tag(raw))
     }
 
+    /// Returns true if the tag conforms to the OpenType spec.
+    ///
+    /// A valid tag contains only bytes in the printable ascii range
+    /// (`0x20..=0x7E`), does not begin with a space, and any spaces are
+    /// trailing padding.
+    ///
+    /// Tags read from font data are not checked, so this can be used to
+    /// detect malformed input.
+    pub const fn is_valid(self) -> bool {
+        Tag::new_checked(&self.0).is_ok()
+    }
+
     // for symmetry with integer types / other things we encode/decode
     /// Return the memory representation of this tag.
     pub const fn to_be_bytes(self) -> [u8; 4] {
@@ -88,10 +101,106 @@ impl Tag {
     }
 }
 
+/// Generates constants for registered tags.
+macro_rules! registered_tags {
+    ($kind:literal, $($name:ident = $tag:literal, $desc:literal;)*) => {
+        $(
+            #[doc = concat!("The `", $tag, "` (", $desc, ") ", $kind, ".")]
+            pub const $name: Tag = Tag::new($tag.as_bytes());
+        )*
+    };
+}
+
+/// Tables registered in the OpenType specification.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/otff#font-tables>
+impl Tag {
+    registered_tags! {
+        "table",
+        AVAR = "avar", "axis variations";
+        BASE = "BASE", "baseline data";
+        CBDT = "CBDT", "color bitmap data";
+        CBLC = "CBLC", "color bitmap location data";
+        CFF = "CFF ", "compact font format";
+        CFF2 = "CFF2", "compact font format 2.0";
+        CMAP = "cmap", "character to glyph index mapping";
+        COLR = "COLR", "color";
+        CPAL = "CPAL", "color palette";
+        CVAR = "cvar", "CVT variations";
+        CVT = "cvt ", "control value";
+        DSIG = "DSIG", "digital signature";
+        EBDT = "EBDT", "embedded bitmap data";
+        EBLC = "EBLC", "embedded bitmap location data";
+        EBSC = "EBSC", "embedded bitmap scaling data";
+        FPGM = "fpgm", "font program";
+        FVAR = "fvar", "font variations";
+        GASP = "gasp", "grid-fitting and scan-conversion procedure";
+        GDEF = "GDEF", "glyph definition";
+        GLYF = "glyf", "glyph data";
+        GPOS = "GPOS", "glyph positioning";
+        GSUB = "GSUB", "glyph substitution";
+        GVAR = "gvar", "glyph variations";
+        HDMX = "hdmx", "horizontal device metrics";
+        HEAD = "head", "font header";
+        HHEA = "hhea", "horizontal header";
+        HMTX = "hmtx", "horizontal metrics";
+        HVAR = "HVAR", "horizontal metrics variations";
+        JSTF = "JSTF", "justification";
+        KERN = "kern", "kerning";
+        LOCA = "loca", "index to location";
+        LTSH = "LTSH", "linear threshold";
+        MATH = "MATH", "mathematical typesetting";
+        MAXP = "maxp", "maximum profile";
+        MERG = "MERG", "merge";
+        META = "meta", "metadata";
+        MVAR = "MVAR", "metrics variations";
+        NAME = "name", "naming";
+        OS2 = "OS/2", "OS/2 and Windows metrics";
+        PCLT = "PCLT", "PCL 5";
+        POST = "post", "PostScript";
+        PREP = "prep", "control value program";
+        SBIX = "sbix", "standard bitmap graphics";
+        STAT = "STAT", "style attributes";
+        SVG = "SVG ", "scalable vector graphics";
+        VDMX = "VDMX", "vertical device metrics";
+        VHEA = "vhea", "vertical header";
+        VMTX = "vmtx", "vertical metrics";
+        VORG = "VORG", "vertical origin";
+        VVAR = "VVAR", "vertical metrics variations";
+    }
+}
+
+/// Axes registered in the OpenType specification.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/dvaraxisreg>
+impl Tag {
+    registered_tags! {
+        "axis",
+        ITAL = "ital", "italic";
+        OPSZ = "opsz", "optical size";
+        SLNT = "slnt", "slant";
+        WDTH = "wdth", "width";
+        WGHT = "wght", "weight";
+    }
+
+    const REGISTERED_AXES: [Tag; 5] = [Tag::ITAL, Tag::OPSZ, Tag::SLNT, Tag::WDTH, Tag::WGHT];
+
+    /// Returns true if this is the tag of an axis registered in the
+    /// OpenType specification.
+    ///
+    /// Registered axis tags use lowercase letters, while uppercase tags are
+    /// reserved for foundry-defined axes.
+    pub fn is_registered_axis(self) -> bool {
+        Self::REGISTERED_AXES.contains(&self)
+    }
+}
+
 /// An error representing an invalid tag.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum InvalidTag {
+    /// The input was empty or longer than four bytes.
     InvalidLength(usize),
+    /// The input contained a byte that is not allowed at this position.
     InvalidByte { pos: usize, byte: u8 },
 }
 
@@ -224,6 +333,26 @@ mod tests {
         assert!(Tag::new_checked(&[0x7F]).is_err());
     }
 
+    #[test]
+    fn validity() {
+        assert!(Tag::CVT.is_valid());
+        assert!(Tag::from_be_bytes(*b"OS/2").is_valid());
+        assert!(!Tag::from_be_bytes(*b" abc").is_valid());
+        assert!(!Tag::from_be_bytes(*b"a bc").is_valid());
+        assert!(!Tag::from_be_bytes([b'a', 0, 0, 0]).is_valid());
+        assert_eq!("cvt".parse(), Ok(Tag::CVT));
+        assert!("c vt".parse::<Tag>().is_err());
+    }
+
+    #[test]
+    fn registered() {
+        assert_eq!(Tag::CFF, Tag::new(b"CFF "));
+        assert_eq!(Tag::OS2, Tag::new(b"OS/2"));
+        assert!(Tag::WGHT.is_registered_axis());
+        assert!(!Tag::new(b"WGHT").is_registered_axis());
+        assert!(!Tag::GLYF.is_registered_axis());
+    }
+
     #[test]
     #[should_panic]
     fn name() {
@@ -1,5 +1,5 @@
 /// 24-bit unsigned integer.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Uint24(u32);
 
 impl Uint24 {
@@ -25,11 +25,51 @@ impl Uint24 {
         }
     }
 
+    /// Returns the value as a u32.
+    pub const fn to_u32(self) -> u32 {
+        self.0
+    }
+
+    /// Checked addition. Returns `None` if the result overflows.
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        Self::checked_new(self.0 + other.0)
+    }
+
+    /// Checked subtraction. Returns `None` if the result underflows.
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.0.checked_sub(other.0) {
+            Some(raw) => Some(Uint24(raw)),
+            None => None,
+        }
+    }
+
+    /// Saturating addition.
+    pub const fn saturating_add(self, other: Self) -> Self {
+        Self::new(self.0 + other.0)
+    }
+
+    /// Saturating subtraction.
+    pub const fn saturating_sub(self, other: Self) -> Self {
+        Uint24(self.0.saturating_sub(other.0))
+    }
+
+    /// Wrapping addition, modulo 2^24.
+    pub const fn wrapping_add(self, other: Self) -> Self {
+        Uint24(self.0.wrapping_add(other.0) & Self::MAX.0)
+    }
+
+    /// Wrapping subtraction, modulo 2^24.
+    pub const fn wrapping_sub(self, other: Self) -> Self {
+        Uint24(self.0.wrapping_sub(other.0) & Self::MAX.0)
+    }
+
+    /// The representation of this number as a big-endian byte array.
     pub const fn to_be_bytes(self) -> [u8; 3] {
         let bytes = self.0.to_be_bytes();
         [bytes[1], bytes[2], bytes[3]]
     }
 
+    /// Create from a big-endian byte array.
     pub const fn from_be_bytes(bytes: [u8; 3]) -> Self {
         Uint24::new((bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32)
     }
@@ -41,12 +81,41 @@ impl From<Uint24> for u32 {
     }
 }
 
+impl From<Uint24> for usize {
+    fn from(src: Uint24) -> usize {
+        src.0 as usize
+    }
+}
+
+impl From<u8> for Uint24 {
+    fn from(src: u8) -> Uint24 {
+        Uint24(src as u32)
+    }
+}
+
+impl From<u16> for Uint24 {
+    fn from(src: u16) -> Uint24 {
+        Uint24(src as u32)
+    }
+}
+
 impl std::fmt::Display for Uint24 {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         self.0.fmt(f)
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Uint24 {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Uint24::from_be_bytes)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <[u8; 3] as arbitrary::Arbitrary>::size_hint(depth)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,4 +132,16 @@ mod tests {
         let val = Uint24::from_be_bytes(bytes);
         assert_eq!(val.to_be_bytes(), bytes);
     }
+
+    #[test]
+    fn arithmetic() {
+        let one = Uint24::new(1);
+        assert_eq!(Uint24::MAX.checked_add(one), None);
+        assert_eq!(Uint24::MAX.saturating_add(one), Uint24::MAX);
+        assert_eq!(Uint24::MAX.wrapping_add(one), Uint24::MIN);
+        assert_eq!(Uint24::MIN.checked_sub(one), None);
+        assert_eq!(Uint24::MIN.saturating_sub(one), Uint24::MIN);
+        assert_eq!(Uint24::MIN.wrapping_sub(one), Uint24::MAX);
+        assert_eq!(one.checked_add(one).map(Uint24::to_u32), Some(2));
+    }
 }
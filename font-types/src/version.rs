@@ -5,6 +5,7 @@
 /// This is a legacy type with an unusual representation. See [the spec][] for
 /// additional details.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Version16Dot16(u32);
 
 /// A type representing a major, minor version pair.
@@ -13,7 +14,10 @@ pub struct Version16Dot16(u32);
 /// represented as a `major_version`, `minor_version` pair. This type encodes
 /// those as a single type, which is useful for some of the generated code that
 /// parses out a version.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///
+/// Versions are ordered by major and then minor version.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MajorMinor {
     /// The major version number
     pub major: u16,
@@ -64,6 +68,16 @@ impl Version16Dot16 {
         (major, minor)
     }
 
+    /// Returns `true` if this version is greater than or equal to
+    /// `major.minor`.
+    ///
+    /// Unlike [`Compatible::compatible`], this does not require the major
+    /// versions to match.
+    pub const fn at_least(self, major: u16, minor: u16) -> bool {
+        let (self_major, self_minor) = self.to_major_minor();
+        self_major > major || (self_major == major && self_minor >= minor)
+    }
+
     /// The representation of this version as a big-endian byte array.
     #[inline]
     pub const fn to_be_bytes(self) -> [u8; 4] {
@@ -91,6 +105,16 @@ impl MajorMinor {
         MajorMinor { major, minor }
     }
 
+    /// Returns `true` if this version is greater than or equal to
+    /// `major.minor`.
+    ///
+    /// Unlike [`Compatible::compatible`], this does not require the major
+    /// versions to match.
+    #[inline]
+    pub const fn at_least(self, major: u16, minor: u16) -> bool {
+        self.major > major || (self.major == major && self.minor >= minor)
+    }
+
     /// The representation of this version as a big-endian byte array.
     #[inline]
     pub const fn to_be_bytes(self) -> [u8; 4] {
@@ -125,7 +149,10 @@ impl Compatible for Version16Dot16 {
 
 impl Compatible<(u16, u16)> for Version16Dot16 {
     fn compatible(&self, other: (u16, u16)) -> bool {
-        self.compatible(Version16Dot16::new(other.0, other.1))
+        // compare the parts directly: not every (major, minor) pair can be
+        // encoded as a Version16Dot16
+        let (self_major, self_minor) = self.to_major_minor();
+        self_major == other.0 && self_minor >= other.1
     }
 }
 
@@ -179,4 +206,57 @@ mod tests {
         assert_eq!(Version16Dot16::new(0, 5).0, 0x00005000);
         assert_eq!(Version16Dot16::new(1, 1).0, 0x00011000);
     }
+
+    #[test]
+    fn ordering() {
+        let mut versions = [
+            MajorMinor::VERSION_2_0,
+            MajorMinor::VERSION_1_2,
+            MajorMinor::new(1, 10),
+            MajorMinor::VERSION_1_0,
+        ];
+        versions.sort();
+        assert_eq!(
+            versions,
+            [
+                MajorMinor::VERSION_1_0,
+                MajorMinor::VERSION_1_2,
+                MajorMinor::new(1, 10),
+                MajorMinor::VERSION_2_0
+            ]
+        );
+        assert!(Version16Dot16::VERSION_0_5 < Version16Dot16::VERSION_1_0);
+        assert!(Version16Dot16::VERSION_2_5 < Version16Dot16::VERSION_3_0);
+    }
+
+    #[test]
+    fn at_least() {
+        let version = MajorMinor::VERSION_1_2;
+        assert!(version.at_least(1, 0));
+        assert!(version.at_least(1, 2));
+        assert!(!version.at_least(1, 3));
+        assert!(!version.at_least(2, 0));
+        assert!(MajorMinor::VERSION_2_0.at_least(1, 3));
+        let version = Version16Dot16::VERSION_2_5;
+        assert!(version.at_least(1, 0));
+        assert!(version.at_least(2, 5));
+        assert!(!version.at_least(3, 0));
+    }
+
+    #[test]
+    fn compatible() {
+        // minor versions are forward compatible, major versions are not
+        let version = MajorMinor::VERSION_1_2;
+        assert!(version.compatible((1, 1)));
+        assert!(version.compatible(MajorMinor::VERSION_1_2));
+        assert!(!version.compatible((1, 3)));
+        assert!(!MajorMinor::VERSION_2_0.compatible((1, 0)));
+        let version = Version16Dot16::VERSION_1_1;
+        assert!(version.compatible((1, 0)));
+        assert!(!version.compatible((0, 5)));
+        // not representable as Version16Dot16, but must not panic
+        assert!(!version.compatible((1, 10)));
+        assert!(2u16.compatible(1));
+        assert!(!0u16.compatible(1));
+    }
 }
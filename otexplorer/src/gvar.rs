@@ -0,0 +1,233 @@
+//! printing the variation data of a single glyph
+
+use std::str::FromStr;
+
+use font_types::{F2Dot14, Fixed, GlyphId, Point, Tag};
+use read_fonts::{
+    tables::{
+        glyf::{Anchor, Glyph, PointFlags},
+        variations::Tuple,
+    },
+    FontRef, TableProvider,
+};
+
+/// The names of the four phantom points that follow the points of a glyph.
+const PHANTOM_POINTS: [&str; 4] = ["left", "right", "top", "bottom"];
+
+/// Print each tuple variation of a glyph.
+///
+/// If a location is provided, this also prints the scalar of each tuple at
+/// that location, and the deltas of all tuples accumulated, including the
+/// deltas inferred for points that a tuple does not reference.
+pub fn print_gvar(font: &FontRef, glyph: &str, location: Option<&str>) -> Result<(), String> {
+    let gvar = font.gvar().map_err(|e| e.to_string())?;
+    let axes = font
+        .fvar()
+        .and_then(|fvar| fvar.axes())
+        .map_err(|e| e.to_string())?;
+    let tags = axes.iter().map(|axis| axis.axis_tag()).collect::<Vec<_>>();
+    let gid = parse_glyph(font, glyph)?;
+    let coords = location
+        .map(|location| normalize_location(font, location))
+        .transpose()?;
+
+    let name = font
+        .post()
+        .ok()
+        .and_then(|post| post.glyph_name(gid).map(String::from));
+    match name {
+        Some(name) => println!("glyph {} ({name})", gid.to_u16()),
+        None => println!("glyph {}", gid.to_u16()),
+    }
+    if let Some(coords) = &coords {
+        println!(
+            "location {} (normalized {})",
+            location.unwrap_or_default(),
+            format_coords(&tags, coords.iter().copied())
+        );
+    }
+    let Some(data) = gvar.glyph_variation_data(gid).map_err(|e| e.to_string())? else {
+        println!("no variations");
+        return Ok(());
+    };
+
+    let outline = Outline::new(font, gid)?;
+    let n_points = outline.points.len();
+    let mut flags = vec![PointFlags::default(); n_points];
+    let mut scratch = vec![Point::default(); n_points];
+    let mut deltas = vec![Point::<Fixed>::default(); n_points];
+    for (i, tuple) in data.tuples().enumerate() {
+        println!();
+        println!("tuple {i}");
+        println!("  peak: {}", format_tuple(&tags, &tuple.peak()));
+        if let (Some(start), Some(end)) = (tuple.intermediate_start(), tuple.intermediate_end()) {
+            println!(
+                "  intermediate: {} to {}",
+                format_tuple(&tags, &start),
+                format_tuple(&tags, &end)
+            );
+        }
+        if let Some(coords) = &coords {
+            match tuple.compute_scalar(coords) {
+                Some(scalar) => {
+                    println!("  scalar: {scalar}");
+                    tuple
+                        .accumulate_deltas(
+                            scalar,
+                            &outline.points,
+                            &outline.contours,
+                            &mut flags,
+                            &mut scratch,
+                            &mut deltas,
+                        )
+                        .map_err(|e| format!("failed to apply tuple {i}: {e}"))?;
+                }
+                None => println!("  scalar: 0 (not applicable at this location)"),
+            }
+        }
+        let tuple_deltas = tuple.deltas().collect::<Vec<_>>();
+        if tuple.all_points() {
+            println!("  points: all");
+        } else {
+            let points = tuple_deltas
+                .iter()
+                .map(|delta| delta.position.to_string())
+                .collect::<Vec<_>>();
+            println!("  points: {}", points.join(", "));
+        }
+        println!("  deltas:");
+        for delta in &tuple_deltas {
+            println!(
+                "    {}: ({}, {})",
+                outline.label(delta.position as usize),
+                delta.x_delta,
+                delta.y_delta
+            );
+        }
+    }
+
+    if coords.is_some() {
+        println!();
+        println!("accumulated deltas:");
+        for (i, delta) in deltas.iter().enumerate() {
+            println!("  {}: ({}, {})", outline.label(i), delta.x, delta.y);
+        }
+    }
+    Ok(())
+}
+
+/// The points that gvar deltas apply to.
+struct Outline {
+    /// The points of a simple glyph, or the offsets of the components of a
+    /// composite glyph, followed by the phantom points.
+    points: Vec<Point<i32>>,
+    /// The end point of each contour; empty for composite glyphs.
+    contours: Vec<u16>,
+    is_composite: bool,
+}
+
+impl Outline {
+    fn new(font: &FontRef, gid: GlyphId) -> Result<Self, String> {
+        let glyf = font.glyf().map_err(|e| e.to_string())?;
+        let loca = font.loca(None).map_err(|e| e.to_string())?;
+        let glyph = loca.get_glyf(gid, &glyf).map_err(|e| e.to_string())?;
+        let (mut points, contours, is_composite) = match glyph {
+            None => (Vec::new(), Vec::new(), false),
+            Some(Glyph::Simple(glyph)) => (
+                glyph
+                    .points()
+                    .map(|point| Point::new(point.x as i32, point.y as i32))
+                    .collect(),
+                glyph
+                    .end_pts_of_contours()
+                    .iter()
+                    .map(|end| end.get())
+                    .collect(),
+                false,
+            ),
+            Some(Glyph::Composite(glyph)) => (
+                glyph
+                    .components()
+                    .map(|component| match component.anchor {
+                        Anchor::Offset { x, y } => Point::new(x as i32, y as i32),
+                        Anchor::Point { .. } => Point::default(),
+                    })
+                    .collect(),
+                Vec::new(),
+                true,
+            ),
+        };
+        // the positions of the phantom points only matter when inferring
+        // deltas, which never happens for them
+        points.extend([Point::default(); PHANTOM_POINTS.len()]);
+        Ok(Outline {
+            points,
+            contours,
+            is_composite,
+        })
+    }
+
+    /// A description of the point at this index.
+    fn label(&self, idx: usize) -> String {
+        let n_outline_points = self.points.len() - PHANTOM_POINTS.len();
+        match PHANTOM_POINTS.get(idx.wrapping_sub(n_outline_points)) {
+            Some(name) => format!("{idx} ({name} phantom point)"),
+            None if self.is_composite => format!("{idx} (component)"),
+            None => idx.to_string(),
+        }
+    }
+}
+
+/// Parse a glyph id, or a glyph name from the post table.
+fn parse_glyph(font: &FontRef, glyph: &str) -> Result<GlyphId, String> {
+    if let Ok(gid) = glyph.parse() {
+        return Ok(GlyphId::new(gid));
+    }
+    let post = font.post().map_err(|e| e.to_string())?;
+    let num_glyphs = font.maxp().map_err(|e| e.to_string())?.num_glyphs();
+    (0..num_glyphs)
+        .map(GlyphId::new)
+        .find(|gid| post.glyph_name(*gid) == Some(glyph))
+        .ok_or_else(|| format!("no glyph named '{glyph}'"))
+}
+
+/// Convert a location like `wght=700,wdth=80` to normalized coordinates,
+/// applying avar.
+fn normalize_location(font: &FontRef, location: &str) -> Result<Vec<F2Dot14>, String> {
+    let fvar = font.fvar().map_err(|e| e.to_string())?;
+    let axes = fvar.axes().map_err(|e| e.to_string())?;
+    let avar_mappings = font.avar().ok().map(|avar| avar.axis_segment_maps());
+    let mut coords = vec![F2Dot14::ZERO; axes.len()];
+    for setting in location.split(',') {
+        let (tag, value) = setting
+            .split_once('=')
+            .ok_or_else(|| format!("expected 'tag=value', found '{setting}'"))?;
+        let tag = Tag::from_str(tag).map_err(|e| e.to_string())?;
+        let value: f64 = value
+            .parse()
+            .map_err(|_| format!("invalid value for '{tag}': '{value}'"))?;
+        let (i, axis) = axes
+            .iter()
+            .enumerate()
+            .find(|(_, axis)| axis.axis_tag() == tag)
+            .ok_or_else(|| format!("no axis '{tag}' in fvar"))?;
+        let mut coord = axis.normalize(Fixed::from_f64(value));
+        if let Some(Ok(mapping)) = avar_mappings.as_ref().and_then(|maps| maps.get(i)) {
+            coord = mapping.apply(coord);
+        }
+        coords[i] = coord.to_f2dot14();
+    }
+    Ok(coords)
+}
+
+fn format_tuple(tags: &[Tag], tuple: &Tuple) -> String {
+    format_coords(tags, (0..tuple.len()).filter_map(|i| tuple.get(i)))
+}
+
+fn format_coords(tags: &[Tag], coords: impl Iterator<Item = F2Dot14>) -> String {
+    tags.iter()
+        .zip(coords)
+        .map(|(tag, coord)| format!("{tag}={coord}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
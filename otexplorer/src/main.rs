@@ -1,18 +1,22 @@
 //! Print the contents of font tables.
 //!
-//! This accepts command line arguments similar to what is present in ttx,
-//! although it does not produce xml output.
+//! This accepts command line arguments similar to what is present in ttx.
+//! By default tables are printed in a custom text format; pass `--xml` to
+//! produce ttx-style xml instead.
 
 use std::{collections::HashSet, str::FromStr};
 
 use font_types::Tag;
 use read_fonts::{traversal::SomeTable, FileRef, FontRef, ReadError, TableProvider, TopLevelTable};
 
+mod gvar;
 mod print;
 mod query;
+mod xml;
 
 use print::PrettyPrinter;
 use query::Query;
+use xml::XmlWriter;
 
 fn main() -> Result<(), Error> {
     let args = flags::Args::from_env().map_err(|e| Error(e.to_string()))?;
@@ -28,11 +32,18 @@ fn main() -> Result<(), Error> {
         return Ok(());
     }
 
+    if let Some(glyph) = &args.gvar {
+        return gvar::print_gvar(&font, glyph, args.location.as_deref()).map_err(Error);
+    }
+
     if let Some(query) = &args.query {
         return query::print_query(&font, query).map_err(Error);
     }
 
     let filter = TableFilter::from_args(&args)?;
+    if args.xml {
+        return write_xml(&font, &filter).map_err(Error::new);
+    }
     print_tables(&font, &filter);
     Ok(())
 }
@@ -75,6 +86,30 @@ fn print_tables(font: &FontRef, filter: &TableFilter) {
     }
 }
 
+fn write_xml(font: &FontRef, filter: &TableFilter) -> std::io::Result<()> {
+    let stdout = std::io::stdout();
+    let mut locked = stdout.lock();
+    let mut writer = XmlWriter::new(font, &mut locked);
+    writer.write_header(font)?;
+    // ttx sorts tables by tag, with 'GlyphOrder' and 'head' first; we have
+    // no GlyphOrder element, but match the rest of the order.
+    let mut tags = font
+        .table_directory
+        .table_records()
+        .iter()
+        .map(|rec| rec.tag())
+        .filter(|tag| filter.should_print(*tag))
+        .collect::<Vec<_>>();
+    tags.sort_by_key(|tag| (*tag != Tag::HEAD, *tag));
+    for tag in tags {
+        match get_some_table(font, tag) {
+            Ok(table) => writer.write_table(tag, &table)?,
+            Err(err) => writer.write_error(tag, err)?,
+        }
+    }
+    writer.write_footer()
+}
+
 fn get_offset_width(font: &FontRef) -> usize {
     // pick how much padding we use for offsets based on the max offset in directory
     let max_off = font
@@ -121,6 +156,11 @@ fn get_some_table<'a>(
         tables::stat::Stat::TAG => font.stat().map(|x| Box::new(x) as _),
         tables::vhea::Vhea::TAG => font.vhea().map(|x| Box::new(x) as _),
         tables::vmtx::Vmtx::TAG => font.vmtx().map(|x| Box::new(x) as _),
+        tables::pclt::Pclt::TAG => font.pclt().map(|x| Box::new(x) as _),
+        tables::ltsh::Ltsh::TAG => font.ltsh().map(|x| Box::new(x) as _),
+        tables::lcar::Lcar::TAG => font.lcar().map(|x| Box::new(x) as _),
+        tables::opbd::Opbd::TAG => font.opbd().map(|x| Box::new(x) as _),
+        tables::math::Math::TAG => font.math().map(|x| Box::new(x) as _),
         _ => Err(ReadError::TableIsMissing(tag)),
     }
 }
@@ -211,6 +251,13 @@ mod flags {
                 optional -q, --query query: Query
                 optional -t, --tables include: String
                 optional -x, --exclude exclude: String
+                /// Write tables as ttx-style xml
+                optional --xml
+                /// Print the gvar tuples of a glyph, given by id or name
+                optional --gvar glyph: String
+                /// With --gvar, a location such as 'wght=700,wdth=80' at which
+                /// to compute scalars and deltas
+                optional --location location: String
             }
 
     }
@@ -166,6 +166,7 @@ impl<'a> PrettyPrinter<'a> {
             FieldType::U16(val) => write!(self, "{val}")?,
             FieldType::I32(val) => write!(self, "{val}")?,
             FieldType::U32(val) => write!(self, "{val}")?,
+            FieldType::I24(val) => write!(self, "{val}")?,
             FieldType::U24(val) => write!(self, "{val}")?,
             FieldType::Tag(val) => write!(self, "{val}")?,
             FieldType::FWord(val) => write!(self, "{val}")?,
@@ -178,6 +179,10 @@ impl<'a> PrettyPrinter<'a> {
             FieldType::GlyphId(val) => self.print_with_style(Color::Yellow.into(), |this| {
                 write!(this, "{}", val.to_u16())
             })?,
+            FieldType::GlyphId24(val) => self.print_with_style(Color::Yellow.into(), |this| {
+                write!(this, "{}", val.to_u32())
+            })?,
+            FieldType::NameId(val) => write!(self, "{val}")?,
             FieldType::ResolvedOffset(ResolvedOffset { offset, target }) => {
                 match target {
                     Ok(table) => {
@@ -237,6 +242,7 @@ impl<'a> PrettyPrinter<'a> {
             FieldType::U16(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::I32(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::U32(val) => self.print_hex(&val.to_be_bytes())?,
+            FieldType::I24(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::U24(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::Tag(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::FWord(val) => self.print_hex(&val.to_be_bytes())?,
@@ -247,6 +253,8 @@ impl<'a> PrettyPrinter<'a> {
             FieldType::Fixed(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::LongDateTime(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::GlyphId(val) => self.print_hex(&val.to_be_bytes())?,
+            FieldType::GlyphId24(val) => self.print_hex(&val.to_be_bytes())?,
+            FieldType::NameId(val) => self.print_hex(&val.to_be_bytes())?,
             FieldType::BareOffset(offset) => self.print_offset_hex(*offset)?,
             _ => (),
         }
@@ -345,6 +353,7 @@ fn is_scalar(field_type: &FieldType) -> bool {
             | FieldType::U16(_)
             | FieldType::I32(_)
             | FieldType::U32(_)
+            | FieldType::I24(_)
             | FieldType::U24(_)
             | FieldType::Tag(_)
             | FieldType::FWord(_)
@@ -355,5 +364,7 @@ fn is_scalar(field_type: &FieldType) -> bool {
             | FieldType::Fixed(_)
             | FieldType::LongDateTime(_)
             | FieldType::GlyphId(_)
+            | FieldType::GlyphId24(_)
+            | FieldType::NameId(_)
     )
 }
@@ -0,0 +1,399 @@
+//! ttx-style xml output.
+//!
+//! The output follows the conventions used by fonttools' ttx, so that it can
+//! be diffed against a ttx dump of the same font:
+//!
+//! - each table is an element named after its tag (`OS/2` becomes `OS_2`)
+//! - scalar fields are written as `<name value="..."/>`
+//! - array items are repeated elements with an `index` attribute
+//! - offsets are followed, and their targets written inline; null offsets
+//!   are omitted
+//! - glyph ids are written as glyph names
+//!
+//! Tables that ttx dumps from its generic table descriptions (such as
+//! GSUB, GPOS and GDEF) use PascalCase element names, with counts written as
+//! comments; other tables use camelCase names. Tables that ttx writes with
+//! custom code (such as glyf, cmap and name) will not match exactly.
+
+use std::io::Write;
+
+use font_types::{GlyphId, LongDateTime, Tag, TT_SFNT_VERSION};
+use read_fonts::{
+    tables::post::Post,
+    traversal::{FieldType, SomeArray, SomeString, SomeTable},
+    FontRef, TableProvider,
+};
+
+const INDENT: &str = "  ";
+
+/// Tables that ttx dumps using its generic table descriptions.
+const PASCAL_CASE_TABLES: &[Tag] = &[
+    Tag::BASE,
+    Tag::COLR,
+    Tag::GDEF,
+    Tag::GPOS,
+    Tag::GSUB,
+    Tag::JSTF,
+    Tag::MATH,
+    Tag::STAT,
+];
+
+pub struct XmlWriter<'a, 'b> {
+    depth: usize,
+    pascal_case: bool,
+    glyph_names: GlyphNames<'b>,
+    writer: &'a mut (dyn Write + 'a),
+}
+
+impl<'a, 'b> XmlWriter<'a, 'b> {
+    pub fn new(font: &FontRef<'b>, writer: &'a mut (dyn Write + 'a)) -> Self {
+        XmlWriter {
+            depth: 0,
+            pascal_case: false,
+            glyph_names: GlyphNames(font.post().ok()),
+            writer,
+        }
+    }
+
+    pub fn write_header(&mut self, font: &FontRef) -> std::io::Result<()> {
+        let sfnt_version = match font.table_directory.sfnt_version() {
+            TT_SFNT_VERSION => "\\x00\\x01\\x00\\x00".to_string(),
+            other => escape(&String::from_utf8_lossy(&other.to_be_bytes())),
+        };
+        writeln!(self.writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(self.writer, r#"<ttFont sfntVersion="{sfnt_version}">"#)?;
+        self.depth = 1;
+        Ok(())
+    }
+
+    pub fn write_footer(&mut self) -> std::io::Result<()> {
+        self.depth = 0;
+        writeln!(self.writer)?;
+        writeln!(self.writer, "</ttFont>")
+    }
+
+    pub fn write_table<'c>(
+        &mut self,
+        tag: Tag,
+        table: &(dyn SomeTable<'c> + 'c),
+    ) -> std::io::Result<()> {
+        self.pascal_case = PASCAL_CASE_TABLES.contains(&tag);
+        let name = tag_to_xml(tag);
+        writeln!(self.writer)?;
+        self.open(&name, None, table)
+    }
+
+    pub fn write_error(&mut self, tag: Tag, error: impl std::fmt::Display) -> std::io::Result<()> {
+        writeln!(self.writer)?;
+        self.write_indent()?;
+        writeln!(
+            self.writer,
+            "<!-- {}: {} -->",
+            tag_to_xml(tag),
+            escape_comment(&error.to_string())
+        )
+    }
+
+    fn write_indent(&mut self) -> std::io::Result<()> {
+        for _ in 0..self.depth {
+            self.writer.write_all(INDENT.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Writes a table or record as an element containing its fields.
+    fn open<'c>(
+        &mut self,
+        name: &str,
+        index: Option<usize>,
+        table: &(dyn SomeTable<'c> + 'c),
+    ) -> std::io::Result<()> {
+        self.write_indent()?;
+        write!(self.writer, "<{name}")?;
+        if let Some(index) = index {
+            write!(self.writer, r#" index="{index}""#)?;
+        }
+        // ttx writes the format of a subtable as an attribute
+        let mut fields = table.iter().peekable();
+        if let Some(field) = fields.next_if(|field| field.name == "format") {
+            if let Some(value) = self.scalar_value(&field.value) {
+                write!(self.writer, r#" Format="{value}""#)?;
+            }
+        }
+        writeln!(self.writer, ">")?;
+        self.depth += 1;
+        for field in fields {
+            let name = self.field_name(field.name);
+            if self.pascal_case && is_count(field.name) {
+                if let Some(value) = self.scalar_value(&field.value) {
+                    self.write_indent()?;
+                    writeln!(self.writer, "<!-- {name}={value} -->")?;
+                    continue;
+                }
+            }
+            self.write_field(&name, None, &field.value)?;
+        }
+        self.depth -= 1;
+        self.write_indent()?;
+        writeln!(self.writer, "</{name}>")
+    }
+
+    fn write_field(
+        &mut self,
+        name: &str,
+        index: Option<usize>,
+        field: &FieldType,
+    ) -> std::io::Result<()> {
+        let index_attr = index
+            .map(|idx| format!(r#" index="{idx}""#))
+            .unwrap_or_default();
+        if let Some(value) = self.scalar_value(field) {
+            self.write_indent()?;
+            return writeln!(
+                self.writer,
+                r#"<{name}{index_attr} value="{}"/>"#,
+                escape(&value)
+            );
+        }
+        match field {
+            FieldType::ResolvedOffset(offset) => match &offset.target {
+                Ok(table) => self.open(name, index, table),
+                Err(e) => self.write_comment(name, e),
+            },
+            FieldType::StringOffset(offset) => match &offset.target {
+                Ok(string) => self.write_string(name, index, string.as_ref()),
+                Err(e) => self.write_comment(name, e),
+            },
+            FieldType::ArrayOffset(offset) => match &offset.target {
+                Ok(array) => self.write_array(name, array.as_ref()),
+                Err(e) => self.write_comment(name, e),
+            },
+            FieldType::Record(record) => self.open(name, index, record),
+            FieldType::Array(array) => self.write_array(name, array.as_ref()),
+            // null offsets are omitted
+            FieldType::BareOffset(offset) if offset.to_u32() == 0 => Ok(()),
+            FieldType::BareOffset(offset) => {
+                self.write_indent()?;
+                writeln!(
+                    self.writer,
+                    "<!-- {name}: unresolved offset 0x{:04X} -->",
+                    offset.to_u32()
+                )
+            }
+            _ => self.write_comment(name, "unknown"),
+        }
+    }
+
+    fn write_array<'c>(
+        &mut self,
+        name: &str,
+        array: &(dyn SomeArray<'c> + 'c),
+    ) -> std::io::Result<()> {
+        let item_name = singular(name);
+        for (i, item) in array.iter().enumerate() {
+            self.write_field(&item_name, Some(i), &item)?;
+        }
+        Ok(())
+    }
+
+    fn write_string(
+        &mut self,
+        name: &str,
+        index: Option<usize>,
+        string: &dyn SomeString,
+    ) -> std::io::Result<()> {
+        let text: String = string.iter_chars().collect();
+        self.write_indent()?;
+        write!(self.writer, "<{name}")?;
+        if let Some(index) = index {
+            write!(self.writer, r#" index="{index}""#)?;
+        }
+        writeln!(self.writer, ">{}</{name}>", escape(&text))
+    }
+
+    fn write_comment(&mut self, name: &str, error: impl std::fmt::Display) -> std::io::Result<()> {
+        self.write_indent()?;
+        writeln!(
+            self.writer,
+            "<!-- {name}: {} -->",
+            escape_comment(&error.to_string())
+        )
+    }
+
+    /// Returns the ttx representation of a scalar, or `None` if the field is
+    /// not a scalar.
+    fn scalar_value(&self, field: &FieldType) -> Option<String> {
+        Some(match field {
+            FieldType::I8(val) => val.to_string(),
+            FieldType::U8(val) => val.to_string(),
+            FieldType::I16(val) => val.to_string(),
+            FieldType::U16(val) => val.to_string(),
+            FieldType::I32(val) => val.to_string(),
+            FieldType::U32(val) => val.to_string(),
+            FieldType::I24(val) => val.to_string(),
+            FieldType::U24(val) => val.to_string(),
+            FieldType::Tag(val) => val.to_string(),
+            FieldType::FWord(val) => val.to_i16().to_string(),
+            FieldType::UfWord(val) => val.to_u16().to_string(),
+            FieldType::MajorMinor(val) => {
+                format!("0x{:08X}", u32::from_be_bytes(val.to_be_bytes()))
+            }
+            FieldType::Version16Dot16(val) => {
+                format!("0x{:08X}", u32::from_be_bytes(val.to_be_bytes()))
+            }
+            FieldType::F2Dot14(val) => fixed_repr(val.to_f32() as f64, 14),
+            FieldType::Fixed(val) => fixed_repr(val.to_f64(), 16),
+            FieldType::LongDateTime(val) => timestamp_to_string(*val),
+            FieldType::GlyphId(val) => self.glyph_names.get(*val),
+            FieldType::GlyphId24(val) => match val.to_glyph_id() {
+                Some(gid) => self.glyph_names.get(gid),
+                None => format!("glyph{:05}", val.to_u32()),
+            },
+            FieldType::NameId(val) => val.to_string(),
+            _ => return None,
+        })
+    }
+
+    /// Converts a field name from the generated snake_case to the ttx style.
+    fn field_name(&self, name: &str) -> String {
+        let name = name
+            .strip_suffix("_offsets")
+            .or_else(|| name.strip_suffix("_offset"))
+            .unwrap_or(name);
+        let mut result = String::with_capacity(name.len());
+        let mut upper = self.pascal_case;
+        for c in name.chars() {
+            if c == '_' {
+                upper = true;
+            } else if upper {
+                result.extend(c.to_uppercase());
+                upper = false;
+            } else {
+                result.push(c);
+            }
+        }
+        result
+    }
+}
+
+/// Glyph names, as written by ttx.
+struct GlyphNames<'a>(Option<Post<'a>>);
+
+impl GlyphNames<'_> {
+    fn get(&self, gid: GlyphId) -> String {
+        match self.0.as_ref().and_then(|post| post.glyph_name(gid)) {
+            Some(name) => name.to_string(),
+            None if gid == GlyphId::NOTDEF => ".notdef".to_string(),
+            None => format!("glyph{:05}", gid.to_u16()),
+        }
+    }
+}
+
+/// Converts a tag to an xml element name, as ttx does.
+fn tag_to_xml(tag: Tag) -> String {
+    if tag == Tag::OS2 {
+        return "OS_2".to_string();
+    }
+    let raw = tag.to_be_bytes();
+    let trimmed = tag.to_string();
+    let trimmed = trimmed.trim_end_matches(' ');
+    let is_identifier = raw[0].is_ascii_alphabetic() || raw[0] == b'_';
+    if is_identifier
+        && trimmed
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_')
+    {
+        return trimmed.to_string();
+    }
+    // escape anything that isn't valid in an identifier
+    raw.iter()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' => (*b as char).to_string(),
+            _ => format!("_{b:02x}"),
+        })
+        .collect()
+}
+
+/// Returns the singular name for items in an array field.
+fn singular(name: &str) -> String {
+    if let Some(stem) = name.strip_suffix("xes") {
+        return format!("{stem}xis");
+    }
+    if let Some(stem) = name.strip_suffix("ies") {
+        return format!("{stem}y");
+    }
+    name.strip_suffix('s').unwrap_or(name).to_string()
+}
+
+/// Returns `true` if a field holds the length of some array.
+fn is_count(name: &str) -> bool {
+    name.ends_with("_count") || name.starts_with("num_")
+}
+
+/// Formats a fixed point value like ttx, using the shortest decimal that
+/// converts back to the same value.
+fn fixed_repr(val: f64, fract_bits: i32) -> String {
+    let scale = 2f64.powi(fract_bits);
+    let bits = (val * scale).round();
+    let shortest = (1..=fract_bits as usize)
+        .map(|precision| format!("{val:.precision$}"))
+        .find(|repr| repr.parse::<f64>().map(|v| (v * scale).round()) == Ok(bits))
+        .unwrap_or_else(|| val.to_string());
+    // match python's repr, which always has at least one decimal
+    let trimmed = shortest.trim_end_matches('0');
+    match trimmed.strip_suffix('.') {
+        Some(int) => format!("{int}.0"),
+        None => trimmed.to_string(),
+    }
+}
+
+/// Formats a timestamp like ttx, which uses the format of `asctime`.
+fn timestamp_to_string(date: LongDateTime) -> String {
+    const DAYS_1904_TO_1970: i64 = 24107;
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let secs = date.as_secs();
+    let days = secs.div_euclid(86400) - DAYS_1904_TO_1970;
+    let time = secs.rem_euclid(86400);
+    let weekday = WEEKDAYS[days.rem_euclid(7) as usize];
+    // civil_from_days, from http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!(
+        "{weekday} {} {day:>2} {:02}:{:02}:{:02} {year}",
+        MONTHS[month as usize - 1],
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            c if c.is_control() && c != '\n' && c != '\t' => {
+                result.push_str(&format!("&#{};", c as u32))
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+fn escape_comment(text: &str) -> String {
+    text.replace("--", "- -")
+}
@@ -0,0 +1,163 @@
+//! Compares outline extraction against FreeType over a corpus of fonts.
+//!
+//! Each iteration parses the font and extracts the unscaled, unhinted outline
+//! of every glyph, so the reported throughput is in glyphs per second. Both
+//! implementations feed their outlines to the same pen, so the cost of
+//! consuming the path is identical.
+//!
+//! By default the corpus is the TrueType test fonts in this repository; set
+//! `PUNCHCUT_BENCH_CORPUS` to a directory to benchmark every `.ttf` and `.otf`
+//! file below it instead. Fonts without `glyf` outlines are skipped.
+//!
+//! Run with `cargo bench -p punchcut --bench outlines`. FreeType is found
+//! with pkg-config, or built from source if it is not installed.
+
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use freetype::{face::LoadFlag, outline::Curve, Library};
+use punchcut::{font::FontRef, Context, GlyphId, Pen};
+
+/// The environment variable naming the corpus directory.
+const CORPUS_VAR: &str = "PUNCHCUT_BENCH_CORPUS";
+
+/// Pen that sums the coordinates it receives, so that no work is optimized
+/// away.
+#[derive(Default)]
+struct SumPen(f32);
+
+impl Pen for SumPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0 += x + y;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0 += x + y;
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.0 += cx0 + cy0 + x + y;
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.0 += cx0 + cy0 + cx1 + cy1 + x + y;
+    }
+
+    fn close(&mut self) {}
+}
+
+/// Parses the font and extracts every outline with punchcut.
+fn punchcut_outlines(context: &mut Context, data: &[u8], pen: &mut SumPen) {
+    let font = FontRef::new(data).unwrap();
+    let mut scaler = context.new_scaler().build(&font);
+    for gid in 0..scaler.glyph_count() {
+        // glyphs that fail to load are skipped, as with FreeType
+        let _ = scaler.outline(GlyphId::new(gid), pen);
+    }
+}
+
+/// Parses the font and extracts every outline with FreeType.
+fn freetype_outlines(library: &Library, data: &Rc<Vec<u8>>, pen: &mut SumPen) {
+    let face = library.new_memory_face(data.clone(), 0).unwrap();
+    let flags = LoadFlag::NO_SCALE | LoadFlag::NO_HINTING;
+    for gid in 0..face.raw().num_glyphs as u32 {
+        // the contour iterator of freetype-rs doesn't handle empty outlines
+        if face.load_glyph(gid, flags).is_err() || face.glyph().raw().outline.n_contours == 0 {
+            continue;
+        }
+        let Some(outline) = face.glyph().outline() else {
+            continue;
+        };
+        for contour in outline.contours_iter() {
+            let start = contour.start();
+            pen.move_to(start.x as f32, start.y as f32);
+            for curve in contour {
+                match curve {
+                    Curve::Line(p) => pen.line_to(p.x as f32, p.y as f32),
+                    Curve::Bezier2(c, p) => {
+                        pen.quad_to(c.x as f32, c.y as f32, p.x as f32, p.y as f32)
+                    }
+                    Curve::Bezier3(c0, c1, p) => pen.curve_to(
+                        c0.x as f32,
+                        c0.y as f32,
+                        c1.x as f32,
+                        c1.y as f32,
+                        p.x as f32,
+                        p.y as f32,
+                    ),
+                }
+            }
+            pen.close();
+        }
+    }
+}
+
+fn outlines(c: &mut Criterion) {
+    let library = Library::init().unwrap();
+    let mut context = Context::new();
+    for path in corpus() {
+        let data = Rc::new(std::fs::read(&path).unwrap());
+        let Ok(font) = FontRef::new(&data) else {
+            continue;
+        };
+        let scaler = context.new_scaler().build(&font);
+        if !scaler.has_outlines() {
+            continue;
+        }
+        let num_glyphs = scaler.glyph_count();
+        let name = path.file_name().unwrap().to_string_lossy();
+        let mut group = c.benchmark_group(name);
+        group.throughput(Throughput::Elements(num_glyphs as u64));
+        group.bench_function("punchcut", |b| {
+            b.iter(|| {
+                let mut pen = SumPen::default();
+                punchcut_outlines(&mut context, black_box(&data), &mut pen);
+                pen.0
+            })
+        });
+        group.bench_function("freetype", |b| {
+            b.iter(|| {
+                let mut pen = SumPen::default();
+                freetype_outlines(&library, black_box(&data), &mut pen);
+                pen.0
+            })
+        });
+        group.finish();
+    }
+}
+
+/// Returns the paths of the fonts in the corpus, in a stable order.
+fn corpus() -> Vec<PathBuf> {
+    let dir = match std::env::var_os(CORPUS_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("../resources/test_fonts/ttf"),
+    };
+    let mut paths = Vec::new();
+    collect_fonts(&dir, &mut paths);
+    if paths.is_empty() {
+        panic!("no fonts found in {}", dir.display());
+    }
+    paths.sort();
+    paths
+}
+
+fn collect_fonts(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let entries =
+        std::fs::read_dir(dir).unwrap_or_else(|e| panic!("failed to read {}: {e}", dir.display()));
+    for path in entries.map(|entry| entry.unwrap().path()) {
+        if path.is_dir() {
+            collect_fonts(&path, paths);
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf"))
+        {
+            paths.push(path);
+        }
+    }
+}
+
+criterion_group!(benches, outlines);
+criterion_main!(benches);
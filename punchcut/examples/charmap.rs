@@ -0,0 +1,179 @@
+//! Query the character map of a font.
+//!
+//! Without options, this prints the number of mapped characters and the
+//! scripts they belong to.
+//!
+//! ```sh
+//! # which glyphs do these characters map to?
+//! cargo run -p punchcut --example charmap -- font.ttf --map U+1F600 --map a
+//! # list every mapping
+//! cargo run -p punchcut --example charmap -- font.ttf --list
+//! # which characters of a text are missing?
+//! cargo run -p punchcut --example charmap -- font.ttf --coverage sample.txt
+//! ```
+//!
+//! With `--coverage`, the exit status is nonzero if any characters of the
+//! text are missing.
+
+use std::collections::BTreeMap;
+
+use punchcut::{
+    font::{FileRef, FontRef, TableProvider},
+    Charmap, GlyphId,
+};
+use unicode_script::UnicodeScript;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn main() -> Result<()> {
+    let args = flags::Args::from_env()?;
+    let data = std::fs::read(&args.font)?;
+    let font = FileRef::new(&data)?
+        .fonts()
+        .nth(args.index.unwrap_or(0) as usize)
+        .ok_or("font index out of range")??;
+    let charmap = Charmap::new(&font);
+    if !charmap.has_map() {
+        return Err("font has no usable cmap subtable".into());
+    }
+
+    for ch in &args.map {
+        let codepoint = parse_codepoint(ch)?;
+        match charmap.map(codepoint) {
+            Some(gid) => println!(
+                "{} -> {}",
+                describe(codepoint),
+                glyph_description(&font, gid)
+            ),
+            None => println!("{} -> not mapped", describe(codepoint)),
+        }
+    }
+    if args.list {
+        for (codepoint, gid) in charmap.mappings() {
+            println!(
+                "{} -> {}",
+                describe(codepoint),
+                glyph_description(&font, gid)
+            );
+        }
+    }
+    if args.scripts || (args.map.is_empty() && !args.list && args.coverage.is_none()) {
+        print_summary(&charmap);
+    }
+    if let Some(path) = &args.coverage {
+        let text = std::fs::read_to_string(path)?;
+        if !print_coverage(&charmap, &text) {
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+/// Prints the number of mappings, and the number in each script.
+fn print_summary(charmap: &Charmap) {
+    let mut scripts = BTreeMap::<&str, usize>::new();
+    let mut total = 0;
+    for (codepoint, _) in charmap.mappings() {
+        total += 1;
+        if let Some(ch) = char::from_u32(codepoint) {
+            *scripts.entry(ch.script().full_name()).or_default() += 1;
+        }
+    }
+    let kind = if charmap.is_symbol() {
+        "symbol"
+    } else {
+        "unicode"
+    };
+    println!("{total} characters mapped ({kind} cmap)");
+    for (script, count) in scripts {
+        println!("  {script}: {count}");
+    }
+}
+
+/// Prints the characters of the text that are missing from the font.
+///
+/// Returns `true` if every character is mapped. Control characters, such
+/// as line breaks, are ignored.
+fn print_coverage(charmap: &Charmap, text: &str) -> bool {
+    let mut occurrences = BTreeMap::<char, usize>::new();
+    for ch in text.chars().filter(|ch| !ch.is_control()) {
+        *occurrences.entry(ch).or_default() += 1;
+    }
+    let missing = occurrences
+        .iter()
+        .filter(|(ch, _)| charmap.map(**ch).is_none())
+        .collect::<Vec<_>>();
+    let total = occurrences.len();
+    let covered = total - missing.len();
+    let percent = if total == 0 {
+        100.0
+    } else {
+        covered as f64 * 100.0 / total as f64
+    };
+    println!("{covered} of {total} distinct characters covered ({percent:.1}%)");
+    for (ch, count) in &missing {
+        println!(
+            "  missing {} ({}, count {count})",
+            describe(**ch as u32),
+            ch.script().full_name()
+        );
+    }
+    missing.is_empty()
+}
+
+/// Parses a single character or a `U+` codepoint.
+fn parse_codepoint(input: &str) -> Result<u32> {
+    if let Some(hex) = input
+        .strip_prefix("U+")
+        .or_else(|| input.strip_prefix("u+"))
+    {
+        return Ok(u32::from_str_radix(hex, 16)?);
+    }
+    let mut chars = input.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Ok(ch as u32),
+        _ => Err(format!("expected a character or U+XXXX, found '{input}'").into()),
+    }
+}
+
+/// Formats a codepoint, with the character itself if it is printable.
+fn describe(codepoint: u32) -> String {
+    match char::from_u32(codepoint).filter(|ch| !ch.is_control() && !ch.is_whitespace()) {
+        Some(ch) => format!("U+{codepoint:04X} {ch}"),
+        None => format!("U+{codepoint:04X}"),
+    }
+}
+
+/// Formats a glyph identifier, with its name if the font has one.
+fn glyph_description(font: &FontRef, gid: GlyphId) -> String {
+    let name = font
+        .post()
+        .ok()
+        .and_then(|post| post.glyph_name(gid).map(String::from));
+    match name {
+        Some(name) => format!("glyph {} ({name})", gid.to_u16()),
+        None => format!("glyph {}", gid.to_u16()),
+    }
+}
+
+mod flags {
+    use std::path::PathBuf;
+
+    xflags::xflags! {
+        /// Query the character map of a font
+        cmd args
+            required font: PathBuf
+            {
+                /// The index of the font, in font collections
+                optional -i, --index index: u32
+                /// A character or codepoint, such as 'a' or 'U+1F600', to map
+                repeated -m, --map char: String
+                /// List every mapped character
+                optional -l, --list
+                /// Print the number of mapped characters in each script
+                optional -s, --scripts
+                /// A UTF-8 text file to check for missing characters
+                optional -c, --coverage coverage: PathBuf
+            }
+    }
+}
@@ -0,0 +1,197 @@
+//! Render a glyph to an SVG path or a PNG image.
+//!
+//! The glyph is a single character, a codepoint such as `U+0041`, or a glyph
+//! identifier such as `gid:42`. The output format is chosen by the extension
+//! of the output path; without an output path, SVG is written to stdout.
+//!
+//! ```sh
+//! cargo run -p punchcut --example render -- font.ttf A --size 64 --output a.png
+//! cargo run -p punchcut --example render -- font.ttf gid:3 --variations wght=700
+//! ```
+//!
+//! The output only depends on the font, glyph and options, so it can be
+//! compared against a reference for visual regression testing.
+
+use std::{fs::File, io::BufWriter, path::Path, str::FromStr};
+
+use punchcut::{
+    font::{FileRef, FontRef, TableProvider, Tag},
+    Context, GlyphId, Mask, Pen, SvgPathPen, Variation,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const DEFAULT_SIZE: f32 = 64.0;
+
+fn main() -> Result<()> {
+    let args = flags::Args::from_env()?;
+    let data = std::fs::read(&args.font)?;
+    let font = FileRef::new(&data)?
+        .fonts()
+        .nth(args.index.unwrap_or(0) as usize)
+        .ok_or("font index out of range")??;
+    let glyph_id = parse_glyph(&font, &args.glyph)?;
+    let variations = match &args.variations {
+        Some(variations) => parse_variations(variations)?,
+        None => Vec::new(),
+    };
+
+    let mut context = Context::new();
+    let mut scaler = context
+        .new_scaler()
+        .size(args.size.unwrap_or(DEFAULT_SIZE))
+        .variations(variations)
+        .build(&font);
+
+    let output = args.output.as_deref();
+    let is_png = output
+        .and_then(Path::extension)
+        .is_some_and(|ext| ext == "png");
+    if is_png {
+        let mut mask = Mask::new();
+        scaler.render(glyph_id, &mut mask)?;
+        write_png(&mask, output.unwrap())
+    } else {
+        let mut pen = SvgPathPen::new().flip_y(true);
+        let mut bounds = BoundsPen::default();
+        scaler.outline(glyph_id, &mut pen)?;
+        scaler.outline(glyph_id, &mut bounds)?;
+        let metrics = scaler.metrics();
+        // the view box covers the line and the outline
+        let [x_min, y_min, x_max, y_max] = bounds.0.unwrap_or_default();
+        let left = x_min.min(0.0).floor();
+        let right = x_max.max(0.0).ceil();
+        let top = y_max.max(metrics.ascent).ceil();
+        let bottom = y_min.min(metrics.descent).floor();
+        let svg = format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+                "\n",
+                r#"  <path d="{}"/>"#,
+                "\n</svg>\n"
+            ),
+            left,
+            -top,
+            right - left,
+            top - bottom,
+            pen.as_str()
+        );
+        match output {
+            Some(path) => std::fs::write(path, svg)?,
+            None => print!("{svg}"),
+        }
+        Ok(())
+    }
+}
+
+/// Resolves a character, `U+` codepoint or `gid:` glyph identifier.
+fn parse_glyph(font: &FontRef, glyph: &str) -> Result<GlyphId> {
+    if let Some(gid) = glyph.strip_prefix("gid:") {
+        return Ok(GlyphId::new(gid.parse()?));
+    }
+    let codepoint = match glyph.strip_prefix("U+") {
+        Some(hex) => u32::from_str_radix(hex, 16)?,
+        None => {
+            let mut chars = glyph.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c as u32,
+                _ => return Err(format!("expected a single character, found '{glyph}'").into()),
+            }
+        }
+    };
+    font.cmap()?
+        .map_codepoint(codepoint)
+        .ok_or_else(|| format!("no glyph for U+{codepoint:04X}").into())
+}
+
+/// Parses variations like `wght=700,wdth=80`.
+fn parse_variations(variations: &str) -> Result<Vec<Variation>> {
+    variations
+        .split(',')
+        .map(|variation| {
+            let (tag, value) = variation
+                .split_once('=')
+                .ok_or_else(|| format!("expected 'tag=value', found '{variation}'"))?;
+            Ok(Variation {
+                tag: Tag::from_str(tag)?,
+                value: value.parse()?,
+            })
+        })
+        .collect()
+}
+
+/// Writes an antialiased mask as a black on white grayscale image.
+fn write_png(mask: &Mask, path: &Path) -> Result<()> {
+    if mask.width == 0 || mask.height == 0 {
+        return Err("glyph has no outline".into());
+    }
+    let writer = BufWriter::new(File::create(path)?);
+    let mut encoder = png::Encoder::new(writer, mask.width, mask.height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let pixels = mask
+        .data
+        .iter()
+        .map(|coverage| 255 - coverage)
+        .collect::<Vec<_>>();
+    encoder.write_header()?.write_image_data(&pixels)?;
+    Ok(())
+}
+
+/// Pen that computes the bounds of the control points of an outline.
+#[derive(Default)]
+struct BoundsPen(Option<[f32; 4]>);
+
+impl BoundsPen {
+    fn add(&mut self, x: f32, y: f32) {
+        let bounds = self.0.get_or_insert([x, y, x, y]);
+        bounds[0] = bounds[0].min(x);
+        bounds[1] = bounds[1].min(y);
+        bounds[2] = bounds[2].max(x);
+        bounds[3] = bounds[3].max(y);
+    }
+}
+
+impl Pen for BoundsPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.add(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.add(x, y);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.add(cx0, cy0);
+        self.add(x, y);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.add(cx0, cy0);
+        self.add(cx1, cy1);
+        self.add(x, y);
+    }
+
+    fn close(&mut self) {}
+}
+
+mod flags {
+    use std::path::PathBuf;
+
+    xflags::xflags! {
+        /// Render a glyph to SVG or PNG
+        cmd args
+            required font: PathBuf
+            required glyph: String
+            {
+                /// The index of the font, in font collections
+                optional -i, --index index: u32
+                /// The size in pixels per em (default 64)
+                optional -s, --size size: f32
+                /// Variation settings, such as 'wght=700,wdth=80'
+                optional -v, --variations variations: String
+                /// The output path; '.png' files are rasterized
+                optional -o, --output output: PathBuf
+            }
+    }
+}
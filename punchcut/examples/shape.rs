@@ -0,0 +1,76 @@
+//! Shape a line of text with the simple shaper.
+//!
+//! Prints each glyph with its cluster, advance and offset, in font units,
+//! followed by the total advance.
+//!
+//! ```sh
+//! cargo run -p punchcut --features shaping --example shape -- font.ttf "fiord"
+//! cargo run -p punchcut --features shaping --example shape -- font.ttf "fil" --language TRK
+//! ```
+
+use std::str::FromStr;
+
+use punchcut::{
+    font::{FileRef, FontRef, TableProvider, Tag},
+    GlyphId, Shaper,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn main() -> Result<()> {
+    let args = flags::Args::from_env()?;
+    let data = std::fs::read(&args.font)?;
+    let font = FileRef::new(&data)?
+        .fonts()
+        .nth(args.index.unwrap_or(0) as usize)
+        .ok_or("font index out of range")??;
+    let script = Tag::from_str(args.script.as_deref().unwrap_or("latn"))?;
+    let language = args.language.as_deref().map(Tag::from_str).transpose()?;
+
+    let glyphs = Shaper::new(&font, script, language).shape(&args.text);
+    let mut total = 0;
+    for glyph in &glyphs {
+        println!(
+            "{} cluster {} advance {} offset ({}, {})",
+            glyph_description(&font, glyph.glyph_id),
+            glyph.cluster,
+            glyph.x_advance,
+            glyph.x_offset,
+            glyph.y_offset
+        );
+        total += glyph.x_advance;
+    }
+    println!("{} glyphs, total advance {total}", glyphs.len());
+    Ok(())
+}
+
+/// Formats a glyph identifier, with its name if the font has one.
+fn glyph_description(font: &FontRef, gid: GlyphId) -> String {
+    let name = font
+        .post()
+        .ok()
+        .and_then(|post| post.glyph_name(gid).map(String::from));
+    match name {
+        Some(name) => format!("glyph {} ({name})", gid.to_u16()),
+        None => format!("glyph {}", gid.to_u16()),
+    }
+}
+
+mod flags {
+    use std::path::PathBuf;
+
+    xflags::xflags! {
+        /// Shape a line of text with the simple shaper
+        cmd args
+            required font: PathBuf
+            required text: String
+            {
+                /// The index of the font, in font collections
+                optional -i, --index index: u32
+                /// The OpenType script tag, 'latn' by default
+                optional -s, --script script: String
+                /// The OpenType language tag, such as 'TRK '
+                optional -l, --language language: String
+            }
+    }
+}
@@ -0,0 +1,306 @@
+//! Packing of rendered glyph masks into textures.
+//!
+//! Masks are placed on square pages with power of two sizes using the
+//! skyline bottom-left heuristic. A new page is started when a mask does
+//! not fit on any existing page, so placements never move and their
+//! texture coordinates remain valid.
+
+use super::{Mask, MaskFormat};
+
+/// Placement of a mask in an atlas.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct Placement {
+    /// Index of the page containing the mask.
+    pub page: usize,
+    /// Column of the left edge of the mask on the page, in pixels.
+    pub x: u32,
+    /// Row of the top edge of the mask on the page, in pixels.
+    pub y: u32,
+    /// Width of the mask in pixels.
+    pub width: u32,
+    /// Height of the mask in pixels.
+    pub height: u32,
+    /// Distance from the glyph origin to the left edge of the mask.
+    pub left: i32,
+    /// Distance from the baseline to the top edge of the mask.
+    pub top: i32,
+    /// Texture coordinates of the mask as `[u0, v0, u1, v1]`, where
+    /// (u0, v0) is the top left corner and v increases downward.
+    pub uv: [f32; 4],
+}
+
+/// Single texture of an atlas.
+#[derive(Clone, Debug)]
+pub struct AtlasPage {
+    size: u32,
+    data: Vec<u8>,
+    /// Top edges of the packed area, as (x, y, width) sorted by x.
+    skyline: Vec<(u32, u32, u32)>,
+}
+
+impl AtlasPage {
+    fn new(size: u32, format: MaskFormat) -> Self {
+        Self {
+            size,
+            data: vec![0; size as usize * size as usize * format.channels()],
+            skyline: vec![(0, 0, size)],
+        }
+    }
+
+    /// Returns the width and height of the page in pixels.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the pixel data of the page with rows from top to bottom,
+    /// in the format of the atlas.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Finds a position for a rectangle of the given size and adds it to
+    /// the skyline.
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        // Choose the position with the lowest top edge, then the
+        // narrowest skyline segment.
+        let (index, y, _) = (0..self.skyline.len())
+            .filter_map(|index| {
+                let y = self.fit(index, width, height)?;
+                Some((index, y, self.skyline[index].2))
+            })
+            .min_by_key(|&(_, y, segment_width)| (y + height, segment_width))?;
+        let x = self.skyline[index].0;
+        self.skyline.insert(index, (x, y + height, width));
+        // Shrink or remove the segments covered by the new one.
+        let right = x + width;
+        let next = index + 1;
+        while let Some(segment) = self.skyline.get_mut(next) {
+            if segment.0 >= right {
+                break;
+            }
+            let overlap = right - segment.0;
+            if overlap < segment.2 {
+                segment.0 += overlap;
+                segment.2 -= overlap;
+                break;
+            }
+            self.skyline.remove(next);
+        }
+        // Merge adjacent segments at the same height.
+        self.skyline.dedup_by(|next, prev| {
+            let is_same_height = next.1 == prev.1;
+            if is_same_height {
+                prev.2 += next.2;
+            }
+            is_same_height
+        });
+        Some((x, y))
+    }
+
+    /// Returns the top of a rectangle with its left edge at the start of
+    /// the given skyline segment, or `None` if it does not fit.
+    fn fit(&self, index: usize, width: u32, height: u32) -> Option<u32> {
+        let x = self.skyline[index].0;
+        if x + width > self.size {
+            return None;
+        }
+        let mut y = 0;
+        let mut remaining = width;
+        for &(_, segment_y, segment_width) in &self.skyline[index..] {
+            y = y.max(segment_y);
+            if y + height > self.size {
+                return None;
+            }
+            if segment_width >= remaining {
+                return Some(y);
+            }
+            remaining -= segment_width;
+        }
+        None
+    }
+}
+
+/// Set of textures containing packed glyph masks.
+#[derive(Clone, Debug)]
+pub struct Atlas {
+    format: MaskFormat,
+    page_size: u32,
+    padding: u32,
+    pages: Vec<AtlasPage>,
+}
+
+impl Atlas {
+    /// Creates a new empty atlas for masks of the given format.
+    ///
+    /// The page size is rounded up to a power of two. The padding is the
+    /// number of empty pixels kept between masks to avoid sampling
+    /// neighboring glyphs.
+    pub fn new(format: MaskFormat, page_size: u32, padding: u32) -> Self {
+        Self {
+            format,
+            page_size: page_size.max(1).next_power_of_two(),
+            padding,
+            pages: Vec::new(),
+        }
+    }
+
+    /// Returns the format of the masks in the atlas.
+    pub fn format(&self) -> MaskFormat {
+        self.format
+    }
+
+    /// Returns the width and height of each page in pixels.
+    pub fn page_size(&self) -> u32 {
+        self.page_size
+    }
+
+    /// Returns the pages of the atlas.
+    pub fn pages(&self) -> &[AtlasPage] {
+        &self.pages
+    }
+
+    /// Removes all masks and pages.
+    pub fn clear(&mut self) {
+        self.pages.clear();
+    }
+
+    /// Copies the mask into the atlas and returns its placement.
+    ///
+    /// Returns `None` if the format of the mask does not match the atlas,
+    /// or if the mask and its padding are larger than a page. Empty masks are not stored
+    /// and have a placement with zero size.
+    pub fn insert(&mut self, mask: &Mask) -> Option<Placement> {
+        if mask.format != self.format {
+            return None;
+        }
+        let (width, height) = (mask.width, mask.height);
+        if width == 0 || height == 0 {
+            return Some(Placement {
+                left: mask.left,
+                top: mask.top,
+                ..Default::default()
+            });
+        }
+        let channels = self.format.channels();
+        if mask.data.len() < width as usize * height as usize * channels {
+            return None;
+        }
+        let (padded_width, padded_height) = (width + self.padding, height + self.padding);
+        if padded_width > self.page_size || padded_height > self.page_size {
+            return None;
+        }
+        let existing = self.pages.iter_mut().enumerate().find_map(|(index, page)| {
+            let position = page.allocate(padded_width, padded_height)?;
+            Some((index, position))
+        });
+        let (page, (x, y)) = match existing {
+            Some(found) => found,
+            None => {
+                let mut page = AtlasPage::new(self.page_size, self.format);
+                let position = page.allocate(padded_width, padded_height)?;
+                self.pages.push(page);
+                (self.pages.len() - 1, position)
+            }
+        };
+        let page_data = &mut self.pages[page].data;
+        let row_len = width as usize * channels;
+        let page_row_len = self.page_size as usize * channels;
+        for (row, src) in mask
+            .data
+            .chunks_exact(row_len)
+            .take(height as usize)
+            .enumerate()
+        {
+            let start = (y as usize + row) * page_row_len + x as usize * channels;
+            page_data[start..start + row_len].copy_from_slice(src);
+        }
+        let size = self.page_size as f32;
+        Some(Placement {
+            page,
+            x,
+            y,
+            width,
+            height,
+            left: mask.left,
+            top: mask.top,
+            uv: [
+                x as f32 / size,
+                y as f32 / size,
+                (x + width) as f32 / size,
+                (y + height) as f32 / size,
+            ],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mask(width: u32, height: u32, value: u8) -> Mask {
+        Mask {
+            left: 1,
+            top: 2,
+            width,
+            height,
+            format: MaskFormat::Alpha,
+            data: vec![value; (width * height) as usize],
+        }
+    }
+
+    fn overlaps(a: &Placement, b: &Placement) -> bool {
+        a.page == b.page
+            && a.x < b.x + b.width
+            && b.x < a.x + a.width
+            && a.y < b.y + b.height
+            && b.y < a.y + a.height
+    }
+
+    #[test]
+    fn place_masks() {
+        let mut atlas = Atlas::new(MaskFormat::Alpha, 50, 1);
+        assert_eq!(atlas.page_size(), 64);
+        let first = atlas.insert(&mask(10, 20, 1)).unwrap();
+        assert_eq!((first.page, first.x, first.y), (0, 0, 0));
+        assert_eq!((first.left, first.top), (1, 2));
+        assert_eq!(first.uv, [0.0, 0.0, 10.0 / 64.0, 20.0 / 64.0]);
+        // The next mask is placed to the right, after the padding.
+        let second = atlas.insert(&mask(5, 5, 2)).unwrap();
+        assert_eq!((second.x, second.y), (11, 0));
+        let page = &atlas.pages()[0];
+        assert_eq!(page.data()[0], 1);
+        assert_eq!(page.data()[10], 0);
+        assert_eq!(page.data()[11], 2);
+        assert_eq!(page.data()[4 * 64 + 15], 2);
+        assert_eq!(page.data()[5 * 64 + 11], 0);
+    }
+
+    #[test]
+    fn fill_pages() {
+        let mut atlas = Atlas::new(MaskFormat::Alpha, 64, 0);
+        let placements = (0..20)
+            .map(|i| atlas.insert(&mask(10 + i % 7, 12 + i % 5, 1)).unwrap())
+            .collect::<Vec<_>>();
+        assert!(atlas.pages().len() > 1);
+        for (i, a) in placements.iter().enumerate() {
+            assert!(a.x + a.width <= 64 && a.y + a.height <= 64);
+            for b in &placements[i + 1..] {
+                assert!(!overlaps(a, b), "{a:?} overlaps {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn rejected_masks() {
+        let mut atlas = Atlas::new(MaskFormat::Alpha, 16, 1);
+        // Too large once padded.
+        assert!(atlas.insert(&mask(16, 4, 1)).is_none());
+        let mut subpixel = mask(2, 2, 1);
+        subpixel.format = MaskFormat::Subpixel;
+        assert!(atlas.insert(&subpixel).is_none());
+        // Empty masks are not stored.
+        let empty = atlas.insert(&mask(0, 0, 0)).unwrap();
+        assert_eq!((empty.width, empty.height), (0, 0));
+        assert!(atlas.pages().is_empty());
+    }
+}
@@ -0,0 +1,251 @@
+//! Automatic hinting for outlines without usable hinting instructions.
+//!
+//! This is a port of the latin writing system of the FreeType autohinter.
+//! Global metrics (standard stem widths and blue zones) are computed from
+//! the outlines of a set of reference characters. Each glyph is then
+//! analyzed for segments and edges which are fitted to the pixel grid,
+//! and the remaining points are interpolated.
+
+mod axis;
+mod metrics;
+mod outline;
+
+pub use metrics::FontMetrics;
+
+use self::{
+    axis::{Axis, Dimension},
+    metrics::{link_params, ScaledMetrics},
+    outline::Outline,
+};
+use crate::{
+    source::glyf::{
+        self,
+        hint::{
+            lru_index,
+            math::{mul_div, mul_fix},
+        },
+    },
+    Hinting, NormalizedCoord,
+};
+
+use read_fonts::types::F26Dot6;
+
+/// Maximum number of fonts with cached metrics.
+const MAX_CACHED_FONTS: usize = 8;
+
+/// Cache of global metrics for fonts.
+#[derive(Clone, Default, Debug)]
+pub struct Cache {
+    fonts: Vec<CachedMetrics>,
+    /// Counter for least recently used eviction.
+    serial: u64,
+}
+
+#[derive(Clone, Default, Debug)]
+struct CachedMetrics {
+    font_id: u64,
+    coords: Vec<NormalizedCoord>,
+    serial: u64,
+    metrics: FontMetrics,
+}
+
+impl Cache {
+    /// Returns a hinter for the given size and mode, using the cached
+    /// metrics for the font or computing them with the given function.
+    ///
+    /// Metrics are not cached for fonts without an identifier.
+    pub fn hinter(
+        &mut self,
+        font_id: Option<u64>,
+        coords: &[NormalizedCoord],
+        size: f32,
+        mode: Hinting,
+        compute: impl FnOnce() -> FontMetrics,
+    ) -> Hinter {
+        let Some(font_id) = font_id else {
+            return Hinter::new(&compute(), size, mode);
+        };
+        self.serial += 1;
+        let index = match self
+            .fonts
+            .iter()
+            .position(|entry| entry.font_id == font_id && entry.coords == coords)
+        {
+            Some(index) => index,
+            None => {
+                let index = lru_index(&mut self.fonts, MAX_CACHED_FONTS, |entry| entry.serial);
+                let entry = &mut self.fonts[index];
+                entry.font_id = font_id;
+                entry.coords.extend_from_slice(coords);
+                entry.metrics = compute();
+                index
+            }
+        };
+        let entry = &mut self.fonts[index];
+        entry.serial = self.serial;
+        Hinter::new(&entry.metrics, size, mode)
+    }
+}
+
+/// Automatic hinter for a particular font, size and mode.
+#[derive(Clone, Debug)]
+pub struct Hinter {
+    metrics: ScaledMetrics,
+    /// True if x coordinates are also hinted.
+    hint_horizontal: bool,
+    len_threshold: i32,
+    len_score: i64,
+    outline: Outline,
+    axis: Axis,
+}
+
+impl Hinter {
+    fn new(metrics: &FontMetrics, size: f32, mode: Hinting) -> Self {
+        let metrics = ScaledMetrics::new(metrics, size);
+        let (len_threshold, len_score) = link_params(metrics.units_per_em, metrics.x_scale);
+        Self {
+            metrics,
+            // Only the full mode moves points horizontally.
+            hint_horizontal: mode == Hinting::Full,
+            len_threshold,
+            len_score,
+            outline: Outline::default(),
+            axis: Axis::default(),
+        }
+    }
+
+    /// Hints the given scaled outline in place.
+    pub fn hint(&mut self, target: &mut glyf::Outline) {
+        let metrics = &self.metrics;
+        if metrics.y_scale != metrics.x_scale {
+            for point in &mut target.points {
+                point.y = F26Dot6::from_bits(mul_div(
+                    point.y.to_bits(),
+                    metrics.y_scale,
+                    metrics.x_scale,
+                ));
+            }
+        }
+        self.outline.fill(target);
+        let dims = if self.hint_horizontal {
+            &[Dimension::Vertical, Dimension::Horizontal][..]
+        } else {
+            &[Dimension::Vertical][..]
+        };
+        for &dim in dims {
+            let std_width = match dim {
+                Dimension::Horizontal => metrics.widths[0],
+                Dimension::Vertical => metrics.widths[1],
+            };
+            let axis = &mut self.axis;
+            axis.compute_segments(&self.outline, dim);
+            axis.link_segments(self.len_threshold, self.len_score);
+            axis.compute_edges((std_width / 5).clamp(1, 16));
+            if dim == Dimension::Vertical {
+                let threshold = mul_fix(metrics.units_per_em as i32 / 40, metrics.y_scale).min(32);
+                axis.compute_blue_edges(&metrics.blues, threshold);
+            }
+            axis.hint_edges(std_width);
+            axis.align_edge_points(&mut self.outline, dim);
+            self.outline.align_strong_points(dim, axis);
+            self.outline.align_weak_points(dim);
+        }
+        for (point, hinted) in target.points.iter_mut().zip(&self.outline.points) {
+            point.x = F26Dot6::from_bits(hinted.x);
+            point.y = F26Dot6::from_bits(hinted.y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{metrics::Blue, *};
+    use read_fonts::{
+        tables::glyf::PointFlags,
+        types::{F26Dot6, Point},
+    };
+
+    /// Builds an outline from a list of contours of on curve points.
+    fn outline(contours: &[&[(i32, i32)]]) -> glyf::Outline {
+        let mut outline = glyf::Outline::new();
+        for contour in contours {
+            for &(x, y) in *contour {
+                outline
+                    .points
+                    .push(Point::new(F26Dot6::from_bits(x), F26Dot6::from_bits(y)));
+                outline.flags.push(PointFlags::on_curve());
+            }
+            outline.contours.push(outline.points.len() as u16 - 1);
+        }
+        outline
+    }
+
+    fn font_metrics() -> FontMetrics {
+        FontMetrics {
+            units_per_em: 1000,
+            widths: [90, 80],
+            blues: vec![
+                Blue {
+                    ref_pos: 0,
+                    shoot_pos: -10,
+                    is_top: false,
+                    is_x_height: false,
+                },
+                Blue {
+                    ref_pos: 500,
+                    shoot_pos: 510,
+                    is_top: true,
+                    is_x_height: true,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn snap_to_blue_zones() {
+        let mut target = outline(&[&[(0, 5), (0, 500), (640, 500), (640, 5)]]);
+        let mut hinter = Cache::default().hinter(None, &[], 16.0, Hinting::Light, font_metrics);
+        hinter.hint(&mut target);
+        let points = target
+            .points
+            .iter()
+            .map(|p| (p.x.to_bits(), p.y.to_bits()))
+            .collect::<Vec<_>>();
+        // The baseline and x-height are fitted to the grid while x is
+        // untouched.
+        assert_eq!(points, [(0, 0), (0, 512), (640, 512), (640, 0)]);
+    }
+
+    #[test]
+    fn full_mode_fits_vertical_stem() {
+        let mut target = outline(&[&[(100, 0), (100, 640), (190, 640), (190, 0)]]);
+        let mut hinter = Cache::default().hinter(None, &[], 16.0, Hinting::Full, font_metrics);
+        hinter.hint(&mut target);
+        let xs = target
+            .points
+            .iter()
+            .map(|p| p.x.to_bits())
+            .collect::<Vec<_>>();
+        let width = xs.iter().max().unwrap() - xs.iter().min().unwrap();
+        // The stem is snapped to the standard width.
+        assert_eq!(width, hinter.metrics.widths[0]);
+    }
+
+    #[test]
+    fn cached_metrics() {
+        let mut cache = Cache::default();
+        let mut count = 0;
+        for _ in 0..2 {
+            cache.hinter(Some(1), &[], 12.0, Hinting::Light, || {
+                count += 1;
+                font_metrics()
+            });
+        }
+        assert_eq!(count, 1);
+        cache.hinter(Some(2), &[], 12.0, Hinting::Light, || {
+            count += 1;
+            font_metrics()
+        });
+        assert_eq!(count, 2);
+    }
+}
@@ -0,0 +1,508 @@
+//! Segments and edges along a single dimension, and the fitting of edges
+//! to the pixel grid.
+
+use super::{
+    metrics::ScaledBlue,
+    outline::{Direction, Outline, OFF_CURVE, TOUCH_X, TOUCH_Y},
+};
+use crate::source::glyf::hint::math::mul_div;
+
+/// Dimension in which points are moved.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Dimension {
+    /// Hints x coordinates using vertical edges.
+    Horizontal,
+    /// Hints y coordinates using horizontal edges.
+    Vertical,
+}
+
+impl Dimension {
+    pub fn touch_flag(self) -> u8 {
+        match self {
+            Self::Horizontal => TOUCH_X,
+            Self::Vertical => TOUCH_Y,
+        }
+    }
+
+    /// Returns the direction of segments that form the lower (or left)
+    /// edge of a stem.
+    fn major_dir(self, is_clockwise: bool) -> Direction {
+        match (self, is_clockwise) {
+            (Self::Horizontal, true) => Direction::Up,
+            (Self::Horizontal, false) => Direction::Down,
+            (Self::Vertical, true) => Direction::Left,
+            (Self::Vertical, false) => Direction::Right,
+        }
+    }
+}
+
+/// Run of points aligned with a dimension.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Segment {
+    pub dir: Direction,
+    /// Position of the segment in the hinted dimension.
+    pub pos: i32,
+    /// Extent of the segment in the perpendicular dimension.
+    pub min: i32,
+    pub max: i32,
+    /// First and last points of the segment.
+    pub first: usize,
+    pub last: usize,
+    /// True if the segment contains curve control points.
+    pub is_round: bool,
+    /// Index of the segment forming a stem with this one.
+    pub link: Option<usize>,
+    /// Index of the segment for which this one is a serif.
+    pub serif: Option<usize>,
+    score: i64,
+    /// Index of the edge containing the segment.
+    pub edge: usize,
+}
+
+/// Set of segments with roughly the same position.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Edge {
+    pub dir: Direction,
+    /// Original position.
+    pub opos: i32,
+    /// Hinted position.
+    pub pos: i32,
+    pub is_round: bool,
+    /// Fitted position of the blue zone to which the edge is snapped.
+    pub blue: Option<i32>,
+    /// Index of the edge forming a stem with this one.
+    pub link: Option<usize>,
+    /// Index of the edge for which this one is a serif.
+    pub serif: Option<usize>,
+    pub is_done: bool,
+}
+
+/// Segments and edges for a single dimension of an outline.
+#[derive(Clone, Default, Debug)]
+pub struct Axis {
+    pub segments: Vec<Segment>,
+    pub edges: Vec<Edge>,
+    major_dir: Direction,
+}
+
+impl Axis {
+    /// Computes the segments of the outline in the given dimension.
+    ///
+    /// This is `af_latin_hints_compute_segments`.
+    pub fn compute_segments(&mut self, outline: &Outline, dim: Dimension) {
+        self.segments.clear();
+        self.edges.clear();
+        self.major_dir = dim.major_dir(outline.is_clockwise);
+        let is_aligned = |dir: Direction| {
+            dir != Direction::None && (dir == self.major_dir || dir == self.major_dir.reverse())
+        };
+        let points = &outline.points;
+        for contour in &outline.contours {
+            // Start at a point where the direction changes so that a run
+            // never wraps around the start of the contour.
+            let Some(start) = contour
+                .clone()
+                .find(|&i| points[i].in_dir != points[i].out_dir)
+            else {
+                continue;
+            };
+            let mut i = start;
+            loop {
+                let dir = points[i].out_dir;
+                if !is_aligned(dir) {
+                    i = points[i].next;
+                    if i == start {
+                        break;
+                    }
+                    continue;
+                }
+                let mut segment = Segment {
+                    dir,
+                    pos: 0,
+                    min: i32::MAX,
+                    max: i32::MIN,
+                    first: i,
+                    last: i,
+                    score: i64::MAX,
+                    ..Default::default()
+                };
+                let (mut min_pos, mut max_pos) = (i32::MAX, i32::MIN);
+                let mut j = i;
+                loop {
+                    let point = &points[j];
+                    let (pos, cross) = (point.orig(dim), point.orig_cross(dim));
+                    min_pos = min_pos.min(pos);
+                    max_pos = max_pos.max(pos);
+                    segment.min = segment.min.min(cross);
+                    segment.max = segment.max.max(cross);
+                    segment.is_round |= point.flags & OFF_CURVE != 0;
+                    segment.last = j;
+                    if j != i && point.out_dir != dir {
+                        break;
+                    }
+                    j = point.next;
+                }
+                segment.pos = ((min_pos as i64 + max_pos as i64) / 2) as i32;
+                self.segments.push(segment);
+                i = segment.last;
+                if i == start {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Links pairs of segments that are likely to form stems.
+    ///
+    /// Both values are in the units of the outline: segments shorter
+    /// than `len_threshold` are ignored and `len_score` penalizes short
+    /// overlaps.
+    ///
+    /// This is `af_latin_hints_link_segments`.
+    pub fn link_segments(&mut self, len_threshold: i32, len_score: i64) {
+        let major_dir = self.major_dir;
+        let segments = &mut self.segments;
+        for i in 0..segments.len() {
+            let seg1 = segments[i];
+            if seg1.dir != major_dir {
+                continue;
+            }
+            for j in 0..segments.len() {
+                let seg2 = segments[j];
+                if seg2.dir != major_dir.reverse() || seg2.pos <= seg1.pos {
+                    continue;
+                }
+                let len = seg1.max.min(seg2.max) as i64 - seg1.min.max(seg2.min) as i64;
+                if len < len_threshold as i64 {
+                    continue;
+                }
+                let score = (seg2.pos - seg1.pos) as i64 + len_score / len.max(1);
+                if score < segments[i].score {
+                    segments[i].score = score;
+                    segments[i].link = Some(j);
+                }
+                if score < segments[j].score {
+                    segments[j].score = score;
+                    segments[j].link = Some(i);
+                }
+            }
+        }
+        // Segments without a mutual link become serifs of the stem
+        // formed by the segment they were linked to.
+        for i in 0..segments.len() {
+            if let Some(link) = segments[i].link {
+                if segments[link].link != Some(i) {
+                    segments[i].link = None;
+                    segments[i].serif = segments[link].link;
+                }
+            }
+        }
+    }
+
+    /// Returns the smallest distance between linked segments.
+    pub fn min_stem_width(&self) -> Option<i32> {
+        self.segments
+            .iter()
+            .filter_map(|segment| {
+                let link = &self.segments[segment.link?];
+                Some((link.pos - segment.pos).abs())
+            })
+            .filter(|width| *width > 0)
+            .min()
+    }
+
+    /// Groups segments with similar positions into edges.
+    ///
+    /// This is `af_latin_hints_compute_edges`.
+    pub fn compute_edges(&mut self, threshold: i32) {
+        self.edges.clear();
+        let mut order = (0..self.segments.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| self.segments[i].pos);
+        for i in order {
+            let segment = self.segments[i];
+            let best = self
+                .edges
+                .iter()
+                .enumerate()
+                .filter(|(_, edge)| edge.dir == segment.dir)
+                .map(|(index, edge)| (index, (edge.opos - segment.pos).abs()))
+                .filter(|(_, dist)| *dist < threshold)
+                .min_by_key(|(_, dist)| *dist)
+                .map(|(index, _)| index);
+            self.segments[i].edge = best.unwrap_or_else(|| {
+                self.edges.push(Edge {
+                    dir: segment.dir,
+                    opos: segment.pos,
+                    pos: segment.pos,
+                    ..Default::default()
+                });
+                self.edges.len() - 1
+            });
+        }
+        let mut round_counts = vec![0i32; self.edges.len()];
+        for segment in &self.segments {
+            round_counts[segment.edge] += if segment.is_round { 1 } else { -1 };
+            let edge_link = segment.link.map(|link| self.segments[link].edge);
+            let edge_serif = segment.serif.map(|serif| self.segments[serif].edge);
+            let edge = &mut self.edges[segment.edge];
+            if edge.link.is_none() {
+                edge.link = edge_link.filter(|link| *link != segment.edge);
+            }
+            if edge.serif.is_none() {
+                edge.serif = edge_serif.filter(|serif| *serif != segment.edge);
+            }
+        }
+        for (edge, count) in self.edges.iter_mut().zip(round_counts) {
+            edge.is_round = count > 0;
+            if edge.link.is_some() {
+                edge.serif = None;
+            }
+        }
+    }
+
+    /// Assigns blue zones to edges.
+    ///
+    /// This is `af_latin_hints_compute_blue_edges`.
+    pub fn compute_blue_edges(&mut self, blues: &[ScaledBlue], threshold: i32) {
+        for edge in &mut self.edges {
+            let is_top_edge = edge.dir != self.major_dir;
+            let mut best_dist = threshold;
+            for blue in blues
+                .iter()
+                .filter(|blue| blue.is_active && blue.is_top == is_top_edge)
+            {
+                let dist = (edge.opos - blue.ref_pos).abs();
+                if dist < best_dist {
+                    best_dist = dist;
+                    edge.blue = Some(blue.ref_fit);
+                }
+                // Round edges beyond the reference position may snap to
+                // the overshoot.
+                if edge.is_round && dist != 0 {
+                    let is_under_ref = edge.opos < blue.ref_pos;
+                    if blue.is_top != is_under_ref {
+                        let dist = (edge.opos - blue.shoot_pos).abs();
+                        if dist < best_dist {
+                            best_dist = dist;
+                            edge.blue = Some(blue.shoot_fit);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Moves the edges to the pixel grid, preserving stem widths.
+    ///
+    /// This is `af_latin_hint_edges`.
+    pub fn hint_edges(&mut self, std_width: i32) {
+        let edges = &mut self.edges;
+        let mut anchor = None;
+        // Snap edges to blue zones along with their linked edges.
+        for i in 0..edges.len() {
+            let Some(blue) = edges[i].blue else {
+                continue;
+            };
+            edges[i].pos = blue;
+            edges[i].is_done = true;
+            if let Some(link) = edges[i].link {
+                if !edges[link].is_done {
+                    let width = stem_width(std_width, edges[link].opos - edges[i].opos, edges[i]);
+                    edges[link].pos = edges[i].pos + width;
+                    edges[link].is_done = true;
+                }
+            }
+            anchor.get_or_insert(i);
+        }
+        // Fit the remaining stems.
+        for i in 0..edges.len() {
+            let Some(link) = edges[i].link else {
+                continue;
+            };
+            if edges[i].is_done {
+                continue;
+            }
+            let (edge, edge2) = (edges[i], edges[link]);
+            let org_len = edge2.opos - edge.opos;
+            let cur_len = stem_width(std_width, org_len, edge);
+            if edge2.is_done {
+                edges[i].pos = edge2.pos - cur_len;
+            } else if let Some(anchor) = anchor {
+                let org_pos = edges[anchor].pos + (edge.opos - edges[anchor].opos);
+                let org_center = org_pos + (org_len >> 1);
+                if cur_len < 96 {
+                    let center = fit_center(org_center, cur_len);
+                    edges[i].pos = center - cur_len / 2;
+                    edges[link].pos = center + cur_len / 2;
+                } else {
+                    let pos1 = pix_round(org_pos);
+                    let delta1 = (pos1 + (cur_len >> 1) - org_center).abs();
+                    let pos2 = pix_round(org_pos + org_len) - cur_len;
+                    let delta2 = (pos2 + (cur_len >> 1) - org_center).abs();
+                    edges[i].pos = if delta1 < delta2 { pos1 } else { pos2 };
+                    edges[link].pos = edges[i].pos + cur_len;
+                }
+            } else {
+                if cur_len < 96 {
+                    let center = fit_center(edge.opos + (org_len >> 1), cur_len);
+                    edges[i].pos = center - cur_len / 2;
+                } else {
+                    edges[i].pos = pix_round(edge.opos);
+                }
+                edges[link].pos = edges[i].pos + stem_width(std_width, org_len, edge);
+                anchor = Some(i);
+            }
+            edges[i].is_done = true;
+            edges[link].is_done = true;
+            if i > 0 && edges[i].pos < edges[i - 1].pos {
+                edges[i].pos = edges[i - 1].pos;
+            }
+        }
+        // Align serifs and the remaining lone edges.
+        for i in 0..edges.len() {
+            if edges[i].is_done {
+                continue;
+            }
+            let edge = edges[i];
+            let serif = edge
+                .serif
+                .map(|serif| edges[serif])
+                .filter(|serif| serif.is_done && (serif.opos - edge.opos).abs() < 64 + 16);
+            if let Some(serif) = serif {
+                edges[i].pos = serif.pos + (edge.opos - serif.opos);
+            } else if let Some(anchor) = anchor {
+                let before = edges[..i].iter().rev().find(|edge| edge.is_done);
+                let after = edges[i + 1..].iter().find(|edge| edge.is_done);
+                edges[i].pos = match (before, after) {
+                    (Some(before), Some(after)) if after.opos != before.opos => {
+                        before.pos
+                            + mul_div(
+                                edge.opos - before.opos,
+                                after.pos - before.pos,
+                                after.opos - before.opos,
+                            )
+                    }
+                    (Some(before), Some(_)) => before.pos,
+                    _ => {
+                        let anchor = edges[anchor];
+                        anchor.pos + ((edge.opos - anchor.opos + 16) & !31)
+                    }
+                };
+            } else {
+                edges[i].pos = pix_round(edge.opos);
+                anchor = Some(i);
+            }
+            edges[i].is_done = true;
+            if i > 0 && edges[i].pos < edges[i - 1].pos {
+                edges[i].pos = edges[i - 1].pos;
+            }
+            if i + 1 < edges.len() && edges[i + 1].is_done && edges[i].pos > edges[i + 1].pos {
+                edges[i].pos = edges[i + 1].pos;
+            }
+        }
+    }
+
+    /// Moves the points of each segment to the hinted position of its
+    /// edge.
+    ///
+    /// This is `af_glyph_hints_align_edge_points`.
+    pub fn align_edge_points(&self, outline: &mut Outline, dim: Dimension) {
+        for segment in &self.segments {
+            let pos = self.edges[segment.edge].pos;
+            let mut i = segment.first;
+            loop {
+                outline.points[i].set(dim, pos);
+                if i == segment.last {
+                    break;
+                }
+                i = outline.points[i].next;
+            }
+        }
+    }
+}
+
+/// Rounds a 26.6 value to the nearest pixel.
+pub fn pix_round(value: i32) -> i32 {
+    (value + 32) & !63
+}
+
+/// Returns the position of the center of a short stem, placed so that
+/// both edges land as close to the pixel grid as possible.
+fn fit_center(org_center: i32, cur_len: i32) -> i32 {
+    let (u_off, d_off) = if cur_len <= 64 { (32, 32) } else { (38, 26) };
+    let center = pix_round(org_center);
+    let error1 = (org_center - (center - u_off)).abs();
+    let error2 = (org_center - (center + d_off)).abs();
+    if error1 < error2 {
+        center - u_off
+    } else {
+        center + d_off
+    }
+}
+
+/// Computes the hinted width of a stem, lightly quantizing it and
+/// snapping it to the standard width when close.
+///
+/// This is the smooth hinting path of `af_latin_compute_stem_width`.
+fn stem_width(std_width: i32, width: i32, base: Edge) -> i32 {
+    let mut dist = width.abs();
+    if base.is_round {
+        if dist < 80 {
+            dist = 64;
+        }
+    } else if dist < 56 {
+        dist = 56;
+    }
+    if std_width > 0 && (dist - std_width).abs() < 40 {
+        dist = std_width.max(48);
+    } else if dist < 3 * 64 {
+        let delta = dist & 63;
+        dist &= !63;
+        dist += match delta {
+            0..=9 => delta,
+            10..=31 => 10,
+            32..=53 => 54,
+            _ => delta,
+        };
+    } else {
+        dist = pix_round(dist);
+    }
+    if width < 0 {
+        -dist
+    } else {
+        dist
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stem_widths() {
+        let edge = Edge::default();
+        // Close to the standard width.
+        assert_eq!(stem_width(100, 120, edge), 100);
+        assert_eq!(stem_width(100, -80, edge), -100);
+        // Lightly quantized.
+        assert_eq!(stem_width(0, 80, edge), 74);
+        assert_eq!(stem_width(0, 100, edge), 118);
+        // Rounded.
+        assert_eq!(stem_width(0, 300, edge), 320);
+        // Minimum widths.
+        assert_eq!(stem_width(0, 20, edge), 56);
+        let round = Edge {
+            is_round: true,
+            ..edge
+        };
+        assert_eq!(stem_width(0, 70, round), 64);
+    }
+
+    #[test]
+    fn stem_centers() {
+        // A one pixel stem is centered on a pixel.
+        assert_eq!(fit_center(100, 64), 96);
+        // Wider stems are offset from the grid.
+        assert_eq!(fit_center(128, 80), 154);
+    }
+}
@@ -0,0 +1,315 @@
+//! Global metrics of a font: standard stem widths and blue zones.
+
+use super::{
+    axis::{Axis, Dimension},
+    outline::{Direction, Outline, OFF_CURVE},
+};
+use crate::source::glyf::{
+    self,
+    hint::math::{div_fix, mul_div, mul_fix},
+};
+
+use read_fonts::{tables::cmap::Cmap, types::GlyphId};
+
+/// Characters that define each blue zone, along with whether the zone
+/// is at the top of the glyphs.
+///
+/// This is a subset of the blue strings of the latin writing system in
+/// FreeType.
+const BLUE_ZONES: [(&str, bool); 6] = [
+    // Capital top.
+    ("THEZOCQS", true),
+    // Capital bottom.
+    ("HEZLOCUS", false),
+    // Ascender.
+    ("bdhkl", true),
+    // Small top (x-height).
+    ("xzroesc", true),
+    // Small bottom.
+    ("xzroesc", false),
+    // Descender.
+    ("pqgjy", false),
+];
+
+/// Index of the x-height zone in [`BLUE_ZONES`].
+const X_HEIGHT_ZONE: usize = 3;
+
+/// Character used to compute the standard stem widths.
+const STEM_CHAR: char = 'o';
+
+/// Scales a constant defined for a 2048 unit em to the given units
+/// per em.
+///
+/// This is `AF_LATIN_CONSTANT`.
+fn latin_constant(units_per_em: u16, value: i32) -> i32 {
+    value * units_per_em as i32 / 2048
+}
+
+/// Blue zone in font units.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Blue {
+    /// Position of flat extremes.
+    pub ref_pos: i32,
+    /// Position of round extremes.
+    pub shoot_pos: i32,
+    pub is_top: bool,
+    pub is_x_height: bool,
+}
+
+/// Metrics of a font in font units, computed by analyzing the outlines
+/// of a set of reference characters.
+#[derive(Clone, Default, Debug)]
+pub struct FontMetrics {
+    pub units_per_em: u16,
+    /// Standard widths of vertical and horizontal stems.
+    pub widths: [i32; 2],
+    pub blues: Vec<Blue>,
+}
+
+impl FontMetrics {
+    /// Computes the metrics using the given function to load unscaled
+    /// outlines.
+    ///
+    /// This is `af_latin_metrics_init_widths` and
+    /// `af_latin_metrics_init_blues`.
+    pub fn new(
+        units_per_em: u16,
+        cmap: Option<&Cmap>,
+        mut load: impl FnMut(GlyphId, &mut glyf::Outline) -> bool,
+    ) -> Self {
+        let mut source = glyf::Outline::new();
+        let mut outline = Outline::default();
+        let mut load_char = |ch: char, outline: &mut Outline| {
+            let glyph_id = cmap
+                .and_then(|cmap| cmap.map_codepoint(ch))
+                .filter(|glyph_id| glyph_id.to_u16() != 0)?;
+            if !load(glyph_id, &mut source) || source.points.is_empty() {
+                return None;
+            }
+            outline.fill(&source);
+            Some(())
+        };
+        let default_width = latin_constant(units_per_em, 50);
+        let mut widths = [default_width; 2];
+        if load_char(STEM_CHAR, &mut outline).is_some() {
+            // Unscaled outlines are in 26.6 format.
+            let scale = 64 << 16;
+            let (len_threshold, len_score) = link_params(units_per_em, scale);
+            let mut axis = Axis::default();
+            for (dim, width) in [Dimension::Horizontal, Dimension::Vertical]
+                .into_iter()
+                .zip(&mut widths)
+            {
+                axis.compute_segments(&outline, dim);
+                axis.link_segments(len_threshold, len_score);
+                if let Some(stem_width) = axis.min_stem_width() {
+                    *width = (stem_width + 32) >> 6;
+                }
+            }
+        }
+        let mut blues = Vec::new();
+        let (mut flats, mut rounds) = (Vec::new(), Vec::new());
+        for (index, (chars, is_top)) in BLUE_ZONES.into_iter().enumerate() {
+            flats.clear();
+            rounds.clear();
+            for ch in chars.chars() {
+                if load_char(ch, &mut outline).is_none() {
+                    continue;
+                }
+                let extremum = outline
+                    .points
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, point)| if is_top { point.oy } else { -point.oy })
+                    .map(|(index, _)| index)
+                    .unwrap_or_default();
+                let point = outline.points[extremum];
+                let is_horizontal = |dir| matches!(dir, Direction::Left | Direction::Right);
+                let is_flat = point.flags & OFF_CURVE == 0
+                    && ((is_horizontal(point.out_dir)
+                        && outline.points[point.next].flags & OFF_CURVE == 0)
+                        || (is_horizontal(point.in_dir)
+                            && outline.points[point.prev].flags & OFF_CURVE == 0));
+                let pos = (point.oy + 32) >> 6;
+                if is_flat {
+                    flats.push(pos);
+                } else {
+                    rounds.push(pos);
+                }
+            }
+            let (Some(mut ref_pos), Some(mut shoot_pos)) =
+                (median(&mut flats), median(&mut rounds))
+            else {
+                let Some(pos) = median(&mut flats).or_else(|| median(&mut rounds)) else {
+                    continue;
+                };
+                blues.push(Blue {
+                    ref_pos: pos,
+                    shoot_pos: pos,
+                    is_top,
+                    is_x_height: index == X_HEIGHT_ZONE,
+                });
+                continue;
+            };
+            // The overshoot must extend beyond the reference position.
+            if is_top != (shoot_pos > ref_pos) {
+                ref_pos = (ref_pos + shoot_pos) / 2;
+                shoot_pos = ref_pos;
+            }
+            blues.push(Blue {
+                ref_pos,
+                shoot_pos,
+                is_top,
+                is_x_height: index == X_HEIGHT_ZONE,
+            });
+        }
+        Self {
+            units_per_em,
+            widths,
+            blues,
+        }
+    }
+}
+
+fn median(values: &mut [i32]) -> Option<i32> {
+    values.sort_unstable();
+    values.get(values.len() / 2).copied()
+}
+
+/// Returns the length threshold and length score used for linking
+/// segments, in units of an outline with the given scale.
+pub fn link_params(units_per_em: u16, scale: i32) -> (i32, i64) {
+    let len_threshold = mul_fix(latin_constant(units_per_em, 8), scale).max(1);
+    let len_score = latin_constant(units_per_em, 6000) as i64;
+    // The score is divided by a length, so it scales quadratically.
+    let len_score = len_score * scale as i64 / 65536 * scale as i64 / 65536;
+    (len_threshold, len_score)
+}
+
+/// Blue zone scaled to a particular size, in 26.6 format.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct ScaledBlue {
+    pub ref_pos: i32,
+    pub ref_fit: i32,
+    pub shoot_pos: i32,
+    pub shoot_fit: i32,
+    pub is_top: bool,
+    /// False if the overshoot is too large to be snapped at this size.
+    pub is_active: bool,
+}
+
+/// Metrics scaled to a particular size, in 26.6 format.
+#[derive(Clone, Default, Debug)]
+pub struct ScaledMetrics {
+    pub units_per_em: u16,
+    /// Scale factors from font units to 26.6 in 16.16 format. The
+    /// vertical scale is adjusted to align the x-height to the pixel grid.
+    pub x_scale: i32,
+    pub y_scale: i32,
+    /// Standard widths of vertical and horizontal stems.
+    pub widths: [i32; 2],
+    pub blues: Vec<ScaledBlue>,
+}
+
+impl ScaledMetrics {
+    /// Scales the metrics to the given size in pixels per em.
+    ///
+    /// This is `af_latin_metrics_scale_dim`.
+    pub fn new(metrics: &FontMetrics, size: f32) -> Self {
+        let units_per_em = metrics.units_per_em;
+        let x_scale = div_fix((size * 64.0) as i32, units_per_em as i32);
+        let mut y_scale = x_scale;
+        if let Some(blue) = metrics.blues.iter().find(|blue| blue.is_x_height) {
+            let scaled = mul_fix(blue.shoot_pos, x_scale);
+            let fitted = (scaled + 40) & !63;
+            if scaled != fitted && scaled > 0 {
+                let new_scale = mul_div(x_scale, fitted, scaled);
+                // Only use the new scale if it doesn't change the size of
+                // the em by more than two pixels.
+                let dist = mul_fix(units_per_em as i32, new_scale - x_scale).abs() & !127;
+                if dist == 0 {
+                    y_scale = new_scale;
+                }
+            }
+        }
+        let blues = metrics
+            .blues
+            .iter()
+            .map(|blue| {
+                let ref_pos = mul_fix(blue.ref_pos, y_scale);
+                let shoot_pos = mul_fix(blue.shoot_pos, y_scale);
+                let dist = mul_fix(blue.ref_pos - blue.shoot_pos, y_scale);
+                // Snap the overshoot to 0, 1/2 or 1 pixel.
+                let delta = match dist.abs() {
+                    0..=31 => 0,
+                    32..=47 => 32,
+                    _ => 64,
+                };
+                let ref_fit = (ref_pos + 32) & !63;
+                ScaledBlue {
+                    ref_pos,
+                    ref_fit,
+                    shoot_pos,
+                    shoot_fit: ref_fit - delta * dist.signum(),
+                    is_top: blue.is_top,
+                    is_active: (-48..=48).contains(&dist),
+                }
+            })
+            .collect();
+        Self {
+            units_per_em,
+            x_scale,
+            y_scale,
+            widths: [
+                mul_fix(metrics.widths[0], x_scale),
+                mul_fix(metrics.widths[1], y_scale),
+            ],
+            blues,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_blues() {
+        let metrics = FontMetrics {
+            units_per_em: 1000,
+            widths: [80, 70],
+            blues: vec![
+                Blue {
+                    ref_pos: 0,
+                    shoot_pos: -12,
+                    is_top: false,
+                    is_x_height: false,
+                },
+                Blue {
+                    ref_pos: 500,
+                    shoot_pos: 510,
+                    is_top: true,
+                    is_x_height: true,
+                },
+            ],
+        };
+        let scaled = ScaledMetrics::new(&metrics, 16.0);
+        assert_eq!(scaled.x_scale, 67109);
+        // The x-height of 8.16 pixels is rounded down to 8 pixels.
+        let x_height = mul_fix(510, scaled.y_scale);
+        assert_eq!(x_height, 512);
+        let baseline = scaled.blues[0];
+        assert_eq!((baseline.ref_fit, baseline.shoot_fit), (0, 0));
+        assert!(baseline.is_active);
+        let top = scaled.blues[1];
+        assert_eq!(top.ref_fit, 512);
+        assert_eq!(top.shoot_fit, 512);
+        assert_eq!(scaled.widths[0], mul_fix(80, scaled.x_scale));
+    }
+
+    #[test]
+    fn link_params_scale() {
+        // Unscaled 26.6 outlines.
+        assert_eq!(link_params(2048, 64 << 16), (512, 6000 * 64 * 64));
+    }
+}
@@ -0,0 +1,321 @@
+//! Outline representation used for automatic hinting.
+
+use super::axis::{Axis, Dimension};
+use crate::{source::glyf, source::glyf::hint::math::mul_div};
+
+/// Direction of a vector, ignoring small deviations from the major axes.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum Direction {
+    #[default]
+    None,
+    Right,
+    Left,
+    Up,
+    Down,
+}
+
+impl Direction {
+    /// Computes the direction of the vector (dx, dy).
+    ///
+    /// A vector is only considered aligned with an axis if its major
+    /// component is at least 14 times larger than its minor component.
+    pub fn new(dx: i32, dy: i32) -> Self {
+        let (ax, ay) = (dx.unsigned_abs() as u64, dy.unsigned_abs() as u64);
+        if ax > ay && ax >= ay * 14 {
+            if dx > 0 {
+                Self::Right
+            } else {
+                Self::Left
+            }
+        } else if ay > ax && ay >= ax * 14 {
+            if dy > 0 {
+                Self::Up
+            } else {
+                Self::Down
+            }
+        } else {
+            Self::None
+        }
+    }
+
+    /// Returns the direction pointing the opposite way.
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::None => Self::None,
+            Self::Right => Self::Left,
+            Self::Left => Self::Right,
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+        }
+    }
+}
+
+/// Point is a control point of a curve.
+pub const OFF_CURVE: u8 = 1;
+/// Point can be interpolated rather than snapped to an edge.
+pub const WEAK: u8 = 2;
+/// Point has been moved in the horizontal dimension.
+pub const TOUCH_X: u8 = 4;
+/// Point has been moved in the vertical dimension.
+pub const TOUCH_Y: u8 = 8;
+
+/// Outline point with the state needed for hinting.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Point {
+    /// Original position.
+    pub ox: i32,
+    pub oy: i32,
+    /// Hinted position.
+    pub x: i32,
+    pub y: i32,
+    pub flags: u8,
+    /// Direction of the incoming and outgoing vectors.
+    pub in_dir: Direction,
+    pub out_dir: Direction,
+    /// Indices of the neighboring points in the contour.
+    pub prev: usize,
+    pub next: usize,
+}
+
+impl Point {
+    /// Returns the original coordinate in the given dimension.
+    pub fn orig(&self, dim: Dimension) -> i32 {
+        match dim {
+            Dimension::Horizontal => self.ox,
+            Dimension::Vertical => self.oy,
+        }
+    }
+
+    /// Returns the original coordinate perpendicular to the given
+    /// dimension.
+    pub fn orig_cross(&self, dim: Dimension) -> i32 {
+        match dim {
+            Dimension::Horizontal => self.oy,
+            Dimension::Vertical => self.ox,
+        }
+    }
+
+    /// Returns the hinted coordinate in the given dimension.
+    pub fn get(&self, dim: Dimension) -> i32 {
+        match dim {
+            Dimension::Horizontal => self.x,
+            Dimension::Vertical => self.y,
+        }
+    }
+
+    /// Moves the point in the given dimension and marks it as touched.
+    pub fn set(&mut self, dim: Dimension, value: i32) {
+        match dim {
+            Dimension::Horizontal => self.x = value,
+            Dimension::Vertical => self.y = value,
+        }
+        self.flags |= dim.touch_flag();
+    }
+
+    pub fn is_touched(&self, dim: Dimension) -> bool {
+        self.flags & dim.touch_flag() != 0
+    }
+}
+
+/// Outline prepared for automatic hinting.
+#[derive(Clone, Default, Debug)]
+pub struct Outline {
+    pub points: Vec<Point>,
+    /// Ranges of points for each contour.
+    pub contours: Vec<core::ops::Range<usize>>,
+    /// True if the outer contours are drawn in clockwise order, as in
+    /// TrueType outlines.
+    pub is_clockwise: bool,
+}
+
+impl Outline {
+    /// Fills the outline from the given source, computing point directions
+    /// and weak points.
+    pub fn fill(&mut self, source: &glyf::Outline) {
+        self.points.clear();
+        self.contours.clear();
+        self.points.extend(
+            source
+                .points
+                .iter()
+                .zip(&source.flags)
+                .map(|(point, flags)| Point {
+                    ox: point.x.to_bits(),
+                    oy: point.y.to_bits(),
+                    x: point.x.to_bits(),
+                    y: point.y.to_bits(),
+                    flags: if flags.is_on_curve() { 0 } else { OFF_CURVE },
+                    ..Default::default()
+                }),
+        );
+        let mut start = 0;
+        for &end in &source.contours {
+            let end = (end as usize + 1).min(self.points.len());
+            if end > start {
+                self.contours.push(start..end);
+            }
+            start = end;
+        }
+        let mut area = 0i64;
+        for contour in &self.contours {
+            let last = contour.end - 1;
+            for i in contour.clone() {
+                let prev = if i == contour.start { last } else { i - 1 };
+                let next = if i == last { contour.start } else { i + 1 };
+                let (p, n) = (self.points[i], self.points[next]);
+                area += p.ox as i64 * n.oy as i64 - n.ox as i64 * p.oy as i64;
+                let point = &mut self.points[i];
+                point.prev = prev;
+                point.next = next;
+                point.out_dir = Direction::new(n.ox - p.ox, n.oy - p.oy);
+            }
+            for i in contour.clone() {
+                let prev = self.points[i].prev;
+                self.points[i].in_dir = self.points[prev].out_dir;
+            }
+        }
+        self.is_clockwise = area < 0;
+        for i in 0..self.points.len() {
+            let point = self.points[i];
+            if point.flags & OFF_CURVE != 0 {
+                self.points[i].flags |= WEAK;
+                continue;
+            }
+            let is_weak = if point.in_dir == point.out_dir {
+                point.out_dir != Direction::None || {
+                    let (prev, next) = (self.points[point.prev], self.points[point.next]);
+                    is_corner_flat(
+                        point.ox - prev.ox,
+                        point.oy - prev.oy,
+                        next.ox - point.ox,
+                        next.oy - point.oy,
+                    )
+                }
+            } else {
+                point.in_dir == point.out_dir.reverse()
+            };
+            if is_weak {
+                self.points[i].flags |= WEAK;
+            }
+        }
+    }
+
+    /// Moves the points that were not snapped to an edge by interpolating
+    /// between the surrounding edges.
+    ///
+    /// This is `af_glyph_hints_align_strong_points`.
+    pub fn align_strong_points(&mut self, dim: Dimension, axis: &Axis) {
+        let edges = &axis.edges;
+        let (Some(first), Some(last)) = (edges.first(), edges.last()) else {
+            return;
+        };
+        for point in &mut self.points {
+            if point.is_touched(dim) || point.flags & WEAK != 0 {
+                continue;
+            }
+            let u = point.orig(dim);
+            let value = if u <= first.opos {
+                first.pos - (first.opos - u)
+            } else if u >= last.opos {
+                last.pos + (u - last.opos)
+            } else {
+                // Edges are sorted by original position.
+                let index = edges.partition_point(|edge| edge.opos < u);
+                let after = &edges[index];
+                if after.opos == u {
+                    after.pos
+                } else {
+                    let before = &edges[index - 1];
+                    before.pos
+                        + mul_div(
+                            u - before.opos,
+                            after.pos - before.pos,
+                            after.opos - before.opos,
+                        )
+                }
+            };
+            point.set(dim, value);
+        }
+    }
+
+    /// Moves the remaining points of each contour by interpolating between
+    /// the surrounding touched points.
+    ///
+    /// This is `af_glyph_hints_align_weak_points`.
+    pub fn align_weak_points(&mut self, dim: Dimension) {
+        for contour in &self.contours {
+            let points = &mut self.points[contour.clone()];
+            let Some(first_touched) = points.iter().position(|point| point.is_touched(dim)) else {
+                continue;
+            };
+            let len = points.len();
+            let mut start = first_touched;
+            loop {
+                // Find the next touched point after start.
+                let mut end = (start + 1) % len;
+                while !points[end].is_touched(dim) {
+                    end = (end + 1) % len;
+                }
+                if end == start {
+                    // Only a single touched point: shift the contour.
+                    let delta = points[start].get(dim) - points[start].orig(dim);
+                    for point in points.iter_mut() {
+                        if !point.is_touched(dim) {
+                            let value = point.orig(dim) + delta;
+                            point.set(dim, value);
+                        }
+                    }
+                    break;
+                }
+                let (p1, p2) = (points[start], points[end]);
+                let mut i = (start + 1) % len;
+                while i != end {
+                    let value = interpolate(dim, &p1, &p2, points[i].orig(dim));
+                    points[i].set(dim, value);
+                    i = (i + 1) % len;
+                }
+                if end == first_touched {
+                    break;
+                }
+                start = end;
+            }
+        }
+    }
+}
+
+/// Interpolates a coordinate between two reference points.
+///
+/// This is `af_iup_interp`.
+fn interpolate(dim: Dimension, p1: &Point, p2: &Point, u: i32) -> i32 {
+    let (mut o1, mut c1, mut o2, mut c2) = (p1.orig(dim), p1.get(dim), p2.orig(dim), p2.get(dim));
+    if o1 > o2 {
+        core::mem::swap(&mut o1, &mut o2);
+        core::mem::swap(&mut c1, &mut c2);
+    }
+    if u <= o1 {
+        u + c1 - o1
+    } else if u >= o2 {
+        u + c2 - o2
+    } else {
+        c1 + mul_div(u - o1, c2 - c1, o2 - o1)
+    }
+}
+
+/// Returns true if the corner formed by the two vectors is almost a
+/// straight line.
+///
+/// This is `ft_corner_is_flat`.
+fn is_corner_flat(in_x: i32, in_y: i32, out_x: i32, out_y: i32) -> bool {
+    let hypot = |x: i32, y: i32| {
+        let (x, y) = (x.unsigned_abs() as i64, y.unsigned_abs() as i64);
+        if x > y {
+            x + ((3 * y) >> 3)
+        } else {
+            y + ((3 * x) >> 3)
+        }
+    };
+    let d_in = hypot(in_x, in_y);
+    let d_out = hypot(out_x, out_y);
+    let d_corner = hypot(in_x + out_x, in_y + out_y);
+    d_in + d_out - d_corner < d_corner >> 4
+}
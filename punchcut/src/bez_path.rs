@@ -0,0 +1,142 @@
+//! Interoperability with the paths of kurbo.
+
+use super::{glyf, Error, Pen};
+
+use kurbo::{BezPath, PathEl, Point};
+
+/// Pen that builds a [`BezPath`].
+#[derive(Clone, Default, Debug)]
+pub struct BezPathPen {
+    path: BezPath,
+}
+
+impl BezPathPen {
+    /// Creates a new pen with an empty path.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the path.
+    pub fn path(&self) -> &BezPath {
+        &self.path
+    }
+
+    /// Returns the path, consuming the pen.
+    pub fn into_path(self) -> BezPath {
+        self.path
+    }
+}
+
+fn point(x: f32, y: f32) -> Point {
+    Point::new(x as f64, y as f64)
+}
+
+impl Pen for BezPathPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.path.move_to(point(x, y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.path.line_to(point(x, y));
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.path.quad_to(point(cx0, cy0), point(x, y));
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.path
+            .curve_to(point(cx0, cy0), point(cx1, cy1), point(x, y));
+    }
+
+    fn close(&mut self) {
+        self.path.close_path();
+    }
+}
+
+impl From<BezPathPen> for BezPath {
+    fn from(pen: BezPathPen) -> Self {
+        pen.path
+    }
+}
+
+/// Converts a TrueType outline to a path, failing if the outline is
+/// malformed.
+impl TryFrom<&glyf::Outline> for BezPath {
+    type Error = Error;
+
+    fn try_from(outline: &glyf::Outline) -> Result<Self, Self::Error> {
+        let mut pen = BezPathPen::new();
+        outline.to_path(&mut pen)?;
+        Ok(pen.path)
+    }
+}
+
+/// Converts a path to an outline with coordinates rounded to 26.6 fixed
+/// point. Cubic curves are represented with cubic off curve points.
+impl From<&BezPath> for glyf::Outline {
+    fn from(path: &BezPath) -> Self {
+        let mut outline = glyf::Outline::new();
+        let mut pen = glyf::OutlinePen::new(&mut outline);
+        let coords = |p: Point| (p.x as f32, p.y as f32);
+        for element in path.elements() {
+            match *element {
+                PathEl::MoveTo(p) => {
+                    let (x, y) = coords(p);
+                    pen.move_to(x, y);
+                }
+                PathEl::LineTo(p) => {
+                    let (x, y) = coords(p);
+                    pen.line_to(x, y);
+                }
+                PathEl::QuadTo(p0, p1) => {
+                    let ((x0, y0), (x1, y1)) = (coords(p0), coords(p1));
+                    pen.quad_to(x0, y0, x1, y1);
+                }
+                PathEl::CurveTo(p0, p1, p2) => {
+                    let ((x0, y0), (x1, y1), (x2, y2)) = (coords(p0), coords(p1), coords(p2));
+                    pen.curve_to(x0, y0, x1, y1, x2, y2);
+                }
+                PathEl::ClosePath => pen.close(),
+            }
+        }
+        drop(pen);
+        outline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{font::FontRef, Context, GlyphId};
+    use read_fonts::test_data::test_fonts;
+
+    #[test]
+    fn outline_round_trip() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().size(16.0).build(&font);
+        let mut pen = BezPathPen::new();
+        scaler.outline(GlyphId::new(1), &mut pen).unwrap();
+        let path = pen.into_path();
+        assert!(!path.elements().is_empty());
+        // Converting through an outline preserves the path, since the
+        // scaled coordinates are already in 26.6 format.
+        let outline = glyf::Outline::from(&path);
+        assert_eq!(BezPath::try_from(&outline).unwrap(), path);
+    }
+
+    #[test]
+    fn cubic_outline() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.curve_to((0.0, 10.0), (10.0, 10.0), (10.0, 0.0));
+        path.close_path();
+        let outline = glyf::Outline::from(&path);
+        assert_eq!(outline.points.len(), 4);
+        assert_eq!(outline.contours, [3]);
+        assert!(outline.flags[1].is_off_curve_cubic());
+        let round_trip = BezPath::try_from(&outline).unwrap();
+        assert_eq!(round_trip.elements()[1], path.elements()[1]);
+    }
+}
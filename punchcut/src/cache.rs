@@ -0,0 +1,320 @@
+//! Caching of scaled outlines.
+
+use super::{GlyphId, NormalizedCoord, Pen};
+
+#[cfg(feature = "hinting")]
+use super::HintingOptions;
+
+use std::collections::HashMap;
+
+/// Maximum number of scaler configurations with cached outlines.
+const MAX_CACHED_CONFIGS: usize = 32;
+
+/// Settings that affect the outlines produced by a scaler.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Key {
+    pub font_id: u64,
+    pub size: f32,
+    pub coords: Vec<NormalizedCoord>,
+    #[cfg(feature = "hinting")]
+    pub hint: Option<HintingOptions>,
+    pub embolden: f32,
+    pub skew: f32,
+}
+
+/// Least recently used cache of scaled outlines, limited by an
+/// approximate memory budget.
+///
+/// Outlines are stored as recorded path commands so that they can be
+/// replayed exactly as they were produced by the scaler.
+#[derive(Clone, Default, Debug)]
+pub struct OutlineCache {
+    /// Maximum number of bytes used by cached outlines. A budget of zero
+    /// disables the cache.
+    budget: usize,
+    /// Number of bytes used by cached outlines.
+    used: usize,
+    configs: Vec<CachedConfig>,
+    glyphs: HashMap<(u64, GlyphId), CachedPath>,
+    /// Counter for least recently used eviction.
+    serial: u64,
+    /// Identifier for the next configuration.
+    next_id: u64,
+}
+
+#[derive(Clone, Default, Debug)]
+struct CachedConfig {
+    id: u64,
+    key: Key,
+    serial: u64,
+}
+
+#[derive(Clone, Default, Debug)]
+struct CachedPath {
+    path: Path,
+    serial: u64,
+}
+
+impl OutlineCache {
+    /// Returns the memory budget in bytes.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Returns the approximate number of bytes used by cached outlines.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Sets the memory budget in bytes, evicting outlines as necessary.
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        if budget == 0 {
+            self.clear();
+        } else if self.used > budget {
+            self.evict(budget);
+        }
+    }
+
+    /// Removes all cached outlines.
+    pub fn clear(&mut self) {
+        self.configs.clear();
+        self.glyphs.clear();
+        self.used = 0;
+    }
+
+    /// Returns the identifier for the outlines of the given configuration,
+    /// or `None` if the cache is disabled.
+    pub fn config_id(&mut self, key: &Key) -> Option<u64> {
+        if self.budget == 0 {
+            return None;
+        }
+        self.serial += 1;
+        let serial = self.serial;
+        if let Some(config) = self.configs.iter_mut().find(|config| &config.key == key) {
+            config.serial = serial;
+            return Some(config.id);
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        let config = CachedConfig {
+            id,
+            key: key.clone(),
+            serial,
+        };
+        if self.configs.len() < MAX_CACHED_CONFIGS {
+            self.configs.push(config);
+        } else if let Some(lru) = self.configs.iter_mut().min_by_key(|config| config.serial) {
+            // Drop all outlines for the replaced configuration.
+            let old_id = lru.id;
+            *lru = config;
+            let used = &mut self.used;
+            self.glyphs.retain(|&(id, _), glyph| {
+                let keep = id != old_id;
+                if !keep {
+                    *used -= glyph.path.size();
+                }
+                keep
+            });
+        }
+        Some(id)
+    }
+
+    /// Returns the cached outline for a glyph in the given configuration.
+    pub fn get(&mut self, config_id: u64, glyph_id: GlyphId) -> Option<&Path> {
+        self.serial += 1;
+        let glyph = self.glyphs.get_mut(&(config_id, glyph_id))?;
+        glyph.serial = self.serial;
+        Some(&glyph.path)
+    }
+
+    /// Adds the outline for a glyph in the given configuration, evicting
+    /// the least recently used outlines if the budget is exceeded.
+    ///
+    /// Outlines that are larger than the budget are not cached.
+    pub fn insert(&mut self, config_id: u64, glyph_id: GlyphId, path: Path) {
+        let size = path.size();
+        if size > self.budget {
+            return;
+        }
+        if self.used + size > self.budget {
+            // Evict a quarter of the budget at a time to avoid scanning
+            // all entries for every insertion.
+            let target = (self.budget - size).min(self.budget / 4 * 3);
+            self.evict(target);
+        }
+        self.serial += 1;
+        let glyph = CachedPath {
+            path,
+            serial: self.serial,
+        };
+        self.used += size;
+        if let Some(old) = self.glyphs.insert((config_id, glyph_id), glyph) {
+            self.used -= old.path.size();
+        }
+    }
+
+    /// Evicts the least recently used outlines until no more than the
+    /// given number of bytes are used.
+    fn evict(&mut self, target: usize) {
+        let mut entries = self
+            .glyphs
+            .iter()
+            .map(|(key, glyph)| (glyph.serial, *key))
+            .collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(serial, _)| *serial);
+        for (_, key) in entries {
+            if self.used <= target {
+                break;
+            }
+            if let Some(glyph) = self.glyphs.remove(&key) {
+                self.used -= glyph.path.size();
+            }
+        }
+    }
+}
+
+/// Path command.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Verb {
+    MoveTo,
+    LineTo,
+    QuadTo,
+    CurveTo,
+    Close,
+}
+
+/// Sequence of recorded path commands.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Path {
+    verbs: Vec<Verb>,
+    coords: Vec<f32>,
+}
+
+impl Path {
+    /// Returns the approximate number of bytes used by the path.
+    fn size(&self) -> usize {
+        core::mem::size_of::<CachedPath>()
+            + self.verbs.len() * core::mem::size_of::<Verb>()
+            + self.coords.len() * core::mem::size_of::<f32>()
+    }
+
+    /// Invokes the functions in the given sink for each recorded command.
+    pub fn replay(&self, sink: &mut impl Pen) {
+        let mut coords = self.coords.iter().copied();
+        let mut next = || coords.next().unwrap_or_default();
+        for verb in &self.verbs {
+            match verb {
+                Verb::MoveTo => sink.move_to(next(), next()),
+                Verb::LineTo => sink.line_to(next(), next()),
+                Verb::QuadTo => sink.quad_to(next(), next(), next(), next()),
+                Verb::CurveTo => sink.curve_to(next(), next(), next(), next(), next(), next()),
+                Verb::Close => sink.close(),
+            }
+        }
+    }
+}
+
+impl Pen for Path {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.verbs.push(Verb::MoveTo);
+        self.coords.extend([x, y]);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.verbs.push(Verb::LineTo);
+        self.coords.extend([x, y]);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.verbs.push(Verb::QuadTo);
+        self.coords.extend([cx0, cy0, x, y]);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.verbs.push(Verb::CurveTo);
+        self.coords.extend([cx0, cy0, cx1, cy1, x, y]);
+    }
+
+    fn close(&mut self) {
+        self.verbs.push(Verb::Close);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: f32) -> Path {
+        let mut path = Path::default();
+        path.move_to(0.0, 0.0);
+        path.line_to(size, 0.0);
+        path.quad_to(size, size, 0.0, size);
+        path.close();
+        path
+    }
+
+    fn key(font_id: u64) -> Key {
+        Key {
+            font_id,
+            size: 16.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn replay_path() {
+        let path = square(10.0);
+        let mut replayed = Path::default();
+        path.replay(&mut replayed);
+        assert_eq!(path, replayed);
+    }
+
+    #[test]
+    fn disabled_cache() {
+        let mut cache = OutlineCache::default();
+        assert_eq!(cache.config_id(&key(1)), None);
+    }
+
+    #[test]
+    fn config_ids() {
+        let mut cache = OutlineCache::default();
+        cache.set_budget(1 << 20);
+        let id = cache.config_id(&key(1)).unwrap();
+        assert_eq!(cache.config_id(&key(1)), Some(id));
+        let mut other = key(1);
+        other.size = 12.0;
+        assert_ne!(cache.config_id(&other), Some(id));
+        // Replacing a configuration drops its outlines.
+        cache.insert(id, GlyphId::new(1), square(1.0));
+        for font_id in 2..MAX_CACHED_CONFIGS as u64 + 2 {
+            cache.config_id(&key(font_id));
+        }
+        assert!(cache.get(id, GlyphId::new(1)).is_none());
+        assert_eq!(cache.used(), 0);
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let size = square(1.0).size();
+        let mut cache = OutlineCache::default();
+        cache.set_budget(size * 4);
+        let id = cache.config_id(&key(1)).unwrap();
+        for gid in 0..4 {
+            cache.insert(id, GlyphId::new(gid), square(gid as f32));
+        }
+        assert_eq!(cache.used(), size * 4);
+        // Touch the first glyph so that it is retained.
+        assert_eq!(cache.get(id, GlyphId::new(0)), Some(&square(0.0)));
+        cache.insert(id, GlyphId::new(4), square(4.0));
+        assert!(cache.used() <= cache.budget());
+        assert!(cache.get(id, GlyphId::new(0)).is_some());
+        assert!(cache.get(id, GlyphId::new(1)).is_none());
+        assert_eq!(cache.get(id, GlyphId::new(4)), Some(&square(4.0)));
+        // Shrinking the budget evicts outlines.
+        cache.set_budget(size);
+        assert!(cache.used() <= size);
+        cache.set_budget(0);
+        assert_eq!(cache.used(), 0);
+    }
+}
@@ -0,0 +1,190 @@
+//! Mapping of characters to nominal glyph identifiers.
+
+use super::GlyphId;
+
+use read_fonts::{
+    tables::cmap::{Cmap12, Cmap4, CmapSubtable, PlatformId},
+    TableProvider,
+};
+
+/// Windows encoding for symbol fonts.
+const WINDOWS_SYMBOL_ENCODING: u16 = 0;
+/// Windows encoding for the basic multilingual plane.
+const WINDOWS_BMP_ENCODING: u16 = 1;
+/// Windows encoding for the full Unicode repertoire.
+const WINDOWS_FULL_ENCODING: u16 = 10;
+
+/// Symbol fonts map characters into the private use area starting here.
+const SYMBOL_BASE: u32 = 0xF000;
+
+/// Mapping of characters to nominal glyph identifiers.
+///
+/// The mapping is taken from the best Unicode subtable in `cmap`: a
+/// subtable covering the full Unicode repertoire (format 12) is preferred
+/// over one covering only the basic multilingual plane (format 4). If there
+/// is no Unicode subtable, a Windows symbol subtable is used, and characters
+/// are also looked up in the `U+F000..=U+F0FF` range where such fonts place
+/// their glyphs.
+#[derive(Clone, Default)]
+pub struct Charmap<'a> {
+    subtable: Option<Subtable<'a>>,
+    is_symbol: bool,
+}
+
+#[derive(Clone)]
+enum Subtable<'a> {
+    Format4(Cmap4<'a>),
+    Format12(Cmap12<'a>),
+}
+
+impl<'a> Charmap<'a> {
+    /// Creates a character map for the given font.
+    ///
+    /// The map is empty if the font has no usable `cmap` subtable.
+    pub fn new(font: &impl TableProvider<'a>) -> Self {
+        let Ok(cmap) = font.cmap() else {
+            return Self::default();
+        };
+        let mut best: Option<(u8, Subtable, bool)> = None;
+        for record in cmap.encoding_records() {
+            let platform = record.platform_id();
+            let encoding = record.encoding_id();
+            let is_symbol = platform == PlatformId::Windows && encoding == WINDOWS_SYMBOL_ENCODING;
+            let is_unicode = platform == PlatformId::Unicode
+                || (platform == PlatformId::Windows
+                    && matches!(encoding, WINDOWS_BMP_ENCODING | WINDOWS_FULL_ENCODING));
+            let (rank, subtable) = match record.subtable(cmap.offset_data()) {
+                Ok(CmapSubtable::Format12(subtable)) if is_unicode => {
+                    (3, Subtable::Format12(subtable))
+                }
+                Ok(CmapSubtable::Format4(subtable)) if is_unicode => {
+                    (2, Subtable::Format4(subtable))
+                }
+                Ok(CmapSubtable::Format4(subtable)) if is_symbol => {
+                    (1, Subtable::Format4(subtable))
+                }
+                _ => continue,
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(best_rank, ..)| rank > *best_rank)
+            {
+                best = Some((rank, subtable, is_symbol));
+            }
+        }
+        match best {
+            Some((_, subtable, is_symbol)) => Self {
+                subtable: Some(subtable),
+                is_symbol,
+            },
+            None => Self::default(),
+        }
+    }
+
+    /// Returns true if a usable subtable was found.
+    pub fn has_map(&self) -> bool {
+        self.subtable.is_some()
+    }
+
+    /// Returns true if the mapping comes from a symbol subtable.
+    pub fn is_symbol(&self) -> bool {
+        self.is_symbol
+    }
+
+    /// Maps a character to a nominal glyph identifier.
+    ///
+    /// Returns `None` if the character is not mapped, or is mapped to
+    /// `.notdef`.
+    pub fn map(&self, ch: impl Into<u32>) -> Option<GlyphId> {
+        let ch = ch.into();
+        let gid = self.map_raw(ch).or_else(|| {
+            if self.is_symbol && ch <= 0xFF {
+                self.map_raw(SYMBOL_BASE + ch)
+            } else {
+                None
+            }
+        })?;
+        (gid != GlyphId::NOTDEF).then_some(gid)
+    }
+
+    /// Returns an iterator over all mappings, in codepoint order.
+    ///
+    /// Codepoints that map to `.notdef` are skipped. For symbol fonts, the
+    /// codepoints are those in the subtable, which are usually in the
+    /// `U+F000..=U+F0FF` range.
+    pub fn mappings(&self) -> impl Iterator<Item = (u32, GlyphId)> + 'a {
+        let (format4, format12) = match &self.subtable {
+            Some(Subtable::Format4(subtable)) => (Some(subtable.iter()), None),
+            Some(Subtable::Format12(subtable)) => (None, Some(subtable.iter())),
+            None => (None, None),
+        };
+        format4
+            .into_iter()
+            .flatten()
+            .chain(format12.into_iter().flatten())
+    }
+
+    fn map_raw(&self, ch: u32) -> Option<GlyphId> {
+        match self.subtable.as_ref()? {
+            Subtable::Format4(subtable) => subtable.map_codepoint(ch),
+            Subtable::Format12(subtable) => subtable.map_codepoint(ch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use read_fonts::{test_data::test_fonts, FontRef};
+    use write_fonts::{tables::cmap::Cmap, FontBuilder};
+
+    #[test]
+    fn map_and_iterate() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let charmap = Charmap::new(&font);
+        assert!(charmap.has_map());
+        assert!(!charmap.is_symbol());
+        assert_eq!(charmap.map('A'), Some(GlyphId::new(1)));
+        assert_eq!(charmap.map('B'), None);
+        let mappings = charmap.mappings().collect::<Vec<_>>();
+        assert_eq!(
+            mappings,
+            [(0x41, 1), (0x60, 3), (0xC0, 2)].map(|(ch, gid)| (ch, GlyphId::new(gid)))
+        );
+        for (ch, gid) in mappings {
+            assert_eq!(charmap.map(ch), Some(gid));
+        }
+    }
+
+    #[test]
+    fn symbol() {
+        // symbol fonts map their glyphs into the private use area
+        let mut cmap = Cmap::from_mappings(BTreeMap::from([('\u{F041}', GlyphId::new(1))]));
+        cmap.encoding_records
+            .retain(|record| record.platform_id == PlatformId::Windows);
+        cmap.encoding_records[0].encoding_id = WINDOWS_SYMBOL_ENCODING;
+        let mut builder = FontBuilder::default();
+        builder.add_typed_table(&cmap).unwrap();
+        let data = builder.build();
+        let charmap = Charmap::new(&FontRef::new(&data).unwrap());
+        assert!(charmap.is_symbol());
+        assert_eq!(charmap.map('A'), Some(GlyphId::new(1)));
+        assert_eq!(charmap.map(0xF041u32), Some(GlyphId::new(1)));
+        assert_eq!(charmap.map('B'), None);
+        assert_eq!(
+            charmap.mappings().collect::<Vec<_>>(),
+            [(0xF041, GlyphId::new(1))]
+        );
+    }
+
+    #[test]
+    fn no_cmap() {
+        let data = FontBuilder::default().build();
+        let charmap = Charmap::new(&FontRef::new(&data).unwrap());
+        assert!(!charmap.has_map());
+        assert_eq!(charmap.map('A'), None);
+        assert_eq!(charmap.mappings().count(), 0);
+    }
+}
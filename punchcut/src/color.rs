@@ -0,0 +1,168 @@
+//! Interfaces for receiving color glyphs.
+
+use super::{GlyphId, Pen};
+
+use read_fonts::types::{Point, Rect};
+
+pub use read_fonts::tables::colr::{CompositeMode, Extend};
+
+/// Color in RGBA format, with 8 bits per component.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+/// Interface for receiving the layers of a color glyph.
+///
+/// Each layer is a filled outline, delivered through the [`Pen`] methods
+/// between a call to [`begin_layer`](Self::begin_layer) and a call to
+/// [`end_layer`](Self::end_layer). Layers are in bottom to top order.
+pub trait ColorPen: Pen {
+    /// Begins a layer filled with the given color.
+    ///
+    /// A color of `None` means the layer should be filled with the current
+    /// foreground (text) color.
+    fn begin_layer(&mut self, color: Option<Color>);
+
+    /// Ends the current layer.
+    fn end_layer(&mut self);
+}
+
+/// Affine transformation matrix.
+///
+/// A point `(x, y)` is transformed to
+/// `(xx * x + xy * y + dx, yx * x + yy * y + dy)`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Transform {
+    pub xx: f32,
+    pub yx: f32,
+    pub xy: f32,
+    pub yy: f32,
+    pub dx: f32,
+    pub dy: f32,
+}
+
+impl Transform {
+    /// The identity transformation.
+    pub const IDENTITY: Self = Self {
+        xx: 1.0,
+        yx: 0.0,
+        xy: 0.0,
+        yy: 1.0,
+        dx: 0.0,
+        dy: 0.0,
+    };
+
+    /// Returns the transformation that applies `self` around the specified
+    /// center point, rather than the origin.
+    pub fn around_center(self, cx: f32, cy: f32) -> Self {
+        Self {
+            dx: self.dx + cx - (self.xx * cx + self.xy * cy),
+            dy: self.dy + cy - (self.yx * cx + self.yy * cy),
+            ..self
+        }
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Axis-aligned rectangle.
+pub type BoundingBox = Rect<f32>;
+
+/// Color at a position along a gradient.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ColorStop {
+    /// Position of the stop along the gradient, where 0.0 is the start and
+    /// 1.0 is the end.
+    pub offset: f32,
+    /// Color of the stop, or `None` for the foreground color.
+    pub color: Option<Color>,
+    /// Multiplier for the alpha of the color.
+    pub alpha: f32,
+}
+
+/// Description of how to fill an area.
+///
+/// The color stops of gradients are sorted by offset.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Brush<'a> {
+    /// Fill with a single color, or with the foreground color if `color` is
+    /// `None`. The alpha of the color is multiplied by `alpha`.
+    Solid { color: Option<Color>, alpha: f32 },
+    /// Gradient along the line from `p0` to `p1`.
+    ///
+    /// The rotation point of the font's gradient has already been applied,
+    /// so the gradient is perpendicular to this line.
+    LinearGradient {
+        p0: Point<f32>,
+        p1: Point<f32>,
+        color_stops: &'a [ColorStop],
+        extend: Extend,
+    },
+    /// Gradient between the circle with center `c0` and radius `r0` and the
+    /// circle with center `c1` and radius `r1`.
+    RadialGradient {
+        c0: Point<f32>,
+        r0: f32,
+        c1: Point<f32>,
+        r1: f32,
+        color_stops: &'a [ColorStop],
+        extend: Extend,
+    },
+    /// Gradient around the point `c0`, between two angles in degrees,
+    /// counter-clockwise from the positive x axis.
+    SweepGradient {
+        c0: Point<f32>,
+        start_angle: f32,
+        end_angle: f32,
+        color_stops: &'a [ColorStop],
+        extend: Extend,
+    },
+}
+
+/// Interface for painting a color glyph.
+///
+/// This receives the paint graph of a `COLR` version 1 color glyph as a
+/// sequence of commands for a stack based renderer. Each push is matched by
+/// a pop of the same kind, and the outlines of glyphs used as clips are
+/// delivered through the [`Pen`] methods.
+///
+/// Version 0 color glyphs are painted as a sequence of glyph clips, each
+/// filled with a solid color.
+pub trait ColorPainter: Pen {
+    /// Pushes a transform, which applies to all following commands until
+    /// the matching [`pop_transform`](Self::pop_transform).
+    fn push_transform(&mut self, transform: Transform);
+
+    /// Pops the most recent transform.
+    fn pop_transform(&mut self);
+
+    /// Pushes a clip to the outline of the specified glyph.
+    ///
+    /// The outline is delivered through the [`Pen`] methods immediately
+    /// after this call.
+    fn push_clip_glyph(&mut self, glyph_id: GlyphId);
+
+    /// Pushes a clip to a rectangle.
+    fn push_clip_box(&mut self, clip_box: BoundingBox);
+
+    /// Pops the most recent clip.
+    fn pop_clip(&mut self);
+
+    /// Fills the area inside the current clips with the given brush.
+    fn fill(&mut self, brush: Brush);
+
+    /// Pushes a new layer, which is composited onto the layer below it with
+    /// the given mode when it is popped.
+    fn push_layer(&mut self, composite_mode: CompositeMode);
+
+    /// Pops the most recent layer.
+    fn pop_layer(&mut self);
+}
@@ -11,11 +11,15 @@ pub enum Error {
     GlyphNotFound(GlyphId),
     /// Exceeded a recursion limit when loading a glyph.
     RecursionLimitExceeded(GlyphId),
+    /// Exceeded a recursion limit when painting a color glyph.
+    PaintRecursionLimitExceeded(GlyphId),
     /// Error occured during hinting.
     #[cfg(feature = "hinting")]
     HintingFailed(GlyphId),
     /// An anchor point had invalid indices.
     InvalidAnchorPoint(GlyphId, u16),
+    /// A PostScript charstring was malformed or used an unsupported operator.
+    InvalidCharstring(GlyphId),
     /// Conversion from outline to path failed.
     ToPath(ToPathError),
     /// Error occured when reading font data.
@@ -44,12 +48,18 @@ impl fmt::Display for Error {
                 "Recursion limit ({}) exceeded when loading composite component {gid}",
                 crate::GLYF_COMPOSITE_RECURSION_LIMIT,
             ),
+            Self::PaintRecursionLimitExceeded(gid) => write!(
+                f,
+                "Recursion limit ({}) exceeded when painting color glyph {gid}",
+                crate::COLR_PAINT_RECURSION_LIMIT,
+            ),
             #[cfg(feature = "hinting")]
             Self::HintingFailed(gid) => write!(f, "Bad hinting bytecode for glyph {gid}"),
             Self::InvalidAnchorPoint(gid, index) => write!(
                 f,
                 "Invalid anchor point index ({index}) for composite glyph {gid}",
             ),
+            Self::InvalidCharstring(gid) => write!(f, "Invalid charstring for glyph {gid}"),
             Self::ToPath(e) => write!(f, "{e}"),
             Self::Read(e) => write!(f, "{e}"),
         }
@@ -58,5 +68,45 @@ impl fmt::Display for Error {
 
 impl std::error::Error for Error {}
 
+impl Error {
+    /// Returns true if the error is caused by malformed data for an
+    /// individual glyph, rather than by the request or the font as a whole.
+    pub(crate) fn is_glyph_specific(&self) -> bool {
+        !matches!(self, Self::NoSources | Self::GlyphNotFound(_))
+    }
+}
+
+/// Policy for handling malformed glyph data.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum ErrorPolicy {
+    /// Return an error for any glyph that fails to load. This is the
+    /// default.
+    #[default]
+    Fail,
+    /// Produce an empty outline for glyphs with malformed data and record
+    /// a [`Warning`]. Errors that are not caused by the glyph data, such as
+    /// [`Error::GlyphNotFound`], are still returned.
+    Recover,
+}
+
+/// Error recovered from while loading a glyph.
+#[derive(Clone, Debug)]
+pub struct Warning {
+    /// Identifier of the glyph that failed to load.
+    pub glyph_id: GlyphId,
+    /// Error that occurred.
+    pub error: Error,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Glyph {} replaced with empty outline: {}",
+            self.glyph_id, self.error
+        )
+    }
+}
+
 /// Result type for errors that may occur when loading glyphs.
 pub type Result<T> = core::result::Result<T, Error>;
@@ -6,8 +6,21 @@ Glyph loading.
 // TODO: this is temporary-- remove when hinting is added.
 #![allow(dead_code, unused_imports, unused_variables)]
 
+mod atlas;
+#[cfg(feature = "hinting")]
+mod autohint;
+#[cfg(feature = "kurbo")]
+mod bez_path;
+mod cache;
+mod charmap;
+mod color;
 mod error;
+mod metrics;
+mod raster;
 mod scaler;
+#[cfg(feature = "shaping")]
+mod shape;
+mod svg;
 
 #[cfg(test)]
 mod test;
@@ -26,12 +39,30 @@ use core::str::FromStr;
 
 pub use read_fonts::types::Pen;
 
-pub use error::{Error, Result};
+pub use atlas::{Atlas, AtlasPage, Placement};
+#[cfg(feature = "kurbo")]
+pub use bez_path::BezPathPen;
+pub use charmap::Charmap;
+pub use color::{
+    BoundingBox, Brush, Color, ColorPainter, ColorPen, ColorStop, CompositeMode, Extend, Transform,
+};
+pub use error::{Error, ErrorPolicy, Result, Warning};
+pub use metrics::{Decoration, Metrics, VerticalMetrics};
+pub use raster::{Mask, MaskFormat, RasterOptions, Rasterizer, RenderMode};
 pub use scaler::{Scaler, ScalerBuilder};
+#[cfg(feature = "shaping")]
+pub use shape::{ShapedGlyph, Shaper};
+pub use source::bitmap::{BitmapData, BitmapGlyph, BitmapMetrics};
+pub use source::glyf::{Component, ComponentAnchor};
+pub use source::GlyphSource;
+pub use svg::SvgPathPen;
 
 /// Limit for recursion when loading TrueType composite glyphs.
 const GLYF_COMPOSITE_RECURSION_LIMIT: usize = 32;
 
+/// Limit for recursion when traversing the paint graph of a color glyph.
+const COLR_PAINT_RECURSION_LIMIT: usize = 64;
+
 /// Modes for hinting.
 ///
 /// Only the `glyf` source supports all hinting modes.
@@ -52,6 +83,50 @@ pub enum Hinting {
     VerticalSubpixel,
 }
 
+/// Engines that can be used for hinting.
+#[cfg(feature = "hinting")]
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum HintingEngine {
+    /// Uses the TrueType bytecode interpreter when the font contains
+    /// hinting instructions and the automatic hinter otherwise. This is the
+    /// default engine.
+    #[default]
+    Auto,
+    /// Always uses the TrueType bytecode interpreter. Outlines that are
+    /// not TrueType are not hinted.
+    Interpreter,
+    /// Always uses the automatic hinter, ignoring any hinting instructions
+    /// in the font.
+    AutoHinter,
+}
+
+/// Options for hinting, combining a mode with the engine that applies it.
+#[cfg(feature = "hinting")]
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct HintingOptions {
+    /// Hinting mode.
+    pub mode: Hinting,
+    /// Engine used for hinting.
+    pub engine: HintingEngine,
+}
+
+#[cfg(feature = "hinting")]
+impl From<Hinting> for HintingOptions {
+    fn from(mode: Hinting) -> Self {
+        Self {
+            mode,
+            engine: HintingEngine::default(),
+        }
+    }
+}
+
+#[cfg(feature = "hinting")]
+impl From<Hinting> for Option<HintingOptions> {
+    fn from(mode: Hinting) -> Self {
+        Some(mode.into())
+    }
+}
+
 /// Type for a normalized variation coordinate.
 pub type NormalizedCoord = read_fonts::types::F2Dot14;
 
@@ -94,17 +169,46 @@ impl From<([u8; 4], f32)> for Variation {
     }
 }
 
+/// Selector for a named instance of a variable font.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum NamedInstance<'a> {
+    /// Index of the instance in the `fvar` table.
+    Index(usize),
+    /// Subfamily or PostScript name of the instance, such as "Bold".
+    Name(&'a str),
+}
+
+impl From<usize> for NamedInstance<'_> {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl<'a> From<&'a str> for NamedInstance<'a> {
+    fn from(name: &'a str) -> Self {
+        Self::Name(name)
+    }
+}
+
 /// Context for loading glyphs.
 #[derive(Clone, Default, Debug)]
 pub struct Context {
     /// Inner context for loading TrueType outlines.
     glyf: glyf::Context,
-    /// Internal storage for TrueType outlines.
+    /// Internal storage for TrueType outlines, also used for outlines from
+    /// other sources when automatic hinting.
     glyf_outline: glyf::Outline,
+    /// Cached metrics for automatic hinting.
+    #[cfg(feature = "hinting")]
+    autohint: autohint::Cache,
     /// Storage for normalized variation coordinates.
     coords: Vec<NormalizedCoord>,
     /// Storage for variation settings.
     variations: Vec<Variation>,
+    /// Cached scaled outlines.
+    outline_cache: cache::OutlineCache,
+    /// Scan converter for rendering outlines.
+    rasterizer: Rasterizer,
 }
 
 impl Context {
@@ -117,6 +221,29 @@ impl Context {
     pub fn new_scaler(&mut self) -> ScalerBuilder {
         ScalerBuilder::new(self)
     }
+
+    /// Sets the approximate maximum number of bytes used for caching scaled
+    /// outlines.
+    ///
+    /// Outlines are cached for scalers with a font identifier, keyed by the
+    /// size, variation coordinates, hinting and synthesis settings. The
+    /// least recently used outlines are evicted when the budget is
+    /// exceeded. The default budget of zero disables the cache.
+    pub fn set_outline_cache_budget(&mut self, budget: usize) {
+        self.outline_cache.set_budget(budget);
+    }
+
+    /// Returns the approximate number of bytes used by cached outlines.
+    pub fn outline_cache_usage(&self) -> usize {
+        self.outline_cache.used()
+    }
+
+    /// Removes all cached outlines.
+    ///
+    /// This should be called if the data for a font identifier changes.
+    pub fn clear_outline_cache(&mut self) {
+        self.outline_cache.clear();
+    }
 }
 
 #[cfg(test)]
@@ -147,4 +274,457 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn cached_outlines() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let outlines = crate::test::parse_glyph_outlines(test_fonts::VAZIRMATN_VAR_GLYPHS);
+        let mut cx = Context::new();
+        cx.set_outline_cache_budget(1 << 20);
+        let mut path = crate::test::Path::default();
+        // Outlines are cached on the first pass and replayed on the second.
+        for _ in 0..2 {
+            for expected_outline in &outlines {
+                path.0.clear();
+                let mut scaler = cx
+                    .new_scaler()
+                    .font_id(Some(1))
+                    .size(expected_outline.size)
+                    .build(&font);
+                scaler
+                    .outline(expected_outline.glyph_id, &mut path)
+                    .unwrap();
+                assert_eq!(path.0, expected_outline.path);
+            }
+        }
+        assert!(cx.outline_cache_usage() != 0);
+        // Outlines are not cached without a font identifier.
+        cx.clear_outline_cache();
+        let mut scaler = cx.new_scaler().size(16.0).build(&font);
+        scaler.outline(GlyphId::new(1), &mut path).unwrap();
+        assert_eq!(cx.outline_cache_usage(), 0);
+    }
+
+    #[test]
+    fn render_masks() {
+        use crate::{Mask, RenderMode};
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut cx = Context::new();
+        let mut path = crate::test::Path::default();
+        let mut mask = Mask::new();
+        let mut scaler = cx.new_scaler().size(16.0).build(&font);
+        scaler.outline(GlyphId::new(1), &mut path).unwrap();
+        scaler.render(GlyphId::new(1), &mut mask).unwrap();
+        // The mask covers the bounds of the outline.
+        let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+        let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+        for coords in path.0.iter().flat_map(|element| element.coords().chunks(2)) {
+            min_x = min_x.min(coords[0]);
+            max_x = max_x.max(coords[0]);
+            min_y = min_y.min(coords[1]);
+            max_y = max_y.max(coords[1]);
+        }
+        assert_eq!(mask.left, min_x.floor() as i32);
+        assert_eq!(mask.top, max_y.ceil() as i32);
+        assert_eq!(mask.width, (max_x.ceil() - min_x.floor()) as u32);
+        assert_eq!(mask.height, (max_y.ceil() - min_y.floor()) as u32);
+        assert_eq!(mask.data.len(), (mask.width * mask.height) as usize);
+        assert!(mask.data.iter().any(|&value| value != 0 && value != 255));
+        let mut scaler = cx
+            .new_scaler()
+            .size(16.0)
+            .raster_options(RenderMode::Monochrome)
+            .build(&font);
+        scaler.render(GlyphId::new(1), &mut mask).unwrap();
+        assert!(mask.data.iter().all(|&value| value == 0 || value == 255));
+        assert!(mask.data.contains(&255));
+        let alpha_width = mask.width;
+        let mut scaler = cx
+            .new_scaler()
+            .size(16.0)
+            .raster_options(RenderMode::Lcd)
+            .build(&font);
+        scaler.render(GlyphId::new(1), &mut mask).unwrap();
+        assert_eq!(mask.format, crate::MaskFormat::Subpixel);
+        assert_eq!(mask.width, alpha_width + 2);
+        assert_eq!(mask.data.len(), (mask.width * mask.height * 3) as usize);
+        let mut scaler = cx
+            .new_scaler()
+            .size(16.0)
+            .raster_options(RenderMode::Msdf)
+            .build(&font);
+        scaler.render(GlyphId::new(1), &mut mask).unwrap();
+        assert_eq!(mask.format, crate::MaskFormat::Msdf);
+        // The default range of 4 pixels extends the mask by 2 pixels.
+        assert_eq!(mask.width, alpha_width + 4);
+        assert_eq!(mask.data.len(), (mask.width * mask.height * 3) as usize);
+    }
+
+    #[test]
+    fn named_instances() {
+        use crate::{NormalizedCoord, ScalerBuilder};
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut cx = Context::new();
+        // Returns the normalized coordinates resolved by the builder.
+        let mut resolve = |configure: &dyn Fn(ScalerBuilder) -> ScalerBuilder| {
+            configure(cx.new_scaler()).build(&font);
+            cx.coords.clone()
+        };
+        let wght = Tag::new(b"wght");
+        let bold = resolve(&|builder| builder.location(&[(wght, 700.0)]));
+        let regular = resolve(&|builder| builder.location(&[(wght, 400.0)]));
+        assert_ne!(bold, regular);
+        // Instance 6 is Bold at 700.
+        assert_eq!(resolve(&|builder| builder.named_instance(6)), bold);
+        assert_eq!(resolve(&|builder| builder.named_instance("Bold")), bold);
+        // Variations are applied on top of the instance.
+        assert_eq!(
+            resolve(&|builder| builder.named_instance("Thin").variations([(wght, 400.0)])),
+            regular
+        );
+        // Unknown instances use the default location.
+        assert!(resolve(&|builder| builder.named_instance("Missing")).is_empty());
+        // Coordinates replace the instance.
+        assert_eq!(
+            resolve(&|builder| builder
+                .named_instance("Bold")
+                .coords([NormalizedCoord::ZERO])),
+            [NormalizedCoord::ZERO]
+        );
+        // Location replaces previous variations.
+        assert_eq!(
+            resolve(&|builder| builder
+                .variations([(wght, 100.0)])
+                .location(&[(wght, 700.0)])),
+            bold
+        );
+    }
+
+    #[test]
+    fn synthetic_styles() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let outlines = crate::test::parse_glyph_outlines(test_fonts::VAZIRMATN_VAR_GLYPHS);
+        let mut cx = Context::new();
+        let extent = |path: &[crate::test::PathElement]| {
+            let xs = path.iter().flat_map(|el| el.coords().iter().step_by(2));
+            let (min, max) = xs.fold((f32::MAX, f32::MIN), |(min, max), x| {
+                (min.min(*x), max.max(*x))
+            });
+            max - min
+        };
+        for expected in outlines.iter().filter(|outline| !outline.points.is_empty()) {
+            let mut load = |embolden: f32, skew: f32| {
+                let mut path = crate::test::Path::default();
+                cx.new_scaler()
+                    .size(expected.size)
+                    .embolden(embolden)
+                    .skew(skew)
+                    .build(&font)
+                    .outline(expected.glyph_id, &mut path)
+                    .unwrap();
+                path.0
+            };
+            let regular = load(0.0, 0.0);
+            let bold = load(1.0, 0.0);
+            let oblique = load(0.0, 12.0);
+            assert_eq!(regular.len(), bold.len());
+            assert_eq!(regular.len(), oblique.len());
+            assert!(extent(&bold) > extent(&regular));
+            assert_ne!(regular, oblique);
+        }
+    }
+
+    #[test]
+    fn transformed_outlines() {
+        use crate::Transform;
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut cx = Context::new();
+        let glyph_id = GlyphId::new(1);
+        let mut load = |transform: Transform| {
+            let mut path = crate::test::Path::default();
+            let builder = cx.new_scaler().size(16.0).transform(transform);
+            #[cfg(feature = "hinting")]
+            let builder = builder.hint(crate::Hinting::Full);
+            builder.build(&font).outline(glyph_id, &mut path).unwrap();
+            path.0
+        };
+        let condensed = Transform {
+            xx: 0.5,
+            dx: 1.0,
+            ..Transform::IDENTITY
+        };
+        let regular = load(Transform::IDENTITY);
+        let transformed = load(condensed);
+        assert_eq!(regular.len(), transformed.len());
+        for (regular, transformed) in regular.iter().zip(&transformed) {
+            for (a, b) in regular
+                .coords()
+                .chunks(2)
+                .zip(transformed.coords().chunks(2))
+            {
+                assert_eq!(b, [a[0] * 0.5 + 1.0, a[1]]);
+            }
+        }
+        // Rotation disables hinting, so the result is the rotated
+        // unhinted outline.
+        let rotated = load(Transform {
+            xx: 0.0,
+            yx: 1.0,
+            xy: -1.0,
+            yy: 0.0,
+            ..Transform::IDENTITY
+        });
+        let mut unhinted = crate::test::Path::default();
+        cx.new_scaler()
+            .size(16.0)
+            .build(&font)
+            .outline(glyph_id, &mut unhinted)
+            .unwrap();
+        for (unhinted, rotated) in unhinted.0.iter().zip(&rotated) {
+            for (a, b) in unhinted.coords().chunks(2).zip(rotated.coords().chunks(2)) {
+                assert_eq!(b, [-a[1], a[0]]);
+            }
+        }
+    }
+
+    #[test]
+    fn vertical_layout() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut cx = Context::new();
+        let glyph_id = GlyphId::new(1);
+        let mut horizontal = crate::test::Path::default();
+        cx.new_scaler()
+            .size(16.0)
+            .build(&font)
+            .outline(glyph_id, &mut horizontal)
+            .unwrap();
+        let mut scaler = cx
+            .new_scaler()
+            .size(16.0)
+            .vertical_layout(true)
+            .build(&font);
+        let origin = scaler.vertical_metrics(glyph_id);
+        assert!(origin.origin_x > 0.0 && origin.origin_y > 0.0);
+        let mut vertical = crate::test::Path::default();
+        scaler.outline(glyph_id, &mut vertical).unwrap();
+        assert_eq!(horizontal.0.len(), vertical.0.len());
+        for (a, b) in horizontal.0.iter().zip(&vertical.0) {
+            for (a, b) in a.coords().chunks(2).zip(b.coords().chunks(2)) {
+                assert_eq!(b, [a[0] - origin.origin_x, a[1] - origin.origin_y]);
+            }
+        }
+    }
+
+    #[test]
+    fn composite_components() {
+        use crate::ComponentAnchor;
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().size(16.0).build(&font);
+        assert!(scaler.components(GlyphId::new(3)).unwrap().is_empty());
+        let components = scaler.components(GlyphId::new(2)).unwrap();
+        assert!(!components.is_empty());
+        // The composite outline is made of the transformed component
+        // outlines.
+        let mut expected = vec![];
+        for component in &components {
+            assert_eq!(component.anchor, ComponentAnchor::Offset);
+            let mut path = crate::test::Path::default();
+            scaler.outline(component.glyph_id, &mut path).unwrap();
+            let t = &component.transform;
+            expected.extend(path.0.iter().flat_map(|el| {
+                el.coords()
+                    .chunks(2)
+                    .flat_map(|p| {
+                        [
+                            t.xx * p[0] + t.xy * p[1] + t.dx,
+                            t.yx * p[0] + t.yy * p[1] + t.dy,
+                        ]
+                    })
+                    .collect::<Vec<_>>()
+            }));
+        }
+        let mut path = crate::test::Path::default();
+        scaler.outline(GlyphId::new(2), &mut path).unwrap();
+        let coords = path.0.iter().flat_map(|el| el.coords().iter().copied());
+        assert!(coords.eq(expected));
+        assert!(scaler.components(GlyphId::new(u16::MAX)).is_err());
+    }
+
+    #[test]
+    fn custom_sources() {
+        use crate::{GlyphSource, NormalizedCoord, Result};
+        /// Provides squares with the size of the em for odd glyph ids.
+        struct Squares;
+        impl GlyphSource for Squares {
+            fn contains(&self, glyph_id: GlyphId) -> bool {
+                glyph_id.to_u16() % 2 == 1
+            }
+
+            fn outline(
+                &mut self,
+                _glyph_id: GlyphId,
+                size: f32,
+                _coords: &[NormalizedCoord],
+                pen: &mut dyn Pen,
+            ) -> Result<()> {
+                pen.move_to(0.0, 0.0);
+                pen.line_to(size, 0.0);
+                pen.line_to(size, size);
+                pen.close();
+                Ok(())
+            }
+        }
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut cx = Context::new();
+        let mut squares = Squares;
+        let mut scaler = cx.new_scaler().size(16.0).source(&mut squares).build(&font);
+        let mut load = |glyph_id: u16| {
+            let mut path = crate::test::Path::default();
+            scaler.outline(GlyphId::new(glyph_id), &mut path).unwrap();
+            path.0
+        };
+        let square = load(1);
+        assert_eq!(square.len(), 4);
+        assert_eq!(square[2].coords(), [16.0, 16.0]);
+        // Glyphs outside the font are loaded from the custom source.
+        assert_eq!(load(u16::MAX), square);
+        // Other glyphs are loaded from the font.
+        let mut expected = crate::test::Path::default();
+        cx.new_scaler()
+            .size(16.0)
+            .build(&font)
+            .outline(GlyphId::new(2), &mut expected)
+            .unwrap();
+        let mut path = crate::test::Path::default();
+        cx.new_scaler()
+            .size(16.0)
+            .source(&mut squares)
+            .build(&font)
+            .outline(GlyphId::new(2), &mut path)
+            .unwrap();
+        assert_eq!(path.0, expected.0);
+    }
+
+    #[test]
+    fn capabilities() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut cx = Context::new();
+        let scaler = cx.new_scaler().size(16.0).build(&font);
+        assert_eq!(scaler.glyph_count(), font.maxp().unwrap().num_glyphs());
+        assert!(scaler.has_outlines());
+        assert!(!scaler.has_color_glyphs());
+        assert!(!scaler.has_bitmaps());
+        assert!(!scaler.has_svg());
+        assert!(!scaler.is_hinted());
+        // The font has no hinting instructions, so hinted outlines use the
+        // automatic hinter.
+        #[cfg(feature = "hinting")]
+        {
+            let scaler = cx
+                .new_scaler()
+                .size(16.0)
+                .hint(crate::Hinting::Full)
+                .build(&font);
+            assert!(scaler.is_hinted());
+        }
+    }
+
+    #[test]
+    fn recover_from_malformed_glyphs() {
+        use crate::{Error, ErrorPolicy};
+        // Corrupt the contour count of a glyph so that its data is too
+        // short.
+        let glyph_id = GlyphId::new(1);
+        let mut data = test_fonts::VAZIRMATN_VAR.to_vec();
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let glyf_offset = font
+            .table_record(Tag::new(b"glyf"))
+            .unwrap()
+            .offset()
+            .to_u32() as usize;
+        let glyph_offset = font.loca(None).unwrap().get_raw(1).unwrap() as usize;
+        data[glyf_offset + glyph_offset..][..2].copy_from_slice(&0x7FFFu16.to_be_bytes());
+        let font = FontRef::new(&data).unwrap();
+        let mut cx = Context::new();
+        let mut path = crate::test::Path::default();
+        let result = cx
+            .new_scaler()
+            .size(16.0)
+            .build(&font)
+            .outline(glyph_id, &mut path);
+        assert!(matches!(result, Err(Error::Read(_))));
+        let mut scaler = cx
+            .new_scaler()
+            .size(16.0)
+            .error_policy(ErrorPolicy::Recover)
+            .build(&font);
+        scaler.outline(glyph_id, &mut path).unwrap();
+        assert!(path.0.is_empty());
+        assert_eq!(scaler.warnings().len(), 1);
+        assert_eq!(scaler.warnings()[0].glyph_id, glyph_id);
+        // Glyphs that do not reference the corrupted glyph are unaffected and
+        // invalid identifiers still fail.
+        scaler.outline(GlyphId::new(3), &mut path).unwrap();
+        assert!(!path.0.is_empty());
+        assert!(scaler.outline(GlyphId::new(u16::MAX), &mut path).is_err());
+        assert_eq!(scaler.warnings().len(), 1);
+        scaler.clear_warnings();
+        assert!(scaler.warnings().is_empty());
+    }
+
+    #[cfg(feature = "hinting")]
+    #[test]
+    fn hinting_engine_selection() {
+        use super::{Hinting, HintingEngine, HintingOptions};
+        // The font has no hinting instructions, so the automatic engine
+        // selects the autohinter.
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let outlines = crate::test::parse_glyph_outlines(test_fonts::VAZIRMATN_VAR_GLYPHS);
+        let mut cx = Context::new();
+        let mut load = |hint: Option<HintingOptions>, glyph_id: GlyphId, size: f32| {
+            let mut path = crate::test::Path::default();
+            cx.new_scaler()
+                .font_id(Some(1))
+                .size(size)
+                .hint(hint)
+                .build(&font)
+                .outline(glyph_id, &mut path)
+                .unwrap();
+            path.0
+        };
+        let with_engine = |engine| HintingOptions {
+            mode: Hinting::Full,
+            engine,
+        };
+        let mut autohinted_count = 0;
+        for expected in &outlines {
+            let (glyph_id, size) = (expected.glyph_id, expected.size);
+            let unhinted = load(None, glyph_id, size);
+            let auto = load(Hinting::Full.into(), glyph_id, size);
+            let autohinter = load(Some(with_engine(HintingEngine::AutoHinter)), glyph_id, size);
+            let interpreter = load(
+                Some(with_engine(HintingEngine::Interpreter)),
+                glyph_id,
+                size,
+            );
+            assert_eq!(auto, autohinter);
+            assert_eq!(auto.len(), unhinted.len());
+            assert_eq!(interpreter.len(), unhinted.len());
+            if auto != unhinted {
+                autohinted_count += 1;
+            }
+            // Hinting only makes small adjustments.
+            for (hinted, unhinted) in auto.iter().zip(&unhinted) {
+                let (hinted, unhinted) = (hinted.coords(), unhinted.coords());
+                for (a, b) in hinted.iter().zip(unhinted) {
+                    assert!(
+                        (a - b).abs() < 2.0,
+                        "{a} {b} in glyph {glyph_id:?} at {size}"
+                    );
+                }
+            }
+        }
+        assert!(autohinted_count > 0);
+    }
 }
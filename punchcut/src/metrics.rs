@@ -0,0 +1,395 @@
+//! Font-wide metrics and vertical glyph metrics.
+
+use super::{GlyphId, NormalizedCoord};
+
+use read_fonts::{
+    tables::{glyf::Glyf, hmtx::Hmtx, hvar::Hvar, loca::Loca, mvar, vmtx::Vmtx, vvar::Vvar},
+    types::{Fixed, Tag},
+    FontData, ReadError, TableProvider,
+};
+
+/// Bit in the OS/2 `fsSelection` field indicating that the typographic
+/// metrics should be used for line spacing.
+const USE_TYPO_METRICS: u16 = 1 << 7;
+
+/// Position and thickness of a line decoration, such as an underline.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct Decoration {
+    /// Offset of the top of the line from the baseline. Positive values
+    /// are above the baseline.
+    pub offset: f32,
+    /// Thickness of the line.
+    pub thickness: f32,
+}
+
+/// Metrics for a font at a particular size and variation location.
+///
+/// Values are in pixels, or in font units for unscaled metrics. Vertical
+/// values follow the font coordinate system, so the descent is usually
+/// negative.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct Metrics {
+    /// Number of font units per em.
+    pub units_per_em: u16,
+    /// Distance from the baseline to the top of the line.
+    pub ascent: f32,
+    /// Distance from the baseline to the bottom of the line.
+    pub descent: f32,
+    /// Extra space between lines.
+    pub line_gap: f32,
+    /// Height of capital letters, if available.
+    pub cap_height: Option<f32>,
+    /// Height of lowercase letters, if available.
+    pub x_height: Option<f32>,
+    /// Underline position and thickness, if available.
+    pub underline: Option<Decoration>,
+    /// Strikeout position and thickness, if available.
+    pub strikeout: Option<Decoration>,
+}
+
+impl Metrics {
+    /// Creates metrics for the given font, size in pixels per em and
+    /// normalized variation coordinates.
+    ///
+    /// A size of 0.0 produces metrics in font units. Line metrics are
+    /// taken from the typographic values in `OS/2` when the font requests
+    /// it, and otherwise from `hhea`, falling back to `OS/2` when the
+    /// `hhea` values are zero. Deltas from `MVAR` are applied for variable
+    /// fonts.
+    pub fn new<'a>(
+        font: &impl TableProvider<'a>,
+        size: f32,
+        coords: &'a [NormalizedCoord],
+    ) -> Self {
+        let units_per_em = font.head().map(|head| head.units_per_em()).unwrap_or(0);
+        let scale = if size != 0.0 && units_per_em != 0 {
+            size.abs() / units_per_em as f32
+        } else {
+            1.0
+        };
+        let mvar = font.mvar().ok();
+        let delta = |tag: Tag| {
+            mvar.as_ref()
+                .filter(|_| !coords.is_empty())
+                .and_then(|mvar| mvar.metric_delta(tag, coords).ok())
+                .map(|delta| delta.to_f64() as f32)
+                .unwrap_or(0.0)
+        };
+        let value = |value: i32, tag: Tag| (value as f32 + delta(tag)) * scale;
+        let os2 = font.os2().ok();
+        let mut metrics = Self {
+            units_per_em,
+            ..Default::default()
+        };
+        let typo = os2.as_ref().map(|os2| {
+            (
+                value(os2.s_typo_ascender() as i32, mvar::tags::HASC),
+                value(os2.s_typo_descender() as i32, mvar::tags::HDSC),
+                value(os2.s_typo_line_gap() as i32, mvar::tags::HLGP),
+            )
+        });
+        let use_typo = os2
+            .as_ref()
+            .map(|os2| os2.fs_selection() & USE_TYPO_METRICS != 0)
+            .unwrap_or_default();
+        let hhea = font
+            .hhea()
+            .ok()
+            .filter(|hhea| hhea.ascender().to_i16() != 0 || hhea.descender().to_i16() != 0)
+            .map(|hhea| {
+                (
+                    value(hhea.ascender().to_i16() as i32, mvar::tags::HASC),
+                    value(hhea.descender().to_i16() as i32, mvar::tags::HDSC),
+                    value(hhea.line_gap().to_i16() as i32, mvar::tags::HLGP),
+                )
+            });
+        let line_metrics = if use_typo {
+            typo.or(hhea)
+        } else {
+            hhea.or(typo)
+        };
+        if let Some((ascent, descent, line_gap)) = line_metrics {
+            metrics.ascent = ascent;
+            metrics.descent = descent;
+            metrics.line_gap = line_gap;
+        } else if let Some(os2) = &os2 {
+            metrics.ascent = value(os2.us_win_ascent() as i32, mvar::tags::HCLA);
+            metrics.descent = -value(os2.us_win_descent() as i32, mvar::tags::HCLD);
+        }
+        if let Some(os2) = &os2 {
+            metrics.cap_height = os2
+                .s_cap_height()
+                .map(|height| value(height as i32, mvar::tags::CPHT));
+            metrics.x_height = os2
+                .sx_height()
+                .map(|height| value(height as i32, mvar::tags::XHGT));
+            metrics.strikeout = Some(Decoration {
+                offset: value(os2.y_strikeout_position() as i32, mvar::tags::STRO),
+                thickness: value(os2.y_strikeout_size() as i32, mvar::tags::STRS),
+            });
+        }
+        if let Ok(post) = font.post() {
+            metrics.underline = Some(Decoration {
+                offset: value(post.underline_position().to_i16() as i32, mvar::tags::UNDO),
+                thickness: value(post.underline_thickness().to_i16() as i32, mvar::tags::UNDS),
+            });
+        }
+        metrics
+    }
+}
+
+/// Metrics for positioning a glyph in vertical text.
+///
+/// Values are in pixels, or in font units for unscaled metrics. The origin
+/// is relative to the horizontal origin of the glyph and follows the font
+/// coordinate system, so it is usually above the baseline.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct VerticalMetrics {
+    /// Distance from the vertical origin of the glyph to the vertical
+    /// origin of the next glyph, downward.
+    pub advance: f32,
+    /// Horizontal position of the vertical origin.
+    pub origin_x: f32,
+    /// Vertical position of the vertical origin.
+    pub origin_y: f32,
+}
+
+/// Source of vertical metrics for the glyphs of a font at a particular
+/// size and variation location.
+#[derive(Clone)]
+pub(crate) struct VerticalLayout<'a> {
+    /// Scale factor from font units to pixels, or 1.0 for unscaled metrics.
+    scale: f32,
+    coords: &'a [NormalizedCoord],
+    units_per_em: u16,
+    /// Unscaled ascent, used when the font has no vertical origins.
+    ascent: f32,
+    hmtx: Option<Hmtx<'a>>,
+    hvar: Option<Hvar<'a>>,
+    vmtx: Option<Vmtx<'a>>,
+    vvar: Option<Vvar<'a>>,
+    vorg: Option<FontData<'a>>,
+    glyf: Option<(Loca<'a>, Glyf<'a>)>,
+}
+
+impl<'a> VerticalLayout<'a> {
+    pub fn new(font: &impl TableProvider<'a>, size: f32, coords: &'a [NormalizedCoord]) -> Self {
+        let unscaled = Metrics::new(font, 0.0, coords);
+        let units_per_em = unscaled.units_per_em;
+        let scale = if size != 0.0 && units_per_em != 0 {
+            size.abs() / units_per_em as f32
+        } else {
+            1.0
+        };
+        Self {
+            scale,
+            coords,
+            units_per_em,
+            ascent: unscaled.ascent,
+            hmtx: font.hmtx().ok(),
+            hvar: font.hvar().ok(),
+            vmtx: font.vmtx().ok(),
+            vvar: font.vvar().ok(),
+            vorg: font.data_for_tag(Tag::VORG),
+            glyf: font.loca(None).ok().zip(font.glyf().ok()),
+        }
+    }
+
+    /// Returns the vertical metrics for the specified glyph.
+    ///
+    /// The advance is taken from `vmtx`, or is the em size if the font has
+    /// no vertical metrics. The vertical origin is horizontally centered on
+    /// the advance width. Its height is taken from `VORG`, or is the top
+    /// side bearing from `vmtx` above the top of the glyph bounding box,
+    /// or is the ascent. Deltas from `HVAR` and `VVAR` are applied for
+    /// variable fonts.
+    pub fn get(&self, glyph_id: GlyphId) -> VerticalMetrics {
+        let coords = self.coords;
+        let delta = |delta: Option<Result<Fixed, ReadError>>| {
+            delta
+                .filter(|_| !coords.is_empty())
+                .and_then(|delta| delta.ok())
+                .map(|delta| delta.to_f64() as f32)
+                .unwrap_or(0.0)
+        };
+        let vvar = self.vvar.as_ref();
+        let advance_width = self
+            .hmtx
+            .as_ref()
+            .and_then(|hmtx| hmtx.advance(glyph_id))
+            .unwrap_or(0) as f32
+            + delta(
+                self.hvar
+                    .as_ref()
+                    .map(|hvar| hvar.advance_width_delta(glyph_id, coords)),
+            );
+        let advance = match self.vmtx.as_ref().and_then(|vmtx| vmtx.advance(glyph_id)) {
+            Some(advance) => {
+                advance as f32 + delta(vvar.map(|vvar| vvar.advance_height_delta(glyph_id, coords)))
+            }
+            None => self.units_per_em as f32,
+        };
+        let origin_y =
+            if let Some(origin_y) = self.vorg.and_then(|vorg| vorg_origin(vorg, glyph_id)) {
+                origin_y as f32 + delta(vvar.map(|vvar| vvar.v_org_delta(glyph_id, coords)))
+            } else if let Some((tsb, y_max)) = self
+                .vmtx
+                .as_ref()
+                .and_then(|vmtx| vmtx.side_bearing(glyph_id))
+                .zip(self.y_max(glyph_id))
+            {
+                tsb as f32 + y_max as f32 + delta(vvar.map(|vvar| vvar.tsb_delta(glyph_id, coords)))
+            } else {
+                self.ascent
+            };
+        VerticalMetrics {
+            advance: advance * self.scale,
+            origin_x: advance_width * 0.5 * self.scale,
+            origin_y: origin_y * self.scale,
+        }
+    }
+
+    /// Returns the top of the bounding box of a TrueType glyph.
+    fn y_max(&self, glyph_id: GlyphId) -> Option<i16> {
+        let (loca, glyf) = self.glyf.as_ref()?;
+        match loca.get_glyf(glyph_id, glyf).ok()? {
+            Some(glyph) => Some(glyph.y_max()),
+            // Empty glyphs have no bounding box, so the side bearing is
+            // measured from the baseline.
+            None => Some(0),
+        }
+    }
+}
+
+/// Returns the height of the vertical origin for a glyph from the `VORG`
+/// table.
+fn vorg_origin(vorg: FontData, glyph_id: GlyphId) -> Option<i16> {
+    let default_origin = vorg.read_at::<i16>(4).ok()?;
+    let count = vorg.read_at::<u16>(6).ok()? as usize;
+    // Records of glyph identifier and origin, sorted by glyph identifier.
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let offset = 8 + mid * 4;
+        let gid = vorg.read_at::<u16>(offset).ok()?;
+        match gid.cmp(&glyph_id.to_u16()) {
+            core::cmp::Ordering::Less => lo = mid + 1,
+            core::cmp::Ordering::Greater => hi = mid,
+            core::cmp::Ordering::Equal => return vorg.read_at::<i16>(offset + 2).ok(),
+        }
+    }
+    Some(default_origin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font::FontRef;
+    use read_fonts::test_data::test_fonts;
+
+    #[test]
+    fn scaled_metrics() {
+        // The font has no OS/2 or post tables, so line metrics come from
+        // hhea and the remaining metrics are missing.
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let hhea = font.hhea().unwrap();
+        let unscaled = Metrics::new(&font, 0.0, &[]);
+        assert_eq!(unscaled.units_per_em, font.head().unwrap().units_per_em());
+        assert_eq!(unscaled.ascent, hhea.ascender().to_i16() as f32);
+        assert_eq!(unscaled.descent, hhea.descender().to_i16() as f32);
+        assert_eq!(unscaled.line_gap, hhea.line_gap().to_i16() as f32);
+        assert_eq!(unscaled.cap_height, None);
+        assert_eq!(unscaled.underline, None);
+        let size = 16.0;
+        let scaled = Metrics::new(&font, size, &[]);
+        let scale = size / unscaled.units_per_em as f32;
+        assert_eq!(scaled.ascent, unscaled.ascent * scale);
+        assert_eq!(scaled.descent, unscaled.descent * scale);
+        assert_eq!(scaled.line_gap, unscaled.line_gap * scale);
+    }
+
+    /// A font with added or replaced tables.
+    struct VerticalFont {
+        font: FontRef<'static>,
+        tables: Vec<(Tag, Vec<u8>)>,
+    }
+
+    impl<'a> TableProvider<'a> for &'a VerticalFont {
+        fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>> {
+            self.tables
+                .iter()
+                .find(|(table_tag, _)| *table_tag == tag)
+                .map(|(_, data)| FontData::new(data))
+                .or_else(|| self.font.data_for_tag(tag))
+        }
+    }
+
+    /// Builds `vhea` and `vmtx` tables with a single long metric with the
+    /// given advance and top side bearing, shared by all glyphs.
+    fn make_vmtx(glyph_count: u16, advance: u16, tsb: i16) -> Vec<(Tag, Vec<u8>)> {
+        let mut vhea = vec![0; 36];
+        vhea[..4].copy_from_slice(&[0, 1, 0x10, 0]);
+        vhea[34..].copy_from_slice(&1u16.to_be_bytes());
+        let mut vmtx = advance.to_be_bytes().to_vec();
+        for _ in 0..glyph_count {
+            vmtx.extend(tsb.to_be_bytes());
+        }
+        vec![(Tag::new(b"vhea"), vhea), (Tag::new(b"vmtx"), vmtx)]
+    }
+
+    #[test]
+    fn vertical_metrics() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let glyph_id = GlyphId::new(1);
+        let upem = font.head().unwrap().units_per_em() as f32;
+        let advance_width = font.hmtx().unwrap().advance(glyph_id).unwrap() as f32;
+        let ascent = font.hhea().unwrap().ascender().to_i16() as f32;
+        // Without vertical tables, the advance is the em size and the
+        // origin is at the ascent.
+        let metrics = VerticalLayout::new(&font, 0.0, &[]).get(glyph_id);
+        assert_eq!(
+            metrics,
+            VerticalMetrics {
+                advance: upem,
+                origin_x: advance_width / 2.0,
+                origin_y: ascent,
+            }
+        );
+        let scaled = VerticalLayout::new(&font, 20.0, &[]).get(glyph_id);
+        assert_eq!(scaled.advance, 20.0);
+        assert_eq!(scaled.origin_y, ascent * 20.0 / upem);
+        // The top side bearing is measured from the top of the glyph.
+        let glyph_count = font.maxp().unwrap().num_glyphs();
+        let mut font = VerticalFont {
+            font,
+            tables: make_vmtx(glyph_count, 1200, 100),
+        };
+        let y_max = font
+            .font
+            .loca(None)
+            .unwrap()
+            .get_glyf(glyph_id, &font.font.glyf().unwrap())
+            .unwrap()
+            .unwrap()
+            .y_max();
+        let metrics = VerticalLayout::new(&&font, 0.0, &[]).get(glyph_id);
+        assert_eq!(metrics.advance, 1200.0);
+        assert_eq!(metrics.origin_y, y_max as f32 + 100.0);
+        // Origins in VORG take precedence, with a default for glyphs
+        // without a record.
+        let mut vorg = vec![0, 1, 0, 0, 3, 0x20, 0, 1];
+        vorg.extend([0, 1, 3, 0x84]);
+        font.tables.push((Tag::new(b"VORG"), vorg));
+        let layout = VerticalLayout::new(&&font, 0.0, &[]);
+        assert_eq!(layout.get(glyph_id).origin_y, 900.0);
+        assert_eq!(layout.get(GlyphId::new(2)).origin_y, 800.0);
+    }
+
+    #[test]
+    fn scaler_metrics() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut cx = crate::Context::new();
+        let scaler = cx.new_scaler().size(20.0).build(&font);
+        assert_eq!(scaler.metrics(), Metrics::new(&font, 20.0, &[]));
+    }
+}
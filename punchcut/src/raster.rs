@@ -0,0 +1,760 @@
+//! Scan conversion of outlines to coverage masks.
+//!
+//! Antialiased masks use the exact area of each pixel covered by the
+//! outline, computed with an accumulation buffer in the style of font-rs.
+//! Monochrome masks turn on the pixels whose centers are inside the outline
+//! and can optionally apply drop-out control to preserve features thinner
+//! than a pixel. Subpixel masks are rendered at three times the resolution
+//! in one direction and filtered to reduce color fringes. Distance field
+//! masks store the signed distance from each pixel to the outline.
+
+mod distance;
+
+use super::Pen;
+
+use read_fonts::types::{Point, Rect};
+
+/// Maximum distance in pixels between a curve and the lines used to
+/// approximate it.
+const FLATNESS: f32 = 0.05;
+
+/// Maximum number of lines used to approximate a single curve.
+const MAX_CURVE_SEGMENTS: usize = 100;
+
+/// Weights of the FIR filter applied to subpixel masks, in units of
+/// 1/256.
+///
+/// This is `FT_LCD_FILTER_DEFAULT`.
+const LCD_FILTER: [u32; 5] = [0x08, 0x4D, 0x56, 0x4D, 0x08];
+
+/// Default width of the range of distances in distance field masks, in
+/// pixels.
+const DEFAULT_DISTANCE_RANGE: f32 = 4.0;
+
+/// Modes for rendering outlines.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum RenderMode {
+    /// Each pixel has a value proportional to the area covered by the
+    /// outline. This is the default mode.
+    #[default]
+    Antialiased,
+    /// Each pixel is either fully on or off, based on whether its center
+    /// is inside the outline.
+    Monochrome,
+    /// Each pixel has separate coverage values for horizontally arranged
+    /// red, green and blue subpixels, for LCD displays.
+    Lcd,
+    /// Each pixel has separate coverage values for vertically arranged
+    /// red, green and blue subpixels, from top to bottom.
+    LcdVertical,
+    /// Each pixel has the signed distance from its center to the outline.
+    Sdf,
+    /// Each pixel has three signed distances to subsets of the edges of
+    /// the outline, chosen so that the median of the three preserves
+    /// sharp corners.
+    Msdf,
+}
+
+impl RenderMode {
+    fn format(self) -> MaskFormat {
+        match self {
+            Self::Antialiased | Self::Monochrome => MaskFormat::Alpha,
+            Self::Lcd | Self::LcdVertical => MaskFormat::Subpixel,
+            Self::Sdf => MaskFormat::Sdf,
+            Self::Msdf => MaskFormat::Msdf,
+        }
+    }
+}
+
+/// Layout of the pixels in a mask.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum MaskFormat {
+    /// A single coverage value per pixel.
+    #[default]
+    Alpha,
+    /// Red, green and blue coverage values per pixel.
+    Subpixel,
+    /// A single distance value per pixel.
+    Sdf,
+    /// Three distance values per pixel.
+    Msdf,
+}
+
+impl MaskFormat {
+    /// Returns the number of bytes per pixel.
+    pub fn channels(self) -> usize {
+        match self {
+            Self::Alpha | Self::Sdf => 1,
+            Self::Subpixel | Self::Msdf => 3,
+        }
+    }
+}
+
+/// Options for rendering outlines.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RasterOptions {
+    /// Rendering mode.
+    pub mode: RenderMode,
+    /// Turns on pixels for parts of the outline that are thinner than a
+    /// pixel and would otherwise be lost. Only applies to the monochrome
+    /// mode.
+    pub dropout_control: bool,
+    /// Width of the range of distances that can be represented in distance
+    /// field masks, in pixels. The outline is at the middle of the range
+    /// and the mask is extended by half of the range on each side. The
+    /// default is 4.0.
+    pub distance_range: f32,
+}
+
+impl Default for RasterOptions {
+    fn default() -> Self {
+        Self {
+            mode: RenderMode::default(),
+            dropout_control: false,
+            distance_range: DEFAULT_DISTANCE_RANGE,
+        }
+    }
+}
+
+impl From<RenderMode> for RasterOptions {
+    fn from(mode: RenderMode) -> Self {
+        Self {
+            mode,
+            ..Default::default()
+        }
+    }
+}
+
+/// Coverage mask produced by rendering an outline.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Mask {
+    /// Distance from the glyph origin to the left edge of the mask, in
+    /// pixels.
+    pub left: i32,
+    /// Distance from the baseline to the top edge of the mask, in pixels.
+    pub top: i32,
+    /// Width of the mask in pixels.
+    pub width: u32,
+    /// Height of the mask in pixels.
+    pub height: u32,
+    /// Layout of the pixels.
+    pub format: MaskFormat,
+    /// Coverage or distance values with rows from top to bottom and the
+    /// number of bytes per pixel given by the format.
+    ///
+    /// Distances are mapped so that 0 and 255 are the ends of the distance
+    /// range, with values above 127.5 inside the outline.
+    pub data: Vec<u8>,
+}
+
+impl Mask {
+    /// Creates a new empty mask.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the coverage values of the pixel at the given column and
+    /// row, or an empty slice if the position is outside the mask.
+    pub fn pixel(&self, x: u32, y: u32) -> &[u8] {
+        if x >= self.width || y >= self.height {
+            return &[];
+        }
+        let channels = self.format.channels();
+        let start = (y * self.width + x) as usize * channels;
+        self.data.get(start..start + channels).unwrap_or_default()
+    }
+}
+
+/// Line in pixel coordinates.
+#[derive(Copy, Clone, Debug)]
+struct Line {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    /// True if this is the first line of a path command.
+    is_segment_start: bool,
+}
+
+/// Scan converter that renders the path received as a [`Pen`] into a
+/// coverage or distance field mask.
+///
+/// Curves are approximated by lines and contours are implicitly closed.
+/// The nonzero winding rule is used to determine the inside of the
+/// outline.
+#[derive(Clone, Default, Debug)]
+pub struct Rasterizer {
+    lines: Vec<Line>,
+    /// End of the range of lines for each contour.
+    contours: Vec<usize>,
+    /// True if the next line starts a path command.
+    is_segment_start: bool,
+    start: (f32, f32),
+    current: (f32, f32),
+    /// Bounds of the path.
+    bounds: Option<Rect<f32>>,
+    /// Storage for coverage accumulation.
+    accumulation: Vec<f32>,
+    /// Storage for scanline crossings.
+    crossings: Vec<(f32, i32)>,
+    /// Storage for unfiltered subpixel coverage.
+    subpixels: Vec<u8>,
+    /// Storage for the colors of the edges of distance fields.
+    edges: Vec<distance::Edge>,
+}
+
+impl Rasterizer {
+    /// Creates a new rasterizer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes the current path.
+    pub fn reset(&mut self) {
+        self.lines.clear();
+        self.contours.clear();
+        self.start = (0.0, 0.0);
+        self.current = (0.0, 0.0);
+        self.bounds = None;
+    }
+
+    /// Renders the current path into the given mask.
+    ///
+    /// The mask is resized to the pixel bounds of the path, which are
+    /// extended by a pixel on each side in the subpixel direction for the
+    /// subpixel modes. An empty path produces an empty mask.
+    pub fn render(&mut self, options: RasterOptions, mask: &mut Mask) {
+        self.close();
+        mask.data.clear();
+        let Some(bounds) = self.bounds else {
+            *mask = Mask::default();
+            return;
+        };
+        let mode = options.mode;
+        // Padding for the subpixel filter, along with the resolution of
+        // the coverage computation relative to pixels.
+        let ((pad_x, scale_x), (pad_y, scale_y)) = match mode {
+            RenderMode::Lcd => ((1.0, 3.0), (0.0, 1.0)),
+            RenderMode::LcdVertical => ((0.0, 1.0), (1.0, 3.0)),
+            RenderMode::Sdf | RenderMode::Msdf => {
+                let pad = (options.distance_range.max(0.0) * 0.5).ceil();
+                ((pad, 1.0), (pad, 1.0))
+            }
+            _ => ((0.0, 1.0), (0.0, 1.0)),
+        };
+        let left = bounds.x_min.floor() - pad_x;
+        let top = bounds.y_max.ceil() + pad_y;
+        let width = (bounds.x_max.ceil() + pad_x - left) as usize;
+        let height = (top - bounds.y_min.floor() + pad_y) as usize;
+        mask.left = left as i32;
+        mask.top = top as i32;
+        mask.width = width as u32;
+        mask.height = height as u32;
+        mask.format = mode.format();
+        mask.data.resize(width * height * mask.format.channels(), 0);
+        // Translate to the mask with rows from top to bottom.
+        for line in &mut self.lines {
+            line.x0 = (line.x0 - left) * scale_x;
+            line.x1 = (line.x1 - left) * scale_x;
+            line.y0 = (top - line.y0) * scale_y;
+            line.y1 = (top - line.y1) * scale_y;
+        }
+        match mode {
+            RenderMode::Antialiased => self.fill_exact(width, height, &mut mask.data),
+            RenderMode::Monochrome => {
+                self.fill_centers(width, height, options.dropout_control, &mut mask.data)
+            }
+            RenderMode::Lcd | RenderMode::LcdVertical => {
+                let (sub_width, sub_height) = (width * scale_x as usize, height * scale_y as usize);
+                let mut subpixels = core::mem::take(&mut self.subpixels);
+                subpixels.clear();
+                subpixels.resize(sub_width * sub_height, 0);
+                self.fill_exact(sub_width, sub_height, &mut subpixels);
+                if mode == RenderMode::Lcd {
+                    // Subpixels of a pixel are adjacent in each row, as in
+                    // the mask.
+                    for (src, dst) in subpixels
+                        .chunks_exact(sub_width)
+                        .zip(mask.data.chunks_exact_mut(sub_width))
+                    {
+                        filter(src.len(), |i| src[i], |i, value| dst[i] = value);
+                    }
+                } else {
+                    // Each column is filtered vertically and the rows of
+                    // subpixels are interleaved in the mask.
+                    for column in 0..width {
+                        filter(
+                            sub_height,
+                            |i| subpixels[i * width + column],
+                            |i, value| mask.data[((i / 3) * width + column) * 3 + i % 3] = value,
+                        );
+                    }
+                }
+                self.subpixels = subpixels;
+            }
+            RenderMode::Sdf => {
+                // The sign of each distance is taken from the pixels with
+                // centers inside the outline.
+                self.fill_centers(width, height, false, &mut mask.data);
+                distance::fill_sdf(&self.lines, width, options.distance_range, &mut mask.data);
+            }
+            RenderMode::Msdf => {
+                distance::color_edges(&self.lines, &self.contours, &mut self.edges);
+                distance::fill_msdf(
+                    &self.lines,
+                    &self.edges,
+                    width,
+                    options.distance_range,
+                    &mut mask.data,
+                );
+            }
+        }
+        self.reset();
+    }
+
+    /// Fills the mask with the exact area coverage of each pixel.
+    fn fill_exact(&mut self, width: usize, height: usize, data: &mut [u8]) {
+        let accumulation = &mut self.accumulation;
+        accumulation.clear();
+        // Coverage at the right edge of the last row spills past the end.
+        accumulation.resize(width * height + 2, 0.0);
+        for line in &self.lines {
+            accumulate_line(accumulation, width, height, line);
+        }
+        let mut sum = 0.0;
+        for (value, coverage) in data.iter_mut().zip(accumulation.iter()) {
+            sum += coverage;
+            *value = (sum.abs().min(1.0) * 255.0 + 0.5) as u8;
+        }
+    }
+
+    /// Fills the mask by turning on the pixels with centers inside the
+    /// outline.
+    ///
+    /// With drop-out control, a span between two crossings of a scanline
+    /// that contains no pixel centers turns on the pixel closest to its
+    /// middle. Both horizontal and vertical scanlines are checked.
+    fn fill_centers(&mut self, width: usize, height: usize, dropout: bool, data: &mut [u8]) {
+        let crossings = &mut self.crossings;
+        for row in 0..height {
+            let y = row as f32 + 0.5;
+            scanline_crossings(&self.lines, y, false, crossings);
+            for_each_span(crossings, |x0, x1| {
+                let start = (x0 - 0.5).ceil().max(0.0) as usize;
+                let end = ((x1 - 0.5).ceil().max(0.0) as usize).min(width);
+                let line = &mut data[row * width..(row + 1) * width];
+                if start < end {
+                    line[start..end].fill(255);
+                } else if dropout {
+                    line[dropout_pixel(x0, x1, width)] = 255;
+                }
+            });
+        }
+        if !dropout {
+            return;
+        }
+        for column in 0..width {
+            let x = column as f32 + 0.5;
+            scanline_crossings(&self.lines, x, true, crossings);
+            for_each_span(crossings, |y0, y1| {
+                let start = (y0 - 0.5).ceil().max(0.0) as usize;
+                let end = ((y1 - 0.5).ceil().max(0.0) as usize).min(height);
+                if start >= end {
+                    data[dropout_pixel(y0, y1, height) * width + column] = 255;
+                }
+            });
+        }
+    }
+
+    fn add_line(&mut self, x0: f32, y0: f32, x1: f32, y1: f32) {
+        if (x0, y0) == (x1, y1) {
+            return;
+        }
+        self.lines.push(Line {
+            x0,
+            y0,
+            x1,
+            y1,
+            is_segment_start: core::mem::take(&mut self.is_segment_start),
+        });
+    }
+
+    fn add_segment_line(&mut self, x: f32, y: f32) {
+        self.add_point(x, y);
+        let (x0, y0) = self.current;
+        self.add_line(x0, y0, x, y);
+        self.current = (x, y);
+    }
+
+    fn add_point(&mut self, x: f32, y: f32) {
+        self.bounds
+            .get_or_insert(Rect::new(x, y, x, y))
+            .include_point(Point::new(x, y));
+    }
+}
+
+impl Pen for Rasterizer {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.add_point(x, y);
+        self.start = (x, y);
+        self.current = (x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.is_segment_start = true;
+        self.add_segment_line(x, y);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.is_segment_start = true;
+        let (x0, y0) = self.current;
+        let (dx, dy) = (x0 - 2.0 * cx0 + x, y0 - 2.0 * cy0 + y);
+        let count = segment_count((dx * dx + dy * dy).sqrt() / 4.0);
+        for i in 1..count {
+            let t = i as f32 / count as f32;
+            let mt = 1.0 - t;
+            let (a, b, c) = (mt * mt, 2.0 * mt * t, t * t);
+            self.add_segment_line(a * x0 + b * cx0 + c * x, a * y0 + b * cy0 + c * y);
+        }
+        self.add_segment_line(x, y);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.is_segment_start = true;
+        let (x0, y0) = self.current;
+        let (dx0, dy0) = (x0 - 2.0 * cx0 + cx1, y0 - 2.0 * cy0 + cy1);
+        let (dx1, dy1) = (cx0 - 2.0 * cx1 + x, cy0 - 2.0 * cy1 + y);
+        let dd = (dx0 * dx0 + dy0 * dy0).max(dx1 * dx1 + dy1 * dy1);
+        let count = segment_count(dd.sqrt() * 0.75);
+        for i in 1..count {
+            let t = i as f32 / count as f32;
+            let mt = 1.0 - t;
+            let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+            self.add_segment_line(
+                a * x0 + b * cx0 + c * cx1 + d * x,
+                a * y0 + b * cy0 + c * cy1 + d * y,
+            );
+        }
+        self.add_segment_line(x, y);
+    }
+
+    fn close(&mut self) {
+        if self.current != self.start {
+            let (x, y) = self.start;
+            self.line_to(x, y);
+        }
+        if self.lines.len() > self.contours.last().copied().unwrap_or_default() {
+            self.contours.push(self.lines.len());
+        }
+    }
+}
+
+/// Returns the number of lines needed to approximate a curve with the
+/// given maximum deviation from its chord.
+fn segment_count(deviation: f32) -> usize {
+    // The deviation decreases with the square of the number of segments.
+    ((deviation / FLATNESS).sqrt().ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS)
+}
+
+/// Adds the signed area covered by a line to the accumulation buffer.
+///
+/// This is the `draw_line` function of font-rs.
+fn accumulate_line(accumulation: &mut [f32], width: usize, height: usize, line: &Line) {
+    let (x0, y0, x1, y1) = (line.x0, line.y0, line.x1, line.y1);
+    if (y0 - y1).abs() <= f32::EPSILON {
+        return;
+    }
+    let (dir, (x0, y0), (_, y1)) = if y0 < y1 {
+        (1.0, (x0, y0), (x1, y1))
+    } else {
+        (-1.0, (x1, y1), (x0, y0))
+    };
+    let dxdy = (line.x1 - line.x0) / (line.y1 - line.y0);
+    let mut x = x0;
+    if y0 < 0.0 {
+        x -= y0 * dxdy;
+    }
+    let row_start = y0.max(0.0) as usize;
+    let row_end = (y1.ceil().max(0.0) as usize).min(height);
+    for row in row_start..row_end {
+        let line_start = row * width;
+        let dy = ((row + 1) as f32).min(y1) - (row as f32).max(y0);
+        let x_next = x + dxdy * dy;
+        let d = dy * dir;
+        let (xa, xb) = if x < x_next { (x, x_next) } else { (x_next, x) };
+        // Clamp to the left edge to tolerate rounding at the bounds.
+        let (xa, xb) = (xa.max(0.0), xb.max(0.0));
+        let xa_floor = xa.floor();
+        let xa_i = xa_floor as usize;
+        let xb_ceil = xb.ceil();
+        let xb_i = xb_ceil as usize;
+        let index = line_start + xa_i;
+        if xb_i <= xa_i + 1 {
+            // The line is within a single pixel.
+            let xm = 0.5 * (xa + xb) - xa_floor;
+            accumulation[index] += d - d * xm;
+            accumulation[index + 1] += d * xm;
+        } else {
+            let s = (xb - xa).recip();
+            let xa_f = xa - xa_floor;
+            let a0 = 0.5 * s * (1.0 - xa_f) * (1.0 - xa_f);
+            let xb_f = xb - xb_ceil + 1.0;
+            let am = 0.5 * s * xb_f * xb_f;
+            accumulation[index] += d * a0;
+            if xb_i == xa_i + 2 {
+                accumulation[index + 1] += d * (1.0 - a0 - am);
+            } else {
+                let a1 = s * (1.5 - xa_f);
+                accumulation[index + 1] += d * (a1 - a0);
+                for coverage in &mut accumulation[index + 2..line_start + xb_i - 1] {
+                    *coverage += d * s;
+                }
+                let a2 = a1 + (xb_i - xa_i - 3) as f32 * s;
+                accumulation[line_start + xb_i - 1] += d * (1.0 - a2 - am);
+            }
+            accumulation[line_start + xb_i] += d * am;
+        }
+        x = x_next;
+    }
+}
+
+/// Computes the sorted crossings of the lines with a horizontal scanline
+/// at the given y, or a vertical scanline at the given x if `vertical` is
+/// true, along with the winding direction of each crossing.
+fn scanline_crossings(lines: &[Line], pos: f32, vertical: bool, crossings: &mut Vec<(f32, i32)>) {
+    crossings.clear();
+    for line in lines {
+        let (u0, v0, u1, v1) = if vertical {
+            (line.y0, line.x0, line.y1, line.x1)
+        } else {
+            (line.x0, line.y0, line.x1, line.y1)
+        };
+        // Half open ranges avoid counting shared endpoints twice.
+        let (dir, lo, hi) = if v0 < v1 { (1, v0, v1) } else { (-1, v1, v0) };
+        if pos < lo || pos >= hi {
+            continue;
+        }
+        let u = u0 + (pos - v0) * (u1 - u0) / (v1 - v0);
+        crossings.push((u, dir));
+    }
+    crossings.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+}
+
+/// Invokes the callback with the start and end of each span of a scanline
+/// that is inside the outline, using the nonzero winding rule.
+fn for_each_span(crossings: &[(f32, i32)], mut f: impl FnMut(f32, f32)) {
+    let mut winding = 0;
+    let mut start = 0.0;
+    for &(pos, dir) in crossings {
+        let was_inside = winding != 0;
+        winding += dir;
+        if !was_inside && winding != 0 {
+            start = pos;
+        } else if was_inside && winding == 0 {
+            f(start, pos);
+        }
+    }
+}
+
+/// Applies the subpixel filter to a sequence of values read with the
+/// given function and writes the results with the other.
+fn filter(len: usize, get: impl Fn(usize) -> u8, mut set: impl FnMut(usize, u8)) {
+    for i in 0..len {
+        let sum = LCD_FILTER
+            .iter()
+            .enumerate()
+            .filter_map(|(k, weight)| {
+                let j = (i + k).checked_sub(2).filter(|&j| j < len)?;
+                Some(weight * get(j) as u32)
+            })
+            .sum::<u32>();
+        set(i, (sum / 256).min(255) as u8);
+    }
+}
+
+/// Returns the pixel to turn on for a span that contains no pixel centers.
+fn dropout_pixel(start: f32, end: f32, len: usize) -> usize {
+    ((0.5 * (start + end)).floor().max(0.0) as usize).min(len.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(options: RasterOptions, contours: &[&[(f32, f32)]]) -> Mask {
+        let mut rasterizer = Rasterizer::new();
+        for contour in contours {
+            rasterizer.move_to(contour[0].0, contour[0].1);
+            for &(x, y) in &contour[1..] {
+                rasterizer.line_to(x, y);
+            }
+            rasterizer.close();
+        }
+        let mut mask = Mask::new();
+        rasterizer.render(options, &mut mask);
+        mask
+    }
+
+    #[test]
+    fn empty_path() {
+        let mut mask = Mask {
+            width: 2,
+            height: 2,
+            data: vec![0; 4],
+            ..Default::default()
+        };
+        Rasterizer::new().render(RasterOptions::default(), &mut mask);
+        assert_eq!(mask, Mask::default());
+    }
+
+    #[test]
+    fn exact_area() {
+        // Square covering half of the pixels on each edge.
+        let square = [(0.5, 0.5), (2.5, 0.5), (2.5, 2.5), (0.5, 2.5)];
+        let mask = render(RasterOptions::default(), &[&square]);
+        assert_eq!((mask.left, mask.top, mask.width, mask.height), (0, 3, 3, 3));
+        #[rustfmt::skip]
+        let expected = [
+            64, 128, 64,
+            128, 255, 128,
+            64, 128, 64,
+        ];
+        assert_eq!(mask.data, expected);
+    }
+
+    #[test]
+    fn winding_rule() {
+        let outer = [(0.0, 0.0), (0.0, 3.0), (3.0, 3.0), (3.0, 0.0)];
+        let same = [(1.0, 1.0), (1.0, 2.0), (2.0, 2.0), (2.0, 1.0)];
+        let reversed = [(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)];
+        for mode in [RenderMode::Antialiased, RenderMode::Monochrome] {
+            // Overlapping contours in the same direction are filled.
+            let mask = render(mode.into(), &[&outer, &same]);
+            assert!(mask.data.iter().all(|&value| value == 255));
+            // A reversed contour is a hole.
+            let mask = render(mode.into(), &[&outer, &reversed]);
+            assert_eq!(mask.pixel(1, 1), [0]);
+            assert_eq!(mask.data.iter().filter(|&&value| value == 255).count(), 8);
+        }
+    }
+
+    #[test]
+    fn curve_area() {
+        // Circle approximated by four quadratic curves.
+        let mut rasterizer = Rasterizer::new();
+        let (r, c) = (8.0, 10.0);
+        rasterizer.move_to(c + r, c);
+        rasterizer.quad_to(c + r, c + r, c, c + r);
+        rasterizer.quad_to(c - r, c + r, c - r, c);
+        rasterizer.quad_to(c - r, c - r, c, c - r);
+        rasterizer.quad_to(c + r, c - r, c + r, c);
+        let mut mask = Mask::new();
+        rasterizer.render(RasterOptions::default(), &mut mask);
+        let area = mask
+            .data
+            .iter()
+            .map(|&value| value as f32 / 255.0)
+            .sum::<f32>();
+        // Each quadratic covers 2/3 of the area between its chord and
+        // the corner of the square.
+        let expected = 4.0 * r * r - 4.0 * (r * r / 2.0) / 3.0;
+        // Flattening the curves loses a small amount of area.
+        assert!((area - expected).abs() < 2.0, "{area} != {expected}");
+    }
+
+    #[test]
+    fn dropout_control() {
+        // Vertical and horizontal stems that are too thin to cover any
+        // pixel centers.
+        let vertical = [(1.1, 0.0), (1.1, 4.0), (1.3, 4.0), (1.3, 0.0)];
+        let horizontal = [(0.0, 1.1), (4.0, 1.1), (4.0, 1.3), (0.0, 1.3)];
+        for stem in [vertical, horizontal] {
+            let mask = render(RenderMode::Monochrome.into(), &[&stem]);
+            assert!(mask.data.iter().all(|&value| value == 0));
+            let options = RasterOptions {
+                mode: RenderMode::Monochrome,
+                dropout_control: true,
+                ..Default::default()
+            };
+            let mask = render(options, &[&stem]);
+            assert_eq!(mask.data.iter().filter(|&&value| value == 255).count(), 4);
+        }
+    }
+    #[test]
+    fn subpixel_filter() {
+        // Stem covering the middle third of the second pixel.
+        let stem = [
+            (4.0 / 3.0, 0.0),
+            (4.0 / 3.0, 1.0),
+            (5.0 / 3.0, 1.0),
+            (5.0 / 3.0, 0.0),
+        ];
+        let mask = render(RenderMode::Lcd.into(), &[&stem]);
+        // The mask is padded by a pixel on each side.
+        assert_eq!((mask.left, mask.top, mask.width, mask.height), (0, 1, 3, 1));
+        assert_eq!(mask.format, MaskFormat::Subpixel);
+        assert_eq!(mask.data.len(), 9);
+        // The coverage of the green subpixel is spread over its neighbors.
+        let expected = LCD_FILTER.map(|weight| (weight * 255 / 256) as u8);
+        assert_eq!(mask.pixel(0, 0), [0, 0, expected[0]]);
+        assert_eq!(mask.pixel(1, 0), &expected[1..4]);
+        assert_eq!(mask.pixel(2, 0), [expected[4], 0, 0]);
+        // The same stem rotated renders vertically arranged subpixels.
+        let stem = stem.map(|(x, y)| (y, 3.0 - x));
+        let mask = render(RenderMode::LcdVertical.into(), &[&stem]);
+        assert_eq!((mask.left, mask.top, mask.width, mask.height), (0, 3, 1, 3));
+        assert_eq!(mask.pixel(0, 0), [0, 0, expected[0]]);
+        assert_eq!(mask.pixel(0, 1), &expected[1..4]);
+        assert_eq!(mask.pixel(0, 2), [expected[4], 0, 0]);
+    }
+    #[test]
+    fn distance_fields() {
+        let square = [(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)];
+        let options = RasterOptions {
+            mode: RenderMode::Sdf,
+            distance_range: 4.0,
+            ..Default::default()
+        };
+        let sdf = render(options, &[&square]);
+        // The mask is extended by half of the range on each side.
+        assert_eq!((sdf.left, sdf.top, sdf.width, sdf.height), (-2, 12, 14, 14));
+        assert_eq!(sdf.format, MaskFormat::Sdf);
+        // Pixels half a pixel inside and outside of the left edge.
+        assert_eq!(sdf.pixel(2, 7), [159]);
+        assert_eq!(sdf.pixel(1, 7), [96]);
+        // Distances beyond the range are clamped.
+        assert_eq!(sdf.pixel(7, 7), [255]);
+        assert_eq!(sdf.pixel(0, 0), [0]);
+        let msdf = render(
+            RasterOptions {
+                mode: RenderMode::Msdf,
+                ..options
+            },
+            &[&square],
+        );
+        assert_eq!(msdf.format, MaskFormat::Msdf);
+        assert_eq!(msdf.data.len(), sdf.data.len() * 3);
+        let median = |pixel: &[u8]| {
+            let (a, b, c) = (pixel[0], pixel[1], pixel[2]);
+            a.min(b).max(a.max(b).min(c))
+        };
+        for y in 0..sdf.height {
+            for x in 0..sdf.width {
+                let (sdf, msdf) = (sdf.pixel(x, y)[0], median(msdf.pixel(x, y)));
+                let is_corner = !(2..12).contains(&x) && !(2..12).contains(&y);
+                if is_corner {
+                    // Outside of the corners, the median is the distance
+                    // to the extended edges, which keeps them sharp.
+                    assert!(msdf >= sdf, "({x}, {y}): {msdf} < {sdf}");
+                } else {
+                    assert!(msdf.abs_diff(sdf) <= 1, "({x}, {y}): {msdf} != {sdf}");
+                }
+            }
+        }
+        // Pixel diagonally outside of a corner by half a pixel in each
+        // direction.
+        assert_eq!(median(msdf.pixel(1, 1)), 96);
+        assert!(sdf.pixel(1, 1)[0] < 96);
+    }
+}
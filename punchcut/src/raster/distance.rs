@@ -0,0 +1,234 @@
+//! Signed and multi-channel signed distance fields.
+//!
+//! The multi-channel construction follows msdfgen: the edges of each
+//! contour are split at corners and assigned pairs of channels so that
+//! the edges meeting at a corner share only one channel. Each channel
+//! stores the distance to its nearest edge, extended past corners, and the
+//! median of the channels reconstructs the outline with sharp corners.
+
+use read_fonts::types::Point;
+
+use super::Line;
+
+/// Sine of the angle between two path commands above which they meet at
+/// a corner. This matches the default of msdfgen (3 radians).
+const CORNER_THRESHOLD: f32 = 0.141;
+
+/// Tolerance for distances to be considered equal when choosing the
+/// nearest edge.
+const DISTANCE_EPSILON: f32 = 1e-4;
+
+const RED: u8 = 1;
+const GREEN: u8 = 2;
+const BLUE: u8 = 4;
+const WHITE: u8 = RED | GREEN | BLUE;
+const CYAN: u8 = GREEN | BLUE;
+const MAGENTA: u8 = RED | BLUE;
+const YELLOW: u8 = RED | GREEN;
+
+/// Colors assigned in turn to the edges between corners.
+const EDGE_COLORS: [u8; 3] = [CYAN, MAGENTA, YELLOW];
+
+/// Color of a line in a multi-channel distance field.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Edge {
+    /// Bit set of the channels that include the line.
+    channels: u8,
+    /// True if the line starts at a corner of the contour.
+    starts_at_corner: bool,
+    /// True if the line ends at a corner of the contour.
+    ends_at_corner: bool,
+}
+
+/// Assigns channels to each line of the given contours.
+///
+/// This is the simple edge coloring strategy of msdfgen.
+pub fn color_edges(lines: &[Line], contours: &[usize], edges: &mut Vec<Edge>) {
+    edges.clear();
+    edges.resize(lines.len(), Edge::default());
+    let mut corners = Vec::new();
+    let mut start = 0;
+    for &end in contours {
+        let len = end - start;
+        corners.clear();
+        corners.extend((0..len).filter(|&k| {
+            let line = &lines[start + k];
+            let prev = &lines[start + (k + len - 1) % len];
+            line.is_segment_start && is_corner(prev, line)
+        }));
+        let contour_edges = &mut edges[start..end];
+        match corners.len() {
+            0 => {
+                for edge in contour_edges {
+                    edge.channels = WHITE;
+                }
+            }
+            1 => {
+                // A single corner is split into three parts so that both
+                // sides of the corner have distinct colors.
+                let corner = corners[0];
+                for k in 0..len {
+                    let edge = &mut contour_edges[(corner + k) % len];
+                    edge.channels = [MAGENTA, WHITE, YELLOW][(3 * k / len).min(2)];
+                    edge.starts_at_corner = k == 0;
+                    edge.ends_at_corner = k == len - 1;
+                }
+            }
+            count => {
+                for (run, &corner) in corners.iter().enumerate() {
+                    // The last run must not have the same color as the
+                    // first.
+                    let channels = if run == count - 1 && count % 3 == 1 {
+                        EDGE_COLORS[1]
+                    } else {
+                        EDGE_COLORS[run % 3]
+                    };
+                    let next_corner = corners[(run + 1) % count];
+                    let run_len = (next_corner + len - corner - 1) % len + 1;
+                    for k in 0..run_len {
+                        let edge = &mut contour_edges[(corner + k) % len];
+                        edge.channels = channels;
+                        edge.starts_at_corner = k == 0;
+                        edge.ends_at_corner = k == run_len - 1;
+                    }
+                }
+            }
+        }
+        start = end;
+    }
+}
+
+/// Fills the mask with signed distances to the nearest line.
+///
+/// The mask must contain nonzero values for the pixels with centers
+/// inside the outline, which are replaced by the distances.
+pub fn fill_sdf(lines: &[Line], width: usize, range: f32, data: &mut [u8]) {
+    for (i, value) in data.iter_mut().enumerate() {
+        let (x, y) = pixel_center(i, width);
+        let distance = lines
+            .iter()
+            .map(|line| Nearest::new(line, x, y).distance)
+            .fold(f32::MAX, f32::min);
+        let sign = if *value != 0 { 1.0 } else { -1.0 };
+        *value = encode(sign * distance, range);
+    }
+}
+
+/// Fills the mask with three signed distances per pixel, one for each
+/// channel assigned to the lines.
+pub fn fill_msdf(lines: &[Line], edges: &[Edge], width: usize, range: f32, data: &mut [u8]) {
+    // Positive area means that the inside is to the left of each line.
+    let area = lines
+        .iter()
+        .map(|line| line.x0 * line.y1 - line.x1 * line.y0)
+        .sum::<f32>();
+    let orientation = if area < 0.0 { -1.0 } else { 1.0 };
+    for (i, pixel) in data.chunks_exact_mut(3).enumerate() {
+        let (x, y) = pixel_center(i, width);
+        let mut nearest: [Option<(Nearest, usize)>; 3] = [None; 3];
+        for (index, (line, edge)) in lines.iter().zip(edges).enumerate() {
+            let candidate = Nearest::new(line, x, y);
+            for (channel, best) in nearest.iter_mut().enumerate() {
+                if edge.channels & (1 << channel) == 0 {
+                    continue;
+                }
+                if best
+                    .as_ref()
+                    .is_none_or(|(best, _)| candidate.is_closer_than(best))
+                {
+                    *best = Some((candidate, index));
+                }
+            }
+        }
+        for (value, best) in pixel.iter_mut().zip(nearest) {
+            let distance = best.map_or(-f32::MAX, |(nearest, index)| {
+                nearest.signed_distance(&lines[index], &edges[index], x, y) * orientation
+            });
+            *value = encode(distance, range);
+        }
+    }
+}
+
+/// Nearest point on a line to a pixel center.
+#[derive(Copy, Clone, Debug)]
+struct Nearest {
+    distance: f32,
+    /// Sine of the angle between the line and the vector to the pixel,
+    /// used to choose between lines that share the nearest point.
+    orthogonality: f32,
+    /// Position of the nearest point along the line, before clamping to
+    /// the ends.
+    t: f32,
+}
+
+impl Nearest {
+    fn new(line: &Line, x: f32, y: f32) -> Self {
+        let (dx, dy) = (line.x1 - line.x0, line.y1 - line.y0);
+        let len_sq = dx * dx + dy * dy;
+        let t = ((x - line.x0) * dx + (y - line.y0) * dy) / len_sq;
+        let clamped = t.clamp(0.0, 1.0);
+        let (vx, vy) = (x - (line.x0 + dx * clamped), y - (line.y0 + dy * clamped));
+        let distance = (vx * vx + vy * vy).sqrt();
+        let orthogonality = if distance > 0.0 {
+            (dx * vy - dy * vx).abs() / (len_sq.sqrt() * distance)
+        } else {
+            1.0
+        };
+        Self {
+            distance,
+            orthogonality,
+            t,
+        }
+    }
+
+    fn is_closer_than(&self, other: &Self) -> bool {
+        if (self.distance - other.distance).abs() <= DISTANCE_EPSILON {
+            self.orthogonality > other.orthogonality
+        } else {
+            self.distance < other.distance
+        }
+    }
+
+    /// Returns the distance with a positive sign on the left of the line.
+    ///
+    /// Beyond a corner, the perpendicular distance to the extension of the
+    /// line is used if it is smaller. This is the pseudo-distance of
+    /// msdfgen.
+    fn signed_distance(&self, line: &Line, edge: &Edge, x: f32, y: f32) -> f32 {
+        let (dx, dy) = (line.x1 - line.x0, line.y1 - line.y0);
+        let perpendicular = (dx * (y - line.y0) - dy * (x - line.x0)) / (dx * dx + dy * dy).sqrt();
+        let is_beyond_corner =
+            (self.t < 0.0 && edge.starts_at_corner) || (self.t > 1.0 && edge.ends_at_corner);
+        if is_beyond_corner && perpendicular.abs() <= self.distance {
+            perpendicular
+        } else if perpendicular < 0.0 {
+            -self.distance
+        } else {
+            self.distance
+        }
+    }
+}
+
+/// Returns true if the two consecutive lines meet at a corner.
+fn is_corner(a: &Line, b: &Line) -> bool {
+    let a = Point::new(a.x1 - a.x0, a.y1 - a.y0);
+    let b = Point::new(b.x1 - b.x0, b.y1 - b.y0);
+    a.dot(b) <= 0.0 || a.cross(b).abs() > CORNER_THRESHOLD * a.length() * b.length()
+}
+
+fn pixel_center(index: usize, width: usize) -> (f32, f32) {
+    ((index % width) as f32 + 0.5, (index / width) as f32 + 0.5)
+}
+
+/// Maps a signed distance in pixels to a byte, with the outline at the
+/// middle of the range.
+fn encode(distance: f32, range: f32) -> u8 {
+    let value = if range > 0.0 {
+        distance / range + 0.5
+    } else if distance > 0.0 {
+        1.0
+    } else {
+        0.0
+    };
+    (value.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+}
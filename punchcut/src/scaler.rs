@@ -1,10 +1,17 @@
-use super::{source::glyf, Context, Error, NormalizedCoord, Pen, Result, Variation};
+use super::{
+    cache::{self, OutlineCache},
+    metrics::VerticalLayout,
+    source::{bitmap, cff, colr, glyf, GlyphSource},
+    BitmapGlyph, ColorPainter, ColorPen, Component, Context, Error, ErrorPolicy, Mask, Metrics,
+    NamedInstance, NormalizedCoord, Pen, RasterOptions, Rasterizer, Result, Transform, Variation,
+    VerticalMetrics, Warning,
+};
 
 #[cfg(feature = "hinting")]
-use super::Hinting;
+use super::{autohint, Hinting, HintingEngine, HintingOptions};
 
 use read_fonts::{
-    types::{Fixed, GlyphId, Tag},
+    types::{F26Dot6, Fixed, GlyphId, NameId, Tag},
     TableProvider,
 };
 
@@ -16,7 +23,20 @@ pub struct ScalerBuilder<'a> {
     font_id: Option<u64>,
     size: f32,
     #[cfg(feature = "hinting")]
-    hint: Option<Hinting>,
+    hint: Option<HintingOptions>,
+    synthesis: Synthesis,
+    transform: Transform,
+    named_instance: Option<InstanceSelector>,
+    raster: RasterOptions,
+    error_policy: ErrorPolicy,
+    sources: Vec<&'a mut dyn GlyphSource>,
+    is_vertical: bool,
+}
+
+/// Owned form of [`NamedInstance`], resolved when the scaler is built.
+enum InstanceSelector {
+    Index(usize),
+    Name(String),
 }
 
 impl<'a> ScalerBuilder<'a> {
@@ -30,6 +50,13 @@ impl<'a> ScalerBuilder<'a> {
             size: 0.0,
             #[cfg(feature = "hinting")]
             hint: None,
+            synthesis: Synthesis::default(),
+            transform: Transform::IDENTITY,
+            named_instance: None,
+            raster: RasterOptions::default(),
+            error_policy: ErrorPolicy::default(),
+            sources: Vec::new(),
+            is_vertical: false,
         }
     }
 
@@ -48,23 +75,95 @@ impl<'a> ScalerBuilder<'a> {
         self
     }
 
-    /// Sets the hinting mode.
+    /// Sets the hinting mode and engine.
     ///
-    /// Passing `None` will disable hinting.
+    /// Accepts either a [`Hinting`] mode, which selects the engine
+    /// automatically, or [`HintingOptions`]. Passing `None` will disable
+    /// hinting.
     #[cfg(feature = "hinting")]
-    pub fn hint(mut self, hint: Option<Hinting>) -> Self {
-        self.hint = hint;
+    pub fn hint(mut self, hint: impl Into<Option<HintingOptions>>) -> Self {
+        self.hint = hint.into();
+        self
+    }
+
+    /// Sets the strength of synthetic emboldening.
+    ///
+    /// This is the amount added to the width and height of each stem, in
+    /// pixels, or in font units for unscaled outlines. A strength of 0.0
+    /// disables emboldening.
+    pub fn embolden(mut self, strength: f32) -> Self {
+        self.synthesis.embolden = strength.max(0.0);
+        self
+    }
+
+    /// Sets the angle in degrees for synthetic oblique outlines.
+    ///
+    /// Positive angles slant outlines to the right. An angle of 0.0
+    /// disables skewing.
+    pub fn skew(mut self, angle: f32) -> Self {
+        self.synthesis.skew = angle;
+        self
+    }
+
+    /// Sets a transformation applied to all outlines, after hinting and
+    /// synthesis.
+    ///
+    /// Hinting is disabled if the transformation rotates or skews
+    /// outlines, since the hinted outlines would no longer align with the
+    /// pixel grid. Embedded bitmaps and metrics are not transformed.
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Sets whether outlines are positioned for vertical text.
+    ///
+    /// When enabled, each outline is moved so that its vertical origin, as
+    /// given by [`Scaler::vertical_metrics`], is at the origin. This is
+    /// applied before the transformation of the scaler.
+    pub fn vertical_layout(mut self, enabled: bool) -> Self {
+        self.is_vertical = enabled;
+        self
+    }
+
+    /// Sets the options for rendering outlines to masks.
+    ///
+    /// Accepts either a [`RenderMode`](crate::RenderMode) or
+    /// [`RasterOptions`].
+    pub fn raster_options(mut self, options: impl Into<RasterOptions>) -> Self {
+        self.raster = options.into();
+        self
+    }
+
+    /// Sets the policy for handling glyphs with malformed data.
+    ///
+    /// With [`ErrorPolicy::Recover`], such glyphs produce empty outlines
+    /// and the errors are available from [`Scaler::warnings`].
+    pub fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.error_policy = policy;
+        self
+    }
+
+    /// Adds a source of outlines that takes precedence over the outlines
+    /// in the font.
+    ///
+    /// Sources are queried in the order they are added. See
+    /// [`GlyphSource`] for the full priority order. Outlines are not cached
+    /// for scalers with custom sources.
+    pub fn source(mut self, source: &'a mut dyn GlyphSource) -> Self {
+        self.sources.push(source);
         self
     }
 
     /// Specifies a variation with a set of normalized coordinates.
     ///
     /// This will clear any variations specified with the variations method.
-    pub fn coords<I>(self, coords: I) -> Self
+    pub fn coords<I>(mut self, coords: I) -> Self
     where
         I: IntoIterator,
         I::Item: Borrow<NormalizedCoord>,
     {
+        self.named_instance = None;
         self.context.variations.clear();
         self.context.coords.clear();
         self.context
@@ -87,38 +186,204 @@ impl<'a> ScalerBuilder<'a> {
         self
     }
 
+    /// Sets the location in user space coordinates, such as `wght` = 700.0.
+    ///
+    /// Unlike the variations method, this replaces any previously specified
+    /// variations, coordinates or named instance. Axes that are not
+    /// specified use their default values.
+    pub fn location(mut self, location: &[(Tag, f32)]) -> Self {
+        self.named_instance = None;
+        self.context.variations.clear();
+        self.variations(location.iter().copied())
+    }
+
+    /// Selects a named instance of a variable font by index or name.
+    ///
+    /// Names are matched against the subfamily and PostScript names of
+    /// each instance. This replaces any previously specified variations
+    /// or coordinates, and the default location is used if the instance
+    /// is not found. Variations added after this method are applied on
+    /// top of the instance.
+    pub fn named_instance<'n>(mut self, instance: impl Into<NamedInstance<'n>>) -> Self {
+        self.context.coords.clear();
+        self.context.variations.clear();
+        self.named_instance = Some(match instance.into() {
+            NamedInstance::Index(index) => InstanceSelector::Index(index),
+            NamedInstance::Name(name) => InstanceSelector::Name(name.into()),
+        });
+        self
+    }
+
     /// Builds a scaler using the currently configured settings
     /// and the specified font.
     pub fn build(mut self, font: &impl TableProvider<'a>) -> Scaler<'a> {
+        #[cfg(feature = "hinting")]
+        if self.transform.xy != 0.0 || self.transform.yx != 0.0 {
+            self.hint = None;
+        }
+        self.resolve_named_instance(font);
         self.resolve_variations(font);
-        let coords = &self.context.coords[..];
-        let glyf = if let Ok(glyf) = glyf::Scaler::new(
-            &mut self.context.glyf,
+        let context = self.context;
+        let coords = &context.coords[..];
+        // Outlines from custom sources are not identified by the font, so
+        // they are excluded from the caches.
+        let cache_font_id = self.font_id.filter(|_| self.sources.is_empty());
+        let cache_id = cache_font_id
+            .filter(|_| context.outline_cache.budget() != 0)
+            .and_then(|font_id| {
+                context.outline_cache.config_id(&cache::Key {
+                    font_id,
+                    size: self.size,
+                    coords: coords.to_vec(),
+                    #[cfg(feature = "hinting")]
+                    hint: self.hint,
+                    embolden: self.synthesis.embolden,
+                    skew: self.synthesis.skew,
+                })
+            });
+        #[cfg(feature = "hinting")]
+        let interpreter_hint = self.hint.filter(|hint| match hint.engine {
+            HintingEngine::Auto => has_hinting_programs(font),
+            HintingEngine::Interpreter => true,
+            HintingEngine::AutoHinter => false,
+        });
+        let mut sources = self
+            .sources
+            .into_iter()
+            .map(OutlineSource::Custom)
+            .collect::<Vec<_>>();
+        let glyf = glyf::Scaler::new(
+            &mut context.glyf,
             font,
             self.font_id,
             self.size,
             #[cfg(feature = "hinting")]
-            self.hint,
+            interpreter_hint.map(|hint| hint.mode),
+            coords,
+        );
+        match glyf {
+            Ok(glyf) => sources.push(OutlineSource::Glyf(glyf)),
+            // PostScript outlines are only used when there are no TrueType
+            // outlines
+            Err(_) => sources.extend(
+                cff::Scaler::new(font, self.size, coords)
+                    .ok()
+                    .map(OutlineSource::Cff),
+            ),
+        }
+        #[allow(unused_mut)]
+        let mut outlines = Outlines {
+            sources,
+            size: self.size,
             coords,
-        ) {
-            Some((glyf, &mut self.context.glyf_outline))
-        } else {
-            None
+            outline: &mut context.glyf_outline,
+            #[cfg(feature = "hinting")]
+            autohint: None,
+            synthesis: self.synthesis,
+            transform: self.transform,
+            vertical: VerticalLayout::new(font, self.size, coords),
+            is_vertical: self.is_vertical,
+            cache: &mut context.outline_cache,
+            cache_id,
+            error_policy: self.error_policy,
+            warnings: Vec::new(),
         };
+        // The automatic hinter is used when the interpreter is not
+        // selected or has no effect.
+        #[cfg(feature = "hinting")]
+        if let Some(hint) = self.hint.filter(|hint| {
+            self.size != 0.0
+                && hint.engine != HintingEngine::Interpreter
+                && !outlines.glyf().is_some_and(|glyf| glyf.is_hinted())
+        }) {
+            let cmap = font.cmap().ok();
+            let units_per_em = font.head().map(|head| head.units_per_em()).unwrap_or(0);
+            if outlines.has_outlines() && units_per_em != 0 {
+                let hinter =
+                    context
+                        .autohint
+                        .hinter(cache_font_id, coords, self.size, hint.mode, || {
+                            autohint::FontMetrics::new(
+                                units_per_em,
+                                cmap.as_ref(),
+                                |gid, outline| outlines.load_unscaled(gid, outline).is_ok(),
+                            )
+                        });
+                outlines.autohint = Some(hinter);
+            }
+        }
         Scaler {
-            outlines: Outlines { glyf },
+            metrics: Metrics::new(font, self.size, coords),
+            glyph_count: font.maxp().map(|maxp| maxp.num_glyphs()).unwrap_or(0),
+            has_svg: font
+                .data_for_tag(Tag::SVG)
+                .is_some_and(|data| !data.is_empty()),
+            outlines,
+            color: colr::ColorGlyphs::new(font, self.size, coords).ok(),
+            bitmaps: bitmap::Bitmaps::new(font, self.size).ok(),
+            rasterizer: &mut context.rasterizer,
+            raster: self.raster,
         }
     }
 
+    fn resolve_named_instance(&mut self, font: &impl TableProvider<'a>) {
+        let Some(selector) = self.named_instance.take() else {
+            return; // nop
+        };
+        let Ok(fvar) = font.fvar() else {
+            return; // nop
+        };
+        let (Ok(axes), Ok(instances)) = (fvar.axes(), fvar.instances()) else {
+            return; // nop
+        };
+        let instance = match selector {
+            InstanceSelector::Index(index) => instances.get(index).ok(),
+            InstanceSelector::Name(name) => {
+                let Ok(name_table) = font.name() else {
+                    return; // nop
+                };
+                let data = name_table.string_data();
+                let is_match = |name_id: NameId| {
+                    name_table
+                        .name_record()
+                        .iter()
+                        .filter(|record| record.name_id() == name_id)
+                        .filter_map(|record| record.string(data).ok())
+                        .any(|string| string.chars().eq(name.chars()))
+                };
+                instances
+                    .iter()
+                    .filter_map(|instance| instance.ok())
+                    .find(|instance| {
+                        is_match(instance.subfamily_name_id)
+                            || instance.post_script_name_id.is_some_and(is_match)
+                    })
+            }
+        };
+        let Some(instance) = instance else {
+            return; // nop
+        };
+        // Instance values come first so that later variations take
+        // precedence.
+        let instance_variations =
+            axes.iter()
+                .zip(instance.coordinates)
+                .map(|(axis, value)| Variation {
+                    tag: axis.axis_tag(),
+                    value: value.get().to_f64() as f32,
+                });
+        self.context.variations.splice(0..0, instance_variations);
+    }
+
     fn resolve_variations(&mut self, font: &impl TableProvider<'a>) {
         if self.context.variations.is_empty() {
             return; // nop
         }
         let Ok(fvar) = font.fvar() else {
-            return;  // nop
+            return; // nop
         };
         let Ok(axes) = fvar.axes() else {
-            return;  // nop
+            return; // nop
         };
         let avar_mappings = font.avar().ok().map(|avar| avar.axis_segment_maps());
         let axis_count = fvar.axis_count() as usize;
@@ -137,8 +402,7 @@ impl<'a> ScalerBuilder<'a> {
                         .flatten()
                         .map(|mapping| mapping.apply(coord))
                         .unwrap_or(coord);
-                    let coord = coord.to_f64() as f32;
-                    *dest_coord = NormalizedCoord::from_f32(coord);
+                    *dest_coord = coord.to_f2dot14();
                 }
             }
         }
@@ -147,38 +411,428 @@ impl<'a> ScalerBuilder<'a> {
 
 /// Glyph scaler for a specific font and configuration.
 pub struct Scaler<'a> {
+    metrics: Metrics,
+    glyph_count: u16,
+    has_svg: bool,
     outlines: Outlines<'a>,
+    color: Option<colr::ColorGlyphs<'a>>,
+    bitmaps: Option<bitmap::Bitmaps<'a>>,
+    rasterizer: &'a mut Rasterizer,
+    raster: RasterOptions,
 }
 
 impl<'a> Scaler<'a> {
+    /// Returns the font-wide metrics for the size and variation location
+    /// of the scaler.
+    pub fn metrics(&self) -> Metrics {
+        self.metrics
+    }
+
+    /// Returns the metrics for positioning the specified glyph in vertical
+    /// text.
+    pub fn vertical_metrics(&self, glyph_id: GlyphId) -> VerticalMetrics {
+        self.outlines.vertical.get(glyph_id)
+    }
+
+    /// Returns the number of glyphs in the font.
+    pub fn glyph_count(&self) -> u16 {
+        self.glyph_count
+    }
+
     /// Returns true if the scaler has a source for simple outlines.
     pub fn has_outlines(&self) -> bool {
         self.outlines.has_outlines()
     }
 
+    /// Returns true if outlines are hinted, either by the TrueType bytecode
+    /// interpreter or by the automatic hinter.
+    pub fn is_hinted(&self) -> bool {
+        self.outlines.is_hinted()
+    }
+
+    /// Returns the errors recovered from while loading glyphs, when the
+    /// scaler was built with [`ErrorPolicy::Recover`].
+    pub fn warnings(&self) -> &[Warning] {
+        &self.outlines.warnings
+    }
+
+    /// Removes all recorded warnings.
+    pub fn clear_warnings(&mut self) {
+        self.outlines.warnings.clear();
+    }
+
     /// Loads a simple outline for the specified glyph identifier and invokes the functions
     /// in the given sink for the sequence of path commands that define the outline.
     pub fn outline(&mut self, glyph_id: GlyphId, sink: &mut impl Pen) -> Result<()> {
         self.outlines.outline(glyph_id, sink)
     }
+
+    /// Returns the components of the specified glyph if it is a TrueType
+    /// composite glyph.
+    ///
+    /// The components are positioned for the size and variation location
+    /// of the scaler, before hinting instructions, synthesis and the
+    /// transformation of the scaler are applied. The list is empty for
+    /// simple glyphs and for fonts without TrueType outlines.
+    pub fn components(&mut self, glyph_id: GlyphId) -> Result<Vec<Component>> {
+        let mut components = Vec::new();
+        if !self.outlines.has_outlines() {
+            return Err(Error::NoSources);
+        }
+        let outline = &mut *self.outlines.outline;
+        let glyf = self
+            .outlines
+            .sources
+            .iter_mut()
+            .find_map(|source| match source {
+                OutlineSource::Glyf(glyf) => Some(glyf),
+                _ => None,
+            });
+        if let Some(glyf) = glyf {
+            glyf.load_components(glyph_id, outline, &mut components)?;
+        }
+        Ok(components)
+    }
+
+    /// Loads a simple outline for the specified glyph identifier and renders
+    /// it into the given mask using the configured raster options.
+    ///
+    /// The mask is resized to the pixel bounds of the outline.
+    pub fn render(&mut self, glyph_id: GlyphId, mask: &mut Mask) -> Result<()> {
+        self.rasterizer.reset();
+        self.outlines.outline(glyph_id, self.rasterizer)?;
+        self.rasterizer.render(self.raster, mask);
+        Ok(())
+    }
+
+    /// Returns true if the scaler has a source for color outlines.
+    pub fn has_color_outlines(&self) -> bool {
+        self.color.is_some()
+    }
+
+    /// Returns true if the font contains color glyphs, either as color
+    /// outlines or as color bitmaps.
+    pub fn has_color_glyphs(&self) -> bool {
+        self.has_color_outlines() || self.bitmaps.as_ref().is_some_and(|b| b.is_color())
+    }
+
+    /// Returns true if the font contains glyphs defined as SVG documents.
+    ///
+    /// The scaler does not render these glyphs; this allows callers to
+    /// select another renderer for them.
+    pub fn has_svg(&self) -> bool {
+        self.has_svg
+    }
+
+    /// Loads the layers of a color outline for the specified glyph identifier
+    /// and invokes the functions in the given sink for each layer, using the
+    /// colors from the specified palette.
+    ///
+    /// Layers that use the foreground color, or a color that is missing from
+    /// the palette, are given a color of `None`. Returns
+    /// [`Error::GlyphNotFound`] if the glyph is not a color glyph.
+    pub fn color_outline(
+        &mut self,
+        glyph_id: GlyphId,
+        palette: usize,
+        sink: &mut impl ColorPen,
+    ) -> Result<()> {
+        let color = self.color.as_ref().ok_or(Error::NoSources)?;
+        let layers = color
+            .layers(glyph_id)
+            .ok_or(Error::GlyphNotFound(glyph_id))?;
+        for layer in layers {
+            sink.begin_layer(color.color(palette, layer));
+            self.outlines.outline(layer.glyph_id(), sink)?;
+            sink.end_layer();
+        }
+        Ok(())
+    }
+
+    /// Paints the color glyph for the specified glyph identifier by invoking
+    /// the functions in the given painter, using the colors from the
+    /// specified palette.
+    ///
+    /// This supports both `COLR` version 1 paint graphs and version 0
+    /// layers. Returns [`Error::GlyphNotFound`] if the glyph is not a color
+    /// glyph.
+    pub fn paint_color_glyph(
+        &mut self,
+        glyph_id: GlyphId,
+        palette: usize,
+        painter: &mut impl ColorPainter,
+    ) -> Result<()> {
+        let color = self.color.as_ref().ok_or(Error::NoSources)?;
+        let outlines = &mut self.outlines;
+        color.paint(glyph_id, palette, painter, |glyph_id, painter| {
+            outlines.outline(glyph_id, painter)
+        })
+    }
+
+    /// Returns true if the scaler has a source for embedded bitmaps.
+    pub fn has_bitmaps(&self) -> bool {
+        self.bitmaps.is_some()
+    }
+
+    /// Returns the embedded bitmap for the specified glyph identifier from
+    /// the strike that best matches the requested size.
+    ///
+    /// The bitmap is not resized; its `scale_x` and `scale_y` fields give
+    /// the factors needed to display it at the requested size. Returns `None`
+    /// if the font has no bitmaps, or the selected strike has no bitmap for
+    /// the glyph.
+    pub fn bitmap(&self, glyph_id: GlyphId) -> Option<BitmapGlyph<'a>> {
+        self.bitmaps.as_ref()?.get(glyph_id)
+    }
+}
+
+/// Returns true if the font contains a font or control value program.
+#[cfg(feature = "hinting")]
+fn has_hinting_programs<'a>(font: &impl TableProvider<'a>) -> bool {
+    [Tag::FPGM, Tag::PREP]
+        .into_iter()
+        .any(|tag| font.data_for_tag(tag).is_some_and(|data| !data.is_empty()))
+}
+
+/// Source of outlines for a scaler.
+enum OutlineSource<'a> {
+    Custom(&'a mut dyn GlyphSource),
+    Glyf(glyf::Scaler<'a>),
+    Cff(cff::Scaler<'a>),
+}
+
+impl OutlineSource<'_> {
+    fn contains(&self, glyph_id: GlyphId) -> bool {
+        match self {
+            Self::Custom(source) => source.contains(glyph_id),
+            Self::Glyf(scaler) => (glyph_id.to_u16() as usize) < scaler.glyph_count(),
+            Self::Cff(scaler) => (glyph_id.to_u16() as usize) < scaler.glyph_count(),
+        }
+    }
 }
 
 /// Outline glyph scalers.
 struct Outlines<'a> {
-    glyf: Option<(glyf::Scaler<'a>, &'a mut glyf::Outline)>,
+    /// Sources of outlines in priority order.
+    sources: Vec<OutlineSource<'a>>,
+    size: f32,
+    coords: &'a [NormalizedCoord],
+    /// Storage for TrueType outlines and for outlines that are
+    /// automatically hinted.
+    outline: &'a mut glyf::Outline,
+    #[cfg(feature = "hinting")]
+    autohint: Option<autohint::Hinter>,
+    synthesis: Synthesis,
+    transform: Transform,
+    /// Source of vertical origins.
+    vertical: VerticalLayout<'a>,
+    /// True if outlines are positioned at their vertical origins.
+    is_vertical: bool,
+    cache: &'a mut OutlineCache,
+    /// Identifier of the configuration in the outline cache, if caching
+    /// is enabled.
+    cache_id: Option<u64>,
+    error_policy: ErrorPolicy,
+    /// Errors recovered from with the recover policy.
+    warnings: Vec<Warning>,
+}
+
+/// Modifications applied to loaded outlines to synthesize missing styles.
+#[derive(Copy, Clone, Default, Debug)]
+struct Synthesis {
+    /// Amount added to the width and height of stems.
+    embolden: f32,
+    /// Angle of the horizontal shear in degrees.
+    skew: f32,
+}
+
+impl Synthesis {
+    fn is_enabled(&self) -> bool {
+        self.embolden != 0.0 || self.skew != 0.0
+    }
+
+    fn apply(&self, outline: &mut glyf::Outline) {
+        if self.embolden != 0.0 {
+            let strength = F26Dot6::from_f64(self.embolden as f64);
+            outline.embolden(strength, strength);
+        }
+        if self.skew != 0.0 {
+            outline.skew(self.skew);
+        }
+    }
 }
 
 impl<'a> Outlines<'a> {
     fn has_outlines(&self) -> bool {
-        self.glyf.is_some()
+        !self.sources.is_empty()
+    }
+
+    #[cfg(feature = "hinting")]
+    fn is_hinted(&self) -> bool {
+        self.autohint.is_some() || self.glyf().is_some_and(|glyf| glyf.is_hinted())
+    }
+
+    #[cfg(not(feature = "hinting"))]
+    fn is_hinted(&self) -> bool {
+        false
+    }
+
+    /// Returns the scaler for TrueType outlines, if present.
+    fn glyf(&self) -> Option<&glyf::Scaler<'a>> {
+        self.sources.iter().find_map(|source| match source {
+            OutlineSource::Glyf(glyf) => Some(glyf),
+            _ => None,
+        })
     }
 
     fn outline(&mut self, glyph_id: GlyphId, sink: &mut impl Pen) -> Result<()> {
-        if let Some((scaler, glyf_outline)) = &mut self.glyf {
-            scaler.load(glyph_id, glyf_outline)?;
-            Ok(glyf_outline.to_path(sink)?)
-        } else {
-            Err(Error::NoSources)
+        if self.error_policy == ErrorPolicy::Fail {
+            return self.transformed_outline(glyph_id, sink);
         }
+        // Record the outline so that nothing reaches the sink if loading
+        // fails partway through.
+        let mut path = cache::Path::default();
+        match self.transformed_outline(glyph_id, &mut path) {
+            Ok(()) => {
+                path.replay(sink);
+                Ok(())
+            }
+            Err(error) if error.is_glyph_specific() => {
+                self.warnings.push(Warning { glyph_id, error });
+                Ok(())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    fn transformed_outline(&mut self, glyph_id: GlyphId, sink: &mut impl Pen) -> Result<()> {
+        let mut transform = self.transform;
+        if self.is_vertical {
+            // Move the vertical origin to the origin before applying the
+            // transformation.
+            let origin = self.vertical.get(glyph_id);
+            let t = &mut transform;
+            t.dx -= t.xx * origin.origin_x + t.xy * origin.origin_y;
+            t.dy -= t.yx * origin.origin_x + t.yy * origin.origin_y;
+        }
+        if transform == Transform::IDENTITY {
+            return self.cached_outline(glyph_id, sink);
+        }
+        self.cached_outline(glyph_id, &mut TransformPen { sink, transform })
+    }
+
+    fn cached_outline(&mut self, glyph_id: GlyphId, sink: &mut impl Pen) -> Result<()> {
+        let Some(cache_id) = self.cache_id else {
+            return self.load(glyph_id, sink);
+        };
+        if let Some(path) = self.cache.get(cache_id, glyph_id) {
+            path.replay(sink);
+            return Ok(());
+        }
+        let mut path = cache::Path::default();
+        self.load(glyph_id, &mut path)?;
+        path.replay(sink);
+        self.cache.insert(cache_id, glyph_id, path);
+        Ok(())
+    }
+
+    fn load(&mut self, glyph_id: GlyphId, sink: &mut impl Pen) -> Result<()> {
+        #[cfg(feature = "hinting")]
+        let is_modified = self.synthesis.is_enabled() || self.autohint.is_some();
+        #[cfg(not(feature = "hinting"))]
+        let is_modified = self.synthesis.is_enabled();
+        let (size, coords) = (self.size, self.coords);
+        let outline = &mut *self.outline;
+        match find_source(&mut self.sources, glyph_id)? {
+            OutlineSource::Glyf(scaler) => scaler.load(glyph_id, outline)?,
+            OutlineSource::Cff(scaler) if !is_modified => return scaler.outline(glyph_id, sink),
+            OutlineSource::Cff(scaler) => {
+                scaler.outline(glyph_id, &mut glyf::OutlinePen::new(outline))?
+            }
+            OutlineSource::Custom(source) if !is_modified => {
+                return source.outline(glyph_id, size, coords, sink)
+            }
+            OutlineSource::Custom(source) => {
+                source.outline(glyph_id, size, coords, &mut glyf::OutlinePen::new(outline))?
+            }
+        }
+        #[cfg(feature = "hinting")]
+        if let Some(hinter) = &mut self.autohint {
+            hinter.hint(self.outline);
+        }
+        self.synthesis.apply(self.outline);
+        Ok(self.outline.to_path(sink)?)
+    }
+
+    /// Loads an outline in font units for computing the metrics used by
+    /// the automatic hinter.
+    #[cfg(feature = "hinting")]
+    fn load_unscaled(&mut self, glyph_id: GlyphId, outline: &mut glyf::Outline) -> Result<()> {
+        let coords = self.coords;
+        match find_source(&mut self.sources, glyph_id)? {
+            OutlineSource::Glyf(scaler) => scaler.load_unscaled(glyph_id, outline),
+            OutlineSource::Cff(scaler) => {
+                scaler.outline_unscaled(glyph_id, &mut glyf::OutlinePen::new(outline))
+            }
+            OutlineSource::Custom(source) => {
+                source.outline(glyph_id, 0.0, coords, &mut glyf::OutlinePen::new(outline))
+            }
+        }
+    }
+}
+
+/// Returns the first source that contains the glyph.
+fn find_source<'s, 'a>(
+    sources: &'s mut [OutlineSource<'a>],
+    glyph_id: GlyphId,
+) -> Result<&'s mut OutlineSource<'a>> {
+    if sources.is_empty() {
+        return Err(Error::NoSources);
+    }
+    sources
+        .iter_mut()
+        .find(|source| source.contains(glyph_id))
+        .ok_or(Error::GlyphNotFound(glyph_id))
+}
+
+/// Pen that transforms coordinates before passing them to another pen.
+struct TransformPen<'a, P> {
+    sink: &'a mut P,
+    transform: Transform,
+}
+
+impl<P: Pen> TransformPen<'_, P> {
+    fn map(&self, x: f32, y: f32) -> (f32, f32) {
+        let t = &self.transform;
+        (t.xx * x + t.xy * y + t.dx, t.yx * x + t.yy * y + t.dy)
+    }
+}
+
+impl<P: Pen> Pen for TransformPen<'_, P> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.map(x, y);
+        self.sink.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.map(x, y);
+        self.sink.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        let (cx0, cy0) = self.map(cx0, cy0);
+        let (x, y) = self.map(x, y);
+        self.sink.quad_to(cx0, cy0, x, y);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        let (cx0, cy0) = self.map(cx0, cy0);
+        let (cx1, cy1) = self.map(cx1, cy1);
+        let (x, y) = self.map(x, y);
+        self.sink.curve_to(cx0, cy0, cx1, cy1, x, y);
+    }
+
+    fn close(&mut self) {
+        self.sink.close();
     }
 }
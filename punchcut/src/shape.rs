@@ -0,0 +1,871 @@
+//! Simple shaping of text with OpenType layout tables.
+
+use super::{Charmap, GlyphId};
+
+use read_fonts::{
+    tables::{
+        gdef::MarkGlyphSets,
+        gpos::{
+            self, AnchorTable, LigatureAttach, MarkBasePosFormat1, MarkLigPosFormat1,
+            MarkMarkPosFormat1, PairPos, PositionLookup, ValueRecord,
+        },
+        gsub::{self, LigatureSet, LigatureSubstFormat1, SingleSubst, SubstitutionLookup},
+        hmtx::Hmtx,
+        layout::{ClassDef, FeatureList, Lookup, LookupFlag, ScriptList},
+    },
+    types::Tag,
+    FontRead, ReadError, ResolveOffset, TableProvider,
+};
+
+/// GSUB features that are applied, in addition to the required feature of
+/// the language system.
+const GSUB_FEATURES: &[Tag] = &[
+    Tag::new(b"ccmp"),
+    Tag::new(b"locl"),
+    Tag::new(b"rlig"),
+    Tag::new(b"liga"),
+    Tag::new(b"clig"),
+];
+
+/// GPOS features that are applied, in addition to the required feature of
+/// the language system.
+const GPOS_FEATURES: &[Tag] = &[Tag::new(b"kern"), Tag::new(b"mark"), Tag::new(b"mkmk")];
+
+/// GDEF glyph classes.
+const BASE_GLYPH: u16 = 1;
+const LIGATURE_GLYPH: u16 = 2;
+const MARK_GLYPH: u16 = 3;
+
+/// A glyph produced by shaping, with its position.
+///
+/// Values are in font units. The offsets move the glyph away from the
+/// current pen position without affecting the advance.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ShapedGlyph {
+    /// The glyph identifier.
+    pub glyph_id: GlyphId,
+    /// Byte offset in the text of the first character that produced the
+    /// glyph.
+    pub cluster: u32,
+    /// Horizontal distance to move the pen after drawing the glyph.
+    pub x_advance: i32,
+    /// Vertical distance to move the pen after drawing the glyph.
+    pub y_advance: i32,
+    /// Horizontal offset of the glyph from the pen position.
+    pub x_offset: i32,
+    /// Vertical offset of the glyph from the pen position.
+    pub y_offset: i32,
+}
+
+/// Minimal shaper for simple scripts, such as Latin.
+///
+/// Text is mapped to nominal glyphs through `cmap`, then the single and
+/// ligature substitutions of the common GSUB features (`ccmp`, `locl`,
+/// `rlig`, `liga` and `clig`) are applied, followed by the pair adjustment
+/// and mark attachment positioning of the `kern`, `mark` and `mkmk` GPOS
+/// features. The required feature of the language system is always
+/// applied as well.
+///
+/// This is not a replacement for a full shaping engine: text is laid out
+/// horizontally and left to right, with no normalization, reordering or
+/// contextual lookups, and no variations. Marks, as classified by GDEF,
+/// are given a zero advance. Malformed tables and lookups are ignored.
+pub struct Shaper<'a> {
+    charmap: Charmap<'a>,
+    hmtx: Option<Hmtx<'a>>,
+    glyph_classes: Option<ClassDef<'a>>,
+    mark_attach_classes: Option<ClassDef<'a>>,
+    mark_glyph_sets: Option<MarkGlyphSets<'a>>,
+    substitutions: Vec<ShapingLookup<Substitution<'a>>>,
+    positions: Vec<ShapingLookup<Positioning<'a>>>,
+}
+
+impl<'a> Shaper<'a> {
+    /// Creates a shaper for the given font, OpenType script tag and
+    /// optional OpenType language tag.
+    ///
+    /// If the script is not present in a layout table, the default script
+    /// is used; if the language is not present or not given, the default
+    /// language system of the script is used.
+    pub fn new(font: &impl TableProvider<'a>, script: Tag, language: Option<Tag>) -> Self {
+        let gdef = font.gdef().ok();
+        let substitutions = font
+            .gsub()
+            .and_then(|gsub| {
+                let lookup_list = gsub.lookup_list()?;
+                let indices = feature_lookups(
+                    &gsub.script_list()?,
+                    &gsub.feature_list()?,
+                    script,
+                    language,
+                    GSUB_FEATURES,
+                )?;
+                Ok(indices
+                    .into_iter()
+                    .filter_map(|index| lookup_list.get(index).ok())
+                    .filter_map(|lookup| Substitution::lookup(lookup)?.ok())
+                    .collect())
+            })
+            .unwrap_or_default();
+        let positions = font
+            .gpos()
+            .and_then(|gpos| {
+                let lookup_list = gpos.lookup_list()?;
+                let indices = feature_lookups(
+                    &gpos.script_list()?,
+                    &gpos.feature_list()?,
+                    script,
+                    language,
+                    GPOS_FEATURES,
+                )?;
+                Ok(indices
+                    .into_iter()
+                    .filter_map(|index| lookup_list.get(index).ok())
+                    .filter_map(|lookup| Positioning::lookup(lookup)?.ok())
+                    .collect())
+            })
+            .unwrap_or_default();
+        Self {
+            charmap: Charmap::new(font),
+            hmtx: font.hmtx().ok(),
+            glyph_classes: gdef.as_ref().and_then(|gdef| gdef.glyph_class_def()?.ok()),
+            mark_attach_classes: gdef
+                .as_ref()
+                .and_then(|gdef| gdef.mark_attach_class_def()?.ok()),
+            mark_glyph_sets: gdef
+                .as_ref()
+                .and_then(|gdef| gdef.mark_glyph_sets_def()?.ok()),
+            substitutions,
+            positions,
+        }
+    }
+
+    /// Shapes the text, returning positioned glyphs in visual order.
+    ///
+    /// Characters that are not mapped by the font produce `.notdef`.
+    pub fn shape(&self, text: &str) -> Vec<ShapedGlyph> {
+        let mut glyphs = text
+            .char_indices()
+            .map(|(cluster, ch)| ShapedGlyph {
+                glyph_id: self.charmap.map(ch).unwrap_or(GlyphId::NOTDEF),
+                cluster: cluster as u32,
+                x_advance: 0,
+                y_advance: 0,
+                x_offset: 0,
+                y_offset: 0,
+            })
+            .collect::<Vec<_>>();
+        for lookup in &self.substitutions {
+            // a malformed subtable ends the lookup, but not shaping
+            let _ = self.substitute(lookup, &mut glyphs);
+        }
+        for glyph in &mut glyphs {
+            if self.glyph_class(glyph.glyph_id) != MARK_GLYPH {
+                glyph.x_advance = self
+                    .hmtx
+                    .as_ref()
+                    .and_then(|hmtx| hmtx.advance(glyph.glyph_id))
+                    .unwrap_or_default() as i32;
+            }
+        }
+        for lookup in &self.positions {
+            let _ = self.position(lookup, &mut glyphs);
+        }
+        glyphs
+    }
+
+    fn substitute(
+        &self,
+        lookup: &ShapingLookup<Substitution>,
+        glyphs: &mut Vec<ShapedGlyph>,
+    ) -> Result<(), ReadError> {
+        let mut i = 0;
+        while i < glyphs.len() {
+            if !self.is_skipped(glyphs[i].glyph_id, lookup) {
+                for subtable in &lookup.subtables {
+                    let applied = match subtable {
+                        Substitution::Single(subtable) => apply_single(subtable, &mut glyphs[i])?,
+                        Substitution::Ligature(subtable) => {
+                            self.apply_ligature(subtable, lookup, glyphs, i)?
+                        }
+                    };
+                    if applied {
+                        break;
+                    }
+                }
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+
+    fn apply_ligature(
+        &self,
+        subtable: &LigatureSubstFormat1,
+        lookup: &ShapingLookup<Substitution>,
+        glyphs: &mut Vec<ShapedGlyph>,
+        i: usize,
+    ) -> Result<bool, ReadError> {
+        let Some(index) = subtable.coverage()?.get(glyphs[i].glyph_id) else {
+            return Ok(false);
+        };
+        let ligature_set: LigatureSet = subtable
+            .ligature_set_offsets()
+            .get(index as usize)
+            .ok_or(ReadError::OutOfBounds)?
+            .get()
+            .resolve(subtable.offset_data())?;
+        for ligature in ligature_set.ligatures() {
+            let ligature = ligature?;
+            let mut components = Vec::new();
+            let mut pos = i;
+            let matched = ligature.component_glyph_ids().iter().all(|component| {
+                match self.next_glyph(glyphs, pos, lookup) {
+                    Some(next) if glyphs[next].glyph_id == component.get() => {
+                        components.push(next);
+                        pos = next;
+                        true
+                    }
+                    _ => false,
+                }
+            });
+            if matched {
+                glyphs[i].glyph_id = ligature.ligature_glyph();
+                // skipped glyphs, such as marks, stay after the ligature
+                for pos in components.into_iter().rev() {
+                    glyphs.remove(pos);
+                }
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn position(
+        &self,
+        lookup: &ShapingLookup<Positioning>,
+        glyphs: &mut [ShapedGlyph],
+    ) -> Result<(), ReadError> {
+        let mut i = 0;
+        while i < glyphs.len() {
+            let mut next = i + 1;
+            if !self.is_skipped(glyphs[i].glyph_id, lookup) {
+                for subtable in &lookup.subtables {
+                    let applied = match subtable {
+                        Positioning::Pair(subtable) => {
+                            match self.apply_pair(subtable, lookup, glyphs, i)? {
+                                Some(end) => {
+                                    next = end;
+                                    true
+                                }
+                                None => false,
+                            }
+                        }
+                        Positioning::MarkToBase(subtable) => {
+                            self.apply_mark_base(subtable, glyphs, i)?
+                        }
+                        Positioning::MarkToLig(subtable) => {
+                            self.apply_mark_lig(subtable, glyphs, i)?
+                        }
+                        Positioning::MarkToMark(subtable) => {
+                            self.apply_mark_mark(subtable, lookup, glyphs, i)?
+                        }
+                    };
+                    if applied {
+                        break;
+                    }
+                }
+            }
+            i = next;
+        }
+        Ok(())
+    }
+
+    /// Applies a pair adjustment, returning the position at which to
+    /// continue if the subtable applied.
+    fn apply_pair(
+        &self,
+        subtable: &PairPos,
+        lookup: &ShapingLookup<Positioning>,
+        glyphs: &mut [ShapedGlyph],
+        i: usize,
+    ) -> Result<Option<usize>, ReadError> {
+        let Some(j) = self.next_glyph(glyphs, i, lookup) else {
+            return Ok(None);
+        };
+        let Some((record1, record2)) =
+            subtable.value_records(glyphs[i].glyph_id, glyphs[j].glyph_id)?
+        else {
+            return Ok(None);
+        };
+        adjust(&mut glyphs[i], &record1);
+        adjust(&mut glyphs[j], &record2);
+        // as in HarfBuzz, the second glyph is only skipped if it was adjusted
+        Ok(Some(if record2 == ValueRecord::default() {
+            j
+        } else {
+            j + 1
+        }))
+    }
+
+    fn apply_mark_base(
+        &self,
+        subtable: &MarkBasePosFormat1,
+        glyphs: &mut [ShapedGlyph],
+        i: usize,
+    ) -> Result<bool, ReadError> {
+        let Some(mark_index) = subtable.mark_coverage()?.get(glyphs[i].glyph_id) else {
+            return Ok(false);
+        };
+        let Some(base) = self.previous_base(glyphs, i) else {
+            return Ok(false);
+        };
+        let Some(base_index) = subtable.base_coverage()?.get(glyphs[base].glyph_id) else {
+            return Ok(false);
+        };
+        let (class, mark_anchor) = mark_anchor(subtable.mark_array()?, mark_index)?;
+        let base_array = subtable.base_array()?;
+        let base_anchor = base_array
+            .base_records()
+            .get(base_index as usize)?
+            .base_anchors(base_array.offset_data())
+            .nth(class as usize)
+            .flatten()
+            .transpose()?;
+        let Some(base_anchor) = base_anchor else {
+            return Ok(false);
+        };
+        attach(glyphs, base, i, &base_anchor, &mark_anchor);
+        Ok(true)
+    }
+
+    /// Marks are attached to the last component of a ligature, since
+    /// components are not tracked through substitution.
+    fn apply_mark_lig(
+        &self,
+        subtable: &MarkLigPosFormat1,
+        glyphs: &mut [ShapedGlyph],
+        i: usize,
+    ) -> Result<bool, ReadError> {
+        let Some(mark_index) = subtable.mark_coverage()?.get(glyphs[i].glyph_id) else {
+            return Ok(false);
+        };
+        let Some(base) = self.previous_base(glyphs, i) else {
+            return Ok(false);
+        };
+        let Some(ligature_index) = subtable.ligature_coverage()?.get(glyphs[base].glyph_id) else {
+            return Ok(false);
+        };
+        let (class, mark_anchor) = mark_anchor(subtable.mark_array()?, mark_index)?;
+        let ligature_array = subtable.ligature_array()?;
+        let ligature_attach: LigatureAttach = ligature_array
+            .ligature_attach_offsets()
+            .get(ligature_index as usize)
+            .ok_or(ReadError::OutOfBounds)?
+            .get()
+            .resolve_with_args(ligature_array.offset_data(), &subtable.mark_class_count())?;
+        let Some(last) = ligature_attach.component_count().checked_sub(1) else {
+            return Ok(false);
+        };
+        let base_anchor = ligature_attach
+            .component_records()
+            .get(last as usize)?
+            .ligature_anchors(ligature_attach.offset_data())
+            .nth(class as usize)
+            .flatten()
+            .transpose()?;
+        let Some(base_anchor) = base_anchor else {
+            return Ok(false);
+        };
+        attach(glyphs, base, i, &base_anchor, &mark_anchor);
+        Ok(true)
+    }
+
+    fn apply_mark_mark(
+        &self,
+        subtable: &MarkMarkPosFormat1,
+        lookup: &ShapingLookup<Positioning>,
+        glyphs: &mut [ShapedGlyph],
+        i: usize,
+    ) -> Result<bool, ReadError> {
+        let Some(mark_index) = subtable.mark1_coverage()?.get(glyphs[i].glyph_id) else {
+            return Ok(false);
+        };
+        let Some(base) = (0..i)
+            .rev()
+            .find(|&j| !self.is_skipped(glyphs[j].glyph_id, lookup))
+        else {
+            return Ok(false);
+        };
+        if self.glyph_class(glyphs[base].glyph_id) != MARK_GLYPH {
+            return Ok(false);
+        }
+        let Some(base_index) = subtable.mark2_coverage()?.get(glyphs[base].glyph_id) else {
+            return Ok(false);
+        };
+        let (class, mark_anchor) = mark_anchor(subtable.mark1_array()?, mark_index)?;
+        let mark2_array = subtable.mark2_array()?;
+        let base_anchor = mark2_array
+            .mark2_records()
+            .get(base_index as usize)?
+            .mark2_anchors(mark2_array.offset_data())
+            .nth(class as usize)
+            .flatten()
+            .transpose()?;
+        let Some(base_anchor) = base_anchor else {
+            return Ok(false);
+        };
+        attach(glyphs, base, i, &base_anchor, &mark_anchor);
+        Ok(true)
+    }
+
+    /// Returns the position of the first glyph after `i` that is not skipped
+    /// by the lookup.
+    fn next_glyph<T>(
+        &self,
+        glyphs: &[ShapedGlyph],
+        i: usize,
+        lookup: &ShapingLookup<T>,
+    ) -> Option<usize> {
+        (i + 1..glyphs.len()).find(|&j| !self.is_skipped(glyphs[j].glyph_id, lookup))
+    }
+
+    /// Returns the position of the closest glyph before `i` that is not a
+    /// mark.
+    fn previous_base(&self, glyphs: &[ShapedGlyph], i: usize) -> Option<usize> {
+        (0..i)
+            .rev()
+            .find(|&j| self.glyph_class(glyphs[j].glyph_id) != MARK_GLYPH)
+    }
+
+    fn glyph_class(&self, glyph_id: GlyphId) -> u16 {
+        self.glyph_classes
+            .as_ref()
+            .map(|classes| classes.get(glyph_id))
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the lookup flags say the glyph should be skipped.
+    fn is_skipped<T>(&self, glyph_id: GlyphId, lookup: &ShapingLookup<T>) -> bool {
+        let flag = lookup.flag;
+        match self.glyph_class(glyph_id) {
+            BASE_GLYPH => flag.ignore_base_glyphs(),
+            LIGATURE_GLYPH => flag.ignore_ligatures(),
+            MARK_GLYPH => {
+                if flag.ignore_marks() {
+                    return true;
+                }
+                if let Some(set) = lookup.mark_filtering_set {
+                    let covered = self
+                        .mark_glyph_sets
+                        .as_ref()
+                        .and_then(|sets| sets.coverages().nth(set as usize)?.ok())
+                        .is_some_and(|coverage| coverage.get(glyph_id).is_some());
+                    return !covered;
+                }
+                if let Some(class) = flag.mark_attachment_type_mask() {
+                    let mark_class = self
+                        .mark_attach_classes
+                        .as_ref()
+                        .map(|classes| classes.get(glyph_id))
+                        .unwrap_or_default();
+                    return mark_class != class;
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A lookup, with the subtables the shaper supports.
+struct ShapingLookup<T> {
+    flag: LookupFlag,
+    mark_filtering_set: Option<u16>,
+    subtables: Vec<T>,
+}
+
+impl<T> ShapingLookup<T> {
+    /// Collects the supported subtables of a lookup, following extension
+    /// subtables with `resolve`.
+    fn new<'a, S: FontRead<'a>>(
+        lookup: Lookup<'a, S>,
+        resolve: impl Fn(S) -> Result<Option<T>, ReadError>,
+    ) -> Result<Self, ReadError> {
+        let flag = lookup.lookup_flag();
+        let mut subtables = Vec::new();
+        for subtable in lookup.subtables() {
+            subtables.extend(resolve(subtable?)?);
+        }
+        Ok(Self {
+            flag,
+            mark_filtering_set: flag
+                .use_mark_filtering_set()
+                .then(|| lookup.mark_filtering_set()),
+            subtables,
+        })
+    }
+}
+
+enum Substitution<'a> {
+    Single(SingleSubst<'a>),
+    Ligature(LigatureSubstFormat1<'a>),
+}
+
+impl<'a> Substitution<'a> {
+    /// Returns `None` if the lookup type is not supported.
+    fn lookup(lookup: SubstitutionLookup<'a>) -> Option<Result<ShapingLookup<Self>, ReadError>> {
+        let lookup = match lookup {
+            SubstitutionLookup::Single(lookup) => {
+                ShapingLookup::new(lookup, |subtable| Ok(Some(Self::Single(subtable))))
+            }
+            SubstitutionLookup::Ligature(lookup) => {
+                ShapingLookup::new(lookup, |subtable| Ok(Some(Self::Ligature(subtable))))
+            }
+            SubstitutionLookup::Extension(lookup) => {
+                ShapingLookup::new(lookup, |subtable| match subtable {
+                    gsub::ExtensionSubtable::Single(extension) => {
+                        Ok(Some(Self::Single(extension.extension()?)))
+                    }
+                    gsub::ExtensionSubtable::Ligature(extension) => {
+                        Ok(Some(Self::Ligature(extension.extension()?)))
+                    }
+                    _ => Ok(None),
+                })
+            }
+            _ => return None,
+        };
+        Some(lookup)
+    }
+}
+
+enum Positioning<'a> {
+    Pair(PairPos<'a>),
+    MarkToBase(MarkBasePosFormat1<'a>),
+    MarkToLig(MarkLigPosFormat1<'a>),
+    MarkToMark(MarkMarkPosFormat1<'a>),
+}
+
+impl<'a> Positioning<'a> {
+    /// Returns `None` if the lookup type is not supported.
+    fn lookup(lookup: PositionLookup<'a>) -> Option<Result<ShapingLookup<Self>, ReadError>> {
+        let lookup = match lookup {
+            PositionLookup::Pair(lookup) => {
+                ShapingLookup::new(lookup, |subtable| Ok(Some(Self::Pair(subtable))))
+            }
+            PositionLookup::MarkToBase(lookup) => {
+                ShapingLookup::new(lookup, |subtable| Ok(Some(Self::MarkToBase(subtable))))
+            }
+            PositionLookup::MarkToLig(lookup) => {
+                ShapingLookup::new(lookup, |subtable| Ok(Some(Self::MarkToLig(subtable))))
+            }
+            PositionLookup::MarkToMark(lookup) => {
+                ShapingLookup::new(lookup, |subtable| Ok(Some(Self::MarkToMark(subtable))))
+            }
+            PositionLookup::Extension(lookup) => {
+                ShapingLookup::new(lookup, |subtable| match subtable {
+                    gpos::ExtensionSubtable::Pair(extension) => {
+                        Ok(Some(Self::Pair(extension.extension()?)))
+                    }
+                    gpos::ExtensionSubtable::MarkToBase(extension) => {
+                        Ok(Some(Self::MarkToBase(extension.extension()?)))
+                    }
+                    gpos::ExtensionSubtable::MarkToLig(extension) => {
+                        Ok(Some(Self::MarkToLig(extension.extension()?)))
+                    }
+                    gpos::ExtensionSubtable::MarkToMark(extension) => {
+                        Ok(Some(Self::MarkToMark(extension.extension()?)))
+                    }
+                    _ => Ok(None),
+                })
+            }
+            _ => return None,
+        };
+        Some(lookup)
+    }
+}
+
+/// Returns the indices of the lookups of the required feature and the given
+/// features, for a script and language, in lookup list order.
+fn feature_lookups(
+    script_list: &ScriptList,
+    feature_list: &FeatureList,
+    script: Tag,
+    language: Option<Tag>,
+    features: &[Tag],
+) -> Result<Vec<u16>, ReadError> {
+    let Some(selected) = script_list.select(&[script]) else {
+        return Ok(Vec::new());
+    };
+    let Some(lang_sys) = script_list
+        .get(selected.index)?
+        .select_lang_sys(language.as_slice())
+    else {
+        return Ok(Vec::new());
+    };
+    let mut lookups = Vec::new();
+    for feature in lang_sys?.features(feature_list) {
+        let feature = feature?;
+        if feature.is_required || features.contains(&feature.tag) {
+            lookups.extend(
+                feature
+                    .feature
+                    .lookup_list_indices()
+                    .iter()
+                    .map(|index| index.get()),
+            );
+        }
+    }
+    lookups.sort_unstable();
+    lookups.dedup();
+    Ok(lookups)
+}
+
+fn apply_single(subtable: &SingleSubst, glyph: &mut ShapedGlyph) -> Result<bool, ReadError> {
+    match subtable {
+        SingleSubst::Format1(subtable) => {
+            if subtable.coverage()?.get(glyph.glyph_id).is_none() {
+                return Ok(false);
+            }
+            // addition is modulo 65536
+            let delta = subtable.delta_glyph_id() as u16;
+            glyph.glyph_id = GlyphId::new(glyph.glyph_id.to_u16().wrapping_add(delta));
+        }
+        SingleSubst::Format2(subtable) => {
+            let Some(index) = subtable.coverage()?.get(glyph.glyph_id) else {
+                return Ok(false);
+            };
+            glyph.glyph_id = subtable
+                .substitute_glyph_ids()
+                .get(index as usize)
+                .ok_or(ReadError::OutOfBounds)?
+                .get();
+        }
+    }
+    Ok(true)
+}
+
+fn adjust(glyph: &mut ShapedGlyph, record: &ValueRecord) {
+    glyph.x_offset += record.x_placement().unwrap_or_default() as i32;
+    glyph.y_offset += record.y_placement().unwrap_or_default() as i32;
+    glyph.x_advance += record.x_advance().unwrap_or_default() as i32;
+    glyph.y_advance += record.y_advance().unwrap_or_default() as i32;
+}
+
+/// Returns the class and anchor of the mark at the given coverage index.
+fn mark_anchor<'a>(
+    mark_array: gpos::MarkArray<'a>,
+    index: u16,
+) -> Result<(u16, AnchorTable<'a>), ReadError> {
+    let record = mark_array
+        .mark_records()
+        .get(index as usize)
+        .ok_or(ReadError::OutOfBounds)?;
+    Ok((
+        record.mark_class(),
+        record.mark_anchor(mark_array.offset_data())?,
+    ))
+}
+
+/// Moves the mark so that its anchor coincides with that of the base.
+fn attach(
+    glyphs: &mut [ShapedGlyph],
+    base: usize,
+    mark: usize,
+    base_anchor: &AnchorTable,
+    mark_anchor: &AnchorTable,
+) {
+    let advance = glyphs[base..mark]
+        .iter()
+        .map(|glyph| glyph.x_advance)
+        .sum::<i32>();
+    let (base_x, base_y) = (glyphs[base].x_offset, glyphs[base].y_offset);
+    let glyph = &mut glyphs[mark];
+    glyph.x_offset =
+        base_x + base_anchor.x_coordinate() as i32 - mark_anchor.x_coordinate() as i32 - advance;
+    glyph.y_offset = base_y + base_anchor.y_coordinate() as i32 - mark_anchor.y_coordinate() as i32;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use read_fonts::FontRef;
+    use write_fonts::{
+        tables::{
+            cmap::Cmap,
+            gdef::Gdef,
+            gpos::{AnchorTable, GposBuilder, MarkBaseBuilder, PairPosBuilder},
+            gsub::{GsubBuilder, LigatureSubBuilder, SingleSubBuilder},
+            hhea::Hhea,
+            hmtx::{Hmtx, LongMetric},
+            layout::{ClassDefBuilder, LookupFlag},
+            maxp::Maxp,
+        },
+        FontBuilder,
+    };
+
+    const LATN: Tag = Tag::new(b"latn");
+    const TRK: Tag = Tag::new(b"TRK ");
+    const DFLT: Tag = Tag::new(b"dflt");
+
+    const F: GlyphId = GlyphId::new(1);
+    const I: GlyphId = GlyphId::new(2);
+    const F_I: GlyphId = GlyphId::new(3);
+    const A: GlyphId = GlyphId::new(4);
+    const V: GlyphId = GlyphId::new(5);
+    const ACUTE: GlyphId = GlyphId::new(6);
+    const DOTLESS_I: GlyphId = GlyphId::new(7);
+    const ADVANCES: [u16; 8] = [500, 300, 250, 550, 600, 600, 200, 250];
+
+    /// Builds a font with an `f_i` ligature, a Turkish localized form,
+    /// kerning between `A` and `V` and an acute accent that attaches to `A`.
+    fn build_font() -> Vec<u8> {
+        let cmap = Cmap::from_mappings(BTreeMap::from([
+            ('f', F),
+            ('i', I),
+            ('A', A),
+            ('V', V),
+            ('\u{301}', ACUTE),
+        ]));
+        let hhea = Hhea {
+            number_of_long_metrics: ADVANCES.len() as u16,
+            ..Default::default()
+        };
+        let hmtx = Hmtx::new(
+            ADVANCES
+                .iter()
+                .map(|advance| LongMetric::new(*advance, 0))
+                .collect(),
+            vec![],
+        );
+        let classes = [(F, 1), (I, 1), (F_I, 2), (A, 1), (V, 1), (ACUTE, 3)]
+            .into_iter()
+            .collect::<ClassDefBuilder>();
+        let gdef = Gdef::new(Some(classes.build()), None, None, None);
+
+        let mut gsub = GsubBuilder::new();
+        let mut locl = SingleSubBuilder::new();
+        locl.insert(I, DOTLESS_I);
+        let locl = gsub.add_single(LookupFlag::empty(), locl);
+        let mut liga = LigatureSubBuilder::new();
+        liga.insert(&[F, I], F_I);
+        let mut ignore_marks = LookupFlag::empty();
+        ignore_marks.set_ignore_marks(true);
+        let liga = gsub.add_ligature(ignore_marks, liga);
+        let mut salt = SingleSubBuilder::new();
+        salt.insert(A, V);
+        let salt = gsub.add_single(LookupFlag::empty(), salt);
+        gsub.add_feature(LATN, TRK, Tag::new(b"locl"), &[locl]);
+        for language in [DFLT, TRK] {
+            gsub.add_feature(LATN, language, Tag::new(b"liga"), &[liga]);
+            gsub.add_feature(LATN, language, Tag::new(b"salt"), &[salt]);
+        }
+
+        let mut gpos = GposBuilder::new();
+        let mut kern = PairPosBuilder::new();
+        kern.insert_kern(A, V, -80);
+        let kern = gpos.add_pair(LookupFlag::empty(), kern);
+        let mut mark = MarkBaseBuilder::new();
+        mark.add_mark(ACUTE, 0, AnchorTable::format_1(100, 500));
+        mark.add_base(A, 0, AnchorTable::format_1(300, 700));
+        let mark = gpos.add_mark_base(LookupFlag::empty(), mark);
+        gpos.add_feature(LATN, DFLT, Tag::new(b"kern"), &[kern]);
+        gpos.add_feature(LATN, DFLT, Tag::new(b"mark"), &[mark]);
+
+        let mut builder = FontBuilder::default();
+        builder
+            .add_typed_table(&cmap)
+            .unwrap()
+            .add_typed_table(&Maxp::new(ADVANCES.len() as u16))
+            .unwrap()
+            .add_typed_table(&hhea)
+            .unwrap()
+            .add_typed_table(&hmtx)
+            .unwrap()
+            .add_typed_table(&gdef)
+            .unwrap()
+            .add_typed_table(&gsub.build())
+            .unwrap()
+            .add_typed_table(&gpos.build())
+            .unwrap();
+        builder.build()
+    }
+
+    fn glyph(glyph_id: GlyphId, cluster: u32, x_advance: i32) -> ShapedGlyph {
+        ShapedGlyph {
+            glyph_id,
+            cluster,
+            x_advance,
+            y_advance: 0,
+            x_offset: 0,
+            y_offset: 0,
+        }
+    }
+
+    #[test]
+    fn ligature_and_kerning() {
+        let data = build_font();
+        let font = FontRef::new(&data).unwrap();
+        let shaper = Shaper::new(&font, LATN, None);
+        assert_eq!(
+            shaper.shape("fiAV"),
+            [glyph(F_I, 0, 550), glyph(A, 2, 520), glyph(V, 3, 600)]
+        );
+        // the ligature lookup skips the mark, which follows the ligature
+        assert_eq!(
+            shaper.shape("f\u{301}i"),
+            [glyph(F_I, 0, 550), glyph(ACUTE, 1, 0)]
+        );
+        assert_eq!(shaper.shape("x"), [glyph(GlyphId::NOTDEF, 0, 500)]);
+    }
+
+    #[test]
+    fn mark_attachment() {
+        let data = build_font();
+        let font = FontRef::new(&data).unwrap();
+        let glyphs = Shaper::new(&font, LATN, None).shape("A\u{301}");
+        assert_eq!(glyphs.len(), 2);
+        assert_eq!(glyphs[0], glyph(A, 0, 600));
+        // the anchors line up, relative to the pen position after the base
+        assert_eq!(
+            glyphs[1],
+            ShapedGlyph {
+                x_offset: 300 - 100 - 600,
+                y_offset: 700 - 500,
+                ..glyph(ACUTE, 1, 0)
+            }
+        );
+    }
+
+    #[test]
+    fn language() {
+        let data = build_font();
+        let font = FontRef::new(&data).unwrap();
+        let shape = |script, language| {
+            Shaper::new(&font, script, language)
+                .shape("fi")
+                .iter()
+                .map(|glyph| glyph.glyph_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(shape(LATN, None), [F_I]);
+        assert_eq!(shape(LATN, Some(TRK)), [F, DOTLESS_I]);
+        // unknown languages use the default language system
+        assert_eq!(shape(LATN, Some(Tag::new(b"DEU "))), [F_I]);
+        // unknown scripts fall back to latn
+        assert_eq!(shape(Tag::new(b"cyrl"), None), [F_I]);
+    }
+
+    #[test]
+    fn no_layout_tables() {
+        let mut builder = FontBuilder::default();
+        let cmap = Cmap::from_mappings(BTreeMap::from([('A', A)]));
+        builder.add_typed_table(&cmap).unwrap();
+        let data = builder.build();
+        let font = FontRef::new(&data).unwrap();
+        let glyphs = Shaper::new(&font, LATN, None).shape("AB");
+        assert_eq!(glyphs, [glyph(A, 0, 0), glyph(GlyphId::NOTDEF, 1, 0)]);
+    }
+}
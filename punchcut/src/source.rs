@@ -3,4 +3,43 @@ Sources for glyphs in a font.
 
 */
 
+pub mod bitmap;
+pub mod cff;
+pub mod colr;
 pub mod glyf;
+
+use crate::{GlyphId, NormalizedCoord, Pen, Result};
+
+/// Provider of glyph outlines that can be registered with a scaler.
+///
+/// A scaler loads each outline from the first source that contains the
+/// glyph, in the following order:
+///
+/// 1. Sources registered with
+///    [`ScalerBuilder::source`](crate::ScalerBuilder::source), in the order
+///    they were added.
+/// 2. TrueType outlines from the `glyf` table.
+/// 3. PostScript outlines from the `CFF2` or `CFF ` table, only if the font
+///    has no TrueType outlines.
+///
+/// Color glyphs and embedded bitmaps are available from their own scaler
+/// methods, and the layers of color glyphs are loaded from these sources.
+/// Hinting and synthesis are applied to outlines from all sources.
+pub trait GlyphSource {
+    /// Returns true if the source provides an outline for the glyph.
+    fn contains(&self, glyph_id: GlyphId) -> bool;
+
+    /// Loads the outline for the specified glyph identifier and invokes the
+    /// functions in the given pen for each path command.
+    ///
+    /// The size is in pixels per em, where a size of 0.0 requests an
+    /// outline in font units. The coordinates are the normalized variation
+    /// coordinates of the scaler.
+    fn outline(
+        &mut self,
+        glyph_id: GlyphId,
+        size: f32,
+        coords: &[NormalizedCoord],
+        pen: &mut dyn Pen,
+    ) -> Result<()>;
+}
@@ -0,0 +1,732 @@
+/*!
+Embedded bitmap glyphs loaded from the `CBDT`/`CBLC`, `sbix` and
+`EBDT`/`EBLC` tables.
+
+A font may have several strikes, each a set of bitmaps for a particular size.
+A single strike is selected for the requested size, and its bitmaps are
+returned with the scale needed to display them at that size.
+
+*/
+
+use crate::{GlyphId, Result};
+
+use read_fonts::{
+    types::{Rect, Tag},
+    ReadError, TableProvider,
+};
+
+/// The graphic type of PNG images in `sbix`.
+const PNG: Tag = Tag::new(b"png ");
+/// The graphic type of `sbix` glyphs that use the image of another glyph.
+const DUPE: Tag = Tag::new(b"dupe");
+
+/// Size of a `BitmapSize` record in `CBLC` and `EBLC`.
+const BITMAP_SIZE_LEN: usize = 48;
+/// Size of small glyph metrics in `CBDT` and `EBDT`.
+const SMALL_METRICS_LEN: usize = 5;
+/// Size of big glyph metrics in `CBDT`, `EBDT`, `CBLC` and `EBLC`.
+const BIG_METRICS_LEN: usize = 8;
+
+/// Embedded bitmap for a glyph.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BitmapGlyph<'a> {
+    /// The image data.
+    pub data: BitmapData<'a>,
+    /// Metrics of the image, in pixels of the strike.
+    pub metrics: BitmapMetrics,
+    /// Horizontal size of the strike, in pixels per em.
+    pub ppem_x: u16,
+    /// Vertical size of the strike, in pixels per em.
+    pub ppem_y: u16,
+    /// Horizontal scale factor from pixels of the strike to pixels at the
+    /// requested size.
+    ///
+    /// This is 1.0 if the strike is exactly the requested size, or if no
+    /// size was requested.
+    pub scale_x: f32,
+    /// Vertical scale factor from pixels of the strike to pixels at the
+    /// requested size.
+    pub scale_y: f32,
+}
+
+/// Data of an embedded bitmap.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BitmapData<'a> {
+    /// A PNG image.
+    Png(&'a [u8]),
+    /// An uncompressed alpha mask, with rows from top to bottom.
+    Mask {
+        data: &'a [u8],
+        /// Number of bits per pixel: 1, 2, 4 or 8.
+        bit_depth: u8,
+        /// `true` if the rows are packed without padding; otherwise each row
+        /// starts on a byte boundary.
+        is_packed: bool,
+    },
+    /// An image in another format, identified by its `sbix` graphic type,
+    /// such as JPEG or TIFF.
+    Other { graphic_type: Tag, data: &'a [u8] },
+}
+
+/// Metrics of an embedded bitmap, in pixels of its strike.
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+pub struct BitmapMetrics {
+    /// Width of the image, or zero if it could not be determined.
+    pub width: u32,
+    /// Height of the image, or zero if it could not be determined.
+    pub height: u32,
+    /// Distance from the glyph origin to the left edge of the image.
+    pub bearing_x: f32,
+    /// Distance from the baseline to the top edge of the image.
+    pub bearing_y: f32,
+    /// Horizontal advance of the glyph.
+    pub advance: f32,
+}
+
+impl BitmapMetrics {
+    /// Returns the bounds of the image relative to the glyph origin, with
+    /// y increasing upward.
+    pub fn bounds(&self) -> Rect<f32> {
+        Rect::new(
+            self.bearing_x,
+            self.bearing_y - self.height as f32,
+            self.bearing_x + self.width as f32,
+            self.bearing_y,
+        )
+    }
+}
+
+/// Embedded bitmap source for a specific font and size.
+pub struct Bitmaps<'a> {
+    strike: Strike<'a>,
+    /// Requested size, or zero for the largest strike.
+    size: f32,
+    /// Horizontal advances, used for `sbix` glyphs.
+    advances: Advances<'a>,
+}
+
+/// A single strike selected from one of the bitmap tables.
+enum Strike<'a> {
+    /// A strike from `CBLC` and `CBDT`, or `EBLC` and `EBDT`.
+    Located {
+        location: &'a [u8],
+        data: &'a [u8],
+        /// Offset of the `BitmapSize` record in the location table.
+        size_offset: usize,
+        ppem_x: u8,
+        ppem_y: u8,
+        bit_depth: u8,
+    },
+    /// A strike from `sbix`.
+    Sbix {
+        sbix: &'a [u8],
+        /// Offset of the strike in the table.
+        offset: usize,
+        ppem: u16,
+        glyph_count: u16,
+    },
+}
+
+/// Horizontal advances in font units.
+struct Advances<'a> {
+    font: &'a [u8],
+    units_per_em: u16,
+    glyph_count: u16,
+    long_metrics_count: u16,
+}
+
+impl<'a> Bitmaps<'a> {
+    /// Creates a new bitmap source for the specified font, selecting the
+    /// strike that best matches the size in pixels per em.
+    ///
+    /// The smallest strike at least as large as the requested size is
+    /// selected, or the largest strike if there is none, or if the size is
+    /// zero. Color bitmaps in `CBDT` are preferred to those in `sbix`, and
+    /// both are preferred to the monochrome and grayscale bitmaps in `EBDT`.
+    pub fn new(font: &impl TableProvider<'a>, size: f32) -> Result<Self> {
+        let size = size.abs();
+        let glyph_count = font.maxp()?.num_glyphs();
+        let strike = Self::located_strike(font, Tag::CBLC, Tag::CBDT, size)
+            .or_else(|| Self::sbix_strike(font, size, glyph_count))
+            .or_else(|| Self::located_strike(font, Tag::EBLC, Tag::EBDT, size))
+            .ok_or(ReadError::TableIsMissing(Tag::CBDT))?;
+        let hhea = font.hhea()?;
+        let advances = Advances {
+            font: font
+                .data_for_tag(Tag::HMTX)
+                .map(|data| data.read_array(0..data.len()))
+                .transpose()?
+                .unwrap_or_default(),
+            units_per_em: font.head()?.units_per_em(),
+            glyph_count,
+            long_metrics_count: hhea.number_of_long_metrics(),
+        };
+        Ok(Self {
+            strike,
+            size,
+            advances,
+        })
+    }
+
+    /// Returns true if the selected strike contains color bitmaps.
+    pub fn is_color(&self) -> bool {
+        match &self.strike {
+            Strike::Located { bit_depth, .. } => *bit_depth == 32,
+            Strike::Sbix { .. } => true,
+        }
+    }
+
+    /// Returns the bitmap for the specified glyph, if the selected strike
+    /// has one.
+    pub fn get(&self, glyph_id: GlyphId) -> Option<BitmapGlyph<'a>> {
+        let (data, metrics, ppem_x, ppem_y) = match &self.strike {
+            Strike::Located {
+                location,
+                data,
+                size_offset,
+                ppem_x,
+                ppem_y,
+                bit_depth,
+            } => {
+                let (data, metrics) =
+                    located_glyph(location, data, *size_offset, *bit_depth, glyph_id)?;
+                (data, metrics, *ppem_x as u16, *ppem_y as u16)
+            }
+            Strike::Sbix {
+                sbix,
+                offset,
+                ppem,
+                glyph_count,
+            } => {
+                let (data, mut metrics) = sbix_glyph(sbix, *offset, *glyph_count, glyph_id)?;
+                metrics.advance = self.advances.get(glyph_id)? as f32 * *ppem as f32
+                    / self.advances.units_per_em.max(1) as f32;
+                (data, metrics, *ppem, *ppem)
+            }
+        };
+        let scale = |ppem: u16| {
+            if self.size == 0.0 || ppem == 0 {
+                1.0
+            } else {
+                self.size / ppem as f32
+            }
+        };
+        Some(BitmapGlyph {
+            data,
+            metrics,
+            ppem_x,
+            ppem_y,
+            scale_x: scale(ppem_x),
+            scale_y: scale(ppem_y),
+        })
+    }
+
+    fn located_strike(
+        font: &impl TableProvider<'a>,
+        location_tag: Tag,
+        data_tag: Tag,
+        size: f32,
+    ) -> Option<Strike<'a>> {
+        let location = font.data_for_tag(location_tag)?;
+        let location = location.read_array::<u8>(0..location.len()).ok()?;
+        let data = font.data_for_tag(data_tag)?;
+        let data = data.read_array::<u8>(0..data.len()).ok()?;
+        let count = read_u32(location, 4).ok()? as usize;
+        let sizes = (0..count).filter_map(|i| {
+            let offset = 8 + i * BITMAP_SIZE_LEN;
+            let record = location.get(offset..offset + BITMAP_SIZE_LEN)?;
+            Some((offset, record[44], record[45], record[46]))
+        });
+        let (size_offset, ppem_x, ppem_y, bit_depth) =
+            select_strike(sizes, size, |(_, _, ppem_y, _)| *ppem_y as u16)?;
+        Some(Strike::Located {
+            location,
+            data,
+            size_offset,
+            ppem_x,
+            ppem_y,
+            bit_depth,
+        })
+    }
+
+    fn sbix_strike(
+        font: &impl TableProvider<'a>,
+        size: f32,
+        glyph_count: u16,
+    ) -> Option<Strike<'a>> {
+        let sbix = font.data_for_tag(Tag::SBIX)?;
+        let sbix = sbix.read_array::<u8>(0..sbix.len()).ok()?;
+        let count = read_u32(sbix, 4).ok()? as usize;
+        let strikes = (0..count).filter_map(|i| {
+            let offset = read_u32(sbix, 8 + i * 4).ok()? as usize;
+            Some((offset, read_u16(sbix, offset).ok()?))
+        });
+        let (offset, ppem) = select_strike(strikes, size, |(_, ppem)| *ppem)?;
+        Some(Strike::Sbix {
+            sbix,
+            offset,
+            ppem,
+            glyph_count,
+        })
+    }
+}
+
+impl Advances<'_> {
+    fn get(&self, glyph_id: GlyphId) -> Option<u16> {
+        if glyph_id.to_u16() >= self.glyph_count {
+            return None;
+        }
+        // glyphs after the last long metric have its advance
+        let index = glyph_id
+            .to_u16()
+            .min(self.long_metrics_count.checked_sub(1)?);
+        read_u16(self.font, index as usize * 4).ok()
+    }
+}
+
+/// Selects the smallest strike at least as large as `size`, or the largest
+/// strike if there is none or `size` is zero.
+fn select_strike<T>(
+    strikes: impl Iterator<Item = T>,
+    size: f32,
+    ppem: impl Fn(&T) -> u16,
+) -> Option<T> {
+    let mut best: Option<T> = None;
+    for strike in strikes {
+        let Some(current) = &best else {
+            best = Some(strike);
+            continue;
+        };
+        let (new, old) = (ppem(&strike) as f32, ppem(current) as f32);
+        let is_better = if size == 0.0 || old < size {
+            new > old
+        } else {
+            new >= size && new < old
+        };
+        if is_better {
+            best = Some(strike);
+        }
+    }
+    best
+}
+
+/// Finds the image of a glyph in a `CBDT` or `EBDT` strike.
+fn located_glyph<'a>(
+    location: &'a [u8],
+    data: &'a [u8],
+    size_offset: usize,
+    bit_depth: u8,
+    glyph_id: GlyphId,
+) -> Option<(BitmapData<'a>, BitmapMetrics)> {
+    let glyph_id = glyph_id.to_u16();
+    let list_offset = read_u32(location, size_offset).ok()? as usize;
+    let subtable_count = read_u32(location, size_offset + 8).ok()? as usize;
+    // each record is the first and last glyph, and the offset of the subtable
+    let (first, subtable_offset) = (0..subtable_count).find_map(|i| {
+        let record = list_offset + i * 8;
+        let first = read_u16(location, record).ok()?;
+        let last = read_u16(location, record + 2).ok()?;
+        let offset = read_u32(location, record + 4).ok()? as usize;
+        (first..=last)
+            .contains(&glyph_id)
+            .then_some((first, list_offset + offset))
+    })?;
+    let index_format = read_u16(location, subtable_offset).ok()?;
+    let image_format = read_u16(location, subtable_offset + 2).ok()?;
+    let image_data_offset = read_u32(location, subtable_offset + 4).ok()? as usize;
+    let body = subtable_offset + 8;
+    let index = (glyph_id - first) as usize;
+    // the range of the image in the data table, and metrics shared by all
+    // images in the subtable
+    let (start, end, index_metrics) = match index_format {
+        1 => (
+            read_u32(location, body + index * 4).ok()? as usize,
+            read_u32(location, body + (index + 1) * 4).ok()? as usize,
+            None,
+        ),
+        3 => (
+            read_u16(location, body + index * 2).ok()? as usize,
+            read_u16(location, body + (index + 1) * 2).ok()? as usize,
+            None,
+        ),
+        2 => {
+            let image_size = read_u32(location, body).ok()? as usize;
+            let metrics = location.get(body + 4..body + 4 + BIG_METRICS_LEN)?;
+            (
+                index * image_size,
+                (index + 1) * image_size,
+                Some(big_metrics(metrics)),
+            )
+        }
+        4 => {
+            let count = read_u32(location, body).ok()? as usize;
+            let pairs = body + 4;
+            let position =
+                (0..count).find(|i| read_u16(location, pairs + i * 4).ok() == Some(glyph_id))?;
+            let pair = pairs + position * 4;
+            (
+                read_u16(location, pair + 2).ok()? as usize,
+                read_u16(location, pair + 6).ok()? as usize,
+                None,
+            )
+        }
+        5 => {
+            let image_size = read_u32(location, body).ok()? as usize;
+            let metrics = location.get(body + 4..body + 4 + BIG_METRICS_LEN)?;
+            let ids = body + 4 + BIG_METRICS_LEN + 4;
+            let count = read_u32(location, ids - 4).ok()? as usize;
+            let position =
+                (0..count).find(|i| read_u16(location, ids + i * 2).ok() == Some(glyph_id))?;
+            (
+                position * image_size,
+                (position + 1) * image_size,
+                Some(big_metrics(metrics)),
+            )
+        }
+        _ => return None,
+    };
+    if end <= start {
+        return None;
+    }
+    let image = data.get(image_data_offset + start..image_data_offset + end)?;
+    let mask = |data, is_packed| BitmapData::Mask {
+        data,
+        bit_depth,
+        is_packed,
+    };
+    let (metrics, image) = match image_format {
+        1 | 2 => {
+            let (metrics, data) = split(image, SMALL_METRICS_LEN)?;
+            (small_metrics(metrics), mask(data, image_format == 2))
+        }
+        5 => (index_metrics?, mask(image, true)),
+        6 | 7 => {
+            let (metrics, data) = split(image, BIG_METRICS_LEN)?;
+            (big_metrics(metrics), mask(data, image_format == 7))
+        }
+        17 => {
+            let (metrics, data) = split(image, SMALL_METRICS_LEN)?;
+            (small_metrics(metrics), BitmapData::Png(png_data(data)?))
+        }
+        18 => {
+            let (metrics, data) = split(image, BIG_METRICS_LEN)?;
+            (big_metrics(metrics), BitmapData::Png(png_data(data)?))
+        }
+        19 => (index_metrics?, BitmapData::Png(png_data(image)?)),
+        // composite bitmaps are not supported
+        _ => return None,
+    };
+    Some((image, metrics))
+}
+
+/// Finds the image of a glyph in an `sbix` strike.
+fn sbix_glyph<'a>(
+    sbix: &'a [u8],
+    strike_offset: usize,
+    glyph_count: u16,
+    glyph_id: GlyphId,
+) -> Option<(BitmapData<'a>, BitmapMetrics)> {
+    let mut glyph_id = glyph_id.to_u16();
+    // a dupe refers to another glyph, which is not itself a dupe
+    for _ in 0..2 {
+        if glyph_id >= glyph_count {
+            return None;
+        }
+        let offsets = strike_offset + 4 + glyph_id as usize * 4;
+        let start = strike_offset + read_u32(sbix, offsets).ok()? as usize;
+        let end = strike_offset + read_u32(sbix, offsets + 4).ok()? as usize;
+        let glyph = sbix.get(start..end)?;
+        let graphic_type = Tag::new_checked(glyph.get(4..8)?).ok()?;
+        let data = &glyph[8..];
+        if graphic_type == DUPE {
+            glyph_id = read_u16(data, 0).ok()?;
+            continue;
+        }
+        let origin_x = read_u16(glyph, 0).ok()? as i16 as f32;
+        let origin_y = read_u16(glyph, 2).ok()? as i16 as f32;
+        let (width, height) = if graphic_type == PNG {
+            png_size(data).unwrap_or_default()
+        } else {
+            (0, 0)
+        };
+        let image = if graphic_type == PNG {
+            BitmapData::Png(data)
+        } else {
+            BitmapData::Other { graphic_type, data }
+        };
+        // the origin offset locates the bottom left corner of the image
+        let metrics = BitmapMetrics {
+            width,
+            height,
+            bearing_x: origin_x,
+            bearing_y: origin_y + height as f32,
+            advance: 0.0,
+        };
+        return Some((image, metrics));
+    }
+    None
+}
+
+fn small_metrics(data: &[u8]) -> BitmapMetrics {
+    BitmapMetrics {
+        height: data[0] as u32,
+        width: data[1] as u32,
+        bearing_x: data[2] as i8 as f32,
+        bearing_y: data[3] as i8 as f32,
+        advance: data[4] as f32,
+    }
+}
+
+/// Reads the horizontal metrics from big glyph metrics.
+fn big_metrics(data: &[u8]) -> BitmapMetrics {
+    small_metrics(&data[..SMALL_METRICS_LEN])
+}
+
+/// Splits glyph metrics of the given size from the start of an image.
+fn split(image: &[u8], metrics_len: usize) -> Option<(&[u8], &[u8])> {
+    (image.len() >= metrics_len).then(|| image.split_at(metrics_len))
+}
+
+/// Returns the PNG data that follows its 32-bit length.
+fn png_data(data: &[u8]) -> Option<&[u8]> {
+    let len = read_u32(data, 0).ok()? as usize;
+    data.get(4..4 + len)
+}
+
+/// Returns the width and height of a PNG image, from its header.
+fn png_size(data: &[u8]) -> Option<(u32, u32)> {
+    // the signature, then the length and type of the IHDR chunk
+    if data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    Some((read_u32(data, 16).ok()?, read_u32(data, 20).ok()?))
+}
+
+fn read_u16(data: &[u8], pos: usize) -> core::result::Result<u16, ReadError> {
+    data.get(pos..pos + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or(ReadError::OutOfBounds)
+}
+
+fn read_u32(data: &[u8], pos: usize) -> core::result::Result<u32, ReadError> {
+    data.get(pos..pos + 4)
+        .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or(ReadError::OutOfBounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitmapData, BitmapMetrics, Bitmaps, Rect};
+    use crate::{font::*, Context, GlyphId};
+
+    use read_fonts::{test_data::test_fonts, FontData};
+
+    /// A TrueType font with added bitmap tables.
+    struct BitmapFont {
+        font: FontRef<'static>,
+        tables: Vec<(Tag, Vec<u8>)>,
+    }
+
+    impl BitmapFont {
+        fn new(tables: Vec<(&[u8; 4], Vec<u8>)>) -> Self {
+            Self {
+                font: FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap(),
+                tables: tables
+                    .into_iter()
+                    .map(|(tag, data)| (Tag::new(tag), data))
+                    .collect(),
+            }
+        }
+    }
+
+    impl<'a> TableProvider<'a> for &'a BitmapFont {
+        fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>> {
+            self.tables
+                .iter()
+                .find(|(table_tag, _)| *table_tag == tag)
+                .map(|(_, data)| FontData::new(data))
+                .or_else(|| self.font.data_for_tag(tag))
+        }
+    }
+
+    /// Builds a `CBLC` or `EBLC` table where each strike has a single index
+    /// subtable, given as its ppem, bit depth, glyph range and subtable.
+    fn make_location(strikes: &[(u8, u8, u16, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut sizes = vec![0, 3, 0, 0];
+        sizes.extend((strikes.len() as u32).to_be_bytes());
+        let mut lists = Vec::new();
+        let mut list_offset = sizes.len() + strikes.len() * super::BITMAP_SIZE_LEN;
+        for (ppem, bit_depth, first, last, subtable) in strikes {
+            let mut list = [first.to_be_bytes(), last.to_be_bytes()].concat();
+            list.extend(8u32.to_be_bytes());
+            list.extend(subtable);
+            sizes.extend((list_offset as u32).to_be_bytes());
+            sizes.extend((list.len() as u32).to_be_bytes());
+            sizes.extend(1u32.to_be_bytes());
+            sizes.extend([0; 28]);
+            sizes.extend(first.to_be_bytes());
+            sizes.extend(last.to_be_bytes());
+            sizes.extend([*ppem, *ppem, *bit_depth, 1]);
+            list_offset += list.len();
+            lists.extend(list);
+        }
+        [sizes, lists].concat()
+    }
+
+    /// Starts of PNG images with an `IHDR` chunk for the given size.
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        data.extend(width.to_be_bytes());
+        data.extend(height.to_be_bytes());
+        data
+    }
+
+    /// Color bitmaps for glyph 1 in strikes of 20 and 40 pixels per em,
+    /// using index format 1 and image format 17. Glyph 2 has no bitmap.
+    fn make_cbdt_font() -> BitmapFont {
+        let mut data = vec![0, 3, 0, 0];
+        let mut strikes = Vec::new();
+        for ppem in [20u8, 40] {
+            let mut image = vec![ppem, ppem / 2, 1, ppem, ppem + 2];
+            image.extend((png(1, 1).len() as u32).to_be_bytes());
+            image.extend(png(ppem as u32 / 2, ppem as u32));
+            let mut subtable = vec![0, 1, 0, 17];
+            subtable.extend((data.len() as u32).to_be_bytes());
+            for offset in [0, image.len(), image.len()] {
+                subtable.extend((offset as u32).to_be_bytes());
+            }
+            data.extend(image);
+            strikes.push((ppem, 32, 1, 2, subtable));
+        }
+        BitmapFont::new(vec![(b"CBLC", make_location(&strikes)), (b"CBDT", data)])
+    }
+
+    #[test]
+    fn cbdt_strike_selection() {
+        let font = make_cbdt_font();
+        for (size, ppem, scale) in [
+            (10.0, 20, 0.5),
+            (20.0, 20, 1.0),
+            (30.0, 40, 0.75),
+            (50.0, 40, 1.25),
+            (0.0, 40, 1.0),
+        ] {
+            let bitmaps = Bitmaps::new(&&font, size).unwrap();
+            let glyph = bitmaps.get(GlyphId::new(1)).unwrap();
+            assert_eq!((glyph.ppem_x, glyph.ppem_y), (ppem, ppem));
+            assert_eq!((glyph.scale_x, glyph.scale_y), (scale, scale));
+            assert_eq!(
+                glyph.data,
+                BitmapData::Png(&png(ppem as u32 / 2, ppem as u32))
+            );
+            assert_eq!(
+                glyph.metrics,
+                BitmapMetrics {
+                    width: ppem as u32 / 2,
+                    height: ppem as u32,
+                    bearing_x: 1.0,
+                    bearing_y: ppem as f32,
+                    advance: ppem as f32 + 2.0,
+                }
+            );
+            assert_eq!(
+                glyph.metrics.bounds(),
+                Rect::new(1.0, 0.0, 1.0 + (ppem / 2) as f32, ppem as f32)
+            );
+            for gid in [0, 2, 3] {
+                assert_eq!(bitmaps.get(GlyphId::new(gid)), None);
+            }
+        }
+    }
+
+    #[test]
+    fn ebdt_mask() {
+        // two byte images for glyphs 1 to 3 with shared metrics, using index
+        // format 2 and image format 5
+        let mut subtable = vec![0, 2, 0, 5, 0, 0, 0, 4, 0, 0, 0, 2];
+        subtable.extend([4, 4, 0xFF, 3, 5, 0, 0, 0]);
+        let location = make_location(&[(12, 1, 1, 3, subtable)]);
+        let data = [0, 2, 0, 0, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66].to_vec();
+        let font = BitmapFont::new(vec![(b"EBLC", location), (b"EBDT", data)]);
+        let bitmaps = Bitmaps::new(&&font, 12.0).unwrap();
+        assert!(!bitmaps.is_color());
+        let glyph = bitmaps.get(GlyphId::new(2)).unwrap();
+        assert_eq!(
+            glyph.data,
+            BitmapData::Mask {
+                data: &[0x33, 0x44],
+                bit_depth: 1,
+                is_packed: true,
+            }
+        );
+        assert_eq!(
+            glyph.metrics,
+            BitmapMetrics {
+                width: 4,
+                height: 4,
+                bearing_x: -1.0,
+                bearing_y: 3.0,
+                advance: 5.0,
+            }
+        );
+        assert_eq!(bitmaps.get(GlyphId::new(0)), None);
+    }
+
+    #[test]
+    fn sbix_glyphs() {
+        // one strike with a PNG for glyph 1 and a dupe of it for glyph 2
+        let mut image = [0, 2, 0xFF, 0xFD].to_vec();
+        image.extend(b"png ");
+        image.extend(png(10, 12));
+        let mut dupe = [0, 0, 0, 0].to_vec();
+        dupe.extend(b"dupe");
+        dupe.extend([0, 1]);
+        let mut sbix = vec![0, 1, 0, 1, 0, 0, 0, 1, 0, 0, 0, 12];
+        sbix.extend([0, 64, 0, 72]);
+        let first = 4 + 5 * 4;
+        let second = first + image.len();
+        let end = second + dupe.len();
+        for offset in [first, first, second, end, end] {
+            sbix.extend((offset as u32).to_be_bytes());
+        }
+        sbix.extend(&image);
+        sbix.extend(dupe);
+        let font = BitmapFont::new(vec![(b"sbix", sbix)]);
+        let advance = (&font).hmtx().unwrap().h_metrics()[1].advance();
+        let bitmaps = Bitmaps::new(&&font, 32.0).unwrap();
+        for gid in [1, 2] {
+            let glyph = bitmaps.get(GlyphId::new(gid)).unwrap();
+            assert_eq!(glyph.data, BitmapData::Png(&image[8..]));
+            assert_eq!((glyph.ppem_x, glyph.scale_x), (64, 0.5));
+            assert_eq!(
+                glyph.metrics,
+                BitmapMetrics {
+                    width: 10,
+                    height: 12,
+                    bearing_x: 2.0,
+                    bearing_y: 9.0,
+                    advance: advance as f32 * 64.0 / 2048.0,
+                }
+            );
+        }
+        for gid in [0, 3, 4] {
+            assert_eq!(bitmaps.get(GlyphId::new(gid)), None);
+        }
+    }
+
+    #[test]
+    fn scaler_bitmaps() {
+        let font = make_cbdt_font();
+        let mut cx = Context::new();
+        let scaler = cx.new_scaler().size(30.0).build(&&font);
+        assert!(scaler.has_bitmaps());
+        assert!(scaler.has_color_glyphs());
+        let glyph = scaler.bitmap(GlyphId::new(1)).unwrap();
+        assert_eq!(glyph.ppem_y, 40);
+
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let scaler = cx.new_scaler().build(&font);
+        assert!(!scaler.has_bitmaps());
+        assert_eq!(scaler.bitmap(GlyphId::new(1)), None);
+    }
+}
@@ -0,0 +1,314 @@
+/*!
+PostScript outlines loaded from the `CFF ` and `CFF2` tables.
+
+*/
+
+mod charstring;
+mod parse;
+mod scaler;
+
+pub use scaler::Scaler;
+
+#[cfg(test)]
+mod tests {
+    use super::Scaler;
+    use crate::test::{Path, PathElement::*};
+    use crate::{Context, GlyphId, NormalizedCoord, ScalerBuilder};
+
+    use read_fonts::{
+        types::{F2Dot14, Tag},
+        FontData, TableProvider,
+    };
+
+    /// A font with a `head` table and a single outline table.
+    struct TestFont {
+        head: Vec<u8>,
+        tag: Tag,
+        outlines: Vec<u8>,
+    }
+
+    impl TestFont {
+        fn new(tag: &[u8; 4], outlines: Vec<u8>, units_per_em: u16) -> Self {
+            let mut head = vec![0; 54];
+            head[..4].copy_from_slice(&[0, 1, 0, 0]);
+            head[18..20].copy_from_slice(&units_per_em.to_be_bytes());
+            Self {
+                head,
+                tag: Tag::new(tag),
+                outlines,
+            }
+        }
+    }
+
+    impl<'a> TableProvider<'a> for &'a TestFont {
+        fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>> {
+            match tag {
+                _ if tag == Tag::HEAD => Some(FontData::new(&self.head)),
+                _ if tag == self.tag => Some(FontData::new(&self.outlines)),
+                _ => None,
+            }
+        }
+    }
+
+    /// Compiles a charstring from a list of numbers, operator names and
+    /// raw bytes written as `#` followed by two hex digits.
+    fn charstring(source: &str) -> Vec<u8> {
+        let mut data = Vec::new();
+        for token in source.split_whitespace() {
+            if let Some(hex) = token.strip_prefix('#') {
+                data.push(u8::from_str_radix(hex, 16).unwrap());
+            } else if let Ok(value) = token.parse::<i32>() {
+                data.extend(number(value));
+            } else {
+                data.extend(match token {
+                    "hstem" => &[1][..],
+                    "rlineto" => &[5],
+                    "callsubr" => &[10],
+                    "return" => &[11],
+                    "endchar" => &[14],
+                    "blend" => &[16],
+                    "hintmask" => &[19],
+                    "rmoveto" => &[21],
+                    "callgsubr" => &[29],
+                    "hvcurveto" => &[31],
+                    _ => panic!("unknown operator {token}"),
+                });
+            }
+        }
+        data
+    }
+
+    fn number(value: i32) -> Vec<u8> {
+        match value {
+            -107..=107 => vec![(value + 139) as u8],
+            108..=1131 => {
+                let value = value - 108;
+                vec![(value / 256 + 247) as u8, (value % 256) as u8]
+            }
+            _ => {
+                let [hi, lo] = (value as i16).to_be_bytes();
+                vec![28, hi, lo]
+            }
+        }
+    }
+
+    /// A DICT integer, always written in five bytes so that offsets can be
+    /// computed before they are known.
+    fn dict_int(value: usize) -> Vec<u8> {
+        let mut data = vec![29];
+        data.extend((value as u32).to_be_bytes());
+        data
+    }
+
+    fn index(objects: &[Vec<u8>], is_cff2: bool) -> Vec<u8> {
+        let mut data = if is_cff2 {
+            (objects.len() as u32).to_be_bytes().to_vec()
+        } else {
+            (objects.len() as u16).to_be_bytes().to_vec()
+        };
+        if objects.is_empty() {
+            return data;
+        }
+        data.push(4);
+        let mut offset = 1u32;
+        data.extend(offset.to_be_bytes());
+        for object in objects {
+            offset += object.len() as u32;
+            data.extend(offset.to_be_bytes());
+        }
+        for object in objects {
+            data.extend(object);
+        }
+        data
+    }
+
+    fn make_cff(charstrings: &[&str], charset: &[u16], subrs: &[&str], gsubrs: &[&str]) -> Vec<u8> {
+        let compile = |sources: &[&str]| {
+            sources
+                .iter()
+                .map(|source| charstring(source))
+                .collect::<Vec<_>>()
+        };
+        let header = [1, 0, 4, 4];
+        let names = index(&[b"Test".to_vec()], false);
+        let strings = index(&[], false);
+        let gsubrs = index(&compile(gsubrs), false);
+        // charset, CharStrings and Private, each with five byte operands
+        let top_dict_len = 6 + 6 + 11;
+        let top_dict_index_len = index(&[vec![0; top_dict_len]], false).len();
+        let charset_offset =
+            header.len() + names.len() + top_dict_index_len + strings.len() + gsubrs.len();
+        let mut charset_data = vec![0];
+        charset_data.extend(charset.iter().flat_map(|sid| sid.to_be_bytes()));
+        let charstrings_offset = charset_offset + charset_data.len();
+        let charstrings = index(&compile(charstrings), false);
+        let private_offset = charstrings_offset + charstrings.len();
+        // the subroutines immediately follow the private DICT
+        let mut private = dict_int(6);
+        private.push(19);
+        let subrs = index(&compile(subrs), false);
+
+        let mut top_dict = dict_int(charset_offset);
+        top_dict.push(15);
+        top_dict.extend(dict_int(charstrings_offset));
+        top_dict.push(17);
+        top_dict.extend(dict_int(private.len()));
+        top_dict.extend(dict_int(private_offset));
+        top_dict.push(18);
+        assert_eq!(top_dict.len(), top_dict_len);
+
+        let mut data = header.to_vec();
+        for part in [
+            names,
+            index(&[top_dict], false),
+            strings,
+            gsubrs,
+            charset_data,
+            charstrings,
+            private,
+            subrs,
+        ] {
+            data.extend(part);
+        }
+        data
+    }
+
+    #[test]
+    fn cff_outlines() {
+        let cff = make_cff(
+            &[
+                "endchar",
+                // a width, a hint and a hintmask, then an outline using a
+                // local and a global subroutine
+                "500 0 10 hstem hintmask #80 10 20 rmoveto 100 0 rlineto -107 callsubr -107 callgsubr endchar",
+                "0 0 rmoveto 10 20 30 40 hvcurveto endchar",
+                // Aacute, composed from A and acute with seac
+                "400 50 60 65 194 endchar",
+            ],
+            // A, acute, Aacute
+            &[34, 125, 200],
+            &["0 100 rlineto return"],
+            &["-100 0 rlineto return"],
+        );
+        let font = TestFont::new(b"CFF ", cff, 1000);
+        let scaler = Scaler::new(&&font, 0.0, &[]).unwrap();
+        let mut path = Path::default();
+        scaler.outline(GlyphId::new(1), &mut path).unwrap();
+        let base = [
+            MoveTo([10.0, 20.0]),
+            LineTo([110.0, 20.0]),
+            LineTo([110.0, 120.0]),
+            LineTo([10.0, 120.0]),
+            LineTo([10.0, 20.0]),
+        ];
+        assert_eq!(path.0, base);
+
+        let mut path = Path::default();
+        scaler.outline(GlyphId::new(3), &mut path).unwrap();
+        let accent = [
+            MoveTo([50.0, 60.0]),
+            CurveTo([60.0, 60.0, 80.0, 90.0, 80.0, 130.0]),
+            LineTo([50.0, 60.0]),
+        ];
+        assert_eq!(path.0, [&base[..], &accent[..]].concat());
+
+        // scaled to 500 pixels per em
+        let scaler = Scaler::new(&&font, 500.0, &[]).unwrap();
+        let mut path = Path::default();
+        scaler.outline(GlyphId::new(2), &mut path).unwrap();
+        assert_eq!(
+            path.0,
+            [
+                MoveTo([0.0, 0.0]),
+                CurveTo([5.0, 0.0, 15.0, 15.0, 15.0, 35.0]),
+                LineTo([0.0, 0.0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_charstrings() {
+        let cff = make_cff(
+            &["endchar", "10 rlineto endchar", "0 callsubr endchar"],
+            &[],
+            &[],
+            &[],
+        );
+        let font = TestFont::new(b"CFF ", cff, 1000);
+        let scaler = Scaler::new(&&font, 0.0, &[]).unwrap();
+        for gid in [1, 2] {
+            assert!(matches!(
+                scaler.outline(GlyphId::new(gid), &mut Path::default()),
+                Err(crate::Error::InvalidCharstring(_))
+            ));
+        }
+        assert!(matches!(
+            scaler.outline(GlyphId::new(3), &mut Path::default()),
+            Err(crate::Error::GlyphNotFound(_))
+        ));
+    }
+
+    fn make_cff2(charstring_source: &str) -> Vec<u8> {
+        // a variation store with one region, peaking at 1.0 on the only axis
+        let mut var_store = vec![0, 30, 0, 1, 0, 0, 0, 12, 0, 1, 0, 0, 0, 22];
+        var_store.extend([0, 1, 0, 1, 0, 0, 0x40, 0, 0x40, 0]);
+        var_store.extend([0, 0, 0, 0, 0, 1, 0, 0]);
+
+        // CharStrings, FDArray and vstore, each with five byte operands
+        let top_dict_len = 6 + 7 + 6;
+        let header = [2, 0, 5, 0, top_dict_len as u8];
+        let gsubrs = index(&[], true);
+        let var_store_offset = header.len() + top_dict_len + gsubrs.len();
+        let charstrings_offset = var_store_offset + var_store.len();
+        let charstrings = index(&[charstring(charstring_source)], true);
+        let fd_array_offset = charstrings_offset + charstrings.len();
+        // an empty private DICT
+        let mut font_dict = dict_int(0);
+        font_dict.extend(dict_int(0));
+        font_dict.push(18);
+        let fd_array = index(&[font_dict], true);
+
+        let mut data = header.to_vec();
+        data.extend(dict_int(charstrings_offset));
+        data.push(17);
+        data.extend(dict_int(fd_array_offset));
+        data.extend([12, 36]);
+        data.extend(dict_int(var_store_offset));
+        data.push(24);
+        assert_eq!(data.len(), header.len() + top_dict_len);
+        for part in [gsubrs, var_store, charstrings, fd_array] {
+            data.extend(part);
+        }
+        data
+    }
+
+    #[test]
+    fn cff2_blend() {
+        let cff2 = make_cff2("100 0 50 10 2 blend rmoveto 100 0 rlineto");
+        let font = TestFont::new(b"CFF2", cff2, 1000);
+        for (coord, x, y) in [(0.0, 100.0, 0.0), (0.5, 125.0, 5.0), (1.0, 150.0, 10.0)] {
+            let coords = [NormalizedCoord::from_f32(coord)];
+            let scaler = Scaler::new(&&font, 0.0, &coords).unwrap();
+            let mut path = Path::default();
+            scaler.outline(GlyphId::new(0), &mut path).unwrap();
+            assert_eq!(
+                path.0,
+                [MoveTo([x, y]), LineTo([x + 100.0, y]), LineTo([x, y]),]
+            );
+        }
+    }
+
+    #[test]
+    fn scaler_uses_cff() {
+        let cff2 = make_cff2("100 0 50 10 2 blend rmoveto 100 0 rlineto");
+        let font = TestFont::new(b"CFF2", cff2, 1000);
+        let mut cx = Context::new();
+        let mut scaler = ScalerBuilder::new(&mut cx)
+            .coords([F2Dot14::from_f32(1.0)])
+            .build(&&font);
+        assert!(scaler.has_outlines());
+        let mut path = Path::default();
+        scaler.outline(GlyphId::new(0), &mut path).unwrap();
+        assert_eq!(path.0[0], MoveTo([150.0, 10.0]));
+    }
+}
@@ -0,0 +1,543 @@
+//! Evaluation of [Type 2] and [CFF2] charstrings.
+//!
+//! Hints are skipped, since they are only needed for hinting, and the
+//! deprecated arithmetic and storage operators are not supported.
+//!
+//! [Type 2]: https://adobe-type-tools.github.io/font-tech-notes/pdfs/5177.Type2.pdf
+//! [CFF2]: https://learn.microsoft.com/en-us/typography/opentype/spec/cff2charstr
+
+use read_fonts::{types::Fixed, ReadError};
+
+use super::parse::{read_u16, read_u32, Index};
+
+/// The maximum depth of the argument stack; this is the CFF2 limit, which is
+/// larger than that of Type 2 charstrings.
+const MAX_STACK: usize = 513;
+/// The maximum nesting depth of subroutine calls.
+const MAX_SUBR_DEPTH: usize = 10;
+
+// one byte operators
+const HSTEM: u8 = 1;
+const VSTEM: u8 = 3;
+const VMOVETO: u8 = 4;
+const RLINETO: u8 = 5;
+const HLINETO: u8 = 6;
+const VLINETO: u8 = 7;
+const RRCURVETO: u8 = 8;
+const CALLSUBR: u8 = 10;
+const RETURN: u8 = 11;
+const ESCAPE: u8 = 12;
+const ENDCHAR: u8 = 14;
+const VSINDEX: u8 = 15;
+const BLEND: u8 = 16;
+const HSTEMHM: u8 = 18;
+const HINTMASK: u8 = 19;
+const CNTRMASK: u8 = 20;
+const RMOVETO: u8 = 21;
+const HMOVETO: u8 = 22;
+const VSTEMHM: u8 = 23;
+const RCURVELINE: u8 = 24;
+const RLINECURVE: u8 = 25;
+const VVCURVETO: u8 = 26;
+const HHCURVETO: u8 = 27;
+const SHORTINT: u8 = 28;
+const CALLGSUBR: u8 = 29;
+const VHCURVETO: u8 = 30;
+const HVCURVETO: u8 = 31;
+const FIXED: u8 = 255;
+
+// two byte operators, following ESCAPE
+const HFLEX: u8 = 34;
+const FLEX: u8 = 35;
+const HFLEX1: u8 = 36;
+const FLEX1: u8 = 37;
+
+/// The source of the subroutines and blend scalars used by a charstring.
+pub trait CharstringContext<'a> {
+    /// `true` if the charstrings are from a `CFF2` table.
+    fn is_cff2(&self) -> bool;
+    /// The global subroutines.
+    fn global_subrs(&self) -> Index<'a>;
+    /// The region scalars of the given item variation data, at the current
+    /// location.
+    fn blend_scalars(&self, vsindex: u16, scalars: &mut Vec<Fixed>) -> Result<(), ReadError>;
+}
+
+/// Receives the outline of a charstring, in font units.
+pub trait CharstringSink {
+    fn move_to(&mut self, x: Fixed, y: Fixed);
+    fn line_to(&mut self, x: Fixed, y: Fixed);
+    fn curve_to(&mut self, cx0: Fixed, cy0: Fixed, cx1: Fixed, cy1: Fixed, x: Fixed, y: Fixed);
+    fn close(&mut self);
+}
+
+/// The accent composition requested by the `seac` form of `endchar`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Seac {
+    /// The offset of the accent from the origin of the base.
+    pub dx: Fixed,
+    pub dy: Fixed,
+    /// The standard encoding code of the base glyph.
+    pub base_code: u8,
+    /// The standard encoding code of the accent glyph.
+    pub accent_code: u8,
+}
+
+/// An error evaluating a charstring.
+#[derive(Clone, Debug)]
+pub enum CharstringError {
+    /// The charstring was malformed.
+    Invalid,
+    /// The charstring used an operator that is not supported.
+    UnsupportedOperator(u16),
+    /// Subroutines were nested too deeply.
+    SubroutineLimitExceeded,
+    /// The font data could not be read.
+    Read(ReadError),
+}
+
+impl From<ReadError> for CharstringError {
+    fn from(e: ReadError) -> Self {
+        Self::Read(e)
+    }
+}
+
+/// Evaluates a charstring, sending its outline to `sink`.
+///
+/// Returns the accent composition, if the charstring ends with `seac`; the
+/// caller is responsible for loading the base and accent glyphs.
+pub fn evaluate<'a>(
+    context: &impl CharstringContext<'a>,
+    local_subrs: Index<'a>,
+    vsindex: u16,
+    charstring: &'a [u8],
+    sink: &mut impl CharstringSink,
+) -> Result<Option<Seac>, CharstringError> {
+    let mut evaluator = Evaluator {
+        is_cff2: context.is_cff2(),
+        global_subrs: context.global_subrs(),
+        local_subrs,
+        stack: [Fixed::ZERO; MAX_STACK],
+        stack_start: 0,
+        stack_len: 0,
+        x: Fixed::ZERO,
+        y: Fixed::ZERO,
+        is_open: false,
+        have_width: context.is_cff2(),
+        stem_count: 0,
+        vsindex,
+        scalars: None,
+        seac: None,
+        done: false,
+    };
+    evaluator.run(context, charstring, sink, 0)?;
+    if evaluator.is_open {
+        sink.close();
+    }
+    Ok(evaluator.seac)
+}
+
+/// The state of a charstring being evaluated.
+struct Evaluator<'a> {
+    is_cff2: bool,
+    global_subrs: Index<'a>,
+    local_subrs: Index<'a>,
+    stack: [Fixed; MAX_STACK],
+    /// The start of the arguments; this is one if the first value is the
+    /// glyph's width.
+    stack_start: usize,
+    stack_len: usize,
+    x: Fixed,
+    y: Fixed,
+    /// `true` if a contour has been started and not closed.
+    is_open: bool,
+    /// `true` once the optional width has been seen, or cannot occur.
+    have_width: bool,
+    stem_count: usize,
+    vsindex: u16,
+    /// The region scalars for the current `vsindex`, computed when needed.
+    scalars: Option<Vec<Fixed>>,
+    seac: Option<Seac>,
+    /// `true` once `endchar` has been reached.
+    done: bool,
+}
+
+impl<'a> Evaluator<'a> {
+    fn run(
+        &mut self,
+        context: &impl CharstringContext<'a>,
+        charstring: &'a [u8],
+        sink: &mut impl CharstringSink,
+        depth: usize,
+    ) -> Result<(), CharstringError> {
+        if depth > MAX_SUBR_DEPTH {
+            return Err(CharstringError::SubroutineLimitExceeded);
+        }
+        let mut pos = 0;
+        while let Some(&b0) = charstring.get(pos) {
+            pos += 1;
+            match b0 {
+                SHORTINT => {
+                    let value = read_u16(charstring, pos)? as i16;
+                    pos += 2;
+                    self.push(Fixed::from_i32(value as i32))?;
+                }
+                32..=246 => self.push(Fixed::from_i32(b0 as i32 - 139))?,
+                247..=250 => {
+                    let b1 = *charstring.get(pos).ok_or(CharstringError::Invalid)? as i32;
+                    pos += 1;
+                    self.push(Fixed::from_i32((b0 as i32 - 247) * 256 + b1 + 108))?;
+                }
+                251..=254 => {
+                    let b1 = *charstring.get(pos).ok_or(CharstringError::Invalid)? as i32;
+                    pos += 1;
+                    self.push(Fixed::from_i32(-(b0 as i32 - 251) * 256 - b1 - 108))?;
+                }
+                FIXED => {
+                    let value = read_u32(charstring, pos)? as i32;
+                    pos += 4;
+                    self.push(Fixed::from_bits(value))?;
+                }
+                HSTEM | VSTEM | HSTEMHM | VSTEMHM => {
+                    self.take_width(!self.args().len().is_multiple_of(2));
+                    self.stem_count += self.args().len() / 2;
+                    self.clear();
+                }
+                HINTMASK | CNTRMASK => {
+                    // any arguments are an implicit vstem
+                    self.take_width(!self.args().len().is_multiple_of(2));
+                    self.stem_count += self.args().len() / 2;
+                    self.clear();
+                    pos += self.stem_count.div_ceil(8);
+                    if pos > charstring.len() {
+                        return Err(CharstringError::Invalid);
+                    }
+                }
+                RMOVETO => {
+                    self.take_width(self.args().len() > 2);
+                    let [dx, dy] = self.fixed_args()?;
+                    self.move_to(dx, dy, sink);
+                }
+                HMOVETO => {
+                    self.take_width(self.args().len() > 1);
+                    let [dx] = self.fixed_args()?;
+                    self.move_to(dx, Fixed::ZERO, sink);
+                }
+                VMOVETO => {
+                    self.take_width(self.args().len() > 1);
+                    let [dy] = self.fixed_args()?;
+                    self.move_to(Fixed::ZERO, dy, sink);
+                }
+                RLINETO => {
+                    let args = self.args_in_groups(2, 0)?;
+                    for pair in args.chunks_exact(2) {
+                        self.line_to(pair[0], pair[1], sink);
+                    }
+                    self.clear();
+                }
+                HLINETO | VLINETO => {
+                    let args = self.args_in_groups(1, 0)?;
+                    let mut horizontal = b0 == HLINETO;
+                    for delta in args {
+                        if horizontal {
+                            self.line_to(delta, Fixed::ZERO, sink);
+                        } else {
+                            self.line_to(Fixed::ZERO, delta, sink);
+                        }
+                        horizontal = !horizontal;
+                    }
+                    self.clear();
+                }
+                RRCURVETO => {
+                    let args = self.args_in_groups(6, 0)?;
+                    for curve in args.chunks_exact(6) {
+                        self.curve_to(curve.try_into().unwrap(), sink);
+                    }
+                    self.clear();
+                }
+                RCURVELINE => {
+                    let args = self.args_in_groups(6, 2)?;
+                    let (curves, line) = args.split_at(args.len() - 2);
+                    for curve in curves.chunks_exact(6) {
+                        self.curve_to(curve.try_into().unwrap(), sink);
+                    }
+                    self.line_to(line[0], line[1], sink);
+                    self.clear();
+                }
+                RLINECURVE => {
+                    let args = self.args_in_groups(2, 6)?;
+                    let (lines, curve) = args.split_at(args.len() - 6);
+                    for pair in lines.chunks_exact(2) {
+                        self.line_to(pair[0], pair[1], sink);
+                    }
+                    self.curve_to(curve.try_into().unwrap(), sink);
+                    self.clear();
+                }
+                HHCURVETO | VVCURVETO => {
+                    // an optional first value is the other coordinate of the
+                    // first control point
+                    let mut args = self.args().to_vec();
+                    let mut first = Fixed::ZERO;
+                    if args.len() % 4 == 1 {
+                        first = args.remove(0);
+                    }
+                    if args.is_empty() || !args.len().is_multiple_of(4) {
+                        return Err(CharstringError::Invalid);
+                    }
+                    for curve in args.chunks_exact(4) {
+                        let [a, b, c, d] = curve.try_into().unwrap();
+                        let z = Fixed::ZERO;
+                        if b0 == HHCURVETO {
+                            self.curve_to([a, first, b, c, d, z], sink);
+                        } else {
+                            self.curve_to([first, a, b, c, z, d], sink);
+                        }
+                        first = Fixed::ZERO;
+                    }
+                    self.clear();
+                }
+                HVCURVETO | VHCURVETO => {
+                    let args = self.args().to_vec();
+                    if args.len() < 4 || !matches!(args.len() % 4, 0 | 1) {
+                        return Err(CharstringError::Invalid);
+                    }
+                    let mut horizontal = b0 == HVCURVETO;
+                    let mut remaining = args.as_slice();
+                    while remaining.len() >= 4 {
+                        let [a, b, c, d] = remaining[..4].try_into().unwrap();
+                        // the final curve may end with a value for the
+                        // other coordinate of its end point
+                        let last = if remaining.len() == 5 {
+                            remaining[4]
+                        } else {
+                            Fixed::ZERO
+                        };
+                        let z = Fixed::ZERO;
+                        if horizontal {
+                            self.curve_to([a, z, b, c, last, d], sink);
+                        } else {
+                            self.curve_to([z, a, b, c, d, last], sink);
+                        }
+                        horizontal = !horizontal;
+                        remaining = &remaining[4..];
+                    }
+                    self.clear();
+                }
+                CALLSUBR | CALLGSUBR => {
+                    let subrs = if b0 == CALLSUBR {
+                        self.local_subrs
+                    } else {
+                        self.global_subrs
+                    };
+                    let index = self.pop()?.to_i32() + subr_bias(subrs.count());
+                    let subr = usize::try_from(index)
+                        .ok()
+                        .and_then(|index| subrs.get(index).ok())
+                        .ok_or(CharstringError::Invalid)?;
+                    self.run(context, subr, sink, depth + 1)?;
+                    if self.done {
+                        return Ok(());
+                    }
+                }
+                RETURN => return Ok(()),
+                ENDCHAR => {
+                    let len = self.args().len();
+                    self.take_width(len == 1 || len == 5);
+                    if self.args().len() == 4 {
+                        let [dx, dy, base, accent] = self.fixed_args()?;
+                        self.seac = Some(Seac {
+                            dx,
+                            dy,
+                            base_code: base.to_i32() as u8,
+                            accent_code: accent.to_i32() as u8,
+                        });
+                    }
+                    self.clear();
+                    self.done = true;
+                    return Ok(());
+                }
+                VSINDEX if self.is_cff2 => {
+                    self.vsindex = self.pop()?.to_i32() as u16;
+                    self.scalars = None;
+                    self.clear();
+                }
+                BLEND if self.is_cff2 => self.blend(context)?,
+                ESCAPE => {
+                    let b1 = *charstring.get(pos).ok_or(CharstringError::Invalid)?;
+                    pos += 1;
+                    self.flex(b1, sink)?;
+                }
+                _ => return Err(CharstringError::UnsupportedOperator(b0 as u16)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Evaluates the flex operators, which are the only supported two-byte
+    /// operators.
+    fn flex(&mut self, op: u8, sink: &mut impl CharstringSink) -> Result<(), CharstringError> {
+        let z = Fixed::ZERO;
+        match op {
+            FLEX => {
+                let args: [Fixed; 13] = self.fixed_args()?;
+                self.curve_to(args[..6].try_into().unwrap(), sink);
+                self.curve_to(args[6..12].try_into().unwrap(), sink);
+            }
+            HFLEX => {
+                let [dx1, dx2, dy2, dx3, dx4, dx5, dx6] = self.fixed_args()?;
+                self.curve_to([dx1, z, dx2, dy2, dx3, z], sink);
+                self.curve_to([dx4, z, dx5, -dy2, dx6, z], sink);
+            }
+            HFLEX1 => {
+                let [dx1, dy1, dx2, dy2, dx3, dx4, dx5, dy5, dx6] = self.fixed_args()?;
+                // the curves end at the starting y coordinate
+                let dy6 = -(dy1 + dy2 + dy5);
+                self.curve_to([dx1, dy1, dx2, dy2, dx3, z], sink);
+                self.curve_to([dx4, z, dx5, dy5, dx6, dy6], sink);
+            }
+            FLEX1 => {
+                let args: [Fixed; 11] = self.fixed_args()?;
+                let (dx, dy) = args[..10]
+                    .chunks_exact(2)
+                    .fold((z, z), |(dx, dy), pair| (dx + pair[0], dy + pair[1]));
+                // the last value is along the larger of the two directions;
+                // the curves end at the starting point in the other one
+                let (dx6, dy6) = if dx.abs() > dy.abs() {
+                    (args[10], -dy)
+                } else {
+                    (-dx, args[10])
+                };
+                let [dx4, dy4, dx5, dy5] = args[6..10].try_into().unwrap();
+                self.curve_to(args[..6].try_into().unwrap(), sink);
+                self.curve_to([dx4, dy4, dx5, dy5, dx6, dy6], sink);
+            }
+            _ => return Err(CharstringError::UnsupportedOperator((12 << 8) | op as u16)),
+        }
+        self.clear();
+        Ok(())
+    }
+
+    /// Blends the values of the masters, leaving the blended values on the
+    /// stack.
+    fn blend(&mut self, context: &impl CharstringContext<'a>) -> Result<(), CharstringError> {
+        let count = self.pop()?.to_i32();
+        if self.scalars.is_none() {
+            let mut scalars = Vec::new();
+            context.blend_scalars(self.vsindex, &mut scalars)?;
+            self.scalars = Some(scalars);
+        }
+        let scalars = self.scalars.as_deref().unwrap_or_default();
+        let count = usize::try_from(count).map_err(|_| CharstringError::Invalid)?;
+        // the default value of each operand, then the deltas for each
+        // region for each operand
+        let operand_count = count * (scalars.len() + 1);
+        let start = self
+            .stack_len
+            .checked_sub(operand_count)
+            .filter(|start| *start >= self.stack_start)
+            .ok_or(CharstringError::Invalid)?;
+        for i in 0..count {
+            let deltas_start = start + count + i * scalars.len();
+            let delta = self.stack[deltas_start..deltas_start + scalars.len()]
+                .iter()
+                .zip(scalars)
+                .fold(Fixed::ZERO, |sum, (delta, scalar)| sum + *delta * *scalar);
+            self.stack[start + i] += delta;
+        }
+        self.stack_len = start + count;
+        Ok(())
+    }
+
+    fn push(&mut self, value: Fixed) -> Result<(), CharstringError> {
+        if self.stack_len == MAX_STACK {
+            return Err(CharstringError::Invalid);
+        }
+        self.stack[self.stack_len] = value;
+        self.stack_len += 1;
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<Fixed, CharstringError> {
+        if self.stack_len == self.stack_start {
+            return Err(CharstringError::Invalid);
+        }
+        self.stack_len -= 1;
+        Ok(self.stack[self.stack_len])
+    }
+
+    fn args(&self) -> &[Fixed] {
+        &self.stack[self.stack_start..self.stack_len]
+    }
+
+    /// Returns the arguments, which must be exactly `N` values.
+    fn fixed_args<const N: usize>(&self) -> Result<[Fixed; N], CharstringError> {
+        self.args().try_into().map_err(|_| CharstringError::Invalid)
+    }
+
+    /// Returns the arguments, which must be one or more groups of `group`
+    /// values, followed by `tail` values.
+    fn args_in_groups(&self, group: usize, tail: usize) -> Result<Vec<Fixed>, CharstringError> {
+        let args = self.args();
+        if args.len() < group + tail || !(args.len() - tail).is_multiple_of(group) {
+            return Err(CharstringError::Invalid);
+        }
+        Ok(args.to_vec())
+    }
+
+    fn clear(&mut self) {
+        self.stack_start = 0;
+        self.stack_len = 0;
+    }
+
+    /// Skips the width, which is an optional first argument to the first
+    /// stack clearing operator in a Type 2 charstring.
+    fn take_width(&mut self, has_width: bool) {
+        if !self.have_width {
+            self.have_width = true;
+            if has_width {
+                self.stack_start = 1;
+            }
+        }
+    }
+
+    fn move_to(&mut self, dx: Fixed, dy: Fixed, sink: &mut impl CharstringSink) {
+        if self.is_open {
+            sink.close();
+        }
+        self.x += dx;
+        self.y += dy;
+        sink.move_to(self.x, self.y);
+        self.is_open = true;
+        self.clear();
+    }
+
+    fn line_to(&mut self, dx: Fixed, dy: Fixed, sink: &mut impl CharstringSink) {
+        self.x += dx;
+        self.y += dy;
+        sink.line_to(self.x, self.y);
+    }
+
+    /// Adds a curve, from the deltas of its two control points and its end
+    /// point, each relative to the previous point.
+    fn curve_to(&mut self, deltas: [Fixed; 6], sink: &mut impl CharstringSink) {
+        let cx0 = self.x + deltas[0];
+        let cy0 = self.y + deltas[1];
+        let cx1 = cx0 + deltas[2];
+        let cy1 = cy0 + deltas[3];
+        self.x = cx1 + deltas[4];
+        self.y = cy1 + deltas[5];
+        sink.curve_to(cx0, cy0, cx1, cy1, self.x, self.y);
+    }
+}
+
+/// The bias added to subroutine numbers, which depends on the number of
+/// subroutines.
+fn subr_bias(count: usize) -> i32 {
+    if count < 1240 {
+        107
+    } else if count < 33900 {
+        1131
+    } else {
+        32768
+    }
+}
@@ -0,0 +1,330 @@
+//! Parsing of the structures shared by the `CFF ` and `CFF2` tables.
+
+use read_fonts::ReadError;
+
+/// Operator for the offset to the charset, in a Top DICT.
+pub const CHARSET: u16 = 15;
+/// Operator for the offset to the CharStrings INDEX, in a Top DICT.
+pub const CHAR_STRINGS: u16 = 17;
+/// Operator for the size and offset of the Private DICT.
+pub const PRIVATE: u16 = 18;
+/// Operator for the offset to the local subroutines, in a Private DICT.
+pub const SUBRS: u16 = 19;
+/// Operator for the default variation store index, in a CFF2 Private DICT.
+pub const VSINDEX: u16 = 22;
+/// Operator for the offset to the variation store, in a CFF2 Top DICT.
+pub const VSTORE: u16 = 24;
+/// Operator for the charstring type, in a Top DICT.
+pub const CHARSTRING_TYPE: u16 = escape(6);
+/// Operator for the registry, ordering and supplement of a CID-keyed font.
+pub const ROS: u16 = escape(30);
+/// Operator for the offset to the Font DICT INDEX, in a Top DICT.
+pub const FD_ARRAY: u16 = escape(36);
+/// Operator for the offset to the FDSelect table, in a Top DICT.
+pub const FD_SELECT: u16 = escape(37);
+
+/// The two-byte operator with the given second byte.
+const fn escape(op: u8) -> u16 {
+    (12 << 8) | op as u16
+}
+
+/// An [INDEX], an array of variable-sized objects.
+///
+/// [INDEX]: https://learn.microsoft.com/en-us/typography/opentype/spec/cff2#5-index-data
+#[derive(Clone, Copy, Default)]
+pub struct Index<'a> {
+    offsets: &'a [u8],
+    off_size: usize,
+    data: &'a [u8],
+}
+
+impl<'a> Index<'a> {
+    /// Parses an INDEX at the start of `data`, returning it and its size.
+    ///
+    /// The count is 32 bits in `CFF2`, and 16 bits in `CFF `.
+    pub fn parse(data: &'a [u8], is_cff2: bool) -> Result<(Self, usize), ReadError> {
+        let (count, header_len) = if is_cff2 {
+            (read_u32(data, 0)? as usize, 4)
+        } else {
+            (read_u16(data, 0)? as usize, 2)
+        };
+        if count == 0 {
+            return Ok((Self::default(), header_len));
+        }
+        let off_size = *data.get(header_len).ok_or(ReadError::OutOfBounds)? as usize;
+        if !(1..=4).contains(&off_size) {
+            return Err(ReadError::MalformedData("invalid INDEX offset size"));
+        }
+        let offsets_start = header_len + 1;
+        let offsets_end = offsets_start + (count + 1) * off_size;
+        let offsets = data
+            .get(offsets_start..offsets_end)
+            .ok_or(ReadError::OutOfBounds)?;
+        let index = Index {
+            offsets,
+            off_size,
+            data: &[],
+        };
+        // offsets are relative to the byte before the object data
+        let data_len = index.offset(count)?.saturating_sub(1);
+        let data = data
+            .get(offsets_end..offsets_end + data_len)
+            .ok_or(ReadError::OutOfBounds)?;
+        Ok((Index { data, ..index }, offsets_end + data_len))
+    }
+
+    /// Returns the number of objects in the INDEX.
+    pub fn count(&self) -> usize {
+        // there is one more offset than there are objects
+        self.offsets
+            .len()
+            .checked_div(self.off_size)
+            .map_or(0, |count| count - 1)
+    }
+
+    /// Returns the data of the object at the given index.
+    pub fn get(&self, index: usize) -> Result<&'a [u8], ReadError> {
+        if index >= self.count() {
+            return Err(ReadError::OutOfBounds);
+        }
+        let start = self.offset(index)?.saturating_sub(1);
+        let end = self.offset(index + 1)?.saturating_sub(1);
+        self.data.get(start..end).ok_or(ReadError::OutOfBounds)
+    }
+
+    fn offset(&self, index: usize) -> Result<usize, ReadError> {
+        let pos = index * self.off_size;
+        let bytes = self
+            .offsets
+            .get(pos..pos + self.off_size)
+            .ok_or(ReadError::OutOfBounds)?;
+        Ok(bytes
+            .iter()
+            .fold(0usize, |offset, byte| (offset << 8) | *byte as usize))
+    }
+}
+
+/// Calls `f` with each operator in a [DICT], and its operands.
+///
+/// Only integer operands are needed for loading outlines, so real numbers
+/// are read as zero. The CFF2 `blend` operator is skipped, which leaves its
+/// operands for the following operator.
+///
+/// [DICT]: https://learn.microsoft.com/en-us/typography/opentype/spec/cff2#6-dict-data
+pub fn parse_dict(
+    data: &[u8],
+    mut f: impl FnMut(u16, &[i32]) -> Result<(), ReadError>,
+) -> Result<(), ReadError> {
+    const BLEND: u16 = 23;
+    const MAX_OPERANDS: usize = 513;
+    let mut operands = Vec::new();
+    let mut pos = 0;
+    while let Some(&b0) = data.get(pos) {
+        pos += 1;
+        match b0 {
+            0..=21 | 23..=27 | 31 => {
+                let op = if b0 == 12 {
+                    let b1 = *data.get(pos).ok_or(ReadError::OutOfBounds)?;
+                    pos += 1;
+                    escape(b1)
+                } else {
+                    b0 as u16
+                };
+                if op != BLEND {
+                    f(op, &operands)?;
+                    operands.clear();
+                }
+            }
+            22 => {
+                // vsindex
+                f(VSINDEX, &operands)?;
+                operands.clear();
+            }
+            28 => {
+                operands.push(read_u16(data, pos)? as i16 as i32);
+                pos += 2;
+            }
+            29 => {
+                operands.push(read_u32(data, pos)? as i32);
+                pos += 4;
+            }
+            30 => {
+                // a real number, as nibbles ending with 0xf
+                loop {
+                    let byte = *data.get(pos).ok_or(ReadError::OutOfBounds)?;
+                    pos += 1;
+                    if byte & 0xF == 0xF || byte >> 4 == 0xF {
+                        break;
+                    }
+                }
+                operands.push(0);
+            }
+            32..=246 => operands.push(b0 as i32 - 139),
+            247..=250 => {
+                let b1 = *data.get(pos).ok_or(ReadError::OutOfBounds)? as i32;
+                pos += 1;
+                operands.push((b0 as i32 - 247) * 256 + b1 + 108);
+            }
+            251..=254 => {
+                let b1 = *data.get(pos).ok_or(ReadError::OutOfBounds)? as i32;
+                pos += 1;
+                operands.push(-(b0 as i32 - 251) * 256 - b1 - 108);
+            }
+            _ => return Err(ReadError::MalformedData("invalid DICT data")),
+        }
+        if operands.len() > MAX_OPERANDS {
+            return Err(ReadError::MalformedData("too many DICT operands"));
+        }
+    }
+    Ok(())
+}
+
+/// Maps glyphs to font DICTs in a CID-keyed font.
+#[derive(Clone, Copy)]
+pub struct FdSelect<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> FdSelect<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the index of the font DICT for the given glyph.
+    pub fn font_dict_index(&self, glyph_id: u16) -> Result<usize, ReadError> {
+        let format = *self.data.first().ok_or(ReadError::OutOfBounds)?;
+        match format {
+            0 => self
+                .data
+                .get(1 + glyph_id as usize)
+                .map(|fd| *fd as usize)
+                .ok_or(ReadError::OutOfBounds),
+            3 | 4 => {
+                // ranges of (first glyph, fd), followed by a sentinel glyph
+                let (count, glyph_size, fd_size) = if format == 3 {
+                    (read_u16(self.data, 1)? as usize, 2, 1)
+                } else {
+                    (read_u32(self.data, 1)? as usize, 4, 2)
+                };
+                let header_len = if format == 3 { 3 } else { 5 };
+                let range_size = glyph_size + fd_size;
+                let read_glyph = |pos| match glyph_size {
+                    2 => read_u16(self.data, pos).map(|g| g as u32),
+                    _ => read_u32(self.data, pos),
+                };
+                let glyph_id = glyph_id as u32;
+                for i in 0..count {
+                    let pos = header_len + i * range_size;
+                    let next = read_glyph(pos + range_size)?;
+                    if read_glyph(pos)? <= glyph_id && glyph_id < next {
+                        return match fd_size {
+                            1 => self
+                                .data
+                                .get(pos + glyph_size)
+                                .map(|fd| *fd as usize)
+                                .ok_or(ReadError::OutOfBounds),
+                            _ => read_u16(self.data, pos + glyph_size).map(|fd| fd as usize),
+                        };
+                    }
+                }
+                Err(ReadError::InvalidGlyphId(
+                    read_fonts::types::GlyphId::new(glyph_id as u16).into(),
+                ))
+            }
+            _ => Err(ReadError::InvalidFormat(format as i64)),
+        }
+    }
+}
+
+/// The names of the glyphs in a `CFF ` font, as string ids.
+#[derive(Clone, Copy)]
+pub enum Charset<'a> {
+    /// The ISOAdobe charset, where each glyph id is its string id.
+    IsoAdobe,
+    /// One of the predefined expert charsets, which are not supported.
+    Expert,
+    /// A charset in the font.
+    Custom(&'a [u8]),
+}
+
+impl Charset<'_> {
+    /// Returns the glyph with the given string id, if there is one.
+    pub fn glyph_id(&self, sid: u16, glyph_count: usize) -> Result<Option<u16>, ReadError> {
+        let data = match self {
+            Self::IsoAdobe => {
+                const ISO_ADOBE_LAST_SID: u16 = 228;
+                let found = sid <= ISO_ADOBE_LAST_SID && (sid as usize) < glyph_count;
+                return Ok(found.then_some(sid));
+            }
+            Self::Expert => return Ok(None),
+            Self::Custom(data) => *data,
+        };
+        if sid == 0 {
+            return Ok(Some(0));
+        }
+        let format = *data.first().ok_or(ReadError::OutOfBounds)?;
+        // glyph 0 is always .notdef, and is not included
+        let mut glyph_id = 1usize;
+        let mut pos = 1;
+        while glyph_id < glyph_count {
+            let first = read_u16(data, pos)?;
+            let count = match format {
+                0 => {
+                    pos += 2;
+                    1
+                }
+                1 => {
+                    let left = *data.get(pos + 2).ok_or(ReadError::OutOfBounds)?;
+                    pos += 3;
+                    left as usize + 1
+                }
+                2 => {
+                    let left = read_u16(data, pos + 2)?;
+                    pos += 4;
+                    left as usize + 1
+                }
+                _ => return Err(ReadError::InvalidFormat(format as i64)),
+            };
+            if sid >= first && ((sid - first) as usize) < count {
+                return Ok(Some((glyph_id + (sid - first) as usize) as u16));
+            }
+            glyph_id += count;
+        }
+        Ok(None)
+    }
+}
+
+/// Returns the string id of the glyph with the given code in the standard
+/// encoding, as used by the `seac` form of `endchar`.
+pub fn standard_encoding_sid(code: u8) -> u16 {
+    let code = code as u16;
+    match code {
+        32..=126 => code - 31,
+        161..=175 => code - 65,
+        177..=180 => code - 66,
+        182..=189 => code - 67,
+        191 => 123,
+        193..=200 => code - 69,
+        202..=203 => code - 70,
+        205..=208 => code - 71,
+        225 => 138,
+        227 => 139,
+        232..=235 => code - 92,
+        241 => 144,
+        245 => 145,
+        248..=251 => code - 102,
+        _ => 0,
+    }
+}
+
+pub fn read_u16(data: &[u8], pos: usize) -> Result<u16, ReadError> {
+    data.get(pos..pos + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or(ReadError::OutOfBounds)
+}
+
+pub fn read_u32(data: &[u8], pos: usize) -> Result<u32, ReadError> {
+    data.get(pos..pos + 4)
+        .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or(ReadError::OutOfBounds)
+}
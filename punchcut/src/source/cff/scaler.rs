@@ -0,0 +1,364 @@
+use super::{
+    charstring::{self, CharstringContext, CharstringSink},
+    parse::{self, Charset, FdSelect, Index},
+};
+use crate::{Error, NormalizedCoord, Pen, Result};
+
+use read_fonts::{
+    tables::variations::ItemVariationStore,
+    types::{Fixed, GlyphId, Tag},
+    FontData, FontRead, ReadError, TableProvider,
+};
+
+/// PostScript glyph scaler for a specific font and configuration.
+pub struct Scaler<'a> {
+    /// True if the outlines are from a `CFF2` table.
+    is_cff2: bool,
+    global_subrs: Index<'a>,
+    charstrings: Index<'a>,
+    /// Font DICT selection for CID-keyed fonts.
+    fd_select: Option<FdSelect<'a>>,
+    /// Subroutines and default variation data for each font DICT; there is a
+    /// single entry for fonts that are not CID-keyed.
+    font_dicts: Vec<FontDict<'a>>,
+    /// Glyph names, used to find the components of `seac` glyphs.
+    charset: Option<Charset<'a>>,
+    var_store: Option<ItemVariationStore<'a>>,
+    /// Normalized variation coordinates.
+    coords: &'a [NormalizedCoord],
+    /// Scale factor from font units to pixels, or 1.0 for unscaled outlines.
+    scale: f32,
+}
+
+/// The parts of a font or private DICT needed for loading outlines.
+#[derive(Clone, Copy, Default)]
+struct FontDict<'a> {
+    subrs: Index<'a>,
+    vsindex: u16,
+}
+
+impl<'a> Scaler<'a> {
+    /// Creates a new scaler for extracting outlines with the specified font
+    /// and configuration.
+    ///
+    /// Outlines are loaded from the `CFF2` table if present, and otherwise
+    /// from the `CFF ` table.
+    pub fn new(
+        font: &impl TableProvider<'a>,
+        size: f32,
+        coords: &'a [NormalizedCoord],
+    ) -> Result<Self> {
+        let (is_cff2, table) = match font.data_for_tag(Tag::CFF2) {
+            Some(data) => (true, data),
+            None => (false, font.expect_data_for_tag(Tag::CFF)?),
+        };
+        let upem = font.head()?.units_per_em();
+        let size = size.abs();
+        let scale = if size != 0. && upem != 0 {
+            size / upem as f32
+        } else {
+            1.0
+        };
+        let mut scaler = Self {
+            is_cff2,
+            global_subrs: Index::default(),
+            charstrings: Index::default(),
+            fd_select: None,
+            font_dicts: Vec::new(),
+            charset: None,
+            var_store: None,
+            coords,
+            scale,
+        };
+        scaler.parse(table.read_array(0..table.len())?)?;
+        Ok(scaler)
+    }
+
+    /// Returns the number of glyphs in the font.
+    pub fn glyph_count(&self) -> usize {
+        self.charstrings.count()
+    }
+
+    /// Loads the outline for the specified glyph identifier and invokes the
+    /// functions in the given pen for each path command.
+    pub fn outline(&self, glyph_id: GlyphId, pen: &mut impl Pen) -> Result<()> {
+        self.outline_with_scale(glyph_id, self.scale, pen)
+    }
+
+    /// Loads the outline for the specified glyph identifier in font units,
+    /// ignoring the size of the scaler.
+    #[cfg(feature = "hinting")]
+    pub(crate) fn outline_unscaled(&self, glyph_id: GlyphId, pen: &mut impl Pen) -> Result<()> {
+        self.outline_with_scale(glyph_id, 1.0, pen)
+    }
+
+    fn outline_with_scale(&self, glyph_id: GlyphId, scale: f32, pen: &mut impl Pen) -> Result<()> {
+        let mut sink = ScalingSink {
+            pen,
+            scale,
+            offset: (Fixed::ZERO, Fixed::ZERO),
+        };
+        let seac = self.evaluate(glyph_id, &mut sink)?;
+        if let Some(seac) = seac {
+            // the base and accent are standard glyphs, found by name
+            let base = self.seac_component(glyph_id, seac.base_code)?;
+            let accent = self.seac_component(glyph_id, seac.accent_code)?;
+            self.evaluate(base, &mut sink)?;
+            sink.offset = (seac.dx, seac.dy);
+            self.evaluate(accent, &mut sink)?;
+        }
+        Ok(())
+    }
+
+    fn evaluate(
+        &self,
+        glyph_id: GlyphId,
+        sink: &mut impl CharstringSink,
+    ) -> Result<Option<charstring::Seac>> {
+        let charstring = self
+            .charstrings
+            .get(glyph_id.to_u16() as usize)
+            .map_err(|_| Error::GlyphNotFound(glyph_id))?;
+        let fd = match &self.fd_select {
+            Some(fd_select) => fd_select.font_dict_index(glyph_id.to_u16())?,
+            None => 0,
+        };
+        let font_dict = self
+            .font_dicts
+            .get(fd)
+            .copied()
+            .ok_or(ReadError::OutOfBounds)?;
+        charstring::evaluate(self, font_dict.subrs, font_dict.vsindex, charstring, sink).map_err(
+            |e| match e {
+                charstring::CharstringError::Read(e) => Error::Read(e),
+                _ => Error::InvalidCharstring(glyph_id),
+            },
+        )
+    }
+
+    fn seac_component(&self, glyph_id: GlyphId, code: u8) -> Result<GlyphId> {
+        let sid = parse::standard_encoding_sid(code);
+        let found = match (sid, &self.charset) {
+            (0, _) | (_, None) => None,
+            (sid, Some(charset)) => charset.glyph_id(sid, self.charstrings.count())?,
+        };
+        found
+            .map(GlyphId::new)
+            .ok_or(Error::InvalidCharstring(glyph_id))
+    }
+
+    fn parse(&mut self, table: &'a [u8]) -> Result<()> {
+        let header_size = *table.get(2).ok_or(ReadError::OutOfBounds)? as usize;
+        let (top_dict, global_subrs_start) = if self.is_cff2 {
+            let top_dict_len = parse::read_u16(table, 3)? as usize;
+            let end = header_size + top_dict_len;
+            let top_dict = table.get(header_size..end).ok_or(ReadError::OutOfBounds)?;
+            (top_dict, end)
+        } else {
+            // the name, top DICT and string INDEXes
+            let data = table.get(header_size..).ok_or(ReadError::OutOfBounds)?;
+            let (_, names_len) = Index::parse(data, false)?;
+            let data = &data[names_len..];
+            let (top_dicts, top_dicts_len) = Index::parse(data, false)?;
+            let (_, strings_len) = Index::parse(&data[top_dicts_len..], false)?;
+            (
+                top_dicts.get(0)?,
+                header_size + names_len + top_dicts_len + strings_len,
+            )
+        };
+        let global_subrs_data = table
+            .get(global_subrs_start..)
+            .ok_or(ReadError::OutOfBounds)?;
+        self.global_subrs = Index::parse(global_subrs_data, self.is_cff2)?.0;
+
+        let mut charstrings = None;
+        let mut charset = None;
+        let mut private = None;
+        let mut fd_array = None;
+        let mut fd_select = None;
+        let mut var_store = None;
+        let mut is_cid = false;
+        parse::parse_dict(top_dict, |op, operands| {
+            let last = operands.last().copied();
+            match op {
+                parse::CHAR_STRINGS => charstrings = last,
+                parse::CHARSET => charset = last,
+                parse::PRIVATE => {
+                    if let [size, offset] = operands {
+                        private = Some((*size, *offset));
+                    }
+                }
+                parse::FD_ARRAY => fd_array = last,
+                parse::FD_SELECT => fd_select = last,
+                parse::VSTORE => var_store = last,
+                parse::ROS => is_cid = true,
+                parse::CHARSTRING_TYPE if last != Some(2) => {
+                    return Err(ReadError::MalformedData("unsupported charstring type"))
+                }
+                _ => {}
+            }
+            Ok(())
+        })?;
+
+        let charstrings = charstrings.ok_or(ReadError::MalformedData("missing CharStrings"))?;
+        self.charstrings = Index::parse(slice_from(table, charstrings)?, self.is_cff2)?.0;
+        if !self.is_cff2 {
+            self.charset = Some(match charset.unwrap_or(0) {
+                0 => Charset::IsoAdobe,
+                1 | 2 => Charset::Expert,
+                offset => Charset::Custom(slice_from(table, offset)?),
+            });
+        }
+        if let Some(offset) = var_store {
+            // the variation store is preceded by its length
+            let data = slice_from(table, offset)?
+                .get(2..)
+                .ok_or(ReadError::OutOfBounds)?;
+            self.var_store = Some(ItemVariationStore::read(FontData::new(data))?);
+        }
+        match fd_array {
+            // CFF2 fonts may have an FDArray without being CID-keyed
+            Some(offset) if is_cid || self.is_cff2 => {
+                let (font_dicts, _) = Index::parse(slice_from(table, offset)?, self.is_cff2)?;
+                for i in 0..font_dicts.count() {
+                    let font_dict = self.parse_font_dict(table, font_dicts.get(i)?)?;
+                    self.font_dicts.push(font_dict);
+                }
+                if let Some(offset) = fd_select {
+                    self.fd_select = Some(FdSelect::new(slice_from(table, offset)?));
+                }
+            }
+            _ => {
+                let font_dict = match private {
+                    Some((size, offset)) => self.parse_private_dict(table, size, offset)?,
+                    None => FontDict::default(),
+                };
+                self.font_dicts.push(font_dict);
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_font_dict(&self, table: &'a [u8], font_dict: &[u8]) -> Result<FontDict<'a>> {
+        let mut private = None;
+        parse::parse_dict(font_dict, |op, operands| {
+            if let (parse::PRIVATE, [size, offset]) = (op, operands) {
+                private = Some((*size, *offset));
+            }
+            Ok(())
+        })?;
+        match private {
+            Some((size, offset)) => self.parse_private_dict(table, size, offset),
+            None => Ok(FontDict::default()),
+        }
+    }
+
+    fn parse_private_dict(&self, table: &'a [u8], size: i32, offset: i32) -> Result<FontDict<'a>> {
+        let start = usize::try_from(offset).map_err(|_| ReadError::OutOfBounds)?;
+        let size = usize::try_from(size).map_err(|_| ReadError::OutOfBounds)?;
+        let private = table
+            .get(start..start + size)
+            .ok_or(ReadError::OutOfBounds)?;
+        let mut subrs = None;
+        let mut vsindex = 0;
+        parse::parse_dict(private, |op, operands| {
+            match op {
+                parse::SUBRS => subrs = operands.last().copied(),
+                parse::VSINDEX => vsindex = operands.last().copied().unwrap_or(0) as u16,
+                _ => {}
+            }
+            Ok(())
+        })?;
+        let subrs = match subrs {
+            // local subroutines are relative to the private DICT
+            Some(subrs_offset) => {
+                let data = slice_from(table, offset.saturating_add(subrs_offset))?;
+                Index::parse(data, self.is_cff2)?.0
+            }
+            None => Index::default(),
+        };
+        Ok(FontDict { subrs, vsindex })
+    }
+}
+
+impl<'a> CharstringContext<'a> for Scaler<'a> {
+    fn is_cff2(&self) -> bool {
+        self.is_cff2
+    }
+
+    fn global_subrs(&self) -> Index<'a> {
+        self.global_subrs
+    }
+
+    fn blend_scalars(
+        &self,
+        vsindex: u16,
+        scalars: &mut Vec<Fixed>,
+    ) -> core::result::Result<(), ReadError> {
+        scalars.clear();
+        let var_store = self
+            .var_store
+            .as_ref()
+            .ok_or(ReadError::MalformedData("blend without a variation store"))?;
+        let data = var_store
+            .item_variation_datas()
+            .nth(vsindex as usize)
+            .flatten()
+            .transpose()?
+            .ok_or(ReadError::MalformedData("invalid vsindex"))?;
+        let regions = var_store.variation_region_list()?.variation_regions();
+        for region_index in data.region_indexes() {
+            let region = regions.get(region_index.get() as usize)?;
+            scalars.push(region.compute_scalar(self.coords));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the data starting at an offset from a DICT.
+fn slice_from(table: &[u8], offset: i32) -> core::result::Result<&[u8], ReadError> {
+    usize::try_from(offset)
+        .ok()
+        .and_then(|offset| table.get(offset..))
+        .ok_or(ReadError::OutOfBounds)
+}
+
+/// Scales and offsets charstring coordinates before sending them to a pen.
+struct ScalingSink<'p, P> {
+    pen: &'p mut P,
+    scale: f32,
+    /// The offset of an accent in a `seac` glyph.
+    offset: (Fixed, Fixed),
+}
+
+impl<P> ScalingSink<'_, P> {
+    fn point(&self, x: Fixed, y: Fixed) -> (f32, f32) {
+        (
+            (x + self.offset.0).to_f64() as f32 * self.scale,
+            (y + self.offset.1).to_f64() as f32 * self.scale,
+        )
+    }
+}
+
+impl<P: Pen> CharstringSink for ScalingSink<'_, P> {
+    fn move_to(&mut self, x: Fixed, y: Fixed) {
+        let (x, y) = self.point(x, y);
+        self.pen.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: Fixed, y: Fixed) {
+        let (x, y) = self.point(x, y);
+        self.pen.line_to(x, y);
+    }
+
+    fn curve_to(&mut self, cx0: Fixed, cy0: Fixed, cx1: Fixed, cy1: Fixed, x: Fixed, y: Fixed) {
+        let (cx0, cy0) = self.point(cx0, cy0);
+        let (cx1, cy1) = self.point(cx1, cy1);
+        let (x, y) = self.point(x, y);
+        self.pen.curve_to(cx0, cy0, cx1, cy1, x, y);
+    }
+
+    fn close(&mut self) {
+        self.pen.close();
+    }
+}
@@ -0,0 +1,340 @@
+/*!
+Color glyphs loaded from the `COLR` and `CPAL` tables.
+
+Version 0 color glyphs are stacks of solid colored layers, and version 1
+color glyphs are graphs of paints, which may include gradients, transforms
+and compositing.
+
+*/
+
+mod paint;
+
+use crate::{Brush, Color, ColorPainter, Error, GlyphId, NormalizedCoord, Result};
+
+use read_fonts::{
+    tables::{
+        colr::{BaseGlyph, Layer},
+        cpal::{ColorRecord, Cpal},
+    },
+    ReadError, TableProvider,
+};
+
+/// Palette entry index for the foreground color.
+const FOREGROUND_PALETTE_INDEX: u16 = 0xFFFF;
+
+/// Color glyph layers and their palettes for a specific font.
+pub struct ColorGlyphs<'a> {
+    /// Base glyph records, sorted by glyph identifier.
+    base_glyphs: &'a [BaseGlyph],
+    layers: &'a [Layer],
+    /// Version 1 paint graphs.
+    paints: Option<paint::Paints<'a>>,
+    palettes: Option<Palettes<'a>>,
+    /// Normalized variation coordinates.
+    coords: &'a [NormalizedCoord],
+    /// Scale factor from font units to pixels, or 1.0 for unscaled paints.
+    scale: f32,
+}
+
+impl<'a> ColorGlyphs<'a> {
+    /// Creates a new source for the color glyphs in the specified font and
+    /// configuration.
+    ///
+    /// Returns an error if the font has no `COLR` table, or the table has no
+    /// color glyphs.
+    pub fn new(
+        font: &impl TableProvider<'a>,
+        size: f32,
+        coords: &'a [NormalizedCoord],
+    ) -> Result<Self> {
+        let colr = font.colr()?;
+        let base_glyphs = colr.base_glyph_records().transpose()?.unwrap_or_default();
+        let layers = colr.layer_records().transpose()?.unwrap_or_default();
+        let paints = paint::Paints::new(&colr)?;
+        if base_glyphs.is_empty() && paints.is_none() {
+            return Err(ReadError::MalformedData("no COLR glyphs").into());
+        }
+        let palettes = font.cpal().ok().and_then(|cpal| Palettes::new(cpal).ok());
+        let upem = font.head()?.units_per_em();
+        let size = size.abs();
+        let scale = if size != 0. && upem != 0 {
+            size / upem as f32
+        } else {
+            1.0
+        };
+        Ok(Self {
+            base_glyphs,
+            layers,
+            paints,
+            palettes,
+            coords,
+            scale,
+        })
+    }
+
+    /// Returns the layers of the specified glyph, in bottom to top order, or
+    /// `None` if it is not a color glyph.
+    pub fn layers(&self, glyph_id: GlyphId) -> Option<&'a [Layer]> {
+        let index = self
+            .base_glyphs
+            .binary_search_by_key(&glyph_id, |base| base.glyph_id())
+            .ok()?;
+        let base = &self.base_glyphs[index];
+        let start = base.first_layer_index() as usize;
+        self.layers.get(start..start + base.num_layers() as usize)
+    }
+
+    /// Returns the color of a layer, using the specified palette.
+    ///
+    /// Returns `None` if the layer uses the foreground color, or its color
+    /// is not in the palette. The first palette is used if the specified one
+    /// does not exist.
+    pub fn color(&self, palette: usize, layer: &Layer) -> Option<Color> {
+        self.palette_color(palette, layer.palette_index())
+    }
+
+    /// Paints the specified glyph, using the colors from the specified
+    /// palette.
+    ///
+    /// The glyph's version 1 paint graph is used if it has one, and otherwise
+    /// its version 0 layers. The `load_outline` function is called to send
+    /// the outline of each glyph used as a clip to the painter.
+    pub fn paint<P: ColorPainter>(
+        &self,
+        glyph_id: GlyphId,
+        palette: usize,
+        painter: &mut P,
+        mut load_outline: impl FnMut(GlyphId, &mut P) -> Result<()>,
+    ) -> Result<()> {
+        if let Some(paints) = &self.paints {
+            if let Some(paint) = paints.base_paint(glyph_id) {
+                return paint::Traversal {
+                    glyphs: self,
+                    paints,
+                    palette,
+                    painter,
+                    load_outline,
+                    glyph_id,
+                }
+                .paint_root(&paint?);
+            }
+        }
+        let layers = self
+            .layers(glyph_id)
+            .ok_or(Error::GlyphNotFound(glyph_id))?;
+        for layer in layers {
+            painter.push_clip_glyph(layer.glyph_id());
+            load_outline(layer.glyph_id(), painter)?;
+            painter.fill(Brush::Solid {
+                color: self.color(palette, layer),
+                alpha: 1.0,
+            });
+            painter.pop_clip();
+        }
+        Ok(())
+    }
+
+    /// Returns the color of a palette entry, or `None` for the foreground
+    /// color.
+    fn palette_color(&self, palette: usize, palette_index: u16) -> Option<Color> {
+        if palette_index == FOREGROUND_PALETTE_INDEX {
+            return None;
+        }
+        self.palettes.as_ref()?.color(palette, palette_index)
+    }
+}
+
+/// The color palettes from a `CPAL` table.
+#[derive(Clone)]
+struct Palettes<'a> {
+    /// Index of the first color record of each palette.
+    first_indices: &'a [read_fonts::types::BigEndian<u16>],
+    entry_count: usize,
+    records: &'a [ColorRecord],
+}
+
+impl<'a> Palettes<'a> {
+    fn new(cpal: Cpal<'a>) -> core::result::Result<Self, ReadError> {
+        Ok(Self {
+            first_indices: cpal.color_record_indices(),
+            entry_count: cpal.num_palette_entries() as usize,
+            records: cpal.color_records_array().transpose()?.unwrap_or_default(),
+        })
+    }
+
+    fn color(&self, palette: usize, palette_index: u16) -> Option<Color> {
+        let palette_index = palette_index as usize;
+        if palette_index >= self.entry_count {
+            return None;
+        }
+        let first = self
+            .first_indices
+            .get(palette)
+            .or_else(|| self.first_indices.first())?
+            .get() as usize;
+        let record = self.records.get(first + palette_index)?;
+        Some(Color {
+            red: record.red(),
+            green: record.green(),
+            blue: record.blue(),
+            alpha: record.alpha(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::Path;
+    use crate::{font::*, Color, ColorPen, Context, Error, GlyphId, Pen};
+
+    use read_fonts::{test_data::test_fonts, FontData};
+
+    /// A TrueType font with added `COLR` and `CPAL` tables.
+    struct ColorFont {
+        font: FontRef<'static>,
+        colr: Vec<u8>,
+        cpal: Vec<u8>,
+    }
+
+    impl<'a> TableProvider<'a> for &'a ColorFont {
+        fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>> {
+            match &tag.into_bytes() {
+                b"COLR" => Some(FontData::new(&self.colr)),
+                b"CPAL" => Some(FontData::new(&self.cpal)),
+                _ => self.font.data_for_tag(tag),
+            }
+        }
+    }
+
+    /// Glyph 3 has three layers: glyph 1 with palette entry 0, glyph 2 with
+    /// the foreground color and glyph 1 with a missing palette entry. There
+    /// are two palettes, each with two entries.
+    fn make_font() -> ColorFont {
+        let colr = [
+            [0, 0, 0, 1, 0, 0, 0, 14, 0, 0, 0, 20, 0, 3].as_slice(),
+            &[0, 3, 0, 0, 0, 3],
+            &[0, 1, 0, 0, 0, 2, 0xFF, 0xFF, 0, 1, 0, 5],
+        ]
+        .concat();
+        let cpal = [
+            [0, 0, 0, 2, 0, 2, 0, 4, 0, 0, 0, 16, 0, 0, 0, 2].as_slice(),
+            // red, green, translucent blue and white, in BGRA order
+            &[0, 0, 0xFF, 0xFF, 0, 0xFF, 0, 0xFF],
+            &[0xFF, 0, 0, 0x80, 0xFF, 0xFF, 0xFF, 0xFF],
+        ]
+        .concat();
+        ColorFont {
+            font: FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap(),
+            colr,
+            cpal,
+        }
+    }
+
+    #[derive(Default)]
+    struct ColorPath {
+        layers: Vec<(Option<Color>, Path)>,
+        open: bool,
+    }
+
+    impl Pen for ColorPath {
+        fn move_to(&mut self, x: f32, y: f32) {
+            self.layers.last_mut().unwrap().1.move_to(x, y);
+        }
+
+        fn line_to(&mut self, x: f32, y: f32) {
+            self.layers.last_mut().unwrap().1.line_to(x, y);
+        }
+
+        fn quad_to(&mut self, x0: f32, y0: f32, x1: f32, y1: f32) {
+            self.layers.last_mut().unwrap().1.quad_to(x0, y0, x1, y1);
+        }
+
+        fn curve_to(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, x2: f32, y2: f32) {
+            let path = &mut self.layers.last_mut().unwrap().1;
+            path.curve_to(x0, y0, x1, y1, x2, y2);
+        }
+
+        fn close(&mut self) {
+            self.layers.last_mut().unwrap().1.close();
+        }
+    }
+
+    impl ColorPen for ColorPath {
+        fn begin_layer(&mut self, color: Option<Color>) {
+            assert!(!self.open);
+            self.open = true;
+            self.layers.push((color, Path::default()));
+        }
+
+        fn end_layer(&mut self) {
+            assert!(self.open);
+            self.open = false;
+        }
+    }
+
+    const RED: Color = Color {
+        red: 0xFF,
+        green: 0,
+        blue: 0,
+        alpha: 0xFF,
+    };
+    const BLUE: Color = Color {
+        red: 0,
+        green: 0,
+        blue: 0xFF,
+        alpha: 0x80,
+    };
+
+    #[test]
+    fn color_layers() {
+        let font = make_font();
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().size(16.0).build(&&font);
+        assert!(scaler.has_color_outlines());
+        let outlines = [1, 2].map(|gid| {
+            let mut path = Path::default();
+            scaler.outline(GlyphId::new(gid), &mut path).unwrap();
+            path.0
+        });
+        // a missing palette falls back to the first
+        for (palette, color) in [(0, RED), (1, BLUE), (5, RED)] {
+            let mut pen = ColorPath::default();
+            scaler
+                .color_outline(GlyphId::new(3), palette, &mut pen)
+                .unwrap();
+            assert!(!pen.open);
+            let layers = pen
+                .layers
+                .into_iter()
+                .map(|(color, path)| (color, path.0))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                layers,
+                [
+                    (Some(color), outlines[0].clone()),
+                    (None, outlines[1].clone()),
+                    (None, outlines[0].clone()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn not_a_color_glyph() {
+        let font = make_font();
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().build(&&font);
+        assert!(matches!(
+            scaler.color_outline(GlyphId::new(1), 0, &mut ColorPath::default()),
+            Err(Error::GlyphNotFound(_))
+        ));
+
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let mut scaler = cx.new_scaler().build(&font);
+        assert!(!scaler.has_color_outlines());
+        assert!(matches!(
+            scaler.color_outline(GlyphId::new(3), 0, &mut ColorPath::default()),
+            Err(Error::NoSources)
+        ));
+    }
+}
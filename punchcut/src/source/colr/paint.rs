@@ -0,0 +1,904 @@
+use super::ColorGlyphs;
+use crate::{
+    BoundingBox, Brush, ColorPainter, ColorStop, CompositeMode, Error, Extend, GlyphId, Result,
+    Transform, COLR_PAINT_RECURSION_LIMIT,
+};
+
+use read_fonts::{
+    tables::{
+        colr::{BaseGlyphList, ClipBox, ClipList, ColorLine, Colr, LayerList, Paint, VarColorLine},
+        variations::{DeltaSetIndex, DeltaSetIndexMap, ItemVariationStore},
+    },
+    types::{F2Dot14, FWord, Fixed, Point},
+    ReadError,
+};
+
+/// Variation index that marks a value as not variable.
+const NO_VARIATION_INDEX: u32 = 0xFFFF_FFFF;
+
+/// The version 1 paint graphs of a `COLR` table.
+pub struct Paints<'a> {
+    base_glyphs: BaseGlyphList<'a>,
+    layers: Option<LayerList<'a>>,
+    clips: Option<ClipList<'a>>,
+    var_index_map: Option<DeltaSetIndexMap<'a>>,
+    var_store: Option<ItemVariationStore<'a>>,
+}
+
+impl<'a> Paints<'a> {
+    /// Returns the paint graphs of the table, or `None` if it has none.
+    pub fn new(colr: &Colr<'a>) -> core::result::Result<Option<Self>, ReadError> {
+        let Some(base_glyphs) = colr.base_glyph_list().transpose()? else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            base_glyphs,
+            layers: colr.layer_list().transpose()?,
+            clips: colr.clip_list().transpose()?,
+            var_index_map: colr.var_index_map().transpose()?,
+            var_store: colr.item_variation_store().transpose()?,
+        }))
+    }
+
+    /// Returns the root paint of the specified glyph, if it has one.
+    pub fn base_paint(&self, glyph_id: GlyphId) -> Option<Result<Paint<'a>>> {
+        let records = self.base_glyphs.base_glyph_paint_records();
+        let index = records
+            .binary_search_by_key(&glyph_id, |record| record.glyph_id())
+            .ok()?;
+        Some(
+            records[index]
+                .paint(self.base_glyphs.offset_data())
+                .map_err(Error::from),
+        )
+    }
+
+    /// Returns the clip box of the specified glyph, if it has one.
+    fn clip_box(&self, glyph_id: GlyphId) -> Option<ClipBox<'a>> {
+        let clips = self.clips.as_ref()?;
+        let clip = clips
+            .clips()
+            .iter()
+            .find(|clip| (clip.start_glyph_id()..=clip.end_glyph_id()).contains(&glyph_id))?;
+        clip.clip_box(clips.offset_data()).ok()
+    }
+
+    /// Returns the deltas for `N` consecutive variable values, in the units
+    /// of the values.
+    fn deltas<const N: usize>(&self, var_index_base: u32, coords: &[F2Dot14]) -> [f32; N] {
+        let mut deltas = [0.0; N];
+        let Some(var_store) = &self.var_store else {
+            return deltas;
+        };
+        if var_index_base == NO_VARIATION_INDEX || coords.is_empty() {
+            return deltas;
+        }
+        for (i, delta) in deltas.iter_mut().enumerate() {
+            let var_index = var_index_base.saturating_add(i as u32);
+            let index = match &self.var_index_map {
+                Some(map) => map.get(var_index),
+                None => Ok(DeltaSetIndex {
+                    outer: (var_index >> 16) as u16,
+                    inner: var_index as u16,
+                }),
+            };
+            *delta = index
+                .and_then(|index| var_store.compute_delta(index, coords))
+                .map(|delta| delta.to_f64() as f32)
+                .unwrap_or_default();
+        }
+        deltas
+    }
+}
+
+/// State for painting a version 1 color glyph.
+pub struct Traversal<'a, 'b, P, F> {
+    pub glyphs: &'b ColorGlyphs<'a>,
+    pub paints: &'b Paints<'a>,
+    pub palette: usize,
+    pub painter: &'b mut P,
+    /// Loads a glyph outline into the painter.
+    pub load_outline: F,
+    /// The glyph being painted, for errors.
+    pub glyph_id: GlyphId,
+}
+
+impl<'a, 'b, P, F> Traversal<'a, 'b, P, F>
+where
+    P: ColorPainter,
+    F: FnMut(GlyphId, &mut P) -> Result<()>,
+{
+    /// Paints the glyph, with its clip box if it has one.
+    pub fn paint_root(&mut self, paint: &Paint<'a>) -> Result<()> {
+        let clip_box = self
+            .paints
+            .clip_box(self.glyph_id)
+            .map(|clip_box| self.clip_box(&clip_box));
+        if let Some(clip_box) = clip_box {
+            self.painter.push_clip_box(clip_box);
+        }
+        self.traverse(paint, 0)?;
+        if clip_box.is_some() {
+            self.painter.pop_clip();
+        }
+        Ok(())
+    }
+
+    fn traverse(&mut self, paint: &Paint<'a>, depth: usize) -> Result<()> {
+        if depth > COLR_PAINT_RECURSION_LIMIT {
+            return Err(Error::PaintRecursionLimitExceeded(self.glyph_id));
+        }
+        let depth = depth + 1;
+        let coords = self.glyphs.coords;
+        let scale = self.glyphs.scale;
+        let scaled = |value: FWord, delta: f32| (value.to_i16() as f32 + delta) * scale;
+        let (transform, child) = match paint {
+            Paint::ColrLayers(layers) => {
+                let list = self
+                    .paints
+                    .layers
+                    .as_ref()
+                    .ok_or(ReadError::MalformedData("missing COLR LayerList"))?;
+                let paints = list
+                    .paints()
+                    .skip(layers.first_layer_index() as usize)
+                    .take(layers.num_layers() as usize);
+                for layer in paints {
+                    self.traverse(&layer?, depth)?;
+                }
+                return Ok(());
+            }
+            Paint::Solid(solid) => {
+                let alpha = f2dot14(solid.alpha(), 0.0);
+                self.fill_solid(solid.palette_index(), alpha);
+                return Ok(());
+            }
+            Paint::VarSolid(solid) => {
+                let [alpha] = self.paints.deltas(solid.var_index_base(), coords);
+                let alpha = f2dot14(solid.alpha(), alpha);
+                self.fill_solid(solid.palette_index(), alpha);
+                return Ok(());
+            }
+            Paint::LinearGradient(gradient) => {
+                let color_line = gradient.color_line()?;
+                let points = [
+                    (gradient.x0(), gradient.y0()),
+                    (gradient.x1(), gradient.y1()),
+                    (gradient.x2(), gradient.y2()),
+                ]
+                .map(|(x, y)| Point::new(scaled(x, 0.0), scaled(y, 0.0)));
+                let stops = self.color_stops(&color_line);
+                self.fill_linear(points, &stops, color_line.extend());
+                return Ok(());
+            }
+            Paint::VarLinearGradient(gradient) => {
+                let color_line = gradient.color_line()?;
+                let d: [f32; 6] = self.paints.deltas(gradient.var_index_base(), coords);
+                let points = [
+                    Point::new(scaled(gradient.x0(), d[0]), scaled(gradient.y0(), d[1])),
+                    Point::new(scaled(gradient.x1(), d[2]), scaled(gradient.y1(), d[3])),
+                    Point::new(scaled(gradient.x2(), d[4]), scaled(gradient.y2(), d[5])),
+                ];
+                let stops = self.var_color_stops(&color_line);
+                self.fill_linear(points, &stops, color_line.extend());
+                return Ok(());
+            }
+            Paint::RadialGradient(gradient) => {
+                let color_line = gradient.color_line()?;
+                let stops = self.color_stops(&color_line);
+                self.painter.fill(Brush::RadialGradient {
+                    c0: Point::new(scaled(gradient.x0(), 0.0), scaled(gradient.y0(), 0.0)),
+                    r0: gradient.radius0().to_u16() as f32 * scale,
+                    c1: Point::new(scaled(gradient.x1(), 0.0), scaled(gradient.y1(), 0.0)),
+                    r1: gradient.radius1().to_u16() as f32 * scale,
+                    color_stops: &stops,
+                    extend: color_line.extend(),
+                });
+                return Ok(());
+            }
+            Paint::VarRadialGradient(gradient) => {
+                let color_line = gradient.color_line()?;
+                let d: [f32; 6] = self.paints.deltas(gradient.var_index_base(), coords);
+                let stops = self.var_color_stops(&color_line);
+                self.painter.fill(Brush::RadialGradient {
+                    c0: Point::new(scaled(gradient.x0(), d[0]), scaled(gradient.y0(), d[1])),
+                    r0: (gradient.radius0().to_u16() as f32 + d[2]) * scale,
+                    c1: Point::new(scaled(gradient.x1(), d[3]), scaled(gradient.y1(), d[4])),
+                    r1: (gradient.radius1().to_u16() as f32 + d[5]) * scale,
+                    color_stops: &stops,
+                    extend: color_line.extend(),
+                });
+                return Ok(());
+            }
+            Paint::SweepGradient(gradient) => {
+                let color_line = gradient.color_line()?;
+                let stops = self.color_stops(&color_line);
+                self.painter.fill(Brush::SweepGradient {
+                    c0: Point::new(
+                        scaled(gradient.center_x(), 0.0),
+                        scaled(gradient.center_y(), 0.0),
+                    ),
+                    start_angle: f2dot14(gradient.start_angle(), 0.0) * 180.0,
+                    end_angle: f2dot14(gradient.end_angle(), 0.0) * 180.0,
+                    color_stops: &stops,
+                    extend: color_line.extend(),
+                });
+                return Ok(());
+            }
+            Paint::VarSweepGradient(gradient) => {
+                let color_line = gradient.color_line()?;
+                let d: [f32; 4] = self.paints.deltas(gradient.var_index_base(), coords);
+                let stops = self.var_color_stops(&color_line);
+                self.painter.fill(Brush::SweepGradient {
+                    c0: Point::new(
+                        scaled(gradient.center_x(), d[0]),
+                        scaled(gradient.center_y(), d[1]),
+                    ),
+                    start_angle: f2dot14(gradient.start_angle(), d[2]) * 180.0,
+                    end_angle: f2dot14(gradient.end_angle(), d[3]) * 180.0,
+                    color_stops: &stops,
+                    extend: color_line.extend(),
+                });
+                return Ok(());
+            }
+            Paint::Glyph(glyph) => {
+                let glyph_id = glyph.glyph_id();
+                self.painter.push_clip_glyph(glyph_id);
+                (self.load_outline)(glyph_id, self.painter)?;
+                self.traverse(&glyph.paint()?, depth)?;
+                self.painter.pop_clip();
+                return Ok(());
+            }
+            Paint::ColrGlyph(glyph) => {
+                let paint = self
+                    .paints
+                    .base_paint(glyph.glyph_id())
+                    .ok_or(Error::GlyphNotFound(glyph.glyph_id()))??;
+                return self.traverse(&paint, depth);
+            }
+            Paint::Composite(composite) => {
+                self.painter.push_layer(CompositeMode::SrcOver);
+                self.traverse(&composite.backdrop_paint()?, depth)?;
+                self.painter.push_layer(composite.composite_mode());
+                self.traverse(&composite.source_paint()?, depth)?;
+                self.painter.pop_layer();
+                self.painter.pop_layer();
+                return Ok(());
+            }
+            Paint::Transform(transform) => {
+                let affine = transform.transform()?;
+                let child = transform.paint()?;
+                let transform = Transform {
+                    xx: fixed(affine.xx(), 0.0),
+                    yx: fixed(affine.yx(), 0.0),
+                    xy: fixed(affine.xy(), 0.0),
+                    yy: fixed(affine.yy(), 0.0),
+                    dx: fixed(affine.dx(), 0.0) * scale,
+                    dy: fixed(affine.dy(), 0.0) * scale,
+                };
+                (transform, child)
+            }
+            Paint::VarTransform(transform) => {
+                let affine = transform.transform()?;
+                let child = transform.paint()?;
+                let d: [f32; 6] = self.paints.deltas(affine.var_index_base(), coords);
+                let transform = Transform {
+                    xx: fixed(affine.xx(), d[0]),
+                    yx: fixed(affine.yx(), d[1]),
+                    xy: fixed(affine.xy(), d[2]),
+                    yy: fixed(affine.yy(), d[3]),
+                    dx: fixed(affine.dx(), d[4]) * scale,
+                    dy: fixed(affine.dy(), d[5]) * scale,
+                };
+                (transform, child)
+            }
+            Paint::Translate(paint) => (
+                translation(scaled(paint.dx(), 0.0), scaled(paint.dy(), 0.0)),
+                paint.paint()?,
+            ),
+            Paint::VarTranslate(paint) => {
+                let d: [f32; 2] = self.paints.deltas(paint.var_index_base(), coords);
+                (
+                    translation(scaled(paint.dx(), d[0]), scaled(paint.dy(), d[1])),
+                    paint.paint()?,
+                )
+            }
+            Paint::Scale(paint) => (
+                scaling(f2dot14(paint.scale_x(), 0.0), f2dot14(paint.scale_y(), 0.0)),
+                paint.paint()?,
+            ),
+            Paint::VarScale(paint) => {
+                let d: [f32; 2] = self.paints.deltas(paint.var_index_base(), coords);
+                (
+                    scaling(
+                        f2dot14(paint.scale_x(), d[0]),
+                        f2dot14(paint.scale_y(), d[1]),
+                    ),
+                    paint.paint()?,
+                )
+            }
+            Paint::ScaleAroundCenter(paint) => (
+                scaling(f2dot14(paint.scale_x(), 0.0), f2dot14(paint.scale_y(), 0.0))
+                    .around_center(scaled(paint.center_x(), 0.0), scaled(paint.center_y(), 0.0)),
+                paint.paint()?,
+            ),
+            Paint::VarScaleAroundCenter(paint) => {
+                let d: [f32; 4] = self.paints.deltas(paint.var_index_base(), coords);
+                (
+                    scaling(
+                        f2dot14(paint.scale_x(), d[0]),
+                        f2dot14(paint.scale_y(), d[1]),
+                    )
+                    .around_center(
+                        scaled(paint.center_x(), d[2]),
+                        scaled(paint.center_y(), d[3]),
+                    ),
+                    paint.paint()?,
+                )
+            }
+            Paint::ScaleUniform(paint) => {
+                let s = f2dot14(paint.scale(), 0.0);
+                (scaling(s, s), paint.paint()?)
+            }
+            Paint::VarScaleUniform(paint) => {
+                let [d] = self.paints.deltas(paint.var_index_base(), coords);
+                let s = f2dot14(paint.scale(), d);
+                (scaling(s, s), paint.paint()?)
+            }
+            Paint::ScaleUniformAroundCenter(paint) => {
+                let s = f2dot14(paint.scale(), 0.0);
+                (
+                    scaling(s, s).around_center(
+                        scaled(paint.center_x(), 0.0),
+                        scaled(paint.center_y(), 0.0),
+                    ),
+                    paint.paint()?,
+                )
+            }
+            Paint::VarScaleUniformAroundCenter(paint) => {
+                let d: [f32; 3] = self.paints.deltas(paint.var_index_base(), coords);
+                let s = f2dot14(paint.scale(), d[0]);
+                (
+                    scaling(s, s).around_center(
+                        scaled(paint.center_x(), d[1]),
+                        scaled(paint.center_y(), d[2]),
+                    ),
+                    paint.paint()?,
+                )
+            }
+            Paint::Rotate(paint) => (rotation(f2dot14(paint.angle(), 0.0)), paint.paint()?),
+            Paint::VarRotate(paint) => {
+                let [d] = self.paints.deltas(paint.var_index_base(), coords);
+                (rotation(f2dot14(paint.angle(), d)), paint.paint()?)
+            }
+            Paint::RotateAroundCenter(paint) => (
+                rotation(f2dot14(paint.angle(), 0.0))
+                    .around_center(scaled(paint.center_x(), 0.0), scaled(paint.center_y(), 0.0)),
+                paint.paint()?,
+            ),
+            Paint::VarRotateAroundCenter(paint) => {
+                let d: [f32; 3] = self.paints.deltas(paint.var_index_base(), coords);
+                (
+                    rotation(f2dot14(paint.angle(), d[0])).around_center(
+                        scaled(paint.center_x(), d[1]),
+                        scaled(paint.center_y(), d[2]),
+                    ),
+                    paint.paint()?,
+                )
+            }
+            Paint::Skew(paint) => (
+                skew(
+                    f2dot14(paint.x_skew_angle(), 0.0),
+                    f2dot14(paint.y_skew_angle(), 0.0),
+                ),
+                paint.paint()?,
+            ),
+            Paint::VarSkew(paint) => {
+                let d: [f32; 2] = self.paints.deltas(paint.var_index_base(), coords);
+                (
+                    skew(
+                        f2dot14(paint.x_skew_angle(), d[0]),
+                        f2dot14(paint.y_skew_angle(), d[1]),
+                    ),
+                    paint.paint()?,
+                )
+            }
+            Paint::SkewAroundCenter(paint) => (
+                skew(
+                    f2dot14(paint.x_skew_angle(), 0.0),
+                    f2dot14(paint.y_skew_angle(), 0.0),
+                )
+                .around_center(scaled(paint.center_x(), 0.0), scaled(paint.center_y(), 0.0)),
+                paint.paint()?,
+            ),
+            Paint::VarSkewAroundCenter(paint) => {
+                let d: [f32; 4] = self.paints.deltas(paint.var_index_base(), coords);
+                (
+                    skew(
+                        f2dot14(paint.x_skew_angle(), d[0]),
+                        f2dot14(paint.y_skew_angle(), d[1]),
+                    )
+                    .around_center(
+                        scaled(paint.center_x(), d[2]),
+                        scaled(paint.center_y(), d[3]),
+                    ),
+                    paint.paint()?,
+                )
+            }
+        };
+        self.painter.push_transform(transform);
+        self.traverse(&child, depth)?;
+        self.painter.pop_transform();
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, palette_index: u16, alpha: f32) {
+        let color = self.glyphs.palette_color(self.palette, palette_index);
+        self.painter.fill(Brush::Solid { color, alpha });
+    }
+
+    /// Fills with a linear gradient, replacing the second point so that the
+    /// gradient is perpendicular to the line from the first point to the
+    /// third, as described in the `COLR` specification.
+    fn fill_linear(&mut self, points: [Point<f32>; 3], stops: &[ColorStop], extend: Extend) {
+        let [p0, p1, p2] = points;
+        let (dx, dy) = (p2.x - p0.x, p2.y - p0.y);
+        // the normal to the line from p0 to p2
+        let (nx, ny) = (dy, -dx);
+        let len_squared = nx * nx + ny * ny;
+        let p1 = if len_squared == 0.0 {
+            p1
+        } else {
+            let t = ((p1.x - p0.x) * nx + (p1.y - p0.y) * ny) / len_squared;
+            Point::new(p0.x + t * nx, p0.y + t * ny)
+        };
+        self.painter.fill(Brush::LinearGradient {
+            p0,
+            p1,
+            color_stops: stops,
+            extend,
+        });
+    }
+
+    fn color_stops(&self, color_line: &ColorLine<'a>) -> Vec<ColorStop> {
+        let mut stops = color_line
+            .color_stops()
+            .iter()
+            .map(|stop| ColorStop {
+                offset: f2dot14(stop.stop_offset(), 0.0),
+                color: self
+                    .glyphs
+                    .palette_color(self.palette, stop.palette_index()),
+                alpha: f2dot14(stop.alpha(), 0.0),
+            })
+            .collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        stops
+    }
+
+    fn var_color_stops(&self, color_line: &VarColorLine<'a>) -> Vec<ColorStop> {
+        let mut stops = color_line
+            .color_stops()
+            .iter()
+            .map(|stop| {
+                let d: [f32; 2] = self
+                    .paints
+                    .deltas(stop.var_index_base(), self.glyphs.coords);
+                ColorStop {
+                    offset: f2dot14(stop.stop_offset(), d[0]),
+                    color: self
+                        .glyphs
+                        .palette_color(self.palette, stop.palette_index()),
+                    alpha: f2dot14(stop.alpha(), d[1]),
+                }
+            })
+            .collect::<Vec<_>>();
+        stops.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        stops
+    }
+
+    fn clip_box(&self, clip_box: &ClipBox<'a>) -> BoundingBox {
+        let scale = self.glyphs.scale;
+        let (bounds, var_index_base) = match clip_box {
+            ClipBox::Format1(clip) => (
+                [clip.x_min(), clip.y_min(), clip.x_max(), clip.y_max()],
+                NO_VARIATION_INDEX,
+            ),
+            ClipBox::Format2(clip) => (
+                [clip.x_min(), clip.y_min(), clip.x_max(), clip.y_max()],
+                clip.var_index_base(),
+            ),
+        };
+        let d: [f32; 4] = self.paints.deltas(var_index_base, self.glyphs.coords);
+        let value = |i: usize| (bounds[i].to_i16() as f32 + d[i]) * scale;
+        BoundingBox {
+            x_min: value(0),
+            y_min: value(1),
+            x_max: value(2),
+            y_max: value(3),
+        }
+    }
+}
+
+/// Converts an F2Dot14 value, with a delta in units of 1/16384.
+fn f2dot14(value: F2Dot14, delta: f32) -> f32 {
+    (value.to_bits() as f32 + delta) / 16384.0
+}
+
+/// Converts a Fixed value, with a delta in units of 1/65536.
+fn fixed(value: Fixed, delta: f32) -> f32 {
+    (value.to_bits() as f32 + delta) / 65536.0
+}
+
+fn translation(dx: f32, dy: f32) -> Transform {
+    Transform {
+        dx,
+        dy,
+        ..Transform::IDENTITY
+    }
+}
+
+fn scaling(sx: f32, sy: f32) -> Transform {
+    Transform {
+        xx: sx,
+        yy: sy,
+        ..Transform::IDENTITY
+    }
+}
+
+/// A counter-clockwise rotation, with the angle in half turns.
+fn rotation(angle: f32) -> Transform {
+    let (sin, cos) = (angle * core::f32::consts::PI).sin_cos();
+    Transform {
+        xx: cos,
+        yx: sin,
+        xy: -sin,
+        yy: cos,
+        ..Transform::IDENTITY
+    }
+}
+
+/// A skew, with the angles in half turns; positive angles are
+/// counter-clockwise.
+fn skew(x_angle: f32, y_angle: f32) -> Transform {
+    Transform {
+        xy: -(x_angle * core::f32::consts::PI).tan(),
+        yx: (y_angle * core::f32::consts::PI).tan(),
+        ..Transform::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{Path, PathElement};
+    use crate::Scaler;
+    use crate::{
+        font::*, BoundingBox, Brush, Color, ColorPainter, ColorStop, CompositeMode, Context, Error,
+        Extend, GlyphId, Pen, Transform,
+    };
+
+    use read_fonts::{
+        test_data::test_fonts,
+        types::{F2Dot14, Point},
+        FontData,
+    };
+
+    #[derive(Clone, PartialEq, Debug)]
+    enum Command {
+        PushTransform(Transform),
+        PopTransform,
+        PushClipGlyph(GlyphId),
+        PushClipBox(BoundingBox),
+        PopClip,
+        Solid(Option<Color>, f32),
+        LinearGradient(Point<f32>, Point<f32>, Vec<ColorStop>, Extend),
+        PushLayer(CompositeMode),
+        PopLayer,
+        Path(PathElement),
+        Close,
+    }
+
+    /// Records the commands sent to a painter, with the outlines of clip
+    /// glyphs.
+    #[derive(Default)]
+    struct Recorder(Vec<Command>);
+
+    impl Recorder {
+        fn push_path(&mut self, f: impl FnOnce(&mut Path)) {
+            let mut path = Path::default();
+            f(&mut path);
+            self.0.extend(path.0.into_iter().map(Command::Path));
+        }
+    }
+
+    impl Pen for Recorder {
+        fn move_to(&mut self, x: f32, y: f32) {
+            self.push_path(|path| path.move_to(x, y));
+        }
+
+        fn line_to(&mut self, x: f32, y: f32) {
+            self.push_path(|path| path.line_to(x, y));
+        }
+
+        fn quad_to(&mut self, x0: f32, y0: f32, x1: f32, y1: f32) {
+            self.push_path(|path| path.quad_to(x0, y0, x1, y1));
+        }
+
+        fn curve_to(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, x2: f32, y2: f32) {
+            self.push_path(|path| path.curve_to(x0, y0, x1, y1, x2, y2));
+        }
+
+        fn close(&mut self) {
+            self.0.push(Command::Close);
+        }
+    }
+
+    impl ColorPainter for Recorder {
+        fn push_transform(&mut self, transform: Transform) {
+            self.0.push(Command::PushTransform(transform));
+        }
+
+        fn pop_transform(&mut self) {
+            self.0.push(Command::PopTransform);
+        }
+
+        fn push_clip_glyph(&mut self, glyph_id: GlyphId) {
+            self.0.push(Command::PushClipGlyph(glyph_id));
+        }
+
+        fn push_clip_box(&mut self, clip_box: BoundingBox) {
+            self.0.push(Command::PushClipBox(clip_box));
+        }
+
+        fn pop_clip(&mut self) {
+            self.0.push(Command::PopClip);
+        }
+
+        fn fill(&mut self, brush: Brush) {
+            self.0.push(match brush {
+                Brush::Solid { color, alpha } => Command::Solid(color, alpha),
+                Brush::LinearGradient {
+                    p0,
+                    p1,
+                    color_stops,
+                    extend,
+                } => Command::LinearGradient(p0, p1, color_stops.to_vec(), extend),
+                _ => panic!("unexpected brush {brush:?}"),
+            });
+        }
+
+        fn push_layer(&mut self, composite_mode: CompositeMode) {
+            self.0.push(Command::PushLayer(composite_mode));
+        }
+
+        fn pop_layer(&mut self) {
+            self.0.push(Command::PopLayer);
+        }
+    }
+
+    /// Returns the commands for the outline of a glyph.
+    fn outline(scaler: &mut Scaler, glyph_id: u16) -> Vec<Command> {
+        let mut recorder = Recorder::default();
+        scaler
+            .outline(GlyphId::new(glyph_id), &mut recorder)
+            .unwrap();
+        recorder.0
+    }
+
+    fn f2dot14(value: f32) -> f32 {
+        F2Dot14::from_f32(value).to_f32()
+    }
+
+    #[test]
+    fn linear_gradient() {
+        let gradient_font = FontRef::new(test_fonts::COLR_GRADIENT_RECT).unwrap();
+        let table = |tag| {
+            gradient_font
+                .table_data(Tag::new(tag))
+                .unwrap()
+                .as_ref()
+                .to_vec()
+        };
+        let font = TestFont::new(table(b"COLR"), table(b"CPAL"));
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().build(&&font);
+        let mut painter = Recorder::default();
+        scaler
+            .paint_color_glyph(GlyphId::new(2), 1, &mut painter)
+            .unwrap();
+        let color = |green| Color {
+            red: 0xAA,
+            green,
+            blue: 0xFF,
+            alpha: 0xFF,
+        };
+        let mut expected = vec![
+            Command::PushClipBox(BoundingBox {
+                x_min: 20.0,
+                y_min: 60.0,
+                x_max: 80.0,
+                y_max: 80.0,
+            }),
+            Command::PushClipGlyph(GlyphId::new(3)),
+        ];
+        expected.extend(outline(&mut scaler, 3));
+        expected.extend([
+            // the second point is moved to be perpendicular to the line from
+            // the first point to the third
+            Command::LinearGradient(
+                Point::new(20.0, 80.0),
+                Point::new(80.0, 80.0),
+                vec![
+                    ColorStop {
+                        offset: f2dot14(0.1),
+                        color: Some(color(0)),
+                        alpha: 1.0,
+                    },
+                    ColorStop {
+                        offset: f2dot14(0.9),
+                        color: Some(color(0xFF)),
+                        alpha: f2dot14(0.8),
+                    },
+                ],
+                Extend::Pad,
+            ),
+            Command::PopClip,
+            Command::PopClip,
+        ]);
+        assert_eq!(painter.0, expected);
+    }
+
+    /// A TrueType font with added `COLR` and `CPAL` tables.
+    struct TestFont {
+        font: FontRef<'static>,
+        colr: Vec<u8>,
+        cpal: Vec<u8>,
+    }
+
+    impl TestFont {
+        fn new(colr: Vec<u8>, cpal: Vec<u8>) -> Self {
+            Self {
+                font: FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap(),
+                colr,
+                cpal,
+            }
+        }
+    }
+
+    impl<'a> TableProvider<'a> for &'a TestFont {
+        fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>> {
+            match &tag.into_bytes() {
+                b"COLR" => Some(FontData::new(&self.colr)),
+                b"CPAL" => Some(FontData::new(&self.cpal)),
+                _ => self.font.data_for_tag(tag),
+            }
+        }
+    }
+
+    /// Glyph 1 is a composite of a solid fill of glyph 2 and a rotated
+    /// foreground fill; glyph 2 is a solid fill of glyph 3, and glyph 3
+    /// refers to itself.
+    fn make_font() -> TestFont {
+        let colr = [
+            // version 1 header, with only a BaseGlyphList
+            [0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 34].as_slice(),
+            &[0; 16],
+            // BaseGlyphList, with paints for glyphs 1, 2 and 3
+            &[
+                0, 0, 0, 3, 0, 1, 0, 0, 0, 22, 0, 2, 0, 0, 0, 48, 0, 3, 0, 0, 0, 59,
+            ],
+            // PaintComposite, multiplying a rotation over glyph 2
+            &[32, 0, 0, 8, 23, 0, 0, 23],
+            // PaintRotateAroundCenter, by a quarter turn around (50, 50)
+            &[26, 0, 0, 10, 0x20, 0, 0, 50, 0, 50],
+            // PaintSolid, with the foreground color
+            &[2, 0xFF, 0xFF, 0x40, 0],
+            // PaintColrGlyph, for glyph 2
+            &[11, 0, 2],
+            // PaintGlyph, for glyph 3 filled with palette entry 1 at half
+            // alpha
+            &[10, 0, 0, 6, 0, 3],
+            &[2, 0, 1, 0x20, 0],
+            // PaintColrGlyph, for glyph 3
+            &[11, 0, 3],
+        ]
+        .concat();
+        // palette entry 1 is cyan
+        let cpal = [
+            [0, 0, 0, 2, 0, 1, 0, 2, 0, 0, 0, 14, 0, 0].as_slice(),
+            &[0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0xFF],
+        ]
+        .concat();
+        TestFont::new(colr, cpal)
+    }
+
+    #[test]
+    fn composite_and_transform() {
+        let font = make_font();
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().build(&&font);
+        let mut painter = Recorder::default();
+        scaler
+            .paint_color_glyph(GlyphId::new(1), 0, &mut painter)
+            .unwrap();
+        let mut expected = vec![
+            Command::PushLayer(CompositeMode::SrcOver),
+            Command::PushClipGlyph(GlyphId::new(3)),
+        ];
+        expected.extend(outline(&mut scaler, 3));
+        let cyan = Color {
+            red: 0,
+            green: 0xFF,
+            blue: 0xFF,
+            alpha: 0xFF,
+        };
+        expected.extend([
+            Command::Solid(Some(cyan), 0.5),
+            Command::PopClip,
+            Command::PushLayer(CompositeMode::Multiply),
+        ]);
+        assert_eq!(painter.0[..expected.len()], expected);
+
+        let Command::PushTransform(transform) = painter.0[expected.len()] else {
+            panic!("expected a transform");
+        };
+        let values = [
+            transform.xx,
+            transform.yx,
+            transform.xy,
+            transform.yy,
+            transform.dx,
+            transform.dy,
+        ];
+        for (value, expected) in values.into_iter().zip([0.0, 1.0, -1.0, 0.0, 100.0, 0.0]) {
+            assert!((value - expected).abs() < 1e-4, "{transform:?}");
+        }
+        assert_eq!(
+            painter.0[expected.len() + 1..],
+            [
+                Command::Solid(None, 1.0),
+                Command::PopTransform,
+                Command::PopLayer,
+                Command::PopLayer,
+            ]
+        );
+    }
+
+    #[test]
+    fn scaled_paint() {
+        let font = make_font();
+        let mut cx = Context::new();
+        // the font has 2048 units per em
+        let mut scaler = cx.new_scaler().size(4096.0).build(&&font);
+        let mut painter = Recorder::default();
+        scaler
+            .paint_color_glyph(GlyphId::new(1), 0, &mut painter)
+            .unwrap();
+        let transform = painter
+            .0
+            .iter()
+            .find_map(|command| match command {
+                Command::PushTransform(transform) => Some(*transform),
+                _ => None,
+            })
+            .unwrap();
+        // only the translation is scaled
+        assert!((transform.dx - 200.0).abs() < 1e-3, "{transform:?}");
+        assert!((transform.yx - 1.0).abs() < 1e-4, "{transform:?}");
+    }
+
+    #[test]
+    fn recursion_limit() {
+        let font = make_font();
+        let mut cx = Context::new();
+        let mut scaler = cx.new_scaler().build(&&font);
+        assert!(matches!(
+            scaler.paint_color_glyph(GlyphId::new(3), 0, &mut Recorder::default()),
+            Err(Error::PaintRecursionLimitExceeded(_))
+        ));
+        assert!(matches!(
+            scaler.paint_color_glyph(GlyphId::new(0), 0, &mut Recorder::default()),
+            Err(Error::GlyphNotFound(_))
+        ));
+    }
+}
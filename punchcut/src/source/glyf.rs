@@ -3,16 +3,55 @@ TrueType outlines loaded from the `glyf` table.
 
 */
 
+#[cfg(feature = "hinting")]
+pub(crate) mod hint;
 mod outline;
 mod scaler;
 
 pub use outline::Outline;
+
+pub(crate) use outline::OutlinePen;
 pub use scaler::Scaler;
 
 pub use read_fonts::types::Point;
 
+use crate::{GlyphId, Transform};
+
 use read_fonts::types::F26Dot6;
 
+/// Component of a TrueType composite glyph.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Component {
+    /// Identifier of the component glyph.
+    pub glyph_id: GlyphId,
+    /// Transformation applied to the outline of the component glyph.
+    ///
+    /// The offset is in pixels for scaled outlines and in font units
+    /// otherwise. For point anchors, this is the offset that aligns the
+    /// anchor points.
+    pub transform: Transform,
+    /// Method used to position the component.
+    pub anchor: ComponentAnchor,
+    /// True if the component provides the advance and side bearings of the
+    /// composite glyph.
+    pub use_my_metrics: bool,
+}
+
+/// Method used to position a component of a composite glyph.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ComponentAnchor {
+    /// The component is moved by an offset.
+    Offset,
+    /// The component is moved so that a point of the component matches a
+    /// point of the components loaded before it.
+    Point {
+        /// Index of the point in the composite glyph.
+        base: u16,
+        /// Index of the point in the component glyph.
+        component: u16,
+    },
+}
+
 /// Context for loading for TrueType glyphs.
 #[derive(Clone, Debug)]
 pub struct Context {
@@ -22,6 +61,9 @@ pub struct Context {
     original: Vec<Point<F26Dot6>>,
     /// Storage for variation deltas.
     deltas: Vec<Point<i32>>,
+    /// Cached state for the hinting interpreter.
+    #[cfg(feature = "hinting")]
+    hint_cache: hint::Cache,
 }
 
 impl Default for Context {
@@ -37,6 +79,8 @@ impl Context {
             unscaled: vec![],
             original: vec![],
             deltas: vec![],
+            #[cfg(feature = "hinting")]
+            hint_cache: Default::default(),
         }
     }
 }
@@ -69,4 +113,35 @@ mod tests {
             assert_eq!(&outline.flags, &expected_outline.tags);
         }
     }
+
+    #[cfg(feature = "hinting")]
+    #[test]
+    fn hinting_without_instructions() {
+        // The font has no font program, so glyph programs fail early and
+        // hinting only rounds the vertical offsets of components.
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let outlines = crate::test::parse_glyph_outlines(test_fonts::VAZIRMATN_VAR_GLYPHS);
+        let mut cx = Context::new();
+        let mut outline = Outline::new();
+        for expected_outline in &outlines {
+            let mut scaler = Scaler::new(
+                &mut cx,
+                &font,
+                Some(1),
+                expected_outline.size,
+                Some(crate::Hinting::Full),
+                &[],
+            )
+            .unwrap();
+            scaler
+                .load(expected_outline.glyph_id, &mut outline)
+                .unwrap();
+            assert_eq!(&outline.contours, &expected_outline.contours);
+            assert_eq!(outline.points.len(), expected_outline.points.len());
+            for (hinted, expected) in outline.points.iter().zip(&expected_outline.points) {
+                assert_eq!(hinted.x, expected.x);
+                assert!((hinted.y - expected.y).abs() < F26Dot6::from_bits(64));
+            }
+        }
+    }
 }
@@ -0,0 +1,12 @@
+//! TrueType bytecode interpreter.
+//!
+//! See <https://learn.microsoft.com/en-us/typography/opentype/spec/ttinst>
+
+mod cache;
+mod engine;
+mod graphics;
+pub(crate) mod math;
+
+pub(crate) use cache::lru_index;
+pub use cache::{Cache, CacheSlot, GlyphOutline};
+pub use engine::Config;
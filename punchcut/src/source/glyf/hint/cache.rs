@@ -0,0 +1,407 @@
+//! Caching of the state produced by the font and control value programs.
+
+use super::{
+    super::scaler::Font,
+    engine::{Config, Definition, Engine, HintError, Program, Zone},
+    graphics::GraphicsState,
+};
+use crate::{Hinting, NormalizedCoord};
+
+use read_fonts::{
+    tables::glyf::PointFlags,
+    types::{F26Dot6, Point},
+};
+
+/// Maximum number of fonts with cached function definitions.
+const MAX_CACHED_FONTS: usize = 8;
+
+/// Maximum number of cached sizes across all fonts.
+const MAX_CACHED_SIZES: usize = 16;
+
+/// Extra space added to the maximum stack size of a font. Matches
+/// FreeType.
+const STACK_SLOP: usize = 32;
+
+/// Extra points added to the maximum twilight points of a font. Matches
+/// FreeType.
+const TWILIGHT_SLOP: usize = 4;
+
+/// Slot in the hinting cache.
+#[derive(Copy, Clone, Debug)]
+pub enum CacheSlot {
+    /// Font without an identifier. The state is recomputed for each
+    /// scaler.
+    Uncached,
+    /// Index of a cached size.
+    Cached(usize),
+}
+
+/// Outline of a glyph to be hinted.
+pub struct GlyphOutline<'a> {
+    /// Unscaled points in font units, or the scaled points of a composite.
+    pub unscaled: &'a [Point<i32>],
+    /// Scaled points before hinting.
+    pub original: &'a [Point<F26Dot6>],
+    /// Points to be hinted, including the four phantom points.
+    pub points: &'a mut [Point<F26Dot6>],
+    pub flags: &'a mut [PointFlags],
+    /// End point indices of each contour.
+    pub contours: &'a [u16],
+    /// Index of the first point, relative to the start of the contours.
+    pub point_base: usize,
+    pub instructions: &'a [u8],
+    pub is_composite: bool,
+}
+
+/// Cache of hinting state for fonts and sizes, along with scratch memory
+/// for executing glyph programs.
+#[derive(Clone, Default, Debug)]
+pub struct Cache {
+    fonts: Vec<FontState>,
+    sizes: Vec<SizeState>,
+    /// Counter for least recently used eviction.
+    serial: u64,
+    /// State for fonts without an identifier.
+    uncached_font: FontState,
+    uncached_size: SizeState,
+    scratch: Scratch,
+}
+
+impl Cache {
+    /// Executes the font and control value programs as necessary and
+    /// returns the slot containing the resulting state.
+    ///
+    /// Returns `None` if either program failed or if the control value
+    /// program disabled hinting.
+    pub fn prepare(
+        &mut self,
+        font: &Font,
+        font_id: Option<u64>,
+        config: Config,
+    ) -> Option<CacheSlot> {
+        let Some(font_id) = font_id else {
+            self.uncached_font
+                .run_fpgm(font, config.mode, &mut self.scratch);
+            self.uncached_size
+                .run_prep(&self.uncached_font, font, config, &mut self.scratch);
+            return self
+                .uncached_size
+                .is_enabled()
+                .then_some(CacheSlot::Uncached);
+        };
+        self.serial += 1;
+        let serial = self.serial;
+        let font_index = match self
+            .fonts
+            .iter()
+            .position(|state| state.font_id == font_id && state.mode == config.mode)
+        {
+            Some(index) => index,
+            None => {
+                let index = lru_index(&mut self.fonts, MAX_CACHED_FONTS, |state| state.serial);
+                let state = &mut self.fonts[index];
+                state.font_id = font_id;
+                state.run_fpgm(font, config.mode, &mut self.scratch);
+                index
+            }
+        };
+        let font_state = &mut self.fonts[font_index];
+        font_state.serial = serial;
+        if !font_state.is_valid {
+            return None;
+        }
+        let size_index = match self
+            .sizes
+            .iter()
+            .position(|state| state.matches(font_id, &config))
+        {
+            Some(index) => index,
+            None => {
+                let index = lru_index(&mut self.sizes, MAX_CACHED_SIZES, |state| state.serial);
+                let state = &mut self.sizes[index];
+                state.font_id = font_id;
+                state.run_prep(&self.fonts[font_index], font, config, &mut self.scratch);
+                index
+            }
+        };
+        let size_state = &mut self.sizes[size_index];
+        size_state.serial = serial;
+        size_state
+            .is_enabled()
+            .then_some(CacheSlot::Cached(size_index))
+    }
+
+    /// Executes the program of a glyph using the state in the given slot.
+    ///
+    /// Returns true if the hinted phantom points should be used for the
+    /// metrics of the glyph, or an error if the slot is invalid.
+    pub fn hint(
+        &mut self,
+        slot: CacheSlot,
+        font: &Font,
+        config: Config,
+        glyph: GlyphOutline,
+    ) -> Result<bool, HintError> {
+        let size = match slot {
+            CacheSlot::Uncached => &mut self.uncached_size,
+            CacheSlot::Cached(index) => self
+                .sizes
+                .get_mut(index)
+                .ok_or(HintError::InvalidArgument)?,
+        };
+        let scratch = &mut self.scratch;
+        // Glyph programs may modify the control values, storage area and
+        // twilight zone but those changes are discarded.
+        scratch.cvt.clear();
+        scratch.cvt.extend_from_slice(&size.cvt);
+        scratch.storage.clear();
+        scratch.storage.extend_from_slice(&size.storage);
+        scratch.twilight.copy_from(&size.twilight);
+        scratch.prepare_stack(font);
+        scratch.original.clear();
+        scratch
+            .original
+            .extend(glyph.original.iter().map(|p| p.map(F26Dot6::to_bits)));
+        scratch.points.clear();
+        scratch
+            .points
+            .extend(glyph.points.iter().map(|p| p.map(F26Dot6::to_bits)));
+        scratch.contours.clear();
+        scratch.contours.extend(
+            glyph
+                .contours
+                .iter()
+                .map(|end| end.wrapping_sub(glyph.point_base as u16)),
+        );
+        let glyph_zone = Zone {
+            unscaled: glyph.unscaled,
+            original: &mut scratch.original,
+            points: &mut scratch.points,
+            flags: glyph.flags,
+            contours: &scratch.contours,
+        };
+        let mut engine = Engine::new(
+            [font.fpgm, font.prep, glyph.instructions],
+            &mut size.functions,
+            &mut size.instructions,
+            &mut scratch.cvt,
+            &mut scratch.storage,
+            &mut scratch.stack,
+            [scratch.twilight.zone(), glyph_zone],
+            config,
+        );
+        // Instruction control bit 2 requests the default graphics state
+        // rather than the one produced by the control value program.
+        if size.gs.instruct_control & 2 == 0 {
+            engine.gs = size.gs;
+        }
+        // As in FreeType, errors in glyph programs are not fatal. The
+        // points are kept as modified up to the failing instruction.
+        let _ = engine.run(Program::Glyph, glyph.is_composite);
+        let save_phantom = !engine.backward_compatibility();
+        for (point, hinted) in glyph.points.iter_mut().zip(&scratch.points) {
+            *point = hinted.map(F26Dot6::from_bits);
+        }
+        Ok(save_phantom)
+    }
+}
+
+/// Returns the index of an entry that can be replaced, growing the list if
+/// it has not reached the maximum size.
+pub(crate) fn lru_index<T: Default>(
+    entries: &mut Vec<T>,
+    max_len: usize,
+    serial: impl Fn(&T) -> u64,
+) -> usize {
+    if entries.len() < max_len {
+        entries.push(T::default());
+        return entries.len() - 1;
+    }
+    let index = entries
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, entry)| serial(entry))
+        .map(|(index, _)| index)
+        .unwrap_or_default();
+    entries[index] = T::default();
+    index
+}
+
+/// Definitions produced by the font program.
+#[derive(Clone, Default, Debug)]
+struct FontState {
+    font_id: u64,
+    mode: Hinting,
+    serial: u64,
+    functions: Vec<Definition>,
+    instructions: Vec<Definition>,
+    is_valid: bool,
+}
+
+impl FontState {
+    fn run_fpgm(&mut self, font: &Font, mode: Hinting, scratch: &mut Scratch) {
+        self.mode = mode;
+        self.functions.clear();
+        self.functions
+            .resize(font.max_function_defs as usize, Default::default());
+        self.instructions.clear();
+        self.instructions
+            .resize(font.max_instruction_defs as usize, Default::default());
+        // The font program runs before any size is selected, so the
+        // control values, storage area and twilight zone are only
+        // placeholders.
+        scratch.cvt.clear();
+        scratch.cvt.resize(font.cvt.len(), 0);
+        scratch.storage.clear();
+        scratch.storage.resize(font.max_storage as usize, 0);
+        scratch.twilight.reset(font);
+        scratch.prepare_stack(font);
+        let config = Config {
+            mode,
+            ppem: 0,
+            point_size: 0,
+            scale: 0,
+            coords: &[],
+            axis_count: font.axis_count,
+        };
+        let mut engine = Engine::new(
+            [font.fpgm, font.prep, &[]],
+            &mut self.functions,
+            &mut self.instructions,
+            &mut scratch.cvt,
+            &mut scratch.storage,
+            &mut scratch.stack,
+            [scratch.twilight.zone(), Zone::empty()],
+            config,
+        );
+        self.is_valid = engine.run(Program::Font, false).is_ok();
+    }
+}
+
+/// State produced by the control value program for a specific size and
+/// set of variation coordinates.
+#[derive(Clone, Default, Debug)]
+struct SizeState {
+    font_id: u64,
+    mode: Hinting,
+    ppem: i32,
+    point_size: i32,
+    scale: i32,
+    coords: Vec<NormalizedCoord>,
+    serial: u64,
+    functions: Vec<Definition>,
+    instructions: Vec<Definition>,
+    cvt: Vec<i32>,
+    storage: Vec<i32>,
+    twilight: Twilight,
+    gs: GraphicsState,
+    is_valid: bool,
+}
+
+impl SizeState {
+    fn matches(&self, font_id: u64, config: &Config) -> bool {
+        self.font_id == font_id
+            && self.mode == config.mode
+            && self.ppem == config.ppem
+            && self.point_size == config.point_size
+            && self.scale == config.scale
+            && self.coords == config.coords
+    }
+
+    /// Returns true if the state is valid and hinting was not disabled by
+    /// the control value program.
+    fn is_enabled(&self) -> bool {
+        self.is_valid && self.gs.instruct_control & 1 == 0
+    }
+
+    fn run_prep(
+        &mut self,
+        font_state: &FontState,
+        font: &Font,
+        config: Config,
+        scratch: &mut Scratch,
+    ) {
+        self.mode = config.mode;
+        self.ppem = config.ppem;
+        self.point_size = config.point_size;
+        self.scale = config.scale;
+        self.coords.clear();
+        self.coords.extend_from_slice(config.coords);
+        // The control value program may add or replace definitions.
+        self.functions.clear();
+        self.functions.extend_from_slice(&font_state.functions);
+        self.instructions.clear();
+        self.instructions
+            .extend_from_slice(&font_state.instructions);
+        font.scale_cvt(config.scale, config.coords, &mut self.cvt);
+        self.storage.clear();
+        self.storage.resize(font.max_storage as usize, 0);
+        self.twilight.reset(font);
+        scratch.prepare_stack(font);
+        let mut engine = Engine::new(
+            [font.fpgm, font.prep, &[]],
+            &mut self.functions,
+            &mut self.instructions,
+            &mut self.cvt,
+            &mut self.storage,
+            &mut scratch.stack,
+            [self.twilight.zone(), Zone::empty()],
+            config,
+        );
+        let result = engine.run(Program::ControlValue, false);
+        let mut gs = engine.gs;
+        gs.reset_after_prep();
+        self.gs = gs;
+        self.is_valid = font_state.is_valid && result.is_ok();
+    }
+}
+
+/// Points of the twilight zone.
+#[derive(Clone, Default, Debug)]
+struct Twilight {
+    original: Vec<Point<i32>>,
+    points: Vec<Point<i32>>,
+    flags: Vec<PointFlags>,
+}
+
+impl Twilight {
+    /// Resets all points to the origin.
+    fn reset(&mut self, font: &Font) {
+        let count = font.max_twilight as usize + TWILIGHT_SLOP;
+        self.original.clear();
+        self.original.resize(count, Point::default());
+        self.points.clear();
+        self.points.resize(count, Point::default());
+        self.flags.clear();
+        self.flags.resize(count, PointFlags::default());
+    }
+
+    fn copy_from(&mut self, other: &Self) {
+        self.original.clone_from(&other.original);
+        self.points.clone_from(&other.points);
+        self.flags.clone_from(&other.flags);
+    }
+
+    fn zone(&mut self) -> Zone<'_> {
+        Zone::new(&mut self.original, &mut self.points, &mut self.flags)
+    }
+}
+
+/// Memory used during execution that is not preserved.
+#[derive(Clone, Default, Debug)]
+struct Scratch {
+    stack: Vec<i32>,
+    cvt: Vec<i32>,
+    storage: Vec<i32>,
+    twilight: Twilight,
+    original: Vec<Point<i32>>,
+    points: Vec<Point<i32>>,
+    contours: Vec<u16>,
+}
+
+impl Scratch {
+    fn prepare_stack(&mut self, font: &Font) {
+        self.stack.clear();
+        self.stack.resize(font.max_stack as usize + STACK_SLOP, 0);
+    }
+}
@@ -0,0 +1,940 @@
+//! The TrueType bytecode interpreter.
+//!
+//! This closely follows the FreeType interpreter (`ttinterp.c`), including
+//! its undocumented behavior and the "v40" backward compatibility mode used
+//! for subpixel hinting.
+
+mod points;
+
+use super::graphics::{GraphicsState, RoundMode};
+use super::math;
+use crate::{Hinting, NormalizedCoord};
+
+use read_fonts::{tables::glyf::PointFlags, types::Point};
+
+/// Maximum depth of nested function calls.
+const MAX_CALL_STACK: usize = 32;
+
+/// Maximum number of instructions executed by a single program. This
+/// guards against infinite loops.
+const MAX_RUN_INSTRUCTIONS: u32 = 1_000_000;
+
+/// Errors that may occur when executing a program.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HintError {
+    /// An undefined opcode was executed.
+    InvalidOpcode(u8),
+    /// An instruction extends past the end of the program.
+    UnexpectedEndOfBytecode,
+    /// The value stack overflowed.
+    StackOverflow,
+    /// The call stack overflowed.
+    CallStackOverflow,
+    /// An argument was out of range.
+    InvalidArgument,
+    /// A call referenced an undefined function.
+    InvalidFunction,
+    /// A jump targeted a location outside of the current function.
+    InvalidJump,
+    /// Division by zero.
+    DivideByZero,
+    /// A function or instruction definition was found in a glyph program.
+    DefinitionInGlyphProgram,
+    /// A function or instruction definition was nested in another.
+    NestedDefinition,
+    /// Exceeded the maximum number of function or instruction definitions.
+    TooManyDefinitions,
+    /// An `ENDF` instruction was found outside of a function.
+    UnexpectedEndf,
+    /// Exceeded the maximum number of executed instructions.
+    ExecutionLimitExceeded,
+}
+
+/// Identifies one of the programs that may contain bytecode.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum Program {
+    /// The font program (`fpgm`).
+    #[default]
+    Font,
+    /// The control value program (`prep`).
+    ControlValue,
+    /// The program of a glyph.
+    Glyph,
+}
+
+/// Function or instruction definition.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct Definition {
+    /// Program containing the definition.
+    pub program: Program,
+    /// Offset of the first instruction of the definition.
+    pub start: u32,
+    /// Offset of the `ENDF` instruction.
+    pub end: u32,
+    /// Function number or opcode.
+    pub key: u16,
+    /// True if the definition is in use.
+    pub is_active: bool,
+}
+
+#[derive(Copy, Clone, Default)]
+struct CallRecord {
+    caller_program: Program,
+    return_pc: usize,
+    count: u32,
+    definition: Definition,
+}
+
+/// Points, flags and contours of one of the two zones accessible to the
+/// interpreter.
+pub struct Zone<'a> {
+    /// Unscaled points, used to measure original distances in the glyph
+    /// zone.
+    pub unscaled: &'a [Point<i32>],
+    /// Scaled points before hinting.
+    pub original: &'a mut [Point<i32>],
+    /// Current points.
+    pub points: &'a mut [Point<i32>],
+    pub flags: &'a mut [PointFlags],
+    /// End point indices of each contour.
+    pub contours: &'a [u16],
+}
+
+impl<'a> Zone<'a> {
+    /// Creates a new zone with no unscaled points or contours, such as the
+    /// twilight zone.
+    pub fn new(
+        original: &'a mut [Point<i32>],
+        points: &'a mut [Point<i32>],
+        flags: &'a mut [PointFlags],
+    ) -> Self {
+        Self {
+            unscaled: &[],
+            original,
+            points,
+            flags,
+            contours: &[],
+        }
+    }
+
+    /// Creates a new empty zone.
+    pub fn empty() -> Self {
+        Self::new(&mut [], &mut [], &mut [])
+    }
+}
+
+/// Parameters for program execution.
+#[derive(Copy, Clone, Debug)]
+pub struct Config<'a> {
+    pub mode: Hinting,
+    /// Size in pixels per em.
+    pub ppem: i32,
+    /// Size in points, in 26.6 format.
+    pub point_size: i32,
+    /// Scale factor from font units to 26.6, in 16.16 format.
+    pub scale: i32,
+    pub coords: &'a [NormalizedCoord],
+    pub axis_count: u16,
+}
+
+/// TrueType bytecode interpreter.
+pub struct Engine<'a> {
+    /// Code for the font, control value and glyph programs.
+    programs: [&'a [u8]; 3],
+    /// Program where execution began.
+    initial_program: Program,
+    /// Program that is currently executing.
+    program: Program,
+    /// Offset, opcode and length of the current instruction.
+    pc: usize,
+    opcode: u8,
+    len: usize,
+    /// False if the current instruction changed the program counter.
+    step: bool,
+    functions: &'a mut [Definition],
+    instructions: &'a mut [Definition],
+    cvt: &'a mut [i32],
+    storage: &'a mut [i32],
+    stack: &'a mut [i32],
+    sp: usize,
+    call_stack: [CallRecord; MAX_CALL_STACK],
+    call_top: usize,
+    /// Twilight and glyph zones.
+    zones: [Zone<'a>; 2],
+    pub gs: GraphicsState,
+    config: Config<'a>,
+    /// Scale factor for unscaled points of the glyph zone.
+    unscaled_scale: i32,
+    is_composite: bool,
+    backward_compat: bool,
+    /// True if `IUP` has been executed for the x and y axes, respectively.
+    did_iup: [bool; 2],
+}
+
+impl<'a> Engine<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        programs: [&'a [u8]; 3],
+        functions: &'a mut [Definition],
+        instructions: &'a mut [Definition],
+        cvt: &'a mut [i32],
+        storage: &'a mut [i32],
+        stack: &'a mut [i32],
+        zones: [Zone<'a>; 2],
+        config: Config<'a>,
+    ) -> Self {
+        Self {
+            programs,
+            initial_program: Program::Font,
+            program: Program::Font,
+            pc: 0,
+            opcode: 0,
+            len: 0,
+            step: false,
+            functions,
+            instructions,
+            cvt,
+            storage,
+            stack,
+            sp: 0,
+            call_stack: Default::default(),
+            call_top: 0,
+            zones,
+            gs: GraphicsState::default(),
+            config,
+            unscaled_scale: config.scale,
+            is_composite: false,
+            backward_compat: false,
+            did_iup: [false; 2],
+        }
+    }
+
+    /// Returns true if backward compatibility mode was active at the end of
+    /// execution.
+    pub fn backward_compatibility(&self) -> bool {
+        self.backward_compat
+    }
+
+    /// Executes the specified program.
+    ///
+    /// For glyph programs, `is_composite` determines whether the glyph zone
+    /// contains a composite glyph. The unscaled points of composites are
+    /// already scaled.
+    pub fn run(&mut self, program: Program, is_composite: bool) -> Result<(), HintError> {
+        self.initial_program = program;
+        self.program = program;
+        self.pc = 0;
+        self.sp = 0;
+        self.call_top = 0;
+        self.is_composite = is_composite;
+        self.unscaled_scale = if is_composite {
+            0x10000
+        } else {
+            self.config.scale
+        };
+        if program == Program::Glyph {
+            self.gs.reset_for_program();
+        } else {
+            self.gs.update_projection_state();
+        }
+        self.backward_compat = self.is_v40()
+            && (self.config.mode == Hinting::VerticalSubpixel || self.gs.instruct_control & 4 == 0);
+        self.did_iup = [false; 2];
+        let mut count = 0;
+        loop {
+            let code = self.programs[self.program as usize];
+            if self.pc >= code.len() {
+                // Running off the end of a function is an error.
+                return if self.call_top > 0 {
+                    Err(HintError::UnexpectedEndOfBytecode)
+                } else {
+                    Ok(())
+                };
+            }
+            count += 1;
+            if count > MAX_RUN_INSTRUCTIONS {
+                return Err(HintError::ExecutionLimitExceeded);
+            }
+            self.opcode = code[self.pc];
+            self.len = opcode_length(code, self.pc).ok_or(HintError::UnexpectedEndOfBytecode)?;
+            self.step = true;
+            self.dispatch()?;
+            if self.step {
+                self.pc += self.len;
+            }
+        }
+    }
+
+    /// Returns true if using the "v40" interpreter that supports subpixel
+    /// hinting.
+    fn is_v40(&self) -> bool {
+        self.config.mode != Hinting::Full
+    }
+
+    fn dispatch(&mut self) -> Result<(), HintError> {
+        let opcode = self.opcode;
+        match opcode {
+            0x00..=0x05 => self.op_svtca(),
+            0x06..=0x07 => self.op_svtl(true),
+            0x08..=0x09 => self.op_svtl(false),
+            0x0A => self.op_svfs(true),
+            0x0B => self.op_svfs(false),
+            // GPV
+            0x0C => {
+                let v = self.gs.proj_vector;
+                self.push(v.x)?;
+                self.push(v.y)?;
+            }
+            // GFV
+            0x0D => {
+                let v = self.gs.freedom_vector;
+                self.push(v.x)?;
+                self.push(v.y)?;
+            }
+            // SFVTPV
+            0x0E => {
+                self.gs.freedom_vector = self.gs.proj_vector;
+                self.gs.update_projection_state();
+            }
+            0x0F => self.op_isect(),
+            // SRP0, SRP1, SRP2
+            0x10 => self.gs.rp0 = self.pop_point(),
+            0x11 => self.gs.rp1 = self.pop_point(),
+            0x12 => self.gs.rp2 = self.pop_point(),
+            // SZP0, SZP1, SZP2, SZPS
+            0x13..=0x16 => {
+                let zone = self.pop();
+                if let Ok(zone @ 0..=1) = usize::try_from(zone) {
+                    match opcode {
+                        0x16 => self.gs.zp = [zone; 3],
+                        _ => self.gs.zp[opcode as usize - 0x13] = zone,
+                    }
+                }
+            }
+            // SLOOP
+            0x17 => {
+                let count = self.pop();
+                if count < 0 {
+                    return Err(HintError::InvalidArgument);
+                }
+                self.gs.loop_counter = count.min(0xFFFF) as u32;
+            }
+            0x18 => self.gs.round_state.mode = RoundMode::ToGrid,
+            0x19 => self.gs.round_state.mode = RoundMode::ToHalfGrid,
+            0x1A => self.gs.min_distance = self.pop(),
+            // ELSE
+            0x1B => {
+                let mut depth = 1;
+                while depth != 0 {
+                    self.skip_code()?;
+                    match self.opcode {
+                        0x58 => depth += 1,
+                        0x59 => depth -= 1,
+                        _ => {}
+                    }
+                }
+            }
+            // JMPR
+            0x1C => {
+                let offset = self.pop();
+                self.jump(offset)?;
+            }
+            0x1D => self.gs.control_value_cutin = self.pop(),
+            0x1E => self.gs.single_width_cutin = self.pop(),
+            0x1F => self.gs.single_width = math::mul_fix(self.pop(), self.config.scale),
+            // DUP
+            0x20 => {
+                let value = self.pop();
+                self.push(value)?;
+                self.push(value)?;
+            }
+            // POP
+            0x21 => {
+                self.pop();
+            }
+            // CLEAR
+            0x22 => self.sp = 0,
+            // SWAP
+            0x23 => {
+                let b = self.pop();
+                let a = self.pop();
+                self.push(b)?;
+                self.push(a)?;
+            }
+            // DEPTH
+            0x24 => self.push(self.sp as i32)?,
+            // CINDEX
+            0x25 => {
+                let index = self.pop();
+                let value = match usize::try_from(index) {
+                    Ok(index @ 1..) if index <= self.sp => self.stack[self.sp - index],
+                    _ => 0,
+                };
+                self.push(value)?;
+            }
+            // MINDEX
+            0x26 => {
+                let index = self.pop();
+                if let Ok(index @ 1..) = usize::try_from(index) {
+                    if index <= self.sp {
+                        let start = self.sp - index;
+                        let value = self.stack[start];
+                        self.stack.copy_within(start + 1..self.sp, start);
+                        self.stack[self.sp - 1] = value;
+                    }
+                }
+            }
+            0x27 => self.op_alignpts(),
+            0x29 => self.op_utp(),
+            // LOOPCALL
+            0x2A => {
+                let key = self.pop();
+                let count = self.pop();
+                let definition = self.find_function(key)?;
+                if self.call_top >= MAX_CALL_STACK {
+                    return Err(HintError::CallStackOverflow);
+                }
+                if count > 0 {
+                    self.call(definition, count as u32)?;
+                }
+            }
+            // CALL
+            0x2B => {
+                let key = self.pop();
+                let definition = self.find_function(key)?;
+                self.call(definition, 1)?;
+            }
+            // FDEF
+            0x2C => {
+                let key = self.pop();
+                self.define(key, false)?;
+            }
+            // ENDF
+            0x2D => {
+                if self.call_top == 0 {
+                    return Err(HintError::UnexpectedEndf);
+                }
+                let record = &mut self.call_stack[self.call_top - 1];
+                record.count -= 1;
+                if record.count > 0 {
+                    // Loop through the current function.
+                    self.pc = record.definition.start as usize;
+                } else {
+                    self.program = record.caller_program;
+                    self.pc = record.return_pc;
+                    self.call_top -= 1;
+                }
+                self.step = false;
+            }
+            0x2E..=0x2F => self.op_mdap(),
+            0x30..=0x31 => self.op_iup(),
+            0x32..=0x33 => self.op_shp(),
+            0x34..=0x35 => self.op_shc(),
+            0x36..=0x37 => self.op_shz(),
+            0x38 => self.op_shpix(),
+            0x39 => self.op_ip(),
+            0x3A..=0x3B => self.op_msirp(),
+            0x3C => self.op_alignrp(),
+            0x3D => self.gs.round_state.mode = RoundMode::ToDoubleGrid,
+            0x3E..=0x3F => self.op_miap(),
+            // NPUSHB, NPUSHW
+            0x40 => self.push_from_code(2, self.len - 2, false)?,
+            0x41 => self.push_from_code(2, (self.len - 2) / 2, true)?,
+            // WS
+            0x42 => {
+                let value = self.pop();
+                let index = self.pop() as usize;
+                if let Some(slot) = self.storage.get_mut(index) {
+                    *slot = value;
+                }
+            }
+            // RS
+            0x43 => {
+                let index = self.pop() as usize;
+                self.push(self.storage.get(index).copied().unwrap_or(0))?;
+            }
+            // WCVTP
+            0x44 => {
+                let value = self.pop();
+                let index = self.pop() as usize;
+                if let Some(slot) = self.cvt.get_mut(index) {
+                    *slot = value;
+                }
+            }
+            // RCVT
+            0x45 => {
+                let index = self.pop() as usize;
+                self.push(self.cvt.get(index).copied().unwrap_or(0))?;
+            }
+            0x46..=0x47 => self.op_gc()?,
+            0x48 => self.op_scfs(),
+            0x49..=0x4A => self.op_md()?,
+            // MPPEM
+            0x4B => self.push(self.config.ppem)?,
+            // MPS
+            0x4C => {
+                // FreeType returns the size in pixels for the v35
+                // interpreter, matching GDI.
+                let size = if self.is_v40() {
+                    self.config.point_size
+                } else {
+                    self.config.ppem
+                };
+                self.push(size)?;
+            }
+            0x4D => self.gs.auto_flip = true,
+            0x4E => self.gs.auto_flip = false,
+            // DEBUG
+            0x4F => {
+                self.pop();
+                return Err(HintError::InvalidOpcode(opcode));
+            }
+            0x50 => self.binary(|a, b| (a < b) as i32)?,
+            0x51 => self.binary(|a, b| (a <= b) as i32)?,
+            0x52 => self.binary(|a, b| (a > b) as i32)?,
+            0x53 => self.binary(|a, b| (a >= b) as i32)?,
+            0x54 => self.binary(|a, b| (a == b) as i32)?,
+            0x55 => self.binary(|a, b| (a != b) as i32)?,
+            // ODD, EVEN
+            0x56 | 0x57 => {
+                let value = self.pop();
+                let value = self.gs.round_state.round(value) & 127;
+                let expected = if opcode == 0x56 { 64 } else { 0 };
+                self.push((value == expected) as i32)?;
+            }
+            // IF
+            0x58 => {
+                if self.pop() == 0 {
+                    // Skip to the matching ELSE or EIF.
+                    let mut depth = 1;
+                    loop {
+                        self.skip_code()?;
+                        match self.opcode {
+                            0x58 => depth += 1,
+                            0x1B if depth == 1 => break,
+                            0x59 => {
+                                depth -= 1;
+                                if depth == 0 {
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            // EIF
+            0x59 => {}
+            0x5A => self.binary(|a, b| (a != 0 && b != 0) as i32)?,
+            0x5B => self.binary(|a, b| (a != 0 || b != 0) as i32)?,
+            0x5C => self.unary(|a| (a == 0) as i32)?,
+            0x5D => self.op_deltap(0),
+            0x5E => self.gs.delta_base = self.pop() as u16,
+            // SDS
+            0x5F => {
+                let shift = self.pop();
+                if !(0..=6).contains(&shift) {
+                    return Err(HintError::InvalidArgument);
+                }
+                self.gs.delta_shift = shift as u16;
+            }
+            0x60 => self.binary(|a, b| a.wrapping_add(b))?,
+            0x61 => self.binary(|a, b| a.wrapping_sub(b))?,
+            // DIV
+            0x62 => {
+                let b = self.pop();
+                let a = self.pop();
+                if b == 0 {
+                    return Err(HintError::DivideByZero);
+                }
+                self.push(math::mul_div_no_round(a, 64, b))?;
+            }
+            0x63 => self.binary(|a, b| math::mul_div(a, b, 64))?,
+            0x64 => self.unary(|a| a.wrapping_abs())?,
+            0x65 => self.unary(|a| a.wrapping_neg())?,
+            0x66 => self.unary(|a| a & !63)?,
+            0x67 => self.unary(|a| a.wrapping_add(63) & !63)?,
+            // ROUND
+            0x68..=0x6B => {
+                let value = self.pop();
+                self.push(self.gs.round_state.round(value))?;
+            }
+            // NROUND
+            0x6C..=0x6F => {}
+            // WCVTF
+            0x70 => {
+                let value = self.pop();
+                let index = self.pop() as usize;
+                if let Some(slot) = self.cvt.get_mut(index) {
+                    *slot = math::mul_fix(value, self.config.scale);
+                }
+            }
+            0x71 => self.op_deltap(16),
+            0x72 => self.op_deltap(32),
+            0x73 => self.op_deltac(0),
+            0x74 => self.op_deltac(16),
+            0x75 => self.op_deltac(32),
+            // SROUND, S45ROUND
+            0x76 => {
+                let selector = self.pop();
+                self.gs.round_state.set_super(0x4000, selector);
+                self.gs.round_state.mode = RoundMode::Super;
+            }
+            0x77 => {
+                let selector = self.pop();
+                self.gs.round_state.set_super(0x2D41, selector);
+                self.gs.round_state.mode = RoundMode::Super45;
+            }
+            // JROT, JROF
+            0x78 | 0x79 => {
+                let condition = self.pop();
+                let offset = self.pop();
+                if (condition != 0) == (opcode == 0x78) {
+                    self.jump(offset)?;
+                }
+            }
+            0x7A => self.gs.round_state.mode = RoundMode::Off,
+            0x7C => self.gs.round_state.mode = RoundMode::UpToGrid,
+            0x7D => self.gs.round_state.mode = RoundMode::DownToGrid,
+            // SANGW, AA: obsolete
+            0x7E | 0x7F => {
+                self.pop();
+            }
+            0x80 => self.op_flippt(),
+            0x81 => self.op_fliprg(true),
+            0x82 => self.op_fliprg(false),
+            // SCANCTRL
+            0x85 => {
+                let value = self.pop();
+                let threshold = value & 0xFF;
+                match threshold {
+                    0xFF => self.gs.scan_control = true,
+                    0 => self.gs.scan_control = false,
+                    _ => {
+                        // The glyph is never rotated or stretched.
+                        if value & 0x100 != 0 && self.config.ppem <= threshold {
+                            self.gs.scan_control = true;
+                        }
+                        if value & 0x800 != 0 && self.config.ppem > threshold {
+                            self.gs.scan_control = false;
+                        }
+                    }
+                }
+            }
+            0x86..=0x87 => self.op_sdpvtl(),
+            // GETINFO
+            0x88 => {
+                let selector = self.pop();
+                let value = self.get_info(selector);
+                self.push(value)?;
+            }
+            // IDEF
+            0x89 => {
+                let key = self.pop();
+                self.define(key, true)?;
+            }
+            // ROLL
+            0x8A => {
+                let c = self.pop();
+                let b = self.pop();
+                let a = self.pop();
+                self.push(b)?;
+                self.push(c)?;
+                self.push(a)?;
+            }
+            0x8B => self.binary(|a, b| a.max(b))?,
+            0x8C => self.binary(|a, b| a.min(b))?,
+            // SCANTYPE
+            0x8D => {
+                let value = self.pop();
+                if value >= 0 {
+                    self.gs.scan_type = value & 0xFFFF;
+                }
+            }
+            // INSTCTRL
+            0x8E => {
+                let selector = self.pop();
+                let value = self.pop();
+                if (1..=3).contains(&selector) {
+                    let bit = 1u8 << (selector - 1);
+                    let value = if value != 0 { bit } else { 0 };
+                    match self.initial_program {
+                        // Only allowed in the control value program...
+                        Program::ControlValue => {
+                            self.gs.instruct_control = (self.gs.instruct_control & !bit) | value;
+                        }
+                        // ...except for temporarily opting out of backward
+                        // compatibility mode for a single glyph.
+                        Program::Glyph
+                            if selector == 3
+                                && self.is_v40()
+                                && self.config.mode != Hinting::VerticalSubpixel =>
+                        {
+                            self.backward_compat = value == 0;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            // GETVARIATION
+            0x91 if self.config.axis_count != 0 => {
+                for i in 0..self.config.axis_count as usize {
+                    let coord = self.config.coords.get(i).copied().unwrap_or_default();
+                    self.push(coord.to_bits() as i32)?;
+                }
+            }
+            // GETDATA
+            0x92 if self.config.axis_count != 0 => self.push(17)?,
+            // PUSHB, PUSHW
+            0xB0..=0xB7 => self.push_from_code(1, (opcode - 0xB0) as usize + 1, false)?,
+            0xB8..=0xBF => self.push_from_code(1, (opcode - 0xB8) as usize + 1, true)?,
+            0xC0..=0xDF => self.op_mdrp(),
+            0xE0..=0xFF => self.op_mirp(),
+            _ => {
+                // Unknown opcodes may be given meaning by an instruction
+                // definition.
+                let definition = self
+                    .instructions
+                    .iter()
+                    .find(|def| def.is_active && def.key == opcode as u16)
+                    .copied()
+                    .ok_or(HintError::InvalidOpcode(opcode))?;
+                self.call(definition, 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_info(&self, selector: i32) -> i32 {
+        let mut result = 0;
+        let is_v40 = self.is_v40();
+        if selector & 1 != 0 {
+            result = if is_v40 { 40 } else { 35 };
+        }
+        // Variable font?
+        if selector & 8 != 0 && self.config.axis_count != 0 {
+            result |= 1 << 10;
+        }
+        let grayscale = if is_v40 {
+            self.config.mode == Hinting::Light
+        } else {
+            true
+        };
+        if selector & 32 != 0 && grayscale {
+            result |= 1 << 12;
+        }
+        if is_v40 {
+            // ClearType enabled, subpixel positioned and symmetrical
+            // smoothing.
+            if selector & 64 != 0 {
+                result |= 1 << 13;
+            }
+            if selector & 1024 != 0 {
+                result |= 1 << 17;
+            }
+            if selector & 2048 != 0 {
+                result |= 1 << 18;
+            }
+            // Grayscale ClearType.
+            if selector & 4096 != 0 && grayscale {
+                result |= 1 << 19;
+            }
+        }
+        result
+    }
+}
+
+// Stack management.
+impl<'a> Engine<'a> {
+    /// Pops a value from the stack.
+    ///
+    /// As in FreeType, a stack underflow is not an error; missing arguments
+    /// are treated as zero.
+    fn pop(&mut self) -> i32 {
+        if self.sp == 0 {
+            0
+        } else {
+            self.sp -= 1;
+            self.stack[self.sp]
+        }
+    }
+
+    /// Pops a point index from the stack.
+    fn pop_point(&mut self) -> usize {
+        self.pop() as u16 as usize
+    }
+
+    fn push(&mut self, value: i32) -> Result<(), HintError> {
+        let slot = self
+            .stack
+            .get_mut(self.sp)
+            .ok_or(HintError::StackOverflow)?;
+        *slot = value;
+        self.sp += 1;
+        Ok(())
+    }
+
+    fn unary(&mut self, f: impl FnOnce(i32) -> i32) -> Result<(), HintError> {
+        let a = self.pop();
+        self.push(f(a))
+    }
+
+    fn binary(&mut self, f: impl FnOnce(i32, i32) -> i32) -> Result<(), HintError> {
+        let b = self.pop();
+        let a = self.pop();
+        self.push(f(a, b))
+    }
+
+    /// Pushes values that are embedded in the code of the current
+    /// instruction.
+    fn push_from_code(
+        &mut self,
+        offset: usize,
+        count: usize,
+        is_words: bool,
+    ) -> Result<(), HintError> {
+        if self.sp + count > self.stack.len() {
+            return Err(HintError::StackOverflow);
+        }
+        let code = self.programs[self.program as usize];
+        let start = self.pc + offset;
+        for i in 0..count {
+            self.stack[self.sp + i] = if is_words {
+                i16::from_be_bytes([code[start + i * 2], code[start + i * 2 + 1]]) as i32
+            } else {
+                code[start + i] as i32
+            };
+        }
+        self.sp += count;
+        Ok(())
+    }
+}
+
+// Flow control and definitions.
+impl<'a> Engine<'a> {
+    /// Advances to the next instruction without executing the current one.
+    fn skip_code(&mut self) -> Result<(), HintError> {
+        let code = self.programs[self.program as usize];
+        self.pc += self.len;
+        self.opcode = *code
+            .get(self.pc)
+            .ok_or(HintError::UnexpectedEndOfBytecode)?;
+        self.len = opcode_length(code, self.pc).ok_or(HintError::UnexpectedEndOfBytecode)?;
+        Ok(())
+    }
+
+    fn jump(&mut self, offset: i32) -> Result<(), HintError> {
+        // A zero offset with an empty stack would loop forever.
+        if offset == 0 && self.sp == 0 {
+            return Err(HintError::InvalidJump);
+        }
+        let pc = self.pc as i64 + offset as i64;
+        if pc < 0
+            || (self.call_top > 0 && pc > self.call_stack[self.call_top - 1].definition.end as i64)
+        {
+            return Err(HintError::InvalidJump);
+        }
+        self.pc = pc as usize;
+        self.step = false;
+        Ok(())
+    }
+
+    fn find_function(&self, key: i32) -> Result<Definition, HintError> {
+        // Functions are usually defined in order, so try a direct lookup
+        // before searching.
+        let is_match = |def: &&Definition| def.is_active && def.key as i32 == key;
+        usize::try_from(key)
+            .ok()
+            .and_then(|index| self.functions.get(index))
+            .filter(is_match)
+            .or_else(|| self.functions.iter().find(is_match))
+            .copied()
+            .ok_or(HintError::InvalidFunction)
+    }
+
+    fn call(&mut self, definition: Definition, count: u32) -> Result<(), HintError> {
+        let record = self
+            .call_stack
+            .get_mut(self.call_top)
+            .ok_or(HintError::CallStackOverflow)?;
+        *record = CallRecord {
+            caller_program: self.program,
+            return_pc: self.pc + self.len,
+            count,
+            definition,
+        };
+        self.call_top += 1;
+        self.program = definition.program;
+        self.pc = definition.start as usize;
+        self.step = false;
+        Ok(())
+    }
+
+    /// Handles the `FDEF` and `IDEF` instructions.
+    fn define(&mut self, key: i32, is_instruction: bool) -> Result<(), HintError> {
+        if self.program == Program::Glyph {
+            return Err(HintError::DefinitionInGlyphProgram);
+        }
+        let definitions = if is_instruction {
+            &mut *self.instructions
+        } else {
+            &mut *self.functions
+        };
+        // Some fonts redefine functions, so replace an existing definition
+        // when present.
+        let index = definitions
+            .iter()
+            .position(|def| def.is_active && def.key as i32 == key)
+            .or_else(|| definitions.iter().position(|def| !def.is_active))
+            .ok_or(HintError::TooManyDefinitions)?;
+        let max_key = if is_instruction { 0xFF } else { 0xFFFF };
+        if !(0..=max_key).contains(&key) {
+            return Err(HintError::TooManyDefinitions);
+        }
+        let mut definition = Definition {
+            program: self.program,
+            start: (self.pc + 1) as u32,
+            end: 0,
+            key: key as u16,
+            is_active: true,
+        };
+        // Skip the body of the definition.
+        loop {
+            self.skip_code()?;
+            match self.opcode {
+                0x2C | 0x89 => return Err(HintError::NestedDefinition),
+                0x2D => {
+                    definition.end = self.pc as u32;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let definitions = if is_instruction {
+            &mut *self.instructions
+        } else {
+            &mut *self.functions
+        };
+        definitions[index] = definition;
+        Ok(())
+    }
+}
+
+/// Returns the length of the instruction at the given offset, or `None`
+/// if it extends past the end of the code.
+fn opcode_length(code: &[u8], pc: usize) -> Option<usize> {
+    let opcode = *code.get(pc)?;
+    let len = match opcode {
+        // NPUSHB, NPUSHW
+        0x40 => 2 + *code.get(pc + 1)? as usize,
+        0x41 => 2 + *code.get(pc + 1)? as usize * 2,
+        // PUSHB, PUSHW
+        0xB0..=0xB7 => 2 + (opcode - 0xB0) as usize,
+        0xB8..=0xBF => 3 + (opcode - 0xB8) as usize * 2,
+        _ => 1,
+    };
+    (pc + len <= code.len()).then_some(len)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,1041 @@
+//! Instructions that manage vectors and move points.
+
+use super::{
+    super::graphics::{UnitVector, ZonePointer, GLYPH, TWILIGHT},
+    math, Engine, HintError,
+};
+
+use read_fonts::{tables::glyf::PointMarker, types::Point};
+
+// Helpers for accessing and moving points.
+impl<'a> Engine<'a> {
+    /// Returns the zone referenced by the zone pointer with the given index.
+    fn zp(&self, index: usize) -> ZonePointer {
+        self.gs.zp[index]
+    }
+
+    fn point_count(&self, zone: ZonePointer) -> usize {
+        self.zones[zone].points.len()
+    }
+
+    /// Returns true if the point is valid for the zone referenced by the
+    /// zone pointer with the given index.
+    fn is_valid_point(&self, zp: usize, point: usize) -> bool {
+        point < self.point_count(self.zp(zp))
+    }
+
+    fn cur(&self, zone: ZonePointer, point: usize) -> Point<i32> {
+        self.zones[zone].points[point]
+    }
+
+    fn org(&self, zone: ZonePointer, point: usize) -> Point<i32> {
+        self.zones[zone].original[point]
+    }
+
+    fn orus(&self, zone: ZonePointer, point: usize) -> Point<i32> {
+        self.zones[zone]
+            .unscaled
+            .get(point)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Projects the vector between two points onto the projection vector.
+    fn project(&self, a: Point<i32>, b: Point<i32>) -> i32 {
+        self.gs
+            .project(a.x.wrapping_sub(b.x), a.y.wrapping_sub(b.y))
+    }
+
+    /// Projects the vector between two points onto the dual projection
+    /// vector.
+    fn dual_project(&self, a: Point<i32>, b: Point<i32>) -> i32 {
+        self.gs
+            .dual_project(a.x.wrapping_sub(b.x), a.y.wrapping_sub(b.y))
+    }
+
+    /// Returns true if `IUP` has been executed for both axes.
+    fn is_post_iup(&self) -> bool {
+        self.did_iup[0] && self.did_iup[1]
+    }
+
+    /// Moves a point along the freedom vector by the given distance, which
+    /// is measured along the projection vector.
+    fn move_point(&mut self, zone: ZonePointer, point: usize, distance: i32) {
+        let free = self.gs.freedom_vector;
+        let fdotp = self.gs.fdotp;
+        // In backward compatibility mode, x movement is never allowed and y
+        // movement is disabled after both IUP instructions.
+        let (allow_x, allow_y) = if self.backward_compat {
+            (false, !self.is_post_iup())
+        } else {
+            (true, true)
+        };
+        let zone = &mut self.zones[zone];
+        if free.x != 0 {
+            if allow_x {
+                let p = &mut zone.points[point];
+                p.x = p.x.wrapping_add(math::mul_div(distance, free.x, fdotp));
+            }
+            zone.flags[point].set_marker(PointMarker::TOUCHED_X);
+        }
+        if free.y != 0 {
+            if allow_y {
+                let p = &mut zone.points[point];
+                p.y = p.y.wrapping_add(math::mul_div(distance, free.y, fdotp));
+            }
+            zone.flags[point].set_marker(PointMarker::TOUCHED_Y);
+        }
+    }
+
+    /// Moves the original position of a point.
+    fn move_original(&mut self, zone: ZonePointer, point: usize, distance: i32) {
+        let free = self.gs.freedom_vector;
+        let fdotp = self.gs.fdotp;
+        let p = &mut self.zones[zone].original[point];
+        if free.x != 0 {
+            p.x = p.x.wrapping_add(math::mul_div(distance, free.x, fdotp));
+        }
+        if free.y != 0 {
+            p.y = p.y.wrapping_add(math::mul_div(distance, free.y, fdotp));
+        }
+    }
+
+    /// Moves a point in the zone referenced by `zp2` by the given
+    /// displacement.
+    fn move_zp2_point(&mut self, point: usize, dx: i32, dy: i32, touch: bool) {
+        let free = self.gs.freedom_vector;
+        let (allow_x, allow_y) = if self.backward_compat {
+            (false, !self.is_post_iup())
+        } else {
+            (true, true)
+        };
+        let zone = self.zp(2);
+        let zone = &mut self.zones[zone];
+        if free.x != 0 {
+            if allow_x {
+                zone.points[point].x = zone.points[point].x.wrapping_add(dx);
+            }
+            if touch {
+                zone.flags[point].set_marker(PointMarker::TOUCHED_X);
+            }
+        }
+        if free.y != 0 {
+            if allow_y {
+                zone.points[point].y = zone.points[point].y.wrapping_add(dy);
+            }
+            if touch {
+                zone.flags[point].set_marker(PointMarker::TOUCHED_Y);
+            }
+        }
+    }
+
+    /// Returns true if the stack contains enough points for the current
+    /// loop counter. Otherwise, resets the loop counter.
+    fn has_loop_points(&mut self) -> bool {
+        if self.sp < self.gs.loop_counter as usize {
+            self.gs.loop_counter = 1;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Pops the points for an instruction that repeats for the loop
+    /// counter and resets the counter.
+    fn pop_loop_points(&mut self) -> impl Iterator<Item = usize> {
+        let count = core::mem::replace(&mut self.gs.loop_counter, 1) as usize;
+        let points = self.stack[self.sp - count..self.sp]
+            .iter()
+            .rev()
+            .map(|point| *point as u16 as usize)
+            .collect::<Vec<_>>();
+        self.sp -= count;
+        points.into_iter()
+    }
+}
+
+// Vectors.
+impl<'a> Engine<'a> {
+    /// Handles the `SVTCA`, `SPVTCA` and `SFVTCA` instructions.
+    pub(super) fn op_svtca(&mut self) {
+        let opcode = self.opcode;
+        let vector = if opcode & 1 != 0 {
+            UnitVector::X_AXIS
+        } else {
+            UnitVector::Y_AXIS
+        };
+        if opcode < 4 {
+            self.gs.proj_vector = vector;
+            self.gs.dual_proj_vector = vector;
+        }
+        if opcode & 2 == 0 {
+            self.gs.freedom_vector = vector;
+        }
+        self.gs.update_projection_state();
+    }
+
+    /// Handles the `SPVTL` and `SFVTL` instructions.
+    pub(super) fn op_svtl(&mut self, is_projection: bool) {
+        let p1 = self.pop_point();
+        let p2 = self.pop_point();
+        if !self.is_valid_point(2, p1) || !self.is_valid_point(1, p2) {
+            return;
+        }
+        let vector = line_vector(
+            self.cur(self.zp(1), p2),
+            self.cur(self.zp(2), p1),
+            self.opcode & 1 != 0,
+        );
+        if is_projection {
+            self.gs.proj_vector = vector;
+            self.gs.dual_proj_vector = vector;
+        } else {
+            self.gs.freedom_vector = vector;
+        }
+        self.gs.update_projection_state();
+    }
+
+    /// Handles the `SPVFS` and `SFVFS` instructions.
+    pub(super) fn op_svfs(&mut self, is_projection: bool) {
+        let y = self.pop() as i16 as i32;
+        let x = self.pop() as i16 as i32;
+        let Some((x, y)) = math::normalize14(x, y) else {
+            return;
+        };
+        let vector = UnitVector { x, y };
+        if is_projection {
+            self.gs.proj_vector = vector;
+            self.gs.dual_proj_vector = vector;
+        } else {
+            self.gs.freedom_vector = vector;
+        }
+        self.gs.update_projection_state();
+    }
+
+    /// Handles the `SDPVTL` instruction.
+    pub(super) fn op_sdpvtl(&mut self) {
+        let p1 = self.pop_point();
+        let p2 = self.pop_point();
+        if !self.is_valid_point(1, p2) || !self.is_valid_point(2, p1) {
+            return;
+        }
+        let (zp1, zp2) = (self.zp(1), self.zp(2));
+        let is_perpendicular = self.opcode & 1 != 0;
+        // The dual projection vector uses the original points.
+        self.gs.dual_proj_vector =
+            line_vector(self.org(zp1, p2), self.org(zp2, p1), is_perpendicular);
+        self.gs.proj_vector = line_vector(self.cur(zp1, p2), self.cur(zp2, p1), is_perpendicular);
+        self.gs.update_projection_state();
+    }
+}
+
+/// Computes the unit vector for the line from `b` to `a`, optionally
+/// rotated counter-clockwise by 90 degrees.
+fn line_vector(a: Point<i32>, b: Point<i32>, is_perpendicular: bool) -> UnitVector {
+    let mut dx = a.x.wrapping_sub(b.x);
+    let mut dy = a.y.wrapping_sub(b.y);
+    let mut is_perpendicular = is_perpendicular;
+    // Coincident points select the x axis.
+    if dx == 0 && dy == 0 {
+        dx = 0x4000;
+        is_perpendicular = false;
+    }
+    if is_perpendicular {
+        (dx, dy) = (dy.wrapping_neg(), dx);
+    }
+    let (x, y) = math::normalize14(dx, dy).unwrap_or((0x4000, 0));
+    UnitVector { x, y }
+}
+
+// Moving points.
+impl<'a> Engine<'a> {
+    /// Handles the `MDAP` instruction.
+    pub(super) fn op_mdap(&mut self) {
+        let point = self.pop_point();
+        if !self.is_valid_point(0, point) {
+            return;
+        }
+        let zone = self.zp(0);
+        let distance = if self.opcode & 1 != 0 {
+            let p = self.cur(zone, point);
+            let distance = self.gs.project(p.x, p.y);
+            self.gs.round_state.round(distance).wrapping_sub(distance)
+        } else {
+            0
+        };
+        self.move_point(zone, point, distance);
+        self.gs.rp0 = point;
+        self.gs.rp1 = point;
+    }
+
+    /// Handles the `MIAP` instruction.
+    pub(super) fn op_miap(&mut self) {
+        let cvt_index = self.pop() as usize;
+        let point = self.pop_point();
+        if !self.is_valid_point(0, point) || cvt_index >= self.cvt.len() {
+            return;
+        }
+        let zone = self.zp(0);
+        let mut distance = self.cvt[cvt_index];
+        // In the twilight zone, the point is created at the distance along
+        // the freedom vector.
+        if zone == TWILIGHT {
+            let free = self.gs.freedom_vector;
+            let p = Point::new(math::mul14(distance, free.x), math::mul14(distance, free.y));
+            self.zones[zone].original[point] = p;
+            self.zones[zone].points[point] = p;
+        }
+        let p = self.cur(zone, point);
+        let original_distance = self.gs.project(p.x, p.y);
+        if self.opcode & 1 != 0 {
+            if distance.wrapping_sub(original_distance).wrapping_abs() > self.gs.control_value_cutin
+            {
+                distance = original_distance;
+            }
+            distance = self.gs.round_state.round(distance);
+        }
+        self.move_point(zone, point, distance.wrapping_sub(original_distance));
+        self.gs.rp0 = point;
+        self.gs.rp1 = point;
+    }
+
+    /// Handles the `MDRP` instruction.
+    pub(super) fn op_mdrp(&mut self) {
+        let opcode = self.opcode;
+        let point = self.pop_point();
+        let rp0 = self.gs.rp0;
+        if self.is_valid_point(1, point) && self.is_valid_point(0, rp0) {
+            let (zp0, zp1) = (self.zp(0), self.zp(1));
+            // The original distance is measured on the unscaled points
+            // unless either is in the twilight zone.
+            let mut original_distance = if zp0 == TWILIGHT || zp1 == TWILIGHT {
+                self.dual_project(self.org(zp1, point), self.org(zp0, rp0))
+            } else {
+                let distance = self.dual_project(self.orus(zp1, point), self.orus(zp0, rp0));
+                math::mul_fix(distance, self.unscaled_scale)
+            };
+            let single_width = self.gs.single_width;
+            let cutin = self.gs.single_width_cutin;
+            if cutin > 0
+                && original_distance < single_width.wrapping_add(cutin)
+                && original_distance > single_width.wrapping_sub(cutin)
+            {
+                original_distance = if original_distance >= 0 {
+                    single_width
+                } else {
+                    -single_width
+                };
+            }
+            let mut distance = if opcode & 4 != 0 {
+                self.gs.round_state.round(original_distance)
+            } else {
+                original_distance
+            };
+            if opcode & 8 != 0 {
+                distance = self.apply_min_distance(original_distance, distance);
+            }
+            let current_distance = self.project(self.cur(zp1, point), self.cur(zp0, rp0));
+            self.move_point(zp1, point, distance.wrapping_sub(current_distance));
+        }
+        self.gs.rp1 = rp0;
+        self.gs.rp2 = point;
+        if opcode & 16 != 0 {
+            self.gs.rp0 = point;
+        }
+    }
+
+    /// Handles the `MIRP` instruction.
+    pub(super) fn op_mirp(&mut self) {
+        let opcode = self.opcode;
+        let cvt_index = self.pop().wrapping_add(1) as u32 as usize;
+        let point = self.pop_point();
+        let rp0 = self.gs.rp0;
+        // A CVT index of -1 refers to an implicit entry with value 0.
+        if self.is_valid_point(1, point)
+            && self.is_valid_point(0, rp0)
+            && cvt_index <= self.cvt.len()
+        {
+            let (zp0, zp1) = (self.zp(0), self.zp(1));
+            let mut cvt_distance = if cvt_index == 0 {
+                0
+            } else {
+                self.cvt[cvt_index - 1]
+            };
+            let single_width = self.gs.single_width;
+            if cvt_distance.wrapping_sub(single_width).wrapping_abs() < self.gs.single_width_cutin {
+                cvt_distance = if cvt_distance >= 0 {
+                    single_width
+                } else {
+                    -single_width
+                };
+            }
+            // In the twilight zone, the point is created at the distance
+            // from the reference point.
+            if zp1 == TWILIGHT {
+                let free = self.gs.freedom_vector;
+                let origin = self.org(zp0, rp0);
+                let p = Point::new(
+                    origin.x.wrapping_add(math::mul14(cvt_distance, free.x)),
+                    origin.y.wrapping_add(math::mul14(cvt_distance, free.y)),
+                );
+                self.zones[zp1].original[point] = p;
+                self.zones[zp1].points[point] = p;
+            }
+            let original_distance = self.dual_project(self.org(zp1, point), self.org(zp0, rp0));
+            let current_distance = self.project(self.cur(zp1, point), self.cur(zp0, rp0));
+            if self.gs.auto_flip && (original_distance ^ cvt_distance) < 0 {
+                cvt_distance = cvt_distance.wrapping_neg();
+            }
+            let mut distance = if opcode & 4 != 0 {
+                // The cut-in test only applies when both points are in the
+                // same zone.
+                if zp0 == zp1
+                    && cvt_distance.wrapping_sub(original_distance).wrapping_abs()
+                        > self.gs.control_value_cutin
+                {
+                    cvt_distance = original_distance;
+                }
+                self.gs.round_state.round(cvt_distance)
+            } else {
+                cvt_distance
+            };
+            if opcode & 8 != 0 {
+                distance = self.apply_min_distance(original_distance, distance);
+            }
+            self.move_point(zp1, point, distance.wrapping_sub(current_distance));
+        }
+        self.gs.rp1 = rp0;
+        if opcode & 16 != 0 {
+            self.gs.rp0 = point;
+        }
+        self.gs.rp2 = point;
+    }
+
+    /// Clamps a distance to the minimum distance, preserving the sign of
+    /// the original distance.
+    fn apply_min_distance(&self, original_distance: i32, distance: i32) -> i32 {
+        let min_distance = self.gs.min_distance;
+        if original_distance >= 0 {
+            distance.max(min_distance)
+        } else {
+            distance.min(min_distance.wrapping_neg())
+        }
+    }
+
+    /// Handles the `MSIRP` instruction.
+    pub(super) fn op_msirp(&mut self) {
+        let distance = self.pop();
+        let point = self.pop_point();
+        let rp0 = self.gs.rp0;
+        if !self.is_valid_point(1, point) || !self.is_valid_point(0, rp0) {
+            return;
+        }
+        let (zp0, zp1) = (self.zp(0), self.zp(1));
+        if zp1 == TWILIGHT {
+            self.zones[zp1].original[point] = self.org(zp0, rp0);
+            self.move_original(zp1, point, distance);
+            self.zones[zp1].points[point] = self.org(zp1, point);
+        }
+        let current_distance = self.project(self.cur(zp1, point), self.cur(zp0, rp0));
+        self.move_point(zp1, point, distance.wrapping_sub(current_distance));
+        self.gs.rp1 = rp0;
+        self.gs.rp2 = point;
+        if self.opcode & 1 != 0 {
+            self.gs.rp0 = point;
+        }
+    }
+
+    /// Handles the `ALIGNRP` instruction.
+    pub(super) fn op_alignrp(&mut self) {
+        let rp0 = self.gs.rp0;
+        if !self.is_valid_point(0, rp0) {
+            self.gs.loop_counter = 1;
+            return;
+        }
+        if !self.has_loop_points() {
+            return;
+        }
+        let (zp0, zp1) = (self.zp(0), self.zp(1));
+        for point in self.pop_loop_points() {
+            if point < self.point_count(zp1) {
+                let distance = self.project(self.cur(zp1, point), self.cur(zp0, rp0));
+                self.move_point(zp1, point, distance.wrapping_neg());
+            }
+        }
+    }
+
+    /// Handles the `ALIGNPTS` instruction.
+    pub(super) fn op_alignpts(&mut self) {
+        let p2 = self.pop_point();
+        let p1 = self.pop_point();
+        if !self.is_valid_point(1, p1) || !self.is_valid_point(0, p2) {
+            return;
+        }
+        let (zp0, zp1) = (self.zp(0), self.zp(1));
+        let distance = self.project(self.cur(zp0, p2), self.cur(zp1, p1)) / 2;
+        self.move_point(zp1, p1, distance);
+        self.move_point(zp0, p2, distance.wrapping_neg());
+    }
+
+    /// Handles the `ISECT` instruction.
+    pub(super) fn op_isect(&mut self) {
+        let b1 = self.pop_point();
+        let b0 = self.pop_point();
+        let a1 = self.pop_point();
+        let a0 = self.pop_point();
+        let point = self.pop_point();
+        if !self.is_valid_point(0, b0)
+            || !self.is_valid_point(0, b1)
+            || !self.is_valid_point(1, a0)
+            || !self.is_valid_point(1, a1)
+            || !self.is_valid_point(2, point)
+        {
+            return;
+        }
+        let (zp0, zp1, zp2) = (self.zp(0), self.zp(1), self.zp(2));
+        let (pa0, pa1) = (self.cur(zp1, a0), self.cur(zp1, a1));
+        let (pb0, pb1) = (self.cur(zp0, b0), self.cur(zp0, b1));
+        let dbx = pb1.x.wrapping_sub(pb0.x);
+        let dby = pb1.y.wrapping_sub(pb0.y);
+        let dax = pa1.x.wrapping_sub(pa0.x);
+        let day = pa1.y.wrapping_sub(pa0.y);
+        let dx = pb0.x.wrapping_sub(pa0.x);
+        let dy = pb0.y.wrapping_sub(pa0.y);
+        let discriminant = math::mul_div(dax, dby.wrapping_neg(), 0x40)
+            .wrapping_add(math::mul_div(day, dbx, 0x40));
+        let dot_product = math::mul_div(dax, dbx, 0x40).wrapping_add(math::mul_div(day, dby, 0x40));
+        // The discriminant and dot product are proportional to the sine and
+        // cosine of the angle between the lines. Reject nearly parallel
+        // lines by thresholding the tangent at 1/19 (about 3 degrees).
+        let p = if 19i32.wrapping_mul(discriminant.wrapping_abs()) > dot_product.wrapping_abs() {
+            let value = math::mul_div(dx, dby.wrapping_neg(), 0x40)
+                .wrapping_add(math::mul_div(dy, dbx, 0x40));
+            Point::new(
+                pa0.x.wrapping_add(math::mul_div(value, dax, discriminant)),
+                pa0.y.wrapping_add(math::mul_div(value, day, discriminant)),
+            )
+        } else {
+            // Otherwise, use the middle of the middles of the lines.
+            Point::new(
+                pa0.x
+                    .wrapping_add(pa1.x)
+                    .wrapping_add(pb0.x.wrapping_add(pb1.x))
+                    / 4,
+                pa0.y
+                    .wrapping_add(pa1.y)
+                    .wrapping_add(pb0.y.wrapping_add(pb1.y))
+                    / 4,
+            )
+        };
+        self.zones[zp2].points[point] = p;
+        self.zones[zp2].flags[point].set_marker(PointMarker::TOUCHED);
+    }
+
+    /// Handles the `SCFS` instruction.
+    pub(super) fn op_scfs(&mut self) {
+        let value = self.pop();
+        let point = self.pop_point();
+        if !self.is_valid_point(2, point) {
+            return;
+        }
+        let zone = self.zp(2);
+        let p = self.cur(zone, point);
+        let current = self.gs.project(p.x, p.y);
+        self.move_point(zone, point, value.wrapping_sub(current));
+        if zone == TWILIGHT {
+            self.zones[zone].original[point] = self.cur(zone, point);
+        }
+    }
+
+    /// Handles the `GC` instruction.
+    pub(super) fn op_gc(&mut self) -> Result<(), HintError> {
+        let point = self.pop() as usize;
+        let value = if self.is_valid_point(2, point) {
+            let zone = self.zp(2);
+            if self.opcode & 1 != 0 {
+                let p = self.org(zone, point);
+                self.gs.dual_project(p.x, p.y)
+            } else {
+                let p = self.cur(zone, point);
+                self.gs.project(p.x, p.y)
+            }
+        } else {
+            0
+        };
+        self.push(value)
+    }
+
+    /// Handles the `MD` instruction.
+    pub(super) fn op_md(&mut self) -> Result<(), HintError> {
+        let p2 = self.pop_point();
+        let p1 = self.pop_point();
+        let distance = if !self.is_valid_point(0, p1) || !self.is_valid_point(1, p2) {
+            0
+        } else {
+            let (zp0, zp1) = (self.zp(0), self.zp(1));
+            if self.opcode & 1 != 0 {
+                self.project(self.cur(zp0, p1), self.cur(zp1, p2))
+            } else if zp0 == TWILIGHT || zp1 == TWILIGHT {
+                self.dual_project(self.org(zp0, p1), self.org(zp1, p2))
+            } else {
+                let distance = self.dual_project(self.orus(zp0, p1), self.orus(zp1, p2));
+                math::mul_fix(distance, self.unscaled_scale)
+            }
+        };
+        self.push(distance)
+    }
+}
+
+// Shifting and interpolating points.
+impl<'a> Engine<'a> {
+    /// Computes the displacement of the reference point for the `SHP`,
+    /// `SHC` and `SHZ` instructions.
+    ///
+    /// Returns the zone and index of the reference point along with the
+    /// displacement.
+    fn point_displacement(&self) -> Option<(ZonePointer, usize, i32, i32)> {
+        let (zone, point) = if self.opcode & 1 != 0 {
+            (self.zp(0), self.gs.rp1)
+        } else {
+            (self.zp(1), self.gs.rp2)
+        };
+        if point >= self.point_count(zone) {
+            return None;
+        }
+        let distance = self.project(self.cur(zone, point), self.org(zone, point));
+        let free = self.gs.freedom_vector;
+        let fdotp = self.gs.fdotp;
+        Some((
+            zone,
+            point,
+            math::mul_div(distance, free.x, fdotp),
+            math::mul_div(distance, free.y, fdotp),
+        ))
+    }
+
+    /// Handles the `SHP` instruction.
+    pub(super) fn op_shp(&mut self) {
+        if !self.has_loop_points() {
+            return;
+        }
+        let Some((_, _, dx, dy)) = self.point_displacement() else {
+            return;
+        };
+        let zone = self.zp(2);
+        for point in self.pop_loop_points() {
+            if point < self.point_count(zone) {
+                if self.backward_compat {
+                    self.move_zp2_point(point, 0, dy, true);
+                } else {
+                    self.move_zp2_point(point, dx, dy, true);
+                }
+            }
+        }
+    }
+
+    /// Handles the `SHC` instruction.
+    pub(super) fn op_shc(&mut self) {
+        let contour = self.pop_point();
+        let zone = self.zp(2);
+        let contours = self.zones[zone].contours;
+        let bound = if zone == TWILIGHT { 1 } else { contours.len() };
+        if contour >= bound {
+            return;
+        }
+        let Some((ref_zone, ref_point, dx, dy)) = self.point_displacement() else {
+            return;
+        };
+        let point_count = self.point_count(zone);
+        let start = if contour == 0 {
+            0
+        } else {
+            contours[contour - 1] as usize + 1
+        };
+        // There are no contours in the twilight zone, so use all points.
+        let end = if zone == TWILIGHT {
+            point_count
+        } else {
+            (contours[contour] as usize + 1).min(point_count)
+        };
+        for point in start..end {
+            if ref_zone != zone || ref_point != point {
+                self.move_zp2_point(point, dx, dy, true);
+            }
+        }
+    }
+
+    /// Handles the `SHZ` instruction.
+    pub(super) fn op_shz(&mut self) {
+        let zone_arg = self.pop();
+        if !(0..2).contains(&zone_arg) {
+            return;
+        }
+        let Some((ref_zone, ref_point, dx, dy)) = self.point_displacement() else {
+            return;
+        };
+        // As in FreeType, this shifts the zone referenced by zp2 rather
+        // than the one given by the argument. Phantom points are not
+        // shifted in the glyph zone.
+        let zone = self.zp(2);
+        let end = if zone == TWILIGHT {
+            self.point_count(zone)
+        } else {
+            self.zones[zone]
+                .contours
+                .last()
+                .map(|end| (*end as usize + 1).min(self.point_count(zone)))
+                .unwrap_or(0)
+        };
+        for point in 0..end {
+            if ref_zone != zone || ref_point != point {
+                self.move_zp2_point(point, dx, dy, false);
+            }
+        }
+    }
+
+    /// Handles the `SHPIX` instruction.
+    pub(super) fn op_shpix(&mut self) {
+        let distance = self.pop();
+        if !self.has_loop_points() {
+            return;
+        }
+        let free = self.gs.freedom_vector;
+        let dx = math::mul14(distance, free.x);
+        let dy = math::mul14(distance, free.y);
+        let in_twilight = self.gs.zp == [TWILIGHT; 3];
+        let zone = self.zp(2);
+        for point in self.pop_loop_points() {
+            if point >= self.point_count(zone) {
+                continue;
+            }
+            if self.backward_compat {
+                // Only allow movement in the twilight zone, or in the y
+                // direction before IUP, matching the behavior of the delta
+                // instructions.
+                let flags = self.zones[zone].flags[point];
+                if in_twilight
+                    || (!self.is_post_iup()
+                        && ((self.is_composite && free.y != 0)
+                            || flags.has_marker(PointMarker::TOUCHED_Y)))
+                {
+                    self.move_zp2_point(point, 0, dy, true);
+                }
+            } else {
+                self.move_zp2_point(point, dx, dy, true);
+            }
+        }
+    }
+
+    /// Handles the `IP` instruction.
+    pub(super) fn op_ip(&mut self) {
+        if !self.has_loop_points() {
+            return;
+        }
+        let (zp0, zp1, zp2) = (self.zp(0), self.zp(1), self.zp(2));
+        let (rp1, rp2) = (self.gs.rp1, self.gs.rp2);
+        let in_twilight = zp0 == TWILIGHT || zp1 == TWILIGHT || zp2 == TWILIGHT;
+        // Original positions are measured on the unscaled points unless in
+        // the twilight zone.
+        let original = |engine: &Self, zone, point| {
+            if in_twilight {
+                engine.org(zone, point)
+            } else {
+                engine.orus(zone, point)
+            }
+        };
+        // Some fonts call IP with invalid reference points, so do
+        // something sane in that case.
+        let is_valid = rp1 < self.point_count(zp0) && rp2 < self.point_count(zp1);
+        let (original_base, current_base, original_range, current_range) = if is_valid {
+            let original_base = original(self, zp0, rp1);
+            let current_base = self.cur(zp0, rp1);
+            (
+                original_base,
+                current_base,
+                self.dual_project(original(self, zp1, rp2), original_base),
+                self.project(self.cur(zp1, rp2), current_base),
+            )
+        } else {
+            Default::default()
+        };
+        for point in self.pop_loop_points() {
+            if point >= self.point_count(zp2) {
+                continue;
+            }
+            let original_distance = self.dual_project(original(self, zp2, point), original_base);
+            let current_distance = self.project(self.cur(zp2, point), current_base);
+            let new_distance = if original_distance == 0 {
+                0
+            } else if original_range != 0 {
+                math::mul_div(original_distance, current_range, original_range)
+            } else {
+                original_distance
+            };
+            self.move_point(zp2, point, new_distance.wrapping_sub(current_distance));
+        }
+    }
+
+    /// Handles the `IUP` instruction.
+    pub(super) fn op_iup(&mut self) {
+        let is_x = self.opcode & 1 != 0;
+        // In backward compatibility mode, IUP is only allowed once for each
+        // axis.
+        if self.backward_compat {
+            if self.is_post_iup() {
+                return;
+            }
+            self.did_iup[if is_x { 0 } else { 1 }] = true;
+        }
+        let zone = &mut self.zones[GLYPH];
+        if zone.contours.is_empty() {
+            return;
+        }
+        let marker = if is_x {
+            PointMarker::TOUCHED_X
+        } else {
+            PointMarker::TOUCHED_Y
+        };
+        let point_count = zone.points.len();
+        let mut iup = Iup {
+            unscaled: zone.unscaled,
+            original: zone.original,
+            points: zone.points,
+            is_x,
+        };
+        let mut point = 0;
+        for end_point in zone.contours {
+            let first_point = point;
+            let end_point = (*end_point as usize).min(point_count.saturating_sub(1));
+            while point <= end_point && !zone.flags[point].has_marker(marker) {
+                point += 1;
+            }
+            if point <= end_point {
+                let first_touched = point;
+                let mut cur_touched = point;
+                point += 1;
+                while point <= end_point {
+                    if zone.flags[point].has_marker(marker) {
+                        iup.interpolate(cur_touched + 1, point - 1, cur_touched, point);
+                        cur_touched = point;
+                    }
+                    point += 1;
+                }
+                if cur_touched == first_touched {
+                    iup.shift(first_point, end_point, cur_touched);
+                } else {
+                    iup.interpolate(cur_touched + 1, end_point, cur_touched, first_touched);
+                    if first_touched > 0 {
+                        iup.interpolate(first_point, first_touched - 1, cur_touched, first_touched);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handles the `UTP` instruction.
+    pub(super) fn op_utp(&mut self) {
+        let point = self.pop_point();
+        if !self.is_valid_point(0, point) {
+            return;
+        }
+        let free = self.gs.freedom_vector;
+        let zone = self.zp(0);
+        let flags = &mut self.zones[zone].flags[point];
+        if free.x != 0 {
+            flags.clear_marker(PointMarker::TOUCHED_X);
+        }
+        if free.y != 0 {
+            flags.clear_marker(PointMarker::TOUCHED_Y);
+        }
+    }
+
+    /// Handles the `FLIPPT` instruction.
+    pub(super) fn op_flippt(&mut self) {
+        if self.backward_compat && self.is_post_iup() {
+            self.gs.loop_counter = 1;
+            return;
+        }
+        if !self.has_loop_points() {
+            return;
+        }
+        for point in self.pop_loop_points() {
+            if let Some(flags) = self.zones[GLYPH].flags.get_mut(point) {
+                flags.flip_on_curve();
+            }
+        }
+    }
+
+    /// Handles the `FLIPRGON` and `FLIPRGOFF` instructions.
+    pub(super) fn op_fliprg(&mut self, on_curve: bool) {
+        let high = self.pop_point();
+        let low = self.pop_point();
+        if self.backward_compat && self.is_post_iup() {
+            return;
+        }
+        let flags = &mut self.zones[GLYPH].flags;
+        if high >= flags.len() || low >= flags.len() {
+            return;
+        }
+        for flags in flags.iter_mut().take(high + 1).skip(low) {
+            if on_curve {
+                flags.set_on_curve();
+            } else {
+                flags.clear_on_curve();
+            }
+        }
+    }
+
+    /// Handles the `DELTAP1`, `DELTAP2` and `DELTAP3` instructions.
+    pub(super) fn op_deltap(&mut self, ppem_offset: i32) {
+        let count = self.pop() as u32;
+        let zone = self.zp(0);
+        for _ in 0..count {
+            if self.sp < 2 {
+                self.sp = 0;
+                break;
+            }
+            let point = self.pop_point();
+            let arg = self.pop();
+            if point >= self.point_count(zone) {
+                // Some fonts contain invalid points, which are ignored.
+                continue;
+            }
+            let Some(delta) = self.delta_value(arg, ppem_offset) else {
+                continue;
+            };
+            if self.backward_compat {
+                let flags = self.zones[zone].flags[point];
+                if !self.is_post_iup()
+                    && ((self.is_composite && self.gs.freedom_vector.y != 0)
+                        || flags.has_marker(PointMarker::TOUCHED_Y))
+                {
+                    self.move_point(zone, point, delta);
+                }
+            } else {
+                self.move_point(zone, point, delta);
+            }
+        }
+    }
+
+    /// Handles the `DELTAC1`, `DELTAC2` and `DELTAC3` instructions.
+    pub(super) fn op_deltac(&mut self, ppem_offset: i32) {
+        let count = self.pop() as u32;
+        let sp = self.sp;
+        for _ in 0..count {
+            if self.sp < 2 {
+                self.sp = 0;
+                break;
+            }
+            let index = self.pop() as usize;
+            let arg = self.pop();
+            if index >= self.cvt.len() {
+                // As in FreeType, an invalid index leaves the remaining
+                // arguments on the stack.
+                self.sp = sp;
+                return;
+            }
+            if let Some(delta) = self.delta_value(arg, ppem_offset) {
+                self.cvt[index] = self.cvt[index].wrapping_add(delta);
+            }
+        }
+    }
+
+    /// Returns the delta for an argument of a delta instruction if it
+    /// applies to the current size.
+    fn delta_value(&self, arg: i32, ppem_offset: i32) -> Option<i32> {
+        let ppem = ((arg & 0xF0) >> 4) + ppem_offset + self.gs.delta_base as i32;
+        if ppem != self.config.ppem {
+            return None;
+        }
+        let mut steps = (arg & 0xF) - 8;
+        if steps >= 0 {
+            steps += 1;
+        }
+        Some(steps * (1 << (6 - self.gs.delta_shift)))
+    }
+}
+
+/// State for interpolating untouched points along one axis.
+struct Iup<'b> {
+    unscaled: &'b [Point<i32>],
+    original: &'b [Point<i32>],
+    points: &'b mut [Point<i32>],
+    is_x: bool,
+}
+
+impl<'b> Iup<'b> {
+    fn coord(&self, p: Point<i32>) -> i32 {
+        if self.is_x {
+            p.x
+        } else {
+            p.y
+        }
+    }
+
+    fn set(&mut self, point: usize, value: i32) {
+        let p = &mut self.points[point];
+        if self.is_x {
+            p.x = value;
+        } else {
+            p.y = value;
+        }
+    }
+
+    fn unscaled(&self, point: usize) -> i32 {
+        self.coord(self.unscaled.get(point).copied().unwrap_or_default())
+    }
+
+    /// Shifts all points in the range by the movement of the touched point.
+    fn shift(&mut self, p1: usize, p2: usize, p: usize) {
+        let delta = self
+            .coord(self.points[p])
+            .wrapping_sub(self.coord(self.original[p]));
+        if delta == 0 {
+            return;
+        }
+        for point in (p1..p).chain(p + 1..=p2) {
+            let value = self.coord(self.points[point]).wrapping_add(delta);
+            self.set(point, value);
+        }
+    }
+
+    /// Interpolates the points in the range between two touched points.
+    fn interpolate(&mut self, p1: usize, p2: usize, ref1: usize, ref2: usize) {
+        if p1 > p2 || ref1 >= self.points.len() || ref2 >= self.points.len() {
+            return;
+        }
+        let (mut ref1, mut ref2) = (ref1, ref2);
+        if self.unscaled(ref1) > self.unscaled(ref2) {
+            core::mem::swap(&mut ref1, &mut ref2);
+        }
+        let (orus1, orus2) = (self.unscaled(ref1), self.unscaled(ref2));
+        let (org1, org2) = (
+            self.coord(self.original[ref1]),
+            self.coord(self.original[ref2]),
+        );
+        let (cur1, cur2) = (self.coord(self.points[ref1]), self.coord(self.points[ref2]));
+        let delta1 = cur1.wrapping_sub(org1);
+        let delta2 = cur2.wrapping_sub(org2);
+        let is_trivial = cur1 == cur2 || orus1 == orus2;
+        let mut scale = None;
+        for point in p1..=p2 {
+            let original = self.coord(self.original[point]);
+            let value = if original <= org1 {
+                original.wrapping_add(delta1)
+            } else if original >= org2 {
+                original.wrapping_add(delta2)
+            } else if is_trivial {
+                cur1
+            } else {
+                let scale = *scale.get_or_insert_with(|| {
+                    math::div_fix(cur2.wrapping_sub(cur1), orus2.wrapping_sub(orus1))
+                });
+                cur1.wrapping_add(math::mul_fix(
+                    self.unscaled(point).wrapping_sub(orus1),
+                    scale,
+                ))
+            };
+            self.set(point, value);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use super::{Config, Definition, Engine, HintError, Program, Zone};
+use crate::Hinting;
+
+use read_fonts::{tables::glyf::PointFlags, types::Point};
+
+/// Storage for running small programs.
+struct Harness {
+    fpgm: Vec<u8>,
+    functions: Vec<Definition>,
+    instructions: Vec<Definition>,
+    cvt: Vec<i32>,
+    storage: Vec<i32>,
+    stack: Vec<i32>,
+    unscaled: Vec<Point<i32>>,
+    original: Vec<Point<i32>>,
+    points: Vec<Point<i32>>,
+    flags: Vec<PointFlags>,
+    contours: Vec<u16>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        Self {
+            fpgm: vec![],
+            functions: vec![Default::default(); 8],
+            instructions: vec![Default::default(); 8],
+            cvt: vec![0; 8],
+            storage: vec![0; 8],
+            stack: vec![0; 32],
+            unscaled: vec![],
+            original: vec![],
+            points: vec![],
+            flags: vec![],
+            contours: vec![],
+        }
+    }
+
+    /// Sets up a glyph zone with the given contour of unscaled points and
+    /// a scale of 1.
+    fn with_glyph(mut self, points: &[(i32, i32)]) -> Self {
+        self.unscaled = points.iter().map(|(x, y)| Point::new(*x, *y)).collect();
+        self.original = self.unscaled.iter().map(|p| *p * 64).collect();
+        self.points = self.original.clone();
+        self.flags = vec![PointFlags::on_curve(); points.len()];
+        self.contours = vec![points.len() as u16 - 1];
+        self
+    }
+
+    /// Runs the given code as the font program.
+    fn run_fpgm(&mut self, code: &[u8]) -> Result<(), HintError> {
+        self.fpgm = code.to_vec();
+        let config = Config {
+            mode: Hinting::Full,
+            ppem: 0,
+            point_size: 0,
+            scale: 0,
+            coords: &[],
+            axis_count: 0,
+        };
+        let mut engine = Engine::new(
+            [&self.fpgm, &[], &[]],
+            &mut self.functions,
+            &mut self.instructions,
+            &mut self.cvt,
+            &mut self.storage,
+            &mut self.stack,
+            [Zone::empty(), Zone::empty()],
+            config,
+        );
+        engine.run(Program::Font, false)
+    }
+
+    /// Runs the given code as a glyph program and returns the resulting
+    /// stack.
+    fn run(&mut self, mode: Hinting, code: &[u8]) -> Result<Vec<i32>, HintError> {
+        let glyph = Zone {
+            unscaled: &self.unscaled,
+            original: &mut self.original,
+            points: &mut self.points,
+            flags: &mut self.flags,
+            contours: &self.contours,
+        };
+        let config = Config {
+            mode,
+            ppem: 16,
+            point_size: 16 * 64,
+            scale: 0x10000 * 64,
+            coords: &[],
+            axis_count: 0,
+        };
+        let mut engine = Engine::new(
+            [&self.fpgm, &[], code],
+            &mut self.functions,
+            &mut self.instructions,
+            &mut self.cvt,
+            &mut self.storage,
+            &mut self.stack,
+            [Zone::empty(), glyph],
+            config,
+        );
+        engine.run(Program::Glyph, false)?;
+        Ok(engine.stack[..engine.sp].to_vec())
+    }
+}
+
+fn run(code: &[u8]) -> Result<Vec<i32>, HintError> {
+    Harness::new().run(Hinting::Full, code)
+}
+
+#[test]
+fn arithmetic() {
+    // PUSHB[1] 10 3 ADD, PUSHB[0] 128 MUL
+    assert_eq!(run(&[0xB1, 10, 3, 0x60, 0xB0, 128, 0x63]).unwrap(), [26]);
+    // PUSHW[1] -128 ABS, PUSHB[0] 64 DIV
+    assert_eq!(
+        run(&[0xB8, 0xFF, 0x80, 0x64, 0xB0, 64, 0x62]).unwrap(),
+        [128]
+    );
+    // PUSHB[1] 1 0 DIV
+    assert_eq!(run(&[0xB1, 1, 0, 0x62]), Err(HintError::DivideByZero));
+    // PUSHB[1] 100 40 LT, PUSHB[1] 100 40 MAX
+    assert_eq!(
+        run(&[0xB1, 100, 40, 0x50, 0xB1, 100, 40, 0x8B]).unwrap(),
+        [0, 100]
+    );
+}
+
+#[test]
+fn stack_management() {
+    // PUSHB[2] 1 2 3 SWAP DUP
+    assert_eq!(run(&[0xB2, 1, 2, 3, 0x23, 0x20]).unwrap(), [1, 3, 2, 2]);
+    // PUSHB[3] 1 2 3 3 CINDEX, then MINDEX with 3
+    assert_eq!(run(&[0xB3, 1, 2, 3, 3, 0x25]).unwrap(), [1, 2, 3, 1]);
+    assert_eq!(run(&[0xB3, 1, 2, 3, 3, 0x26]).unwrap(), [2, 3, 1]);
+    // PUSHB[2] 1 2 3 ROLL DEPTH
+    assert_eq!(run(&[0xB2, 1, 2, 3, 0x8A, 0x24]).unwrap(), [2, 3, 1, 3]);
+    // NPUSHB 40 values overflows the stack
+    let mut code = vec![0x40, 40];
+    code.extend(0..40);
+    assert_eq!(run(&code), Err(HintError::StackOverflow));
+}
+
+#[test]
+fn control_flow() {
+    // PUSHB[0] 1 IF PUSHB[0] 10 ELSE PUSHB[0] 20 EIF
+    let code = [0xB0, 1, 0x58, 0xB0, 10, 0x1B, 0xB0, 20, 0x59];
+    assert_eq!(run(&code).unwrap(), [10]);
+    let code = [0xB0, 0, 0x58, 0xB0, 10, 0x1B, 0xB0, 20, 0x59];
+    assert_eq!(run(&code).unwrap(), [20]);
+    // Nested conditions in the skipped branch.
+    let code = [
+        0xB0, 0, 0x58, 0xB0, 1, 0x58, 0xB0, 10, 0x59, 0x1B, 0xB0, 20, 0x59,
+    ];
+    assert_eq!(run(&code).unwrap(), [20]);
+    // PUSHB[1] 7 3 JMPR skips the next PUSHB[0]
+    let code = [0xB1, 7, 3, 0x1C, 0xB0, 1, 0xB0, 2];
+    assert_eq!(run(&code).unwrap(), [7, 2]);
+}
+
+#[test]
+fn functions() {
+    // Functions must be defined outside of glyph programs.
+    assert_eq!(
+        run(&[0xB0, 0, 0x2C, 0x2D]),
+        Err(HintError::DefinitionInGlyphProgram)
+    );
+    let mut harness = Harness::new();
+    // Function 1 doubles the top of the stack.
+    harness
+        .run_fpgm(&[0xB0, 1, 0x2C, 0x20, 0x60, 0x2D])
+        .unwrap();
+    // PUSHB[1] 3 1 CALL
+    assert_eq!(
+        harness.run(Hinting::Full, &[0xB1, 3, 1, 0x2B]).unwrap(),
+        [6]
+    );
+    // PUSHB[2] 3 4 1 LOOPCALL
+    assert_eq!(
+        harness.run(Hinting::Full, &[0xB2, 3, 4, 1, 0x2A]).unwrap(),
+        [48]
+    );
+    // Undefined function
+    assert_eq!(
+        harness.run(Hinting::Full, &[0xB1, 3, 2, 0x2B]),
+        Err(HintError::InvalidFunction)
+    );
+}
+
+#[test]
+fn storage_and_cvt() {
+    // PUSHB[1] 2 42 WS, PUSHB[0] 2 RS
+    assert_eq!(run(&[0xB1, 2, 42, 0x42, 0xB0, 2, 0x43]).unwrap(), [42]);
+    // PUSHB[1] 1 2 WCVTF writes a scaled value, PUSHB[0] 1 RCVT
+    assert_eq!(run(&[0xB1, 1, 2, 0x70, 0xB0, 1, 0x45]).unwrap(), [128]);
+}
+
+#[test]
+fn move_and_interpolate() {
+    let mut harness = Harness::new().with_glyph(&[(0, 0), (10, 0), (20, 0), (20, 10)]);
+    // SVTCA[x], PUSHB[1] 2 32 SCFS moves point 2 to x = 0.5 pixels
+    // PUSHB[0] 0 MDAP[r] rounds point 0 in place
+    // IUP[x] interpolates point 1 between the touched points
+    let code = [0x01, 0xB1, 2, 32, 0x48, 0xB0, 0, 0x2F, 0x31];
+    harness.run(Hinting::Full, &code).unwrap();
+    // Point 3 is outside the range of the touched points, so it shifts
+    // with point 2.
+    let xs = harness.points.iter().map(|p| p.x).collect::<Vec<_>>();
+    assert_eq!(xs, [0, 16, 32, 32]);
+}
+
+#[test]
+fn backward_compatibility() {
+    let mut harness = Harness::new().with_glyph(&[(0, 0), (10, 10)]);
+    // SVTCA[x], PUSHB[1] 1 64 SHPIX, SVTCA[y], PUSHB[1] 1 64 SHPIX
+    let code = [0x01, 0xB1, 1, 64, 0x38, 0x00, 0xB1, 1, 64, 0x38];
+    harness.run(Hinting::Full, &code).unwrap();
+    assert_eq!(harness.points[1], Point::new(704, 704));
+    // In backward compatibility mode, x movement is disallowed and y
+    // movement requires the point to be touched in y.
+    let mut harness = Harness::new().with_glyph(&[(0, 0), (10, 10)]);
+    harness.run(Hinting::VerticalSubpixel, &code).unwrap();
+    assert_eq!(harness.points[1], Point::new(640, 640));
+    // MDAP[nr] touches point 1 in y before the shift.
+    let code = [0x00, 0xB0, 1, 0x2E, 0xB1, 1, 64, 0x38];
+    let mut harness = Harness::new().with_glyph(&[(0, 0), (10, 10)]);
+    harness.run(Hinting::VerticalSubpixel, &code).unwrap();
+    assert_eq!(harness.points[1], Point::new(640, 704));
+}
+
+#[test]
+fn instruction_limits() {
+    // An infinite loop: PUSHW[0] -3 JMPR
+    let code = [0xB8, 0xFF, 0xFD, 0x1C];
+    assert_eq!(run(&code), Err(HintError::ExecutionLimitExceeded));
+    // A truncated push
+    assert_eq!(run(&[0xB1, 1]), Err(HintError::UnexpectedEndOfBytecode));
+    // An undefined opcode
+    assert_eq!(run(&[0x28]), Err(HintError::InvalidOpcode(0x28)));
+}
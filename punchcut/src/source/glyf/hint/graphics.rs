@@ -0,0 +1,271 @@
+//! Graphics state and rounding.
+
+use super::math;
+
+use read_fonts::types::F26Dot6;
+
+/// Unit vector in 2.14 format.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct UnitVector {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl UnitVector {
+    /// Vector along the x axis.
+    pub const X_AXIS: Self = Self { x: 0x4000, y: 0 };
+    /// Vector along the y axis.
+    pub const Y_AXIS: Self = Self { x: 0, y: 0x4000 };
+}
+
+/// Modes for rounding distances.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub enum RoundMode {
+    ToHalfGrid,
+    #[default]
+    ToGrid,
+    ToDoubleGrid,
+    DownToGrid,
+    UpToGrid,
+    Off,
+    Super,
+    Super45,
+}
+
+/// State for rounding distances.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct RoundState {
+    pub mode: RoundMode,
+    /// Parameters for super rounding.
+    pub period: i32,
+    pub phase: i32,
+    pub threshold: i32,
+}
+
+impl Default for RoundState {
+    fn default() -> Self {
+        Self {
+            mode: RoundMode::ToGrid,
+            period: 64,
+            phase: 0,
+            threshold: 0,
+        }
+    }
+}
+
+impl RoundState {
+    /// Rounds the given distance using the current mode.
+    pub fn round(&self, distance: i32) -> i32 {
+        use RoundMode::*;
+        let value = F26Dot6::from_bits(distance);
+        let [period, phase, threshold] =
+            [self.period, self.phase, self.threshold].map(F26Dot6::from_bits);
+        let rounded = match self.mode {
+            Off => value,
+            ToGrid => value.round_to_grid(),
+            ToHalfGrid => value.round_to_half_grid(),
+            ToDoubleGrid => value.round_to_double_grid(),
+            DownToGrid => value.round_down_to_grid(),
+            UpToGrid => value.round_up_to_grid(),
+            Super => value.super_round(period, phase, threshold),
+            Super45 => value.super_round_45(period, phase, threshold),
+        };
+        rounded.to_bits()
+    }
+
+    /// Sets the parameters for super rounding from the packed selector,
+    /// using the given grid period in 2.14 format.
+    pub fn set_super(&mut self, grid_period: i32, selector: i32) {
+        let period = match selector & 0xC0 {
+            0 => grid_period / 2,
+            0x80 => grid_period * 2,
+            // 0xC0 is reserved, but treated like 0x40
+            _ => grid_period,
+        };
+        let phase = match selector & 0x30 {
+            0 => 0,
+            0x10 => period / 4,
+            0x20 => period / 2,
+            _ => period * 3 / 4,
+        };
+        let threshold = if selector & 0x0F == 0 {
+            period - 1
+        } else {
+            ((selector & 0x0F) - 4) * period / 8
+        };
+        // Convert to 26.6.
+        self.period = period >> 8;
+        self.phase = phase >> 8;
+        self.threshold = threshold >> 8;
+    }
+}
+
+/// Zone identifier: either the twilight zone or the glyph zone.
+pub type ZonePointer = usize;
+
+/// Index of the twilight zone.
+pub const TWILIGHT: ZonePointer = 0;
+/// Index of the glyph zone.
+pub const GLYPH: ZonePointer = 1;
+
+/// Graphics state for the TrueType interpreter.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_graphics_state>
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct GraphicsState {
+    pub auto_flip: bool,
+    pub control_value_cutin: i32,
+    pub delta_base: u16,
+    pub delta_shift: u16,
+    pub dual_proj_vector: UnitVector,
+    pub proj_vector: UnitVector,
+    pub freedom_vector: UnitVector,
+    /// Cached dot product of the projection and freedom vectors.
+    pub fdotp: i32,
+    pub instruct_control: u8,
+    pub loop_counter: u32,
+    pub min_distance: i32,
+    pub round_state: RoundState,
+    pub rp0: usize,
+    pub rp1: usize,
+    pub rp2: usize,
+    pub scan_control: bool,
+    pub scan_type: i32,
+    pub single_width_cutin: i32,
+    pub single_width: i32,
+    /// Zone pointers, indexed by `gep0`, `gep1` and `gep2`.
+    pub zp: [ZonePointer; 3],
+}
+
+impl Default for GraphicsState {
+    fn default() -> Self {
+        Self {
+            auto_flip: true,
+            control_value_cutin: 68,
+            delta_base: 9,
+            delta_shift: 3,
+            dual_proj_vector: UnitVector::X_AXIS,
+            proj_vector: UnitVector::X_AXIS,
+            freedom_vector: UnitVector::X_AXIS,
+            fdotp: 0x4000,
+            instruct_control: 0,
+            loop_counter: 1,
+            min_distance: 64,
+            round_state: RoundState::default(),
+            rp0: 0,
+            rp1: 0,
+            rp2: 0,
+            scan_control: false,
+            scan_type: 0,
+            single_width_cutin: 0,
+            single_width: 0,
+            zp: [GLYPH; 3],
+        }
+    }
+}
+
+impl GraphicsState {
+    /// Resets the state that is not preserved between the execution of
+    /// programs.
+    pub fn reset_for_program(&mut self) {
+        self.zp = [GLYPH; 3];
+        self.proj_vector = UnitVector::X_AXIS;
+        self.dual_proj_vector = UnitVector::X_AXIS;
+        self.freedom_vector = UnitVector::X_AXIS;
+        self.round_state.mode = RoundMode::ToGrid;
+        self.loop_counter = 1;
+        self.update_projection_state();
+    }
+
+    /// Resets the state that may not be modified by the control value
+    /// program.
+    pub fn reset_after_prep(&mut self) {
+        self.proj_vector = UnitVector::X_AXIS;
+        self.dual_proj_vector = UnitVector::X_AXIS;
+        self.freedom_vector = UnitVector::X_AXIS;
+        self.rp0 = 0;
+        self.rp1 = 0;
+        self.rp2 = 0;
+        self.zp = [GLYPH; 3];
+        self.loop_counter = 1;
+        self.update_projection_state();
+    }
+
+    /// Updates the cached dot product after a vector changes.
+    pub fn update_projection_state(&mut self) {
+        let (proj, free) = (self.proj_vector, self.freedom_vector);
+        self.fdotp = if free.x == 0x4000 {
+            proj.x
+        } else if free.y == 0x4000 {
+            proj.y
+        } else {
+            ((proj.x as i64 * free.x as i64 + proj.y as i64 * free.y as i64) >> 14) as i32
+        };
+        // At small sizes, the dot product can become too small, resulting
+        // in overflows and spikes.
+        if self.fdotp.abs() < 0x400 {
+            self.fdotp = 0x4000;
+        }
+    }
+
+    /// Projects a vector onto the projection vector.
+    pub fn project(&self, dx: i32, dy: i32) -> i32 {
+        math::dot14(dx, dy, self.proj_vector.x, self.proj_vector.y)
+    }
+
+    /// Projects a vector onto the dual projection vector.
+    pub fn dual_project(&self, dx: i32, dy: i32) -> i32 {
+        math::dot14(dx, dy, self.dual_proj_vector.x, self.dual_proj_vector.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RoundMode, RoundState};
+
+    fn round(mode: RoundMode, values: &[i32]) -> Vec<i32> {
+        let state = RoundState {
+            mode,
+            ..Default::default()
+        };
+        values.iter().map(|value| state.round(*value)).collect()
+    }
+
+    #[test]
+    fn round_modes() {
+        let values = [0, 20, 32, 100, -20, -100];
+        assert_eq!(round(RoundMode::ToGrid, &values), [0, 0, 64, 128, 0, -128]);
+        assert_eq!(
+            round(RoundMode::ToHalfGrid, &values),
+            [32, 32, 32, 96, -32, -96]
+        );
+        assert_eq!(
+            round(RoundMode::ToDoubleGrid, &values),
+            [0, 32, 32, 96, -32, -96]
+        );
+        assert_eq!(round(RoundMode::DownToGrid, &values), [0, 0, 0, 64, 0, -64]);
+        assert_eq!(
+            round(RoundMode::UpToGrid, &values),
+            [0, 64, 64, 128, -64, -128]
+        );
+        assert_eq!(round(RoundMode::Off, &values), values);
+    }
+
+    #[test]
+    fn super_round() {
+        let mut state = RoundState {
+            mode: RoundMode::Super,
+            ..Default::default()
+        };
+        // period 1, phase 0, threshold 1/2: same as round to grid
+        state.set_super(0x4000, 0x48);
+        assert_eq!((state.period, state.phase, state.threshold), (64, 0, 32));
+        assert_eq!(state.round(20), 0);
+        assert_eq!(state.round(40), 64);
+        assert_eq!(state.round(-40), -64);
+        // period 1, phase 1/2, threshold 1/2: same as round to half grid
+        state.set_super(0x4000, 0x68);
+        assert_eq!(state.round(20), 32);
+        assert_eq!(state.round(100), 96);
+    }
+}
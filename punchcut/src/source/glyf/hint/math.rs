@@ -0,0 +1,165 @@
+//! Fixed point arithmetic matching the FreeType implementation.
+
+/// Multiplies two 16.16 values with rounding.
+///
+/// This is `FT_MulFix`.
+pub fn mul_fix(a: i32, b: i32) -> i32 {
+    let ab = a as i64 * b as i64;
+    ((ab + 0x8000 - i64::from(ab < 0)) >> 16) as i32
+}
+
+/// Divides two 16.16 values with rounding.
+///
+/// This is `FT_DivFix`.
+pub fn div_fix(a: i32, b: i32) -> i32 {
+    let mut sign = 1;
+    let mut a = a as i64;
+    let mut b = b as i64;
+    if a < 0 {
+        a = -a;
+        sign = -1;
+    }
+    if b < 0 {
+        b = -b;
+        sign = -sign;
+    }
+    let q = if b == 0 {
+        0x7FFFFFFF
+    } else {
+        (((a << 16) + (b >> 1)) / b) as i32
+    };
+    if sign < 0 {
+        q.wrapping_neg()
+    } else {
+        q
+    }
+}
+
+/// Computes `a * b / c` with rounding.
+///
+/// This is `FT_MulDiv`.
+pub fn mul_div(a: i32, b: i32, c: i32) -> i32 {
+    let (sign, a, b, c) = unsign(a, b, c);
+    let d = (a * b + (c >> 1)).checked_div(c).unwrap_or(0x7FFFFFFF);
+    sign_result(sign, d)
+}
+
+/// Computes `a * b / c` without rounding.
+///
+/// This is `FT_MulDiv_No_Round`.
+pub fn mul_div_no_round(a: i32, b: i32, c: i32) -> i32 {
+    let (sign, a, b, c) = unsign(a, b, c);
+    let d = (a * b).checked_div(c).unwrap_or(0x7FFFFFFF);
+    sign_result(sign, d)
+}
+
+fn unsign(a: i32, b: i32, c: i32) -> (i32, u64, u64, u64) {
+    let mut sign = 1;
+    let mut abs = |x: i32| {
+        if x < 0 {
+            sign = -sign;
+        }
+        x.unsigned_abs() as u64
+    };
+    let (a, b, c) = (abs(a), abs(b), abs(c));
+    (sign, a, b, c)
+}
+
+fn sign_result(sign: i32, d: u64) -> i32 {
+    let d = d as i32;
+    if sign < 0 {
+        d.wrapping_neg()
+    } else {
+        d
+    }
+}
+
+/// Multiplies a value by a 2.14 value with rounding.
+///
+/// This is `TT_MulFix14`.
+pub fn mul14(a: i32, b: i32) -> i32 {
+    let ab = a as i64 * b as i64;
+    ((ab + 0x2000 + (ab >> 63)) >> 14) as i32
+}
+
+/// Computes the dot product of a vector with a 2.14 unit vector.
+///
+/// This is `TT_DotFix14`.
+pub fn dot14(ax: i32, ay: i32, bx: i32, by: i32) -> i32 {
+    let d = ax as i64 * bx as i64 + ay as i64 * by as i64;
+    ((d + 0x2000 + (d >> 63)) >> 14) as i32
+}
+
+/// Normalizes the given vector to a 2.14 unit vector.
+///
+/// Returns `None` for a zero length vector. This follows the algorithm
+/// in `FT_Vector_NormLen`, and produces the same results.
+pub fn normalize14(x: i32, y: i32) -> Option<(i32, i32)> {
+    let (sx, mut ux) = (if x < 0 { -1 } else { 1 }, x.unsigned_abs());
+    let (sy, mut uy) = (if y < 0 { -1 } else { 1 }, y.unsigned_abs());
+    if ux == 0 {
+        return (uy != 0).then_some((0, sy * 0x4000));
+    }
+    if uy == 0 {
+        return Some((sx * 0x4000, 0));
+    }
+    let approx_len = |x: u32, y: u32| if x > y { x + (y >> 1) } else { y + (x >> 1) };
+    let len = approx_len(ux, uy);
+    // Prenormalize so that the approximate length is between 2/3 and 4/3.
+    let mut shift = len.leading_zeros() as i32;
+    shift -= 15 + (len >= (0xAAAAAAAAu32 >> shift)) as i32;
+    let len = if shift > 0 {
+        ux <<= shift;
+        uy <<= shift;
+        approx_len(ux, uy)
+    } else {
+        ux >>= -shift;
+        uy >>= -shift;
+        len >> -shift
+    };
+    // Lower linear approximation for the reciprocal length minus one.
+    let mut b = 0x10000 - len as i32;
+    let (x, y) = (ux as i32, uy as i32);
+    let (mut u, mut v);
+    // Newton's iterations.
+    loop {
+        u = (x + (x.wrapping_mul(b) >> 16)) as u32;
+        v = (y + (y.wrapping_mul(b) >> 16)) as u32;
+        let mut z =
+            (u.wrapping_mul(u).wrapping_add(v.wrapping_mul(v)) as i32).wrapping_neg() / 0x200;
+        z = z * ((0x10000 + b) >> 8) / 0x10000;
+        b += z;
+        if z <= 0 {
+            break;
+        }
+    }
+    Some((sx * (u as i32) / 4, sy * (v as i32) / 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_math() {
+        assert_eq!(mul_fix(0x18000, 0x20000), 0x30000);
+        assert_eq!(mul_fix(-0x18000, 0x20000), -0x30000);
+        assert_eq!(div_fix(0x30000, 0x20000), 0x18000);
+        assert_eq!(mul_div(100, 3, 2), 150);
+        assert_eq!(mul_div(-5, 1, 2), -3);
+        assert_eq!(mul_div_no_round(-5, 1, 2), -2);
+        assert_eq!(mul14(64, 0x2000), 32);
+        assert_eq!(dot14(100, 50, 0x4000, 0), 100);
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(normalize14(0, 0), None);
+        assert_eq!(normalize14(10, 0), Some((0x4000, 0)));
+        assert_eq!(normalize14(0, -3), Some((0, -0x4000)));
+        let (x, y) = normalize14(100, 100).unwrap();
+        assert_eq!((x, y), (0x2D41, 0x2D41));
+        let (x, y) = normalize14(-300, 400).unwrap();
+        assert_eq!((x, y), (-0x2666, 0x3333));
+    }
+}
@@ -35,4 +35,273 @@ impl Outline {
     pub fn to_path(&self, sink: &mut impl Pen) -> Result<(), ToPathError> {
         read_fonts::tables::glyf::to_path(&self.points, &self.flags, &self.contours, sink)
     }
+
+    /// Emboldens the outline by the given strengths, which are the total
+    /// amounts added to the width and height of each stem.
+    ///
+    /// Each point is moved along the bisector of the angle at the point,
+    /// producing a curve parallel to the original. This follows
+    /// `FT_Outline_EmboldenXY`.
+    pub fn embolden(&mut self, x_strength: F26Dot6, y_strength: F26Dot6) {
+        let x_strength = x_strength.to_f64() / 2.0;
+        let y_strength = y_strength.to_f64() / 2.0;
+        if x_strength <= 0.0 && y_strength <= 0.0 {
+            return;
+        }
+        let mut area = 0.0;
+        let mut first = 0;
+        for &last in &self.contours {
+            let last = last as usize;
+            let Some(contour) = self.points.get(first..=last) else {
+                break;
+            };
+            for (i, p) in contour.iter().enumerate() {
+                let n = contour[(i + 1) % contour.len()];
+                area += p.x.to_f64() * n.y.to_f64() - n.x.to_f64() * p.y.to_f64();
+            }
+            first = last + 1;
+        }
+        if area == 0.0 {
+            return;
+        }
+        // TrueType orientation has clockwise outer contours.
+        let is_clockwise = area < 0.0;
+        let mut first = 0;
+        for &last in &self.contours {
+            let last = last as usize;
+            if last >= self.points.len() || first > last {
+                break;
+            }
+            let points = &mut self.points[first..=last];
+            let len = points.len();
+            let vector = |from: Point<F26Dot6>, to: Point<F26Dot6>| {
+                let (x, y) = ((to.x - from.x).to_f64(), (to.y - from.y).to_f64());
+                let l = x.hypot(y);
+                if l == 0.0 {
+                    ((0.0, 0.0), 0.0)
+                } else {
+                    ((x / l, y / l), l)
+                }
+            };
+            // The point j cycles through the contour while i only advances
+            // when points are moved. The anchor k marks the first moved
+            // point.
+            let (mut i, mut j, mut k) = (len - 1, 0, None);
+            let (mut v_in, mut l_in) = ((0.0, 0.0), 0.0);
+            let (mut anchor, mut l_anchor) = ((0.0, 0.0), 0.0);
+            while j != i && Some(i) != k {
+                let (v_out, l_out) = if Some(j) != k {
+                    let (v_out, l_out) = vector(points[i], points[j]);
+                    if l_out == 0.0 {
+                        j = (j + 1) % len;
+                        continue;
+                    }
+                    (v_out, l_out)
+                } else {
+                    (anchor, l_anchor)
+                };
+                if l_in != 0.0 {
+                    if k.is_none() {
+                        k = Some(i);
+                        anchor = v_in;
+                        l_anchor = l_in;
+                    }
+                    let d = v_in.0 * v_out.0 + v_in.1 * v_out.1;
+                    // Only shift if the turn is less than ~160 degrees.
+                    let (mut shift_x, mut shift_y) = (0.0, 0.0);
+                    if d > -0.9375 {
+                        let d = d + 1.0;
+                        // Shift along the lateral bisector in the proper
+                        // orientation.
+                        shift_x = v_in.1 + v_out.1;
+                        shift_y = v_in.0 + v_out.0;
+                        let mut q = v_out.0 * v_in.1 - v_out.1 * v_in.0;
+                        if is_clockwise {
+                            shift_x = -shift_x;
+                            q = -q;
+                        } else {
+                            shift_y = -shift_y;
+                        }
+                        // Restrict the shift to better handle collapsing
+                        // segments.
+                        let l = l_in.min(l_out);
+                        shift_x *= if x_strength * q <= l * d {
+                            x_strength / d
+                        } else {
+                            l / q
+                        };
+                        shift_y *= if y_strength * q <= l * d {
+                            y_strength / d
+                        } else {
+                            l / q
+                        };
+                    }
+                    while i != j {
+                        let point = &mut points[i];
+                        point.x += F26Dot6::from_f64(x_strength + shift_x);
+                        point.y += F26Dot6::from_f64(y_strength + shift_y);
+                        i = (i + 1) % len;
+                    }
+                } else {
+                    i = j;
+                }
+                v_in = v_out;
+                l_in = l_out;
+                j = (j + 1) % len;
+            }
+            first = last + 1;
+        }
+    }
+
+    /// Slants the outline horizontally by the given angle in degrees.
+    ///
+    /// Positive angles slant the outline to the right.
+    pub fn skew(&mut self, angle: f32) {
+        let factor = (angle as f64).to_radians().tan();
+        for point in &mut self.points {
+            point.x += F26Dot6::from_f64(point.y.to_f64() * factor);
+        }
+    }
+}
+
+/// Pen that captures a path as an outline with cubic control points.
+///
+/// Coordinates are converted to 26.6 fixed point.
+pub(crate) struct OutlinePen<'a> {
+    outline: &'a mut Outline,
+    contour_start: usize,
+}
+
+impl<'a> OutlinePen<'a> {
+    pub fn new(outline: &'a mut Outline) -> Self {
+        outline.clear();
+        Self {
+            outline,
+            contour_start: 0,
+        }
+    }
+
+    fn push(&mut self, x: f32, y: f32, flags: PointFlags) {
+        self.outline.points.push(Point::new(
+            F26Dot6::from_f64(x as f64),
+            F26Dot6::from_f64(y as f64),
+        ));
+        self.outline.flags.push(flags);
+    }
+
+    fn end_contour(&mut self) {
+        let outline = &mut self.outline;
+        let len = outline.points.len();
+        if len <= self.contour_start {
+            return;
+        }
+        // Drop the final point if it duplicates the first.
+        if len - self.contour_start > 1
+            && outline.points[len - 1] == outline.points[self.contour_start]
+            && outline.flags[len - 1].is_on_curve()
+        {
+            outline.points.pop();
+            outline.flags.pop();
+        }
+        outline.contours.push(outline.points.len() as u16 - 1);
+        self.contour_start = outline.points.len();
+    }
+}
+
+impl Pen for OutlinePen<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.end_contour();
+        self.push(x, y, PointFlags::on_curve());
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.push(x, y, PointFlags::on_curve());
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.push(cx0, cy0, PointFlags::off_curve_quadratic());
+        self.push(x, y, PointFlags::on_curve());
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.push(cx0, cy0, PointFlags::off_curve_cubic());
+        self.push(cx1, cy1, PointFlags::off_curve_cubic());
+        self.push(x, y, PointFlags::on_curve());
+    }
+
+    fn close(&mut self) {
+        self.end_contour();
+    }
+}
+
+impl Drop for OutlinePen<'_> {
+    fn drop(&mut self) {
+        self.end_contour();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(points: &[(i32, i32)]) -> Outline {
+        let mut outline = Outline::new();
+        for &(x, y) in points {
+            outline
+                .points
+                .push(Point::new(F26Dot6::from_bits(x), F26Dot6::from_bits(y)));
+            outline.flags.push(PointFlags::on_curve());
+        }
+        outline.contours.push(points.len() as u16 - 1);
+        outline
+    }
+
+    fn coords(outline: &Outline) -> Vec<(i32, i32)> {
+        outline
+            .points
+            .iter()
+            .map(|point| (point.x.to_bits(), point.y.to_bits()))
+            .collect()
+    }
+
+    #[test]
+    fn embolden() {
+        let strength = F26Dot6::from_bits(64);
+        // Clockwise, as in TrueType.
+        let mut outline = square(&[(0, 0), (0, 640), (640, 640), (640, 0)]);
+        outline.embolden(strength, strength);
+        assert_eq!(coords(&outline), [(0, 0), (0, 704), (704, 704), (704, 0)]);
+        // Counter-clockwise, as in PostScript.
+        let mut outline = square(&[(0, 0), (640, 0), (640, 640), (0, 640)]);
+        outline.embolden(strength, strength);
+        assert_eq!(coords(&outline), [(0, 0), (704, 0), (704, 704), (0, 704)]);
+    }
+
+    #[test]
+    fn skew() {
+        let mut outline = square(&[(0, 0), (0, 640), (640, 640), (640, 0)]);
+        outline.skew(45.0);
+        assert_eq!(
+            coords(&outline),
+            [(0, 0), (640, 640), (1280, 640), (640, 0)]
+        );
+    }
+
+    #[test]
+    fn capture_path() {
+        let mut outline = Outline::new();
+        let mut pen = OutlinePen::new(&mut outline);
+        pen.move_to(0.0, 0.0);
+        pen.line_to(1.0, 0.0);
+        pen.curve_to(1.0, 0.5, 0.5, 1.0, 0.0, 1.0);
+        pen.line_to(0.0, 0.0);
+        pen.close();
+        drop(pen);
+        assert_eq!(
+            coords(&outline),
+            [(0, 0), (64, 0), (64, 32), (32, 64), (0, 64)]
+        );
+        assert_eq!(outline.contours, [4]);
+        assert!(!outline.flags[2].is_on_curve());
+    }
 }
@@ -1,18 +1,24 @@
-use super::{Context, Outline, Point};
-use crate::{Error, NormalizedCoord, Result, GLYF_COMPOSITE_RECURSION_LIMIT};
+use super::{Component, ComponentAnchor, Context, Outline, Point};
+use crate::{Error, NormalizedCoord, Result, Transform, GLYF_COMPOSITE_RECURSION_LIMIT};
 
 #[cfg(feature = "hinting")]
-use {crate::Hinting, read_fonts::tables::glyf::PointMarker};
+use {
+    super::hint::{CacheSlot, Config as HintConfig, GlyphOutline},
+    crate::Hinting,
+    read_fonts::tables::{cvar::Cvar, glyf::PointMarker},
+};
 
 use read_fonts::{
     tables::{
-        glyf::{Anchor, CompositeGlyph, CompositeGlyphFlags, Glyf, Glyph, PointFlags, SimpleGlyph},
+        glyf::{
+            Anchor, CompositeGlyph, CompositeGlyphFlags, Glyf, Glyph, GlyphBounds, SimpleGlyph,
+        },
         hmtx::Hmtx,
         hvar::Hvar,
         loca::Loca,
     },
-    types::{BigEndian, F26Dot6, F2Dot14, GlyphId, Tag},
-    TableProvider,
+    types::{BigEndian, BigEndianSlice, F26Dot6, F2Dot14, Fixed, GlyphId, Tag},
+    ReadError, TableProvider,
 };
 
 /// TrueType glyph scaler for a specific font and configuration.
@@ -32,6 +38,9 @@ pub struct Scaler<'a> {
     ppem: u16,
     /// Scale factor from font units to 26.6 fixed point.
     scale: F26Dot6,
+    /// Size in points, in 26.6 fixed point.
+    #[cfg(feature = "hinting")]
+    point_size: i32,
     /// Hint mode.
     #[cfg(feature = "hinting")]
     hint: Option<Hinting>,
@@ -62,7 +71,8 @@ impl<'a> Scaler<'a> {
         } else {
             (false, F26Dot6::ZERO)
         };
-        Ok(Self {
+        #[allow(unused_mut)]
+        let mut scaler = Self {
             context,
             font,
             font_id,
@@ -72,14 +82,71 @@ impl<'a> Scaler<'a> {
             ppem,
             scale,
             #[cfg(feature = "hinting")]
+            point_size: (size * 64.) as i32,
+            #[cfg(feature = "hinting")]
             hint,
             coords,
+        };
+        #[cfg(feature = "hinting")]
+        if let Some(config) = scaler.hint_config() {
+            scaler.cache_slot =
+                scaler
+                    .context
+                    .hint_cache
+                    .prepare(&scaler.font, scaler.font_id, config);
+        }
+        Ok(scaler)
+    }
+
+    /// Returns the configuration for the hinting interpreter, or `None` if
+    /// hinting is disabled.
+    #[cfg(feature = "hinting")]
+    fn hint_config(&self) -> Option<HintConfig<'a>> {
+        if !self.is_scaled {
+            return None;
+        }
+        Some(HintConfig {
+            mode: self.hint?,
+            ppem: self.ppem as i32,
+            point_size: self.point_size,
+            scale: self.scale.to_bits(),
+            coords: self.coords,
+            axis_count: self.font.axis_count,
         })
     }
 
+    /// Returns the number of glyphs in the font.
+    pub fn glyph_count(&self) -> usize {
+        self.font.glyph_count as usize
+    }
+
     /// Loads an outline for the specified glyph identifier to the preallocated
     /// target.
     pub fn load(&mut self, glyph_id: GlyphId, outline: &mut Outline) -> Result<()> {
+        self.load_glyph(glyph_id, outline, None)
+    }
+
+    /// Loads an outline for the specified glyph identifier and records the
+    /// components of the glyph if it is a composite.
+    ///
+    /// Only the direct components are recorded, in the order they are
+    /// defined. The components list is empty for simple glyphs.
+    pub fn load_components(
+        &mut self,
+        glyph_id: GlyphId,
+        outline: &mut Outline,
+        components: &mut Vec<Component>,
+    ) -> Result<()> {
+        components.clear();
+        self.load_glyph(glyph_id, outline, Some(components))
+    }
+
+    fn load_glyph(
+        &mut self,
+        glyph_id: GlyphId,
+        outline: &mut Outline,
+        components: Option<&mut Vec<Component>>,
+    ) -> Result<()> {
         outline.clear();
         self.context.unscaled.clear();
         self.context.original.clear();
@@ -87,7 +154,27 @@ impl<'a> Scaler<'a> {
         if glyph_id.to_u16() >= self.font.glyph_count {
             return Err(Error::GlyphNotFound(glyph_id));
         }
-        GlyphScaler::new(self).load(glyph_id, outline, 0)
+        let mut scaler = GlyphScaler::new(self);
+        scaler.components = components;
+        scaler.load(glyph_id, outline, 0)
+    }
+
+    /// Loads an outline in font units, ignoring the size and hinting
+    /// settings of the scaler.
+    #[cfg(feature = "hinting")]
+    pub(crate) fn load_unscaled(&mut self, glyph_id: GlyphId, outline: &mut Outline) -> Result<()> {
+        let is_scaled = core::mem::replace(&mut self.is_scaled, false);
+        let cache_slot = self.cache_slot.take();
+        let result = self.load(glyph_id, outline);
+        self.is_scaled = is_scaled;
+        self.cache_slot = cache_slot;
+        result
+    }
+
+    /// Returns true if outlines are hinted by the bytecode interpreter.
+    #[cfg(feature = "hinting")]
+    pub fn is_hinted(&self) -> bool {
+        self.cache_slot.is_some()
     }
 }
 
@@ -103,17 +190,21 @@ struct GlyphScaler<'a, 'b> {
     ///
     /// See <https://learn.microsoft.com/en-us/typography/opentype/spec/tt_instructing_glyphs#phantom-points>
     phantom: [Point<F26Dot6>; 4],
+    /// Storage for the components of the requested glyph, if they are
+    /// being recorded.
+    components: Option<&'b mut Vec<Component>>,
 }
 
 impl<'a, 'b> GlyphScaler<'a, 'b> {
     pub fn new(scaler: &'b mut Scaler<'a>) -> Self {
         #[cfg(feature = "hinting")]
-        let hint = scaler.hint.is_some() && scaler.is_scaled;
+        let hint = scaler.cache_slot.is_some();
         Self {
             scaler,
             #[cfg(feature = "hinting")]
             hint,
             phantom: Default::default(),
+            components: None,
         }
     }
 }
@@ -129,16 +220,16 @@ impl<'a, 'b> GlyphScaler<'a, 'b> {
         if recurse_depth > GLYF_COMPOSITE_RECURSION_LIMIT {
             return Err(Error::RecursionLimitExceeded(glyph_id));
         }
-        let Some(glyph) = self.scaler.font.glyph(glyph_id) else {
+        // Components may reference glyphs that are not in the font.
+        if glyph_id.to_u16() >= self.scaler.font.glyph_count {
             return Err(Error::GlyphNotFound(glyph_id));
-        };
-        let glyph = match glyph {
+        }
+        let glyph = match self.scaler.font.glyph(glyph_id)? {
             Some(glyph) => glyph,
             // This is a valid empty glyph
             None => return Ok(()),
         };
-        let bounds = [glyph.x_min(), glyph.x_max(), glyph.y_min(), glyph.y_max()];
-        self.setup_phantom(bounds, glyph_id);
+        self.setup_phantom(glyph.header_bounds(), glyph_id);
         match glyph {
             Glyph::Simple(simple) => self.load_simple(&simple, glyph_id, outline, recurse_depth),
             Glyph::Composite(composite) => {
@@ -160,9 +251,7 @@ impl<'a, 'b> GlyphScaler<'a, 'b> {
         let end_pts = simple.end_pts_of_contours();
         let contour_count = end_pts.len();
         let contour_end = contour_base + contour_count;
-        outline
-            .contours
-            .extend(end_pts.iter().map(|end_pt| end_pt.get()));
+        end_pts.extend_native(&mut outline.contours);
         let mut point_count = simple.num_points();
         outline
             .flags
@@ -245,11 +334,9 @@ impl<'a, 'b> GlyphScaler<'a, 'b> {
                 .context
                 .original
                 .extend_from_slice(&outline.points[point_base..point_end]);
-            // When hinting, round the components of the phantom points.
-            for point in &mut outline.points[point_end - 4..] {
-                point.x = point.x.round();
-                point.y = point.y.round();
-            }
+            // When hinting, round the horizontal phantom points in x and
+            // the vertical phantom points in y.
+            round_phantom(&mut outline.points[point_end - 4..]);
             // Apply hinting to the set of contours for this outline.
             if !self.hint(outline, point_base, contour_base, ins, false) {
                 return Err(Error::HintingFailed(glyph_id));
@@ -405,6 +492,28 @@ impl<'a, 'b> GlyphScaler<'a, 'b> {
                     point.y += dy;
                 }
             }
+            if let Some(components) = self.components.as_mut().filter(|_| recurse_depth == 0) {
+                components.push(Component {
+                    glyph_id: component.glyph,
+                    transform: Transform {
+                        xx: xform.xx.to_f32(),
+                        yx: xform.yx.to_f32(),
+                        xy: xform.xy.to_f32(),
+                        yy: xform.yy.to_f32(),
+                        dx: dx.to_f64() as f32,
+                        dy: dy.to_f64() as f32,
+                    },
+                    anchor: match anchor {
+                        Anchor::Offset { .. } => ComponentAnchor::Offset,
+                        Anchor::Point { base, component } => {
+                            ComponentAnchor::Point { base, component }
+                        }
+                    },
+                    use_my_metrics: component
+                        .flags
+                        .contains(CompositeGlyphFlags::USE_MY_METRICS),
+                });
+            }
         }
         #[cfg(feature = "hinting")]
         if self.hint {
@@ -429,10 +538,7 @@ impl<'a, 'b> GlyphScaler<'a, 'b> {
                     .extend_from_slice(&outline.points[point_base..]);
                 let point_end = outline.points.len();
                 // Round the phantom points.
-                for p in &mut outline.points[point_end - 4..] {
-                    p.x = p.x.round();
-                    p.y = p.y.round();
-                }
+                round_phantom(&mut outline.points[point_end - 4..]);
                 // Clear the "touched" flags that are used during IUP processing.
                 for flag in &mut outline.flags[point_base..] {
                     flag.clear_marker(PointMarker::TOUCHED);
@@ -459,13 +565,52 @@ impl<'a, 'b> GlyphScaler<'a, 'b> {
         ins: &[u8],
         is_composite: bool,
     ) -> bool {
-        true
+        let (Some(slot), Some(config)) = (self.scaler.cache_slot, self.scaler.hint_config()) else {
+            return true;
+        };
+        let glyph = GlyphOutline {
+            unscaled: &self.scaler.context.unscaled,
+            original: &self.scaler.context.original,
+            points: &mut outline.points[point_base..],
+            flags: &mut outline.flags[point_base..],
+            contours: &outline.contours[contour_base..],
+            // Contours of composites are already offset by the start of
+            // the glyph.
+            point_base: if is_composite { point_base } else { 0 },
+            instructions: ins,
+            is_composite,
+        };
+        match self
+            .scaler
+            .context
+            .hint_cache
+            .hint(slot, &self.scaler.font, config, glyph)
+        {
+            Ok(save_phantom) => {
+                if save_phantom {
+                    let point_count = outline.points.len() - point_base;
+                    self.save_phantom(outline, point_base, point_count);
+                }
+                true
+            }
+            Err(_) => false,
+        }
     }
 }
 
+/// Rounds the phantom points as FreeType does before hinting: the
+/// horizontal metrics in x and the vertical metrics in y.
+#[cfg(feature = "hinting")]
+fn round_phantom(phantom: &mut [Point<F26Dot6>]) {
+    phantom[0].x = phantom[0].x.round();
+    phantom[1].x = phantom[1].x.round();
+    phantom[2].y = phantom[2].y.round();
+    phantom[3].y = phantom[3].y.round();
+}
+
 // Phantom point management.
 impl<'a, 'b> GlyphScaler<'a, 'b> {
-    fn setup_phantom(&mut self, bounds: [i16; 4], glyph_id: GlyphId) {
+    fn setup_phantom(&mut self, bounds: GlyphBounds, glyph_id: GlyphId) {
         let font = &self.scaler.font;
         let lsb = font.lsb(glyph_id, self.scaler.coords);
         let advance = font.advance_width(glyph_id, self.scaler.coords);
@@ -474,12 +619,12 @@ impl<'a, 'b> GlyphScaler<'a, 'b> {
         let vadvance = 0;
         let tsb = 0;
         // The four "phantom" points as computed by FreeType.
-        self.phantom[0].x = F26Dot6::from_bits(bounds[0] as i32 - lsb);
+        self.phantom[0].x = F26Dot6::from_bits(bounds.x_min as i32 - lsb);
         self.phantom[0].y = F26Dot6::ZERO;
         self.phantom[1].x = self.phantom[0].x + F26Dot6::from_bits(advance);
         self.phantom[1].y = F26Dot6::ZERO;
         self.phantom[2].x = F26Dot6::from_bits(advance / 2);
-        self.phantom[2].y = F26Dot6::from_bits(bounds[3] as i32 + tsb);
+        self.phantom[2].y = F26Dot6::from_bits(bounds.y_max as i32 + tsb);
         self.phantom[3].x = F26Dot6::from_bits(advance / 2);
         self.phantom[3].y = self.phantom[2].y - F26Dot6::from_bits(vadvance);
     }
@@ -503,54 +648,6 @@ impl<'a, 'b> GlyphScaler<'a, 'b> {
     }
 }
 
-#[cfg(feature = "hinting")]
-/// Slot for the hinting cache.
-#[derive(Copy, Clone)]
-enum CacheSlot {
-    /// Uncached font.
-    Uncached,
-    /// Font and size cache indices.
-    Cached {
-        font_index: usize,
-        size_index: usize,
-    },
-}
-
-// Cache management and hinting.
-#[cfg(feature = "hinting")]
-impl Context {
-    /// Prepares for the cache for hinting.
-    fn prepare_for_hinting(
-        &mut self,
-        font: &Font,
-        font_id: Option<u64>,
-        coords: &[NormalizedCoord],
-        ppem: u16,
-        scale: i32,
-        mode: Hinting,
-    ) -> Option<CacheSlot> {
-        None
-    }
-
-    #[allow(clippy::too_many_arguments)]
-    fn hint(
-        &mut self,
-        data: &Font,
-        coords: &[NormalizedCoord],
-        slot: CacheSlot,
-        scaled: &mut [Point<F26Dot6>],
-        flags: &mut [PointFlags],
-        contours: &mut [u16],
-        phantom: &mut [Point<F26Dot6>],
-        point_base: usize,
-        contour_base: usize,
-        ins: &[u8],
-        is_composite: bool,
-    ) -> bool {
-        true
-    }
-}
-
 /// Contains the tables and limits necessary for loading, scaling and hinting
 /// a TrueType glyph.
 #[derive(Clone)]
@@ -570,6 +667,8 @@ pub struct Font<'a> {
     pub max_instruction_defs: u16,
     pub max_twilight: u16,
     pub axis_count: u16,
+    #[cfg(feature = "hinting")]
+    pub cvar: Option<Cvar<'a>>,
 }
 
 impl<'a> Font<'a> {
@@ -580,15 +679,15 @@ impl<'a> Font<'a> {
         let hvar = font.hvar().ok();
         let upem = font.head()?.units_per_em();
         let fpgm = font
-            .data_for_tag(Tag::new(b"fpgm"))
+            .data_for_tag(Tag::FPGM)
             .map(|data| data.read_array(0..data.len()).unwrap())
             .unwrap_or_default();
         let prep = font
-            .data_for_tag(Tag::new(b"prep"))
+            .data_for_tag(Tag::PREP)
             .map(|data| data.read_array(0..data.len()).unwrap())
             .unwrap_or_default();
         let cvt = font
-            .data_for_tag(Tag::new(b"cvt"))
+            .data_for_tag(Tag::CVT)
             .and_then(|data| data.read_array(0..data.len()).ok())
             .unwrap_or_default();
         let maxp = font.maxp()?;
@@ -610,26 +709,17 @@ impl<'a> Font<'a> {
             max_instruction_defs: maxp.max_instruction_defs().unwrap_or(0),
             max_twilight: maxp.max_twilight_points().unwrap_or(0),
             axis_count,
+            #[cfg(feature = "hinting")]
+            cvar: font.cvar().ok(),
         })
     }
 
-    fn glyph(&self, gid: GlyphId) -> Option<Option<Glyph<'a>>> {
-        self.loca.get_glyf(gid, &self.glyf).ok()
+    fn glyph(&self, gid: GlyphId) -> core::result::Result<Option<Glyph<'a>>, ReadError> {
+        self.loca.get_glyf(gid, &self.glyf)
     }
 
     fn advance_width(&self, gid: GlyphId, coords: &[NormalizedCoord]) -> i32 {
-        let default_advance = self
-            .hmtx
-            .h_metrics()
-            .last()
-            .map(|metric| metric.advance())
-            .unwrap_or(0);
-        let mut advance = self
-            .hmtx
-            .h_metrics()
-            .get(gid.to_u16() as usize)
-            .map(|metric| metric.advance())
-            .unwrap_or(default_advance) as i32;
+        let mut advance = self.hmtx.advance(gid).unwrap_or(0) as i32;
         if let Some(hvar) = &self.hvar {
             advance += hvar
                 .advance_width_delta(gid, coords)
@@ -641,19 +731,7 @@ impl<'a> Font<'a> {
     }
 
     fn lsb(&self, gid: GlyphId, coords: &[NormalizedCoord]) -> i32 {
-        let gid_index = gid.to_u16() as usize;
-        let mut lsb = self
-            .hmtx
-            .h_metrics()
-            .get(gid_index)
-            .map(|metric| metric.side_bearing())
-            .unwrap_or_else(|| {
-                self.hmtx
-                    .left_side_bearings()
-                    .get(gid_index.saturating_sub(self.hmtx.h_metrics().len()))
-                    .map(|lsb| lsb.get())
-                    .unwrap_or(0)
-            }) as i32;
+        let mut lsb = self.hmtx.side_bearing(gid).unwrap_or(0) as i32;
         if let Some(hvar) = &self.hvar {
             lsb += hvar
                 .lsb_delta(gid, coords)
@@ -664,19 +742,29 @@ impl<'a> Font<'a> {
         lsb
     }
 
+    /// Computes the control values for the given scale factor and
+    /// variation coordinates.
     #[cfg(feature = "hinting")]
-    pub(crate) fn scale_cvt(&self, scale: Option<i32>, scaled_cvt: &mut Vec<i32>) {
-        if scaled_cvt.len() < self.cvt.len() {
-            scaled_cvt.resize(self.cvt.len(), 0);
-        }
-        for (src, dest) in self.cvt.iter().zip(scaled_cvt.iter_mut()) {
-            *dest = src.get() as i32 * 64;
-        }
-        if let Some(scale) = scale {
-            let scale = F26Dot6::from_bits(scale >> 6);
-            for value in &mut scaled_cvt[..] {
-                *value = (F26Dot6::from_bits(*value) * scale).to_bits();
+    pub(crate) fn scale_cvt(
+        &self,
+        scale: i32,
+        coords: &[NormalizedCoord],
+        scaled_cvt: &mut Vec<i32>,
+    ) {
+        scaled_cvt.clear();
+        scaled_cvt.extend(self.cvt.iter().map(|value| value.get() as i32 * 64));
+        if let Some(cvar) = self.cvar.as_ref().filter(|_| !coords.is_empty()) {
+            let mut deltas = vec![Fixed::ZERO; scaled_cvt.len()];
+            if cvar.deltas(self.axis_count, coords, &mut deltas).is_ok() {
+                for (value, delta) in scaled_cvt.iter_mut().zip(&deltas) {
+                    // Convert 16.16 to 26.6 with rounding.
+                    *value += (delta.to_bits() + 0x200) >> 10;
+                }
             }
         }
+        let scale = F26Dot6::from_bits(scale >> 6);
+        for value in scaled_cvt.iter_mut() {
+            *value = (F26Dot6::from_bits(*value) * scale).to_bits();
+        }
     }
 }
@@ -0,0 +1,157 @@
+//! Conversion of outlines to SVG path data.
+
+use super::Pen;
+
+use core::fmt::{self, Write};
+
+/// Default number of digits after the decimal point.
+const DEFAULT_PRECISION: usize = 2;
+
+/// Pen that produces SVG path data, suitable for the `d` attribute of a
+/// `path` element.
+///
+/// Commands are separated by spaces and coordinates by commas, with
+/// trailing zeros removed, such as `M10,0 L10.5,20 Z`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SvgPathPen {
+    data: String,
+    precision: usize,
+    flip_y: bool,
+}
+
+impl Default for SvgPathPen {
+    fn default() -> Self {
+        Self {
+            data: String::new(),
+            precision: DEFAULT_PRECISION,
+            flip_y: false,
+        }
+    }
+}
+
+impl SvgPathPen {
+    /// Creates a new pen with a precision of two digits after the decimal
+    /// point and no vertical flip.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of digits after the decimal point.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Sets whether y coordinates are negated.
+    ///
+    /// Font coordinates increase upward while SVG coordinates increase
+    /// downward, so flipping renders glyphs upright.
+    pub fn flip_y(mut self, flip_y: bool) -> Self {
+        self.flip_y = flip_y;
+        self
+    }
+
+    /// Returns the path data.
+    pub fn as_str(&self) -> &str {
+        &self.data
+    }
+
+    /// Returns the path data, consuming the pen.
+    pub fn into_string(self) -> String {
+        self.data
+    }
+
+    /// Removes the path data, keeping the configuration.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    fn command(&mut self, command: char, coords: &[f32]) {
+        if !self.data.is_empty() {
+            self.data.push(' ');
+        }
+        self.data.push(command);
+        for (i, pair) in coords.chunks_exact(2).enumerate() {
+            if i != 0 {
+                self.data.push(' ');
+            }
+            let y = if self.flip_y { -pair[1] } else { pair[1] };
+            self.number(pair[0]);
+            self.data.push(',');
+            self.number(y);
+        }
+    }
+
+    fn number(&mut self, value: f32) {
+        let start = self.data.len();
+        let precision = self.precision;
+        // Writing to a string cannot fail.
+        let _ = write!(self.data, "{value:.precision$}");
+        if self.data[start..].contains('.') {
+            let len = self.data.trim_end_matches('0').trim_end_matches('.').len();
+            self.data.truncate(len);
+        }
+        if &self.data[start..] == "-0" {
+            self.data.replace_range(start.., "0");
+        }
+    }
+}
+
+impl Pen for SvgPathPen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.command('M', &[x, y]);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.command('L', &[x, y]);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        self.command('Q', &[cx0, cy0, x, y]);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        self.command('C', &[cx0, cy0, cx1, cy1, x, y]);
+    }
+
+    fn close(&mut self) {
+        self.command('Z', &[]);
+    }
+}
+
+impl fmt::Display for SvgPathPen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(pen: &mut SvgPathPen) {
+        pen.move_to(10.0, 0.0);
+        pen.line_to(10.5, 20.0);
+        pen.quad_to(1.0 / 3.0, -0.001, 0.0, 5.25);
+        pen.curve_to(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+        pen.close();
+    }
+
+    #[test]
+    fn path_data() {
+        let mut pen = SvgPathPen::new();
+        draw(&mut pen);
+        assert_eq!(pen.as_str(), "M10,0 L10.5,20 Q0.33,0 0,5.25 C1,2 3,4 5,6 Z");
+    }
+
+    #[test]
+    fn precision_and_flip() {
+        let mut pen = SvgPathPen::new().precision(0).flip_y(true);
+        draw(&mut pen);
+        assert_eq!(pen.to_string(), "M10,0 L10,-20 Q0,0 0,-5 C1,-2 3,-4 5,-6 Z");
+        pen.clear();
+        let mut pen = pen.precision(4);
+        pen.line_to(0.25, 0.125);
+        assert_eq!(pen.into_string(), "L0.25,-0.125");
+    }
+}
@@ -17,6 +17,17 @@ pub enum PathElement {
 
 use PathElement::*;
 
+impl PathElement {
+    /// Returns the coordinates of the element.
+    pub fn coords(&self) -> &[f32] {
+        match self {
+            MoveTo(coords) | LineTo(coords) => coords,
+            QuadTo(coords) => coords,
+            CurveTo(coords) => coords,
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct Path(pub Vec<PathElement>);
 
@@ -0,0 +1,124 @@
+//! Compares element-wise and bulk conversion of big-endian arrays.
+//!
+//! Run with `cargo bench -p read-fonts --features test_data`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use read_fonts::{
+    tables::{gvar::GlyphVariationData, loca::Loca},
+    test_data::test_fonts,
+    types::{BigEndian, BigEndianSlice, F2Dot14, GlyphId, Scalar},
+    FontRef, TableProvider,
+};
+
+fn convert<T: Scalar + Copy + Default>(c: &mut Criterion, name: &str, data: &[BigEndian<T>]) {
+    let mut dst = vec![T::default(); data.len()];
+    let mut group = c.benchmark_group(name);
+    group.bench_function("get", |b| {
+        b.iter(|| {
+            for (dst, src) in dst.iter_mut().zip(black_box(data)) {
+                *dst = src.get();
+            }
+        })
+    });
+    group.bench_function("copy_to_native", |b| {
+        b.iter(|| black_box(data).copy_to_native(&mut dst))
+    });
+    group.finish();
+}
+
+fn loca(c: &mut Criterion) {
+    let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+    match font.loca(None).unwrap() {
+        Loca::Short(offsets) => convert(c, "loca_short", offsets),
+        Loca::Long(offsets) => convert(c, "loca_long", offsets),
+    }
+    // a larger array, so that the per-call overhead is negligible
+    let words: Vec<BigEndian<i16>> = (0..4096i16).map(BigEndian::from).collect();
+    convert(c, "words", &words);
+}
+
+fn loca_offsets(c: &mut Criterion) {
+    // the test fonts have few glyphs, so use synthetic tables of a
+    // realistic size
+    let short: Vec<BigEndian<u16>> = (0..4096u16).map(BigEndian::from).collect();
+    let long: Vec<BigEndian<u32>> = (0..4096u32).map(BigEndian::from).collect();
+    let mut dst = vec![0u32; 4096];
+    for (name, loca) in [("short", Loca::Short(&short)), ("long", Loca::Long(&long))] {
+        let mut group = c.benchmark_group(format!("loca_offsets_{name}"));
+        group.bench_function("get_raw", |b| {
+            b.iter(|| {
+                for (i, dst) in dst.iter_mut().enumerate() {
+                    *dst = black_box(&loca).get_raw(i).unwrap_or_default();
+                }
+            })
+        });
+        group.bench_function("copy_offsets", |b| {
+            b.iter(|| black_box(&loca).copy_offsets(&mut dst))
+        });
+        group.finish();
+    }
+}
+
+fn shared_tuples(c: &mut Criterion) {
+    let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+    let tuples = font.gvar().unwrap().shared_tuples().unwrap();
+    let len = tuples
+        .tuples()
+        .iter()
+        .map(|tuple| tuple.map(|tuple| tuple.len()).unwrap_or_default())
+        .sum();
+    let mut dst = vec![F2Dot14::ZERO; len];
+    let mut group = c.benchmark_group("shared_tuples");
+    group.bench_function("get", |b| {
+        b.iter(|| {
+            let coords = black_box(&tuples)
+                .tuples()
+                .iter()
+                .flatten()
+                .flat_map(|tuple| (0..tuple.len()).filter_map(move |i| tuple.get(i)));
+            for (dst, coord) in dst.iter_mut().zip(coords) {
+                *dst = coord;
+            }
+        })
+    });
+    group.bench_function("copy_coordinates", |b| {
+        b.iter(|| black_box(&tuples).copy_coordinates(&mut dst))
+    });
+    group.finish();
+}
+
+fn gvar_deltas(c: &mut Criterion) {
+    let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+    let gvar = font.gvar().unwrap();
+    let num_glyphs = font.maxp().unwrap().num_glyphs();
+    let data: Vec<GlyphVariationData> = (0..num_glyphs)
+        .filter_map(|gid| gvar.glyph_variation_data(GlyphId::new(gid)).ok()?)
+        .collect();
+    let mut buf = vec![0i16; u16::MAX as usize];
+    let mut group = c.benchmark_group("gvar_deltas");
+    group.bench_function("iter", |b| {
+        b.iter(|| {
+            let mut sum = 0i32;
+            for tuple in data.iter().flat_map(|data| data.tuples()) {
+                for delta in tuple.deltas() {
+                    sum += delta.x_delta as i32 + delta.y_delta as i32;
+                }
+            }
+            sum
+        })
+    });
+    group.bench_function("read_into", |b| {
+        b.iter(|| {
+            let mut sum = 0i32;
+            for tuple in data.iter().flat_map(|data| data.tuples()) {
+                let len = tuple.packed_deltas().iter().read_into(&mut buf);
+                sum += buf[..len].iter().map(|d| *d as i32).sum::<i32>();
+            }
+            sum
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, loca, loca_offsets, shared_tuples, gvar_deltas);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+//! Compares glyph loading with and without cached table handles.
+//!
+//! Run with `cargo bench -p read-fonts --features test_data`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use read_fonts::{
+    test_data::test_fonts, types::GlyphId, CachedFontRef, FontRef, ReadError, TableProvider,
+};
+
+/// Loads the outline and variation data for every glyph, fetching the
+/// tables for each glyph as a scaler would.
+fn load_glyphs<'a>(font: &impl TableProvider<'a>, num_glyphs: u16) -> Result<usize, ReadError> {
+    let mut total = 0;
+    for gid in (0..num_glyphs).map(GlyphId::new) {
+        let glyf = font.glyf()?;
+        if let Some(glyph) = font.loca(None)?.get_glyf(gid, &glyf)? {
+            total += glyph.number_of_contours() as usize;
+        }
+        if let Some(data) = font.gvar()?.glyph_variation_data(gid)? {
+            total += data.tuples().count();
+        }
+        total += font.hmtx()?.advance(gid).unwrap_or_default() as usize;
+    }
+    Ok(total)
+}
+
+fn glyph_loading(c: &mut Criterion) {
+    let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+    let num_glyphs = font.maxp().unwrap().num_glyphs();
+    let mut group = c.benchmark_group("load_glyphs");
+    group.bench_function("FontRef", |b| {
+        b.iter(|| load_glyphs(black_box(&font), num_glyphs).unwrap())
+    });
+    let cached = CachedFontRef::new(font.clone());
+    group.bench_function("CachedFontRef", |b| {
+        b.iter(|| load_glyphs(black_box(&cached), num_glyphs).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, glyph_loading);
+criterion_main!(benches);
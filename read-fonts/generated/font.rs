@@ -42,11 +42,17 @@ impl TableDirectoryMarker {
 impl<'a> FontRead<'a> for TableDirectory<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("sfntVersion");
         cursor.advance::<u32>();
+        cursor.set_field("numTables");
         let num_tables: u16 = cursor.read()?;
+        cursor.set_field("searchRange");
         cursor.advance::<u16>();
+        cursor.set_field("entrySelector");
         cursor.advance::<u16>();
+        cursor.set_field("rangeShift");
         cursor.advance::<u16>();
+        cursor.set_field("tableRecords");
         let table_records_byte_len = num_tables as usize * TableRecord::RAW_BYTE_LEN;
         cursor.advance_by(table_records_byte_len);
         cursor.finish(TableDirectoryMarker {
@@ -222,26 +228,39 @@ impl TTCHeaderMarker {
 impl<'a> FontRead<'a> for TTCHeader<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("ttcTag");
         cursor.advance::<Tag>();
+        cursor.set_field("version");
         let version: MajorMinor = cursor.read()?;
+        cursor.set_field("numFonts");
         let num_fonts: u32 = cursor.read()?;
+        cursor.set_field("tableDirectoryOffsets");
         let table_directory_offsets_byte_len = num_fonts as usize * u32::RAW_BYTE_LEN;
         cursor.advance_by(table_directory_offsets_byte_len);
+        cursor.set_field("dsigTag");
         let dsig_tag_byte_start = version
-            .compatible((2, 0))
+            .compatible(MajorMinor::new(2, 0))
             .then(|| cursor.position())
             .transpose()?;
-        version.compatible((2, 0)).then(|| cursor.advance::<u32>());
+        version
+            .compatible(MajorMinor::new(2, 0))
+            .then(|| cursor.advance::<u32>());
+        cursor.set_field("dsigLength");
         let dsig_length_byte_start = version
-            .compatible((2, 0))
+            .compatible(MajorMinor::new(2, 0))
             .then(|| cursor.position())
             .transpose()?;
-        version.compatible((2, 0)).then(|| cursor.advance::<u32>());
+        version
+            .compatible(MajorMinor::new(2, 0))
+            .then(|| cursor.advance::<u32>());
+        cursor.set_field("dsigOffset");
         let dsig_offset_byte_start = version
-            .compatible((2, 0))
+            .compatible(MajorMinor::new(2, 0))
             .then(|| cursor.position())
             .transpose()?;
-        version.compatible((2, 0)).then(|| cursor.advance::<u32>());
+        version
+            .compatible(MajorMinor::new(2, 0))
+            .then(|| cursor.advance::<u32>());
         cursor.finish(TTCHeaderMarker {
             table_directory_offsets_byte_len,
             dsig_tag_byte_start,
@@ -313,13 +332,13 @@ impl<'a> SomeTable<'a> for TTCHeader<'a> {
                 "table_directory_offsets",
                 self.table_directory_offsets(),
             )),
-            4usize if version.compatible((2, 0)) => {
+            4usize if version.compatible(MajorMinor::new(2, 0)) => {
                 Some(Field::new("dsig_tag", self.dsig_tag().unwrap()))
             }
-            5usize if version.compatible((2, 0)) => {
+            5usize if version.compatible(MajorMinor::new(2, 0)) => {
                 Some(Field::new("dsig_length", self.dsig_length().unwrap()))
             }
-            6usize if version.compatible((2, 0)) => {
+            6usize if version.compatible(MajorMinor::new(2, 0)) => {
                 Some(Field::new("dsig_offset", self.dsig_offset().unwrap()))
             }
             _ => None,
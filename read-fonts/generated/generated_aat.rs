@@ -0,0 +1,894 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[allow(unused_imports)]
+use crate::codegen_prelude::*;
+
+/// An [AAT lookup table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6Tables.html)
+/// with 16-bit values, mapping glyph ids to values.
+pub enum Lookup<'a> {
+    Format0(Lookup0<'a>),
+    Format2(Lookup2<'a>),
+    Format4(Lookup4<'a>),
+    Format6(Lookup6<'a>),
+    Format8(Lookup8<'a>),
+    Format10(Lookup10<'a>),
+}
+
+impl<'a> FontRead<'a> for Lookup<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let format: u16 = data.read_at(0)?;
+        match format {
+            Lookup0Marker::FORMAT => Ok(Self::Format0(FontRead::read(data)?)),
+            Lookup2Marker::FORMAT => Ok(Self::Format2(FontRead::read(data)?)),
+            Lookup4Marker::FORMAT => Ok(Self::Format4(FontRead::read(data)?)),
+            Lookup6Marker::FORMAT => Ok(Self::Format6(FontRead::read(data)?)),
+            Lookup8Marker::FORMAT => Ok(Self::Format8(FontRead::read(data)?)),
+            Lookup10Marker::FORMAT => Ok(Self::Format10(FontRead::read(data)?)),
+            other => Err(ReadError::InvalidFormat(other.into())),
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> Lookup<'a> {
+    fn dyn_inner<'b>(&'b self) -> &'b dyn SomeTable<'a> {
+        match self {
+            Self::Format0(table) => table,
+            Self::Format2(table) => table,
+            Self::Format4(table) => table,
+            Self::Format6(table) => table,
+            Self::Format8(table) => table,
+            Self::Format10(table) => table,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Lookup<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.dyn_inner().fmt(f)
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Lookup<'a> {
+    fn type_name(&self) -> &str {
+        self.dyn_inner().type_name()
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        self.dyn_inner().get_field(idx)
+    }
+}
+
+impl Format<u16> for Lookup0Marker {
+    const FORMAT: u16 = 0;
+}
+
+/// Simple array format; the lookup data is an array of values indexed by
+/// glyph id.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct Lookup0Marker {
+    values_byte_len: usize,
+}
+
+impl Lookup0Marker {
+    fn format_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn values_byte_range(&self) -> Range<usize> {
+        let start = self.format_byte_range().end;
+        start..start + self.values_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for Lookup0<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("format");
+        cursor.advance::<u16>();
+        cursor.set_field("values");
+        let values_byte_len = cursor.remaining_bytes();
+        cursor.advance_by(values_byte_len);
+        cursor.finish(Lookup0Marker { values_byte_len })
+    }
+}
+
+/// Simple array format; the lookup data is an array of values indexed by
+/// glyph id.
+pub type Lookup0<'a> = TableRef<'a, Lookup0Marker>;
+
+impl<'a> Lookup0<'a> {
+    /// Format number is set to 0.
+    pub fn format(&self) -> u16 {
+        let range = self.shape.format_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Values indexed by glyph id.
+    pub fn values(&self) -> &'a [BigEndian<u16>] {
+        let range = self.shape.values_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Lookup0<'a> {
+    fn type_name(&self) -> &str {
+        "Lookup0"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("format", self.format())),
+            1usize => Some(Field::new("values", self.values())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Lookup0<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+impl Format<u16> for Lookup2Marker {
+    const FORMAT: u16 = 2;
+}
+
+/// Segment single format; each segment maps a range of glyphs to a single
+/// value.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct Lookup2Marker {
+    segments_byte_len: usize,
+}
+
+impl Lookup2Marker {
+    fn format_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn unit_size_byte_range(&self) -> Range<usize> {
+        let start = self.format_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn n_units_byte_range(&self) -> Range<usize> {
+        let start = self.unit_size_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn search_range_byte_range(&self) -> Range<usize> {
+        let start = self.n_units_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn entry_selector_byte_range(&self) -> Range<usize> {
+        let start = self.search_range_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn range_shift_byte_range(&self) -> Range<usize> {
+        let start = self.entry_selector_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn segments_byte_range(&self) -> Range<usize> {
+        let start = self.range_shift_byte_range().end;
+        start..start + self.segments_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for Lookup2<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("format");
+        cursor.advance::<u16>();
+        cursor.set_field("unitSize");
+        cursor.advance::<u16>();
+        cursor.set_field("nUnits");
+        let n_units: u16 = cursor.read()?;
+        cursor.set_field("searchRange");
+        cursor.advance::<u16>();
+        cursor.set_field("entrySelector");
+        cursor.advance::<u16>();
+        cursor.set_field("rangeShift");
+        cursor.advance::<u16>();
+        cursor.set_field("segments");
+        let segments_byte_len = n_units as usize * LookupSegment::RAW_BYTE_LEN;
+        cursor.advance_by(segments_byte_len);
+        cursor.finish(Lookup2Marker { segments_byte_len })
+    }
+}
+
+/// Segment single format; each segment maps a range of glyphs to a single
+/// value.
+pub type Lookup2<'a> = TableRef<'a, Lookup2Marker>;
+
+impl<'a> Lookup2<'a> {
+    /// Format number is set to 2.
+    pub fn format(&self) -> u16 {
+        let range = self.shape.format_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Size of a lookup unit for this search in bytes.
+    pub fn unit_size(&self) -> u16 {
+        let range = self.shape.unit_size_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of units of the preceding size to be searched.
+    pub fn n_units(&self) -> u16 {
+        let range = self.shape.n_units_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The value of `unit_size` times the largest power of 2 that is less
+    /// than or equal to `n_units`.
+    pub fn search_range(&self) -> u16 {
+        let range = self.shape.search_range_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The log base 2 of the largest power of 2 less than or equal to
+    /// `n_units`.
+    pub fn entry_selector(&self) -> u16 {
+        let range = self.shape.entry_selector_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The value of `unit_size` times the difference of `n_units` minus
+    /// the largest power of 2 less than or equal to `n_units`.
+    pub fn range_shift(&self) -> u16 {
+        let range = self.shape.range_shift_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Segments, sorted by last glyph.
+    pub fn segments(&self) -> &'a [LookupSegment] {
+        let range = self.shape.segments_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Lookup2<'a> {
+    fn type_name(&self) -> &str {
+        "Lookup2"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("format", self.format())),
+            1usize => Some(Field::new("unit_size", self.unit_size())),
+            2usize => Some(Field::new("n_units", self.n_units())),
+            3usize => Some(Field::new("search_range", self.search_range())),
+            4usize => Some(Field::new("entry_selector", self.entry_selector())),
+            5usize => Some(Field::new("range_shift", self.range_shift())),
+            6usize => Some(Field::new(
+                "segments",
+                traversal::FieldType::array_of_records(
+                    stringify!(LookupSegment),
+                    self.segments(),
+                    self.offset_data(),
+                ),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Lookup2<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+impl Format<u16> for Lookup4Marker {
+    const FORMAT: u16 = 4;
+}
+
+/// Segment array format; each segment maps a range of glyphs to an array of
+/// values.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct Lookup4Marker {
+    segments_byte_len: usize,
+}
+
+impl Lookup4Marker {
+    fn format_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn unit_size_byte_range(&self) -> Range<usize> {
+        let start = self.format_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn n_units_byte_range(&self) -> Range<usize> {
+        let start = self.unit_size_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn search_range_byte_range(&self) -> Range<usize> {
+        let start = self.n_units_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn entry_selector_byte_range(&self) -> Range<usize> {
+        let start = self.search_range_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn range_shift_byte_range(&self) -> Range<usize> {
+        let start = self.entry_selector_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn segments_byte_range(&self) -> Range<usize> {
+        let start = self.range_shift_byte_range().end;
+        start..start + self.segments_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for Lookup4<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("format");
+        cursor.advance::<u16>();
+        cursor.set_field("unitSize");
+        cursor.advance::<u16>();
+        cursor.set_field("nUnits");
+        let n_units: u16 = cursor.read()?;
+        cursor.set_field("searchRange");
+        cursor.advance::<u16>();
+        cursor.set_field("entrySelector");
+        cursor.advance::<u16>();
+        cursor.set_field("rangeShift");
+        cursor.advance::<u16>();
+        cursor.set_field("segments");
+        let segments_byte_len = n_units as usize * LookupSegment::RAW_BYTE_LEN;
+        cursor.advance_by(segments_byte_len);
+        cursor.finish(Lookup4Marker { segments_byte_len })
+    }
+}
+
+/// Segment array format; each segment maps a range of glyphs to an array of
+/// values.
+pub type Lookup4<'a> = TableRef<'a, Lookup4Marker>;
+
+impl<'a> Lookup4<'a> {
+    /// Format number is set to 4.
+    pub fn format(&self) -> u16 {
+        let range = self.shape.format_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Size of a lookup unit for this search in bytes.
+    pub fn unit_size(&self) -> u16 {
+        let range = self.shape.unit_size_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of units of the preceding size to be searched.
+    pub fn n_units(&self) -> u16 {
+        let range = self.shape.n_units_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The value of `unit_size` times the largest power of 2 that is less
+    /// than or equal to `n_units`.
+    pub fn search_range(&self) -> u16 {
+        let range = self.shape.search_range_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The log base 2 of the largest power of 2 less than or equal to
+    /// `n_units`.
+    pub fn entry_selector(&self) -> u16 {
+        let range = self.shape.entry_selector_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The value of `unit_size` times the difference of `n_units` minus
+    /// the largest power of 2 less than or equal to `n_units`.
+    pub fn range_shift(&self) -> u16 {
+        let range = self.shape.range_shift_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Segments, sorted by last glyph. The value of each segment is an
+    /// offset from the start of the lookup table to an array of values.
+    pub fn segments(&self) -> &'a [LookupSegment] {
+        let range = self.shape.segments_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Lookup4<'a> {
+    fn type_name(&self) -> &str {
+        "Lookup4"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("format", self.format())),
+            1usize => Some(Field::new("unit_size", self.unit_size())),
+            2usize => Some(Field::new("n_units", self.n_units())),
+            3usize => Some(Field::new("search_range", self.search_range())),
+            4usize => Some(Field::new("entry_selector", self.entry_selector())),
+            5usize => Some(Field::new("range_shift", self.range_shift())),
+            6usize => Some(Field::new(
+                "segments",
+                traversal::FieldType::array_of_records(
+                    stringify!(LookupSegment),
+                    self.segments(),
+                    self.offset_data(),
+                ),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Lookup4<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// A segment in a format 2 or format 4 lookup table.
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct LookupSegment {
+    /// Last glyph index in this segment.
+    pub last_glyph: BigEndian<u16>,
+    /// First glyph index in this segment.
+    pub first_glyph: BigEndian<u16>,
+    /// The lookup value (format 2) or an offset to the lookup values
+    /// (format 4).
+    pub value: BigEndian<u16>,
+}
+
+impl LookupSegment {
+    /// Last glyph index in this segment.
+    pub fn last_glyph(&self) -> u16 {
+        self.last_glyph.get()
+    }
+
+    /// First glyph index in this segment.
+    pub fn first_glyph(&self) -> u16 {
+        self.first_glyph.get()
+    }
+
+    /// The lookup value (format 2) or an offset to the lookup values
+    /// (format 4).
+    pub fn value(&self) -> u16 {
+        self.value.get()
+    }
+}
+
+impl FixedSize for LookupSegment {
+    const RAW_BYTE_LEN: usize = u16::RAW_BYTE_LEN + u16::RAW_BYTE_LEN + u16::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for LookupSegment {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "LookupSegment",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("last_glyph", self.last_glyph())),
+                1usize => Some(Field::new("first_glyph", self.first_glyph())),
+                2usize => Some(Field::new("value", self.value())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
+
+impl Format<u16> for Lookup6Marker {
+    const FORMAT: u16 = 6;
+}
+
+/// Single table format; the lookup data is a sorted list of glyph and
+/// value pairs.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct Lookup6Marker {
+    entries_byte_len: usize,
+}
+
+impl Lookup6Marker {
+    fn format_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn unit_size_byte_range(&self) -> Range<usize> {
+        let start = self.format_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn n_units_byte_range(&self) -> Range<usize> {
+        let start = self.unit_size_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn search_range_byte_range(&self) -> Range<usize> {
+        let start = self.n_units_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn entry_selector_byte_range(&self) -> Range<usize> {
+        let start = self.search_range_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn range_shift_byte_range(&self) -> Range<usize> {
+        let start = self.entry_selector_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn entries_byte_range(&self) -> Range<usize> {
+        let start = self.range_shift_byte_range().end;
+        start..start + self.entries_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for Lookup6<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("format");
+        cursor.advance::<u16>();
+        cursor.set_field("unitSize");
+        cursor.advance::<u16>();
+        cursor.set_field("nUnits");
+        let n_units: u16 = cursor.read()?;
+        cursor.set_field("searchRange");
+        cursor.advance::<u16>();
+        cursor.set_field("entrySelector");
+        cursor.advance::<u16>();
+        cursor.set_field("rangeShift");
+        cursor.advance::<u16>();
+        cursor.set_field("entries");
+        let entries_byte_len = n_units as usize * LookupSingle::RAW_BYTE_LEN;
+        cursor.advance_by(entries_byte_len);
+        cursor.finish(Lookup6Marker { entries_byte_len })
+    }
+}
+
+/// Single table format; the lookup data is a sorted list of glyph and
+/// value pairs.
+pub type Lookup6<'a> = TableRef<'a, Lookup6Marker>;
+
+impl<'a> Lookup6<'a> {
+    /// Format number is set to 6.
+    pub fn format(&self) -> u16 {
+        let range = self.shape.format_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Size of a lookup unit for this search in bytes.
+    pub fn unit_size(&self) -> u16 {
+        let range = self.shape.unit_size_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of units of the preceding size to be searched.
+    pub fn n_units(&self) -> u16 {
+        let range = self.shape.n_units_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The value of `unit_size` times the largest power of 2 that is less
+    /// than or equal to `n_units`.
+    pub fn search_range(&self) -> u16 {
+        let range = self.shape.search_range_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The log base 2 of the largest power of 2 less than or equal to
+    /// `n_units`.
+    pub fn entry_selector(&self) -> u16 {
+        let range = self.shape.entry_selector_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The value of `unit_size` times the difference of `n_units` minus
+    /// the largest power of 2 less than or equal to `n_units`.
+    pub fn range_shift(&self) -> u16 {
+        let range = self.shape.range_shift_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Entries, sorted by glyph.
+    pub fn entries(&self) -> &'a [LookupSingle] {
+        let range = self.shape.entries_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Lookup6<'a> {
+    fn type_name(&self) -> &str {
+        "Lookup6"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("format", self.format())),
+            1usize => Some(Field::new("unit_size", self.unit_size())),
+            2usize => Some(Field::new("n_units", self.n_units())),
+            3usize => Some(Field::new("search_range", self.search_range())),
+            4usize => Some(Field::new("entry_selector", self.entry_selector())),
+            5usize => Some(Field::new("range_shift", self.range_shift())),
+            6usize => Some(Field::new(
+                "entries",
+                traversal::FieldType::array_of_records(
+                    stringify!(LookupSingle),
+                    self.entries(),
+                    self.offset_data(),
+                ),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Lookup6<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// An entry in a format 6 lookup table.
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct LookupSingle {
+    /// The glyph index.
+    pub glyph: BigEndian<u16>,
+    /// The lookup value.
+    pub value: BigEndian<u16>,
+}
+
+impl LookupSingle {
+    /// The glyph index.
+    pub fn glyph(&self) -> u16 {
+        self.glyph.get()
+    }
+
+    /// The lookup value.
+    pub fn value(&self) -> u16 {
+        self.value.get()
+    }
+}
+
+impl FixedSize for LookupSingle {
+    const RAW_BYTE_LEN: usize = u16::RAW_BYTE_LEN + u16::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for LookupSingle {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "LookupSingle",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("glyph", self.glyph())),
+                1usize => Some(Field::new("value", self.value())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
+
+impl Format<u16> for Lookup8Marker {
+    const FORMAT: u16 = 8;
+}
+
+/// Trimmed array format; the lookup data is an array of values for a
+/// contiguous range of glyphs.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct Lookup8Marker {
+    values_byte_len: usize,
+}
+
+impl Lookup8Marker {
+    fn format_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn first_glyph_byte_range(&self) -> Range<usize> {
+        let start = self.format_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn glyph_count_byte_range(&self) -> Range<usize> {
+        let start = self.first_glyph_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn values_byte_range(&self) -> Range<usize> {
+        let start = self.glyph_count_byte_range().end;
+        start..start + self.values_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for Lookup8<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("format");
+        cursor.advance::<u16>();
+        cursor.set_field("firstGlyph");
+        cursor.advance::<u16>();
+        cursor.set_field("glyphCount");
+        let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("values");
+        let values_byte_len = glyph_count as usize * u16::RAW_BYTE_LEN;
+        cursor.advance_by(values_byte_len);
+        cursor.finish(Lookup8Marker { values_byte_len })
+    }
+}
+
+/// Trimmed array format; the lookup data is an array of values for a
+/// contiguous range of glyphs.
+pub type Lookup8<'a> = TableRef<'a, Lookup8Marker>;
+
+impl<'a> Lookup8<'a> {
+    /// Format number is set to 8.
+    pub fn format(&self) -> u16 {
+        let range = self.shape.format_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// First glyph index included in the trimmed array.
+    pub fn first_glyph(&self) -> u16 {
+        let range = self.shape.first_glyph_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Total number of glyphs (equivalent to the last glyph minus the value
+    /// of `first_glyph` plus 1).
+    pub fn glyph_count(&self) -> u16 {
+        let range = self.shape.glyph_count_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The lookup values, indexed by glyph index minus `first_glyph`.
+    pub fn values(&self) -> &'a [BigEndian<u16>] {
+        let range = self.shape.values_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Lookup8<'a> {
+    fn type_name(&self) -> &str {
+        "Lookup8"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("format", self.format())),
+            1usize => Some(Field::new("first_glyph", self.first_glyph())),
+            2usize => Some(Field::new("glyph_count", self.glyph_count())),
+            3usize => Some(Field::new("values", self.values())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Lookup8<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+impl Format<u16> for Lookup10Marker {
+    const FORMAT: u16 = 10;
+}
+
+/// Extended trimmed array format; like format 8, but with values of
+/// arbitrary size.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct Lookup10Marker {
+    values_byte_len: usize,
+}
+
+impl Lookup10Marker {
+    fn format_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn unit_size_byte_range(&self) -> Range<usize> {
+        let start = self.format_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn first_glyph_byte_range(&self) -> Range<usize> {
+        let start = self.unit_size_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn glyph_count_byte_range(&self) -> Range<usize> {
+        let start = self.first_glyph_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn values_byte_range(&self) -> Range<usize> {
+        let start = self.glyph_count_byte_range().end;
+        start..start + self.values_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for Lookup10<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("format");
+        cursor.advance::<u16>();
+        cursor.set_field("unitSize");
+        cursor.advance::<u16>();
+        cursor.set_field("firstGlyph");
+        cursor.advance::<u16>();
+        cursor.set_field("glyphCount");
+        cursor.advance::<u16>();
+        cursor.set_field("values");
+        let values_byte_len = cursor.remaining_bytes();
+        cursor.advance_by(values_byte_len);
+        cursor.finish(Lookup10Marker { values_byte_len })
+    }
+}
+
+/// Extended trimmed array format; like format 8, but with values of
+/// arbitrary size.
+pub type Lookup10<'a> = TableRef<'a, Lookup10Marker>;
+
+impl<'a> Lookup10<'a> {
+    /// Format number is set to 10.
+    pub fn format(&self) -> u16 {
+        let range = self.shape.format_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Size of a lookup value in bytes.
+    pub fn unit_size(&self) -> u16 {
+        let range = self.shape.unit_size_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// First glyph index included in the trimmed array.
+    pub fn first_glyph(&self) -> u16 {
+        let range = self.shape.first_glyph_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Total number of glyphs (equivalent to the last glyph minus the value
+    /// of `first_glyph` plus 1).
+    pub fn glyph_count(&self) -> u16 {
+        let range = self.shape.glyph_count_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The raw lookup values, each `unit_size` bytes long.
+    pub fn values(&self) -> &'a [u8] {
+        let range = self.shape.values_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Lookup10<'a> {
+    fn type_name(&self) -> &str {
+        "Lookup10"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("format", self.format())),
+            1usize => Some(Field::new("unit_size", self.unit_size())),
+            2usize => Some(Field::new("first_glyph", self.first_glyph())),
+            3usize => Some(Field::new("glyph_count", self.glyph_count())),
+            4usize => Some(Field::new("values", self.values())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Lookup10<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
@@ -39,9 +39,13 @@ impl TopLevelTable for Avar<'_> {
 impl<'a> FontRead<'a> for Avar<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("Reserved");
         cursor.advance::<u16>();
+        cursor.set_field("axisCount");
         cursor.advance::<u16>();
+        cursor.set_field("axisSegmentMaps");
         let axis_segment_maps_byte_len = cursor.remaining_bytes();
         cursor.advance_by(axis_segment_maps_byte_len);
         cursor.finish(AvarMarker {
@@ -39,15 +39,19 @@ impl TopLevelTable for Base<'_> {
 impl<'a> FontRead<'a> for Base<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: MajorMinor = cursor.read()?;
+        cursor.set_field("horizAxisOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("vertAxisOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("itemVarStoreOffset");
         let item_var_store_offset_byte_start = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.position())
             .transpose()?;
         version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.advance::<Offset32>());
         cursor.finish(BaseMarker {
             item_var_store_offset_byte_start,
@@ -119,12 +123,9 @@ impl<'a> SomeTable<'a> for Base<'a> {
                 "vert_axis_offset",
                 FieldType::offset(self.vert_axis_offset(), self.vert_axis()),
             )),
-            3usize if version.compatible((1, 1)) => Some(Field::new(
+            3usize if version.compatible(MajorMinor::new(1, 1)) => Some(Field::new(
                 "item_var_store_offset",
-                FieldType::offset(
-                    self.item_var_store_offset().unwrap(),
-                    self.item_var_store().unwrap(),
-                ),
+                FieldType::offset(self.item_var_store_offset().unwrap(), self.item_var_store()),
             )),
             _ => None,
         }
@@ -157,7 +158,9 @@ impl AxisMarker {
 impl<'a> FontRead<'a> for Axis<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("baseTagListOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("baseScriptListOffset");
         cursor.advance::<Offset16>();
         cursor.finish(AxisMarker {})
     }
@@ -241,7 +244,9 @@ impl BaseTagListMarker {
 impl<'a> FontRead<'a> for BaseTagList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("baseTagCount");
         let base_tag_count: u16 = cursor.read()?;
+        cursor.set_field("baselineTags");
         let baseline_tags_byte_len = base_tag_count as usize * Tag::RAW_BYTE_LEN;
         cursor.advance_by(baseline_tags_byte_len);
         cursor.finish(BaseTagListMarker {
@@ -311,7 +316,9 @@ impl BaseScriptListMarker {
 impl<'a> FontRead<'a> for BaseScriptList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("baseScriptCount");
         let base_script_count: u16 = cursor.read()?;
+        cursor.set_field("baseScriptRecords");
         let base_script_records_byte_len =
             base_script_count as usize * BaseScriptRecord::RAW_BYTE_LEN;
         cursor.advance_by(base_script_records_byte_len);
@@ -446,9 +453,13 @@ impl BaseScriptMarker {
 impl<'a> FontRead<'a> for BaseScript<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("baseValuesOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("defaultMinMaxOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("baseLangSysCount");
         let base_lang_sys_count: u16 = cursor.read()?;
+        cursor.set_field("baseLangSysRecords");
         let base_lang_sys_records_byte_len =
             base_lang_sys_count as usize * BaseLangSysRecord::RAW_BYTE_LEN;
         cursor.advance_by(base_lang_sys_records_byte_len);
@@ -614,8 +625,11 @@ impl BaseValuesMarker {
 impl<'a> FontRead<'a> for BaseValues<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("defaultBaselineIndex");
         cursor.advance::<u16>();
+        cursor.set_field("baseCoordCount");
         let base_coord_count: u16 = cursor.read()?;
+        cursor.set_field("baseCoordOffsets");
         let base_coord_offsets_byte_len = base_coord_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(base_coord_offsets_byte_len);
         cursor.finish(BaseValuesMarker {
@@ -727,9 +741,13 @@ impl MinMaxMarker {
 impl<'a> FontRead<'a> for MinMax<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("minCoordOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("maxCoordOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("featMinMaxCount");
         let feat_min_max_count: u16 = cursor.read()?;
+        cursor.set_field("featMinMaxRecords");
         let feat_min_max_records_byte_len =
             feat_min_max_count as usize * FeatMinMaxRecord::RAW_BYTE_LEN;
         cursor.advance_by(feat_min_max_records_byte_len);
@@ -849,7 +867,7 @@ impl FeatMinMaxRecord {
     }
 
     /// Attempt to resolve [`min_coord_offset`][Self::min_coord_offset].
-    pub fn min_coord<'a>(&self, data: FontData<'a>) -> Option<Result<MinMax<'a>, ReadError>> {
+    pub fn min_coord<'a>(&self, data: FontData<'a>) -> Option<Result<BaseCoord<'a>, ReadError>> {
         self.min_coord_offset().resolve(data)
     }
 
@@ -860,7 +878,7 @@ impl FeatMinMaxRecord {
     }
 
     /// Attempt to resolve [`max_coord_offset`][Self::max_coord_offset].
-    pub fn max_coord<'a>(&self, data: FontData<'a>) -> Option<Result<MinMax<'a>, ReadError>> {
+    pub fn max_coord<'a>(&self, data: FontData<'a>) -> Option<Result<BaseCoord<'a>, ReadError>> {
         self.max_coord_offset().resolve(data)
     }
 }
@@ -960,7 +978,9 @@ impl BaseCoordFormat1Marker {
 impl<'a> FontRead<'a> for BaseCoordFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("baseCoordFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coordinate");
         cursor.advance::<i16>();
         cursor.finish(BaseCoordFormat1Marker {})
     }
@@ -1035,9 +1055,13 @@ impl BaseCoordFormat2Marker {
 impl<'a> FontRead<'a> for BaseCoordFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("baseCoordFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coordinate");
         cursor.advance::<i16>();
+        cursor.set_field("referenceGlyph");
         cursor.advance::<u16>();
+        cursor.set_field("baseCoordPoint");
         cursor.advance::<u16>();
         cursor.finish(BaseCoordFormat2Marker {})
     }
@@ -1122,8 +1146,11 @@ impl BaseCoordFormat3Marker {
 impl<'a> FontRead<'a> for BaseCoordFormat3<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("baseCoordFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coordinate");
         cursor.advance::<i16>();
+        cursor.set_field("deviceOffset");
         cursor.advance::<Offset16>();
         cursor.finish(BaseCoordFormat3Marker {})
     }
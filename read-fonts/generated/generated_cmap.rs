@@ -35,8 +35,11 @@ impl TopLevelTable for Cmap<'_> {
 impl<'a> FontRead<'a> for Cmap<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<u16>();
+        cursor.set_field("numTables");
         let num_tables: u16 = cursor.read()?;
+        cursor.set_field("encodingRecords");
         let encoding_records_byte_len = num_tables as usize * EncodingRecord::RAW_BYTE_LEN;
         cursor.advance_by(encoding_records_byte_len);
         cursor.finish(CmapMarker {
@@ -204,6 +207,20 @@ impl<'a> From<PlatformId> for FieldType<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for PlatformId {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(&[
+            Self::Unicode,
+            Self::Macintosh,
+            Self::ISO,
+            Self::Windows,
+            Self::Custom,
+        ])
+        .copied()
+    }
+}
+
 /// The different cmap subtable formats.
 pub enum CmapSubtable<'a> {
     Format0(Cmap0<'a>),
@@ -302,9 +319,13 @@ impl Cmap0Marker {
 impl<'a> FontRead<'a> for Cmap0<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("length");
         cursor.advance::<u16>();
+        cursor.set_field("language");
         cursor.advance::<u16>();
+        cursor.set_field("glyphIdArray");
         let glyph_id_array_byte_len = 256_usize * u8::RAW_BYTE_LEN;
         cursor.advance_by(glyph_id_array_byte_len);
         cursor.finish(Cmap0Marker {
@@ -375,6 +396,7 @@ impl Format<u16> for Cmap2Marker {
 #[doc(hidden)]
 pub struct Cmap2Marker {
     sub_header_keys_byte_len: usize,
+    sub_header_data_byte_len: usize,
 }
 
 impl Cmap2Marker {
@@ -394,18 +416,30 @@ impl Cmap2Marker {
         let start = self.language_byte_range().end;
         start..start + self.sub_header_keys_byte_len
     }
+    fn sub_header_data_byte_range(&self) -> Range<usize> {
+        let start = self.sub_header_keys_byte_range().end;
+        start..start + self.sub_header_data_byte_len
+    }
 }
 
 impl<'a> FontRead<'a> for Cmap2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("length");
+        let length: u16 = cursor.read()?;
+        cursor.set_field("language");
         cursor.advance::<u16>();
-        cursor.advance::<u16>();
+        cursor.set_field("subHeaderKeys");
         let sub_header_keys_byte_len = 256_usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(sub_header_keys_byte_len);
+        cursor.set_field("subHeaderData");
+        let sub_header_data_byte_len = transforms::subtract(length, 518_usize) * u8::RAW_BYTE_LEN;
+        cursor.advance_by(sub_header_data_byte_len);
         cursor.finish(Cmap2Marker {
             sub_header_keys_byte_len,
+            sub_header_data_byte_len,
         })
     }
 }
@@ -439,6 +473,16 @@ impl<'a> Cmap2<'a> {
         let range = self.shape.sub_header_keys_byte_range();
         self.data.read_array(range).unwrap()
     }
+
+    /// The variable-length array of SubHeader records, followed by the
+    /// subarrays used for mapping the low byte of 2-byte characters.
+    ///
+    /// These are kept as raw bytes, since their lengths are only known
+    /// by inspecting the subHeaderKeys and SubHeader records.
+    pub fn sub_header_data(&self) -> &'a [u8] {
+        let range = self.shape.sub_header_data_byte_range();
+        self.data.read_array(range).unwrap()
+    }
 }
 
 #[cfg(feature = "traversal")]
@@ -452,6 +496,7 @@ impl<'a> SomeTable<'a> for Cmap2<'a> {
             1usize => Some(Field::new("length", self.length())),
             2usize => Some(Field::new("language", self.language())),
             3usize => Some(Field::new("sub_header_keys", self.sub_header_keys())),
+            4usize => Some(Field::new("sub_header_data", self.sub_header_data())),
             _ => None,
         }
     }
@@ -596,22 +641,35 @@ impl Cmap4Marker {
 impl<'a> FontRead<'a> for Cmap4<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("length");
         cursor.advance::<u16>();
+        cursor.set_field("language");
         cursor.advance::<u16>();
+        cursor.set_field("segCountX2");
         let seg_count_x2: u16 = cursor.read()?;
+        cursor.set_field("searchRange");
         cursor.advance::<u16>();
+        cursor.set_field("entrySelector");
         cursor.advance::<u16>();
+        cursor.set_field("rangeShift");
         cursor.advance::<u16>();
+        cursor.set_field("endCode");
         let end_code_byte_len = transforms::half(seg_count_x2) * u16::RAW_BYTE_LEN;
         cursor.advance_by(end_code_byte_len);
+        cursor.set_field("reservedPad");
         cursor.advance::<u16>();
+        cursor.set_field("startCode");
         let start_code_byte_len = transforms::half(seg_count_x2) * u16::RAW_BYTE_LEN;
         cursor.advance_by(start_code_byte_len);
+        cursor.set_field("idDelta");
         let id_delta_byte_len = transforms::half(seg_count_x2) * i16::RAW_BYTE_LEN;
         cursor.advance_by(id_delta_byte_len);
+        cursor.set_field("idRangeOffsets");
         let id_range_offsets_byte_len = transforms::half(seg_count_x2) * u16::RAW_BYTE_LEN;
         cursor.advance_by(id_range_offsets_byte_len);
+        cursor.set_field("glyphIdArray");
         let glyph_id_array_byte_len = cursor.remaining_bytes();
         cursor.advance_by(glyph_id_array_byte_len);
         cursor.finish(Cmap4Marker {
@@ -778,11 +836,17 @@ impl Cmap6Marker {
 impl<'a> FontRead<'a> for Cmap6<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("length");
         cursor.advance::<u16>();
+        cursor.set_field("language");
         cursor.advance::<u16>();
+        cursor.set_field("firstCode");
         cursor.advance::<u16>();
+        cursor.set_field("entryCount");
         let entry_count: u16 = cursor.read()?;
+        cursor.set_field("glyphIdArray");
         let glyph_id_array_byte_len = entry_count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(glyph_id_array_byte_len);
         cursor.finish(Cmap6Marker {
@@ -904,13 +968,20 @@ impl Cmap8Marker {
 impl<'a> FontRead<'a> for Cmap8<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("reserved");
         cursor.advance::<u16>();
+        cursor.set_field("length");
         cursor.advance::<u32>();
+        cursor.set_field("language");
         cursor.advance::<u32>();
+        cursor.set_field("is32");
         let is32_byte_len = 8192_usize * u8::RAW_BYTE_LEN;
         cursor.advance_by(is32_byte_len);
+        cursor.set_field("numGroups");
         let num_groups: u32 = cursor.read()?;
+        cursor.set_field("groups");
         let groups_byte_len = num_groups as usize * SequentialMapGroup::RAW_BYTE_LEN;
         cursor.advance_by(groups_byte_len);
         cursor.finish(Cmap8Marker {
@@ -1099,12 +1170,19 @@ impl Cmap10Marker {
 impl<'a> FontRead<'a> for Cmap10<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("reserved");
         cursor.advance::<u16>();
+        cursor.set_field("length");
         cursor.advance::<u32>();
+        cursor.set_field("language");
         cursor.advance::<u32>();
+        cursor.set_field("startCharCode");
         cursor.advance::<u32>();
+        cursor.set_field("numChars");
         cursor.advance::<u32>();
+        cursor.set_field("glyphIdArray");
         let glyph_id_array_byte_len = cursor.remaining_bytes();
         cursor.advance_by(glyph_id_array_byte_len);
         cursor.finish(Cmap10Marker {
@@ -1221,11 +1299,17 @@ impl Cmap12Marker {
 impl<'a> FontRead<'a> for Cmap12<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("reserved");
         cursor.advance::<u16>();
+        cursor.set_field("length");
         cursor.advance::<u32>();
+        cursor.set_field("language");
         cursor.advance::<u32>();
+        cursor.set_field("numGroups");
         let num_groups: u32 = cursor.read()?;
+        cursor.set_field("groups");
         let groups_byte_len = num_groups as usize * SequentialMapGroup::RAW_BYTE_LEN;
         cursor.advance_by(groups_byte_len);
         cursor.finish(Cmap12Marker { groups_byte_len })
@@ -1340,11 +1424,17 @@ impl Cmap13Marker {
 impl<'a> FontRead<'a> for Cmap13<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("reserved");
         cursor.advance::<u16>();
+        cursor.set_field("length");
         cursor.advance::<u32>();
+        cursor.set_field("language");
         cursor.advance::<u32>();
+        cursor.set_field("numGroups");
         let num_groups: u32 = cursor.read()?;
+        cursor.set_field("groups");
         let groups_byte_len = num_groups as usize * ConstantMapGroup::RAW_BYTE_LEN;
         cursor.advance_by(groups_byte_len);
         cursor.finish(Cmap13Marker { groups_byte_len })
@@ -1503,9 +1593,13 @@ impl Cmap14Marker {
 impl<'a> FontRead<'a> for Cmap14<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("length");
         cursor.advance::<u32>();
+        cursor.set_field("numVarSelectorRecords");
         let num_var_selector_records: u32 = cursor.read()?;
+        cursor.set_field("varSelector");
         let var_selector_byte_len =
             num_var_selector_records as usize * VariationSelector::RAW_BYTE_LEN;
         cursor.advance_by(var_selector_byte_len);
@@ -1672,7 +1766,9 @@ impl DefaultUvsMarker {
 impl<'a> FontRead<'a> for DefaultUvs<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("numUnicodeValueRanges");
         let num_unicode_value_ranges: u32 = cursor.read()?;
+        cursor.set_field("ranges");
         let ranges_byte_len = num_unicode_value_ranges as usize * UnicodeRange::RAW_BYTE_LEN;
         cursor.advance_by(ranges_byte_len);
         cursor.finish(DefaultUvsMarker { ranges_byte_len })
@@ -1748,7 +1844,9 @@ impl NonDefaultUvsMarker {
 impl<'a> FontRead<'a> for NonDefaultUvs<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("numUvsMappings");
         let num_uvs_mappings: u32 = cursor.read()?;
+        cursor.set_field("uvsMapping");
         let uvs_mapping_byte_len = num_uvs_mappings as usize * UvsMapping::RAW_BYTE_LEN;
         cursor.advance_by(uvs_mapping_byte_len);
         cursor.finish(NonDefaultUvsMarker {
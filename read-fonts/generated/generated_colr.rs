@@ -67,31 +67,41 @@ impl TopLevelTable for Colr<'_> {
 impl<'a> FontRead<'a> for Colr<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: u16 = cursor.read()?;
+        cursor.set_field("numBaseGlyphRecords");
         cursor.advance::<u16>();
+        cursor.set_field("baseGlyphRecordsOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("layerRecordsOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("numLayerRecords");
         cursor.advance::<u16>();
+        cursor.set_field("baseGlyphListOffset");
         let base_glyph_list_offset_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(1).then(|| cursor.advance::<Offset32>());
+        cursor.set_field("layerListOffset");
         let layer_list_offset_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(1).then(|| cursor.advance::<Offset32>());
+        cursor.set_field("clipListOffset");
         let clip_list_offset_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(1).then(|| cursor.advance::<Offset32>());
+        cursor.set_field("varIndexMapOffset");
         let var_index_map_offset_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(1).then(|| cursor.advance::<Offset32>());
+        cursor.set_field("itemVariationStoreOffset");
         let item_variation_store_offset_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
@@ -254,32 +264,26 @@ impl<'a> SomeTable<'a> for Colr<'a> {
                 "base_glyph_list_offset",
                 FieldType::offset(
                     self.base_glyph_list_offset().unwrap(),
-                    self.base_glyph_list().unwrap(),
+                    self.base_glyph_list(),
                 ),
             )),
             6usize if version.compatible(1) => Some(Field::new(
                 "layer_list_offset",
-                FieldType::offset(
-                    self.layer_list_offset().unwrap(),
-                    self.layer_list().unwrap(),
-                ),
+                FieldType::offset(self.layer_list_offset().unwrap(), self.layer_list()),
             )),
             7usize if version.compatible(1) => Some(Field::new(
                 "clip_list_offset",
-                FieldType::offset(self.clip_list_offset().unwrap(), self.clip_list().unwrap()),
+                FieldType::offset(self.clip_list_offset().unwrap(), self.clip_list()),
             )),
             8usize if version.compatible(1) => Some(Field::new(
                 "var_index_map_offset",
-                FieldType::offset(
-                    self.var_index_map_offset().unwrap(),
-                    self.var_index_map().unwrap(),
-                ),
+                FieldType::offset(self.var_index_map_offset().unwrap(), self.var_index_map()),
             )),
             9usize if version.compatible(1) => Some(Field::new(
                 "item_variation_store_offset",
                 FieldType::offset(
                     self.item_variation_store_offset().unwrap(),
-                    self.item_variation_store().unwrap(),
+                    self.item_variation_store(),
                 ),
             )),
             _ => None,
@@ -407,7 +411,9 @@ impl BaseGlyphListMarker {
 impl<'a> FontRead<'a> for BaseGlyphList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("numBaseGlyphPaintRecords");
         let num_base_glyph_paint_records: u32 = cursor.read()?;
+        cursor.set_field("baseGlyphPaintRecords");
         let base_glyph_paint_records_byte_len =
             num_base_glyph_paint_records as usize * BaseGlyphPaint::RAW_BYTE_LEN;
         cursor.advance_by(base_glyph_paint_records_byte_len);
@@ -534,7 +540,9 @@ impl LayerListMarker {
 impl<'a> FontRead<'a> for LayerList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("numLayers");
         let num_layers: u32 = cursor.read()?;
+        cursor.set_field("paintOffsets");
         let paint_offsets_byte_len = num_layers as usize * Offset32::RAW_BYTE_LEN;
         cursor.advance_by(paint_offsets_byte_len);
         cursor.finish(LayerListMarker {
@@ -626,8 +634,11 @@ impl ClipListMarker {
 impl<'a> FontRead<'a> for ClipList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("numClips");
         let num_clips: u32 = cursor.read()?;
+        cursor.set_field("clips");
         let clips_byte_len = num_clips as usize * Clip::RAW_BYTE_LEN;
         cursor.advance_by(clips_byte_len);
         cursor.finish(ClipListMarker { clips_byte_len })
@@ -824,10 +835,15 @@ impl ClipBoxFormat1Marker {
 impl<'a> FontRead<'a> for ClipBoxFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("xMin");
         cursor.advance::<FWord>();
+        cursor.set_field("yMin");
         cursor.advance::<FWord>();
+        cursor.set_field("xMax");
         cursor.advance::<FWord>();
+        cursor.set_field("yMax");
         cursor.advance::<FWord>();
         cursor.finish(ClipBoxFormat1Marker {})
     }
@@ -931,11 +947,17 @@ impl ClipBoxFormat2Marker {
 impl<'a> FontRead<'a> for ClipBoxFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("xMin");
         cursor.advance::<FWord>();
+        cursor.set_field("yMin");
         cursor.advance::<FWord>();
+        cursor.set_field("xMax");
         cursor.advance::<FWord>();
+        cursor.set_field("yMax");
         cursor.advance::<FWord>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(ClipBoxFormat2Marker {})
     }
@@ -1233,8 +1255,11 @@ impl ColorLineMarker {
 impl<'a> FontRead<'a> for ColorLine<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("extend");
         cursor.advance::<Extend>();
+        cursor.set_field("numStops");
         let num_stops: u16 = cursor.read()?;
+        cursor.set_field("colorStops");
         let color_stops_byte_len = num_stops as usize * ColorStop::RAW_BYTE_LEN;
         cursor.advance_by(color_stops_byte_len);
         cursor.finish(ColorLineMarker {
@@ -1319,8 +1344,11 @@ impl VarColorLineMarker {
 impl<'a> FontRead<'a> for VarColorLine<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("extend");
         cursor.advance::<Extend>();
+        cursor.set_field("numStops");
         let num_stops: u16 = cursor.read()?;
+        cursor.set_field("colorStops");
         let color_stops_byte_len = num_stops as usize * VarColorStop::RAW_BYTE_LEN;
         cursor.advance_by(color_stops_byte_len);
         cursor.finish(VarColorLineMarker {
@@ -1424,6 +1452,13 @@ impl<'a> From<Extend> for FieldType<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Extend {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(&[Self::Pad, Self::Repeat, Self::Reflect]).copied()
+    }
+}
+
 /// [Paint](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#paint-tables) tables
 pub enum Paint<'a> {
     ColrLayers(PaintColrLayers<'a>),
@@ -1607,8 +1642,11 @@ impl PaintColrLayersMarker {
 impl<'a> FontRead<'a> for PaintColrLayers<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("numLayers");
         cursor.advance::<u8>();
+        cursor.set_field("firstLayerIndex");
         cursor.advance::<u32>();
         cursor.finish(PaintColrLayersMarker {})
     }
@@ -1686,8 +1724,11 @@ impl PaintSolidMarker {
 impl<'a> FontRead<'a> for PaintSolid<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paletteIndex");
         cursor.advance::<u16>();
+        cursor.set_field("alpha");
         cursor.advance::<F2Dot14>();
         cursor.finish(PaintSolidMarker {})
     }
@@ -1769,9 +1810,13 @@ impl PaintVarSolidMarker {
 impl<'a> FontRead<'a> for PaintVarSolid<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paletteIndex");
         cursor.advance::<u16>();
+        cursor.set_field("alpha");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarSolidMarker {})
     }
@@ -1876,13 +1921,21 @@ impl PaintLinearGradientMarker {
 impl<'a> FontRead<'a> for PaintLinearGradient<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("colorLineOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("x0");
         cursor.advance::<FWord>();
+        cursor.set_field("y0");
         cursor.advance::<FWord>();
+        cursor.set_field("x1");
         cursor.advance::<FWord>();
+        cursor.set_field("y1");
         cursor.advance::<FWord>();
+        cursor.set_field("x2");
         cursor.advance::<FWord>();
+        cursor.set_field("y2");
         cursor.advance::<FWord>();
         cursor.finish(PaintLinearGradientMarker {})
     }
@@ -2028,14 +2081,23 @@ impl PaintVarLinearGradientMarker {
 impl<'a> FontRead<'a> for PaintVarLinearGradient<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("colorLineOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("x0");
         cursor.advance::<FWord>();
+        cursor.set_field("y0");
         cursor.advance::<FWord>();
+        cursor.set_field("x1");
         cursor.advance::<FWord>();
+        cursor.set_field("y1");
         cursor.advance::<FWord>();
+        cursor.set_field("x2");
         cursor.advance::<FWord>();
+        cursor.set_field("y2");
         cursor.advance::<FWord>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarLinearGradientMarker {})
     }
@@ -2190,13 +2252,21 @@ impl PaintRadialGradientMarker {
 impl<'a> FontRead<'a> for PaintRadialGradient<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("colorLineOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("x0");
         cursor.advance::<FWord>();
+        cursor.set_field("y0");
         cursor.advance::<FWord>();
+        cursor.set_field("radius0");
         cursor.advance::<UfWord>();
+        cursor.set_field("x1");
         cursor.advance::<FWord>();
+        cursor.set_field("y1");
         cursor.advance::<FWord>();
+        cursor.set_field("radius1");
         cursor.advance::<UfWord>();
         cursor.finish(PaintRadialGradientMarker {})
     }
@@ -2342,14 +2412,23 @@ impl PaintVarRadialGradientMarker {
 impl<'a> FontRead<'a> for PaintVarRadialGradient<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("colorLineOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("x0");
         cursor.advance::<FWord>();
+        cursor.set_field("y0");
         cursor.advance::<FWord>();
+        cursor.set_field("radius0");
         cursor.advance::<UfWord>();
+        cursor.set_field("x1");
         cursor.advance::<FWord>();
+        cursor.set_field("y1");
         cursor.advance::<FWord>();
+        cursor.set_field("radius1");
         cursor.advance::<UfWord>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarRadialGradientMarker {})
     }
@@ -2494,11 +2573,17 @@ impl PaintSweepGradientMarker {
 impl<'a> FontRead<'a> for PaintSweepGradient<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("colorLineOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
+        cursor.set_field("startAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("endAngle");
         cursor.advance::<F2Dot14>();
         cursor.finish(PaintSweepGradientMarker {})
     }
@@ -2624,12 +2709,19 @@ impl PaintVarSweepGradientMarker {
 impl<'a> FontRead<'a> for PaintVarSweepGradient<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("colorLineOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
+        cursor.set_field("startAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("endAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarSweepGradientMarker {})
     }
@@ -2748,8 +2840,11 @@ impl PaintGlyphMarker {
 impl<'a> FontRead<'a> for PaintGlyph<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("glyphId");
         cursor.advance::<GlyphId>();
         cursor.finish(PaintGlyphMarker {})
     }
@@ -2832,7 +2927,9 @@ impl PaintColrGlyphMarker {
 impl<'a> FontRead<'a> for PaintColrGlyph<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("glyphId");
         cursor.advance::<GlyphId>();
         cursor.finish(PaintColrGlyphMarker {})
     }
@@ -2903,8 +3000,11 @@ impl PaintTransformMarker {
 impl<'a> FontRead<'a> for PaintTransform<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("transformOffset");
         cursor.advance::<Offset24>();
         cursor.finish(PaintTransformMarker {})
     }
@@ -3000,8 +3100,11 @@ impl PaintVarTransformMarker {
 impl<'a> FontRead<'a> for PaintVarTransform<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("transformOffset");
         cursor.advance::<Offset24>();
         cursor.finish(PaintVarTransformMarker {})
     }
@@ -3105,11 +3208,17 @@ impl Affine2x3Marker {
 impl<'a> FontRead<'a> for Affine2x3<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("xx");
         cursor.advance::<Fixed>();
+        cursor.set_field("yx");
         cursor.advance::<Fixed>();
+        cursor.set_field("xy");
         cursor.advance::<Fixed>();
+        cursor.set_field("yy");
         cursor.advance::<Fixed>();
+        cursor.set_field("dx");
         cursor.advance::<Fixed>();
+        cursor.set_field("dy");
         cursor.advance::<Fixed>();
         cursor.finish(Affine2x3Marker {})
     }
@@ -3220,12 +3329,19 @@ impl VarAffine2x3Marker {
 impl<'a> FontRead<'a> for VarAffine2x3<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("xx");
         cursor.advance::<Fixed>();
+        cursor.set_field("yx");
         cursor.advance::<Fixed>();
+        cursor.set_field("xy");
         cursor.advance::<Fixed>();
+        cursor.set_field("yy");
         cursor.advance::<Fixed>();
+        cursor.set_field("dx");
         cursor.advance::<Fixed>();
+        cursor.set_field("dy");
         cursor.advance::<Fixed>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(VarAffine2x3Marker {})
     }
@@ -3339,9 +3455,13 @@ impl PaintTranslateMarker {
 impl<'a> FontRead<'a> for PaintTranslate<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("dx");
         cursor.advance::<FWord>();
+        cursor.set_field("dy");
         cursor.advance::<FWord>();
         cursor.finish(PaintTranslateMarker {})
     }
@@ -3443,10 +3563,15 @@ impl PaintVarTranslateMarker {
 impl<'a> FontRead<'a> for PaintVarTranslate<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("dx");
         cursor.advance::<FWord>();
+        cursor.set_field("dy");
         cursor.advance::<FWord>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarTranslateMarker {})
     }
@@ -3551,9 +3676,13 @@ impl PaintScaleMarker {
 impl<'a> FontRead<'a> for PaintScale<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("scaleX");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("scaleY");
         cursor.advance::<F2Dot14>();
         cursor.finish(PaintScaleMarker {})
     }
@@ -3655,10 +3784,15 @@ impl PaintVarScaleMarker {
 impl<'a> FontRead<'a> for PaintVarScale<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("scaleX");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("scaleY");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarScaleMarker {})
     }
@@ -3773,11 +3907,17 @@ impl PaintScaleAroundCenterMarker {
 impl<'a> FontRead<'a> for PaintScaleAroundCenter<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("scaleX");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("scaleY");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
         cursor.finish(PaintScaleAroundCenterMarker {})
     }
@@ -3901,12 +4041,19 @@ impl PaintVarScaleAroundCenterMarker {
 impl<'a> FontRead<'a> for PaintVarScaleAroundCenter<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("scaleX");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("scaleY");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarScaleAroundCenterMarker {})
     }
@@ -4025,8 +4172,11 @@ impl PaintScaleUniformMarker {
 impl<'a> FontRead<'a> for PaintScaleUniform<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("scale");
         cursor.advance::<F2Dot14>();
         cursor.finish(PaintScaleUniformMarker {})
     }
@@ -4117,9 +4267,13 @@ impl PaintVarScaleUniformMarker {
 impl<'a> FontRead<'a> for PaintVarScaleUniform<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("scale");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarScaleUniformMarker {})
     }
@@ -4222,10 +4376,15 @@ impl PaintScaleUniformAroundCenterMarker {
 impl<'a> FontRead<'a> for PaintScaleUniformAroundCenter<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("scale");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
         cursor.finish(PaintScaleUniformAroundCenterMarker {})
     }
@@ -4338,11 +4497,17 @@ impl PaintVarScaleUniformAroundCenterMarker {
 impl<'a> FontRead<'a> for PaintVarScaleUniformAroundCenter<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("scale");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarScaleUniformAroundCenterMarker {})
     }
@@ -4454,8 +4619,11 @@ impl PaintRotateMarker {
 impl<'a> FontRead<'a> for PaintRotate<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("angle");
         cursor.advance::<F2Dot14>();
         cursor.finish(PaintRotateMarker {})
     }
@@ -4547,9 +4715,13 @@ impl PaintVarRotateMarker {
 impl<'a> FontRead<'a> for PaintVarRotate<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("angle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarRotateMarker {})
     }
@@ -4652,10 +4824,15 @@ impl PaintRotateAroundCenterMarker {
 impl<'a> FontRead<'a> for PaintRotateAroundCenter<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("angle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
         cursor.finish(PaintRotateAroundCenterMarker {})
     }
@@ -4769,11 +4946,17 @@ impl PaintVarRotateAroundCenterMarker {
 impl<'a> FontRead<'a> for PaintVarRotateAroundCenter<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("angle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarRotateAroundCenterMarker {})
     }
@@ -4888,9 +5071,13 @@ impl PaintSkewMarker {
 impl<'a> FontRead<'a> for PaintSkew<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("xSkewAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("ySkewAngle");
         cursor.advance::<F2Dot14>();
         cursor.finish(PaintSkewMarker {})
     }
@@ -4994,10 +5181,15 @@ impl PaintVarSkewMarker {
 impl<'a> FontRead<'a> for PaintVarSkew<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("xSkewAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("ySkewAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarSkewMarker {})
     }
@@ -5114,11 +5306,17 @@ impl PaintSkewAroundCenterMarker {
 impl<'a> FontRead<'a> for PaintSkewAroundCenter<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("xSkewAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("ySkewAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
         cursor.finish(PaintSkewAroundCenterMarker {})
     }
@@ -5244,12 +5442,19 @@ impl PaintVarSkewAroundCenterMarker {
 impl<'a> FontRead<'a> for PaintVarSkewAroundCenter<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("paintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("xSkewAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("ySkewAngle");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("centerX");
         cursor.advance::<FWord>();
+        cursor.set_field("centerY");
         cursor.advance::<FWord>();
+        cursor.set_field("varIndexBase");
         cursor.advance::<u32>();
         cursor.finish(PaintVarSkewAroundCenterMarker {})
     }
@@ -5374,9 +5579,13 @@ impl PaintCompositeMarker {
 impl<'a> FontRead<'a> for PaintComposite<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("sourcePaintOffset");
         cursor.advance::<Offset24>();
+        cursor.set_field("compositeMode");
         cursor.advance::<CompositeMode>();
+        cursor.set_field("backdropPaintOffset");
         cursor.advance::<Offset24>();
         cursor.finish(PaintCompositeMarker {})
     }
@@ -5544,3 +5753,40 @@ impl<'a> From<CompositeMode> for FieldType<'a> {
         (src as u8).into()
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for CompositeMode {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(&[
+            Self::Clear,
+            Self::Src,
+            Self::Dest,
+            Self::SrcOver,
+            Self::DestOver,
+            Self::SrcIn,
+            Self::DestIn,
+            Self::SrcOut,
+            Self::DestOut,
+            Self::SrcAtop,
+            Self::DestAtop,
+            Self::Xor,
+            Self::Plus,
+            Self::Screen,
+            Self::Overlay,
+            Self::Darken,
+            Self::Lighten,
+            Self::ColorDodge,
+            Self::ColorBurn,
+            Self::HardLight,
+            Self::SoftLight,
+            Self::Difference,
+            Self::Exclusion,
+            Self::Multiply,
+            Self::HslHue,
+            Self::HslSaturation,
+            Self::HslColor,
+            Self::HslLuminosity,
+        ])
+        .copied()
+    }
+}
@@ -62,23 +62,32 @@ impl TopLevelTable for Cpal<'_> {
 impl<'a> FontRead<'a> for Cpal<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: u16 = cursor.read()?;
+        cursor.set_field("numPaletteEntries");
         cursor.advance::<u16>();
+        cursor.set_field("numPalettes");
         let num_palettes: u16 = cursor.read()?;
+        cursor.set_field("numColorRecords");
         cursor.advance::<u16>();
+        cursor.set_field("colorRecordsArrayOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("colorRecordIndices");
         let color_record_indices_byte_len = num_palettes as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(color_record_indices_byte_len);
+        cursor.set_field("paletteTypesArrayOffset");
         let palette_types_array_offset_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(1).then(|| cursor.advance::<Offset32>());
+        cursor.set_field("paletteLabelsArrayOffset");
         let palette_labels_array_offset_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(1).then(|| cursor.advance::<Offset32>());
+        cursor.set_field("paletteEntryLabelsArrayOffset");
         let palette_entry_labels_array_offset_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
@@ -236,21 +245,21 @@ impl<'a> SomeTable<'a> for Cpal<'a> {
                 "palette_types_array_offset",
                 FieldType::offset_to_array_of_scalars(
                     self.palette_types_array_offset().unwrap(),
-                    self.palette_types_array().unwrap(),
+                    self.palette_types_array(),
                 ),
             )),
             7usize if version.compatible(1) => Some(Field::new(
                 "palette_labels_array_offset",
                 FieldType::offset_to_array_of_scalars(
                     self.palette_labels_array_offset().unwrap(),
-                    self.palette_labels_array().unwrap(),
+                    self.palette_labels_array(),
                 ),
             )),
             8usize if version.compatible(1) => Some(Field::new(
                 "palette_entry_labels_array_offset",
                 FieldType::offset_to_array_of_scalars(
                     self.palette_entry_labels_array_offset().unwrap(),
-                    self.palette_entry_labels_array().unwrap(),
+                    self.palette_entry_labels_array(),
                 ),
             )),
             _ => None,
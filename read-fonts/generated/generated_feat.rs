@@ -0,0 +1,243 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[allow(unused_imports)]
+use crate::codegen_prelude::*;
+
+/// The Graphite [Feat (Feature)](https://github.com/silnrsi/graphite/blob/master/doc/table_feat.txt) table
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct FeatMarker {
+    features_byte_len: usize,
+}
+
+impl FeatMarker {
+    fn version_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + Version16Dot16::RAW_BYTE_LEN
+    }
+    fn num_feat_byte_range(&self) -> Range<usize> {
+        let start = self.version_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn _reserved1_byte_range(&self) -> Range<usize> {
+        let start = self.num_feat_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn _reserved2_byte_range(&self) -> Range<usize> {
+        let start = self._reserved1_byte_range().end;
+        start..start + u32::RAW_BYTE_LEN
+    }
+    fn features_byte_range(&self) -> Range<usize> {
+        let start = self._reserved2_byte_range().end;
+        start..start + self.features_byte_len
+    }
+}
+
+impl TopLevelTable for Feat<'_> {
+    /// `Feat`
+    const TAG: Tag = Tag::new(b"Feat");
+}
+
+impl<'a> FontRead<'a> for Feat<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("version");
+        cursor.advance::<Version16Dot16>();
+        cursor.set_field("numFeat");
+        let num_feat: u16 = cursor.read()?;
+        cursor.set_field("Reserved1");
+        cursor.advance::<u16>();
+        cursor.set_field("Reserved2");
+        cursor.advance::<u32>();
+        cursor.set_field("features");
+        let features_byte_len = num_feat as usize * FeatureDefn::RAW_BYTE_LEN;
+        cursor.advance_by(features_byte_len);
+        cursor.finish(FeatMarker { features_byte_len })
+    }
+}
+
+/// The Graphite [Feat (Feature)](https://github.com/silnrsi/graphite/blob/master/doc/table_feat.txt) table
+pub type Feat<'a> = TableRef<'a, FeatMarker>;
+
+impl<'a> Feat<'a> {
+    /// Table version; this crate supports version 2.0 and later.
+    pub fn version(&self) -> Version16Dot16 {
+        let range = self.shape.version_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of features.
+    pub fn num_feat(&self) -> u16 {
+        let range = self.shape.num_feat_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Array of feature definitions, sorted by feature id.
+    pub fn features(&self) -> &'a [FeatureDefn] {
+        let range = self.shape.features_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Feat<'a> {
+    fn type_name(&self) -> &str {
+        "Feat"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("version", self.version())),
+            1usize => Some(Field::new("num_feat", self.num_feat())),
+            2usize => Some(Field::new(
+                "features",
+                traversal::FieldType::array_of_records(
+                    stringify!(FeatureDefn),
+                    self.features(),
+                    self.offset_data(),
+                ),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Feat<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// A feature definition in the [Feat] table.
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct FeatureDefn {
+    /// The feature id; often a four-character tag.
+    pub id: BigEndian<u32>,
+    /// Number of settings for this feature.
+    pub num_settings: BigEndian<u16>,
+    pub _reserved: BigEndian<u16>,
+    /// Offset in bytes from the beginning of the Feat table to the
+    /// array of settings for this feature.
+    pub settings_offset: BigEndian<Offset32>,
+    /// Feature flags; bit 15 indicates the feature is hidden from the
+    /// user interface.
+    pub flags: BigEndian<u16>,
+    /// The name id of the feature label in the `name` table.
+    pub label: BigEndian<u16>,
+}
+
+impl FeatureDefn {
+    /// The feature id; often a four-character tag.
+    pub fn id(&self) -> u32 {
+        self.id.get()
+    }
+
+    /// Number of settings for this feature.
+    pub fn num_settings(&self) -> u16 {
+        self.num_settings.get()
+    }
+
+    /// Offset in bytes from the beginning of the Feat table to the
+    /// array of settings for this feature.
+    pub fn settings_offset(&self) -> Offset32 {
+        self.settings_offset.get()
+    }
+
+    /// Attempt to resolve [`settings_offset`][Self::settings_offset].
+    pub fn settings<'a>(&self, data: FontData<'a>) -> Result<&'a [FeatureSettingDefn], ReadError> {
+        let args = self.num_settings();
+        self.settings_offset().resolve_with_args(data, &args)
+    }
+
+    /// Feature flags; bit 15 indicates the feature is hidden from the
+    /// user interface.
+    pub fn flags(&self) -> u16 {
+        self.flags.get()
+    }
+
+    /// The name id of the feature label in the `name` table.
+    pub fn label(&self) -> u16 {
+        self.label.get()
+    }
+}
+
+impl FixedSize for FeatureDefn {
+    const RAW_BYTE_LEN: usize = u32::RAW_BYTE_LEN
+        + u16::RAW_BYTE_LEN
+        + u16::RAW_BYTE_LEN
+        + Offset32::RAW_BYTE_LEN
+        + u16::RAW_BYTE_LEN
+        + u16::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for FeatureDefn {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "FeatureDefn",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("id", self.id())),
+                1usize => Some(Field::new("num_settings", self.num_settings())),
+                2usize => Some(Field::new(
+                    "settings_offset",
+                    traversal::FieldType::offset_to_array_of_records(
+                        self.settings_offset(),
+                        self.settings(_data),
+                        stringify!(FeatureSettingDefn),
+                        _data,
+                    ),
+                )),
+                3usize => Some(Field::new("flags", self.flags())),
+                4usize => Some(Field::new("label", self.label())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
+
+/// A setting of a feature in the [Feat] table.
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct FeatureSettingDefn {
+    /// The value of the setting.
+    pub value: BigEndian<i16>,
+    /// The name id of the setting label in the `name` table.
+    pub label: BigEndian<u16>,
+}
+
+impl FeatureSettingDefn {
+    /// The value of the setting.
+    pub fn value(&self) -> i16 {
+        self.value.get()
+    }
+
+    /// The name id of the setting label in the `name` table.
+    pub fn label(&self) -> u16 {
+        self.label.get()
+    }
+}
+
+impl FixedSize for FeatureSettingDefn {
+    const RAW_BYTE_LEN: usize = i16::RAW_BYTE_LEN + u16::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for FeatureSettingDefn {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "FeatureSettingDefn",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("value", self.value())),
+                1usize => Some(Field::new("label", self.label())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
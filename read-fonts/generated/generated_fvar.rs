@@ -49,12 +49,19 @@ impl TopLevelTable for Fvar<'_> {
 impl<'a> FontRead<'a> for Fvar<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("axisInstanceArraysOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("Reserved");
         cursor.advance::<u16>();
+        cursor.set_field("axisCount");
         cursor.advance::<u16>();
+        cursor.set_field("axisSize");
         cursor.advance::<u16>();
+        cursor.set_field("instanceCount");
         cursor.advance::<u16>();
+        cursor.set_field("instanceSize");
         cursor.advance::<u16>();
         cursor.finish(FvarMarker {})
     }
@@ -175,8 +182,10 @@ impl<'a> FontReadWithArgs<'a> for AxisInstanceArrays<'a> {
     fn read_with_args(data: FontData<'a>, args: &(u16, u16, u16)) -> Result<Self, ReadError> {
         let (axis_count, instance_count, instance_size) = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("axes");
         let axes_byte_len = axis_count as usize * VariationAxisRecord::RAW_BYTE_LEN;
         cursor.advance_by(axes_byte_len);
+        cursor.set_field("instances");
         let instances_byte_len = instance_count as usize
             * <InstanceRecord as ComputeSize>::compute_size(&(axis_count, instance_size));
         cursor.advance_by(instances_byte_len);
@@ -267,7 +276,7 @@ pub struct VariationAxisRecord {
     /// Axis qualifiers — see details below.
     pub flags: BigEndian<u16>,
     /// The name ID for entries in the 'name' table that provide a display name for this axis.
-    pub axis_name_id: BigEndian<u16>,
+    pub axis_name_id: BigEndian<NameId>,
 }
 
 impl VariationAxisRecord {
@@ -297,7 +306,7 @@ impl VariationAxisRecord {
     }
 
     /// The name ID for entries in the 'name' table that provide a display name for this axis.
-    pub fn axis_name_id(&self) -> u16 {
+    pub fn axis_name_id(&self) -> NameId {
         self.axis_name_id.get()
     }
 }
@@ -308,7 +317,7 @@ impl FixedSize for VariationAxisRecord {
         + Fixed::RAW_BYTE_LEN
         + Fixed::RAW_BYTE_LEN
         + u16::RAW_BYTE_LEN
-        + u16::RAW_BYTE_LEN;
+        + NameId::RAW_BYTE_LEN;
 }
 
 #[cfg(feature = "traversal")]
@@ -52,24 +52,31 @@ impl TopLevelTable for Gdef<'_> {
 impl<'a> FontRead<'a> for Gdef<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: MajorMinor = cursor.read()?;
+        cursor.set_field("glyphClassDefOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("attachListOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("ligCaretListOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("markAttachClassDefOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("markGlyphSetsDefOffset");
         let mark_glyph_sets_def_offset_byte_start = version
-            .compatible((1, 2))
+            .compatible(MajorMinor::new(1, 2))
             .then(|| cursor.position())
             .transpose()?;
         version
-            .compatible((1, 2))
+            .compatible(MajorMinor::new(1, 2))
             .then(|| cursor.advance::<Offset16>());
+        cursor.set_field("itemVarStoreOffset");
         let item_var_store_offset_byte_start = version
-            .compatible((1, 3))
+            .compatible(MajorMinor::new(1, 3))
             .then(|| cursor.position())
             .transpose()?;
         version
-            .compatible((1, 3))
+            .compatible(MajorMinor::new(1, 3))
             .then(|| cursor.advance::<Offset32>());
         cursor.finish(GdefMarker {
             mark_glyph_sets_def_offset_byte_start,
@@ -195,19 +202,16 @@ impl<'a> SomeTable<'a> for Gdef<'a> {
                     self.mark_attach_class_def(),
                 ),
             )),
-            5usize if version.compatible((1, 2)) => Some(Field::new(
+            5usize if version.compatible(MajorMinor::new(1, 2)) => Some(Field::new(
                 "mark_glyph_sets_def_offset",
                 FieldType::offset(
                     self.mark_glyph_sets_def_offset().unwrap(),
-                    self.mark_glyph_sets_def().unwrap(),
+                    self.mark_glyph_sets_def(),
                 ),
             )),
-            6usize if version.compatible((1, 3)) => Some(Field::new(
+            6usize if version.compatible(MajorMinor::new(1, 3)) => Some(Field::new(
                 "item_var_store_offset",
-                FieldType::offset(
-                    self.item_var_store_offset().unwrap(),
-                    self.item_var_store().unwrap(),
-                ),
+                FieldType::offset(self.item_var_store_offset().unwrap(), self.item_var_store()),
             )),
             _ => None,
         }
@@ -266,6 +270,14 @@ impl<'a> From<GlyphClassDef> for FieldType<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for GlyphClassDef {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(&[Self::Base, Self::Ligature, Self::Mark, Self::Component])
+            .copied()
+    }
+}
+
 /// [Attachment Point List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#attachment-point-list-table)
 #[derive(Debug, Clone, Copy)]
 #[doc(hidden)]
@@ -291,8 +303,11 @@ impl AttachListMarker {
 impl<'a> FontRead<'a> for AttachList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("attachPointOffsets");
         let attach_point_offsets_byte_len = glyph_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(attach_point_offsets_byte_len);
         cursor.finish(AttachListMarker {
@@ -398,7 +413,9 @@ impl AttachPointMarker {
 impl<'a> FontRead<'a> for AttachPoint<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("pointCount");
         let point_count: u16 = cursor.read()?;
+        cursor.set_field("pointIndices");
         let point_indices_byte_len = point_count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(point_indices_byte_len);
         cursor.finish(AttachPointMarker {
@@ -470,8 +487,11 @@ impl LigCaretListMarker {
 impl<'a> FontRead<'a> for LigCaretList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("ligGlyphCount");
         let lig_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("ligGlyphOffsets");
         let lig_glyph_offsets_byte_len = lig_glyph_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(lig_glyph_offsets_byte_len);
         cursor.finish(LigCaretListMarker {
@@ -577,7 +597,9 @@ impl LigGlyphMarker {
 impl<'a> FontRead<'a> for LigGlyph<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("caretCount");
         let caret_count: u16 = cursor.read()?;
+        cursor.set_field("caretValueOffsets");
         let caret_value_offsets_byte_len = caret_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(caret_value_offsets_byte_len);
         cursor.finish(LigGlyphMarker {
@@ -716,7 +738,9 @@ impl CaretValueFormat1Marker {
 impl<'a> FontRead<'a> for CaretValueFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("caretValueFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coordinate");
         cursor.advance::<i16>();
         cursor.finish(CaretValueFormat1Marker {})
     }
@@ -783,7 +807,9 @@ impl CaretValueFormat2Marker {
 impl<'a> FontRead<'a> for CaretValueFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("caretValueFormat");
         cursor.advance::<u16>();
+        cursor.set_field("caretValuePointIndex");
         cursor.advance::<u16>();
         cursor.finish(CaretValueFormat2Marker {})
     }
@@ -857,8 +883,11 @@ impl CaretValueFormat3Marker {
 impl<'a> FontRead<'a> for CaretValueFormat3<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("caretValueFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coordinate");
         cursor.advance::<i16>();
+        cursor.set_field("deviceOffset");
         cursor.advance::<Offset16>();
         cursor.finish(CaretValueFormat3Marker {})
     }
@@ -949,8 +978,11 @@ impl MarkGlyphSetsMarker {
 impl<'a> FontRead<'a> for MarkGlyphSets<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("markGlyphSetCount");
         let mark_glyph_set_count: u16 = cursor.read()?;
+        cursor.set_field("coverageOffsets");
         let coverage_offsets_byte_len = mark_glyph_set_count as usize * Offset32::RAW_BYTE_LEN;
         cursor.advance_by(coverage_offsets_byte_len);
         cursor.finish(MarkGlyphSetsMarker {
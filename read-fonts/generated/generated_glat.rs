@@ -0,0 +1,239 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[allow(unused_imports)]
+use crate::codegen_prelude::*;
+
+/// The Graphite [Glat (Glyph Attributes)](https://github.com/silnrsi/graphite/blob/master/doc/table_glat_gloc.txt) table
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct GlatMarker {}
+
+impl GlatMarker {
+    fn version_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + Version16Dot16::RAW_BYTE_LEN
+    }
+}
+
+impl TopLevelTable for Glat<'_> {
+    /// `Glat`
+    const TAG: Tag = Tag::new(b"Glat");
+}
+
+impl<'a> FontRead<'a> for Glat<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("version");
+        cursor.advance::<Version16Dot16>();
+        cursor.finish(GlatMarker {})
+    }
+}
+
+/// The Graphite [Glat (Glyph Attributes)](https://github.com/silnrsi/graphite/blob/master/doc/table_glat_gloc.txt) table
+pub type Glat<'a> = TableRef<'a, GlatMarker>;
+
+impl<'a> Glat<'a> {
+    /// Table version; one of 1.0, 2.0 or 3.0.
+    ///
+    /// Version 3.0 tables are followed by a 32-bit word containing the
+    /// compression scheme and flags; see [Glat::compression] and
+    /// [Glat::has_octaboxes].
+    pub fn version(&self) -> Version16Dot16 {
+        let range = self.shape.version_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Glat<'a> {
+    fn type_name(&self) -> &str {
+        "Glat"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("version", self.version())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Glat<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// The bounding octabox of a glyph in a version 3.0 [Glat] table.
+///
+/// This is followed by one [SubBox] for each bit set in `bitmap`.
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct GlyphBox {
+    /// A 4x4 bitmap indicating which cells of the glyph's bounding box
+    /// contain sub-boxes.
+    pub bitmap: BigEndian<u16>,
+    /// Minimum of the negative diagonal, scaled to 0..255 of the bounding box.
+    pub diag_neg_min: u8,
+    /// Maximum of the negative diagonal, scaled to 0..255 of the bounding box.
+    pub diag_neg_max: u8,
+    /// Minimum of the positive diagonal, scaled to 0..255 of the bounding box.
+    pub diag_pos_min: u8,
+    /// Maximum of the positive diagonal, scaled to 0..255 of the bounding box.
+    pub diag_pos_max: u8,
+}
+
+impl GlyphBox {
+    /// A 4x4 bitmap indicating which cells of the glyph's bounding box
+    /// contain sub-boxes.
+    pub fn bitmap(&self) -> u16 {
+        self.bitmap.get()
+    }
+
+    /// Minimum of the negative diagonal, scaled to 0..255 of the bounding box.
+    pub fn diag_neg_min(&self) -> u8 {
+        self.diag_neg_min
+    }
+
+    /// Maximum of the negative diagonal, scaled to 0..255 of the bounding box.
+    pub fn diag_neg_max(&self) -> u8 {
+        self.diag_neg_max
+    }
+
+    /// Minimum of the positive diagonal, scaled to 0..255 of the bounding box.
+    pub fn diag_pos_min(&self) -> u8 {
+        self.diag_pos_min
+    }
+
+    /// Maximum of the positive diagonal, scaled to 0..255 of the bounding box.
+    pub fn diag_pos_max(&self) -> u8 {
+        self.diag_pos_max
+    }
+}
+
+impl FixedSize for GlyphBox {
+    const RAW_BYTE_LEN: usize = u16::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for GlyphBox {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "GlyphBox",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("bitmap", self.bitmap())),
+                1usize => Some(Field::new("diag_neg_min", self.diag_neg_min())),
+                2usize => Some(Field::new("diag_neg_max", self.diag_neg_max())),
+                3usize => Some(Field::new("diag_pos_min", self.diag_pos_min())),
+                4usize => Some(Field::new("diag_pos_max", self.diag_pos_max())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
+
+/// A sub-box of a [GlyphBox], with all values scaled to 0..255 of the
+/// glyph's bounding box.
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct SubBox {
+    /// Left edge.
+    pub left: u8,
+    /// Right edge.
+    pub right: u8,
+    /// Bottom edge.
+    pub bottom: u8,
+    /// Top edge.
+    pub top: u8,
+    /// Minimum of the positive diagonal.
+    pub diag_pos_min: u8,
+    /// Maximum of the positive diagonal.
+    pub diag_pos_max: u8,
+    /// Minimum of the negative diagonal.
+    pub diag_neg_min: u8,
+    /// Maximum of the negative diagonal.
+    pub diag_neg_max: u8,
+}
+
+impl SubBox {
+    /// Left edge.
+    pub fn left(&self) -> u8 {
+        self.left
+    }
+
+    /// Right edge.
+    pub fn right(&self) -> u8 {
+        self.right
+    }
+
+    /// Bottom edge.
+    pub fn bottom(&self) -> u8 {
+        self.bottom
+    }
+
+    /// Top edge.
+    pub fn top(&self) -> u8 {
+        self.top
+    }
+
+    /// Minimum of the positive diagonal.
+    pub fn diag_pos_min(&self) -> u8 {
+        self.diag_pos_min
+    }
+
+    /// Maximum of the positive diagonal.
+    pub fn diag_pos_max(&self) -> u8 {
+        self.diag_pos_max
+    }
+
+    /// Minimum of the negative diagonal.
+    pub fn diag_neg_min(&self) -> u8 {
+        self.diag_neg_min
+    }
+
+    /// Maximum of the negative diagonal.
+    pub fn diag_neg_max(&self) -> u8 {
+        self.diag_neg_max
+    }
+}
+
+impl FixedSize for SubBox {
+    const RAW_BYTE_LEN: usize = u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for SubBox {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "SubBox",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("left", self.left())),
+                1usize => Some(Field::new("right", self.right())),
+                2usize => Some(Field::new("bottom", self.bottom())),
+                3usize => Some(Field::new("top", self.top())),
+                4usize => Some(Field::new("diag_pos_min", self.diag_pos_min())),
+                5usize => Some(Field::new("diag_pos_max", self.diag_pos_max())),
+                6usize => Some(Field::new("diag_neg_min", self.diag_neg_min())),
+                7usize => Some(Field::new("diag_neg_max", self.diag_neg_max())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
@@ -102,16 +102,25 @@ impl SimpleGlyphMarker {
 impl<'a> FontRead<'a> for SimpleGlyph<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("numberOfContours");
         let number_of_contours: i16 = cursor.read()?;
+        cursor.set_field("xMin");
         cursor.advance::<i16>();
+        cursor.set_field("yMin");
         cursor.advance::<i16>();
+        cursor.set_field("xMax");
         cursor.advance::<i16>();
+        cursor.set_field("yMax");
         cursor.advance::<i16>();
+        cursor.set_field("endPtsOfContours");
         let end_pts_of_contours_byte_len = number_of_contours as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(end_pts_of_contours_byte_len);
+        cursor.set_field("instructionLength");
         let instruction_length: u16 = cursor.read()?;
+        cursor.set_field("instructions");
         let instructions_byte_len = instruction_length as usize * u8::RAW_BYTE_LEN;
         cursor.advance_by(instructions_byte_len);
+        cursor.set_field("glyphData");
         let glyph_data_byte_len = cursor.remaining_bytes();
         cursor.advance_by(glyph_data_byte_len);
         cursor.finish(SimpleGlyphMarker {
@@ -605,6 +614,13 @@ impl<'a> From<SimpleGlyphFlags> for FieldType<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for SimpleGlyphFlags {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Self::from_bits_truncate)
+    }
+}
+
 /// [CompositeGlyph](https://docs.microsoft.com/en-us/typography/opentype/spec/glyf#glyph-headers)
 #[derive(Debug, Clone, Copy)]
 #[doc(hidden)]
@@ -642,11 +658,17 @@ impl CompositeGlyphMarker {
 impl<'a> FontRead<'a> for CompositeGlyph<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("numberOfContours");
         cursor.advance::<i16>();
+        cursor.set_field("xMin");
         cursor.advance::<i16>();
+        cursor.set_field("yMin");
         cursor.advance::<i16>();
+        cursor.set_field("xMax");
         cursor.advance::<i16>();
+        cursor.set_field("yMax");
         cursor.advance::<i16>();
+        cursor.set_field("componentData");
         let component_data_byte_len = cursor.remaining_bytes();
         cursor.advance_by(component_data_byte_len);
         cursor.finish(CompositeGlyphMarker {
@@ -1101,6 +1123,13 @@ impl<'a> From<CompositeGlyphFlags> for FieldType<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for CompositeGlyphFlags {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Self::from_bits_truncate)
+    }
+}
+
 /// Simple or composite glyph.
 pub enum Glyph<'a> {
     Simple(SimpleGlyph<'a>),
@@ -44,16 +44,21 @@ impl TopLevelTable for Gpos<'_> {
 impl<'a> FontRead<'a> for Gpos<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: MajorMinor = cursor.read()?;
+        cursor.set_field("scriptListOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("featureListOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("lookupListOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("featureVariationsOffset");
         let feature_variations_offset_byte_start = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.position())
             .transpose()?;
         version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.advance::<Offset32>());
         cursor.finish(GposMarker {
             feature_variations_offset_byte_start,
@@ -141,11 +146,11 @@ impl<'a> SomeTable<'a> for Gpos<'a> {
                 "lookup_list_offset",
                 FieldType::offset(self.lookup_list_offset(), self.lookup_list()),
             )),
-            4usize if version.compatible((1, 1)) => Some(Field::new(
+            4usize if version.compatible(MajorMinor::new(1, 1)) => Some(Field::new(
                 "feature_variations_offset",
                 FieldType::offset(
                     self.feature_variations_offset().unwrap(),
-                    self.feature_variations().unwrap(),
+                    self.feature_variations(),
                 ),
             )),
             _ => None,
@@ -559,6 +564,13 @@ impl<'a> From<ValueFormat> for FieldType<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ValueFormat {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Self::from_bits_truncate)
+    }
+}
+
 /// [Anchor Tables](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos#anchor-tables)
 /// position one glyph with respect to another.
 pub enum AnchorTable<'a> {
@@ -634,8 +646,11 @@ impl AnchorFormat1Marker {
 impl<'a> FontRead<'a> for AnchorFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("anchorFormat");
         cursor.advance::<u16>();
+        cursor.set_field("xCoordinate");
         cursor.advance::<i16>();
+        cursor.set_field("yCoordinate");
         cursor.advance::<i16>();
         cursor.finish(AnchorFormat1Marker {})
     }
@@ -717,9 +732,13 @@ impl AnchorFormat2Marker {
 impl<'a> FontRead<'a> for AnchorFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("anchorFormat");
         cursor.advance::<u16>();
+        cursor.set_field("xCoordinate");
         cursor.advance::<i16>();
+        cursor.set_field("yCoordinate");
         cursor.advance::<i16>();
+        cursor.set_field("anchorPoint");
         cursor.advance::<u16>();
         cursor.finish(AnchorFormat2Marker {})
     }
@@ -812,10 +831,15 @@ impl AnchorFormat3Marker {
 impl<'a> FontRead<'a> for AnchorFormat3<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("anchorFormat");
         cursor.advance::<u16>();
+        cursor.set_field("xCoordinate");
         cursor.advance::<i16>();
+        cursor.set_field("yCoordinate");
         cursor.advance::<i16>();
+        cursor.set_field("xDeviceOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("yDeviceOffset");
         cursor.advance::<Offset16>();
         cursor.finish(AnchorFormat3Marker {})
     }
@@ -923,7 +947,9 @@ impl MarkArrayMarker {
 impl<'a> FontRead<'a> for MarkArray<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("markCount");
         let mark_count: u16 = cursor.read()?;
+        cursor.set_field("markRecords");
         let mark_records_byte_len = mark_count as usize * MarkRecord::RAW_BYTE_LEN;
         cursor.advance_by(mark_records_byte_len);
         cursor.finish(MarkArrayMarker {
@@ -1105,9 +1131,13 @@ impl SinglePosFormat1Marker {
 impl<'a> FontRead<'a> for SinglePosFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("posFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("valueFormat");
         let value_format: ValueFormat = cursor.read()?;
+        cursor.set_field("valueRecord");
         let value_record_byte_len = <ValueRecord as ComputeSize>::compute_size(&value_format);
         cursor.advance_by(value_record_byte_len);
         cursor.finish(SinglePosFormat1Marker {
@@ -1220,10 +1250,15 @@ impl SinglePosFormat2Marker {
 impl<'a> FontRead<'a> for SinglePosFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("posFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("valueFormat");
         let value_format: ValueFormat = cursor.read()?;
+        cursor.set_field("valueCount");
         let value_count: u16 = cursor.read()?;
+        cursor.set_field("valueRecords");
         let value_records_byte_len =
             value_count as usize * <ValueRecord as ComputeSize>::compute_size(&value_format);
         cursor.advance_by(value_records_byte_len);
@@ -1396,11 +1431,17 @@ impl PairPosFormat1Marker {
 impl<'a> FontRead<'a> for PairPosFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("posFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("valueFormat1");
         cursor.advance::<ValueFormat>();
+        cursor.set_field("valueFormat2");
         cursor.advance::<ValueFormat>();
+        cursor.set_field("pairSetCount");
         let pair_set_count: u16 = cursor.read()?;
+        cursor.set_field("pairSetOffsets");
         let pair_set_offsets_byte_len = pair_set_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(pair_set_offsets_byte_len);
         cursor.finish(PairPosFormat1Marker {
@@ -1541,7 +1582,9 @@ impl<'a> FontReadWithArgs<'a> for PairSet<'a> {
     ) -> Result<Self, ReadError> {
         let (value_format1, value_format2) = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("pairValueCount");
         let pair_value_count: u16 = cursor.read()?;
+        cursor.set_field("pairValueRecords");
         let pair_value_records_byte_len = pair_value_count as usize
             * <PairValueRecord as ComputeSize>::compute_size(&(value_format1, value_format2));
         cursor.advance_by(pair_value_records_byte_len);
@@ -1742,14 +1785,23 @@ impl PairPosFormat2Marker {
 impl<'a> FontRead<'a> for PairPosFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("posFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("valueFormat1");
         let value_format1: ValueFormat = cursor.read()?;
+        cursor.set_field("valueFormat2");
         let value_format2: ValueFormat = cursor.read()?;
+        cursor.set_field("classDef1Offset");
         cursor.advance::<Offset16>();
+        cursor.set_field("classDef2Offset");
         cursor.advance::<Offset16>();
+        cursor.set_field("class1Count");
         let class1_count: u16 = cursor.read()?;
+        cursor.set_field("class2Count");
         let class2_count: u16 = cursor.read()?;
+        cursor.set_field("class1Records");
         let class1_records_byte_len = class1_count as usize
             * <Class1Record as ComputeSize>::compute_size(&(
                 class2_count,
@@ -2059,9 +2111,13 @@ impl CursivePosFormat1Marker {
 impl<'a> FontRead<'a> for CursivePosFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("posFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("entryExitCount");
         let entry_exit_count: u16 = cursor.read()?;
+        cursor.set_field("entryExitRecord");
         let entry_exit_record_byte_len = entry_exit_count as usize * EntryExitRecord::RAW_BYTE_LEN;
         cursor.advance_by(entry_exit_record_byte_len);
         cursor.finish(CursivePosFormat1Marker {
@@ -2245,11 +2301,17 @@ impl MarkBasePosFormat1Marker {
 impl<'a> FontRead<'a> for MarkBasePosFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("posFormat");
         cursor.advance::<u16>();
+        cursor.set_field("markCoverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("baseCoverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("markClassCount");
         cursor.advance::<u16>();
+        cursor.set_field("markArrayOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("baseArrayOffset");
         cursor.advance::<Offset16>();
         cursor.finish(MarkBasePosFormat1Marker {})
     }
@@ -2389,7 +2451,9 @@ impl<'a> FontReadWithArgs<'a> for BaseArray<'a> {
     fn read_with_args(data: FontData<'a>, args: &u16) -> Result<Self, ReadError> {
         let mark_class_count = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("baseCount");
         let base_count: u16 = cursor.read()?;
+        cursor.set_field("baseRecords");
         let base_records_byte_len =
             base_count as usize * <BaseRecord as ComputeSize>::compute_size(&mark_class_count);
         cursor.advance_by(base_records_byte_len);
@@ -2565,11 +2629,17 @@ impl MarkLigPosFormat1Marker {
 impl<'a> FontRead<'a> for MarkLigPosFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("posFormat");
         cursor.advance::<u16>();
+        cursor.set_field("markCoverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("ligatureCoverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("markClassCount");
         cursor.advance::<u16>();
+        cursor.set_field("markArrayOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("ligatureArrayOffset");
         cursor.advance::<Offset16>();
         cursor.finish(MarkLigPosFormat1Marker {})
     }
@@ -2709,7 +2779,9 @@ impl<'a> FontReadWithArgs<'a> for LigatureArray<'a> {
     fn read_with_args(data: FontData<'a>, args: &u16) -> Result<Self, ReadError> {
         let mark_class_count = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("ligatureCount");
         let ligature_count: u16 = cursor.read()?;
+        cursor.set_field("ligatureAttachOffsets");
         let ligature_attach_offsets_byte_len = ligature_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(ligature_attach_offsets_byte_len);
         cursor.finish(LigatureArrayMarker {
@@ -2815,7 +2887,9 @@ impl<'a> FontReadWithArgs<'a> for LigatureAttach<'a> {
     fn read_with_args(data: FontData<'a>, args: &u16) -> Result<Self, ReadError> {
         let mark_class_count = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("componentCount");
         let component_count: u16 = cursor.read()?;
+        cursor.set_field("componentRecords");
         let component_records_byte_len = component_count as usize
             * <ComponentRecord as ComputeSize>::compute_size(&mark_class_count);
         cursor.advance_by(component_records_byte_len);
@@ -2991,11 +3065,17 @@ impl MarkMarkPosFormat1Marker {
 impl<'a> FontRead<'a> for MarkMarkPosFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("posFormat");
         cursor.advance::<u16>();
+        cursor.set_field("mark1CoverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("mark2CoverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("markClassCount");
         cursor.advance::<u16>();
+        cursor.set_field("mark1ArrayOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("mark2ArrayOffset");
         cursor.advance::<Offset16>();
         cursor.finish(MarkMarkPosFormat1Marker {})
     }
@@ -3135,7 +3215,9 @@ impl<'a> FontReadWithArgs<'a> for Mark2Array<'a> {
     fn read_with_args(data: FontData<'a>, args: &u16) -> Result<Self, ReadError> {
         let mark_class_count = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("mark2Count");
         let mark2_count: u16 = cursor.read()?;
+        cursor.set_field("mark2Records");
         let mark2_records_byte_len =
             mark2_count as usize * <Mark2Record as ComputeSize>::compute_size(&mark_class_count);
         cursor.advance_by(mark2_records_byte_len);
@@ -3311,8 +3393,11 @@ impl<T> Copy for ExtensionPosFormat1Marker<T> {}
 impl<'a, T> FontRead<'a> for ExtensionPosFormat1<'a, T> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("posFormat");
         cursor.advance::<u16>();
+        cursor.set_field("extensionLookupType");
         cursor.advance::<u16>();
+        cursor.set_field("extensionOffset");
         cursor.advance::<Offset32>();
         cursor.finish(ExtensionPosFormat1Marker {
             offset_type: std::marker::PhantomData,
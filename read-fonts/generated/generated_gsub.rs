@@ -43,16 +43,21 @@ impl TopLevelTable for Gsub<'_> {
 impl<'a> FontRead<'a> for Gsub<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: MajorMinor = cursor.read()?;
+        cursor.set_field("scriptListOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("featureListOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("lookupListOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("featureVariationsOffset");
         let feature_variations_offset_byte_start = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.position())
             .transpose()?;
         version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.advance::<Offset32>());
         cursor.finish(GsubMarker {
             feature_variations_offset_byte_start,
@@ -141,11 +146,11 @@ impl<'a> SomeTable<'a> for Gsub<'a> {
                 "lookup_list_offset",
                 FieldType::offset(self.lookup_list_offset(), self.lookup_list()),
             )),
-            4usize if version.compatible((1, 1)) => Some(Field::new(
+            4usize if version.compatible(MajorMinor::new(1, 1)) => Some(Field::new(
                 "feature_variations_offset",
                 FieldType::offset(
                     self.feature_variations_offset().unwrap(),
-                    self.feature_variations().unwrap(),
+                    self.feature_variations(),
                 ),
             )),
             _ => None,
@@ -286,8 +291,11 @@ impl SingleSubstFormat1Marker {
 impl<'a> FontRead<'a> for SingleSubstFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("substFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("deltaGlyphId");
         cursor.advance::<i16>();
         cursor.finish(SingleSubstFormat1Marker {})
     }
@@ -381,9 +389,13 @@ impl SingleSubstFormat2Marker {
 impl<'a> FontRead<'a> for SingleSubstFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("substFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("substituteGlyphIds");
         let substitute_glyph_ids_byte_len = glyph_count as usize * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(substitute_glyph_ids_byte_len);
         cursor.finish(SingleSubstFormat2Marker {
@@ -490,9 +502,13 @@ impl MultipleSubstFormat1Marker {
 impl<'a> FontRead<'a> for MultipleSubstFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("substFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("sequenceCount");
         let sequence_count: u16 = cursor.read()?;
+        cursor.set_field("sequenceOffsets");
         let sequence_offsets_byte_len = sequence_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(sequence_offsets_byte_len);
         cursor.finish(MultipleSubstFormat1Marker {
@@ -606,7 +622,9 @@ impl SequenceMarker {
 impl<'a> FontRead<'a> for Sequence<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("substituteGlyphIds");
         let substitute_glyph_ids_byte_len = glyph_count as usize * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(substitute_glyph_ids_byte_len);
         cursor.finish(SequenceMarker {
@@ -690,9 +708,13 @@ impl AlternateSubstFormat1Marker {
 impl<'a> FontRead<'a> for AlternateSubstFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("substFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("alternateSetCount");
         let alternate_set_count: u16 = cursor.read()?;
+        cursor.set_field("alternateSetOffsets");
         let alternate_set_offsets_byte_len = alternate_set_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(alternate_set_offsets_byte_len);
         cursor.finish(AlternateSubstFormat1Marker {
@@ -809,7 +831,9 @@ impl AlternateSetMarker {
 impl<'a> FontRead<'a> for AlternateSet<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("alternateGlyphIds");
         let alternate_glyph_ids_byte_len = glyph_count as usize * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(alternate_glyph_ids_byte_len);
         cursor.finish(AlternateSetMarker {
@@ -892,9 +916,13 @@ impl LigatureSubstFormat1Marker {
 impl<'a> FontRead<'a> for LigatureSubstFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("substFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("ligatureSetCount");
         let ligature_set_count: u16 = cursor.read()?;
+        cursor.set_field("ligatureSetOffsets");
         let ligature_set_offsets_byte_len = ligature_set_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(ligature_set_offsets_byte_len);
         cursor.finish(LigatureSubstFormat1Marker {
@@ -1008,7 +1036,9 @@ impl LigatureSetMarker {
 impl<'a> FontRead<'a> for LigatureSet<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("ligatureCount");
         let ligature_count: u16 = cursor.read()?;
+        cursor.set_field("ligatureOffsets");
         let ligature_offsets_byte_len = ligature_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(ligature_offsets_byte_len);
         cursor.finish(LigatureSetMarker {
@@ -1102,8 +1132,11 @@ impl LigatureMarker {
 impl<'a> FontRead<'a> for Ligature<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("ligatureGlyph");
         cursor.advance::<GlyphId>();
+        cursor.set_field("componentCount");
         let component_count: u16 = cursor.read()?;
+        cursor.set_field("componentGlyphIds");
         let component_glyph_ids_byte_len =
             transforms::subtract(component_count, 1_usize) * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(component_glyph_ids_byte_len);
@@ -1201,8 +1234,11 @@ impl<T> Copy for ExtensionSubstFormat1Marker<T> {}
 impl<'a, T> FontRead<'a> for ExtensionSubstFormat1<'a, T> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("substFormat");
         cursor.advance::<u16>();
+        cursor.set_field("extensionLookupType");
         cursor.advance::<u16>();
+        cursor.set_field("extensionOffset");
         cursor.advance::<Offset32>();
         cursor.finish(ExtensionSubstFormat1Marker {
             offset_type: std::marker::PhantomData,
@@ -1389,17 +1425,25 @@ impl ReverseChainSingleSubstFormat1Marker {
 impl<'a> FontRead<'a> for ReverseChainSingleSubstFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("substFormat");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("backtrackGlyphCount");
         let backtrack_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("backtrackCoverageOffsets");
         let backtrack_coverage_offsets_byte_len =
             backtrack_glyph_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(backtrack_coverage_offsets_byte_len);
+        cursor.set_field("lookaheadGlyphCount");
         let lookahead_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("lookaheadCoverageOffsets");
         let lookahead_coverage_offsets_byte_len =
             lookahead_glyph_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(lookahead_coverage_offsets_byte_len);
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("substituteGlyphIds");
         let substitute_glyph_ids_byte_len = glyph_count as usize * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(substitute_glyph_ids_byte_len);
         cursor.finish(ReverseChainSingleSubstFormat1Marker {
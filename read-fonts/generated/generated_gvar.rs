@@ -55,13 +55,21 @@ impl TopLevelTable for Gvar<'_> {
 impl<'a> FontRead<'a> for Gvar<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("axisCount");
         cursor.advance::<u16>();
+        cursor.set_field("sharedTupleCount");
         cursor.advance::<u16>();
+        cursor.set_field("sharedTuplesOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("flags");
         let flags: GvarFlags = cursor.read()?;
+        cursor.set_field("glyphVariationDataArrayOffset");
         cursor.advance::<u32>();
+        cursor.set_field("glyphVariationDataOffsets");
         let glyph_variation_data_offsets_byte_len =
             transforms::add(glyph_count, 1_usize) * <U16Or32 as ComputeSize>::compute_size(&flags);
         cursor.advance_by(glyph_variation_data_offsets_byte_len);
@@ -475,6 +483,13 @@ impl<'a> From<GvarFlags> for FieldType<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for GvarFlags {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Self::from_bits_truncate)
+    }
+}
+
 /// Array of tuple records shared across all glyph variation data tables.
 #[derive(Debug, Clone, Copy)]
 #[doc(hidden)]
@@ -498,6 +513,7 @@ impl<'a> FontReadWithArgs<'a> for SharedTuples<'a> {
     fn read_with_args(data: FontData<'a>, args: &(u16, u16)) -> Result<Self, ReadError> {
         let (shared_tuple_count, axis_count) = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("tuples");
         let tuples_byte_len =
             shared_tuple_count as usize * <Tuple as ComputeSize>::compute_size(&axis_count);
         cursor.advance_by(tuples_byte_len);
@@ -570,8 +586,11 @@ impl GlyphVariationDataHeaderMarker {
 impl<'a> FontRead<'a> for GlyphVariationDataHeader<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("tupleVariationCount");
         cursor.advance::<TupleVariationCount>();
+        cursor.set_field("serializedDataOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("tupleVariationHeaders");
         let tuple_variation_headers_byte_len = cursor.remaining_bytes();
         cursor.advance_by(tuple_variation_headers_byte_len);
         cursor.finish(GlyphVariationDataHeaderMarker {
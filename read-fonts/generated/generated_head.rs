@@ -89,22 +89,39 @@ impl TopLevelTable for Head<'_> {
 impl<'a> FontRead<'a> for Head<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("fontRevision");
         cursor.advance::<Fixed>();
+        cursor.set_field("checksumAdjustment");
         cursor.advance::<u32>();
+        cursor.set_field("magicNumber");
         cursor.advance::<u32>();
+        cursor.set_field("flags");
         cursor.advance::<u16>();
+        cursor.set_field("unitsPerEm");
         cursor.advance::<u16>();
+        cursor.set_field("created");
         cursor.advance::<LongDateTime>();
+        cursor.set_field("modified");
         cursor.advance::<LongDateTime>();
+        cursor.set_field("xMin");
         cursor.advance::<i16>();
+        cursor.set_field("yMin");
         cursor.advance::<i16>();
+        cursor.set_field("xMax");
         cursor.advance::<i16>();
+        cursor.set_field("yMax");
         cursor.advance::<i16>();
+        cursor.set_field("macStyle");
         cursor.advance::<u16>();
+        cursor.set_field("lowestRecPpem");
         cursor.advance::<u16>();
+        cursor.set_field("fontDirectionHint");
         cursor.advance::<i16>();
+        cursor.set_field("indexToLocFormat");
         cursor.advance::<i16>();
+        cursor.set_field("glyphDataFormat");
         cursor.advance::<i16>();
         cursor.finish(HeadMarker {})
     }
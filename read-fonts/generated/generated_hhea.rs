@@ -89,22 +89,39 @@ impl TopLevelTable for Hhea<'_> {
 impl<'a> FontRead<'a> for Hhea<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("ascender");
         cursor.advance::<FWord>();
+        cursor.set_field("descender");
         cursor.advance::<FWord>();
+        cursor.set_field("lineGap");
         cursor.advance::<FWord>();
+        cursor.set_field("advanceWidthMax");
         cursor.advance::<UfWord>();
+        cursor.set_field("minLeftSideBearing");
         cursor.advance::<FWord>();
+        cursor.set_field("minRightSideBearing");
         cursor.advance::<FWord>();
+        cursor.set_field("xMaxExtent");
         cursor.advance::<FWord>();
+        cursor.set_field("caretSlopeRise");
         cursor.advance::<i16>();
+        cursor.set_field("caretSlopeRun");
         cursor.advance::<i16>();
+        cursor.set_field("caretOffset");
         cursor.advance::<i16>();
+        cursor.set_field("reserved1");
         cursor.advance::<i16>();
+        cursor.set_field("reserved2");
         cursor.advance::<i16>();
+        cursor.set_field("reserved3");
         cursor.advance::<i16>();
+        cursor.set_field("reserved4");
         cursor.advance::<i16>();
+        cursor.set_field("metricDataFormat");
         cursor.advance::<i16>();
+        cursor.set_field("numberOfLongMetrics");
         cursor.advance::<u16>();
         cursor.finish(HheaMarker {})
     }
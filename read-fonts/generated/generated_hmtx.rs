@@ -37,8 +37,10 @@ impl<'a> FontReadWithArgs<'a> for Hmtx<'a> {
     fn read_with_args(data: FontData<'a>, args: &(u16, u16)) -> Result<Self, ReadError> {
         let (number_of_h_metrics, num_glyphs) = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("hMetrics");
         let h_metrics_byte_len = number_of_h_metrics as usize * LongMetric::RAW_BYTE_LEN;
         cursor.advance_by(h_metrics_byte_len);
+        cursor.set_field("leftSideBearings");
         let left_side_bearings_byte_len =
             transforms::subtract(num_glyphs, number_of_h_metrics) * i16::RAW_BYTE_LEN;
         cursor.advance_by(left_side_bearings_byte_len);
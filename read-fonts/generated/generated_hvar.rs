@@ -41,10 +41,15 @@ impl TopLevelTable for Hvar<'_> {
 impl<'a> FontRead<'a> for Hvar<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("itemVariationStoreOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("advanceWidthMappingOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("lsbMappingOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("rsbMappingOffset");
         cursor.advance::<Offset32>();
         cursor.finish(HvarMarker {})
     }
@@ -0,0 +1,321 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[allow(unused_imports)]
+use crate::codegen_prelude::*;
+
+/// The [kern (Kerning)](https://learn.microsoft.com/en-us/typography/opentype/spec/kern) table
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct KernMarker {
+    subtables_byte_len: usize,
+}
+
+impl KernMarker {
+    fn version_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn n_tables_byte_range(&self) -> Range<usize> {
+        let start = self.version_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn subtables_byte_range(&self) -> Range<usize> {
+        let start = self.n_tables_byte_range().end;
+        start..start + self.subtables_byte_len
+    }
+}
+
+impl TopLevelTable for Kern<'_> {
+    /// `kern`
+    const TAG: Tag = Tag::new(b"kern");
+}
+
+impl<'a> FontRead<'a> for Kern<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("version");
+        cursor.advance::<u16>();
+        cursor.set_field("nTables");
+        cursor.advance::<u16>();
+        cursor.set_field("subtables");
+        let subtables_byte_len = cursor.remaining_bytes();
+        cursor.advance_by(subtables_byte_len);
+        cursor.finish(KernMarker { subtables_byte_len })
+    }
+}
+
+/// The [kern (Kerning)](https://learn.microsoft.com/en-us/typography/opentype/spec/kern) table
+pub type Kern<'a> = TableRef<'a, KernMarker>;
+
+impl<'a> Kern<'a> {
+    /// Table version number; set to 0.
+    pub fn version(&self) -> u16 {
+        let range = self.shape.version_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of subtables in the kerning table.
+    pub fn n_tables(&self) -> u16 {
+        let range = self.shape.n_tables_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The kerning subtables.
+    pub fn subtables(&self) -> VarLenArray<'a, KernSubtable<'a>> {
+        let range = self.shape.subtables_byte_range();
+        VarLenArray::read(self.data.split_off(range.start).unwrap()).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Kern<'a> {
+    fn type_name(&self) -> &str {
+        "Kern"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("version", self.version())),
+            1usize => Some(Field::new("n_tables", self.n_tables())),
+            2usize => Some(Field::new(
+                "subtables",
+                traversal::FieldType::var_array(
+                    "KernSubtable",
+                    self.subtables(),
+                    self.offset_data(),
+                ),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Kern<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// A [kerning subtable](https://learn.microsoft.com/en-us/typography/opentype/spec/kern#kerning-subtables), with its header.
+#[derive(Clone, Debug)]
+pub struct KernSubtable<'a> {
+    /// Kern subtable version number; set to 0.
+    pub version: BigEndian<u16>,
+    /// Length of the subtable, in bytes (including this header).
+    pub length: BigEndian<u16>,
+    /// The format of the subtable in the high byte, and what type of
+    /// information it contains in the low byte.
+    pub coverage: BigEndian<u16>,
+    /// The format-specific subtable data.
+    pub data: &'a [u8],
+}
+
+impl<'a> KernSubtable<'a> {
+    /// Kern subtable version number; set to 0.
+    pub fn version(&self) -> u16 {
+        self.version.get()
+    }
+
+    /// Length of the subtable, in bytes (including this header).
+    pub fn length(&self) -> u16 {
+        self.length.get()
+    }
+
+    /// The format of the subtable in the high byte, and what type of
+    /// information it contains in the low byte.
+    pub fn coverage(&self) -> u16 {
+        self.coverage.get()
+    }
+
+    /// The format-specific subtable data.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for KernSubtable<'a> {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "KernSubtable",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("version", self.version())),
+                1usize => Some(Field::new("length", self.length())),
+                2usize => Some(Field::new("coverage", self.coverage())),
+                3usize => Some(Field::new("data", self.data())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
+
+/// [Format 0](https://learn.microsoft.com/en-us/typography/opentype/spec/kern#format-0) kerning subtable: ordered list of kerning pairs
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct Kern0Marker {
+    pairs_byte_len: usize,
+}
+
+impl Kern0Marker {
+    fn n_pairs_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn search_range_byte_range(&self) -> Range<usize> {
+        let start = self.n_pairs_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn entry_selector_byte_range(&self) -> Range<usize> {
+        let start = self.search_range_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn range_shift_byte_range(&self) -> Range<usize> {
+        let start = self.entry_selector_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn pairs_byte_range(&self) -> Range<usize> {
+        let start = self.range_shift_byte_range().end;
+        start..start + self.pairs_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for Kern0<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("nPairs");
+        let n_pairs: u16 = cursor.read()?;
+        cursor.set_field("searchRange");
+        cursor.advance::<u16>();
+        cursor.set_field("entrySelector");
+        cursor.advance::<u16>();
+        cursor.set_field("rangeShift");
+        cursor.advance::<u16>();
+        cursor.set_field("pairs");
+        let pairs_byte_len = n_pairs as usize * KernPair::RAW_BYTE_LEN;
+        cursor.advance_by(pairs_byte_len);
+        cursor.finish(Kern0Marker { pairs_byte_len })
+    }
+}
+
+/// [Format 0](https://learn.microsoft.com/en-us/typography/opentype/spec/kern#format-0) kerning subtable: ordered list of kerning pairs
+pub type Kern0<'a> = TableRef<'a, Kern0Marker>;
+
+impl<'a> Kern0<'a> {
+    /// This gives the number of kerning pairs in the table.
+    pub fn n_pairs(&self) -> u16 {
+        let range = self.shape.n_pairs_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The largest power of two less than or equal to the value of
+    /// nPairs, multiplied by the size in bytes of an entry in the table.
+    pub fn search_range(&self) -> u16 {
+        let range = self.shape.search_range_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// This is calculated as log2 of the largest power of two less
+    /// than or equal to the value of nPairs.
+    pub fn entry_selector(&self) -> u16 {
+        let range = self.shape.entry_selector_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The value of nPairs minus the largest power of two less than or
+    /// equal to nPairs, and then multiplied by the size in bytes of an
+    /// entry in the table.
+    pub fn range_shift(&self) -> u16 {
+        let range = self.shape.range_shift_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The kerning pairs, sorted by left and then right glyph.
+    pub fn pairs(&self) -> &'a [KernPair] {
+        let range = self.shape.pairs_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Kern0<'a> {
+    fn type_name(&self) -> &str {
+        "Kern0"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("n_pairs", self.n_pairs())),
+            1usize => Some(Field::new("search_range", self.search_range())),
+            2usize => Some(Field::new("entry_selector", self.entry_selector())),
+            3usize => Some(Field::new("range_shift", self.range_shift())),
+            4usize => Some(Field::new(
+                "pairs",
+                traversal::FieldType::array_of_records(
+                    stringify!(KernPair),
+                    self.pairs(),
+                    self.offset_data(),
+                ),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Kern0<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// A kerning pair in a [format 0](https://learn.microsoft.com/en-us/typography/opentype/spec/kern#format-0) subtable
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct KernPair {
+    /// The glyph index for the left-hand glyph in the kerning pair.
+    pub left: BigEndian<GlyphId>,
+    /// The glyph index for the right-hand glyph in the kerning pair.
+    pub right: BigEndian<GlyphId>,
+    /// The kerning value for the above pair, in font design units.
+    pub value: BigEndian<FWord>,
+}
+
+impl KernPair {
+    /// The glyph index for the left-hand glyph in the kerning pair.
+    pub fn left(&self) -> GlyphId {
+        self.left.get()
+    }
+
+    /// The glyph index for the right-hand glyph in the kerning pair.
+    pub fn right(&self) -> GlyphId {
+        self.right.get()
+    }
+
+    /// The kerning value for the above pair, in font design units.
+    pub fn value(&self) -> FWord {
+        self.value.get()
+    }
+}
+
+impl FixedSize for KernPair {
+    const RAW_BYTE_LEN: usize = GlyphId::RAW_BYTE_LEN + GlyphId::RAW_BYTE_LEN + FWord::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for KernPair {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "KernPair",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("left", self.left())),
+                1usize => Some(Field::new("right", self.right())),
+                2usize => Some(Field::new("value", self.value())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
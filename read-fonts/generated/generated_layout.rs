@@ -26,7 +26,9 @@ impl ScriptListMarker {
 impl<'a> FontRead<'a> for ScriptList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("scriptCount");
         let script_count: u16 = cursor.read()?;
+        cursor.set_field("scriptRecords");
         let script_records_byte_len = script_count as usize * ScriptRecord::RAW_BYTE_LEN;
         cursor.advance_by(script_records_byte_len);
         cursor.finish(ScriptListMarker {
@@ -155,8 +157,11 @@ impl ScriptMarker {
 impl<'a> FontRead<'a> for Script<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("defaultLangSysOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("langSysCount");
         let lang_sys_count: u16 = cursor.read()?;
+        cursor.set_field("langSysRecords");
         let lang_sys_records_byte_len = lang_sys_count as usize * LangSysRecord::RAW_BYTE_LEN;
         cursor.advance_by(lang_sys_records_byte_len);
         cursor.finish(ScriptMarker {
@@ -306,9 +311,13 @@ impl LangSysMarker {
 impl<'a> FontRead<'a> for LangSys<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("lookupOrderOffset");
         cursor.advance::<u16>();
+        cursor.set_field("requiredFeatureIndex");
         cursor.advance::<u16>();
+        cursor.set_field("featureIndexCount");
         let feature_index_count: u16 = cursor.read()?;
+        cursor.set_field("featureIndices");
         let feature_indices_byte_len = feature_index_count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(feature_indices_byte_len);
         cursor.finish(LangSysMarker {
@@ -391,7 +400,9 @@ impl FeatureListMarker {
 impl<'a> FontRead<'a> for FeatureList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("featureCount");
         let feature_count: u16 = cursor.read()?;
+        cursor.set_field("featureRecords");
         let feature_records_byte_len = feature_count as usize * FeatureRecord::RAW_BYTE_LEN;
         cursor.advance_by(feature_records_byte_len);
         cursor.finish(FeatureListMarker {
@@ -528,8 +539,11 @@ impl<'a> FontReadWithArgs<'a> for Feature<'a> {
     fn read_with_args(data: FontData<'a>, args: &Tag) -> Result<Self, ReadError> {
         let feature_tag = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("featureParamsOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("lookupIndexCount");
         let lookup_index_count: u16 = cursor.read()?;
+        cursor.set_field("lookupListIndices");
         let lookup_list_indices_byte_len = lookup_index_count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(lookup_list_indices_byte_len);
         cursor.finish(FeatureMarker {
@@ -635,7 +649,9 @@ impl<T> Copy for LookupListMarker<T> {}
 impl<'a, T> FontRead<'a> for LookupList<'a, T> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("lookupCount");
         let lookup_count: u16 = cursor.read()?;
+        cursor.set_field("lookupOffsets");
         let lookup_offsets_byte_len = lookup_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(lookup_offsets_byte_len);
         cursor.finish(LookupListMarker {
@@ -767,11 +783,16 @@ impl<T> Copy for LookupMarker<T> {}
 impl<'a, T> FontRead<'a> for Lookup<'a, T> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("lookupType");
         cursor.advance::<u16>();
+        cursor.set_field("lookupFlag");
         cursor.advance::<LookupFlag>();
+        cursor.set_field("subTableCount");
         let sub_table_count: u16 = cursor.read()?;
+        cursor.set_field("subtableOffsets");
         let subtable_offsets_byte_len = sub_table_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(subtable_offsets_byte_len);
+        cursor.set_field("markFilteringSet");
         cursor.advance::<u16>();
         cursor.finish(LookupMarker {
             subtable_offsets_byte_len,
@@ -909,8 +930,11 @@ impl CoverageFormat1Marker {
 impl<'a> FontRead<'a> for CoverageFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("coverageFormat");
         cursor.advance::<u16>();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("glyphArray");
         let glyph_array_byte_len = glyph_count as usize * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(glyph_array_byte_len);
         cursor.finish(CoverageFormat1Marker {
@@ -993,8 +1017,11 @@ impl CoverageFormat2Marker {
 impl<'a> FontRead<'a> for CoverageFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("coverageFormat");
         cursor.advance::<u16>();
+        cursor.set_field("rangeCount");
         let range_count: u16 = cursor.read()?;
+        cursor.set_field("rangeRecords");
         let range_records_byte_len = range_count as usize * RangeRecord::RAW_BYTE_LEN;
         cursor.advance_by(range_records_byte_len);
         cursor.finish(CoverageFormat2Marker {
@@ -1185,9 +1212,13 @@ impl ClassDefFormat1Marker {
 impl<'a> FontRead<'a> for ClassDefFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("classFormat");
         cursor.advance::<u16>();
+        cursor.set_field("startGlyphId");
         cursor.advance::<GlyphId>();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("classValueArray");
         let class_value_array_byte_len = glyph_count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(class_value_array_byte_len);
         cursor.finish(ClassDefFormat1Marker {
@@ -1277,8 +1308,11 @@ impl ClassDefFormat2Marker {
 impl<'a> FontRead<'a> for ClassDefFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("classFormat");
         cursor.advance::<u16>();
+        cursor.set_field("classRangeCount");
         let class_range_count: u16 = cursor.read()?;
+        cursor.set_field("classRangeRecords");
         let class_range_records_byte_len =
             class_range_count as usize * ClassRangeRecord::RAW_BYTE_LEN;
         cursor.advance_by(class_range_records_byte_len);
@@ -1509,9 +1543,13 @@ impl SequenceContextFormat1Marker {
 impl<'a> FontRead<'a> for SequenceContextFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("seqRuleSetCount");
         let seq_rule_set_count: u16 = cursor.read()?;
+        cursor.set_field("seqRuleSetOffsets");
         let seq_rule_set_offsets_byte_len = seq_rule_set_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(seq_rule_set_offsets_byte_len);
         cursor.finish(SequenceContextFormat1Marker {
@@ -1627,7 +1665,9 @@ impl SequenceRuleSetMarker {
 impl<'a> FontRead<'a> for SequenceRuleSet<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("seqRuleCount");
         let seq_rule_count: u16 = cursor.read()?;
+        cursor.set_field("seqRuleOffsets");
         let seq_rule_offsets_byte_len = seq_rule_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(seq_rule_offsets_byte_len);
         cursor.finish(SequenceRuleSetMarker {
@@ -1726,11 +1766,15 @@ impl SequenceRuleMarker {
 impl<'a> FontRead<'a> for SequenceRule<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("seqLookupCount");
         let seq_lookup_count: u16 = cursor.read()?;
+        cursor.set_field("inputSequence");
         let input_sequence_byte_len =
             transforms::subtract(glyph_count, 1_usize) * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(input_sequence_byte_len);
+        cursor.set_field("seqLookupRecords");
         let seq_lookup_records_byte_len =
             seq_lookup_count as usize * SequenceLookupRecord::RAW_BYTE_LEN;
         cursor.advance_by(seq_lookup_records_byte_len);
@@ -1837,10 +1881,15 @@ impl SequenceContextFormat2Marker {
 impl<'a> FontRead<'a> for SequenceContextFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("classDefOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("classSeqRuleSetCount");
         let class_seq_rule_set_count: u16 = cursor.read()?;
+        cursor.set_field("classSeqRuleSetOffsets");
         let class_seq_rule_set_offsets_byte_len =
             class_seq_rule_set_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(class_seq_rule_set_offsets_byte_len);
@@ -1977,7 +2026,9 @@ impl ClassSequenceRuleSetMarker {
 impl<'a> FontRead<'a> for ClassSequenceRuleSet<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("classSeqRuleCount");
         let class_seq_rule_count: u16 = cursor.read()?;
+        cursor.set_field("classSeqRuleOffsets");
         let class_seq_rule_offsets_byte_len =
             class_seq_rule_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(class_seq_rule_offsets_byte_len);
@@ -2082,11 +2133,15 @@ impl ClassSequenceRuleMarker {
 impl<'a> FontRead<'a> for ClassSequenceRule<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("seqLookupCount");
         let seq_lookup_count: u16 = cursor.read()?;
+        cursor.set_field("inputSequence");
         let input_sequence_byte_len =
             transforms::subtract(glyph_count, 1_usize) * u16::RAW_BYTE_LEN;
         cursor.advance_by(input_sequence_byte_len);
+        cursor.set_field("seqLookupRecords");
         let seq_lookup_records_byte_len =
             seq_lookup_count as usize * SequenceLookupRecord::RAW_BYTE_LEN;
         cursor.advance_by(seq_lookup_records_byte_len);
@@ -2195,11 +2250,16 @@ impl SequenceContextFormat3Marker {
 impl<'a> FontRead<'a> for SequenceContextFormat3<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("glyphCount");
         let glyph_count: u16 = cursor.read()?;
+        cursor.set_field("seqLookupCount");
         let seq_lookup_count: u16 = cursor.read()?;
+        cursor.set_field("coverageOffsets");
         let coverage_offsets_byte_len = glyph_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(coverage_offsets_byte_len);
+        cursor.set_field("seqLookupRecords");
         let seq_lookup_records_byte_len =
             seq_lookup_count as usize * SequenceLookupRecord::RAW_BYTE_LEN;
         cursor.advance_by(seq_lookup_records_byte_len);
@@ -2377,9 +2437,13 @@ impl ChainedSequenceContextFormat1Marker {
 impl<'a> FontRead<'a> for ChainedSequenceContextFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("chainedSeqRuleSetCount");
         let chained_seq_rule_set_count: u16 = cursor.read()?;
+        cursor.set_field("chainedSeqRuleSetOffsets");
         let chained_seq_rule_set_offsets_byte_len =
             chained_seq_rule_set_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(chained_seq_rule_set_offsets_byte_len);
@@ -2499,7 +2563,9 @@ impl ChainedSequenceRuleSetMarker {
 impl<'a> FontRead<'a> for ChainedSequenceRuleSet<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("chainedSeqRuleCount");
         let chained_seq_rule_count: u16 = cursor.read()?;
+        cursor.set_field("chainedSeqRuleOffsets");
         let chained_seq_rule_offsets_byte_len =
             chained_seq_rule_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(chained_seq_rule_offsets_byte_len);
@@ -2622,17 +2688,25 @@ impl ChainedSequenceRuleMarker {
 impl<'a> FontRead<'a> for ChainedSequenceRule<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("backtrackGlyphCount");
         let backtrack_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("backtrackSequence");
         let backtrack_sequence_byte_len = backtrack_glyph_count as usize * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(backtrack_sequence_byte_len);
+        cursor.set_field("inputGlyphCount");
         let input_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("inputSequence");
         let input_sequence_byte_len =
             transforms::subtract(input_glyph_count, 1_usize) * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(input_sequence_byte_len);
+        cursor.set_field("lookaheadGlyphCount");
         let lookahead_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("lookaheadSequence");
         let lookahead_sequence_byte_len = lookahead_glyph_count as usize * GlyphId::RAW_BYTE_LEN;
         cursor.advance_by(lookahead_sequence_byte_len);
+        cursor.set_field("seqLookupCount");
         let seq_lookup_count: u16 = cursor.read()?;
+        cursor.set_field("seqLookupRecords");
         let seq_lookup_records_byte_len =
             seq_lookup_count as usize * SequenceLookupRecord::RAW_BYTE_LEN;
         cursor.advance_by(seq_lookup_records_byte_len);
@@ -2783,12 +2857,19 @@ impl ChainedSequenceContextFormat2Marker {
 impl<'a> FontRead<'a> for ChainedSequenceContextFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("coverageOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("backtrackClassDefOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("inputClassDefOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("lookaheadClassDefOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("chainedClassSeqRuleSetCount");
         let chained_class_seq_rule_set_count: u16 = cursor.read()?;
+        cursor.set_field("chainedClassSeqRuleSetOffsets");
         let chained_class_seq_rule_set_offsets_byte_len =
             chained_class_seq_rule_set_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(chained_class_seq_rule_set_offsets_byte_len);
@@ -2965,7 +3046,9 @@ impl ChainedClassSequenceRuleSetMarker {
 impl<'a> FontRead<'a> for ChainedClassSequenceRuleSet<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("chainedClassSeqRuleCount");
         let chained_class_seq_rule_count: u16 = cursor.read()?;
+        cursor.set_field("chainedClassSeqRuleOffsets");
         let chained_class_seq_rule_offsets_byte_len =
             chained_class_seq_rule_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(chained_class_seq_rule_offsets_byte_len);
@@ -3088,17 +3171,25 @@ impl ChainedClassSequenceRuleMarker {
 impl<'a> FontRead<'a> for ChainedClassSequenceRule<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("backtrackGlyphCount");
         let backtrack_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("backtrackSequence");
         let backtrack_sequence_byte_len = backtrack_glyph_count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(backtrack_sequence_byte_len);
+        cursor.set_field("inputGlyphCount");
         let input_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("inputSequence");
         let input_sequence_byte_len =
             transforms::subtract(input_glyph_count, 1_usize) * u16::RAW_BYTE_LEN;
         cursor.advance_by(input_sequence_byte_len);
+        cursor.set_field("lookaheadGlyphCount");
         let lookahead_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("lookaheadSequence");
         let lookahead_sequence_byte_len = lookahead_glyph_count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(lookahead_sequence_byte_len);
+        cursor.set_field("seqLookupCount");
         let seq_lookup_count: u16 = cursor.read()?;
+        cursor.set_field("seqLookupRecords");
         let seq_lookup_records_byte_len =
             seq_lookup_count as usize * SequenceLookupRecord::RAW_BYTE_LEN;
         cursor.advance_by(seq_lookup_records_byte_len);
@@ -3261,19 +3352,28 @@ impl ChainedSequenceContextFormat3Marker {
 impl<'a> FontRead<'a> for ChainedSequenceContextFormat3<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("backtrackGlyphCount");
         let backtrack_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("backtrackCoverageOffsets");
         let backtrack_coverage_offsets_byte_len =
             backtrack_glyph_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(backtrack_coverage_offsets_byte_len);
+        cursor.set_field("inputGlyphCount");
         let input_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("inputCoverageOffsets");
         let input_coverage_offsets_byte_len = input_glyph_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(input_coverage_offsets_byte_len);
+        cursor.set_field("lookaheadGlyphCount");
         let lookahead_glyph_count: u16 = cursor.read()?;
+        cursor.set_field("lookaheadCoverageOffsets");
         let lookahead_coverage_offsets_byte_len =
             lookahead_glyph_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(lookahead_coverage_offsets_byte_len);
+        cursor.set_field("seqLookupCount");
         let seq_lookup_count: u16 = cursor.read()?;
+        cursor.set_field("seqLookupRecords");
         let seq_lookup_records_byte_len =
             seq_lookup_count as usize * SequenceLookupRecord::RAW_BYTE_LEN;
         cursor.advance_by(seq_lookup_records_byte_len);
@@ -3551,6 +3651,19 @@ impl<'a> From<DeltaFormat> for FieldType<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for DeltaFormat {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.choose(&[
+            Self::Local2BitDeltas,
+            Self::Local4BitDeltas,
+            Self::Local8BitDeltas,
+            Self::VariationIndex,
+        ])
+        .copied()
+    }
+}
+
 /// [Device Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#device-and-variationindex-tables)
 #[derive(Debug, Clone, Copy)]
 #[doc(hidden)]
@@ -3580,9 +3693,13 @@ impl DeviceMarker {
 impl<'a> FontRead<'a> for Device<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("startSize");
         let start_size: u16 = cursor.read()?;
+        cursor.set_field("endSize");
         let end_size: u16 = cursor.read()?;
+        cursor.set_field("deltaFormat");
         let delta_format: DeltaFormat = cursor.read()?;
+        cursor.set_field("deltaValue");
         let delta_value_byte_len =
             DeltaFormat::value_count(delta_format, start_size, end_size) * u16::RAW_BYTE_LEN;
         cursor.advance_by(delta_value_byte_len);
@@ -3667,8 +3784,11 @@ impl VariationIndexMarker {
 impl<'a> FontRead<'a> for VariationIndex<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("deltaSetOuterIndex");
         cursor.advance::<u16>();
+        cursor.set_field("deltaSetInnerIndex");
         cursor.advance::<u16>();
+        cursor.set_field("deltaFormat");
         cursor.advance::<u16>();
         cursor.finish(VariationIndexMarker {})
     }
@@ -3752,8 +3872,11 @@ impl FeatureVariationsMarker {
 impl<'a> FontRead<'a> for FeatureVariations<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("featureVariationRecordCount");
         let feature_variation_record_count: u32 = cursor.read()?;
+        cursor.set_field("featureVariationRecords");
         let feature_variation_records_byte_len =
             feature_variation_record_count as usize * FeatureVariationRecord::RAW_BYTE_LEN;
         cursor.advance_by(feature_variation_records_byte_len);
@@ -3906,7 +4029,9 @@ impl ConditionSetMarker {
 impl<'a> FontRead<'a> for ConditionSet<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("conditionCount");
         let condition_count: u16 = cursor.read()?;
+        cursor.set_field("conditionOffsets");
         let condition_offsets_byte_len = condition_count as usize * Offset32::RAW_BYTE_LEN;
         cursor.advance_by(condition_offsets_byte_len);
         cursor.finish(ConditionSetMarker {
@@ -4006,9 +4131,13 @@ impl ConditionFormat1Marker {
 impl<'a> FontRead<'a> for ConditionFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("axisIndex");
         cursor.advance::<u16>();
+        cursor.set_field("filterRangeMinValue");
         cursor.advance::<F2Dot14>();
+        cursor.set_field("filterRangeMaxValue");
         cursor.advance::<F2Dot14>();
         cursor.finish(ConditionFormat1Marker {})
     }
@@ -4100,8 +4229,11 @@ impl FeatureTableSubstitutionMarker {
 impl<'a> FontRead<'a> for FeatureTableSubstitution<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("substitutionCount");
         let substitution_count: u16 = cursor.read()?;
+        cursor.set_field("substitutions");
         let substitutions_byte_len =
             substitution_count as usize * FeatureTableSubstitutionRecord::RAW_BYTE_LEN;
         cursor.advance_by(substitutions_byte_len);
@@ -4243,10 +4375,15 @@ impl SizeParamsMarker {
 impl<'a> FontRead<'a> for SizeParams<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("designSize");
         cursor.advance::<u16>();
+        cursor.set_field("identifier");
         cursor.advance::<u16>();
+        cursor.set_field("nameEntry");
         cursor.advance::<u16>();
+        cursor.set_field("rangeStart");
         cursor.advance::<u16>();
+        cursor.set_field("rangeEnd");
         cursor.advance::<u16>();
         cursor.finish(SizeParamsMarker {})
     }
@@ -4348,7 +4485,9 @@ impl StylisticSetParamsMarker {
 impl<'a> FontRead<'a> for StylisticSetParams<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<u16>();
+        cursor.set_field("uiNameId");
         cursor.advance::<u16>();
         cursor.finish(StylisticSetParamsMarker {})
     }
@@ -4447,13 +4586,21 @@ impl CharacterVariantParamsMarker {
 impl<'a> FontRead<'a> for CharacterVariantParams<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("featUiLabelNameId");
         cursor.advance::<u16>();
+        cursor.set_field("featUiTooltipTextNameId");
         cursor.advance::<u16>();
+        cursor.set_field("sampleTextNameId");
         cursor.advance::<u16>();
+        cursor.set_field("numNamedParameters");
         cursor.advance::<u16>();
+        cursor.set_field("firstParamUiLabelNameId");
         cursor.advance::<u16>();
+        cursor.set_field("charCount");
         let char_count: u16 = cursor.read()?;
+        cursor.set_field("character");
         let character_byte_len = char_count as usize * Uint24::RAW_BYTE_LEN;
         cursor.advance_by(character_byte_len);
         cursor.finish(CharacterVariantParamsMarker { character_byte_len })
@@ -0,0 +1,157 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[allow(unused_imports)]
+use crate::codegen_prelude::*;
+
+/// The [lcar (Ligature Caret)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6lcar.html) table
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct LcarMarker {
+    lookup_data_byte_len: usize,
+}
+
+impl LcarMarker {
+    fn version_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + MajorMinor::RAW_BYTE_LEN
+    }
+    fn format_byte_range(&self) -> Range<usize> {
+        let start = self.version_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn lookup_data_byte_range(&self) -> Range<usize> {
+        let start = self.format_byte_range().end;
+        start..start + self.lookup_data_byte_len
+    }
+}
+
+impl TopLevelTable for Lcar<'_> {
+    /// `lcar`
+    const TAG: Tag = Tag::new(b"lcar");
+}
+
+impl<'a> FontRead<'a> for Lcar<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("version");
+        cursor.advance::<MajorMinor>();
+        cursor.set_field("format");
+        cursor.advance::<u16>();
+        cursor.set_field("lookupData");
+        let lookup_data_byte_len = cursor.remaining_bytes();
+        cursor.advance_by(lookup_data_byte_len);
+        cursor.finish(LcarMarker {
+            lookup_data_byte_len,
+        })
+    }
+}
+
+/// The [lcar (Ligature Caret)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6lcar.html) table
+pub type Lcar<'a> = TableRef<'a, LcarMarker>;
+
+impl<'a> Lcar<'a> {
+    /// Version number of the ligature caret table; set to 1.0.
+    pub fn version(&self) -> MajorMinor {
+        let range = self.shape.version_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Format of the ligature caret table; 0 for distance-based carets and
+    /// 1 for control point indices.
+    pub fn format(&self) -> u16 {
+        let range = self.shape.format_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Lcar<'a> {
+    fn type_name(&self) -> &str {
+        "Lcar"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("version", self.version())),
+            1usize => Some(Field::new("format", self.format())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Lcar<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// The ligature carets for a single glyph in the [Lcar] table.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct LigCaretClassEntryMarker {
+    partials_byte_len: usize,
+}
+
+impl LigCaretClassEntryMarker {
+    fn count_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn partials_byte_range(&self) -> Range<usize> {
+        let start = self.count_byte_range().end;
+        start..start + self.partials_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for LigCaretClassEntry<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("count");
+        let count: u16 = cursor.read()?;
+        cursor.set_field("partials");
+        let partials_byte_len = count as usize * i16::RAW_BYTE_LEN;
+        cursor.advance_by(partials_byte_len);
+        cursor.finish(LigCaretClassEntryMarker { partials_byte_len })
+    }
+}
+
+/// The ligature carets for a single glyph in the [Lcar] table.
+pub type LigCaretClassEntry<'a> = TableRef<'a, LigCaretClassEntryMarker>;
+
+impl<'a> LigCaretClassEntry<'a> {
+    /// Number of caret positions in this ligature.
+    pub fn count(&self) -> u16 {
+        let range = self.shape.count_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The caret positions, as distances in font units if the table format
+    /// is 0, or as control point indices if the table format is 1.
+    pub fn partials(&self) -> &'a [BigEndian<i16>] {
+        let range = self.shape.partials_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for LigCaretClassEntry<'a> {
+    fn type_name(&self) -> &str {
+        "LigCaretClassEntry"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("count", self.count())),
+            1usize => Some(Field::new("partials", self.partials())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for LigCaretClassEntry<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
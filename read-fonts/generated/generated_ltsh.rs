@@ -0,0 +1,93 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[allow(unused_imports)]
+use crate::codegen_prelude::*;
+
+/// The [LTSH (Linear Threshold)](https://learn.microsoft.com/en-us/typography/opentype/spec/ltsh) table
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct LtshMarker {
+    y_pels_byte_len: usize,
+}
+
+impl LtshMarker {
+    fn version_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn num_glyphs_byte_range(&self) -> Range<usize> {
+        let start = self.version_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn y_pels_byte_range(&self) -> Range<usize> {
+        let start = self.num_glyphs_byte_range().end;
+        start..start + self.y_pels_byte_len
+    }
+}
+
+impl TopLevelTable for Ltsh<'_> {
+    /// `LTSH`
+    const TAG: Tag = Tag::new(b"LTSH");
+}
+
+impl<'a> FontRead<'a> for Ltsh<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("version");
+        cursor.advance::<u16>();
+        cursor.set_field("numGlyphs");
+        let num_glyphs: u16 = cursor.read()?;
+        cursor.set_field("yPels");
+        let y_pels_byte_len = num_glyphs as usize * u8::RAW_BYTE_LEN;
+        cursor.advance_by(y_pels_byte_len);
+        cursor.finish(LtshMarker { y_pels_byte_len })
+    }
+}
+
+/// The [LTSH (Linear Threshold)](https://learn.microsoft.com/en-us/typography/opentype/spec/ltsh) table
+pub type Ltsh<'a> = TableRef<'a, LtshMarker>;
+
+impl<'a> Ltsh<'a> {
+    /// Table version; set to 0.
+    pub fn version(&self) -> u16 {
+        let range = self.shape.version_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of glyphs; should match the `maxp` table.
+    pub fn num_glyphs(&self) -> u16 {
+        let range = self.shape.num_glyphs_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The vertical pel height at which each glyph can be assumed to scale
+    /// linearly; 1 if the glyph always scales linearly.
+    pub fn y_pels(&self) -> &'a [u8] {
+        let range = self.shape.y_pels_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Ltsh<'a> {
+    fn type_name(&self) -> &str {
+        "Ltsh"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("version", self.version())),
+            1usize => Some(Field::new("num_glyphs", self.num_glyphs())),
+            2usize => Some(Field::new("y_pels", self.y_pels())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Ltsh<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
@@ -47,11 +47,17 @@ impl TopLevelTable for Mvar<'_> {
 impl<'a> FontRead<'a> for Mvar<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("Reserved");
         cursor.advance::<u16>();
+        cursor.set_field("valueRecordSize");
         cursor.advance::<u16>();
+        cursor.set_field("valueRecordCount");
         let value_record_count: u16 = cursor.read()?;
+        cursor.set_field("itemVariationStoreOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("valueRecords");
         let value_records_byte_len = value_record_count as usize * ValueRecord::RAW_BYTE_LEN;
         cursor.advance_by(value_records_byte_len);
         cursor.finish(MvarMarker {
@@ -50,11 +50,16 @@ impl TopLevelTable for Name<'_> {
 impl<'a> FontRead<'a> for Name<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: u16 = cursor.read()?;
+        cursor.set_field("count");
         let count: u16 = cursor.read()?;
+        cursor.set_field("storageOffset");
         cursor.advance::<u16>();
+        cursor.set_field("nameRecord");
         let name_record_byte_len = count as usize * NameRecord::RAW_BYTE_LEN;
         cursor.advance_by(name_record_byte_len);
+        cursor.set_field("langTagCount");
         let lang_tag_count_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
@@ -64,6 +69,7 @@ impl<'a> FontRead<'a> for Name<'a> {
             .then(|| cursor.read::<u16>())
             .transpose()?
             .unwrap_or(0);
+        cursor.set_field("langTagRecord");
         let lang_tag_record_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
@@ -222,7 +228,7 @@ pub struct NameRecord {
     /// Language ID.
     pub language_id: BigEndian<u16>,
     /// Name ID.
-    pub name_id: BigEndian<u16>,
+    pub name_id: BigEndian<NameId>,
     /// String length (in bytes).
     pub length: BigEndian<u16>,
     /// String offset from start of storage area (in bytes).
@@ -246,7 +252,7 @@ impl NameRecord {
     }
 
     /// Name ID.
-    pub fn name_id(&self) -> u16 {
+    pub fn name_id(&self) -> NameId {
         self.name_id.get()
     }
 
@@ -265,7 +271,7 @@ impl FixedSize for NameRecord {
     const RAW_BYTE_LEN: usize = u16::RAW_BYTE_LEN
         + u16::RAW_BYTE_LEN
         + u16::RAW_BYTE_LEN
-        + u16::RAW_BYTE_LEN
+        + NameId::RAW_BYTE_LEN
         + u16::RAW_BYTE_LEN
         + Offset16::RAW_BYTE_LEN;
 }
@@ -0,0 +1,147 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[allow(unused_imports)]
+use crate::codegen_prelude::*;
+
+/// The [opbd (Optical Bounds)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6opbd.html) table
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct OpbdMarker {
+    lookup_data_byte_len: usize,
+}
+
+impl OpbdMarker {
+    fn version_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + MajorMinor::RAW_BYTE_LEN
+    }
+    fn format_byte_range(&self) -> Range<usize> {
+        let start = self.version_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn lookup_data_byte_range(&self) -> Range<usize> {
+        let start = self.format_byte_range().end;
+        start..start + self.lookup_data_byte_len
+    }
+}
+
+impl TopLevelTable for Opbd<'_> {
+    /// `opbd`
+    const TAG: Tag = Tag::new(b"opbd");
+}
+
+impl<'a> FontRead<'a> for Opbd<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("version");
+        cursor.advance::<MajorMinor>();
+        cursor.set_field("format");
+        cursor.advance::<u16>();
+        cursor.set_field("lookupData");
+        let lookup_data_byte_len = cursor.remaining_bytes();
+        cursor.advance_by(lookup_data_byte_len);
+        cursor.finish(OpbdMarker {
+            lookup_data_byte_len,
+        })
+    }
+}
+
+/// The [opbd (Optical Bounds)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6opbd.html) table
+pub type Opbd<'a> = TableRef<'a, OpbdMarker>;
+
+impl<'a> Opbd<'a> {
+    /// Version number of the optical bounds table; set to 1.0.
+    pub fn version(&self) -> MajorMinor {
+        let range = self.shape.version_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Format of the optical bounds table; 0 for distances in font units and
+    /// 1 for control point indices.
+    pub fn format(&self) -> u16 {
+        let range = self.shape.format_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for Opbd<'a> {
+    fn type_name(&self) -> &str {
+        "Opbd"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("version", self.version())),
+            1usize => Some(Field::new("format", self.format())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Opbd<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// The optical bounds of a glyph in the [Opbd] table.
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct OpticalBounds {
+    /// Left side optical bound.
+    pub left: BigEndian<i16>,
+    /// Top side optical bound.
+    pub top: BigEndian<i16>,
+    /// Right side optical bound.
+    pub right: BigEndian<i16>,
+    /// Bottom side optical bound.
+    pub bottom: BigEndian<i16>,
+}
+
+impl OpticalBounds {
+    /// Left side optical bound.
+    pub fn left(&self) -> i16 {
+        self.left.get()
+    }
+
+    /// Top side optical bound.
+    pub fn top(&self) -> i16 {
+        self.top.get()
+    }
+
+    /// Right side optical bound.
+    pub fn right(&self) -> i16 {
+        self.right.get()
+    }
+
+    /// Bottom side optical bound.
+    pub fn bottom(&self) -> i16 {
+        self.bottom.get()
+    }
+}
+
+impl FixedSize for OpticalBounds {
+    const RAW_BYTE_LEN: usize =
+        i16::RAW_BYTE_LEN + i16::RAW_BYTE_LEN + i16::RAW_BYTE_LEN + i16::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for OpticalBounds {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "OpticalBounds",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("left", self.left())),
+                1usize => Some(Field::new("top", self.top())),
+                2usize => Some(Field::new("right", self.right())),
+                3usize => Some(Field::new("bottom", self.bottom())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
@@ -188,77 +188,116 @@ impl TopLevelTable for Os2<'_> {
 impl<'a> FontRead<'a> for Os2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: u16 = cursor.read()?;
+        cursor.set_field("xAvgCharWidth");
         cursor.advance::<i16>();
+        cursor.set_field("usWeightClass");
         cursor.advance::<u16>();
+        cursor.set_field("usWidthClass");
         cursor.advance::<u16>();
+        cursor.set_field("fsType");
         cursor.advance::<u16>();
+        cursor.set_field("ySubscriptXSize");
         cursor.advance::<i16>();
+        cursor.set_field("ySubscriptYSize");
         cursor.advance::<i16>();
+        cursor.set_field("ySubscriptXOffset");
         cursor.advance::<i16>();
+        cursor.set_field("ySubscriptYOffset");
         cursor.advance::<i16>();
+        cursor.set_field("ySuperscriptXSize");
         cursor.advance::<i16>();
+        cursor.set_field("ySuperscriptYSize");
         cursor.advance::<i16>();
+        cursor.set_field("ySuperscriptXOffset");
         cursor.advance::<i16>();
+        cursor.set_field("ySuperscriptYOffset");
         cursor.advance::<i16>();
+        cursor.set_field("yStrikeoutSize");
         cursor.advance::<i16>();
+        cursor.set_field("yStrikeoutPosition");
         cursor.advance::<i16>();
+        cursor.set_field("sFamilyClass");
         cursor.advance::<i16>();
+        cursor.set_field("panose10");
         let panose_10_byte_len = 10_usize * u8::RAW_BYTE_LEN;
         cursor.advance_by(panose_10_byte_len);
+        cursor.set_field("ulUnicodeRange1");
         cursor.advance::<u32>();
+        cursor.set_field("ulUnicodeRange2");
         cursor.advance::<u32>();
+        cursor.set_field("ulUnicodeRange3");
         cursor.advance::<u32>();
+        cursor.set_field("ulUnicodeRange4");
         cursor.advance::<u32>();
+        cursor.set_field("achVendId");
         cursor.advance::<Tag>();
+        cursor.set_field("fsSelection");
         cursor.advance::<u16>();
+        cursor.set_field("usFirstCharIndex");
         cursor.advance::<u16>();
+        cursor.set_field("usLastCharIndex");
         cursor.advance::<u16>();
+        cursor.set_field("sTypoAscender");
         cursor.advance::<i16>();
+        cursor.set_field("sTypoDescender");
         cursor.advance::<i16>();
+        cursor.set_field("sTypoLineGap");
         cursor.advance::<i16>();
+        cursor.set_field("usWinAscent");
         cursor.advance::<u16>();
+        cursor.set_field("usWinDescent");
         cursor.advance::<u16>();
+        cursor.set_field("ulCodePageRange1");
         let ul_code_page_range_1_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(1).then(|| cursor.advance::<u32>());
+        cursor.set_field("ulCodePageRange2");
         let ul_code_page_range_2_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(1).then(|| cursor.advance::<u32>());
+        cursor.set_field("sxHeight");
         let sx_height_byte_start = version
             .compatible(2)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(2).then(|| cursor.advance::<i16>());
+        cursor.set_field("sCapHeight");
         let s_cap_height_byte_start = version
             .compatible(2)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(2).then(|| cursor.advance::<i16>());
+        cursor.set_field("usDefaultChar");
         let us_default_char_byte_start = version
             .compatible(2)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(2).then(|| cursor.advance::<u16>());
+        cursor.set_field("usBreakChar");
         let us_break_char_byte_start = version
             .compatible(2)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(2).then(|| cursor.advance::<u16>());
+        cursor.set_field("usMaxContext");
         let us_max_context_byte_start = version
             .compatible(2)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(2).then(|| cursor.advance::<u16>());
+        cursor.set_field("usLowerOpticalPointSize");
         let us_lower_optical_point_size_byte_start = version
             .compatible(5)
             .then(|| cursor.position())
             .transpose()?;
         version.compatible(5).then(|| cursor.advance::<u16>());
+        cursor.set_field("usUpperOpticalPointSize");
         let us_upper_optical_point_size_byte_start = version
             .compatible(5)
             .then(|| cursor.position())
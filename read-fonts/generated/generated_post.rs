@@ -75,40 +75,52 @@ impl TopLevelTable for Post<'_> {
 impl<'a> FontRead<'a> for Post<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: Version16Dot16 = cursor.read()?;
+        cursor.set_field("italicAngle");
         cursor.advance::<Fixed>();
+        cursor.set_field("underlinePosition");
         cursor.advance::<FWord>();
+        cursor.set_field("underlineThickness");
         cursor.advance::<FWord>();
+        cursor.set_field("isFixedPitch");
         cursor.advance::<u32>();
+        cursor.set_field("minMemType42");
         cursor.advance::<u32>();
+        cursor.set_field("maxMemType42");
         cursor.advance::<u32>();
+        cursor.set_field("minMemType1");
         cursor.advance::<u32>();
+        cursor.set_field("maxMemType1");
         cursor.advance::<u32>();
+        cursor.set_field("numGlyphs");
         let num_glyphs_byte_start = version
-            .compatible((2, 0))
+            .compatible(Version16Dot16::new(2, 0))
             .then(|| cursor.position())
             .transpose()?;
         let num_glyphs = version
-            .compatible((2, 0))
+            .compatible(Version16Dot16::new(2, 0))
             .then(|| cursor.read::<u16>())
             .transpose()?
             .unwrap_or(0);
+        cursor.set_field("glyphNameIndex");
         let glyph_name_index_byte_start = version
-            .compatible((2, 0))
+            .compatible(Version16Dot16::new(2, 0))
             .then(|| cursor.position())
             .transpose()?;
         let glyph_name_index_byte_len = version
-            .compatible((2, 0))
+            .compatible(Version16Dot16::new(2, 0))
             .then_some(num_glyphs as usize * u16::RAW_BYTE_LEN);
         if let Some(value) = glyph_name_index_byte_len {
             cursor.advance_by(value);
         }
+        cursor.set_field("stringData");
         let string_data_byte_start = version
-            .compatible((2, 0))
+            .compatible(Version16Dot16::new(2, 0))
             .then(|| cursor.position())
             .transpose()?;
         let string_data_byte_len = version
-            .compatible((2, 0))
+            .compatible(Version16Dot16::new(2, 0))
             .then_some(cursor.remaining_bytes());
         if let Some(value) = string_data_byte_len {
             cursor.advance_by(value);
@@ -237,14 +249,14 @@ impl<'a> SomeTable<'a> for Post<'a> {
             6usize => Some(Field::new("max_mem_type42", self.max_mem_type42())),
             7usize => Some(Field::new("min_mem_type1", self.min_mem_type1())),
             8usize => Some(Field::new("max_mem_type1", self.max_mem_type1())),
-            9usize if version.compatible((2, 0)) => {
+            9usize if version.compatible(Version16Dot16::new(2, 0)) => {
                 Some(Field::new("num_glyphs", self.num_glyphs().unwrap()))
             }
-            10usize if version.compatible((2, 0)) => Some(Field::new(
+            10usize if version.compatible(Version16Dot16::new(2, 0)) => Some(Field::new(
                 "glyph_name_index",
                 self.glyph_name_index().unwrap(),
             )),
-            11usize if version.compatible((2, 0)) => {
+            11usize if version.compatible(Version16Dot16::new(2, 0)) => {
                 Some(Field::new("string_data", self.traverse_string_data()))
             }
             _ => None,
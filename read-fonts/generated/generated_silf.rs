@@ -0,0 +1,870 @@
+// THIS FILE IS AUTOGENERATED.
+// Any changes to this file will be overwritten.
+// For more information about how codegen works, see font-codegen/README.md
+
+#[allow(unused_imports)]
+use crate::codegen_prelude::*;
+
+/// A subtable of the [Silf] table, containing the rules for a single
+/// writing system.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct SilfSubtableMarker {
+    j_levels_byte_len: usize,
+    crit_features_byte_len: usize,
+    script_tags_byte_len: usize,
+    pass_offsets_byte_len: usize,
+    pseudo_maps_byte_len: usize,
+    trailing_data_byte_len: usize,
+}
+
+impl SilfSubtableMarker {
+    fn rule_version_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + Version16Dot16::RAW_BYTE_LEN
+    }
+    fn pass_offset_byte_range(&self) -> Range<usize> {
+        let start = self.rule_version_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn pseudos_offset_byte_range(&self) -> Range<usize> {
+        let start = self.pass_offset_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn max_glyph_id_byte_range(&self) -> Range<usize> {
+        let start = self.pseudos_offset_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn extra_ascent_byte_range(&self) -> Range<usize> {
+        let start = self.max_glyph_id_byte_range().end;
+        start..start + i16::RAW_BYTE_LEN
+    }
+    fn extra_descent_byte_range(&self) -> Range<usize> {
+        let start = self.extra_ascent_byte_range().end;
+        start..start + i16::RAW_BYTE_LEN
+    }
+    fn num_passes_byte_range(&self) -> Range<usize> {
+        let start = self.extra_descent_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn i_subst_byte_range(&self) -> Range<usize> {
+        let start = self.num_passes_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn i_pos_byte_range(&self) -> Range<usize> {
+        let start = self.i_subst_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn i_just_byte_range(&self) -> Range<usize> {
+        let start = self.i_pos_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn i_bidi_byte_range(&self) -> Range<usize> {
+        let start = self.i_just_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn flags_byte_range(&self) -> Range<usize> {
+        let start = self.i_bidi_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn max_pre_context_byte_range(&self) -> Range<usize> {
+        let start = self.flags_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn max_post_context_byte_range(&self) -> Range<usize> {
+        let start = self.max_pre_context_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn attr_pseudo_byte_range(&self) -> Range<usize> {
+        let start = self.max_post_context_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn attr_break_weight_byte_range(&self) -> Range<usize> {
+        let start = self.attr_pseudo_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn attr_directionality_byte_range(&self) -> Range<usize> {
+        let start = self.attr_break_weight_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn attr_mirroring_byte_range(&self) -> Range<usize> {
+        let start = self.attr_directionality_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn attr_skip_passes_byte_range(&self) -> Range<usize> {
+        let start = self.attr_mirroring_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn num_j_levels_byte_range(&self) -> Range<usize> {
+        let start = self.attr_skip_passes_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn j_levels_byte_range(&self) -> Range<usize> {
+        let start = self.num_j_levels_byte_range().end;
+        start..start + self.j_levels_byte_len
+    }
+    fn num_lig_comp_byte_range(&self) -> Range<usize> {
+        let start = self.j_levels_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn num_user_defn_byte_range(&self) -> Range<usize> {
+        let start = self.num_lig_comp_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn max_comp_per_lig_byte_range(&self) -> Range<usize> {
+        let start = self.num_user_defn_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn direction_byte_range(&self) -> Range<usize> {
+        let start = self.max_comp_per_lig_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn attr_collisions_byte_range(&self) -> Range<usize> {
+        let start = self.direction_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn _reserved1_byte_range(&self) -> Range<usize> {
+        let start = self.attr_collisions_byte_range().end;
+        start..start + Uint24::RAW_BYTE_LEN
+    }
+    fn num_crit_features_byte_range(&self) -> Range<usize> {
+        let start = self._reserved1_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn crit_features_byte_range(&self) -> Range<usize> {
+        let start = self.num_crit_features_byte_range().end;
+        start..start + self.crit_features_byte_len
+    }
+    fn _reserved2_byte_range(&self) -> Range<usize> {
+        let start = self.crit_features_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn num_script_tag_byte_range(&self) -> Range<usize> {
+        let start = self._reserved2_byte_range().end;
+        start..start + u8::RAW_BYTE_LEN
+    }
+    fn script_tags_byte_range(&self) -> Range<usize> {
+        let start = self.num_script_tag_byte_range().end;
+        start..start + self.script_tags_byte_len
+    }
+    fn lb_gid_byte_range(&self) -> Range<usize> {
+        let start = self.script_tags_byte_range().end;
+        start..start + GlyphId::RAW_BYTE_LEN
+    }
+    fn pass_offsets_byte_range(&self) -> Range<usize> {
+        let start = self.lb_gid_byte_range().end;
+        start..start + self.pass_offsets_byte_len
+    }
+    fn num_pseudo_byte_range(&self) -> Range<usize> {
+        let start = self.pass_offsets_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn search_pseudo_byte_range(&self) -> Range<usize> {
+        let start = self.num_pseudo_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn pseudo_selector_byte_range(&self) -> Range<usize> {
+        let start = self.search_pseudo_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn pseudo_shift_byte_range(&self) -> Range<usize> {
+        let start = self.pseudo_selector_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn pseudo_maps_byte_range(&self) -> Range<usize> {
+        let start = self.pseudo_shift_byte_range().end;
+        start..start + self.pseudo_maps_byte_len
+    }
+    fn trailing_data_byte_range(&self) -> Range<usize> {
+        let start = self.pseudo_maps_byte_range().end;
+        start..start + self.trailing_data_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for SilfSubtable<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("ruleVersion");
+        cursor.advance::<Version16Dot16>();
+        cursor.set_field("passOffset");
+        cursor.advance::<u16>();
+        cursor.set_field("pseudosOffset");
+        cursor.advance::<u16>();
+        cursor.set_field("maxGlyphId");
+        cursor.advance::<u16>();
+        cursor.set_field("extraAscent");
+        cursor.advance::<i16>();
+        cursor.set_field("extraDescent");
+        cursor.advance::<i16>();
+        cursor.set_field("numPasses");
+        let num_passes: u8 = cursor.read()?;
+        cursor.set_field("iSubst");
+        cursor.advance::<u8>();
+        cursor.set_field("iPos");
+        cursor.advance::<u8>();
+        cursor.set_field("iJust");
+        cursor.advance::<u8>();
+        cursor.set_field("iBidi");
+        cursor.advance::<u8>();
+        cursor.set_field("flags");
+        cursor.advance::<u8>();
+        cursor.set_field("maxPreContext");
+        cursor.advance::<u8>();
+        cursor.set_field("maxPostContext");
+        cursor.advance::<u8>();
+        cursor.set_field("attrPseudo");
+        cursor.advance::<u8>();
+        cursor.set_field("attrBreakWeight");
+        cursor.advance::<u8>();
+        cursor.set_field("attrDirectionality");
+        cursor.advance::<u8>();
+        cursor.set_field("attrMirroring");
+        cursor.advance::<u8>();
+        cursor.set_field("attrSkipPasses");
+        cursor.advance::<u8>();
+        cursor.set_field("numJLevels");
+        let num_j_levels: u8 = cursor.read()?;
+        cursor.set_field("jLevels");
+        let j_levels_byte_len = num_j_levels as usize * JustificationLevel::RAW_BYTE_LEN;
+        cursor.advance_by(j_levels_byte_len);
+        cursor.set_field("numLigComp");
+        cursor.advance::<u16>();
+        cursor.set_field("numUserDefn");
+        cursor.advance::<u8>();
+        cursor.set_field("maxCompPerLig");
+        cursor.advance::<u8>();
+        cursor.set_field("direction");
+        cursor.advance::<u8>();
+        cursor.set_field("attrCollisions");
+        cursor.advance::<u8>();
+        cursor.set_field("Reserved1");
+        cursor.advance::<Uint24>();
+        cursor.set_field("numCritFeatures");
+        let num_crit_features: u8 = cursor.read()?;
+        cursor.set_field("critFeatures");
+        let crit_features_byte_len = num_crit_features as usize * u16::RAW_BYTE_LEN;
+        cursor.advance_by(crit_features_byte_len);
+        cursor.set_field("Reserved2");
+        cursor.advance::<u8>();
+        cursor.set_field("numScriptTag");
+        let num_script_tag: u8 = cursor.read()?;
+        cursor.set_field("scriptTags");
+        let script_tags_byte_len = num_script_tag as usize * Tag::RAW_BYTE_LEN;
+        cursor.advance_by(script_tags_byte_len);
+        cursor.set_field("lbGid");
+        cursor.advance::<GlyphId>();
+        cursor.set_field("passOffsets");
+        let pass_offsets_byte_len = transforms::add(num_passes, 1_usize) * u32::RAW_BYTE_LEN;
+        cursor.advance_by(pass_offsets_byte_len);
+        cursor.set_field("numPseudo");
+        let num_pseudo: u16 = cursor.read()?;
+        cursor.set_field("searchPseudo");
+        cursor.advance::<u16>();
+        cursor.set_field("pseudoSelector");
+        cursor.advance::<u16>();
+        cursor.set_field("pseudoShift");
+        cursor.advance::<u16>();
+        cursor.set_field("pseudoMaps");
+        let pseudo_maps_byte_len = num_pseudo as usize * PseudoMap::RAW_BYTE_LEN;
+        cursor.advance_by(pseudo_maps_byte_len);
+        cursor.set_field("trailingData");
+        let trailing_data_byte_len = cursor.remaining_bytes();
+        cursor.advance_by(trailing_data_byte_len);
+        cursor.finish(SilfSubtableMarker {
+            j_levels_byte_len,
+            crit_features_byte_len,
+            script_tags_byte_len,
+            pass_offsets_byte_len,
+            pseudo_maps_byte_len,
+            trailing_data_byte_len,
+        })
+    }
+}
+
+/// A subtable of the [Silf] table, containing the rules for a single
+/// writing system.
+pub type SilfSubtable<'a> = TableRef<'a, SilfSubtableMarker>;
+
+impl<'a> SilfSubtable<'a> {
+    /// The version of the rules in this subtable.
+    pub fn rule_version(&self) -> Version16Dot16 {
+        let range = self.shape.rule_version_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Offset in bytes from the start of the subtable to the pass offsets.
+    pub fn pass_offset(&self) -> u16 {
+        let range = self.shape.pass_offset_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Offset in bytes from the start of the subtable to the pseudo glyph
+    /// map.
+    pub fn pseudos_offset(&self) -> u16 {
+        let range = self.shape.pseudos_offset_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The largest glyph id (including pseudo glyphs) used in the rules.
+    pub fn max_glyph_id(&self) -> u16 {
+        let range = self.shape.max_glyph_id_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Extra ascent to add to the font ascent.
+    pub fn extra_ascent(&self) -> i16 {
+        let range = self.shape.extra_ascent_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Extra descent to add to the font descent.
+    pub fn extra_descent(&self) -> i16 {
+        let range = self.shape.extra_descent_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of rendering passes.
+    pub fn num_passes(&self) -> u8 {
+        let range = self.shape.num_passes_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Index of the first substitution pass.
+    pub fn i_subst(&self) -> u8 {
+        let range = self.shape.i_subst_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Index of the first positioning pass.
+    pub fn i_pos(&self) -> u8 {
+        let range = self.shape.i_pos_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Index of the first justification pass.
+    pub fn i_just(&self) -> u8 {
+        let range = self.shape.i_just_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Index of the pass before which bidi processing occurs, or 0xFF if
+    /// there is none.
+    pub fn i_bidi(&self) -> u8 {
+        let range = self.shape.i_bidi_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Subtable flags.
+    pub fn flags(&self) -> u8 {
+        let range = self.shape.flags_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Maximum number of glyphs of pre-context in any rule.
+    pub fn max_pre_context(&self) -> u8 {
+        let range = self.shape.max_pre_context_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Maximum number of glyphs of post-context in any rule.
+    pub fn max_post_context(&self) -> u8 {
+        let range = self.shape.max_post_context_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Glyph attribute number of the actual glyph id of a pseudo glyph.
+    pub fn attr_pseudo(&self) -> u8 {
+        let range = self.shape.attr_pseudo_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Glyph attribute number of the breakweight attribute.
+    pub fn attr_break_weight(&self) -> u8 {
+        let range = self.shape.attr_break_weight_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Glyph attribute number of the directionality attribute.
+    pub fn attr_directionality(&self) -> u8 {
+        let range = self.shape.attr_directionality_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Glyph attribute number of the first mirroring attribute.
+    pub fn attr_mirroring(&self) -> u8 {
+        let range = self.shape.attr_mirroring_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Glyph attribute number of the first skip passes attribute.
+    pub fn attr_skip_passes(&self) -> u8 {
+        let range = self.shape.attr_skip_passes_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of justification levels.
+    pub fn num_j_levels(&self) -> u8 {
+        let range = self.shape.num_j_levels_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The justification levels.
+    pub fn j_levels(&self) -> &'a [JustificationLevel] {
+        let range = self.shape.j_levels_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+
+    /// Glyph attribute number of the first ligature component attribute.
+    pub fn num_lig_comp(&self) -> u16 {
+        let range = self.shape.num_lig_comp_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of user defined slot attributes.
+    pub fn num_user_defn(&self) -> u8 {
+        let range = self.shape.num_user_defn_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Maximum number of components per ligature.
+    pub fn max_comp_per_lig(&self) -> u8 {
+        let range = self.shape.max_comp_per_lig_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The default direction of the writing system.
+    pub fn direction(&self) -> u8 {
+        let range = self.shape.direction_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Glyph attribute number of the first collision attribute.
+    pub fn attr_collisions(&self) -> u8 {
+        let range = self.shape.attr_collisions_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Number of critical features.
+    pub fn num_crit_features(&self) -> u8 {
+        let range = self.shape.num_crit_features_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Ids of the critical features.
+    pub fn crit_features(&self) -> &'a [BigEndian<u16>] {
+        let range = self.shape.crit_features_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+
+    /// Number of scripts supported by this subtable.
+    pub fn num_script_tag(&self) -> u8 {
+        let range = self.shape.num_script_tag_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// The scripts supported by this subtable.
+    pub fn script_tags(&self) -> &'a [BigEndian<Tag>] {
+        let range = self.shape.script_tags_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+
+    /// The glyph id of the line-break pseudo glyph.
+    pub fn lb_gid(&self) -> GlyphId {
+        let range = self.shape.lb_gid_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Offsets in bytes from the start of the subtable to the start of each
+    /// pass; the final offset marks the end of the last pass.
+    pub fn pass_offsets(&self) -> &'a [BigEndian<u32>] {
+        let range = self.shape.pass_offsets_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+
+    /// Number of pseudo glyph mappings.
+    pub fn num_pseudo(&self) -> u16 {
+        let range = self.shape.num_pseudo_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Largest power of 2 less than or equal to `num_pseudo`.
+    pub fn search_pseudo(&self) -> u16 {
+        let range = self.shape.search_pseudo_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Log base 2 of `search_pseudo`.
+    pub fn pseudo_selector(&self) -> u16 {
+        let range = self.shape.pseudo_selector_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// `num_pseudo` minus `search_pseudo`.
+    pub fn pseudo_shift(&self) -> u16 {
+        let range = self.shape.pseudo_shift_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Mappings from codepoints to pseudo glyphs, sorted by codepoint.
+    pub fn pseudo_maps(&self) -> &'a [PseudoMap] {
+        let range = self.shape.pseudo_maps_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+
+    /// The class map followed by the pass data; see [Silf::class_map]
+    /// and [SilfSubtable::pass_data].
+    pub fn trailing_data(&self) -> &'a [u8] {
+        let range = self.shape.trailing_data_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for SilfSubtable<'a> {
+    fn type_name(&self) -> &str {
+        "SilfSubtable"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("rule_version", self.rule_version())),
+            1usize => Some(Field::new("pass_offset", self.pass_offset())),
+            2usize => Some(Field::new("pseudos_offset", self.pseudos_offset())),
+            3usize => Some(Field::new("max_glyph_id", self.max_glyph_id())),
+            4usize => Some(Field::new("extra_ascent", self.extra_ascent())),
+            5usize => Some(Field::new("extra_descent", self.extra_descent())),
+            6usize => Some(Field::new("num_passes", self.num_passes())),
+            7usize => Some(Field::new("i_subst", self.i_subst())),
+            8usize => Some(Field::new("i_pos", self.i_pos())),
+            9usize => Some(Field::new("i_just", self.i_just())),
+            10usize => Some(Field::new("i_bidi", self.i_bidi())),
+            11usize => Some(Field::new("flags", self.flags())),
+            12usize => Some(Field::new("max_pre_context", self.max_pre_context())),
+            13usize => Some(Field::new("max_post_context", self.max_post_context())),
+            14usize => Some(Field::new("attr_pseudo", self.attr_pseudo())),
+            15usize => Some(Field::new("attr_break_weight", self.attr_break_weight())),
+            16usize => Some(Field::new(
+                "attr_directionality",
+                self.attr_directionality(),
+            )),
+            17usize => Some(Field::new("attr_mirroring", self.attr_mirroring())),
+            18usize => Some(Field::new("attr_skip_passes", self.attr_skip_passes())),
+            19usize => Some(Field::new("num_j_levels", self.num_j_levels())),
+            20usize => Some(Field::new(
+                "j_levels",
+                traversal::FieldType::array_of_records(
+                    stringify!(JustificationLevel),
+                    self.j_levels(),
+                    self.offset_data(),
+                ),
+            )),
+            21usize => Some(Field::new("num_lig_comp", self.num_lig_comp())),
+            22usize => Some(Field::new("num_user_defn", self.num_user_defn())),
+            23usize => Some(Field::new("max_comp_per_lig", self.max_comp_per_lig())),
+            24usize => Some(Field::new("direction", self.direction())),
+            25usize => Some(Field::new("attr_collisions", self.attr_collisions())),
+            26usize => Some(Field::new("num_crit_features", self.num_crit_features())),
+            27usize => Some(Field::new("crit_features", self.crit_features())),
+            28usize => Some(Field::new("num_script_tag", self.num_script_tag())),
+            29usize => Some(Field::new("script_tags", self.script_tags())),
+            30usize => Some(Field::new("lb_gid", self.lb_gid())),
+            31usize => Some(Field::new("pass_offsets", self.pass_offsets())),
+            32usize => Some(Field::new("num_pseudo", self.num_pseudo())),
+            33usize => Some(Field::new("search_pseudo", self.search_pseudo())),
+            34usize => Some(Field::new("pseudo_selector", self.pseudo_selector())),
+            35usize => Some(Field::new("pseudo_shift", self.pseudo_shift())),
+            36usize => Some(Field::new(
+                "pseudo_maps",
+                traversal::FieldType::array_of_records(
+                    stringify!(PseudoMap),
+                    self.pseudo_maps(),
+                    self.offset_data(),
+                ),
+            )),
+            37usize => Some(Field::new("trailing_data", self.trailing_data())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for SilfSubtable<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// A justification level in a [SilfSubtable].
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct JustificationLevel {
+    /// Glyph attribute number of the stretch attribute for this level.
+    pub attr_stretch: u8,
+    /// Glyph attribute number of the shrink attribute for this level.
+    pub attr_shrink: u8,
+    /// Glyph attribute number of the step attribute for this level.
+    pub attr_step: u8,
+    /// Glyph attribute number of the weight attribute for this level.
+    pub attr_weight: u8,
+    /// Which level starts the next stage.
+    pub runto: u8,
+    pub _reserved1: u8,
+    pub _reserved2: u8,
+    pub _reserved3: u8,
+}
+
+impl JustificationLevel {
+    /// Glyph attribute number of the stretch attribute for this level.
+    pub fn attr_stretch(&self) -> u8 {
+        self.attr_stretch
+    }
+
+    /// Glyph attribute number of the shrink attribute for this level.
+    pub fn attr_shrink(&self) -> u8 {
+        self.attr_shrink
+    }
+
+    /// Glyph attribute number of the step attribute for this level.
+    pub fn attr_step(&self) -> u8 {
+        self.attr_step
+    }
+
+    /// Glyph attribute number of the weight attribute for this level.
+    pub fn attr_weight(&self) -> u8 {
+        self.attr_weight
+    }
+
+    /// Which level starts the next stage.
+    pub fn runto(&self) -> u8 {
+        self.runto
+    }
+}
+
+impl FixedSize for JustificationLevel {
+    const RAW_BYTE_LEN: usize = u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN
+        + u8::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for JustificationLevel {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "JustificationLevel",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("attr_stretch", self.attr_stretch())),
+                1usize => Some(Field::new("attr_shrink", self.attr_shrink())),
+                2usize => Some(Field::new("attr_step", self.attr_step())),
+                3usize => Some(Field::new("attr_weight", self.attr_weight())),
+                4usize => Some(Field::new("runto", self.runto())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
+
+/// A mapping from a codepoint to a pseudo glyph in a [SilfSubtable].
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct PseudoMap {
+    /// The unicode codepoint.
+    pub unicode: BigEndian<u32>,
+    /// The pseudo glyph id.
+    pub n_pseudo: BigEndian<GlyphId>,
+}
+
+impl PseudoMap {
+    /// The unicode codepoint.
+    pub fn unicode(&self) -> u32 {
+        self.unicode.get()
+    }
+
+    /// The pseudo glyph id.
+    pub fn n_pseudo(&self) -> GlyphId {
+        self.n_pseudo.get()
+    }
+}
+
+impl FixedSize for PseudoMap {
+    const RAW_BYTE_LEN: usize = u32::RAW_BYTE_LEN + GlyphId::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for PseudoMap {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "PseudoMap",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("unicode", self.unicode())),
+                1usize => Some(Field::new("n_pseudo", self.n_pseudo())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
+
+/// A non-linear class in a [SilfSubtable] class map, which maps glyphs to
+/// their index within the class.
+#[derive(Debug, Clone, Copy)]
+#[doc(hidden)]
+pub struct LookupClassMarker {
+    lookups_byte_len: usize,
+}
+
+impl LookupClassMarker {
+    fn num_ids_byte_range(&self) -> Range<usize> {
+        let start = 0;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn search_range_byte_range(&self) -> Range<usize> {
+        let start = self.num_ids_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn entry_selector_byte_range(&self) -> Range<usize> {
+        let start = self.search_range_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn range_shift_byte_range(&self) -> Range<usize> {
+        let start = self.entry_selector_byte_range().end;
+        start..start + u16::RAW_BYTE_LEN
+    }
+    fn lookups_byte_range(&self) -> Range<usize> {
+        let start = self.range_shift_byte_range().end;
+        start..start + self.lookups_byte_len
+    }
+}
+
+impl<'a> FontRead<'a> for LookupClass<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        cursor.set_field("numIds");
+        let num_ids: u16 = cursor.read()?;
+        cursor.set_field("searchRange");
+        cursor.advance::<u16>();
+        cursor.set_field("entrySelector");
+        cursor.advance::<u16>();
+        cursor.set_field("rangeShift");
+        cursor.advance::<u16>();
+        cursor.set_field("lookups");
+        let lookups_byte_len = num_ids as usize * LookupPair::RAW_BYTE_LEN;
+        cursor.advance_by(lookups_byte_len);
+        cursor.finish(LookupClassMarker { lookups_byte_len })
+    }
+}
+
+/// A non-linear class in a [SilfSubtable] class map, which maps glyphs to
+/// their index within the class.
+pub type LookupClass<'a> = TableRef<'a, LookupClassMarker>;
+
+impl<'a> LookupClass<'a> {
+    /// Number of glyphs in the class.
+    pub fn num_ids(&self) -> u16 {
+        let range = self.shape.num_ids_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Largest power of 2 less than or equal to `num_ids`.
+    pub fn search_range(&self) -> u16 {
+        let range = self.shape.search_range_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Log base 2 of `search_range`.
+    pub fn entry_selector(&self) -> u16 {
+        let range = self.shape.entry_selector_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// `num_ids` minus `search_range`.
+    pub fn range_shift(&self) -> u16 {
+        let range = self.shape.range_shift_byte_range();
+        self.data.read_at(range.start).unwrap()
+    }
+
+    /// Glyph and index pairs, sorted by glyph id.
+    pub fn lookups(&self) -> &'a [LookupPair] {
+        let range = self.shape.lookups_byte_range();
+        self.data.read_array(range).unwrap()
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeTable<'a> for LookupClass<'a> {
+    fn type_name(&self) -> &str {
+        "LookupClass"
+    }
+    fn get_field(&self, idx: usize) -> Option<Field<'a>> {
+        match idx {
+            0usize => Some(Field::new("num_ids", self.num_ids())),
+            1usize => Some(Field::new("search_range", self.search_range())),
+            2usize => Some(Field::new("entry_selector", self.entry_selector())),
+            3usize => Some(Field::new("range_shift", self.range_shift())),
+            4usize => Some(Field::new(
+                "lookups",
+                traversal::FieldType::array_of_records(
+                    stringify!(LookupPair),
+                    self.lookups(),
+                    self.offset_data(),
+                ),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for LookupClass<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn SomeTable<'a>).fmt(f)
+    }
+}
+
+/// A glyph and its index in a [LookupClass].
+#[derive(Clone, Debug)]
+#[repr(C)]
+#[repr(packed)]
+pub struct LookupPair {
+    /// The glyph id.
+    pub glyph_id: BigEndian<GlyphId>,
+    /// The index of the glyph within the class.
+    pub index: BigEndian<u16>,
+}
+
+impl LookupPair {
+    /// The glyph id.
+    pub fn glyph_id(&self) -> GlyphId {
+        self.glyph_id.get()
+    }
+
+    /// The index of the glyph within the class.
+    pub fn index(&self) -> u16 {
+        self.index.get()
+    }
+}
+
+impl FixedSize for LookupPair {
+    const RAW_BYTE_LEN: usize = GlyphId::RAW_BYTE_LEN + u16::RAW_BYTE_LEN;
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> SomeRecord<'a> for LookupPair {
+    fn traverse(self, data: FontData<'a>) -> RecordResolver<'a> {
+        RecordResolver {
+            name: "LookupPair",
+            get_field: Box::new(move |idx, _data| match idx {
+                0usize => Some(Field::new("glyph_id", self.glyph_id())),
+                1usize => Some(Field::new("index", self.index())),
+                _ => None,
+            }),
+            data,
+        }
+    }
+}
@@ -39,7 +39,7 @@ impl StatMarker {
     }
     fn elided_fallback_name_id_byte_range(&self) -> Option<Range<usize>> {
         let start = self.elided_fallback_name_id_byte_start?;
-        Some(start..start + u16::RAW_BYTE_LEN)
+        Some(start..start + NameId::RAW_BYTE_LEN)
     }
 }
 
@@ -51,17 +51,26 @@ impl TopLevelTable for Stat<'_> {
 impl<'a> FontRead<'a> for Stat<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: MajorMinor = cursor.read()?;
+        cursor.set_field("designAxisSize");
         cursor.advance::<u16>();
+        cursor.set_field("designAxisCount");
         cursor.advance::<u16>();
+        cursor.set_field("designAxesOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("axisValueCount");
         cursor.advance::<u16>();
+        cursor.set_field("offsetToAxisValueOffsets");
         cursor.advance::<Offset32>();
+        cursor.set_field("elidedFallbackNameId");
         let elided_fallback_name_id_byte_start = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.position())
             .transpose()?;
-        version.compatible((1, 1)).then(|| cursor.advance::<u16>());
+        version
+            .compatible(MajorMinor::new(1, 1))
+            .then(|| cursor.advance::<NameId>());
         cursor.finish(StatMarker {
             elided_fallback_name_id_byte_start,
         })
@@ -135,7 +144,7 @@ impl<'a> Stat<'a> {
     /// Name ID used as fallback when projection of names into a
     /// particular font model produces a subfamily name containing only
     /// elidable elements.
-    pub fn elided_fallback_name_id(&self) -> Option<u16> {
+    pub fn elided_fallback_name_id(&self) -> Option<NameId> {
         let range = self.shape.elided_fallback_name_id_byte_range()?;
         Some(self.data.read_at(range.start).unwrap())
     }
@@ -169,7 +178,7 @@ impl<'a> SomeTable<'a> for Stat<'a> {
                     self.offset_to_axis_values(),
                 ),
             )),
-            6usize if version.compatible((1, 1)) => Some(Field::new(
+            6usize if version.compatible(MajorMinor::new(1, 1)) => Some(Field::new(
                 "elided_fallback_name_id",
                 self.elided_fallback_name_id().unwrap(),
             )),
@@ -194,7 +203,7 @@ pub struct AxisRecord {
     pub axis_tag: BigEndian<Tag>,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this axis.
-    pub axis_name_id: BigEndian<u16>,
+    pub axis_name_id: BigEndian<NameId>,
     /// A value that applications can use to determine primary sorting
     /// of face names, or for ordering of labels when composing family
     /// or face names.
@@ -209,7 +218,7 @@ impl AxisRecord {
 
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this axis.
-    pub fn axis_name_id(&self) -> u16 {
+    pub fn axis_name_id(&self) -> NameId {
         self.axis_name_id.get()
     }
 
@@ -222,7 +231,7 @@ impl AxisRecord {
 }
 
 impl FixedSize for AxisRecord {
-    const RAW_BYTE_LEN: usize = Tag::RAW_BYTE_LEN + u16::RAW_BYTE_LEN + u16::RAW_BYTE_LEN;
+    const RAW_BYTE_LEN: usize = Tag::RAW_BYTE_LEN + NameId::RAW_BYTE_LEN + u16::RAW_BYTE_LEN;
 }
 
 #[cfg(feature = "traversal")]
@@ -263,6 +272,7 @@ impl<'a> FontReadWithArgs<'a> for AxisValueArray<'a> {
     fn read_with_args(data: FontData<'a>, args: &u16) -> Result<Self, ReadError> {
         let axis_value_count = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("axisValueOffsets");
         let axis_value_offsets_byte_len = axis_value_count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(axis_value_offsets_byte_len);
         cursor.finish(AxisValueArrayMarker {
@@ -398,7 +408,7 @@ impl AxisValueFormat1Marker {
     }
     fn value_name_id_byte_range(&self) -> Range<usize> {
         let start = self.flags_byte_range().end;
-        start..start + u16::RAW_BYTE_LEN
+        start..start + NameId::RAW_BYTE_LEN
     }
     fn value_byte_range(&self) -> Range<usize> {
         let start = self.value_name_id_byte_range().end;
@@ -409,10 +419,15 @@ impl AxisValueFormat1Marker {
 impl<'a> FontRead<'a> for AxisValueFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("axisIndex");
         cursor.advance::<u16>();
+        cursor.set_field("flags");
         cursor.advance::<AxisValueTableFlags>();
-        cursor.advance::<u16>();
+        cursor.set_field("valueNameId");
+        cursor.advance::<NameId>();
+        cursor.set_field("value");
         cursor.advance::<Fixed>();
         cursor.finish(AxisValueFormat1Marker {})
     }
@@ -444,7 +459,7 @@ impl<'a> AxisValueFormat1<'a> {
 
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this attribute value.
-    pub fn value_name_id(&self) -> u16 {
+    pub fn value_name_id(&self) -> NameId {
         let range = self.shape.value_name_id_byte_range();
         self.data.read_at(range.start).unwrap()
     }
@@ -504,7 +519,7 @@ impl AxisValueFormat2Marker {
     }
     fn value_name_id_byte_range(&self) -> Range<usize> {
         let start = self.flags_byte_range().end;
-        start..start + u16::RAW_BYTE_LEN
+        start..start + NameId::RAW_BYTE_LEN
     }
     fn nominal_value_byte_range(&self) -> Range<usize> {
         let start = self.value_name_id_byte_range().end;
@@ -523,12 +538,19 @@ impl AxisValueFormat2Marker {
 impl<'a> FontRead<'a> for AxisValueFormat2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("axisIndex");
         cursor.advance::<u16>();
+        cursor.set_field("flags");
         cursor.advance::<AxisValueTableFlags>();
-        cursor.advance::<u16>();
+        cursor.set_field("valueNameId");
+        cursor.advance::<NameId>();
+        cursor.set_field("nominalValue");
         cursor.advance::<Fixed>();
+        cursor.set_field("rangeMinValue");
         cursor.advance::<Fixed>();
+        cursor.set_field("rangeMaxValue");
         cursor.advance::<Fixed>();
         cursor.finish(AxisValueFormat2Marker {})
     }
@@ -560,7 +582,7 @@ impl<'a> AxisValueFormat2<'a> {
 
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this attribute value.
-    pub fn value_name_id(&self) -> u16 {
+    pub fn value_name_id(&self) -> NameId {
         let range = self.shape.value_name_id_byte_range();
         self.data.read_at(range.start).unwrap()
     }
@@ -636,7 +658,7 @@ impl AxisValueFormat3Marker {
     }
     fn value_name_id_byte_range(&self) -> Range<usize> {
         let start = self.flags_byte_range().end;
-        start..start + u16::RAW_BYTE_LEN
+        start..start + NameId::RAW_BYTE_LEN
     }
     fn value_byte_range(&self) -> Range<usize> {
         let start = self.value_name_id_byte_range().end;
@@ -651,11 +673,17 @@ impl AxisValueFormat3Marker {
 impl<'a> FontRead<'a> for AxisValueFormat3<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("axisIndex");
         cursor.advance::<u16>();
+        cursor.set_field("flags");
         cursor.advance::<AxisValueTableFlags>();
-        cursor.advance::<u16>();
+        cursor.set_field("valueNameId");
+        cursor.advance::<NameId>();
+        cursor.set_field("value");
         cursor.advance::<Fixed>();
+        cursor.set_field("linkedValue");
         cursor.advance::<Fixed>();
         cursor.finish(AxisValueFormat3Marker {})
     }
@@ -687,7 +715,7 @@ impl<'a> AxisValueFormat3<'a> {
 
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this attribute value.
-    pub fn value_name_id(&self) -> u16 {
+    pub fn value_name_id(&self) -> NameId {
         let range = self.shape.value_name_id_byte_range();
         self.data.read_at(range.start).unwrap()
     }
@@ -756,7 +784,7 @@ impl AxisValueFormat4Marker {
     }
     fn value_name_id_byte_range(&self) -> Range<usize> {
         let start = self.flags_byte_range().end;
-        start..start + u16::RAW_BYTE_LEN
+        start..start + NameId::RAW_BYTE_LEN
     }
     fn axis_values_byte_range(&self) -> Range<usize> {
         let start = self.value_name_id_byte_range().end;
@@ -767,10 +795,15 @@ impl AxisValueFormat4Marker {
 impl<'a> FontRead<'a> for AxisValueFormat4<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("axisCount");
         let axis_count: u16 = cursor.read()?;
+        cursor.set_field("flags");
         cursor.advance::<AxisValueTableFlags>();
-        cursor.advance::<u16>();
+        cursor.set_field("valueNameId");
+        cursor.advance::<NameId>();
+        cursor.set_field("axisValues");
         let axis_values_byte_len = axis_count as usize * AxisValueRecord::RAW_BYTE_LEN;
         cursor.advance_by(axis_values_byte_len);
         cursor.finish(AxisValueFormat4Marker {
@@ -804,7 +837,7 @@ impl<'a> AxisValueFormat4<'a> {
 
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this combination of axis values.
-    pub fn value_name_id(&self) -> u16 {
+    pub fn value_name_id(&self) -> NameId {
         let range = self.shape.value_name_id_byte_range();
         self.data.read_at(range.start).unwrap()
     }
@@ -1207,3 +1240,10 @@ impl<'a> From<AxisValueTableFlags> for FieldType<'a> {
         src.bits().into()
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for AxisValueTableFlags {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Self::from_bits_truncate)
+    }
+}
@@ -31,8 +31,11 @@ impl Table1Marker {
 impl<'a> FontRead<'a> for Table1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("heft");
         cursor.advance::<u32>();
+        cursor.set_field("flex");
         cursor.advance::<u16>();
         cursor.finish(Table1Marker {})
     }
@@ -107,8 +110,11 @@ impl Table2Marker {
 impl<'a> FontRead<'a> for Table2<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("valueCount");
         let value_count: u16 = cursor.read()?;
+        cursor.set_field("values");
         let values_byte_len = value_count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(values_byte_len);
         cursor.finish(Table2Marker { values_byte_len })
@@ -178,7 +184,9 @@ impl Table3Marker {
 impl<'a> FontRead<'a> for Table3<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("something");
         cursor.advance::<u16>();
         cursor.finish(Table3Marker {})
     }
@@ -55,32 +55,41 @@ impl KindsOfOffsetsMarker {
 impl<'a> FontRead<'a> for KindsOfOffsets<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: MajorMinor = cursor.read()?;
+        cursor.set_field("nonnullableOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("nullableOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("arrayOffsetCount");
         cursor.advance::<u16>();
+        cursor.set_field("arrayOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("recordArrayOffset");
         cursor.advance::<Offset16>();
+        cursor.set_field("versionedNullableRecordArrayOffset");
         let versioned_nullable_record_array_offset_byte_start = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.position())
             .transpose()?;
         version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.advance::<Offset16>());
+        cursor.set_field("versionedNonnullableOffset");
         let versioned_nonnullable_offset_byte_start = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.position())
             .transpose()?;
         version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.advance::<Offset16>());
+        cursor.set_field("versionedNullableOffset");
         let versioned_nullable_offset_byte_start = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.position())
             .transpose()?;
         version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.advance::<Offset32>());
         cursor.finish(KindsOfOffsetsMarker {
             versioned_nullable_record_array_offset_byte_start,
@@ -227,27 +236,27 @@ impl<'a> SomeTable<'a> for KindsOfOffsets<'a> {
                     self.offset_data(),
                 ),
             )),
-            6usize if version.compatible((1, 1)) => Some(Field::new(
+            6usize if version.compatible(MajorMinor::new(1, 1)) => Some(Field::new(
                 "versioned_nullable_record_array_offset",
                 traversal::FieldType::offset_to_array_of_records(
                     self.versioned_nullable_record_array_offset().unwrap(),
-                    self.versioned_nullable_record_array().unwrap(),
+                    self.versioned_nullable_record_array(),
                     stringify!(Shmecord),
                     self.offset_data(),
                 ),
             )),
-            7usize if version.compatible((1, 1)) => Some(Field::new(
+            7usize if version.compatible(MajorMinor::new(1, 1)) => Some(Field::new(
                 "versioned_nonnullable_offset",
                 FieldType::offset(
                     self.versioned_nonnullable_offset().unwrap(),
                     self.versioned_nonnullable().unwrap(),
                 ),
             )),
-            8usize if version.compatible((1, 1)) => Some(Field::new(
+            8usize if version.compatible(MajorMinor::new(1, 1)) => Some(Field::new(
                 "versioned_nullable_offset",
                 FieldType::offset(
                     self.versioned_nullable_offset().unwrap(),
-                    self.versioned_nullable().unwrap(),
+                    self.versioned_nullable(),
                 ),
             )),
             _ => None,
@@ -303,28 +312,34 @@ impl KindsOfArraysOfOffsetsMarker {
 impl<'a> FontRead<'a> for KindsOfArraysOfOffsets<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: MajorMinor = cursor.read()?;
+        cursor.set_field("count");
         let count: u16 = cursor.read()?;
+        cursor.set_field("nonnullableOffsets");
         let nonnullable_offsets_byte_len = count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(nonnullable_offsets_byte_len);
+        cursor.set_field("nullableOffsets");
         let nullable_offsets_byte_len = count as usize * Offset16::RAW_BYTE_LEN;
         cursor.advance_by(nullable_offsets_byte_len);
+        cursor.set_field("versionedNonnullableOffsets");
         let versioned_nonnullable_offsets_byte_start = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.position())
             .transpose()?;
         let versioned_nonnullable_offsets_byte_len = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then_some(count as usize * Offset16::RAW_BYTE_LEN);
         if let Some(value) = versioned_nonnullable_offsets_byte_len {
             cursor.advance_by(value);
         }
+        cursor.set_field("versionedNullableOffsets");
         let versioned_nullable_offsets_byte_start = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| cursor.position())
             .transpose()?;
         let versioned_nullable_offsets_byte_len = version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then_some(count as usize * Offset16::RAW_BYTE_LEN);
         if let Some(value) = versioned_nullable_offsets_byte_len {
             cursor.advance_by(value);
@@ -452,7 +467,7 @@ impl<'a> SomeTable<'a> for KindsOfArraysOfOffsets<'a> {
                     ),
                 )
             }),
-            4usize if version.compatible((1, 1)) => Some({
+            4usize if version.compatible(MajorMinor::new(1, 1)) => Some({
                 let data = self.data;
                 Field::new(
                     "versioned_nonnullable_offsets",
@@ -466,7 +481,7 @@ impl<'a> SomeTable<'a> for KindsOfArraysOfOffsets<'a> {
                     ),
                 )
             }),
-            5usize if version.compatible((1, 1)) => Some({
+            5usize if version.compatible(MajorMinor::new(1, 1)) => Some({
                 let data = self.data;
                 Field::new(
                     "versioned_nullable_offsets",
@@ -533,12 +548,17 @@ impl KindsOfArraysMarker {
 impl<'a> FontRead<'a> for KindsOfArrays<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         let version: u16 = cursor.read()?;
+        cursor.set_field("count");
         let count: u16 = cursor.read()?;
+        cursor.set_field("scalars");
         let scalars_byte_len = count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(scalars_byte_len);
+        cursor.set_field("records");
         let records_byte_len = count as usize * Shmecord::RAW_BYTE_LEN;
         cursor.advance_by(records_byte_len);
+        cursor.set_field("versionedScalars");
         let versioned_scalars_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
@@ -549,6 +569,7 @@ impl<'a> FontRead<'a> for KindsOfArrays<'a> {
         if let Some(value) = versioned_scalars_byte_len {
             cursor.advance_by(value);
         }
+        cursor.set_field("versionedRecords");
         let versioned_records_byte_start = version
             .compatible(1)
             .then(|| cursor.position())
@@ -666,6 +687,7 @@ impl DummyMarker {
 impl<'a> FontRead<'a> for Dummy<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("value");
         cursor.advance::<u16>();
         cursor.finish(DummyMarker {})
     }
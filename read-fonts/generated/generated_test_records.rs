@@ -38,11 +38,16 @@ impl BasicTableMarker {
 impl<'a> FontRead<'a> for BasicTable<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("simpleCount");
         let simple_count: u16 = cursor.read()?;
+        cursor.set_field("simpleRecords");
         let simple_records_byte_len = simple_count as usize * SimpleRecord::RAW_BYTE_LEN;
         cursor.advance_by(simple_records_byte_len);
+        cursor.set_field("arraysInnerCount");
         let arrays_inner_count: u16 = cursor.read()?;
+        cursor.set_field("arrayRecordsCount");
         let array_records_count: u32 = cursor.read()?;
+        cursor.set_field("arrayRecords");
         let array_records_byte_len = array_records_count as usize
             * <ContainsArrays as ComputeSize>::compute_size(&arrays_inner_count);
         cursor.advance_by(array_records_byte_len);
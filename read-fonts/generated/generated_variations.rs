@@ -45,14 +45,19 @@ impl<'a> FontReadWithArgs<'a> for TupleVariationHeader<'a> {
     fn read_with_args(data: FontData<'a>, args: &u16) -> Result<Self, ReadError> {
         let axis_count = *args;
         let mut cursor = data.cursor();
+        cursor.set_field("variationDataSize");
         cursor.advance::<u16>();
+        cursor.set_field("tupleIndex");
         let tuple_index: TupleIndex = cursor.read()?;
+        cursor.set_field("peakTuple");
         let peak_tuple_byte_len =
             TupleIndex::tuple_len(tuple_index, axis_count, 0_usize) * F2Dot14::RAW_BYTE_LEN;
         cursor.advance_by(peak_tuple_byte_len);
+        cursor.set_field("intermediateStartTuple");
         let intermediate_start_tuple_byte_len =
             TupleIndex::tuple_len(tuple_index, axis_count, 1_usize) * F2Dot14::RAW_BYTE_LEN;
         cursor.advance_by(intermediate_start_tuple_byte_len);
+        cursor.set_field("intermediateEndTuple");
         let intermediate_end_tuple_byte_len =
             TupleIndex::tuple_len(tuple_index, axis_count, 1_usize) * F2Dot14::RAW_BYTE_LEN;
         cursor.advance_by(intermediate_end_tuple_byte_len);
@@ -199,9 +204,13 @@ impl DeltaSetIndexMapFormat0Marker {
 impl<'a> FontRead<'a> for DeltaSetIndexMapFormat0<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("entryFormat");
         let entry_format: EntryFormat = cursor.read()?;
+        cursor.set_field("mapCount");
         let map_count: u16 = cursor.read()?;
+        cursor.set_field("mapData");
         let map_data_byte_len = EntryFormat::map_size(entry_format, map_count) * u8::RAW_BYTE_LEN;
         cursor.advance_by(map_data_byte_len);
         cursor.finish(DeltaSetIndexMapFormat0Marker { map_data_byte_len })
@@ -294,9 +303,13 @@ impl DeltaSetIndexMapFormat1Marker {
 impl<'a> FontRead<'a> for DeltaSetIndexMapFormat1<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u8>();
+        cursor.set_field("entryFormat");
         let entry_format: EntryFormat = cursor.read()?;
+        cursor.set_field("mapCount");
         let map_count: u32 = cursor.read()?;
+        cursor.set_field("mapData");
         let map_data_byte_len = EntryFormat::map_size(entry_format, map_count) * u8::RAW_BYTE_LEN;
         cursor.advance_by(map_data_byte_len);
         cursor.finish(DeltaSetIndexMapFormat1Marker { map_data_byte_len })
@@ -709,6 +722,13 @@ impl<'a> From<EntryFormat> for FieldType<'a> {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for EntryFormat {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Self::from_bits_truncate)
+    }
+}
+
 /// The [VariationRegionList](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#variation-regions) table
 #[derive(Debug, Clone, Copy)]
 #[doc(hidden)]
@@ -734,8 +754,11 @@ impl VariationRegionListMarker {
 impl<'a> FontRead<'a> for VariationRegionList<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("axisCount");
         let axis_count: u16 = cursor.read()?;
+        cursor.set_field("regionCount");
         let region_count: u16 = cursor.read()?;
+        cursor.set_field("variationRegions");
         let variation_regions_byte_len =
             region_count as usize * <VariationRegion as ComputeSize>::compute_size(&axis_count);
         cursor.advance_by(variation_regions_byte_len);
@@ -937,9 +960,13 @@ impl ItemVariationStoreMarker {
 impl<'a> FontRead<'a> for ItemVariationStore<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("format");
         cursor.advance::<u16>();
+        cursor.set_field("variationRegionListOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("itemVariationDataCount");
         let item_variation_data_count: u16 = cursor.read()?;
+        cursor.set_field("itemVariationDataOffsets");
         let item_variation_data_offsets_byte_len =
             item_variation_data_count as usize * Offset32::RAW_BYTE_LEN;
         cursor.advance_by(item_variation_data_offsets_byte_len);
@@ -1075,12 +1102,19 @@ impl ItemVariationDataMarker {
 impl<'a> FontRead<'a> for ItemVariationData<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
-        cursor.advance::<u16>();
-        cursor.advance::<u16>();
+        cursor.set_field("itemCount");
+        let item_count: u16 = cursor.read()?;
+        cursor.set_field("wordDeltaCount");
+        let word_delta_count: u16 = cursor.read()?;
+        cursor.set_field("regionIndexCount");
         let region_index_count: u16 = cursor.read()?;
+        cursor.set_field("regionIndexes");
         let region_indexes_byte_len = region_index_count as usize * u16::RAW_BYTE_LEN;
         cursor.advance_by(region_indexes_byte_len);
-        let delta_sets_byte_len = cursor.remaining_bytes();
+        cursor.set_field("deltaSets");
+        let delta_sets_byte_len =
+            ItemVariationData::delta_sets_len(item_count, word_delta_count, region_index_count)
+                * u8::RAW_BYTE_LEN;
         cursor.advance_by(delta_sets_byte_len);
         cursor.finish(ItemVariationDataMarker {
             region_indexes_byte_len,
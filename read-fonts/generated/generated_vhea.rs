@@ -89,22 +89,39 @@ impl TopLevelTable for Vhea<'_> {
 impl<'a> FontRead<'a> for Vhea<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<Version16Dot16>();
+        cursor.set_field("ascender");
         cursor.advance::<FWord>();
+        cursor.set_field("descender");
         cursor.advance::<FWord>();
+        cursor.set_field("lineGap");
         cursor.advance::<FWord>();
+        cursor.set_field("advanceHeightMax");
         cursor.advance::<UfWord>();
+        cursor.set_field("minTopSideBearing");
         cursor.advance::<FWord>();
+        cursor.set_field("minBottomSideBearing");
         cursor.advance::<FWord>();
+        cursor.set_field("yMaxExtent");
         cursor.advance::<FWord>();
+        cursor.set_field("caretSlopeRise");
         cursor.advance::<i16>();
+        cursor.set_field("caretSlopeRun");
         cursor.advance::<i16>();
+        cursor.set_field("caretOffset");
         cursor.advance::<i16>();
+        cursor.set_field("reserved1");
         cursor.advance::<i16>();
+        cursor.set_field("reserved2");
         cursor.advance::<i16>();
+        cursor.set_field("reserved3");
         cursor.advance::<i16>();
+        cursor.set_field("reserved4");
         cursor.advance::<i16>();
+        cursor.set_field("metricDataFormat");
         cursor.advance::<i16>();
+        cursor.set_field("numberOfLongVerMetrics");
         cursor.advance::<u16>();
         cursor.finish(VheaMarker {})
     }
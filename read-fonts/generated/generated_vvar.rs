@@ -45,11 +45,17 @@ impl TopLevelTable for Vvar<'_> {
 impl<'a> FontRead<'a> for Vvar<'a> {
     fn read(data: FontData<'a>) -> Result<Self, ReadError> {
         let mut cursor = data.cursor();
+        cursor.set_field("version");
         cursor.advance::<MajorMinor>();
+        cursor.set_field("itemVariationStoreOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("advanceHeightMappingOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("tsbMappingOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("bsbMappingOffset");
         cursor.advance::<Offset32>();
+        cursor.set_field("vOrgMappingOffset");
         cursor.advance::<Offset32>();
         cursor.finish(VvarMarker {})
     }
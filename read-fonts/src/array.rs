@@ -25,7 +25,8 @@ pub struct ComputedArray<'a, T: ReadArgs> {
 impl<'a, T: ComputeSize> ComputedArray<'a, T> {
     pub fn new(data: FontData<'a>, args: T::Args) -> Self {
         let item_len = T::compute_size(&args);
-        let len = data.len() / item_len;
+        // zero-sized items cannot be located, so treat them as empty
+        let len = data.len().checked_div(item_len).unwrap_or(0);
         ComputedArray {
             item_len,
             len,
@@ -104,7 +105,8 @@ impl<T: ReadArgs> std::fmt::Debug for ComputedArray<'_, T> {
 /// a linear scan.
 pub struct VarLenArray<'a, T> {
     data: FontData<'a>,
-    phantom: std::marker::PhantomData<*const T>,
+    // we don't own any T, and this keeps the array Send + Sync
+    phantom: std::marker::PhantomData<fn() -> T>,
 }
 
 impl<'a, T: FontRead<'a> + VarSize> VarLenArray<'a, T> {
@@ -0,0 +1,141 @@
+//! A font reference that caches parsed tables.
+
+use std::sync::OnceLock;
+
+use types::Tag;
+
+use crate::{tables, FontData, FontRef, ReadError, TableProvider};
+
+macro_rules! cached_tables {
+    ($($name:ident: $ty:ty),* $(,)?) => {
+        #[derive(Clone, Default)]
+        struct TableCache<'a> {
+            loca: OnceLock<Result<tables::loca::Loca<'a>, ReadError>>,
+            $($name: OnceLock<Result<$ty, ReadError>>,)*
+        }
+
+        impl<'a> TableProvider<'a> for CachedFontRef<'a> {
+            fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>> {
+                self.font.data_for_tag(tag)
+            }
+
+            /// The table is only cached when `is_long` is not provided, and
+            /// is instead determined by the `head` table.
+            fn loca(
+                &self,
+                is_long: impl Into<Option<bool>>,
+            ) -> Result<tables::loca::Loca<'a>, ReadError> {
+                match is_long.into() {
+                    Some(is_long) => self.font.loca(is_long),
+                    None => self
+                        .cache
+                        .loca
+                        .get_or_init(|| self.font.loca(None))
+                        .clone(),
+                }
+            }
+
+            $(
+                fn $name(&self) -> Result<$ty, ReadError> {
+                    self.cache.$name.get_or_init(|| self.font.$name()).clone()
+                }
+            )*
+        }
+    };
+}
+
+cached_tables! {
+    head: tables::head::Head<'a>,
+    name: tables::name::Name<'a>,
+    hhea: tables::hhea::Hhea<'a>,
+    vhea: tables::vhea::Vhea<'a>,
+    hmtx: tables::hmtx::Hmtx<'a>,
+    vmtx: tables::vmtx::Vmtx<'a>,
+    fvar: tables::fvar::Fvar<'a>,
+    avar: tables::avar::Avar<'a>,
+    hvar: tables::hvar::Hvar<'a>,
+    vvar: tables::vvar::Vvar<'a>,
+    mvar: tables::mvar::Mvar<'a>,
+    maxp: tables::maxp::Maxp<'a>,
+    os2: tables::os2::Os2<'a>,
+    post: tables::post::Post<'a>,
+    glyf: tables::glyf::Glyf<'a>,
+    gvar: tables::gvar::Gvar<'a>,
+    cmap: tables::cmap::Cmap<'a>,
+    gdef: tables::gdef::Gdef<'a>,
+    gpos: tables::gpos::Gpos<'a>,
+    gsub: tables::gsub::Gsub<'a>,
+    colr: tables::colr::Colr<'a>,
+    cpal: tables::cpal::Cpal<'a>,
+    stat: tables::stat::Stat<'a>,
+}
+
+/// Reference to a font that lazily caches its parsed top-level tables.
+///
+/// Each table accessor on [`TableProvider`] parses and validates the table
+/// header on every call. This type performs that work only once per table,
+/// which is worthwhile when tables are requested repeatedly, such as when
+/// loading many glyphs.
+///
+/// The result of the first access, including any error, is returned for all
+/// subsequent accesses. The cache is thread safe, so a `CachedFontRef` can be
+/// shared between threads.
+#[derive(Clone)]
+pub struct CachedFontRef<'a> {
+    font: FontRef<'a>,
+    cache: TableCache<'a>,
+}
+
+impl<'a> CachedFontRef<'a> {
+    /// Creates a new caching reference to the given font.
+    pub fn new(font: FontRef<'a>) -> Self {
+        Self {
+            font,
+            cache: Default::default(),
+        }
+    }
+
+    /// Returns the underlying font.
+    pub fn font(&self) -> &FontRef<'a> {
+        &self.font
+    }
+}
+
+impl<'a> From<FontRef<'a>> for CachedFontRef<'a> {
+    fn from(font: FontRef<'a>) -> Self {
+        Self::new(font)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::test_fonts;
+    use types::GlyphId;
+
+    #[test]
+    fn cached_tables_match() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let cached = CachedFontRef::new(font.clone());
+        for _ in 0..2 {
+            assert_eq!(
+                cached.head().unwrap().units_per_em(),
+                font.head().unwrap().units_per_em()
+            );
+            assert_eq!(
+                cached.gvar().unwrap().glyph_count(),
+                font.gvar().unwrap().glyph_count()
+            );
+            let loca = cached.loca(None).unwrap();
+            assert_eq!(loca.len(), font.loca(None).unwrap().len());
+            let glyf = cached.glyf().unwrap();
+            assert!(loca.get_glyf(GlyphId::new(1), &glyf).unwrap().is_some());
+            assert!(matches!(
+                cached.gsub(),
+                Err(ReadError::TableIsMissing(tag)) if tag == Tag::new(b"GSUB")
+            ));
+        }
+        // explicitly provided args bypass the cache
+        assert!(cached.loca(false).is_ok());
+    }
+}
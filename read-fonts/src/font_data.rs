@@ -5,7 +5,7 @@ use std::ops::{Range, RangeBounds};
 use types::{FixedSize, ReadScalar};
 
 use crate::array::ComputedArray;
-use crate::read::{ComputeSize, FontReadWithArgs, ReadError};
+use crate::read::{ComputeSize, ErrorLocation, FontReadWithArgs, ReadError};
 use crate::table_ref::TableRef;
 use crate::FontRead;
 
@@ -29,6 +29,10 @@ pub struct FontData<'a> {
 pub struct Cursor<'a> {
     pos: usize,
     data: FontData<'a>,
+    // the name of the field currently being read, for error reporting
+    field: Option<&'static str>,
+    // the first field that extended past the end of the data, and its offset
+    overflow: Option<(&'static str, usize)>,
 }
 
 impl<'a> FontData<'a> {
@@ -168,6 +172,8 @@ impl<'a> FontData<'a> {
         Cursor {
             pos: 0,
             data: *self,
+            field: None,
+            overflow: None,
         }
     }
 
@@ -177,28 +183,53 @@ impl<'a> FontData<'a> {
 }
 
 impl<'a> Cursor<'a> {
-    pub(crate) fn advance<T: ReadScalar>(&mut self) {
-        self.pos += T::RAW_BYTE_LEN
+    /// Sets the name of the field being read, used to provide context for
+    /// errors.
+    pub(crate) fn set_field(&mut self, name: &'static str) {
+        self.field = Some(name);
+    }
+
+    pub(crate) fn advance<T: FixedSize>(&mut self) {
+        self.advance_by(T::RAW_BYTE_LEN)
     }
 
     pub(crate) fn advance_by(&mut self, n_bytes: usize) {
+        let start = self.pos;
         self.pos += n_bytes;
+        if self.overflow.is_none() && self.pos > self.data.len() {
+            self.overflow = self.field.map(|field| (field, start));
+        }
+    }
+
+    /// Adds the current field and position to an error.
+    fn locate(&self, error: ReadError, offset: usize) -> ReadError {
+        match (error, self.field) {
+            (ReadError::OutOfBounds, Some(field)) => ReadError::OutOfBoundsAt(ErrorLocation {
+                table: None,
+                field,
+                index: None,
+                offset: Some(offset),
+            }),
+            (error, _) => error,
+        }
     }
 
     pub(crate) fn read<T: ReadScalar>(&mut self) -> Result<T, ReadError> {
-        let temp = self.data.read_at(self.pos);
-        self.pos += T::RAW_BYTE_LEN;
-        temp
+        let start = self.pos;
+        let temp = self.data.read_at(start);
+        self.advance::<T>();
+        temp.map_err(|e| self.locate(e, start))
     }
 
     pub(crate) fn read_with_args<T>(&mut self, args: &T::Args) -> Result<T, ReadError>
     where
         T: FontReadWithArgs<'a> + ComputeSize,
     {
+        let start = self.pos;
         let len = T::compute_size(args);
-        let temp = self.data.read_with_args(self.pos..self.pos + len, args);
-        self.pos += len;
-        temp
+        let temp = self.data.read_with_args(start..start + len, args);
+        self.advance_by(len);
+        temp.map_err(|e| self.locate(e, start))
     }
 
     // only used in records that contain arrays :/
@@ -210,17 +241,19 @@ impl<'a> Cursor<'a> {
     where
         T: FontReadWithArgs<'a> + ComputeSize,
     {
+        let start = self.pos;
         let len = len * T::compute_size(args);
-        let temp = self.data.read_with_args(self.pos..self.pos + len, args);
-        self.pos += len;
-        temp
+        let temp = self.data.read_with_args(start..start + len, args);
+        self.advance_by(len);
+        temp.map_err(|e| self.locate(e, start))
     }
 
     pub(crate) fn read_array<T: FixedSize>(&mut self, n_elem: usize) -> Result<&'a [T], ReadError> {
+        let start = self.pos;
         let len = n_elem * T::RAW_BYTE_LEN;
-        let temp = self.data.read_array(self.pos..self.pos + len);
-        self.pos += len;
-        temp
+        let temp = self.data.read_array(start..start + len);
+        self.advance_by(len);
+        temp.map_err(|e| self.locate(e, start))
     }
 
     /// read a value, validating it with the provided function if successful.
@@ -260,7 +293,16 @@ impl<'a> Cursor<'a> {
 
     pub(crate) fn finish<T>(self, shape: T) -> Result<TableRef<'a, T>, ReadError> {
         let data = self.data;
-        data.check_in_bounds(self.pos)?;
+        data.check_in_bounds(self.pos)
+            .map_err(|e| match self.overflow {
+                Some((field, offset)) => ReadError::OutOfBoundsAt(ErrorLocation {
+                    table: None,
+                    field,
+                    index: None,
+                    offset: Some(offset),
+                }),
+                None => e,
+            })?;
         Ok(TableRef { data, shape })
     }
 }
@@ -1,4 +1,10 @@
 //! Parsing OpentType tables.
+//!
+//! # Thread safety
+//!
+//! All of the types in this crate are read-only views into borrowed font
+//! data, without any interior mutability. They are all `Send` and `Sync`,
+//! so a parsed font or table can be shared freely between threads.
 
 #![deny(rustdoc::broken_intra_doc_links)]
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -12,8 +18,11 @@ extern crate std;
 extern crate core as std;
 
 pub mod array;
+#[cfg(feature = "std")]
+mod cached_font;
 mod font_data;
 mod offset;
+mod partial_font;
 mod read;
 mod table_provider;
 mod table_ref;
@@ -30,10 +39,15 @@ pub mod test_data;
 #[path = "tests/test_helpers.rs"]
 pub mod test_helpers;
 
+#[cfg(feature = "std")]
+pub use cached_font::CachedFontRef;
 pub use font_data::FontData;
 pub use offset::{Offset, ResolveNullableOffset, ResolveOffset};
-pub use read::{ComputeSize, FontRead, FontReadWithArgs, ReadArgs, ReadError, VarSize};
-pub use table_provider::{TableProvider, TopLevelTable};
+pub use partial_font::PartialFontRef;
+pub use read::{
+    ComputeSize, ErrorLocation, FontRead, FontReadWithArgs, ReadArgs, ReadError, VarSize,
+};
+pub use table_provider::{Direction, TableProvider, TopLevelTable};
 pub use table_ref::TableRef;
 
 /// Public re-export of the font-types crate.
@@ -182,16 +196,82 @@ impl<'a> FontRef<'a> {
 
     /// Returns the data for the table with the specified tag, if present.
     pub fn table_data(&self, tag: Tag) -> Option<FontData<'a>> {
-        self.table_directory
-            .table_records()
+        self.table_record(tag)
+            .and_then(|record| self.data_for_record(record))
+    }
+
+    /// Returns an iterator over the records in the table directory.
+    ///
+    /// This includes records for tables that are not otherwise modeled by
+    /// this crate; use [`FontRef::data_for_record`] to access their raw data.
+    pub fn table_records(&self) -> impl Iterator<Item = &'a TableRecord> + 'a + Clone {
+        self.table_directory.table_records().iter()
+    }
+
+    /// Returns the table directory record for the specified tag, if present.
+    pub fn table_record(&self, tag: Tag) -> Option<&'a TableRecord> {
+        let records = self.table_directory.table_records();
+        records
             .binary_search_by(|rec| rec.tag.get().cmp(&tag))
             .ok()
-            .and_then(|idx| self.table_directory.table_records().get(idx))
-            .and_then(|record| {
-                let start = record.offset().non_null()?;
-                let len = record.length() as usize;
-                self.data.slice(start..start + len)
-            })
+            .map(|idx| &records[idx])
+    }
+
+    /// Returns the data referenced by the given table record.
+    ///
+    /// Returns `None` if the record's offset is null or if the table extends
+    /// beyond the end of the font data.
+    pub fn data_for_record(&self, record: &TableRecord) -> Option<FontData<'a>> {
+        let start = record.offset().non_null()?;
+        let len = record.length() as usize;
+        self.data.slice(start..start.checked_add(len)?)
+    }
+
+    /// Returns an iterator over the tags of all tables in the font, in
+    /// table directory order.
+    pub fn table_tags(&self) -> impl Iterator<Item = Tag> + 'a + Clone {
+        self.table_records().map(|record| record.tag())
+    }
+
+    /// Returns the length in bytes of the table with the specified tag, if present.
+    pub fn table_len(&self, tag: Tag) -> Option<u32> {
+        self.table_record(tag).map(TableRecord::length)
+    }
+
+    /// Returns true if the font contains a table with the specified tag.
+    pub fn has_table(&self, tag: Tag) -> bool {
+        self.table_len(tag).is_some()
+    }
+
+    /// Returns true if the font is variable (has an `fvar` table).
+    pub fn is_variable(&self) -> bool {
+        self.has_table(tables::fvar::Fvar::TAG)
+    }
+
+    /// Returns true if the font contains glyph outlines in either a `glyf`,
+    /// `CFF ` or `CFF2` table.
+    pub fn has_outlines(&self) -> bool {
+        [Tag::GLYF, Tag::CFF, Tag::CFF2]
+            .into_iter()
+            .any(|tag| self.has_table(tag))
+    }
+
+    /// Returns true if the font contains color glyphs in any of the `COLR`,
+    /// `sbix`, `CBDT` or `SVG ` tables.
+    pub fn has_color(&self) -> bool {
+        [Tag::COLR, Tag::SBIX, Tag::CBDT, Tag::SVG]
+            .into_iter()
+            .any(|tag| self.has_table(tag))
+    }
+
+    /// Returns the number of glyphs in the font, as reported by the `maxp` table.
+    pub fn glyph_count(&self) -> Result<u16, ReadError> {
+        self.maxp().map(|maxp| maxp.num_glyphs())
+    }
+
+    /// Returns the units per em of the font, as reported by the `head` table.
+    pub fn units_per_em(&self) -> Result<u16, ReadError> {
+        self.head().map(|head| head.units_per_em())
     }
 
     fn with_table_directory(
@@ -220,3 +300,108 @@ impl TableRecord {
         Offset32::new(self.offset.get())
     }
 }
+
+// Compile-time checks for the thread safety guarantee in the crate docs.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<FontData<'static>>();
+    assert_send_sync::<FileRef<'static>>();
+    assert_send_sync::<FontRef<'static>>();
+    assert_send_sync::<CollectionRef<'static>>();
+    assert_send_sync::<PartialFontRef<'static>>();
+    #[cfg(feature = "std")]
+    assert_send_sync::<CachedFontRef<'static>>();
+    assert_send_sync::<ReadError>();
+    assert_send_sync::<VarLenArray<'static, tables::variations::TupleVariationHeader<'static>>>();
+    assert_send_sync::<ComputedArray<'static, tables::gpos::ValueRecord>>();
+
+    use tables::*;
+    assert_send_sync::<aat::Lookup<'static>>();
+    assert_send_sync::<avar::Avar<'static>>();
+    assert_send_sync::<base::Base<'static>>();
+    assert_send_sync::<cmap::Cmap<'static>>();
+    assert_send_sync::<cmap::CmapSubtable<'static>>();
+    assert_send_sync::<colr::Colr<'static>>();
+    assert_send_sync::<cpal::Cpal<'static>>();
+    assert_send_sync::<cvar::Cvar<'static>>();
+    assert_send_sync::<feat::Feat<'static>>();
+    assert_send_sync::<fvar::Fvar<'static>>();
+    assert_send_sync::<gdef::Gdef<'static>>();
+    assert_send_sync::<glat::Glat<'static>>();
+    assert_send_sync::<gloc::Gloc<'static>>();
+    assert_send_sync::<glyf::Glyf<'static>>();
+    assert_send_sync::<glyf::Glyph<'static>>();
+    assert_send_sync::<gpos::Gpos<'static>>();
+    assert_send_sync::<gsub::Gsub<'static>>();
+    assert_send_sync::<gvar::Gvar<'static>>();
+    assert_send_sync::<gvar::GlyphVariationData<'static>>();
+    assert_send_sync::<head::Head<'static>>();
+    assert_send_sync::<hhea::Hhea<'static>>();
+    assert_send_sync::<hmtx::Hmtx<'static>>();
+    assert_send_sync::<hvar::Hvar<'static>>();
+    assert_send_sync::<kern::Kern<'static>>();
+    assert_send_sync::<layout::FeatureParams<'static>>();
+    assert_send_sync::<lcar::Lcar<'static>>();
+    assert_send_sync::<loca::Loca<'static>>();
+    assert_send_sync::<ltsh::Ltsh<'static>>();
+    assert_send_sync::<math::Math<'static>>();
+    assert_send_sync::<maxp::Maxp<'static>>();
+    assert_send_sync::<mvar::Mvar<'static>>();
+    assert_send_sync::<name::Name<'static>>();
+    assert_send_sync::<opbd::Opbd<'static>>();
+    assert_send_sync::<os2::Os2<'static>>();
+    assert_send_sync::<pclt::Pclt<'static>>();
+    assert_send_sync::<post::Post<'static>>();
+    assert_send_sync::<silf::Silf<'static>>();
+    assert_send_sync::<stat::Stat<'static>>();
+    assert_send_sync::<variations::ItemVariationStore<'static>>();
+    assert_send_sync::<vhea::Vhea<'static>>();
+    assert_send_sync::<vmtx::Vmtx<'static>>();
+    assert_send_sync::<vvar::Vvar<'static>>();
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::test_fonts;
+
+    #[test]
+    fn font_metadata() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let tags = font.table_tags().collect::<Vec<_>>();
+        assert_eq!(tags.len(), 14);
+        assert_eq!(tags[0], Tag::new(b"HVAR"));
+        assert_eq!(font.table_len(Tag::new(b"gvar")), Some(186));
+        assert_eq!(font.table_len(Tag::new(b"GSUB")), None);
+        assert!(font.is_variable());
+        assert!(font.has_outlines());
+        assert!(!font.has_color());
+        assert_eq!(font.glyph_count().unwrap(), 4);
+        assert_eq!(font.units_per_em().unwrap(), 2048);
+
+        let font = FontRef::new(test_fonts::COLR_GRADIENT_RECT).unwrap();
+        assert!(!font.is_variable());
+        assert!(font.has_color());
+        assert_eq!(font.units_per_em().unwrap(), 1024);
+    }
+
+    #[test]
+    fn table_records() {
+        let font = FontRef::new(test_fonts::SIMPLE_GLYF).unwrap();
+        let records = font.table_records().collect::<Vec<_>>();
+        assert_eq!(records.len(), 7);
+        for record in records {
+            let data = font.data_for_record(record).unwrap();
+            assert_eq!(data.len(), record.length() as usize);
+            assert_eq!(
+                font.table_data(record.tag()).map(|data| data.as_bytes()),
+                Some(data.as_bytes())
+            );
+        }
+        let head = font.table_record(Tag::new(b"head")).unwrap();
+        assert_eq!(head.tag(), Tag::new(b"head"));
+        assert_eq!(head.length(), 54);
+        assert!(font.table_record(Tag::new(b"GPOS")).is_none());
+    }
+}
@@ -0,0 +1,154 @@
+//! Access to fonts whose data is only partially available.
+
+use std::ops::Range;
+
+use types::{FixedSize, Tag};
+
+use crate::{FontData, FontRef, ReadError, TableProvider, TableRecord};
+
+// the fixed-size portion of the table directory, before the table records
+const TABLE_DIRECTORY_HEADER_LEN: usize = 12;
+
+/// Reference to a font whose data has only been partially loaded.
+///
+/// This is intended for fonts that are transferred incrementally. The font
+/// data is a buffer spanning the entire font file, of which only the byte
+/// ranges in `loaded` are known to be valid. The `loaded` ranges must be
+/// sorted by their start offset.
+///
+/// Requesting a table that is not fully loaded results in a
+/// [`ReadError::NotLoaded`] error containing the byte range that must be
+/// fetched before the table can be read.
+#[derive(Clone)]
+pub struct PartialFontRef<'a> {
+    font: FontRef<'a>,
+    loaded: &'a [Range<usize>],
+}
+
+impl<'a> PartialFontRef<'a> {
+    /// Creates a new reference to a partially loaded font.
+    ///
+    /// The table directory must be loaded; if it is not, this returns a
+    /// [`ReadError::NotLoaded`] error.
+    pub fn new(data: &'a [u8], loaded: &'a [Range<usize>]) -> Result<Self, ReadError> {
+        check_loaded(loaded, 0..TABLE_DIRECTORY_HEADER_LEN)?;
+        let num_tables: u16 = FontData::new(data).read_at(4)?;
+        let directory_len =
+            TABLE_DIRECTORY_HEADER_LEN + num_tables as usize * TableRecord::RAW_BYTE_LEN;
+        check_loaded(loaded, 0..directory_len)?;
+        Ok(Self {
+            font: FontRef::new(data)?,
+            loaded,
+        })
+    }
+
+    /// Returns the table directory record for the specified tag, if present.
+    ///
+    /// This is always available, since the table directory is loaded.
+    pub fn table_record(&self, tag: Tag) -> Option<&'a TableRecord> {
+        self.font.table_record(tag)
+    }
+
+    /// Returns the byte range of the table with the specified tag, if present.
+    pub fn table_range(&self, tag: Tag) -> Option<Range<usize>> {
+        let record = self.table_record(tag)?;
+        let start = record.offset().to_u32() as usize;
+        Some(start..start.saturating_add(record.length() as usize))
+    }
+
+    /// Returns the first portion of `range` that has not been loaded, if any.
+    ///
+    /// The result extends to the end of `range`, even if parts of it are
+    /// already loaded.
+    pub fn missing_range(&self, range: Range<usize>) -> Option<Range<usize>> {
+        missing_range(self.loaded, range)
+    }
+
+    /// Returns true if all of the bytes in `range` have been loaded.
+    pub fn is_loaded(&self, range: Range<usize>) -> bool {
+        self.missing_range(range).is_none()
+    }
+}
+
+impl<'a> TableProvider<'a> for PartialFontRef<'a> {
+    fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>> {
+        self.expect_data_for_tag(tag).ok()
+    }
+
+    fn expect_data_for_tag(&self, tag: Tag) -> Result<FontData<'a>, ReadError> {
+        let range = self
+            .table_range(tag)
+            .ok_or(ReadError::TableIsMissing(tag))?;
+        check_loaded(self.loaded, range)?;
+        self.font
+            .table_data(tag)
+            .ok_or(ReadError::TableIsMissing(tag))
+    }
+}
+
+fn check_loaded(loaded: &[Range<usize>], range: Range<usize>) -> Result<(), ReadError> {
+    match missing_range(loaded, range) {
+        Some(missing) => Err(ReadError::NotLoaded(missing)),
+        None => Ok(()),
+    }
+}
+
+fn missing_range(loaded: &[Range<usize>], range: Range<usize>) -> Option<Range<usize>> {
+    let mut pos = range.start;
+    for chunk in loaded {
+        if pos >= range.end || chunk.start > pos {
+            break;
+        }
+        pos = pos.max(chunk.end);
+    }
+    (pos < range.end).then_some(pos..range.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::test_fonts;
+
+    #[test]
+    fn missing_ranges() {
+        let loaded = [0..10, 10..20, 30..40];
+        assert_eq!(missing_range(&loaded, 0..20), None);
+        assert_eq!(missing_range(&loaded, 5..25), Some(20..25));
+        assert_eq!(missing_range(&loaded, 32..35), None);
+        assert_eq!(missing_range(&loaded, 35..50), Some(40..50));
+        assert_eq!(missing_range(&loaded, 45..50), Some(45..50));
+        assert_eq!(missing_range(&loaded, 25..25), None);
+    }
+
+    #[test]
+    fn partial_font() {
+        let data = test_fonts::SIMPLE_GLYF;
+        let header = 0..12;
+        let directory = 0..12 + 7 * 16;
+        assert!(matches!(
+            PartialFontRef::new(data, &[]),
+            Err(ReadError::NotLoaded(range)) if range == (0..12)
+        ));
+        assert!(matches!(
+            PartialFontRef::new(data, std::slice::from_ref(&header)),
+            Err(ReadError::NotLoaded(range)) if range == (12..12 + 7 * 16)
+        ));
+
+        let font = PartialFontRef::new(data, std::slice::from_ref(&directory)).unwrap();
+        let head_range = font.table_range(Tag::new(b"head")).unwrap();
+        match font.head() {
+            Err(ReadError::NotLoaded(range)) => assert_eq!(range, head_range),
+            _ => panic!("head should not be loaded"),
+        }
+        assert!(font.data_for_tag(Tag::new(b"head")).is_none());
+        assert!(matches!(
+            font.gpos(),
+            Err(ReadError::TableIsMissing(tag)) if tag == Tag::new(b"GPOS")
+        ));
+
+        let loaded = [directory, head_range.clone()];
+        let font = PartialFontRef::new(data, &loaded).unwrap();
+        assert!(font.is_loaded(head_range));
+        assert_eq!(font.head().unwrap().units_per_em(), 1024);
+    }
+}
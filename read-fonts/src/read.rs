@@ -1,6 +1,8 @@
 //! Traits for interpreting font data
 
-use types::{FixedSize, ReadScalar, Tag};
+use std::ops::Range;
+
+use types::{FixedSize, GlyphId24, ReadScalar, Tag};
 
 use crate::font_data::FontData;
 
@@ -82,29 +84,42 @@ pub trait VarSize {
 #[derive(Debug, Clone)]
 pub enum ReadError {
     OutOfBounds,
+    /// Data was out of bounds while reading a specific field.
+    OutOfBoundsAt(ErrorLocation),
     // i64 is flexible enough to store any value we might encounter
     InvalidFormat(i64),
     InvalidSfnt(u32),
     InvalidTtc(Tag),
     InvalidCollectionIndex(u32),
+    InvalidGlyphId(GlyphId24),
     InvalidArrayLen,
     ValidationError,
     NullOffset,
     TableIsMissing(Tag),
     MetricIsMissing(Tag),
     MalformedData(&'static str),
+    /// The requested data lies in a byte range of the font that has not yet
+    /// been loaded; see [`PartialFontRef`](crate::PartialFontRef).
+    NotLoaded(Range<usize>),
 }
 
 impl std::fmt::Display for ReadError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             ReadError::OutOfBounds => write!(f, "An offset was out of bounds"),
+            ReadError::OutOfBoundsAt(location) => match location.offset {
+                Some(offset) => write!(f, "{location}: offset 0x{offset:X} beyond end of data"),
+                None => write!(f, "{location}: offset overflowed"),
+            },
             ReadError::InvalidFormat(x) => write!(f, "Invalid format '{x}'"),
             ReadError::InvalidSfnt(ver) => write!(f, "Invalid sfnt version 0x{ver:08X}"),
             ReadError::InvalidTtc(tag) => write!(f, "Invalid ttc tag {tag}"),
             ReadError::InvalidCollectionIndex(ix) => {
                 write!(f, "Invalid index {ix} for font collection")
             }
+            ReadError::InvalidGlyphId(gid) => {
+                write!(f, "Glyph id {} is out of range", gid.to_u32())
+            }
             ReadError::InvalidArrayLen => {
                 write!(f, "Specified array length not a multiple of item size")
             }
@@ -113,9 +128,66 @@ impl std::fmt::Display for ReadError {
             ReadError::TableIsMissing(tag) => write!(f, "the {tag} table is missing"),
             ReadError::MetricIsMissing(tag) => write!(f, "the {tag} metric is missing"),
             ReadError::MalformedData(msg) => write!(f, "Malformed data: '{msg}'"),
+            ReadError::NotLoaded(range) => {
+                write!(
+                    f,
+                    "bytes {}..{} have not been loaded",
+                    range.start, range.end
+                )
+            }
         }
     }
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for ReadError {}
+
+impl ReadError {
+    /// Returns the location at which this error occurred, if known.
+    pub fn location(&self) -> Option<&ErrorLocation> {
+        match self {
+            ReadError::OutOfBoundsAt(location) => Some(location),
+            _ => None,
+        }
+    }
+
+    /// Sets the table tag of the error location, if it has a location that
+    /// does not already specify a table.
+    pub fn with_table(mut self, tag: Tag) -> Self {
+        if let ReadError::OutOfBoundsAt(location) = &mut self {
+            location.table.get_or_insert(tag);
+        }
+        self
+    }
+}
+
+/// Describes where in the font data a [`ReadError`] occurred.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorLocation {
+    /// The tag of the table being read, if known.
+    pub table: Option<Tag>,
+    /// The name of the field being read, as it is written in the spec
+    /// (for example, `glyphVariationDataOffsets`).
+    pub field: &'static str,
+    /// The index of the element being read, for array fields.
+    pub index: Option<usize>,
+    /// The byte offset at which the error occurred, relative to the start of
+    /// the table (or subtable) containing the field.
+    ///
+    /// This is `None` if the offset could not be computed, because it does
+    /// not fit in a `usize`.
+    pub offset: Option<usize>,
+}
+
+impl std::fmt::Display for ErrorLocation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(table) = self.table {
+            write!(f, "{table}.")?;
+        }
+        write!(f, "{}", self.field)?;
+        if let Some(index) = self.index {
+            write!(f, "[{index}]")?;
+        }
+        Ok(())
+    }
+}
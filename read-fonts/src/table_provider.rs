@@ -1,6 +1,6 @@
 //! a trait for things that can serve font tables
 
-use types::Tag;
+use types::{F2Dot14, Fixed, GlyphId, Tag};
 
 use crate::{tables, FontData, FontRead, FontReadWithArgs, ReadError};
 
@@ -12,6 +12,14 @@ pub trait TopLevelTable {
     const TAG: Tag;
 }
 
+/// The direction of text flow, used to select kerning values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Direction {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+
 /// An interface for accessing tables from a font (or font-like object)
 pub trait TableProvider<'a> {
     fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>>;
@@ -21,7 +29,9 @@ pub trait TableProvider<'a> {
     }
 
     fn expect_table<T: TopLevelTable + FontRead<'a>>(&self) -> Result<T, ReadError> {
-        self.expect_data_for_tag(T::TAG).and_then(FontRead::read)
+        self.expect_data_for_tag(T::TAG)
+            .and_then(FontRead::read)
+            .map_err(|e| e.with_table(T::TAG))
     }
 
     fn expect_table_args<T: TopLevelTable + FontReadWithArgs<'a>>(
@@ -30,6 +40,7 @@ pub trait TableProvider<'a> {
     ) -> Result<T, ReadError> {
         self.expect_data_for_tag(T::TAG)
             .and_then(|data| T::read_with_args(data, args))
+            .map_err(|e| e.with_table(T::TAG))
     }
 
     fn head(&self) -> Result<tables::head::Head<'a>, ReadError> {
@@ -111,6 +122,10 @@ pub trait TableProvider<'a> {
         self.expect_table()
     }
 
+    fn cvar(&self) -> Result<tables::cvar::Cvar<'a>, ReadError> {
+        self.expect_table()
+    }
+
     fn cmap(&self) -> Result<tables::cmap::Cmap<'a>, ReadError> {
         self.expect_table()
     }
@@ -138,6 +153,111 @@ pub trait TableProvider<'a> {
     fn stat(&self) -> Result<tables::stat::Stat<'a>, ReadError> {
         self.expect_table()
     }
+
+    fn feat(&self) -> Result<tables::feat::Feat<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn glat(&self) -> Result<tables::glat::Glat<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn gloc(&self) -> Result<tables::gloc::Gloc<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn silf(&self) -> Result<tables::silf::Silf<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn pclt(&self) -> Result<tables::pclt::Pclt<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn ltsh(&self) -> Result<tables::ltsh::Ltsh<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn lcar(&self) -> Result<tables::lcar::Lcar<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn opbd(&self) -> Result<tables::opbd::Opbd<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    fn math(&self) -> Result<tables::math::Math<'a>, ReadError> {
+        self.expect_table()
+    }
+
+    /// Returns the advance width for the specified glyph, adjusted by HVAR
+    /// deltas for the given normalized variation coordinates.
+    ///
+    /// Glyphs beyond the last long metric in hmtx share its advance width.
+    /// If the font has no HVAR table, the coordinates are ignored.
+    fn advance_width(&self, glyph_id: GlyphId, coords: &[F2Dot14]) -> Result<Fixed, ReadError> {
+        let advance = self
+            .hmtx()?
+            .advance(glyph_id)
+            .ok_or(ReadError::InvalidGlyphId(glyph_id.into()))?;
+        let mut advance = Fixed::from_i32(advance as i32);
+        if !coords.is_empty() {
+            match self.hvar() {
+                Ok(hvar) => advance += hvar.advance_width_delta(glyph_id, coords)?,
+                Err(ReadError::TableIsMissing(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(advance)
+    }
+
+    /// Returns the left side bearing for the specified glyph, adjusted by HVAR
+    /// deltas for the given normalized variation coordinates.
+    ///
+    /// Deltas are only applied when HVAR contains a left side bearing mapping.
+    /// Otherwise, varied side bearings must be computed from the outline.
+    fn left_side_bearing(&self, glyph_id: GlyphId, coords: &[F2Dot14]) -> Result<Fixed, ReadError> {
+        let lsb = self
+            .hmtx()?
+            .side_bearing(glyph_id)
+            .ok_or(ReadError::InvalidGlyphId(glyph_id.into()))?;
+        let mut lsb = Fixed::from_i32(lsb as i32);
+        if !coords.is_empty() {
+            match self.hvar() {
+                Ok(hvar) if hvar.lsb_mapping().is_some() => {
+                    lsb += hvar.lsb_delta(glyph_id, coords)?
+                }
+                Ok(_) | Err(ReadError::TableIsMissing(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(lsb)
+    }
+
+    /// Returns the kerning adjustment for a pair of glyphs, in font units.
+    ///
+    /// The pair adjustment lookups of the GPOS `kern` feature, or `vkrn` in
+    /// the vertical direction, are used if there are any (see [`Gpos::kerning`](tables::gpos::Gpos::kerning)).
+    /// Otherwise, the legacy `kern` table is used, which has no accessor of
+    /// its own on this trait but can be read with
+    /// [`expect_table`](Self::expect_table). If the font has neither, the
+    /// adjustment is zero.
+    fn kern(&self, left: GlyphId, right: GlyphId, direction: Direction) -> Result<i32, ReadError> {
+        match self.gpos() {
+            Ok(gpos) => {
+                if let Some(value) = gpos.kerning(left, right, direction)? {
+                    return Ok(value);
+                }
+            }
+            Err(ReadError::TableIsMissing(_)) => {}
+            Err(e) => return Err(e),
+        }
+        match self.expect_table::<tables::kern::Kern>() {
+            Ok(kern) => Ok(kern.kerning(left, right, direction)),
+            Err(ReadError::TableIsMissing(_)) => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -191,4 +311,74 @@ mod tests {
         assert_eq!(hmtx.h_metrics().len(), 1);
         assert_eq!(hmtx.left_side_bearings().len(), 2);
     }
+
+    #[test]
+    fn advance_width_and_lsb() {
+        let font = crate::FontRef::new(crate::test_data::test_fonts::VAZIRMATN_VAR).unwrap();
+        let gid = GlyphId::new(1);
+        let hmtx = font.hmtx().unwrap();
+        let advance = Fixed::from_i32(hmtx.advance(gid).unwrap() as i32);
+        let lsb = Fixed::from_i32(hmtx.side_bearing(gid).unwrap() as i32);
+        assert_eq!(font.advance_width(gid, &[]).unwrap(), advance);
+        assert_eq!(font.left_side_bearing(gid, &[]).unwrap(), lsb);
+        let coords = [F2Dot14::from_f32(-0.75)];
+        assert_eq!(
+            font.advance_width(gid, &coords).unwrap(),
+            advance + Fixed::from_f64(-84.75)
+        );
+        let hvar = font.hvar().unwrap();
+        let lsb_delta = hvar.lsb_delta(gid, &coords).unwrap_or(Fixed::ZERO);
+        assert_eq!(
+            font.left_side_bearing(gid, &coords).unwrap(),
+            lsb + lsb_delta
+        );
+        let num_glyphs = font.maxp().unwrap().num_glyphs();
+        assert!(font.advance_width(GlyphId::new(num_glyphs), &[]).is_err());
+    }
+
+    #[test]
+    fn kern_without_tables() {
+        struct Empty;
+        impl TableProvider<'static> for Empty {
+            fn data_for_tag(&self, _tag: Tag) -> Option<FontData<'static>> {
+                None
+            }
+        }
+        let (left, right) = (GlyphId::new(1), GlyphId::new(2));
+        assert_eq!(Empty.kern(left, right, Direction::Horizontal).unwrap(), 0);
+    }
+
+    #[test]
+    fn error_location() {
+        struct TruncatedMaxp;
+        impl TableProvider<'static> for TruncatedMaxp {
+            fn data_for_tag(&self, tag: Tag) -> Option<FontData<'static>> {
+                (tag == Tag::new(b"maxp")).then(|| FontData::new(&[0, 0, 0x50, 0, 0]))
+            }
+        }
+        let err = TruncatedMaxp.maxp().unwrap_err();
+        assert_eq!(
+            err.location(),
+            Some(&crate::ErrorLocation {
+                table: Some(Tag::new(b"maxp")),
+                field: "numGlyphs",
+                index: None,
+                offset: Some(4),
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "maxp.numGlyphs: offset 0x4 beyond end of data"
+        );
+        let overflowed = ReadError::OutOfBoundsAt(crate::ErrorLocation {
+            table: Some(Tag::new(b"gvar")),
+            field: "glyphVariationDataOffsets",
+            index: Some(1),
+            offset: None,
+        });
+        assert_eq!(
+            overflowed.to_string(),
+            "gvar.glyphVariationDataOffsets[1]: offset overflowed"
+        );
+    }
 }
@@ -1,12 +1,17 @@
 //! The various font tables
 
+pub mod aat;
 pub mod avar;
 pub mod base;
 pub mod cmap;
 pub mod colr;
 pub mod cpal;
+pub mod cvar;
+pub mod feat;
 pub mod fvar;
 pub mod gdef;
+pub mod glat;
+pub mod gloc;
 pub mod glyf;
 pub mod gpos;
 pub mod gsub;
@@ -15,13 +20,20 @@ pub mod head;
 pub mod hhea;
 pub mod hmtx;
 pub mod hvar;
+pub mod kern;
 pub mod layout;
+pub mod lcar;
 pub mod loca;
+pub mod ltsh;
+pub mod math;
 pub mod maxp;
 pub mod mvar;
 pub mod name;
+pub mod opbd;
 pub mod os2;
+pub mod pclt;
 pub mod post;
+pub mod silf;
 pub mod stat;
 pub mod variations;
 pub mod vhea;
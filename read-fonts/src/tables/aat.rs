@@ -0,0 +1,140 @@
+//! Shared [AAT](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6Tables.html) table structures.
+
+include!("../../generated/generated_aat.rs");
+
+impl<'a> Lookup<'a> {
+    /// Returns the value associated with the given glyph, if any.
+    pub fn value(&self, glyph_id: GlyphId) -> Option<u16> {
+        let gid = glyph_id.to_u16();
+        match self {
+            Self::Format0(lookup) => lookup.values().get(gid as usize).map(|v| v.get()),
+            Self::Format2(lookup) => {
+                check_unit_size::<LookupSegment>(lookup.unit_size())?;
+                find_segment(lookup.segments(), gid).map(|segment| segment.value())
+            }
+            Self::Format4(lookup) => {
+                check_unit_size::<LookupSegment>(lookup.unit_size())?;
+                let segment = find_segment(lookup.segments(), gid)?;
+                let index = (gid - segment.first_glyph()) as usize;
+                let offset = segment.value() as usize + index * u16::RAW_BYTE_LEN;
+                lookup.offset_data().read_at(offset).ok()
+            }
+            Self::Format6(lookup) => {
+                check_unit_size::<LookupSingle>(lookup.unit_size())?;
+                let entries = lookup.entries();
+                entries
+                    .binary_search_by_key(&gid, |entry| entry.glyph())
+                    .ok()
+                    .map(|idx| entries[idx].value())
+            }
+            Self::Format8(lookup) => {
+                let index = gid.checked_sub(lookup.first_glyph())?;
+                lookup.values().get(index as usize).map(|v| v.get())
+            }
+            Self::Format10(lookup) => {
+                let index = gid.checked_sub(lookup.first_glyph())?;
+                if index >= lookup.glyph_count() {
+                    return None;
+                }
+                let data = FontData::new(lookup.values());
+                let offset = index as usize * lookup.unit_size() as usize;
+                match lookup.unit_size() {
+                    1 => data.read_at::<u8>(offset).ok().map(u16::from),
+                    2 => data.read_at::<u16>(offset).ok(),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+// the binary search header allows units larger than the records we read,
+// which would only be the case for lookups with values larger than 16 bits.
+fn check_unit_size<T: FixedSize>(unit_size: u16) -> Option<()> {
+    (unit_size as usize == T::RAW_BYTE_LEN).then_some(())
+}
+
+fn find_segment(segments: &[LookupSegment], gid: u16) -> Option<&LookupSegment> {
+    let idx = segments.partition_point(|segment| segment.last_glyph() < gid);
+    segments
+        .get(idx)
+        .filter(|segment| segment.first_glyph() <= gid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    fn lookup_values(lookup: &Lookup, gids: &[u16]) -> Vec<Option<u16>> {
+        gids.iter()
+            .map(|gid| lookup.value(GlyphId::new(*gid)))
+            .collect()
+    }
+
+    #[test]
+    fn format_0() {
+        let buf = BeBuffer::new().push(0u16).extend([5u16, 6, 7]);
+        let lookup = Lookup::read(buf.font_data()).unwrap();
+        assert_eq!(lookup_values(&lookup, &[0, 2, 3]), [Some(5), Some(7), None]);
+    }
+
+    #[test]
+    fn format_2() {
+        let buf = BeBuffer::new()
+            .extend([2u16, 6, 3, 12, 1, 6])
+            .extend([5u16, 3, 100])
+            .extend([12u16, 10, 200])
+            .extend([0xFFFFu16, 0xFFFF, 0]);
+        let lookup = Lookup::read(buf.font_data()).unwrap();
+        assert_eq!(
+            lookup_values(&lookup, &[2, 3, 5, 6, 10, 12, 13]),
+            [None, Some(100), Some(100), None, Some(200), Some(200), None]
+        );
+    }
+
+    #[test]
+    fn format_4() {
+        let buf = BeBuffer::new()
+            .extend([4u16, 6, 2, 12, 1, 0])
+            .extend([4u16, 2, 24])
+            .extend([0xFFFFu16, 0xFFFF, 0])
+            .extend([7u16, 8, 9]);
+        let lookup = Lookup::read(buf.font_data()).unwrap();
+        assert_eq!(
+            lookup_values(&lookup, &[1, 2, 3, 4, 5]),
+            [None, Some(7), Some(8), Some(9), None]
+        );
+    }
+
+    #[test]
+    fn format_6() {
+        let buf = BeBuffer::new()
+            .extend([6u16, 4, 2, 8, 1, 0])
+            .extend([3u16, 30, 9, 90]);
+        let lookup = Lookup::read(buf.font_data()).unwrap();
+        assert_eq!(
+            lookup_values(&lookup, &[3, 4, 9]),
+            [Some(30), None, Some(90)]
+        );
+    }
+
+    #[test]
+    fn format_8_and_10() {
+        let buf = BeBuffer::new().extend([8u16, 10, 2, 1, 2]);
+        let lookup = Lookup::read(buf.font_data()).unwrap();
+        assert_eq!(
+            lookup_values(&lookup, &[9, 10, 11, 12]),
+            [None, Some(1), Some(2), None]
+        );
+
+        let buf = BeBuffer::new()
+            .extend([10u16, 1, 10, 2])
+            .extend([1u8, 2, 3]);
+        let lookup = Lookup::read(buf.font_data()).unwrap();
+        assert_eq!(
+            lookup_values(&lookup, &[9, 10, 11, 12]),
+            [None, Some(1), Some(2), None]
+        );
+    }
+}
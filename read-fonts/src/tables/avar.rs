@@ -35,7 +35,8 @@ impl<'a> VarSize for SegmentMaps<'a> {
     type Size = u16;
 
     fn read_len_at(data: FontData, pos: usize) -> Option<usize> {
-        Some(data.read_at::<u16>(pos).ok()? as usize * AxisValueMap::RAW_BYTE_LEN)
+        let count = data.read_at::<u16>(pos).ok()? as usize;
+        Some(u16::RAW_BYTE_LEN + count * AxisValueMap::RAW_BYTE_LEN)
     }
 }
 
@@ -4,6 +4,20 @@ use super::{layout::Device, variations::ItemVariationStore};
 
 include!("../../generated/generated_base.rs");
 
+impl BaseCoord<'_> {
+    /// The X or Y value of the coordinate, in design units.
+    ///
+    /// This is shared by all formats; formats 2 and 3 only add ways of
+    /// adjusting it.
+    pub fn coordinate(&self) -> i16 {
+        match self {
+            BaseCoord::Format1(item) => item.coordinate(),
+            BaseCoord::Format2(item) => item.coordinate(),
+            BaseCoord::Format3(item) => item.coordinate(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use font_types::MajorMinor;
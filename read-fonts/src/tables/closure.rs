@@ -0,0 +1,444 @@
+//! Computing the closure of a set of glyphs under GSUB substitution.
+//!
+//! This is the set of glyphs that may be produced by shaping some text whose
+//! glyphs are in the input set, and is the main input needed to subset a
+//! font.
+
+use std::collections::BTreeSet;
+
+use types::{GlyphId, Tag};
+
+use super::{
+    AlternateSubstFormat1, ChainedSequenceContext, ClassDef, CoverageTable, ExtensionSubtable,
+    Gsub, LigatureSubstFormat1, Lookup, MultipleSubstFormat1, ReverseChainSingleSubstFormat1,
+    SequenceContext, SingleSubst, SubstitutionLookup, SubstitutionLookupList,
+};
+use crate::tables::layout::SequenceLookupRecord;
+use crate::{FontRead, ReadError};
+
+/// The maximum depth of nested contextual lookups we will follow.
+///
+/// This matches `HB_MAX_NESTING_LEVEL` in HarfBuzz.
+const MAX_NESTING_LEVEL: u8 = 6;
+
+impl<'a> Gsub<'a> {
+    /// Return the indices of all lookups referenced by the provided features.
+    ///
+    /// Features are matched by tag, regardless of the script or language
+    /// system that references them.
+    pub fn collect_lookups(&self, features: &[Tag]) -> Result<BTreeSet<u16>, ReadError> {
+        let feature_list = self.feature_list()?;
+        let mut lookups = BTreeSet::new();
+        for record in feature_list.feature_records() {
+            if features.contains(&record.feature_tag()) {
+                let feature = record.feature(feature_list.offset_data())?;
+                lookups.extend(feature.lookup_list_indices().iter().map(|idx| idx.get()));
+            }
+        }
+        Ok(lookups)
+    }
+
+    /// Compute the closure of `glyphs` under the lookups referenced by `features`.
+    ///
+    /// The lookups are applied repeatedly until no new glyphs are produced.
+    /// Contextual lookups are handled conservatively: if every position of a
+    /// rule's context intersects the current set, the nested lookups are applied
+    /// to the whole set. The result may therefore include some glyphs that
+    /// could never actually be produced, but it will never miss one.
+    pub fn closure(
+        &self,
+        glyphs: &BTreeSet<GlyphId>,
+        features: &[Tag],
+    ) -> Result<BTreeSet<GlyphId>, ReadError> {
+        let lookups = self.collect_lookups(features)?;
+        let mut ctx = ClosureCtx {
+            glyphs: glyphs.clone(),
+            lookup_list: self.lookup_list()?,
+            nesting_level: 0,
+        };
+        loop {
+            let prev_len = ctx.glyphs.len();
+            for lookup_index in &lookups {
+                ctx.close_lookup(*lookup_index)?;
+            }
+            if ctx.glyphs.len() == prev_len {
+                return Ok(ctx.glyphs);
+            }
+        }
+    }
+}
+
+struct ClosureCtx<'a> {
+    glyphs: BTreeSet<GlyphId>,
+    lookup_list: SubstitutionLookupList<'a>,
+    nesting_level: u8,
+}
+
+impl ClosureCtx<'_> {
+    fn close_lookup(&mut self, lookup_index: u16) -> Result<(), ReadError> {
+        if self.nesting_level >= MAX_NESTING_LEVEL {
+            return Ok(());
+        }
+        let lookup = self.lookup_list.get(lookup_index)?;
+        self.nesting_level += 1;
+        let result = lookup.add_reachable_glyphs(self);
+        self.nesting_level -= 1;
+        result
+    }
+
+    fn close_nested(&mut self, records: &[SequenceLookupRecord]) -> Result<(), ReadError> {
+        for record in records {
+            self.close_lookup(record.lookup_list_index())?;
+        }
+        Ok(())
+    }
+
+    fn contains(&self, gid: GlyphId) -> bool {
+        self.glyphs.contains(&gid)
+    }
+
+    fn intersects_coverage(&self, coverage: &CoverageTable) -> bool {
+        self.glyphs.iter().any(|gid| coverage.get(*gid).is_some())
+    }
+
+    fn intersects_class(&self, class_def: &ClassDef, class: u16) -> bool {
+        // class 0 is every glyph not explicitly assigned a class
+        if class == 0 {
+            self.glyphs.iter().any(|gid| class_def.get(*gid) == 0)
+        } else {
+            class_def
+                .iter()
+                .any(|(gid, gid_class)| gid_class == class && self.contains(gid))
+        }
+    }
+
+    /// Returns `true` if some glyph in the set is covered and has the given class.
+    fn intersects_coverage_and_class(
+        &self,
+        coverage: &CoverageTable,
+        class_def: &ClassDef,
+        class: u16,
+    ) -> bool {
+        self.glyphs
+            .iter()
+            .any(|gid| coverage.get(*gid).is_some() && class_def.get(*gid) == class)
+    }
+}
+
+/// A lookup or subtable that can add glyphs to a closure.
+trait GlyphClosure {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError>;
+}
+
+impl<'a, T: FontRead<'a> + GlyphClosure> GlyphClosure for Lookup<'a, T> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        for subtable in self.subtables() {
+            subtable?.add_reachable_glyphs(ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl GlyphClosure for SubstitutionLookup<'_> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        match self {
+            SubstitutionLookup::Single(lookup) => lookup.add_reachable_glyphs(ctx),
+            SubstitutionLookup::Multiple(lookup) => lookup.add_reachable_glyphs(ctx),
+            SubstitutionLookup::Alternate(lookup) => lookup.add_reachable_glyphs(ctx),
+            SubstitutionLookup::Ligature(lookup) => lookup.add_reachable_glyphs(ctx),
+            SubstitutionLookup::Contextual(lookup) => lookup.add_reachable_glyphs(ctx),
+            SubstitutionLookup::ChainContextual(lookup) => lookup.add_reachable_glyphs(ctx),
+            SubstitutionLookup::Extension(lookup) => lookup.add_reachable_glyphs(ctx),
+            SubstitutionLookup::Reverse(lookup) => lookup.add_reachable_glyphs(ctx),
+        }
+    }
+}
+
+impl GlyphClosure for ExtensionSubtable<'_> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        match self {
+            ExtensionSubtable::Single(table) => table.extension()?.add_reachable_glyphs(ctx),
+            ExtensionSubtable::Multiple(table) => table.extension()?.add_reachable_glyphs(ctx),
+            ExtensionSubtable::Alternate(table) => table.extension()?.add_reachable_glyphs(ctx),
+            ExtensionSubtable::Ligature(table) => table.extension()?.add_reachable_glyphs(ctx),
+            ExtensionSubtable::Contextual(table) => table.extension()?.add_reachable_glyphs(ctx),
+            ExtensionSubtable::ChainContextual(table) => {
+                table.extension()?.add_reachable_glyphs(ctx)
+            }
+            ExtensionSubtable::Reverse(table) => table.extension()?.add_reachable_glyphs(ctx),
+        }
+    }
+}
+
+impl GlyphClosure for SingleSubst<'_> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        let new_glyphs: Vec<_> = match self {
+            SingleSubst::Format1(table) => {
+                let delta = table.delta_glyph_id() as u16;
+                table
+                    .coverage()?
+                    .iter()
+                    .filter(|gid| ctx.contains(*gid))
+                    // addition is modulo 65536
+                    .map(|gid| GlyphId::new(gid.to_u16().wrapping_add(delta)))
+                    .collect()
+            }
+            SingleSubst::Format2(table) => table
+                .coverage()?
+                .iter()
+                .zip(table.substitute_glyph_ids())
+                .filter(|(gid, _)| ctx.contains(*gid))
+                .map(|(_, sub)| sub.get())
+                .collect(),
+        };
+        ctx.glyphs.extend(new_glyphs);
+        Ok(())
+    }
+}
+
+impl GlyphClosure for MultipleSubstFormat1<'_> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        let mut new_glyphs = Vec::new();
+        for (gid, sequence) in self.coverage()?.iter().zip(self.sequences()) {
+            if ctx.contains(gid) {
+                new_glyphs.extend(sequence?.substitute_glyph_ids().iter().map(|g| g.get()));
+            }
+        }
+        ctx.glyphs.extend(new_glyphs);
+        Ok(())
+    }
+}
+
+impl GlyphClosure for AlternateSubstFormat1<'_> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        let mut new_glyphs = Vec::new();
+        for (gid, alternates) in self.coverage()?.iter().zip(self.alternate_sets()) {
+            if ctx.contains(gid) {
+                new_glyphs.extend(alternates?.alternate_glyph_ids().iter().map(|g| g.get()));
+            }
+        }
+        ctx.glyphs.extend(new_glyphs);
+        Ok(())
+    }
+}
+
+impl GlyphClosure for LigatureSubstFormat1<'_> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        let mut new_glyphs = Vec::new();
+        for (gid, ligatures) in self.coverage()?.iter().zip(self.ligature_sets()) {
+            if !ctx.contains(gid) {
+                continue;
+            }
+            for ligature in ligatures?.ligatures() {
+                let ligature = ligature?;
+                if ligature
+                    .component_glyph_ids()
+                    .iter()
+                    .all(|g| ctx.contains(g.get()))
+                {
+                    new_glyphs.push(ligature.ligature_glyph());
+                }
+            }
+        }
+        ctx.glyphs.extend(new_glyphs);
+        Ok(())
+    }
+}
+
+impl GlyphClosure for SequenceContext<'_> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        match self {
+            SequenceContext::Format1(table) => {
+                let coverage = table.coverage()?;
+                for (gid, rule_set) in coverage.iter().zip(table.seq_rule_sets()) {
+                    let Some(rule_set) = rule_set.transpose()? else {
+                        continue;
+                    };
+                    if !ctx.contains(gid) {
+                        continue;
+                    }
+                    for rule in rule_set.seq_rules() {
+                        let rule = rule?;
+                        if rule.input_sequence().iter().all(|g| ctx.contains(g.get())) {
+                            ctx.close_nested(rule.seq_lookup_records())?;
+                        }
+                    }
+                }
+            }
+            SequenceContext::Format2(table) => {
+                let coverage = table.coverage()?;
+                let class_def = table.class_def()?;
+                for (class, rule_set) in table.class_seq_rule_sets().enumerate() {
+                    let Some(rule_set) = rule_set.transpose()? else {
+                        continue;
+                    };
+                    if !ctx.intersects_coverage_and_class(&coverage, &class_def, class as u16) {
+                        continue;
+                    }
+                    for rule in rule_set.class_seq_rules() {
+                        let rule = rule?;
+                        if rule
+                            .input_sequence()
+                            .iter()
+                            .all(|class| ctx.intersects_class(&class_def, class.get()))
+                        {
+                            ctx.close_nested(rule.seq_lookup_records())?;
+                        }
+                    }
+                }
+            }
+            SequenceContext::Format3(table) => {
+                for coverage in table.coverages() {
+                    if !ctx.intersects_coverage(&coverage?) {
+                        return Ok(());
+                    }
+                }
+                ctx.close_nested(table.seq_lookup_records())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GlyphClosure for ChainedSequenceContext<'_> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        match self {
+            ChainedSequenceContext::Format1(table) => {
+                let coverage = table.coverage()?;
+                for (gid, rule_set) in coverage.iter().zip(table.chained_seq_rule_sets()) {
+                    let Some(rule_set) = rule_set.transpose()? else {
+                        continue;
+                    };
+                    if !ctx.contains(gid) {
+                        continue;
+                    }
+                    for rule in rule_set.chained_seq_rules() {
+                        let rule = rule?;
+                        if rule
+                            .backtrack_sequence()
+                            .iter()
+                            .chain(rule.input_sequence())
+                            .chain(rule.lookahead_sequence())
+                            .all(|g| ctx.contains(g.get()))
+                        {
+                            ctx.close_nested(rule.seq_lookup_records())?;
+                        }
+                    }
+                }
+            }
+            ChainedSequenceContext::Format2(table) => {
+                let coverage = table.coverage()?;
+                let backtrack_class_def = table.backtrack_class_def()?;
+                let input_class_def = table.input_class_def()?;
+                let lookahead_class_def = table.lookahead_class_def()?;
+                for (class, rule_set) in table.chained_class_seq_rule_sets().enumerate() {
+                    let Some(rule_set) = rule_set.transpose()? else {
+                        continue;
+                    };
+                    if !ctx.intersects_coverage_and_class(&coverage, &input_class_def, class as u16)
+                    {
+                        continue;
+                    }
+                    for rule in rule_set.chained_class_seq_rules() {
+                        let rule = rule?;
+                        let matches =
+                            rule.backtrack_sequence().iter().all(|class| {
+                                ctx.intersects_class(&backtrack_class_def, class.get())
+                            }) && rule
+                                .input_sequence()
+                                .iter()
+                                .all(|class| ctx.intersects_class(&input_class_def, class.get()))
+                                && rule.lookahead_sequence().iter().all(|class| {
+                                    ctx.intersects_class(&lookahead_class_def, class.get())
+                                });
+                        if matches {
+                            ctx.close_nested(rule.seq_lookup_records())?;
+                        }
+                    }
+                }
+            }
+            ChainedSequenceContext::Format3(table) => {
+                let coverages = table
+                    .backtrack_coverages()
+                    .chain(table.input_coverages())
+                    .chain(table.lookahead_coverages());
+                for coverage in coverages {
+                    if !ctx.intersects_coverage(&coverage?) {
+                        return Ok(());
+                    }
+                }
+                ctx.close_nested(table.seq_lookup_records())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GlyphClosure for ReverseChainSingleSubstFormat1<'_> {
+    fn add_reachable_glyphs(&self, ctx: &mut ClosureCtx) -> Result<(), ReadError> {
+        for coverage in self.backtrack_coverages().chain(self.lookahead_coverages()) {
+            if !ctx.intersects_coverage(&coverage?) {
+                return Ok(());
+            }
+        }
+        let new_glyphs: Vec<_> = self
+            .coverage()?
+            .iter()
+            .zip(self.substitute_glyph_ids())
+            .filter(|(gid, _)| ctx.contains(*gid))
+            .map(|(_, sub)| sub.get())
+            .collect();
+        ctx.glyphs.extend(new_glyphs);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::gsub::CLOSURE_GSUB;
+
+    fn closure(glyphs: &[u16], features: &[&[u8; 4]]) -> Vec<u16> {
+        let gsub = Gsub::read(CLOSURE_GSUB).unwrap();
+        let glyphs = glyphs.iter().copied().map(GlyphId::new).collect();
+        let features = features.iter().map(|t| Tag::new(*t)).collect::<Vec<_>>();
+        gsub.closure(&glyphs, &features)
+            .unwrap()
+            .into_iter()
+            .map(GlyphId::to_u16)
+            .collect()
+    }
+
+    #[test]
+    fn collect_lookups() {
+        let gsub = Gsub::read(CLOSURE_GSUB).unwrap();
+        let lookups = gsub.collect_lookups(&[Tag::new(b"liga")]).unwrap();
+        assert_eq!(lookups.into_iter().collect::<Vec<_>>(), [1]);
+        let lookups = gsub.collect_lookups(&[Tag::new(b"kern")]).unwrap();
+        assert!(lookups.is_empty());
+    }
+
+    #[test]
+    fn single_subst() {
+        assert_eq!(closure(&[10], &[b"salt"]), [10, 20]);
+        assert_eq!(closure(&[11], &[b"salt"]), [11]);
+        // feature not selected
+        assert_eq!(closure(&[10], &[b"liga"]), [10]);
+    }
+
+    #[test]
+    fn nested_ligature() {
+        // the ligature lookup is only reachable through the contextual lookup
+        assert_eq!(closure(&[20, 21], &[b"liga"]), [20, 21, 30]);
+        // context does not match
+        assert_eq!(closure(&[21], &[b"liga"]), [21]);
+        // ligature components not all present
+        assert_eq!(closure(&[10], &[b"liga", b"salt"]), [10, 20]);
+    }
+
+    #[test]
+    fn fixed_point() {
+        // 20 is only produced by 'salt', and then enables 'liga'
+        assert_eq!(closure(&[10, 21], &[b"liga", b"salt"]), [10, 20, 21, 30]);
+    }
+}
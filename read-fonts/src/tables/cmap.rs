@@ -22,6 +22,14 @@ impl<'a> Cmap<'a> {
     }
 }
 
+// the enum is generated, so the default can't be derived
+#[allow(clippy::derivable_impls)]
+impl Default for PlatformId {
+    fn default() -> Self {
+        PlatformId::Unicode
+    }
+}
+
 impl<'a> Cmap4<'a> {
     /// Maps a codepoint to a nominal glyph identifier.
     pub fn map_codepoint(&self, codepoint: impl Into<u32>) -> Option<GlyphId> {
@@ -42,26 +50,50 @@ impl<'a> Cmap4<'a> {
             } else if codepoint > end_codes.get(i)?.get() {
                 lo = i + 1;
             } else {
-                let deltas = self.id_delta();
-                let range_offsets = self.id_range_offsets();
-                let delta = deltas.get(i)?.get() as i32;
-                let range_offset = range_offsets.get(i)?.get() as usize;
-                if range_offset == 0 {
-                    return Some(GlyphId::new((codepoint as i32 + delta) as u16));
-                }
-                // sigh
-                let mut offset = range_offset / 2 + (codepoint - start_code) as usize;
-                offset = offset.saturating_sub(range_offsets.len() - i);
-                let gid = self.glyph_id_array().get(offset)?.get();
-                if gid != 0 {
-                    return Some(GlyphId::new((gid as i32 + delta) as u16));
-                } else {
-                    return None;
-                }
+                return self.lookup_glyph_id(i, codepoint);
             }
         }
         None
     }
+
+    /// Returns an iterator over all mappings from codepoint to nominal glyph
+    /// identifier, in codepoint order.
+    ///
+    /// Codepoints that map to the `.notdef` glyph are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphId)> + 'a {
+        let subtable = self.clone();
+        let segments = self
+            .start_code()
+            .iter()
+            .zip(self.end_code())
+            .map(|(start, end)| (start.get(), end.get()))
+            .enumerate();
+        segments.flat_map(move |(i, (start, end))| {
+            let subtable = subtable.clone();
+            (start..=end).filter_map(move |codepoint| {
+                subtable
+                    .lookup_glyph_id(i, codepoint)
+                    .filter(|gid| *gid != GlyphId::NOTDEF)
+                    .map(|gid| (codepoint as u32, gid))
+            })
+        })
+    }
+
+    /// Maps a codepoint to a glyph identifier using the segment at `index`.
+    fn lookup_glyph_id(&self, index: usize, codepoint: u16) -> Option<GlyphId> {
+        let start_code = self.start_code().get(index)?.get();
+        let range_offsets = self.id_range_offsets();
+        let delta = self.id_delta().get(index)?.get() as i32;
+        let range_offset = range_offsets.get(index)?.get() as usize;
+        if range_offset == 0 {
+            return Some(GlyphId::new((codepoint as i32 + delta) as u16));
+        }
+        // sigh
+        let mut offset = range_offset / 2 + (codepoint - start_code) as usize;
+        offset = offset.saturating_sub(range_offsets.len() - index);
+        let gid = self.glyph_id_array().get(offset)?.get();
+        (gid != 0).then(|| GlyphId::new((gid as i32 + delta) as u16))
+    }
 }
 
 impl<'a> Cmap12<'a> {
@@ -89,6 +121,21 @@ impl<'a> Cmap12<'a> {
         }
         None
     }
+
+    /// Returns an iterator over all mappings from codepoint to nominal glyph
+    /// identifier, in codepoint order.
+    ///
+    /// Codepoints that map to the `.notdef` glyph are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, GlyphId)> + 'a {
+        self.groups().iter().flat_map(|group| {
+            let start = group.start_char_code();
+            let start_gid = group.start_glyph_id();
+            (start..=group.end_char_code().min(0x10FFFF)).filter_map(move |codepoint| {
+                let gid = start_gid.wrapping_add(codepoint - start) as u16;
+                (gid != 0).then(|| (codepoint, GlyphId::new(gid)))
+            })
+        })
+    }
 }
 
 #[cfg(test)]
@@ -111,4 +158,28 @@ mod tests {
         assert_eq!(cmap.map_codepoint(0xE_u32), Some(GlyphId::new(2)));
         assert_eq!(cmap.map_codepoint('B'), None);
     }
+
+    #[test]
+    fn iter_mappings() {
+        use super::CmapSubtable;
+        for font in [
+            test_data::test_fonts::VAZIRMATN_VAR,
+            test_data::test_fonts::SIMPLE_GLYF,
+        ] {
+            let font = FontRef::new(font).unwrap();
+            let cmap = font.cmap().unwrap();
+            for record in cmap.encoding_records() {
+                let mappings = match record.subtable(cmap.offset_data()).unwrap() {
+                    CmapSubtable::Format4(subtable) => subtable.iter().collect::<Vec<_>>(),
+                    CmapSubtable::Format12(subtable) => subtable.iter().collect(),
+                    _ => continue,
+                };
+                assert!(!mappings.is_empty());
+                assert!(mappings.windows(2).all(|pair| pair[0].0 < pair[1].0));
+                for (codepoint, gid) in mappings {
+                    assert_eq!(cmap.map_codepoint(codepoint), Some(gid));
+                }
+            }
+        }
+    }
 }
@@ -3,3 +3,18 @@
 use super::variations::{DeltaSetIndexMap, ItemVariationStore};
 
 include!("../../generated/generated_colr.rs");
+
+// these enums are generated, so the defaults can't be derived
+#[allow(clippy::derivable_impls)]
+impl Default for Extend {
+    fn default() -> Self {
+        Extend::Pad
+    }
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for CompositeMode {
+    fn default() -> Self {
+        CompositeMode::SrcOver
+    }
+}
@@ -0,0 +1,123 @@
+//! The [cvar (CVT Variations)](https://learn.microsoft.com/en-us/typography/opentype/spec/cvar)
+//! table
+
+include!("../../generated/generated_cvar.rs");
+
+use super::variations::{
+    PackedDeltas, PackedPointNumbers, TupleVariationCount, TupleVariationHeader,
+    TupleVariationHeaderIter,
+};
+
+impl<'a> Cvar<'a> {
+    fn raw_tuple_header_data(&self) -> FontData<'a> {
+        let range = self.shape.tuple_variation_headers_byte_range();
+        self.data.split_off(range.start).unwrap()
+    }
+
+    /// Computes the accumulated deltas for the control value table at the
+    /// given normalized variation coordinates.
+    ///
+    /// The `axis_count` must be the number of axes in the `fvar` table. The
+    /// deltas of each applicable tuple are scaled and added to the entry in
+    /// `deltas` for the corresponding CVT value. Unlike glyph variations,
+    /// deltas for values that are not referenced by a tuple are not inferred.
+    pub fn deltas(
+        &self,
+        axis_count: u16,
+        coords: &[F2Dot14],
+        deltas: &mut [Fixed],
+    ) -> Result<(), ReadError> {
+        let count = self.tuple_variation_count();
+        let mut serialized_data = self.serialized_data()?;
+        let shared_point_numbers = if count.shared_point_numbers() {
+            let (packed, data) = PackedPointNumbers::split_off_front(serialized_data);
+            serialized_data = data;
+            Some(packed)
+        } else {
+            None
+        };
+        let headers = TupleVariationHeaderIter::new(
+            self.raw_tuple_header_data(),
+            count.count() as usize,
+            axis_count,
+        );
+        for header in headers {
+            let header = header?;
+            let data_len = header.variation_data_size() as usize;
+            let data = serialized_data
+                .take_up_to(data_len)
+                .ok_or(ReadError::OutOfBounds)?;
+            let (point_numbers, packed_deltas) = if header.tuple_index().private_point_numbers() {
+                PackedPointNumbers::split_off_front(data)
+            } else {
+                (
+                    shared_point_numbers.clone().ok_or(ReadError::OutOfBounds)?,
+                    data,
+                )
+            };
+            // the peak tuple is always embedded in cvar
+            let Some(peak) = header.peak_tuple() else {
+                continue;
+            };
+            let Some(scalar) = header.compute_scalar(peak, axis_count, coords) else {
+                continue;
+            };
+            if scalar == Fixed::ZERO {
+                continue;
+            }
+            let packed_deltas = PackedDeltas::new(packed_deltas);
+            let mut apply = |index: usize, delta: i16| {
+                if let Some(target) = deltas.get_mut(index) {
+                    *target += Fixed::from_i32(delta as i32) * scalar;
+                }
+            };
+            if point_numbers.count() == 0 {
+                for (index, delta) in packed_deltas.iter().enumerate() {
+                    apply(index, delta);
+                }
+            } else {
+                for (index, delta) in point_numbers.iter().zip(packed_deltas.iter()) {
+                    apply(index as usize, delta);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cvt_deltas() {
+        let data = [
+            // version, two tuples with shared points, offset to data
+            [0, 1, 0, 0, 0x80, 2, 0, 24].as_slice(),
+            // a tuple peaking at 1.0 on the first of two axes
+            &[0, 3, 0x80, 0, 0x40, 0, 0, 0],
+            // a tuple peaking at -1.0 on the second axis, with private points
+            &[0, 5, 0xA0, 0, 0, 0, 0xC0, 0],
+            // shared points 0 and 2
+            &[2, 1, 0, 2],
+            // deltas of 10 and -20
+            &[1, 10, 0xEC],
+            // point 1 with a delta of 100
+            &[1, 0, 1, 0, 100],
+        ]
+        .concat();
+        let cvar = Cvar::read(FontData::new(&data)).unwrap();
+        assert_eq!(cvar.version(), MajorMinor::VERSION_1_0);
+        for (coords, expected) in [
+            ([0.0, 0.0], [0.0, 0.0, 0.0]),
+            ([0.5, 0.0], [5.0, 0.0, -10.0]),
+            ([1.0, -0.5], [10.0, 50.0, -20.0]),
+            ([-1.0, -1.0], [0.0, 100.0, 0.0]),
+        ] {
+            let coords = coords.map(F2Dot14::from_f32);
+            let mut deltas = [Fixed::ZERO; 3];
+            cvar.deltas(2, &coords, &mut deltas).unwrap();
+            assert_eq!(deltas, expected.map(Fixed::from_f64));
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! The Graphite [Feat (Feature)](https://github.com/silnrsi/graphite/blob/master/doc/table_feat.txt) table
+
+include!("../../generated/generated_feat.rs");
+
+impl<'a> Feat<'a> {
+    /// Returns the definition of the feature with the given id, if present.
+    pub fn feature(&self, id: u32) -> Option<&'a FeatureDefn> {
+        let features = self.features();
+        features
+            .binary_search_by_key(&id, |feature| feature.id())
+            .ok()
+            .map(|idx| &features[idx])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn read_feat() {
+        let buf = BeBuffer::new()
+            .push(Version16Dot16::VERSION_2_0)
+            .extend([2u16, 0]) // num_feat, reserved
+            .push(0u32) // reserved
+            // feature 1 with one setting
+            .push(1u32)
+            .extend([1u16, 0])
+            .push(44u32) // settings offset
+            .extend([0x8000u16, 258])
+            // feature 'smcp' with two settings
+            .push(u32::from_be_bytes(*b"smcp"))
+            .extend([2u16, 0])
+            .push(48u32)
+            .extend([0u16, 256])
+            // settings
+            .extend([7i16, 259, 0, 257, 1])
+            .push(258u16);
+        let feat = Feat::read(buf.font_data()).unwrap();
+        assert_eq!(feat.version(), Version16Dot16::VERSION_2_0);
+        assert_eq!(feat.num_feat(), 2);
+
+        let smcp = feat.feature(u32::from_be_bytes(*b"smcp")).unwrap();
+        assert_eq!(smcp.label(), 256);
+        let settings = smcp.settings(feat.offset_data()).unwrap();
+        let settings = settings
+            .iter()
+            .map(|s| (s.value(), s.label()))
+            .collect::<Vec<_>>();
+        assert_eq!(settings, [(0, 257), (1, 258)]);
+
+        let hidden = feat.feature(1).unwrap();
+        assert_eq!(hidden.flags(), 0x8000);
+        let settings = hidden.settings(feat.offset_data()).unwrap();
+        assert_eq!(settings.len(), 1);
+        assert_eq!((settings[0].value(), settings[0].label()), (7, 259));
+        assert!(feat.feature(2).is_none());
+    }
+}
@@ -0,0 +1,294 @@
+//! The Graphite [Glat (Glyph Attributes)](https://github.com/silnrsi/graphite/blob/master/doc/table_glat_gloc.txt) table
+
+include!("../../generated/generated_glat.rs");
+
+use super::gloc::Gloc;
+
+/// The compression scheme of a Graphite `Glat` or `Silf` table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// The table is not compressed.
+    None,
+    /// The table (after the first eight bytes) is compressed with LZ4; the
+    /// value is the size of the uncompressed table.
+    Lz4(u32),
+    /// An unknown compression scheme.
+    Unknown(u8),
+}
+
+impl Compression {
+    /// Decode the compression scheme from the second 32-bit word of a table.
+    pub(crate) fn from_header(header: u32) -> Self {
+        match header >> 27 {
+            0 => Self::None,
+            1 => Self::Lz4(header & 0x07FF_FFFF),
+            scheme => Self::Unknown(scheme as u8),
+        }
+    }
+}
+
+impl<'a> Glat<'a> {
+    /// The compression scheme (top five bits) and flags; present in version
+    /// 3.0 and later.
+    pub fn compression_and_flags(&self) -> Option<u32> {
+        (self.version() >= Version16Dot16::VERSION_3_0)
+            .then(|| {
+                self.offset_data()
+                    .read_at(Version16Dot16::RAW_BYTE_LEN)
+                    .ok()
+            })
+            .flatten()
+    }
+
+    /// Returns the compression scheme used by this table.
+    pub fn compression(&self) -> Compression {
+        self.compression_and_flags()
+            .map(Compression::from_header)
+            .unwrap_or(Compression::None)
+    }
+
+    /// Returns true if each glyph's attributes are preceded by an octabox.
+    pub fn has_octaboxes(&self) -> bool {
+        self.compression() == Compression::None
+            && self.compression_and_flags().unwrap_or_default() & 1 != 0
+    }
+
+    /// Returns the attributes for the given glyph, located using the `Gloc` table.
+    ///
+    /// Compressed tables are not supported, and will return an error.
+    pub fn glyph_attributes(
+        &self,
+        gloc: &Gloc,
+        gid: GlyphId,
+    ) -> Result<GlyphAttributes<'a>, ReadError> {
+        if self.compression() != Compression::None {
+            return Err(ReadError::MalformedData(
+                "compressed Glat tables are not supported",
+            ));
+        }
+        let mut data = self
+            .offset_data()
+            .slice(gloc.glyph_range(gid)?)
+            .ok_or(ReadError::OutOfBounds)?;
+        let octabox = if self.has_octaboxes() {
+            let glyph_box: &GlyphBox = data.read_ref_at(0)?;
+            let n_subboxes = glyph_box.bitmap().count_ones() as usize;
+            let start = GlyphBox::RAW_BYTE_LEN;
+            let end = start + n_subboxes * SubBox::RAW_BYTE_LEN;
+            let subboxes = data.read_array(start..end)?;
+            data = data.split_off(end).ok_or(ReadError::OutOfBounds)?;
+            Some(Octabox {
+                glyph_box,
+                subboxes,
+            })
+        } else {
+            None
+        };
+        Ok(GlyphAttributes {
+            octabox,
+            data,
+            is_wide: self.version() >= Version16Dot16::VERSION_2_0,
+        })
+    }
+}
+
+/// The bounding octabox of a glyph, with its sub-boxes.
+#[derive(Clone, Copy, Debug)]
+pub struct Octabox<'a> {
+    pub glyph_box: &'a GlyphBox,
+    pub subboxes: &'a [SubBox],
+}
+
+/// The attributes of a single glyph in the [Glat] table.
+#[derive(Clone)]
+pub struct GlyphAttributes<'a> {
+    octabox: Option<Octabox<'a>>,
+    data: FontData<'a>,
+    // version 2.0 and later use 16-bit attribute numbers and counts
+    is_wide: bool,
+}
+
+impl<'a> GlyphAttributes<'a> {
+    /// Returns the glyph's octabox, if present.
+    pub fn octabox(&self) -> Option<Octabox<'a>> {
+        self.octabox
+    }
+
+    /// Returns an iterator over the `(attribute number, value)` pairs of
+    /// this glyph.
+    pub fn iter(&self) -> GlyphAttributeIter<'a> {
+        GlyphAttributeIter {
+            data: self.data,
+            pos: 0,
+            is_wide: self.is_wide,
+            attr: 0,
+            remaining: 0,
+        }
+    }
+
+    /// Returns the value of the given attribute, if present.
+    pub fn get(&self, attr: u16) -> Option<i16> {
+        self.iter()
+            .map_while(Result::ok)
+            .find_map(|(id, value)| (id == attr).then_some(value))
+    }
+}
+
+/// An iterator over the attributes of a glyph.
+///
+/// See [GlyphAttributes::iter].
+#[derive(Clone)]
+pub struct GlyphAttributeIter<'a> {
+    data: FontData<'a>,
+    pos: usize,
+    is_wide: bool,
+    // the next attribute number in the current run
+    attr: u16,
+    // the number of values left in the current run
+    remaining: u16,
+}
+
+impl GlyphAttributeIter<'_> {
+    fn read<T: ReadScalar>(&mut self) -> Result<T, ReadError> {
+        let value = self.data.read_at(self.pos)?;
+        self.pos += T::RAW_BYTE_LEN;
+        Ok(value)
+    }
+
+    fn next_run(&mut self) -> Result<(), ReadError> {
+        (self.attr, self.remaining) = if self.is_wide {
+            (self.read::<u16>()?, self.read::<u16>()?)
+        } else {
+            (self.read::<u8>()? as u16, self.read::<u8>()? as u16)
+        };
+        Ok(())
+    }
+}
+
+impl Iterator for GlyphAttributeIter<'_> {
+    type Item = Result<(u16, i16), ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining == 0 {
+            if self.pos >= self.data.len() {
+                return None;
+            }
+            if let Err(e) = self.next_run() {
+                self.pos = self.data.len();
+                return Some(Err(e));
+            }
+        }
+        let result = self.read::<i16>().map(|value| (self.attr, value));
+        match result {
+            Ok(_) => {
+                self.attr = self.attr.wrapping_add(1);
+                self.remaining -= 1;
+            }
+            Err(_) => {
+                self.pos = self.data.len();
+                self.remaining = 0;
+            }
+        }
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tables::gloc::GlocFlags, test_helpers::BeBuffer};
+
+    fn gloc_data(locations: &[u16]) -> BeBuffer {
+        BeBuffer::new()
+            .push(Version16Dot16::VERSION_1_0)
+            .push(GlocFlags::empty())
+            .push(0u16)
+            .extend(locations.iter().copied())
+    }
+
+    #[test]
+    fn version_1_attributes() {
+        let glat_buf = BeBuffer::new()
+            .push(Version16Dot16::VERSION_1_0)
+            // glyph 0: attrs 1, 2, then attr 10
+            .extend([1u8, 2])
+            .extend([100i16, -100])
+            .extend([10u8, 1])
+            .push(5i16)
+            // glyph 1: no attributes
+            // glyph 2: attr 3
+            .extend([3u8, 1])
+            .push(7i16);
+        let gloc_buf = gloc_data(&[4, 14, 14, 18]);
+        let glat = Glat::read(glat_buf.font_data()).unwrap();
+        let gloc = Gloc::read(gloc_buf.font_data()).unwrap();
+        assert_eq!(glat.compression(), Compression::None);
+        assert!(!glat.has_octaboxes());
+
+        let attrs = glat.glyph_attributes(&gloc, GlyphId::new(0)).unwrap();
+        let values = attrs.iter().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(values, [(1, 100), (2, -100), (10, 5)]);
+        assert_eq!(attrs.get(2), Some(-100));
+        assert_eq!(attrs.get(3), None);
+        assert!(attrs.octabox().is_none());
+
+        let attrs = glat.glyph_attributes(&gloc, GlyphId::new(1)).unwrap();
+        assert_eq!(attrs.iter().count(), 0);
+        let attrs = glat.glyph_attributes(&gloc, GlyphId::new(2)).unwrap();
+        assert_eq!(attrs.get(3), Some(7));
+    }
+
+    #[test]
+    fn version_3_octaboxes() {
+        let glat_buf = BeBuffer::new()
+            .push(Version16Dot16::VERSION_3_0)
+            .push(1u32) // uncompressed, with octaboxes
+            // glyph box with two sub-boxes
+            .push(0b1001u16)
+            .extend([1u8, 2, 3, 4])
+            .extend([0u8, 128, 0, 128, 10, 20, 30, 40])
+            .extend([128u8, 255, 128, 255, 50, 60, 70, 80])
+            // one 16-bit run
+            .extend([300u16, 2])
+            .extend([-1i16, 1]);
+        let gloc_buf = gloc_data(&[8, 38]);
+        let glat = Glat::read(glat_buf.font_data()).unwrap();
+        let gloc = Gloc::read(gloc_buf.font_data()).unwrap();
+        assert!(glat.has_octaboxes());
+
+        let attrs = glat.glyph_attributes(&gloc, GlyphId::new(0)).unwrap();
+        let octabox = attrs.octabox().unwrap();
+        assert_eq!(octabox.glyph_box.diag_pos_max(), 4);
+        assert_eq!(octabox.subboxes.len(), 2);
+        assert_eq!(octabox.subboxes[1].diag_neg_max(), 80);
+        let values = attrs.iter().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(values, [(300, -1), (301, 1)]);
+    }
+
+    #[test]
+    fn compressed() {
+        let glat_buf = BeBuffer::new()
+            .push(Version16Dot16::VERSION_3_0)
+            .push((1u32 << 27) | 1000);
+        let gloc_buf = gloc_data(&[8, 8]);
+        let glat = Glat::read(glat_buf.font_data()).unwrap();
+        let gloc = Gloc::read(gloc_buf.font_data()).unwrap();
+        assert_eq!(glat.compression(), Compression::Lz4(1000));
+        assert!(glat.glyph_attributes(&gloc, GlyphId::new(0)).is_err());
+    }
+
+    #[test]
+    fn truncated_run() {
+        let glat_buf = BeBuffer::new()
+            .push(Version16Dot16::VERSION_1_0)
+            .extend([1u8, 3])
+            .extend([1i16, 2]);
+        let gloc_buf = gloc_data(&[4, 10]);
+        let glat = Glat::read(glat_buf.font_data()).unwrap();
+        let gloc = Gloc::read(gloc_buf.font_data()).unwrap();
+        let attrs = glat.glyph_attributes(&gloc, GlyphId::new(0)).unwrap();
+        let values = attrs.iter().collect::<Vec<_>>();
+        assert_eq!(values.len(), 3);
+        assert!(values[2].is_err());
+    }
+}
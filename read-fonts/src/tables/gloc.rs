@@ -0,0 +1,137 @@
+//! The Graphite [Gloc (Glyph Locator)](https://github.com/silnrsi/graphite/blob/master/doc/table_glat_gloc.txt) table
+
+include!("../../generated/generated_gloc.rs");
+
+/// The array of glyph locations in a [Gloc] table.
+#[derive(Clone)]
+pub enum GlyphLocations<'a> {
+    Short(&'a [BigEndian<u16>]),
+    Long(&'a [BigEndian<u32>]),
+}
+
+impl<'a> GlyphLocations<'a> {
+    /// Returns the number of locations, which is one more than the number of
+    /// glyphs with attributes.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Short(locations) => locations.len(),
+            Self::Long(locations) => locations.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the location at the given index.
+    pub fn get(&self, idx: usize) -> Option<u32> {
+        match self {
+            Self::Short(locations) => locations.get(idx).map(|loc| loc.get() as u32),
+            Self::Long(locations) => locations.get(idx).map(|loc| loc.get()),
+        }
+    }
+}
+
+impl<'a> Gloc<'a> {
+    fn attrib_ids_byte_len(&self) -> usize {
+        if self.flags().contains(GlocFlags::ATTRIB_IDS) {
+            self.num_attribs() as usize * u16::RAW_BYTE_LEN
+        } else {
+            0
+        }
+    }
+
+    /// Returns the glyph locations, as byte offsets from the start of the
+    /// `Glat` table.
+    pub fn locations(&self) -> Result<GlyphLocations<'a>, ReadError> {
+        let data = FontData::new(self.location_data());
+        let end = data
+            .len()
+            .checked_sub(self.attrib_ids_byte_len())
+            .ok_or(ReadError::OutOfBounds)?;
+        if self.flags().contains(GlocFlags::LONG_FORMAT) {
+            data.read_array(0..end).map(GlyphLocations::Long)
+        } else {
+            data.read_array(0..end).map(GlyphLocations::Short)
+        }
+    }
+
+    /// Returns the number of glyphs with attributes.
+    pub fn num_glyphs(&self) -> usize {
+        self.locations()
+            .map(|locations| locations.len().saturating_sub(1))
+            .unwrap_or_default()
+    }
+
+    /// Returns the byte range of the attributes for the given glyph in the
+    /// `Glat` table.
+    pub fn glyph_range(&self, gid: GlyphId) -> Result<Range<usize>, ReadError> {
+        let locations = self.locations()?;
+        let idx = gid.to_u16() as usize;
+        let (Some(start), Some(end)) = (locations.get(idx), locations.get(idx + 1)) else {
+            return Err(ReadError::InvalidGlyphId(gid.into()));
+        };
+        if start > end {
+            return Err(ReadError::MalformedData("glyph locations not in order"));
+        }
+        Ok(start as usize..end as usize)
+    }
+
+    /// Returns the attribute ids, if present.
+    ///
+    /// These are the name ids of the attribute labels in the `name` table.
+    pub fn attrib_ids(&self) -> Option<&'a [BigEndian<u16>]> {
+        let len = self.attrib_ids_byte_len();
+        if len == 0 {
+            return None;
+        }
+        let data = FontData::new(self.location_data());
+        let start = data.len().checked_sub(len)?;
+        data.read_array(start..data.len()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn short_locations() {
+        let buf = BeBuffer::new()
+            .push(Version16Dot16::VERSION_1_0)
+            .push(GlocFlags::empty())
+            .push(2u16) // num_attribs
+            .extend([4u16, 10, 10, 18]);
+        let gloc = Gloc::read(buf.font_data()).unwrap();
+        assert_eq!(gloc.num_glyphs(), 3);
+        assert_eq!(gloc.glyph_range(GlyphId::new(0)).unwrap(), 4..10);
+        assert_eq!(gloc.glyph_range(GlyphId::new(1)).unwrap(), 10..10);
+        assert_eq!(gloc.glyph_range(GlyphId::new(2)).unwrap(), 10..18);
+        assert!(matches!(
+            gloc.glyph_range(GlyphId::new(3)),
+            Err(ReadError::InvalidGlyphId(_))
+        ));
+        assert!(gloc.attrib_ids().is_none());
+    }
+
+    #[test]
+    fn long_locations_with_attrib_ids() {
+        let buf = BeBuffer::new()
+            .push(Version16Dot16::VERSION_1_0)
+            .push(GlocFlags::LONG_FORMAT | GlocFlags::ATTRIB_IDS)
+            .push(3u16) // num_attribs
+            .extend([8u32, 0x10000])
+            .extend([256u16, 257, 258]);
+        let gloc = Gloc::read(buf.font_data()).unwrap();
+        assert_eq!(gloc.num_glyphs(), 1);
+        assert_eq!(gloc.glyph_range(GlyphId::new(0)).unwrap(), 8..0x10000);
+        let ids = gloc
+            .attrib_ids()
+            .unwrap()
+            .iter()
+            .map(|id| id.get())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [256, 257, 258]);
+    }
+}
@@ -1,7 +1,7 @@
 //! The [glyf (Glyph Data)](https://docs.microsoft.com/en-us/typography/opentype/spec/glyf) table
 
 use std::fmt;
-use types::{F26Dot6, Pen, Point};
+use types::{F26Dot6, Pen, Point, Rect};
 
 include!("../../generated/generated_glyf.rs");
 
@@ -22,6 +22,11 @@ impl<'a> Glyph<'a> {
     field_getter!(x_max, i16);
     field_getter!(y_min, i16);
     field_getter!(y_max, i16);
+
+    /// Returns the bounding box stored in the glyph header.
+    pub fn header_bounds(&self) -> GlyphBounds {
+        Rect::new(self.x_min(), self.y_min(), self.x_max(), self.y_max())
+    }
 }
 
 /// Marker bits for point flags that are set during variation delta
@@ -98,6 +103,27 @@ impl PointFlags {
         self.0 & Self::OFF_CURVE_CUBIC != 0
     }
 
+    /// Flips the state of the on curve flag.
+    ///
+    /// This is used for the TrueType `FLIPPT` instruction.
+    pub fn flip_on_curve(&mut self) {
+        self.0 ^= Self::ON_CURVE;
+    }
+
+    /// Enables the on curve flag.
+    ///
+    /// This is used for the TrueType `FLIPRGON` instruction.
+    pub fn set_on_curve(&mut self) {
+        self.0 |= Self::ON_CURVE;
+    }
+
+    /// Disables the on curve flag.
+    ///
+    /// This is used for the TrueType `FLIPRGOFF` instruction.
+    pub fn clear_on_curve(&mut self) {
+        self.0 &= !Self::ON_CURVE;
+    }
+
     /// Returns true if the given marker is set for this point.
     pub fn has_marker(self, marker: PointMarker) -> bool {
         self.0 & marker.0 != 0
@@ -158,6 +184,9 @@ impl<'a> SimpleGlyph<'a> {
                 i += 1;
             }
         }
+        // Coordinates are decoded one at a time rather than with the bulk
+        // big-endian conversion: 16-bit values are interleaved with 8-bit and
+        // repeated ones, and their runs are too short for it to pay off.
         let mut x = 0i32;
         for (&point_flags, point) in flags.iter().zip(points.as_mut()) {
             let mut delta = 0i32;
@@ -414,6 +443,22 @@ pub struct Transform {
     pub yy: F2Dot14,
 }
 
+impl Transform {
+    /// Returns true if this is the identity transform.
+    pub fn is_identity(&self) -> bool {
+        self.xx == F2Dot14::ONE
+            && self.yx == F2Dot14::ZERO
+            && self.xy == F2Dot14::ZERO
+            && self.yy == F2Dot14::ONE
+    }
+
+    /// Applies the transform to the given point.
+    pub fn transform_point(&self, point: Point<f32>) -> Point<f32> {
+        let [xx, yx, xy, yy] = [self.xx, self.yx, self.xy, self.yy].map(F2Dot14::to_f32);
+        Point::new(point.x * xx + point.y * xy, point.x * yx + point.y * yy)
+    }
+}
+
 impl Default for Transform {
     fn default() -> Self {
         Self {
@@ -444,6 +489,32 @@ pub enum Anchor {
     Point { base: u16, component: u16 },
 }
 
+impl Component {
+    /// Returns true if the component specifies a scale or 2x2 transform.
+    pub fn has_transform(&self) -> bool {
+        self.flags.intersects(
+            CompositeGlyphFlags::WE_HAVE_A_SCALE
+                | CompositeGlyphFlags::WE_HAVE_AN_X_AND_Y_SCALE
+                | CompositeGlyphFlags::WE_HAVE_A_TWO_BY_TWO,
+        )
+    }
+
+    /// Returns true if the offset of this component should be transformed
+    /// along with its points.
+    ///
+    /// This is only the case when the `SCALED_COMPONENT_OFFSET` flag is set
+    /// without the `UNSCALED_COMPONENT_OFFSET` flag. When neither is set, the
+    /// offset is left unscaled, matching the behavior of FreeType and
+    /// fontTools.
+    pub fn is_offset_scaled(&self) -> bool {
+        self.has_transform()
+            && self.flags
+                & (CompositeGlyphFlags::SCALED_COMPONENT_OFFSET
+                    | CompositeGlyphFlags::UNSCALED_COMPONENT_OFFSET)
+                == CompositeGlyphFlags::SCALED_COMPONENT_OFFSET
+    }
+}
+
 impl<'a> CompositeGlyph<'a> {
     /// Returns an iterator over the components of the composite glyph.
     pub fn components(&self) -> impl Iterator<Item = Component> + 'a + Clone {
@@ -564,6 +635,204 @@ impl<'a> SomeTable<'a> for Component {
     }
 }
 
+/// Bounding box of a glyph in font units.
+pub type GlyphBounds = Rect<i16>;
+
+/// Maximum nesting depth for composite glyphs when resolving an outline.
+pub const MAX_COMPOSITE_DEPTH: usize = 32;
+
+/// An outline with all composite components resolved into a flat list of
+/// points.
+///
+/// Coordinates are in font units. Component transforms may produce
+/// fractional values, so points are stored as `f32`.
+#[cfg(feature = "std")]
+#[derive(Clone, Default, Debug)]
+pub struct FlattenedOutline {
+    /// Points of all contours in the outline.
+    pub points: Vec<Point<f32>>,
+    /// Flags for each point.
+    pub flags: Vec<PointFlags>,
+    /// Index of the last point of each contour.
+    pub contours: Vec<usize>,
+}
+
+#[cfg(feature = "std")]
+impl FlattenedOutline {
+    /// Removes all points and contours from the outline.
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.flags.clear();
+        self.contours.clear();
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Glyf<'a> {
+    /// Resolves the outline for the given glyph, recursively flattening
+    /// composite glyphs into a single list of points and contours.
+    ///
+    /// Component transforms and offsets are applied, and point-matching
+    /// anchors are resolved against the points accumulated so far. Returns an
+    /// error if composites are nested deeper than [`MAX_COMPOSITE_DEPTH`].
+    pub fn flattened_outline(
+        &self,
+        loca: &super::loca::Loca<'a>,
+        gid: GlyphId,
+    ) -> Result<FlattenedOutline, ReadError> {
+        let mut outline = FlattenedOutline::default();
+        self.flatten_into(loca, gid, &mut outline)?;
+        Ok(outline)
+    }
+
+    /// Same as [`flattened_outline`](Self::flattened_outline), but appends to
+    /// an existing outline to allow reuse of allocations.
+    pub fn flatten_into(
+        &self,
+        loca: &super::loca::Loca<'a>,
+        gid: GlyphId,
+        outline: &mut FlattenedOutline,
+    ) -> Result<(), ReadError> {
+        let mut scratch = Vec::new();
+        self.flatten_impl(loca, gid, outline, &mut scratch, 0)
+    }
+
+    /// Returns the bounding box for the given glyph.
+    ///
+    /// For simple glyphs, this is read directly from the glyph header without
+    /// decoding the outline. Bounds for composite glyphs are computed from
+    /// the flattened outline since the header values are not always reliable.
+    /// Empty glyphs have zero bounds.
+    pub fn bounds(
+        &self,
+        loca: &super::loca::Loca<'a>,
+        gid: GlyphId,
+    ) -> Result<GlyphBounds, ReadError> {
+        self.bounds_impl(loca, gid, &mut FlattenedOutline::default())
+    }
+
+    /// Returns the bounding boxes for all glyphs in the font, indexed by
+    /// glyph identifier.
+    ///
+    /// This is more efficient than calling [`bounds`](Self::bounds) for each
+    /// glyph as storage for composite outlines is reused.
+    pub fn all_bounds(&self, loca: &super::loca::Loca<'a>) -> Result<Vec<GlyphBounds>, ReadError> {
+        let mut outline = FlattenedOutline::default();
+        (0..loca.len())
+            .map(|gid| {
+                let gid = GlyphId::new(u16::try_from(gid).map_err(|_| ReadError::OutOfBounds)?);
+                self.bounds_impl(loca, gid, &mut outline)
+            })
+            .collect()
+    }
+
+    fn bounds_impl(
+        &self,
+        loca: &super::loca::Loca<'a>,
+        gid: GlyphId,
+        outline: &mut FlattenedOutline,
+    ) -> Result<GlyphBounds, ReadError> {
+        match loca.get_glyf(gid, self)? {
+            None => Ok(GlyphBounds::default()),
+            Some(glyph @ Glyph::Simple(_)) => Ok(glyph.header_bounds()),
+            Some(Glyph::Composite(_)) => {
+                outline.clear();
+                self.flatten_into(loca, gid, outline)?;
+                let Some(bounds) = Rect::from_points(outline.points.iter().copied()) else {
+                    return Ok(GlyphBounds::default());
+                };
+                // float to int casts saturate, so out of range values are
+                // clamped to the i16 range
+                Ok(Rect::new(
+                    bounds.x_min.floor() as i16,
+                    bounds.y_min.floor() as i16,
+                    bounds.x_max.ceil() as i16,
+                    bounds.y_max.ceil() as i16,
+                ))
+            }
+        }
+    }
+
+    fn flatten_impl(
+        &self,
+        loca: &super::loca::Loca<'a>,
+        gid: GlyphId,
+        outline: &mut FlattenedOutline,
+        scratch: &mut Vec<Point<i32>>,
+        depth: usize,
+    ) -> Result<(), ReadError> {
+        if depth > MAX_COMPOSITE_DEPTH {
+            return Err(ReadError::MalformedData(
+                "composite glyph nesting depth exceeded",
+            ));
+        }
+        let Some(glyph) = loca.get_glyf(gid, self)? else {
+            // empty glyph
+            return Ok(());
+        };
+        let point_base = outline.points.len();
+        match glyph {
+            Glyph::Simple(simple) => {
+                let num_points = simple.num_points();
+                scratch.clear();
+                scratch.resize(num_points, Point::default());
+                outline
+                    .flags
+                    .resize(point_base + num_points, PointFlags::default());
+                simple.read_points_fast(scratch, &mut outline.flags[point_base..])?;
+                outline
+                    .points
+                    .extend(scratch.iter().map(|p| p.map(|v| v as f32)));
+                outline.contours.extend(
+                    simple
+                        .end_pts_of_contours()
+                        .iter()
+                        .map(|end| end.get() as usize + point_base),
+                );
+            }
+            Glyph::Composite(composite) => {
+                for component in composite.components() {
+                    let start = outline.points.len();
+                    self.flatten_impl(loca, component.glyph, outline, scratch, depth + 1)?;
+                    let points = &mut outline.points[start..];
+                    if component.has_transform() {
+                        for point in points.iter_mut() {
+                            *point = component.transform.transform_point(*point);
+                        }
+                    }
+                    let offset = match component.anchor {
+                        Anchor::Offset { x, y } => {
+                            let offset = Point::new(x as f32, y as f32);
+                            if component.is_offset_scaled() {
+                                component.transform.transform_point(offset)
+                            } else {
+                                offset
+                            }
+                        }
+                        Anchor::Point { base, component } => {
+                            let base = outline.points[point_base..start].get(base as usize).ok_or(
+                                ReadError::MalformedData("invalid composite base anchor point"),
+                            )?;
+                            let component = outline.points.get(start + component as usize).ok_or(
+                                ReadError::MalformedData(
+                                    "invalid composite component anchor point",
+                                ),
+                            )?;
+                            *base - *component
+                        }
+                    };
+                    if offset != Point::default() {
+                        for point in &mut outline.points[start..] {
+                            *point += offset;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Errors that can occur when converting an outline to a path.
 #[derive(Clone, Debug)]
 pub enum ToPathError {
@@ -747,9 +1016,12 @@ pub fn to_path(
 
 #[cfg(test)]
 mod tests {
-    use super::Glyph;
+    use super::{Glyf, Glyph, GlyphBounds, Point};
     use crate::test_data;
-    use crate::{FontRef, GlyphId, TableProvider};
+    use crate::test_helpers::BeBuffer;
+    use crate::{
+        tables::loca::Loca, FontRead, FontReadWithArgs, FontRef, GlyphId, ReadError, TableProvider,
+    };
 
     #[test]
     fn simple_glyph() {
@@ -788,4 +1060,138 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn flatten_offset_composite() {
+        let font = FontRef::new(test_data::test_fonts::VAZIRMATN_VAR).unwrap();
+        let loca = font.loca(None).unwrap();
+        let glyf = font.glyf().unwrap();
+        // glyph 2 is composed of glyph 1 and glyph 3 offset by (303, 311)
+        let outline = glyf.flattened_outline(&loca, GlyphId::new(2)).unwrap();
+        let base = glyf.flattened_outline(&loca, GlyphId::new(1)).unwrap();
+        let mark = glyf.flattened_outline(&loca, GlyphId::new(3)).unwrap();
+        let expected_points = base
+            .points
+            .iter()
+            .copied()
+            .chain(mark.points.iter().map(|p| *p + Point::new(303.0, 311.0)))
+            .collect::<Vec<_>>();
+        assert_eq!(outline.points, expected_points);
+        let expected_contours = base
+            .contours
+            .iter()
+            .copied()
+            .chain(mark.contours.iter().map(|end| end + base.points.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(outline.contours, expected_contours);
+        assert_eq!(outline.flags.len(), outline.points.len());
+    }
+
+    fn composite_glyf_and_loca() -> (BeBuffer, BeBuffer) {
+        let glyf = BeBuffer::new()
+            // glyph 0: simple triangle (0, 0), (100, 0), (0, 100)
+            .extend([1i16, 0, 0, 100, 100])
+            .extend([2u16, 0])
+            .extend([1u8, 1, 1])
+            .extend([0i16, 100, -100])
+            .extend([0i16, 0, 100])
+            .push(0u8)
+            // glyph 1: glyph 0 scaled by 0.5 at (10, 20) followed by glyph 0
+            // rotated 90 degrees and attached with point 0 to point 1
+            .extend([-1i16, 0, 0, 0, 0])
+            .extend([0x2Bu16, 0])
+            .extend([10i16, 20, 0x2000])
+            .extend([0x80u16, 0])
+            .extend([1u8, 0])
+            .extend([0i16, 0x4000, -0x4000, 0])
+            // glyph 2: glyph 0 scaled by 0.5 with a scaled offset of (10, 20)
+            .extend([-1i16, 0, 0, 0, 0])
+            .extend([0x80Bu16, 0])
+            .extend([10i16, 20, 0x2000])
+            // glyph 3: references itself
+            .extend([-1i16, 0, 0, 0, 0])
+            .extend([0x3u16, 3])
+            .extend([0i16, 0]);
+        let loca = BeBuffer::new().extend([0u16, 15, 32, 42, 51]);
+        (glyf, loca)
+    }
+
+    #[test]
+    fn flatten_transformed_composite() {
+        let (glyf_buf, loca_buf) = composite_glyf_and_loca();
+        let glyf = Glyf::read(glyf_buf.font_data()).unwrap();
+        let loca = Loca::read_with_args(loca_buf.font_data(), &false).unwrap();
+        let outline = glyf.flattened_outline(&loca, GlyphId::new(1)).unwrap();
+        let points = outline
+            .points
+            .iter()
+            .map(|p| (p.x, p.y))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            [
+                (10.0, 20.0),
+                (60.0, 20.0),
+                (10.0, 70.0),
+                (60.0, 20.0),
+                (60.0, 120.0),
+                (-40.0, 20.0),
+            ]
+        );
+        assert_eq!(outline.contours, [2, 5]);
+        let outline = glyf.flattened_outline(&loca, GlyphId::new(2)).unwrap();
+        let points = outline
+            .points
+            .iter()
+            .map(|p| (p.x, p.y))
+            .collect::<Vec<_>>();
+        assert_eq!(points, [(5.0, 10.0), (55.0, 10.0), (5.0, 60.0)]);
+    }
+
+    #[test]
+    fn flatten_recursion_limit() {
+        let (glyf_buf, loca_buf) = composite_glyf_and_loca();
+        let glyf = Glyf::read(glyf_buf.font_data()).unwrap();
+        let loca = Loca::read_with_args(loca_buf.font_data(), &false).unwrap();
+        assert!(matches!(
+            glyf.flattened_outline(&loca, GlyphId::new(3)),
+            Err(ReadError::MalformedData(_))
+        ));
+    }
+
+    #[test]
+    fn glyph_bounds() {
+        let font = FontRef::new(test_data::test_fonts::VAZIRMATN_VAR).unwrap();
+        let loca = font.loca(None).unwrap();
+        let glyf = font.glyf().unwrap();
+        let all_bounds = glyf.all_bounds(&loca).unwrap();
+        assert_eq!(all_bounds.len(), loca.len());
+        for (gid, bounds) in all_bounds.iter().enumerate() {
+            let gid = GlyphId::new(gid as u16);
+            assert_eq!(glyf.bounds(&loca, gid).unwrap(), *bounds);
+            match loca.get_glyf(gid, &glyf).unwrap() {
+                // composite bounds in this font match the header
+                Some(glyph) => assert_eq!(*bounds, glyph.header_bounds()),
+                None => assert_eq!(*bounds, GlyphBounds::default()),
+            }
+        }
+    }
+
+    #[test]
+    fn computed_composite_bounds() {
+        let (glyf_buf, loca_buf) = composite_glyf_and_loca();
+        let glyf = Glyf::read(glyf_buf.font_data()).unwrap();
+        let loca = Loca::read_with_args(loca_buf.font_data(), &false).unwrap();
+        // header bounds are all zero, so these are computed
+        assert_eq!(
+            glyf.bounds(&loca, GlyphId::new(1)).unwrap(),
+            GlyphBounds {
+                x_min: -40,
+                y_min: 20,
+                x_max: 60,
+                y_max: 120
+            }
+        );
+        assert!(glyf.all_bounds(&loca).is_err());
+    }
 }
@@ -6,6 +6,7 @@
 mod value_record;
 
 use crate::array::ComputedArray;
+use crate::Direction;
 
 /// reexport stuff from layout that we use
 pub use super::layout::{
@@ -27,3 +28,227 @@ pub type PositionSequenceContext<'a> = super::layout::SequenceContext<'a>;
 
 /// A GPOS [ChainedSequenceContext](super::layout::ChainedSequenceContext)
 pub type PositionChainContext<'a> = super::layout::ChainedSequenceContext<'a>;
+
+impl<'a> Gpos<'a> {
+    /// Returns the pair adjustment for two glyphs made by the lookups of the
+    /// `kern` feature, or of the `vkrn` feature in the vertical direction,
+    /// in font units.
+    ///
+    /// Lookups are applied in lookup list order, regardless of the script or
+    /// language system that references them, and their flags are ignored.
+    /// Within each pair adjustment lookup, the first subtable that applies
+    /// to the pair provides the value; the values of all lookups are summed.
+    ///
+    /// Returns `None` if no feature with that tag references any lookups.
+    pub fn kerning(
+        &self,
+        left: GlyphId,
+        right: GlyphId,
+        direction: Direction,
+    ) -> Result<Option<i32>, ReadError> {
+        let tag = match direction {
+            Direction::Horizontal => Tag::new(b"kern"),
+            Direction::Vertical => Tag::new(b"vkrn"),
+        };
+        let mut lookups = FeatureLookups::new(self.feature_list()?, tag)?;
+        let lookup_list = self.lookup_list()?;
+        let mut value = None;
+        while let Some(index) = lookups.next()? {
+            let adjustment = match lookup_list.get(index)? {
+                PositionLookup::Pair(lookup) => {
+                    pair_lookup_kerning(lookup.subtables(), left, right, direction)?
+                }
+                PositionLookup::Extension(lookup) => {
+                    let subtables = lookup.subtables().filter_map(|subtable| match subtable {
+                        Ok(ExtensionSubtable::Pair(extension)) => Some(extension.extension()),
+                        Ok(_) => None,
+                        Err(e) => Some(Err(e)),
+                    });
+                    pair_lookup_kerning(subtables, left, right, direction)?
+                }
+                _ => 0,
+            };
+            value = Some(value.unwrap_or(0) + adjustment);
+        }
+        Ok(value)
+    }
+}
+
+/// The indices of the lookups referenced by the features with a given tag,
+/// in ascending order and without duplicates.
+#[cfg(feature = "std")]
+struct FeatureLookups {
+    indices: std::collections::btree_set::IntoIter<u16>,
+}
+
+#[cfg(feature = "std")]
+impl FeatureLookups {
+    fn new(feature_list: FeatureList, tag: Tag) -> Result<Self, ReadError> {
+        let mut indices = std::collections::BTreeSet::new();
+        for record in feature_list.feature_records() {
+            if record.feature_tag() == tag {
+                let feature = record.feature(feature_list.offset_data())?;
+                indices.extend(feature.lookup_list_indices().iter().map(|idx| idx.get()));
+            }
+        }
+        Ok(Self {
+            indices: indices.into_iter(),
+        })
+    }
+
+    fn next(&mut self) -> Result<Option<u16>, ReadError> {
+        Ok(self.indices.next())
+    }
+}
+
+/// The indices of the lookups referenced by the features with a given tag,
+/// in ascending order and without duplicates.
+///
+/// Without an allocator, each call to `next` finds the smallest index
+/// greater than the previous one in the matching features.
+#[cfg(not(feature = "std"))]
+struct FeatureLookups<'a> {
+    feature_list: FeatureList<'a>,
+    tag: Tag,
+    last: Option<u16>,
+}
+
+#[cfg(not(feature = "std"))]
+impl<'a> FeatureLookups<'a> {
+    fn new(feature_list: FeatureList<'a>, tag: Tag) -> Result<Self, ReadError> {
+        Ok(Self {
+            feature_list,
+            tag,
+            last: None,
+        })
+    }
+
+    fn next(&mut self) -> Result<Option<u16>, ReadError> {
+        let mut next = None;
+        for record in self.feature_list.feature_records() {
+            if record.feature_tag() != self.tag {
+                continue;
+            }
+            let feature = record.feature(self.feature_list.offset_data())?;
+            for index in feature.lookup_list_indices() {
+                let index = index.get();
+                if self.last.is_none_or(|last| index > last) && next.is_none_or(|next| index < next)
+                {
+                    next = Some(index);
+                }
+            }
+        }
+        self.last = next;
+        Ok(next)
+    }
+}
+
+/// Returns the adjustment made by the first subtable that applies to the pair.
+fn pair_lookup_kerning<'a>(
+    subtables: impl Iterator<Item = Result<PairPos<'a>, ReadError>>,
+    left: GlyphId,
+    right: GlyphId,
+    direction: Direction,
+) -> Result<i32, ReadError> {
+    for subtable in subtables {
+        if let Some(record) = subtable?.value_record(left, right)? {
+            let adjustment = match direction {
+                Direction::Horizontal => record.x_advance(),
+                Direction::Vertical => record.y_advance(),
+            };
+            return Ok(adjustment.unwrap_or_default() as i32);
+        }
+    }
+    Ok(0)
+}
+
+impl<'a> PairPos<'a> {
+    /// Returns the value record for the first glyph of a pair, or `None` if
+    /// the subtable does not apply to the pair.
+    pub fn value_record(
+        &self,
+        left: GlyphId,
+        right: GlyphId,
+    ) -> Result<Option<ValueRecord>, ReadError> {
+        Ok(self.value_records(left, right)?.map(|(record, _)| record))
+    }
+
+    /// Returns the value records for the first and second glyphs of a pair,
+    /// or `None` if the subtable does not apply to the pair.
+    ///
+    /// This is like [`value_record`](Self::value_record), but also returns
+    /// the record that adjusts the second glyph.
+    pub fn value_records(
+        &self,
+        left: GlyphId,
+        right: GlyphId,
+    ) -> Result<Option<(ValueRecord, ValueRecord)>, ReadError> {
+        match self {
+            Self::Format1(subtable) => {
+                let Some(coverage_index) = subtable.coverage()?.get(left) else {
+                    return Ok(None);
+                };
+                let pair_set: PairSet = subtable
+                    .pair_set_offsets()
+                    .get(coverage_index as usize)
+                    .ok_or(ReadError::OutOfBounds)?
+                    .get()
+                    .resolve_with_args(
+                        subtable.offset_data(),
+                        &(subtable.value_format1(), subtable.value_format2()),
+                    )?;
+                // records are ordered by the glyph id of the second glyph
+                let records = pair_set.pair_value_records();
+                let (mut lo, mut hi) = (0, records.len());
+                while lo < hi {
+                    let mid = (lo + hi) / 2;
+                    let record = records.get(mid)?;
+                    match record.second_glyph().cmp(&right) {
+                        std::cmp::Ordering::Less => lo = mid + 1,
+                        std::cmp::Ordering::Greater => hi = mid,
+                        std::cmp::Ordering::Equal => {
+                            return Ok(Some((record.value_record1, record.value_record2)))
+                        }
+                    }
+                }
+                Ok(None)
+            }
+            Self::Format2(subtable) => {
+                if subtable.coverage()?.get(left).is_none() {
+                    return Ok(None);
+                }
+                let class1 = subtable.class_def1()?.get(left);
+                let class2 = subtable.class_def2()?.get(right);
+                if class1 >= subtable.class1_count() || class2 >= subtable.class2_count() {
+                    return Ok(None);
+                }
+                let class1_record = subtable.class1_records().get(class1 as usize)?;
+                let class2_record = class1_record.class2_records().get(class2 as usize)?;
+                Ok(Some((
+                    class2_record.value_record1,
+                    class2_record.value_record2,
+                )))
+            }
+        }
+    }
+}
+
+impl<'a> AnchorTable<'a> {
+    /// The horizontal value of the anchor, in design units.
+    pub fn x_coordinate(&self) -> i16 {
+        match self {
+            Self::Format1(anchor) => anchor.x_coordinate(),
+            Self::Format2(anchor) => anchor.x_coordinate(),
+            Self::Format3(anchor) => anchor.x_coordinate(),
+        }
+    }
+
+    /// The vertical value of the anchor, in design units.
+    pub fn y_coordinate(&self) -> i16 {
+        match self {
+            Self::Format1(anchor) => anchor.y_coordinate(),
+            Self::Format2(anchor) => anchor.y_coordinate(),
+            Self::Format3(anchor) => anchor.y_coordinate(),
+        }
+    }
+}
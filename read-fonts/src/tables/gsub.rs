@@ -7,6 +7,10 @@ pub use super::layout::{
     Lookup, LookupList, ScriptList, SequenceContext,
 };
 
+#[cfg(feature = "std")]
+#[path = "./closure.rs"]
+mod closure;
+
 #[cfg(test)]
 #[path = "../tests/test_gsub.rs"]
 mod tests;
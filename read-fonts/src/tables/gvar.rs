@@ -3,6 +3,11 @@
 
 include!("../../generated/generated_gvar.rs");
 
+use core::cmp::Ordering;
+
+use crate::ErrorLocation;
+
+use super::glyf::{PointFlags, PointMarker};
 use super::variations::{
     DeltaRunIter, PackedDeltas, PackedPointNumbers, PackedPointNumbersIter, Tuple,
     TupleVariationCount, TupleVariationHeader, TupleVariationHeaderIter,
@@ -61,24 +66,111 @@ impl<'a> GlyphVariationDataHeader<'a> {
 }
 
 impl<'a> Gvar<'a> {
-    fn data_for_gid(&self, gid: GlyphId) -> Result<FontData<'a>, ReadError> {
-        let start_idx = gid.to_u16() as usize;
-        let end_idx = start_idx + 1;
-        let data_start = self.glyph_variation_data_array_offset();
-        let start = data_start + self.glyph_variation_data_offsets().get(start_idx)?.get();
-        let end = data_start + self.glyph_variation_data_offsets().get(end_idx)?.get();
+    /// Returns the number of glyphs that have entries in the offsets array.
+    ///
+    /// The `glyphCount` field is only 16 bits. Fonts with more glyphs set it
+    /// to 0xFFFF and continue the offsets array up to the start of the glyph
+    /// variation data, so in that case the count is derived from the size
+    /// of the array.
+    pub fn num_glyphs(&self) -> u32 {
+        let glyph_count = self.glyph_count() as u32;
+        if glyph_count != u16::MAX as u32 {
+            return glyph_count;
+        }
+        let offsets_start = self.shape.glyph_variation_data_offsets_byte_range().start;
+        let offsets_end = (self.glyph_variation_data_array_offset() as usize).min(self.data.len());
+        let offset_size = U16Or32::compute_size(&self.flags());
+        let entries = offsets_end.saturating_sub(offsets_start) / offset_size;
+        // the last entry marks the end of the data for the last glyph
+        glyph_count.max(entries.saturating_sub(1) as u32)
+    }
+
+    /// Returns the entry at the given index in the offsets array, which may
+    /// be beyond the `glyphCount` field.
+    fn offset_at(&self, index: usize) -> Option<usize> {
+        let flags = self.flags();
+        let pos = index
+            .checked_mul(U16Or32::compute_size(&flags))?
+            .checked_add(self.shape.glyph_variation_data_offsets_byte_range().start)?;
+        let data = self.data.split_off(pos)?;
+        U16Or32::read_with_args(data, &flags)
+            .ok()
+            .map(|offset| offset.get() as usize)
+    }
 
-        self.data
-            .slice(start as usize..end as usize)
-            .ok_or(ReadError::OutOfBounds)
+    /// Returns the data for the entry at the given index in the offsets
+    /// array, or `None` if the glyph has no variations.
+    fn data_for_index(&self, index: u32) -> Result<Option<FontData<'a>>, ReadError> {
+        let index = index as usize;
+        let locate = |offset| {
+            ReadError::OutOfBoundsAt(ErrorLocation {
+                table: Some(Self::TAG),
+                field: "glyphVariationDataOffsets",
+                index: Some(index),
+                offset,
+            })
+        };
+        let (Some(start), Some(end)) = (self.offset_at(index), self.offset_at(index + 1)) else {
+            return Err(ReadError::OutOfBounds);
+        };
+        let data_start = self.glyph_variation_data_array_offset() as usize;
+        let (Some(start), Some(end)) = (data_start.checked_add(start), data_start.checked_add(end))
+        else {
+            return Err(locate(None));
+        };
+        match start.cmp(&end) {
+            Ordering::Equal => Ok(None),
+            Ordering::Greater => Err(ReadError::MalformedData(
+                "glyph variation data offsets are not in ascending order",
+            )),
+            Ordering::Less => self
+                .data
+                .slice(start..end)
+                .map(Some)
+                .ok_or_else(|| locate(Some(end))),
+        }
     }
 
     /// Get the variation data for a specific glyph.
-    pub fn glyph_variation_data(&self, gid: GlyphId) -> Result<GlyphVariationData<'a>, ReadError> {
+    ///
+    /// Returns `None` if the glyph has no variations, and an
+    /// [`InvalidGlyphId`](ReadError::InvalidGlyphId) error if the glyph
+    /// identifier is not less than [`num_glyphs`](Self::num_glyphs).
+    ///
+    /// This accepts both 16-bit and 24-bit glyph ids.
+    pub fn glyph_variation_data(
+        &self,
+        gid: impl Into<GlyphId24>,
+    ) -> Result<Option<GlyphVariationData<'a>>, ReadError> {
+        let gid = gid.into();
+        if gid.to_u32() >= self.num_glyphs() {
+            return Err(ReadError::InvalidGlyphId(gid));
+        }
+        let Some(data) = self.data_for_index(gid.to_u32())? else {
+            return Ok(None);
+        };
         let shared_tuples = self.shared_tuples()?;
         let axis_count = self.axis_count();
-        let data = self.data_for_gid(gid)?;
-        GlyphVariationData::new(data, axis_count, shared_tuples)
+        GlyphVariationData::new(data, axis_count, shared_tuples).map(Some)
+    }
+}
+
+impl<'a> SharedTuples<'a> {
+    /// Converts the coordinates of all of the tuples at once, writing them to
+    /// `dst`.
+    ///
+    /// Each tuple has one coordinate per axis, and the tuples are written in
+    /// order. This is considerably faster than reading each coordinate of
+    /// each tuple in turn.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `dst` is not the number of tuples multiplied
+    /// by the axis count.
+    pub fn copy_coordinates(&self, dst: &mut [F2Dot14]) {
+        let range = self.shape.tuples_byte_range();
+        let coords: &[BigEndian<F2Dot14>] = self.data.read_array(range).unwrap();
+        coords.copy_to_native(dst);
     }
 }
 
@@ -130,6 +222,133 @@ impl<'a> GlyphVariationData<'a> {
     fn tuple_count(&self) -> usize {
         self.tuple_count.count() as usize
     }
+
+    /// Computes the accumulated deltas for all points of a glyph at the given
+    /// normalized variation coordinates.
+    ///
+    /// The deltas of each applicable tuple are scaled and summed. For tuples
+    /// that only reference a subset of points, deltas for the remaining points
+    /// in each contour are inferred using interpolation of untouched points
+    /// (IUP).
+    ///
+    /// `points` holds the original (unvaried) coordinates of the glyph,
+    /// including the four phantom points, and `contours` holds the end point
+    /// index of each contour. For composite glyphs, `points` should contain
+    /// one entry per component followed by the phantom points and `contours`
+    /// should be empty, in which case no inference is performed.
+    ///
+    /// The `flags`, `scratch` and `deltas` buffers must have the same length
+    /// as `points`. Only the [`HAS_DELTA`](PointMarker::HAS_DELTA) marker is
+    /// modified in `flags`. On success, `deltas` contains the final delta
+    /// for each point.
+    pub fn compute_deltas(
+        &self,
+        coords: &[F2Dot14],
+        points: &[Point<i32>],
+        contours: &[u16],
+        flags: &mut [PointFlags],
+        scratch: &mut [Point<Fixed>],
+        deltas: &mut [Point<Fixed>],
+    ) -> Result<(), ReadError> {
+        let n_points = points.len();
+        if flags.len() != n_points || scratch.len() != n_points || deltas.len() != n_points {
+            return Err(ReadError::InvalidArrayLen);
+        }
+        deltas.fill(Point::default());
+        for tuple in self.tuples() {
+            let Some(scalar) = tuple.compute_scalar(coords) else {
+                continue;
+            };
+            if scalar == Fixed::ZERO {
+                continue;
+            }
+            tuple.accumulate_deltas(scalar, points, contours, flags, scratch, deltas)?;
+        }
+        Ok(())
+    }
+}
+
+/// Infers deltas for points without explicit deltas in each contour.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/gvar#inferred-deltas-for-un-referenced-point-numbers>
+fn interpolate_untouched(
+    points: &[Point<i32>],
+    contours: &[u16],
+    flags: &[PointFlags],
+    deltas: &mut [Point<Fixed>],
+) -> Result<(), ReadError> {
+    let has_delta = |ix: usize| flags[ix].has_marker(PointMarker::HAS_DELTA);
+    let mut start = 0;
+    for &end in contours {
+        let end = end as usize;
+        if end < start || end >= points.len() {
+            return Err(ReadError::MalformedData("invalid contour end point"));
+        }
+        let contour = start..end + 1;
+        start = end + 1;
+        let Some(first_touched) = contour.clone().find(|ix| has_delta(*ix)) else {
+            // no explicit deltas in this contour
+            continue;
+        };
+        let mut prev = first_touched;
+        for ix in first_touched + 1..contour.end {
+            if has_delta(ix) {
+                interpolate_range(points, deltas, prev, ix, prev + 1..ix);
+                prev = ix;
+            }
+        }
+        // wrap around from the last touched point to the first
+        interpolate_range(points, deltas, prev, first_touched, prev + 1..contour.end);
+        interpolate_range(
+            points,
+            deltas,
+            prev,
+            first_touched,
+            contour.start..first_touched,
+        );
+    }
+    Ok(())
+}
+
+/// Interpolates deltas for the points in `range` from the reference points
+/// `p1` and `p2`.
+///
+/// This matches the behavior of FreeType's `tt_interpolate_deltas`: when
+/// the reference points have the same coordinate but different deltas, no
+/// delta is inferred for that coordinate.
+fn interpolate_range(
+    points: &[Point<i32>],
+    deltas: &mut [Point<Fixed>],
+    p1: usize,
+    p2: usize,
+    range: core::ops::Range<usize>,
+) {
+    fn interpolate(coord: i32, ref1: (i32, Fixed), ref2: (i32, Fixed)) -> Fixed {
+        let ((in1, d1), (in2, d2)) = if ref1.0 <= ref2.0 {
+            (ref1, ref2)
+        } else {
+            (ref2, ref1)
+        };
+        if in1 == in2 && d1 != d2 {
+            Fixed::ZERO
+        } else if coord <= in1 {
+            d1
+        } else if coord >= in2 {
+            d2
+        } else {
+            let t = Fixed::from_i32(coord - in1);
+            d1 + t.mul_div(d2 - d1, Fixed::from_i32(in2 - in1))
+        }
+    }
+    let (ref1, ref2) = (points[p1], points[p2]);
+    let (d1, d2) = (deltas[p1], deltas[p2]);
+    for ix in range {
+        let point = points[ix];
+        deltas[ix] = Point::new(
+            interpolate(point.x, (ref1.x, d1.x), (ref2.x, d2.x)),
+            interpolate(point.y, (ref1.y, d1.y), (ref2.y, d2.y)),
+        );
+    }
 }
 
 /// An iterator over the [`TupleVariation`]s for a specific glyph.
@@ -201,7 +420,6 @@ impl<'a> TupleVariation<'a> {
             .unwrap_or_default()
     }
 
-    // transcribed from pinot/moscato
     /// Compute the scalar for a this tuple at a given point in design space.
     ///
     /// The `coords` slice must be of lesser or equal length to the number of axes.
@@ -209,46 +427,95 @@ impl<'a> TupleVariation<'a> {
     ///
     /// Returns `None` if this tuple is not applicable at the provided coordinates.
     pub fn compute_scalar(&self, coords: &[F2Dot14]) -> Option<Fixed> {
-        const ZERO: Fixed = Fixed::ZERO;
-        let mut scalar = Fixed::ONE;
-        let peak = self.peak();
-        let inter_start = self.header.intermediate_start_tuple();
-        let inter_end = self.header.intermediate_end_tuple();
-        if peak.len() != self.axis_count as usize {
-            return None;
-        }
+        self.header
+            .compute_scalar(self.peak(), self.axis_count, coords)
+    }
 
-        for i in 0..self.axis_count {
-            let i = i as usize;
-            let coord = coords.get(i).copied().unwrap_or_default().to_fixed();
-            let peak = peak.get(i).unwrap_or_default().to_fixed();
-            if peak == ZERO || peak == coord {
-                continue;
-            }
+    /// Returns the start of the intermediate region of this tuple, if it has
+    /// one.
+    pub fn intermediate_start(&self) -> Option<Tuple<'a>> {
+        self.header.intermediate_start_tuple()
+    }
 
-            if coord == ZERO {
-                return None;
-            }
+    /// Returns the end of the intermediate region of this tuple, if it has
+    /// one.
+    pub fn intermediate_end(&self) -> Option<Tuple<'a>> {
+        self.header.intermediate_end_tuple()
+    }
 
-            if let (Some(inter_start), Some(inter_end)) = (&inter_start, &inter_end) {
-                let start = inter_start.get(i).unwrap_or_default().to_fixed();
-                let end = inter_end.get(i).unwrap_or_default().to_fixed();
-                if coord <= start || coord >= end {
-                    return None;
-                }
-                if coord < peak {
-                    scalar = scalar.mul_div(coord - start, peak - start);
-                } else {
-                    scalar = scalar.mul_div(end - coord, end - peak);
+    /// Adds the deltas of this tuple, multiplied by `scalar`, to the deltas
+    /// of all points of a glyph.
+    ///
+    /// Deltas for points that are not referenced by this tuple are inferred
+    /// (IUP). The arguments are the same as for
+    /// [`GlyphVariationData::compute_deltas`], except that `deltas` is added
+    /// to rather than overwritten.
+    pub fn accumulate_deltas(
+        &self,
+        scalar: Fixed,
+        points: &[Point<i32>],
+        contours: &[u16],
+        flags: &mut [PointFlags],
+        scratch: &mut [Point<Fixed>],
+        deltas: &mut [Point<Fixed>],
+    ) -> Result<(), ReadError> {
+        let n_points = points.len();
+        if flags.len() != n_points || scratch.len() != n_points || deltas.len() != n_points {
+            return Err(ReadError::InvalidArrayLen);
+        }
+        let scale = |x_delta: i16, y_delta: i16| {
+            Point::new(
+                Fixed::from_i32(x_delta as i32) * scalar,
+                Fixed::from_i32(y_delta as i32) * scalar,
+            )
+        };
+        if self.all_points() {
+            // deltas are stored sequentially for every point, so decode
+            // them in bulk rather than one at a time
+            const CHUNK_LEN: usize = 64;
+            let total = self.packed_deltas.count() / 2;
+            let mut x_iter = self.packed_deltas.iter();
+            let mut y_iter = self.packed_deltas.iter_from(total);
+            let (mut x_buf, mut y_buf) = ([0i16; CHUNK_LEN], [0i16; CHUNK_LEN]);
+            for targets in deltas[..total.min(n_points)].chunks_mut(CHUNK_LEN) {
+                let len = targets.len();
+                let x_len = x_iter.read_into(&mut x_buf[..len]);
+                let y_len = y_iter.read_into(&mut y_buf[..len]);
+                let len = x_len.min(y_len);
+                for ((target, x), y) in targets.iter_mut().zip(&x_buf[..len]).zip(&y_buf[..len]) {
+                    *target += scale(*x, *y);
                 }
-            } else {
-                if coord < peak.min(ZERO) || coord > peak.max(ZERO) {
-                    return None;
+                if len < targets.len() {
+                    break;
                 }
-                scalar = scalar.mul_div(coord, peak);
             }
+            return Ok(());
+        }
+        scratch.fill(Point::default());
+        for flag in flags.iter_mut() {
+            flag.clear_marker(PointMarker::HAS_DELTA);
+        }
+        for delta in self.deltas() {
+            let ix = delta.position as usize;
+            if ix < n_points {
+                scratch[ix] = scale(delta.x_delta, delta.y_delta);
+                flags[ix].set_marker(PointMarker::HAS_DELTA);
+            }
+        }
+        interpolate_untouched(points, contours, flags, scratch)?;
+        for (target, delta) in deltas.iter_mut().zip(scratch.iter()) {
+            *target += *delta;
         }
-        Some(scalar)
+        Ok(())
+    }
+
+    /// Returns the raw packed deltas for this tuple.
+    ///
+    /// All of the x deltas are stored before all of the y deltas. This is
+    /// useful for decoding the deltas in bulk with
+    /// [`DeltaRunIter::read_into`].
+    pub fn packed_deltas(&self) -> &PackedDeltas<'a> {
+        &self.packed_deltas
     }
 
     /// Iterate over the deltas for this tuple.
@@ -257,10 +524,7 @@ impl<'a> TupleVariation<'a> {
     pub fn deltas(&self) -> DeltaIter<'a> {
         let total = self.packed_deltas.count() / 2;
         let x_iter = self.packed_deltas.iter();
-        let mut y_iter = self.packed_deltas.iter();
-        for _ in 0..total {
-            y_iter.next();
-        }
+        let y_iter = self.packed_deltas.iter_from(total);
         DeltaIter {
             cur: 0,
             total,
@@ -315,7 +579,7 @@ impl<'a> Iterator for DeltaIter<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{FontRef, TableProvider};
+    use crate::{test_helpers::BeBuffer, FontRef, TableProvider};
 
     // Shared tuples in the 'gvar' table of the Skia font, as printed
     // in Apple's TrueType specification.
@@ -381,6 +645,14 @@ mod tests {
             .collect();
 
         assert_eq!(tuple_vec, EXPECTED);
+
+        let mut coords = [F2Dot14::ZERO; 16];
+        tuples.copy_coordinates(&mut coords);
+        let expected = EXPECTED
+            .iter()
+            .flat_map(|(a, b)| [*a, *b])
+            .collect::<Vec<_>>();
+        assert_eq!(coords, expected.as_slice());
     }
 
     // https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6gvar.html
@@ -444,7 +716,7 @@ mod tests {
             .unwrap()
             .gvar()
             .unwrap();
-        let a_glyph_var = gvar.glyph_variation_data(GlyphId::new(1)).unwrap();
+        let a_glyph_var = gvar.glyph_variation_data(GlyphId::new(1)).unwrap().unwrap();
         assert_eq!(a_glyph_var.axis_count, 1);
         let mut tuples = a_glyph_var.tuples();
         let tup1 = tuples.next().unwrap();
@@ -469,7 +741,7 @@ mod tests {
         assert_eq!(tup2.deltas().map(|d| d.y_delta).collect::<Vec<_>>(), y_vals);
         assert!(tuples.next().is_none());
 
-        let agrave_glyph_var = gvar.glyph_variation_data(GlyphId::new(2)).unwrap();
+        let agrave_glyph_var = gvar.glyph_variation_data(GlyphId::new(2)).unwrap().unwrap();
         let mut tuples = agrave_glyph_var.tuples();
         let tup1 = tuples.next().unwrap();
         assert_eq!(
@@ -485,7 +757,7 @@ mod tests {
                 .collect::<Vec<_>>(),
             &[(1, -54, -1), (3, 59, 0)]
         );
-        let grave_glyph_var = gvar.glyph_variation_data(GlyphId::new(3)).unwrap();
+        let grave_glyph_var = gvar.glyph_variation_data(GlyphId::new(3)).unwrap().unwrap();
         let mut tuples = grave_glyph_var.tuples();
         let tup1 = tuples.next().unwrap();
         let tup2 = tuples.next().unwrap();
@@ -496,4 +768,214 @@ mod tests {
             &[0, -20, -20, 0, 0, 0, 0, 0]
         );
     }
+
+    // A single tuple with a peak at 1.0 on one axis and explicit deltas for
+    // points 0 and 2
+    static IUP_GLYPH_VAR_DATA: FontData = FontData::new(&[
+        0x00, 0x01, // tupleVariationCount
+        0x00, 0x0A, // dataOffset
+        0x00, 0x09, // variationDataSize
+        0xA0, 0x00, // EMBEDDED_PEAK_TUPLE | PRIVATE_POINT_NUMBERS
+        0x40, 0x00, // peak: 1.0
+        0x02, 0x01, 0x00, 0x02, // points 0, 2
+        0x03, 10, 20, 0, 40, // x deltas: 10, 20; y deltas: 0, 40
+    ]);
+
+    #[test]
+    fn compute_deltas_with_iup() {
+        let shared_tuples = SharedTuples::read_with_args(FontData::new(&[]), &(0, 1)).unwrap();
+        let var_data = GlyphVariationData::new(IUP_GLYPH_VAR_DATA, 1, shared_tuples).unwrap();
+        // single contour followed by four phantom points
+        let points = [
+            (0, 0),
+            (50, 50),
+            (100, 100),
+            (0, 100),
+            (0, 0),
+            (0, 0),
+            (0, 0),
+            (0, 0),
+        ]
+        .map(|(x, y)| Point::new(x, y));
+        let mut flags = [PointFlags::default(); 8];
+        let mut scratch = [Point::default(); 8];
+        let mut deltas = [Point::default(); 8];
+        var_data
+            .compute_deltas(
+                &[F2Dot14::from_f32(0.5)],
+                &points,
+                &[3],
+                &mut flags,
+                &mut scratch,
+                &mut deltas,
+            )
+            .unwrap();
+        let deltas = deltas
+            .iter()
+            .map(|d| (d.x.to_f64(), d.y.to_f64()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            deltas,
+            [
+                (5.0, 0.0),
+                (7.5, 10.0),
+                (10.0, 20.0),
+                (5.0, 20.0),
+                (0.0, 0.0),
+                (0.0, 0.0),
+                (0.0, 0.0),
+                (0.0, 0.0)
+            ]
+        );
+        // no deltas at the default location
+        let mut deltas = [Point::new(Fixed::ONE, Fixed::ONE); 8];
+        var_data
+            .compute_deltas(&[], &points, &[3], &mut flags, &mut scratch, &mut deltas)
+            .unwrap();
+        assert!(deltas.iter().all(|d| *d == Point::default()));
+    }
+
+    #[test]
+    fn interpolate_coincident_reference_points() {
+        // points 0 and 2 have explicit deltas and the same x coordinate
+        let points = [(0, 0), (50, 50), (0, 100), (100, 50)].map(|(x, y)| Point::new(x, y));
+        let mut flags = [PointFlags::default(); 4];
+        flags[0].set_marker(PointMarker::HAS_DELTA);
+        flags[2].set_marker(PointMarker::HAS_DELTA);
+        let mut deltas = [Point::default(); 4];
+        deltas[0] = Point::new(Fixed::from_i32(10), Fixed::from_i32(10));
+        deltas[2] = Point::new(Fixed::from_i32(20), Fixed::from_i32(10));
+        interpolate_untouched(&points, &[3], &flags, &mut deltas).unwrap();
+        let deltas = deltas
+            .iter()
+            .map(|d| (d.x.to_i32(), d.y.to_i32()))
+            .collect::<Vec<_>>();
+        // the x deltas differ, so none are inferred; the y deltas are equal
+        assert_eq!(deltas, [(10, 10), (0, 10), (20, 10), (0, 10)]);
+    }
+
+    #[test]
+    fn compute_deltas_all_points() {
+        use crate::test_data::test_fonts;
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let gvar = font.gvar().unwrap();
+        let var_data = gvar.glyph_variation_data(GlyphId::new(1)).unwrap().unwrap();
+        // every tuple for this glyph references all points, so the original
+        // coordinates are irrelevant
+        let points = [Point::default(); 18];
+        let mut flags = [PointFlags::default(); 18];
+        let mut scratch = [Point::default(); 18];
+        let mut deltas = [Point::default(); 18];
+        var_data
+            .compute_deltas(
+                &[F2Dot14::from_f32(-0.5)],
+                &points,
+                &[],
+                &mut flags,
+                &mut scratch,
+                &mut deltas,
+            )
+            .unwrap();
+        let tuple = var_data.tuples().next().unwrap();
+        for (delta, expected) in deltas.iter().zip(tuple.deltas()) {
+            assert_eq!(delta.x, Fixed::from_f64(expected.x_delta as f64 * 0.5));
+            assert_eq!(delta.y, Fixed::from_f64(expected.y_delta as f64 * 0.5));
+        }
+        assert!(var_data
+            .compute_deltas(
+                &[],
+                &points[1..],
+                &[],
+                &mut flags,
+                &mut scratch,
+                &mut deltas
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn glyph_variation_data_out_of_range() {
+        use crate::test_data::test_fonts;
+        let gvar = FontRef::new(test_fonts::VAZIRMATN_VAR)
+            .unwrap()
+            .gvar()
+            .unwrap();
+        let gid = GlyphId::new(gvar.glyph_count());
+        assert!(matches!(
+            gvar.glyph_variation_data(gid),
+            Err(ReadError::InvalidGlyphId(err_gid)) if err_gid == gid.into()
+        ));
+        let wide_gid = GlyphId24::checked_new(0x10000).unwrap();
+        assert!(matches!(
+            gvar.glyph_variation_data(wide_gid),
+            Err(ReadError::InvalidGlyphId(err_gid)) if err_gid == wide_gid
+        ));
+        // .notdef has no variations in this font
+        assert!(gvar
+            .glyph_variation_data(GlyphId::NOTDEF)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn glyph_count_overflow() {
+        let glyph_count = 0x10001;
+        let header_len = 20;
+        let offsets_len = (glyph_count + 1) * 2;
+        let data_offset = (header_len + offsets_len) as u32;
+        // all glyphs but the last have no variations, and the last has
+        // a header with no tuples
+        let data = BeBuffer::new()
+            .push(MajorMinor::VERSION_1_0)
+            .push(1u16) // axis count
+            .push(0u16) // shared tuple count
+            .push(data_offset) // shared tuples (there are none)
+            .push(u16::MAX) // glyph count
+            .push(0u16) // flags
+            .push(data_offset)
+            .extend(vec![0u16; glyph_count])
+            .push(2u16)
+            .push(0u16) // tuple variation count
+            .push(4u16); // data offset
+        let gvar = Gvar::read(data.font_data()).unwrap();
+        assert_eq!(gvar.num_glyphs(), glyph_count as u32);
+        let last = GlyphId24::checked_new(glyph_count as u32 - 1).unwrap();
+        let var_data = gvar.glyph_variation_data(last).unwrap().unwrap();
+        assert_eq!(var_data.tuples().count(), 0);
+        assert!(gvar
+            .glyph_variation_data(GlyphId::new(u16::MAX))
+            .unwrap()
+            .is_none());
+        let past_end = GlyphId24::checked_new(glyph_count as u32).unwrap();
+        assert!(matches!(
+            gvar.glyph_variation_data(past_end),
+            Err(ReadError::InvalidGlyphId(_))
+        ));
+    }
+
+    #[test]
+    fn truncated_glyph_variation_data() {
+        use crate::test_data::test_fonts;
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let data = font.data_for_tag(Gvar::TAG).unwrap();
+        let truncated = data.slice(..data.len() - 1).unwrap();
+        let gvar = Gvar::read(truncated).unwrap();
+        let last_gid = GlyphId::new(gvar.glyph_count() - 1);
+        let Err(err) = gvar.glyph_variation_data(last_gid) else {
+            panic!("expected an error for truncated data");
+        };
+        let location = err.location().unwrap();
+        assert_eq!(location.table, Some(Gvar::TAG));
+        assert_eq!(location.field, "glyphVariationDataOffsets");
+        assert_eq!(location.index, Some(last_gid.to_u16() as usize));
+        assert_eq!(location.offset, Some(data.len()));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "gvar.glyphVariationDataOffsets[{}]: offset 0x{:X} beyond end of data",
+                last_gid.to_u16(),
+                data.len()
+            )
+        );
+    }
 }
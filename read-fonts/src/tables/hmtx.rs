@@ -1,3 +1,70 @@
 //! The [hmtx (Horizontal Metrics)](https://docs.microsoft.com/en-us/typography/opentype/spec/hmtx) table
 
 include!("../../generated/generated_hmtx.rs");
+
+impl<'a> Hmtx<'a> {
+    /// Returns the advance width for the specified glyph identifier.
+    ///
+    /// Glyphs beyond the last long metric share its advance width. Returns
+    /// `None` if the glyph identifier is out of range for this table.
+    pub fn advance(&self, glyph_id: GlyphId) -> Option<u16> {
+        let ix = glyph_id.to_u16() as usize;
+        let metrics = self.h_metrics();
+        if ix >= metrics.len() + self.left_side_bearings().len() {
+            return None;
+        }
+        metrics
+            .get(ix)
+            .or_else(|| metrics.last())
+            .map(LongMetric::advance)
+    }
+
+    /// Returns the left side bearing for the specified glyph identifier.
+    ///
+    /// Returns `None` if the glyph identifier is out of range for this table.
+    pub fn side_bearing(&self, glyph_id: GlyphId) -> Option<i16> {
+        let ix = glyph_id.to_u16() as usize;
+        let metrics = self.h_metrics();
+        match metrics.get(ix) {
+            Some(metric) => Some(metric.side_bearing()),
+            None => self
+                .left_side_bearings()
+                .get(ix - metrics.len())
+                .map(|lsb| lsb.get()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hmtx;
+    use crate::{test_data, FontData, FontReadWithArgs, FontRef, TableProvider};
+    use types::GlyphId;
+
+    #[test]
+    fn advance_and_side_bearing() {
+        let font = FontRef::new(test_data::test_fonts::VAZIRMATN_VAR).unwrap();
+        let hmtx = font.hmtx().unwrap();
+        let num_glyphs = font.maxp().unwrap().num_glyphs();
+        for (ix, metric) in hmtx.h_metrics().iter().enumerate() {
+            let gid = GlyphId::new(ix as u16);
+            assert_eq!(hmtx.advance(gid), Some(metric.advance()));
+            assert_eq!(hmtx.side_bearing(gid), Some(metric.side_bearing()));
+        }
+        assert_eq!(hmtx.advance(GlyphId::new(num_glyphs)), None);
+        assert_eq!(hmtx.side_bearing(GlyphId::new(num_glyphs)), None);
+    }
+
+    #[test]
+    fn trailing_advance_repeats() {
+        // one long metric and two trailing side bearings
+        let data = FontData::new(&[0, 4, 0, 6, 0, 30, 0, 111]);
+        let hmtx = Hmtx::read_with_args(data, &(1, 3)).unwrap();
+        for gid in 0..3 {
+            assert_eq!(hmtx.advance(GlyphId::new(gid)), Some(4));
+        }
+        assert_eq!(hmtx.side_bearing(GlyphId::new(0)), Some(6));
+        assert_eq!(hmtx.side_bearing(GlyphId::new(2)), Some(111));
+        assert_eq!(hmtx.advance(GlyphId::new(3)), None);
+    }
+}
@@ -1,6 +1,6 @@
 //! An fvar InstanceRecord
 
-use types::{BigEndian, Fixed, FixedSize};
+use types::{BigEndian, Fixed, FixedSize, NameId};
 
 #[cfg(feature = "traversal")]
 use crate::traversal::{Field, RecordResolver, SomeRecord};
@@ -10,13 +10,13 @@ use crate::{ComputeSize, FontData, FontReadWithArgs, ReadArgs, ReadError};
 #[derive(Clone, Debug)]
 pub struct InstanceRecord<'a> {
     /// The name ID for entries in the 'name' table that provide subfamily names for this instance.
-    pub subfamily_name_id: u16,
+    pub subfamily_name_id: NameId,
     /// Reserved for future use — set to 0.
     pub flags: u16,
     /// The coordinates array for this instance.
     pub coordinates: &'a [BigEndian<Fixed>],
     /// Optional. The name ID for entries in the 'name' table that provide PostScript names for this instance.
-    pub post_script_name_id: Option<u16>,
+    pub post_script_name_id: Option<NameId>,
 }
 
 impl ReadArgs for InstanceRecord<'_> {
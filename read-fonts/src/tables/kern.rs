@@ -0,0 +1,200 @@
+//! The [kern (Kerning)](https://learn.microsoft.com/en-us/typography/opentype/spec/kern) table
+//!
+//! Only the OpenType version of the table is supported; Apple's version,
+//! which has a 32-bit version number, fails to parse.
+
+use crate::Direction;
+
+include!("../../generated/generated_kern.rs");
+
+/// The size of the header that precedes the data of each subtable.
+const SUBTABLE_HEADER_LEN: usize = 6;
+
+impl<'a> Kern<'a> {
+    /// Returns the kerning adjustment for a pair of glyphs, in font units.
+    ///
+    /// The values of all applicable format 0 subtables are combined: each is
+    /// added, unless it is marked as overriding the accumulated value. Other
+    /// formats, and subtables that set minimum values, are ignored.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId, direction: Direction) -> i32 {
+        let mut value = 0;
+        for subtable in self
+            .subtables()
+            .iter()
+            .take(self.n_tables() as usize)
+            .flatten()
+        {
+            if subtable.is_horizontal() != (direction == Direction::Horizontal)
+                || subtable.is_cross_stream()
+                || subtable.is_minimum()
+            {
+                continue;
+            }
+            let Some(Ok(kern0)) = subtable.kern0() else {
+                continue;
+            };
+            if let Some(adjustment) = kern0.kerning(left, right) {
+                if subtable.is_override() {
+                    value = adjustment as i32;
+                } else {
+                    value += adjustment as i32;
+                }
+            }
+        }
+        value
+    }
+}
+
+impl<'a> KernSubtable<'a> {
+    /// The format of the subtable.
+    pub fn format(&self) -> u8 {
+        (self.coverage() >> 8) as u8
+    }
+
+    /// True if the subtable has horizontal data, false if vertical.
+    pub fn is_horizontal(&self) -> bool {
+        self.coverage() & 0x1 != 0
+    }
+
+    /// True if the subtable has minimum values, rather than kerning values.
+    pub fn is_minimum(&self) -> bool {
+        self.coverage() & 0x2 != 0
+    }
+
+    /// True if the kerning is perpendicular to the flow of the text.
+    pub fn is_cross_stream(&self) -> bool {
+        self.coverage() & 0x4 != 0
+    }
+
+    /// True if the values replace those accumulated so far, rather than
+    /// being added to them.
+    pub fn is_override(&self) -> bool {
+        self.coverage() & 0x8 != 0
+    }
+
+    /// The data of the subtable as format 0, or `None` if it has another
+    /// format.
+    pub fn kern0(&self) -> Option<Result<Kern0<'a>, ReadError>> {
+        (self.format() == 0).then(|| Kern0::read(FontData::new(self.data())))
+    }
+}
+
+impl<'a> VarSize for KernSubtable<'a> {
+    type Size = u16;
+
+    fn read_len_at(data: FontData, pos: usize) -> Option<usize> {
+        let length = data.read_at::<u16>(pos + 2).ok()? as usize;
+        let format = data.read_at::<u16>(pos + 4).ok()? >> 8;
+        if format != 0 {
+            return Some(length);
+        }
+        // the length of large format 0 subtables overflows, so we use the
+        // number of pairs instead
+        let n_pairs = data.read_at::<u16>(pos + SUBTABLE_HEADER_LEN).ok()? as usize;
+        Some(SUBTABLE_HEADER_LEN + 4 * u16::RAW_BYTE_LEN + n_pairs * KernPair::RAW_BYTE_LEN)
+    }
+}
+
+impl<'a> FontRead<'a> for KernSubtable<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let len = Self::read_len_at(data, 0).ok_or(ReadError::OutOfBounds)?;
+        let mut cursor = data.cursor();
+        Ok(KernSubtable {
+            version: cursor.read()?,
+            length: cursor.read()?,
+            coverage: cursor.read()?,
+            data: data.read_array(SUBTABLE_HEADER_LEN..len)?,
+        })
+    }
+}
+
+impl<'a> Kern0<'a> {
+    /// Returns the kerning value for a pair of glyphs, if the pair is in
+    /// the subtable.
+    pub fn kerning(&self, left: GlyphId, right: GlyphId) -> Option<i16> {
+        let pairs = self.pairs();
+        let idx = pairs
+            .binary_search_by(|pair| (pair.left(), pair.right()).cmp(&(left, right)))
+            .ok()?;
+        Some(pairs[idx].value().to_i16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    const HORIZONTAL: u16 = 0x1;
+    const OVERRIDE: u16 = 0x8;
+
+    /// A format 0 subtable with the given coverage and (left, right, value)
+    /// pairs.
+    fn subtable(buf: BeBuffer, coverage: u16, pairs: &[(u16, u16, i16)]) -> BeBuffer {
+        let length = (14 + pairs.len() * 6) as u16;
+        let mut buf = buf
+            .extend([0u16, length, coverage])
+            .extend([pairs.len() as u16, 0, 0, 0]);
+        for (left, right, value) in pairs {
+            buf = buf.extend([*left, *right]).push(*value);
+        }
+        buf
+    }
+
+    #[test]
+    fn read_kern() {
+        let buf = BeBuffer::new().extend([0u16, 2]);
+        let buf = subtable(buf, HORIZONTAL, &[(1, 2, -50), (1, 3, 20), (4, 1, 10)]);
+        let buf = subtable(buf, 0, &[(1, 2, 7)]);
+        let kern = Kern::read(buf.font_data()).unwrap();
+        let subtables = kern
+            .subtables()
+            .iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(subtables.len(), 2);
+        assert!(subtables[0].is_horizontal());
+        assert_eq!(subtables[0].kern0().unwrap().unwrap().n_pairs(), 3);
+        assert!(!subtables[1].is_horizontal());
+
+        let kerning = |left, right, direction| {
+            kern.kerning(GlyphId::new(left), GlyphId::new(right), direction)
+        };
+        assert_eq!(kerning(1, 2, Direction::Horizontal), -50);
+        assert_eq!(kerning(1, 3, Direction::Horizontal), 20);
+        assert_eq!(kerning(4, 1, Direction::Horizontal), 10);
+        assert_eq!(kerning(2, 1, Direction::Horizontal), 0);
+        assert_eq!(kerning(1, 2, Direction::Vertical), 7);
+    }
+
+    #[test]
+    fn combine_subtables() {
+        let buf = BeBuffer::new().extend([0u16, 3]);
+        let buf = subtable(buf, HORIZONTAL, &[(1, 2, -50)]);
+        let buf = subtable(buf, HORIZONTAL, &[(1, 2, -10), (1, 3, 5)]);
+        let buf = subtable(buf, HORIZONTAL | OVERRIDE, &[(1, 3, 30)]);
+        let kern = Kern::read(buf.font_data()).unwrap();
+        assert_eq!(
+            kern.kerning(GlyphId::new(1), GlyphId::new(2), Direction::Horizontal),
+            -60
+        );
+        assert_eq!(
+            kern.kerning(GlyphId::new(1), GlyphId::new(3), Direction::Horizontal),
+            30
+        );
+    }
+
+    #[test]
+    fn overflowing_length() {
+        // the length of this subtable is too large for 16 bits
+        let pairs = (0..11_000u16).map(|i| (1, i, 1)).collect::<Vec<_>>();
+        let buf = subtable(BeBuffer::new().extend([0u16, 1]), HORIZONTAL, &pairs);
+        let kern = Kern::read(buf.font_data()).unwrap();
+        let kern0 = kern.subtables().get(0).unwrap().unwrap().kern0();
+        assert_eq!(kern0.unwrap().unwrap().n_pairs(), 11_000);
+        assert_eq!(
+            kern.kerning(GlyphId::new(1), GlyphId::new(10_999), Direction::Horizontal),
+            1
+        );
+    }
+}
@@ -70,6 +70,88 @@ impl<'a> SomeTable<'a> for FeatureParams<'a> {
     }
 }
 
+impl<'a> FeatureVariations<'a> {
+    /// Return the feature table substitution for the first record whose
+    /// condition set matches the provided normalized variation coordinates.
+    ///
+    /// Returns `None` if no condition set matches.
+    pub fn find_substitution(
+        &self,
+        coords: &[F2Dot14],
+    ) -> Option<Result<FeatureTableSubstitution<'a>, ReadError>> {
+        let data = self.offset_data();
+        for record in self.feature_variation_records() {
+            let matches = record
+                .condition_set(data)
+                .and_then(|condition_set| condition_set.matches(coords));
+            match matches {
+                Ok(true) => return Some(record.feature_table_substitution(data)),
+                Ok(false) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        None
+    }
+}
+
+impl ConditionSet<'_> {
+    /// Returns `true` if all conditions in this set are satisfied by the
+    /// provided normalized variation coordinates.
+    ///
+    /// An empty condition set is always satisfied.
+    pub fn matches(&self, coords: &[F2Dot14]) -> Result<bool, ReadError> {
+        for condition in self.conditions() {
+            if !condition?.matches(coords) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl ConditionFormat1<'_> {
+    /// Returns `true` if the coordinate for this condition's axis is within
+    /// the filter range.
+    ///
+    /// Missing coordinates are treated as the default (zero) value.
+    pub fn matches(&self, coords: &[F2Dot14]) -> bool {
+        let coord = coords
+            .get(self.axis_index() as usize)
+            .copied()
+            .unwrap_or_default();
+        self.filter_range_min_value() <= coord && coord <= self.filter_range_max_value()
+    }
+}
+
+impl<'a> FeatureTableSubstitution<'a> {
+    /// Return the alternate feature table for the feature at `feature_index`,
+    /// if it is substituted.
+    pub fn substitute(&self, feature_index: u16) -> Option<Result<Feature<'a>, ReadError>> {
+        let records = self.substitutions();
+        let idx = records
+            .binary_search_by_key(&feature_index, |rec| rec.feature_index())
+            .ok()?;
+        Some(records[idx].alternate_feature(self.offset_data()))
+    }
+}
+
+impl<'a> LangSysFeature<'a> {
+    /// Replace this feature's table with its alternate in `substitution`, if
+    /// one exists.
+    pub fn apply_substitution(
+        self,
+        substitution: &FeatureTableSubstitution<'a>,
+    ) -> Result<Self, ReadError> {
+        match substitution.substitute(self.index) {
+            Some(feature) => Ok(LangSysFeature {
+                feature: feature?,
+                ..self
+            }),
+            None => Ok(self),
+        }
+    }
+}
+
 impl FeatureTableSubstitutionRecord {
     pub fn alternate_feature<'a>(&self, data: FontData<'a>) -> Result<Feature<'a>, ReadError> {
         self.alternate_feature_offset()
@@ -93,6 +175,235 @@ impl CoverageTable<'_> {
             .flatten()
             .chain(iter2.into_iter().flatten())
     }
+
+    /// If this glyph is covered, return its coverage index.
+    pub fn get(&self, gid: GlyphId) -> Option<u16> {
+        match self {
+            CoverageTable::Format1(t) => t.get(gid),
+            CoverageTable::Format2(t) => t.get(gid),
+        }
+    }
+}
+
+/// Tag for the default script, used when a requested script is not found.
+pub const DFLT_SCRIPT: Tag = Tag::new(b"DFLT");
+
+/// The result of selecting a script with [`ScriptList::select`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SelectedScript {
+    /// The index of the selected script in the script list.
+    pub index: u16,
+    /// The tag of the selected script.
+    pub tag: Tag,
+    /// `true` if none of the requested scripts were present, and this is a
+    /// fallback script.
+    pub is_fallback: bool,
+}
+
+impl<'a> ScriptList<'a> {
+    /// Iterate over the tags of the scripts in this list.
+    pub fn script_tags(&self) -> impl Iterator<Item = Tag> + 'a {
+        self.script_records().iter().map(|rec| rec.script_tag())
+    }
+
+    /// Return the index of the script with the provided tag, if present.
+    pub fn index_for_tag(&self, tag: Tag) -> Option<u16> {
+        self.script_records()
+            .binary_search_by_key(&tag, |rec| rec.script_tag())
+            .ok()
+            .map(|idx| idx as u16)
+    }
+
+    /// Return the script at the provided index.
+    pub fn get(&self, index: u16) -> Result<Script<'a>, ReadError> {
+        self.script_records()
+            .get(index as usize)
+            .ok_or(ReadError::OutOfBounds)
+            .and_then(|rec| rec.script(self.offset_data()))
+    }
+
+    /// Select the first of `tags` present in this list.
+    ///
+    /// If none of the requested scripts are present, this falls back to the
+    /// default script ('DFLT'), then to the non-standard 'dflt' tag, and finally
+    /// to 'latn', matching the behaviour of HarfBuzz.
+    pub fn select(&self, tags: &[Tag]) -> Option<SelectedScript> {
+        for tag in tags {
+            if let Some(index) = self.index_for_tag(*tag) {
+                return Some(SelectedScript {
+                    index,
+                    tag: *tag,
+                    is_fallback: false,
+                });
+            }
+        }
+        for tag in [DFLT_SCRIPT, Tag::new(b"dflt"), Tag::new(b"latn")] {
+            if let Some(index) = self.index_for_tag(tag) {
+                return Some(SelectedScript {
+                    index,
+                    tag,
+                    is_fallback: true,
+                });
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Script<'a> {
+    /// Iterate over the tags of the (non-default) language systems in this script.
+    pub fn lang_sys_tags(&self) -> impl Iterator<Item = Tag> + 'a {
+        self.lang_sys_records().iter().map(|rec| rec.lang_sys_tag())
+    }
+
+    /// Return the index of the language system with the provided tag, if present.
+    pub fn lang_sys_index_for_tag(&self, tag: Tag) -> Option<u16> {
+        self.lang_sys_records()
+            .binary_search_by_key(&tag, |rec| rec.lang_sys_tag())
+            .ok()
+            .map(|idx| idx as u16)
+    }
+
+    /// Return the language system with the provided tag, or the default
+    /// language system if `tag` is `None`.
+    pub fn lang_sys_for_tag(&self, tag: Option<Tag>) -> Option<Result<LangSys<'a>, ReadError>> {
+        let Some(tag) = tag else {
+            return self.default_lang_sys();
+        };
+        let index = self.lang_sys_index_for_tag(tag)?;
+        Some(self.lang_sys_records()[index as usize].lang_sys(self.offset_data()))
+    }
+
+    /// Select the first of `tags` present in this script, falling back to
+    /// the default language system.
+    ///
+    /// As in HarfBuzz, a language system with the (non-standard) tag 'dflt' is
+    /// used if no default language system is present.
+    pub fn select_lang_sys(&self, tags: &[Tag]) -> Option<Result<LangSys<'a>, ReadError>> {
+        tags.iter()
+            .find_map(|tag| self.lang_sys_for_tag(Some(*tag)))
+            .or_else(|| self.default_lang_sys())
+            .or_else(|| self.lang_sys_for_tag(Some(Tag::new(b"dflt"))))
+    }
+}
+
+/// A feature selected by a [`LangSys`].
+#[derive(Clone)]
+pub struct LangSysFeature<'a> {
+    /// The index of this feature in the [`FeatureList`].
+    pub index: u16,
+    /// The feature tag.
+    pub tag: Tag,
+    /// `true` if this is the language system's required feature.
+    pub is_required: bool,
+    /// The feature table.
+    pub feature: Feature<'a>,
+}
+
+impl<'a> LangSys<'a> {
+    /// The index of the required feature, if any.
+    pub fn required_feature(&self) -> Option<u16> {
+        match self.required_feature_index() {
+            0xFFFF => None,
+            index => Some(index),
+        }
+    }
+
+    /// Iterate over the indices of all features selected by this language
+    /// system, starting with the required feature, if present.
+    pub fn all_feature_indices(&self) -> impl Iterator<Item = u16> + 'a {
+        self.required_feature()
+            .into_iter()
+            .chain(self.feature_indices().iter().map(|idx| idx.get()))
+    }
+
+    /// Iterate over the features selected by this language system, starting
+    /// with the required feature, if present.
+    pub fn features(
+        &self,
+        feature_list: &FeatureList<'a>,
+    ) -> impl Iterator<Item = Result<LangSysFeature<'a>, ReadError>> + 'a {
+        let feature_list = feature_list.clone();
+        let has_required = self.required_feature().is_some();
+        self.all_feature_indices()
+            .enumerate()
+            .map(move |(i, index)| {
+                let (tag, feature) = feature_list.get(index)?;
+                Ok(LangSysFeature {
+                    index,
+                    tag,
+                    is_required: has_required && i == 0,
+                    feature,
+                })
+            })
+    }
+}
+
+impl<'a> FeatureList<'a> {
+    /// Iterate over the tags of the features in this list.
+    pub fn feature_tags(&self) -> impl Iterator<Item = Tag> + 'a {
+        self.feature_records().iter().map(|rec| rec.feature_tag())
+    }
+
+    /// Return the tag and feature table at the provided index.
+    pub fn get(&self, index: u16) -> Result<(Tag, Feature<'a>), ReadError> {
+        let record = self
+            .feature_records()
+            .get(index as usize)
+            .ok_or(ReadError::OutOfBounds)?;
+        Ok((record.feature_tag(), record.feature(self.offset_data())?))
+    }
+
+    /// Return the indices of the lookups for the feature at the provided index.
+    ///
+    /// If a substitution is provided (see [`FeatureVariations::find_substitution`])
+    /// and it contains an alternate for this feature, the alternate's lookups are
+    /// returned instead.
+    pub fn lookup_indices(
+        &self,
+        index: u16,
+        substitution: Option<&FeatureTableSubstitution<'a>>,
+    ) -> Result<&'a [BigEndian<u16>], ReadError> {
+        let feature = match substitution.and_then(|subst| subst.substitute(index)) {
+            Some(feature) => feature?,
+            None => self.get(index)?.1,
+        };
+        Ok(feature.lookup_list_indices())
+    }
+}
+
+impl<'a, T: FontRead<'a>> LookupList<'a, T> {
+    /// Return the lookup at the provided index.
+    pub fn get(&self, index: u16) -> Result<T, ReadError> {
+        self.lookup_offsets()
+            .get(index as usize)
+            .ok_or(ReadError::OutOfBounds)
+            .and_then(|offset| self.resolve_offset(offset.get()))
+    }
+}
+
+impl CoverageFormat1<'_> {
+    /// If this glyph is covered, return its coverage index.
+    pub fn get(&self, gid: GlyphId) -> Option<u16> {
+        self.glyph_array()
+            .binary_search_by(|probe| probe.get().cmp(&gid))
+            .ok()
+            .map(|idx| idx as u16)
+    }
+}
+
+impl CoverageFormat2<'_> {
+    /// If this glyph is covered, return its coverage index.
+    pub fn get(&self, gid: GlyphId) -> Option<u16> {
+        let records = self.range_records();
+        let idx = records
+            .binary_search_by(|rec| cmp_range(rec.start_glyph_id(), rec.end_glyph_id(), gid))
+            .ok()?;
+        let record = &records[idx];
+        record
+            .start_coverage_index()
+            .checked_add(gid.to_u16() - record.start_glyph_id().to_u16())
+    }
 }
 
 impl RangeRecord {
@@ -101,6 +412,78 @@ impl RangeRecord {
     }
 }
 
+impl ClassDef<'_> {
+    /// Return the class of the provided glyph.
+    ///
+    /// Glyphs that are not explicitly assigned a class belong to class 0.
+    pub fn get(&self, gid: GlyphId) -> u16 {
+        match self {
+            ClassDef::Format1(t) => t.get(gid),
+            ClassDef::Format2(t) => t.get(gid),
+        }
+    }
+
+    /// Iterate over each glyph that is explicitly assigned a class, and its class.
+    pub fn iter(&self) -> impl Iterator<Item = (GlyphId, u16)> + '_ {
+        let (iter1, iter2) = match self {
+            ClassDef::Format1(t) => (Some(t.iter()), None),
+            ClassDef::Format2(t) => (None, Some(t.iter())),
+        };
+        iter1
+            .into_iter()
+            .flatten()
+            .chain(iter2.into_iter().flatten())
+    }
+}
+
+impl ClassDefFormat1<'_> {
+    /// Return the class of the provided glyph.
+    pub fn get(&self, gid: GlyphId) -> u16 {
+        let idx = (gid.to_u16() as usize).wrapping_sub(self.start_glyph_id().to_u16() as usize);
+        self.class_value_array()
+            .get(idx)
+            .map(|class| class.get())
+            .unwrap_or(0)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (GlyphId, u16)> + '_ {
+        let start = self.start_glyph_id().to_u16();
+        self.class_value_array()
+            .iter()
+            .enumerate()
+            .map(move |(i, class)| (GlyphId::new(start.wrapping_add(i as u16)), class.get()))
+    }
+}
+
+impl ClassDefFormat2<'_> {
+    /// Return the class of the provided glyph.
+    pub fn get(&self, gid: GlyphId) -> u16 {
+        let records = self.class_range_records();
+        records
+            .binary_search_by(|rec| cmp_range(rec.start_glyph_id(), rec.end_glyph_id(), gid))
+            .map(|idx| records[idx].class())
+            .unwrap_or(0)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (GlyphId, u16)> + '_ {
+        self.class_range_records().iter().flat_map(|rec| {
+            (rec.start_glyph_id().to_u16()..=rec.end_glyph_id().to_u16())
+                .map(|gid| (GlyphId::new(gid), rec.class()))
+        })
+    }
+}
+
+/// Compare a glyph range to a glyph, for binary searching ranges.
+fn cmp_range(start: GlyphId, end: GlyphId, gid: GlyphId) -> std::cmp::Ordering {
+    if end < gid {
+        std::cmp::Ordering::Less
+    } else if start > gid {
+        std::cmp::Ordering::Greater
+    } else {
+        std::cmp::Ordering::Equal
+    }
+}
+
 impl Default for DeltaFormat {
     fn default() -> Self {
         DeltaFormat::Local2BitDeltas
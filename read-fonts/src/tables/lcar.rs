@@ -0,0 +1,51 @@
+//! The [lcar (Ligature Caret)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6lcar.html) table
+
+include!("../../generated/generated_lcar.rs");
+
+use super::aat::Lookup;
+
+impl<'a> Lcar<'a> {
+    /// Returns the lookup table mapping glyphs to their caret entries.
+    pub fn lookup(&self) -> Result<Lookup<'a>, ReadError> {
+        let data = self.offset_data();
+        let start = self.shape.lookup_data_byte_range().start;
+        Lookup::read(data.split_off(start).ok_or(ReadError::OutOfBounds)?)
+    }
+
+    /// Returns the ligature carets for the given glyph, if any.
+    ///
+    /// The interpretation of the caret values depends on the table
+    /// [format](Lcar::format).
+    pub fn carets(&self, glyph_id: GlyphId) -> Result<Option<LigCaretClassEntry<'a>>, ReadError> {
+        let Some(offset) = self.lookup()?.value(glyph_id) else {
+            return Ok(None);
+        };
+        let data = self
+            .offset_data()
+            .split_off(offset as usize)
+            .ok_or(ReadError::OutOfBounds)?;
+        LigCaretClassEntry::read(data).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn read_carets() {
+        let buf = BeBuffer::new()
+            .push(MajorMinor::VERSION_1_0)
+            .push(0u16) // format
+            // format 8 lookup for glyph 4
+            .extend([8u16, 4, 1, 14])
+            // carets for glyph 4
+            .extend([2u16, 300, 600]);
+        let lcar = Lcar::read(buf.font_data()).unwrap();
+        assert_eq!(lcar.format(), 0);
+        let carets = lcar.carets(GlyphId::new(4)).unwrap().unwrap();
+        assert_eq!(carets.partials(), [300, 600]);
+        assert!(lcar.carets(GlyphId::new(5)).unwrap().is_none());
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     read::{FontRead, FontReadWithArgs, ReadArgs, ReadError},
     table_provider::TopLevelTable,
 };
-use types::{BigEndian, GlyphId, Tag};
+use types::{BigEndian, BigEndianSlice, GlyphId24, Tag};
 
 #[cfg(feature = "traversal")]
 use crate::traversal;
@@ -21,7 +21,7 @@ pub enum Loca<'a> {
 }
 
 impl TopLevelTable for Loca<'_> {
-    const TAG: Tag = Tag::new(b"loca");
+    const TAG: Tag = Tag::LOCA;
 }
 
 impl<'a> Loca<'a> {
@@ -44,12 +44,49 @@ impl<'a> Loca<'a> {
         }
     }
 
+    /// Converts all of the offsets at once, writing them to `dst`.
+    ///
+    /// As with [`get_raw`](Self::get_raw), short offsets are multiplied by
+    /// two. This is considerably faster than calling `get_raw` for each
+    /// entry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` does not have one element per entry in the table,
+    /// including the final entry that marks the end of the last glyph.
+    pub fn copy_offsets(&self, dst: &mut [u32]) {
+        match self {
+            Loca::Short(data) => {
+                assert_eq!(
+                    data.len(),
+                    dst.len(),
+                    "source and destination slices have different lengths"
+                );
+                const CHUNK_LEN: usize = 64;
+                let mut buf = [0u16; CHUNK_LEN];
+                for (src, dst) in data.chunks(CHUNK_LEN).zip(dst.chunks_mut(CHUNK_LEN)) {
+                    let buf = &mut buf[..src.len()];
+                    src.copy_to_native(buf);
+                    for (dst, offset) in dst.iter_mut().zip(buf.iter()) {
+                        *dst = *offset as u32 * 2;
+                    }
+                }
+            }
+            Loca::Long(data) => data.copy_to_native(dst),
+        }
+    }
+
+    /// Returns the glyph for the given glyph id, or `None` if it has no
+    /// outline.
+    ///
+    /// This accepts both 16-bit and 24-bit glyph ids, since a long loca
+    /// table can address more than 65535 glyphs.
     pub fn get_glyf(
         &self,
-        gid: GlyphId,
+        gid: impl Into<GlyphId24>,
         glyf: &super::glyf::Glyf<'a>,
     ) -> Result<Option<super::glyf::Glyph<'a>>, ReadError> {
-        let idx = gid.to_u16() as usize;
+        let idx = gid.into().to_u32() as usize;
         let start = self.get_raw(idx).ok_or(ReadError::OutOfBounds)?;
         let end = self.get_raw(idx + 1).ok_or(ReadError::OutOfBounds)?;
         if start == end {
@@ -119,3 +156,36 @@ impl<'a> std::fmt::Debug for Loca<'a> {
         (self as &dyn traversal::SomeTable<'a>).fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn copy_offsets() {
+        let short = BeBuffer::new().extend([0u16, 10, 10, 30]);
+        let long = BeBuffer::new().extend([0u32, 100, 100, 250]);
+        for (data, is_long) in [(short, false), (long, true)] {
+            let loca = Loca::read_with_args(data.font_data(), &is_long).unwrap();
+            let mut offsets = vec![0; loca.len() + 1];
+            loca.copy_offsets(&mut offsets);
+            let expected = (0..=loca.len())
+                .map(|i| loca.get_raw(i).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(offsets, expected);
+        }
+    }
+
+    #[test]
+    fn glyph_id_beyond_16_bits() {
+        let data = BeBuffer::new().extend(vec![0u32; 0x10002]);
+        let loca = Loca::read_with_args(data.font_data(), &true).unwrap();
+        let glyf = super::super::glyf::Glyf::read(crate::FontData::new(&[])).unwrap();
+        let last = GlyphId24::checked_new(0x10000).unwrap();
+        assert!(loca.get_glyf(last, &glyf).unwrap().is_none());
+        // this would be glyph 1 if the id were truncated to 16 bits
+        let past_end = GlyphId24::checked_new(0x10001).unwrap();
+        assert!(loca.get_glyf(past_end, &glyf).is_err());
+    }
+}
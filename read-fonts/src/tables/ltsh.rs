@@ -0,0 +1,26 @@
+//! The [LTSH (Linear Threshold)](https://learn.microsoft.com/en-us/typography/opentype/spec/ltsh) table
+
+include!("../../generated/generated_ltsh.rs");
+
+impl<'a> Ltsh<'a> {
+    /// Returns the pel height at and above which the given glyph scales
+    /// linearly, or `None` if the glyph is out of range.
+    pub fn y_pel(&self, gid: GlyphId) -> Option<u8> {
+        self.y_pels().get(gid.to_u16() as usize).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn read_ltsh() {
+        let buf = BeBuffer::new().extend([0u16, 3]).extend([1u8, 12, 255]);
+        let ltsh = Ltsh::read(buf.font_data()).unwrap();
+        assert_eq!(ltsh.num_glyphs(), 3);
+        assert_eq!(ltsh.y_pel(GlyphId::new(1)), Some(12));
+        assert_eq!(ltsh.y_pel(GlyphId::new(3)), None);
+    }
+}
@@ -0,0 +1,5 @@
+//! The [MATH](https://learn.microsoft.com/en-us/typography/opentype/spec/math) table
+
+use super::layout::{CoverageTable, Device};
+
+include!("../../generated/generated_math.rs");
@@ -0,0 +1,50 @@
+//! The [opbd (Optical Bounds)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6opbd.html) table
+
+include!("../../generated/generated_opbd.rs");
+
+use super::aat::Lookup;
+
+impl<'a> Opbd<'a> {
+    /// Returns the lookup table mapping glyphs to their optical bounds.
+    pub fn lookup(&self) -> Result<Lookup<'a>, ReadError> {
+        let data = self.offset_data();
+        let start = self.shape.lookup_data_byte_range().start;
+        Lookup::read(data.split_off(start).ok_or(ReadError::OutOfBounds)?)
+    }
+
+    /// Returns the optical bounds for the given glyph, if any.
+    ///
+    /// The interpretation of the bounds depends on the table
+    /// [format](Opbd::format).
+    pub fn bounds(&self, glyph_id: GlyphId) -> Result<Option<&'a OpticalBounds>, ReadError> {
+        let Some(offset) = self.lookup()?.value(glyph_id) else {
+            return Ok(None);
+        };
+        self.offset_data().read_ref_at(offset as usize).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn read_bounds() {
+        let buf = BeBuffer::new()
+            .push(MajorMinor::VERSION_1_0)
+            .push(0u16) // format
+            // format 6 lookup for glyph 3
+            .extend([6u16, 4, 1, 4, 0, 0])
+            .extend([3u16, 26])
+            .extend([0xFFFFu16, 0])
+            .extend([-10i16, 0, 20, 0]);
+        let opbd = Opbd::read(buf.font_data()).unwrap();
+        let bounds = opbd.bounds(GlyphId::new(3)).unwrap().unwrap();
+        assert_eq!(
+            (bounds.left(), bounds.top(), bounds.right(), bounds.bottom()),
+            (-10, 0, 20, 0)
+        );
+        assert!(opbd.bounds(GlyphId::new(4)).unwrap().is_none());
+    }
+}
@@ -0,0 +1,56 @@
+//! The [PCLT (PCL 5)](https://learn.microsoft.com/en-us/typography/opentype/spec/pclt) table
+
+include!("../../generated/generated_pclt.rs");
+
+impl<'a> Pclt<'a> {
+    /// Returns the typeface name, with trailing padding removed.
+    ///
+    /// Returns `None` if the name is not valid ASCII.
+    pub fn typeface_str(&self) -> Option<&'a str> {
+        trimmed_ascii(self.typeface())
+    }
+
+    /// Returns the font file name, with trailing padding removed.
+    ///
+    /// Returns `None` if the name is not valid ASCII.
+    pub fn file_name_str(&self) -> Option<&'a str> {
+        trimmed_ascii(self.file_name())
+    }
+}
+
+fn trimmed_ascii(bytes: &[u8]) -> Option<&str> {
+    if !bytes.is_ascii() {
+        return None;
+    }
+    core::str::from_utf8(bytes)
+        .ok()
+        .map(|s| s.trim_end_matches([' ', '\0']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    #[test]
+    fn read_pclt() {
+        let buf = BeBuffer::new()
+            .push(Version16Dot16::VERSION_1_0)
+            .push(0x1234_5678u32) // font number
+            .extend([512u16, 1082, 0, 4101, 1456, 277])
+            .extend(*b"Courier Bold\0\0\0\0")
+            .extend([0xffu8; 8])
+            .extend(*b"CRB   ")
+            .extend([3i8, 0])
+            .extend([1u8, 0]);
+        let pclt = Pclt::read(buf.font_data()).unwrap();
+        assert_eq!(pclt.font_number(), 0x1234_5678);
+        assert_eq!(pclt.x_height(), 1082);
+        assert_eq!(pclt.cap_height(), 1456);
+        assert_eq!(pclt.typeface_str(), Some("Courier Bold"));
+        assert_eq!(pclt.character_complement(), [0xff; 8]);
+        assert_eq!(pclt.file_name_str(), Some("CRB"));
+        assert_eq!(pclt.stroke_weight(), 3);
+        assert_eq!(pclt.serif_style(), 1);
+    }
+}
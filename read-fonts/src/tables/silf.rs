@@ -0,0 +1,355 @@
+//! The Graphite [Silf (Silf Rules)](https://github.com/silnrsi/graphite/blob/master/doc/table_silf.txt) table
+
+include!("../../generated/generated_silf.rs");
+
+pub use super::glat::Compression;
+
+#[cfg(feature = "traversal")]
+use crate::traversal;
+
+/// The Graphite [Silf (Silf Rules)](https://github.com/silnrsi/graphite/blob/master/doc/table_silf.txt) table
+///
+/// The header of this table changes between versions in a way that the
+/// generated parsers cannot express, so it is parsed by hand.
+#[derive(Clone)]
+pub struct Silf<'a> {
+    data: FontData<'a>,
+    version: Version16Dot16,
+    compiler_version: Option<u32>,
+    subtable_offsets: &'a [BigEndian<Offset32>],
+}
+
+impl TopLevelTable for Silf<'_> {
+    const TAG: Tag = Tag::new(b"Silf");
+}
+
+impl<'a> FontRead<'a> for Silf<'a> {
+    fn read(data: FontData<'a>) -> Result<Self, ReadError> {
+        let mut cursor = data.cursor();
+        let version: Version16Dot16 = cursor.read()?;
+        let compiler_version = if version >= Version16Dot16::VERSION_3_0 {
+            Some(cursor.read::<u32>()?)
+        } else {
+            None
+        };
+        let mut silf = Silf {
+            data,
+            version,
+            compiler_version,
+            subtable_offsets: &[],
+        };
+        // the remainder of a compressed table cannot be read in place
+        if silf.compression() == Compression::None {
+            let num_sub: u16 = cursor.read()?;
+            cursor.advance::<u16>();
+            silf.subtable_offsets = cursor.read_array(num_sub as usize)?;
+        }
+        Ok(silf)
+    }
+}
+
+impl<'a> Silf<'a> {
+    /// Table version.
+    pub fn version(&self) -> Version16Dot16 {
+        self.version
+    }
+
+    /// The version of the compiler that generated the table; present in
+    /// version 3.0 and later.
+    ///
+    /// In version 5.0 and later the top five bits of this field indicate the
+    /// compression scheme; see [Silf::compression].
+    pub fn compiler_version(&self) -> Option<u32> {
+        self.compiler_version
+    }
+
+    /// Returns the compression scheme used by this table.
+    ///
+    /// Compressed tables must be decompressed before their subtables can
+    /// be read, and report no subtables.
+    pub fn compression(&self) -> Compression {
+        match self.compiler_version {
+            Some(header) if self.version >= Version16Dot16::new(5, 0) => {
+                Compression::from_header(header)
+            }
+            _ => Compression::None,
+        }
+    }
+
+    /// Number of Silf subtables.
+    pub fn num_sub(&self) -> u16 {
+        self.subtable_offsets.len() as u16
+    }
+
+    /// Offsets in bytes from the beginning of the Silf table to each subtable.
+    pub fn subtable_offsets(&self) -> &'a [BigEndian<Offset32>] {
+        self.subtable_offsets
+    }
+
+    /// Returns the subtable at the given index.
+    ///
+    /// Only subtables of version 3.0 and later tables are supported.
+    pub fn subtable(&self, index: usize) -> Result<SilfSubtable<'a>, ReadError> {
+        if self.version < Version16Dot16::VERSION_3_0 {
+            return Err(ReadError::InvalidFormat(
+                (self.version.to_major_minor().0).into(),
+            ));
+        }
+        self.subtable_offsets
+            .get(index)
+            .ok_or(ReadError::OutOfBounds)?
+            .get()
+            .resolve(self.data)
+    }
+
+    /// Returns an iterator over the subtables in this table.
+    pub fn subtables(&self) -> impl Iterator<Item = Result<SilfSubtable<'a>, ReadError>> + '_ {
+        (0..self.subtable_offsets.len()).map(|ix| self.subtable(ix))
+    }
+
+    /// Returns the class map of the given subtable.
+    ///
+    /// The size of the class offsets depends on the version of this table,
+    /// which is why this is not a method on the subtable itself.
+    pub fn class_map(&self, subtable: &SilfSubtable<'a>) -> Result<ClassMap<'a>, ReadError> {
+        ClassMap::read(
+            FontData::new(subtable.trailing_data()),
+            self.version >= Version16Dot16::new(4, 0),
+        )
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> traversal::SomeTable<'a> for Silf<'a> {
+    fn type_name(&self) -> &str {
+        "Silf"
+    }
+
+    fn get_field(&self, idx: usize) -> Option<traversal::Field<'a>> {
+        match idx {
+            0usize => Some(traversal::Field::new("version", self.version())),
+            1usize => Some(traversal::Field::new(
+                "compiler_version",
+                self.compiler_version().unwrap_or_default(),
+            )),
+            2usize => Some(traversal::Field::new("num_sub", self.num_sub())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "traversal")]
+impl<'a> std::fmt::Debug for Silf<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (self as &dyn traversal::SomeTable<'a>).fmt(f)
+    }
+}
+
+impl<'a> SilfSubtable<'a> {
+    /// Returns the pseudo glyph for the given codepoint, if one is defined.
+    pub fn pseudo_glyph(&self, codepoint: u32) -> Option<GlyphId> {
+        let maps = self.pseudo_maps();
+        maps.binary_search_by_key(&codepoint, |map| map.unicode())
+            .ok()
+            .map(|idx| maps[idx].n_pseudo())
+    }
+
+    /// Returns the raw data for the pass at the given index.
+    pub fn pass_data(&self, index: usize) -> Result<FontData<'a>, ReadError> {
+        let offsets = self.pass_offsets();
+        let (Some(start), Some(end)) = (offsets.get(index), offsets.get(index + 1)) else {
+            return Err(ReadError::OutOfBounds);
+        };
+        self.offset_data()
+            .slice(start.get() as usize..end.get() as usize)
+            .ok_or(ReadError::OutOfBounds)
+    }
+}
+
+/// The class map of a [SilfSubtable], used to map glyphs to and from the
+/// glyph classes referenced by rules.
+#[derive(Clone)]
+pub struct ClassMap<'a> {
+    data: FontData<'a>,
+    num_class: u16,
+    num_linear: u16,
+    long_offsets: bool,
+}
+
+/// A glyph class in a [ClassMap].
+#[derive(Clone)]
+pub enum Class<'a> {
+    /// An ordered list of glyphs, used as the output of substitutions.
+    Linear(&'a [BigEndian<GlyphId>]),
+    /// A sorted lookup from glyph to index, used to match input glyphs.
+    Lookup(LookupClass<'a>),
+}
+
+impl<'a> ClassMap<'a> {
+    fn read(data: FontData<'a>, long_offsets: bool) -> Result<Self, ReadError> {
+        let num_class: u16 = data.read_at(0)?;
+        let num_linear: u16 = data.read_at(2)?;
+        if num_linear > num_class {
+            return Err(ReadError::MalformedData(
+                "more linear classes than classes in class map",
+            ));
+        }
+        Ok(Self {
+            data,
+            num_class,
+            num_linear,
+            long_offsets,
+        })
+    }
+
+    /// Returns the total number of classes.
+    pub fn num_classes(&self) -> u16 {
+        self.num_class
+    }
+
+    /// Returns the number of linear classes; these precede the lookup classes.
+    pub fn num_linear(&self) -> u16 {
+        self.num_linear
+    }
+
+    fn class_offset(&self, index: usize) -> Result<usize, ReadError> {
+        const HEADER_LEN: usize = 4;
+        if self.long_offsets {
+            self.data
+                .read_at::<u32>(HEADER_LEN + index * u32::RAW_BYTE_LEN)
+                .map(|offset| offset as usize)
+        } else {
+            self.data
+                .read_at::<u16>(HEADER_LEN + index * u16::RAW_BYTE_LEN)
+                .map(|offset| offset as usize)
+        }
+    }
+
+    /// Returns the class at the given index.
+    pub fn class(&self, index: u16) -> Result<Class<'a>, ReadError> {
+        if index >= self.num_class {
+            return Err(ReadError::OutOfBounds);
+        }
+        let start = self.class_offset(index as usize)?;
+        if index < self.num_linear {
+            let end = self.class_offset(index as usize + 1)?;
+            if start > end {
+                return Err(ReadError::MalformedData("class offsets not in order"));
+            }
+            self.data.read_array(start..end).map(Class::Linear)
+        } else {
+            let data = self.data.split_off(start).ok_or(ReadError::OutOfBounds)?;
+            LookupClass::read(data).map(Class::Lookup)
+        }
+    }
+}
+
+impl<'a> Class<'a> {
+    /// Returns the index of the glyph in this class, if present.
+    pub fn index_of(&self, gid: GlyphId) -> Option<u16> {
+        match self {
+            Self::Linear(glyphs) => glyphs
+                .iter()
+                .position(|glyph| glyph.get() == gid)
+                .map(|idx| idx as u16),
+            Self::Lookup(lookup) => {
+                let pairs = lookup.lookups();
+                pairs
+                    .binary_search_by_key(&gid, |pair| pair.glyph_id())
+                    .ok()
+                    .map(|idx| pairs[idx].index())
+            }
+        }
+    }
+
+    /// Returns the glyph at the given index in a linear class.
+    pub fn glyph(&self, index: u16) -> Option<GlyphId> {
+        match self {
+            Self::Linear(glyphs) => glyphs.get(index as usize).map(|glyph| glyph.get()),
+            Self::Lookup(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::BeBuffer;
+
+    fn push_subtable(buf: BeBuffer) -> BeBuffer {
+        buf.push(Version16Dot16::new(4, 0)) // rule version
+            .extend([46u16, 54, 10]) // pass offset, pseudos offset, max glyph id
+            .extend([0i16, 0]) // extra ascent, descent
+            .push(1u8) // num passes
+            .extend([0u8; 12]) // pass indices, flags, contexts and attributes
+            .push(0u8) // num justification levels
+            .push(0u16) // num lig comp
+            .extend([0u8; 7]) // user defn, max comp, direction, collisions, reserved
+            .push(0u8) // num crit features
+            .push(0u8) // reserved
+            .push(1u8) // num script tags
+            .push(Tag::new(b"latn"))
+            .push(GlyphId::new(9)) // line break glyph
+            .extend([104u32, 108]) // pass offsets
+            .extend([1u16, 1, 0, 0]) // pseudo search header
+            .push(0x41u32)
+            .push(GlyphId::new(8))
+            // class map: one linear class and one lookup class
+            .extend([2u16, 1])
+            .extend([16u32, 20, 36])
+            .extend([5u16, 3])
+            .extend([2u16, 2, 1, 0])
+            .extend([3u16, 1, 5, 0])
+            // pass data
+            .push(0xdeadbeef_u32)
+    }
+
+    #[test]
+    fn read_silf() {
+        let buf = BeBuffer::new()
+            .push(Version16Dot16::new(4, 0))
+            .push(0x00040000u32) // compiler version
+            .extend([1u16, 0])
+            .push(16u32);
+        let buf = push_subtable(buf);
+        let silf = Silf::read(buf.font_data()).unwrap();
+        assert_eq!(silf.compression(), Compression::None);
+        assert_eq!(silf.num_sub(), 1);
+        let subtable = silf.subtable(0).unwrap();
+        assert!(silf.subtable(1).is_err());
+        assert_eq!(subtable.num_passes(), 1);
+        assert_eq!(subtable.script_tags()[0].get(), Tag::new(b"latn"));
+        assert_eq!(subtable.lb_gid(), GlyphId::new(9));
+        assert_eq!(subtable.pseudo_glyph(0x41), Some(GlyphId::new(8)));
+        assert_eq!(subtable.pseudo_glyph(0x42), None);
+        assert_eq!(
+            subtable.pass_data(0).unwrap().read_at::<u32>(0).unwrap(),
+            0xdeadbeef
+        );
+        assert!(subtable.pass_data(1).is_err());
+
+        let class_map = silf.class_map(&subtable).unwrap();
+        assert_eq!(class_map.num_classes(), 2);
+        assert_eq!(class_map.num_linear(), 1);
+        let linear = class_map.class(0).unwrap();
+        assert_eq!(linear.glyph(1), Some(GlyphId::new(3)));
+        assert_eq!(linear.index_of(GlyphId::new(5)), Some(0));
+        let lookup = class_map.class(1).unwrap();
+        assert_eq!(lookup.index_of(GlyphId::new(5)), Some(0));
+        assert_eq!(lookup.index_of(GlyphId::new(3)), Some(1));
+        assert_eq!(lookup.index_of(GlyphId::new(4)), None);
+        assert!(class_map.class(2).is_err());
+    }
+
+    #[test]
+    fn compressed() {
+        let buf = BeBuffer::new()
+            .push(Version16Dot16::new(5, 0))
+            .push((1u32 << 27) | 100)
+            .extend([0u8; 8]);
+        let silf = Silf::read(buf.font_data()).unwrap();
+        assert_eq!(silf.compression(), Compression::Lz4(100));
+        assert_eq!(silf.num_sub(), 0);
+        assert_eq!(silf.subtables().count(), 0);
+    }
+}
@@ -2,6 +2,8 @@
 
 include!("../../generated/generated_variations.rs");
 
+use types::BigEndianSlice;
+
 /// Outer and inner indices for reading from an [ItemVariationStore].
 #[derive(Copy, Clone, Debug)]
 pub struct DeltaSetIndex {
@@ -12,6 +14,7 @@ pub struct DeltaSetIndex {
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TupleIndex(u16);
 
 impl TupleIndex {
@@ -21,21 +24,21 @@ impl TupleIndex {
     /// If set, the low 12 bits of the tupleIndex value are ignored.
     ///
     /// Note that this must always be set within the 'cvar' table.
-    const EMBEDDED_PEAK_TUPLE: u16 = 0x8000;
+    pub const EMBEDDED_PEAK_TUPLE: u16 = 0x8000;
 
     /// Flag indicating that this tuple variation table applies to an
     /// intermediate region within the variation space.
     ///
     /// If set, the header includes the two intermediate-region, start and end
     /// tuple records, immediately after the peak tuple record (if present).
-    const INTERMEDIATE_REGION: u16 = 0x4000;
+    pub const INTERMEDIATE_REGION: u16 = 0x4000;
     /// Flag indicating that the serialized data for this tuple variation table
     /// includes packed “point” number data.
     ///
     /// If set, this tuple variation table uses that number data; if clear,
     /// this tuple variation table uses shared number data found at the start
     /// of the serialized data for this glyph variation data or 'cvar' table.
-    const PRIVATE_POINT_NUMBERS: u16 = 0x2000;
+    pub const PRIVATE_POINT_NUMBERS: u16 = 0x2000;
     //0x1000	Reserved	Reserved for future use — set to 0.
     //
     /// Mask for the low 12 bits to give the shared tuple records index.
@@ -51,6 +54,11 @@ impl TupleIndex {
         .unwrap_or_default()
     }
 
+    /// Create a new `TupleIndex` from raw bits.
+    pub fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
     pub fn bits(self) -> u16 {
         self.0
     }
@@ -101,11 +109,16 @@ impl TupleVariationCount {
     ///
     /// These shared numbers are represented as packed point number data at the
     /// start of the serialized data.
-    const SHARED_POINT_NUMBERS: u16 = 0x8000;
+    pub const SHARED_POINT_NUMBERS: u16 = 0x8000;
 
     /// Mask for the low 12 bits to give the shared tuple records index.
     const COUNT_MASK: u16 = 0x0FFF;
 
+    /// Create a new `TupleVariationCount` from raw bits.
+    pub fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
     pub fn bits(self) -> u16 {
         self.0
     }
@@ -171,19 +184,73 @@ impl<'a> TupleVariationHeader<'a> {
         })
     }
 
+    // transcribed from pinot/moscato
+    /// Computes the scalar for this tuple at a given point in design space,
+    /// given its resolved peak tuple.
+    ///
+    /// Returns `None` if this tuple is not applicable at the provided
+    /// coordinates.
+    pub(crate) fn compute_scalar(
+        &self,
+        peak: Tuple,
+        axis_count: u16,
+        coords: &[F2Dot14],
+    ) -> Option<Fixed> {
+        const ZERO: Fixed = Fixed::ZERO;
+        let mut scalar = Fixed::ONE;
+        let inter_start = self.intermediate_start_tuple();
+        let inter_end = self.intermediate_end_tuple();
+        if peak.len() != axis_count as usize {
+            return None;
+        }
+
+        for i in 0..axis_count {
+            let i = i as usize;
+            let coord = coords.get(i).copied().unwrap_or_default().to_fixed();
+            let peak = peak.get(i).unwrap_or_default().to_fixed();
+            if peak == ZERO || peak == coord {
+                continue;
+            }
+
+            if coord == ZERO {
+                return None;
+            }
+
+            if let (Some(inter_start), Some(inter_end)) = (&inter_start, &inter_end) {
+                let start = inter_start.get(i).unwrap_or_default().to_fixed();
+                let end = inter_end.get(i).unwrap_or_default().to_fixed();
+                if coord <= start || coord >= end {
+                    return None;
+                }
+                if coord < peak {
+                    scalar = scalar.mul_div(coord - start, peak - start);
+                } else {
+                    scalar = scalar.mul_div(end - coord, end - peak);
+                }
+            } else {
+                if coord < peak.min(ZERO) || coord > peak.max(ZERO) {
+                    return None;
+                }
+                scalar = scalar.mul_div(coord, peak);
+            }
+        }
+        Some(scalar)
+    }
+
     /// Compute the actual length of this table in bytes
     fn byte_len(&self, axis_count: u16) -> usize {
         const FIXED_LEN: usize = u16::RAW_BYTE_LEN + TupleIndex::RAW_BYTE_LEN;
-        let tuple_byte_len = F2Dot14::RAW_BYTE_LEN * 2 * axis_count as usize;
+        let tuple_byte_len = F2Dot14::RAW_BYTE_LEN * axis_count as usize;
         let index = self.tuple_index();
         FIXED_LEN
             + index
                 .embedded_peak_tuple()
                 .then_some(tuple_byte_len)
                 .unwrap_or_default()
+            // the intermediate region has start and end tuples
             + index
                 .intermediate_region()
-                .then_some(tuple_byte_len)
+                .then_some(tuple_byte_len * 2)
                 .unwrap_or_default()
     }
 }
@@ -216,25 +283,36 @@ impl Default for Tuple<'_> {
 #[derive(Clone, Debug)]
 pub struct PackedPointNumbers<'a> {
     data: FontData<'a>,
+    count: u16,
+    count_bytes: usize,
 }
 
 impl<'a> PackedPointNumbers<'a> {
     /// read point numbers off the front of this data, returning the remaining data
     pub fn split_off_front(data: FontData<'a>) -> (Self, FontData<'a>) {
-        let this = PackedPointNumbers { data };
+        let this = Self::new(data);
         let total_len = this.total_len();
         let remainder = data.split_off(total_len).unwrap_or_default();
         (this, remainder)
     }
 
+    fn new(data: FontData<'a>) -> Self {
+        let (count, count_bytes) = Self::count_and_count_bytes(data);
+        PackedPointNumbers {
+            data,
+            count,
+            count_bytes,
+        }
+    }
+
     /// The number of points in this set
     pub fn count(&self) -> u16 {
-        self.count_and_count_bytes().0
+        self.count
     }
 
     /// compute the count, and the number of bytes used to store it
-    fn count_and_count_bytes(&self) -> (u16, usize) {
-        match self.data.read_at::<u8>(0).unwrap_or(0) {
+    fn count_and_count_bytes(data: FontData) -> (u16, usize) {
+        match data.read_at::<u8>(0).unwrap_or(0) {
             0 => (0, 1),
             count @ 1..=127 => (count as u16, 1),
             _ => {
@@ -242,7 +320,7 @@ impl<'a> PackedPointNumbers<'a> {
                 // The count is read from interpreting the two bytes as a big-endian
                 // uint16 value with the high-order bit masked out."
 
-                let count = self.data.read_at::<u16>(0).unwrap_or_default() & 0x7FFF;
+                let count = data.read_at::<u16>(0).unwrap_or_default() & 0x7FFF;
                 // a weird case where I'm following fonttools: if the 'use words' bit
                 // is set, but the total count is still 0, treat it like 0 first byte
                 if count == 0 {
@@ -256,7 +334,7 @@ impl<'a> PackedPointNumbers<'a> {
 
     /// the number of bytes to encode the packed point numbers
     fn total_len(&self) -> usize {
-        let (n_points, mut n_bytes) = self.count_and_count_bytes();
+        let (n_points, mut n_bytes) = (self.count, self.count_bytes);
         if n_points == 0 {
             return n_bytes;
         }
@@ -265,7 +343,9 @@ impl<'a> PackedPointNumbers<'a> {
 
         let mut n_seen = 0;
         while n_seen < n_points {
-            let Some((count, two_bytes)) = read_control_byte(&mut cursor) else { return n_bytes };
+            let Some((count, two_bytes)) = read_control_byte(&mut cursor) else {
+                return n_bytes;
+            };
             let word_size = 1 + usize::from(two_bytes);
             let run_size = word_size * count as usize;
             n_bytes += run_size + 1; // plus the control byte;
@@ -278,10 +358,17 @@ impl<'a> PackedPointNumbers<'a> {
 
     /// Iterate over the packed points
     pub fn iter(&self) -> PackedPointNumbersIter<'a> {
-        let (count, n_bytes) = self.count_and_count_bytes();
         let mut cursor = self.data.cursor();
-        cursor.advance_by(n_bytes);
-        PackedPointNumbersIter::new(count, cursor)
+        cursor.advance_by(self.count_bytes);
+        PackedPointNumbersIter::new(self.count, cursor)
+    }
+
+    /// Iterate over the packed points, starting at the given index.
+    ///
+    /// Runs preceding the index are summed in bulk rather than being
+    /// decoded one point at a time.
+    pub fn iter_from(&self, index: usize) -> PackedPointNumbersIter<'a> {
+        self.iter().skip_fast(index)
     }
 }
 
@@ -307,6 +394,20 @@ impl<'a> PackedPointNumbersIter<'a> {
             },
         }
     }
+
+    /// Skips up to `n` points, summing the values of whole runs at once.
+    fn skip_fast(mut self, n: usize) -> Self {
+        // if our count is zero, every point is included
+        if self.count == 0 {
+            self.last_val = self.last_val.wrapping_add(n as u16);
+            return self;
+        }
+        let n = n.min((self.count - self.seen) as usize);
+        let (sum, skipped) = self.current_run.skip_fast(n);
+        self.last_val = self.last_val.wrapping_add(sum);
+        self.seen += skipped as u16;
+        self
+    }
 }
 
 /// Implements the logic for iterating over the individual runs
@@ -317,6 +418,51 @@ struct PointRunIter<'a> {
     cursor: Cursor<'a>,
 }
 
+impl PointRunIter<'_> {
+    /// Skips up to `n` values, returning their (wrapping) sum and the
+    /// number of values that were skipped.
+    fn skip_fast(&mut self, n: usize) -> (u16, usize) {
+        let mut sum = 0u16;
+        let mut skipped = 0;
+        while skipped < n {
+            if self.remaining == 0 {
+                let Some(run) = read_control_byte(&mut self.cursor) else {
+                    break;
+                };
+                (self.remaining, self.two_bytes) = run;
+            }
+            let count = (self.remaining as usize).min(n - skipped);
+            let run_sum = if self.two_bytes {
+                self.cursor
+                    .read_array::<BigEndian<u16>>(count)
+                    .ok()
+                    .map(|words| {
+                        words
+                            .iter()
+                            .fold(0u16, |acc, word| acc.wrapping_add(word.get()))
+                    })
+            } else {
+                self.cursor.read_array::<u8>(count).ok().map(|bytes| {
+                    bytes
+                        .iter()
+                        .fold(0u16, |acc, b| acc.wrapping_add(*b as u16))
+                })
+            };
+            let Some(run_sum) = run_sum else {
+                // the data is truncated: leave the cursor at the end so
+                // iteration stops
+                self.cursor.advance_by(self.cursor.remaining_bytes());
+                self.remaining = 0;
+                break;
+            };
+            sum = sum.wrapping_add(run_sum);
+            self.remaining -= count as u8;
+            skipped += count;
+        }
+        (sum, skipped)
+    }
+}
+
 impl Iterator for PointRunIter<'_> {
     type Item = u16;
 
@@ -380,17 +526,26 @@ pub struct PackedDeltas<'a> {
 impl<'a> PackedDeltas<'a> {
     /// NOTE: this is unbounded, and assumes all of data is deltas.
     pub(crate) fn new(data: FontData<'a>) -> Self {
-        let count = DeltaRunIter::new(data.cursor()).count();
+        let count = DeltaRunIter::new(data.cursor()).skip_fast(usize::MAX).1;
         Self { data, count }
     }
 
-    pub(crate) fn count(&self) -> usize {
+    /// The number of deltas in this set.
+    pub fn count(&self) -> usize {
         self.count
     }
 
-    pub(crate) fn iter(&self) -> DeltaRunIter<'a> {
+    /// Iterate over the deltas.
+    pub fn iter(&self) -> DeltaRunIter<'a> {
         DeltaRunIter::new(self.data.cursor())
     }
+
+    /// Iterate over the deltas, starting at the given index.
+    ///
+    /// Runs preceding the index are skipped without decoding their values.
+    pub fn iter_from(&self, index: usize) -> DeltaRunIter<'a> {
+        self.iter().skip_fast(index).0
+    }
 }
 
 /// Implements the logic for iterating over the individual runs
@@ -402,6 +557,14 @@ pub struct DeltaRunIter<'a> {
     cursor: Cursor<'a>,
 }
 
+/// Flag indicating that this run contains no data,
+/// and that the deltas for this run are all zero.
+const DELTAS_ARE_ZERO: u8 = 0x80;
+/// Flag indicating the data type for delta values in the run.
+const DELTAS_ARE_WORDS: u8 = 0x40;
+/// Mask for the low 6 bits to provide the number of delta values in the run, minus one.
+const DELTA_RUN_COUNT_MASK: u8 = 0x3F;
+
 impl<'a> DeltaRunIter<'a> {
     fn new(cursor: Cursor<'a>) -> Self {
         DeltaRunIter {
@@ -411,29 +574,101 @@ impl<'a> DeltaRunIter<'a> {
             cursor,
         }
     }
+
+    /// Reads the control byte for the next run.
+    fn start_run(&mut self) -> Option<()> {
+        let control: u8 = self.cursor.read().ok()?;
+        self.are_zero = (control & DELTAS_ARE_ZERO) != 0;
+        self.two_bytes = (control & DELTAS_ARE_WORDS) != 0;
+        self.remaining = (control & DELTA_RUN_COUNT_MASK) + 1;
+        Some(())
+    }
+
+    /// Skips up to `n` deltas without decoding them, returning the advanced
+    /// iterator and the number of deltas that were skipped.
+    fn skip_fast(mut self, n: usize) -> (Self, usize) {
+        let mut skipped = 0;
+        while skipped < n {
+            if self.remaining == 0 && self.start_run().is_none() {
+                break;
+            }
+            let value_size = match (self.are_zero, self.two_bytes) {
+                (true, _) => 0,
+                (false, true) => 2,
+                (false, false) => 1,
+            };
+            let count = (self.remaining as usize).min(n - skipped);
+            if value_size != 0 && self.cursor.remaining_bytes() < count * value_size {
+                // the data is truncated: skip the values that are present
+                // and leave the cursor at the end so iteration stops
+                skipped += self.cursor.remaining_bytes() / value_size;
+                self.cursor.advance_by(self.cursor.remaining_bytes());
+                self.remaining = 0;
+                break;
+            }
+            self.cursor.advance_by(count * value_size);
+            self.remaining -= count as u8;
+            skipped += count;
+        }
+        (self, skipped)
+    }
+
+    /// Decodes deltas into `dst`, returning the number of deltas written.
+    ///
+    /// This is equivalent to (but faster than) filling `dst` from the
+    /// iterator: runs of zeros and words are decoded in bulk. Fewer than
+    /// `dst.len()` deltas are written if the data runs out.
+    pub fn read_into(&mut self, dst: &mut [i16]) -> usize {
+        let mut written = 0;
+        while written < dst.len() {
+            if self.remaining == 0 {
+                if self.start_run().is_none() {
+                    break;
+                }
+                continue;
+            }
+            let wanted = (self.remaining as usize).min(dst.len() - written);
+            let value_size = if self.two_bytes { 2 } else { 1 };
+            let count = if self.are_zero {
+                wanted
+            } else {
+                wanted.min(self.cursor.remaining_bytes() / value_size)
+            };
+            let out = &mut dst[written..written + count];
+            if self.are_zero {
+                out.fill(0);
+            } else if self.two_bytes {
+                // the length was checked above, so this can't fail
+                if let Ok(words) = self.cursor.read_array::<BigEndian<i16>>(count) {
+                    words.copy_to_native(out);
+                }
+            } else if let Ok(bytes) = self.cursor.read_array::<u8>(count) {
+                for (delta, byte) in out.iter_mut().zip(bytes) {
+                    *delta = *byte as i8 as i16;
+                }
+            }
+            self.remaining -= count as u8;
+            written += count;
+            if count < wanted {
+                // the data is truncated
+                self.remaining = 0;
+                break;
+            }
+        }
+        written
+    }
 }
 
 impl Iterator for DeltaRunIter<'_> {
     type Item = i16;
 
     fn next(&mut self) -> Option<Self::Item> {
-        /// Flag indicating that this run contains no data,
-        /// and that the deltas for this run are all zero.
-        const DELTAS_ARE_ZERO: u8 = 0x80;
-        /// Flag indicating the data type for delta values in the run.
-        const DELTAS_ARE_WORDS: u8 = 0x40;
-        /// Mask for the low 6 bits to provide the number of delta values in the run, minus one.
-        const DELTA_RUN_COUNT_MASK: u8 = 0x3F;
-
         // if no items remain in this run, start the next one.
         // NOTE: we use `while` so we can sanely handle the case where some
         // run in the middle of the data has an explicit zero length
         //TODO: create a font with data of this shape and go crash some font parsers
         while self.remaining == 0 {
-            let control: u8 = self.cursor.read().ok()?;
-            self.are_zero = (control & DELTAS_ARE_ZERO) != 0;
-            self.two_bytes = (control & DELTAS_ARE_WORDS) != 0;
-            self.remaining = (control & DELTA_RUN_COUNT_MASK) + 1;
+            self.start_run()?;
         }
 
         self.remaining -= 1;
@@ -595,12 +830,9 @@ impl<'a> ItemVariationData<'a> {
     pub fn delta_set(&self, inner_index: u16) -> impl Iterator<Item = Fixed> + 'a + Clone {
         let word_delta_count = self.word_delta_count();
         let long_words = word_delta_count & 0x8000 != 0;
-        let (word_size, small_size) = if long_words { (4, 2) } else { (2, 1) };
+        let region_count = self.region_index_count();
+        let offset = Self::row_size(word_delta_count, region_count) * inner_index as usize;
         let word_delta_count = word_delta_count & 0x7FFF;
-        let region_count = self.region_index_count() as usize;
-        let row_size = word_delta_count as usize * word_size
-            + region_count.saturating_sub(word_delta_count as usize) * small_size;
-        let offset = row_size * inner_index as usize;
         ItemDeltas {
             cursor: FontData::new(self.delta_sets())
                 .slice(offset..)
@@ -608,12 +840,32 @@ impl<'a> ItemVariationData<'a> {
                 .cursor(),
             word_delta_count,
             long_words,
-            len: region_count as u16,
+            len: region_count,
             pos: 0,
         }
     }
 }
 
+impl ItemVariationData<'_> {
+    /// The size of each row of deltas, given the packed word delta count.
+    fn row_size(word_delta_count: u16, region_index_count: u16) -> usize {
+        let long_words = word_delta_count & 0x8000 != 0;
+        let (word_size, small_size) = if long_words { (4, 2) } else { (2, 1) };
+        let word_delta_count = (word_delta_count & 0x7FFF) as usize;
+        let region_count = region_index_count as usize;
+        word_delta_count * word_size + region_count.saturating_sub(word_delta_count) * small_size
+    }
+
+    // called from codegen
+    pub(crate) fn delta_sets_len(
+        item_count: u16,
+        word_delta_count: u16,
+        region_index_count: u16,
+    ) -> usize {
+        item_count as usize * Self::row_size(word_delta_count, region_index_count)
+    }
+}
+
 #[derive(Clone)]
 struct ItemDeltas<'a> {
     cursor: Cursor<'a>,
@@ -716,7 +968,7 @@ mod tests {
     fn packed_points() {
         fn decode_points(bytes: &[u8]) -> Option<Vec<u16>> {
             let data = FontData::new(bytes);
-            let packed = PackedPointNumbers { data };
+            let packed = PackedPointNumbers::new(data);
             if packed.count() == 0 {
                 None
             } else {
@@ -750,9 +1002,7 @@ mod tests {
     #[test]
     fn packed_point_byte_len() {
         fn count_bytes(bytes: &[u8]) -> usize {
-            let packed = PackedPointNumbers {
-                data: FontData::new(bytes),
-            };
+            let packed = PackedPointNumbers::new(FontData::new(bytes));
             packed.total_len()
         }
 
@@ -805,6 +1055,69 @@ mod tests {
         assert_eq!(deltas.iter().collect::<Vec<_>>(), EXPECTED);
     }
 
+    #[test]
+    fn packed_deltas_seek() {
+        static INPUT: FontData = FontData::new(&[
+            0x03, 0x0A, 0x97, 0x00, 0xC6, 0x87, 0x41, 0x10, 0x22, 0xFB, 0x34,
+        ]);
+        let deltas = PackedDeltas::new(INPUT);
+        for i in 0..=deltas.count() + 1 {
+            assert_eq!(
+                deltas.iter_from(i).collect::<Vec<_>>(),
+                deltas.iter().skip(i).collect::<Vec<_>>(),
+                "mismatch when seeking to {i}"
+            );
+        }
+    }
+
+    #[test]
+    fn packed_deltas_truncated() {
+        // two words in the run, but only one byte of data
+        let deltas = PackedDeltas::new(FontData::new(&[0x41, 0x01]));
+        assert_eq!(deltas.count(), deltas.iter().count());
+        // three bytes in the run, but only two present
+        let deltas = PackedDeltas::new(FontData::new(&[0x02, 0x01, 0x02]));
+        assert_eq!(deltas.count(), 2);
+        assert_eq!(deltas.iter().collect::<Vec<_>>(), &[1, 2]);
+        assert_eq!(deltas.iter_from(1).collect::<Vec<_>>(), &[2]);
+    }
+
+    #[test]
+    fn packed_deltas_read_into() {
+        static INPUT: FontData = FontData::new(&[
+            0x03, 0x0A, 0x97, 0x00, 0xC6, 0x87, 0x41, 0x10, 0x22, 0xFB, 0x34,
+        ]);
+        let deltas = PackedDeltas::new(INPUT);
+        let expected = deltas.iter().collect::<Vec<_>>();
+        // decoding in pieces of every size matches the iterator
+        for chunk_len in 1..=expected.len() + 1 {
+            let mut iter = deltas.iter();
+            let mut decoded = vec![];
+            let mut buf = vec![0; chunk_len];
+            loop {
+                let n = iter.read_into(&mut buf);
+                decoded.extend_from_slice(&buf[..n]);
+                if n < chunk_len {
+                    break;
+                }
+            }
+            assert_eq!(decoded, expected, "mismatch with chunks of {chunk_len}");
+        }
+        let mut buf = [0; 4];
+        assert_eq!(deltas.iter_from(12).read_into(&mut buf), 2);
+        assert_eq!(buf[..2], [4130, -1228]);
+    }
+
+    #[test]
+    fn packed_deltas_read_into_truncated() {
+        let mut buf = [0; 4];
+        let deltas = PackedDeltas::new(FontData::new(&[0x41, 0x01]));
+        assert_eq!(deltas.iter().read_into(&mut buf), 0);
+        let deltas = PackedDeltas::new(FontData::new(&[0x02, 0x01, 0x02]));
+        assert_eq!(deltas.iter().read_into(&mut buf), 2);
+        assert_eq!(buf[..2], [1, 2]);
+    }
+
     #[test]
     fn packed_point_split() {
         static INPUT: FontData =
@@ -815,4 +1128,33 @@ mod tests {
         assert_eq!(points.total_len(), 4);
         assert_eq!(data.len(), INPUT.len() - 4);
     }
+
+    #[test]
+    fn packed_point_iter_from() {
+        // a run of three bytes, then a run of two words
+        static INPUT: FontData = FontData::new(&[5, 2, 1, 2, 3, 0x81, 0x01, 0x00, 0x00, 0x05]);
+        let (points, _) = PackedPointNumbers::split_off_front(INPUT);
+        assert_eq!(points.count(), 5);
+        let all = points.iter().collect::<Vec<_>>();
+        assert_eq!(all, [1, 3, 6, 262, 267]);
+        for i in 0..=all.len() + 1 {
+            let from = points.iter_from(i).collect::<Vec<_>>();
+            let skipped = points.iter().skip(i).collect::<Vec<_>>();
+            assert_eq!(from, skipped, "{i}");
+        }
+    }
+
+    #[test]
+    fn packed_point_iter_from_all_points() {
+        let (points, _) = PackedPointNumbers::split_off_front(FontData::new(&[0]));
+        assert_eq!(points.count(), 0);
+        assert_eq!(points.iter_from(7).take(3).collect::<Vec<_>>(), [7, 8, 9]);
+    }
+
+    #[test]
+    fn packed_point_iter_from_truncated() {
+        let (points, _) = PackedPointNumbers::split_off_front(FontData::new(&[4, 3, 1, 2]));
+        assert!(points.iter_from(1).eq(points.iter().skip(1)));
+        assert_eq!(points.iter_from(3).next(), None);
+    }
 }
@@ -3,3 +3,56 @@
 pub use super::hmtx::LongMetric;
 
 include!("../../generated/generated_vmtx.rs");
+
+impl<'a> Vmtx<'a> {
+    /// Returns the advance height for the specified glyph identifier.
+    ///
+    /// Glyphs beyond the last long metric share its advance height. Returns
+    /// `None` if the glyph identifier is out of range for this table.
+    pub fn advance(&self, glyph_id: GlyphId) -> Option<u16> {
+        let ix = glyph_id.to_u16() as usize;
+        let metrics = self.v_metrics();
+        if ix >= metrics.len() + self.top_side_bearings().len() {
+            return None;
+        }
+        metrics
+            .get(ix)
+            .or_else(|| metrics.last())
+            .map(LongMetric::advance)
+    }
+
+    /// Returns the top side bearing for the specified glyph identifier.
+    ///
+    /// Returns `None` if the glyph identifier is out of range for this table.
+    pub fn side_bearing(&self, glyph_id: GlyphId) -> Option<i16> {
+        let ix = glyph_id.to_u16() as usize;
+        let metrics = self.v_metrics();
+        match metrics.get(ix) {
+            Some(metric) => Some(metric.side_bearing()),
+            None => self
+                .top_side_bearings()
+                .get(ix - metrics.len())
+                .map(|tsb| tsb.get()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vmtx;
+    use crate::{FontData, FontReadWithArgs};
+    use types::GlyphId;
+
+    #[test]
+    fn advance_and_side_bearing() {
+        // two long metrics and one trailing side bearing
+        let data = FontData::new(&[0, 10, 0, 1, 0, 20, 0, 2, 0, 3]);
+        let vmtx = Vmtx::read_with_args(data, &(2, 3)).unwrap();
+        assert_eq!(vmtx.advance(GlyphId::new(0)), Some(10));
+        assert_eq!(vmtx.advance(GlyphId::new(2)), Some(20));
+        assert_eq!(vmtx.side_bearing(GlyphId::new(1)), Some(2));
+        assert_eq!(vmtx.side_bearing(GlyphId::new(2)), Some(3));
+        assert_eq!(vmtx.advance(GlyphId::new(3)), None);
+        assert_eq!(vmtx.side_bearing(GlyphId::new(3)), None);
+    }
+}
@@ -1,5 +1,7 @@
 use super::*;
 use crate::test_data::gpos as test_data;
+use crate::test_helpers::BeBuffer;
+use crate::{Direction, TableProvider};
 
 #[test]
 fn singleposformat1() {
@@ -232,6 +234,202 @@ fn anchorformat2() {
     assert_eq!(table.anchor_point(), 13);
 }
 
+#[test]
+fn anchor_coordinates() {
+    let anchor = AnchorTable::read(test_data::ANCHORFORMAT1).unwrap();
+    assert_eq!((anchor.x_coordinate(), anchor.y_coordinate()), (189, -103));
+    let anchor = AnchorTable::read(test_data::ANCHORFORMAT2).unwrap();
+    assert_eq!(anchor.x_coordinate(), 322);
+}
+
+#[test]
+fn pair_pos_value_record() {
+    let record = |data, left, right| {
+        PairPos::read(data)
+            .unwrap()
+            .value_record(GlyphId::new(left), GlyphId::new(right))
+            .unwrap()
+            .map(|record| record.x_advance())
+    };
+    let format1 = test_data::PAIRPOSFORMAT1;
+    assert_eq!(record(format1, 0x2D, 0x59), Some(Some(-30)));
+    assert_eq!(record(format1, 0x31, 0x59), Some(Some(-40)));
+    assert_eq!(record(format1, 0x2D, 0x5A), None);
+    assert_eq!(record(format1, 0x2E, 0x59), None);
+
+    let format2 = test_data::PAIRPOSFORMAT2;
+    assert_eq!(record(format2, 0x46, 0x6A), Some(Some(-50)));
+    assert_eq!(record(format2, 0x49, 0x6B), Some(Some(-50)));
+    // covered glyphs always match, if only with class 0 of the second glyph
+    assert_eq!(record(format2, 0x46, 0x10), Some(Some(0)));
+    assert_eq!(record(format2, 0x48, 0x6A), None);
+}
+
+#[test]
+fn pair_pos_value_records() {
+    let record = |data, left, right| {
+        PairPos::read(data)
+            .unwrap()
+            .value_records(GlyphId::new(left), GlyphId::new(right))
+            .unwrap()
+            .map(|(record1, record2)| (record1.x_advance(), record2.x_placement()))
+    };
+    let format1 = test_data::PAIRPOSFORMAT1;
+    assert_eq!(record(format1, 0x2D, 0x59), Some((Some(-30), Some(-20))));
+    assert_eq!(record(format1, 0x31, 0x59), Some((Some(-40), Some(-25))));
+    assert_eq!(record(format1, 0x2D, 0x5A), None);
+    assert_eq!(record(format1, 0x2E, 0x59), None);
+
+    let format2 = test_data::PAIRPOSFORMAT2;
+    assert_eq!(record(format2, 0x46, 0x6A), Some((Some(-50), None)));
+    assert_eq!(record(format2, 0x49, 0x6B), Some((Some(-50), None)));
+    // covered glyphs always match, if only with class 0 of the second glyph
+    assert_eq!(record(format2, 0x46, 0x10), Some((Some(0), None)));
+    assert_eq!(record(format2, 0x48, 0x6A), None);
+}
+
+/// Builds a GPOS table with an empty script list.
+///
+/// Features are a tag and the indices of their lookups; lookups are a
+/// lookup type and the data of their only subtable.
+fn build_gpos(features: &[(&[u8; 4], &[u16])], lookups: &[(u16, &[u8])]) -> BeBuffer {
+    const FEATURE_LIST_OFFSET: usize = 12;
+    let feature_list_header_len = 2 + features.len() * 6;
+    let feature_list_len = feature_list_header_len
+        + features
+            .iter()
+            .map(|(_, indices)| 4 + indices.len() * 2)
+            .sum::<usize>();
+    let lookup_list_offset = FEATURE_LIST_OFFSET + feature_list_len;
+    let mut buf = BeBuffer::new()
+        .extend([1u16, 0, 10, FEATURE_LIST_OFFSET as u16])
+        .push(lookup_list_offset as u16)
+        .push(0u16);
+
+    buf = buf.push(features.len() as u16);
+    let mut offset = feature_list_header_len;
+    for (tag, indices) in features {
+        buf = buf.push(Tag::new(*tag)).push(offset as u16);
+        offset += 4 + indices.len() * 2;
+    }
+    for (_, indices) in features {
+        buf = buf
+            .extend([0u16, indices.len() as u16])
+            .extend(indices.iter().copied());
+    }
+
+    buf = buf.push(lookups.len() as u16);
+    let mut offset = 2 + lookups.len() * 2;
+    for (_, data) in lookups {
+        buf = buf.push(offset as u16);
+        offset += 8 + data.len();
+    }
+    for (lookup_type, data) in lookups {
+        buf = buf
+            .extend([*lookup_type, 0, 1, 8])
+            .extend(data.iter().copied());
+    }
+    buf
+}
+
+/// Wraps a subtable in an extension subtable.
+fn extension(lookup_type: u16, data: &[u8]) -> Vec<u8> {
+    BeBuffer::new()
+        .extend([1u16, lookup_type])
+        .push(8u32)
+        .extend(data.iter().copied())
+        .to_vec()
+}
+
+#[test]
+fn kerning() {
+    let format1 = test_data::PAIRPOSFORMAT1.as_ref();
+    let format2 = test_data::PAIRPOSFORMAT2.as_ref();
+    let extension = extension(2, format2);
+    let lookups: &[(u16, &[u8])] = &[(2, format1), (9, &extension), (2, format2)];
+    let kerning = |features: &[(&[u8; 4], &[u16])], left, right, direction| {
+        let buf = build_gpos(features, lookups);
+        Gpos::read(buf.font_data())
+            .unwrap()
+            .kerning(GlyphId::new(left), GlyphId::new(right), direction)
+            .unwrap()
+    };
+
+    let features: &[(&[u8; 4], &[u16])] = &[(b"kern", &[0, 1]), (b"mark", &[2])];
+    assert_eq!(
+        kerning(features, 0x2D, 0x59, Direction::Horizontal),
+        Some(-30)
+    );
+    // through the extension lookup; the lookup of the other feature is ignored
+    assert_eq!(
+        kerning(features, 0x46, 0x6A, Direction::Horizontal),
+        Some(-50)
+    );
+    // vertical kerning uses the lookups of the `vkrn` feature
+    assert_eq!(kerning(features, 0x46, 0x6A, Direction::Vertical), None);
+    assert_eq!(
+        kerning(features, 0x10, 0x11, Direction::Horizontal),
+        Some(0)
+    );
+
+    // the values of all lookups are summed
+    let features: &[(&[u8; 4], &[u16])] = &[(b"kern", &[2]), (b"kern", &[1])];
+    assert_eq!(
+        kerning(features, 0x46, 0x6A, Direction::Horizontal),
+        Some(-100)
+    );
+
+    let features: &[(&[u8; 4], &[u16])] = &[(b"mark", &[0, 1, 2])];
+    assert_eq!(kerning(features, 0x2D, 0x59, Direction::Horizontal), None);
+
+    // the test data has no vertical adjustments
+    let features: &[(&[u8; 4], &[u16])] = &[(b"vkrn", &[0, 1])];
+    assert_eq!(kerning(features, 0x46, 0x6A, Direction::Vertical), Some(0));
+    assert_eq!(kerning(features, 0x46, 0x6A, Direction::Horizontal), None);
+}
+
+#[test]
+fn kern_falls_back_to_kern_table() {
+    struct Provider {
+        gpos: BeBuffer,
+        kern: BeBuffer,
+    }
+    impl<'a> TableProvider<'a> for &'a Provider {
+        fn data_for_tag(&self, tag: Tag) -> Option<FontData<'a>> {
+            match &tag.into_bytes() {
+                b"GPOS" => Some(self.gpos.font_data()),
+                b"kern" => Some(self.kern.font_data()),
+                _ => None,
+            }
+        }
+    }
+
+    // a single horizontal format 0 subtable, with one pair
+    let kern_table = BeBuffer::new()
+        .extend([0u16, 1, 0, 20, 1])
+        .extend([1u16, 0, 0, 0, 0x2D, 0x59])
+        .push(-5i16);
+    let lookups: &[(u16, &[u8])] = &[(2, test_data::PAIRPOSFORMAT1.as_ref())];
+    let kern = |provider: &Provider, direction| {
+        provider
+            .kern(GlyphId::new(0x2D), GlyphId::new(0x59), direction)
+            .unwrap()
+    };
+
+    let provider = Provider {
+        gpos: build_gpos(&[(b"kern", &[0])], lookups),
+        kern: kern_table.clone(),
+    };
+    assert_eq!(kern(&provider, Direction::Horizontal), -30);
+
+    let provider = Provider {
+        gpos: build_gpos(&[(b"mark", &[0])], lookups),
+        kern: kern_table,
+    };
+    assert_eq!(kern(&provider, Direction::Horizontal), -5);
+    assert_eq!(kern(&provider, Direction::Vertical), 0);
+}
+
 //FIXME: enable when we have device tables working
 //#[test]
 //fn anchorformat3() {
@@ -39,3 +39,102 @@ fn example_3_featurelist_and_feature() {
     assert!(feature.feature_params_offset().is_null());
     assert_eq!(feature.lookup_list_indices().len(), 1);
 }
+
+#[test]
+fn select_script() {
+    let table = ScriptList::read(test_data::SCRIPTS).unwrap();
+    assert_eq!(
+        table.script_tags().collect::<Vec<_>>(),
+        [Tag::new(b"hani"), Tag::new(b"kana"), Tag::new(b"latn")]
+    );
+    let selected = table
+        .select(&[Tag::new(b"cyrl"), Tag::new(b"kana")])
+        .unwrap();
+    assert_eq!(selected.index, 1);
+    assert_eq!(selected.tag, Tag::new(b"kana"));
+    assert!(!selected.is_fallback);
+
+    // no DFLT script, so we fall back to latn
+    let selected = table.select(&[Tag::new(b"cyrl")]).unwrap();
+    assert_eq!(selected.index, 2);
+    assert_eq!(selected.tag, Tag::new(b"latn"));
+    assert!(selected.is_fallback);
+}
+
+#[test]
+fn select_lang_sys() {
+    let table = Script::read(test_data::SCRIPTS_AND_LANGUAGES).unwrap();
+    assert_eq!(
+        table.lang_sys_tags().collect::<Vec<_>>(),
+        [Tag::new(b"URD ")]
+    );
+
+    let urdu = table
+        .select_lang_sys(&[Tag::new(b"FAR "), Tag::new(b"URD ")])
+        .unwrap()
+        .unwrap();
+    assert_eq!(urdu.required_feature(), Some(3));
+    assert_eq!(urdu.all_feature_indices().collect::<Vec<_>>(), [3, 0, 1, 2]);
+
+    let default = table
+        .select_lang_sys(&[Tag::new(b"FAR ")])
+        .unwrap()
+        .unwrap();
+    assert_eq!(default.required_feature(), None);
+    assert_eq!(default.all_feature_indices().collect::<Vec<_>>(), [0, 1, 2]);
+}
+
+#[test]
+fn lang_sys_features() {
+    let script = Script::read(test_data::SCRIPTS_AND_LANGUAGES).unwrap();
+    let feature_list = FeatureList::read(test_data::FEATURELIST_AND_FEATURE).unwrap();
+    let default = script.lang_sys_for_tag(None).unwrap().unwrap();
+    let features = default
+        .features(&feature_list)
+        .map(|feature| feature.map(|f| (f.index, f.tag, f.is_required)))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let liga = Tag::new(b"liga");
+    assert_eq!(
+        features,
+        [(0, liga, false), (1, liga, false), (2, liga, false)]
+    );
+
+    // the urdu required feature index is out of bounds for this feature list
+    let urdu = script
+        .lang_sys_for_tag(Some(Tag::new(b"URD ")))
+        .unwrap()
+        .unwrap();
+    assert!(urdu.features(&feature_list).next().unwrap().is_err());
+}
+
+#[test]
+fn feature_variations() {
+    let table = FeatureVariations::read(test_data::FEATURE_VARIATIONS).unwrap();
+    let feature_list = FeatureList::read(test_data::FEATURELIST_AND_FEATURE).unwrap();
+    let lookups = |coord: f32, feature_index: u16| {
+        let subst = table
+            .find_substitution(&[F2Dot14::from_f32(coord)])
+            .transpose()
+            .unwrap();
+        feature_list
+            .lookup_indices(feature_index, subst.as_ref())
+            .unwrap()
+            .iter()
+            .map(|idx| idx.get())
+            .collect::<Vec<_>>()
+    };
+
+    // first record matches
+    assert_eq!(lookups(0.75, 0), [5]);
+    assert_eq!(lookups(1.0, 1), [0, 1]);
+    // second record always matches
+    assert_eq!(lookups(0.25, 0), [1]);
+    assert_eq!(lookups(0.25, 1), [6, 7]);
+    assert_eq!(lookups(-1.0, 1), [6, 7]);
+
+    // missing coordinates are treated as zero
+    let subst = table.find_substitution(&[]).unwrap().unwrap();
+    assert!(subst.substitute(0).is_none());
+    assert!(subst.substitute(1).is_some());
+}
@@ -161,6 +161,29 @@ pub mod layout {
         0x02,
     ]);
 
+    /// Two records: feature 0 -> lookup 5 when axis 0 is in [0.5, 1.0], and
+    /// (with an empty condition set) feature 1 -> lookups 6, 7 otherwise.
+    #[rustfmt::skip]
+    pub static FEATURE_VARIATIONS: FontData<'static> = FontData::new(&[
+        0x00, 0x01, 0x00, 0x00, // version 1.0
+        0x00, 0x00, 0x00, 0x02, // record count
+        0x00, 0x00, 0x00, 0x18, 0x00, 0x00, 0x00, 0x28, // record 0
+        0x00, 0x00, 0x00, 0x26, 0x00, 0x00, 0x00, 0x3A, // record 1
+        // condition set 0
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x06,
+        0x00, 0x01, 0x00, 0x00, 0x20, 0x00, 0x40, 0x00, // axis 0, 0.5..=1.0
+        // condition set 1
+        0x00, 0x00,
+        // feature table substitution 0
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x01,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, // feature 0
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x05,
+        // feature table substitution 1
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x01,
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x0C, // feature 1
+        0x00, 0x00, 0x00, 0x02, 0x00, 0x06, 0x00, 0x07,
+    ]);
+
     #[rustfmt::skip]
     pub static FEATURELIST_AND_FEATURE: FontData<'static> = FontData::new(&[
         0x00, 0x03, 0x6C, 0x69, 0x67, 0x61, 0x00, 0x14, 0x6C, 0x69, 0x67,
@@ -296,6 +319,42 @@ pub mod gsub {
         0x00, 0xA6, 0x00, 0xB7, 0x00, 0xC3, 0x00, 0xD2, 0x00, 0xE9, 0x00, 0xF1,
         0x00, 0xFC, 0x01, 0x0C, 0x01, 0x19, 0x01, 0x29, 0x01, 0x3A, 0x01, 0x40,
     ]);
+    /// A GSUB table exercising glyph closure:
+    ///
+    /// - 'liga' -> lookup 1, chain context: `[20]` -> lookup 0
+    /// - 'salt' -> lookup 2, single subst: 10 -> 20
+    /// - lookup 0 (only reachable via lookup 1), ligature: 20 21 -> 30
+    #[rustfmt::skip]
+    pub static CLOSURE_GSUB: FontData<'static> = FontData::new(&[
+        0x00, 0x01, 0x00, 0x00, // version 1.0
+        0x00, 0x0A, 0x00, 0x0C, // script list, feature list offsets
+        0x00, 0x26,             // lookup list offset
+        // ScriptList
+        0x00, 0x00,             // script count
+        // FeatureList
+        0x00, 0x02,             // feature count
+        b'l', b'i', b'g', b'a', 0x00, 0x0E,
+        b's', b'a', b'l', b't', 0x00, 0x14,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x01, // 'liga': lookup 1
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x02, // 'salt': lookup 2
+        // LookupList
+        0x00, 0x03, 0x00, 0x08, 0x00, 0x28, 0x00, 0x46,
+        // lookup 0: ligature
+        0x00, 0x04, 0x00, 0x00, 0x00, 0x01, 0x00, 0x08,
+        0x00, 0x01, 0x00, 0x08, 0x00, 0x01, 0x00, 0x0E,
+        0x00, 0x01, 0x00, 0x01, 0x00, 0x14, // coverage: [20]
+        0x00, 0x01, 0x00, 0x04,             // ligature set
+        0x00, 0x1E, 0x00, 0x02, 0x00, 0x15, // 20 21 -> 30
+        // lookup 1: chained context format 3
+        0x00, 0x06, 0x00, 0x00, 0x00, 0x01, 0x00, 0x08,
+        0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x10,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x01, 0x00, 0x01, 0x00, 0x14, // coverage: [20]
+        // lookup 2: single subst format 1
+        0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x08,
+        0x00, 0x01, 0x00, 0x06, 0x00, 0x0A, // delta 10
+        0x00, 0x01, 0x00, 0x01, 0x00, 0x0A, // coverage: [10]
+    ]);
 }
 
 pub mod post {
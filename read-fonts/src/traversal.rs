@@ -11,8 +11,8 @@
 use std::{fmt::Debug, ops::Deref};
 
 use types::{
-    BigEndian, F2Dot14, FWord, Fixed, GlyphId, LongDateTime, MajorMinor, Nullable, Offset16,
-    Offset24, Offset32, Scalar, Tag, UfWord, Uint24, Version16Dot16,
+    BigEndian, F2Dot14, FWord, Fixed, GlyphId, GlyphId24, Int24, LongDateTime, MajorMinor, NameId,
+    Nullable, Offset16, Offset24, Offset32, Scalar, Tag, UfWord, Uint24, Version16Dot16,
 };
 
 use crate::{
@@ -31,6 +31,7 @@ pub enum FieldType<'a> {
     U16(u16),
     I32(i32),
     U32(u32),
+    I24(Int24),
     U24(Uint24),
     Tag(Tag),
     FWord(FWord),
@@ -41,6 +42,8 @@ pub enum FieldType<'a> {
     Fixed(Fixed),
     LongDateTime(LongDateTime),
     GlyphId(GlyphId),
+    GlyphId24(GlyphId24),
+    NameId(NameId),
     BareOffset(OffsetType),
     ResolvedOffset(ResolvedOffset<'a>),
     /// Used in tables like name/post so we can actually print the strings
@@ -519,6 +522,7 @@ impl<'a> Debug for FieldType<'a> {
             Self::U16(arg0) => arg0.fmt(f),
             Self::I32(arg0) => arg0.fmt(f),
             Self::U32(arg0) => arg0.fmt(f),
+            Self::I24(arg0) => arg0.fmt(f),
             Self::U24(arg0) => arg0.fmt(f),
             Self::Tag(arg0) => arg0.fmt(f),
             Self::FWord(arg0) => arg0.to_i16().fmt(f),
@@ -532,6 +536,11 @@ impl<'a> Debug for FieldType<'a> {
                 write!(f, "g")?;
                 arg0.to_u16().fmt(f)
             }
+            Self::GlyphId24(arg0) => {
+                write!(f, "g")?;
+                arg0.to_u32().fmt(f)
+            }
+            Self::NameId(arg0) => arg0.fmt(f),
             Self::StringOffset(string) => match &string.target {
                 Ok(arg0) => arg0.as_ref().fmt(f),
                 Err(_) => string.target.fmt(f),
@@ -649,6 +658,12 @@ impl<'a> From<i32> for FieldType<'a> {
     }
 }
 
+impl<'a> From<Int24> for FieldType<'a> {
+    fn from(src: Int24) -> FieldType<'a> {
+        FieldType::I24(src)
+    }
+}
+
 impl<'a> From<Uint24> for FieldType<'a> {
     fn from(src: Uint24) -> FieldType<'a> {
         FieldType::U24(src)
@@ -709,6 +724,18 @@ impl<'a> From<GlyphId> for FieldType<'a> {
     }
 }
 
+impl<'a> From<NameId> for FieldType<'a> {
+    fn from(src: NameId) -> FieldType<'a> {
+        FieldType::NameId(src)
+    }
+}
+
+impl<'a> From<GlyphId24> for FieldType<'a> {
+    fn from(src: GlyphId24) -> FieldType<'a> {
+        FieldType::GlyphId24(src)
+    }
+}
+
 impl<'a> From<RecordResolver<'a>> for FieldType<'a> {
     fn from(src: RecordResolver<'a>) -> Self {
         FieldType::Record(src)
@@ -0,0 +1,149 @@
+#![parse_module(read_fonts::tables::aat)]
+
+/// An [AAT lookup table](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6Tables.html)
+/// with 16-bit values, mapping glyph ids to values.
+format u16 Lookup {
+    Format0(Lookup0),
+    Format2(Lookup2),
+    Format4(Lookup4),
+    Format6(Lookup6),
+    Format8(Lookup8),
+    Format10(Lookup10),
+}
+
+/// Simple array format; the lookup data is an array of values indexed by
+/// glyph id.
+table Lookup0 {
+    /// Format number is set to 0.
+    #[format = 0]
+    format: u16,
+    /// Values indexed by glyph id.
+    #[count(..)]
+    values: [u16],
+}
+
+/// Segment single format; each segment maps a range of glyphs to a single
+/// value.
+table Lookup2 {
+    /// Format number is set to 2.
+    #[format = 2]
+    format: u16,
+    /// Size of a lookup unit for this search in bytes.
+    unit_size: u16,
+    /// Number of units of the preceding size to be searched.
+    n_units: u16,
+    /// The value of `unit_size` times the largest power of 2 that is less
+    /// than or equal to `n_units`.
+    search_range: u16,
+    /// The log base 2 of the largest power of 2 less than or equal to
+    /// `n_units`.
+    entry_selector: u16,
+    /// The value of `unit_size` times the difference of `n_units` minus
+    /// the largest power of 2 less than or equal to `n_units`.
+    range_shift: u16,
+    /// Segments, sorted by last glyph.
+    #[count($n_units)]
+    segments: [LookupSegment],
+}
+
+/// Segment array format; each segment maps a range of glyphs to an array of
+/// values.
+table Lookup4 {
+    /// Format number is set to 4.
+    #[format = 4]
+    format: u16,
+    /// Size of a lookup unit for this search in bytes.
+    unit_size: u16,
+    /// Number of units of the preceding size to be searched.
+    n_units: u16,
+    /// The value of `unit_size` times the largest power of 2 that is less
+    /// than or equal to `n_units`.
+    search_range: u16,
+    /// The log base 2 of the largest power of 2 less than or equal to
+    /// `n_units`.
+    entry_selector: u16,
+    /// The value of `unit_size` times the difference of `n_units` minus
+    /// the largest power of 2 less than or equal to `n_units`.
+    range_shift: u16,
+    /// Segments, sorted by last glyph. The value of each segment is an
+    /// offset from the start of the lookup table to an array of values.
+    #[count($n_units)]
+    segments: [LookupSegment],
+}
+
+/// A segment in a format 2 or format 4 lookup table.
+record LookupSegment {
+    /// Last glyph index in this segment.
+    last_glyph: u16,
+    /// First glyph index in this segment.
+    first_glyph: u16,
+    /// The lookup value (format 2) or an offset to the lookup values
+    /// (format 4).
+    value: u16,
+}
+
+/// Single table format; the lookup data is a sorted list of glyph and
+/// value pairs.
+table Lookup6 {
+    /// Format number is set to 6.
+    #[format = 6]
+    format: u16,
+    /// Size of a lookup unit for this search in bytes.
+    unit_size: u16,
+    /// Number of units of the preceding size to be searched.
+    n_units: u16,
+    /// The value of `unit_size` times the largest power of 2 that is less
+    /// than or equal to `n_units`.
+    search_range: u16,
+    /// The log base 2 of the largest power of 2 less than or equal to
+    /// `n_units`.
+    entry_selector: u16,
+    /// The value of `unit_size` times the difference of `n_units` minus
+    /// the largest power of 2 less than or equal to `n_units`.
+    range_shift: u16,
+    /// Entries, sorted by glyph.
+    #[count($n_units)]
+    entries: [LookupSingle],
+}
+
+/// An entry in a format 6 lookup table.
+record LookupSingle {
+    /// The glyph index.
+    glyph: u16,
+    /// The lookup value.
+    value: u16,
+}
+
+/// Trimmed array format; the lookup data is an array of values for a
+/// contiguous range of glyphs.
+table Lookup8 {
+    /// Format number is set to 8.
+    #[format = 8]
+    format: u16,
+    /// First glyph index included in the trimmed array.
+    first_glyph: u16,
+    /// Total number of glyphs (equivalent to the last glyph minus the value
+    /// of `first_glyph` plus 1).
+    glyph_count: u16,
+    /// The lookup values, indexed by glyph index minus `first_glyph`.
+    #[count($glyph_count)]
+    values: [u16],
+}
+
+/// Extended trimmed array format; like format 8, but with values of
+/// arbitrary size.
+table Lookup10 {
+    /// Format number is set to 10.
+    #[format = 10]
+    format: u16,
+    /// Size of a lookup value in bytes.
+    unit_size: u16,
+    /// First glyph index included in the trimmed array.
+    first_glyph: u16,
+    /// Total number of glyphs (equivalent to the last glyph minus the value
+    /// of `first_glyph` plus 1).
+    glyph_count: u16,
+    /// The raw lookup values, each `unit_size` bytes long.
+    #[count(..)]
+    values: [u8],
+}
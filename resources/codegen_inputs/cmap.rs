@@ -67,7 +67,7 @@ table Cmap2 {
     #[format = 2]
     format: u16,
     /// This is the length in bytes of the subtable.
-    #[compile(panic!("not implemented"))]
+    #[compile(self.compute_length())]
     length: u16,
     /// For requirements on use of the language field, see “Use of
     /// the language field in 'cmap' subtables” in this document.
@@ -76,15 +76,13 @@ table Cmap2 {
     /// index × 8.
     #[count(256)]
     sub_header_keys: [u16],
-
-    //FIXME: these two fields will require some custom handling
-    ///// Variable-length array of SubHeader records.
-    //#[count( )]
-    //sub_headers: [SubHeader],
-    ///// Variable-length array containing subarrays used for mapping the
-    ///// low byte of 2-byte characters.
-    //#[count( )]
-    //glyph_id_array: [u16],
+    /// The variable-length array of SubHeader records, followed by the
+    /// subarrays used for mapping the low byte of 2-byte characters.
+    ///
+    /// These are kept as raw bytes, since their lengths are only known
+    /// by inspecting the subHeaderKeys and SubHeader records.
+    #[count(subtract($length, 518))]
+    sub_header_data: [u8],
 }
 
 
@@ -106,27 +104,33 @@ table Cmap4 {
     #[format = 4]
     format: u16,
     /// This is the length in bytes of the subtable.
+    #[compile(self.compute_length())]
     length: u16,
     /// For requirements on use of the language field, see “Use of
     /// the language field in 'cmap' subtables” in this document.
     language: u16,
     /// 2 × segCount.
+    #[compile(self.compute_seg_count_x2())]
     seg_count_x2: u16,
     /// Maximum power of 2 less than or equal to segCount, times 2
     /// ((2**floor(log2(segCount))) * 2, where “**” is an
     /// exponentiation operator)
+    #[compile(self.compute_search_range())]
     search_range: u16,
     /// Log2 of the maximum power of 2 less than or equal to numTables
     /// (log2(searchRange/2), which is equal to floor(log2(segCount)))
+    #[compile(self.compute_entry_selector())]
     entry_selector: u16,
     /// segCount times 2, minus searchRange ((segCount * 2) -
     /// searchRange)
+    #[compile(self.compute_range_shift())]
     range_shift: u16,
     /// End characterCode for each segment, last=0xFFFF.
     #[count(half($seg_count_x2))]
     end_code: [u16],
     /// Set to 0.
     #[skip_getter]
+    #[compile(0)]
     reserved_pad: u16,
     /// Start character code for each segment.
     #[count(half($seg_count_x2))]
@@ -168,6 +172,7 @@ table Cmap8 {
     format: u16,
     /// Reserved; set to 0
     #[skip_getter]
+    #[compile(0)]
     reserved: u16,
     /// Byte length of this subtable (including the header)
     length: u32,
@@ -207,6 +212,7 @@ table Cmap10 {
     format: u16,
     /// Reserved; set to 0
     #[skip_getter]
+    #[compile(0)]
     reserved: u16,
     /// Byte length of this subtable (including the header)
     length: u32,
@@ -229,13 +235,16 @@ table Cmap12 {
     format: u16,
     /// Reserved; set to 0
     #[skip_getter]
+    #[compile(0)]
     reserved: u16,
     /// Byte length of this subtable (including the header)
+    #[compile(self.compute_length())]
     length: u32,
     /// For requirements on use of the language field, see “Use of
     /// the language field in 'cmap' subtables” in this document.
     language: u32,
     /// Number of groupings which follow
+    #[compile(array_len($groups))]
     num_groups: u32,
     /// Array of SequentialMapGroup records.
     #[count($num_groups)]
@@ -249,6 +258,7 @@ table Cmap13 {
     format: u16,
     /// Reserved; set to 0
     #[skip_getter]
+    #[compile(0)]
     reserved: u16,
     /// Byte length of this subtable (including the header)
     length: u32,
@@ -279,8 +289,10 @@ table Cmap14 {
     #[format = 14]
     format: u16,
     /// Byte length of this subtable (including this header)
+    #[compile(self.compute_length())]
     length: u32,
     /// Number of variation Selector Records
+    #[compile(array_len($var_selector))]
     num_var_selector_records: u32,
     /// Array of VariationSelector records.
     #[count($num_var_selector_records)]
@@ -304,6 +316,7 @@ record VariationSelector {
 /// [Default UVS table](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap#default-uvs-table)
 table DefaultUvs {
     /// Number of Unicode character ranges.
+    #[compile(array_len($ranges))]
     num_unicode_value_ranges: u32,
     /// Array of UnicodeRange records.
     #[count($num_unicode_value_ranges)]
@@ -312,6 +325,7 @@ table DefaultUvs {
 
 /// [Non-Default UVS table](https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#non-default-uvs-table)
 table NonDefaultUvs {
+    #[compile(array_len($uvs_mapping))]
     num_uvs_mappings: u32,
     #[count($num_uvs_mappings)]
     uvs_mapping: [UvsMapping]
@@ -6,8 +6,10 @@
 table Colr {
     /// Table version number - set to 0 or 1.
     #[version]
+    #[compile(self.compute_version())]
     version: u16,
     /// Number of BaseGlyph records; may be 0 in a version 1 table.
+    #[compile(self.compute_num_base_glyph_records())]
     num_base_glyph_records: u16,
     /// Offset to baseGlyphRecords array (may be NULL).
     #[nullable]
@@ -18,6 +20,7 @@ table Colr {
     #[read_offset_with($num_layer_records)]
     layer_records_offset: Offset32<[Layer]>,
     /// Number of Layer records; may be 0 in a version 1 table.
+    #[compile(self.compute_num_layer_records())]
     num_layer_records: u16,
     /// Offset to BaseGlyphList table.
     #[since_version(1)]
@@ -61,6 +64,7 @@ record Layer {
 
 /// [BaseGlyphList](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#baseglyphlist-layerlist-and-cliplist) table
 table BaseGlyphList {
+    #[compile(array_len($base_glyph_paint_records))]
     num_base_glyph_paint_records: u32,
     #[count($num_base_glyph_paint_records)]
     base_glyph_paint_records: [BaseGlyphPaint],
@@ -76,6 +80,7 @@ record BaseGlyphPaint {
 
 /// [LayerList](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#baseglyphlist-layerlist-and-cliplist) table
 table LayerList {
+    #[compile(array_len($paint_offsets))]
     num_layers: u32,
     /// Offsets to Paint tables.
     #[count($num_layers)]
@@ -85,8 +90,10 @@ table LayerList {
 /// [ClipList](https://learn.microsoft.com/en-us/typography/opentype/spec/colr#baseglyphlist-layerlist-and-cliplist) table
 table ClipList {
     /// Set to 1.
+    #[compile(1)]
     format: u8,
     /// Number of Clip records.
+    #[compile(array_len($clips))]
     num_clips: u32,
     /// Clip records. Sorted by startGlyphID.
     #[count($num_clips)]
@@ -186,6 +193,7 @@ table ColorLine {
     /// An Extend enum value.
     extend: Extend,
     /// Number of ColorStop records.
+    #[compile(array_len($color_stops))]
     num_stops: u16,
     #[count($num_stops)]
     color_stops: [ColorStop],
@@ -196,6 +204,7 @@ table VarColorLine {
     /// An Extend enum value.
     extend: Extend,
     /// Number of ColorStop records.
+    #[compile(array_len($color_stops))]
     num_stops: u16,
     /// Allows for variations.
     #[count($num_stops)]
@@ -0,0 +1,44 @@
+#![parse_module(read_fonts::tables::feat)]
+
+/// The Graphite [Feat (Feature)](https://github.com/silnrsi/graphite/blob/master/doc/table_feat.txt) table
+#[tag = "Feat"]
+table Feat {
+    /// Table version; this crate supports version 2.0 and later.
+    version: Version16Dot16,
+    /// Number of features.
+    num_feat: u16,
+    #[skip_getter]
+    _reserved1: u16,
+    #[skip_getter]
+    _reserved2: u32,
+    /// Array of feature definitions, sorted by feature id.
+    #[count($num_feat)]
+    features: [FeatureDefn],
+}
+
+/// A feature definition in the [Feat] table.
+record FeatureDefn {
+    /// The feature id; often a four-character tag.
+    id: u32,
+    /// Number of settings for this feature.
+    num_settings: u16,
+    #[skip_getter]
+    _reserved: u16,
+    /// Offset in bytes from the beginning of the Feat table to the
+    /// array of settings for this feature.
+    #[read_offset_with($num_settings)]
+    settings_offset: Offset32<[FeatureSettingDefn]>,
+    /// Feature flags; bit 15 indicates the feature is hidden from the
+    /// user interface.
+    flags: u16,
+    /// The name id of the feature label in the `name` table.
+    label: u16,
+}
+
+/// A setting of a feature in the [Feat] table.
+record FeatureSettingDefn {
+    /// The value of the setting.
+    value: i16,
+    /// The name id of the setting label in the `name` table.
+    label: u16,
+}
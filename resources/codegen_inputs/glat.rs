@@ -0,0 +1,50 @@
+#![parse_module(read_fonts::tables::glat)]
+
+/// The Graphite [Glat (Glyph Attributes)](https://github.com/silnrsi/graphite/blob/master/doc/table_glat_gloc.txt) table
+#[tag = "Glat"]
+table Glat {
+    /// Table version; one of 1.0, 2.0 or 3.0.
+    ///
+    /// Version 3.0 tables are followed by a 32-bit word containing the
+    /// compression scheme and flags; see [Glat::compression] and
+    /// [Glat::has_octaboxes].
+    version: Version16Dot16,
+}
+
+/// The bounding octabox of a glyph in a version 3.0 [Glat] table.
+///
+/// This is followed by one [SubBox] for each bit set in `bitmap`.
+record GlyphBox {
+    /// A 4x4 bitmap indicating which cells of the glyph's bounding box
+    /// contain sub-boxes.
+    bitmap: u16,
+    /// Minimum of the negative diagonal, scaled to 0..255 of the bounding box.
+    diag_neg_min: u8,
+    /// Maximum of the negative diagonal, scaled to 0..255 of the bounding box.
+    diag_neg_max: u8,
+    /// Minimum of the positive diagonal, scaled to 0..255 of the bounding box.
+    diag_pos_min: u8,
+    /// Maximum of the positive diagonal, scaled to 0..255 of the bounding box.
+    diag_pos_max: u8,
+}
+
+/// A sub-box of a [GlyphBox], with all values scaled to 0..255 of the
+/// glyph's bounding box.
+record SubBox {
+    /// Left edge.
+    left: u8,
+    /// Right edge.
+    right: u8,
+    /// Bottom edge.
+    bottom: u8,
+    /// Top edge.
+    top: u8,
+    /// Minimum of the positive diagonal.
+    diag_pos_min: u8,
+    /// Maximum of the positive diagonal.
+    diag_pos_max: u8,
+    /// Minimum of the negative diagonal.
+    diag_neg_min: u8,
+    /// Maximum of the negative diagonal.
+    diag_neg_max: u8,
+}
@@ -0,0 +1,24 @@
+#![parse_module(read_fonts::tables::gloc)]
+
+/// The Graphite [Gloc (Glyph Locator)](https://github.com/silnrsi/graphite/blob/master/doc/table_glat_gloc.txt) table
+#[tag = "Gloc"]
+table Gloc {
+    /// Table version; set to 1.0.
+    version: Version16Dot16,
+    /// Flags describing the format of the location array.
+    flags: GlocFlags,
+    /// The number of attributes per glyph.
+    num_attribs: u16,
+    /// The glyph locations followed by the optional attribute ids; see
+    /// [Gloc::locations] and [Gloc::attrib_ids].
+    #[count(..)]
+    location_data: [u8],
+}
+
+/// Flags in the [Gloc] table.
+flags u16 GlocFlags {
+    /// Locations are 32-bit values; otherwise they are 16-bit.
+    LONG_FORMAT = 0x0001,
+    /// The table ends with an array of attribute ids.
+    ATTRIB_IDS = 0x0002,
+}
@@ -0,0 +1,56 @@
+#![parse_module(read_fonts::tables::kern)]
+
+/// The [kern (Kerning)](https://learn.microsoft.com/en-us/typography/opentype/spec/kern) table
+#[tag = "kern"]
+table Kern {
+    /// Table version number; set to 0.
+    version: u16,
+    /// Number of subtables in the kerning table.
+    n_tables: u16,
+    /// The kerning subtables.
+    #[count(..)]
+    subtables: VarLenArray<KernSubtable<'a>>,
+}
+
+/// A [kerning subtable](https://learn.microsoft.com/en-us/typography/opentype/spec/kern#kerning-subtables), with its header.
+record KernSubtable<'a> {
+    /// Kern subtable version number; set to 0.
+    version: u16,
+    /// Length of the subtable, in bytes (including this header).
+    length: u16,
+    /// The format of the subtable in the high byte, and what type of
+    /// information it contains in the low byte.
+    coverage: u16,
+    /// The format-specific subtable data.
+    #[count(..)]
+    data: [u8],
+}
+
+/// [Format 0](https://learn.microsoft.com/en-us/typography/opentype/spec/kern#format-0) kerning subtable: ordered list of kerning pairs
+table Kern0 {
+    /// This gives the number of kerning pairs in the table.
+    n_pairs: u16,
+    /// The largest power of two less than or equal to the value of
+    /// nPairs, multiplied by the size in bytes of an entry in the table.
+    search_range: u16,
+    /// This is calculated as log2 of the largest power of two less
+    /// than or equal to the value of nPairs.
+    entry_selector: u16,
+    /// The value of nPairs minus the largest power of two less than or
+    /// equal to nPairs, and then multiplied by the size in bytes of an
+    /// entry in the table.
+    range_shift: u16,
+    /// The kerning pairs, sorted by left and then right glyph.
+    #[count($n_pairs)]
+    pairs: [KernPair],
+}
+
+/// A kerning pair in a [format 0](https://learn.microsoft.com/en-us/typography/opentype/spec/kern#format-0) subtable
+record KernPair {
+    /// The glyph index for the left-hand glyph in the kerning pair.
+    left: GlyphId,
+    /// The glyph index for the right-hand glyph in the kerning pair.
+    right: GlyphId,
+    /// The kerning value for the above pair, in font design units.
+    value: FWord,
+}
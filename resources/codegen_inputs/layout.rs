@@ -79,6 +79,7 @@ record FeatureRecord {
     feature_tag: Tag,
     /// Offset to Feature table, from beginning of FeatureList
     #[read_offset_with($feature_tag)]
+    #[validate(validate_feature_params)]
     feature_offset: Offset16<Feature>,
 }
 
@@ -143,6 +144,7 @@ table CoverageFormat1 {
     glyph_count: u16,
     /// Array of glyph IDs — in numerical order
     #[count($glyph_count)]
+    #[validate(validate_glyph_array)]
     glyph_array: [GlyphId],
 }
 
@@ -156,6 +158,7 @@ table CoverageFormat2 {
     range_count: u16,
     /// Array of glyph ranges — ordered by startGlyphID.
     #[count($range_count)]
+    #[validate(validate_range_records)]
     range_records: [RangeRecord],
 }
 
@@ -200,6 +203,7 @@ table ClassDefFormat2 {
     class_range_count: u16,
     /// Array of ClassRangeRecords — ordered by startGlyphID
     #[count($class_range_count)]
+    #[validate(validate_class_range_records)]
     class_range_records: [ClassRangeRecord],
 }
 
@@ -0,0 +1,26 @@
+#![parse_module(read_fonts::tables::lcar)]
+
+/// The [lcar (Ligature Caret)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6lcar.html) table
+#[tag = "lcar"]
+table Lcar {
+    /// Version number of the ligature caret table; set to 1.0.
+    version: MajorMinor,
+    /// Format of the ligature caret table; 0 for distance-based carets and
+    /// 1 for control point indices.
+    format: u16,
+    /// A lookup table mapping glyphs to offsets from the start of the table
+    /// to a [LigCaretClassEntry]; see [Lcar::lookup].
+    #[skip_getter]
+    #[count(..)]
+    lookup_data: [u8],
+}
+
+/// The ligature carets for a single glyph in the [Lcar] table.
+table LigCaretClassEntry {
+    /// Number of caret positions in this ligature.
+    count: u16,
+    /// The caret positions, as distances in font units if the table format
+    /// is 0, or as control point indices if the table format is 1.
+    #[count($count)]
+    partials: [i16],
+}
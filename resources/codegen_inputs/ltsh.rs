@@ -0,0 +1,14 @@
+#![parse_module(read_fonts::tables::ltsh)]
+
+/// The [LTSH (Linear Threshold)](https://learn.microsoft.com/en-us/typography/opentype/spec/ltsh) table
+#[tag = "LTSH"]
+table Ltsh {
+    /// Table version; set to 0.
+    version: u16,
+    /// Number of glyphs; should match the `maxp` table.
+    num_glyphs: u16,
+    /// The vertical pel height at which each glyph can be assumed to scale
+    /// linearly; 1 if the glyph always scales linearly.
+    #[count($num_glyphs)]
+    y_pels: [u8],
+}
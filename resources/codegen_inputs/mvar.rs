@@ -5,13 +5,17 @@
 table Mvar {
     /// Major version number of the horizontal metrics variations table — set to 1.
     /// Minor version number of the horizontal metrics variations table — set to 0.
+    #[compile(MajorMinor::VERSION_1_0)]
     version: MajorMinor,
     /// Not used; set to 0.
     #[skip_getter]
+    #[compile(0)]
     _reserved: u16,
     /// The size in bytes of each value record — must be greater than zero.
+    #[compile(8)]
     value_record_size: u16,
     /// The number of value records — may be zero.
+    #[compile(array_len($value_records))]
     value_record_count: u16,
     /// Offset in bytes from the start of this table to the item variation store table. If valueRecordCount is zero, set to zero; if valueRecordCount is greater than zero, must be greater than zero.
     #[nullable]
@@ -0,0 +1,28 @@
+#![parse_module(read_fonts::tables::opbd)]
+
+/// The [opbd (Optical Bounds)](https://developer.apple.com/fonts/TrueType-Reference-Manual/RM06/Chap6opbd.html) table
+#[tag = "opbd"]
+table Opbd {
+    /// Version number of the optical bounds table; set to 1.0.
+    version: MajorMinor,
+    /// Format of the optical bounds table; 0 for distances in font units and
+    /// 1 for control point indices.
+    format: u16,
+    /// A lookup table mapping glyphs to offsets from the start of the table
+    /// to an [OpticalBounds] record; see [Opbd::lookup].
+    #[skip_getter]
+    #[count(..)]
+    lookup_data: [u8],
+}
+
+/// The optical bounds of a glyph in the [Opbd] table.
+record OpticalBounds {
+    /// Left side optical bound.
+    left: i16,
+    /// Top side optical bound.
+    top: i16,
+    /// Right side optical bound.
+    right: i16,
+    /// Bottom side optical bound.
+    bottom: i16,
+}
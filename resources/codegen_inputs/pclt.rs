@@ -0,0 +1,39 @@
+#![parse_module(read_fonts::tables::pclt)]
+
+/// The [PCLT (PCL 5)](https://learn.microsoft.com/en-us/typography/opentype/spec/pclt) table
+#[tag = "PCLT"]
+table Pclt {
+    /// Table version; set to 1.0.
+    version: Version16Dot16,
+    /// Unique identifier for the font, assigned by the font vendor.
+    font_number: u32,
+    /// Width of the space in FUnits.
+    pitch: u16,
+    /// Height of the optical x-height in FUnits.
+    x_height: u16,
+    /// Style word, encoding posture, appearance width and structure.
+    style: u16,
+    /// Type family and vendor code.
+    type_family: u16,
+    /// Height of the optical cap height in FUnits.
+    cap_height: u16,
+    /// PCL symbol set value.
+    symbol_set: u16,
+    /// The typeface name, as ASCII padded with spaces or nulls.
+    #[count(16)]
+    typeface: [u8],
+    /// Bit field identifying the symbol collections provided by the font.
+    #[count(8)]
+    character_complement: [u8],
+    /// A six-character ASCII name for the font file.
+    #[count(6)]
+    file_name: [u8],
+    /// The stroke weight, from -7 (ultra thin) to 7 (ultra black).
+    stroke_weight: i8,
+    /// The width type, from -5 (ultra compressed) to 5 (ultra expanded).
+    width_type: i8,
+    /// The serif style, encoding serif type in the bottom six bits.
+    serif_style: u8,
+    #[skip_getter]
+    _reserved: u8,
+}
@@ -0,0 +1,148 @@
+#![parse_module(read_fonts::tables::silf)]
+
+/// A subtable of the [Silf] table, containing the rules for a single
+/// writing system.
+table SilfSubtable {
+    /// The version of the rules in this subtable.
+    rule_version: Version16Dot16,
+    /// Offset in bytes from the start of the subtable to the pass offsets.
+    pass_offset: u16,
+    /// Offset in bytes from the start of the subtable to the pseudo glyph
+    /// map.
+    pseudos_offset: u16,
+    /// The largest glyph id (including pseudo glyphs) used in the rules.
+    max_glyph_id: u16,
+    /// Extra ascent to add to the font ascent.
+    extra_ascent: i16,
+    /// Extra descent to add to the font descent.
+    extra_descent: i16,
+    /// Number of rendering passes.
+    num_passes: u8,
+    /// Index of the first substitution pass.
+    i_subst: u8,
+    /// Index of the first positioning pass.
+    i_pos: u8,
+    /// Index of the first justification pass.
+    i_just: u8,
+    /// Index of the pass before which bidi processing occurs, or 0xFF if
+    /// there is none.
+    i_bidi: u8,
+    /// Subtable flags.
+    flags: u8,
+    /// Maximum number of glyphs of pre-context in any rule.
+    max_pre_context: u8,
+    /// Maximum number of glyphs of post-context in any rule.
+    max_post_context: u8,
+    /// Glyph attribute number of the actual glyph id of a pseudo glyph.
+    attr_pseudo: u8,
+    /// Glyph attribute number of the breakweight attribute.
+    attr_break_weight: u8,
+    /// Glyph attribute number of the directionality attribute.
+    attr_directionality: u8,
+    /// Glyph attribute number of the first mirroring attribute.
+    attr_mirroring: u8,
+    /// Glyph attribute number of the first skip passes attribute.
+    attr_skip_passes: u8,
+    /// Number of justification levels.
+    num_j_levels: u8,
+    /// The justification levels.
+    #[count($num_j_levels)]
+    j_levels: [JustificationLevel],
+    /// Glyph attribute number of the first ligature component attribute.
+    num_lig_comp: u16,
+    /// Number of user defined slot attributes.
+    num_user_defn: u8,
+    /// Maximum number of components per ligature.
+    max_comp_per_lig: u8,
+    /// The default direction of the writing system.
+    direction: u8,
+    /// Glyph attribute number of the first collision attribute.
+    attr_collisions: u8,
+    #[skip_getter]
+    _reserved1: Uint24,
+    /// Number of critical features.
+    num_crit_features: u8,
+    /// Ids of the critical features.
+    #[count($num_crit_features)]
+    crit_features: [u16],
+    #[skip_getter]
+    _reserved2: u8,
+    /// Number of scripts supported by this subtable.
+    num_script_tag: u8,
+    /// The scripts supported by this subtable.
+    #[count($num_script_tag)]
+    script_tags: [Tag],
+    /// The glyph id of the line-break pseudo glyph.
+    lb_gid: GlyphId,
+    /// Offsets in bytes from the start of the subtable to the start of each
+    /// pass; the final offset marks the end of the last pass.
+    #[count(add($num_passes, 1))]
+    pass_offsets: [u32],
+    /// Number of pseudo glyph mappings.
+    num_pseudo: u16,
+    /// Largest power of 2 less than or equal to `num_pseudo`.
+    search_pseudo: u16,
+    /// Log base 2 of `search_pseudo`.
+    pseudo_selector: u16,
+    /// `num_pseudo` minus `search_pseudo`.
+    pseudo_shift: u16,
+    /// Mappings from codepoints to pseudo glyphs, sorted by codepoint.
+    #[count($num_pseudo)]
+    pseudo_maps: [PseudoMap],
+    /// The class map followed by the pass data; see [Silf::class_map]
+    /// and [SilfSubtable::pass_data].
+    #[count(..)]
+    trailing_data: [u8],
+}
+
+/// A justification level in a [SilfSubtable].
+record JustificationLevel {
+    /// Glyph attribute number of the stretch attribute for this level.
+    attr_stretch: u8,
+    /// Glyph attribute number of the shrink attribute for this level.
+    attr_shrink: u8,
+    /// Glyph attribute number of the step attribute for this level.
+    attr_step: u8,
+    /// Glyph attribute number of the weight attribute for this level.
+    attr_weight: u8,
+    /// Which level starts the next stage.
+    runto: u8,
+    #[skip_getter]
+    _reserved1: u8,
+    #[skip_getter]
+    _reserved2: u8,
+    #[skip_getter]
+    _reserved3: u8,
+}
+
+/// A mapping from a codepoint to a pseudo glyph in a [SilfSubtable].
+record PseudoMap {
+    /// The unicode codepoint.
+    unicode: u32,
+    /// The pseudo glyph id.
+    n_pseudo: GlyphId,
+}
+
+/// A non-linear class in a [SilfSubtable] class map, which maps glyphs to
+/// their index within the class.
+table LookupClass {
+    /// Number of glyphs in the class.
+    num_ids: u16,
+    /// Largest power of 2 less than or equal to `num_ids`.
+    search_range: u16,
+    /// Log base 2 of `search_range`.
+    entry_selector: u16,
+    /// `num_ids` minus `search_range`.
+    range_shift: u16,
+    /// Glyph and index pairs, sorted by glyph id.
+    #[count($num_ids)]
+    lookups: [LookupPair],
+}
+
+/// A glyph and its index in a [LookupClass].
+record LookupPair {
+    /// The glyph id.
+    glyph_id: GlyphId,
+    /// The index of the glyph within the class.
+    index: u16,
+}
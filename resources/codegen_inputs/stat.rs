@@ -6,7 +6,7 @@
 table Stat {
     /// Major/minor version number. Set to 1.2 for new fonts.
     #[version]
-    #[compile(MajorMinor::VERSION_1_2)]
+    #[compile(self.compute_version())]
     version: MajorMinor,
     /// The size in bytes of each axis record.
     #[compile(8)]
@@ -22,6 +22,7 @@ table Stat {
     /// to zero; if designAxisCount is greater than zero, must be
     /// greater than zero.
     #[read_offset_with($design_axis_count)]
+    #[validate(validate_design_axes)]
     design_axes_offset: Offset32<[AxisRecord]>,
     /// The number of axis value tables.
     #[compile(array_len($offset_to_axis_value_offsets))]
@@ -33,12 +34,13 @@ table Stat {
     #[read_offset_with($axis_value_count)]
     #[compile_type(OffsetMarker<Vec<OffsetMarker<AxisValue>>, WIDTH_32>)]
     #[to_owned(convert_axis_value_offsets(obj.offset_to_axis_values()))]
+    #[validate(validate_axis_values)]
     offset_to_axis_value_offsets: Offset32<AxisValueArray>,
     /// Name ID used as fallback when projection of names into a
     /// particular font model produces a subfamily name containing only
     /// elidable elements.
     #[since_version(1,1)]
-    elided_fallback_name_id: u16,
+    elided_fallback_name_id: NameId,
 }
 
 /// [Axis Records](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-records)
@@ -47,7 +49,7 @@ record AxisRecord {
     axis_tag: Tag,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this axis.
-    axis_name_id: u16,
+    axis_name_id: NameId,
     /// A value that applications can use to determine primary sorting
     /// of face names, or for ordering of labels when composing family
     /// or face names.
@@ -86,7 +88,7 @@ table AxisValueFormat1 {
     flags: AxisValueTableFlags,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this attribute value.
-    value_name_id: u16,
+    value_name_id: NameId,
     /// A numeric value for this attribute value.
     value: Fixed,
 }
@@ -104,7 +106,7 @@ table AxisValueFormat2 {
     flags: AxisValueTableFlags,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this attribute value.
-    value_name_id: u16,
+    value_name_id: NameId,
     /// A nominal numeric value for this attribute value.
     nominal_value: Fixed,
     /// The minimum value for a range associated with the specified
@@ -128,7 +130,7 @@ table AxisValueFormat3 {
     flags: AxisValueTableFlags,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this attribute value.
-    value_name_id: u16,
+    value_name_id: NameId,
     /// A numeric value for this attribute value.
     value: Fixed,
     /// The numeric value for a style-linked mapping from this value.
@@ -148,7 +150,7 @@ table AxisValueFormat4 {
     flags: AxisValueTableFlags,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this combination of axis values.
-    value_name_id: u16,
+    value_name_id: NameId,
     /// Array of AxisValue records that provide the combination of axis
     /// values, one for each contributing axis.
     #[count($axis_count)]
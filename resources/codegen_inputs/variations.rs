@@ -102,6 +102,7 @@ table VariationRegionList {
     /// Array of variation regions.
     #[count($region_count)]
     #[read_with($axis_count)]
+    #[validate(validate_variation_regions)]
     variation_regions: ComputedArray<VariationRegion<'a>>,
 }
 
@@ -155,7 +156,8 @@ table ItemVariationData {
     #[count($region_index_count)]
     region_indexes: [u16],
     /// Delta-set rows.
-    #[count(..)]
+    #[count(item_variation_data_len($item_count, $word_delta_count, $region_index_count))]
+    #[validate(validate_delta_sets)]
     delta_sets: [u8],
 }
 
@@ -0,0 +1,146 @@
+//! Subset and instance a font.
+//!
+//! The glyphs to keep are given as codepoints, glyph identifiers, or both;
+//! they are extended with every glyph reachable through `GSUB` and composite
+//! glyphs. Variable fonts can be instanced by pinning axes, which happens
+//! before subsetting.
+//!
+//! ```sh
+//! cargo run -p write-fonts --example subset -- font.ttf -o out.ttf --unicodes U+0041-005A,U+0020
+//! cargo run -p write-fonts --example subset -- font.ttf -o out.ttf --gids 1,5-7
+//! cargo run -p write-fonts --example subset -- font.ttf -o out.ttf --variations wght=400,wdth=100
+//! ```
+//!
+//! Subsetting only supports static fonts with TrueType outlines, so variable
+//! fonts must have every axis pinned.
+
+use std::{collections::BTreeSet, ops::RangeInclusive, str::FromStr};
+
+use write_fonts::{
+    gid_mapper::{glyph_closure, GidMapper},
+    instancer::Instancer,
+    read::{FileRef, FontRef, TableProvider},
+    types::{GlyphId, Tag},
+    FontBuilder,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<()> {
+    let args = flags::Args::from_env()?;
+    let data = std::fs::read(&args.font)?;
+    let font = FileRef::new(&data)?
+        .fonts()
+        .nth(args.index.unwrap_or(0) as usize)
+        .ok_or("font index out of range")??;
+    // copy the font out of its collection, if any
+    let mut data = FontBuilder::from(font).build();
+
+    if let Some(variations) = &args.variations {
+        let font = FontRef::new(&data)?;
+        let mut instancer = Instancer::new(font);
+        for (tag, value) in parse_variations(variations)? {
+            instancer.pin_axis(tag, value);
+        }
+        data = instancer.instantiate()?;
+    }
+
+    if args.unicodes.is_some() || args.gids.is_some() {
+        let font = FontRef::new(&data)?;
+        if font.is_variable() {
+            return Err("only static fonts can be subset; pin every axis with --variations".into());
+        }
+        let mut glyphs = BTreeSet::new();
+        if let Some(unicodes) = &args.unicodes {
+            let cmap = font.cmap()?;
+            for range in parse_ranges(unicodes, |s| {
+                let hex = s
+                    .strip_prefix("U+")
+                    .or_else(|| s.strip_prefix("0x"))
+                    .unwrap_or(s);
+                u32::from_str_radix(hex, 16)
+            })? {
+                glyphs.extend(range.filter_map(|codepoint| cmap.map_codepoint(codepoint)));
+            }
+        }
+        if let Some(gids) = &args.gids {
+            for range in parse_ranges(gids, u16::from_str)? {
+                glyphs.extend(range.map(GlyphId::new));
+            }
+        }
+        let num_glyphs = font.maxp()?.num_glyphs();
+        if let Some(gid) = glyphs.iter().find(|gid| gid.to_u16() >= num_glyphs) {
+            return Err(format!("glyph {gid} is out of range ({num_glyphs} glyphs)").into());
+        }
+        let closure = glyph_closure(&font, glyphs)?;
+        let remapped = GidMapper::retain(closure)?.remap_font(&font)?;
+        for tag in &remapped.dropped_tables {
+            eprintln!("dropped table '{tag}'");
+        }
+        data = remapped.data;
+    }
+
+    std::fs::write(&args.output, data)?;
+    Ok(())
+}
+
+/// Parses comma separated values and inclusive ranges, like `1,5-7`.
+fn parse_ranges<T, E>(
+    input: &str,
+    parse: impl Fn(&str) -> std::result::Result<T, E>,
+) -> Result<Vec<RangeInclusive<T>>>
+where
+    T: Copy,
+    E: std::error::Error + 'static,
+{
+    input
+        .split(',')
+        .map(|item| {
+            let item = item.trim();
+            let (start, end) = item.split_once('-').unwrap_or((item, item));
+            Ok(parse(start)?..=parse(end)?)
+        })
+        .collect()
+}
+
+/// Parses variations like `wght=400,wdth=100`.
+fn parse_variations(variations: &str) -> Result<Vec<(Tag, f32)>> {
+    variations
+        .split(',')
+        .map(|variation| {
+            let (tag, value) = variation
+                .split_once('=')
+                .ok_or_else(|| format!("expected 'tag=value', found '{variation}'"))?;
+            Ok((Tag::from_str(tag)?, value.parse()?))
+        })
+        .collect()
+}
+
+mod flags {
+    use std::path::PathBuf;
+
+    xflags::xflags! {
+        /// Subset and instance a font
+        cmd args
+            required font: PathBuf
+            {
+                /// The output path
+                required -o, --output output: PathBuf
+                /// The index of the font, in font collections
+                optional -i, --index index: u32
+                /// Codepoints to keep, such as 'U+0041-005A,U+0020'
+                optional -u, --unicodes unicodes: String
+                /// Glyph ids to keep, such as '1,5-7'
+                optional -g, --gids gids: String
+                /// Axis locations to pin, such as 'wght=400,wdth=100'
+                optional -v, --variations variations: String
+            }
+    }
+}
@@ -0,0 +1,88 @@
+//! Check fonts for problems, for use in CI.
+//!
+//! Each font is checked with [`validate_font`], and the issues are printed
+//! one per line. The exit status is nonzero if any errors are found, or any
+//! warnings when `--strict` is passed.
+//!
+//! ```sh
+//! cargo run -p write-fonts --example validate -- font.ttf other.ttf
+//! cargo run -p write-fonts --example validate -- --strict font.ttc
+//! ```
+
+use write_fonts::{
+    read::FileRef,
+    validate::{validate_font, Severity},
+};
+
+fn main() {
+    let args = match flags::Args::from_env() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+    let fail_at = if args.strict {
+        Severity::Warning
+    } else {
+        Severity::Error
+    };
+    let mut failed = false;
+    for path in &args.fonts {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("{}: error: {e}", path.display());
+                failed = true;
+                continue;
+            }
+        };
+        let fonts = match FileRef::new(&data) {
+            Ok(FileRef::Font(font)) => vec![(None, Ok(font))],
+            Ok(FileRef::Collection(collection)) => (0..collection.len())
+                .map(|i| (Some(i), collection.get(i)))
+                .collect(),
+            Err(e) => vec![(None, Err(e))],
+        };
+        for (index, font) in fonts {
+            let name = match index {
+                Some(i) => format!("{}#{i}", path.display()),
+                None => path.display().to_string(),
+            };
+            let issues = match font {
+                Ok(font) => validate_font(&font),
+                Err(e) => {
+                    eprintln!("{name}: error: {e}");
+                    failed = true;
+                    continue;
+                }
+            };
+            for issue in issues {
+                if issue.severity == Severity::Warning && args.errors_only {
+                    continue;
+                }
+                failed |= issue.severity >= fail_at;
+                println!("{name}: {issue}");
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+mod flags {
+    use std::path::PathBuf;
+
+    xflags::xflags! {
+        /// Check fonts for problems
+        cmd args
+            repeated fonts: PathBuf
+            {
+                /// Fail if there are any warnings
+                optional --strict
+                /// Only print errors
+                optional -e, --errors-only
+            }
+    }
+}
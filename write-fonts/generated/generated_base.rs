@@ -7,6 +7,7 @@ use crate::codegen_prelude::*;
 
 /// The [BASE](https://learn.microsoft.com/en-us/typography/opentype/spec/base) (Baseline) table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Base {
     /// Offset to horizontal Axis table, from beginning of BASE table (may be NULL)
     pub horiz_axis: NullableOffsetMarker<Axis>,
@@ -35,9 +36,12 @@ impl FontWrite for Base {
         self.horiz_axis.write_into(writer);
         self.vert_axis.write_into(writer);
         version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| self.item_var_store.write_into(writer));
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Base {
@@ -80,6 +84,7 @@ impl<'a> FontRead<'a> for Base {
 
 /// [Axis Table](https://learn.microsoft.com/en-us/typography/opentype/spec/base#axis-tables-horizaxis-and-vertaxis)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Axis {
     /// Offset to BaseTagList table, from beginning of Axis table (may
     /// be NULL)
@@ -137,6 +142,7 @@ impl<'a> FontRead<'a> for Axis {
 
 /// [BaseTagList Table](https://learn.microsoft.com/en-us/typography/opentype/spec/base#basetaglist-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseTagList {
     /// Array of 4-byte baseline identification tags — must be in
     /// alphabetical order
@@ -191,6 +197,7 @@ impl<'a> FontRead<'a> for BaseTagList {
 
 /// [BaseScriptList Table](https://learn.microsoft.com/en-us/typography/opentype/spec/base#basescriptlist-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseScriptList {
     /// Array of BaseScriptRecords, in alphabetical order by
     /// baseScriptTag
@@ -247,6 +254,7 @@ impl<'a> FontRead<'a> for BaseScriptList {
 
 /// [BaseScriptRecord](https://learn.microsoft.com/en-us/typography/opentype/spec/base#basescriptrecord)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseScriptRecord {
     /// 4-byte script identification tag
     pub base_script_tag: Tag,
@@ -295,6 +303,7 @@ impl FromObjRef<read_fonts::tables::base::BaseScriptRecord> for BaseScriptRecord
 
 /// [BaseScript Table](https://learn.microsoft.com/en-us/typography/opentype/spec/base#basescript-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseScript {
     /// Offset to BaseValues table, from beginning of BaseScript table (may be NULL)
     pub base_values: NullableOffsetMarker<BaseValues>,
@@ -370,6 +379,7 @@ impl<'a> FontRead<'a> for BaseScript {
 
 /// [BaseLangSysRecord](https://learn.microsoft.com/en-us/typography/opentype/spec/base#baselangsysrecord)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseLangSysRecord {
     /// 4-byte language system identification tag
     pub base_lang_sys_tag: Tag,
@@ -418,6 +428,7 @@ impl FromObjRef<read_fonts::tables::base::BaseLangSysRecord> for BaseLangSysReco
 
 /// [BaseValues](https://learn.microsoft.com/en-us/typography/opentype/spec/base#basevalues-table) table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseValues {
     /// Index number of default baseline for this script — equals
     /// index position of baseline tag in baselineTags array of the
@@ -480,6 +491,7 @@ impl<'a> FontRead<'a> for BaseValues {
 
 /// [MinMax](https://learn.microsoft.com/en-us/typography/opentype/spec/base#minmax-table) table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MinMax {
     /// Offset to BaseCoord table that defines the minimum extent
     /// value, from the beginning of MinMax table (may be NULL)
@@ -557,24 +569,25 @@ impl<'a> FontRead<'a> for MinMax {
 
 /// [FeatMinMaxRecord](https://learn.microsoft.com/en-us/typography/opentype/spec/base#baselangsysrecord)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FeatMinMaxRecord {
     /// 4-byte feature identification tag — must match feature tag in
     /// FeatureList
     pub feature_table_tag: Tag,
     /// Offset to BaseCoord table that defines the minimum extent
     /// value, from beginning of MinMax table (may be NULL)
-    pub min_coord: NullableOffsetMarker<MinMax>,
+    pub min_coord: NullableOffsetMarker<BaseCoord>,
     /// Offset to BaseCoord table that defines the maximum extent
     /// value, from beginning of MinMax table (may be NULL)
-    pub max_coord: NullableOffsetMarker<MinMax>,
+    pub max_coord: NullableOffsetMarker<BaseCoord>,
 }
 
 impl FeatMinMaxRecord {
     /// Construct a new `FeatMinMaxRecord`
     pub fn new(
         feature_table_tag: Tag,
-        min_coord: Option<MinMax>,
-        max_coord: Option<MinMax>,
+        min_coord: Option<BaseCoord>,
+        max_coord: Option<BaseCoord>,
     ) -> Self {
         Self {
             feature_table_tag,
@@ -619,6 +632,7 @@ impl FromObjRef<read_fonts::tables::base::FeatMinMaxRecord> for FeatMinMaxRecord
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum BaseCoord {
     Format1(BaseCoordFormat1),
     Format2(BaseCoordFormat2),
@@ -693,6 +707,7 @@ impl<'a> FontRead<'a> for BaseCoord {
 
 /// [BaseCoordFormat1](https://learn.microsoft.com/en-us/typography/opentype/spec/base#basecoord-format-1)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseCoordFormat1 {
     /// X or Y value, in design units
     pub coordinate: i16,
@@ -736,6 +751,7 @@ impl<'a> FontRead<'a> for BaseCoordFormat1 {
 
 /// [BaseCoordFormat2](https://learn.microsoft.com/en-us/typography/opentype/spec/base#basecoord-format-2)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseCoordFormat2 {
     /// X or Y value, in design units
     pub coordinate: i16,
@@ -791,6 +807,7 @@ impl<'a> FontRead<'a> for BaseCoordFormat2 {
 
 /// [BaseCoordFormat3](https://learn.microsoft.com/en-us/typography/opentype/spec/base#basecoord-format-3)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BaseCoordFormat3 {
     /// X or Y value, in design units
     pub coordinate: i16,
@@ -7,6 +7,7 @@ use crate::codegen_prelude::*;
 
 /// [CPAL (Color Palette Table)](https://learn.microsoft.com/en-us/typography/opentype/spec/cpal#palette-table-header) table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Cpal {
     /// Number of palette entries in each palette.
     pub num_palette_entries: u16,
@@ -69,7 +70,7 @@ impl Cpal {
 impl FontWrite for Cpal {
     #[allow(clippy::unnecessary_cast)]
     fn write_into(&self, writer: &mut TableWriter) {
-        let version = 0 as u16;
+        let version = self.compute_version() as u16;
         version.write_into(writer);
         self.num_palette_entries.write_into(writer);
         self.num_palettes.write_into(writer);
@@ -86,6 +87,9 @@ impl FontWrite for Cpal {
             .compatible(1)
             .then(|| self.palette_entry_labels_array.write_into(writer));
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Cpal {
@@ -133,6 +137,7 @@ impl<'a> FontRead<'a> for Cpal {
 
 /// [CPAL (Color Record)](https://learn.microsoft.com/en-us/typography/opentype/spec/cpal#palette-entries-and-color-records) record
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ColorRecord {
     /// Blue value (B0).
     pub blue: u8,
@@ -7,6 +7,7 @@ use crate::codegen_prelude::*;
 
 /// The OpenType [Table Directory](https://docs.microsoft.com/en-us/typography/opentype/spec/otff#table-directory)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TableDirectory {
     /// 0x00010000 or 0x4F54544F
     pub sfnt_version: u32,
@@ -63,6 +64,7 @@ impl Validate for TableDirectory {
 
 /// Record for a table in a font.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TableRecord {
     /// Table identifier.
     pub tag: Tag,
@@ -101,6 +103,7 @@ impl Validate for TableRecord {
 
 /// [TTC Header](https://learn.microsoft.com/en-us/typography/opentype/spec/otff#ttc-header)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TTCHeader {
     /// Font Collection ID string: \"ttcf\"
     pub ttc_tag: Tag,
@@ -126,17 +129,17 @@ impl Validate for TTCHeader {
                 }
             });
             ctx.in_field("dsig_tag", |ctx| {
-                if version.compatible((2, 0)) && self.dsig_tag.is_none() {
+                if version.compatible(MajorMinor::new(2, 0)) && self.dsig_tag.is_none() {
                     ctx.report(format!("field must be present for version {version}"));
                 }
             });
             ctx.in_field("dsig_length", |ctx| {
-                if version.compatible((2, 0)) && self.dsig_length.is_none() {
+                if version.compatible(MajorMinor::new(2, 0)) && self.dsig_length.is_none() {
                     ctx.report(format!("field must be present for version {version}"));
                 }
             });
             ctx.in_field("dsig_offset", |ctx| {
-                if version.compatible((2, 0)) && self.dsig_offset.is_none() {
+                if version.compatible(MajorMinor::new(2, 0)) && self.dsig_offset.is_none() {
                     ctx.report(format!("field must be present for version {version}"));
                 }
             });
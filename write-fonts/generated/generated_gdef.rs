@@ -9,6 +9,7 @@ pub use read_fonts::tables::gdef::GlyphClassDef;
 
 /// [GDEF](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#gdef-header) 1.0
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Gdef {
     /// Offset to class definition table for glyph type, from beginning
     /// of GDEF header (may be NULL)
@@ -58,12 +59,15 @@ impl FontWrite for Gdef {
         self.lig_caret_list.write_into(writer);
         self.mark_attach_class_def.write_into(writer);
         version
-            .compatible((1, 2))
+            .compatible(MajorMinor::new(1, 2))
             .then(|| self.mark_glyph_sets_def.write_into(writer));
         version
-            .compatible((1, 3))
+            .compatible(MajorMinor::new(1, 3))
             .then(|| self.item_var_store.write_into(writer));
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Gdef {
@@ -125,6 +129,7 @@ impl FontWrite for GlyphClassDef {
 
 /// [Attachment Point List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#attachment-point-list-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AttachList {
     /// Offset to Coverage table - from beginning of AttachList table
     pub coverage: OffsetMarker<CoverageTable>,
@@ -187,6 +192,7 @@ impl<'a> FontRead<'a> for AttachList {
 
 /// Part of [AttachList]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AttachPoint {
     /// Array of contour point indices -in increasing numerical order
     pub point_indices: Vec<u16>,
@@ -240,6 +246,7 @@ impl<'a> FontRead<'a> for AttachPoint {
 
 /// [Ligature Caret List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#ligature-caret-list-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LigCaretList {
     /// Offset to Coverage table - from beginning of LigCaretList table
     pub coverage: OffsetMarker<CoverageTable>,
@@ -302,6 +309,7 @@ impl<'a> FontRead<'a> for LigCaretList {
 
 /// [Ligature Glyph Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#ligature-glyph-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LigGlyph {
     /// Array of offsets to CaretValue tables, from beginning of
     /// LigGlyph table — in increasing coordinate order
@@ -356,6 +364,7 @@ impl<'a> FontRead<'a> for LigGlyph {
 
 /// [Caret Value Tables](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#caret-value-tables)
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum CaretValue {
     Format1(CaretValueFormat1),
     Format2(CaretValueFormat2),
@@ -426,6 +435,7 @@ impl<'a> FontRead<'a> for CaretValue {
 
 /// [CaretValue Format 1](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#caretvalue-format-1)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CaretValueFormat1 {
     /// X or Y value, in design units
     pub coordinate: i16,
@@ -469,6 +479,7 @@ impl<'a> FontRead<'a> for CaretValueFormat1 {
 
 /// [CaretValue Format 2](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#caretvalue-format-2)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CaretValueFormat2 {
     /// Contour point index on glyph
     pub caret_value_point_index: u16,
@@ -514,6 +525,7 @@ impl<'a> FontRead<'a> for CaretValueFormat2 {
 
 /// [CaretValue Format 3](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#caretvalue-format-3)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CaretValueFormat3 {
     /// X or Y value, in design units
     pub coordinate: i16,
@@ -572,6 +584,7 @@ impl<'a> FontRead<'a> for CaretValueFormat3 {
 
 /// [Mark Glyph Sets Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#mark-glyph-sets-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MarkGlyphSets {
     /// Array of offsets to mark glyph set coverage tables, from the
     /// start of the MarkGlyphSets table.
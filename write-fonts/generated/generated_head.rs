@@ -7,6 +7,7 @@ use crate::codegen_prelude::*;
 
 /// <https://docs.microsoft.com/en-us/typography/opentype/spec/head>
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Head {
     /// Set by font manufacturer.
     pub font_revision: Fixed,
@@ -129,6 +130,9 @@ impl FontWrite for Head {
         self.index_to_loc_format.write_into(writer);
         (0 as i16).write_into(writer);
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Head {
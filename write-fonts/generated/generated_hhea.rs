@@ -7,6 +7,7 @@ use crate::codegen_prelude::*;
 
 /// [hhea](https://docs.microsoft.com/en-us/typography/opentype/spec/hhea) Horizontal Header Table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Hhea {
     /// Typographic ascent.
     pub ascender: FWord,
@@ -91,6 +92,9 @@ impl FontWrite for Hhea {
         (0 as i16).write_into(writer);
         self.number_of_long_metrics.write_into(writer);
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Hhea {
@@ -9,6 +9,7 @@ pub use read_fonts::tables::layout::DeltaFormat;
 
 /// [Script List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-list-table-and-script-record)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ScriptList {
     /// Array of ScriptRecords, listed alphabetically by script tag
     pub script_records: Vec<ScriptRecord>,
@@ -63,6 +64,7 @@ impl<'a> FontRead<'a> for ScriptList {
 
 /// [Script Record](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-list-table-and-script-record)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ScriptRecord {
     /// 4-byte script tag identifier
     pub script_tag: Tag,
@@ -108,6 +110,7 @@ impl FromObjRef<read_fonts::tables::layout::ScriptRecord> for ScriptRecord {
 
 /// [Script Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-table-and-language-system-record)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Script {
     /// Offset to default LangSys table, from beginning of Script table
     /// — may be NULL
@@ -170,6 +173,7 @@ impl<'a> FontRead<'a> for Script {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LangSysRecord {
     /// 4-byte LangSysTag identifier
     pub lang_sys_tag: Tag,
@@ -218,6 +222,7 @@ impl FromObjRef<read_fonts::tables::layout::LangSysRecord> for LangSysRecord {
 
 /// [Language System Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#language-system-table)
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LangSys {
     /// Index of a feature required for this language system; if no
     /// required features = 0xFFFF
@@ -287,6 +292,7 @@ impl<'a> FontRead<'a> for LangSys {
 
 /// [Feature List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#feature-list-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FeatureList {
     /// Array of FeatureRecords — zero-based (first feature has
     /// FeatureIndex = 0), listed alphabetically by feature tag
@@ -343,6 +349,7 @@ impl<'a> FontRead<'a> for FeatureList {
 
 /// Part of [FeatureList]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FeatureRecord {
     /// 4-byte feature identification tag
     pub feature_tag: Tag,
@@ -371,7 +378,7 @@ impl Validate for FeatureRecord {
     fn validate_impl(&self, ctx: &mut ValidationCtx) {
         ctx.in_table("FeatureRecord", |ctx| {
             ctx.in_field("feature", |ctx| {
-                self.feature.validate_impl(ctx);
+                self.validate_feature_params(ctx);
             });
         })
     }
@@ -391,6 +398,7 @@ impl FromObjRef<read_fonts::tables::layout::FeatureRecord> for FeatureRecord {
 
 /// [Feature Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#feature-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Feature {
     /// Offset from start of Feature table to FeatureParams table, if defined for the feature and present, else NULL
     pub feature_params: NullableOffsetMarker<FeatureParams>,
@@ -447,6 +455,7 @@ impl<'a> FromTableRef<read_fonts::tables::layout::Feature<'a>> for Feature {}
 
 /// [Lookup List Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#lookup-list-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LookupList<T> {
     /// Array of offsets to Lookup tables, from beginning of LookupList
     /// — zero based (first lookup is Lookup index = 0)
@@ -504,6 +513,7 @@ where
 
 /// [Lookup Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#lookup-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Lookup<T> {
     /// Lookup qualifiers
     pub lookup_flag: LookupFlag,
@@ -563,6 +573,7 @@ where
 
 /// [Coverage Format 1](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#coverage-format-1)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CoverageFormat1 {
     /// Array of glyph IDs — in numerical order
     pub glyph_array: Vec<GlyphId>,
@@ -593,6 +604,7 @@ impl Validate for CoverageFormat1 {
                 if self.glyph_array.len() > (u16::MAX as usize) {
                     ctx.report("array exceeds max length");
                 }
+                self.validate_glyph_array(ctx);
             });
         })
     }
@@ -618,6 +630,7 @@ impl<'a> FontRead<'a> for CoverageFormat1 {
 
 /// [Coverage Format 2](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#coverage-format-2)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CoverageFormat2 {
     /// Array of glyph ranges — ordered by startGlyphID.
     pub range_records: Vec<RangeRecord>,
@@ -648,7 +661,7 @@ impl Validate for CoverageFormat2 {
                 if self.range_records.len() > (u16::MAX as usize) {
                     ctx.report("array exceeds max length");
                 }
-                self.range_records.validate_impl(ctx);
+                self.validate_range_records(ctx);
             });
         })
     }
@@ -674,6 +687,7 @@ impl<'a> FontRead<'a> for CoverageFormat2 {
 
 /// Used in [CoverageFormat2]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RangeRecord {
     /// First glyph ID in the range
     pub start_glyph_id: GlyphId,
@@ -718,6 +732,7 @@ impl FromObjRef<read_fonts::tables::layout::RangeRecord> for RangeRecord {
 
 /// [Coverage Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#coverage-table)
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum CoverageTable {
     Format1(CoverageFormat1),
     Format2(CoverageFormat2),
@@ -780,6 +795,7 @@ impl<'a> FontRead<'a> for CoverageTable {
 
 /// [Class Definition Table Format 1](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#class-definition-table-format-1)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClassDefFormat1 {
     /// First glyph ID of the classValueArray
     pub start_glyph_id: GlyphId,
@@ -840,6 +856,7 @@ impl<'a> FontRead<'a> for ClassDefFormat1 {
 
 /// [Class Definition Table Format 2](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#class-definition-table-format-2)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClassDefFormat2 {
     /// Array of ClassRangeRecords — ordered by startGlyphID
     pub class_range_records: Vec<ClassRangeRecord>,
@@ -870,7 +887,7 @@ impl Validate for ClassDefFormat2 {
                 if self.class_range_records.len() > (u16::MAX as usize) {
                     ctx.report("array exceeds max length");
                 }
-                self.class_range_records.validate_impl(ctx);
+                self.validate_class_range_records(ctx);
             });
         })
     }
@@ -896,6 +913,7 @@ impl<'a> FontRead<'a> for ClassDefFormat2 {
 
 /// Used in [ClassDefFormat2]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClassRangeRecord {
     /// First glyph ID in the range
     pub start_glyph_id: GlyphId,
@@ -946,6 +964,7 @@ impl FromObjRef<read_fonts::tables::layout::ClassRangeRecord> for ClassRangeReco
 
 /// A [Class Definition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#class-definition-table)
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ClassDef {
     Format1(ClassDefFormat1),
     Format2(ClassDefFormat2),
@@ -1007,6 +1026,7 @@ impl<'a> FontRead<'a> for ClassDef {
 
 /// [Sequence Lookup Record](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#sequence-lookup-record)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SequenceLookupRecord {
     /// Index (zero-based) into the input glyph sequence
     pub sequence_index: u16,
@@ -1046,6 +1066,7 @@ impl FromObjRef<read_fonts::tables::layout::SequenceLookupRecord> for SequenceLo
 
 /// [Sequence Context Format 1](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#sequence-context-format-1-simple-glyph-contexts)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SequenceContextFormat1 {
     /// Offset to Coverage table, from beginning of
     /// SequenceContextFormat1 table
@@ -1119,6 +1140,7 @@ impl<'a> FontRead<'a> for SequenceContextFormat1 {
 
 /// Part of [SequenceContextFormat1]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SequenceRuleSet {
     /// Array of offsets to SequenceRule tables, from beginning of the
     /// SequenceRuleSet table
@@ -1174,6 +1196,7 @@ impl<'a> FontRead<'a> for SequenceRuleSet {
 
 /// Part of [SequenceContextFormat1]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SequenceRule {
     /// Array of input glyph IDs—starting with the second glyph
     pub input_sequence: Vec<GlyphId>,
@@ -1238,6 +1261,7 @@ impl<'a> FontRead<'a> for SequenceRule {
 
 /// [Sequence Context Format 2](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#sequence-context-format-2-class-based-glyph-contexts)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SequenceContextFormat2 {
     /// Offset to Coverage table, from beginning of
     /// SequenceContextFormat2 table
@@ -1327,6 +1351,7 @@ impl<'a> FontRead<'a> for SequenceContextFormat2 {
 
 /// Part of [SequenceContextFormat2]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClassSequenceRuleSet {
     /// Array of offsets to ClassSequenceRule tables, from beginning of
     /// ClassSequenceRuleSet table
@@ -1388,6 +1413,7 @@ impl<'a> FontRead<'a> for ClassSequenceRuleSet {
 
 /// Part of [SequenceContextFormat2]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ClassSequenceRule {
     /// Sequence of classes to be matched to the input glyph sequence,
     /// beginning with the second glyph position
@@ -1450,6 +1476,7 @@ impl<'a> FontRead<'a> for ClassSequenceRule {
 
 /// [Sequence Context Format 3](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#sequence-context-format-3-coverage-based-glyph-contexts)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SequenceContextFormat3 {
     /// Array of offsets to Coverage tables, from beginning of
     /// SequenceContextFormat3 subtable
@@ -1529,6 +1556,7 @@ impl<'a> FontRead<'a> for SequenceContextFormat3 {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum SequenceContext {
     Format1(SequenceContextFormat1),
     Format2(SequenceContextFormat2),
@@ -1611,6 +1639,7 @@ impl<'a> FontRead<'a> for SequenceContext {
 
 /// [Chained Sequence Context Format 1](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#chained-sequence-context-format-1-simple-glyph-contexts)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChainedSequenceContextFormat1 {
     /// Offset to Coverage table, from beginning of
     /// ChainSequenceContextFormat1 table
@@ -1690,6 +1719,7 @@ impl<'a> FontRead<'a> for ChainedSequenceContextFormat1 {
 
 /// Part of [ChainedSequenceContextFormat1]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChainedSequenceRuleSet {
     /// Array of offsets to ChainedSequenceRule tables, from beginning
     /// of ChainedSequenceRuleSet table
@@ -1756,6 +1786,7 @@ impl<'a> FontRead<'a> for ChainedSequenceRuleSet {
 
 /// Part of [ChainedSequenceContextFormat1]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChainedSequenceRule {
     /// Array of backtrack glyph IDs
     pub backtrack_sequence: Vec<GlyphId>,
@@ -1847,6 +1878,7 @@ impl<'a> FontRead<'a> for ChainedSequenceRule {
 
 /// [Chained Sequence Context Format 2](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#chained-sequence-context-format-2-class-based-glyph-contexts)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChainedSequenceContextFormat2 {
     /// Offset to Coverage table, from beginning of
     /// ChainedSequenceContextFormat2 table
@@ -1959,6 +1991,7 @@ impl<'a> FontRead<'a> for ChainedSequenceContextFormat2 {
 
 /// Part of [ChainedSequenceContextFormat2]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChainedClassSequenceRuleSet {
     /// Array of offsets to ChainedClassSequenceRule tables, from
     /// beginning of ChainedClassSequenceRuleSet
@@ -2028,6 +2061,7 @@ impl<'a> FontRead<'a> for ChainedClassSequenceRuleSet {
 
 /// Part of [ChainedSequenceContextFormat2]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChainedClassSequenceRule {
     /// Array of backtrack-sequence classes
     pub backtrack_sequence: Vec<u16>,
@@ -2125,6 +2159,7 @@ impl<'a> FontRead<'a> for ChainedClassSequenceRule {
 
 /// [Chained Sequence Context Format 3](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#chained-sequence-context-format-3-coverage-based-glyph-contexts)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ChainedSequenceContextFormat3 {
     /// Array of offsets to coverage tables for the backtrack sequence
     pub backtrack_coverages: Vec<OffsetMarker<CoverageTable>>,
@@ -2235,6 +2270,7 @@ impl<'a> FontRead<'a> for ChainedSequenceContextFormat3 {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum ChainedSequenceContext {
     Format1(ChainedSequenceContextFormat1),
     Format2(ChainedSequenceContextFormat2),
@@ -2344,6 +2380,7 @@ impl FontWrite for DeltaFormat {
 
 /// [Device Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#device-and-variationindex-tables)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Device {
     /// Smallest size to correct, in ppem
     pub start_size: u16,
@@ -2390,6 +2427,7 @@ impl<'a> FontRead<'a> for Device {
 
 /// Variation index table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VariationIndex {
     /// A delta-set outer index — used to select an item variation
     /// data subtable within the item variation store.
@@ -2445,6 +2483,7 @@ impl<'a> FontRead<'a> for VariationIndex {
 
 /// [FeatureVariations Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#featurevariations-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FeatureVariations {
     /// Array of feature variation records.
     pub feature_variation_records: Vec<FeatureVariationRecord>,
@@ -2504,6 +2543,7 @@ impl<'a> FontRead<'a> for FeatureVariations {
 
 /// Part of [FeatureVariations]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FeatureVariationRecord {
     /// Offset to a condition set table, from beginning of
     /// FeatureVariations table.
@@ -2562,6 +2602,7 @@ impl FromObjRef<read_fonts::tables::layout::FeatureVariationRecord> for FeatureV
 
 /// [ConditionSet Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#conditionset-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ConditionSet {
     /// Array of offsets to condition tables, from beginning of the
     /// ConditionSet table.
@@ -2617,6 +2658,7 @@ impl<'a> FontRead<'a> for ConditionSet {
 
 /// [Condition Table Format 1](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#condition-table-format-1-font-variation-axis-range): Font Variation Axis Range
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ConditionFormat1 {
     /// Index (zero-based) for the variation axis within the 'fvar'
     /// table.
@@ -2679,6 +2721,7 @@ impl<'a> FontRead<'a> for ConditionFormat1 {
 
 /// [FeatureTableSubstitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#featuretablesubstitution-table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FeatureTableSubstitution {
     /// Array of feature table substitution records.
     pub substitutions: Vec<FeatureTableSubstitutionRecord>,
@@ -2743,6 +2786,7 @@ impl<'a> FontRead<'a> for FeatureTableSubstitution {
 
 /// Used in [FeatureTableSubstitution]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FeatureTableSubstitutionRecord {
     /// The feature table index to match.
     pub feature_index: u16,
@@ -2793,6 +2837,7 @@ impl FromObjRef<read_fonts::tables::layout::FeatureTableSubstitutionRecord>
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SizeParams {
     /// The first value represents the design size in 720/inch units (decipoints).
     ///
@@ -2880,6 +2925,7 @@ impl<'a> FontRead<'a> for SizeParams {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct StylisticSetParams {
     /// The 'name' table name ID that specifies a string (or strings, for
     /// multiple languages) for a user-interface label for this feature.
@@ -2931,6 +2977,7 @@ impl<'a> FontRead<'a> for StylisticSetParams {
 
 /// featureParams for ['cv01'-'cv99'](https://docs.microsoft.com/en-us/typography/opentype/spec/features_ae#cv01-cv99)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct CharacterVariantParams {
     /// The 'name' table name ID that specifies a string (or strings,
     /// for multiple languages) for a user-interface label for this
@@ -7,6 +7,7 @@ use crate::codegen_prelude::*;
 
 /// [Naming table version 1](https://docs.microsoft.com/en-us/typography/opentype/spec/name#naming-table-version-1)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Name {
     /// The name records where count is the number of records.
     pub name_record: BTreeSet<NameRecord>,
@@ -47,6 +48,9 @@ impl FontWrite for Name {
             });
         });
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Name {
@@ -98,6 +102,7 @@ impl<'a> FontRead<'a> for Name {
 
 /// Part of [Name]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct LangTagRecord {
     /// Language-tag string offset from start of storage area (in
     /// bytes).
@@ -128,6 +133,7 @@ impl FromObjRef<read_fonts::tables::name::LangTagRecord> for LangTagRecord {
 
 ///[Name Records](https://docs.microsoft.com/en-us/typography/opentype/spec/name#name-records)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct NameRecord {
     /// Platform ID.
     pub platform_id: u16,
@@ -136,7 +142,7 @@ pub struct NameRecord {
     /// Language ID.
     pub language_id: u16,
     /// Name ID.
-    pub name_id: u16,
+    pub name_id: NameId,
     /// String offset from start of storage area (in bytes).
     pub string: OffsetMarker<String>,
 }
@@ -148,7 +154,7 @@ impl NameRecord {
         platform_id: u16,
         encoding_id: u16,
         language_id: u16,
-        name_id: u16,
+        name_id: NameId,
         string: OffsetMarker<String>,
     ) -> Self {
         Self {
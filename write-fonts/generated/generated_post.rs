@@ -7,6 +7,7 @@ use crate::codegen_prelude::*;
 
 /// [post (PostScript)](https://docs.microsoft.com/en-us/typography/opentype/spec/post#header) table
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Post {
     /// 0x00010000 for version 1.0 0x00020000 for version 2.0
     /// 0x00025000 for version 2.5 (deprecated) 0x00030000 for version
@@ -109,25 +110,28 @@ impl FontWrite for Post {
         self.max_mem_type42.write_into(writer);
         self.min_mem_type1.write_into(writer);
         self.max_mem_type1.write_into(writer);
-        version.compatible((2, 0)).then(|| {
+        version.compatible(Version16Dot16::new(2, 0)).then(|| {
             self.num_glyphs
                 .as_ref()
                 .expect("missing versioned field should have failed validation")
                 .write_into(writer)
         });
-        version.compatible((2, 0)).then(|| {
+        version.compatible(Version16Dot16::new(2, 0)).then(|| {
             self.glyph_name_index
                 .as_ref()
                 .expect("missing versioned field should have failed validation")
                 .write_into(writer)
         });
-        version.compatible((2, 0)).then(|| {
+        version.compatible(Version16Dot16::new(2, 0)).then(|| {
             self.string_data
                 .as_ref()
                 .expect("missing versioned field should have failed validation")
                 .write_into(writer)
         });
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Post {
@@ -135,12 +139,13 @@ impl Validate for Post {
         ctx.in_table("Post", |ctx| {
             let version = self.version;
             ctx.in_field("num_glyphs", |ctx| {
-                if version.compatible((2, 0)) && self.num_glyphs.is_none() {
+                if version.compatible(Version16Dot16::new(2, 0)) && self.num_glyphs.is_none() {
                     ctx.report(format!("field must be present for version {version}"));
                 }
             });
             ctx.in_field("glyph_name_index", |ctx| {
-                if version.compatible((2, 0)) && self.glyph_name_index.is_none() {
+                if version.compatible(Version16Dot16::new(2, 0)) && self.glyph_name_index.is_none()
+                {
                     ctx.report(format!("field must be present for version {version}"));
                 }
                 if self.glyph_name_index.is_some()
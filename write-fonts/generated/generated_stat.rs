@@ -9,6 +9,7 @@ pub use read_fonts::tables::stat::AxisValueTableFlags;
 
 /// [STAT](https://docs.microsoft.com/en-us/typography/opentype/spec/stat) (Style Attributes Table)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Stat {
     /// Offset in bytes from the beginning of the STAT table to the
     /// start of the design axes array. If designAxisCount is zero, set
@@ -23,40 +24,45 @@ pub struct Stat {
     /// Name ID used as fallback when projection of names into a
     /// particular font model produces a subfamily name containing only
     /// elidable elements.
-    pub elided_fallback_name_id: Option<u16>,
+    pub elided_fallback_name_id: Option<NameId>,
 }
 
 impl FontWrite for Stat {
     #[allow(clippy::unnecessary_cast)]
     fn write_into(&self, writer: &mut TableWriter) {
-        let version = MajorMinor::VERSION_1_2 as MajorMinor;
+        let version = self.compute_version() as MajorMinor;
         version.write_into(writer);
         (8 as u16).write_into(writer);
         (array_len(&self.design_axes).unwrap() as u16).write_into(writer);
         self.design_axes.write_into(writer);
         (array_len(&self.offset_to_axis_values).unwrap() as u16).write_into(writer);
         self.offset_to_axis_values.write_into(writer);
-        version.compatible((1, 1)).then(|| {
+        version.compatible(MajorMinor::new(1, 1)).then(|| {
             self.elided_fallback_name_id
                 .as_ref()
                 .expect("missing versioned field should have failed validation")
                 .write_into(writer)
         });
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Stat {
     fn validate_impl(&self, ctx: &mut ValidationCtx) {
         ctx.in_table("Stat", |ctx| {
-            let version: MajorMinor = MajorMinor::VERSION_1_2;
+            let version: MajorMinor = self.compute_version();
             ctx.in_field("design_axes", |ctx| {
-                self.design_axes.validate_impl(ctx);
+                self.validate_design_axes(ctx);
             });
             ctx.in_field("offset_to_axis_values", |ctx| {
-                self.offset_to_axis_values.validate_impl(ctx);
+                self.validate_axis_values(ctx);
             });
             ctx.in_field("elided_fallback_name_id", |ctx| {
-                if version.compatible((1, 1)) && self.elided_fallback_name_id.is_none() {
+                if version.compatible(MajorMinor::new(1, 1))
+                    && self.elided_fallback_name_id.is_none()
+                {
                     ctx.report(format!("field must be present for version {version}"));
                 }
             });
@@ -89,12 +95,13 @@ impl<'a> FontRead<'a> for Stat {
 
 /// [Axis Records](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-records)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AxisRecord {
     /// A tag identifying the axis of design variation.
     pub axis_tag: Tag,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this axis.
-    pub axis_name_id: u16,
+    pub axis_name_id: NameId,
     /// A value that applications can use to determine primary sorting
     /// of face names, or for ordering of labels when composing family
     /// or face names.
@@ -103,7 +110,7 @@ pub struct AxisRecord {
 
 impl AxisRecord {
     /// Construct a new `AxisRecord`
-    pub fn new(axis_tag: Tag, axis_name_id: u16, axis_ordering: u16) -> Self {
+    pub fn new(axis_tag: Tag, axis_name_id: NameId, axis_ordering: u16) -> Self {
         Self {
             axis_tag,
             axis_name_id,
@@ -136,6 +143,7 @@ impl FromObjRef<read_fonts::tables::stat::AxisRecord> for AxisRecord {
 
 /// An array of [AxisValue] tables.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AxisValueArray {
     /// Array of offsets to axis value tables, in bytes from the start
     /// of the axis value offsets array.
@@ -182,6 +190,7 @@ impl<'a> FromTableRef<read_fonts::tables::stat::AxisValueArray<'a>> for AxisValu
 
 /// [Axis Value Tables](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-tables)
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum AxisValue {
     Format1(AxisValueFormat1),
     Format2(AxisValueFormat2),
@@ -194,7 +203,7 @@ impl AxisValue {
     pub fn format_1(
         axis_index: u16,
         flags: AxisValueTableFlags,
-        value_name_id: u16,
+        value_name_id: NameId,
         value: Fixed,
     ) -> Self {
         Self::Format1(AxisValueFormat1::new(
@@ -209,7 +218,7 @@ impl AxisValue {
     pub fn format_2(
         axis_index: u16,
         flags: AxisValueTableFlags,
-        value_name_id: u16,
+        value_name_id: NameId,
         nominal_value: Fixed,
         range_min_value: Fixed,
         range_max_value: Fixed,
@@ -228,7 +237,7 @@ impl AxisValue {
     pub fn format_3(
         axis_index: u16,
         flags: AxisValueTableFlags,
-        value_name_id: u16,
+        value_name_id: NameId,
         value: Fixed,
         linked_value: Fixed,
     ) -> Self {
@@ -244,7 +253,7 @@ impl AxisValue {
     /// Construct a new `AxisValueFormat4` subtable
     pub fn format_4(
         flags: AxisValueTableFlags,
-        value_name_id: u16,
+        value_name_id: NameId,
         axis_values: Vec<AxisValueRecord>,
     ) -> Self {
         Self::Format4(AxisValueFormat4::new(flags, value_name_id, axis_values))
@@ -301,6 +310,7 @@ impl<'a> FontRead<'a> for AxisValue {
 
 /// [Axis value table format 1](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-table-format-1)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AxisValueFormat1 {
     /// Zero-base index into the axis record array identifying the axis
     /// of design variation to which the axis value table applies. Must
@@ -310,7 +320,7 @@ pub struct AxisValueFormat1 {
     pub flags: AxisValueTableFlags,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this attribute value.
-    pub value_name_id: u16,
+    pub value_name_id: NameId,
     /// A numeric value for this attribute value.
     pub value: Fixed,
 }
@@ -320,7 +330,7 @@ impl AxisValueFormat1 {
     pub fn new(
         axis_index: u16,
         flags: AxisValueTableFlags,
-        value_name_id: u16,
+        value_name_id: NameId,
         value: Fixed,
     ) -> Self {
         Self {
@@ -369,6 +379,7 @@ impl<'a> FontRead<'a> for AxisValueFormat1 {
 
 /// [Axis value table format 2](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-table-format-2)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AxisValueFormat2 {
     /// Zero-base index into the axis record array identifying the axis
     /// of design variation to which the axis value table applies. Must
@@ -378,7 +389,7 @@ pub struct AxisValueFormat2 {
     pub flags: AxisValueTableFlags,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this attribute value.
-    pub value_name_id: u16,
+    pub value_name_id: NameId,
     /// A nominal numeric value for this attribute value.
     pub nominal_value: Fixed,
     /// The minimum value for a range associated with the specified
@@ -394,7 +405,7 @@ impl AxisValueFormat2 {
     pub fn new(
         axis_index: u16,
         flags: AxisValueTableFlags,
-        value_name_id: u16,
+        value_name_id: NameId,
         nominal_value: Fixed,
         range_min_value: Fixed,
         range_max_value: Fixed,
@@ -451,6 +462,7 @@ impl<'a> FontRead<'a> for AxisValueFormat2 {
 
 /// [Axis value table format 3](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-table-format-3)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AxisValueFormat3 {
     /// Zero-base index into the axis record array identifying the axis
     /// of design variation to which the axis value table applies. Must
@@ -460,7 +472,7 @@ pub struct AxisValueFormat3 {
     pub flags: AxisValueTableFlags,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this attribute value.
-    pub value_name_id: u16,
+    pub value_name_id: NameId,
     /// A numeric value for this attribute value.
     pub value: Fixed,
     /// The numeric value for a style-linked mapping from this value.
@@ -472,7 +484,7 @@ impl AxisValueFormat3 {
     pub fn new(
         axis_index: u16,
         flags: AxisValueTableFlags,
-        value_name_id: u16,
+        value_name_id: NameId,
         value: Fixed,
         linked_value: Fixed,
     ) -> Self {
@@ -525,12 +537,13 @@ impl<'a> FontRead<'a> for AxisValueFormat3 {
 
 /// [Axis value table format 4](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-table-format-4)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AxisValueFormat4 {
     /// Flags — see below for details.
     pub flags: AxisValueTableFlags,
     /// The name ID for entries in the 'name' table that provide a
     /// display string for this combination of axis values.
-    pub value_name_id: u16,
+    pub value_name_id: NameId,
     /// Array of AxisValue records that provide the combination of axis
     /// values, one for each contributing axis.
     pub axis_values: Vec<AxisValueRecord>,
@@ -540,7 +553,7 @@ impl AxisValueFormat4 {
     /// Construct a new `AxisValueFormat4`
     pub fn new(
         flags: AxisValueTableFlags,
-        value_name_id: u16,
+        value_name_id: NameId,
         axis_values: Vec<AxisValueRecord>,
     ) -> Self {
         Self {
@@ -597,6 +610,7 @@ impl<'a> FontRead<'a> for AxisValueFormat4 {
 
 /// Part of [AxisValueFormat4]
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct AxisValueRecord {
     /// Zero-base index into the axis record array identifying the axis
     /// to which this value applies. Must be less than designAxisCount.
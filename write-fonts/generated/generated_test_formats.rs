@@ -6,6 +6,7 @@
 use crate::codegen_prelude::*;
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Table1 {
     pub heft: u32,
     pub flex: u16,
@@ -50,6 +51,7 @@ impl<'a> FontRead<'a> for Table1 {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Table2 {
     pub values: Vec<u16>,
 }
@@ -103,6 +105,7 @@ impl<'a> FontRead<'a> for Table2 {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Table3 {
     pub something: u16,
 }
@@ -137,6 +140,7 @@ impl<'a> FontRead<'a> for Table3 {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum MyTable {
     Format1(Table1),
     MyFormat22(Table2),
@@ -6,6 +6,7 @@
 use crate::codegen_prelude::*;
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct KindsOfOffsets {
     /// The major/minor version of the GDEF table
     pub version: MajorMinor,
@@ -51,16 +52,16 @@ impl FontWrite for KindsOfOffsets {
         self.array.write_into(writer);
         self.record_array.write_into(writer);
         version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| self.versioned_nullable_record_array.write_into(writer));
-        version.compatible((1, 1)).then(|| {
+        version.compatible(MajorMinor::new(1, 1)).then(|| {
             self.versioned_nonnullable
                 .as_ref()
                 .expect("missing versioned field should have failed validation")
                 .write_into(writer)
         });
         version
-            .compatible((1, 1))
+            .compatible(MajorMinor::new(1, 1))
             .then(|| self.versioned_nullable.write_into(writer));
     }
 }
@@ -82,7 +83,8 @@ impl Validate for KindsOfOffsets {
                 self.versioned_nullable_record_array.validate_impl(ctx);
             });
             ctx.in_field("versioned_nonnullable", |ctx| {
-                if version.compatible((1, 1)) && self.versioned_nonnullable.is_none() {
+                if version.compatible(MajorMinor::new(1, 1)) && self.versioned_nonnullable.is_none()
+                {
                     ctx.report(format!("field must be present for version {version}"));
                 }
                 self.versioned_nonnullable.validate_impl(ctx);
@@ -130,6 +132,7 @@ impl<'a> FontRead<'a> for KindsOfOffsets {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct KindsOfArraysOfOffsets {
     /// A normal array offset
     pub nonnullables: Vec<OffsetMarker<Dummy>>,
@@ -149,13 +152,13 @@ impl FontWrite for KindsOfArraysOfOffsets {
         (array_len(&self.nonnullables).unwrap() as u16).write_into(writer);
         self.nonnullables.write_into(writer);
         self.nullables.write_into(writer);
-        version.compatible((1, 1)).then(|| {
+        version.compatible(MajorMinor::new(1, 1)).then(|| {
             self.versioned_nonnullables
                 .as_ref()
                 .expect("missing versioned field should have failed validation")
                 .write_into(writer)
         });
-        version.compatible((1, 1)).then(|| {
+        version.compatible(MajorMinor::new(1, 1)).then(|| {
             self.versioned_nullables
                 .as_ref()
                 .expect("missing versioned field should have failed validation")
@@ -181,7 +184,9 @@ impl Validate for KindsOfArraysOfOffsets {
                 self.nullables.validate_impl(ctx);
             });
             ctx.in_field("versioned_nonnullables", |ctx| {
-                if version.compatible((1, 1)) && self.versioned_nonnullables.is_none() {
+                if version.compatible(MajorMinor::new(1, 1))
+                    && self.versioned_nonnullables.is_none()
+                {
                     ctx.report(format!("field must be present for version {version}"));
                 }
                 if self.versioned_nonnullables.is_some()
@@ -192,7 +197,7 @@ impl Validate for KindsOfArraysOfOffsets {
                 self.versioned_nonnullables.validate_impl(ctx);
             });
             ctx.in_field("versioned_nullables", |ctx| {
-                if version.compatible((1, 1)) && self.versioned_nullables.is_none() {
+                if version.compatible(MajorMinor::new(1, 1)) && self.versioned_nullables.is_none() {
                     ctx.report(format!("field must be present for version {version}"));
                 }
                 if self.versioned_nullables.is_some()
@@ -239,6 +244,7 @@ impl<'a> FontRead<'a> for KindsOfArraysOfOffsets {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct KindsOfArrays {
     pub version: u16,
     /// an array of scalars
@@ -355,6 +361,7 @@ impl<'a> FontRead<'a> for KindsOfArrays {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Dummy {
     pub value: u16,
 }
@@ -388,6 +395,7 @@ impl<'a> FontRead<'a> for Dummy {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Shmecord {
     pub length: u16,
     pub breadth: u32,
@@ -6,6 +6,7 @@
 use crate::codegen_prelude::*;
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BasicTable {
     pub simple_records: Vec<SimpleRecord>,
     pub array_records: Vec<ContainsArrays>,
@@ -75,6 +76,7 @@ impl<'a> FontRead<'a> for BasicTable {
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct SimpleRecord {
     pub val1: u16,
     pub va2: u32,
@@ -108,6 +110,7 @@ impl FromObjRef<read_fonts::codegen_test::records::SimpleRecord> for SimpleRecor
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ContainsArrays {
     pub scalars: Vec<u16>,
     pub records: Vec<SimpleRecord>,
@@ -161,6 +164,7 @@ impl FromObjRef<read_fonts::codegen_test::records::ContainsArrays<'_>> for Conta
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ContainsOffests {
     pub array: OffsetMarker<Vec<SimpleRecord>>,
     pub other: OffsetMarker<BasicTable, WIDTH_32>,
@@ -9,6 +9,7 @@ pub use read_fonts::tables::variations::EntryFormat;
 
 /// [TupleVariationHeader](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#tuplevariationheader)
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct TupleVariationHeader {
     /// The size in bytes of the serialized data for this tuple
     /// variation table.
@@ -93,6 +94,7 @@ impl<'a> FromTableRef<read_fonts::tables::variations::TupleVariationHeader<'a>>
 /// variation space using tuple records. A tuple record identifies a position
 /// in terms of normalized coordinates, which use F2DOT14 values.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Tuple {
     /// Coordinate array specifying a position within the font’s variation space.
     ///
@@ -138,6 +140,7 @@ impl FromObjRef<read_fonts::tables::variations::Tuple<'_>> for Tuple {
 
 /// The [DeltaSetIndexMap](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#associating-target-items-to-variation-data) table format 0
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DeltaSetIndexMapFormat0 {
     /// A packed field that describes the compressed representation of
     /// delta-set indices. See details below.
@@ -203,6 +206,7 @@ impl<'a> FontRead<'a> for DeltaSetIndexMapFormat0 {
 
 /// The [DeltaSetIndexMap](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#associating-target-items-to-variation-data) table format 1
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct DeltaSetIndexMapFormat1 {
     /// A packed field that describes the compressed representation of
     /// delta-set indices. See details below.
@@ -268,6 +272,7 @@ impl<'a> FontRead<'a> for DeltaSetIndexMapFormat1 {
 
 /// The [DeltaSetIndexMap](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#associating-target-items-to-variation-data) table
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum DeltaSetIndexMap {
     Format0(DeltaSetIndexMapFormat0),
     Format1(DeltaSetIndexMapFormat1),
@@ -344,6 +349,7 @@ impl FontWrite for EntryFormat {
 
 /// The [VariationRegionList](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#variation-regions) table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VariationRegionList {
     /// Array of variation regions.
     pub variation_regions: Vec<VariationRegion>,
@@ -372,7 +378,7 @@ impl Validate for VariationRegionList {
                 if self.variation_regions.len() > (u16::MAX as usize) {
                     ctx.report("array exceeds max length");
                 }
-                self.variation_regions.validate_impl(ctx);
+                self.validate_variation_regions(ctx);
             });
         })
     }
@@ -410,6 +416,7 @@ impl<'a> FontRead<'a> for VariationRegionList {
 
 /// The [VariationRegion](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#variation-regions) record
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct VariationRegion {
     /// Array of region axis coordinates records, in the order of axes
     /// given in the 'fvar' table.
@@ -457,6 +464,7 @@ impl FromObjRef<read_fonts::tables::variations::VariationRegion<'_>> for Variati
 
 /// The [RegionAxisCoordinates](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#variation-regions) record
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RegionAxisCoordinates {
     /// The region start coordinate value for the current axis.
     pub start_coord: F2Dot14,
@@ -504,6 +512,7 @@ impl FromObjRef<read_fonts::tables::variations::RegionAxisCoordinates> for Regio
 
 /// The [ItemVariationStore](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#item-variation-store-header-and-item-variation-data-subtables) table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ItemVariationStore {
     /// Format— set to 1
     pub format: u16,
@@ -586,6 +595,7 @@ impl<'a> FontRead<'a> for ItemVariationStore {
 
 /// The [ItemVariationData](https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#item-variation-store-header-and-item-variation-data-subtables) subtable
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ItemVariationData {
     /// The number of delta sets for distinct items.
     pub item_count: u16,
@@ -634,6 +644,9 @@ impl Validate for ItemVariationData {
                     ctx.report("array exceeds max length");
                 }
             });
+            ctx.in_field("delta_sets", |ctx| {
+                self.validate_delta_sets(ctx);
+            });
         })
     }
 }
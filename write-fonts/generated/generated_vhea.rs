@@ -7,6 +7,7 @@ use crate::codegen_prelude::*;
 
 /// The [vhea](https://docs.microsoft.com/en-us/typography/opentype/spec/vhea) Vertical Header Table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Vhea {
     /// Typographic ascent.
     pub ascender: FWord,
@@ -90,6 +91,9 @@ impl FontWrite for Vhea {
         (0 as i16).write_into(writer);
         self.number_of_long_ver_metrics.write_into(writer);
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Vhea {
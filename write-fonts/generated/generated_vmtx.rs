@@ -7,6 +7,7 @@ use crate::codegen_prelude::*;
 
 /// The [vmtx (Vertical Metrics)](https://docs.microsoft.com/en-us/typography/opentype/spec/vmtx) table
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Vmtx {
     /// Paired advance height and top side bearing values for each
     /// glyph. Records are indexed by glyph ID.
@@ -30,6 +31,9 @@ impl FontWrite for Vmtx {
         self.v_metrics.write_into(writer);
         self.top_side_bearings.write_into(writer);
     }
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
 }
 
 impl Validate for Vmtx {
@@ -0,0 +1,202 @@
+//! Removing unwanted tables before a font is distributed.
+//!
+//! Font editors and build tools often leave tables in a font that are of no
+//! use to the applications that will display it, such as the sources of
+//! hinting programs or build timestamps. A [`TableCleaner`] removes these,
+//! along with empty tables and the obsolete `DSIG` table, in the same way
+//! as the fixers in fontTools and gftools.
+//!
+//! ```no_run
+//! # let path_to_font = "";
+//! use write_fonts::{cleanup::TableCleaner, read::FontRef};
+//!
+//! let data = std::fs::read(path_to_font).unwrap();
+//! let font = FontRef::new(&data).unwrap();
+//! let cleaned = TableCleaner::new(font).stub_dsig(true).clean();
+//! println!("removed {:?}", cleaned.removed_tables);
+//! ```
+
+use std::collections::BTreeSet;
+
+use read_fonts::FontRef;
+use types::Tag;
+
+use crate::FontBuilder;
+
+/// Tables that are only used by font editors and build tools.
+///
+/// These are the tables removed by gftools when fixing fonts for
+/// distribution: the FontForge timestamp, the ttfautohint parameters, the
+/// VTT hinting sources, and the build debug information.
+pub const TOOL_TABLES: [Tag; 9] = [
+    Tag::new(b"FFTM"),
+    Tag::new(b"TTFA"),
+    Tag::new(b"TSI0"),
+    Tag::new(b"TSI1"),
+    Tag::new(b"TSI2"),
+    Tag::new(b"TSI3"),
+    Tag::new(b"TSI5"),
+    Tag::new(b"prop"),
+    Tag::new(b"Debg"),
+];
+
+/// An empty `DSIG` table: version 1, with no signatures and no flags.
+const DSIG_STUB: [u8; 8] = [0, 0, 0, 1, 0, 0, 0, 0];
+
+/// Removes unwanted tables from a font.
+///
+/// By default this removes `DSIG`, the tables listed in [`TOOL_TABLES`],
+/// and any table with no data. The other tables are copied unchanged, and
+/// the table directory is rewritten in tag order with new checksums.
+#[derive(Clone)]
+pub struct TableCleaner<'a> {
+    font: FontRef<'a>,
+    stub_dsig: bool,
+    remove: BTreeSet<Tag>,
+    keep: BTreeSet<Tag>,
+}
+
+/// A font with unwanted tables removed.
+#[derive(Clone, Debug)]
+pub struct CleanedFont {
+    /// The binary data of the new font.
+    pub data: Vec<u8>,
+    /// The tables that were removed, in tag order.
+    pub removed_tables: Vec<Tag>,
+}
+
+impl<'a> TableCleaner<'a> {
+    /// Create a cleaner for this font, with the default set of tables to
+    /// remove.
+    pub fn new(font: FontRef<'a>) -> Self {
+        TableCleaner {
+            font,
+            stub_dsig: false,
+            remove: TOOL_TABLES.into_iter().chain([Tag::DSIG]).collect(),
+            keep: BTreeSet::new(),
+        }
+    }
+
+    /// Replace the `DSIG` table with an empty one, rather than removing it.
+    ///
+    /// The empty table is added if the font has no `DSIG`. Some older
+    /// applications, such as versions of Microsoft Office before 2016, only
+    /// show OpenType features for fonts that have a `DSIG` table; a signature
+    /// is not checked, and would in any case be invalidated by editing the
+    /// font.
+    pub fn stub_dsig(&mut self, stub_dsig: bool) -> &mut Self {
+        self.stub_dsig = stub_dsig;
+        self
+    }
+
+    /// Also remove the table with this tag.
+    pub fn remove_table(&mut self, tag: Tag) -> &mut Self {
+        self.keep.remove(&tag);
+        self.remove.insert(tag);
+        self
+    }
+
+    /// Keep the table with this tag, even if it would be removed by default.
+    pub fn keep_table(&mut self, tag: Tag) -> &mut Self {
+        self.remove.remove(&tag);
+        self.keep.insert(tag);
+        self
+    }
+
+    /// Remove the unwanted tables, returning the new font.
+    pub fn clean(&self) -> CleanedFont {
+        let mut builder = FontBuilder::from(self.font.clone());
+        let mut removed_tables = Vec::new();
+        for record in self.font.table_records() {
+            let tag = record.tag();
+            if self.keep.contains(&tag) || (tag == Tag::DSIG && self.stub_dsig) {
+                continue;
+            }
+            if self.remove.contains(&tag) || record.length() == 0 {
+                builder.remove_table(tag);
+                removed_tables.push(tag);
+            }
+        }
+        if self.stub_dsig {
+            builder.add_table(Tag::DSIG, DSIG_STUB.as_slice());
+        }
+        removed_tables.sort_unstable();
+        removed_tables.dedup();
+        CleanedFont {
+            data: builder.build(),
+            removed_tables,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::TableProvider;
+
+    use super::*;
+    const FFTM: Tag = Tag::new(b"FFTM");
+    const EMPT: Tag = Tag::new(b"EMPT");
+
+    fn make_font(tables: &[(Tag, &[u8])]) -> Vec<u8> {
+        let mut builder = FontBuilder::default();
+        for (tag, data) in tables {
+            builder.add_table(*tag, *data);
+        }
+        builder.build()
+    }
+
+    fn tags(data: &[u8]) -> Vec<Tag> {
+        FontRef::new(data).unwrap().table_tags().collect()
+    }
+
+    #[test]
+    fn remove_default_tables() {
+        let data = make_font(&[
+            (Tag::HEAD, &[0; 54]),
+            (Tag::CMAP, &[1, 2, 3, 4]),
+            (Tag::DSIG, &[0, 0, 0, 1, 0, 0, 0, 0]),
+            (FFTM, &[5; 28]),
+            (EMPT, &[]),
+        ]);
+        let cleaned = TableCleaner::new(FontRef::new(&data).unwrap()).clean();
+        assert_eq!(cleaned.removed_tables, [Tag::DSIG, EMPT, FFTM]);
+        assert_eq!(tags(&cleaned.data), [Tag::CMAP, Tag::HEAD]);
+        let font = FontRef::new(&cleaned.data).unwrap();
+        assert_eq!(font.table_data(Tag::CMAP).unwrap().as_ref(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn stub_dsig() {
+        let data = make_font(&[(Tag::HEAD, &[0; 54]), (Tag::DSIG, &[0xFF; 100])]);
+        let font = FontRef::new(&data).unwrap();
+        let cleaned = TableCleaner::new(font.clone()).stub_dsig(true).clean();
+        assert!(cleaned.removed_tables.is_empty());
+        let new_font = FontRef::new(&cleaned.data).unwrap();
+        assert_eq!(new_font.table_data(Tag::DSIG).unwrap().as_ref(), DSIG_STUB);
+
+        // a stub is added if there is no DSIG
+        let data = make_font(&[(Tag::HEAD, &[0; 54])]);
+        let font = FontRef::new(&data).unwrap();
+        let cleaned = TableCleaner::new(font).stub_dsig(true).clean();
+        assert_eq!(tags(&cleaned.data), [Tag::DSIG, Tag::HEAD]);
+    }
+
+    #[test]
+    fn keep_and_remove() {
+        let data = make_font(&[
+            (Tag::HEAD, &[0; 54]),
+            (Tag::CMAP, &[1; 4]),
+            (FFTM, &[5; 28]),
+        ]);
+        let font = FontRef::new(&data).unwrap();
+        let cleaned = TableCleaner::new(font)
+            .keep_table(FFTM)
+            .remove_table(Tag::CMAP)
+            .clean();
+        assert_eq!(cleaned.removed_tables, [Tag::CMAP]);
+        assert_eq!(tags(&cleaned.data), [FFTM, Tag::HEAD]);
+        // the checksum adjustment is recomputed
+        let head = FontRef::new(&cleaned.data).unwrap().head().unwrap();
+        assert_ne!(head.checksum_adjustment(), 0);
+    }
+}
@@ -0,0 +1,597 @@
+//! Removing duplicate glyph data.
+//!
+//! Fonts often contain glyphs with identical outlines, such as the Latin,
+//! Greek and Cyrillic capital 'A', or glyphs that differ only in their
+//! layout behaviour. [`dedup_glyphs`] rewrites the `glyf` table of a font so
+//! that each repeated simple glyph refers to the first copy of its outline
+//! instead of repeating it; see [`GlyfLocaBuilder::deduplicate_glyphs`] for
+//! the details.
+//!
+//! Repeated color bitmaps in the `CBDT` and `sbix` tables are stored once in
+//! the same way: each index entry of a repeated image in `CBLC` locates the
+//! first copy of its data, and each repeated `sbix` bitmap is replaced by a
+//! reference to the first glyph with the same data.
+
+use std::fmt::Display;
+
+use read_fonts::{tables::glyf::Glyph as ReadGlyph, FontData, FontRef, ReadError, TableProvider};
+use types::{GlyphId, Tag};
+
+use crate::{
+    dump_table,
+    from_obj::ToOwnedTable,
+    tables::{
+        cbdt::{self, Image, IMAGE_FORMAT_NO_METRICS, IMAGE_FORMAT_SMALL_METRICS},
+        cblc::{BigGlyphMetrics, BitmapSize, Cblc, SbitLineMetrics},
+        glyf::{GlyfLocaBuilder, Glyph},
+        head::Head,
+        maxp::Maxp,
+        sbix::{GlyphData, Sbix, Strike},
+    },
+    FontBuilder,
+};
+
+/// An error that prevents the glyphs of a font from being deduplicated.
+#[derive(Debug)]
+pub enum DedupError {
+    /// The font is variable; repeated glyphs cannot be replaced, since the
+    /// `gvar` deltas of a composite glyph apply to its components.
+    VariableFont,
+    /// The font is missing a required table.
+    ///
+    /// In particular, this is returned for fonts that do not have TrueType
+    /// outlines.
+    MissingTable(Tag),
+    /// A table in the font could not be read.
+    Read(ReadError),
+    /// A rebuilt table could not be written.
+    Write(crate::Error),
+}
+
+/// Replace repeated simple glyphs in a font with references to the first
+/// copy of each outline.
+///
+/// This rebuilds the `glyf` and `loca` tables, and updates `head` and `maxp`
+/// to match. If there are no repeated glyphs, the new `glyf` table has the
+/// same contents as the original.
+///
+/// The `CBDT` and `CBLC` tables, and the `sbix` table, are rebuilt so that
+/// repeated bitmaps are stored once, but are only replaced if that makes
+/// them smaller. Color bitmaps with big metrics (image format 18) are not
+/// supported, and are copied unchanged.
+///
+/// The other tables are copied unchanged, except for `DSIG`, which is
+/// dropped.
+pub fn dedup_glyphs(font: &FontRef) -> Result<Vec<u8>, DedupError> {
+    if font.is_variable() {
+        return Err(DedupError::VariableFont);
+    }
+    for tag in [Tag::GLYF, Tag::LOCA, Tag::HEAD, Tag::MAXP] {
+        if !font.has_table(tag) {
+            return Err(DedupError::MissingTable(tag));
+        }
+    }
+    let loca = font.loca(None)?;
+    let glyf = font.glyf()?;
+    let mut maxp: Maxp = font.maxp()?.to_owned_table();
+
+    let mut builder = GlyfLocaBuilder::new();
+    builder.deduplicate_glyphs();
+    for gid in 0..maxp.num_glyphs {
+        let glyph = match loca.get_glyf(GlyphId::new(gid), &glyf)? {
+            Some(glyph) => glyph.to_owned_table(),
+            None => Glyph::Empty,
+        };
+        builder.add_glyph(&glyph);
+    }
+
+    // each repeated glyph is now a composite with a single component
+    for (_, original) in builder.duplicates() {
+        let Some(ReadGlyph::Simple(original)) = loca.get_glyf(*original, &glyf)? else {
+            continue;
+        };
+        let update = |field: &mut Option<u16>, value: u16| {
+            if let Some(field) = field {
+                *field = (*field).max(value);
+            }
+        };
+        update(&mut maxp.max_composite_points, original.num_points() as u16);
+        update(
+            &mut maxp.max_composite_contours,
+            original.number_of_contours() as u16,
+        );
+        update(&mut maxp.max_component_elements, 1);
+        update(&mut maxp.max_component_depth, 1);
+    }
+
+    let (glyf, loca, loca_format) = builder.build();
+    let mut head: Head = font.head()?.to_owned_table();
+    head.set_loca_format(loca_format);
+
+    let mut builder = FontBuilder::default();
+    for (tag, table) in [
+        (Tag::GLYF, dump_table(&glyf)),
+        (Tag::LOCA, dump_table(&loca)),
+        (Tag::HEAD, dump_table(&head)),
+        (Tag::MAXP, dump_table(&maxp)),
+    ] {
+        builder.add_table(tag, table.map_err(DedupError::Write)?);
+    }
+    // the tables that are not copied from the original font
+    let mut replaced = vec![Tag::GLYF, Tag::LOCA, Tag::HEAD, Tag::MAXP, Tag::DSIG];
+    if let (Some(cblc), Some(cbdt)) = (font.table_data(Tag::CBLC), font.table_data(Tag::CBDT)) {
+        if let Some(strikes) = read_color_bitmaps(cblc, cbdt)? {
+            let (new_cblc, new_cbdt) = write_color_bitmaps(strikes);
+            let new_cblc = dump_table(&new_cblc).map_err(DedupError::Write)?;
+            if new_cblc.len() + new_cbdt.len() < cblc.len() + cbdt.len() {
+                builder.add_table(Tag::CBLC, new_cblc);
+                builder.add_table(Tag::CBDT, new_cbdt);
+                replaced.extend([Tag::CBLC, Tag::CBDT]);
+            }
+        }
+    }
+    if let Some(data) = font.table_data(Tag::SBIX) {
+        let mut sbix = read_sbix(data, maxp.num_glyphs)?;
+        sbix.strikes.iter_mut().for_each(Strike::share_duplicates);
+        let new_sbix = dump_table(&sbix).map_err(DedupError::Write)?;
+        if new_sbix.len() < data.len() {
+            builder.add_table(Tag::SBIX, new_sbix);
+            replaced.push(Tag::SBIX);
+        }
+    }
+    for record in font.table_records() {
+        let tag = record.tag();
+        if replaced.contains(&tag) {
+            continue;
+        }
+        if let Some(data) = font.data_for_record(record) {
+            builder.add_table(tag, data.as_ref().to_owned());
+        }
+    }
+    Ok(builder.build())
+}
+
+/// A strike of color bitmaps, without its index subtables, and its images.
+type ColorStrike = (BitmapSize, Vec<Image>);
+
+/// Read the images of each strike in the `CBLC` and `CBDT` tables.
+///
+/// Returns `None` if an image is in a format that can't be rebuilt.
+fn read_color_bitmaps(
+    cblc: FontData,
+    cbdt: FontData,
+) -> Result<Option<Vec<ColorStrike>>, ReadError> {
+    const BITMAP_SIZE_LEN: usize = 48;
+    let num_sizes = cblc.read_at::<u32>(4)? as usize;
+    let mut strikes = Vec::new();
+    for i in 0..num_sizes {
+        let record = 8 + i * BITMAP_SIZE_LEN;
+        let size = BitmapSize {
+            hori: read_line_metrics(cblc, record + 16)?,
+            vert: read_line_metrics(cblc, record + 28)?,
+            ppem_x: cblc.read_at(record + 44)?,
+            ppem_y: cblc.read_at(record + 45)?,
+            bit_depth: cblc.read_at(record + 46)?,
+            flags: cblc.read_at(record + 47)?,
+            index_subtables: Vec::new(),
+        };
+        let list = cblc.read_at::<u32>(record)? as usize;
+        let num_subtables = cblc.read_at::<u32>(record + 8)? as usize;
+        let mut images = Vec::new();
+        for j in 0..num_subtables {
+            let subtable_record = list + j * 8;
+            let first_glyph = cblc.read_at::<u16>(subtable_record)?;
+            let last_glyph = cblc.read_at::<u16>(subtable_record + 2)?;
+            let subtable = list + cblc.read_at::<u32>(subtable_record + 4)? as usize;
+            let index_format = cblc.read_at::<u16>(subtable)?;
+            let image_format = cblc.read_at::<u16>(subtable + 2)?;
+            let image_data = cblc.read_at::<u32>(subtable + 4)? as usize;
+            // the glyph, and the start and end of its image in `CBDT`
+            let mut locations = Vec::new();
+            let mut shared_metrics = None;
+            let range = first_glyph..=last_glyph;
+            match index_format {
+                1 | 3 => {
+                    let offset_len = if index_format == 1 { 4 } else { 2 };
+                    let read_offset = |k: usize| -> Result<usize, ReadError> {
+                        let pos = subtable + 8 + k * offset_len;
+                        match index_format {
+                            1 => cblc.read_at::<u32>(pos).map(|offset| offset as usize),
+                            _ => cblc.read_at::<u16>(pos).map(|offset| offset as usize),
+                        }
+                    };
+                    for (k, glyph) in range.enumerate() {
+                        let (start, end) = (read_offset(k)?, read_offset(k + 1)?);
+                        locations.push((glyph, image_data + start, image_data + end));
+                    }
+                }
+                2 | 5 => {
+                    let image_size = cblc.read_at::<u32>(subtable + 8)? as usize;
+                    shared_metrics = Some(read_big_metrics(cblc, subtable + 12)?);
+                    let glyphs = if index_format == 2 {
+                        range.collect::<Vec<_>>()
+                    } else {
+                        let num_glyphs = cblc.read_at::<u32>(subtable + 20)? as usize;
+                        (0..num_glyphs)
+                            .map(|k| cblc.read_at::<u16>(subtable + 24 + k * 2))
+                            .collect::<Result<_, _>>()?
+                    };
+                    for (k, glyph) in glyphs.into_iter().enumerate() {
+                        let start = image_data + k * image_size;
+                        locations.push((glyph, start, start + image_size));
+                    }
+                }
+                4 => {
+                    let num_glyphs = cblc.read_at::<u32>(subtable + 8)? as usize;
+                    let pair = |k: usize| -> Result<(u16, usize), ReadError> {
+                        let pos = subtable + 12 + k * 4;
+                        Ok((cblc.read_at(pos)?, cblc.read_at::<u16>(pos + 2)? as usize))
+                    };
+                    for k in 0..num_glyphs {
+                        let ((glyph, start), (_, end)) = (pair(k)?, pair(k + 1)?);
+                        locations.push((glyph, image_data + start, image_data + end));
+                    }
+                }
+                _ => return Ok(None),
+            }
+            for (glyph, start, end) in locations {
+                let image = cbdt.slice(start..end).ok_or(ReadError::OutOfBounds)?;
+                let (metrics, png_start) = match (image_format, shared_metrics) {
+                    (IMAGE_FORMAT_SMALL_METRICS, _) => {
+                        let metrics = BigGlyphMetrics {
+                            height: image.read_at(0)?,
+                            width: image.read_at(1)?,
+                            hori_bearing_x: image.read_at(2)?,
+                            hori_bearing_y: image.read_at(3)?,
+                            hori_advance: image.read_at(4)?,
+                            ..Default::default()
+                        };
+                        (metrics, cbdt::SMALL_METRICS_LEN)
+                    }
+                    // images that get their own metrics when rebuilt can't
+                    // keep vertical metrics
+                    (IMAGE_FORMAT_NO_METRICS, Some(metrics))
+                        if metrics.vert_bearing_x == 0
+                            && metrics.vert_bearing_y == 0
+                            && metrics.vert_advance == 0 =>
+                    {
+                        (metrics, 0)
+                    }
+                    _ => return Ok(None),
+                };
+                let png_len = image.read_at::<u32>(png_start)? as usize;
+                let png = image.read_array::<u8>(png_start + 4..png_start + 4 + png_len)?;
+                images.push(Image {
+                    glyph: GlyphId::new(glyph),
+                    metrics,
+                    png: png.to_vec(),
+                });
+            }
+        }
+        images.sort_by_key(|image| image.glyph);
+        images.dedup_by_key(|image| image.glyph);
+        strikes.push((size, images));
+    }
+    Ok(Some(strikes))
+}
+
+fn read_line_metrics(data: FontData, pos: usize) -> Result<SbitLineMetrics, ReadError> {
+    Ok(SbitLineMetrics {
+        ascender: data.read_at(pos)?,
+        descender: data.read_at(pos + 1)?,
+        width_max: data.read_at(pos + 2)?,
+        caret_slope_numerator: data.read_at(pos + 3)?,
+        caret_slope_denominator: data.read_at(pos + 4)?,
+        caret_offset: data.read_at(pos + 5)?,
+        min_origin_sb: data.read_at(pos + 6)?,
+        min_advance_sb: data.read_at(pos + 7)?,
+        max_before_bl: data.read_at(pos + 8)?,
+        min_after_bl: data.read_at(pos + 9)?,
+    })
+}
+
+fn read_big_metrics(data: FontData, pos: usize) -> Result<BigGlyphMetrics, ReadError> {
+    Ok(BigGlyphMetrics {
+        height: data.read_at(pos)?,
+        width: data.read_at(pos + 1)?,
+        hori_bearing_x: data.read_at(pos + 2)?,
+        hori_bearing_y: data.read_at(pos + 3)?,
+        hori_advance: data.read_at(pos + 4)?,
+        vert_bearing_x: data.read_at(pos + 5)?,
+        vert_bearing_y: data.read_at(pos + 6)?,
+        vert_advance: data.read_at(pos + 7)?,
+    })
+}
+
+/// Write the images of each strike, returning the `CBLC` table and the data
+/// of the `CBDT` table.
+fn write_color_bitmaps(strikes: Vec<ColorStrike>) -> (Cblc, Vec<u8>) {
+    // the header: major and minor version
+    let mut data = vec![0, 3, 0, 0];
+    let bitmap_sizes = strikes
+        .into_iter()
+        .map(|(size, images)| BitmapSize {
+            index_subtables: cbdt::write_strike(images, &mut data),
+            ..size
+        })
+        .collect();
+    (Cblc::new(bitmap_sizes), data)
+}
+
+/// Read the bitmaps of each strike in the `sbix` table.
+fn read_sbix(data: FontData, num_glyphs: u16) -> Result<Sbix, ReadError> {
+    const GLYPH_HEADER_LEN: usize = 8;
+    let flags = data.read_at::<u16>(2)?;
+    let num_strikes = data.read_at::<u32>(4)? as usize;
+    let mut strikes = Vec::new();
+    for i in 0..num_strikes {
+        let strike = data.read_at::<u32>(8 + i * 4)? as usize;
+        let offset = |glyph: usize| -> Result<usize, ReadError> {
+            Ok(strike + data.read_at::<u32>(strike + 4 + glyph * 4)? as usize)
+        };
+        let mut glyphs = Vec::with_capacity(num_glyphs as usize);
+        for glyph in 0..num_glyphs as usize {
+            let (start, end) = (offset(glyph)?, offset(glyph + 1)?);
+            if end <= start {
+                glyphs.push(None);
+                continue;
+            }
+            glyphs.push(Some(GlyphData {
+                origin_offset_x: data.read_at(start)?,
+                origin_offset_y: data.read_at(start + 2)?,
+                graphic_type: data.read_at(start + 4)?,
+                data: data
+                    .read_array::<u8>(start + GLYPH_HEADER_LEN..end)?
+                    .to_vec(),
+            }));
+        }
+        strikes.push(Strike {
+            ppem: data.read_at(strike)?,
+            ppi: data.read_at(strike + 2)?,
+            glyphs,
+        });
+    }
+    Ok(Sbix {
+        draw_outlines: flags & 0b10 != 0,
+        strikes,
+    })
+}
+
+impl From<ReadError> for DedupError {
+    fn from(value: ReadError) -> Self {
+        DedupError::Read(value)
+    }
+}
+
+impl Display for DedupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DedupError::VariableFont => write!(f, "variable fonts are not supported"),
+            DedupError::MissingTable(tag) => write!(f, "missing required table '{tag}'"),
+            DedupError::Read(error) => write!(f, "failed to read table: {error}"),
+            DedupError::Write(error) => write!(f, "failed to write table: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for DedupError {}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::tables::glyf::CurvePoint;
+
+    use super::*;
+    use crate::tables::{
+        cblc::{IndexSubtable, IndexSubtableFormat},
+        glyf::{Contour, SimpleGlyph},
+        sbix::DUPE,
+    };
+
+    fn square(size: i16) -> Glyph {
+        let contour = Contour::from(vec![
+            CurvePoint::on_curve(0, 0),
+            CurvePoint::on_curve(0, size),
+            CurvePoint::on_curve(size, size),
+            CurvePoint::on_curve(size, 0),
+        ]);
+        SimpleGlyph::new(vec![contour]).into()
+    }
+
+    /// The start of a PNG file, with some distinguishing pixel data.
+    fn png(pixels: u8) -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend([0, 0, 0, 20, 0, 0, 0, 20, pixels]);
+        png
+    }
+
+    fn make_font(glyphs: &[Glyph]) -> Vec<u8> {
+        make_font_with_tables(glyphs, &[])
+    }
+
+    fn make_font_with_tables(glyphs: &[Glyph], tables: &[(Tag, Vec<u8>)]) -> Vec<u8> {
+        let mut builder = GlyfLocaBuilder::new();
+        for glyph in glyphs {
+            builder.add_glyph(glyph);
+        }
+        let (glyf, loca, format) = builder.build();
+        let mut head = Head::default();
+        head.set_loca_format(format);
+        let maxp = Maxp {
+            max_points: Some(4),
+            max_contours: Some(1),
+            max_composite_points: Some(0),
+            max_composite_contours: Some(0),
+            max_zones: Some(2),
+            max_twilight_points: Some(0),
+            max_storage: Some(0),
+            max_function_defs: Some(0),
+            max_instruction_defs: Some(0),
+            max_stack_elements: Some(0),
+            max_size_of_instructions: Some(0),
+            max_component_elements: Some(0),
+            max_component_depth: Some(0),
+            ..Maxp::new(glyphs.len() as u16)
+        };
+        let mut builder = FontBuilder::default();
+        builder
+            .add_table(Tag::GLYF, dump_table(&glyf).unwrap())
+            .add_table(Tag::LOCA, dump_table(&loca).unwrap())
+            .add_table(Tag::HEAD, dump_table(&head).unwrap())
+            .add_table(Tag::MAXP, dump_table(&maxp).unwrap())
+            .add_table(Tag::new(b"kern"), vec![0; 4]);
+        for (tag, data) in tables {
+            builder.add_table(*tag, data.clone());
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn builder_duplicates() {
+        let mut builder = GlyfLocaBuilder::new();
+        builder.deduplicate_glyphs();
+        for glyph in [Glyph::Empty, square(100), square(200), square(100)] {
+            builder.add_glyph(&glyph);
+        }
+        builder.add_glyph(&Glyph::Empty).add_glyph(&square(100));
+        assert_eq!(
+            builder.duplicates(),
+            [(3, 1), (5, 1)].map(|(gid, original)| (GlyphId::new(gid), GlyphId::new(original)))
+        );
+    }
+
+    #[test]
+    fn dedup_font() {
+        let glyphs = [Glyph::Empty, square(100), square(200), square(100)];
+        let data = make_font(&glyphs);
+        let font = FontRef::new(&data).unwrap();
+        let deduped = dedup_glyphs(&font).unwrap();
+        let new_font = FontRef::new(&deduped).unwrap();
+
+        let old_glyf = font.table_data(Tag::GLYF).unwrap();
+        let new_glyf = new_font.table_data(Tag::GLYF).unwrap();
+        assert!(new_glyf.len() < old_glyf.len());
+        assert_eq!(new_font.table_data(Tag::new(b"kern")).unwrap().len(), 4);
+
+        let loca = new_font.loca(None).unwrap();
+        let glyf = new_font.glyf().unwrap();
+        let Some(ReadGlyph::Composite(composite)) = loca.get_glyf(GlyphId::new(3), &glyf).unwrap()
+        else {
+            panic!("glyph should be a composite");
+        };
+        let component = composite.components().next().unwrap();
+        assert_eq!(component.glyph, GlyphId::new(1));
+        assert_eq!((composite.x_max(), composite.y_max()), (100, 100));
+        assert!(matches!(
+            loca.get_glyf(GlyphId::new(2), &glyf).unwrap(),
+            Some(ReadGlyph::Simple(_))
+        ));
+
+        let maxp = new_font.maxp().unwrap();
+        assert_eq!(maxp.max_composite_points(), Some(4));
+        assert_eq!(maxp.max_composite_contours(), Some(1));
+        assert_eq!(maxp.max_component_elements(), Some(1));
+        assert_eq!(maxp.max_component_depth(), Some(1));
+    }
+
+    #[test]
+    fn no_duplicates() {
+        let data = make_font(&[Glyph::Empty, square(100), square(200)]);
+        let font = FontRef::new(&data).unwrap();
+        let deduped = dedup_glyphs(&font).unwrap();
+        let new_font = FontRef::new(&deduped).unwrap();
+        assert_eq!(
+            new_font.table_data(Tag::GLYF).unwrap().as_ref(),
+            font.table_data(Tag::GLYF).unwrap().as_ref()
+        );
+        assert_eq!(new_font.maxp().unwrap().max_component_depth(), Some(0));
+    }
+
+    #[test]
+    fn dedup_color_bitmaps() {
+        // a single range of images with small metrics; glyphs 1 and 3 match
+        let mut cbdt = vec![0, 3, 0, 0];
+        let mut offsets = vec![0];
+        for pixels in [1, 2, 1] {
+            cbdt.extend([20, 20, 0, 16, 20]);
+            cbdt.extend((png(pixels).len() as u32).to_be_bytes());
+            cbdt.extend(png(pixels));
+            offsets.push(cbdt.len() as u16 - 4);
+        }
+        let cblc = Cblc::new(vec![BitmapSize {
+            ppem_x: 20,
+            ppem_y: 20,
+            bit_depth: 32,
+            flags: 1,
+            index_subtables: vec![IndexSubtable {
+                first_glyph: GlyphId::new(1),
+                last_glyph: GlyphId::new(3),
+                image_format: 17,
+                image_data_offset: 4,
+                format: IndexSubtableFormat::Format3 { offsets },
+            }],
+            ..Default::default()
+        }]);
+        let tables = [
+            (Tag::CBLC, dump_table(&cblc).unwrap()),
+            (Tag::CBDT, cbdt.clone()),
+        ];
+        let glyphs = [Glyph::Empty, square(100), square(200), square(300)];
+        let data = make_font_with_tables(&glyphs, &tables);
+        let font = FontRef::new(&data).unwrap();
+        let deduped = dedup_glyphs(&font).unwrap();
+        let new_font = FontRef::new(&deduped).unwrap();
+
+        let new_cbdt = new_font.table_data(Tag::CBDT).unwrap();
+        // two images, which now share their metrics in `CBLC`
+        assert_eq!(new_cbdt.len(), 4 + 2 * (4 + png(1).len()));
+        let strikes = read_color_bitmaps(new_font.table_data(Tag::CBLC).unwrap(), new_cbdt)
+            .unwrap()
+            .unwrap();
+        let (size, images) = &strikes[0];
+        assert_eq!((size.ppem_x, size.flags), (20, 1));
+        let images = images
+            .iter()
+            .map(|image| {
+                (
+                    image.glyph.to_u16(),
+                    image.metrics.hori_advance,
+                    image.png[24],
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(images, [(1, 20, 1), (2, 20, 2), (3, 20, 1)]);
+    }
+
+    #[test]
+    fn dedup_sbix() {
+        let bitmap = |pixels| {
+            Some(GlyphData {
+                graphic_type: Tag::new(b"png "),
+                data: png(pixels),
+                ..Default::default()
+            })
+        };
+        let sbix = Sbix::new(vec![Strike {
+            ppem: 20,
+            ppi: 72,
+            glyphs: vec![None, bitmap(1), bitmap(2), bitmap(1)],
+        }]);
+        let sbix = dump_table(&sbix).unwrap();
+        let glyphs = [Glyph::Empty, square(100), square(200), square(300)];
+        let data = make_font_with_tables(&glyphs, &[(Tag::SBIX, sbix.clone())]);
+        let font = FontRef::new(&data).unwrap();
+        let deduped = dedup_glyphs(&font).unwrap();
+        let new_font = FontRef::new(&deduped).unwrap();
+
+        let new_sbix = new_font.table_data(Tag::SBIX).unwrap();
+        assert!(new_sbix.len() < sbix.len());
+        let new_sbix = read_sbix(new_sbix, 4).unwrap();
+        let glyphs = &new_sbix.strikes[0].glyphs;
+        assert_eq!(glyphs[1], bitmap(1));
+        let dupe = glyphs[3].as_ref().unwrap();
+        assert_eq!(
+            (dupe.graphic_type, dupe.data.as_slice()),
+            (DUPE, [0, 1].as_slice())
+        );
+    }
+}
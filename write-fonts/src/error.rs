@@ -0,0 +1,63 @@
+//! Errors that occur when compiling tables
+
+use std::fmt::Display;
+
+use crate::{validate::ValidationReport, write::TableType};
+
+/// An error that occurs when serializing a table.
+#[derive(Debug)]
+pub enum Error {
+    /// The table failed validation.
+    ValidationFailed(ValidationReport),
+    /// The table could not be packed: some offsets overflow even after we
+    /// attempted to reorganize the table.
+    PackingFailed(PackingError),
+}
+
+/// An error indicating that a table contains unresolvable offset overflows.
+#[derive(Clone, Debug)]
+pub struct PackingError {
+    pub(crate) table_type: TableType,
+}
+
+impl PackingError {
+    /// The type of the table that could not be packed.
+    pub fn table_type(&self) -> TableType {
+        self.table_type
+    }
+}
+
+impl From<ValidationReport> for Error {
+    fn from(value: ValidationReport) -> Self {
+        Error::ValidationFailed(value)
+    }
+}
+
+impl From<PackingError> for Error {
+    fn from(value: PackingError) -> Self {
+        Error::PackingFailed(value)
+    }
+}
+
+impl Display for PackingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.table_type {
+            TableType::TopLevel(tag) => {
+                write!(f, "unresolvable offset overflow in '{tag}' table")
+            }
+            TableType::Unknown => write!(f, "unresolvable offset overflow in table"),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ValidationFailed(report) => report.fmt(f),
+            Error::PackingFailed(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PackingError {}
+impl std::error::Error for Error {}
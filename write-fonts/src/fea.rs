@@ -0,0 +1,240 @@
+//! Compiling [feature files]
+//!
+//! This is a front-end for the GSUB, GPOS and GDEF builders: it parses a
+//! feature file and lowers its rules onto the builders, producing the layout
+//! tables of a font.
+//!
+//! A practical subset of the syntax is supported:
+//!
+//! - `languagesystem` statements, and `script` and `language` statements in
+//!   features
+//! - named glyph classes, including glyph ranges such as `[a - z]`
+//! - `feature` and `lookup` blocks, `lookupflag` statements and references to
+//!   named lookups
+//! - single, multiple, alternate and ligature substitutions, and chaining
+//!   contextual substitutions, including `ignore sub` rules and inline
+//!   replacements
+//! - single and pair positioning, and mark-to-base and mark-to-mark
+//!   attachment with `markClass` definitions
+//! - the `GlyphClassDef`, `Attach` and `LigatureCaretBy*` statements of the
+//!   GDEF table
+//!
+//! Anything else, such as `include` statements, reverse chaining, contextual
+//! positioning and other tables, is reported as an error.
+//!
+//! If there is no `GlyphClassDef` statement, the glyph classes of the GDEF
+//! table are inferred from the mark attachment rules, as in other feature file
+//! compilers.
+//!
+//! ```
+//! # use write_fonts::fea::{compile, GlyphMap};
+//! let glyph_map = [".notdef", "f", "i", "f_i"].into_iter().collect::<GlyphMap>();
+//! let fea = "
+//!     languagesystem DFLT dflt;
+//!     feature liga {
+//!         sub f i by f_i;
+//!     } liga;
+//! ";
+//! let compiled = compile(fea, &glyph_map).unwrap();
+//! assert!(compiled.gsub.is_some());
+//! ```
+//!
+//! [feature files]: https://adobe-type-tools.github.io/afdko/OpenTypeFeatureFileSpecification.html
+
+mod compile;
+mod lexer;
+mod parse;
+
+use std::collections::HashMap;
+
+use types::GlyphId;
+
+use crate::{
+    tables::{
+        gdef::{Gdef, GdefInputError},
+        gpos::Gpos,
+        gsub::Gsub,
+    },
+    FontBuilder,
+};
+
+/// A map from glyph names to glyph ids.
+///
+/// This is usually collected from the names of all glyphs in the font, in
+/// glyph id order.
+#[derive(Clone, Debug, Default)]
+pub struct GlyphMap {
+    ids: HashMap<String, GlyphId>,
+    len: u16,
+}
+
+/// The tables compiled from a feature file.
+#[derive(Clone, Debug, Default)]
+pub struct Compilation {
+    pub gsub: Option<Gsub>,
+    pub gpos: Option<Gpos>,
+    pub gdef: Option<Gdef>,
+}
+
+/// A position in a feature file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Location {
+    /// The line, starting from 1.
+    pub line: usize,
+    /// The column, in characters, starting from 1.
+    pub column: usize,
+}
+
+/// An error encountered while compiling a feature file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeaError {
+    location: Option<Location>,
+    kind: FeaErrorKind,
+}
+
+/// The kinds of errors encountered while compiling a feature file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FeaErrorKind {
+    /// The source is not valid feature file syntax.
+    InvalidSyntax(String),
+    /// A token was not what the grammar requires.
+    UnexpectedToken { expected: String, found: String },
+    /// The source ended in the middle of a statement or block.
+    UnexpectedEof { expected: String },
+    /// A glyph name is not in the glyph map.
+    UnknownGlyph(String),
+    /// A glyph class is used before it is defined.
+    UnknownClass(String),
+    /// A mark class is used before it is defined.
+    UnknownMarkClass(String),
+    /// A lookup is referenced before it is defined.
+    UnknownLookup(String),
+    /// A rule or statement is not valid where it is used.
+    InvalidRule(String),
+    /// The source uses syntax that is not supported by this compiler.
+    Unsupported(String),
+    /// The GDEF table is not consistent with the glyph map.
+    Gdef(GdefInputError),
+}
+
+/// Compile a feature file.
+pub fn compile(source: &str, glyph_map: &GlyphMap) -> Result<Compilation, FeaError> {
+    let tokens = lexer::tokenize(source)?;
+    let statements = parse::parse(tokens)?;
+    compile::Compiler::new(glyph_map).compile(&statements)
+}
+
+impl GlyphMap {
+    /// The id of the glyph with this name.
+    pub fn get(&self, name: &str) -> Option<GlyphId> {
+        self.ids.get(name).copied()
+    }
+
+    /// The number of glyphs in the map.
+    pub fn len(&self) -> u16 {
+        self.len
+    }
+
+    /// Returns `true` if the map has no glyphs.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for GlyphMap {
+    /// Create a map from glyph names, in glyph id order.
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        let mut map = GlyphMap::default();
+        for name in iter {
+            // if we're over u16::MAX glyphs, crash
+            let id = GlyphId::new(map.len);
+            map.ids.entry(name.into()).or_insert(id);
+            map.len = map.len.checked_add(1).unwrap();
+        }
+        map
+    }
+}
+
+impl Compilation {
+    /// Compile the tables, and add them to a font.
+    pub fn add_to(&self, builder: &mut FontBuilder) -> Result<(), crate::Error> {
+        use read_fonts::TopLevelTable;
+        if let Some(gsub) = &self.gsub {
+            builder.add_table(Gsub::TAG, crate::dump_table(gsub)?);
+        }
+        if let Some(gpos) = &self.gpos {
+            builder.add_table(Gpos::TAG, crate::dump_table(gpos)?);
+        }
+        if let Some(gdef) = &self.gdef {
+            builder.add_table(Gdef::TAG, crate::dump_table(gdef)?);
+        }
+        Ok(())
+    }
+}
+
+impl FeaError {
+    pub(crate) fn new(location: Location, kind: FeaErrorKind) -> Self {
+        FeaError {
+            location: Some(location),
+            kind,
+        }
+    }
+
+    /// Where the error occurred, if it can be attributed to a position in the
+    /// source.
+    pub fn location(&self) -> Option<Location> {
+        self.location
+    }
+
+    /// The kind of error.
+    pub fn kind(&self) -> &FeaErrorKind {
+        &self.kind
+    }
+}
+
+impl From<GdefInputError> for FeaError {
+    fn from(value: GdefInputError) -> Self {
+        FeaError {
+            location: None,
+            kind: FeaErrorKind::Gdef(value),
+        }
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl std::fmt::Display for FeaErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeaErrorKind::InvalidSyntax(msg) => f.write_str(msg),
+            FeaErrorKind::UnexpectedToken { expected, found } => {
+                write!(f, "expected {expected}, found {found}")
+            }
+            FeaErrorKind::UnexpectedEof { expected } => {
+                write!(f, "expected {expected}, found end of file")
+            }
+            FeaErrorKind::UnknownGlyph(name) => write!(f, "unknown glyph '{name}'"),
+            FeaErrorKind::UnknownClass(name) => write!(f, "unknown glyph class '@{name}'"),
+            FeaErrorKind::UnknownMarkClass(name) => write!(f, "unknown mark class '@{name}'"),
+            FeaErrorKind::UnknownLookup(name) => write!(f, "unknown lookup '{name}'"),
+            FeaErrorKind::InvalidRule(msg) => f.write_str(msg),
+            FeaErrorKind::Unsupported(what) => write!(f, "{what} is not supported"),
+            FeaErrorKind::Gdef(err) => write!(f, "invalid GDEF table: {err}"),
+        }
+    }
+}
+
+impl std::fmt::Display for FeaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.location {
+            Some(location) => write!(f, "{location}: {}", self.kind),
+            None => self.kind.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for FeaError {}
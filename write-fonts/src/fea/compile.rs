@@ -0,0 +1,1639 @@
+//! Lowering parsed feature files onto the layout table builders.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use read_fonts::tables::layout::LookupFlag;
+use types::{GlyphId, Tag};
+
+use super::{
+    parse::{
+        Anchor, GlyphExpr, ListItem, LookupFlagItem, Node, Replacement, SequenceItem, Statement,
+        ValueExpr,
+    },
+    Compilation, FeaError, FeaErrorKind, GlyphMap, Location,
+};
+use crate::tables::{
+    gdef::{CaretValue, GdefBuilder, GlyphClassDef},
+    gpos::{
+        AnchorTable, GposBuilder, MarkBaseBuilder, MarkMarkBuilder, PairPosBuilder,
+        SinglePosBuilder, ValueRecord,
+    },
+    gsub::{
+        AlternateSubBuilder, ChainContextBuilder, ChainRule, GsubBuilder, LigatureSubBuilder,
+        MultipleSubBuilder, SingleSubBuilder,
+    },
+};
+
+const DEFAULT_SCRIPT: Tag = Tag::new(b"DFLT");
+const DEFAULT_LANGUAGE: Tag = Tag::new(b"dflt");
+
+/// Features in which a single number adjusts the vertical advance.
+const VERTICAL_FEATURES: [Tag; 4] = [
+    Tag::new(b"valt"),
+    Tag::new(b"vhal"),
+    Tag::new(b"vkrn"),
+    Tag::new(b"vpal"),
+];
+
+/// The state of a compilation.
+pub(crate) struct Compiler<'a> {
+    glyph_map: &'a GlyphMap,
+    language_systems: Vec<(Tag, Tag)>,
+    classes: HashMap<String, Vec<GlyphId>>,
+    mark_classes: HashMap<String, MarkClass>,
+    gsub_lookups: Vec<PendingLookup>,
+    gpos_lookups: Vec<PendingLookup>,
+    // `None` for lookups that have no rules
+    named_lookups: HashMap<String, Option<LookupId>>,
+    features: Vec<FeatureEntry>,
+    has_features: bool,
+    state: BlockState,
+    gdef: GdefBuilder,
+    has_gdef_table: bool,
+    explicit_classes: Option<BTreeMap<GlyphId, GlyphClassDef>>,
+    inferred_classes: BTreeMap<GlyphId, GlyphClassDef>,
+    // the mark attachment class of each set is its index + 1
+    mark_attach_classes: Vec<BTreeSet<GlyphId>>,
+}
+
+/// The state of the feature or lookup block being compiled.
+#[derive(Clone, Debug, Default)]
+struct BlockState {
+    feature: Option<Tag>,
+    lookup_name: Option<String>,
+    script: Option<Tag>,
+    lang_systems: Vec<(Tag, Tag)>,
+    flags: LookupFlag,
+    mark_filter: Option<BTreeSet<GlyphId>>,
+    // the lookup that rules are added to, if it is still open
+    current: Option<LookupId>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Table {
+    Gsub,
+    Gpos,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct LookupId {
+    table: Table,
+    index: usize,
+}
+
+/// A lookup is registered for a feature in a language system.
+#[derive(Clone, Copy, Debug)]
+struct FeatureEntry {
+    script: Tag,
+    language: Tag,
+    feature: Tag,
+    lookup: LookupId,
+}
+
+struct PendingLookup {
+    flags: LookupFlag,
+    mark_filter: Option<BTreeSet<GlyphId>>,
+    kind: LookupKind,
+}
+
+enum LookupKind {
+    SingleSub(SingleSubBuilder),
+    MultipleSub(MultipleSubBuilder),
+    AlternateSub(AlternateSubBuilder),
+    LigatureSub(LigatureSubBuilder),
+    ChainSub(ChainContextBuilder),
+    SinglePos(SinglePosBuilder),
+    // also tracks which pairs were added by glyph-specific rules
+    PairPos(PairPosBuilder, HashMap<(GlyphId, GlyphId), bool>),
+    MarkBase(MarkBaseBuilder),
+    MarkMark(MarkMarkBuilder),
+}
+
+/// The substitutions of a (non-contextual) substitution rule.
+enum SubRule {
+    Single(Vec<(GlyphId, GlyphId)>),
+    Multiple(Vec<(GlyphId, Vec<GlyphId>)>),
+    Alternate(Vec<(GlyphId, Vec<GlyphId>)>),
+    Ligature(Vec<(Vec<GlyphId>, GlyphId)>),
+}
+
+struct MarkClass {
+    // classes are numbered in the order they are defined
+    id: u16,
+    marks: BTreeMap<GlyphId, AnchorTable>,
+}
+
+impl<'a> Compiler<'a> {
+    pub(crate) fn new(glyph_map: &'a GlyphMap) -> Self {
+        Compiler {
+            glyph_map,
+            language_systems: Vec::new(),
+            classes: HashMap::new(),
+            mark_classes: HashMap::new(),
+            gsub_lookups: Vec::new(),
+            gpos_lookups: Vec::new(),
+            named_lookups: HashMap::new(),
+            features: Vec::new(),
+            has_features: false,
+            state: BlockState::default(),
+            gdef: GdefBuilder::new(),
+            has_gdef_table: false,
+            explicit_classes: None,
+            inferred_classes: BTreeMap::new(),
+            mark_attach_classes: Vec::new(),
+        }
+    }
+
+    pub(crate) fn compile(mut self, statements: &[Node]) -> Result<Compilation, FeaError> {
+        for node in statements {
+            self.top_level_statement(node)?;
+        }
+        self.build()
+    }
+
+    fn top_level_statement(&mut self, node: &Node) -> Result<(), FeaError> {
+        let location = node.location;
+        match &node.statement {
+            Statement::LanguageSystem { script, language } => {
+                if self.has_features {
+                    return Err(invalid(
+                        location,
+                        "languagesystem statements must come before features",
+                    ));
+                }
+                if !self.language_systems.contains(&(*script, *language)) {
+                    self.language_systems.push((*script, *language));
+                }
+                Ok(())
+            }
+            Statement::ClassDef { .. } | Statement::MarkClass { .. } => self.definition(node),
+            Statement::Feature { tag, statements } => {
+                self.has_features = true;
+                let lang_systems = if self.language_systems.is_empty() {
+                    vec![(DEFAULT_SCRIPT, DEFAULT_LANGUAGE)]
+                } else {
+                    self.language_systems.clone()
+                };
+                self.state = BlockState {
+                    feature: Some(*tag),
+                    lang_systems,
+                    ..Default::default()
+                };
+                for node in statements {
+                    self.block_statement(node)?;
+                }
+                self.state = BlockState::default();
+                Ok(())
+            }
+            Statement::Lookup { name, statements } => self.lookup_block(name, statements, location),
+            Statement::GdefTable(statements) => {
+                self.has_gdef_table = true;
+                for node in statements {
+                    self.gdef_statement(node)?;
+                }
+                Ok(())
+            }
+            _ => Err(invalid(location, "statement must be in a block")),
+        }
+    }
+
+    fn block_statement(&mut self, node: &Node) -> Result<(), FeaError> {
+        let location = node.location;
+        match &node.statement {
+            Statement::ClassDef { .. } | Statement::MarkClass { .. } => self.definition(node),
+            Statement::Lookup { name, statements } => {
+                if self.state.lookup_name.is_some() {
+                    return Err(invalid(location, "lookup blocks cannot be nested"));
+                }
+                self.lookup_block(name, statements, location)
+            }
+            Statement::LookupRef(name) => {
+                if self.state.lookup_name.is_some() {
+                    return Err(invalid(
+                        location,
+                        "lookups cannot be referenced in a lookup block",
+                    ));
+                }
+                let id = *self.named_lookups.get(name).ok_or_else(|| {
+                    FeaError::new(location, FeaErrorKind::UnknownLookup(name.clone()))
+                })?;
+                if let Some(id) = id {
+                    self.register(id);
+                }
+                self.state.current = None;
+                Ok(())
+            }
+            Statement::Script(tag) => {
+                self.check_language_statement(location)?;
+                self.state.script = Some(*tag);
+                self.state.lang_systems = vec![(*tag, DEFAULT_LANGUAGE)];
+                self.state.flags = LookupFlag::empty();
+                self.state.mark_filter = None;
+                self.state.current = None;
+                Ok(())
+            }
+            Statement::Language {
+                tag,
+                include_default,
+            } => {
+                self.check_language_statement(location)?;
+                let script = self.state.script.unwrap_or(DEFAULT_SCRIPT);
+                let feature = self.state.feature.unwrap();
+                if *include_default && *tag != DEFAULT_LANGUAGE {
+                    let inherited = self
+                        .features
+                        .iter()
+                        .filter(|entry| {
+                            entry.feature == feature
+                                && entry.script == script
+                                && entry.language == DEFAULT_LANGUAGE
+                        })
+                        .map(|entry| FeatureEntry {
+                            language: *tag,
+                            ..*entry
+                        })
+                        .collect::<Vec<_>>();
+                    self.features.extend(inherited);
+                }
+                self.state.lang_systems = vec![(script, *tag)];
+                self.state.current = None;
+                Ok(())
+            }
+            Statement::LookupFlag(items) => self.lookup_flag(items, location),
+            // subtables are split as needed when the lookups are built
+            Statement::Subtable => Ok(()),
+            Statement::Sub {
+                sequence,
+                replacement,
+            } => self.sub_rule(sequence, replacement.as_ref(), location),
+            Statement::IgnoreSub(rules) => {
+                for sequence in rules {
+                    let rule = self.chain_rule(sequence, location)?;
+                    self.add_chain_rule(rule, location)?;
+                }
+                Ok(())
+            }
+            Statement::Pos {
+                sequence,
+                enumerated,
+            } => self.pos_rule(sequence, *enumerated, location),
+            Statement::MarkBase { base, marks } => {
+                self.mark_attachment(base, marks, false, location)
+            }
+            Statement::MarkMark { base, marks } => {
+                self.mark_attachment(base, marks, true, location)
+            }
+            _ => Err(invalid(location, "statement is not allowed in a block")),
+        }
+    }
+
+    fn check_language_statement(&self, location: Location) -> Result<(), FeaError> {
+        if self.state.lookup_name.is_some() || self.state.feature.is_none() {
+            return Err(invalid(
+                location,
+                "script and language statements must be in a feature block",
+            ));
+        }
+        Ok(())
+    }
+
+    fn definition(&mut self, node: &Node) -> Result<(), FeaError> {
+        let location = node.location;
+        match &node.statement {
+            Statement::ClassDef { name, glyphs } => {
+                let glyphs = self.glyphs(glyphs, location)?;
+                self.classes.insert(name.clone(), glyphs);
+            }
+            Statement::MarkClass {
+                glyphs,
+                anchor,
+                name,
+            } => {
+                let anchor = anchor_table(anchor)
+                    .ok_or_else(|| invalid(location, "mark class anchors cannot be NULL"))?;
+                let glyphs = self.glyphs(glyphs, location)?;
+                let id = self.mark_classes.len() as u16;
+                let class = self
+                    .mark_classes
+                    .entry(name.clone())
+                    .or_insert_with(|| MarkClass {
+                        id,
+                        marks: BTreeMap::new(),
+                    });
+                for glyph in glyphs {
+                    class.marks.insert(glyph, anchor.clone());
+                    self.inferred_classes.insert(glyph, GlyphClassDef::Mark);
+                }
+            }
+            _ => unreachable!("not a definition"),
+        }
+        Ok(())
+    }
+
+    fn lookup_block(
+        &mut self,
+        name: &str,
+        statements: &[Node],
+        location: Location,
+    ) -> Result<(), FeaError> {
+        if self.named_lookups.contains_key(name) {
+            return Err(invalid(
+                location,
+                format!("lookup '{name}' is already defined"),
+            ));
+        }
+        let outer = self.state.clone();
+        self.state.lookup_name = Some(name.to_owned());
+        self.state.current = None;
+        for node in statements {
+            self.block_statement(node)?;
+        }
+        let id = self.state.current;
+        self.named_lookups.insert(name.to_owned(), id);
+        self.state = outer;
+        if let Some(id) = id {
+            self.register(id);
+        }
+        // later rules must not be added to a lookup before this one
+        self.state.current = None;
+        Ok(())
+    }
+
+    fn lookup_flag(
+        &mut self,
+        items: &[LookupFlagItem],
+        location: Location,
+    ) -> Result<(), FeaError> {
+        if let (Some(name), Some(_)) = (&self.state.lookup_name, self.state.current) {
+            return Err(invalid(
+                location,
+                format!("lookupflag must come before the rules of lookup '{name}'"),
+            ));
+        }
+        let mut flags = LookupFlag::empty();
+        let mut mark_filter = None;
+        for item in items {
+            match item {
+                LookupFlagItem::Bits(bits) => flags = LookupFlag::from_bits_truncate(*bits),
+                LookupFlagItem::RightToLeft => flags.set_right_to_left(true),
+                LookupFlagItem::IgnoreBaseGlyphs => flags.set_ignore_base_glyphs(true),
+                LookupFlagItem::IgnoreLigatures => flags.set_ignore_ligatures(true),
+                LookupFlagItem::IgnoreMarks => flags.set_ignore_marks(true),
+                LookupFlagItem::MarkAttachmentType(glyphs) => {
+                    let glyphs = self.glyphs(glyphs, location)?.into_iter().collect();
+                    let class = self.mark_attach_class(glyphs, location)?;
+                    flags.set_mark_attachment_type(class);
+                }
+                LookupFlagItem::UseMarkFilteringSet(glyphs) => {
+                    mark_filter = Some(self.glyphs(glyphs, location)?.into_iter().collect());
+                }
+            }
+        }
+        self.state.flags = flags;
+        self.state.mark_filter = mark_filter;
+        self.state.current = None;
+        Ok(())
+    }
+
+    /// The mark attachment class for this set of glyphs.
+    fn mark_attach_class(
+        &mut self,
+        glyphs: BTreeSet<GlyphId>,
+        location: Location,
+    ) -> Result<u16, FeaError> {
+        if let Some(i) = self
+            .mark_attach_classes
+            .iter()
+            .position(|set| *set == glyphs)
+        {
+            return Ok(i as u16 + 1);
+        }
+        // a glyph can only have one mark attachment class
+        if self
+            .mark_attach_classes
+            .iter()
+            .any(|set| !set.is_disjoint(&glyphs))
+        {
+            return Err(invalid(
+                location,
+                "mark attachment classes must not overlap",
+            ));
+        }
+        if self.mark_attach_classes.len() >= u8::MAX as usize {
+            return Err(invalid(location, "too many mark attachment classes"));
+        }
+        self.mark_attach_classes.push(glyphs);
+        Ok(self.mark_attach_classes.len() as u16)
+    }
+
+    fn sub_rule(
+        &mut self,
+        sequence: &[SequenceItem],
+        replacement: Option<&Replacement>,
+        location: Location,
+    ) -> Result<(), FeaError> {
+        if sequence.iter().any(|item| item.marked) {
+            return self.chain_sub_rule(sequence, replacement, location);
+        }
+        if sequence.iter().any(|item| !item.lookups.is_empty()) {
+            return Err(invalid(
+                location,
+                "lookups can only be applied to marked glyphs",
+            ));
+        }
+        let inputs = sequence
+            .iter()
+            .map(|item| self.glyphs(&item.glyphs, location))
+            .collect::<Result<Vec<_>, _>>()?;
+        let rule = self.substitution(&inputs, replacement, location)?;
+        let id = self.lookup_for(rule.empty_lookup(), location)?;
+        rule.add_to(&mut self.lookup_mut(id).kind);
+        Ok(())
+    }
+
+    /// Determine the kind of substitution from the shape of the rule.
+    fn substitution(
+        &self,
+        inputs: &[Vec<GlyphId>],
+        replacement: Option<&Replacement>,
+        location: Location,
+    ) -> Result<SubRule, FeaError> {
+        let Some(replacement) = replacement else {
+            return Err(invalid(location, "expected 'by' or 'from'"));
+        };
+        let replacements = match replacement {
+            Replacement::From(alternates) => {
+                let [targets] = inputs else {
+                    return Err(invalid(
+                        location,
+                        "alternate substitutions must replace a single glyph",
+                    ));
+                };
+                let alternates = self.glyphs(alternates, location)?;
+                return Ok(SubRule::Alternate(
+                    targets
+                        .iter()
+                        .map(|target| (*target, alternates.clone()))
+                        .collect(),
+                ));
+            }
+            // deletion is a multiple substitution with no replacement glyphs
+            Replacement::By(glyphs) if glyphs == &[GlyphExpr::Glyph("NULL".into())] => Vec::new(),
+            Replacement::By(glyphs) => glyphs
+                .iter()
+                .map(|glyphs| self.glyphs(glyphs, location))
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        match (inputs, replacements.as_slice()) {
+            ([targets], [replacements]) => {
+                let pairs = if let [replacement] = replacements.as_slice() {
+                    targets
+                        .iter()
+                        .map(|target| (*target, *replacement))
+                        .collect()
+                } else if targets.len() == replacements.len() {
+                    targets
+                        .iter()
+                        .copied()
+                        .zip(replacements.iter().copied())
+                        .collect()
+                } else {
+                    return Err(invalid(
+                        location,
+                        "the classes of a single substitution must have the same length",
+                    ));
+                };
+                Ok(SubRule::Single(pairs))
+            }
+            ([targets], replacements) => {
+                let sequence = replacements
+                    .iter()
+                    .map(|glyphs| match glyphs.as_slice() {
+                        [glyph] => Ok(*glyph),
+                        _ => Err(invalid(
+                            location,
+                            "a multiple substitution cannot replace a glyph with classes",
+                        )),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(SubRule::Multiple(
+                    targets
+                        .iter()
+                        .map(|target| (*target, sequence.clone()))
+                        .collect(),
+                ))
+            }
+            (inputs, [ligature]) if ligature.len() == 1 => Ok(SubRule::Ligature(
+                sequences(inputs)
+                    .into_iter()
+                    .map(|sequence| (sequence, ligature[0]))
+                    .collect(),
+            )),
+            _ => Err(FeaError::new(
+                location,
+                FeaErrorKind::Unsupported("many-to-many substitution".into()),
+            )),
+        }
+    }
+
+    fn chain_sub_rule(
+        &mut self,
+        sequence: &[SequenceItem],
+        replacement: Option<&Replacement>,
+        location: Location,
+    ) -> Result<(), FeaError> {
+        let mut rule = self.chain_rule(sequence, location)?;
+        let input = sequence.iter().filter(|item| item.marked);
+        if let Some(replacement) = replacement {
+            if sequence.iter().any(|item| !item.lookups.is_empty()) {
+                return Err(invalid(
+                    location,
+                    "a rule cannot have both lookups and a replacement",
+                ));
+            }
+            let inputs = input
+                .map(|item| self.glyphs(&item.glyphs, location))
+                .collect::<Result<Vec<_>, _>>()?;
+            let inline = self.substitution(&inputs, Some(replacement), location)?;
+            let mut kind = inline.empty_lookup();
+            inline.add_to(&mut kind);
+            let id = self.push_lookup(kind);
+            rule = rule.lookup(0, id.index as u16);
+        } else {
+            for (i, item) in input.enumerate() {
+                for name in &item.lookups {
+                    let index = self.named_lookup_index(name, Table::Gsub, location)?;
+                    rule = rule.lookup(i as u16, index);
+                }
+            }
+        }
+        self.add_chain_rule(rule, location)
+    }
+
+    /// The context of a contextual rule, without any lookups.
+    fn chain_rule(
+        &self,
+        sequence: &[SequenceItem],
+        location: Location,
+    ) -> Result<ChainRule, FeaError> {
+        let start = sequence.iter().position(|item| item.marked);
+        let end = sequence.iter().rposition(|item| item.marked);
+        let (Some(start), Some(end)) = (start, end) else {
+            return Err(invalid(
+                location,
+                "contextual rules must mark their input glyphs",
+            ));
+        };
+        if !sequence[start..=end].iter().all(|item| item.marked) {
+            return Err(invalid(location, "marked glyphs must be contiguous"));
+        }
+        if sequence
+            .iter()
+            .any(|item| !item.marked && !item.lookups.is_empty())
+        {
+            return Err(invalid(
+                location,
+                "lookups can only be applied to marked glyphs",
+            ));
+        }
+        let sets = |items: &[SequenceItem]| {
+            items
+                .iter()
+                .map(|item| {
+                    self.glyphs(&item.glyphs, location)
+                        .map(|glyphs| glyphs.into_iter().collect::<BTreeSet<_>>())
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(ChainRule::new(sets(&sequence[start..=end])?)
+            .backtrack(sets(&sequence[..start])?)
+            .lookahead(sets(&sequence[end + 1..])?))
+    }
+
+    fn add_chain_rule(&mut self, rule: ChainRule, location: Location) -> Result<(), FeaError> {
+        let id = self.lookup_for(LookupKind::ChainSub(Default::default()), location)?;
+        let LookupKind::ChainSub(builder) = &mut self.lookup_mut(id).kind else {
+            unreachable!("lookup has the requested kind");
+        };
+        builder.add_rule(rule);
+        Ok(())
+    }
+
+    fn named_lookup_index(
+        &self,
+        name: &str,
+        table: Table,
+        location: Location,
+    ) -> Result<u16, FeaError> {
+        match self.named_lookups.get(name) {
+            None => Err(FeaError::new(
+                location,
+                FeaErrorKind::UnknownLookup(name.to_owned()),
+            )),
+            Some(None) => Err(invalid(location, format!("lookup '{name}' is empty"))),
+            Some(Some(id)) if id.table != table => Err(invalid(
+                location,
+                format!("lookup '{name}' is in the wrong table"),
+            )),
+            Some(Some(id)) => Ok(id.index as u16),
+        }
+    }
+
+    fn pos_rule(
+        &mut self,
+        sequence: &[SequenceItem],
+        enumerated: bool,
+        location: Location,
+    ) -> Result<(), FeaError> {
+        if sequence.iter().any(|item| item.marked) {
+            return Err(FeaError::new(
+                location,
+                FeaErrorKind::Unsupported("contextual positioning".into()),
+            ));
+        }
+        if sequence.iter().any(|item| !item.lookups.is_empty()) {
+            return Err(invalid(
+                location,
+                "lookups can only be applied to marked glyphs",
+            ));
+        }
+        match sequence {
+            [item] => {
+                let value = item
+                    .value
+                    .ok_or_else(|| invalid(location, "expected a value record"))?;
+                let value = self.value_record(value);
+                let glyphs = self.glyphs(&item.glyphs, location)?;
+                let id = self.lookup_for(LookupKind::SinglePos(Default::default()), location)?;
+                let LookupKind::SinglePos(builder) = &mut self.lookup_mut(id).kind else {
+                    unreachable!("lookup has the requested kind");
+                };
+                for glyph in glyphs {
+                    builder.insert(glyph, value.clone());
+                }
+            }
+            [first, second] => {
+                let (value1, value2) = match (first.value, second.value) {
+                    // `pos a b -50;` adjusts the first glyph
+                    (None, Some(value)) => (value, ValueExpr::Null),
+                    (Some(value1), value2) => (value1, value2.unwrap_or(ValueExpr::Null)),
+                    (None, None) => return Err(invalid(location, "expected a value record")),
+                };
+                let (value1, value2) = (self.value_record(value1), self.value_record(value2));
+                // pairs of single glyphs take precedence over pairs of classes
+                let specific = enumerated
+                    || matches!(
+                        (&first.glyphs, &second.glyphs),
+                        (GlyphExpr::Glyph(_), GlyphExpr::Glyph(_))
+                    );
+                let firsts = self.glyphs(&first.glyphs, location)?;
+                let seconds = self.glyphs(&second.glyphs, location)?;
+                let kind = LookupKind::PairPos(Default::default(), Default::default());
+                let id = self.lookup_for(kind, location)?;
+                let LookupKind::PairPos(builder, seen) = &mut self.lookup_mut(id).kind else {
+                    unreachable!("lookup has the requested kind");
+                };
+                for first in &firsts {
+                    for second in &seconds {
+                        let replace = match seen.get(&(*first, *second)) {
+                            None => true,
+                            Some(existing) => specific && !existing,
+                        };
+                        if replace {
+                            builder.insert(*first, *second, value1.clone(), value2.clone());
+                            seen.insert((*first, *second), specific);
+                        }
+                    }
+                }
+            }
+            _ => {
+                return Err(invalid(
+                    location,
+                    "positioning rules must have one or two glyphs",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn value_record(&self, value: ValueExpr) -> ValueRecord {
+        let non_zero = |value: i16| (value != 0).then_some(value);
+        match value {
+            ValueExpr::Advance(advance) => {
+                let vertical = self
+                    .state
+                    .feature
+                    .is_some_and(|tag| VERTICAL_FEATURES.contains(&tag));
+                if vertical {
+                    ValueRecord {
+                        y_advance: non_zero(advance),
+                        ..Default::default()
+                    }
+                } else {
+                    ValueRecord {
+                        x_advance: non_zero(advance),
+                        ..Default::default()
+                    }
+                }
+            }
+            ValueExpr::Record {
+                x_placement,
+                y_placement,
+                x_advance,
+                y_advance,
+            } => ValueRecord {
+                x_placement: non_zero(x_placement),
+                y_placement: non_zero(y_placement),
+                x_advance: non_zero(x_advance),
+                y_advance: non_zero(y_advance),
+                ..Default::default()
+            },
+            ValueExpr::Null => ValueRecord::default(),
+        }
+    }
+
+    fn mark_attachment(
+        &mut self,
+        base: &GlyphExpr,
+        marks: &[(Anchor, String)],
+        is_mark_mark: bool,
+        location: Location,
+    ) -> Result<(), FeaError> {
+        let bases = self.glyphs(base, location)?;
+        let mut classes = Vec::new();
+        for (anchor, name) in marks {
+            let class = self.mark_classes.get(name).ok_or_else(|| {
+                FeaError::new(location, FeaErrorKind::UnknownMarkClass(name.clone()))
+            })?;
+            classes.push((class.id, class.marks.clone(), anchor_table(anchor)));
+        }
+        for glyph in &bases {
+            if is_mark_mark {
+                self.inferred_classes.insert(*glyph, GlyphClassDef::Mark);
+            } else {
+                self.inferred_classes
+                    .entry(*glyph)
+                    .or_insert(GlyphClassDef::Base);
+            }
+        }
+
+        if is_mark_mark {
+            let id = self.lookup_for(LookupKind::MarkMark(Default::default()), location)?;
+            let LookupKind::MarkMark(builder) = &mut self.lookup_mut(id).kind else {
+                unreachable!("lookup has the requested kind");
+            };
+            for (class, marks, anchor) in classes {
+                for (mark, mark_anchor) in marks {
+                    builder.add_mark(mark, class, mark_anchor);
+                }
+                if let Some(anchor) = anchor {
+                    for base in &bases {
+                        builder.add_base_mark(*base, class, anchor.clone());
+                    }
+                }
+            }
+        } else {
+            let id = self.lookup_for(LookupKind::MarkBase(Default::default()), location)?;
+            let LookupKind::MarkBase(builder) = &mut self.lookup_mut(id).kind else {
+                unreachable!("lookup has the requested kind");
+            };
+            for (class, marks, anchor) in classes {
+                for (mark, mark_anchor) in marks {
+                    builder.add_mark(mark, class, mark_anchor);
+                }
+                if let Some(anchor) = anchor {
+                    for base in &bases {
+                        builder.add_base(*base, class, anchor.clone());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn gdef_statement(&mut self, node: &Node) -> Result<(), FeaError> {
+        let location = node.location;
+        match &node.statement {
+            Statement::GlyphClassDef(classes) => {
+                let mut explicit = self.explicit_classes.take().unwrap_or_default();
+                let class_defs = [
+                    GlyphClassDef::Base,
+                    GlyphClassDef::Ligature,
+                    GlyphClassDef::Mark,
+                    GlyphClassDef::Component,
+                ];
+                for (glyphs, class) in classes.iter().zip(class_defs) {
+                    if let Some(glyphs) = glyphs {
+                        for glyph in self.glyphs(glyphs, location)? {
+                            explicit.insert(glyph, class);
+                        }
+                    }
+                }
+                self.explicit_classes = Some(explicit);
+            }
+            Statement::Attach { glyphs, points } => {
+                for glyph in self.glyphs(glyphs, location)? {
+                    self.gdef.set_attach_points(glyph, points.clone());
+                }
+            }
+            Statement::LigatureCaretByPos { glyphs, carets } => {
+                for glyph in self.glyphs(glyphs, location)? {
+                    let carets = carets.iter().copied().map(CaretValue::format_1).collect();
+                    self.gdef.set_ligature_carets(glyph, carets);
+                }
+            }
+            Statement::LigatureCaretByIndex { glyphs, points } => {
+                for glyph in self.glyphs(glyphs, location)? {
+                    let carets = points.iter().copied().map(CaretValue::format_2).collect();
+                    self.gdef.set_ligature_carets(glyph, carets);
+                }
+            }
+            _ => {
+                return Err(invalid(
+                    location,
+                    "statement is not allowed in a GDEF block",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// The lookup that a rule of this kind should be added to, creating it if
+    /// necessary.
+    fn lookup_for(&mut self, kind: LookupKind, location: Location) -> Result<LookupId, FeaError> {
+        if let Some(id) = self.state.current {
+            if self.lookup_mut(id).kind.same_type(&kind) {
+                return Ok(id);
+            }
+            if let Some(name) = &self.state.lookup_name {
+                return Err(invalid(
+                    location,
+                    format!("lookup '{name}' contains rules of different types"),
+                ));
+            }
+        }
+        let id = self.push_lookup(kind);
+        self.state.current = Some(id);
+        // named lookups are registered when the block ends
+        if self.state.lookup_name.is_none() {
+            self.register(id);
+        }
+        Ok(id)
+    }
+
+    /// Add a lookup with the current flags.
+    fn push_lookup(&mut self, kind: LookupKind) -> LookupId {
+        let table = kind.table();
+        let lookups = match table {
+            Table::Gsub => &mut self.gsub_lookups,
+            Table::Gpos => &mut self.gpos_lookups,
+        };
+        lookups.push(PendingLookup {
+            flags: self.state.flags,
+            mark_filter: self.state.mark_filter.clone(),
+            kind,
+        });
+        LookupId {
+            table,
+            index: lookups.len() - 1,
+        }
+    }
+
+    fn lookup_mut(&mut self, id: LookupId) -> &mut PendingLookup {
+        match id.table {
+            Table::Gsub => &mut self.gsub_lookups[id.index],
+            Table::Gpos => &mut self.gpos_lookups[id.index],
+        }
+    }
+
+    /// Register a lookup for the current feature, in the current language
+    /// systems.
+    fn register(&mut self, lookup: LookupId) {
+        let Some(feature) = self.state.feature else {
+            return;
+        };
+        for (script, language) in &self.state.lang_systems {
+            self.features.push(FeatureEntry {
+                script: *script,
+                language: *language,
+                feature,
+                lookup,
+            });
+        }
+    }
+
+    fn glyph(&self, name: &str, location: Location) -> Result<GlyphId, FeaError> {
+        self.glyph_map
+            .get(name)
+            .ok_or_else(|| FeaError::new(location, FeaErrorKind::UnknownGlyph(name.to_owned())))
+    }
+
+    /// The glyphs of a glyph class, in order.
+    fn class(&self, name: &str, location: Location) -> Result<Vec<GlyphId>, FeaError> {
+        if let Some(glyphs) = self.classes.get(name) {
+            return Ok(glyphs.clone());
+        }
+        // mark classes can also be used as glyph classes
+        if let Some(class) = self.mark_classes.get(name) {
+            return Ok(class.marks.keys().copied().collect());
+        }
+        Err(FeaError::new(
+            location,
+            FeaErrorKind::UnknownClass(name.to_owned()),
+        ))
+    }
+
+    fn glyphs(&self, expr: &GlyphExpr, location: Location) -> Result<Vec<GlyphId>, FeaError> {
+        match expr {
+            GlyphExpr::Glyph(name) => Ok(vec![self.glyph(name, location)?]),
+            GlyphExpr::Class(name) => self.class(name, location),
+            GlyphExpr::List(items) => {
+                let mut glyphs = Vec::new();
+                for item in items {
+                    match item {
+                        ListItem::Glyph(name) => glyphs.push(self.glyph(name, location)?),
+                        ListItem::Class(name) => glyphs.extend(self.class(name, location)?),
+                        ListItem::Range(start, end) => {
+                            let names = expand_range(start, end).ok_or_else(|| {
+                                invalid(location, format!("invalid glyph range '{start} - {end}'"))
+                            })?;
+                            for name in names {
+                                glyphs.push(self.glyph(&name, location)?);
+                            }
+                        }
+                    }
+                }
+                Ok(glyphs)
+            }
+        }
+    }
+
+    fn build(self) -> Result<Compilation, FeaError> {
+        let mut gdef = self.gdef;
+        let classes = self.explicit_classes.unwrap_or(self.inferred_classes);
+        let mut needs_gdef =
+            self.has_gdef_table || !classes.is_empty() || !self.mark_attach_classes.is_empty();
+        for (glyph, class) in classes {
+            gdef.set_glyph_class(glyph, class);
+        }
+        for (i, glyphs) in self.mark_attach_classes.iter().enumerate() {
+            for glyph in glyphs {
+                gdef.set_mark_attach_class(*glyph, i as u16 + 1);
+            }
+        }
+
+        let has_gsub = !self.gsub_lookups.is_empty();
+        let mut gsub = GsubBuilder::new();
+        for lookup in self.gsub_lookups {
+            let flags = lookup.flags;
+            let index = match lookup.kind {
+                LookupKind::SingleSub(builder) => gsub.add_single(flags, builder),
+                LookupKind::MultipleSub(builder) => gsub.add_multiple(flags, builder),
+                LookupKind::AlternateSub(builder) => gsub.add_alternate(flags, builder),
+                LookupKind::LigatureSub(builder) => gsub.add_ligature(flags, builder),
+                LookupKind::ChainSub(builder) => gsub.add_chain(flags, builder),
+                _ => unreachable!("positioning lookup in GSUB"),
+            };
+            if let Some(glyphs) = lookup.mark_filter {
+                gsub.set_mark_filtering_set(index, gdef.add_mark_glyph_set(glyphs));
+                needs_gdef = true;
+            }
+        }
+        let has_gpos = !self.gpos_lookups.is_empty();
+        let mut gpos = GposBuilder::new();
+        for lookup in self.gpos_lookups {
+            let flags = lookup.flags;
+            let index = match lookup.kind {
+                LookupKind::SinglePos(builder) => gpos.add_single(flags, builder),
+                LookupKind::PairPos(builder, _) => gpos.add_pair(flags, builder),
+                LookupKind::MarkBase(builder) => gpos.add_mark_base(flags, builder),
+                LookupKind::MarkMark(builder) => gpos.add_mark_mark(flags, builder),
+                _ => unreachable!("substitution lookup in GPOS"),
+            };
+            if let Some(glyphs) = lookup.mark_filter {
+                gpos.set_mark_filtering_set(index, gdef.add_mark_glyph_set(glyphs));
+                needs_gdef = true;
+            }
+        }
+        for entry in self.features {
+            let index = entry.lookup.index as u16;
+            match entry.lookup.table {
+                Table::Gsub => {
+                    gsub.add_feature(entry.script, entry.language, entry.feature, &[index])
+                }
+                Table::Gpos => {
+                    gpos.add_feature(entry.script, entry.language, entry.feature, &[index])
+                }
+            }
+        }
+
+        Ok(Compilation {
+            gsub: has_gsub.then(|| gsub.build()),
+            gpos: has_gpos.then(|| gpos.build()),
+            gdef: needs_gdef
+                .then(|| gdef.build(self.glyph_map.len()))
+                .transpose()?,
+        })
+    }
+}
+
+impl LookupKind {
+    fn table(&self) -> Table {
+        match self {
+            LookupKind::SingleSub(_)
+            | LookupKind::MultipleSub(_)
+            | LookupKind::AlternateSub(_)
+            | LookupKind::LigatureSub(_)
+            | LookupKind::ChainSub(_) => Table::Gsub,
+            LookupKind::SinglePos(_)
+            | LookupKind::PairPos(..)
+            | LookupKind::MarkBase(_)
+            | LookupKind::MarkMark(_) => Table::Gpos,
+        }
+    }
+
+    fn same_type(&self, other: &LookupKind) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl SubRule {
+    fn empty_lookup(&self) -> LookupKind {
+        match self {
+            SubRule::Single(_) => LookupKind::SingleSub(Default::default()),
+            SubRule::Multiple(_) => LookupKind::MultipleSub(Default::default()),
+            SubRule::Alternate(_) => LookupKind::AlternateSub(Default::default()),
+            SubRule::Ligature(_) => LookupKind::LigatureSub(Default::default()),
+        }
+    }
+
+    fn add_to(self, lookup: &mut LookupKind) {
+        match (self, lookup) {
+            (SubRule::Single(items), LookupKind::SingleSub(builder)) => {
+                for (target, replacement) in items {
+                    builder.insert(target, replacement);
+                }
+            }
+            (SubRule::Multiple(items), LookupKind::MultipleSub(builder)) => {
+                for (target, replacement) in items {
+                    builder.insert(target, replacement);
+                }
+            }
+            (SubRule::Alternate(items), LookupKind::AlternateSub(builder)) => {
+                for (target, alternates) in items {
+                    builder.insert(target, &alternates);
+                }
+            }
+            (SubRule::Ligature(items), LookupKind::LigatureSub(builder)) => {
+                for (sequence, ligature) in items {
+                    builder.insert(&sequence, ligature);
+                }
+            }
+            _ => unreachable!("lookup kind does not match rule"),
+        }
+    }
+}
+
+fn invalid(location: Location, message: impl Into<String>) -> FeaError {
+    FeaError::new(location, FeaErrorKind::InvalidRule(message.into()))
+}
+
+fn anchor_table(anchor: &Anchor) -> Option<AnchorTable> {
+    anchor.map(|anchor| match anchor.contour_point {
+        Some(point) => AnchorTable::format_2(anchor.x, anchor.y, point),
+        None => AnchorTable::format_1(anchor.x, anchor.y),
+    })
+}
+
+/// Every sequence that takes one glyph from each position.
+fn sequences(positions: &[Vec<GlyphId>]) -> Vec<Vec<GlyphId>> {
+    positions
+        .iter()
+        .fold(vec![Vec::new()], |sequences, glyphs| {
+            sequences
+                .iter()
+                .flat_map(|sequence| {
+                    glyphs.iter().map(move |glyph| {
+                        let mut sequence = sequence.clone();
+                        sequence.push(*glyph);
+                        sequence
+                    })
+                })
+                .collect()
+        })
+}
+
+/// The glyph names in a range, such as `a.sc - z.sc` or `cid01 - cid20`.
+///
+/// The names must differ only in a single letter, or in a number.
+fn expand_range<'a>(start: &'a str, end: &'a str) -> Option<Vec<String>> {
+    // a range of letters
+    if start.len() == end.len() {
+        let diffs = start
+            .bytes()
+            .zip(end.bytes())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .collect::<Vec<_>>();
+        if let [(i, (first, last))] = diffs.as_slice() {
+            let same_case = (first.is_ascii_lowercase() && last.is_ascii_lowercase())
+                || (first.is_ascii_uppercase() && last.is_ascii_uppercase());
+            if same_case && first < last {
+                let (prefix, suffix) = (&start[..*i], &start[i + 1..]);
+                return Some(
+                    (*first..=*last)
+                        .map(|c| format!("{prefix}{}{suffix}", c as char))
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    // a range of numbers
+    let common = start
+        .bytes()
+        .zip(end.bytes())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let prefix = start[..common].trim_end_matches(|c: char| c.is_ascii_digit());
+    let split_number = |name: &'a str| -> (&'a str, &'a str) {
+        let rest = &name[prefix.len()..];
+        let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+        (&rest[..len], &rest[len..])
+    };
+    let (first, suffix) = split_number(start);
+    let (last, end_suffix) = split_number(end);
+    if first.is_empty() || last.is_empty() || suffix != end_suffix {
+        return None;
+    }
+    let (first_value, last_value) = (first.parse::<u32>().ok()?, last.parse::<u32>().ok()?);
+    if first_value >= last_value {
+        return None;
+    }
+    let width = first.len();
+    Some(
+        (first_value..=last_value)
+            .map(|value| format!("{prefix}{value:0width$}{suffix}"))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fea::compile,
+        tables::{
+            gdef::GdefInputError,
+            gpos::{PairPos, PositionLookup, SinglePos},
+            gsub::{SingleSubst, SubstitutionLookup},
+            layout::{ChainedSequenceContext, FeatureList, ScriptList},
+        },
+        OffsetMarker,
+    };
+
+    const GLYPHS: &[&str] = &[
+        ".notdef",
+        "a",
+        "b",
+        "c",
+        "f",
+        "i",
+        "l",
+        "f_i",
+        "f_l",
+        "a.sc",
+        "b.sc",
+        "c.sc",
+        "A",
+        "V",
+        "T",
+        "o",
+        "acutecomb",
+        "gravecomb",
+        "a.alt1",
+        "a.alt2",
+        "cid08",
+        "cid09",
+        "cid10",
+    ];
+
+    fn gid(name: &str) -> GlyphId {
+        GlyphId::new(GLYPHS.iter().position(|n| *n == name).unwrap() as u16)
+    }
+
+    fn compile_ok(fea: &str) -> Compilation {
+        let glyph_map = GLYPHS.iter().copied().collect::<GlyphMap>();
+        compile(fea, &glyph_map).unwrap()
+    }
+
+    fn compile_err(fea: &str) -> FeaError {
+        let glyph_map = GLYPHS.iter().copied().collect::<GlyphMap>();
+        compile(fea, &glyph_map).unwrap_err()
+    }
+
+    /// The lookups of each feature, as `script/language/feature`.
+    fn features(scripts: &ScriptList, features: &FeatureList) -> Vec<(String, Vec<u16>)> {
+        let mut result = Vec::new();
+        for record in &scripts.script_records {
+            let script = &record.script;
+            let lang_systems = script
+                .default_lang_sys
+                .iter()
+                .map(|lang_sys| (DEFAULT_LANGUAGE, lang_sys))
+                .chain(
+                    script
+                        .lang_sys_records
+                        .iter()
+                        .map(|rec| (rec.lang_sys_tag, &*rec.lang_sys)),
+                );
+            for (language, lang_sys) in lang_systems {
+                for index in &lang_sys.feature_indices {
+                    let feature = &features.feature_records[*index as usize];
+                    result.push((
+                        format!("{}/{language}/{}", record.script_tag, feature.feature_tag),
+                        feature.feature.lookup_list_indices.clone(),
+                    ));
+                }
+            }
+        }
+        result.sort();
+        result
+    }
+
+    fn single_subst(subtable: &SingleSubst, glyph: GlyphId) -> Option<GlyphId> {
+        match subtable {
+            SingleSubst::Format1(table) => table.coverage.iter().any(|g| g == glyph).then(|| {
+                GlyphId::new((glyph.to_u16() as i32 + table.delta_glyph_id as i32) as u16)
+            }),
+            SingleSubst::Format2(table) => table
+                .coverage
+                .iter()
+                .position(|g| g == glyph)
+                .map(|i| table.substitute_glyph_ids[i]),
+        }
+    }
+
+    fn kern_value(subtables: &[OffsetMarker<PairPos>], first: GlyphId, second: GlyphId) -> i16 {
+        for subtable in subtables {
+            match &**subtable {
+                PairPos::Format1(table) => {
+                    let Some(idx) = table.coverage.iter().position(|gid| gid == first) else {
+                        continue;
+                    };
+                    if let Some(record) = table.pair_sets[idx]
+                        .pair_value_records
+                        .iter()
+                        .find(|rec| rec.second_glyph == second)
+                    {
+                        return record.value_record1.x_advance.unwrap_or(0);
+                    }
+                }
+                PairPos::Format2(table) => {
+                    if table.coverage.iter().any(|gid| gid == first) {
+                        let class1 = table.class_def1.get(first) as usize;
+                        let class2 = table.class_def2.get(second) as usize;
+                        return table.class1_records[class1].class2_records[class2]
+                            .value_record1
+                            .x_advance
+                            .unwrap_or(0);
+                    }
+                }
+            }
+        }
+        0
+    }
+
+    #[test]
+    fn substitutions() {
+        let compiled = compile_ok(
+            "
+            languagesystem DFLT dflt;
+            languagesystem latn dflt;
+            @lower = [a - c];
+            feature smcp {
+                sub @lower by [a.sc - c.sc];
+            } smcp;
+            feature liga {
+                sub f i by f_i;
+                sub f l by f_l;
+            } liga;
+            feature ccmp {
+                sub f_i by f i;
+                sub c by NULL;
+            } ccmp;
+            feature aalt {
+                sub a from [a.alt1 a.alt2];
+            } aalt;
+            ",
+        );
+        assert!(compiled.gpos.is_none());
+        assert!(compiled.gdef.is_none());
+        let gsub = compiled.gsub.unwrap();
+        let lookups = &gsub.lookup_list.lookups;
+        assert_eq!(lookups.len(), 4);
+
+        let SubstitutionLookup::Single(smcp) = &*lookups[0] else {
+            panic!("expected a single substitution");
+        };
+        assert_eq!(
+            single_subst(&smcp.subtables[0], gid("b")),
+            Some(gid("b.sc"))
+        );
+        let SubstitutionLookup::Ligature(liga) = &*lookups[1] else {
+            panic!("expected a ligature substitution");
+        };
+        let ligatures = &liga.subtables[0].ligature_sets[0].ligatures;
+        assert_eq!(ligatures.len(), 2);
+        assert_eq!(ligatures[0].component_glyph_ids, [gid("i")]);
+        let SubstitutionLookup::Multiple(ccmp) = &*lookups[2] else {
+            panic!("expected a multiple substitution");
+        };
+        let sequences = &ccmp.subtables[0].sequences;
+        // c, then f_i
+        assert!(sequences[0].substitute_glyph_ids.is_empty());
+        assert_eq!(sequences[1].substitute_glyph_ids, [gid("f"), gid("i")]);
+        let SubstitutionLookup::Alternate(aalt) = &*lookups[3] else {
+            panic!("expected an alternate substitution");
+        };
+        assert_eq!(
+            aalt.subtables[0].alternate_sets[0].alternate_glyph_ids,
+            [gid("a.alt1"), gid("a.alt2")]
+        );
+
+        let features = features(&gsub.script_list, &gsub.feature_list);
+        assert_eq!(features.len(), 8);
+        assert!(features.contains(&("DFLT/dflt/liga".into(), vec![1])));
+        assert!(features.contains(&("latn/dflt/aalt".into(), vec![3])));
+    }
+
+    #[test]
+    fn contextual_substitutions() {
+        let compiled = compile_ok(
+            "
+            lookup ALT {
+                sub a by a.alt1;
+            } ALT;
+            feature calt {
+                sub f a' lookup ALT b;
+                ignore sub a a';
+                sub c a' by a.alt2;
+            } calt;
+            ",
+        );
+        let gsub = compiled.gsub.unwrap();
+        let lookups = &gsub.lookup_list.lookups;
+        // the inline substitution is an anonymous lookup
+        assert_eq!(lookups.len(), 3);
+        assert!(matches!(&*lookups[2], SubstitutionLookup::Single(_)));
+        let SubstitutionLookup::ChainContextual(calt) = &*lookups[1] else {
+            panic!("expected a chaining lookup");
+        };
+        let mut rules = Vec::new();
+        for subtable in &calt.subtables {
+            let ChainedSequenceContext::Format1(table) = &***subtable else {
+                panic!("expected format 1");
+            };
+            for rule_set in table
+                .chained_seq_rule_sets
+                .iter()
+                .filter_map(|set| set.as_ref())
+            {
+                for rule in &rule_set.chained_seq_rules {
+                    rules.push((
+                        rule.backtrack_sequence.clone(),
+                        rule.seq_lookup_records
+                            .iter()
+                            .map(|rec| rec.lookup_list_index)
+                            .collect::<Vec<_>>(),
+                    ));
+                }
+            }
+        }
+        assert_eq!(rules.len(), 3);
+        assert!(rules.contains(&(vec![gid("f")], vec![0])));
+        assert!(rules.contains(&(vec![gid("a")], vec![])));
+        assert!(rules.contains(&(vec![gid("c")], vec![2])));
+
+        // named lookups are only registered where they are defined
+        let features = features(&gsub.script_list, &gsub.feature_list);
+        assert_eq!(features, [("DFLT/dflt/calt".to_string(), vec![1])]);
+    }
+
+    #[test]
+    fn languages() {
+        let compiled = compile_ok(
+            "
+            languagesystem latn dflt;
+            feature liga {
+                script latn;
+                sub f i by f_i;
+                language TRK;
+                sub f l by f_l;
+                language DEU exclude_dflt;
+                sub a by b;
+            } liga;
+            ",
+        );
+        let gsub = compiled.gsub.unwrap();
+        let features = features(&gsub.script_list, &gsub.feature_list);
+        assert_eq!(
+            features,
+            [
+                ("latn/DEU /liga".to_string(), vec![2]),
+                ("latn/TRK /liga".to_string(), vec![0, 1]),
+                ("latn/dflt/liga".to_string(), vec![0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn positioning() {
+        let compiled = compile_ok(
+            "
+            @round = [o c];
+            feature kern {
+                pos T @round -60;
+                pos T o -40;
+                pos A V -80;
+                enum pos [A V] [a b] -10;
+                pos @round T -15;
+            } kern;
+            feature vkrn {
+                pos a 50;
+            } vkrn;
+            feature cpsp {
+                pos [A V T] <5 0 10 0>;
+            } cpsp;
+            ",
+        );
+        assert!(compiled.gdef.is_none());
+        let gpos = compiled.gpos.unwrap();
+        let lookups = &gpos.lookup_list.lookups;
+        assert_eq!(lookups.len(), 3);
+        let PositionLookup::Pair(kern) = &*lookups[0] else {
+            panic!("expected pair positioning");
+        };
+        let kern = &kern.subtables;
+        assert_eq!(kern_value(kern, gid("T"), gid("c")), -60);
+        // the glyph pair overrides the class pair
+        assert_eq!(kern_value(kern, gid("T"), gid("o")), -40);
+        assert_eq!(kern_value(kern, gid("A"), gid("V")), -80);
+        assert_eq!(kern_value(kern, gid("V"), gid("b")), -10);
+        assert_eq!(kern_value(kern, gid("o"), gid("T")), -15);
+
+        let PositionLookup::Single(vkrn) = &*lookups[1] else {
+            panic!("expected single positioning");
+        };
+        let SinglePos::Format1(vkrn) = &*vkrn.subtables[0] else {
+            panic!("expected format 1");
+        };
+        assert_eq!(vkrn.value_record.y_advance, Some(50));
+        assert_eq!(vkrn.value_record.x_advance, None);
+        let PositionLookup::Single(cpsp) = &*lookups[2] else {
+            panic!("expected single positioning");
+        };
+        let SinglePos::Format1(cpsp) = &*cpsp.subtables[0] else {
+            panic!("expected format 1");
+        };
+        assert_eq!(cpsp.coverage.iter().count(), 3);
+        assert_eq!(cpsp.value_record.x_placement, Some(5));
+        assert_eq!(cpsp.value_record.x_advance, Some(10));
+    }
+
+    #[test]
+    fn mark_attachment() {
+        let compiled = compile_ok(
+            "
+            markClass acutecomb <anchor 150 -10> @TOP;
+            markClass gravecomb <anchor 150 -10> @TOP;
+            feature mark {
+                pos base [a b] <anchor 250 450> mark @TOP;
+            } mark;
+            feature mkmk {
+                pos mark acutecomb <anchor 150 200 contourpoint 3> mark @TOP;
+            } mkmk;
+            ",
+        );
+        let gpos = compiled.gpos.unwrap();
+        let lookups = &gpos.lookup_list.lookups;
+        let PositionLookup::MarkToBase(mark) = &*lookups[0] else {
+            panic!("expected mark-to-base positioning");
+        };
+        let mark = &mark.subtables[0];
+        assert_eq!(mark.mark_coverage.iter().count(), 2);
+        assert_eq!(
+            mark.base_coverage.iter().collect::<Vec<_>>(),
+            [gid("a"), gid("b")]
+        );
+        assert_eq!(mark.base_array.base_records[0].base_anchors.len(), 1);
+        let PositionLookup::MarkToMark(mkmk) = &*lookups[1] else {
+            panic!("expected mark-to-mark positioning");
+        };
+        let mkmk = &mkmk.subtables[0];
+        let anchor = mkmk.mark2_array.mark2_records[0].mark2_anchors[0]
+            .as_ref()
+            .unwrap();
+        let AnchorTable::Format2(anchor) = anchor else {
+            panic!("expected format 2");
+        };
+        assert_eq!(anchor.anchor_point, 3);
+
+        // glyph classes are inferred from the rules
+        let gdef = compiled.gdef.unwrap();
+        let classes = gdef.glyph_class_def.as_ref().unwrap();
+        assert_eq!(classes.get(gid("a")), GlyphClassDef::Base as u16);
+        assert_eq!(classes.get(gid("acutecomb")), GlyphClassDef::Mark as u16);
+        assert_eq!(classes.get(gid("f")), 0);
+    }
+
+    #[test]
+    fn gdef() {
+        let compiled = compile_ok(
+            "
+            markClass [acutecomb gravecomb] <anchor 0 500> @TOP;
+            table GDEF {
+                GlyphClassDef [a b], [f_i], [acutecomb], ;
+                LigatureCaretByPos f_i 300;
+                Attach a 3;
+            } GDEF;
+            feature mark {
+                lookupflag MarkAttachmentType [acutecomb];
+                pos base a <anchor 250 450> mark @TOP;
+                lookupflag UseMarkFilteringSet [gravecomb];
+                pos base b <anchor 250 450> mark @TOP;
+            } mark;
+            ",
+        );
+        let gdef = compiled.gdef.unwrap();
+        let classes = gdef.glyph_class_def.as_ref().unwrap();
+        // the explicit classes replace the inferred ones
+        assert_eq!(classes.get(gid("gravecomb")), 0);
+        assert_eq!(classes.get(gid("f_i")), GlyphClassDef::Ligature as u16);
+        assert!(gdef.lig_caret_list.is_some());
+        assert!(gdef.attach_list.is_some());
+        let mark_classes = gdef.mark_attach_class_def.as_ref().unwrap();
+        assert_eq!(mark_classes.get(gid("acutecomb")), 1);
+        let sets = gdef.mark_glyph_sets_def.as_ref().unwrap();
+        assert_eq!(sets.coverages.len(), 1);
+
+        let gpos = compiled.gpos.unwrap();
+        let lookups = &gpos.lookup_list.lookups;
+        let PositionLookup::MarkToBase(first) = &*lookups[0] else {
+            panic!("expected mark-to-base positioning");
+        };
+        assert_eq!(first.lookup_flag.mark_attachment_type_mask(), Some(1));
+        let PositionLookup::MarkToBase(second) = &*lookups[1] else {
+            panic!("expected mark-to-base positioning");
+        };
+        assert!(second.lookup_flag.use_mark_filtering_set());
+        assert_eq!(second.mark_filtering_set, 0);
+    }
+
+    #[test]
+    fn errors() {
+        let err = compile_err("feature liga {\n    sub f x by f_i;\n} liga;");
+        assert_eq!(err.kind(), &FeaErrorKind::UnknownGlyph("x".into()));
+        assert_eq!(err.location(), Some(Location { line: 2, column: 5 }));
+
+        let err = compile_err("feature calt { sub a' lookup NOPE b; } calt;");
+        assert_eq!(err.kind(), &FeaErrorKind::UnknownLookup("NOPE".into()));
+
+        let err = compile_err("feature liga { sub @lower by a; } liga;");
+        assert_eq!(err.kind(), &FeaErrorKind::UnknownClass("lower".into()));
+
+        let err = compile_err("lookup MIXED { sub a by b; sub f i by f_i; } MIXED;");
+        assert!(matches!(err.kind(), FeaErrorKind::InvalidRule(_)));
+
+        let err = compile_err("feature kern { pos a' b 10; } kern;");
+        assert!(matches!(err.kind(), FeaErrorKind::Unsupported(_)));
+
+        let err = compile_err("feature liga { sub f i by f_i; } liga;\nlanguagesystem DFLT dflt;");
+        assert_eq!(err.location(), Some(Location { line: 2, column: 1 }));
+
+        // lookup flags must come before the rules of a lookup
+        let err = compile_err("lookup L { sub a by b; lookupflag IgnoreMarks; } L;");
+        assert!(matches!(err.kind(), FeaErrorKind::InvalidRule(_)));
+
+        // GDEF errors are found when the table is built
+        let err =
+            compile_err("table GDEF { LigatureCaretByPos a 300; GlyphClassDef [a],,,; } GDEF;");
+        assert_eq!(
+            err.kind(),
+            &FeaErrorKind::Gdef(GdefInputError::CaretsForNonLigature(gid("a")))
+        );
+        assert_eq!(err.location(), None);
+    }
+
+    #[test]
+    fn glyph_ranges() {
+        let range = |start, end| expand_range(start, end).unwrap();
+        assert_eq!(range("a", "c"), ["a", "b", "c"]);
+        assert_eq!(range("a.sc", "c.sc"), ["a.sc", "b.sc", "c.sc"]);
+        assert_eq!(range("cid08", "cid10"), ["cid08", "cid09", "cid10"]);
+        assert_eq!(range("A.alt2", "A.alt4"), ["A.alt2", "A.alt3", "A.alt4"]);
+        assert_eq!(expand_range("a", "C"), None);
+        assert_eq!(expand_range("c", "a"), None);
+        assert_eq!(expand_range("a.sc", "b.alt"), None);
+    }
+}
@@ -0,0 +1,242 @@
+//! Splitting feature files into tokens.
+
+use super::{FeaError, FeaErrorKind, Location};
+
+/// A token, and where it starts in the source.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum TokenKind {
+    /// A keyword, tag, or glyph name.
+    ///
+    /// Names that are escaped with a backslash are never keywords.
+    Name {
+        text: String,
+        escaped: bool,
+    },
+    /// A named glyph class, without the leading '@'.
+    Class(String),
+    Number(i32),
+    Semi,
+    Comma,
+    Equals,
+    Hyphen,
+    Quote,
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    LeftAngle,
+    RightAngle,
+}
+
+impl TokenKind {
+    /// A description of the token, for error messages.
+    pub fn describe(&self) -> String {
+        match self {
+            TokenKind::Name { text, .. } => format!("'{text}'"),
+            TokenKind::Class(name) => format!("'@{name}'"),
+            TokenKind::Number(value) => format!("'{value}'"),
+            TokenKind::Semi => "';'".into(),
+            TokenKind::Comma => "','".into(),
+            TokenKind::Equals => "'='".into(),
+            TokenKind::Hyphen => "'-'".into(),
+            TokenKind::Quote => "'''".into(),
+            TokenKind::LeftBrace => "'{'".into(),
+            TokenKind::RightBrace => "'}'".into(),
+            TokenKind::LeftBracket => "'['".into(),
+            TokenKind::RightBracket => "']'".into(),
+            TokenKind::LeftAngle => "'<'".into(),
+            TokenKind::RightAngle => "'>'".into(),
+        }
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '+' | '*' | ':' | '^' | '|' | '~')
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || matches!(c, '_' | '.')
+}
+
+/// Split the source into tokens, skipping whitespace and comments.
+pub(crate) fn tokenize(source: &str) -> Result<Vec<Token>, FeaError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let (mut line, mut column) = (1, 1);
+
+    // consume the longest run of characters matching the predicate
+    fn take_while(
+        chars: &mut std::iter::Peekable<std::str::Chars>,
+        column: &mut usize,
+        pred: impl Fn(char) -> bool,
+    ) -> String {
+        let mut text = String::new();
+        while let Some(c) = chars.peek().copied().filter(|c| pred(*c)) {
+            text.push(c);
+            chars.next();
+            *column += 1;
+        }
+        text
+    }
+
+    while let Some(&c) = chars.peek() {
+        let location = Location { line, column };
+        let kind = match c {
+            '\n' => {
+                chars.next();
+                line += 1;
+                column = 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+                column += 1;
+                continue;
+            }
+            '#' => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+                continue;
+            }
+            '@' => {
+                chars.next();
+                column += 1;
+                let name = take_while(&mut chars, &mut column, is_name_char);
+                if name.is_empty() {
+                    return Err(FeaError::new(
+                        location,
+                        FeaErrorKind::InvalidSyntax("expected a class name after '@'".into()),
+                    ));
+                }
+                TokenKind::Class(name)
+            }
+            '\\' => {
+                chars.next();
+                column += 1;
+                let text = take_while(&mut chars, &mut column, is_name_char);
+                if text.is_empty() {
+                    return Err(FeaError::new(
+                        location,
+                        FeaErrorKind::InvalidSyntax("expected a glyph name after '\\'".into()),
+                    ));
+                }
+                TokenKind::Name {
+                    text,
+                    escaped: true,
+                }
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                chars.next();
+                column += 1;
+                let digits = take_while(&mut chars, &mut column, |c| c.is_ascii_digit());
+                if c == '-' && digits.is_empty() {
+                    TokenKind::Hyphen
+                } else {
+                    let text = format!("{c}{digits}");
+                    let value = text.parse().map_err(|_| {
+                        FeaError::new(
+                            location,
+                            FeaErrorKind::InvalidSyntax(format!("number '{text}' is too large")),
+                        )
+                    })?;
+                    TokenKind::Number(value)
+                }
+            }
+            c if is_name_start(c) => TokenKind::Name {
+                text: take_while(&mut chars, &mut column, is_name_char),
+                escaped: false,
+            },
+            _ => {
+                chars.next();
+                column += 1;
+                match c {
+                    ';' => TokenKind::Semi,
+                    ',' => TokenKind::Comma,
+                    '=' => TokenKind::Equals,
+                    '\'' => TokenKind::Quote,
+                    '{' => TokenKind::LeftBrace,
+                    '}' => TokenKind::RightBrace,
+                    '[' => TokenKind::LeftBracket,
+                    ']' => TokenKind::RightBracket,
+                    '<' => TokenKind::LeftAngle,
+                    '>' => TokenKind::RightAngle,
+                    _ => {
+                        return Err(FeaError::new(
+                            location,
+                            FeaErrorKind::InvalidSyntax(format!("unexpected character '{c}'")),
+                        ))
+                    }
+                }
+            }
+        };
+        tokens.push(Token { kind, location });
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source)
+            .unwrap()
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    fn name(text: &str) -> TokenKind {
+        TokenKind::Name {
+            text: text.into(),
+            escaped: false,
+        }
+    }
+
+    #[test]
+    fn tokens() {
+        assert_eq!(
+            kinds("pos @A f_i.alt -50; # kerning\nsub \\sub' [a - c];"),
+            [
+                name("pos"),
+                TokenKind::Class("A".into()),
+                name("f_i.alt"),
+                TokenKind::Number(-50),
+                TokenKind::Semi,
+                name("sub"),
+                TokenKind::Name {
+                    text: "sub".into(),
+                    escaped: true
+                },
+                TokenKind::Quote,
+                TokenKind::LeftBracket,
+                name("a"),
+                TokenKind::Hyphen,
+                name("c"),
+                TokenKind::RightBracket,
+                TokenKind::Semi,
+            ]
+        );
+    }
+
+    #[test]
+    fn positions() {
+        let tokens = tokenize("feature liga {\n  sub f i by f_i;").unwrap();
+        let sub = &tokens[3];
+        assert_eq!(sub.kind, name("sub"));
+        assert_eq!(sub.location, Location { line: 2, column: 3 });
+    }
+
+    #[test]
+    fn bad_character() {
+        let err = tokenize("sub a by b;\n  $").unwrap_err();
+        assert_eq!(err.location(), Some(Location { line: 2, column: 3 }));
+    }
+}
@@ -0,0 +1,923 @@
+//! Parsing feature file tokens into statements.
+
+use types::Tag;
+
+use super::{
+    lexer::{Token, TokenKind},
+    FeaError, FeaErrorKind, Location,
+};
+
+/// A statement, and where it starts in the source.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Node {
+    pub statement: Statement,
+    pub location: Location,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Statement {
+    LanguageSystem {
+        script: Tag,
+        language: Tag,
+    },
+    ClassDef {
+        name: String,
+        glyphs: GlyphExpr,
+    },
+    MarkClass {
+        glyphs: GlyphExpr,
+        anchor: Anchor,
+        name: String,
+    },
+    Feature {
+        tag: Tag,
+        statements: Vec<Node>,
+    },
+    Lookup {
+        name: String,
+        statements: Vec<Node>,
+    },
+    LookupRef(String),
+    Script(Tag),
+    Language {
+        tag: Tag,
+        include_default: bool,
+    },
+    LookupFlag(Vec<LookupFlagItem>),
+    Subtable,
+    Sub {
+        sequence: Vec<SequenceItem>,
+        replacement: Option<Replacement>,
+    },
+    IgnoreSub(Vec<Vec<SequenceItem>>),
+    Pos {
+        sequence: Vec<SequenceItem>,
+        /// `true` for `enum pos` rules, whose classes are expanded into
+        /// glyph-specific pairs.
+        enumerated: bool,
+    },
+    MarkBase {
+        base: GlyphExpr,
+        marks: Vec<(Anchor, String)>,
+    },
+    MarkMark {
+        base: GlyphExpr,
+        marks: Vec<(Anchor, String)>,
+    },
+    GlyphClassDef([Option<GlyphExpr>; 4]),
+    Attach {
+        glyphs: GlyphExpr,
+        points: Vec<u16>,
+    },
+    LigatureCaretByPos {
+        glyphs: GlyphExpr,
+        carets: Vec<i16>,
+    },
+    LigatureCaretByIndex {
+        glyphs: GlyphExpr,
+        points: Vec<u16>,
+    },
+    GdefTable(Vec<Node>),
+}
+
+/// A glyph, or a class of glyphs.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum GlyphExpr {
+    Glyph(String),
+    Class(String),
+    List(Vec<ListItem>),
+}
+
+/// An item in a bracketed glyph class.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ListItem {
+    Glyph(String),
+    Range(String, String),
+    Class(String),
+}
+
+/// An item in the sequence of a substitution or positioning rule.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SequenceItem {
+    pub glyphs: GlyphExpr,
+    /// `true` if the item is marked with a `'`, as part of the input of a
+    /// contextual rule.
+    pub marked: bool,
+    pub lookups: Vec<String>,
+    pub value: Option<ValueExpr>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Replacement {
+    By(Vec<GlyphExpr>),
+    From(GlyphExpr),
+}
+
+/// An anchor point; `None` for the `NULL` anchor.
+pub(crate) type Anchor = Option<AnchorPoint>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct AnchorPoint {
+    pub x: i16,
+    pub y: i16,
+    pub contour_point: Option<u16>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ValueExpr {
+    /// A single number, which adjusts the advance in the direction of the
+    /// feature.
+    Advance(i16),
+    Record {
+        x_placement: i16,
+        y_placement: i16,
+        x_advance: i16,
+        y_advance: i16,
+    },
+    Null,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum LookupFlagItem {
+    Bits(u16),
+    RightToLeft,
+    IgnoreBaseGlyphs,
+    IgnoreLigatures,
+    IgnoreMarks,
+    MarkAttachmentType(GlyphExpr),
+    UseMarkFilteringSet(GlyphExpr),
+}
+
+/// Parse tokens into a list of top-level statements.
+pub(crate) fn parse(tokens: Vec<Token>) -> Result<Vec<Node>, FeaError> {
+    let end = tokens
+        .last()
+        .map(|token| token.location)
+        .unwrap_or(Location { line: 1, column: 1 });
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        end,
+    };
+    let mut statements = Vec::new();
+    while !parser.at_end() {
+        statements.push(parser.top_level_statement()?);
+    }
+    Ok(statements)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    end: Location,
+}
+
+impl Parser {
+    fn at_end(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos).map(|token| &token.kind)
+    }
+
+    fn location(&self) -> Location {
+        self.tokens
+            .get(self.pos)
+            .map(|token| token.location)
+            .unwrap_or(self.end)
+    }
+
+    fn error(&self, kind: FeaErrorKind) -> FeaError {
+        FeaError::new(self.location(), kind)
+    }
+
+    fn unexpected(&self, expected: &str) -> FeaError {
+        match self.peek() {
+            Some(found) => self.error(FeaErrorKind::UnexpectedToken {
+                expected: expected.into(),
+                found: found.describe(),
+            }),
+            None => self.error(FeaErrorKind::UnexpectedEof {
+                expected: expected.into(),
+            }),
+        }
+    }
+
+    /// Consume the next token if it is `kind`.
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        let found = self.peek() == Some(kind);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<(), FeaError> {
+        if self.eat(&kind) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&kind.describe()))
+        }
+    }
+
+    /// Returns `true` if the next token is this (unescaped) keyword.
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(TokenKind::Name { text, escaped: false }) if text == keyword)
+    }
+
+    /// Consume the next token if it is this keyword.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.at_keyword(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), FeaError> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{keyword}'")))
+        }
+    }
+
+    fn expect_name(&mut self, expected: &str) -> Result<String, FeaError> {
+        match self.peek() {
+            Some(TokenKind::Name { text, .. }) => {
+                let text = text.clone();
+                self.pos += 1;
+                Ok(text)
+            }
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+
+    fn expect_tag(&mut self) -> Result<Tag, FeaError> {
+        let location = self.location();
+        let name = self.expect_name("a tag")?;
+        Tag::new_checked(name.as_bytes()).map_err(|_| {
+            FeaError::new(
+                location,
+                FeaErrorKind::InvalidSyntax(format!("'{name}' is not a valid tag")),
+            )
+        })
+    }
+
+    fn expect_number<T: TryFrom<i32>>(&mut self) -> Result<T, FeaError> {
+        match self.peek() {
+            Some(TokenKind::Number(value)) => {
+                let value = *value;
+                let result = T::try_from(value).map_err(|_| {
+                    self.error(FeaErrorKind::InvalidSyntax(format!(
+                        "number '{value}' is out of range"
+                    )))
+                });
+                self.pos += 1;
+                result
+            }
+            _ => Err(self.unexpected("a number")),
+        }
+    }
+
+    fn expect_class_name(&mut self) -> Result<String, FeaError> {
+        match self.peek() {
+            Some(TokenKind::Class(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => Err(self.unexpected("a glyph class")),
+        }
+    }
+
+    /// Check that the block is closed with its own name: `} name;`
+    fn expect_block_end(&mut self, name: &str) -> Result<(), FeaError> {
+        self.expect(TokenKind::RightBrace)?;
+        let location = self.location();
+        let end_name = self.expect_name(&format!("'{name}'"))?;
+        if end_name != name {
+            return Err(FeaError::new(
+                location,
+                FeaErrorKind::InvalidSyntax(format!("block '{name}' is closed with '{end_name}'")),
+            ));
+        }
+        self.expect(TokenKind::Semi)
+    }
+
+    fn unsupported(&self, what: &str) -> FeaError {
+        self.error(FeaErrorKind::Unsupported(what.into()))
+    }
+
+    fn top_level_statement(&mut self) -> Result<Node, FeaError> {
+        let location = self.location();
+        // cloned, so that the guards below can consume tokens
+        let statement = match self.peek().cloned() {
+            Some(TokenKind::Class(_)) => self.class_def()?,
+            _ if self.eat_keyword("languagesystem") => {
+                let script = self.expect_tag()?;
+                let language = self.expect_tag()?;
+                self.expect(TokenKind::Semi)?;
+                Statement::LanguageSystem { script, language }
+            }
+            _ if self.at_keyword("markClass") => self.mark_class()?,
+            _ if self.eat_keyword("feature") => {
+                let tag = self.expect_tag()?;
+                if self.at_keyword("useExtension") {
+                    self.pos += 1;
+                }
+                self.expect(TokenKind::LeftBrace)?;
+                let statements = self.block_statements()?;
+                self.expect_block_end(&tag.to_string())?;
+                Statement::Feature { tag, statements }
+            }
+            _ if self.at_keyword("lookup") => self.lookup()?,
+            _ if self.eat_keyword("table") => {
+                let tag = self.expect_tag()?;
+                if tag != Tag::GDEF {
+                    return Err(FeaError::new(
+                        location,
+                        FeaErrorKind::Unsupported(format!("the '{tag}' table block")),
+                    ));
+                }
+                self.expect(TokenKind::LeftBrace)?;
+                let mut statements = Vec::new();
+                while !matches!(self.peek(), Some(TokenKind::RightBrace) | None) {
+                    statements.push(self.gdef_statement()?);
+                }
+                self.expect_block_end("GDEF")?;
+                Statement::GdefTable(statements)
+            }
+            Some(TokenKind::Name { text, .. }) => {
+                return Err(self.unsupported(&format!("the '{text}' statement")))
+            }
+            _ => return Err(self.unexpected("a statement")),
+        };
+        Ok(Node {
+            statement,
+            location,
+        })
+    }
+
+    /// The statements in a feature or lookup block, up to the closing brace.
+    fn block_statements(&mut self) -> Result<Vec<Node>, FeaError> {
+        let mut statements = Vec::new();
+        while !matches!(self.peek(), Some(TokenKind::RightBrace) | None) {
+            statements.push(self.block_statement()?);
+        }
+        Ok(statements)
+    }
+
+    fn block_statement(&mut self) -> Result<Node, FeaError> {
+        let location = self.location();
+        // cloned, so that the guards below can consume tokens
+        let statement = match self.peek().cloned() {
+            Some(TokenKind::Class(_)) => self.class_def()?,
+            _ if self.at_keyword("markClass") => self.mark_class()?,
+            _ if self.at_keyword("lookup") => self.lookup()?,
+            _ if self.eat_keyword("script") => {
+                let tag = self.expect_tag()?;
+                self.expect(TokenKind::Semi)?;
+                Statement::Script(tag)
+            }
+            _ if self.eat_keyword("language") => {
+                let tag = self.expect_tag()?;
+                let include_default = if self.eat_keyword("exclude_dflt") {
+                    false
+                } else {
+                    self.eat_keyword("include_dflt");
+                    true
+                };
+                if self.at_keyword("required") {
+                    return Err(self.unsupported("the 'required' keyword"));
+                }
+                self.expect(TokenKind::Semi)?;
+                Statement::Language {
+                    tag,
+                    include_default,
+                }
+            }
+            _ if self.eat_keyword("lookupflag") => self.lookup_flag()?,
+            _ if self.eat_keyword("subtable") => {
+                self.expect(TokenKind::Semi)?;
+                Statement::Subtable
+            }
+            _ if self.eat_keyword("sub") || self.eat_keyword("substitute") => self.sub_rule()?,
+            _ if self.eat_keyword("ignore") => {
+                if !(self.eat_keyword("sub") || self.eat_keyword("substitute")) {
+                    if self.at_keyword("pos") || self.at_keyword("position") {
+                        return Err(self.unsupported("contextual positioning"));
+                    }
+                    return Err(self.unexpected("'sub'"));
+                }
+                let mut rules = vec![self.sequence(false)?];
+                while self.eat(&TokenKind::Comma) {
+                    rules.push(self.sequence(false)?);
+                }
+                self.expect(TokenKind::Semi)?;
+                Statement::IgnoreSub(rules)
+            }
+            _ if self.eat_keyword("pos") || self.eat_keyword("position") => self.pos_rule(false)?,
+            _ if self.eat_keyword("enum") || self.eat_keyword("enumerate") => {
+                if !(self.eat_keyword("pos") || self.eat_keyword("position")) {
+                    return Err(self.unexpected("'pos'"));
+                }
+                self.pos_rule(true)?
+            }
+            _ if self.at_keyword("rsub") || self.at_keyword("reversesub") => {
+                return Err(self.unsupported("reverse chaining substitution"))
+            }
+            Some(TokenKind::Name { text, .. }) => {
+                return Err(self.unsupported(&format!("the '{text}' statement")))
+            }
+            _ => return Err(self.unexpected("a statement")),
+        };
+        Ok(Node {
+            statement,
+            location,
+        })
+    }
+
+    fn gdef_statement(&mut self) -> Result<Node, FeaError> {
+        let location = self.location();
+        let statement = if self.eat_keyword("GlyphClassDef") {
+            let mut classes: [Option<GlyphExpr>; 4] = Default::default();
+            for (i, class) in classes.iter_mut().enumerate() {
+                if i > 0 {
+                    self.expect(TokenKind::Comma)?;
+                }
+                if !matches!(self.peek(), Some(TokenKind::Comma | TokenKind::Semi)) {
+                    *class = Some(self.glyph_expr()?);
+                }
+            }
+            self.expect(TokenKind::Semi)?;
+            Statement::GlyphClassDef(classes)
+        } else if self.eat_keyword("Attach") {
+            let glyphs = self.glyph_expr()?;
+            let points = self.numbers()?;
+            Statement::Attach { glyphs, points }
+        } else if self.eat_keyword("LigatureCaretByPos") {
+            let glyphs = self.glyph_expr()?;
+            let carets = self.numbers()?;
+            Statement::LigatureCaretByPos { glyphs, carets }
+        } else if self.eat_keyword("LigatureCaretByIndex") {
+            let glyphs = self.glyph_expr()?;
+            let points = self.numbers()?;
+            Statement::LigatureCaretByIndex { glyphs, points }
+        } else {
+            match self.peek() {
+                Some(TokenKind::Name { text, .. }) => {
+                    return Err(self.unsupported(&format!("the GDEF '{text}' statement")))
+                }
+                _ => return Err(self.unexpected("a GDEF statement")),
+            }
+        };
+        Ok(Node {
+            statement,
+            location,
+        })
+    }
+
+    /// One or more numbers, terminated by a semicolon.
+    fn numbers<T: TryFrom<i32>>(&mut self) -> Result<Vec<T>, FeaError> {
+        let mut numbers = vec![self.expect_number()?];
+        while !self.eat(&TokenKind::Semi) {
+            numbers.push(self.expect_number()?);
+        }
+        Ok(numbers)
+    }
+
+    fn class_def(&mut self) -> Result<Statement, FeaError> {
+        let name = self.expect_class_name()?;
+        self.expect(TokenKind::Equals)?;
+        let glyphs = self.glyph_expr()?;
+        self.expect(TokenKind::Semi)?;
+        Ok(Statement::ClassDef { name, glyphs })
+    }
+
+    fn mark_class(&mut self) -> Result<Statement, FeaError> {
+        self.expect_keyword("markClass")?;
+        let glyphs = self.glyph_expr()?;
+        let anchor = self.anchor()?;
+        let name = self.expect_class_name()?;
+        self.expect(TokenKind::Semi)?;
+        Ok(Statement::MarkClass {
+            glyphs,
+            anchor,
+            name,
+        })
+    }
+
+    /// A lookup block, or a reference to a named lookup.
+    fn lookup(&mut self) -> Result<Statement, FeaError> {
+        self.expect_keyword("lookup")?;
+        let name = self.expect_name("a lookup name")?;
+        if self.eat(&TokenKind::Semi) {
+            return Ok(Statement::LookupRef(name));
+        }
+        // extension lookups are used automatically where needed
+        self.eat_keyword("useExtension");
+        self.expect(TokenKind::LeftBrace)?;
+        let statements = self.block_statements()?;
+        self.expect_block_end(&name)?;
+        Ok(Statement::Lookup { name, statements })
+    }
+
+    fn lookup_flag(&mut self) -> Result<Statement, FeaError> {
+        if matches!(self.peek(), Some(TokenKind::Number(_))) {
+            let bits = self.expect_number()?;
+            self.expect(TokenKind::Semi)?;
+            return Ok(Statement::LookupFlag(vec![LookupFlagItem::Bits(bits)]));
+        }
+        let mut items = Vec::new();
+        while !self.eat(&TokenKind::Semi) {
+            let location = self.location();
+            let item = match self.expect_name("a lookup flag")?.as_str() {
+                "RightToLeft" => LookupFlagItem::RightToLeft,
+                "IgnoreBaseGlyphs" => LookupFlagItem::IgnoreBaseGlyphs,
+                "IgnoreLigatures" => LookupFlagItem::IgnoreLigatures,
+                "IgnoreMarks" => LookupFlagItem::IgnoreMarks,
+                "MarkAttachmentType" => LookupFlagItem::MarkAttachmentType(self.glyph_expr()?),
+                "UseMarkFilteringSet" => LookupFlagItem::UseMarkFilteringSet(self.glyph_expr()?),
+                other => {
+                    return Err(FeaError::new(
+                        location,
+                        FeaErrorKind::InvalidSyntax(format!("unknown lookup flag '{other}'")),
+                    ))
+                }
+            };
+            items.push(item);
+        }
+        Ok(Statement::LookupFlag(items))
+    }
+
+    fn sub_rule(&mut self) -> Result<Statement, FeaError> {
+        let sequence = self.sequence(false)?;
+        let replacement = if self.eat_keyword("by") {
+            let mut glyphs = vec![self.glyph_expr()?];
+            while !matches!(self.peek(), Some(TokenKind::Semi) | None) {
+                glyphs.push(self.glyph_expr()?);
+            }
+            Some(Replacement::By(glyphs))
+        } else if self.eat_keyword("from") {
+            Some(Replacement::From(self.glyph_expr()?))
+        } else {
+            None
+        };
+        self.expect(TokenKind::Semi)?;
+        Ok(Statement::Sub {
+            sequence,
+            replacement,
+        })
+    }
+
+    fn pos_rule(&mut self, enumerated: bool) -> Result<Statement, FeaError> {
+        let mark_attachment = if self.eat_keyword("base") {
+            Some(false)
+        } else if self.eat_keyword("mark") {
+            Some(true)
+        } else if self.at_keyword("ligature") {
+            return Err(self.unsupported("mark-to-ligature positioning"));
+        } else if self.at_keyword("cursive") {
+            return Err(self.unsupported("cursive positioning"));
+        } else {
+            None
+        };
+        let Some(is_mark_mark) = mark_attachment else {
+            let sequence = self.sequence(true)?;
+            self.expect(TokenKind::Semi)?;
+            return Ok(Statement::Pos {
+                sequence,
+                enumerated,
+            });
+        };
+        let base = self.glyph_expr()?;
+        let mut marks = Vec::new();
+        while !self.eat(&TokenKind::Semi) {
+            let anchor = self.anchor()?;
+            self.expect_keyword("mark")?;
+            marks.push((anchor, self.expect_class_name()?));
+        }
+        if marks.is_empty() {
+            return Err(self.error(FeaErrorKind::InvalidRule(
+                "expected at least one mark class".into(),
+            )));
+        }
+        Ok(if is_mark_mark {
+            Statement::MarkMark { base, marks }
+        } else {
+            Statement::MarkBase { base, marks }
+        })
+    }
+
+    /// The glyphs of a rule, up to the keyword or semicolon that ends it.
+    ///
+    /// Value records are only allowed in positioning rules.
+    fn sequence(&mut self, allow_values: bool) -> Result<Vec<SequenceItem>, FeaError> {
+        let mut items = Vec::new();
+        while !(matches!(self.peek(), Some(TokenKind::Semi | TokenKind::Comma) | None)
+            || self.at_keyword("by")
+            || self.at_keyword("from"))
+        {
+            let glyphs = self.glyph_expr()?;
+            let marked = self.eat(&TokenKind::Quote);
+            let mut lookups = Vec::new();
+            while self.eat_keyword("lookup") {
+                lookups.push(self.expect_name("a lookup name")?);
+            }
+            let value = match self.peek() {
+                Some(TokenKind::Number(_) | TokenKind::LeftAngle) if allow_values => {
+                    Some(self.value_record()?)
+                }
+                _ => None,
+            };
+            items.push(SequenceItem {
+                glyphs,
+                marked,
+                lookups,
+                value,
+            });
+        }
+        if items.is_empty() {
+            return Err(self.unexpected("a glyph or glyph class"));
+        }
+        Ok(items)
+    }
+
+    fn glyph_expr(&mut self) -> Result<GlyphExpr, FeaError> {
+        match self.peek() {
+            Some(TokenKind::Class(_)) => Ok(GlyphExpr::Class(self.expect_class_name()?)),
+            Some(TokenKind::LeftBracket) => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                while !self.eat(&TokenKind::RightBracket) {
+                    if let Some(TokenKind::Class(_)) = self.peek() {
+                        items.push(ListItem::Class(self.expect_class_name()?));
+                        continue;
+                    }
+                    let start = self.expect_name("a glyph name")?;
+                    if self.eat(&TokenKind::Hyphen) {
+                        let end = self.expect_name("a glyph name")?;
+                        items.push(ListItem::Range(start, end));
+                    } else {
+                        items.push(ListItem::Glyph(start));
+                    }
+                }
+                Ok(GlyphExpr::List(items))
+            }
+            Some(TokenKind::Name { .. }) => Ok(GlyphExpr::Glyph(self.expect_name("a glyph")?)),
+            _ => Err(self.unexpected("a glyph or glyph class")),
+        }
+    }
+
+    fn anchor(&mut self) -> Result<Anchor, FeaError> {
+        self.expect(TokenKind::LeftAngle)?;
+        self.expect_keyword("anchor")?;
+        if self.eat_keyword("NULL") {
+            self.expect(TokenKind::RightAngle)?;
+            return Ok(None);
+        }
+        if !matches!(self.peek(), Some(TokenKind::Number(_))) {
+            return Err(self.unsupported("named anchors"));
+        }
+        let x = self.expect_number()?;
+        let y = self.expect_number()?;
+        let contour_point = if self.eat_keyword("contourpoint") {
+            Some(self.expect_number()?)
+        } else {
+            None
+        };
+        if self.at(&TokenKind::LeftAngle) {
+            return Err(self.unsupported("device tables"));
+        }
+        self.expect(TokenKind::RightAngle)?;
+        Ok(Some(AnchorPoint {
+            x,
+            y,
+            contour_point,
+        }))
+    }
+
+    fn at(&self, kind: &TokenKind) -> bool {
+        self.peek() == Some(kind)
+    }
+
+    fn value_record(&mut self) -> Result<ValueExpr, FeaError> {
+        if !self.eat(&TokenKind::LeftAngle) {
+            return Ok(ValueExpr::Advance(self.expect_number()?));
+        }
+        if self.eat_keyword("NULL") {
+            self.expect(TokenKind::RightAngle)?;
+            return Ok(ValueExpr::Null);
+        }
+        if !matches!(self.peek(), Some(TokenKind::Number(_))) {
+            return Err(self.unsupported("named value records"));
+        }
+        let first = self.expect_number()?;
+        if self.eat(&TokenKind::RightAngle) {
+            return Ok(ValueExpr::Advance(first));
+        }
+        let y_placement = self.expect_number()?;
+        let x_advance = self.expect_number()?;
+        let y_advance = self.expect_number()?;
+        if self.at(&TokenKind::LeftAngle) {
+            return Err(self.unsupported("device tables"));
+        }
+        self.expect(TokenKind::RightAngle)?;
+        Ok(ValueExpr::Record {
+            x_placement: first,
+            y_placement,
+            x_advance,
+            y_advance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fea::lexer::tokenize;
+
+    fn parse_str(source: &str) -> Result<Vec<Node>, FeaError> {
+        parse(tokenize(source).unwrap())
+    }
+
+    fn statements(source: &str) -> Vec<Statement> {
+        parse_str(source)
+            .unwrap()
+            .into_iter()
+            .map(|node| node.statement)
+            .collect()
+    }
+
+    fn glyph(name: &str) -> GlyphExpr {
+        GlyphExpr::Glyph(name.into())
+    }
+
+    fn item(glyphs: GlyphExpr) -> SequenceItem {
+        SequenceItem {
+            glyphs,
+            marked: false,
+            lookups: Vec::new(),
+            value: None,
+        }
+    }
+
+    #[test]
+    fn feature_block() {
+        let parsed = statements(
+            "languagesystem latn dflt;
+            @lower = [a - c x];
+            feature liga {
+                script latn;
+                language TRK exclude_dflt;
+                lookupflag IgnoreMarks;
+                sub f i by f_i;
+                sub x a' lookup ALT b;
+            } liga;",
+        );
+        assert_eq!(
+            parsed[0],
+            Statement::LanguageSystem {
+                script: Tag::new(b"latn"),
+                language: Tag::new(b"dflt")
+            }
+        );
+        assert_eq!(
+            parsed[1],
+            Statement::ClassDef {
+                name: "lower".into(),
+                glyphs: GlyphExpr::List(vec![
+                    ListItem::Range("a".into(), "c".into()),
+                    ListItem::Glyph("x".into())
+                ])
+            }
+        );
+        let Statement::Feature { tag, statements } = &parsed[2] else {
+            panic!("expected a feature");
+        };
+        assert_eq!(*tag, Tag::new(b"liga"));
+        let statements = statements
+            .iter()
+            .map(|node| &node.statement)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            *statements[1],
+            Statement::Language {
+                tag: Tag::new(b"TRK"),
+                include_default: false
+            }
+        );
+        assert_eq!(
+            *statements[2],
+            Statement::LookupFlag(vec![LookupFlagItem::IgnoreMarks])
+        );
+        assert_eq!(
+            *statements[3],
+            Statement::Sub {
+                sequence: vec![item(glyph("f")), item(glyph("i"))],
+                replacement: Some(Replacement::By(vec![glyph("f_i")])),
+            }
+        );
+        let Statement::Sub { sequence, .. } = statements[4] else {
+            panic!("expected a substitution");
+        };
+        assert!(sequence[1].marked);
+        assert_eq!(sequence[1].lookups, ["ALT"]);
+        assert!(!sequence[2].marked);
+    }
+
+    #[test]
+    fn positioning() {
+        let parsed = statements(
+            "feature kern {
+                pos a b -50;
+                pos c <10 0 20 0> d <NULL>;
+                pos base [a b] <anchor 250 450> mark @TOP <anchor NULL> mark @BOTTOM;
+            } kern;",
+        );
+        let Statement::Feature { statements, .. } = &parsed[0] else {
+            panic!("expected a feature");
+        };
+        let Statement::Pos { sequence: pair, .. } = &statements[0].statement else {
+            panic!("expected a pair");
+        };
+        assert_eq!(pair[0].value, None);
+        assert_eq!(pair[1].value, Some(ValueExpr::Advance(-50)));
+        let Statement::Pos { sequence: pair, .. } = &statements[1].statement else {
+            panic!("expected a pair");
+        };
+        assert_eq!(
+            pair[0].value,
+            Some(ValueExpr::Record {
+                x_placement: 10,
+                y_placement: 0,
+                x_advance: 20,
+                y_advance: 0
+            })
+        );
+        assert_eq!(pair[1].value, Some(ValueExpr::Null));
+        let Statement::MarkBase { marks, .. } = &statements[2].statement else {
+            panic!("expected mark-to-base");
+        };
+        assert_eq!(
+            marks[0],
+            (
+                Some(AnchorPoint {
+                    x: 250,
+                    y: 450,
+                    contour_point: None
+                }),
+                "TOP".into()
+            )
+        );
+        assert_eq!(marks[1], (None, "BOTTOM".into()));
+    }
+
+    #[test]
+    fn gdef_table() {
+        let parsed = statements(
+            "table GDEF {
+                GlyphClassDef @BASE, , [acute], ;
+                LigatureCaretByPos f_i 300;
+            } GDEF;",
+        );
+        let Statement::GdefTable(statements) = &parsed[0] else {
+            panic!("expected a GDEF table");
+        };
+        assert_eq!(
+            statements[0].statement,
+            Statement::GlyphClassDef([
+                Some(GlyphExpr::Class("BASE".into())),
+                None,
+                Some(GlyphExpr::List(vec![ListItem::Glyph("acute".into())])),
+                None
+            ])
+        );
+        assert_eq!(
+            statements[1].statement,
+            Statement::LigatureCaretByPos {
+                glyphs: glyph("f_i"),
+                carets: vec![300]
+            }
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse_str("feature liga {\n  sub f i by f_i\n} liga;").unwrap_err();
+        assert_eq!(err.location(), Some(Location { line: 3, column: 1 }));
+        assert!(matches!(err.kind(), FeaErrorKind::UnexpectedToken { .. }));
+
+        let err = parse_str("feature liga { sub f i by f_i; } kern;").unwrap_err();
+        assert!(matches!(err.kind(), FeaErrorKind::InvalidSyntax(_)));
+
+        let err = parse_str("feature kern { pos cursive a <anchor 0 0> <anchor NULL>; } kern;")
+            .unwrap_err();
+        assert!(matches!(err.kind(), FeaErrorKind::Unsupported(_)));
+
+        let err = parse_str("feature liga {").unwrap_err();
+        assert!(matches!(err.kind(), FeaErrorKind::UnexpectedEof { .. }));
+    }
+}
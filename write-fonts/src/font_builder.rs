@@ -1,23 +1,213 @@
 //!  A builder for top-level font objects
 
 use std::borrow::Cow;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
-use types::{Tag, TT_SFNT_VERSION};
+use read_fonts::{FontRef, TableProvider};
+use types::{GlyphId, LongDateTime, Tag, CFF_SFTN_VERSION, TTC_HEADER_TAG, TT_SFNT_VERSION};
+
+use crate::tables::{
+    glyf::{Bbox, Contour, GlyfLocaBuilder, Glyph},
+    head::Head,
+    hmtx::Hmtx,
+    maxp::Maxp,
+};
 
 include!("../generated/generated_font.rs");
 
 const TABLE_RECORD_LEN: usize = 16;
 
+/// The position of `checksumAdjustment` in the `head` table.
+const HEAD_CHECKSUM_RANGE: std::ops::Range<usize> = 8..12;
+/// The position of `modified` in the `head` table.
+const HEAD_MODIFIED_RANGE: std::ops::Range<usize> = 28..36;
+/// The checksum of a complete font, after `checksumAdjustment` is set.
+const CHECKSUM_MAGIC: u32 = 0xB1B0AFBA;
+
 /// Build a font from some set of tables.
+///
+/// The output depends only on the tables that are added (and on the modified
+/// date, if it is set): the same tables always produce the same bytes.
+///
+/// A builder can also be created from an existing font, in order to edit it:
+///
+/// ```no_run
+/// # let path_to_font = "";
+/// use write_fonts::{read::FontRef, tables::name::Name, types::NameId, FontBuilder};
+///
+/// let data = std::fs::read(path_to_font).unwrap();
+/// let font = FontRef::new(&data).unwrap();
+/// let mut builder = FontBuilder::from(font);
+/// builder
+///     .edit_table(|name: &mut Name| name.name_record.retain(|rec| rec.name_id != NameId::SAMPLE_TEXT))
+///     .unwrap();
+/// let new_font = builder.build();
+/// ```
+///
+/// Tables are only parsed when they are edited; all other tables, including
+/// those this crate does not support, are copied unchanged.
 #[derive(Debug, Clone, Default)]
 pub struct FontBuilder<'a> {
     tables: BTreeMap<Tag, Cow<'a, [u8]>>,
+    modified_date: Option<LongDateTime>,
+}
+
+/// An error that prevents a table of a [`FontBuilder`] from being edited.
+#[derive(Debug)]
+pub enum EditError {
+    /// A table to be edited, or a table needed to edit it, is missing or
+    /// could not be read.
+    Read(ReadError),
+    /// An edited table could not be written.
+    Write(crate::Error),
+    /// A glyph id is not less than the number of glyphs in the font.
+    GlyphOutOfRange { glyph: GlyphId, num_glyphs: u16 },
 }
 
 impl<'a> FontBuilder<'a> {
     pub fn add_table(&mut self, tag: Tag, data: impl Into<Cow<'a, [u8]>>) -> &mut Self {
         self.tables.insert(tag, data.into());
+        if tag == Tag::HEAD {
+            self.apply_modified_date();
+        }
+        self
+    }
+
+    /// Compile a table and add it, replacing any table with the same tag.
+    pub fn add_typed_table<T: TopLevelTable + FontWrite + Validate>(
+        &mut self,
+        table: &T,
+    ) -> Result<&mut Self, crate::Error> {
+        let data = crate::dump_table(table)?;
+        Ok(self.add_table(T::TAG, data))
+    }
+
+    /// Remove a table, returning its data if it was present.
+    pub fn remove_table(&mut self, tag: Tag) -> Option<Cow<'a, [u8]>> {
+        self.tables.remove(&tag)
+    }
+
+    /// The data of the table with this tag, if the builder contains it.
+    pub fn table_data(&self, tag: Tag) -> Option<&[u8]> {
+        self.tables.get(&tag).map(|data| data.as_ref())
+    }
+
+    /// Parse a table into its compile type, modify it, and replace the table
+    /// with the result.
+    ///
+    /// The table is compiled from scratch, so the new data may differ from
+    /// the old even if `edit` makes no changes: for instance, subtables that
+    /// were shared may be written more than once, or in a different order.
+    pub fn edit_table<T>(&mut self, edit: impl FnOnce(&mut T)) -> Result<&mut Self, EditError>
+    where
+        T: TopLevelTable + FontWrite + Validate + for<'b> FontRead<'b>,
+    {
+        let mut table: T = (&*self).expect_table()?;
+        edit(&mut table);
+        self.add_typed_table(&table).map_err(EditError::Write)
+    }
+
+    /// Replace the outline of one glyph in the `glyf` table.
+    ///
+    /// This also updates the `loca` table and its format in `head`, the font
+    /// bounding box in `head`, the glyph's left side bearing in `hmtx` (if
+    /// present), and the maximum point and contour counts in `maxp`. The data
+    /// of the other glyphs is copied unchanged.
+    ///
+    /// In a variable font the glyph's variations in `gvar` are not changed,
+    /// and must still match the new outline.
+    pub fn set_glyph(&mut self, glyph: GlyphId, new_glyph: &Glyph) -> Result<&mut Self, EditError> {
+        let font = &*self;
+        let mut maxp: Maxp = font.maxp()?.to_owned_table();
+        let num_glyphs = maxp.num_glyphs;
+        if glyph.to_u16() >= num_glyphs {
+            return Err(EditError::GlyphOutOfRange { glyph, num_glyphs });
+        }
+        let loca = font.loca(None)?;
+        let glyf = font.glyf()?;
+        let mut builder = GlyfLocaBuilder::new();
+        let mut font_bbox: Option<Bbox> = None;
+        for gid in (0..num_glyphs).map(GlyphId::new) {
+            let bbox = if gid == glyph {
+                builder.add_glyph(new_glyph);
+                new_glyph.bbox()
+            } else {
+                let start = loca.get_raw(gid.to_u16() as usize);
+                let end = loca.get_raw(gid.to_u16() as usize + 1);
+                let data = start
+                    .zip(end)
+                    .and_then(|(start, end)| glyf.offset_data().slice(start as usize..end as usize))
+                    .ok_or(ReadError::OutOfBounds)?;
+                builder.add_glyph_data(data.as_ref());
+                loca.get_glyf(gid, &glyf)?.map(|glyph| Bbox {
+                    x_min: glyph.x_min(),
+                    y_min: glyph.y_min(),
+                    x_max: glyph.x_max(),
+                    y_max: glyph.y_max(),
+                })
+            };
+            if let Some(bbox) = bbox {
+                font_bbox = Some(font_bbox.map_or(bbox, |font_bbox| font_bbox.union(bbox)));
+            }
+        }
+
+        let mut head: Head = font.head()?.to_owned_table();
+        let hmtx: Option<Hmtx> = match font.contains(Tag::HMTX) {
+            true => Some(font.hmtx()?.to_owned_table()),
+            false => None,
+        };
+        if let Glyph::Simple(simple) = new_glyph {
+            let num_points = simple.contours().iter().map(Contour::len).sum();
+            let update = |field: &mut Option<u16>, value: usize| {
+                if let Some(field) = field {
+                    *field = (*field).max(value.min(u16::MAX as usize) as u16);
+                }
+            };
+            update(&mut maxp.max_points, num_points);
+            update(&mut maxp.max_contours, simple.contours().len());
+        }
+        let (glyf, loca, loca_format) = builder.build();
+        head.set_loca_format(loca_format);
+        let font_bbox = font_bbox.unwrap_or_default();
+        head.x_min = font_bbox.x_min;
+        head.y_min = font_bbox.y_min;
+        head.x_max = font_bbox.x_max;
+        head.y_max = font_bbox.y_max;
+
+        let write =
+            |result: Result<&mut Self, crate::Error>| result.map(|_| ()).map_err(EditError::Write);
+        write(self.add_typed_table(&glyf))?;
+        write(self.add_typed_table(&loca))?;
+        write(self.add_typed_table(&head))?;
+        write(self.add_typed_table(&maxp))?;
+        if let Some(mut hmtx) = hmtx {
+            let side_bearing = new_glyph.bbox().map(|bbox| bbox.x_min).unwrap_or_default();
+            let index = glyph.to_u16() as usize;
+            match hmtx.h_metrics.get_mut(index) {
+                Some(metric) => metric.side_bearing = side_bearing,
+                None => {
+                    if let Some(lsb) = hmtx
+                        .left_side_bearings
+                        .get_mut(index - hmtx.h_metrics.len())
+                    {
+                        *lsb = side_bearing;
+                    }
+                }
+            }
+            write(self.add_typed_table(&hmtx))?;
+        }
+        Ok(self)
+    }
+
+    /// Override the `modified` field of the `head` table.
+    ///
+    /// This applies to a `head` table added before or after this is called.
+    /// Tools that stamp `head` with the current time can use this to produce
+    /// reproducible output, for instance by passing the time from the
+    /// `SOURCE_DATE_EPOCH` environment variable.
+    pub fn set_modified_date(&mut self, date: LongDateTime) -> &mut Self {
+        self.modified_date = Some(date);
+        self.apply_modified_date();
         self
     }
 
@@ -26,42 +216,287 @@ impl<'a> FontBuilder<'a> {
         self.tables.contains_key(&tag)
     }
 
+    /// Assemble all of the tables into a font.
+    ///
+    /// Tables are written in tag order, each padded to a four-byte boundary.
+    /// The checksum of each table is computed, and if there is a `head` table
+    /// its `checksumAdjustment` field is updated for the final font.
     pub fn build(&mut self) -> Vec<u8> {
-        let header_len = std::mem::size_of::<u32>() // sfnt
+        let mut position = self.directory_len() as u32;
+        let offsets = self
+            .tables
+            .values()
+            .map(|data| {
+                let offset = position;
+                position += padded_len(data);
+                offset
+            })
+            .collect::<Vec<_>>();
+
+        let (mut data, checksum) = self.directory(&offsets);
+        for table in self.tables.values() {
+            data.extend_from_slice(table);
+            data.resize(data.len() + (padded_len(table) as usize - table.len()), 0);
+        }
+        if let Some(head_offset) = self.head_offset(&offsets) {
+            set_checksum_adjustment(&mut data[head_offset..], checksum);
+        }
+        data
+    }
+
+    fn apply_modified_date(&mut self) {
+        let Some(date) = self.modified_date else {
+            return;
+        };
+        if let Some(head) = self.tables.get_mut(&Tag::HEAD) {
+            if head.len() >= HEAD_MODIFIED_RANGE.end {
+                head.to_mut()[HEAD_MODIFIED_RANGE].copy_from_slice(&date.to_be_bytes());
+            }
+        }
+    }
+
+    /// The size of the table directory of this font.
+    fn directory_len(&self) -> usize {
+        std::mem::size_of::<u32>() // sfnt
             + std::mem::size_of::<u16>() * 4 // num_tables to range_shift
-            + self.tables.len() * TABLE_RECORD_LEN;
+            + self.tables.len() * TABLE_RECORD_LEN
+    }
 
-        let mut position = header_len as u32;
+    /// The offset of the `head` table, if it has a `checksumAdjustment` field.
+    fn head_offset(&self, offsets: &[u32]) -> Option<usize> {
+        self.tables
+            .iter()
+            .zip(offsets)
+            .find(|((tag, data), _)| **tag == Tag::HEAD && data.len() >= HEAD_CHECKSUM_RANGE.end)
+            .map(|(_, offset)| *offset as usize)
+    }
+
+    /// Write the table directory, given the offset of each table.
+    ///
+    /// Returns the directory, and the checksum of the font: the sum of the
+    /// checksums of the directory and of each table, with the `head` table's
+    /// `checksumAdjustment` set to zero.
+    fn directory(&self, offsets: &[u32]) -> (Vec<u8>, u32) {
+        let mut font_checksum = 0u32;
         let table_records = self
             .tables
-            .iter_mut()
-            .map(|(tag, data)| {
-                let offset = position;
-                let length = data.len() as u32;
-                position += length;
-                let (checksum, padding) = checksum_and_padding(data);
-                position += padding;
-                TableRecord::new(*tag, checksum, offset, length)
+            .iter()
+            .zip(offsets)
+            .map(|((tag, data), offset)| {
+                let (mut checksum, _) = checksum_and_padding(data);
+                if *tag == Tag::HEAD && data.len() >= HEAD_CHECKSUM_RANGE.end {
+                    // the head checksum is computed with checksumAdjustment set to 0
+                    checksum = checksum.wrapping_sub(read_u32(&data[HEAD_CHECKSUM_RANGE]));
+                }
+                font_checksum = font_checksum.wrapping_add(checksum);
+                TableRecord::new(*tag, checksum, *offset, data.len() as u32)
             })
             .collect();
 
-        let directory = TableDirectory::new(TT_SFNT_VERSION, 0, 0, 0, table_records);
+        let sfnt_version = if self.contains(Tag::CFF) || self.contains(Tag::CFF2) {
+            CFF_SFTN_VERSION
+        } else {
+            TT_SFNT_VERSION
+        };
+        let (search_range, entry_selector, range_shift) = search_params(self.tables.len());
+        let directory = TableDirectory::new(
+            sfnt_version,
+            search_range,
+            entry_selector,
+            range_shift,
+            table_records,
+        );
 
         let mut writer = TableWriter::default();
         directory.write_into(&mut writer);
-        let mut data = writer.into_data();
-        for table in self.tables.values() {
+        let data = writer.into_data();
+        let (checksum, _) = checksum_and_padding(&data);
+        (data, font_checksum.wrapping_add(checksum))
+    }
+}
+
+impl<'a> From<FontRef<'a>> for FontBuilder<'a> {
+    /// Create a builder containing all of the tables of a font.
+    ///
+    /// The table data is borrowed from the font until it is replaced.
+    fn from(font: FontRef<'a>) -> Self {
+        let mut builder = FontBuilder::default();
+        for record in font.table_records() {
+            if let Some(data) = font.data_for_record(record) {
+                builder.add_table(record.tag(), data);
+            }
+        }
+        builder
+    }
+}
+
+impl<'b> TableProvider<'b> for &'b FontBuilder<'_> {
+    fn data_for_tag(&self, tag: Tag) -> Option<FontData<'b>> {
+        self.tables.get(&tag).map(|data| FontData::new(data))
+    }
+}
+
+impl std::fmt::Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditError::Read(error) => write!(f, "failed to read table: {error}"),
+            EditError::Write(error) => write!(f, "failed to write table: {error}"),
+            EditError::GlyphOutOfRange { glyph, num_glyphs } => write!(
+                f,
+                "glyph {} is out of range for a font with {num_glyphs} glyphs",
+                glyph.to_u16()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+impl From<ReadError> for EditError {
+    fn from(value: ReadError) -> Self {
+        EditError::Read(value)
+    }
+}
+
+/// Build a font collection (a TTC file) from a set of fonts.
+///
+/// Tables with identical contents are written once, and shared by each font
+/// that contains them. The exception is the `head` table, which is never
+/// shared, since its `checksumAdjustment` is specific to each font.
+#[derive(Debug, Clone, Default)]
+pub struct CollectionBuilder<'a> {
+    fonts: Vec<FontBuilder<'a>>,
+}
+
+impl<'a> CollectionBuilder<'a> {
+    /// Add a font to the collection; its index is the number of fonts
+    /// previously added.
+    pub fn add_font(&mut self, font: FontBuilder<'a>) -> &mut Self {
+        self.fonts.push(font);
+        self
+    }
+
+    /// Assemble all of the fonts into a collection.
+    ///
+    /// The collection header is followed by the table directory of each font,
+    /// and then by the tables, in the order they are first used.
+    pub fn build(&self) -> Vec<u8> {
+        let header_len = std::mem::size_of::<u32>() * 3 // tag, version, num_fonts
+            + self.fonts.len() * std::mem::size_of::<u32>();
+        let mut position = header_len as u32;
+        let directory_offsets = self
+            .fonts
+            .iter()
+            .map(|font| {
+                let offset = position;
+                position += font.directory_len() as u32;
+                offset
+            })
+            .collect::<Vec<_>>();
+
+        // assign each table an offset, sharing identical tables
+        let mut shared = HashMap::new();
+        let mut tables = Vec::new();
+        let table_offsets = self
+            .fonts
+            .iter()
+            .map(|font| {
+                font.tables
+                    .iter()
+                    .map(|(tag, data)| {
+                        let data = data.as_ref();
+                        if *tag != Tag::HEAD {
+                            if let Some(offset) = shared.get(data) {
+                                return *offset;
+                            }
+                            shared.insert(data, position);
+                        }
+                        tables.push(data);
+                        let offset = position;
+                        position += padded_len(data);
+                        offset
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut data = Vec::with_capacity(position as usize);
+        data.extend_from_slice(&TTC_HEADER_TAG.to_be_bytes());
+        data.extend_from_slice(&MajorMinor::VERSION_1_0.to_be_bytes());
+        data.extend_from_slice(&(self.fonts.len() as u32).to_be_bytes());
+        for offset in &directory_offsets {
+            data.extend_from_slice(&offset.to_be_bytes());
+        }
+        let mut checksums = Vec::with_capacity(self.fonts.len());
+        for (font, offsets) in self.fonts.iter().zip(&table_offsets) {
+            let (directory, checksum) = font.directory(offsets);
+            data.extend_from_slice(&directory);
+            checksums.push(checksum);
+        }
+        for table in tables {
             data.extend_from_slice(table);
-            let rem = table.len() % 4;
-            let padding = [0u8; 4];
-            data.extend_from_slice(&padding[..rem]);
+            data.resize(data.len() + (padded_len(table) as usize - table.len()), 0);
+        }
+        for ((font, offsets), checksum) in self.fonts.iter().zip(&table_offsets).zip(checksums) {
+            if let Some(head_offset) = font.head_offset(offsets) {
+                set_checksum_adjustment(&mut data[head_offset..], checksum);
+            }
         }
         data
     }
 }
 
-fn checksum_and_padding(table: &[u8]) -> (u32, u32) {
-    let padding = table.len() % 4;
+/// Replace the table with this tag in a font, or insert it if the font does
+/// not contain it, and return the data of the new font.
+///
+/// The other tables are copied unchanged; the table directory, the checksums,
+/// and the `head` table's `checksumAdjustment` are recomputed.
+pub fn splice_table(font: &FontRef, tag: Tag, data: &[u8]) -> Vec<u8> {
+    let mut builder = FontBuilder::default();
+    for record in font.table_records() {
+        if let Some(table) = font.data_for_record(record) {
+            builder.add_table(record.tag(), table);
+        }
+    }
+    builder.add_table(tag, data);
+    builder.build()
+}
+
+/// Set the `checksumAdjustment` of the `head` table at the start of `head`,
+/// for a font with this checksum.
+fn set_checksum_adjustment(head: &mut [u8], font_checksum: u32) {
+    let adjustment = CHECKSUM_MAGIC.wrapping_sub(font_checksum);
+    head[HEAD_CHECKSUM_RANGE].copy_from_slice(&adjustment.to_be_bytes());
+}
+
+/// The length of a table, padded to a four-byte boundary.
+fn padded_len(table: &[u8]) -> u32 {
+    let (_, padding) = checksum_and_padding(table);
+    table.len() as u32 + padding
+}
+
+/// Compute the `searchRange`, `entrySelector` and `rangeShift` fields of the
+/// table directory.
+fn search_params(num_tables: usize) -> (u16, u16, u16) {
+    if num_tables == 0 {
+        return (0, 0, 0);
+    }
+    let entry_selector = num_tables.ilog2();
+    let search_range = (1usize << entry_selector) * TABLE_RECORD_LEN;
+    let range_shift = num_tables * TABLE_RECORD_LEN - search_range;
+    (
+        search_range as u16,
+        entry_selector as u16,
+        range_shift as u16,
+    )
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes.try_into().unwrap_or_default())
+}
+
+pub(crate) fn checksum_and_padding(table: &[u8]) -> (u32, u32) {
+    let padding = (4 - table.len() % 4) % 4;
     let mut sum = 0u32;
     let mut iter = table.chunks_exact(4);
     for quad in &mut iter {
@@ -82,6 +517,347 @@ fn checksum_and_padding(table: &[u8]) -> (u32, u32) {
 
 impl TTCHeader {
     fn compute_version(&self) -> MajorMinor {
-        panic!("TTCHeader writing not supported (yet)")
+        if self.dsig_tag.is_some() {
+            MajorMinor::VERSION_2_0
+        } else {
+            MajorMinor::VERSION_1_0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::{
+        tables::glyf::{CurvePoint, Glyph as ReadGlyph},
+        CollectionRef,
+    };
+
+    use super::*;
+    use crate::tables::glyf::SimpleGlyph;
+    use types::NameId;
+
+    /// The checksum of a font in a collection: its directory, and its tables.
+    fn font_checksum(data: &[u8], index: usize, font: &FontRef) -> u32 {
+        let start = read_u32(&data[12 + index * 4..16 + index * 4]) as usize;
+        let directory_len = 12 + font.table_directory.table_records().len() * TABLE_RECORD_LEN;
+        let directory = &data[start..start + directory_len];
+        font.table_records()
+            .fold(checksum_and_padding(directory).0, |sum, record| {
+                let table = font.data_for_record(record).unwrap();
+                sum.wrapping_add(checksum_and_padding(table.as_ref()).0)
+            })
+    }
+
+    #[test]
+    fn padding_and_checksums() {
+        let mut head = vec![0u8; 54];
+        head[8..12].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+        head[0] = 1;
+        let mut builder = FontBuilder::default();
+        builder
+            .add_table(Tag::new(b"zzzz"), vec![1, 2, 3, 4, 5])
+            .add_table(Tag::HEAD, head)
+            .add_table(Tag::CMAP, vec![0xFF; 3]);
+        let data = builder.build();
+
+        let font = FontRef::new(&data).unwrap();
+        let directory = &font.table_directory;
+        assert_eq!(directory.search_range(), 32);
+        assert_eq!(directory.entry_selector(), 1);
+        assert_eq!(directory.range_shift(), 16);
+
+        let records = directory.table_records();
+        let tags = records.iter().map(|rec| rec.tag()).collect::<Vec<_>>();
+        assert_eq!(tags, [Tag::CMAP, Tag::HEAD, Tag::new(b"zzzz")]);
+        for record in records {
+            assert_eq!(record.offset().to_u32() % 4, 0);
+            let start = record.offset().to_u32() as usize;
+            let padded_len = (record.length() as usize).div_ceil(4) * 4;
+            let mut table = data[start..start + padded_len].to_vec();
+            if record.tag() == Tag::HEAD {
+                table[8..12].fill(0);
+            }
+            assert_eq!(checksum_and_padding(&table).0, record.checksum());
+        }
+        assert_eq!(data.len() % 4, 0);
+        assert_eq!(data.len(), 12 + 3 * 16 + 4 + 56 + 8);
+        assert_eq!(checksum_and_padding(&data).0, CHECKSUM_MAGIC);
+    }
+
+    #[test]
+    fn search_params_values() {
+        assert_eq!(search_params(0), (0, 0, 0));
+        assert_eq!(search_params(1), (16, 0, 0));
+        assert_eq!(search_params(9), (128, 3, 16));
+        assert_eq!(search_params(16), (256, 4, 0));
+    }
+
+    #[test]
+    fn collection() {
+        let head = |n| {
+            let mut head = vec![0u8; 54];
+            head[0] = n;
+            head
+        };
+        let shared = vec![1, 2, 3, 4, 5];
+        let mut one = FontBuilder::default();
+        one.add_table(Tag::HEAD, head(1))
+            .add_table(Tag::new(b"aaaa"), shared.clone())
+            .add_table(Tag::new(b"bbbb"), vec![1]);
+        let mut two = FontBuilder::default();
+        two.add_table(Tag::HEAD, head(1))
+            .add_table(Tag::new(b"aaaa"), shared)
+            .add_table(Tag::new(b"cccc"), vec![2; 7]);
+        let data = CollectionBuilder::default()
+            .add_font(one)
+            .add_font(two)
+            .build();
+
+        let collection = CollectionRef::new(&data).unwrap();
+        assert_eq!(collection.len(), 2);
+        let fonts = collection.iter().collect::<Result<Vec<_>, _>>().unwrap();
+        let offset = |font: &FontRef, tag| font.table_record(tag).unwrap().offset().to_u32();
+        let aaaa = Tag::new(b"aaaa");
+        assert_eq!(offset(&fonts[0], aaaa), offset(&fonts[1], aaaa));
+        assert_ne!(offset(&fonts[0], Tag::HEAD), offset(&fonts[1], Tag::HEAD));
+        assert_eq!(
+            fonts[1].table_data(Tag::new(b"cccc")).unwrap().as_ref(),
+            [2; 7]
+        );
+        // header, two directories of three tables, the tables
+        assert_eq!(data.len(), 20 + 2 * 60 + 2 * 56 + 8 + 4 + 8);
+        for (i, font) in fonts.iter().enumerate() {
+            assert_eq!(offset(font, Tag::HEAD) % 4, 0);
+            let head = font.table_data(Tag::HEAD).unwrap();
+            let adjustment = read_u32(&head.as_ref()[HEAD_CHECKSUM_RANGE]);
+            let checksum = font_checksum(&data, i, font).wrapping_sub(adjustment);
+            assert_eq!(adjustment, CHECKSUM_MAGIC.wrapping_sub(checksum));
+        }
+    }
+
+    #[test]
+    fn modified_date() {
+        let date = LongDateTime::new(3_000_000_000);
+        let mut head = vec![0u8; 54];
+        head[HEAD_MODIFIED_RANGE].copy_from_slice(&[0xFF; 8]);
+        let mut before = FontBuilder::default();
+        before
+            .set_modified_date(date)
+            .add_table(Tag::HEAD, head.clone());
+        let mut after = FontBuilder::default();
+        after.add_table(Tag::HEAD, head).set_modified_date(date);
+
+        let data = before.build();
+        assert_eq!(data, after.build());
+        let font = FontRef::new(&data).unwrap();
+        assert_eq!(font.head().unwrap().modified(), date);
+        assert_eq!(checksum_and_padding(&data).0, CHECKSUM_MAGIC);
+    }
+
+    #[test]
+    fn splice() {
+        let mut builder = FontBuilder::default();
+        builder
+            .add_table(Tag::HEAD, vec![0u8; 54])
+            .add_table(Tag::CMAP, vec![1, 2, 3]);
+        let data = builder.build();
+        let font = FontRef::new(&data).unwrap();
+
+        let replaced = splice_table(&font, Tag::CMAP, &[4, 5, 6, 7, 8]);
+        let inserted = splice_table(&font, Tag::NAME, &[9]);
+        for (data, tag, table) in [
+            (&replaced, Tag::CMAP, &[4, 5, 6, 7, 8][..]),
+            (&inserted, Tag::NAME, &[9]),
+        ] {
+            let font = FontRef::new(data).unwrap();
+            assert_eq!(font.table_data(tag).unwrap().as_ref(), table);
+            assert_eq!(font.table_data(Tag::HEAD).unwrap().len(), 54);
+            assert_eq!(checksum_and_padding(data).0, CHECKSUM_MAGIC);
+        }
+        assert_eq!(FontRef::new(&inserted).unwrap().table_tags().count(), 3);
+    }
+
+    fn square(size: i16) -> Glyph {
+        let contour = Contour::from(vec![
+            CurvePoint::on_curve(0, 0),
+            CurvePoint::on_curve(0, size),
+            CurvePoint::on_curve(size, size),
+            CurvePoint::on_curve(size, 0),
+        ]);
+        SimpleGlyph::new(vec![contour]).into()
+    }
+
+    fn make_font() -> Vec<u8> {
+        use crate::tables::{
+            hhea::Hhea,
+            hmtx::LongMetric,
+            name::{Name, NameRecord},
+        };
+        let mut glyf_builder = GlyfLocaBuilder::new();
+        glyf_builder
+            .add_glyph(&Glyph::Empty)
+            .add_glyph(&square(100))
+            .add_glyph(&square(200));
+        let (glyf, loca, format) = glyf_builder.build();
+        let mut head = Head::default();
+        head.set_loca_format(format);
+        let maxp = Maxp {
+            max_points: Some(4),
+            max_contours: Some(1),
+            max_composite_points: Some(0),
+            max_composite_contours: Some(0),
+            max_zones: Some(2),
+            max_twilight_points: Some(0),
+            max_storage: Some(0),
+            max_function_defs: Some(0),
+            max_instruction_defs: Some(0),
+            max_stack_elements: Some(0),
+            max_size_of_instructions: Some(0),
+            max_component_elements: Some(0),
+            max_component_depth: Some(0),
+            ..Maxp::new(3)
+        };
+        let hhea = Hhea {
+            number_of_long_metrics: 2,
+            ..Default::default()
+        };
+        let hmtx = Hmtx::new(
+            vec![LongMetric::new(0, 0), LongMetric::new(300, 0)],
+            vec![0],
+        );
+        let name = Name::new(
+            [
+                (NameId::FAMILY_NAME, "Old Family"),
+                (NameId::SUBFAMILY_NAME, "Regular"),
+            ]
+            .into_iter()
+            .map(|(id, string)| NameRecord::new(3, 1, 0x409, id, string.to_string().into()))
+            .collect(),
+        );
+        let mut builder = FontBuilder::default();
+        builder
+            .add_typed_table(&glyf)
+            .unwrap()
+            .add_typed_table(&loca)
+            .unwrap()
+            .add_typed_table(&head)
+            .unwrap()
+            .add_typed_table(&maxp)
+            .unwrap()
+            .add_typed_table(&hhea)
+            .unwrap()
+            .add_typed_table(&hmtx)
+            .unwrap()
+            .add_typed_table(&name)
+            .unwrap()
+            .add_table(Tag::new(b"XXXX"), vec![1, 2, 3]);
+        builder.build()
+    }
+
+    #[test]
+    fn edit_font() {
+        use crate::tables::name::{Name, NameRecord};
+
+        let data = make_font();
+        let font = FontRef::new(&data).unwrap();
+        let mut builder = FontBuilder::from(font.clone());
+        builder
+            .edit_table(|name: &mut Name| {
+                name.name_record
+                    .retain(|record| record.name_id != NameId::FAMILY_NAME);
+                name.name_record.insert(NameRecord::new(
+                    3,
+                    1,
+                    0x409,
+                    NameId::FAMILY_NAME,
+                    "New Family".to_string().into(),
+                ));
+            })
+            .unwrap();
+        let data = builder.build();
+        let new_font = FontRef::new(&data).unwrap();
+
+        let name = new_font.name().unwrap();
+        let family = name
+            .name_record()
+            .iter()
+            .find(|record| record.name_id() == NameId::FAMILY_NAME)
+            .unwrap();
+        let family = family.string(name.string_data()).unwrap();
+        assert_eq!(family.chars().collect::<String>(), "New Family");
+        for tag in [Tag::new(b"XXXX"), Tag::GLYF, Tag::HMTX] {
+            assert_eq!(
+                new_font.table_data(tag).unwrap().as_ref(),
+                font.table_data(tag).unwrap().as_ref()
+            );
+        }
+        assert_eq!(checksum_and_padding(&data).0, CHECKSUM_MAGIC);
+    }
+
+    #[test]
+    fn edit_missing_table() {
+        let mut builder = FontBuilder::default();
+        let result = builder.edit_table(|_: &mut Head| ());
+        assert!(matches!(
+            result,
+            Err(EditError::Read(ReadError::TableIsMissing(Tag::HEAD)))
+        ));
+    }
+
+    #[test]
+    fn replace_glyph() {
+        let data = make_font();
+        let font = FontRef::new(&data).unwrap();
+        let mut builder = FontBuilder::from(font.clone());
+        let big = SimpleGlyph::new(vec![
+            Contour::from(vec![
+                CurvePoint::on_curve(-50, -50),
+                CurvePoint::on_curve(-50, 400),
+                CurvePoint::on_curve(400, 400),
+            ]),
+            Contour::from(vec![
+                CurvePoint::on_curve(10, 10),
+                CurvePoint::on_curve(20, 20),
+                CurvePoint::on_curve(30, 10),
+            ]),
+        ]);
+        builder.set_glyph(GlyphId::new(2), &big.into()).unwrap();
+        assert!(matches!(
+            builder.set_glyph(GlyphId::new(3), &Glyph::Empty),
+            Err(EditError::GlyphOutOfRange { num_glyphs: 3, .. })
+        ));
+        let data = builder.build();
+        let new_font = FontRef::new(&data).unwrap();
+
+        let loca = new_font.loca(None).unwrap();
+        let glyf = new_font.glyf().unwrap();
+        let old_loca = font.loca(None).unwrap();
+        let old_glyf = font.glyf().unwrap();
+        // glyph 1 is unchanged
+        let glyph_data = |loca: &read_fonts::tables::loca::Loca,
+                          glyf: &read_fonts::tables::glyf::Glyf| {
+            let (start, end) = (loca.get_raw(1).unwrap(), loca.get_raw(2).unwrap());
+            glyf.offset_data().as_ref()[start as usize..end as usize].to_vec()
+        };
+        assert_eq!(glyph_data(&loca, &glyf), glyph_data(&old_loca, &old_glyf));
+        let Some(ReadGlyph::Simple(glyph)) = loca.get_glyf(GlyphId::new(2), &glyf).unwrap() else {
+            panic!("glyph should be simple");
+        };
+        assert_eq!(glyph.number_of_contours(), 2);
+
+        let head = new_font.head().unwrap();
+        assert_eq!(
+            (head.x_min(), head.y_min(), head.x_max(), head.y_max()),
+            (-50, -50, 400, 400)
+        );
+        let maxp = new_font.maxp().unwrap();
+        assert_eq!(maxp.max_points(), Some(6));
+        assert_eq!(maxp.max_contours(), Some(2));
+        let hmtx = new_font.hmtx().unwrap();
+        assert_eq!(hmtx.left_side_bearings(), [-50]);
+        assert_eq!(
+            new_font.table_data(Tag::new(b"XXXX")).unwrap().as_ref(),
+            [1, 2, 3]
+        );
     }
 }
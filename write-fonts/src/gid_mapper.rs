@@ -0,0 +1,1444 @@
+//! Renumbering the glyphs of a font.
+//!
+//! A [`GidMapper`] describes a new glyph order: each glyph that is kept is
+//! given a new id, and glyphs that are not kept are dropped. This is the
+//! common core of subsetting (keep some glyphs, in their existing order),
+//! merging (append the glyphs of one font after those of another) and
+//! optimizations that reorder glyphs, such as sorting them so that similar
+//! glyphs are adjacent and compress better.
+//!
+//! The mapping can be applied to a single compiled table with the
+//! [`RemapGlyphs`] trait, which is implemented for the `GSUB`, `GPOS`, `GDEF`
+//! and `COLR` tables, or to a whole font with [`GidMapper::remap_font`],
+//! which also rebuilds `glyf`, `loca`, `hmtx`, `vmtx`, `cmap` and `post`.
+//!
+//! References to dropped glyphs are removed: coverage entries and the data
+//! associated with them, substitution and contextual rules that mention a
+//! dropped glyph, cmap mappings, composite glyph components, and COLR paints
+//! that draw a dropped glyph. The mapping should usually include every glyph
+//! reachable from the glyphs that are kept (their closure), which can be
+//! computed with [`glyph_closure`].
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+use read_fonts::{
+    tables::{cmap::CmapSubtable, glyf::Glyph as ReadGlyph},
+    FontRef, ReadError, TableProvider,
+};
+use types::{GlyphId, Tag};
+
+use crate::{
+    dump_table,
+    from_obj::ToOwnedTable,
+    merge::GLYPH_DEPENDENT_TABLES,
+    tables::{
+        cmap::{Cmap, CmapBuilder},
+        colr::{BaseGlyph, Clip, ClipList, Colr, Layer, Paint, PaintColrLayers},
+        gdef::Gdef,
+        glyf::{Bbox, Component, CompositeGlyph, Glyf, GlyfLocaBuilder, Glyph},
+        gpos::{
+            CursivePosFormat1, ExtensionPosFormat1, ExtensionSubtable as PosExtension, Gpos,
+            MarkBasePosFormat1, MarkLigPosFormat1, MarkMarkPosFormat1, PairPos,
+            PositionChainContext, PositionLookup, PositionSequenceContext, SinglePos,
+        },
+        gsub::{
+            AlternateSubstFormat1, ExtensionSubstFormat1, ExtensionSubtable as SubstExtension,
+            Gsub, LigatureSubstFormat1, MultipleSubstFormat1, ReverseChainSingleSubstFormat1,
+            SingleSubBuilder, SingleSubst, SubstitutionChainContext, SubstitutionLookup,
+            SubstitutionSequenceContext,
+        },
+        head::Head,
+        hhea::Hhea,
+        hmtx::{Hmtx, LongMetric},
+        layout::{
+            ChainedSequenceContext, ClassDef, ClassDefBuilder, CoverageTable, CoverageTableBuilder,
+            Lookup, SequenceContext,
+        },
+        loca::{Loca, LocaFormat},
+        maxp::Maxp,
+        post::PostBuilder,
+        vhea::Vhea,
+        vmtx::Vmtx,
+    },
+    FontBuilder, NullableOffsetMarker, OffsetMarker,
+};
+
+/// Tables that are rebuilt by [`GidMapper::remap_font`].
+const REMAPPED_TABLES: [Tag; 14] = [
+    Tag::CMAP,
+    Tag::COLR,
+    Tag::GDEF,
+    Tag::GLYF,
+    Tag::GPOS,
+    Tag::GSUB,
+    Tag::HEAD,
+    Tag::HHEA,
+    Tag::HMTX,
+    Tag::LOCA,
+    Tag::MAXP,
+    Tag::POST,
+    Tag::VHEA,
+    Tag::VMTX,
+];
+
+/// A mapping from the glyph ids of a font to new glyph ids.
+///
+/// The new glyph ids are always contiguous, starting at zero.
+///
+/// ```
+/// # use write_fonts::{gid_mapper::GidMapper, types::GlyphId};
+/// // keep .notdef and glyphs 5 and 3, in that order
+/// let order = [0, 5, 3].map(GlyphId::new);
+/// let mapper = GidMapper::from_order(order).unwrap();
+/// assert_eq!(mapper.get(GlyphId::new(3)), Some(GlyphId::new(2)));
+/// assert_eq!(mapper.get(GlyphId::new(4)), None);
+/// assert_eq!(mapper.num_glyphs(), 3);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GidMapper {
+    // indexed by old glyph id
+    new_ids: Vec<Option<GlyphId>>,
+    // indexed by new glyph id
+    old_ids: Vec<GlyphId>,
+}
+
+/// A font whose glyphs have been renumbered.
+#[derive(Clone, Debug)]
+pub struct RemappedFont {
+    /// The binary data of the new font.
+    pub data: Vec<u8>,
+    /// Tables that refer to glyph ids but could not be remapped, and so were
+    /// dropped.
+    pub dropped_tables: Vec<Tag>,
+}
+
+/// An error that prevents glyphs from being renumbered.
+#[derive(Debug)]
+pub enum GidMapError {
+    /// A glyph occurs more than once in the new glyph order.
+    DuplicateGlyph(GlyphId),
+    /// The new glyph order has more than 65535 glyphs.
+    TooManyGlyphs,
+    /// The font is variable; the glyphs of variable fonts cannot be remapped.
+    VariableFont,
+    /// The font is missing a required table.
+    ///
+    /// In particular, this is returned for fonts that do not have TrueType
+    /// outlines.
+    MissingTable(Tag),
+    /// A table in the font could not be read.
+    Read(ReadError),
+    /// A remapped table could not be written.
+    Write(crate::Error),
+}
+
+/// A table that can have its glyph ids renumbered.
+pub trait RemapGlyphs {
+    /// Replace every glyph id in this table with its new id, removing
+    /// anything that refers to a glyph that is dropped.
+    fn remap_glyphs(&mut self, mapper: &GidMapper);
+}
+
+impl GidMapper {
+    /// Create a mapping from the new glyph order.
+    ///
+    /// The glyph at position `i` in `order` will have the glyph id `i`, and
+    /// glyphs that are not in `order` are dropped.
+    pub fn from_order(order: impl IntoIterator<Item = GlyphId>) -> Result<Self, GidMapError> {
+        let mut mapper = GidMapper::default();
+        for old in order {
+            let new = mapper.old_ids.len();
+            if new >= u16::MAX as usize {
+                return Err(GidMapError::TooManyGlyphs);
+            }
+            let idx = old.to_u16() as usize;
+            if idx >= mapper.new_ids.len() {
+                mapper.new_ids.resize(idx + 1, None);
+            }
+            if mapper.new_ids[idx].is_some() {
+                return Err(GidMapError::DuplicateGlyph(old));
+            }
+            mapper.new_ids[idx] = Some(GlyphId::new(new as u16));
+            mapper.old_ids.push(old);
+        }
+        Ok(mapper)
+    }
+
+    /// Create a mapping that keeps only these glyphs, in their current order.
+    ///
+    /// Duplicate glyphs are ignored. This is the mapping used when
+    /// subsetting a font; it should usually include `.notdef`.
+    pub fn retain(glyphs: impl IntoIterator<Item = GlyphId>) -> Result<Self, GidMapError> {
+        let mut glyphs = glyphs.into_iter().collect::<Vec<_>>();
+        glyphs.sort_unstable();
+        glyphs.dedup();
+        Self::from_order(glyphs)
+    }
+
+    /// The new id of a glyph, or `None` if it is dropped.
+    pub fn get(&self, old: GlyphId) -> Option<GlyphId> {
+        self.new_ids.get(old.to_u16() as usize).copied().flatten()
+    }
+
+    /// The original id of the glyph with this new id.
+    pub fn old_id(&self, new: GlyphId) -> Option<GlyphId> {
+        self.old_ids.get(new.to_u16() as usize).copied()
+    }
+
+    /// The number of glyphs after remapping.
+    pub fn num_glyphs(&self) -> u16 {
+        // from_order ensures this fits
+        self.old_ids.len() as u16
+    }
+
+    /// Iterate over the `(old, new)` ids of each kept glyph, in the new order.
+    pub fn iter(&self) -> impl Iterator<Item = (GlyphId, GlyphId)> + '_ {
+        self.old_ids
+            .iter()
+            .enumerate()
+            .map(|(new, old)| (*old, GlyphId::new(new as u16)))
+    }
+
+    /// Renumber the glyphs of a font.
+    ///
+    /// The `glyf`, `loca`, `hmtx`, `vmtx`, `cmap`, `post`, `GSUB`, `GPOS`,
+    /// `GDEF` and `COLR` tables are remapped, and `head`, `maxp`, `hhea` and
+    /// `vhea` are updated to match. Other tables that refer to glyph ids are
+    /// dropped, and are listed in [`RemappedFont::dropped_tables`]; the
+    /// remaining tables are copied unchanged.
+    ///
+    /// Only static fonts with TrueType outlines are supported.
+    pub fn remap_font(&self, font: &FontRef) -> Result<RemappedFont, GidMapError> {
+        if font.is_variable() {
+            return Err(GidMapError::VariableFont);
+        }
+        for tag in [
+            Tag::GLYF,
+            Tag::LOCA,
+            Tag::HEAD,
+            Tag::MAXP,
+            Tag::HHEA,
+            Tag::HMTX,
+        ] {
+            if !font.has_table(tag) {
+                return Err(GidMapError::MissingTable(tag));
+            }
+        }
+        let mut tables = BTreeMap::new();
+        let mut add_table = |tag, data: Result<Vec<u8>, crate::Error>| {
+            tables.insert(tag, data.map_err(GidMapError::Write)?);
+            Ok::<_, GidMapError>(())
+        };
+
+        let (glyf, loca, loca_format) = self.remap_glyf(font)?;
+        add_table(Tag::GLYF, dump_table(&glyf))?;
+        add_table(Tag::LOCA, dump_table(&loca))?;
+        let mut head: Head = font.head()?.to_owned_table();
+        head.set_loca_format(loca_format);
+        add_table(Tag::HEAD, dump_table(&head))?;
+        let mut maxp: Maxp = font.maxp()?.to_owned_table();
+        maxp.num_glyphs = self.num_glyphs();
+        add_table(Tag::MAXP, dump_table(&maxp))?;
+
+        let hmtx = font.hmtx()?;
+        let h_metrics = self
+            .old_ids
+            .iter()
+            .map(|gid| {
+                LongMetric::new(
+                    hmtx.advance(*gid).unwrap_or_default(),
+                    hmtx.side_bearing(*gid).unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut hhea: Hhea = font.hhea()?.to_owned_table();
+        hhea.number_of_long_metrics = h_metrics.len() as u16;
+        add_table(Tag::HHEA, dump_table(&hhea))?;
+        add_table(Tag::HMTX, dump_table(&Hmtx::new(h_metrics, Vec::new())))?;
+
+        if let (Ok(vhea), Ok(vmtx)) = (font.vhea(), font.vmtx()) {
+            let metrics = vmtx.v_metrics();
+            let bearings = vmtx.top_side_bearings();
+            let v_metrics = self
+                .old_ids
+                .iter()
+                .map(|gid| {
+                    let idx = gid.to_u16() as usize;
+                    let advance = metrics
+                        .get(idx)
+                        .or(metrics.last())
+                        .map(|metric| metric.advance())
+                        .unwrap_or_default();
+                    let tsb = match metrics.get(idx) {
+                        Some(metric) => metric.side_bearing(),
+                        None => bearings
+                            .get(idx - metrics.len())
+                            .map(|tsb| tsb.get())
+                            .unwrap_or_default(),
+                    };
+                    LongMetric::new(advance, tsb)
+                })
+                .collect::<Vec<_>>();
+            let mut vhea: Vhea = vhea.to_owned_table();
+            vhea.number_of_long_ver_metrics = v_metrics.len() as u16;
+            add_table(Tag::VHEA, dump_table(&vhea))?;
+            add_table(Tag::VMTX, dump_table(&Vmtx::new(v_metrics, Vec::new())))?;
+        }
+
+        if font.has_table(Tag::CMAP) {
+            add_table(Tag::CMAP, dump_table(&self.remap_cmap(font)?))?;
+        }
+        if let Ok(post) = font.post() {
+            let names = self
+                .old_ids
+                .iter()
+                .map(|gid| post.glyph_name(*gid))
+                .collect::<Option<Vec<_>>>();
+            let mut builder = PostBuilder::new();
+            builder
+                .italic_angle(post.italic_angle())
+                .underline(post.underline_position(), post.underline_thickness())
+                .fixed_pitch(post.is_fixed_pitch() != 0);
+            for name in names.unwrap_or_default() {
+                builder.add_glyph_name(name);
+            }
+            let new_post = builder.build();
+            add_table(Tag::POST, dump_table(&new_post))?;
+        }
+
+        if let Ok(gsub) = font.gsub() {
+            add_table(
+                Tag::GSUB,
+                dump_table(&self.remap_table::<Gsub>(&gsub.to_owned_table())),
+            )?;
+        }
+        if let Ok(gpos) = font.gpos() {
+            add_table(
+                Tag::GPOS,
+                dump_table(&self.remap_table::<Gpos>(&gpos.to_owned_table())),
+            )?;
+        }
+        if let Ok(gdef) = font.gdef() {
+            add_table(
+                Tag::GDEF,
+                dump_table(&self.remap_table::<Gdef>(&gdef.to_owned_table())),
+            )?;
+        }
+        if let Ok(colr) = font.colr() {
+            add_table(
+                Tag::COLR,
+                dump_table(&self.remap_table::<Colr>(&colr.to_owned_table())),
+            )?;
+        }
+
+        let mut dropped_tables = Vec::new();
+        for record in font.table_records() {
+            let tag = record.tag();
+            if REMAPPED_TABLES.contains(&tag) || tag == Tag::DSIG {
+                continue;
+            }
+            if GLYPH_DEPENDENT_TABLES.contains(&tag) {
+                dropped_tables.push(tag);
+                continue;
+            }
+            if let Some(data) = font.data_for_record(record) {
+                tables.insert(tag, data.as_ref().to_owned());
+            }
+        }
+
+        let mut builder = FontBuilder::default();
+        for (tag, data) in tables {
+            builder.add_table(tag, data);
+        }
+        Ok(RemappedFont {
+            data: builder.build(),
+            dropped_tables,
+        })
+    }
+
+    fn remap_table<T: RemapGlyphs + Clone>(&self, table: &T) -> T {
+        let mut table = table.clone();
+        table.remap_glyphs(self);
+        table
+    }
+
+    fn remap_glyf(&self, font: &FontRef) -> Result<(Glyf, Loca, LocaFormat), GidMapError> {
+        let loca = font.loca(None)?;
+        let glyf = font.glyf()?;
+        let mut builder = GlyfLocaBuilder::new();
+        for gid in &self.old_ids {
+            let glyph = match loca.get_glyf(*gid, &glyf)? {
+                None => Glyph::Empty,
+                Some(ReadGlyph::Simple(glyph)) => Glyph::Simple(glyph.to_owned_table()),
+                Some(ReadGlyph::Composite(glyph)) => {
+                    let bbox = Bbox {
+                        x_min: glyph.x_min(),
+                        y_min: glyph.y_min(),
+                        x_max: glyph.x_max(),
+                        y_max: glyph.y_max(),
+                    };
+                    let mut components = glyph.components().filter_map(|component| {
+                        Some(Component::new(
+                            self.get(component.glyph)?,
+                            component.anchor,
+                            component.transform,
+                            component.flags,
+                        ))
+                    });
+                    match components.next() {
+                        None => Glyph::Empty,
+                        Some(first) => {
+                            let mut new_glyph = CompositeGlyph::new(first, bbox);
+                            for component in components {
+                                new_glyph.add_component(component, bbox);
+                            }
+                            new_glyph.set_instructions(
+                                glyph.instructions().unwrap_or_default().to_owned(),
+                            );
+                            Glyph::Composite(new_glyph)
+                        }
+                    }
+                }
+            };
+            builder.add_glyph(&glyph);
+        }
+        Ok(builder.build())
+    }
+
+    fn remap_cmap(&self, font: &FontRef) -> Result<Cmap, GidMapError> {
+        let cmap = font.cmap()?;
+        let mut mappings = BTreeMap::new();
+        let mut uvs = None;
+        for record in cmap.encoding_records() {
+            match record.subtable(cmap.offset_data())? {
+                CmapSubtable::Format4(subtable) => {
+                    for (codepoint, gid) in subtable.iter() {
+                        mappings.entry(codepoint).or_insert(gid);
+                    }
+                }
+                CmapSubtable::Format12(subtable) => {
+                    for (codepoint, gid) in subtable.iter() {
+                        mappings.entry(codepoint).or_insert(gid);
+                    }
+                }
+                CmapSubtable::Format14(subtable) => uvs = Some(subtable),
+                _ => (),
+            }
+        }
+
+        let mut builder = CmapBuilder::default();
+        for (codepoint, gid) in &mappings {
+            if let (Some(chr), Some(gid)) = (char::from_u32(*codepoint), self.get(*gid)) {
+                builder.add_mapping(chr, gid);
+            }
+        }
+        let Some(uvs) = uvs else {
+            return Ok(builder.build());
+        };
+        let data = uvs.offset_data();
+        for selector in uvs.var_selector() {
+            let Some(selector_chr) = char::from_u32(selector.var_selector().into()) else {
+                continue;
+            };
+            let mut sequences = Vec::new();
+            if let Some(default_uvs) = selector.default_uvs(data).transpose()? {
+                for range in default_uvs.ranges() {
+                    let start = u32::from(range.start_unicode_value());
+                    for base in start..=start + range.additional_count() as u32 {
+                        if let Some(gid) = mappings.get(&base) {
+                            sequences.push((base, *gid));
+                        }
+                    }
+                }
+            }
+            if let Some(non_default_uvs) = selector.non_default_uvs(data).transpose()? {
+                for mapping in non_default_uvs.uvs_mapping() {
+                    let gid = GlyphId::new(mapping.glyph_id());
+                    sequences.push((u32::from(mapping.unicode_value()), gid));
+                }
+            }
+            for (base, gid) in sequences {
+                if let (Some(base_chr), Some(gid)) = (char::from_u32(base), self.get(gid)) {
+                    builder.add_variation_sequence(base_chr, selector_chr, gid);
+                }
+            }
+        }
+        Ok(builder.build())
+    }
+
+    /// Remap a coverage table, along with an array that is indexed by
+    /// coverage index.
+    ///
+    /// Items whose glyph is dropped are removed, as are items for which
+    /// `remap_item` returns `false`; the remaining items are sorted into the
+    /// order of their new glyph ids.
+    fn remap_coverage<T>(
+        &self,
+        coverage: &mut CoverageTable,
+        items: &mut Vec<T>,
+        mut remap_item: impl FnMut(&mut T) -> bool,
+    ) {
+        let mut pairs = coverage
+            .iter()
+            .zip(std::mem::take(items))
+            .filter_map(|(gid, mut item)| {
+                let gid = self.get(gid)?;
+                remap_item(&mut item).then_some((gid, item))
+            })
+            .collect::<Vec<_>>();
+        pairs.sort_by_key(|(gid, _)| *gid);
+        let (glyphs, new_items): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+        *coverage = CoverageTableBuilder::from_glyphs(glyphs).build();
+        *items = new_items;
+    }
+
+    /// Remap a coverage table that has no associated array.
+    fn remap_coverage_glyphs(&self, coverage: &mut CoverageTable) {
+        *coverage = coverage
+            .iter()
+            .filter_map(|gid| self.get(gid))
+            .collect::<CoverageTableBuilder>()
+            .build();
+    }
+
+    /// Remap each glyph in a sequence, returning `false` if any is dropped.
+    fn remap_sequence(&self, glyphs: &mut [GlyphId]) -> bool {
+        for gid in glyphs {
+            match self.get(*gid) {
+                Some(new) => *gid = new,
+                None => return false,
+            }
+        }
+        true
+    }
+}
+
+/// Compute the set of glyphs needed to render some glyphs of a font.
+///
+/// This is the closure of `glyphs` under the substitutions of every feature
+/// in `GSUB`, followed by the components of any composite glyphs in `glyf`.
+/// `.notdef` is always included. The result can be passed to
+/// [`GidMapper::retain`] to subset the font.
+pub fn glyph_closure(
+    font: &FontRef,
+    glyphs: impl IntoIterator<Item = GlyphId>,
+) -> Result<BTreeSet<GlyphId>, ReadError> {
+    let mut closure = glyphs.into_iter().collect::<BTreeSet<_>>();
+    closure.insert(GlyphId::NOTDEF);
+    if let Ok(gsub) = font.gsub() {
+        let features = gsub
+            .feature_list()?
+            .feature_records()
+            .iter()
+            .map(|record| record.feature_tag())
+            .collect::<Vec<_>>();
+        closure = gsub.closure(&closure, &features)?;
+    }
+    if let (Ok(glyf), Ok(loca)) = (font.glyf(), font.loca(None)) {
+        let mut pending = closure.iter().copied().collect::<Vec<_>>();
+        while let Some(gid) = pending.pop() {
+            if let Some(ReadGlyph::Composite(glyph)) = loca.get_glyf(gid, &glyf)? {
+                for component in glyph.components() {
+                    if closure.insert(component.glyph) {
+                        pending.push(component.glyph);
+                    }
+                }
+            }
+        }
+    }
+    Ok(closure)
+}
+
+impl<T: RemapGlyphs, const N: usize> RemapGlyphs for OffsetMarker<T, N> {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        (**self).remap_glyphs(mapper)
+    }
+}
+
+impl<T: RemapGlyphs, const N: usize> RemapGlyphs for NullableOffsetMarker<T, N> {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        if let Some(table) = (**self).as_mut() {
+            table.remap_glyphs(mapper)
+        }
+    }
+}
+
+impl<T: RemapGlyphs> RemapGlyphs for Vec<T> {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        for item in self {
+            item.remap_glyphs(mapper);
+        }
+    }
+}
+
+impl RemapGlyphs for CoverageTable {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        mapper.remap_coverage_glyphs(self)
+    }
+}
+
+impl RemapGlyphs for ClassDef {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        *self = self
+            .iter()
+            .filter_map(|(gid, class)| Some((mapper.get(gid)?, class)))
+            .collect::<ClassDefBuilder>()
+            .build();
+    }
+}
+
+impl<T: RemapGlyphs> RemapGlyphs for Lookup<T> {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        self.subtables.remap_glyphs(mapper)
+    }
+}
+
+impl RemapGlyphs for SequenceContext {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        match self {
+            SequenceContext::Format1(table) => {
+                mapper.remap_coverage(&mut table.coverage, &mut table.seq_rule_sets, |set| {
+                    if let Some(set) = set.as_mut() {
+                        set.seq_rules
+                            .retain_mut(|rule| mapper.remap_sequence(&mut rule.input_sequence));
+                    }
+                    true
+                })
+            }
+            SequenceContext::Format2(table) => {
+                // rule sets are indexed by class, not by coverage index
+                table.coverage.remap_glyphs(mapper);
+                table.class_def.remap_glyphs(mapper);
+            }
+            SequenceContext::Format3(table) => table.coverages.remap_glyphs(mapper),
+        }
+    }
+}
+
+impl RemapGlyphs for ChainedSequenceContext {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        match self {
+            ChainedSequenceContext::Format1(table) => mapper.remap_coverage(
+                &mut table.coverage,
+                &mut table.chained_seq_rule_sets,
+                |set| {
+                    if let Some(set) = set.as_mut() {
+                        set.chained_seq_rules.retain_mut(|rule| {
+                            mapper.remap_sequence(&mut rule.backtrack_sequence)
+                                && mapper.remap_sequence(&mut rule.input_sequence)
+                                && mapper.remap_sequence(&mut rule.lookahead_sequence)
+                        });
+                    }
+                    true
+                },
+            ),
+            ChainedSequenceContext::Format2(table) => {
+                table.coverage.remap_glyphs(mapper);
+                table.backtrack_class_def.remap_glyphs(mapper);
+                table.input_class_def.remap_glyphs(mapper);
+                table.lookahead_class_def.remap_glyphs(mapper);
+            }
+            ChainedSequenceContext::Format3(table) => {
+                table.backtrack_coverages.remap_glyphs(mapper);
+                table.input_coverages.remap_glyphs(mapper);
+                table.lookahead_coverages.remap_glyphs(mapper);
+            }
+        }
+    }
+}
+
+impl RemapGlyphs for SubstitutionSequenceContext {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        (**self).remap_glyphs(mapper)
+    }
+}
+
+impl RemapGlyphs for SubstitutionChainContext {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        (**self).remap_glyphs(mapper)
+    }
+}
+
+impl RemapGlyphs for PositionSequenceContext {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        (**self).remap_glyphs(mapper)
+    }
+}
+
+impl RemapGlyphs for PositionChainContext {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        (**self).remap_glyphs(mapper)
+    }
+}
+
+impl RemapGlyphs for Gsub {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        self.lookup_list.lookups.remap_glyphs(mapper)
+    }
+}
+
+impl RemapGlyphs for SubstitutionLookup {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        match self {
+            SubstitutionLookup::Single(lookup) => lookup.remap_glyphs(mapper),
+            SubstitutionLookup::Multiple(lookup) => lookup.remap_glyphs(mapper),
+            SubstitutionLookup::Alternate(lookup) => lookup.remap_glyphs(mapper),
+            SubstitutionLookup::Ligature(lookup) => lookup.remap_glyphs(mapper),
+            SubstitutionLookup::Contextual(lookup) => lookup.remap_glyphs(mapper),
+            SubstitutionLookup::ChainContextual(lookup) => lookup.remap_glyphs(mapper),
+            SubstitutionLookup::Extension(lookup) => lookup.remap_glyphs(mapper),
+            SubstitutionLookup::Reverse(lookup) => lookup.remap_glyphs(mapper),
+        }
+    }
+}
+
+impl RemapGlyphs for SubstExtension {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        match self {
+            SubstExtension::Single(table) => table.remap_glyphs(mapper),
+            SubstExtension::Multiple(table) => table.remap_glyphs(mapper),
+            SubstExtension::Alternate(table) => table.remap_glyphs(mapper),
+            SubstExtension::Ligature(table) => table.remap_glyphs(mapper),
+            SubstExtension::Contextual(table) => table.remap_glyphs(mapper),
+            SubstExtension::ChainContextual(table) => table.remap_glyphs(mapper),
+            SubstExtension::Reverse(table) => table.remap_glyphs(mapper),
+        }
+    }
+}
+
+impl<T: RemapGlyphs> RemapGlyphs for ExtensionSubstFormat1<T> {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        self.extension.remap_glyphs(mapper)
+    }
+}
+
+impl RemapGlyphs for SingleSubst {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        let pairs: Vec<(GlyphId, GlyphId)> = match self {
+            SingleSubst::Format1(table) => table
+                .coverage
+                .iter()
+                .map(|gid| {
+                    let sub = gid.to_u16().wrapping_add(table.delta_glyph_id as u16);
+                    (gid, GlyphId::new(sub))
+                })
+                .collect(),
+            SingleSubst::Format2(table) => table
+                .coverage
+                .iter()
+                .zip(table.substitute_glyph_ids.iter().copied())
+                .collect(),
+        };
+        let mut builder = SingleSubBuilder::new();
+        for (target, replacement) in pairs {
+            if let (Some(target), Some(replacement)) = (mapper.get(target), mapper.get(replacement))
+            {
+                builder.insert(target, replacement);
+            }
+        }
+        // the builder always produces a single subtable
+        if let Some(subtable) = builder.build().pop() {
+            *self = subtable;
+        }
+    }
+}
+
+impl RemapGlyphs for MultipleSubstFormat1 {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        mapper.remap_coverage(&mut self.coverage, &mut self.sequences, |sequence| {
+            mapper.remap_sequence(&mut sequence.substitute_glyph_ids)
+        })
+    }
+}
+
+impl RemapGlyphs for AlternateSubstFormat1 {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        mapper.remap_coverage(&mut self.coverage, &mut self.alternate_sets, |set| {
+            set.alternate_glyph_ids = set
+                .alternate_glyph_ids
+                .iter()
+                .filter_map(|gid| mapper.get(*gid))
+                .collect();
+            !set.alternate_glyph_ids.is_empty()
+        })
+    }
+}
+
+impl RemapGlyphs for LigatureSubstFormat1 {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        mapper.remap_coverage(&mut self.coverage, &mut self.ligature_sets, |set| {
+            set.ligatures.retain_mut(|ligature| {
+                match mapper.get(ligature.ligature_glyph) {
+                    Some(gid) => ligature.ligature_glyph = gid,
+                    None => return false,
+                }
+                mapper.remap_sequence(&mut ligature.component_glyph_ids)
+            });
+            !set.ligatures.is_empty()
+        })
+    }
+}
+
+impl RemapGlyphs for ReverseChainSingleSubstFormat1 {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        mapper.remap_coverage(&mut self.coverage, &mut self.substitute_glyph_ids, |gid| {
+            mapper.remap_sequence(std::slice::from_mut(gid))
+        });
+        self.backtrack_coverages.remap_glyphs(mapper);
+        self.lookahead_coverages.remap_glyphs(mapper);
+    }
+}
+
+impl RemapGlyphs for Gpos {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        self.lookup_list.lookups.remap_glyphs(mapper)
+    }
+}
+
+impl RemapGlyphs for PositionLookup {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        match self {
+            PositionLookup::Single(lookup) => lookup.remap_glyphs(mapper),
+            PositionLookup::Pair(lookup) => lookup.remap_glyphs(mapper),
+            PositionLookup::Cursive(lookup) => lookup.remap_glyphs(mapper),
+            PositionLookup::MarkToBase(lookup) => lookup.remap_glyphs(mapper),
+            PositionLookup::MarkToLig(lookup) => lookup.remap_glyphs(mapper),
+            PositionLookup::MarkToMark(lookup) => lookup.remap_glyphs(mapper),
+            PositionLookup::Contextual(lookup) => lookup.remap_glyphs(mapper),
+            PositionLookup::ChainContextual(lookup) => lookup.remap_glyphs(mapper),
+            PositionLookup::Extension(lookup) => lookup.remap_glyphs(mapper),
+        }
+    }
+}
+
+impl RemapGlyphs for PosExtension {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        match self {
+            PosExtension::Single(table) => table.remap_glyphs(mapper),
+            PosExtension::Pair(table) => table.remap_glyphs(mapper),
+            PosExtension::Cursive(table) => table.remap_glyphs(mapper),
+            PosExtension::MarkToBase(table) => table.remap_glyphs(mapper),
+            PosExtension::MarkToLig(table) => table.remap_glyphs(mapper),
+            PosExtension::MarkToMark(table) => table.remap_glyphs(mapper),
+            PosExtension::Contextual(table) => table.remap_glyphs(mapper),
+            PosExtension::ChainContextual(table) => table.remap_glyphs(mapper),
+        }
+    }
+}
+
+impl<T: RemapGlyphs> RemapGlyphs for ExtensionPosFormat1<T> {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        self.extension.remap_glyphs(mapper)
+    }
+}
+
+impl RemapGlyphs for SinglePos {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        match self {
+            SinglePos::Format1(table) => table.coverage.remap_glyphs(mapper),
+            SinglePos::Format2(table) => {
+                mapper.remap_coverage(&mut table.coverage, &mut table.value_records, |_| true)
+            }
+        }
+    }
+}
+
+impl RemapGlyphs for PairPos {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        match self {
+            PairPos::Format1(table) => {
+                mapper.remap_coverage(&mut table.coverage, &mut table.pair_sets, |set| {
+                    let records = &mut set.pair_value_records;
+                    records.retain_mut(|record| match mapper.get(record.second_glyph) {
+                        Some(gid) => {
+                            record.second_glyph = gid;
+                            true
+                        }
+                        None => false,
+                    });
+                    records.sort_by_key(|record| record.second_glyph);
+                    true
+                })
+            }
+            PairPos::Format2(table) => {
+                // class records are indexed by class, not by coverage index
+                table.coverage.remap_glyphs(mapper);
+                table.class_def1.remap_glyphs(mapper);
+                table.class_def2.remap_glyphs(mapper);
+            }
+        }
+    }
+}
+
+impl RemapGlyphs for CursivePosFormat1 {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        mapper.remap_coverage(&mut self.coverage, &mut self.entry_exit_record, |_| true)
+    }
+}
+
+impl RemapGlyphs for MarkBasePosFormat1 {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        let marks = &mut self.mark_array.mark_records;
+        mapper.remap_coverage(&mut self.mark_coverage, marks, |_| true);
+        let bases = &mut self.base_array.base_records;
+        mapper.remap_coverage(&mut self.base_coverage, bases, |_| true);
+    }
+}
+
+impl RemapGlyphs for MarkLigPosFormat1 {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        let marks = &mut self.mark_array.mark_records;
+        mapper.remap_coverage(&mut self.mark_coverage, marks, |_| true);
+        let ligatures = &mut self.ligature_array.ligature_attaches;
+        mapper.remap_coverage(&mut self.ligature_coverage, ligatures, |_| true);
+    }
+}
+
+impl RemapGlyphs for MarkMarkPosFormat1 {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        let marks = &mut self.mark1_array.mark_records;
+        mapper.remap_coverage(&mut self.mark1_coverage, marks, |_| true);
+        let marks = &mut self.mark2_array.mark2_records;
+        mapper.remap_coverage(&mut self.mark2_coverage, marks, |_| true);
+    }
+}
+
+impl RemapGlyphs for Gdef {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        self.glyph_class_def.remap_glyphs(mapper);
+        if let Some(attach_list) = self.attach_list.as_mut() {
+            let points = &mut attach_list.attach_points;
+            mapper.remap_coverage(&mut attach_list.coverage, points, |_| true);
+        }
+        if let Some(lig_caret_list) = self.lig_caret_list.as_mut() {
+            let lig_glyphs = &mut lig_caret_list.lig_glyphs;
+            mapper.remap_coverage(&mut lig_caret_list.coverage, lig_glyphs, |_| true);
+        }
+        self.mark_attach_class_def.remap_glyphs(mapper);
+        if let Some(mark_glyph_sets) = self.mark_glyph_sets_def.as_mut() {
+            mark_glyph_sets.coverages.remap_glyphs(mapper);
+        }
+    }
+}
+
+impl RemapGlyphs for Colr {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        // the layers of each base glyph are contiguous, so they are rebuilt
+        // in the order of the new base glyph ids.
+        if let Some(base_glyphs) = self.base_glyph_records.as_mut() {
+            let old_layers = self.layer_records.as_deref().unwrap_or_default();
+            let mut remapped = base_glyphs
+                .iter()
+                .filter_map(|base| Some((mapper.get(base.glyph_id)?, base)))
+                .collect::<Vec<_>>();
+            remapped.sort_by_key(|(gid, _)| *gid);
+            let mut layers = Vec::new();
+            let mut new_base_glyphs = Vec::new();
+            for (gid, base) in remapped {
+                let first = base.first_layer_index as usize;
+                let first_layer_index = layers.len() as u16;
+                layers.extend(
+                    old_layers
+                        .iter()
+                        .skip(first)
+                        .take(base.num_layers as usize)
+                        .filter_map(|layer| {
+                            Some(Layer::new(mapper.get(layer.glyph_id)?, layer.palette_index))
+                        }),
+                );
+                let num_layers = layers.len() as u16 - first_layer_index;
+                new_base_glyphs.push(BaseGlyph::new(gid, first_layer_index, num_layers));
+            }
+            *base_glyphs = new_base_glyphs;
+            self.layer_records.set(layers);
+        }
+        if let Some(base_glyph_list) = self.base_glyph_list.as_mut() {
+            let records = &mut base_glyph_list.base_glyph_paint_records;
+            records.retain_mut(|record| match mapper.get(record.glyph_id) {
+                Some(gid) => {
+                    record.glyph_id = gid;
+                    record.paint.remap_glyphs(mapper);
+                    true
+                }
+                None => false,
+            });
+            records.sort_by_key(|record| record.glyph_id);
+        }
+        if let Some(layer_list) = self.layer_list.as_mut() {
+            layer_list.paints.remap_glyphs(mapper);
+        }
+        if let Some(clip_list) = self.clip_list.as_mut() {
+            clip_list.remap_glyphs(mapper);
+        }
+    }
+}
+
+impl RemapGlyphs for ClipList {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        // the ranges of glyphs are split up and regrouped, sharing the
+        // original clip boxes.
+        let mut glyphs = self
+            .clips
+            .iter()
+            .enumerate()
+            .flat_map(|(i, clip)| {
+                (clip.start_glyph_id.to_u16()..=clip.end_glyph_id.to_u16())
+                    .filter_map(move |gid| Some((mapper.get(GlyphId::new(gid))?, i)))
+            })
+            .collect::<Vec<_>>();
+        glyphs.sort_unstable();
+        glyphs.dedup_by_key(|(gid, _)| *gid);
+        let mut clips: Vec<(GlyphId, GlyphId, usize)> = Vec::new();
+        for (gid, i) in glyphs {
+            match clips.last_mut() {
+                Some((_, end, prev)) if *prev == i && end.to_u16() + 1 == gid.to_u16() => {
+                    *end = gid
+                }
+                _ => clips.push((gid, gid, i)),
+            }
+        }
+        self.clips = clips
+            .into_iter()
+            .map(|(start_glyph_id, end_glyph_id, i)| Clip {
+                start_glyph_id,
+                end_glyph_id,
+                clip_box: self.clips[i].clip_box.clone(),
+            })
+            .collect();
+    }
+}
+
+impl RemapGlyphs for Paint {
+    fn remap_glyphs(&mut self, mapper: &GidMapper) {
+        let keep = match self {
+            Paint::Glyph(paint) => match mapper.get(paint.glyph_id) {
+                Some(gid) => {
+                    paint.glyph_id = gid;
+                    paint.paint.remap_glyphs(mapper);
+                    true
+                }
+                None => false,
+            },
+            Paint::ColrGlyph(paint) => match mapper.get(paint.glyph_id) {
+                Some(gid) => {
+                    paint.glyph_id = gid;
+                    true
+                }
+                None => false,
+            },
+            Paint::ColrLayers(_)
+            | Paint::Solid(_)
+            | Paint::VarSolid(_)
+            | Paint::LinearGradient(_)
+            | Paint::VarLinearGradient(_)
+            | Paint::RadialGradient(_)
+            | Paint::VarRadialGradient(_)
+            | Paint::SweepGradient(_)
+            | Paint::VarSweepGradient(_) => true,
+            Paint::Transform(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarTransform(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::Translate(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarTranslate(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::Scale(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarScale(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::ScaleAroundCenter(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarScaleAroundCenter(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::ScaleUniform(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarScaleUniform(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::ScaleUniformAroundCenter(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarScaleUniformAroundCenter(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::Rotate(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarRotate(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::RotateAroundCenter(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarRotateAroundCenter(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::Skew(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarSkew(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::SkewAroundCenter(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::VarSkewAroundCenter(paint) => {
+                paint.paint.remap_glyphs(mapper);
+                true
+            }
+            Paint::Composite(paint) => {
+                paint.source_paint.remap_glyphs(mapper);
+                paint.backdrop_paint.remap_glyphs(mapper);
+                true
+            }
+        };
+        // a paint that draws a dropped glyph is replaced by one that draws
+        // nothing
+        if !keep {
+            *self = Paint::ColrLayers(PaintColrLayers::new(0, 0));
+        }
+    }
+}
+
+impl From<ReadError> for GidMapError {
+    fn from(value: ReadError) -> Self {
+        GidMapError::Read(value)
+    }
+}
+
+impl Display for GidMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GidMapError::DuplicateGlyph(gid) => {
+                write!(f, "glyph {gid} occurs more than once in the glyph order")
+            }
+            GidMapError::TooManyGlyphs => write!(f, "more than 65535 glyphs"),
+            GidMapError::VariableFont => write!(f, "variable fonts are not supported"),
+            GidMapError::MissingTable(tag) => write!(f, "missing required table '{tag}'"),
+            GidMapError::Read(error) => write!(f, "failed to read table: {error}"),
+            GidMapError::Write(error) => write!(f, "failed to write table: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for GidMapError {}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::tables::glyf::{Anchor, CompositeGlyphFlags};
+
+    use super::*;
+    use crate::tables::{
+        colr::ClipBox,
+        glyf::{Contour, CurvePoint, SimpleGlyph, Transform},
+        gpos::{AnchorTable, EntryExitRecord},
+        gsub::{GsubBuilder, LigatureSubBuilder},
+        layout::LookupFlag,
+    };
+
+    fn gids<const N: usize>(ids: [u16; N]) -> [GlyphId; N] {
+        ids.map(GlyphId::new)
+    }
+
+    fn square(size: i16) -> Glyph {
+        let contour = Contour::from(vec![
+            CurvePoint::on_curve(0, 0),
+            CurvePoint::on_curve(0, size),
+            CurvePoint::on_curve(size, size),
+            CurvePoint::on_curve(size, 0),
+        ]);
+        SimpleGlyph::new(vec![contour]).into()
+    }
+
+    /// A font with `.notdef`, 'a', 'b', and 'c', a composite of 'a'.
+    ///
+    /// The advance of glyph `i` is `100 * i`; `GSUB` substitutes 'a' with
+    /// 'b', and the sequence 'a b' with 'c'.
+    fn make_font() -> Vec<u8> {
+        let composite = CompositeGlyph::new(
+            Component::new(
+                GlyphId::new(1),
+                Anchor::Offset { x: 10, y: 20 },
+                Transform::default(),
+                CompositeGlyphFlags::empty(),
+            ),
+            Bbox {
+                x_min: 10,
+                y_min: 20,
+                x_max: 110,
+                y_max: 120,
+            },
+        );
+        let glyphs = [Glyph::Empty, square(100), square(200), composite.into()];
+        let mut builder = GlyfLocaBuilder::new();
+        for glyph in &glyphs {
+            builder.add_glyph(glyph);
+        }
+        let (glyf, loca, format) = builder.build();
+        let mut head = Head {
+            units_per_em: 1000,
+            ..Default::default()
+        };
+        head.set_loca_format(format);
+        let metrics = (0..4)
+            .map(|i| LongMetric::new(100 * i, 0))
+            .collect::<Vec<_>>();
+        let hhea = Hhea {
+            number_of_long_metrics: 4,
+            ..Default::default()
+        };
+        let cmap =
+            CmapBuilder::new(['a', 'b', 'c'].into_iter().zip(gids([1, 2, 3])).collect()).build();
+        let mut single = SingleSubBuilder::new();
+        single.insert(GlyphId::new(1), GlyphId::new(2));
+        let mut ligatures = LigatureSubBuilder::new();
+        ligatures.insert(&gids([1, 2]), GlyphId::new(3));
+        let mut gsub = GsubBuilder::new();
+        let single = gsub.add_single(LookupFlag::empty(), single);
+        let ligature = gsub.add_ligature(LookupFlag::empty(), ligatures);
+        gsub.add_feature(
+            Tag::new(b"DFLT"),
+            Tag::new(b"dflt"),
+            Tag::new(b"liga"),
+            &[single, ligature],
+        );
+
+        let mut builder = FontBuilder::default();
+        builder
+            .add_table(Tag::GLYF, dump_table(&glyf).unwrap())
+            .add_table(Tag::LOCA, dump_table(&loca).unwrap())
+            .add_table(Tag::HEAD, dump_table(&head).unwrap())
+            .add_table(Tag::MAXP, dump_table(&Maxp::new(4)).unwrap())
+            .add_table(Tag::HHEA, dump_table(&hhea).unwrap())
+            .add_table(
+                Tag::HMTX,
+                dump_table(&Hmtx::new(metrics, Vec::new())).unwrap(),
+            )
+            .add_table(Tag::CMAP, dump_table(&cmap).unwrap())
+            .add_table(Tag::GSUB, dump_table(&gsub.build()).unwrap())
+            .add_table(Tag::new(b"kern"), vec![0; 4]);
+        builder.build()
+    }
+
+    /// A ligature's sequence of glyphs, and the ligature glyph.
+    type LigatureRule = (Vec<GlyphId>, GlyphId);
+
+    /// The single substitutions and ligatures in the first two lookups.
+    fn substitutions(font: &FontRef) -> (Vec<(GlyphId, GlyphId)>, Vec<LigatureRule>) {
+        let gsub: Gsub = font.gsub().unwrap().to_owned_table();
+        let lookups = &gsub.lookup_list.lookups;
+        let SubstitutionLookup::Single(singles) = &*lookups[0] else {
+            panic!("wrong lookup type");
+        };
+        let singles = singles
+            .subtables
+            .iter()
+            .flat_map(|subtable| match &**subtable {
+                SingleSubst::Format1(table) => table
+                    .coverage
+                    .iter()
+                    .map(|gid| {
+                        let sub = gid.to_u16().wrapping_add(table.delta_glyph_id as u16);
+                        (gid, GlyphId::new(sub))
+                    })
+                    .collect::<Vec<_>>(),
+                SingleSubst::Format2(table) => table
+                    .coverage
+                    .iter()
+                    .zip(table.substitute_glyph_ids.iter().copied())
+                    .collect(),
+            })
+            .collect();
+        let SubstitutionLookup::Ligature(ligatures) = &*lookups[1] else {
+            panic!("wrong lookup type");
+        };
+        let mut sequences = Vec::new();
+        for subtable in &ligatures.subtables {
+            for (first, set) in subtable.coverage.iter().zip(&subtable.ligature_sets) {
+                for ligature in &set.ligatures {
+                    let mut sequence = vec![first];
+                    sequence.extend(&ligature.component_glyph_ids);
+                    sequences.push((sequence, ligature.ligature_glyph));
+                }
+            }
+        }
+        (singles, sequences)
+    }
+
+    #[test]
+    fn mapping() {
+        let mapper = GidMapper::from_order(gids([0, 3, 1])).unwrap();
+        assert_eq!(mapper.num_glyphs(), 3);
+        assert_eq!(mapper.get(GlyphId::new(3)), Some(GlyphId::new(1)));
+        assert_eq!(mapper.get(GlyphId::new(2)), None);
+        assert_eq!(mapper.get(GlyphId::new(100)), None);
+        assert_eq!(mapper.old_id(GlyphId::new(2)), Some(GlyphId::new(1)));
+        assert_eq!(
+            mapper.iter().collect::<Vec<_>>(),
+            [(0, 0), (3, 1), (1, 2)].map(|(old, new)| (GlyphId::new(old), GlyphId::new(new)))
+        );
+
+        let retained = GidMapper::retain(gids([5, 0, 2, 5])).unwrap();
+        assert_eq!(retained.num_glyphs(), 3);
+        assert_eq!(retained.get(GlyphId::new(5)), Some(GlyphId::new(2)));
+
+        assert!(matches!(
+            GidMapper::from_order(gids([0, 1, 0])),
+            Err(GidMapError::DuplicateGlyph(gid)) if gid == GlyphId::NOTDEF
+        ));
+        assert!(matches!(
+            GidMapper::from_order((0..=u16::MAX).map(GlyphId::new)),
+            Err(GidMapError::TooManyGlyphs)
+        ));
+    }
+
+    #[test]
+    fn closure() {
+        let data = make_font();
+        let font = FontRef::new(&data).unwrap();
+        // 'c' is not substituted, but uses 'a' as a component
+        let closure = glyph_closure(&font, gids([3])).unwrap();
+        assert_eq!(closure.into_iter().collect::<Vec<_>>(), gids([0, 1, 3]));
+        // 'a' becomes 'b', and 'a b' becomes 'c'
+        let closure = glyph_closure(&font, gids([1])).unwrap();
+        assert_eq!(closure.into_iter().collect::<Vec<_>>(), gids([0, 1, 2, 3]));
+        let closure = glyph_closure(&font, []).unwrap();
+        assert_eq!(closure.into_iter().collect::<Vec<_>>(), gids([0]));
+    }
+
+    #[test]
+    fn reorder_font() {
+        let data = make_font();
+        let font = FontRef::new(&data).unwrap();
+        let mapper = GidMapper::from_order(gids([0, 3, 2, 1])).unwrap();
+        let remapped = mapper.remap_font(&font).unwrap();
+        assert_eq!(remapped.dropped_tables, [Tag::new(b"kern")]);
+
+        let font = FontRef::new(&remapped.data).unwrap();
+        let cmap = font.cmap().unwrap();
+        assert_eq!(cmap.map_codepoint('a'), Some(GlyphId::new(3)));
+        assert_eq!(cmap.map_codepoint('c'), Some(GlyphId::new(1)));
+        let hmtx = font.hmtx().unwrap();
+        assert_eq!(hmtx.advance(GlyphId::new(1)), Some(300));
+        assert_eq!(hmtx.advance(GlyphId::new(3)), Some(100));
+        assert_eq!(font.maxp().unwrap().num_glyphs(), 4);
+
+        let glyf = font.glyf().unwrap();
+        let loca = font.loca(None).unwrap();
+        let Some(ReadGlyph::Composite(composite)) = loca.get_glyf(GlyphId::new(1), &glyf).unwrap()
+        else {
+            panic!("expected a composite glyph");
+        };
+        let components = composite.components().collect::<Vec<_>>();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].glyph, GlyphId::new(3));
+
+        let (singles, ligatures) = substitutions(&font);
+        assert_eq!(singles, [(GlyphId::new(3), GlyphId::new(2))]);
+        assert_eq!(ligatures, [(gids([3, 2]).to_vec(), GlyphId::new(1))]);
+    }
+
+    #[test]
+    fn drop_glyphs() {
+        let data = make_font();
+        let font = FontRef::new(&data).unwrap();
+        // drop 'b'
+        let mapper = GidMapper::retain(gids([0, 1, 3])).unwrap();
+        let remapped = mapper.remap_font(&font).unwrap();
+
+        let font = FontRef::new(&remapped.data).unwrap();
+        assert_eq!(font.maxp().unwrap().num_glyphs(), 3);
+        let cmap = font.cmap().unwrap();
+        assert_eq!(cmap.map_codepoint('a'), Some(GlyphId::new(1)));
+        assert_eq!(cmap.map_codepoint('b'), None);
+        assert_eq!(cmap.map_codepoint('c'), Some(GlyphId::new(2)));
+        assert_eq!(font.hmtx().unwrap().advance(GlyphId::new(2)), Some(300));
+
+        // both rules use 'b'
+        let (singles, ligatures) = substitutions(&font);
+        assert!(singles.is_empty());
+        assert!(ligatures.is_empty());
+    }
+
+    #[test]
+    fn coverage_order() {
+        let mut table = CursivePosFormat1::new(
+            CoverageTableBuilder::from_glyphs(gids([1, 2, 3]).to_vec()).build(),
+            vec![
+                EntryExitRecord::new(None, Some(AnchorTable::format_1(1, 0))),
+                EntryExitRecord::new(None, Some(AnchorTable::format_1(2, 0))),
+                EntryExitRecord::new(None, Some(AnchorTable::format_1(3, 0))),
+            ],
+        );
+        let mapper = GidMapper::from_order(gids([0, 3, 1])).unwrap();
+        table.remap_glyphs(&mapper);
+        assert_eq!(table.coverage.iter().collect::<Vec<_>>(), gids([1, 2]));
+        let exits = table
+            .entry_exit_record
+            .iter()
+            .map(|record| match record.exit_anchor.as_ref() {
+                Some(AnchorTable::Format1(anchor)) => anchor.x_coordinate,
+                _ => panic!("missing anchor"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(exits, [3, 1]);
+    }
+
+    #[test]
+    fn clip_list() {
+        let clip = |start, end, size: i16| Clip {
+            start_glyph_id: GlyphId::new(start),
+            end_glyph_id: GlyphId::new(end),
+            clip_box: ClipBox::format_1(0.into(), 0.into(), size.into(), size.into()).into(),
+        };
+        let mut clips = ClipList::new(vec![clip(1, 3, 10), clip(4, 4, 20)]);
+        // glyphs 1 and 3 are no longer adjacent, and glyph 4 moves between them
+        let mapper = GidMapper::from_order(gids([0, 1, 4, 3])).unwrap();
+        clips.remap_glyphs(&mapper);
+        let ranges = clips
+            .clips
+            .iter()
+            .map(|clip| {
+                let ClipBox::Format1(clip_box) = &*clip.clip_box else {
+                    panic!("wrong format");
+                };
+                (
+                    clip.start_glyph_id.to_u16(),
+                    clip.end_glyph_id.to_u16(),
+                    clip_box.x_max.to_i16(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(1, 1, 10), (2, 2, 20), (3, 3, 10)]);
+    }
+}
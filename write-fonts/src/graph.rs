@@ -2,15 +2,24 @@
 
 use super::write::TableData;
 use std::{
-    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque},
     sync::atomic::AtomicUsize,
 };
 
+mod dump;
+mod layout;
+
+pub use dump::{GraphDump, GraphEdge, GraphNode};
+
 static OBJECT_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
+/// The maximum number of rounds of overflow resolution we will attempt.
+const MAX_RESOLUTION_ROUNDS: usize = 32;
+
 #[derive(Debug, Clone, Copy, PartialOrd, Ord, Hash, PartialEq, Eq)]
 pub(crate) struct ObjectId(usize);
 
+/// The size of an offset; the discriminant is the size in bytes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum OffsetLen {
@@ -108,7 +117,7 @@ struct Distance {
     order: u32,
 }
 
-//TODO: remove me? maybe? not really used right now...
+/// A bump applied to a node's distance, used to move it closer to its parents
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 struct Priority(u8);
 
@@ -118,7 +127,6 @@ impl Priority {
     const TWO: Priority = Priority(2);
     const THREE: Priority = Priority(3);
 
-    #[cfg(test)]
     fn increase(&mut self) -> bool {
         let result = *self != Priority::THREE;
         self.0 = (self.0 + 1).min(3);
@@ -151,7 +159,6 @@ impl Node {
         }
     }
 
-    #[cfg(test)]
     fn raise_priority(&mut self) -> bool {
         self.priority.increase()
     }
@@ -203,26 +210,67 @@ impl Graph {
         }
     }
 
-    pub(crate) fn topological_sort(&mut self) {
+    /// Sort the graph, attempting to resolve any offset overflows.
+    ///
+    /// This is modeled on the [hb-repacker]: we first try a simple ordering,
+    /// and if that overflows we progressively try more invasive changes:
+    /// sorting by distance, isolating 32-bit subgraphs, splitting large
+    /// subtables and promoting lookups to extension lookups (for GSUB & GPOS),
+    /// and finally duplicating shared nodes and raising node priorities.
+    ///
+    /// Returns `true` if the resulting order has no overflows.
+    ///
+    /// [hb-repacker]: https://github.com/harfbuzz/harfbuzz/blob/main/src/hb-repacker.hh
+    pub(crate) fn pack_objects(&mut self) -> bool {
         self.sort_kahn();
-        if !self.find_overflows().is_empty() {
-            self.sort_shortest_distance();
+        if !self.has_overflows() {
+            return true;
+        }
+        self.sort_shortest_distance();
+        if !self.has_overflows() {
+            return true;
         }
-        if !self.find_overflows().is_empty() {
-            self.assign_32bit_spaces();
+
+        if let Some(ext_type) = self.layout_extension_type() {
+            if let Some(lookups) = self.layout_lookups() {
+                self.split_subtables_if_needed(&lookups);
+                self.promote_extensions_if_needed(&lookups, ext_type);
+                self.remove_orphans();
+            }
+        }
+
+        self.assign_32bit_spaces();
+        self.sort_shortest_distance();
+
+        for _ in 0..MAX_RESOLUTION_ROUNDS {
+            let overflows = self.find_overflows();
+            if overflows.is_empty() {
+                return true;
+            }
+            if !self.try_resolve_overflows(&overflows) {
+                break;
+            }
+            self.remove_orphans();
             self.sort_shortest_distance();
         }
+        !self.has_overflows()
+    }
+
+    fn has_overflows(&self) -> bool {
+        !self.find_overflows().is_empty()
     }
 
+    /// Returns the (parent, child) pairs of all offsets that cannot be
+    /// represented with the current ordering.
     fn find_overflows(&self) -> Vec<(ObjectId, ObjectId)> {
         let mut result = Vec::new();
-        for (parent_id, data) in &self.objects {
+        for parent_id in &self.order {
             let parent = &self.nodes[parent_id];
-            for link in &data.offsets {
+            for link in &self.objects[parent_id].offsets {
                 let child = &self.nodes[&link.object];
-                //TODO: account for 'whence'
-                let rel_off = child.position - parent.position;
-                if link.len.max_value() < rel_off {
+                let rel_off =
+                    child.position as i64 - (parent.position as i64 + link.adjustment as i64);
+                if rel_off < 0 || rel_off > link.len.max_value() as i64 {
                     result.push((*parent_id, link.object));
                 }
             }
@@ -230,6 +278,88 @@ impl Graph {
         result
     }
 
+    /// Attempt to fix the provided overflows.
+    ///
+    /// If an overflowing child is shared with other parents, we give the
+    /// overflowing parent its own copy, which can be placed closer to it;
+    /// otherwise we raise the child's priority so it is packed sooner.
+    ///
+    /// Returns `false` if no changes could be made.
+    fn try_resolve_overflows(&mut self, overflows: &[(ObjectId, ObjectId)]) -> bool {
+        self.update_parents();
+        let mut made_changes = false;
+        let mut seen = HashSet::new();
+        for (parent, child) in overflows {
+            if !seen.insert(*child) {
+                continue;
+            }
+            let has_other_parents = self.nodes[child].parents.iter().any(|(id, _)| id != parent);
+            if has_other_parents {
+                self.duplicate_child(*parent, *child);
+                made_changes = true;
+            } else if self.nodes.get_mut(child).unwrap().raise_priority() {
+                made_changes = true;
+            }
+        }
+        made_changes
+    }
+
+    /// Point all of `parent`'s links to `child` at a new copy of `child`.
+    ///
+    /// The copy shares the original's children.
+    fn duplicate_child(&mut self, parent: ObjectId, child: ObjectId) -> ObjectId {
+        let new_id = ObjectId::next();
+        let obj = self.objects[&child].clone();
+        let mut node = Node::new(obj.bytes.len() as u32);
+        node.space = self.nodes[&child].space;
+        self.objects.insert(new_id, obj);
+        self.nodes.insert(new_id, node);
+        for link in self
+            .objects
+            .get_mut(&parent)
+            .unwrap()
+            .offsets
+            .iter_mut()
+            .filter(|link| link.object == child)
+        {
+            link.object = new_id;
+        }
+        self.parents_invalid = true;
+        self.distance_invalid = true;
+        new_id
+    }
+
+    /// Add a new object to the graph, returning its id.
+    fn add_object(&mut self, obj: TableData) -> ObjectId {
+        let id = ObjectId::next();
+        self.nodes
+            .insert(id, Node::new(obj.bytes.len().try_into().unwrap()));
+        self.objects.insert(id, obj);
+        self.parents_invalid = true;
+        self.distance_invalid = true;
+        id
+    }
+
+    /// Replace the contents of an existing object.
+    fn replace_object(&mut self, id: ObjectId, obj: TableData) {
+        self.nodes.get_mut(&id).unwrap().size = obj.bytes.len().try_into().unwrap();
+        self.objects.insert(id, obj);
+        self.parents_invalid = true;
+        self.distance_invalid = true;
+    }
+
+    /// Remove any objects that are no longer reachable from the root.
+    fn remove_orphans(&mut self) {
+        let reachable = self.find_descendents(self.root);
+        if reachable.len() == self.objects.len() {
+            return;
+        }
+        self.objects.retain(|id, _| reachable.contains(id));
+        self.nodes.retain(|id, _| reachable.contains(id));
+        self.parents_invalid = true;
+        self.distance_invalid = true;
+    }
+
     fn update_parents(&mut self) {
         if !self.parents_invalid {
             return;
@@ -363,9 +493,16 @@ impl Graph {
 
     /// Returns `true` if there were any 32bit subgraphs
     fn assign_32bit_spaces(&mut self) -> bool {
+        // we may be called again after the graph has been modified, so
+        // discard any previous assignments
+        for node in self.nodes.values_mut() {
+            node.space = Space::REACHABLE;
+        }
+        self.next_space = Space::INIT;
         self.update_parents();
-        // find all the nodes that have incoming 32-bit edges
-        let mut roots = HashSet::new();
+        // find all the nodes that have incoming 32-bit edges. These are visited
+        // in id order (and not hash order) so that the output is reproducible.
+        let mut roots = BTreeSet::new();
         for (id, node) in &self.nodes {
             if node
                 .parents
@@ -383,10 +520,8 @@ impl Graph {
         // assign all nodes reachable from 16/24 bit edges to space 0.
         self.assign_space_0();
 
-        while !roots.is_empty() {
-            let root = *roots.iter().next().unwrap();
+        while let Some(root) = roots.pop_first() {
             self.isolate_and_assign_space(root);
-            roots.remove(&root);
         }
         self.update_parents();
         true
@@ -401,6 +536,23 @@ impl Graph {
             return;
         }
 
+        // - if root itself is reachable from 16-bit space, the 32-bit parents
+        // get their own copy of the whole subgraph.
+        let root = if self.nodes[&root].space == Space::SHORT_REACHABLE {
+            let new_root = self.duplicate_subgraph(root, &mut HashMap::new());
+            for link in self
+                .objects
+                .values_mut()
+                .flat_map(|obj| obj.offsets.iter_mut())
+                .filter(|link| link.object == root && link.len == OffsetLen::Offset32)
+            {
+                link.object = new_root;
+            }
+            new_root
+        } else {
+            root
+        };
+
         #[derive(Debug, Clone)]
         enum Op {
             Reprioritize(Space),
@@ -544,7 +696,6 @@ impl Graph {
         }
     }
 
-    #[cfg(test)]
     fn find_descendents(&self, root: ObjectId) -> HashSet<ObjectId> {
         let mut result = HashSet::new();
         let mut stack = VecDeque::from([root]);
@@ -811,7 +962,60 @@ mod tests {
             .add_link(ids[0], ids[2], OffsetLen::Offset16)
             .add_link(ids[1], ids[2], OffsetLen::Offset16)
             .build();
-        graph.topological_sort();
+        assert!(graph.pack_objects());
+    }
+
+    #[test]
+    fn assign_32bit_spaces_is_reproducible() {
+        // the spaces of 32-bit subgraphs must not depend on hash order
+        let ids = make_ids::<9>();
+        let sizes = [10; 9];
+        let mut builder = TestGraphBuilder::new(ids, sizes);
+        for i in 1..5 {
+            builder
+                .add_link(ids[0], ids[i], OffsetLen::Offset32)
+                .add_link(ids[i], ids[i + 4], OffsetLen::Offset16);
+        }
+        let order = |builder: &TestGraphBuilder| {
+            let mut graph = builder.build();
+            graph.assign_32bit_spaces();
+            graph.sort_shortest_distance();
+            graph.order
+        };
+        let expected = order(&builder);
+        assert_eq!(
+            expected,
+            [0, 1, 5, 2, 6, 3, 7, 4, 8].map(|i| ids[i]).to_vec()
+        );
+        for _ in 0..10 {
+            assert_eq!(order(&builder), expected);
+        }
+    }
+
+    #[test]
+    fn resolve_overflow_by_duplication() {
+        // two large 32-bit subgraphs share a child that can't be placed
+        // close enough to both of them, so it must be duplicated.
+        let ids = make_ids::<4>();
+        let sizes = [10, 60000, 60000, 100];
+        let mut graph = TestGraphBuilder::new(ids, sizes)
+            .add_link(ids[0], ids[1], OffsetLen::Offset32)
+            .add_link(ids[0], ids[2], OffsetLen::Offset32)
+            .add_link(ids[1], ids[3], OffsetLen::Offset16)
+            .add_link(ids[2], ids[3], OffsetLen::Offset16)
+            .build();
+        assert!(graph.pack_objects());
+        assert_eq!(graph.nodes.len(), 5);
         assert!(graph.find_overflows().is_empty());
     }
+
+    #[test]
+    fn unresolvable_overflow() {
+        let ids = make_ids::<2>();
+        let sizes = [u16::MAX as usize + 10, 10];
+        let mut graph = TestGraphBuilder::new(ids, sizes)
+            .add_link(ids[0], ids[1], OffsetLen::Offset16)
+            .build();
+        assert!(!graph.pack_objects());
+    }
 }
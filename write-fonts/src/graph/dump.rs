@@ -0,0 +1,291 @@
+//! Describing a packed graph, for debugging serialization.
+//!
+//! When a table fails to pack, or is packed in an unexpected way, it can be
+//! hard to see why from the bytes alone. A [`GraphDump`] records the size
+//! and position of each object in the graph and the offsets between them,
+//! and can be written as [Graphviz] or JSON for inspection.
+//!
+//! [Graphviz]: https://graphviz.org
+
+use std::{collections::HashMap, fmt::Write};
+
+use super::{Graph, OffsetLen};
+use crate::write::TableType;
+
+/// The objects in a table's graph, in the order they were packed.
+///
+/// This is returned by [`dump_table_graph`](crate::dump_table_graph).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphDump {
+    /// The objects, in packed order; the first is the root.
+    pub nodes: Vec<GraphNode>,
+}
+
+/// A single object in a [`GraphDump`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphNode {
+    /// The type of the object, if it is known to the serializer.
+    pub table_type: TableType,
+    /// The size of the object, in bytes.
+    pub size: u32,
+    /// The position of the object in the packed table.
+    pub position: u32,
+    /// The offsets from this object to its children.
+    pub offsets: Vec<GraphEdge>,
+}
+
+/// An offset from one object in a [`GraphDump`] to another.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GraphEdge {
+    /// The index of the child in [`GraphDump::nodes`].
+    pub child: usize,
+    /// The position of the offset within its parent.
+    pub position: u32,
+    /// The size of the offset.
+    pub len: OffsetLen,
+    /// The value of the offset in this packing, which may not fit in `len`.
+    pub value: i64,
+}
+
+impl GraphEdge {
+    /// `true` if the value of this offset cannot be written.
+    pub fn overflows(&self) -> bool {
+        self.value < 0 || self.value > self.len.max_value() as i64
+    }
+}
+
+impl GraphDump {
+    /// Describe the graph, in its current order.
+    pub(crate) fn new(graph: &Graph) -> Self {
+        let indices = graph
+            .order
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect::<HashMap<_, _>>();
+        let mut positions = Vec::with_capacity(graph.order.len());
+        let mut position = 0u32;
+        for id in &graph.order {
+            positions.push(position);
+            position += graph.objects[id].bytes.len() as u32;
+        }
+
+        let nodes = graph
+            .order
+            .iter()
+            .zip(&positions)
+            .map(|(id, position)| {
+                let obj = &graph.objects[id];
+                let offsets = obj
+                    .offsets
+                    .iter()
+                    .map(|link| {
+                        let child = indices[&link.object];
+                        GraphEdge {
+                            child,
+                            position: link.pos,
+                            len: link.len,
+                            value: positions[child] as i64
+                                - (*position as i64 + link.adjustment as i64),
+                        }
+                    })
+                    .collect();
+                GraphNode {
+                    table_type: obj.type_,
+                    size: obj.bytes.len() as u32,
+                    position: *position,
+                    offsets,
+                }
+            })
+            .collect();
+        GraphDump { nodes }
+    }
+
+    /// `true` if any offset in the graph overflows.
+    pub fn has_overflows(&self) -> bool {
+        self.nodes
+            .iter()
+            .flat_map(|node| &node.offsets)
+            .any(GraphEdge::overflows)
+    }
+
+    /// Write the graph in the [DOT] language, for rendering with Graphviz.
+    ///
+    /// Nodes are labelled with their index, type, size and position, and
+    /// offsets that overflow are drawn in red.
+    ///
+    /// [DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n    node [shape=box];\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let name = type_name(node.table_type)
+                .map(|name| format!(" {}", escape(&name)))
+                .unwrap_or_default();
+            writeln!(
+                out,
+                "    n{i} [label=\"{i}{name}\\n{} bytes at {}\"];",
+                node.size, node.position
+            )
+            .unwrap();
+        }
+        for (i, node) in self.nodes.iter().enumerate() {
+            for edge in &node.offsets {
+                let color = if edge.overflows() { ", color=red" } else { "" };
+                writeln!(
+                    out,
+                    "    n{i} -> n{} [label=\"{} at {}: {}\"{color}];",
+                    edge.child, edge.len, edge.position, edge.value
+                )
+                .unwrap();
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Write the graph as JSON.
+    ///
+    /// The result is an object with a `nodes` array, in packed order. Each
+    /// node has its `type` (a tag, or `null`), `size`, `position` and
+    /// `offsets`; each offset has the index of its `child`, its `position`
+    /// in the parent, its `len` in bytes, its `value` and whether it
+    /// `overflows`.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"nodes\":[");
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let type_ = type_name(node.table_type)
+                .map(|name| format!("\"{}\"", escape(&name)))
+                .unwrap_or_else(|| "null".into());
+            write!(
+                out,
+                "{{\"type\":{type_},\"size\":{},\"position\":{},\"offsets\":[",
+                node.size, node.position
+            )
+            .unwrap();
+            for (j, edge) in node.offsets.iter().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                write!(
+                    out,
+                    "{{\"child\":{},\"position\":{},\"len\":{},\"value\":{},\"overflows\":{}}}",
+                    edge.child,
+                    edge.position,
+                    edge.len as u8,
+                    edge.value,
+                    edge.overflows()
+                )
+                .unwrap();
+            }
+            out.push_str("]}");
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+fn type_name(table_type: TableType) -> Option<String> {
+    match table_type {
+        TableType::TopLevel(tag) => Some(tag.to_string()),
+        TableType::Unknown => None,
+    }
+}
+
+/// Escape a string for use in a quoted DOT or JSON string.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use types::Tag;
+
+    use super::*;
+    use crate::graph::ObjectId;
+    use crate::write::TableData;
+
+    fn make_graph(sizes: &[usize], links: &[(usize, usize, OffsetLen)]) -> Graph {
+        let ids = sizes.iter().map(|_| ObjectId::next()).collect::<Vec<_>>();
+        let mut objects = sizes
+            .iter()
+            .zip(&ids)
+            .map(|(size, id)| (*id, TableData::make_mock(*size)))
+            .collect::<HashMap<_, _>>();
+        for (from, to, len) in links {
+            objects
+                .get_mut(&ids[*from])
+                .unwrap()
+                .add_mock_offset(ids[*to], *len);
+        }
+        objects.get_mut(&ids[0]).unwrap().type_ = TableType::TopLevel(Tag::new(b"GSUB"));
+        Graph::from_objects(objects, ids[0])
+    }
+
+    #[test]
+    fn describe_packed_graph() {
+        let mut graph = make_graph(
+            &[10, 20, 30],
+            &[
+                (0, 1, OffsetLen::Offset16),
+                (0, 2, OffsetLen::Offset32),
+                (1, 2, OffsetLen::Offset16),
+            ],
+        );
+        assert!(graph.pack_objects());
+        let dump = GraphDump::new(&graph);
+        assert!(!dump.has_overflows());
+        assert_eq!(
+            dump.nodes
+                .iter()
+                .map(|node| (node.size, node.position))
+                .collect::<Vec<_>>(),
+            [(10, 0), (20, 10), (30, 30)]
+        );
+        assert_eq!(
+            dump.to_json(),
+            concat!(
+                "{\"nodes\":[",
+                "{\"type\":\"GSUB\",\"size\":10,\"position\":0,\"offsets\":[",
+                "{\"child\":1,\"position\":0,\"len\":2,\"value\":10,\"overflows\":false},",
+                "{\"child\":2,\"position\":2,\"len\":4,\"value\":30,\"overflows\":false}]},",
+                "{\"type\":null,\"size\":20,\"position\":10,\"offsets\":[",
+                "{\"child\":2,\"position\":0,\"len\":2,\"value\":20,\"overflows\":false}]},",
+                "{\"type\":null,\"size\":30,\"position\":30,\"offsets\":[]}",
+                "]}"
+            )
+        );
+        let dot = dump.to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("    n0 [label=\"0 GSUB\\n10 bytes at 0\"];\n"));
+        assert!(dot.contains("    n1 -> n2 [label=\"Offset16 at 0: 20\"];\n"));
+    }
+
+    #[test]
+    fn mark_overflows() {
+        let mut graph = make_graph(
+            &[u16::MAX as usize + 10, 10],
+            &[(0, 1, OffsetLen::Offset16)],
+        );
+        assert!(!graph.pack_objects());
+        let dump = GraphDump::new(&graph);
+        assert!(dump.has_overflows());
+        assert!(dump.nodes[0].offsets[0].overflows());
+        assert!(dump
+            .to_dot()
+            .contains("    n0 -> n1 [label=\"Offset16 at 0: 65545\", color=red];\n"));
+    }
+}
@@ -0,0 +1,330 @@
+//! Overflow resolution specific to the GSUB & GPOS tables.
+//!
+//! These tables have well-known structure that lets us make changes that
+//! are not possible in the general case: we can wrap lookup subtables in
+//! extension subtables, which use 32-bit offsets, and we can split large
+//! subtables into multiple smaller subtables.
+
+use std::collections::HashSet;
+
+use read_fonts::{tables::layout::CoverageTable, FontData, FontRead};
+use types::Tag;
+
+use super::{Graph, ObjectId, OffsetLen};
+use crate::{
+    tables::layout::CoverageTableBuilder,
+    write::{TableData, TableType},
+    FontWrite, TableWriter,
+};
+
+/// The lookup type of extension lookups in GSUB.
+const GSUB_EXTENSION: u16 = 7;
+/// The lookup type of extension lookups in GPOS.
+const GPOS_EXTENSION: u16 = 9;
+/// The lookup type of pair adjustment lookups in GPOS.
+const GPOS_PAIR: u16 = 2;
+
+/// The position of the lookupListOffset in the GSUB/GPOS header.
+const LOOKUP_LIST_POS: u32 = 8;
+/// The size of the fixed portion of a Lookup table.
+const LOOKUP_HEADER_LEN: usize = 6;
+/// The size of the fixed portion of a PairPosFormat1 table.
+const PAIR_POS_HEADER_LEN: usize = 10;
+/// The size of the fixed portion of a coverage table.
+const COVERAGE_HEADER_LEN: usize = 4;
+
+/// The largest subtable subgraph we will try to address with 16-bit offsets.
+const MAX_SUBTABLE_SIZE: usize = u16::MAX as usize;
+
+impl Graph {
+    /// If this is a GSUB or GPOS table, return the extension lookup type.
+    pub(super) fn layout_extension_type(&self) -> Option<u16> {
+        match self.objects[&self.root].type_ {
+            TableType::TopLevel(Tag::GSUB) => Some(GSUB_EXTENSION),
+            TableType::TopLevel(Tag::GPOS) => Some(GPOS_EXTENSION),
+            _ => None,
+        }
+    }
+
+    /// The ids of the lookups in this GSUB or GPOS table, in order.
+    pub(super) fn layout_lookups(&self) -> Option<Vec<ObjectId>> {
+        let lookup_list = self.objects[&self.root]
+            .offsets
+            .iter()
+            .find(|link| link.pos == LOOKUP_LIST_POS)?
+            .object;
+        let mut links = self.objects[&lookup_list]
+            .offsets
+            .iter()
+            .collect::<Vec<_>>();
+        links.sort_by_key(|link| link.pos);
+        Some(links.into_iter().map(|link| link.object).collect())
+    }
+
+    /// Split any subtables that are too large to be packed.
+    ///
+    /// Currently this only handles PairPos format 1 subtables, which can grow
+    /// very large in fonts with extensive kerning.
+    pub(super) fn split_subtables_if_needed(&mut self, lookups: &[ObjectId]) {
+        if self.layout_extension_type() != Some(GPOS_EXTENSION) {
+            return;
+        }
+        for lookup in lookups {
+            self.split_pair_pos_subtables(*lookup);
+        }
+    }
+
+    fn split_pair_pos_subtables(&mut self, lookup_id: ObjectId) {
+        let lookup = &self.objects[&lookup_id];
+        let Some(lookup_type) = read_u16(&lookup.bytes, 0) else {
+            return;
+        };
+        let is_extension = lookup_type == GPOS_EXTENSION;
+        let mut links = lookup.offsets.clone();
+        links.sort_by_key(|link| link.pos);
+
+        let mut subtables = Vec::with_capacity(links.len());
+        let mut did_split = false;
+        for link in links {
+            subtables.push(link.object);
+            let (subtable, subtable_type) = if is_extension {
+                let ext = &self.objects[&link.object];
+                match (ext.offsets.first(), read_u16(&ext.bytes, 2)) {
+                    (Some(ext_link), Some(ext_type)) => (ext_link.object, ext_type),
+                    _ => continue,
+                }
+            } else {
+                (link.object, lookup_type)
+            };
+            if subtable_type != GPOS_PAIR {
+                continue;
+            }
+            for new_subtable in self.split_pair_pos_format1(subtable) {
+                did_split = true;
+                let new_subtable = if is_extension {
+                    self.add_object(extension_subtable(GPOS_PAIR, new_subtable))
+                } else {
+                    new_subtable
+                };
+                subtables.push(new_subtable);
+            }
+        }
+
+        if did_split && subtables.len() <= u16::MAX as usize {
+            let lookup = &self.objects[&lookup_id];
+            let tail = LOOKUP_HEADER_LEN + lookup.offsets.len() * 2;
+            let mut new_lookup = TableData::new(lookup.type_);
+            new_lookup.bytes.extend_from_slice(&lookup.bytes[..4]);
+            new_lookup
+                .bytes
+                .extend_from_slice(&(subtables.len() as u16).to_be_bytes());
+            for subtable in subtables {
+                new_lookup.add_offset(subtable, 2, 0);
+            }
+            new_lookup
+                .bytes
+                .extend_from_slice(lookup.bytes.get(tail..).unwrap_or_default());
+            self.replace_object(lookup_id, new_lookup);
+        }
+    }
+
+    /// If this subtable is a PairPos format 1 subtable that is too large,
+    /// split it into multiple subtables.
+    ///
+    /// The first of these subtables replaces the original; the ids of the
+    /// remaining subtables are returned, and must be added to the lookup.
+    fn split_pair_pos_format1(&mut self, subtable: ObjectId) -> Vec<ObjectId> {
+        let table = &self.objects[&subtable];
+        if read_u16(&table.bytes, 0) != Some(1) {
+            return Vec::new();
+        }
+        let (Some(value_formats), Some(pair_set_count)) =
+            (table.bytes.get(4..8), read_u16(&table.bytes, 8))
+        else {
+            return Vec::new();
+        };
+        let value_formats = value_formats.to_owned();
+        let pair_set_count = pair_set_count as usize;
+
+        let Some(coverage) = table.offsets.iter().find(|link| link.pos == 2) else {
+            return Vec::new();
+        };
+        let Ok(coverage) =
+            CoverageTable::read(FontData::new(&self.objects[&coverage.object].bytes))
+        else {
+            return Vec::new();
+        };
+        let coverage = coverage.iter().collect::<Vec<_>>();
+        let mut pair_sets = table
+            .offsets
+            .iter()
+            .filter(|link| link.pos >= PAIR_POS_HEADER_LEN as u32)
+            .collect::<Vec<_>>();
+        pair_sets.sort_by_key(|link| link.pos);
+        let pair_sets = pair_sets
+            .into_iter()
+            .map(|link| link.object)
+            .collect::<Vec<_>>();
+        if pair_sets.len() != pair_set_count || coverage.len() != pair_set_count {
+            return Vec::new();
+        }
+
+        // each pair set costs an offset, a coverage entry (at most), and its
+        // own subgraph
+        let costs = pair_sets
+            .iter()
+            .map(|id| 4 + self.subgraph_size(*id))
+            .collect::<Vec<_>>();
+        let base_cost = PAIR_POS_HEADER_LEN + COVERAGE_HEADER_LEN;
+        if base_cost + costs.iter().sum::<usize>() <= MAX_SUBTABLE_SIZE {
+            return Vec::new();
+        }
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let mut current = base_cost;
+        for (i, cost) in costs.iter().enumerate() {
+            if i > start && current + cost > MAX_SUBTABLE_SIZE {
+                chunks.push(start..i);
+                start = i;
+                current = base_cost;
+            }
+            current += cost;
+        }
+        chunks.push(start..pair_set_count);
+        if chunks.len() == 1 {
+            return Vec::new();
+        }
+
+        let mut new_subtables = Vec::with_capacity(chunks.len() - 1);
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let coverage = coverage[chunk.clone()]
+                .iter()
+                .copied()
+                .collect::<CoverageTableBuilder>()
+                .build();
+            let mut writer = TableWriter::default();
+            coverage.write_into(&mut writer);
+            let coverage = self.add_object(TableData {
+                bytes: writer.into_data(),
+                ..Default::default()
+            });
+
+            let mut pair_pos = TableData::default();
+            pair_pos.bytes.extend_from_slice(&1u16.to_be_bytes());
+            pair_pos.add_offset(coverage, 2, 0);
+            pair_pos.bytes.extend_from_slice(&value_formats);
+            pair_pos
+                .bytes
+                .extend_from_slice(&(chunk.len() as u16).to_be_bytes());
+            for pair_set in &pair_sets[chunk] {
+                pair_pos.add_offset(*pair_set, 2, 0);
+            }
+            if i == 0 {
+                self.replace_object(subtable, pair_pos);
+            } else {
+                new_subtables.push(self.add_object(pair_pos));
+            }
+        }
+        new_subtables
+    }
+
+    /// Promote lookups to extension lookups, until the portion of the graph
+    /// that is reachable via 16-bit offsets is small enough to be packed.
+    ///
+    /// Larger lookups are promoted first.
+    pub(super) fn promote_extensions_if_needed(&mut self, lookups: &[ObjectId], ext_type: u16) {
+        let mut short_size = self.short_reachable_size();
+        if short_size <= u16::MAX as usize {
+            return;
+        }
+
+        let mut candidates = lookups
+            .iter()
+            .filter(|id| read_u16(&self.objects[id].bytes, 0) != Some(ext_type))
+            .map(|id| (self.subgraph_size(*id), *id))
+            .collect::<Vec<_>>();
+        candidates.sort_by(|a, b| b.cmp(a));
+
+        for (_, lookup) in candidates {
+            if short_size <= u16::MAX as usize {
+                break;
+            }
+            self.promote_lookup(lookup, ext_type);
+            short_size = self.short_reachable_size();
+        }
+    }
+
+    /// Convert this lookup to an extension lookup, wrapping each of its
+    /// subtables in an extension subtable.
+    fn promote_lookup(&mut self, lookup_id: ObjectId, ext_type: u16) {
+        let lookup = &self.objects[&lookup_id];
+        let Some(lookup_type) = read_u16(&lookup.bytes, 0) else {
+            return;
+        };
+        if lookup_type == ext_type {
+            return;
+        }
+        let subtables = lookup
+            .offsets
+            .iter()
+            .map(|link| link.object)
+            .collect::<Vec<_>>();
+        let extensions = subtables
+            .into_iter()
+            .map(|subtable| self.add_object(extension_subtable(lookup_type, subtable)))
+            .collect::<Vec<_>>();
+
+        let mut lookup = self.objects[&lookup_id].clone();
+        lookup.bytes[..2].copy_from_slice(&ext_type.to_be_bytes());
+        for (link, ext) in lookup.offsets.iter_mut().zip(extensions) {
+            link.object = ext;
+        }
+        self.replace_object(lookup_id, lookup);
+    }
+
+    /// The total size of all objects reachable from the root via 16 or
+    /// 24-bit offsets.
+    fn short_reachable_size(&self) -> usize {
+        let mut seen = HashSet::new();
+        let mut stack = vec![self.root];
+        let mut size = 0;
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            let obj = &self.objects[&id];
+            size += obj.bytes.len();
+            stack.extend(
+                obj.offsets
+                    .iter()
+                    .filter(|link| link.len != OffsetLen::Offset32)
+                    .map(|link| link.object),
+            );
+        }
+        size
+    }
+
+    /// The total size of the subgraph rooted at `root`.
+    fn subgraph_size(&self, root: ObjectId) -> usize {
+        self.find_descendents(root)
+            .iter()
+            .map(|id| self.objects[id].bytes.len())
+            .sum()
+    }
+}
+
+/// An ExtensionSubstFormat1 or ExtensionPosFormat1 table.
+fn extension_subtable(lookup_type: u16, subtable: ObjectId) -> TableData {
+    let mut data = TableData::default();
+    data.bytes.extend_from_slice(&1u16.to_be_bytes());
+    data.bytes.extend_from_slice(&lookup_type.to_be_bytes());
+    data.add_offset(subtable, 4, 0);
+    data
+}
+
+fn read_u16(bytes: &[u8], pos: usize) -> Option<u16> {
+    bytes
+        .get(pos..pos + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+}
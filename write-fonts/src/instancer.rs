@@ -0,0 +1,1316 @@
+//! Instancing of variable fonts.
+//!
+//! An [`Instancer`] pins some or all of the axes of a variable font to fixed
+//! values. If every axis is pinned the result is a static font; otherwise
+//! the result is a variable font with fewer axes.
+//!
+//! The outlines and metrics in the `glyf`, `hmtx` and `vmtx` tables are
+//! updated with the `gvar` deltas (or the `HVAR` and `VVAR` deltas, for fonts
+//! without `gvar`), and `MVAR` deltas are applied to the `OS/2`, `hhea`,
+//! `vhea` and `post` tables. The `fvar`, `avar`, `gvar` and `STAT` tables are
+//! updated for the remaining axes, or dropped if no axes remain.
+//!
+//! There are some limitations:
+//!
+//! - `CFF2` outlines are not supported.
+//! - When only some axes are pinned, the `HVAR`, `VVAR` and `MVAR` tables
+//!   are dropped; advances at other locations are derived from the phantom
+//!   points in `gvar`, and the `MVAR` deltas are applied at the default
+//!   location of the remaining axes.
+//! - The `cvar` table is dropped without being applied to `cvt `.
+//! - Variation data in `GDEF`, `GPOS` and `GSUB` is left unchanged.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use kurbo::Rect;
+use read_fonts::{
+    tables::{
+        fvar::{Fvar, VariationAxisRecord},
+        glyf::{Anchor, CompositeGlyphFlags, PointFlags, Transform},
+        gvar::{GlyphVariationData, Gvar as ReadGvar, TupleVariation},
+        hmtx::LongMetric as ReadLongMetric,
+        mvar::{tags as mvar_tags, Mvar},
+    },
+    FontRef, ReadError, TableProvider,
+};
+use types::{BigEndian, F2Dot14, Fixed, GlyphId, Point, Tag};
+
+use crate::{
+    dump_table,
+    from_obj::ToOwnedTable,
+    tables::{
+        avar::{Avar, AxisValueMap, SegmentMaps},
+        fvar::{Fvar as FvarTable, InstanceRecord, VariationAxisRecord as FvarAxis},
+        glyf::{
+            Bbox, Component, CompositeGlyph, Contour, CurvePoint, GlyfLocaBuilder, Glyph,
+            SimpleGlyph,
+        },
+        gvar::{
+            iup_delta_optimize, GlyphDelta, GlyphDeltas, GlyphVariations, Gvar, GvarInputError,
+        },
+        head::Head,
+        hhea::Hhea,
+        hmtx::{Hmtx, LongMetric},
+        os2::Os2,
+        post::Post,
+        stat::{AxisValue, Stat},
+        variations::Tuple,
+        vhea::Vhea,
+        vmtx::Vmtx,
+    },
+    FontBuilder,
+};
+
+/// The number of phantom points appended to the points of each glyph.
+const N_PHANTOM_POINTS: usize = 4;
+/// The maximum depth of nested composite glyphs.
+const MAX_COMPONENT_DEPTH: usize = 64;
+/// The tolerance, in font units, used when optimizing the deltas of the
+/// remaining axes.
+const IUP_TOLERANCE: f64 = 0.5;
+
+/// The OS/2 usWidthClass for each width percentage, in order.
+const WIDTH_CLASSES: [(f64, u16); 9] = [
+    (50.0, 1),
+    (62.5, 2),
+    (75.0, 3),
+    (87.5, 4),
+    (100.0, 5),
+    (112.5, 6),
+    (125.0, 7),
+    (150.0, 8),
+    (200.0, 9),
+];
+
+/// Pins the axes of a variable font to produce a static, or reduced-axis,
+/// font.
+///
+/// ```no_run
+/// # let bytes: &[u8] = &[];
+/// use write_fonts::{instancer::Instancer, read::FontRef, types::Tag};
+///
+/// let font = FontRef::new(bytes).unwrap();
+/// let static_font = Instancer::new(font)
+///     .pin_axis(Tag::new(b"wght"), 700.0)
+///     .instantiate()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Instancer<'a> {
+    font: FontRef<'a>,
+    pins: BTreeMap<Tag, f32>,
+}
+
+/// An error that occurs when instancing a font.
+#[derive(Debug)]
+pub enum InstancerError {
+    /// The font does not have an `fvar` table.
+    NotVariable,
+    /// An axis was pinned that does not exist in the font.
+    UnknownAxis(Tag),
+    /// The font contains a table that cannot be instanced.
+    UnsupportedTable(Tag),
+    /// A table in the input font could not be read.
+    Read(ReadError),
+    /// The `gvar` table for the remaining axes could not be compiled.
+    Gvar(GvarInputError),
+    /// A table in the output font could not be written.
+    Write(crate::Error),
+}
+
+/// The location of an instance, in the axis order of `fvar`.
+struct Location {
+    /// The normalized coordinate of each axis; zero for axes that are not
+    /// pinned.
+    coords: Vec<F2Dot14>,
+    /// The user coordinate of each pinned axis, clamped to the axis range.
+    user: Vec<Option<Fixed>>,
+}
+
+/// A glyph, with the points that will be varied.
+struct GlyphPoints {
+    outline: Outline,
+    /// The points of the glyph followed by the four phantom points. For a
+    /// composite glyph, there is one point per component.
+    points: Vec<Point<i32>>,
+    /// The variations that remain after pinning.
+    variations: Vec<GlyphDeltas>,
+}
+
+enum Outline {
+    Empty,
+    Simple {
+        on_curve: Vec<bool>,
+        contour_ends: Vec<u16>,
+        instructions: Vec<u8>,
+    },
+    Composite {
+        components: Vec<ComponentInfo>,
+        instructions: Vec<u8>,
+    },
+}
+
+struct ComponentInfo {
+    glyph: GlyphId,
+    anchor: Anchor,
+    transform: Transform,
+    flags: CompositeGlyphFlags,
+}
+
+/// A region of the remaining axes, and the deltas that apply in it.
+struct RemainingTuple {
+    peak: Vec<F2Dot14>,
+    intermediate: Option<(Vec<F2Dot14>, Vec<F2Dot14>)>,
+    deltas: Vec<Point<Fixed>>,
+}
+
+/// The tables derived from the glyph outlines.
+struct GlyphTables {
+    glyf: Vec<u8>,
+    loca: Vec<u8>,
+    gvar: Option<Vec<u8>>,
+    head: Head,
+    hhea: Option<Hhea>,
+    hmtx: Option<Vec<u8>>,
+    vhea: Option<Vhea>,
+    vmtx: Option<Vec<u8>>,
+}
+
+impl<'a> Instancer<'a> {
+    /// Create a new instancer for this font.
+    ///
+    /// By default no axes are pinned.
+    pub fn new(font: FontRef<'a>) -> Self {
+        Instancer {
+            font,
+            pins: BTreeMap::new(),
+        }
+    }
+
+    /// Pin the axis with this tag to a value, in user coordinates.
+    ///
+    /// Values outside the range of the axis are clamped.
+    pub fn pin_axis(&mut self, tag: Tag, value: f32) -> &mut Self {
+        self.pins.insert(tag, value);
+        self
+    }
+
+    /// Create the instance, returning the binary data of the new font.
+    pub fn instantiate(&self) -> Result<Vec<u8>, InstancerError> {
+        let font = &self.font;
+        if !font.is_variable() {
+            return Err(InstancerError::NotVariable);
+        }
+        if font.has_table(Tag::CFF2) {
+            return Err(InstancerError::UnsupportedTable(Tag::CFF2));
+        }
+        let fvar = font.fvar()?;
+        let location = self.location(&fvar)?;
+        let is_full = location.is_full();
+        let has_gvar = font.has_table(Tag::GVAR);
+        if !is_full && !has_gvar && font.has_table(Tag::HVAR) {
+            return Err(InstancerError::UnsupportedTable(Tag::HVAR));
+        }
+
+        // tables that are replaced; `None` means the table is dropped
+        let mut tables = BTreeMap::<Tag, Option<Vec<u8>>>::new();
+        for tag in [Tag::HVAR, Tag::VVAR, Tag::MVAR, Tag::CVAR] {
+            tables.insert(tag, None);
+        }
+        if is_full {
+            for tag in [Tag::FVAR, Tag::AVAR, Tag::GVAR] {
+                tables.insert(tag, None);
+            }
+        } else {
+            tables.insert(Tag::FVAR, Some(compile_fvar(&fvar, &location)?));
+            if font.has_table(Tag::AVAR) {
+                tables.insert(Tag::AVAR, Some(self.compile_avar(&location)?));
+            }
+        }
+
+        let mut head: Head = font.head()?.to_owned_table();
+        let mut hhea: Option<Hhea> = font.hhea().ok().map(|hhea| hhea.to_owned_table());
+        let mut vhea: Option<Vhea> = font.vhea().ok().map(|vhea| vhea.to_owned_table());
+        if font.has_table(Tag::GLYF) {
+            let glyph_tables = self.instance_glyphs(&location)?;
+            tables.insert(Tag::GLYF, Some(glyph_tables.glyf));
+            tables.insert(Tag::LOCA, Some(glyph_tables.loca));
+            if !is_full {
+                tables.insert(Tag::GVAR, glyph_tables.gvar);
+            }
+            tables.extend(
+                [
+                    (Tag::HMTX, glyph_tables.hmtx),
+                    (Tag::VMTX, glyph_tables.vmtx),
+                ]
+                .into_iter()
+                .filter(|(_, data)| data.is_some()),
+            );
+            head = glyph_tables.head;
+            hhea = glyph_tables.hhea.or(hhea);
+            vhea = glyph_tables.vhea.or(vhea);
+        }
+
+        let mvar = font.mvar().ok();
+        let delta = |tag| metric_delta(mvar.as_ref(), tag, &location.coords);
+        if let Some(hhea) = hhea.as_mut() {
+            hhea.caret_slope_rise = add_i16(hhea.caret_slope_rise, delta(mvar_tags::HCRS)?);
+            hhea.caret_slope_run = add_i16(hhea.caret_slope_run, delta(mvar_tags::HCRN)?);
+            hhea.caret_offset = add_i16(hhea.caret_offset, delta(mvar_tags::HCOF)?);
+            tables.insert(Tag::HHEA, Some(dump_table(hhea)?));
+        }
+        if let Some(vhea) = vhea.as_mut() {
+            let ascender = add_i16(vhea.ascender.to_i16(), delta(mvar_tags::VASC)?);
+            let descender = add_i16(vhea.descender.to_i16(), delta(mvar_tags::VDSC)?);
+            let line_gap = add_i16(vhea.line_gap.to_i16(), delta(mvar_tags::VLGP)?);
+            vhea.ascender = ascender.into();
+            vhea.descender = descender.into();
+            vhea.line_gap = line_gap.into();
+            vhea.caret_slope_rise = add_i16(vhea.caret_slope_rise, delta(mvar_tags::VCRS)?);
+            vhea.caret_slope_run = add_i16(vhea.caret_slope_run, delta(mvar_tags::VCRN)?);
+            vhea.caret_offset = add_i16(vhea.caret_offset, delta(mvar_tags::VCOF)?);
+            tables.insert(Tag::VHEA, Some(dump_table(vhea)?));
+        }
+        if let Ok(os2) = font.os2() {
+            let mut os2: Os2 = os2.to_owned_table();
+            self.instance_os2(&mut os2, &fvar, &location, &delta)?;
+            tables.insert(Tag::OS2, Some(dump_table(&os2)?));
+        }
+        if let Ok(post) = font.post() {
+            let mut post: Post = post.to_owned_table();
+            let position = add_i16(post.underline_position.to_i16(), delta(mvar_tags::UNDO)?);
+            let thickness = add_i16(post.underline_thickness.to_i16(), delta(mvar_tags::UNDS)?);
+            post.underline_position = position.into();
+            post.underline_thickness = thickness.into();
+            if let Some(slant) = location.user_value(&fvar, Tag::SLNT)? {
+                post.italic_angle = slant.clamp(Fixed::from_i32(-90), Fixed::from_i32(90));
+            }
+            tables.insert(Tag::POST, Some(dump_table(&post)?));
+        }
+        if let Ok(stat) = font.stat() {
+            let mut stat: Stat = stat.to_owned_table();
+            prune_stat(&mut stat, &fvar, &location)?;
+            tables.insert(Tag::STAT, Some(dump_table(&stat)?));
+        }
+        tables.insert(Tag::HEAD, Some(dump_table(&head)?));
+
+        let mut builder = FontBuilder::default();
+        for tag in font.table_tags() {
+            match tables.remove(&tag) {
+                Some(Some(data)) => builder.add_table(tag, data),
+                Some(None) => continue,
+                None => match font.table_data(tag) {
+                    Some(data) => builder.add_table(tag, data.as_ref().to_owned()),
+                    None => continue,
+                },
+            };
+        }
+        Ok(builder.build())
+    }
+
+    fn location(&self, fvar: &Fvar) -> Result<Location, InstancerError> {
+        let axes = fvar.axes()?;
+        if let Some(tag) = self
+            .pins
+            .keys()
+            .find(|tag| !axes.iter().any(|axis| axis.axis_tag() == **tag))
+        {
+            return Err(InstancerError::UnknownAxis(*tag));
+        }
+        let avar = self.font.avar().ok();
+        let segment_maps = avar.as_ref().map(|avar| avar.axis_segment_maps());
+        let mut coords = Vec::with_capacity(axes.len());
+        let mut user = Vec::with_capacity(axes.len());
+        for (i, axis) in axes.iter().enumerate() {
+            let Some(value) = self.pins.get(&axis.axis_tag()) else {
+                coords.push(F2Dot14::ZERO);
+                user.push(None);
+                continue;
+            };
+            let value = clamp_to_axis(axis, Fixed::from_f64(*value as f64));
+            let mut coord = axis.normalize(value);
+            if let Some(map) = segment_maps
+                .as_ref()
+                .and_then(|maps| maps.get(i))
+                .transpose()?
+            {
+                coord = map.apply(coord);
+            }
+            coords.push(coord.to_f2dot14());
+            user.push(Some(value));
+        }
+        Ok(Location { coords, user })
+    }
+
+    /// Compile an `avar` table containing the maps of the remaining axes.
+    fn compile_avar(&self, location: &Location) -> Result<Vec<u8>, InstancerError> {
+        let avar = self.font.avar()?;
+        let mut segment_maps = Vec::new();
+        for (i, map) in avar.axis_segment_maps().iter().enumerate() {
+            let map = map?;
+            if location.is_pinned(i) {
+                continue;
+            }
+            let value_maps = map
+                .axis_value_maps()
+                .iter()
+                .map(|value_map| {
+                    AxisValueMap::new(value_map.from_coordinate(), value_map.to_coordinate())
+                })
+                .collect();
+            segment_maps.push(SegmentMaps::new(value_maps));
+        }
+        Ok(dump_table(&Avar::new(segment_maps))?)
+    }
+
+    /// Apply gvar deltas to the glyph outlines and metrics.
+    fn instance_glyphs(&self, location: &Location) -> Result<GlyphTables, InstancerError> {
+        let font = &self.font;
+        let num_glyphs = font.maxp()?.num_glyphs();
+        let mut head: Head = font.head()?.to_owned_table();
+        let loca = font.loca(None)?;
+        let glyf = font.glyf()?;
+        let gvar = font.gvar().ok();
+        let hmtx = font.hmtx().ok();
+        let vmtx = font.vmtx().ok();
+        let hvar = font.hvar().ok();
+        let vvar = font.vvar().ok();
+
+        let mut glyphs = Vec::with_capacity(num_glyphs as usize);
+        for gid in (0..num_glyphs).map(GlyphId::new) {
+            let glyph = loca.get_glyf(gid, &glyf)?;
+            let h_metric = hmtx
+                .as_ref()
+                .and_then(|hmtx| long_metric(hmtx.h_metrics(), hmtx.left_side_bearings(), gid));
+            let v_metric = vmtx
+                .as_ref()
+                .and_then(|vmtx| long_metric(vmtx.v_metrics(), vmtx.top_side_bearings(), gid));
+            let mut glyph = GlyphPoints::new(glyph.as_ref(), h_metric, v_metric)?;
+            if let Some(gvar) = gvar.as_ref() {
+                glyph.apply_gvar(gvar, gid, location)?;
+            } else {
+                let n_points = glyph.points.len();
+                if let Some(hvar) = hvar.as_ref() {
+                    let delta = hvar.advance_width_delta(gid, &location.coords)?;
+                    glyph.points[n_points - 3].x += delta.to_i32();
+                }
+                if let Some(vvar) = vvar.as_ref() {
+                    let delta = vvar.advance_height_delta(gid, &location.coords)?;
+                    glyph.points[n_points - 1].y -= delta.to_i32();
+                }
+            }
+            glyphs.push(glyph);
+        }
+
+        let mut builder = GlyfLocaBuilder::new();
+        let mut bbox: Option<Bbox> = None;
+        let mut h_metrics = Vec::with_capacity(glyphs.len());
+        let mut v_metrics = Vec::with_capacity(glyphs.len());
+        // (min lsb, min rsb, max extent) for glyphs with outlines
+        let mut h_extents: Option<(i16, i16, i16)> = None;
+        let mut v_extents: Option<(i16, i16, i16)> = None;
+        for (gid, glyph) in glyphs.iter().enumerate() {
+            let output = glyph.to_glyph(&glyphs, gid);
+            let glyph_bbox = output.bbox();
+            builder.add_glyph(&output);
+
+            let [pp1, pp2, pp3, pp4] = glyph.phantom_points();
+            let advance = clamp_u16(pp2.x - pp1.x);
+            let lsb = clamp_i16(glyph_bbox.map(|b| b.x_min as i32).unwrap_or(0) - pp1.x);
+            h_metrics.push(LongMetric::new(advance, lsb));
+            let v_advance = clamp_u16(pp3.y - pp4.y);
+            let tsb = clamp_i16(pp3.y - glyph_bbox.map(|b| b.y_max as i32).unwrap_or(0));
+            v_metrics.push(LongMetric::new(v_advance, tsb));
+
+            if let Some(glyph_bbox) = glyph_bbox {
+                bbox = Some(bbox.map_or(glyph_bbox, |bbox| bbox.union(glyph_bbox)));
+                let width = glyph_bbox.x_max - glyph_bbox.x_min;
+                let rsb = clamp_i16(advance as i32 - lsb as i32 - width as i32);
+                let extent = lsb.saturating_add(width);
+                h_extents = Some(update_extents(h_extents, lsb, rsb, extent));
+                let height = glyph_bbox.y_max - glyph_bbox.y_min;
+                let bsb = clamp_i16(v_advance as i32 - tsb as i32 - height as i32);
+                let extent = tsb.saturating_add(height);
+                v_extents = Some(update_extents(v_extents, tsb, bsb, extent));
+            }
+        }
+
+        let gvar = if location.is_full() {
+            None
+        } else {
+            let variations = glyphs
+                .iter_mut()
+                .enumerate()
+                .map(|(gid, glyph)| {
+                    GlyphVariations::new(
+                        GlyphId::new(gid as u16),
+                        std::mem::take(&mut glyph.variations),
+                    )
+                })
+                .collect();
+            let gvar = Gvar::new(variations, location.free_axes().count() as u16)?;
+            Some(dump_table(&gvar)?)
+        };
+
+        let (glyf, loca, loca_format) = builder.build();
+        head.set_loca_format(loca_format);
+        let bbox = bbox.unwrap_or_default();
+        head.x_min = bbox.x_min;
+        head.y_min = bbox.y_min;
+        head.x_max = bbox.x_max;
+        head.y_max = bbox.y_max;
+
+        let mut hhea = None;
+        let mut hmtx_data = None;
+        if let (Ok(hhea_table), true) = (font.hhea(), hmtx.is_some()) {
+            let mut table: Hhea = hhea_table.to_owned_table();
+            let advance_max = h_metrics.iter().map(|m| m.advance).max().unwrap_or(0);
+            let (min_lsb, min_rsb, max_extent) = h_extents.unwrap_or_default();
+            table.advance_width_max = advance_max.into();
+            table.min_left_side_bearing = min_lsb.into();
+            table.min_right_side_bearing = min_rsb.into();
+            table.x_max_extent = max_extent.into();
+            table.number_of_long_metrics = h_metrics.len() as u16;
+            hhea = Some(table);
+            hmtx_data = Some(dump_table(&Hmtx::new(h_metrics, Vec::new()))?);
+        }
+        let mut vhea = None;
+        let mut vmtx_data = None;
+        if let (Ok(vhea_table), true) = (font.vhea(), vmtx.is_some()) {
+            let mut table: Vhea = vhea_table.to_owned_table();
+            let advance_max = v_metrics.iter().map(|m| m.advance).max().unwrap_or(0);
+            let (min_tsb, min_bsb, max_extent) = v_extents.unwrap_or_default();
+            table.advance_height_max = advance_max.into();
+            table.min_top_side_bearing = min_tsb.into();
+            table.min_bottom_side_bearing = min_bsb.into();
+            table.y_max_extent = max_extent.into();
+            table.number_of_long_ver_metrics = v_metrics.len() as u16;
+            vhea = Some(table);
+            vmtx_data = Some(dump_table(&Vmtx::new(v_metrics, Vec::new()))?);
+        }
+
+        Ok(GlyphTables {
+            glyf: dump_table(&glyf)?,
+            loca: dump_table(&loca)?,
+            gvar,
+            head,
+            hhea,
+            hmtx: hmtx_data,
+            vhea,
+            vmtx: vmtx_data,
+        })
+    }
+
+    fn instance_os2(
+        &self,
+        os2: &mut Os2,
+        fvar: &Fvar,
+        location: &Location,
+        delta: &impl Fn(Tag) -> Result<i32, ReadError>,
+    ) -> Result<(), InstancerError> {
+        use mvar_tags::*;
+        os2.s_typo_ascender = add_i16(os2.s_typo_ascender, delta(HASC)?);
+        os2.s_typo_descender = add_i16(os2.s_typo_descender, delta(HDSC)?);
+        os2.s_typo_line_gap = add_i16(os2.s_typo_line_gap, delta(HLGP)?);
+        os2.us_win_ascent = add_u16(os2.us_win_ascent, delta(HCLA)?);
+        os2.us_win_descent = add_u16(os2.us_win_descent, delta(HCLD)?);
+        os2.y_subscript_x_size = add_i16(os2.y_subscript_x_size, delta(SBXS)?);
+        os2.y_subscript_y_size = add_i16(os2.y_subscript_y_size, delta(SBYS)?);
+        os2.y_subscript_x_offset = add_i16(os2.y_subscript_x_offset, delta(SBXO)?);
+        os2.y_subscript_y_offset = add_i16(os2.y_subscript_y_offset, delta(SBYO)?);
+        os2.y_superscript_x_size = add_i16(os2.y_superscript_x_size, delta(SPXS)?);
+        os2.y_superscript_y_size = add_i16(os2.y_superscript_y_size, delta(SPYS)?);
+        os2.y_superscript_x_offset = add_i16(os2.y_superscript_x_offset, delta(SPXO)?);
+        os2.y_superscript_y_offset = add_i16(os2.y_superscript_y_offset, delta(SPYO)?);
+        os2.y_strikeout_size = add_i16(os2.y_strikeout_size, delta(STRS)?);
+        os2.y_strikeout_position = add_i16(os2.y_strikeout_position, delta(STRO)?);
+        if let Some(x_height) = os2.sx_height.as_mut() {
+            *x_height = add_i16(*x_height, delta(XHGT)?);
+        }
+        if let Some(cap_height) = os2.s_cap_height.as_mut() {
+            *cap_height = add_i16(*cap_height, delta(CPHT)?);
+        }
+        if let Some(weight) = location.user_value(fvar, Tag::WGHT)? {
+            os2.us_weight_class = weight.to_f64().round().clamp(1.0, 1000.0) as u16;
+        }
+        if let Some(width) = location.user_value(fvar, Tag::WDTH)? {
+            let width = width.to_f64();
+            os2.us_width_class = WIDTH_CLASSES
+                .iter()
+                .min_by(|a, b| (a.0 - width).abs().total_cmp(&(b.0 - width).abs()))
+                .map(|(_, class)| *class)
+                .unwrap_or(5);
+        }
+        Ok(())
+    }
+}
+
+impl Location {
+    fn is_pinned(&self, axis: usize) -> bool {
+        self.user.get(axis).map(Option::is_some).unwrap_or(false)
+    }
+
+    fn is_full(&self) -> bool {
+        self.user.iter().all(Option::is_some)
+    }
+
+    /// The indices of the axes that are not pinned.
+    fn free_axes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.user
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| value.is_none().then_some(i))
+    }
+
+    /// The user value of the axis with this tag, if it is pinned.
+    fn user_value(&self, fvar: &Fvar, tag: Tag) -> Result<Option<Fixed>, ReadError> {
+        Ok(fvar
+            .axes()?
+            .iter()
+            .position(|axis| axis.axis_tag() == tag)
+            .and_then(|i| self.user[i]))
+    }
+
+    /// The scalar of this tuple for the pinned axes.
+    ///
+    /// This follows [`TupleVariation::compute_scalar`], ignoring axes that
+    /// are not pinned.
+    fn pinned_scalar(&self, tuple: &TupleVariation) -> Option<Fixed> {
+        let peak = tuple.peak();
+        let start = tuple.intermediate_start();
+        let end = tuple.intermediate_end();
+        if peak.values.len() != self.coords.len() {
+            return None;
+        }
+        let mut scalar = Fixed::ONE;
+        for (i, coord) in self.coords.iter().enumerate() {
+            if !self.is_pinned(i) {
+                continue;
+            }
+            let coord = coord.to_fixed();
+            let peak = peak.values[i].get().to_fixed();
+            if peak == Fixed::ZERO || peak == coord {
+                continue;
+            }
+            if coord == Fixed::ZERO {
+                return None;
+            }
+            if let (Some(start), Some(end)) = (&start, &end) {
+                let start = tuple_value(start.values, i).to_fixed();
+                let end = tuple_value(end.values, i).to_fixed();
+                if coord <= start || coord >= end {
+                    return None;
+                }
+                if coord < peak {
+                    scalar = scalar.mul_div(coord - start, peak - start);
+                } else {
+                    scalar = scalar.mul_div(end - coord, end - peak);
+                }
+            } else {
+                if coord < peak.min(Fixed::ZERO) || coord > peak.max(Fixed::ZERO) {
+                    return None;
+                }
+                scalar = scalar.mul_div(coord, peak);
+            }
+        }
+        Some(scalar)
+    }
+
+    /// The values of a tuple for the axes that are not pinned.
+    fn free_values(&self, values: &[BigEndian<F2Dot14>]) -> Vec<F2Dot14> {
+        self.free_axes().map(|i| tuple_value(values, i)).collect()
+    }
+}
+
+impl GlyphPoints {
+    fn new(
+        glyph: Option<&read_fonts::tables::glyf::Glyph>,
+        h_metric: Option<(u16, i16)>,
+        v_metric: Option<(u16, i16)>,
+    ) -> Result<Self, ReadError> {
+        use read_fonts::tables::glyf::Glyph as ReadGlyph;
+        let (outline, mut points) = match glyph {
+            None => (Outline::Empty, Vec::new()),
+            Some(ReadGlyph::Simple(glyph)) => {
+                let n_points = glyph.num_points();
+                let mut points = vec![Point::default(); n_points];
+                let mut flags = vec![PointFlags::default(); n_points];
+                glyph.read_points_fast(&mut points, &mut flags)?;
+                let outline = Outline::Simple {
+                    on_curve: flags.iter().map(|flag| flag.is_on_curve()).collect(),
+                    contour_ends: glyph
+                        .end_pts_of_contours()
+                        .iter()
+                        .map(|end| end.get())
+                        .collect(),
+                    instructions: glyph.instructions().to_owned(),
+                };
+                (outline, points)
+            }
+            Some(ReadGlyph::Composite(glyph)) => {
+                let mut components = Vec::new();
+                let mut points = Vec::new();
+                for component in glyph.components() {
+                    points.push(match component.anchor {
+                        Anchor::Offset { x, y } => Point::new(x as i32, y as i32),
+                        Anchor::Point { .. } => Point::default(),
+                    });
+                    components.push(ComponentInfo {
+                        glyph: component.glyph,
+                        anchor: component.anchor,
+                        transform: component.transform,
+                        flags: component.flags,
+                    });
+                }
+                let outline = Outline::Composite {
+                    components,
+                    instructions: glyph.instructions().unwrap_or_default().to_owned(),
+                };
+                (outline, points)
+            }
+        };
+
+        let (x_min, y_max) = glyph
+            .map(|glyph| (glyph.x_min() as i32, glyph.y_max() as i32))
+            .unwrap_or_default();
+        let (advance, lsb) = h_metric.unwrap_or_default();
+        let left = x_min - lsb as i32;
+        points.push(Point::new(left, 0));
+        points.push(Point::new(left + advance as i32, 0));
+        let (v_advance, tsb) = v_metric.unwrap_or_default();
+        let top = y_max + tsb as i32;
+        points.push(Point::new(0, top));
+        points.push(Point::new(0, top - v_advance as i32));
+
+        Ok(GlyphPoints {
+            outline,
+            points,
+            variations: Vec::new(),
+        })
+    }
+
+    /// Apply the deltas of the pinned axes to the points of this glyph, and
+    /// compute the variations that remain.
+    fn apply_gvar(
+        &mut self,
+        gvar: &ReadGvar,
+        gid: GlyphId,
+        location: &Location,
+    ) -> Result<(), ReadError> {
+        let Some(var_data) = gvar.glyph_variation_data(gid)? else {
+            return Ok(());
+        };
+        let n_points = self.points.len();
+        let contour_ends = match &self.outline {
+            Outline::Simple { contour_ends, .. } => contour_ends.clone(),
+            _ => Vec::new(),
+        };
+        let mut flags = vec![PointFlags::default(); n_points];
+        let mut scratch = vec![Point::default(); n_points];
+        let mut base_deltas = vec![Point::default(); n_points];
+        let remaining = self.split_tuples(
+            &var_data,
+            location,
+            &contour_ends,
+            &mut flags,
+            &mut scratch,
+            &mut base_deltas,
+        )?;
+
+        for (point, delta) in self.points.iter_mut().zip(&base_deltas) {
+            point.x = (Fixed::from_i32(point.x) + delta.x).to_i32();
+            point.y = (Fixed::from_i32(point.y) + delta.y).to_i32();
+        }
+        if let Outline::Composite { components, .. } = &mut self.outline {
+            for (component, point) in components.iter_mut().zip(&self.points) {
+                if let Anchor::Offset { x, y } = &mut component.anchor {
+                    *x = clamp_i16(point.x);
+                    *y = clamp_i16(point.y);
+                }
+            }
+        }
+
+        let points = self
+            .points
+            .iter()
+            .map(|point| Point::new(clamp_i16(point.x), clamp_i16(point.y)))
+            .collect::<Vec<_>>();
+        let contour_ends = contour_ends
+            .iter()
+            .map(|end| *end as usize)
+            .collect::<Vec<_>>();
+        for tuple in remaining {
+            let deltas = tuple
+                .deltas
+                .iter()
+                .map(|delta| Point::new(clamp_i16(delta.x.to_i32()), clamp_i16(delta.y.to_i32())))
+                .collect::<Vec<_>>();
+            if deltas.iter().all(|delta| delta.x == 0 && delta.y == 0) {
+                continue;
+            }
+            let deltas = match &self.outline {
+                Outline::Simple { .. } => {
+                    iup_delta_optimize(&deltas, &points, &contour_ends, IUP_TOLERANCE).ok()
+                }
+                _ => None,
+            }
+            .unwrap_or_else(|| {
+                deltas
+                    .iter()
+                    .map(|delta| GlyphDelta::required(delta.x, delta.y))
+                    .collect()
+            });
+            let intermediate = tuple
+                .intermediate
+                .map(|(start, end)| (Tuple::new(start), Tuple::new(end)));
+            self.variations.push(GlyphDeltas::new(
+                Tuple::new(tuple.peak),
+                deltas,
+                intermediate,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sort the tuples of this glyph into those that only depend on the
+    /// pinned axes, whose deltas are added to `base_deltas`, and those that
+    /// remain, which are scaled and merged by region.
+    fn split_tuples(
+        &self,
+        var_data: &GlyphVariationData,
+        location: &Location,
+        contour_ends: &[u16],
+        flags: &mut [PointFlags],
+        scratch: &mut [Point<Fixed>],
+        base_deltas: &mut [Point<Fixed>],
+    ) -> Result<Vec<RemainingTuple>, ReadError> {
+        let mut remaining: Vec<RemainingTuple> = Vec::new();
+        for tuple in var_data.tuples() {
+            let Some(scalar) = location.pinned_scalar(&tuple) else {
+                continue;
+            };
+            if scalar == Fixed::ZERO {
+                continue;
+            }
+            let peak = location.free_values(tuple.peak().values);
+            if peak.iter().all(|value| *value == F2Dot14::ZERO) {
+                tuple.accumulate_deltas(
+                    scalar,
+                    &self.points,
+                    contour_ends,
+                    flags,
+                    scratch,
+                    base_deltas,
+                )?;
+                continue;
+            }
+            let intermediate = tuple
+                .intermediate_start()
+                .zip(tuple.intermediate_end())
+                .map(|(start, end)| {
+                    (
+                        location.free_values(start.values),
+                        location.free_values(end.values),
+                    )
+                });
+            let mut deltas = vec![Point::default(); self.points.len()];
+            tuple.accumulate_deltas(
+                scalar,
+                &self.points,
+                contour_ends,
+                flags,
+                scratch,
+                &mut deltas,
+            )?;
+            match remaining
+                .iter_mut()
+                .find(|existing| existing.peak == peak && existing.intermediate == intermediate)
+            {
+                Some(existing) => {
+                    for (existing, delta) in existing.deltas.iter_mut().zip(deltas) {
+                        *existing += delta;
+                    }
+                }
+                None => remaining.push(RemainingTuple {
+                    peak,
+                    intermediate,
+                    deltas,
+                }),
+            }
+        }
+        Ok(remaining)
+    }
+
+    fn phantom_points(&self) -> [Point<i32>; N_PHANTOM_POINTS] {
+        let start = self.points.len() - N_PHANTOM_POINTS;
+        self.points[start..].try_into().unwrap_or_default()
+    }
+
+    fn outline_points(&self) -> &[Point<i32>] {
+        &self.points[..self.points.len() - N_PHANTOM_POINTS]
+    }
+
+    fn to_glyph(&self, glyphs: &[GlyphPoints], gid: usize) -> Glyph {
+        match &self.outline {
+            Outline::Empty => Glyph::Empty,
+            Outline::Simple {
+                on_curve,
+                contour_ends,
+                instructions,
+            } => {
+                let points = self.outline_points();
+                let mut contours = Vec::with_capacity(contour_ends.len());
+                let mut start = 0;
+                for end in contour_ends {
+                    let end = (*end as usize + 1).min(points.len());
+                    let contour = points[start..end]
+                        .iter()
+                        .zip(&on_curve[start..end])
+                        .map(|(point, on_curve)| {
+                            CurvePoint::new(clamp_i16(point.x), clamp_i16(point.y), *on_curve)
+                        })
+                        .collect::<Vec<_>>();
+                    contours.push(Contour::from(contour));
+                    start = end;
+                }
+                let mut glyph = SimpleGlyph::new(contours);
+                glyph.set_instructions(instructions.clone());
+                glyph.into()
+            }
+            Outline::Composite {
+                components,
+                instructions,
+            } => {
+                let mut points = Vec::new();
+                flatten_glyph(glyphs, gid, 0, &mut points);
+                let bbox = points
+                    .split_first()
+                    .map(|(first, rest)| {
+                        let first = kurbo::Point::new(first.x as f64, first.y as f64);
+                        rest.iter()
+                            .fold(Rect::from_points(first, first), |rect, point| {
+                                rect.union_pt(kurbo::Point::new(point.x as f64, point.y as f64))
+                            })
+                            .into()
+                    })
+                    .unwrap_or_default();
+                let mut components = components.iter().map(|component| {
+                    Component::new(
+                        component.glyph,
+                        component.anchor,
+                        component.transform.clone(),
+                        component.flags,
+                    )
+                });
+                let Some(first) = components.next() else {
+                    return Glyph::Empty;
+                };
+                let mut glyph = CompositeGlyph::new(first, bbox);
+                for component in components {
+                    glyph.add_component(component, bbox);
+                }
+                glyph.set_instructions(instructions.clone());
+                glyph.into()
+            }
+        }
+    }
+}
+
+/// Append the points of the outline of this glyph to `out`, resolving any
+/// components.
+fn flatten_glyph(glyphs: &[GlyphPoints], gid: usize, depth: usize, out: &mut Vec<Point<f32>>) {
+    let Some(glyph) = glyphs.get(gid) else {
+        return;
+    };
+    if depth > MAX_COMPONENT_DEPTH {
+        return;
+    }
+    match &glyph.outline {
+        Outline::Empty => (),
+        Outline::Simple { .. } => out.extend(
+            glyph
+                .outline_points()
+                .iter()
+                .map(|point| Point::new(point.x as f32, point.y as f32)),
+        ),
+        Outline::Composite { components, .. } => {
+            let start = out.len();
+            for component in components {
+                let mut points = Vec::new();
+                flatten_glyph(
+                    glyphs,
+                    component.glyph.to_u16() as usize,
+                    depth + 1,
+                    &mut points,
+                );
+                for point in points.iter_mut() {
+                    *point = component.transform.transform_point(*point);
+                }
+                let offset = match component.anchor {
+                    Anchor::Offset { x, y } => {
+                        let offset = Point::new(x as f32, y as f32);
+                        if is_offset_scaled(component) {
+                            component.transform.transform_point(offset)
+                        } else {
+                            offset
+                        }
+                    }
+                    Anchor::Point { base, component } => {
+                        match (
+                            out.get(start + base as usize),
+                            points.get(component as usize),
+                        ) {
+                            (Some(base), Some(component)) => *base - *component,
+                            _ => Point::default(),
+                        }
+                    }
+                };
+                out.extend(points.into_iter().map(|point| point + offset));
+            }
+        }
+    }
+}
+
+/// See [`read_fonts::tables::glyf::Component::is_offset_scaled`].
+fn is_offset_scaled(component: &ComponentInfo) -> bool {
+    component.flags.intersects(
+        CompositeGlyphFlags::WE_HAVE_A_SCALE
+            | CompositeGlyphFlags::WE_HAVE_AN_X_AND_Y_SCALE
+            | CompositeGlyphFlags::WE_HAVE_A_TWO_BY_TWO,
+    ) && component.flags
+        & (CompositeGlyphFlags::SCALED_COMPONENT_OFFSET
+            | CompositeGlyphFlags::UNSCALED_COMPONENT_OFFSET)
+        == CompositeGlyphFlags::SCALED_COMPONENT_OFFSET
+}
+
+/// Compile an `fvar` table containing the remaining axes, and the instances
+/// that are at the pinned location.
+fn compile_fvar(fvar: &Fvar, location: &Location) -> Result<Vec<u8>, InstancerError> {
+    let axes = fvar.axes()?;
+    let free_axes = location
+        .free_axes()
+        .map(|i| {
+            let axis = &axes[i];
+            FvarAxis::new(
+                axis.axis_tag(),
+                axis.min_value(),
+                axis.default_value(),
+                axis.max_value(),
+                axis.flags(),
+                axis.axis_name_id(),
+            )
+        })
+        .collect();
+    let instances = fvar
+        .instances()?
+        .iter()
+        .filter_map(|instance| instance.ok())
+        .filter(|instance| {
+            instance
+                .coordinates
+                .iter()
+                .zip(&location.user)
+                .all(|(coord, pin)| pin.map(|pin| pin == coord.get()).unwrap_or(true))
+        })
+        .map(|instance| InstanceRecord {
+            subfamily_name_id: instance.subfamily_name_id,
+            flags: instance.flags,
+            coordinates: location
+                .free_axes()
+                .map(|i| instance.coordinates[i].get())
+                .collect(),
+            post_script_name_id: instance.post_script_name_id,
+        })
+        .collect();
+    Ok(dump_table(&FvarTable::new(free_axes, instances))?)
+}
+
+/// Remove the axis values of `STAT` that do not apply at the pinned
+/// location.
+fn prune_stat(stat: &mut Stat, fvar: &Fvar, location: &Location) -> Result<(), ReadError> {
+    let axes = fvar.axes()?;
+    let pins = stat
+        .design_axes
+        .iter()
+        .map(|record| {
+            axes.iter()
+                .position(|axis| axis.axis_tag() == record.axis_tag)
+                .and_then(|i| location.user[i])
+        })
+        .collect::<Vec<_>>();
+    let pin = |axis_index: u16| pins.get(axis_index as usize).copied().flatten();
+    stat.offset_to_axis_values.retain(|value| match &**value {
+        AxisValue::Format1(value) => pin(value.axis_index).is_none_or(|pin| pin == value.value),
+        AxisValue::Format2(value) => pin(value.axis_index)
+            .is_none_or(|pin| value.range_min_value <= pin && pin <= value.range_max_value),
+        AxisValue::Format3(value) => pin(value.axis_index).is_none_or(|pin| pin == value.value),
+        AxisValue::Format4(value) => value
+            .axis_values
+            .iter()
+            .all(|record| pin(record.axis_index).is_none_or(|pin| pin == record.value)),
+    });
+    Ok(())
+}
+
+/// The delta for this MVAR metric, rounded to an integer.
+fn metric_delta(mvar: Option<&Mvar>, tag: Tag, coords: &[F2Dot14]) -> Result<i32, ReadError> {
+    let Some(mvar) = mvar else {
+        return Ok(0);
+    };
+    match mvar.metric_delta(tag, coords) {
+        Ok(delta) => Ok(delta.to_i32()),
+        Err(ReadError::MetricIsMissing(_)) => Ok(0),
+        Err(e) => Err(e),
+    }
+}
+
+/// The advance and side bearing of a glyph in `hmtx` or `vmtx`.
+fn long_metric(
+    metrics: &[ReadLongMetric],
+    bearings: &[BigEndian<i16>],
+    gid: GlyphId,
+) -> Option<(u16, i16)> {
+    let idx = gid.to_u16() as usize;
+    let last = metrics.last()?;
+    match metrics.get(idx) {
+        Some(metric) => Some((metric.advance(), metric.side_bearing())),
+        None => bearings
+            .get(idx - metrics.len())
+            .map(|bearing| (last.advance(), bearing.get())),
+    }
+}
+
+fn update_extents(
+    extents: Option<(i16, i16, i16)>,
+    min_start: i16,
+    min_end: i16,
+    max_extent: i16,
+) -> (i16, i16, i16) {
+    match extents {
+        Some((start, end, extent)) => (
+            start.min(min_start),
+            end.min(min_end),
+            extent.max(max_extent),
+        ),
+        None => (min_start, min_end, max_extent),
+    }
+}
+
+fn clamp_to_axis(axis: &VariationAxisRecord, value: Fixed) -> Fixed {
+    let min = axis.min_value();
+    value.clamp(min, axis.max_value().max(min))
+}
+
+fn tuple_value(values: &[BigEndian<F2Dot14>], i: usize) -> F2Dot14 {
+    values.get(i).map(|value| value.get()).unwrap_or_default()
+}
+
+fn add_i16(value: i16, delta: i32) -> i16 {
+    clamp_i16(value as i32 + delta)
+}
+
+fn add_u16(value: u16, delta: i32) -> u16 {
+    clamp_u16(value as i32 + delta)
+}
+
+fn clamp_i16(value: i32) -> i16 {
+    value.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+}
+
+fn clamp_u16(value: i32) -> u16 {
+    value.clamp(0, u16::MAX as i32) as u16
+}
+
+impl From<ReadError> for InstancerError {
+    fn from(value: ReadError) -> Self {
+        InstancerError::Read(value)
+    }
+}
+
+impl From<GvarInputError> for InstancerError {
+    fn from(value: GvarInputError) -> Self {
+        InstancerError::Gvar(value)
+    }
+}
+
+impl From<crate::Error> for InstancerError {
+    fn from(value: crate::Error) -> Self {
+        InstancerError::Write(value)
+    }
+}
+
+impl Display for InstancerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstancerError::NotVariable => write!(f, "font is not variable"),
+            InstancerError::UnknownAxis(tag) => write!(f, "font has no '{tag}' axis"),
+            InstancerError::UnsupportedTable(tag) => {
+                write!(f, "instancing the '{tag}' table is not supported")
+            }
+            InstancerError::Read(error) => write!(f, "failed to read font: {error}"),
+            InstancerError::Gvar(error) => write!(f, "failed to compile gvar: {error:?}"),
+            InstancerError::Write(error) => write!(f, "failed to write font: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for InstancerError {}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::{test_data::test_fonts, types::Tag};
+
+    use super::*;
+
+    /// The points of a glyph, including phantom points, and the coordinates
+    /// of its components.
+    fn glyph_points(font: &FontRef, gid: GlyphId) -> Vec<Point<i32>> {
+        let glyph = font
+            .loca(None)
+            .unwrap()
+            .get_glyf(gid, &font.glyf().unwrap())
+            .unwrap();
+        let glyph = GlyphPoints::new(glyph.as_ref(), None, None).unwrap();
+        glyph.outline_points().to_owned()
+    }
+
+    #[test]
+    fn full_instance() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let instance = Instancer::new(font.clone())
+            .pin_axis(Tag::WGHT, 700.0)
+            .instantiate()
+            .unwrap();
+        let instance = FontRef::new(&instance).unwrap();
+        for tag in [Tag::FVAR, Tag::AVAR, Tag::GVAR, Tag::HVAR] {
+            assert!(!instance.has_table(tag), "{tag}");
+        }
+        assert!(instance.has_table(Tag::STAT));
+
+        let avar = font.avar().unwrap();
+        let wght = font.fvar().unwrap().axes().unwrap()[0].normalize(Fixed::from_i32(700));
+        let coords = [avar
+            .axis_segment_maps()
+            .get(0)
+            .unwrap()
+            .unwrap()
+            .apply(wght)
+            .to_f2dot14()];
+        let gvar = font.gvar().unwrap();
+        let glyf = font.glyf().unwrap();
+        let loca = font.loca(None).unwrap();
+        let mut n_varied = 0;
+        for gid in (0..font.maxp().unwrap().num_glyphs()).map(GlyphId::new) {
+            let mut expected = glyph_points(&font, gid);
+            if let (Some(var_data), Some(glyph)) = (
+                gvar.glyph_variation_data(gid).unwrap(),
+                loca.get_glyf(gid, &glyf).unwrap(),
+            ) {
+                let h_metric = font
+                    .hmtx()
+                    .ok()
+                    .map(|hmtx| (hmtx.advance(gid).unwrap(), hmtx.side_bearing(gid).unwrap()));
+                let glyph = GlyphPoints::new(Some(&glyph), h_metric, None).unwrap();
+                let contours = match &glyph.outline {
+                    Outline::Simple { contour_ends, .. } => contour_ends.clone(),
+                    _ => Vec::new(),
+                };
+                let n = glyph.points.len();
+                let mut flags = vec![PointFlags::default(); n];
+                let mut scratch = vec![Point::default(); n];
+                let mut deltas = vec![Point::default(); n];
+                var_data
+                    .compute_deltas(
+                        &coords,
+                        &glyph.points,
+                        &contours,
+                        &mut flags,
+                        &mut scratch,
+                        &mut deltas,
+                    )
+                    .unwrap();
+                for (point, delta) in expected.iter_mut().zip(&deltas) {
+                    point.x = (Fixed::from_i32(point.x) + delta.x).to_i32();
+                    point.y = (Fixed::from_i32(point.y) + delta.y).to_i32();
+                }
+                let advance = font.advance_width(gid, &coords).unwrap().to_f64();
+                let instanced = instance.hmtx().unwrap().advance(gid).unwrap() as f64;
+                assert!((advance - instanced).abs() <= 1.0, "{gid}");
+                n_varied += 1;
+            }
+            assert_eq!(glyph_points(&instance, gid), expected, "{gid}");
+        }
+        assert!(n_varied > 0);
+    }
+
+    #[test]
+    fn pin_nothing() {
+        // with no pins, the font is rebuilt with the same variations
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let instance = Instancer::new(font.clone()).instantiate().unwrap();
+        let instance = FontRef::new(&instance).unwrap();
+
+        let fvar = instance.fvar().unwrap();
+        assert_eq!(fvar.axis_count(), 1);
+        assert_eq!(fvar.axes().unwrap()[0].axis_tag(), Tag::WGHT);
+        assert_eq!(
+            fvar.instances().unwrap().len(),
+            font.fvar().unwrap().instances().unwrap().len()
+        );
+        assert_eq!(
+            instance.table_data(Tag::AVAR).unwrap().as_ref(),
+            font.table_data(Tag::AVAR).unwrap().as_ref()
+        );
+
+        let coords = [F2Dot14::from_f32(0.6)];
+        for gid in (0..font.maxp().unwrap().num_glyphs()).map(GlyphId::new) {
+            let expected = font.advance_width(gid, &coords).unwrap().to_f64();
+            let points = instanced_points(&instance, gid, &coords);
+            let [pp1, pp2] = [points[points.len() - 4], points[points.len() - 3]];
+            let advance = (pp2.x - pp1.x) as f64;
+            assert!((advance - expected).abs() <= 1.0, "{gid}");
+        }
+    }
+
+    #[test]
+    fn unknown_axis() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let result = Instancer::new(font)
+            .pin_axis(Tag::new(b"wdth"), 100.0)
+            .instantiate();
+        assert!(matches!(
+            result,
+            Err(InstancerError::UnknownAxis(tag)) if tag == Tag::WDTH
+        ));
+    }
+
+    #[test]
+    fn not_variable() {
+        let font = FontRef::new(test_fonts::SIMPLE_GLYF).unwrap();
+        assert!(matches!(
+            Instancer::new(font).instantiate(),
+            Err(InstancerError::NotVariable)
+        ));
+    }
+
+    /// The points of a glyph in `font`, varied by its gvar deltas.
+    fn instanced_points(font: &FontRef, gid: GlyphId, coords: &[F2Dot14]) -> Vec<Point<i32>> {
+        let glyph = font
+            .loca(None)
+            .unwrap()
+            .get_glyf(gid, &font.glyf().unwrap())
+            .unwrap();
+        let hmtx = font.hmtx().unwrap();
+        let h_metric = (hmtx.advance(gid).unwrap(), hmtx.side_bearing(gid).unwrap());
+        let mut glyph = GlyphPoints::new(glyph.as_ref(), Some(h_metric), None).unwrap();
+        let location = Location {
+            coords: coords.to_owned(),
+            user: vec![Some(Fixed::ZERO); coords.len()],
+        };
+        glyph
+            .apply_gvar(&font.gvar().unwrap(), gid, &location)
+            .unwrap();
+        glyph.points
+    }
+}
@@ -1,22 +1,37 @@
 //! Raw types for compiling opentype tables
 
+pub mod cleanup;
 mod collections;
+pub mod dedup;
+pub mod error;
+pub mod fea;
 mod font_builder;
 pub mod from_obj;
+pub mod gid_mapper;
 mod graph;
+pub mod instancer;
+pub mod merge;
 mod offsets;
+pub mod round_trip;
 pub mod tables;
 pub mod validate;
+#[cfg(feature = "woff")]
+pub mod woff;
 mod write;
 
+#[cfg(all(test, feature = "arbitrary"))]
+#[path = "tests/arbitrary.rs"]
+mod arbitrary_tests;
 #[cfg(test)]
 mod codegen_test;
 #[cfg(test)]
 mod hex_diff;
 
-pub use font_builder::FontBuilder;
+pub use error::Error;
+pub use font_builder::{splice_table, CollectionBuilder, EditError, FontBuilder};
+pub use graph::{GraphDump, GraphEdge, GraphNode, OffsetLen};
 pub use offsets::{NullableOffsetMarker, OffsetMarker};
-pub use write::{dump_table, FontWrite, TableWriter};
+pub use write::{dump_table, dump_table_graph, FontWrite, TableType, TableWriter};
 
 /// Rexport of the common font types
 pub extern crate font_types as types;
@@ -30,7 +45,7 @@ pub(crate) mod codegen_prelude {
     pub use super::from_obj::{FromObjRef, FromTableRef, ToOwnedObj, ToOwnedTable};
     pub use super::offsets::{NullableOffsetMarker, OffsetMarker, WIDTH_16, WIDTH_24, WIDTH_32};
     pub use super::validate::{Validate, ValidationCtx};
-    pub use super::write::{FontWrite, TableWriter};
+    pub use super::write::{FontWrite, TableType, TableWriter};
     pub use std::collections::BTreeSet;
     pub use types::*;
 
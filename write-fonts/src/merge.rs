@@ -0,0 +1,907 @@
+//! Merging multiple fonts into one.
+//!
+//! A [`FontMerger`] combines the glyphs of several fonts, for instance a
+//! Latin font and a CJK companion, into a single font. The glyphs of each font
+//! are appended in order, so glyph ids in the first font are unchanged and
+//! glyph ids in later fonts are offset by the number of glyphs before them.
+//!
+//! The `glyf`, `loca`, `hmtx`, `vmtx` and `cmap` tables are combined, and the
+//! `head`, `hhea`, `vhea`, `maxp`, `OS/2` and `post` tables are updated to
+//! cover all of the glyphs. Other tables are taken from the first font that
+//! contains them. Tables that refer to glyph ids, such as `GSUB`, `GPOS` and
+//! `GDEF`, are only kept from the first font; if a later font contains one of
+//! these tables it is dropped, and a [`MergeConflict`] is reported.
+//!
+//! Only static fonts with TrueType outlines can be merged.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::Display;
+use std::ops::Range;
+
+use read_fonts::{
+    tables::{cmap::CmapSubtable, glyf::Glyph as ReadGlyph},
+    FontData, FontRef, ReadError, TableProvider,
+};
+use types::{GlyphId, Tag};
+
+use crate::{
+    dump_table,
+    from_obj::ToOwnedTable,
+    tables::{
+        cmap::CmapBuilder,
+        glyf::{Bbox, Component, CompositeGlyph, GlyfLocaBuilder, Glyph},
+        head::Head,
+        hhea::Hhea,
+        hmtx::{Hmtx, LongMetric},
+        maxp::Maxp,
+        os2::Os2,
+        post::PostBuilder,
+        vhea::Vhea,
+        vmtx::Vmtx,
+    },
+    validate::Validate,
+    FontBuilder, FontWrite,
+};
+
+/// Tables that are rebuilt from all of the input fonts.
+const MERGED_TABLES: [Tag; 11] = [
+    Tag::CMAP,
+    Tag::GLYF,
+    Tag::HEAD,
+    Tag::HHEA,
+    Tag::HMTX,
+    Tag::LOCA,
+    Tag::MAXP,
+    Tag::OS2,
+    Tag::POST,
+    Tag::VHEA,
+    Tag::VMTX,
+];
+
+/// Tables that contain glyph ids, and so can only be copied from the first
+/// font.
+pub(crate) const GLYPH_DEPENDENT_TABLES: [Tag; 17] = [
+    Tag::BASE,
+    Tag::CBDT,
+    Tag::CBLC,
+    Tag::COLR,
+    Tag::EBDT,
+    Tag::EBLC,
+    Tag::EBSC,
+    Tag::GDEF,
+    Tag::GPOS,
+    Tag::GSUB,
+    Tag::JSTF,
+    Tag::LTSH,
+    Tag::MATH,
+    Tag::VORG,
+    Tag::HDMX,
+    Tag::KERN,
+    Tag::SBIX,
+];
+
+/// Combines multiple fonts into one.
+///
+/// ```no_run
+/// # let (latin, cjk): (&[u8], &[u8]) = (&[], &[]);
+/// use write_fonts::{merge::FontMerger, read::FontRef};
+///
+/// let merged = FontMerger::new()
+///     .add_font(FontRef::new(latin).unwrap())
+///     .add_font(FontRef::new(cjk).unwrap())
+///     .merge()
+///     .unwrap();
+/// for conflict in &merged.conflicts {
+///     eprintln!("{conflict}");
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct FontMerger<'a> {
+    fonts: Vec<FontRef<'a>>,
+}
+
+/// The result of merging fonts.
+#[derive(Clone, Debug)]
+pub struct MergedFont {
+    /// The binary data of the merged font.
+    pub data: Vec<u8>,
+    /// Data from the input fonts that could not be merged.
+    pub conflicts: Vec<MergeConflict>,
+    /// The range of glyph ids used by each input font.
+    glyph_ranges: Vec<Range<u16>>,
+}
+
+/// Data from an input font that is not included in the merged font.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MergeConflict {
+    /// A codepoint is mapped by more than one font; the mapping from the
+    /// first font is used.
+    DuplicateCodepoint { codepoint: u32, font: usize },
+    /// A variation sequence is mapped by more than one font; the mapping from
+    /// the first font is used.
+    DuplicateVariationSequence {
+        base: u32,
+        selector: u32,
+        font: usize,
+    },
+    /// A table in this font could not be merged, and was dropped.
+    DroppedTable { tag: Tag, font: usize },
+}
+
+/// An error that prevents fonts from being merged.
+#[derive(Debug)]
+pub enum MergeError {
+    /// No fonts were provided.
+    NoFonts,
+    /// A font does not have the same units per em as the first font.
+    UnitsPerEmMismatch {
+        font: usize,
+        units_per_em: u16,
+        expected: u16,
+    },
+    /// A font is variable; variable fonts cannot be merged.
+    VariableFont(usize),
+    /// A font is missing a required table.
+    ///
+    /// In particular, this is returned for fonts that do not have TrueType
+    /// outlines.
+    MissingTable { font: usize, tag: Tag },
+    /// The merged font would have more than 65535 glyphs.
+    TooManyGlyphs,
+    /// A table in an input font could not be read.
+    Read { font: usize, error: ReadError },
+    /// A table in the merged font could not be written.
+    Write(crate::Error),
+}
+
+impl<'a> FontMerger<'a> {
+    /// Create a new, empty merger.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a font; fonts are merged in the order they are added.
+    pub fn add_font(&mut self, font: FontRef<'a>) -> &mut Self {
+        self.fonts.push(font);
+        self
+    }
+
+    /// Merge the fonts.
+    pub fn merge(&self) -> Result<MergedFont, MergeError> {
+        let first = self.fonts.first().ok_or(MergeError::NoFonts)?;
+        let units_per_em = read(0, first.head())?.units_per_em();
+        let mut glyph_ranges = Vec::with_capacity(self.fonts.len());
+        let mut start = 0u32;
+        for (i, font) in self.fonts.iter().enumerate() {
+            if font.is_variable() {
+                return Err(MergeError::VariableFont(i));
+            }
+            for tag in [
+                Tag::GLYF,
+                Tag::LOCA,
+                Tag::HEAD,
+                Tag::MAXP,
+                Tag::HHEA,
+                Tag::HMTX,
+            ] {
+                if !font.has_table(tag) {
+                    return Err(MergeError::MissingTable { font: i, tag });
+                }
+            }
+            let font_upem = read(i, font.head())?.units_per_em();
+            if font_upem != units_per_em {
+                return Err(MergeError::UnitsPerEmMismatch {
+                    font: i,
+                    units_per_em: font_upem,
+                    expected: units_per_em,
+                });
+            }
+            let end = start + read(i, font.maxp())?.num_glyphs() as u32;
+            if end > u16::MAX as u32 {
+                return Err(MergeError::TooManyGlyphs);
+            }
+            glyph_ranges.push(start as u16..end as u16);
+            start = end;
+        }
+
+        let mut merge = Merge {
+            fonts: &self.fonts,
+            glyph_ranges: &glyph_ranges,
+            conflicts: Vec::new(),
+            tables: BTreeMap::new(),
+        };
+        merge.merge_glyphs()?;
+        merge.merge_metrics()?;
+        let codepoints = merge.merge_cmap()?;
+        merge.merge_os2(&codepoints)?;
+        merge.merge_post()?;
+        merge.copy_other_tables();
+
+        let mut builder = FontBuilder::default();
+        for (tag, data) in merge.tables {
+            builder.add_table(tag, data);
+        }
+        Ok(MergedFont {
+            data: builder.build(),
+            conflicts: merge.conflicts,
+            glyph_ranges,
+        })
+    }
+}
+
+impl MergedFont {
+    /// The id in the merged font of a glyph from one of the input fonts.
+    ///
+    /// Returns `None` if `font` is not the index of an input font, or if the
+    /// glyph id is out of range for that font.
+    pub fn glyph_id(&self, font: usize, gid: GlyphId) -> Option<GlyphId> {
+        let range = self.glyph_ranges.get(font)?;
+        let new_gid = range.start.checked_add(gid.to_u16())?;
+        range.contains(&new_gid).then(|| GlyphId::new(new_gid))
+    }
+}
+
+/// The state of a merge in progress.
+struct Merge<'a, 'b> {
+    fonts: &'b [FontRef<'a>],
+    glyph_ranges: &'b [Range<u16>],
+    conflicts: Vec<MergeConflict>,
+    tables: BTreeMap<Tag, Vec<u8>>,
+}
+
+impl Merge<'_, '_> {
+    fn new_gid(&self, font: usize, gid: GlyphId) -> GlyphId {
+        GlyphId::new(self.glyph_ranges[font].start.wrapping_add(gid.to_u16()))
+    }
+
+    fn glyph_ids(&self, font: usize) -> impl Iterator<Item = GlyphId> {
+        let range = &self.glyph_ranges[font];
+        (0..range.end - range.start).map(GlyphId::new)
+    }
+
+    fn add_table(
+        &mut self,
+        tag: Tag,
+        table: &(impl FontWrite + Validate),
+    ) -> Result<(), MergeError> {
+        let data = dump_table(table).map_err(MergeError::Write)?;
+        self.tables.insert(tag, data);
+        Ok(())
+    }
+
+    fn drop_table(&mut self, tag: Tag, font: usize) {
+        self.conflicts
+            .push(MergeConflict::DroppedTable { tag, font });
+    }
+
+    /// Combine `glyf`, `loca`, `maxp` and `head`.
+    fn merge_glyphs(&mut self) -> Result<(), MergeError> {
+        let mut builder = GlyfLocaBuilder::new();
+        for (i, font) in self.fonts.iter().enumerate() {
+            let loca = read(i, font.loca(None))?;
+            let glyf = read(i, font.glyf())?;
+            for gid in self.glyph_ids(i) {
+                let glyph = match read(i, loca.get_glyf(gid, &glyf))? {
+                    None => Glyph::Empty,
+                    Some(ReadGlyph::Simple(glyph)) => Glyph::Simple(glyph.to_owned_table()),
+                    Some(ReadGlyph::Composite(glyph)) => {
+                        let bbox = Bbox {
+                            x_min: glyph.x_min(),
+                            y_min: glyph.y_min(),
+                            x_max: glyph.x_max(),
+                            y_max: glyph.y_max(),
+                        };
+                        let mut components = glyph.components().map(|component| {
+                            Component::new(
+                                self.new_gid(i, component.glyph),
+                                component.anchor,
+                                component.transform,
+                                component.flags,
+                            )
+                        });
+                        let Some(first) = components.next() else {
+                            builder.add_glyph(&Glyph::Empty);
+                            continue;
+                        };
+                        let mut new_glyph = CompositeGlyph::new(first, bbox);
+                        for component in components {
+                            new_glyph.add_component(component, bbox);
+                        }
+                        new_glyph
+                            .set_instructions(glyph.instructions().unwrap_or_default().to_owned());
+                        Glyph::Composite(new_glyph)
+                    }
+                };
+                builder.add_glyph(&glyph);
+            }
+        }
+        let (glyf, loca, loca_format) = builder.build();
+        self.add_table(Tag::GLYF, &glyf)?;
+        self.add_table(Tag::LOCA, &loca)?;
+
+        let mut head: Head = read(0, self.fonts[0].head())?.to_owned_table();
+        head.set_loca_format(loca_format);
+        let mut maxp: Maxp = read(0, self.fonts[0].maxp())?.to_owned_table();
+        maxp.num_glyphs = self.glyph_ranges.last().map(|r| r.end).unwrap_or(0);
+        for (i, font) in self.fonts.iter().enumerate().skip(1) {
+            let font_head = read(i, font.head())?;
+            head.x_min = head.x_min.min(font_head.x_min());
+            head.y_min = head.y_min.min(font_head.y_min());
+            head.x_max = head.x_max.max(font_head.x_max());
+            head.y_max = head.y_max.max(font_head.y_max());
+
+            let font_maxp: Maxp = read(i, font.maxp())?.to_owned_table();
+            for (field, other) in [
+                (&mut maxp.max_points, font_maxp.max_points),
+                (&mut maxp.max_contours, font_maxp.max_contours),
+                (
+                    &mut maxp.max_composite_points,
+                    font_maxp.max_composite_points,
+                ),
+                (
+                    &mut maxp.max_composite_contours,
+                    font_maxp.max_composite_contours,
+                ),
+                (&mut maxp.max_zones, font_maxp.max_zones),
+                (&mut maxp.max_twilight_points, font_maxp.max_twilight_points),
+                (&mut maxp.max_storage, font_maxp.max_storage),
+                (&mut maxp.max_function_defs, font_maxp.max_function_defs),
+                (
+                    &mut maxp.max_instruction_defs,
+                    font_maxp.max_instruction_defs,
+                ),
+                (&mut maxp.max_stack_elements, font_maxp.max_stack_elements),
+                (
+                    &mut maxp.max_size_of_instructions,
+                    font_maxp.max_size_of_instructions,
+                ),
+                (
+                    &mut maxp.max_component_elements,
+                    font_maxp.max_component_elements,
+                ),
+                (&mut maxp.max_component_depth, font_maxp.max_component_depth),
+            ] {
+                if let (Some(field), Some(other)) = (field, other) {
+                    *field = (*field).max(other);
+                }
+            }
+        }
+        self.add_table(Tag::HEAD, &head)?;
+        self.add_table(Tag::MAXP, &maxp)
+    }
+
+    /// Combine `hmtx` and `hhea`, and `vmtx` and `vhea` if every font has them.
+    fn merge_metrics(&mut self) -> Result<(), MergeError> {
+        let mut hhea: Hhea = read(0, self.fonts[0].hhea())?.to_owned_table();
+        let mut h_metrics = Vec::new();
+        for (i, font) in self.fonts.iter().enumerate() {
+            let hmtx = read(i, font.hmtx())?;
+            for gid in self.glyph_ids(i) {
+                let advance = hmtx.advance(gid).unwrap_or_default();
+                let lsb = hmtx.side_bearing(gid).unwrap_or_default();
+                h_metrics.push(LongMetric::new(advance, lsb));
+            }
+            if i > 0 {
+                let font_hhea = read(i, font.hhea())?;
+                hhea.ascender = hhea.ascender.max(font_hhea.ascender());
+                hhea.descender = hhea.descender.min(font_hhea.descender());
+                hhea.line_gap = hhea.line_gap.max(font_hhea.line_gap());
+                hhea.advance_width_max = hhea.advance_width_max.max(font_hhea.advance_width_max());
+                hhea.min_left_side_bearing = hhea
+                    .min_left_side_bearing
+                    .min(font_hhea.min_left_side_bearing());
+                hhea.min_right_side_bearing = hhea
+                    .min_right_side_bearing
+                    .min(font_hhea.min_right_side_bearing());
+                hhea.x_max_extent = hhea.x_max_extent.max(font_hhea.x_max_extent());
+            }
+        }
+        hhea.number_of_long_metrics = h_metrics.len() as u16;
+        self.add_table(Tag::HHEA, &hhea)?;
+        self.add_table(Tag::HMTX, &Hmtx::new(h_metrics, Vec::new()))?;
+
+        let has_vertical = |font: &FontRef| font.has_table(Tag::VHEA) && font.has_table(Tag::VMTX);
+        if !self.fonts.iter().any(has_vertical) {
+            return Ok(());
+        }
+        if !self.fonts.iter().all(has_vertical) {
+            for (i, font) in self.fonts.iter().enumerate() {
+                for tag in [Tag::VHEA, Tag::VMTX] {
+                    if font.has_table(tag) {
+                        self.drop_table(tag, i);
+                    }
+                }
+            }
+            return Ok(());
+        }
+        let mut vhea: Vhea = read(0, self.fonts[0].vhea())?.to_owned_table();
+        let mut v_metrics = Vec::new();
+        for (i, font) in self.fonts.iter().enumerate() {
+            let vmtx = read(i, font.vmtx())?;
+            let metrics = vmtx.v_metrics();
+            let bearings = vmtx.top_side_bearings();
+            for gid in self.glyph_ids(i) {
+                let idx = gid.to_u16() as usize;
+                let advance = metrics
+                    .get(idx)
+                    .or(metrics.last())
+                    .map(|metric| metric.advance())
+                    .unwrap_or_default();
+                let tsb = match metrics.get(idx) {
+                    Some(metric) => metric.side_bearing(),
+                    None => bearings
+                        .get(idx - metrics.len())
+                        .map(|tsb| tsb.get())
+                        .unwrap_or_default(),
+                };
+                v_metrics.push(LongMetric::new(advance, tsb));
+            }
+            if i > 0 {
+                let font_vhea = read(i, font.vhea())?;
+                vhea.ascender = vhea.ascender.max(font_vhea.ascender());
+                vhea.descender = vhea.descender.min(font_vhea.descender());
+                vhea.line_gap = vhea.line_gap.max(font_vhea.line_gap());
+                vhea.advance_height_max =
+                    vhea.advance_height_max.max(font_vhea.advance_height_max());
+                vhea.min_top_side_bearing = vhea
+                    .min_top_side_bearing
+                    .min(font_vhea.min_top_side_bearing());
+                vhea.min_bottom_side_bearing = vhea
+                    .min_bottom_side_bearing
+                    .min(font_vhea.min_bottom_side_bearing());
+                vhea.y_max_extent = vhea.y_max_extent.max(font_vhea.y_max_extent());
+            }
+        }
+        vhea.number_of_long_ver_metrics = v_metrics.len() as u16;
+        self.add_table(Tag::VHEA, &vhea)?;
+        self.add_table(Tag::VMTX, &Vmtx::new(v_metrics, Vec::new()))
+    }
+
+    /// Combine the `cmap` tables, returning the mapped codepoints.
+    fn merge_cmap(&mut self) -> Result<BTreeSet<char>, MergeError> {
+        let mut mappings = BTreeMap::new();
+        let mut sequences = HashSet::new();
+        let mut builder = CmapBuilder::default();
+        for (i, font) in self.fonts.iter().enumerate() {
+            let Ok(cmap) = font.cmap() else {
+                continue;
+            };
+            let mut font_mappings = BTreeMap::new();
+            let mut uvs = None;
+            for record in cmap.encoding_records() {
+                match read(i, record.subtable(cmap.offset_data()))? {
+                    CmapSubtable::Format4(subtable) => {
+                        for (codepoint, gid) in subtable.iter() {
+                            font_mappings.entry(codepoint).or_insert(gid);
+                        }
+                    }
+                    CmapSubtable::Format12(subtable) => {
+                        for (codepoint, gid) in subtable.iter() {
+                            font_mappings.entry(codepoint).or_insert(gid);
+                        }
+                    }
+                    CmapSubtable::Format14(subtable) => uvs = Some(subtable),
+                    _ => (),
+                }
+            }
+            for (codepoint, gid) in &font_mappings {
+                let Some(chr) = char::from_u32(*codepoint) else {
+                    continue;
+                };
+                if mappings.contains_key(&chr) {
+                    self.conflicts.push(MergeConflict::DuplicateCodepoint {
+                        codepoint: *codepoint,
+                        font: i,
+                    });
+                    continue;
+                }
+                let gid = self.new_gid(i, *gid);
+                mappings.insert(chr, gid);
+                builder.add_mapping(chr, gid);
+            }
+
+            let Some(uvs) = uvs else {
+                continue;
+            };
+            let data = uvs.offset_data();
+            for selector in uvs.var_selector() {
+                let selector_value = u32::from(selector.var_selector());
+                let mut font_sequences = Vec::new();
+                if let Some(default_uvs) = read(i, selector.default_uvs(data).transpose())? {
+                    for range in default_uvs.ranges() {
+                        let start = u32::from(range.start_unicode_value());
+                        for base in start..=start + range.additional_count() as u32 {
+                            if let Some(gid) = font_mappings.get(&base) {
+                                font_sequences.push((base, *gid));
+                            }
+                        }
+                    }
+                }
+                if let Some(non_default_uvs) = read(i, selector.non_default_uvs(data).transpose())?
+                {
+                    for mapping in non_default_uvs.uvs_mapping() {
+                        let gid = GlyphId::new(mapping.glyph_id());
+                        font_sequences.push((u32::from(mapping.unicode_value()), gid));
+                    }
+                }
+                for (base, gid) in font_sequences {
+                    let (Some(base_chr), Some(selector_chr)) =
+                        (char::from_u32(base), char::from_u32(selector_value))
+                    else {
+                        continue;
+                    };
+                    if !sequences.insert((base, selector_value)) {
+                        self.conflicts
+                            .push(MergeConflict::DuplicateVariationSequence {
+                                base,
+                                selector: selector_value,
+                                font: i,
+                            });
+                        continue;
+                    }
+                    builder.add_variation_sequence(base_chr, selector_chr, self.new_gid(i, gid));
+                }
+            }
+        }
+        if !mappings.is_empty() {
+            self.add_table(Tag::CMAP, &builder.build())?;
+        }
+        Ok(mappings.into_keys().collect())
+    }
+
+    /// Update `OS/2` from the first font to cover the glyphs of all fonts.
+    fn merge_os2(&mut self, codepoints: &BTreeSet<char>) -> Result<(), MergeError> {
+        let Ok(first) = self.fonts[0].os2() else {
+            return Ok(());
+        };
+        let mut os2: Os2 = first.to_owned_table();
+        for font in &self.fonts[1..] {
+            let Ok(other) = font.os2() else {
+                continue;
+            };
+            os2.ul_unicode_range_1 |= other.ul_unicode_range_1();
+            os2.ul_unicode_range_2 |= other.ul_unicode_range_2();
+            os2.ul_unicode_range_3 |= other.ul_unicode_range_3();
+            os2.ul_unicode_range_4 |= other.ul_unicode_range_4();
+            for (field, other) in [
+                (&mut os2.ul_code_page_range_1, other.ul_code_page_range_1()),
+                (&mut os2.ul_code_page_range_2, other.ul_code_page_range_2()),
+            ] {
+                if let (Some(field), Some(other)) = (field, other) {
+                    *field |= other;
+                }
+            }
+            os2.s_typo_ascender = os2.s_typo_ascender.max(other.s_typo_ascender());
+            os2.s_typo_descender = os2.s_typo_descender.min(other.s_typo_descender());
+            os2.s_typo_line_gap = os2.s_typo_line_gap.max(other.s_typo_line_gap());
+            os2.us_win_ascent = os2.us_win_ascent.max(other.us_win_ascent());
+            os2.us_win_descent = os2.us_win_descent.max(other.us_win_descent());
+            if let (Some(max_context), Some(other)) =
+                (os2.us_max_context.as_mut(), other.us_max_context())
+            {
+                // contextual lookups are only kept from the first font, but
+                // this is harmless if it is too large
+                *max_context = (*max_context).max(other);
+            }
+        }
+        let clamp = |chr: &char| (*chr as u32).min(0xFFFF) as u16;
+        os2.us_first_char_index = codepoints.first().map(clamp).unwrap_or(0);
+        os2.us_last_char_index = codepoints.last().map(clamp).unwrap_or(0);
+        self.add_table(Tag::OS2, &os2)
+    }
+
+    /// Combine the glyph names in `post`, if every font has them.
+    fn merge_post(&mut self) -> Result<(), MergeError> {
+        let Ok(first) = self.fonts[0].post() else {
+            return Ok(());
+        };
+        let mut names = Vec::new();
+        let mut seen = HashSet::new();
+        let mut has_names = true;
+        'fonts: for (i, font) in self.fonts.iter().enumerate() {
+            let Ok(post) = font.post() else {
+                has_names = false;
+                break;
+            };
+            for gid in self.glyph_ids(i) {
+                let Some(name) = post.glyph_name(gid) else {
+                    has_names = false;
+                    break 'fonts;
+                };
+                let mut unique = name.to_owned();
+                let mut n = 1;
+                while !seen.insert(unique.clone()) {
+                    unique = format!("{name}.{n}");
+                    n += 1;
+                }
+                names.push(unique);
+            }
+        }
+
+        let mut post = PostBuilder::new();
+        post.italic_angle(first.italic_angle())
+            .underline(first.underline_position(), first.underline_thickness())
+            .fixed_pitch(first.is_fixed_pitch() != 0);
+        if has_names {
+            for name in names {
+                post.add_glyph_name(name);
+            }
+        }
+        let post = post.build();
+        self.add_table(Tag::POST, &post)
+    }
+
+    /// Copy the remaining tables from the first font that has them.
+    fn copy_other_tables(&mut self) {
+        let mut sources: BTreeMap<Tag, FontData> = BTreeMap::new();
+        for (i, font) in self.fonts.iter().enumerate() {
+            for tag in font.table_tags() {
+                if MERGED_TABLES.contains(&tag) || tag == Tag::DSIG {
+                    continue;
+                }
+                let Some(data) = font.table_data(tag) else {
+                    continue;
+                };
+                match sources.get(&tag) {
+                    // identical copies of a table are not a conflict
+                    Some(existing) if existing.as_ref() == data.as_ref() => (),
+                    Some(_) => self.drop_table(tag, i),
+                    None if i > 0 && GLYPH_DEPENDENT_TABLES.contains(&tag) => {
+                        self.drop_table(tag, i)
+                    }
+                    None => {
+                        sources.insert(tag, data);
+                    }
+                }
+            }
+        }
+        for (tag, data) in sources {
+            self.tables.insert(tag, data.as_ref().to_owned());
+        }
+    }
+}
+
+fn read<T>(font: usize, result: Result<T, ReadError>) -> Result<T, MergeError> {
+    result.map_err(|error| MergeError::Read { font, error })
+}
+
+impl From<crate::Error> for MergeError {
+    fn from(value: crate::Error) -> Self {
+        MergeError::Write(value)
+    }
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeConflict::DuplicateCodepoint { codepoint, font } => write!(
+                f,
+                "font {font}: U+{codepoint:04X} is already mapped by an earlier font"
+            ),
+            MergeConflict::DuplicateVariationSequence {
+                base,
+                selector,
+                font,
+            } => write!(
+                f,
+                "font {font}: variation sequence U+{base:04X} U+{selector:04X} is already mapped by an earlier font"
+            ),
+            MergeConflict::DroppedTable { tag, font } => {
+                write!(f, "font {font}: the '{tag}' table could not be merged")
+            }
+        }
+    }
+}
+
+impl Display for MergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeError::NoFonts => write!(f, "no fonts to merge"),
+            MergeError::UnitsPerEmMismatch {
+                font,
+                units_per_em,
+                expected,
+            } => write!(
+                f,
+                "font {font} has {units_per_em} units per em, expected {expected}"
+            ),
+            MergeError::VariableFont(font) => {
+                write!(f, "font {font} is variable, which is not supported")
+            }
+            MergeError::MissingTable { font, tag } => {
+                write!(f, "font {font} is missing the required '{tag}' table")
+            }
+            MergeError::TooManyGlyphs => write!(f, "the merged font has too many glyphs"),
+            MergeError::Read { font, error } => write!(f, "failed to read font {font}: {error}"),
+            MergeError::Write(error) => write!(f, "failed to write merged font: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::{
+        tables::glyf::{Anchor, CompositeGlyphFlags},
+        test_data::test_fonts,
+    };
+
+    use super::*;
+    use crate::tables::glyf::{Contour, CurvePoint, SimpleGlyph, Transform};
+
+    fn square(size: i16) -> Glyph {
+        let contour = Contour::from(vec![
+            CurvePoint::on_curve(0, 0),
+            CurvePoint::on_curve(0, size),
+            CurvePoint::on_curve(size, size),
+            CurvePoint::on_curve(size, 0),
+        ]);
+        SimpleGlyph::new(vec![contour]).into()
+    }
+
+    /// A minimal font with these glyphs; glyph `i` is mapped to the `i`th
+    /// codepoint in `chars`, starting from glyph 1.
+    fn make_font(units_per_em: u16, glyphs: &[Glyph], chars: &[char]) -> Vec<u8> {
+        let mut builder = GlyfLocaBuilder::new();
+        for glyph in glyphs {
+            builder.add_glyph(glyph);
+        }
+        let (glyf, loca, format) = builder.build();
+        let mut head = Head {
+            units_per_em,
+            ..Default::default()
+        };
+        head.set_loca_format(format);
+        let metrics = glyphs
+            .iter()
+            .map(|glyph| LongMetric::new(500, glyph.bbox().unwrap_or_default().x_min))
+            .collect::<Vec<_>>();
+        let hhea = Hhea {
+            number_of_long_metrics: metrics.len() as u16,
+            ..Default::default()
+        };
+        let cmap = CmapBuilder::new(
+            chars
+                .iter()
+                .enumerate()
+                .map(|(i, chr)| (*chr, GlyphId::new(i as u16 + 1)))
+                .collect(),
+        )
+        .build();
+        let mut builder = FontBuilder::default();
+        builder
+            .add_table(Tag::GLYF, dump_table(&glyf).unwrap())
+            .add_table(Tag::LOCA, dump_table(&loca).unwrap())
+            .add_table(Tag::HEAD, dump_table(&head).unwrap())
+            .add_table(
+                Tag::MAXP,
+                dump_table(&Maxp::new(glyphs.len() as u16)).unwrap(),
+            )
+            .add_table(Tag::HHEA, dump_table(&hhea).unwrap())
+            .add_table(
+                Tag::HMTX,
+                dump_table(&Hmtx::new(metrics, Vec::new())).unwrap(),
+            )
+            .add_table(Tag::CMAP, dump_table(&cmap).unwrap());
+        builder.build()
+    }
+
+    #[test]
+    fn merge_two_fonts() {
+        let latin = make_font(1000, &[Glyph::Empty, square(100)], &['a']);
+        let composite = CompositeGlyph::new(
+            Component::new(
+                GlyphId::new(1),
+                Anchor::Offset { x: 10, y: 20 },
+                Transform::default(),
+                CompositeGlyphFlags::empty(),
+            ),
+            Default::default(),
+        );
+        let cjk = make_font(
+            1000,
+            &[Glyph::Empty, square(200), composite.into()],
+            &['a', '中'],
+        );
+        let merged = FontMerger::new()
+            .add_font(FontRef::new(&latin).unwrap())
+            .add_font(FontRef::new(&cjk).unwrap())
+            .merge()
+            .unwrap();
+        assert_eq!(
+            merged.conflicts,
+            [MergeConflict::DuplicateCodepoint {
+                codepoint: 'a' as u32,
+                font: 1
+            }]
+        );
+        assert_eq!(merged.glyph_id(1, GlyphId::new(2)), Some(GlyphId::new(4)));
+        assert_eq!(merged.glyph_id(1, GlyphId::new(3)), None);
+
+        let font = FontRef::new(&merged.data).unwrap();
+        assert_eq!(font.maxp().unwrap().num_glyphs(), 5);
+        let cmap = font.cmap().unwrap();
+        assert_eq!(cmap.map_codepoint('a'), Some(GlyphId::new(1)));
+        assert_eq!(cmap.map_codepoint('中'), Some(GlyphId::new(4)));
+
+        let loca = font.loca(None).unwrap();
+        let glyf = font.glyf().unwrap();
+        let Some(ReadGlyph::Composite(glyph)) = loca.get_glyf(GlyphId::new(4), &glyf).unwrap()
+        else {
+            panic!("expected a composite glyph");
+        };
+        let component = glyph.components().next().unwrap();
+        assert_eq!(component.glyph, GlyphId::new(3));
+        let Some(ReadGlyph::Simple(glyph)) = loca.get_glyf(GlyphId::new(3), &glyf).unwrap() else {
+            panic!("expected a simple glyph");
+        };
+        assert_eq!(glyph.x_max(), 200);
+        assert_eq!(font.hmtx().unwrap().h_metrics().len(), 5);
+    }
+
+    #[test]
+    fn merge_layout_tables() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        assert!(matches!(
+            FontMerger::new().add_font(font).merge(),
+            Err(MergeError::VariableFont(0))
+        ));
+
+        // glyph-dependent tables are only kept from the first font
+        let with_gdef = |font: &[u8], gdef: Vec<u8>| {
+            let font = FontRef::new(font).unwrap();
+            let mut builder = FontBuilder::default();
+            for tag in font.table_tags() {
+                builder.add_table(tag, font.table_data(tag).unwrap().as_ref().to_owned());
+            }
+            builder.add_table(Tag::new(b"GDEF"), gdef);
+            builder.build()
+        };
+        let first_gdef = vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let first = with_gdef(
+            &make_font(1000, &[Glyph::Empty, square(10)], &['a']),
+            first_gdef.clone(),
+        );
+        let second = with_gdef(
+            &make_font(1000, &[Glyph::Empty], &[]),
+            vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+        );
+        let merged = FontMerger::new()
+            .add_font(FontRef::new(&first).unwrap())
+            .add_font(FontRef::new(&second).unwrap())
+            .merge()
+            .unwrap();
+        assert_eq!(
+            merged.conflicts,
+            [MergeConflict::DroppedTable {
+                tag: Tag::new(b"GDEF"),
+                font: 1
+            }]
+        );
+        let font = FontRef::new(&merged.data).unwrap();
+        assert_eq!(
+            font.table_data(Tag::new(b"GDEF")).unwrap().as_ref(),
+            first_gdef.as_slice()
+        );
+        assert_eq!(font.maxp().unwrap().num_glyphs(), 3);
+    }
+
+    #[test]
+    fn units_per_em_mismatch() {
+        let a = make_font(1000, &[Glyph::Empty], &[]);
+        let b = make_font(2048, &[Glyph::Empty], &[]);
+        let result = FontMerger::new()
+            .add_font(FontRef::new(&a).unwrap())
+            .add_font(FontRef::new(&b).unwrap())
+            .merge();
+        assert!(matches!(
+            result,
+            Err(MergeError::UnitsPerEmMismatch {
+                font: 1,
+                units_per_em: 2048,
+                expected: 1000
+            })
+        ));
+    }
+}
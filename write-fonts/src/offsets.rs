@@ -5,7 +5,6 @@ use super::write::{FontWrite, TableWriter};
 /// The width in bytes of an Offset16
 pub const WIDTH_16: usize = 2;
 /// The width in bytes of an Offset24
-#[allow(dead_code)] // will be used one day :')
 pub const WIDTH_24: usize = 3;
 /// The width in bytes of an Offset32
 pub const WIDTH_32: usize = 4;
@@ -13,9 +12,13 @@ pub const WIDTH_32: usize = 4;
 /// An offset subtable.
 ///
 /// The generic const `N` is the width of the offset, in bytes.
+///
+/// The subtable is boxed, so that tables can (indirectly) contain themselves,
+/// as in the paint graphs of the COLR table.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct OffsetMarker<T, const N: usize = WIDTH_16> {
-    obj: T,
+    obj: Box<T>,
     //error: Option<Box<ReadError>>,
 }
 
@@ -23,6 +26,7 @@ pub struct OffsetMarker<T, const N: usize = WIDTH_16> {
 ///
 /// The generic const `N` is the width of the offset, in bytes.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct NullableOffsetMarker<T, const N: usize = WIDTH_16> {
     obj: Option<T>,
 }
@@ -71,18 +75,18 @@ impl<T, const N: usize> AsMut<T> for OffsetMarker<T, N> {
 impl<const N: usize, T> OffsetMarker<T, N> {
     /// Create a new marker.
     pub fn new(obj: T) -> Self {
-        OffsetMarker { obj }
+        OffsetMarker { obj: Box::new(obj) }
     }
 
     //FIXME: maybe we get rid of this when we have From/Into impl'd?
     /// Set the contents of the marker, replacing any existing contents.
     pub fn set(&mut self, obj: T) {
-        self.obj = obj;
+        *self.obj = obj;
     }
 
     /// Convert into the inner type
     pub fn into_inner(self) -> T {
-        self.obj
+        *self.obj
     }
 }
 
@@ -105,7 +109,7 @@ impl<const N: usize, T> NullableOffsetMarker<T, N> {
 
 impl<const N: usize, T: FontWrite> FontWrite for OffsetMarker<T, N> {
     fn write_into(&self, writer: &mut TableWriter) {
-        writer.write_offset(&self.obj, N);
+        writer.write_offset(self.obj.as_ref(), N);
     }
 }
 
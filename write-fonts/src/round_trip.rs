@@ -0,0 +1,272 @@
+//! Checking that tables survive a round trip through the compile types.
+//!
+//! Each table in a font that has a compile type is parsed, converted to that
+//! type, serialized, and parsed again. The two parsed tables are then
+//! compared by their contents, with offsets resolved, so that differences in
+//! layout (such as the order of subtables, or sharing) are not reported.
+
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+};
+
+use read_fonts::{
+    tables::{self as read_tables},
+    traversal::SomeTable,
+    FontData, FontRef, ReadError, TableProvider, TopLevelTable,
+};
+use types::Tag;
+
+use crate::{from_obj::ToOwnedTable, tables, write::dump_table};
+
+/// The result of round-tripping a single table.
+#[derive(Debug)]
+pub struct TableReport {
+    /// The tag of the table.
+    pub tag: Tag,
+    /// What happened to it.
+    pub status: TableStatus,
+}
+
+/// The outcome of round-tripping a table.
+#[derive(Debug)]
+pub enum TableStatus {
+    /// The table read back is the same as the original.
+    Equal,
+    /// This table has no compile type.
+    Unsupported,
+    /// The original table could not be parsed.
+    ReadFailed(ReadError),
+    /// Converting or serializing the table panicked.
+    ///
+    /// This usually means that the table contains a subtable that the
+    /// compile types do not support; it contains the panic message.
+    Panicked(String),
+    /// The compiled table could not be serialized.
+    WriteFailed(crate::Error),
+    /// The serialized table could not be parsed.
+    ReparseFailed(ReadError),
+    /// The table read back differs from the original.
+    ///
+    /// This contains the first line of the debug representation of each
+    /// table that differs.
+    Mismatch {
+        line: usize,
+        expected: String,
+        found: String,
+    },
+}
+
+impl TableReport {
+    /// `true` if the table was round-tripped, and is unchanged.
+    pub fn is_equal(&self) -> bool {
+        matches!(self.status, TableStatus::Equal)
+    }
+
+    /// `true` if the table was read, but could not be written and read back
+    /// unchanged.
+    ///
+    /// Tables that are unsupported, or that could not be read from the
+    /// original font, are not failures.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self.status,
+            TableStatus::Panicked(_)
+                | TableStatus::WriteFailed(_)
+                | TableStatus::ReparseFailed(_)
+                | TableStatus::Mismatch { .. }
+        )
+    }
+}
+
+/// Round-trip each table in the font, in table directory order.
+///
+/// A panic while round-tripping one table is reported as
+/// [`TableStatus::Panicked`] for that table, and does not stop the others
+/// from being checked.
+pub fn round_trip(font: &FontRef) -> Vec<TableReport> {
+    font.table_tags()
+        .map(|tag| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| round_trip_table(font, tag)));
+            let status = match result {
+                Ok(Ok(())) => TableStatus::Equal,
+                Ok(Err(status)) => status,
+                Err(payload) => TableStatus::Panicked(panic_message(payload)),
+            };
+            TableReport { tag, status }
+        })
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Round-trip each table in the font, and panic if any of them changed.
+///
+/// The panic message lists each table that failed.
+pub fn assert_round_trip(font: &FontRef) {
+    let failures = round_trip(font)
+        .into_iter()
+        .filter(TableReport::is_failure)
+        .map(|report| format!("{}: {:?}", report.tag, report.status))
+        .collect::<Vec<_>>();
+    if !failures.is_empty() {
+        panic!("round trip failed:\n{}", failures.join("\n"));
+    }
+}
+
+/// A font with the data of one table replaced.
+struct Replaced<'a, 'b> {
+    font: &'b FontRef<'a>,
+    tag: Tag,
+    data: FontData<'b>,
+}
+
+impl<'b> TableProvider<'b> for Replaced<'_, 'b> {
+    fn data_for_tag(&self, tag: Tag) -> Option<FontData<'b>> {
+        if tag == self.tag {
+            Some(self.data)
+        } else {
+            self.font.data_for_tag(tag)
+        }
+    }
+}
+
+/// For each supported table, its read module, its type, and the method of
+/// `TableProvider` that reads it.
+macro_rules! round_trip_tables {
+    ($font:ident, $tag:ident, $( $module:ident :: $typ:ident => $read:ident $(::<$arg:ty>)? ),* $(,)?) => {
+        match $tag {
+            $(
+                read_tables::$module::$typ::TAG => {
+                    let original = $font.$read$(::<$arg>)?().map_err(TableStatus::ReadFailed)?;
+                    let owned: tables::$module::$typ = original.to_owned_table();
+                    let bytes = dump_table(&owned).map_err(TableStatus::WriteFailed)?;
+                    let replaced = Replaced {
+                        font: $font,
+                        tag: $tag,
+                        data: FontData::new(&bytes),
+                    };
+                    let reparsed = replaced.$read$(::<$arg>)?().map_err(TableStatus::ReparseFailed)?;
+                    compare(&original, &reparsed)
+                }
+            )*
+            _ => Err(TableStatus::Unsupported),
+        }
+    };
+}
+
+fn round_trip_table(font: &FontRef, tag: Tag) -> Result<(), TableStatus> {
+    round_trip_tables!(font, tag,
+        base::Base => expect_table::<read_tables::base::Base>,
+        cmap::Cmap => cmap,
+        colr::Colr => colr,
+        cpal::Cpal => cpal,
+        gdef::Gdef => gdef,
+        gpos::Gpos => gpos,
+        gsub::Gsub => gsub,
+        head::Head => head,
+        hhea::Hhea => hhea,
+        hmtx::Hmtx => hmtx,
+        hvar::Hvar => hvar,
+        maxp::Maxp => maxp,
+        mvar::Mvar => mvar,
+        name::Name => name,
+        os2::Os2 => os2,
+        post::Post => post,
+        stat::Stat => stat,
+        vhea::Vhea => vhea,
+        vmtx::Vmtx => vmtx,
+    )
+}
+
+fn compare<'a, 'b>(
+    original: &(dyn SomeTable<'a> + 'a),
+    reparsed: &(dyn SomeTable<'b> + 'b),
+) -> Result<(), TableStatus> {
+    let expected = format!("{original:#?}");
+    let found = format!("{reparsed:#?}");
+    let expected = expected.lines().map(str::trim).collect::<Vec<_>>();
+    let found = found.lines().map(str::trim).collect::<Vec<_>>();
+    match (0..expected.len().max(found.len())).find(|i| expected.get(*i) != found.get(*i)) {
+        None => Ok(()),
+        Some(line) => Err(TableStatus::Mismatch {
+            line,
+            expected: expected.get(line).copied().unwrap_or_default().to_owned(),
+            found: found.get(line).copied().unwrap_or_default().to_owned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::test_data::test_fonts;
+    use types::GlyphId;
+
+    use super::*;
+    use crate::FontBuilder;
+
+    #[test]
+    fn test_fonts() {
+        for data in [
+            test_fonts::VAZIRMATN_VAR,
+            test_fonts::SIMPLE_GLYF,
+            test_fonts::COLR_GRADIENT_RECT,
+        ] {
+            assert_round_trip(&FontRef::new(data).unwrap());
+        }
+    }
+
+    #[test]
+    fn reports() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let reports = round_trip(&font);
+        assert_eq!(reports.len(), font.table_tags().count());
+        let status = |tag| {
+            &reports
+                .iter()
+                .find(|report| report.tag == tag)
+                .unwrap()
+                .status
+        };
+        assert!(matches!(status(Tag::new(b"HVAR")), TableStatus::Equal));
+        assert!(matches!(
+            status(Tag::new(b"glyf")),
+            TableStatus::Unsupported
+        ));
+    }
+
+    #[test]
+    fn cmap_format_2() {
+        // a single subHeader for one-byte codes, mapping 'A'..='C'
+        let mut sub_header_data = Vec::new();
+        for value in [0x41u16, 3, 0, 2, 1, 2, 3] {
+            sub_header_data.extend(value.to_be_bytes());
+        }
+        let format_2 = tables::cmap::CmapSubtable::format_2(0, vec![0; 256], sub_header_data);
+        let mut cmap = tables::cmap::Cmap::from_mappings(
+            [('A', GlyphId::new(1)), ('B', GlyphId::new(2))]
+                .into_iter()
+                .collect(),
+        );
+        cmap.encoding_records.insert(
+            0,
+            tables::cmap::EncodingRecord::new(tables::cmap::PlatformId::Macintosh, 0, format_2),
+        );
+        let data = FontBuilder::default()
+            .add_typed_table(&cmap)
+            .unwrap()
+            .build();
+        let font = FontRef::new(&data).unwrap();
+        let reports = round_trip(&font);
+        assert_eq!(reports.len(), 1);
+        assert!(matches!(reports[0].status, TableStatus::Equal));
+    }
+}
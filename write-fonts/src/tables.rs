@@ -1,18 +1,34 @@
 //! A directory of all the font tables.
 
+pub mod avar;
 pub mod base;
+pub mod cbdt;
+pub mod cblc;
+pub mod cff2;
+pub mod cmap;
+pub mod colr;
+pub mod cpal;
+pub mod cvar;
+pub mod fvar;
 pub mod gdef;
 pub mod glyf;
 pub mod gpos;
 pub mod gsub;
+pub mod gvar;
 pub mod head;
 pub mod hhea;
 pub mod hmtx;
+pub mod hvar;
+pub mod kern;
 pub mod layout;
+pub mod loca;
+pub mod math;
 pub mod maxp;
+pub mod mvar;
 pub mod name;
 pub mod os2;
 pub mod post;
+pub mod sbix;
 pub mod stat;
 pub mod variations;
 pub mod vhea;
@@ -0,0 +1,203 @@
+//! The [avar (Axis Variations)](https://docs.microsoft.com/en-us/typography/opentype/spec/avar) table
+
+use read_fonts::TopLevelTable;
+use types::{F2Dot14, MajorMinor, Tag};
+
+use crate::{
+    validate::{Validate, ValidationCtx},
+    write::TableType,
+    FontWrite, TableWriter,
+};
+
+/// The [avar] table.
+///
+/// [avar]: https://docs.microsoft.com/en-us/typography/opentype/spec/avar
+#[derive(Clone, Debug, Default)]
+pub struct Avar {
+    /// The segment maps array — one segment map for each axis, in the order of axes specified in the 'fvar' table.
+    pub axis_segment_maps: Vec<SegmentMaps>,
+}
+
+/// The mapping of normalized coordinates for a single axis.
+///
+/// Every segment map must include the mappings -1 → -1, 0 → 0 and 1 → 1,
+/// and the mappings must be in order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SegmentMaps {
+    /// The array of axis value map records for this axis.
+    pub axis_value_maps: Vec<AxisValueMap>,
+}
+
+/// A single mapping of a normalized coordinate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AxisValueMap {
+    /// A normalized coordinate value obtained using default normalization.
+    pub from_coordinate: F2Dot14,
+    /// The modified, normalized coordinate value.
+    pub to_coordinate: F2Dot14,
+}
+
+impl Avar {
+    /// Construct a new `Avar`
+    pub fn new(axis_segment_maps: Vec<SegmentMaps>) -> Self {
+        Avar { axis_segment_maps }
+    }
+}
+
+impl SegmentMaps {
+    /// Construct a new `SegmentMaps`
+    pub fn new(axis_value_maps: Vec<AxisValueMap>) -> Self {
+        SegmentMaps { axis_value_maps }
+    }
+
+    /// The segment map for an axis whose coordinates are not modified.
+    pub fn identity() -> Self {
+        Self::from_f32_pairs([(-1.0, -1.0), (0.0, 0.0), (1.0, 1.0)])
+    }
+
+    /// Create a segment map from pairs of `(from, to)` normalized coordinates.
+    ///
+    /// The required -1, 0 and 1 mappings are not added automatically.
+    pub fn from_f32_pairs(pairs: impl IntoIterator<Item = (f32, f32)>) -> Self {
+        Self::new(
+            pairs
+                .into_iter()
+                .map(|(from, to)| AxisValueMap::new(F2Dot14::from_f32(from), F2Dot14::from_f32(to)))
+                .collect(),
+        )
+    }
+}
+
+impl AxisValueMap {
+    /// Construct a new `AxisValueMap`
+    pub fn new(from_coordinate: F2Dot14, to_coordinate: F2Dot14) -> Self {
+        AxisValueMap {
+            from_coordinate,
+            to_coordinate,
+        }
+    }
+}
+
+impl TopLevelTable for Avar {
+    const TAG: Tag = Tag::AVAR;
+}
+
+impl FontWrite for Avar {
+    fn write_into(&self, writer: &mut TableWriter) {
+        MajorMinor::VERSION_1_0.write_into(writer);
+        // reserved
+        0u16.write_into(writer);
+        (self.axis_segment_maps.len() as u16).write_into(writer);
+        self.axis_segment_maps.write_into(writer);
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
+}
+
+impl FontWrite for SegmentMaps {
+    fn write_into(&self, writer: &mut TableWriter) {
+        (self.axis_value_maps.len() as u16).write_into(writer);
+        self.axis_value_maps.write_into(writer);
+    }
+}
+
+impl FontWrite for AxisValueMap {
+    fn write_into(&self, writer: &mut TableWriter) {
+        self.from_coordinate.write_into(writer);
+        self.to_coordinate.write_into(writer);
+    }
+}
+
+impl Validate for Avar {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        ctx.in_table("avar", |ctx| {
+            ctx.in_field("axis_segment_maps", |ctx| {
+                if self.axis_segment_maps.len() > u16::MAX as usize {
+                    ctx.report("too many segment maps");
+                }
+                ctx.in_array(|ctx| {
+                    for map in &self.axis_segment_maps {
+                        ctx.array_item(|ctx| map.validate_impl(ctx))
+                    }
+                })
+            })
+        })
+    }
+}
+
+impl Validate for SegmentMaps {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        ctx.in_field("axis_value_maps", |ctx| {
+            let maps = &self.axis_value_maps;
+            if maps.len() > u16::MAX as usize {
+                ctx.report("too many axis value maps");
+            }
+            for value in [-1.0, 0.0, 1.0].map(F2Dot14::from_f32) {
+                if !maps.contains(&AxisValueMap::new(value, value)) {
+                    ctx.report(format!("missing required mapping {value} -> {value}"));
+                }
+            }
+            for pair in maps.windows(2) {
+                if pair[0].from_coordinate >= pair[1].from_coordinate {
+                    ctx.report("from coordinates must be in increasing order");
+                }
+                if pair[0].to_coordinate > pair[1].to_coordinate {
+                    ctx.report("to coordinates must not decrease");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::{FontData, FontRead};
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let avar = Avar::new(vec![
+            SegmentMaps::identity(),
+            SegmentMaps::from_f32_pairs([(-1.0, -1.0), (0.0, 0.0), (0.5, 0.25), (1.0, 1.0)]),
+        ]);
+        let bytes = crate::dump_table(&avar).unwrap();
+        let read = read_fonts::tables::avar::Avar::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read.axis_count(), 2);
+        let maps = read
+            .axis_segment_maps()
+            .iter()
+            .map(|map| {
+                map.unwrap()
+                    .axis_value_maps()
+                    .iter()
+                    .map(|map| (map.from_coordinate(), map.to_coordinate()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(maps[0].len(), 3);
+        assert_eq!(
+            maps[1][2],
+            (F2Dot14::from_f32(0.5), F2Dot14::from_f32(0.25))
+        );
+    }
+
+    #[test]
+    fn validation() {
+        let missing_default = SegmentMaps::from_f32_pairs([(-1.0, -1.0), (1.0, 1.0)]);
+        assert!(crate::dump_table(&Avar::new(vec![missing_default])).is_err());
+        let unordered = SegmentMaps::from_f32_pairs([
+            (-1.0, -1.0),
+            (0.0, 0.0),
+            (0.5, 0.5),
+            (0.25, 0.75),
+            (1.0, 1.0),
+        ]);
+        assert!(crate::dump_table(&Avar::new(vec![unordered])).is_err());
+        let decreasing =
+            SegmentMaps::from_f32_pairs([(-1.0, -1.0), (0.0, 0.0), (0.5, -0.5), (1.0, 1.0)]);
+        assert!(crate::dump_table(&Avar::new(vec![decreasing])).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 //! The [BASE](https://learn.microsoft.com/en-us/typography/opentype/spec/base) table
 
-use super::layout::Device;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::layout::{DeltaFormat, Device};
 use super::variations::ItemVariationStore;
 
 include!("../../generated/generated_base.rs");
@@ -14,3 +16,395 @@ impl Base {
         }
     }
 }
+
+/// The direction of the baselines in a [`Base`] table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BaseAxis {
+    /// Baselines for horizontal text, stored in the `HorizAxis` table.
+    Horizontal,
+    /// Baselines for vertical text, stored in the `VertAxis` table.
+    Vertical,
+}
+
+/// A builder for the BASE table.
+///
+/// Baseline positions are collected per script and direction. The baseline
+/// tag list of each direction is the sorted set of all the baseline tags used
+/// by its scripts, and every script that has baselines must provide a
+/// position for each of them.
+#[derive(Clone, Debug, Default)]
+pub struct BaseBuilder {
+    scripts: BTreeMap<(BaseAxis, Tag), ScriptValues>,
+    var_store: Option<ItemVariationStore>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ScriptValues {
+    default_baseline: Option<Tag>,
+    coords: BTreeMap<Tag, BaseCoord>,
+    // the default min/max is stored with no language
+    min_max: BTreeMap<Option<Tag>, (Option<BaseCoord>, Option<BaseCoord>)>,
+}
+
+/// An error in the input to [`BaseBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BaseInputError {
+    /// A script's default baseline is not one of its baselines.
+    UnknownDefaultBaseline { script: Tag, baseline: Tag },
+    /// A script has no position for a baseline used by another script with
+    /// the same direction.
+    MissingBaseline { script: Tag, baseline: Tag },
+    /// A coordinate refers to variation data that is not in the variation
+    /// store.
+    MissingVariationData { script: Tag, outer: u16, inner: u16 },
+}
+
+impl BaseBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the baseline positions of a script.
+    ///
+    /// The `default_baseline` is the baseline that the script's glyphs are
+    /// designed on, and must be one of the tags in `coords`.
+    pub fn set_baselines(
+        &mut self,
+        axis: BaseAxis,
+        script: Tag,
+        default_baseline: Tag,
+        coords: impl IntoIterator<Item = (Tag, BaseCoord)>,
+    ) {
+        let values = self.scripts.entry((axis, script)).or_default();
+        values.default_baseline = Some(default_baseline);
+        values.coords = coords.into_iter().collect();
+    }
+
+    /// Set the minimum and maximum extents of a script, or of one of its
+    /// languages.
+    ///
+    /// If `language` is `None`, this sets the default extents of the script.
+    pub fn set_min_max(
+        &mut self,
+        axis: BaseAxis,
+        script: Tag,
+        language: Option<Tag>,
+        min: Option<BaseCoord>,
+        max: Option<BaseCoord>,
+    ) {
+        self.scripts
+            .entry((axis, script))
+            .or_default()
+            .min_max
+            .insert(language, (min, max));
+    }
+
+    /// Set the item variation store, for variable coordinates.
+    ///
+    /// Variable coordinates are [`BaseCoordFormat3`] tables whose device
+    /// table is a `VariationIndex`.
+    pub fn set_var_store(&mut self, var_store: ItemVariationStore) {
+        self.var_store = Some(var_store);
+    }
+
+    /// Build the table.
+    pub fn build(self) -> Result<Base, BaseInputError> {
+        self.validate()?;
+        let mut base = Base::new(
+            self.build_axis(BaseAxis::Horizontal),
+            self.build_axis(BaseAxis::Vertical),
+        );
+        base.item_var_store = self.var_store.into();
+        Ok(base)
+    }
+
+    fn build_axis(&self, axis: BaseAxis) -> Option<Axis> {
+        let scripts = self
+            .scripts
+            .iter()
+            .filter(|((script_axis, _), _)| *script_axis == axis)
+            .map(|((_, script), values)| (*script, values))
+            .collect::<Vec<_>>();
+        if scripts.is_empty() {
+            return None;
+        }
+        let tags = self.baseline_tags(axis);
+        let records = scripts
+            .into_iter()
+            .map(|(script, values)| {
+                let base_values = values.default_baseline.map(|default| {
+                    let default_index = tags.iter().position(|tag| *tag == default);
+                    BaseValues::new(
+                        default_index.unwrap_or_default() as u16,
+                        tags.iter().map(|tag| values.coords[tag].clone()).collect(),
+                    )
+                });
+                let mut min_max = values.min_max.clone();
+                let default_min_max = min_max
+                    .remove(&None)
+                    .map(|(min, max)| MinMax::new(min, max, Vec::new()));
+                let lang_sys_records = min_max
+                    .into_iter()
+                    .filter_map(|(lang, (min, max))| {
+                        Some(BaseLangSysRecord::new(
+                            lang?,
+                            MinMax::new(min, max, Vec::new()),
+                        ))
+                    })
+                    .collect();
+                BaseScriptRecord::new(
+                    script,
+                    BaseScript::new(base_values, default_min_max, lang_sys_records),
+                )
+            })
+            .collect();
+        let tag_list = (!tags.is_empty()).then(|| BaseTagList::new(tags));
+        Some(Axis::new(tag_list, BaseScriptList::new(records)))
+    }
+
+    /// The sorted baseline tags used by the scripts of one direction.
+    fn baseline_tags(&self, axis: BaseAxis) -> Vec<Tag> {
+        self.scripts
+            .iter()
+            .filter(|((script_axis, _), _)| *script_axis == axis)
+            .flat_map(|(_, values)| values.coords.keys().copied())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn validate(&self) -> Result<(), BaseInputError> {
+        for ((axis, script), values) in &self.scripts {
+            let script = *script;
+            if let Some(default) = values.default_baseline {
+                if !values.coords.contains_key(&default) {
+                    return Err(BaseInputError::UnknownDefaultBaseline {
+                        script,
+                        baseline: default,
+                    });
+                }
+                if let Some(baseline) = self
+                    .baseline_tags(*axis)
+                    .into_iter()
+                    .find(|tag| !values.coords.contains_key(tag))
+                {
+                    return Err(BaseInputError::MissingBaseline { script, baseline });
+                }
+            }
+            let all_coords = values.coords.values().chain(
+                values
+                    .min_max
+                    .values()
+                    .flat_map(|(min, max)| min.iter().chain(max.iter())),
+            );
+            for coord in all_coords {
+                let BaseCoord::Format3(coord) = coord else {
+                    continue;
+                };
+                let Some(device) = coord.device.as_ref() else {
+                    continue;
+                };
+                if device.delta_format != DeltaFormat::VariationIndex {
+                    continue;
+                }
+                // a VariationIndex table has the same layout as a Device
+                let (outer, inner) = (device.start_size, device.end_size);
+                let exists = self
+                    .var_store
+                    .as_ref()
+                    .and_then(|store| store.item_variation_datas.get(outer as usize))
+                    .and_then(|data| data.as_ref())
+                    .is_some_and(|data| inner < data.item_count);
+                if !exists {
+                    return Err(BaseInputError::MissingVariationData {
+                        script,
+                        outer,
+                        inner,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for BaseInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaseInputError::UnknownDefaultBaseline { script, baseline } => write!(
+                f,
+                "default baseline '{baseline}' of script '{script}' has no position"
+            ),
+            BaseInputError::MissingBaseline { script, baseline } => {
+                write!(f, "script '{script}' has no position for baseline '{baseline}'")
+            }
+            BaseInputError::MissingVariationData {
+                script,
+                outer,
+                inner,
+            } => write!(
+                f,
+                "coordinate of script '{script}' uses variation data {outer}/{inner}, which is not in the variation store"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BaseInputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::{layout::VariationIndex, variations::ItemVariationData};
+
+    const HANG: Tag = Tag::new(b"hang");
+    const IDEO: Tag = Tag::new(b"ideo");
+    const ROMN: Tag = Tag::new(b"romn");
+    const DEVA: Tag = Tag::new(b"deva");
+    const LATN: Tag = Tag::new(b"latn");
+
+    fn coords<const N: usize>(coords: [(Tag, i16); N]) -> Vec<(Tag, BaseCoord)> {
+        coords
+            .into_iter()
+            .map(|(tag, coord)| (tag, BaseCoord::format_1(coord)))
+            .collect()
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = BaseBuilder::new();
+        builder.set_baselines(
+            BaseAxis::Horizontal,
+            LATN,
+            ROMN,
+            coords([(ROMN, 0), (IDEO, -120), (HANG, 700)]),
+        );
+        builder.set_baselines(
+            BaseAxis::Horizontal,
+            DEVA,
+            HANG,
+            coords([(HANG, 650), (ROMN, 0), (IDEO, -120)]),
+        );
+        builder.set_min_max(
+            BaseAxis::Horizontal,
+            LATN,
+            None,
+            Some(BaseCoord::format_1(-250)),
+            Some(BaseCoord::format_1(900)),
+        );
+        builder.set_min_max(
+            BaseAxis::Horizontal,
+            LATN,
+            Some(Tag::new(b"VIT ")),
+            None,
+            Some(BaseCoord::format_1(1100)),
+        );
+        let base = builder.build().unwrap();
+        assert!(base.vert_axis.is_none());
+
+        let dumped = crate::dump_table(&base).unwrap();
+        let loaded = read_fonts::tables::base::Base::read(FontData::new(&dumped)).unwrap();
+        let axis = loaded.horiz_axis().unwrap().unwrap();
+        let tags = axis.base_tag_list().unwrap().unwrap();
+        assert_eq!(tags.baseline_tags(), &[HANG, IDEO, ROMN]);
+        let scripts = axis.base_script_list().unwrap();
+        let records = scripts.base_script_records();
+        assert_eq!(records[0].base_script_tag(), DEVA);
+        assert_eq!(records[1].base_script_tag(), LATN);
+
+        let latn = records[1].base_script(scripts.offset_data()).unwrap();
+        let values = latn.base_values().unwrap().unwrap();
+        assert_eq!(values.default_baseline_index(), 2);
+        let positions = values
+            .base_coords()
+            .map(|coord| coord.unwrap().coordinate())
+            .collect::<Vec<_>>();
+        assert_eq!(positions, [700, -120, 0]);
+        let min_max = latn.default_min_max().unwrap().unwrap();
+        assert_eq!(min_max.min_coord().unwrap().unwrap().coordinate(), -250);
+        let lang = &latn.base_lang_sys_records()[0];
+        assert_eq!(lang.base_lang_sys_tag(), Tag::new(b"VIT "));
+        let lang_min_max = lang.min_max(latn.offset_data()).unwrap();
+        assert!(lang_min_max.min_coord().is_none());
+
+        let deva = records[0].base_script(scripts.offset_data()).unwrap();
+        let values = deva.base_values().unwrap().unwrap();
+        assert_eq!(values.default_baseline_index(), 0);
+    }
+
+    #[test]
+    fn builder_errors() {
+        let mut builder = BaseBuilder::new();
+        builder.set_baselines(BaseAxis::Vertical, LATN, IDEO, coords([(ROMN, 0)]));
+        assert_eq!(
+            builder.clone().build().unwrap_err(),
+            BaseInputError::UnknownDefaultBaseline {
+                script: LATN,
+                baseline: IDEO
+            }
+        );
+
+        builder.set_baselines(BaseAxis::Vertical, LATN, ROMN, coords([(ROMN, 0)]));
+        builder.set_baselines(
+            BaseAxis::Vertical,
+            DEVA,
+            HANG,
+            coords([(ROMN, 0), (HANG, 600)]),
+        );
+        assert_eq!(
+            builder.clone().build().unwrap_err(),
+            BaseInputError::MissingBaseline {
+                script: LATN,
+                baseline: HANG
+            }
+        );
+        // other directions are independent
+        builder.set_baselines(BaseAxis::Vertical, DEVA, ROMN, coords([(ROMN, 0)]));
+        builder.set_baselines(BaseAxis::Horizontal, DEVA, HANG, coords([(HANG, 600)]));
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn builder_variable_coords() {
+        let variable = BaseCoord::format_3(700, Some(VariationIndex::new(0, 1, 0x8000).into()));
+        let mut builder = BaseBuilder::new();
+        builder.set_baselines(BaseAxis::Horizontal, LATN, HANG, [(HANG, variable)]);
+        assert_eq!(
+            builder.clone().build().unwrap_err(),
+            BaseInputError::MissingVariationData {
+                script: LATN,
+                outer: 0,
+                inner: 1
+            }
+        );
+
+        let mut var_store = ItemVariationStore::default();
+        let data = ItemVariationData {
+            item_count: 2,
+            ..Default::default()
+        };
+        var_store.item_variation_datas.push(Some(data).into());
+        builder.set_var_store(var_store);
+        let base = builder.build().unwrap();
+        assert!(base.item_var_store.is_some());
+
+        let dumped = crate::dump_table(&base).unwrap();
+        let loaded = read_fonts::tables::base::Base::read(FontData::new(&dumped)).unwrap();
+        assert_eq!(loaded.version(), MajorMinor::VERSION_1_1);
+        let axis = loaded.horiz_axis().unwrap().unwrap();
+        let scripts = axis.base_script_list().unwrap();
+        let latn = scripts.base_script_records()[0]
+            .base_script(scripts.offset_data())
+            .unwrap();
+        let values = latn.base_values().unwrap().unwrap();
+        let read_fonts::tables::base::BaseCoord::Format3(coord) =
+            values.base_coords().next().unwrap().unwrap()
+        else {
+            panic!("wrong format");
+        };
+        let device = coord.device().unwrap().unwrap();
+        assert_eq!(device.start_size(), 0);
+        assert_eq!(device.end_size(), 1);
+    }
+}
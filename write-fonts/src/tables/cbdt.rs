@@ -0,0 +1,675 @@
+//! The [CBDT (Color Bitmap Data)](https://learn.microsoft.com/en-us/typography/opentype/spec/cbdt) table
+
+use std::collections::{BTreeMap, HashMap};
+
+use read_fonts::TopLevelTable;
+use types::{GlyphId, Tag};
+
+use super::{
+    cblc::{
+        BigGlyphMetrics, BitmapSize, Cblc, IndexSubtable, IndexSubtableFormat, SbitLineMetrics,
+    },
+    hmtx::Hmtx,
+};
+use crate::{
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+/// Glyph images with small metrics and PNG data.
+pub(crate) const IMAGE_FORMAT_SMALL_METRICS: u16 = 17;
+/// Glyph images with PNG data only; the metrics are in `CBLC`.
+pub(crate) const IMAGE_FORMAT_NO_METRICS: u16 = 19;
+/// The size of the small metrics stored with each image in format 17.
+pub(crate) const SMALL_METRICS_LEN: usize = 5;
+/// The `CBLC` flag for strikes with horizontal metrics.
+const HORIZONTAL_METRICS: u8 = 1;
+
+/// The [CBDT] table.
+///
+/// This is constructed with a [CbdtCblcBuilder].
+///
+/// [CBDT]: https://learn.microsoft.com/en-us/typography/opentype/spec/cbdt
+#[derive(Clone, Debug, Default)]
+pub struct Cbdt(Vec<u8>);
+
+/// A builder for the `CBDT` and `CBLC` tables.
+///
+/// Glyph images are PNG data, added for each strike by its size in pixels
+/// per em. The width and height of each image are read from its PNG header,
+/// and its advance is the glyph's advance in `hmtx`, scaled to the size of
+/// the strike.
+///
+/// The images of each strike are located by the index subtables that take
+/// the least space: either one subtable for each run of consecutive glyph
+/// ids, or a single subtable for all the glyphs of the strike. Images that
+/// all have the same size and metrics are stored without per-glyph metrics.
+/// An image that is identical to the image of an earlier glyph in the same
+/// strike, with the same metrics, is only stored once.
+#[derive(Clone, Debug, Default)]
+pub struct CbdtCblcBuilder {
+    strikes: BTreeMap<u8, BTreeMap<GlyphId, ColorGlyph>>,
+}
+
+#[derive(Clone, Debug)]
+struct ColorGlyph {
+    png: Vec<u8>,
+    bearing_x: i8,
+    bearing_y: i8,
+}
+
+/// An error in the input to [`CbdtCblcBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CbdtInputError {
+    /// A glyph id is not less than the number of glyphs in `hmtx`.
+    GlyphOutOfRange { glyph: GlyphId, num_glyphs: u16 },
+    /// The image of a glyph is not PNG data.
+    InvalidPng { ppem: u8, glyph: GlyphId },
+    /// The image of a glyph is more than 255 pixels wide or high.
+    ImageTooLarge { ppem: u8, glyph: GlyphId },
+    /// The `units_per_em` is zero.
+    InvalidUnitsPerEm,
+}
+
+impl CbdtCblcBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the image of a glyph in the strike with the given size, replacing
+    /// any existing image.
+    ///
+    /// The bearings are the position of the top left corner of the image
+    /// relative to the glyph origin, in pixels.
+    pub fn add_png(
+        &mut self,
+        ppem: u8,
+        glyph: GlyphId,
+        png: Vec<u8>,
+        bearing_x: i8,
+        bearing_y: i8,
+    ) {
+        self.strikes.entry(ppem).or_default().insert(
+            glyph,
+            ColorGlyph {
+                png,
+                bearing_x,
+                bearing_y,
+            },
+        );
+    }
+
+    /// Build the tables.
+    ///
+    /// The advances of the glyphs are taken from `hmtx`, which must contain
+    /// metrics for every glyph in the font.
+    pub fn build(self, hmtx: &Hmtx, units_per_em: u16) -> Result<(Cbdt, Cblc), CbdtInputError> {
+        if units_per_em == 0 {
+            return Err(CbdtInputError::InvalidUnitsPerEm);
+        }
+        let num_glyphs = (hmtx.h_metrics.len() + hmtx.left_side_bearings.len()) as u16;
+        let advance = |glyph: GlyphId| {
+            let metric = hmtx
+                .h_metrics
+                .get(glyph.to_u16() as usize)
+                .or(hmtx.h_metrics.last());
+            metric.map(|metric| metric.advance).unwrap_or_default()
+        };
+
+        // the header: major and minor version
+        let mut data = vec![0, 3, 0, 0];
+        let mut bitmap_sizes = Vec::new();
+        for (ppem, glyphs) in self.strikes {
+            let mut images = Vec::with_capacity(glyphs.len());
+            for (glyph, image) in glyphs {
+                if glyph.to_u16() >= num_glyphs {
+                    return Err(CbdtInputError::GlyphOutOfRange { glyph, num_glyphs });
+                }
+                let (width, height) =
+                    png_size(&image.png).ok_or(CbdtInputError::InvalidPng { ppem, glyph })?;
+                let (Ok(width), Ok(height)) = (u8::try_from(width), u8::try_from(height)) else {
+                    return Err(CbdtInputError::ImageTooLarge { ppem, glyph });
+                };
+                let scaled = (advance(glyph) as u32 * ppem as u32 + units_per_em as u32 / 2)
+                    / units_per_em as u32;
+                let metrics = BigGlyphMetrics {
+                    height,
+                    width,
+                    hori_bearing_x: image.bearing_x,
+                    hori_bearing_y: image.bearing_y,
+                    hori_advance: scaled.min(u8::MAX as u32) as u8,
+                    ..Default::default()
+                };
+                images.push(Image {
+                    glyph,
+                    metrics,
+                    png: image.png,
+                });
+            }
+            let hori = line_metrics(&images);
+            let index_subtables = write_strike(images, &mut data);
+            bitmap_sizes.push(BitmapSize {
+                hori,
+                ppem_x: ppem,
+                ppem_y: ppem,
+                bit_depth: 32,
+                flags: HORIZONTAL_METRICS,
+                index_subtables,
+                ..Default::default()
+            });
+        }
+        Ok((Cbdt(data), Cblc::new(bitmap_sizes)))
+    }
+}
+
+/// A glyph image, with its metrics in pixels.
+pub(crate) struct Image {
+    pub(crate) glyph: GlyphId,
+    pub(crate) metrics: BigGlyphMetrics,
+    pub(crate) png: Vec<u8>,
+}
+
+/// The images covered by a single index subtable.
+#[derive(Clone, Copy)]
+struct Group<'a> {
+    images: &'a [Image],
+    // the images can be located with glyph ids rather than a range
+    sparse: bool,
+    // the images share the same size and metrics
+    uniform: bool,
+}
+
+impl<'a> Group<'a> {
+    fn new(images: &'a [Image], sparse: bool) -> Self {
+        let group = Group {
+            images,
+            sparse,
+            uniform: false,
+        };
+        let first = &images[0];
+        let can_be_uniform = images
+            .iter()
+            .all(|image| image.metrics == first.metrics && image.png.len() == first.png.len());
+        let uniform = Group {
+            uniform: true,
+            ..group
+        };
+        // sharing metrics is not always smaller, for a few glyphs
+        if can_be_uniform && (!group.is_valid() || uniform.cost() <= group.cost()) {
+            uniform
+        } else {
+            group
+        }
+    }
+
+    fn image_format(&self) -> u16 {
+        if self.uniform {
+            IMAGE_FORMAT_NO_METRICS
+        } else {
+            IMAGE_FORMAT_SMALL_METRICS
+        }
+    }
+
+    fn image_len(&self, image: &Image) -> usize {
+        match self.image_format() {
+            IMAGE_FORMAT_NO_METRICS => 4 + image.png.len(),
+            _ => SMALL_METRICS_LEN + 4 + image.png.len(),
+        }
+    }
+
+    /// The offsets of each image, and of the end of the last image, from the
+    /// start of the first image.
+    fn offsets(&self) -> impl Iterator<Item = usize> + '_ {
+        std::iter::once(0).chain(self.images.iter().scan(0, |offset, image| {
+            *offset += self.image_len(image);
+            Some(*offset)
+        }))
+    }
+
+    fn format(&self) -> IndexSubtableFormat {
+        let first = &self.images[0];
+        let image_size = self.image_len(first) as u32;
+        let glyphs = || self.images.iter().map(|image| image.glyph).collect();
+        let total_len = self.offsets().last().unwrap_or_default();
+        match (self.uniform, self.sparse) {
+            (true, false) => IndexSubtableFormat::Format2 {
+                image_size,
+                metrics: first.metrics,
+            },
+            (true, true) => IndexSubtableFormat::Format5 {
+                image_size,
+                metrics: first.metrics,
+                glyphs: glyphs(),
+            },
+            (false, true) => IndexSubtableFormat::Format4 {
+                glyphs: glyphs(),
+                offsets: self.offsets().map(|offset| offset as u16).collect(),
+            },
+            (false, false) if total_len <= u16::MAX as usize => IndexSubtableFormat::Format3 {
+                offsets: self.offsets().map(|offset| offset as u16).collect(),
+            },
+            (false, false) => IndexSubtableFormat::Format1 {
+                offsets: self.offsets().map(|offset| offset as u32).collect(),
+            },
+        }
+    }
+
+    /// Whether this group can be located by a single subtable.
+    fn is_valid(&self) -> bool {
+        // format 4 has no variant with 32-bit offsets
+        self.uniform || !self.sparse || self.offsets().last() <= Some(u16::MAX as usize)
+    }
+
+    /// The space this group takes up in both tables, apart from the image
+    /// data itself.
+    fn cost(&self) -> usize {
+        let metrics_len = match self.image_format() {
+            IMAGE_FORMAT_SMALL_METRICS => SMALL_METRICS_LEN * self.images.len(),
+            _ => 0,
+        };
+        8 + self.format().len() + metrics_len
+    }
+}
+
+/// Write the images of a strike to the `CBDT` data, and return the subtables
+/// that locate them, in glyph id order.
+///
+/// The images must be sorted by glyph id. Each image that repeats an earlier
+/// one is not written again; its glyph gets a subtable of its own, which
+/// locates the earlier copy.
+pub(crate) fn write_strike(images: Vec<Image>, data: &mut Vec<u8>) -> Vec<IndexSubtable> {
+    let mut first_uses = HashMap::new();
+    let originals = images
+        .iter()
+        .map(|image| {
+            *first_uses
+                .entry((image.metrics, image.png.as_slice()))
+                .or_insert(image.glyph)
+        })
+        .collect::<Vec<_>>();
+    drop(first_uses);
+
+    let mut unique = Vec::with_capacity(images.len());
+    let mut duplicates = Vec::new();
+    for (image, original) in images.into_iter().zip(originals) {
+        if image.glyph == original {
+            unique.push(image);
+        } else {
+            duplicates.push((image.glyph, original));
+        }
+    }
+
+    let mut locations = HashMap::new();
+    let mut subtables = Vec::new();
+    // a sparse subtable would overlap the subtables of repeated images
+    for group in plan_subtables(&unique, duplicates.is_empty()) {
+        let subtable = write_subtable(group, data);
+        for (image, offset) in group.images.iter().zip(group.offsets()) {
+            let location = (
+                subtable.image_data_offset + offset as u32,
+                group.image_len(image) as u32,
+            );
+            locations.insert(image.glyph, (group.image_format(), image.metrics, location));
+        }
+        subtables.push(subtable);
+    }
+    for (glyph, original) in duplicates {
+        let (image_format, metrics, (image_data_offset, image_size)) = locations[&original];
+        let format = match image_format {
+            IMAGE_FORMAT_NO_METRICS => IndexSubtableFormat::Format2 {
+                image_size,
+                metrics,
+            },
+            _ => match u16::try_from(image_size) {
+                Ok(len) => IndexSubtableFormat::Format3 {
+                    offsets: vec![0, len],
+                },
+                Err(_) => IndexSubtableFormat::Format1 {
+                    offsets: vec![0, image_size],
+                },
+            },
+        };
+        subtables.push(IndexSubtable {
+            first_glyph: glyph,
+            last_glyph: glyph,
+            image_format,
+            image_data_offset,
+            format,
+        });
+    }
+    subtables.sort_by_key(|subtable| subtable.first_glyph);
+    subtables
+}
+
+/// Divide the images of a strike into the groups that take the least space.
+///
+/// A single sparse subtable for all the images is only considered if
+/// `allow_sparse` is set.
+fn plan_subtables(images: &[Image], allow_sparse: bool) -> Vec<Group<'_>> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..=images.len() {
+        let is_next = |i: usize| images[i].glyph.to_u16() == images[i - 1].glyph.to_u16() + 1;
+        if i == images.len() || !is_next(i) {
+            runs.push(Group::new(&images[start..i], false));
+            start = i;
+        }
+    }
+    if allow_sparse && runs.len() > 1 {
+        let sparse = Group::new(images, true);
+        let runs_cost = runs.iter().map(Group::cost).sum::<usize>();
+        if sparse.is_valid() && sparse.cost() < runs_cost {
+            return vec![sparse];
+        }
+    }
+    runs
+}
+
+/// Write the images of a group to the `CBDT` data, and return the subtable
+/// that locates them.
+fn write_subtable(group: Group, data: &mut Vec<u8>) -> IndexSubtable {
+    let image_data_offset = data.len() as u32;
+    for image in group.images {
+        if group.image_format() == IMAGE_FORMAT_SMALL_METRICS {
+            let metrics = image.metrics;
+            data.extend([metrics.height, metrics.width]);
+            data.extend([metrics.hori_bearing_x, metrics.hori_bearing_y].map(|v| v as u8));
+            data.push(metrics.hori_advance);
+        }
+        data.extend((image.png.len() as u32).to_be_bytes());
+        data.extend_from_slice(&image.png);
+    }
+    IndexSubtable {
+        first_glyph: group.images[0].glyph,
+        last_glyph: group.images[group.images.len() - 1].glyph,
+        image_format: group.image_format(),
+        image_data_offset,
+        format: group.format(),
+    }
+}
+
+/// Compute the horizontal line metrics of a strike from its glyph metrics.
+fn line_metrics(images: &[Image]) -> SbitLineMetrics {
+    let clamp = |value: i32| value.clamp(i8::MIN as i32, i8::MAX as i32) as i8;
+    let metrics = || images.iter().map(|image| image.metrics);
+    let max_before_bl = metrics().map(|m| m.hori_bearing_y as i32).max();
+    let min_after_bl = metrics()
+        .map(|m| m.hori_bearing_y as i32 - m.height as i32)
+        .min();
+    SbitLineMetrics {
+        ascender: clamp(max_before_bl.unwrap_or_default()),
+        descender: clamp(min_after_bl.unwrap_or_default()),
+        width_max: metrics().map(|m| m.width).max().unwrap_or_default(),
+        min_origin_sb: clamp(
+            metrics()
+                .map(|m| m.hori_bearing_x as i32)
+                .min()
+                .unwrap_or_default(),
+        ),
+        min_advance_sb: clamp(
+            metrics()
+                .map(|m| m.hori_advance as i32 - m.hori_bearing_x as i32 - m.width as i32)
+                .min()
+                .unwrap_or_default(),
+        ),
+        max_before_bl: clamp(max_before_bl.unwrap_or_default()),
+        min_after_bl: clamp(min_after_bl.unwrap_or_default()),
+        ..Default::default()
+    }
+}
+
+/// The width and height of a PNG image, from its header.
+pub(crate) fn png_size(png: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    // the header chunk must come first: length, type, then width and height
+    if png.len() < 24 || !png.starts_with(SIGNATURE) || &png[12..16] != b"IHDR" {
+        return None;
+    }
+    let read = |pos: usize| u32::from_be_bytes(png[pos..pos + 4].try_into().unwrap());
+    Some((read(16), read(20)))
+}
+
+impl TopLevelTable for Cbdt {
+    const TAG: Tag = Tag::CBDT;
+}
+
+impl FontWrite for Cbdt {
+    fn write_into(&self, writer: &mut TableWriter) {
+        writer.write_slice(&self.0)
+    }
+}
+
+impl Validate for Cbdt {
+    fn validate_impl(&self, _ctx: &mut ValidationCtx) {}
+}
+
+impl std::fmt::Display for CbdtInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CbdtInputError::GlyphOutOfRange { glyph, num_glyphs } => write!(
+                f,
+                "glyph {} is out of range for a font with {num_glyphs} glyphs",
+                glyph.to_u16()
+            ),
+            CbdtInputError::InvalidPng { ppem, glyph } => write!(
+                f,
+                "image of glyph {} at {ppem} ppem is not a PNG",
+                glyph.to_u16()
+            ),
+            CbdtInputError::ImageTooLarge { ppem, glyph } => write!(
+                f,
+                "image of glyph {} at {ppem} ppem is larger than 255 pixels",
+                glyph.to_u16()
+            ),
+            CbdtInputError::InvalidUnitsPerEm => write!(f, "units per em must not be zero"),
+        }
+    }
+}
+
+impl std::error::Error for CbdtInputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::hmtx::LongMetric;
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    /// The start of a PNG file, with some distinguishing pixel data.
+    fn png(width: u32, height: u32, pixels: u8) -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend(width.to_be_bytes());
+        png.extend(height.to_be_bytes());
+        png.push(pixels);
+        png
+    }
+
+    fn hmtx(advances: &[u16]) -> Hmtx {
+        Hmtx::new(
+            advances
+                .iter()
+                .map(|advance| LongMetric::new(*advance, 0))
+                .collect(),
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn png_header() {
+        assert_eq!(png_size(&png(136, 128, 0)), Some((136, 128)));
+        assert_eq!(png_size(b"\x89PNG\r\n\x1a\n"), None);
+        assert_eq!(png_size(&[0; 32]), None);
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = CbdtCblcBuilder::new();
+        builder.add_png(109, gid(1), png(136, 128, 1), 0, 101);
+        builder.add_png(109, gid(2), png(120, 128, 2), 4, 101);
+        builder.add_png(109, gid(3), png(136, 128, 3), 0, 101);
+        let (cbdt, cblc) = builder.build(&hmtx(&[0, 2550, 2300, 2550]), 2048).unwrap();
+
+        let size = &cblc.bitmap_sizes[0];
+        assert_eq!((size.ppem_x, size.ppem_y, size.bit_depth), (109, 109, 32));
+        assert_eq!(size.hori.ascender, 101);
+        assert_eq!(size.hori.descender, -27);
+        assert_eq!(size.hori.width_max, 136);
+        // glyph 2 is 120 pixels wide, at 4 pixels from the origin, and advances 122
+        assert_eq!(size.hori.min_advance_sb, -2);
+        // a single range, with variable metrics
+        assert_eq!(size.index_subtables.len(), 1);
+        let subtable = &size.index_subtables[0];
+        assert_eq!(
+            (subtable.first_glyph, subtable.last_glyph),
+            (gid(1), gid(3))
+        );
+        assert_eq!(subtable.image_format, 17);
+        assert_eq!(subtable.image_data_offset, 4);
+        let len = png(1, 1, 1).len() as u16 + 9;
+        assert_eq!(
+            subtable.format,
+            IndexSubtableFormat::Format3 {
+                offsets: vec![0, len, len * 2, len * 3]
+            }
+        );
+
+        // advance is 2550 units at 109 ppem
+        let data = crate::dump_table(&cbdt).unwrap();
+        assert_eq!(&data[..4], &[0, 3, 0, 0]);
+        assert_eq!(&data[4..9], &[128, 136, 0, 101, 136]);
+        assert_eq!(&data[9..13], &(png(1, 1, 1).len() as u32).to_be_bytes());
+        assert_eq!(&data[13..13 + png(1, 1, 1).len()], png(136, 128, 1));
+    }
+
+    #[test]
+    fn uniform_and_sparse_glyphs() {
+        let mut builder = CbdtCblcBuilder::new();
+        for glyph in [1, 2, 5, 6, 9, 10] {
+            builder.add_png(20, gid(glyph), png(20, 20, glyph as u8), 0, 16);
+        }
+        let (_, cblc) = builder.build(&hmtx(&[500; 11]), 500).unwrap();
+        let subtables = &cblc.bitmap_sizes[0].index_subtables;
+        assert_eq!(subtables.len(), 1);
+        assert_eq!(subtables[0].image_format, 19);
+        let IndexSubtableFormat::Format5 {
+            image_size,
+            metrics,
+            glyphs,
+        } = &subtables[0].format
+        else {
+            panic!("wrong format: {:?}", subtables[0].format);
+        };
+        assert_eq!(*image_size as usize, 4 + png(1, 1, 1).len());
+        assert_eq!(metrics.hori_advance, 20);
+        assert_eq!(glyphs.len(), 6);
+
+        // a single glyph is smaller with its own metrics
+        let mut builder = CbdtCblcBuilder::new();
+        builder.add_png(20, gid(1), png(20, 20, 0), 0, 16);
+        let (_, cblc) = builder.build(&hmtx(&[500; 2]), 500).unwrap();
+        let subtable = &cblc.bitmap_sizes[0].index_subtables[0];
+        assert_eq!(subtable.index_format(), 3);
+    }
+
+    #[test]
+    fn cblc_layout() {
+        let mut builder = CbdtCblcBuilder::new();
+        builder.add_png(20, gid(1), png(20, 20, 1), 0, 16);
+        builder.add_png(20, gid(2), png(18, 20, 2), 0, 16);
+        builder.add_png(40, gid(2), png(36, 40, 2), 0, 32);
+        let (cbdt, cblc) = builder.build(&hmtx(&[500; 3]), 500).unwrap();
+        let data = crate::dump_table(&cblc).unwrap();
+        let read_u32 = |pos: usize| u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap());
+        assert_eq!(&data[..4], &[0, 3, 0, 0]);
+        assert_eq!(read_u32(4), 2);
+        // the subtable lists follow two 48-byte sizes
+        let first_list = read_u32(8) as usize;
+        assert_eq!(first_list, 8 + 2 * 48);
+        let first_list_len = read_u32(12) as usize;
+        assert_eq!(read_u32(16), 1);
+        assert_eq!(read_u32(8 + 48), (first_list + first_list_len) as u32);
+        // start and end glyph, then ppem, bit depth and flags
+        assert_eq!(&data[8 + 40..8 + 48], &[0, 1, 0, 2, 20, 20, 32, 1]);
+        assert_eq!(&data[8 + 48 + 40..8 + 96], &[0, 2, 0, 2, 40, 40, 32, 1]);
+
+        // first glyph, last glyph and offset of the subtable
+        assert_eq!(&data[first_list..first_list + 4], &[0, 1, 0, 2]);
+        assert_eq!(read_u32(first_list + 4), 8);
+        let second_subtable = first_list + first_list_len + 8;
+        // index format, image format and data offset
+        assert_eq!(&data[second_subtable..second_subtable + 4], &[0, 3, 0, 17]);
+        let image = read_u32(second_subtable + 4) as usize;
+        let cbdt = crate::dump_table(&cbdt).unwrap();
+        assert_eq!(&cbdt[image..image + 2], &[40, 36]);
+        assert_eq!(image + 9 + png(1, 1, 1).len(), cbdt.len());
+    }
+
+    #[test]
+    fn repeated_images() {
+        let mut builder = CbdtCblcBuilder::new();
+        builder.add_png(20, gid(1), png(20, 20, 1), 0, 16);
+        builder.add_png(20, gid(2), png(18, 20, 2), 0, 16);
+        builder.add_png(20, gid(3), png(20, 20, 1), 0, 16);
+        // the same image, but with different metrics
+        builder.add_png(20, gid(4), png(20, 20, 1), 1, 16);
+        let (cbdt, cblc) = builder.build(&hmtx(&[500; 5]), 500).unwrap();
+        let cbdt = crate::dump_table(&cbdt).unwrap();
+        let image_len = 9 + png(1, 1, 1).len();
+        assert_eq!(cbdt.len(), 4 + 3 * image_len);
+
+        let subtables = &cblc.bitmap_sizes[0].index_subtables;
+        let ranges = subtables
+            .iter()
+            .map(|sub| (sub.first_glyph.to_u16(), sub.last_glyph.to_u16()))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(1, 2), (3, 3), (4, 4)]);
+        assert_eq!(subtables[1].image_format, 17);
+        assert_eq!(
+            subtables[1].image_data_offset,
+            subtables[0].image_data_offset
+        );
+        assert_eq!(
+            subtables[1].format,
+            IndexSubtableFormat::Format3 {
+                offsets: vec![0, image_len as u16]
+            }
+        );
+        assert_ne!(
+            subtables[2].image_data_offset,
+            subtables[0].image_data_offset
+        );
+        assert!(cblc.validate().is_ok());
+    }
+
+    #[test]
+    fn builder_errors() {
+        let mut builder = CbdtCblcBuilder::new();
+        builder.add_png(20, gid(2), png(20, 20, 0), 0, 16);
+        assert_eq!(
+            builder.clone().build(&hmtx(&[500; 2]), 500).unwrap_err(),
+            CbdtInputError::GlyphOutOfRange {
+                glyph: gid(2),
+                num_glyphs: 2
+            }
+        );
+        builder.add_png(20, gid(1), png(256, 20, 0), 0, 16);
+        assert_eq!(
+            builder.clone().build(&hmtx(&[500; 3]), 500).unwrap_err(),
+            CbdtInputError::ImageTooLarge {
+                ppem: 20,
+                glyph: gid(1)
+            }
+        );
+        builder.add_png(20, gid(1), vec![0; 40], 0, 16);
+        assert_eq!(
+            builder.build(&hmtx(&[500; 3]), 500).unwrap_err(),
+            CbdtInputError::InvalidPng {
+                ppem: 20,
+                glyph: gid(1)
+            }
+        );
+    }
+}
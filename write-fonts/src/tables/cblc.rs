@@ -0,0 +1,451 @@
+//! The [CBLC (Color Bitmap Location)](https://learn.microsoft.com/en-us/typography/opentype/spec/cblc) table
+
+use read_fonts::TopLevelTable;
+use types::{GlyphId, Tag};
+
+use crate::{
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+/// The size of the table header.
+const HEADER_LEN: usize = 8;
+/// The size of a `BitmapSize` record.
+const BITMAP_SIZE_LEN: usize = 48;
+/// The size of an `IndexSubtableRecord`.
+const SUBTABLE_RECORD_LEN: usize = 8;
+/// The size of the header shared by all index subtables.
+const SUBTABLE_HEADER_LEN: usize = 8;
+/// The size of [`BigGlyphMetrics`].
+const BIG_METRICS_LEN: usize = 8;
+
+/// The [CBLC] table.
+///
+/// This is usually constructed with a
+/// [CbdtCblcBuilder](super::cbdt::CbdtCblcBuilder), which also builds the
+/// `CBDT` table that contains the glyph images.
+///
+/// [CBLC]: https://learn.microsoft.com/en-us/typography/opentype/spec/cblc
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cblc {
+    /// The strikes, one for each size of bitmaps.
+    pub bitmap_sizes: Vec<BitmapSize>,
+}
+
+/// The location of the bitmaps of a single strike.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitmapSize {
+    /// Line metrics for text rendered horizontally.
+    pub hori: SbitLineMetrics,
+    /// Line metrics for text rendered vertically.
+    pub vert: SbitLineMetrics,
+    /// Horizontal pixels per em.
+    pub ppem_x: u8,
+    /// Vertical pixels per em.
+    pub ppem_y: u8,
+    /// The bit depth of the strike; always 32 for color bitmaps.
+    pub bit_depth: u8,
+    /// Vertical or horizontal metrics flags.
+    pub flags: u8,
+    /// The index subtables, in glyph id order.
+    pub index_subtables: Vec<IndexSubtable>,
+}
+
+/// Line metrics for a strike, in pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SbitLineMetrics {
+    pub ascender: i8,
+    pub descender: i8,
+    pub width_max: u8,
+    pub caret_slope_numerator: i8,
+    pub caret_slope_denominator: i8,
+    pub caret_offset: i8,
+    pub min_origin_sb: i8,
+    pub min_advance_sb: i8,
+    pub max_before_bl: i8,
+    pub min_after_bl: i8,
+}
+
+/// The metrics of a single glyph bitmap, in pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigGlyphMetrics {
+    pub height: u8,
+    pub width: u8,
+    pub hori_bearing_x: i8,
+    pub hori_bearing_y: i8,
+    pub hori_advance: u8,
+    pub vert_bearing_x: i8,
+    pub vert_bearing_y: i8,
+    pub vert_advance: u8,
+}
+
+/// A table locating the images of a range of glyphs in the `CBDT` table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexSubtable {
+    /// The first glyph covered by this subtable.
+    pub first_glyph: GlyphId,
+    /// The last glyph covered by this subtable.
+    pub last_glyph: GlyphId,
+    /// The format of the glyph images in the `CBDT` table.
+    pub image_format: u16,
+    /// The offset of the first glyph image, from the start of the `CBDT`
+    /// table.
+    pub image_data_offset: u32,
+    /// The location of each glyph image, relative to `image_data_offset`.
+    pub format: IndexSubtableFormat,
+}
+
+/// The different ways an [`IndexSubtable`] can locate its glyph images.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndexSubtableFormat {
+    /// Variable-size images with 32-bit offsets, one for each glyph in the
+    /// range and one for the end of the last image.
+    Format1 { offsets: Vec<u32> },
+    /// Images that all have the same size and metrics.
+    Format2 {
+        image_size: u32,
+        metrics: BigGlyphMetrics,
+    },
+    /// Variable-size images with 16-bit offsets, one for each glyph in the
+    /// range and one for the end of the last image.
+    Format3 { offsets: Vec<u16> },
+    /// Variable-size images for a sparse set of glyphs. There is one more
+    /// offset than there are glyphs.
+    Format4 {
+        glyphs: Vec<GlyphId>,
+        offsets: Vec<u16>,
+    },
+    /// Images for a sparse set of glyphs that all have the same size and
+    /// metrics.
+    Format5 {
+        image_size: u32,
+        metrics: BigGlyphMetrics,
+        glyphs: Vec<GlyphId>,
+    },
+}
+
+impl Cblc {
+    /// Create a new table from its strikes.
+    pub fn new(bitmap_sizes: Vec<BitmapSize>) -> Self {
+        Cblc { bitmap_sizes }
+    }
+}
+
+impl BitmapSize {
+    fn subtable_list_len(&self) -> usize {
+        self.index_subtables
+            .iter()
+            .map(|subtable| SUBTABLE_RECORD_LEN + subtable.len())
+            .sum()
+    }
+}
+
+impl IndexSubtable {
+    /// The index format of this subtable.
+    pub fn index_format(&self) -> u16 {
+        match self.format {
+            IndexSubtableFormat::Format1 { .. } => 1,
+            IndexSubtableFormat::Format2 { .. } => 2,
+            IndexSubtableFormat::Format3 { .. } => 3,
+            IndexSubtableFormat::Format4 { .. } => 4,
+            IndexSubtableFormat::Format5 { .. } => 5,
+        }
+    }
+
+    /// The number of bytes this subtable takes up in the table, including
+    /// padding.
+    pub(crate) fn len(&self) -> usize {
+        self.format.len()
+    }
+
+    fn num_glyphs(&self) -> usize {
+        match &self.format {
+            IndexSubtableFormat::Format4 { glyphs, .. }
+            | IndexSubtableFormat::Format5 { glyphs, .. } => glyphs.len(),
+            _ => (self.last_glyph.to_u16() as usize + 1)
+                .saturating_sub(self.first_glyph.to_u16() as usize),
+        }
+    }
+}
+
+impl IndexSubtableFormat {
+    /// The number of bytes a subtable in this format takes up, including
+    /// padding.
+    pub(crate) fn len(&self) -> usize {
+        let len = SUBTABLE_HEADER_LEN
+            + match self {
+                IndexSubtableFormat::Format1 { offsets } => offsets.len() * 4,
+                IndexSubtableFormat::Format2 { .. } => 4 + BIG_METRICS_LEN,
+                IndexSubtableFormat::Format3 { offsets } => offsets.len() * 2,
+                IndexSubtableFormat::Format4 { offsets, .. } => 4 + offsets.len() * 4,
+                IndexSubtableFormat::Format5 { glyphs, .. } => {
+                    4 + BIG_METRICS_LEN + 4 + glyphs.len() * 2
+                }
+            };
+        // subtables are aligned to four bytes
+        len.next_multiple_of(4)
+    }
+}
+
+impl TopLevelTable for Cblc {
+    const TAG: Tag = Tag::CBLC;
+}
+
+impl FontWrite for Cblc {
+    fn write_into(&self, writer: &mut TableWriter) {
+        3u16.write_into(writer);
+        0u16.write_into(writer);
+        (self.bitmap_sizes.len() as u32).write_into(writer);
+        // the subtable lists follow the array of sizes, in the same order
+        let mut list_offset = HEADER_LEN + self.bitmap_sizes.len() * BITMAP_SIZE_LEN;
+        for size in &self.bitmap_sizes {
+            let list_len = size.subtable_list_len();
+            (list_offset as u32).write_into(writer);
+            (list_len as u32).write_into(writer);
+            (size.index_subtables.len() as u32).write_into(writer);
+            // colorRef, unused
+            0u32.write_into(writer);
+            size.hori.write_into(writer);
+            size.vert.write_into(writer);
+            let first = size.index_subtables.first();
+            let last = size.index_subtables.last();
+            first
+                .map(|sub| sub.first_glyph)
+                .unwrap_or_default()
+                .write_into(writer);
+            last.map(|sub| sub.last_glyph)
+                .unwrap_or_default()
+                .write_into(writer);
+            size.ppem_x.write_into(writer);
+            size.ppem_y.write_into(writer);
+            size.bit_depth.write_into(writer);
+            size.flags.write_into(writer);
+            list_offset += list_len;
+        }
+        for size in &self.bitmap_sizes {
+            let mut subtable_offset = size.index_subtables.len() * SUBTABLE_RECORD_LEN;
+            for subtable in &size.index_subtables {
+                subtable.first_glyph.write_into(writer);
+                subtable.last_glyph.write_into(writer);
+                (subtable_offset as u32).write_into(writer);
+                subtable_offset += subtable.len();
+            }
+            for subtable in &size.index_subtables {
+                subtable.write_into(writer);
+            }
+        }
+    }
+}
+
+impl FontWrite for SbitLineMetrics {
+    fn write_into(&self, writer: &mut TableWriter) {
+        self.ascender.write_into(writer);
+        self.descender.write_into(writer);
+        self.width_max.write_into(writer);
+        self.caret_slope_numerator.write_into(writer);
+        self.caret_slope_denominator.write_into(writer);
+        self.caret_offset.write_into(writer);
+        self.min_origin_sb.write_into(writer);
+        self.min_advance_sb.write_into(writer);
+        self.max_before_bl.write_into(writer);
+        self.min_after_bl.write_into(writer);
+        // padding
+        0u16.write_into(writer);
+    }
+}
+
+impl FontWrite for BigGlyphMetrics {
+    fn write_into(&self, writer: &mut TableWriter) {
+        self.height.write_into(writer);
+        self.width.write_into(writer);
+        self.hori_bearing_x.write_into(writer);
+        self.hori_bearing_y.write_into(writer);
+        self.hori_advance.write_into(writer);
+        self.vert_bearing_x.write_into(writer);
+        self.vert_bearing_y.write_into(writer);
+        self.vert_advance.write_into(writer);
+    }
+}
+
+impl FontWrite for IndexSubtable {
+    fn write_into(&self, writer: &mut TableWriter) {
+        self.index_format().write_into(writer);
+        self.image_format.write_into(writer);
+        self.image_data_offset.write_into(writer);
+        match &self.format {
+            IndexSubtableFormat::Format1 { offsets } => offsets.write_into(writer),
+            IndexSubtableFormat::Format2 {
+                image_size,
+                metrics,
+            } => {
+                image_size.write_into(writer);
+                metrics.write_into(writer);
+            }
+            IndexSubtableFormat::Format3 { offsets } => {
+                offsets.write_into(writer);
+                if offsets.len() % 2 == 1 {
+                    0u16.write_into(writer);
+                }
+            }
+            IndexSubtableFormat::Format4 { glyphs, offsets } => {
+                (glyphs.len() as u32).write_into(writer);
+                // the final offset is paired with a glyph id of zero
+                let glyphs = glyphs.iter().copied().chain(Some(GlyphId::NOTDEF));
+                for (glyph, offset) in glyphs.zip(offsets) {
+                    glyph.write_into(writer);
+                    offset.write_into(writer);
+                }
+            }
+            IndexSubtableFormat::Format5 {
+                image_size,
+                metrics,
+                glyphs,
+            } => {
+                image_size.write_into(writer);
+                metrics.write_into(writer);
+                (glyphs.len() as u32).write_into(writer);
+                glyphs.write_into(writer);
+                if glyphs.len() % 2 == 1 {
+                    0u16.write_into(writer);
+                }
+            }
+        }
+    }
+}
+
+impl Validate for Cblc {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        ctx.in_table("Cblc", |ctx| {
+            ctx.in_field("bitmap_sizes", |ctx| {
+                ctx.in_array(|ctx| {
+                    for size in &self.bitmap_sizes {
+                        ctx.array_item(|ctx| size.validate_impl(ctx))
+                    }
+                })
+            })
+        })
+    }
+}
+
+impl Validate for BitmapSize {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        ctx.in_table("BitmapSize", |ctx| {
+            ctx.in_field("index_subtables", |ctx| {
+                if self
+                    .index_subtables
+                    .windows(2)
+                    .any(|pair| pair[0].last_glyph >= pair[1].first_glyph)
+                {
+                    ctx.report("subtables must be sorted and must not overlap");
+                }
+                ctx.in_array(|ctx| {
+                    for subtable in &self.index_subtables {
+                        ctx.array_item(|ctx| subtable.validate_impl(ctx))
+                    }
+                })
+            })
+        })
+    }
+}
+
+impl Validate for IndexSubtable {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        if self.first_glyph > self.last_glyph {
+            ctx.report("first glyph must not be greater than last glyph");
+            return;
+        }
+        let num_glyphs = self.num_glyphs();
+        let num_offsets = match &self.format {
+            IndexSubtableFormat::Format1 { offsets } => Some(offsets.len()),
+            IndexSubtableFormat::Format3 { offsets } => Some(offsets.len()),
+            IndexSubtableFormat::Format4 { offsets, .. } => Some(offsets.len()),
+            _ => None,
+        };
+        if num_offsets.is_some_and(|len| len != num_glyphs + 1) {
+            ctx.report("there must be one more offset than there are glyphs");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    #[test]
+    fn subtable_lengths() {
+        let metrics = BigGlyphMetrics::default();
+        let formats = [
+            (
+                IndexSubtableFormat::Format1 {
+                    offsets: vec![0; 3],
+                },
+                20,
+            ),
+            (
+                IndexSubtableFormat::Format2 {
+                    image_size: 10,
+                    metrics,
+                },
+                20,
+            ),
+            (
+                IndexSubtableFormat::Format3 {
+                    offsets: vec![0; 3],
+                },
+                16,
+            ),
+            (
+                IndexSubtableFormat::Format4 {
+                    glyphs: vec![gid(1), gid(4)],
+                    offsets: vec![0; 3],
+                },
+                24,
+            ),
+            (
+                IndexSubtableFormat::Format5 {
+                    image_size: 10,
+                    metrics,
+                    glyphs: vec![gid(1), gid(4), gid(9)],
+                },
+                32,
+            ),
+        ];
+        for (format, len) in formats {
+            let subtable = IndexSubtable {
+                first_glyph: gid(1),
+                last_glyph: gid(2),
+                image_format: 17,
+                image_data_offset: 4,
+                format,
+            };
+            assert_eq!(subtable.len(), len, "format {}", subtable.index_format());
+            let mut writer = TableWriter::default();
+            subtable.write_into(&mut writer);
+            assert_eq!(writer.into_data().len(), len);
+        }
+    }
+
+    #[test]
+    fn validate_offsets() {
+        let subtable = IndexSubtable {
+            first_glyph: gid(3),
+            last_glyph: gid(5),
+            image_format: 17,
+            image_data_offset: 4,
+            format: IndexSubtableFormat::Format3 {
+                offsets: vec![0; 3],
+            },
+        };
+        let cblc = Cblc::new(vec![BitmapSize {
+            index_subtables: vec![subtable],
+            ..Default::default()
+        }]);
+        let report = cblc.validate().unwrap_err();
+        let error = report.errors().next().unwrap();
+        assert_eq!(error.path(), "Cblc.bitmap_sizes[0].index_subtables[0]");
+    }
+}
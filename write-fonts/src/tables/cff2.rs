@@ -0,0 +1,619 @@
+//! The [CFF2 (Compact Font Format 2)](https://learn.microsoft.com/en-us/typography/opentype/spec/cff2) table
+
+use kurbo::{BezPath, PathEl, Point};
+
+use read_fonts::TopLevelTable;
+use types::Tag;
+
+use crate::{
+    tables::variations::{
+        ItemVariationData, ItemVariationStore, VariationModel, VariationRegionList,
+    },
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+// charstring operators
+const HLINETO: u8 = 6;
+const VLINETO: u8 = 7;
+const RLINETO: u8 = 5;
+const RRCURVETO: u8 = 8;
+const RMOVETO: u8 = 21;
+const BLEND: u8 = 16;
+
+// DICT operators
+const CHAR_STRINGS: u8 = 17;
+const PRIVATE: u8 = 18;
+const VSTORE: u8 = 24;
+const ESCAPE: u8 = 12;
+const FONT_MATRIX: u8 = 7;
+const FD_ARRAY: u8 = 36;
+
+/// The maximum depth of the charstring argument stack.
+const MAX_STACK: usize = 513;
+
+/// The [CFF2 (Compact Font Format 2)](https://learn.microsoft.com/en-us/typography/opentype/spec/cff2) table
+///
+/// This is constructed with a [Cff2Builder].
+#[derive(Clone, Debug, Default)]
+pub struct Cff2(Vec<u8>);
+
+impl TopLevelTable for Cff2 {
+    const TAG: Tag = Tag::CFF2;
+}
+
+impl FontWrite for Cff2 {
+    fn write_into(&self, writer: &mut TableWriter) {
+        writer.write_slice(&self.0)
+    }
+}
+
+impl Validate for Cff2 {
+    fn validate_impl(&self, _ctx: &mut ValidationCtx) {}
+}
+
+/// An error encoding the outline of a glyph as a charstring.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CharstringError {
+    /// The number of outlines is not the number of masters.
+    MasterCountMismatch { expected: usize, found: usize },
+    /// The outlines of the masters do not have the same structure.
+    IncompatibleMasters,
+    /// A coordinate does not fit in a charstring number.
+    CoordinateOutOfRange,
+    /// A segment has more blended operands than fit on the argument stack.
+    StackOverflow,
+}
+
+/// A builder for the `CFF2` table.
+///
+/// Glyphs are added in glyph id order, as cubic outlines; quadratic curves
+/// are converted to cubics, and coordinates are rounded to integers.
+///
+/// For a variable font, the builder is created with the model of the
+/// masters, and each glyph has an outline for each master. Coordinates that
+/// vary are encoded with `blend` operators, using deltas for the regions of
+/// the model, which are stored in the table's variation store.
+///
+/// The table has a single Font DICT, with an empty Private DICT, and no
+/// subroutines.
+#[derive(Clone, Debug)]
+pub struct Cff2Builder {
+    units_per_em: u16,
+    model: Option<VariationModel>,
+    charstrings: Vec<Vec<u8>>,
+}
+
+impl Cff2Builder {
+    /// Create a builder for a font that does not vary.
+    pub fn new(units_per_em: u16) -> Self {
+        Cff2Builder {
+            units_per_em,
+            model: None,
+            charstrings: Vec::new(),
+        }
+    }
+
+    /// Create a builder for a variable font, with the model of its masters.
+    pub fn variable(units_per_em: u16, model: VariationModel) -> Self {
+        Cff2Builder {
+            model: Some(model),
+            ..Cff2Builder::new(units_per_em)
+        }
+    }
+
+    /// Add a glyph that has the same outline in every master; its id is the
+    /// number of glyphs previously added.
+    pub fn add_glyph(&mut self, path: &BezPath) -> Result<&mut Self, CharstringError> {
+        let charstring = encode_charstring(std::slice::from_ref(path), None)?;
+        self.charstrings.push(charstring);
+        Ok(self)
+    }
+
+    /// Add a glyph with an outline for each master; its id is the number of
+    /// glyphs previously added.
+    ///
+    /// The outlines are in the order of the locations the model was created
+    /// with. For a builder that is not variable, there is a single master.
+    pub fn add_variable_glyph(
+        &mut self,
+        masters: &[BezPath],
+    ) -> Result<&mut Self, CharstringError> {
+        let charstring = encode_charstring(masters, self.model.as_ref())?;
+        self.charstrings.push(charstring);
+        Ok(self)
+    }
+
+    /// Build the table.
+    pub fn build(self) -> Cff2 {
+        let var_store = self.model.as_ref().map(|model| {
+            let regions = model.regions();
+            let data =
+                ItemVariationData::new(0, 0, (0..regions.len() as u16).collect(), Vec::new());
+            let store =
+                ItemVariationStore::new(1, VariationRegionList::new(regions), vec![Some(data)]);
+            let bytes = crate::dump_table(&store).expect("a store with no deltas is always valid");
+            let mut var_store = (bytes.len() as u16).to_be_bytes().to_vec();
+            var_store.extend(bytes);
+            var_store
+        });
+        let global_subrs = encode_index(&[]);
+        let charstrings = encode_index(&self.charstrings);
+
+        // offsets in DICTs are always encoded in five bytes, so the size of
+        // the top DICT does not depend on them.
+        let font_matrix = (self.units_per_em != 1000).then(|| {
+            let scale = 1.0 / self.units_per_em as f64;
+            [scale, 0.0, 0.0, scale, 0.0, 0.0]
+        });
+        let top_dict = |charstrings_offset, var_store_offset, fd_array_offset| {
+            let mut dict = Vec::new();
+            if let Some(matrix) = font_matrix {
+                for value in matrix {
+                    encode_dict_real(value, &mut dict);
+                }
+                dict.extend([ESCAPE, FONT_MATRIX]);
+            }
+            encode_dict_offset(charstrings_offset, &mut dict);
+            dict.push(CHAR_STRINGS);
+            if let Some(offset) = var_store_offset {
+                encode_dict_offset(offset, &mut dict);
+                dict.push(VSTORE);
+            }
+            encode_dict_offset(fd_array_offset, &mut dict);
+            dict.extend([ESCAPE, FD_ARRAY]);
+            dict
+        };
+        let font_dict = |private_offset| {
+            let mut dict = Vec::new();
+            encode_dict_int(0, &mut dict);
+            encode_dict_offset(private_offset, &mut dict);
+            dict.push(PRIVATE);
+            dict
+        };
+
+        const HEADER_SIZE: usize = 5;
+        let top_dict_size = top_dict(0, var_store.as_ref().map(|_| 0), 0).len();
+        let charstrings_offset = HEADER_SIZE + top_dict_size + global_subrs.len();
+        let var_store_offset = charstrings_offset + charstrings.len();
+        let fd_array_offset = var_store_offset + var_store.as_ref().map(Vec::len).unwrap_or(0);
+        let fd_array_size = encode_index(&[font_dict(0)]).len();
+        let private_offset = fd_array_offset + fd_array_size;
+
+        let top_dict = top_dict(
+            charstrings_offset as u32,
+            var_store.as_ref().map(|_| var_store_offset as u32),
+            fd_array_offset as u32,
+        );
+        let mut data = vec![2, 0, HEADER_SIZE as u8];
+        data.extend((top_dict.len() as u16).to_be_bytes());
+        data.extend(top_dict);
+        data.extend(global_subrs);
+        data.extend(charstrings);
+        data.extend(var_store.unwrap_or_default());
+        data.extend(encode_index(&[font_dict(private_offset as u32)]));
+        Cff2(data)
+    }
+}
+
+/// A charstring operand: its value in the default master, and its deltas for
+/// each region of the model.
+struct Operand {
+    value: i32,
+    deltas: Vec<i32>,
+}
+
+impl Operand {
+    fn is_zero(&self) -> bool {
+        self.value == 0 && self.deltas.iter().all(|delta| *delta == 0)
+    }
+}
+
+/// Encode the outlines of the masters of a glyph as a single charstring.
+fn encode_charstring(
+    masters: &[BezPath],
+    model: Option<&VariationModel>,
+) -> Result<Vec<u8>, CharstringError> {
+    let expected = model.map(VariationModel::master_count).unwrap_or(1);
+    if masters.len() != expected {
+        return Err(CharstringError::MasterCountMismatch {
+            expected,
+            found: masters.len(),
+        });
+    }
+    let elements = masters
+        .iter()
+        .map(|path| path.elements())
+        .collect::<Vec<_>>();
+    if elements.iter().any(|els| {
+        els.len() != elements[0].len()
+            || els
+                .iter()
+                .zip(elements[0])
+                .any(|(a, b)| std::mem::discriminant(a) != std::mem::discriminant(b))
+    }) {
+        return Err(CharstringError::IncompatibleMasters);
+    }
+
+    let default_master = model.map(VariationModel::default_master).unwrap_or(0);
+    // the current point of each master, rounded
+    let mut current = vec![(0, 0); masters.len()];
+    let mut charstring = Vec::new();
+    for i in 0..elements[0].len() {
+        // the points of this element in each master
+        let points = elements
+            .iter()
+            .map(|els| match els[i] {
+                PathEl::MoveTo(p) | PathEl::LineTo(p) => vec![p],
+                PathEl::QuadTo(p1, p2) => {
+                    let p0 = els[..i]
+                        .iter()
+                        .rev()
+                        .find_map(end_point)
+                        .unwrap_or_default();
+                    vec![
+                        p0 + (p1 - p0) * (2.0 / 3.0),
+                        p2 + (p1 - p2) * (2.0 / 3.0),
+                        p2,
+                    ]
+                }
+                PathEl::CurveTo(p1, p2, p3) => vec![p1, p2, p3],
+                PathEl::ClosePath => Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        if points[0].is_empty() {
+            continue;
+        }
+        // the relative coordinates of each point, in each master
+        let mut relative = vec![Vec::new(); masters.len()];
+        for (master, points) in points.iter().enumerate() {
+            for point in points {
+                let (x, y) = round(*point)?;
+                let (prev_x, prev_y) = current[master];
+                relative[master].extend([x - prev_x, y - prev_y]);
+                current[master] = (x, y);
+            }
+        }
+        let mut operands = (0..relative[0].len())
+            .map(|j| {
+                let values = relative.iter().map(|rel| rel[j]).collect::<Vec<_>>();
+                Operand {
+                    value: values[default_master],
+                    deltas: model.map(|model| model.deltas(&values)).unwrap_or_default(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let operator = match elements[0][i] {
+            PathEl::MoveTo(_) => RMOVETO,
+            PathEl::LineTo(_) if operands[1].is_zero() => {
+                operands.pop();
+                HLINETO
+            }
+            PathEl::LineTo(_) if operands[0].is_zero() => {
+                operands.remove(0);
+                VLINETO
+            }
+            PathEl::LineTo(_) => RLINETO,
+            _ => RRCURVETO,
+        };
+        encode_operands(&operands, &mut charstring)?;
+        charstring.push(operator);
+    }
+    Ok(charstring)
+}
+
+fn end_point(el: &PathEl) -> Option<Point> {
+    match el {
+        PathEl::MoveTo(p) | PathEl::LineTo(p) | PathEl::QuadTo(_, p) | PathEl::CurveTo(_, _, p) => {
+            Some(*p)
+        }
+        PathEl::ClosePath => None,
+    }
+}
+
+fn round(point: Point) -> Result<(i32, i32), CharstringError> {
+    let round = |value: f64| {
+        let value = (value + 0.5).floor();
+        (i16::MIN as f64..=i16::MAX as f64)
+            .contains(&value)
+            .then_some(value as i32)
+            .ok_or(CharstringError::CoordinateOutOfRange)
+    };
+    Ok((round(point.x)?, round(point.y)?))
+}
+
+/// Push the operands of an operator, blending them if any of them vary.
+fn encode_operands(operands: &[Operand], charstring: &mut Vec<u8>) -> Result<(), CharstringError> {
+    for operand in operands {
+        encode_charstring_int(operand.value, charstring)?;
+    }
+    if operands.iter().all(|op| op.deltas.iter().all(|d| *d == 0)) {
+        return Ok(());
+    }
+    let region_count = operands[0].deltas.len();
+    if operands.len() * (region_count + 1) + 1 > MAX_STACK {
+        return Err(CharstringError::StackOverflow);
+    }
+    for operand in operands {
+        for delta in &operand.deltas {
+            encode_charstring_int(*delta, charstring)?;
+        }
+    }
+    encode_charstring_int(operands.len() as i32, charstring)?;
+    charstring.push(BLEND);
+    Ok(())
+}
+
+/// Encode an integer in a charstring.
+fn encode_charstring_int(value: i32, out: &mut Vec<u8>) -> Result<(), CharstringError> {
+    match value {
+        -1131..=1131 => encode_small_int(value, out),
+        _ => {
+            let value = i16::try_from(value).map_err(|_| CharstringError::CoordinateOutOfRange)?;
+            out.push(28);
+            out.extend(value.to_be_bytes());
+        }
+    }
+    Ok(())
+}
+
+/// Encode an integer in a DICT.
+fn encode_dict_int(value: i32, out: &mut Vec<u8>) {
+    match value {
+        -1131..=1131 => encode_small_int(value, out),
+        _ => match i16::try_from(value) {
+            Ok(value) => {
+                out.push(28);
+                out.extend(value.to_be_bytes());
+            }
+            Err(_) => {
+                out.push(29);
+                out.extend(value.to_be_bytes());
+            }
+        },
+    }
+}
+
+/// Encode an offset in a DICT; this always takes five bytes.
+fn encode_dict_offset(offset: u32, out: &mut Vec<u8>) {
+    out.push(29);
+    out.extend(offset.to_be_bytes());
+}
+
+/// The one and two byte encodings shared by charstrings and DICTs.
+fn encode_small_int(value: i32, out: &mut Vec<u8>) {
+    match value {
+        -107..=107 => out.push((value + 139) as u8),
+        108..=1131 => {
+            let value = value - 108;
+            out.extend([(value >> 8) as u8 + 247, value as u8]);
+        }
+        _ => {
+            let value = -value - 108;
+            out.extend([(value >> 8) as u8 + 251, value as u8]);
+        }
+    }
+}
+
+/// Encode a real number in a DICT, as a string of nibbles.
+fn encode_dict_real(value: f64, out: &mut Vec<u8>) {
+    let text = format!("{value}");
+    let mut nibbles = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '0'..='9' => nibbles.push(c as u8 - b'0'),
+            '.' => nibbles.push(0xa),
+            '-' => nibbles.push(0xe),
+            'e' | 'E' if chars.peek() == Some(&'-') => {
+                chars.next();
+                nibbles.push(0xc);
+            }
+            'e' | 'E' => nibbles.push(0xb),
+            _ => (),
+        }
+    }
+    nibbles.push(0xf);
+    if nibbles.len() % 2 == 1 {
+        nibbles.push(0xf);
+    }
+    out.push(30);
+    out.extend(nibbles.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+}
+
+/// Encode a CFF2 INDEX: a count, the offset of each item, and the items.
+fn encode_index(items: &[Vec<u8>]) -> Vec<u8> {
+    let mut out = (items.len() as u32).to_be_bytes().to_vec();
+    if items.is_empty() {
+        return out;
+    }
+    // offsets are one-based
+    let mut offsets = vec![1u32];
+    for item in items {
+        offsets.push(offsets.last().unwrap() + item.len() as u32);
+    }
+    let off_size = match *offsets.last().unwrap() {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        0x10000..=0xFFFFFF => 3,
+        _ => 4,
+    };
+    out.push(off_size as u8);
+    for offset in offsets {
+        out.extend(&offset.to_be_bytes()[4 - off_size..]);
+    }
+    for item in items {
+        out.extend(item);
+    }
+    out
+}
+
+impl std::fmt::Display for CharstringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CharstringError::MasterCountMismatch { expected, found } => {
+                write!(
+                    f,
+                    "expected an outline for {expected} masters, found {found}"
+                )
+            }
+            CharstringError::IncompatibleMasters => {
+                f.write_str("the outlines of the masters are not compatible")
+            }
+            CharstringError::CoordinateOutOfRange => {
+                f.write_str("a coordinate is too large for a charstring")
+            }
+            CharstringError::StackOverflow => {
+                f.write_str("too many blended operands for the argument stack")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CharstringError {}
+
+#[cfg(test)]
+mod tests {
+    use types::F2Dot14;
+
+    use super::*;
+
+    fn square(size: f64) -> BezPath {
+        let mut path = BezPath::new();
+        path.move_to((10.0, 0.0));
+        path.line_to((10.0 + size, 0.0));
+        path.line_to((10.0 + size, size));
+        path.curve_to((size, size + 50.0), (20.0, size + 50.0), (10.0, size));
+        path.close_path();
+        path
+    }
+
+    /// Read the items of the INDEX at the start of the data, and its length.
+    fn read_index(data: &[u8]) -> (Vec<&[u8]>, usize) {
+        let count = u32::from_be_bytes(data[..4].try_into().unwrap()) as usize;
+        if count == 0 {
+            return (Vec::new(), 4);
+        }
+        let off_size = data[4] as usize;
+        let offset = |i: usize| {
+            let start = 5 + i * off_size;
+            data[start..start + off_size]
+                .iter()
+                .fold(0usize, |acc, b| acc << 8 | *b as usize)
+        };
+        let data_start = 5 + (count + 1) * off_size - 1;
+        let items = (0..count)
+            .map(|i| &data[data_start + offset(i)..data_start + offset(i + 1)])
+            .collect();
+        (items, data_start + offset(count))
+    }
+
+    #[test]
+    fn numbers() {
+        let encode = |value| {
+            let mut out = Vec::new();
+            encode_charstring_int(value, &mut out).unwrap();
+            out
+        };
+        assert_eq!(encode(0), [139]);
+        assert_eq!(encode(-107), [32]);
+        assert_eq!(encode(108), [247, 0]);
+        assert_eq!(encode(1131), [250, 255]);
+        assert_eq!(encode(-1131), [254, 255]);
+        assert_eq!(encode(2000), [28, 0x07, 0xd0]);
+        let mut out = Vec::new();
+        assert_eq!(
+            encode_charstring_int(40000, &mut out),
+            Err(CharstringError::CoordinateOutOfRange)
+        );
+
+        let mut real = Vec::new();
+        encode_dict_real(-2.25, &mut real);
+        assert_eq!(real, [30, 0xe2, 0xa2, 0x5f]);
+        real.clear();
+        encode_dict_real(0.00048828125, &mut real);
+        assert_eq!(real[..3], [30, 0x0a, 0x00]);
+    }
+
+    #[test]
+    fn static_charstring() {
+        let charstring = encode_charstring(&[square(100.0)], None).unwrap();
+        assert_eq!(
+            charstring,
+            [
+                149, 139, RMOVETO, // 10 0
+                239, HLINETO, // 100
+                239, VLINETO, // 100
+                129, 189, 59, 139, 129, 89, RRCURVETO, // -10 50, -80 0, -10 -50
+            ]
+        );
+    }
+
+    #[test]
+    fn blended_charstring() {
+        let model =
+            VariationModel::new(vec![vec![F2Dot14::ZERO], vec![F2Dot14::from_f32(1.0)]]).unwrap();
+        let charstring = encode_charstring(&[square(100.0), square(120.0)], Some(&model)).unwrap();
+        // the move does not vary, the lines vary by 20, and only the second
+        // control point of the curve varies
+        assert_eq!(
+            charstring,
+            [
+                149, 139, RMOVETO, // 10 0
+                239, 159, 140, BLEND, HLINETO, // 100 +20
+                239, 159, 140, BLEND, VLINETO, // 100 +20
+                129, 189, 59, 139, 129, 89, // -10 50, -80 0, -10 -50
+                139, 139, 119, 139, 139, 139, // deltas: 0 0, -20 0, 0 0
+                145, BLEND, RRCURVETO,
+            ]
+        );
+
+        let incompatible = encode_charstring(&[square(100.0), BezPath::new()], Some(&model));
+        assert_eq!(incompatible, Err(CharstringError::IncompatibleMasters));
+    }
+
+    #[test]
+    fn table_structure() {
+        let model =
+            VariationModel::new(vec![vec![F2Dot14::ZERO], vec![F2Dot14::from_f32(1.0)]]).unwrap();
+        let mut builder = Cff2Builder::variable(2048, model);
+        builder.add_glyph(&BezPath::new()).unwrap();
+        builder
+            .add_variable_glyph(&[square(100.0), square(120.0)])
+            .unwrap();
+        let data = builder.build().0;
+
+        assert_eq!(data[..3], [2, 0, 5]);
+        let top_dict_len = u16::from_be_bytes([data[3], data[4]]) as usize;
+        let top_dict = &data[5..5 + top_dict_len];
+        // font matrix, then three five-byte offsets
+        assert_eq!(top_dict[0], 30);
+        let offset = |op: &[u8]| {
+            let pos = top_dict
+                .windows(op.len())
+                .rposition(|window| window == op)
+                .unwrap();
+            assert_eq!(top_dict[pos - 5], 29);
+            u32::from_be_bytes(top_dict[pos - 4..pos].try_into().unwrap()) as usize
+        };
+        let charstrings = offset(&[CHAR_STRINGS]);
+        let var_store = offset(&[VSTORE]);
+        let fd_array = offset(&[ESCAPE, FD_ARRAY]);
+
+        // an empty global subrs INDEX follows the top DICT
+        assert_eq!(read_index(&data[5 + top_dict_len..]).1, 4);
+        assert_eq!(charstrings, 5 + top_dict_len + 4);
+        let (glyphs, len) = read_index(&data[charstrings..]);
+        assert_eq!(glyphs.len(), 2);
+        assert!(glyphs[0].is_empty());
+        assert_eq!(var_store, charstrings + len);
+        let store_len = u16::from_be_bytes([data[var_store], data[var_store + 1]]) as usize;
+        assert_eq!(fd_array, var_store + 2 + store_len);
+        let (font_dicts, len) = read_index(&data[fd_array..]);
+        assert_eq!(font_dicts.len(), 1);
+        // an empty private DICT, at the end of the table
+        assert_eq!(font_dicts[0][0], 139);
+        assert_eq!(*font_dicts[0].last().unwrap(), PRIVATE);
+        assert_eq!(data.len(), fd_array + len);
+    }
+}
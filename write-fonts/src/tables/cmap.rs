@@ -0,0 +1,547 @@
+//! The [cmap](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap) table
+
+include!("../../generated/generated_cmap.rs");
+
+use std::collections::BTreeMap;
+
+// https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#unicode-platform-platform-id--0
+const UNICODE_BMP_ENCODING: u16 = 3;
+const UNICODE_FULL_ENCODING: u16 = 4;
+const UNICODE_VARIATION_ENCODING: u16 = 5;
+// https://learn.microsoft.com/en-us/typography/opentype/spec/cmap#windows-platform-platform-id--3
+const WINDOWS_BMP_ENCODING: u16 = 1;
+const WINDOWS_FULL_ENCODING: u16 = 10;
+
+/// The size of a segment in a format 4 subtable.
+const CMAP4_SEGMENT_LEN: usize = 4 * u16::RAW_BYTE_LEN;
+
+/// A builder for a [`Cmap`] table.
+///
+/// The subtable formats are chosen based on the mappings: a format 4
+/// subtable is used for the Basic Multilingual Plane, and a format 12 subtable
+/// is added if any codepoints are outside of it. Each subtable is referenced by
+/// both a Unicode and a Windows encoding record, and the data is shared between
+/// them.
+///
+/// Unicode variation sequences are stored in a format 14 subtable.
+#[derive(Clone, Debug, Default)]
+pub struct CmapBuilder {
+    mappings: BTreeMap<char, GlyphId>,
+    // selector -> base -> glyph
+    variation_sequences: BTreeMap<char, BTreeMap<char, GlyphId>>,
+}
+
+impl CmapBuilder {
+    /// Create a new builder with the provided nominal glyph mappings.
+    pub fn new(mappings: BTreeMap<char, GlyphId>) -> Self {
+        CmapBuilder {
+            mappings,
+            variation_sequences: BTreeMap::new(),
+        }
+    }
+
+    /// Add a mapping from a codepoint to a glyph.
+    pub fn add_mapping(&mut self, codepoint: char, glyph: GlyphId) -> &mut Self {
+        self.mappings.insert(codepoint, glyph);
+        self
+    }
+
+    /// Add a mapping for the sequence of `base` followed by the variation
+    /// selector `selector`.
+    ///
+    /// If `glyph` is the glyph that `base` is mapped to, the sequence is
+    /// recorded as using the default glyph.
+    pub fn add_variation_sequence(
+        &mut self,
+        base: char,
+        selector: char,
+        glyph: GlyphId,
+    ) -> &mut Self {
+        self.variation_sequences
+            .entry(selector)
+            .or_default()
+            .insert(base, glyph);
+        self
+    }
+
+    /// Build the table.
+    pub fn build(&self) -> Cmap {
+        let bmp = Cmap4::from_mappings(
+            self.mappings
+                .range(..='\u{FFFF}')
+                .map(|(cp, gid)| (*cp as u16, *gid)),
+        );
+        let needs_full = self.mappings.keys().any(|cp| *cp > '\u{FFFF}');
+        // the format 4 length is 16 bits, so we may need to use format 12
+        let bmp = bmp.filter(|cmap4| cmap4.compute_length() <= u16::MAX as usize);
+        let full = (needs_full || bmp.is_none()).then(|| {
+            CmapSubtable::Format12(Cmap12::from_mappings(
+                self.mappings.iter().map(|(cp, gid)| (*cp as u32, *gid)),
+            ))
+        });
+        let uvs = (!self.variation_sequences.is_empty()).then(|| {
+            CmapSubtable::Format14(Cmap14::from_variation_sequences(
+                &self.variation_sequences,
+                &self.mappings,
+            ))
+        });
+
+        let bmp = bmp.map(CmapSubtable::Format4);
+        let mut records = Vec::new();
+        let mut add_record = |platform, encoding, subtable: &Option<CmapSubtable>| {
+            if let Some(subtable) = subtable {
+                records.push(EncodingRecord::new(platform, encoding, subtable.clone()));
+            }
+        };
+        // records must be sorted by platform and encoding
+        add_record(PlatformId::Unicode, UNICODE_BMP_ENCODING, &bmp);
+        add_record(PlatformId::Unicode, UNICODE_FULL_ENCODING, &full);
+        add_record(PlatformId::Unicode, UNICODE_VARIATION_ENCODING, &uvs);
+        add_record(PlatformId::Windows, WINDOWS_BMP_ENCODING, &bmp);
+        add_record(PlatformId::Windows, WINDOWS_FULL_ENCODING, &full);
+        Cmap::new(0, records)
+    }
+}
+
+impl Cmap {
+    /// Create a new table from a set of nominal glyph mappings.
+    ///
+    /// See [`CmapBuilder`] for details, or to add variation sequences.
+    pub fn from_mappings(mappings: BTreeMap<char, GlyphId>) -> Self {
+        CmapBuilder::new(mappings).build()
+    }
+}
+
+impl Cmap2 {
+    fn compute_length(&self) -> usize {
+        // format, length and language
+        const HEADER_LEN: usize = 3 * u16::RAW_BYTE_LEN;
+        HEADER_LEN + self.sub_header_keys.len() * u16::RAW_BYTE_LEN + self.sub_header_data.len()
+    }
+}
+
+/// A segment of a format 4 subtable.
+struct Cmap4Segment {
+    start: u16,
+    end: u16,
+    // if `None`, glyphs are found by adding the delta to the codepoint
+    glyphs: Option<Vec<u16>>,
+    delta: i16,
+}
+
+impl Cmap4 {
+    /// Create a new subtable from mappings, sorted by codepoint.
+    ///
+    /// Each run of contiguous codepoints becomes a single segment if its glyphs
+    /// are consecutive; otherwise it either is split into segments with
+    /// consecutive glyphs or stores its glyphs explicitly, whichever is
+    /// smaller.
+    fn from_mappings(mappings: impl Iterator<Item = (u16, GlyphId)>) -> Option<Self> {
+        let mut segments = Vec::new();
+        let mappings = mappings.collect::<Vec<_>>();
+        for run in runs(&mappings, |prev, next| next.0 == prev.0 + 1) {
+            let sequential = runs(run, |prev, next| {
+                next.1.to_u16() == prev.1.to_u16().wrapping_add(1)
+            })
+            .collect::<Vec<_>>();
+            if sequential.len() * CMAP4_SEGMENT_LEN > CMAP4_SEGMENT_LEN + run.len() * 2 {
+                segments.push(Cmap4Segment {
+                    start: run[0].0,
+                    end: run[run.len() - 1].0,
+                    glyphs: Some(run.iter().map(|(_, gid)| gid.to_u16()).collect()),
+                    delta: 0,
+                });
+            } else {
+                segments.extend(sequential.into_iter().map(|seq| Cmap4Segment {
+                    start: seq[0].0,
+                    end: seq[seq.len() - 1].0,
+                    glyphs: None,
+                    delta: seq[0].1.to_u16().wrapping_sub(seq[0].0) as i16,
+                }));
+            }
+        }
+        // the last segment must end at 0xFFFF
+        if segments.last().map(|seg| seg.end) != Some(0xFFFF) {
+            segments.push(Cmap4Segment {
+                start: 0xFFFF,
+                end: 0xFFFF,
+                glyphs: None,
+                delta: 1,
+            });
+        }
+
+        let seg_count = segments.len();
+        let mut id_range_offsets = Vec::with_capacity(seg_count);
+        let mut glyph_id_array = Vec::new();
+        for (i, segment) in segments.iter().enumerate() {
+            match &segment.glyphs {
+                Some(glyphs) => {
+                    // relative to this entry in the id_range_offsets array
+                    let offset = (seg_count - i + glyph_id_array.len()) * u16::RAW_BYTE_LEN;
+                    id_range_offsets.push(offset.try_into().ok()?);
+                    glyph_id_array.extend(glyphs);
+                }
+                None => id_range_offsets.push(0),
+            }
+        }
+        Some(Cmap4::new(
+            0,
+            segments.iter().map(|seg| seg.end).collect(),
+            segments.iter().map(|seg| seg.start).collect(),
+            segments.iter().map(|seg| seg.delta).collect(),
+            id_range_offsets,
+            glyph_id_array,
+        ))
+    }
+
+    fn compute_length(&self) -> usize {
+        // format, length, language, seg_count_x2, search_range,
+        // entry_selector, range_shift, reserved_pad
+        const FIXED_LEN: usize = 8 * u16::RAW_BYTE_LEN;
+        FIXED_LEN
+            + self.end_code.len() * CMAP4_SEGMENT_LEN
+            + self.glyph_id_array.len() * u16::RAW_BYTE_LEN
+    }
+
+    fn compute_seg_count_x2(&self) -> u16 {
+        (self.end_code.len() * 2) as u16
+    }
+
+    fn compute_search_range(&self) -> u16 {
+        2 * (1u16 << self.compute_entry_selector())
+    }
+
+    fn compute_entry_selector(&self) -> u16 {
+        (self.end_code.len().max(1).ilog2()) as u16
+    }
+
+    fn compute_range_shift(&self) -> u16 {
+        self.compute_seg_count_x2()
+            .saturating_sub(self.compute_search_range())
+    }
+}
+
+impl Cmap12 {
+    /// Create a new subtable from mappings, sorted by codepoint.
+    fn from_mappings(mappings: impl Iterator<Item = (u32, GlyphId)>) -> Self {
+        let mappings = mappings.collect::<Vec<_>>();
+        let groups = runs(&mappings, |prev, next| {
+            next.0 == prev.0 + 1 && next.1.to_u16() == prev.1.to_u16().wrapping_add(1)
+        })
+        .map(|run| {
+            let (start, gid) = run[0];
+            let end = run[run.len() - 1].0;
+            SequentialMapGroup::new(start, end, gid.to_u16() as u32)
+        })
+        .collect();
+        Cmap12::new(0, groups)
+    }
+
+    fn compute_length(&self) -> usize {
+        // format, reserved, length, language, num_groups
+        const FIXED_LEN: usize = 2 * u16::RAW_BYTE_LEN + 3 * u32::RAW_BYTE_LEN;
+        const GROUP_LEN: usize = 3 * u32::RAW_BYTE_LEN;
+        FIXED_LEN + GROUP_LEN * self.groups.len()
+    }
+}
+
+impl Cmap14 {
+    fn from_variation_sequences(
+        sequences: &BTreeMap<char, BTreeMap<char, GlyphId>>,
+        mappings: &BTreeMap<char, GlyphId>,
+    ) -> Self {
+        let records = sequences
+            .iter()
+            .map(|(selector, bases)| {
+                let (default, non_default): (Vec<_>, Vec<_>) = bases
+                    .iter()
+                    .partition(|(base, gid)| mappings.get(*base) == Some(*gid));
+                let default = default
+                    .into_iter()
+                    .map(|(base, _)| *base as u32)
+                    .collect::<Vec<_>>();
+                let default_uvs = (!default.is_empty()).then(|| {
+                    DefaultUvs::new(
+                        runs(&default, |prev, next| *next == prev + 1)
+                            .flat_map(|run| run.chunks(u8::MAX as usize + 1))
+                            .map(|run| {
+                                UnicodeRange::new(Uint24::new(run[0]), (run.len() - 1) as u8)
+                            })
+                            .collect(),
+                    )
+                });
+                let non_default_uvs = (!non_default.is_empty()).then(|| {
+                    NonDefaultUvs::new(
+                        non_default
+                            .into_iter()
+                            .map(|(base, gid)| {
+                                UvsMapping::new(Uint24::new(*base as u32), gid.to_u16())
+                            })
+                            .collect(),
+                    )
+                });
+                VariationSelector::new(Uint24::new(*selector as u32), default_uvs, non_default_uvs)
+            })
+            .collect();
+        Cmap14::new(records)
+    }
+
+    fn compute_length(&self) -> usize {
+        // format, length, num_var_selector_records
+        const FIXED_LEN: usize = u16::RAW_BYTE_LEN + 2 * u32::RAW_BYTE_LEN;
+        // var_selector, default_uvs_offset, non_default_uvs_offset
+        const RECORD_LEN: usize = Uint24::RAW_BYTE_LEN + 2 * u32::RAW_BYTE_LEN;
+        const UNICODE_RANGE_LEN: usize = Uint24::RAW_BYTE_LEN + u8::RAW_BYTE_LEN;
+        const UVS_MAPPING_LEN: usize = Uint24::RAW_BYTE_LEN + u16::RAW_BYTE_LEN;
+        // the length includes the default and non-default uvs tables
+        let subtables_len: usize = self
+            .var_selector
+            .iter()
+            .map(|record| {
+                record.default_uvs.as_ref().map_or(0, |uvs| {
+                    u32::RAW_BYTE_LEN + uvs.ranges.len() * UNICODE_RANGE_LEN
+                }) + record.non_default_uvs.as_ref().map_or(0, |uvs| {
+                    u32::RAW_BYTE_LEN + uvs.uvs_mapping.len() * UVS_MAPPING_LEN
+                })
+            })
+            .sum();
+        FIXED_LEN + self.var_selector.len() * RECORD_LEN + subtables_len
+    }
+}
+
+/// Split `items` into runs, where each pair of adjacent items in a run
+/// satisfies `same_run(prev, next)`.
+fn runs<T>(items: &[T], same_run: impl Fn(&T, &T) -> bool) -> impl Iterator<Item = &[T]> {
+    let mut remaining = items;
+    std::iter::from_fn(move || {
+        if remaining.is_empty() {
+            return None;
+        }
+        let len = 1 + remaining
+            .windows(2)
+            .take_while(|pair| same_run(&pair[0], &pair[1]))
+            .count();
+        let (run, rest) = remaining.split_at(len);
+        remaining = rest;
+        Some(run)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use read::{tables::cmap as read_cmap, FontData, FontRead};
+
+    use super::*;
+
+    fn round_trip(cmap: &Cmap) -> Vec<u8> {
+        crate::dump_table(cmap).unwrap()
+    }
+
+    fn records(cmap: &read_cmap::Cmap) -> Vec<(PlatformId, u16, u32)> {
+        cmap.encoding_records()
+            .iter()
+            .map(|rec| {
+                (
+                    rec.platform_id(),
+                    rec.encoding_id(),
+                    rec.subtable_offset().to_u32(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bmp_only() {
+        let mappings = [('a', 1), ('b', 2), ('c', 3), ('z', 10), ('\u{20AC}', 4)]
+            .into_iter()
+            .map(|(cp, gid)| (cp, GlyphId::new(gid)))
+            .collect::<BTreeMap<_, _>>();
+        let bytes = round_trip(&Cmap::from_mappings(mappings.clone()));
+        let cmap = read_cmap::Cmap::read(FontData::new(&bytes)).unwrap();
+
+        let records = records(&cmap);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, PlatformId::Unicode);
+        assert_eq!(records[1].0, PlatformId::Windows);
+        assert_eq!(records[1].1, WINDOWS_BMP_ENCODING);
+        // the subtable is shared
+        assert_eq!(records[0].2, records[1].2);
+
+        let Ok(read_cmap::CmapSubtable::Format4(cmap4)) =
+            cmap.encoding_records()[0].subtable(cmap.offset_data())
+        else {
+            panic!("expected format 4");
+        };
+        // 'a'..='c', 'z', '€', and the final segment
+        assert_eq!(cmap4.seg_count_x2(), 8);
+        assert_eq!(cmap4.search_range(), 8);
+        assert_eq!(cmap4.entry_selector(), 2);
+        assert_eq!(cmap4.range_shift(), 0);
+        assert_eq!(cmap4.length() as usize, cmap4.offset_data().len());
+        for (cp, gid) in &mappings {
+            assert_eq!(cmap.map_codepoint(*cp), Some(*gid));
+        }
+        assert_eq!(cmap.map_codepoint('d'), None);
+    }
+
+    #[test]
+    fn glyph_id_array() {
+        // contiguous codepoints with unordered glyphs are cheaper to store
+        // as an array than as one segment per glyph
+        let mappings = ('a'..='z')
+            .zip([7u16, 3, 9, 1, 20, 14, 2, 5, 30, 11].into_iter().cycle())
+            .map(|(cp, gid)| (cp, GlyphId::new(gid)))
+            .collect::<BTreeMap<_, _>>();
+        let bytes = round_trip(&Cmap::from_mappings(mappings.clone()));
+        let cmap = read_cmap::Cmap::read(FontData::new(&bytes)).unwrap();
+        let Ok(read_cmap::CmapSubtable::Format4(cmap4)) =
+            cmap.encoding_records()[0].subtable(cmap.offset_data())
+        else {
+            panic!("expected format 4");
+        };
+        assert_eq!(cmap4.seg_count_x2(), 4);
+        assert_eq!(cmap4.glyph_id_array().len(), 26);
+        for (cp, gid) in &mappings {
+            assert_eq!(cmap4.map_codepoint(*cp), Some(*gid));
+        }
+    }
+
+    #[test]
+    fn supplementary_planes() {
+        let mappings = [('a', 1), ('b', 2), ('\u{1F600}', 3), ('\u{1F601}', 4)]
+            .into_iter()
+            .map(|(cp, gid)| (cp, GlyphId::new(gid)))
+            .collect::<BTreeMap<_, _>>();
+        let bytes = round_trip(&Cmap::from_mappings(mappings.clone()));
+        let cmap = read_cmap::Cmap::read(FontData::new(&bytes)).unwrap();
+
+        let records = records(&cmap);
+        let ids = records
+            .iter()
+            .map(|(platform, encoding, _)| (*platform, *encoding))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [
+                (PlatformId::Unicode, UNICODE_BMP_ENCODING),
+                (PlatformId::Unicode, UNICODE_FULL_ENCODING),
+                (PlatformId::Windows, WINDOWS_BMP_ENCODING),
+                (PlatformId::Windows, WINDOWS_FULL_ENCODING),
+            ]
+        );
+        assert_eq!(records[0].2, records[2].2);
+        assert_eq!(records[1].2, records[3].2);
+        assert_ne!(records[0].2, records[1].2);
+
+        let Ok(read_cmap::CmapSubtable::Format12(cmap12)) =
+            cmap.encoding_records()[1].subtable(cmap.offset_data())
+        else {
+            panic!("expected format 12");
+        };
+        assert_eq!(cmap12.num_groups(), 2);
+        assert_eq!(cmap12.length() as usize, cmap12.offset_data().len());
+        for (cp, gid) in &mappings {
+            assert_eq!(cmap12.map_codepoint(*cp), Some(*gid));
+        }
+    }
+
+    #[test]
+    fn format_2_round_trip() {
+        // a single subHeader for one-byte codes, mapping 'A'..='C'
+        let mut sub_header_data = Vec::new();
+        for value in [0x41u16, 3, 0, 2] {
+            sub_header_data.extend(value.to_be_bytes());
+        }
+        for gid in [1u16, 2, 3] {
+            sub_header_data.extend(gid.to_be_bytes());
+        }
+        let subtable = CmapSubtable::format_2(0, vec![0; 256], sub_header_data.clone());
+        let cmap = Cmap::new(
+            0,
+            vec![EncodingRecord::new(PlatformId::Macintosh, 0, subtable)],
+        );
+        let bytes = round_trip(&cmap);
+        let cmap = read_cmap::Cmap::read(FontData::new(&bytes)).unwrap();
+        let subtable = cmap.encoding_records()[0]
+            .subtable(cmap.offset_data())
+            .unwrap();
+        let read_cmap::CmapSubtable::Format2(subtable) = subtable else {
+            panic!("expected format 2 subtable");
+        };
+        assert_eq!(subtable.length() as usize, 518 + sub_header_data.len());
+        assert_eq!(subtable.sub_header_data(), sub_header_data);
+    }
+
+    #[test]
+    fn format_4_overflow() {
+        // every other codepoint, so each needs its own segment
+        let mappings = (0..20_000u32)
+            .filter_map(|i| char::from_u32(i * 2))
+            .enumerate()
+            .map(|(i, cp)| (cp, GlyphId::new(i as u16 + 1)))
+            .collect::<BTreeMap<_, _>>();
+        let bytes = round_trip(&Cmap::from_mappings(mappings.clone()));
+        let cmap = read_cmap::Cmap::read(FontData::new(&bytes)).unwrap();
+        let ids = records(&cmap)
+            .iter()
+            .map(|(platform, encoding, _)| (*platform, *encoding))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [
+                (PlatformId::Unicode, UNICODE_FULL_ENCODING),
+                (PlatformId::Windows, WINDOWS_FULL_ENCODING),
+            ]
+        );
+        for (cp, gid) in mappings.iter().step_by(97) {
+            assert_eq!(cmap.map_codepoint(*cp), Some(*gid));
+        }
+    }
+
+    #[test]
+    fn variation_sequences() {
+        let mut builder = CmapBuilder::default();
+        builder
+            .add_mapping('a', GlyphId::new(1))
+            .add_mapping('b', GlyphId::new(2))
+            .add_mapping('c', GlyphId::new(3))
+            .add_variation_sequence('a', '\u{FE00}', GlyphId::new(1))
+            .add_variation_sequence('b', '\u{FE00}', GlyphId::new(2))
+            .add_variation_sequence('c', '\u{FE00}', GlyphId::new(7))
+            .add_variation_sequence('a', '\u{FE01}', GlyphId::new(8));
+        let bytes = round_trip(&builder.build());
+        let cmap = read_cmap::Cmap::read(FontData::new(&bytes)).unwrap();
+        let record = cmap
+            .encoding_records()
+            .iter()
+            .find(|rec| rec.encoding_id() == UNICODE_VARIATION_ENCODING)
+            .unwrap();
+        let Ok(read_cmap::CmapSubtable::Format14(cmap14)) = record.subtable(cmap.offset_data())
+        else {
+            panic!("expected format 14");
+        };
+        let data = cmap14.offset_data();
+        assert_eq!(cmap14.length() as usize, data.len());
+        let selectors = cmap14.var_selector();
+        assert_eq!(selectors.len(), 2);
+
+        let fe00 = &selectors[0];
+        assert_eq!(fe00.var_selector(), Uint24::new(0xFE00));
+        let default = fe00.default_uvs(data).unwrap().unwrap();
+        let ranges = default.ranges();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].start_unicode_value(), Uint24::new('a' as u32));
+        assert_eq!(ranges[0].additional_count(), 1);
+        let non_default = fe00.non_default_uvs(data).unwrap().unwrap();
+        let mapping = &non_default.uvs_mapping()[0];
+        assert_eq!(mapping.unicode_value(), Uint24::new('c' as u32));
+        assert_eq!(mapping.glyph_id(), 7);
+
+        let fe01 = &selectors[1];
+        assert!(fe01.default_uvs(data).is_none());
+        assert_eq!(
+            fe01.non_default_uvs(data).unwrap().unwrap().uvs_mapping()[0].glyph_id(),
+            8
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! The [COLR (Color)](https://learn.microsoft.com/en-us/typography/opentype/spec/colr) table
+
+include!("../../generated/generated_colr.rs");
+
+use std::collections::{BTreeMap, HashMap};
+
+use super::variations::{DeltaSetIndexMap, ItemVariationStore};
+
+/// The maximum number of layers in a [PaintColrLayers].
+const MAX_LAYERS: usize = u8::MAX as usize;
+
+impl Colr {
+    fn compute_version(&self) -> u16 {
+        if self.base_glyph_list.is_some()
+            || self.layer_list.is_some()
+            || self.clip_list.is_some()
+            || self.var_index_map.is_some()
+            || self.item_variation_store.is_some()
+        {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn compute_num_base_glyph_records(&self) -> u16 {
+        self.base_glyph_records
+            .as_ref()
+            .map(|records| records.len() as u16)
+            .unwrap_or_default()
+    }
+
+    fn compute_num_layer_records(&self) -> u16 {
+        self.layer_records
+            .as_ref()
+            .map(|records| records.len() as u16)
+            .unwrap_or_default()
+    }
+}
+
+/// A builder for the COLR table.
+///
+/// Color glyphs can be described either as a list of layers, each a glyph
+/// filled with a single palette color (version 0), or as a graph of paints
+/// (version 1). A version 1 table is built if any glyph has a paint graph.
+///
+/// Lists of layers in paint graphs are added with
+/// [`add_layers`](Self::add_layers), which shares layers between glyphs: a
+/// list of layers that has been added before is reused, and runs of layers
+/// that match an earlier list are replaced by a reference to it.
+#[derive(Clone, Debug, Default)]
+pub struct ColrBuilder {
+    v0_glyphs: BTreeMap<GlyphId, Vec<Layer>>,
+    v1_glyphs: BTreeMap<GlyphId, Paint>,
+    clip_boxes: BTreeMap<GlyphId, ClipBox>,
+    layers: Vec<Paint>,
+    // the id of each layer in `layers`; identical paints have the same id
+    layer_ids: Vec<u32>,
+    paint_ids: HashMap<Vec<u8>, u32>,
+    next_paint_id: u32,
+    // every run of two or more layers in `layers` that was added as (part of)
+    // a list, and the index of its first layer
+    layer_runs: HashMap<Vec<u32>, u32>,
+    var_store: Option<(ItemVariationStore, Option<DeltaSetIndexMap>)>,
+}
+
+impl ColrBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the layers of a version 0 color glyph, from bottom to top.
+    pub fn set_v0_glyph(&mut self, glyph: GlyphId, layers: Vec<Layer>) {
+        self.v0_glyphs.insert(glyph, layers);
+    }
+
+    /// Set the root of the paint graph of a version 1 color glyph.
+    pub fn set_v1_glyph(&mut self, glyph: GlyphId, paint: Paint) {
+        self.v1_glyphs.insert(glyph, paint);
+    }
+
+    /// Set the clip box of a version 1 color glyph.
+    pub fn set_clip_box(&mut self, glyph: GlyphId, clip_box: ClipBox) {
+        self.clip_boxes.insert(glyph, clip_box);
+    }
+
+    /// Set the variation data referenced by variable paints, clip boxes and
+    /// color stops.
+    pub fn set_var_store(
+        &mut self,
+        var_store: ItemVariationStore,
+        var_index_map: Option<DeltaSetIndexMap>,
+    ) {
+        self.var_store = Some((var_store, var_index_map));
+    }
+
+    /// Add a list of paints, from bottom to top, returning a paint that draws
+    /// all of them.
+    ///
+    /// This is generally a [PaintColrLayers] referencing the layer list. A
+    /// single layer is returned as is, and lists of more than 255 layers are
+    /// split into nested [PaintColrLayers].
+    pub fn add_layers(&mut self, layers: Vec<Paint>) -> Paint {
+        let ids = layers
+            .iter()
+            .map(|paint| self.paint_id(paint))
+            .collect::<Vec<_>>();
+        if let Some(first) = self.layer_runs.get(&ids) {
+            return Paint::colr_layers(ids.len() as u8, *first);
+        }
+
+        // replace the longest runs that match earlier lists
+        let mut new_layers = Vec::with_capacity(layers.len());
+        let mut new_ids = Vec::with_capacity(layers.len());
+        let mut layers = layers.into_iter();
+        let mut i = 0;
+        while i < ids.len() {
+            let reused = (i + 2..=ids.len())
+                .rev()
+                .filter(|end| end - i < ids.len() && end - i <= MAX_LAYERS)
+                .find_map(|end| Some((end, *self.layer_runs.get(&ids[i..end])?)));
+            let paint = match reused {
+                Some((end, first)) => {
+                    layers.nth(end - i - 1);
+                    let paint = Paint::colr_layers((end - i) as u8, first);
+                    i = end;
+                    paint
+                }
+                None => {
+                    i += 1;
+                    layers.next().unwrap()
+                }
+            };
+            new_ids.push(self.paint_id(&paint));
+            new_layers.push(paint);
+        }
+
+        if new_layers.len() == 1 {
+            return new_layers.pop().unwrap();
+        }
+        if new_layers.len() > MAX_LAYERS {
+            let mut chunks = Vec::new();
+            while !new_layers.is_empty() {
+                let rest = new_layers.split_off(new_layers.len().min(MAX_LAYERS));
+                chunks.push(std::mem::replace(&mut new_layers, rest));
+            }
+            let paints = chunks
+                .into_iter()
+                .map(|chunk| self.add_layers(chunk))
+                .collect();
+            return self.add_layers(paints);
+        }
+
+        let first = self.layers.len() as u32;
+        for start in 0..new_ids.len() {
+            for end in start + 2..=new_ids.len() {
+                self.layer_runs
+                    .entry(new_ids[start..end].to_vec())
+                    .or_insert(first + start as u32);
+            }
+        }
+        self.layers.extend(new_layers);
+        self.layer_ids.extend(new_ids.iter().copied());
+        Paint::colr_layers(new_ids.len() as u8, first)
+    }
+
+    /// An id for a paint; identical paints have the same id.
+    fn paint_id(&mut self, paint: &Paint) -> u32 {
+        let next_id = self.next_paint_id;
+        self.next_paint_id += 1;
+        match crate::dump_table(paint) {
+            Ok(bytes) => *self.paint_ids.entry(bytes).or_insert(next_id),
+            // a paint we can't serialize is never shared
+            Err(_) => next_id,
+        }
+    }
+
+    /// Build the table.
+    pub fn build(self) -> Colr {
+        let mut colr = Colr::default();
+        if !self.v0_glyphs.is_empty() {
+            let mut base_glyphs = Vec::with_capacity(self.v0_glyphs.len());
+            let mut layers = Vec::<Layer>::new();
+            let key = |layers: &[Layer]| {
+                layers
+                    .iter()
+                    .map(|layer| (layer.glyph_id, layer.palette_index))
+                    .collect::<Vec<_>>()
+            };
+            let mut layer_lists = HashMap::new();
+            for (glyph, glyph_layers) in self.v0_glyphs {
+                let first = *layer_lists.entry(key(&glyph_layers)).or_insert_with(|| {
+                    let first = layers.len();
+                    layers.extend(glyph_layers.iter().cloned());
+                    first
+                });
+                base_glyphs.push(BaseGlyph::new(
+                    glyph,
+                    first as u16,
+                    glyph_layers.len() as u16,
+                ));
+            }
+            colr.base_glyph_records = Some(base_glyphs).into();
+            colr.layer_records = Some(layers).into();
+        }
+
+        if !self.v1_glyphs.is_empty() {
+            let records = self
+                .v1_glyphs
+                .into_iter()
+                .map(|(glyph, paint)| BaseGlyphPaint::new(glyph, paint))
+                .collect();
+            colr.base_glyph_list = BaseGlyphList::new(records).into();
+        }
+        if !self.layers.is_empty() {
+            colr.layer_list = LayerList::new(self.layers).into();
+        }
+        if !self.clip_boxes.is_empty() {
+            colr.clip_list = ClipList::new(clip_ranges(self.clip_boxes)).into();
+        }
+        if let Some((var_store, var_index_map)) = self.var_store {
+            colr.item_variation_store = var_store.into();
+            colr.var_index_map = var_index_map.into();
+        }
+        colr
+    }
+}
+
+/// Combine consecutive glyphs that have the same clip box into ranges.
+fn clip_ranges(clip_boxes: BTreeMap<GlyphId, ClipBox>) -> Vec<Clip> {
+    let key = |clip_box: &ClipBox| match clip_box {
+        ClipBox::Format1(b) => (1, b.x_min, b.y_min, b.x_max, b.y_max, 0),
+        ClipBox::Format2(b) => (2, b.x_min, b.y_min, b.x_max, b.y_max, b.var_index_base),
+    };
+    let mut clips = Vec::<Clip>::new();
+    for (glyph, clip_box) in clip_boxes {
+        match clips.last_mut() {
+            Some(clip)
+                if clip.end_glyph_id.to_u16() + 1 == glyph.to_u16()
+                    && key(&clip.clip_box) == key(&clip_box) =>
+            {
+                clip.end_glyph_id = glyph;
+            }
+            _ => clips.push(Clip::new(glyph, glyph, clip_box)),
+        }
+    }
+    clips
+}
+
+#[cfg(test)]
+mod tests {
+    use font_types::GlyphId;
+
+    use super::*;
+
+    fn solid(palette_index: u16) -> Paint {
+        Paint::solid(palette_index, F2Dot14::from_f32(1.0))
+    }
+
+    fn glyph(id: u16, paint: Paint) -> Paint {
+        Paint::glyph(paint, GlyphId::new(id))
+    }
+
+    fn colr_layers(paint: &Paint) -> (u8, u32) {
+        match paint {
+            Paint::ColrLayers(layers) => (layers.num_layers, layers.first_layer_index),
+            _ => panic!("expected PaintColrLayers, found {paint:?}"),
+        }
+    }
+
+    #[test]
+    fn v0() {
+        let mut builder = ColrBuilder::new();
+        let layers = vec![
+            Layer::new(GlyphId::new(10), 0),
+            Layer::new(GlyphId::new(11), 1),
+        ];
+        builder.set_v0_glyph(GlyphId::new(2), layers.clone());
+        builder.set_v0_glyph(GlyphId::new(1), vec![Layer::new(GlyphId::new(12), 2)]);
+        builder.set_v0_glyph(GlyphId::new(3), layers);
+        let colr = builder.build();
+
+        let bytes = crate::dump_table(&colr).unwrap();
+        let read = read_fonts::tables::colr::Colr::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read.version(), 0);
+        let base_glyphs = read.base_glyph_records().unwrap().unwrap();
+        let records = base_glyphs
+            .iter()
+            .map(|rec| {
+                (
+                    rec.glyph_id().to_u16(),
+                    rec.first_layer_index(),
+                    rec.num_layers(),
+                )
+            })
+            .collect::<Vec<_>>();
+        // identical layer lists are shared
+        assert_eq!(records, [(1, 0, 1), (2, 1, 2), (3, 1, 2)]);
+        assert_eq!(read.num_layer_records(), 3);
+    }
+
+    #[test]
+    fn shared_layers() {
+        let mut builder = ColrBuilder::new();
+        let a = builder.add_layers(vec![glyph(1, solid(0)), glyph(2, solid(1))]);
+        assert_eq!(colr_layers(&a), (2, 0));
+        // the same list again
+        let b = builder.add_layers(vec![glyph(1, solid(0)), glyph(2, solid(1))]);
+        assert_eq!(colr_layers(&b), (2, 0));
+        // a list that contains the first one
+        let c = builder.add_layers(vec![
+            glyph(3, solid(2)),
+            glyph(1, solid(0)),
+            glyph(2, solid(1)),
+        ]);
+        assert_eq!(colr_layers(&c), (2, 2));
+        let Paint::ColrLayers(reused) = &builder.layers[3] else {
+            panic!("expected PaintColrLayers");
+        };
+        assert_eq!((reused.num_layers, reused.first_layer_index), (2, 0));
+        // a single layer is not wrapped
+        let d = builder.add_layers(vec![glyph(4, solid(0))]);
+        assert!(matches!(d, Paint::Glyph(_)));
+
+        builder.set_v1_glyph(GlyphId::new(1), a);
+        builder.set_v1_glyph(GlyphId::new(2), c);
+        builder.set_v1_glyph(GlyphId::new(3), d);
+        let colr = builder.build();
+        assert_eq!(colr.layer_list.as_ref().unwrap().paints.len(), 4);
+
+        let bytes = crate::dump_table(&colr).unwrap();
+        let read = read_fonts::tables::colr::Colr::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read.version(), 1);
+        let read = Colr::read(FontData::new(&bytes)).unwrap();
+        let base_glyphs = &read
+            .base_glyph_list
+            .as_ref()
+            .unwrap()
+            .base_glyph_paint_records;
+        assert_eq!(base_glyphs.len(), 3);
+        assert_eq!(colr_layers(&base_glyphs[1].paint), (2, 2));
+    }
+
+    #[test]
+    fn many_layers() {
+        let mut builder = ColrBuilder::new();
+        let layers = (0..600).map(|i| glyph(i, solid(0))).collect();
+        let paint = builder.add_layers(layers);
+        // 255 + 255 + 90 layers, and a list of those three
+        assert_eq!(colr_layers(&paint), (3, 600));
+        let nested = builder.layers[600..]
+            .iter()
+            .map(colr_layers)
+            .collect::<Vec<_>>();
+        assert_eq!(nested, [(255, 0), (255, 255), (90, 510)]);
+    }
+
+    #[test]
+    fn clip_ranges() {
+        let clip = |x_max: i16| ClipBox::format_1(0.into(), 0.into(), x_max.into(), 100.into());
+        let mut builder = ColrBuilder::new();
+        for (gid, x_max) in [(1, 50), (2, 50), (3, 60), (5, 60), (6, 60)] {
+            builder.set_v1_glyph(GlyphId::new(gid), solid(0));
+            builder.set_clip_box(GlyphId::new(gid), clip(x_max));
+        }
+        let colr = builder.build();
+        let ranges = colr
+            .clip_list
+            .as_ref()
+            .unwrap()
+            .clips
+            .iter()
+            .map(|clip| (clip.start_glyph_id.to_u16(), clip.end_glyph_id.to_u16()))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [(1, 2), (3, 3), (5, 6)]);
+        crate::dump_table(&colr).unwrap();
+    }
+}
@@ -0,0 +1,255 @@
+//! The [CPAL (Color Palette)](https://learn.microsoft.com/en-us/typography/opentype/spec/cpal) table
+
+include!("../../generated/generated_cpal.rs");
+
+impl Cpal {
+    fn compute_version(&self) -> u16 {
+        if self.palette_types_array.is_some()
+            || self.palette_labels_array.is_some()
+            || self.palette_entry_labels_array.is_some()
+        {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// The value for a palette or palette entry without a label.
+const NO_NAME_ID: u16 = 0xFFFF;
+
+/// A builder for the CPAL table.
+///
+/// Palettes are added in order; each must have the same number of colors.
+/// Palettes with identical colors share their color records. If any palette
+/// has a type or label, or any palette entry has a label, a version 1 table
+/// is built.
+#[derive(Clone, Debug, Default)]
+pub struct CpalBuilder {
+    palettes: Vec<Vec<ColorRecord>>,
+    palette_types: Vec<u32>,
+    palette_labels: Vec<u16>,
+    entry_labels: Vec<u16>,
+}
+
+/// An error in the input to [`CpalBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CpalInputError {
+    /// A palette has a different number of colors than the first palette.
+    PaletteSizeMismatch {
+        palette: u16,
+        expected: usize,
+        found: usize,
+    },
+    /// There are labels for more palette entries than are in each palette.
+    TooManyEntryLabels { entries: usize, labels: usize },
+    /// There are more color records than fit in the table.
+    TooManyColors,
+}
+
+impl ColorRecord {
+    /// Create a color from its red, green, blue and alpha components.
+    pub fn from_rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        ColorRecord::new(blue, green, red, alpha)
+    }
+}
+
+impl CpalBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a palette, returning its index.
+    pub fn add_palette(&mut self, colors: Vec<ColorRecord>) -> u16 {
+        self.palettes.push(colors);
+        self.palette_types.push(0);
+        self.palette_labels.push(NO_NAME_ID);
+        // if we're over u16::MAX palettes, crash
+        (self.palettes.len() - 1).try_into().unwrap()
+    }
+
+    /// Set the type flags of a palette, such as whether it is usable on a
+    /// light or dark background.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no palette with this index.
+    pub fn set_palette_type(&mut self, palette: u16, flags: u32) {
+        self.palette_types[palette as usize] = flags;
+    }
+
+    /// Set the name id of a palette's label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no palette with this index.
+    pub fn set_palette_label(&mut self, palette: u16, name_id: u16) {
+        self.palette_labels[palette as usize] = name_id;
+    }
+
+    /// Set the name ids of the labels of the entries in each palette.
+    pub fn set_entry_labels(&mut self, name_ids: Vec<u16>) {
+        self.entry_labels = name_ids;
+    }
+
+    /// Build the table.
+    pub fn build(self) -> Result<Cpal, CpalInputError> {
+        let num_entries = self.palettes.first().map(Vec::len).unwrap_or_default();
+        if let Some((i, palette)) = self
+            .palettes
+            .iter()
+            .enumerate()
+            .find(|(_, palette)| palette.len() != num_entries)
+        {
+            return Err(CpalInputError::PaletteSizeMismatch {
+                palette: i as u16,
+                expected: num_entries,
+                found: palette.len(),
+            });
+        }
+        if self.entry_labels.len() > num_entries {
+            return Err(CpalInputError::TooManyEntryLabels {
+                entries: num_entries,
+                labels: self.entry_labels.len(),
+            });
+        }
+
+        let keys = self
+            .palettes
+            .iter()
+            .map(|palette| {
+                palette
+                    .iter()
+                    .map(|color| [color.red, color.green, color.blue, color.alpha])
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut color_records = Vec::new();
+        let mut color_record_indices = Vec::with_capacity(self.palettes.len());
+        for (i, palette) in self.palettes.iter().enumerate() {
+            let first_index = match keys[..i].iter().position(|prev| *prev == keys[i]) {
+                Some(prev) => color_record_indices[prev],
+                None => {
+                    let first_index = color_records.len();
+                    color_records.extend(palette.iter().cloned());
+                    u16::try_from(first_index).map_err(|_| CpalInputError::TooManyColors)?
+                }
+            };
+            color_record_indices.push(first_index);
+        }
+        let num_color_records =
+            u16::try_from(color_records.len()).map_err(|_| CpalInputError::TooManyColors)?;
+
+        let mut cpal = Cpal::new(
+            num_entries as u16,
+            self.palettes.len() as u16,
+            num_color_records,
+            (!color_records.is_empty()).then_some(color_records),
+            color_record_indices,
+        );
+        if self.palette_types.iter().any(|flags| *flags != 0) {
+            cpal.palette_types_array = Some(self.palette_types).into();
+        }
+        if self.palette_labels.iter().any(|label| *label != NO_NAME_ID) {
+            cpal.palette_labels_array = Some(self.palette_labels).into();
+        }
+        if !self.entry_labels.is_empty() {
+            let mut labels = self.entry_labels;
+            labels.resize(num_entries, NO_NAME_ID);
+            cpal.palette_entry_labels_array = Some(labels).into();
+        }
+        Ok(cpal)
+    }
+}
+
+impl std::fmt::Display for CpalInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CpalInputError::PaletteSizeMismatch {
+                palette,
+                expected,
+                found,
+            } => write!(
+                f,
+                "palette {palette} has {found} colors, but the first palette has {expected}"
+            ),
+            CpalInputError::TooManyEntryLabels { entries, labels } => write!(
+                f,
+                "{labels} palette entry labels, but palettes have {entries} entries"
+            ),
+            CpalInputError::TooManyColors => f.write_str("too many color records"),
+        }
+    }
+}
+
+impl std::error::Error for CpalInputError {}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::FontRead;
+
+    use super::*;
+
+    #[test]
+    fn shared_palettes() {
+        let red = ColorRecord::from_rgba(255, 0, 0, 255);
+        let blue = ColorRecord::from_rgba(0, 0, 255, 255);
+        let mut builder = CpalBuilder::new();
+        builder.add_palette(vec![red.clone(), blue.clone()]);
+        builder.add_palette(vec![blue.clone(), red.clone()]);
+        builder.add_palette(vec![red.clone(), blue.clone()]);
+        let cpal = builder.build().unwrap();
+        assert_eq!(cpal.num_color_records, 4);
+        assert_eq!(cpal.color_record_indices, [0, 2, 0]);
+
+        let bytes = crate::dump_table(&cpal).unwrap();
+        let read = read_fonts::tables::cpal::Cpal::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read.version(), 0);
+        let records = read.color_records_array().unwrap().unwrap();
+        assert_eq!(records[2].red(), 0);
+        assert_eq!(records[2].blue(), 255);
+    }
+
+    #[test]
+    fn labels() {
+        let black = ColorRecord::from_rgba(0, 0, 0, 255);
+        let mut builder = CpalBuilder::new();
+        builder.add_palette(vec![black.clone(), black.clone()]);
+        let dark = builder.add_palette(vec![black.clone(), black]);
+        builder.set_palette_type(dark, 2);
+        builder.set_entry_labels(vec![256]);
+        let cpal = builder.build().unwrap();
+
+        let bytes = crate::dump_table(&cpal).unwrap();
+        let read = read_fonts::tables::cpal::Cpal::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read.version(), 1);
+        let types = read.palette_types_array().unwrap().unwrap();
+        assert_eq!(
+            types.iter().map(|flags| flags.get()).collect::<Vec<_>>(),
+            [0, 2]
+        );
+        assert!(read.palette_labels_array().is_none());
+        let entry_labels = read.palette_entry_labels_array().unwrap().unwrap();
+        assert_eq!(
+            entry_labels.iter().map(|id| id.get()).collect::<Vec<_>>(),
+            [256, 0xFFFF]
+        );
+    }
+
+    #[test]
+    fn mismatched_palettes() {
+        let black = ColorRecord::from_rgba(0, 0, 0, 255);
+        let mut builder = CpalBuilder::new();
+        builder.add_palette(vec![black.clone(), black.clone()]);
+        builder.add_palette(vec![black]);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            CpalInputError::PaletteSizeMismatch {
+                palette: 1,
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! Converting cubic curves to quadratic splines.
+//!
+//! This is a port of the [cu2qu] algorithm from fontTools: a cubic is split
+//! into a growing number of pieces until a quadratic spline through them is
+//! within the error tolerance. For the same input, the output is the same as
+//! that of fontTools.
+//!
+//! A spline is returned as a list of points: the start point, the off-curve
+//! points, and the end point. The on-curve points between consecutive
+//! off-curve points are implied, at their midpoints, as in the glyf table.
+//!
+//! [cu2qu]: https://github.com/fonttools/fonttools/tree/main/Lib/fontTools/cu2qu
+
+use kurbo::{CubicBez, Point, Vec2};
+
+/// The maximum number of quadratic segments a cubic is split into.
+const MAX_SEGMENTS: usize = 100;
+
+/// Approximate a cubic curve with a quadratic spline.
+///
+/// The spline is no further than `max_error` from the curve at any point.
+/// Returns `None` if no spline of up to 100 segments is close enough.
+pub fn cubic_to_quadratic(cubic: CubicBez, max_error: f64) -> Option<Vec<Point>> {
+    let cubic = to_vecs(cubic);
+    (1..=MAX_SEGMENTS)
+        .find_map(|n| approx_spline(cubic, n, max_error))
+        .map(to_points)
+}
+
+/// Approximate several cubic curves with quadratic splines that have the same
+/// number of points.
+///
+/// This is used for the same curve in each master of a variable font, so
+/// that the converted outlines remain compatible for interpolation. Every
+/// spline is no further than `max_error` from its curve.
+///
+/// Returns `None` if no splines of up to 100 segments are close enough.
+pub fn cubics_to_quadratic(cubics: &[CubicBez], max_error: f64) -> Option<Vec<Vec<Point>>> {
+    let cubics = cubics.iter().copied().map(to_vecs).collect::<Vec<_>>();
+    let mut splines = vec![Vec::new(); cubics.len()];
+    if cubics.is_empty() {
+        return Some(Vec::new());
+    }
+    // find the smallest n that works for every curve, starting each attempt
+    // with the curve that failed last
+    let (mut i, mut last_failed, mut n) = (0, 0, 1);
+    loop {
+        match approx_spline(cubics[i], n, max_error) {
+            Some(spline) => {
+                splines[i] = spline;
+                i = (i + 1) % cubics.len();
+                if i == last_failed {
+                    return Some(splines.into_iter().map(to_points).collect());
+                }
+            }
+            None if n == MAX_SEGMENTS => return None,
+            None => {
+                n += 1;
+                last_failed = i;
+            }
+        }
+    }
+}
+
+fn to_vecs(cubic: CubicBez) -> [Vec2; 4] {
+    [cubic.p0, cubic.p1, cubic.p2, cubic.p3].map(Point::to_vec2)
+}
+
+fn to_points(spline: Vec<Vec2>) -> Vec<Point> {
+    spline.into_iter().map(Vec2::to_point).collect()
+}
+
+/// Approximate a cubic with a spline of `n` quadratic segments.
+fn approx_spline(cubic: [Vec2; 4], n: usize, tolerance: f64) -> Option<Vec<Vec2>> {
+    if n == 1 {
+        return approx_quadratic(cubic, tolerance).map(Vec::from);
+    }
+    let pieces = split_into_n(cubic, n);
+    let mut next_q1 = approx_control(0.0, pieces[0]);
+    let mut q2 = cubic[0];
+    let mut d1 = Vec2::ZERO;
+    let mut spline = vec![cubic[0], next_q1];
+    for i in 1..=n {
+        let [_, c1, c2, c3] = pieces[i - 1];
+        let q0 = q2;
+        let q1 = next_q1;
+        if i < n {
+            next_q1 = approx_control(i as f64 / (n - 1) as f64, pieces[i]);
+            spline.push(next_q1);
+            q2 = (q1 + next_q1) * 0.5;
+        } else {
+            q2 = c3;
+        }
+        let d0 = d1;
+        d1 = q2 - c3;
+        if d1.hypot() > tolerance
+            || !farthest_fit_inside(
+                [
+                    d0,
+                    q0 + (q1 - q0) * (2.0 / 3.0) - c1,
+                    q2 + (q1 - q2) * (2.0 / 3.0) - c2,
+                    d1,
+                ],
+                tolerance,
+            )
+        {
+            return None;
+        }
+    }
+    spline.push(cubic[3]);
+    Some(spline)
+}
+
+/// Approximate a cubic with a single quadratic, whose control point is the
+/// intersection of the cubic's end tangents.
+fn approx_quadratic(cubic: [Vec2; 4], tolerance: f64) -> Option<[Vec2; 3]> {
+    let [p0, p1, p2, p3] = cubic;
+    let q1 = intersect(p0, p1, p2, p3)?;
+    let c1 = p0 + (q1 - p0) * (2.0 / 3.0);
+    let c2 = p3 + (q1 - p3) * (2.0 / 3.0);
+    farthest_fit_inside([Vec2::ZERO, c1 - p1, c2 - p2, Vec2::ZERO], tolerance)
+        .then_some([p0, q1, p3])
+}
+
+/// The intersection of the line through `a` and `b` with the line through
+/// `c` and `d`.
+fn intersect(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Option<Vec2> {
+    let ab = b - a;
+    let cd = d - c;
+    let p = Vec2::new(-ab.y, ab.x);
+    let denominator = p.dot(cd);
+    if denominator == 0.0 {
+        return None;
+    }
+    let h = p.dot(a - c) / denominator;
+    let result = c + cd * h;
+    // the lines may be degenerate
+    result.is_finite().then_some(result)
+}
+
+/// The control point of the quadratic approximating a piece of a cubic, for
+/// the `t`th of the pieces.
+fn approx_control(t: f64, cubic: [Vec2; 4]) -> Vec2 {
+    let [p0, p1, p2, p3] = cubic;
+    let p1 = p0 + (p1 - p0) * 1.5;
+    let p2 = p3 + (p2 - p3) * 1.5;
+    p1 + (p2 - p1) * t
+}
+
+/// Returns `true` if the cubic is within `tolerance` of the origin.
+///
+/// The cubic is the difference between a curve and its approximation, so
+/// this checks that the approximation is close enough.
+fn farthest_fit_inside(cubic: [Vec2; 4], tolerance: f64) -> bool {
+    let [p0, p1, p2, p3] = cubic;
+    if p2.hypot() <= tolerance && p1.hypot() <= tolerance {
+        return true;
+    }
+    let mid = (p0 + (p1 + p2) * 3.0 + p3) * 0.125;
+    if mid.hypot() > tolerance {
+        return false;
+    }
+    let deriv3 = (p3 + p2 - p1 - p0) * 0.125;
+    farthest_fit_inside([p0, (p0 + p1) * 0.5, mid - deriv3, mid], tolerance)
+        && farthest_fit_inside([mid, mid + deriv3, (p2 + p3) * 0.5, p3], tolerance)
+}
+
+/// Split a cubic into `n` pieces, of equal ranges of `t`.
+fn split_into_n(cubic: [Vec2; 4], n: usize) -> Vec<[Vec2; 4]> {
+    // the common cases are computed exactly as in fontTools, so that the
+    // output is the same
+    match n {
+        2 => split_in_two(cubic).to_vec(),
+        3 => split_in_three(cubic).to_vec(),
+        4 => split_in_two(cubic)
+            .into_iter()
+            .flat_map(split_in_two)
+            .collect(),
+        6 => split_in_two(cubic)
+            .into_iter()
+            .flat_map(split_in_three)
+            .collect(),
+        _ => {
+            let [p0, p1, p2, p3] = cubic;
+            // the power basis coefficients
+            let c = (p1 - p0) * 3.0;
+            let b = (p2 - p1) * 3.0 - c;
+            let d = p0;
+            let a = p3 - d - c - b;
+            let dt = 1.0 / n as f64;
+            let (dt2, dt3) = (dt * dt, dt * dt * dt);
+            (0..n)
+                .map(|i| {
+                    let t1 = i as f64 * dt;
+                    let t1_2 = t1 * t1;
+                    let a1 = a * dt3;
+                    let b1 = (a * 3.0 * t1 + b) * dt2;
+                    let c1 = (b * 2.0 * t1 + c + a * 3.0 * t1_2) * dt;
+                    let d1 = a * t1 * t1_2 + b * t1_2 + c * t1 + d;
+                    let q1 = c1 / 3.0 + d1;
+                    let q2 = (b1 + c1) / 3.0 + q1;
+                    [d1, q1, q2, a1 + d1 + c1 + b1]
+                })
+                .collect()
+        }
+    }
+}
+
+fn split_in_two(cubic: [Vec2; 4]) -> [[Vec2; 4]; 2] {
+    let [p0, p1, p2, p3] = cubic;
+    let mid = (p0 + (p1 + p2) * 3.0 + p3) * 0.125;
+    let deriv3 = (p3 + p2 - p1 - p0) * 0.125;
+    [
+        [p0, (p0 + p1) * 0.5, mid - deriv3, mid],
+        [mid, mid + deriv3, (p2 + p3) * 0.5, p3],
+    ]
+}
+
+fn split_in_three(cubic: [Vec2; 4]) -> [[Vec2; 4]; 3] {
+    let [p0, p1, p2, p3] = cubic;
+    let mid1 = (p0 * 8.0 + p1 * 12.0 + p2 * 6.0 + p3) * (1.0 / 27.0);
+    let deriv1 = (p3 + p2 * 3.0 - p0 * 4.0) * (1.0 / 27.0);
+    let mid2 = (p0 + p1 * 6.0 + p2 * 12.0 + p3 * 8.0) * (1.0 / 27.0);
+    let deriv2 = (p3 * 4.0 - p1 * 3.0 - p0) * (1.0 / 27.0);
+    [
+        [p0, (p0 * 2.0 + p1) / 3.0, mid1 - deriv1, mid1],
+        [mid1, mid1 + deriv1, mid2 - deriv2, mid2],
+        [mid2, mid2 + deriv2, (p2 + p3 * 2.0) / 3.0, p3],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use kurbo::{ParamCurve, QuadBez};
+
+    use super::*;
+
+    fn cubic(points: [(f64, f64); 4]) -> CubicBez {
+        let [p0, p1, p2, p3] = points;
+        CubicBez::new(p0, p1, p2, p3)
+    }
+
+    /// The quadratic segments of a spline, with their implied on-curve points.
+    fn segments(spline: &[Point]) -> Vec<QuadBez> {
+        let last = spline.len() - 2;
+        (1..=last)
+            .map(|i| {
+                let start = if i == 1 {
+                    spline[0]
+                } else {
+                    spline[i - 1].midpoint(spline[i])
+                };
+                let end = if i == last {
+                    spline[i + 1]
+                } else {
+                    spline[i].midpoint(spline[i + 1])
+                };
+                QuadBez::new(start, spline[i], end)
+            })
+            .collect()
+    }
+
+    /// The greatest distance between the spline and the curve, by sampling.
+    ///
+    /// The `i`th of `n` segments approximates the curve between `t = i / n`
+    /// and `t = (i + 1) / n`.
+    fn max_distance(cubic: CubicBez, spline: &[Point]) -> f64 {
+        let segments = segments(spline);
+        let n = segments.len() as f64;
+        segments
+            .iter()
+            .enumerate()
+            .flat_map(|(i, quad)| {
+                (0..=100).map(move |j| {
+                    let t = j as f64 / 100.0;
+                    quad.eval(t).distance(cubic.eval((i as f64 + t) / n))
+                })
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn elevated_quadratic() {
+        // a quadratic, elevated to a cubic, converts back exactly
+        let quad = QuadBez::new((0.0, 0.0), (50.0, 100.0), (100.0, 0.0));
+        let spline = cubic_to_quadratic(quad.raise(), 0.001).unwrap();
+        assert_eq!(spline, [quad.p0, quad.p1, quad.p2]);
+    }
+
+    #[test]
+    fn within_tolerance() {
+        let curve = cubic([(0.0, 0.0), (0.0, 550.0), (1000.0, -200.0), (1000.0, 400.0)]);
+        let mut last_len = 0;
+        for max_error in [5.0, 1.0, 0.1] {
+            let spline = cubic_to_quadratic(curve, max_error).unwrap();
+            assert_eq!(spline.first(), Some(&curve.p0));
+            assert_eq!(spline.last(), Some(&curve.p3));
+            assert!(max_distance(curve, &spline) <= max_error);
+            // a lower tolerance needs more segments
+            assert!(spline.len() > last_len);
+            last_len = spline.len();
+        }
+    }
+
+    #[test]
+    fn same_as_cu2qu() {
+        // kurbo's spline approximation is also a port of cu2qu
+        let curves = [
+            cubic([(0.0, 0.0), (0.0, 550.0), (1000.0, -200.0), (1000.0, 400.0)]),
+            cubic([
+                (100.0, 100.0),
+                (150.0, 700.0),
+                (650.0, 720.0),
+                (700.0, 90.0),
+            ]),
+            cubic([(12.5, -3.0), (400.0, 0.0), (-300.0, 80.0), (90.0, 90.0)]),
+        ];
+        for curve in curves {
+            for max_error in [1.0, 0.25, 0.01] {
+                let ours = cubic_to_quadratic(curve, max_error).unwrap();
+                let theirs = curve.approx_spline(max_error).unwrap();
+                assert_eq!(ours.len(), theirs.points().len());
+                for (a, b) in ours.iter().zip(theirs.points()) {
+                    assert!(a.distance(*b) < 1e-9, "{a:?} != {b:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn compatible_splines() {
+        // the same curve in two masters; the second needs more segments
+        let light = cubic([(0.0, 0.0), (0.0, 100.0), (100.0, 100.0), (100.0, 0.0)]);
+        let bold = cubic([(0.0, 0.0), (0.0, 900.0), (1000.0, -300.0), (1000.0, 500.0)]);
+        let alone = cubic_to_quadratic(light, 1.0).unwrap();
+        let splines = cubics_to_quadratic(&[light, bold], 1.0).unwrap();
+        assert_eq!(splines[0].len(), splines[1].len());
+        assert!(splines[0].len() > alone.len());
+        assert_eq!(splines[1], cubic_to_quadratic(bold, 1.0).unwrap());
+        assert!(max_distance(light, &splines[0]) <= 1.0);
+    }
+
+    #[test]
+    fn degenerate_curves() {
+        // a straight line, with coincident control points
+        let line = cubic([(0.0, 0.0), (0.0, 0.0), (100.0, 0.0), (100.0, 0.0)]);
+        let spline = cubic_to_quadratic(line, 0.5).unwrap();
+        assert!(max_distance(line, &spline) <= 0.5);
+        let point = cubic([(10.0, 10.0); 4]);
+        assert!(cubic_to_quadratic(point, 0.5).is_some());
+        assert_eq!(cubics_to_quadratic(&[], 1.0), Some(Vec::new()));
+    }
+}
@@ -0,0 +1,407 @@
+//! The [cvar (CVT Variations)](https://learn.microsoft.com/en-us/typography/opentype/spec/cvar) table
+
+use std::collections::BTreeMap;
+
+use read_fonts::TopLevelTable;
+use types::{F2Dot14, MajorMinor, Tag};
+
+use crate::{
+    tables::variations::{
+        encode_deltas, encode_point_numbers, explicit_intermediate_region, PackedTupleVariation,
+        Tuple, TupleVariationStore, VariationModel, VariationModelError,
+    },
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+/// The size of the fields before the tuple variation headers.
+const HEADER_LEN: usize = 8;
+
+/// The [cvar] table.
+///
+/// This is compiled from the deltas of each region with [`Cvar::new`], or
+/// from the `cvt ` values of each master with a [`CvarBuilder`].
+///
+/// [cvar]: https://learn.microsoft.com/en-us/typography/opentype/spec/cvar
+#[derive(Clone, Debug, Default)]
+pub struct Cvar {
+    store: TupleVariationStore,
+}
+
+/// The deltas for every value in the `cvt ` table at a single region of the
+/// design space.
+#[derive(Clone, Debug)]
+pub struct CvtDeltas {
+    peak_tuple: Tuple,
+    intermediate_region: Option<(Tuple, Tuple)>,
+    deltas: Vec<i16>,
+}
+
+/// A builder for the `cvar` table, from the `cvt ` values of each master.
+///
+/// The values of the default master are the ones stored in the `cvt ` table;
+/// the deltas of the other masters are computed with a [`VariationModel`].
+#[derive(Clone, Debug, Default)]
+pub struct CvarBuilder {
+    masters: Vec<(Vec<F2Dot14>, Vec<i16>)>,
+}
+
+/// An error in the input to [`Cvar::new`] or [`CvarBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CvarInputError {
+    /// The variations do not all have the same number of deltas.
+    InconsistentDeltaLength,
+    /// A tuple does not have one value per axis.
+    InconsistentTupleLengths,
+    /// There are too many variations, or too much data, to be encoded.
+    DataTooLarge,
+    /// A master does not have the same number of `cvt ` values as the first.
+    InconsistentCvtLength { master: usize },
+    /// The delta of a `cvt ` value does not fit in 16 bits.
+    DeltaOutOfRange { index: usize },
+    /// The master locations do not form a valid model.
+    Model(VariationModelError),
+}
+
+impl Cvar {
+    /// Compile a new table.
+    ///
+    /// Each variation has a delta for every value in the `cvt ` table.
+    /// Variations whose deltas are all zero are omitted, and zero deltas are
+    /// omitted from a variation when this makes it smaller.
+    pub fn new(variations: Vec<CvtDeltas>, axis_count: u16) -> Result<Self, CvarInputError> {
+        let axis_count = axis_count as usize;
+        if let Some(first) = variations.first() {
+            if variations
+                .iter()
+                .any(|var| var.deltas.len() != first.deltas.len())
+            {
+                return Err(CvarInputError::InconsistentDeltaLength);
+            }
+        }
+        if variations.iter().any(|var| {
+            var.peak_tuple.values.len() != axis_count
+                || var
+                    .intermediate_region
+                    .as_ref()
+                    .is_some_and(|(start, end)| {
+                        start.values.len() != axis_count || end.values.len() != axis_count
+                    })
+        }) {
+            return Err(CvarInputError::InconsistentTupleLengths);
+        }
+
+        let packed = variations
+            .iter()
+            .filter(|var| var.deltas.iter().any(|delta| *delta != 0))
+            .map(CvtDeltas::pack)
+            .collect::<Vec<_>>();
+        // every peak tuple is embedded, as cvar has no shared tuples
+        let store = TupleVariationStore::compile(&packed, &BTreeMap::new())
+            .filter(|store| HEADER_LEN + store.headers.len() <= u16::MAX as usize)
+            .ok_or(CvarInputError::DataTooLarge)?;
+        Ok(Cvar { store })
+    }
+
+    /// The number of variations in the table.
+    pub fn variation_count(&self) -> u16 {
+        self.store.count & 0x0FFF
+    }
+}
+
+impl CvtDeltas {
+    /// Create a new set of deltas.
+    ///
+    /// If `intermediate_region` is `None`, the region is inferred from the
+    /// peak tuple, spanning from zero to the peak on each axis.
+    pub fn new(
+        peak_tuple: Tuple,
+        deltas: Vec<i16>,
+        intermediate_region: Option<(Tuple, Tuple)>,
+    ) -> Self {
+        CvtDeltas {
+            peak_tuple,
+            intermediate_region,
+            deltas,
+        }
+    }
+
+    /// Pack the point numbers and deltas, omitting zero deltas if that is
+    /// smaller than writing them all.
+    fn pack(&self) -> PackedTupleVariation<'_> {
+        let mut point_numbers = encode_point_numbers(None);
+        let mut deltas = Vec::new();
+        encode_deltas(self.deltas.iter().copied(), &mut deltas);
+
+        let non_zero = (0..self.deltas.len() as u16)
+            .filter(|i| self.deltas[*i as usize] != 0)
+            .collect::<Vec<_>>();
+        if non_zero.len() < self.deltas.len() {
+            let sparse_points = encode_point_numbers(Some(&non_zero));
+            let mut sparse_deltas = Vec::new();
+            encode_deltas(
+                non_zero.iter().map(|i| self.deltas[*i as usize]),
+                &mut sparse_deltas,
+            );
+            if sparse_points.len() + sparse_deltas.len() < point_numbers.len() + deltas.len() {
+                point_numbers = sparse_points;
+                deltas = sparse_deltas;
+            }
+        }
+
+        PackedTupleVariation {
+            peak_tuple: &self.peak_tuple.values,
+            intermediate_region: explicit_intermediate_region(
+                &self.peak_tuple,
+                self.intermediate_region.as_ref(),
+            )
+            .map(|(start, end)| (start.values.as_slice(), end.values.as_slice())),
+            point_numbers,
+            deltas,
+        }
+    }
+}
+
+impl CvarBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the `cvt ` values of the master at this normalized location.
+    ///
+    /// Every master must have the same number of values, and exactly one must
+    /// be at the default location.
+    pub fn add_master(&mut self, location: Vec<F2Dot14>, cvt: Vec<i16>) -> &mut Self {
+        self.masters.push((location, cvt));
+        self
+    }
+
+    /// Build the table.
+    pub fn build(self) -> Result<Cvar, CvarInputError> {
+        let (locations, values): (Vec<_>, Vec<_>) = self.masters.into_iter().unzip();
+        let model = VariationModel::new(locations).map_err(CvarInputError::Model)?;
+        let cvt_len = values.first().map(Vec::len).unwrap_or_default();
+        if let Some(master) = values.iter().position(|cvt| cvt.len() != cvt_len) {
+            return Err(CvarInputError::InconsistentCvtLength { master });
+        }
+
+        let regions = model.regions();
+        let mut deltas = vec![Vec::with_capacity(cvt_len); regions.len()];
+        for index in 0..cvt_len {
+            let master_values = values
+                .iter()
+                .map(|cvt| cvt[index] as i32)
+                .collect::<Vec<_>>();
+            for (region_deltas, delta) in deltas.iter_mut().zip(model.deltas(&master_values)) {
+                let delta =
+                    i16::try_from(delta).map_err(|_| CvarInputError::DeltaOutOfRange { index })?;
+                region_deltas.push(delta);
+            }
+        }
+
+        let variations = regions
+            .into_iter()
+            .zip(deltas)
+            .map(|(region, deltas)| {
+                let coords =
+                    |f: fn(&_) -> F2Dot14| Tuple::new(region.region_axes.iter().map(f).collect());
+                CvtDeltas::new(
+                    coords(|axis| axis.peak_coord),
+                    deltas,
+                    Some((
+                        coords(|axis| axis.start_coord),
+                        coords(|axis| axis.end_coord),
+                    )),
+                )
+            })
+            .collect();
+        Cvar::new(variations, model.axis_count() as u16)
+    }
+}
+
+impl TopLevelTable for Cvar {
+    const TAG: Tag = Tag::CVAR;
+}
+
+impl FontWrite for Cvar {
+    fn write_into(&self, writer: &mut TableWriter) {
+        MajorMinor::VERSION_1_0.write_into(writer);
+        self.store.count.write_into(writer);
+        ((HEADER_LEN + self.store.headers.len()) as u16).write_into(writer);
+        writer.write_slice(&self.store.headers);
+        writer.write_slice(&self.store.serialized);
+    }
+}
+
+impl Validate for Cvar {
+    // the input is checked when the table is constructed
+    fn validate_impl(&self, _ctx: &mut ValidationCtx) {}
+}
+
+impl std::fmt::Display for CvarInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CvarInputError::InconsistentDeltaLength => f.write_str("inconsistent number of deltas"),
+            CvarInputError::InconsistentTupleLengths => {
+                f.write_str("tuple length does not match axis count")
+            }
+            CvarInputError::DataTooLarge => f.write_str("variation data is too large"),
+            CvarInputError::InconsistentCvtLength { master } => {
+                write!(
+                    f,
+                    "master {master} has an inconsistent number of cvt values"
+                )
+            }
+            CvarInputError::DeltaOutOfRange { index } => {
+                write!(f, "delta of cvt value {index} is out of range")
+            }
+            CvarInputError::Model(err) => write!(f, "invalid master locations: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for CvarInputError {}
+
+#[cfg(test)]
+mod tests {
+    use read::{
+        tables::variations::{PackedPointNumbers, TupleVariationHeader},
+        FontData, FontReadWithArgs,
+    };
+
+    use super::*;
+
+    fn tuple(values: &[f32]) -> Tuple {
+        Tuple::new(values.iter().copied().map(F2Dot14::from_f32).collect())
+    }
+
+    fn loc(values: &[f32]) -> Vec<F2Dot14> {
+        tuple(values).values
+    }
+
+    fn read_u16(data: &[u8], pos: usize) -> u16 {
+        u16::from_be_bytes([data[pos], data[pos + 1]])
+    }
+
+    #[test]
+    fn header_and_sparse_deltas() {
+        let cvar = Cvar::new(
+            vec![
+                CvtDeltas::new(tuple(&[1.0]), vec![0; 200], None),
+                CvtDeltas::new(tuple(&[1.0]), [vec![300], vec![0; 199]].concat(), None),
+                CvtDeltas::new(
+                    tuple(&[-1.0]),
+                    vec![1; 200],
+                    Some((tuple(&[-1.0]), tuple(&[0.0]))),
+                ),
+            ],
+            1,
+        )
+        .unwrap();
+        // the all-zero variation is dropped
+        assert_eq!(cvar.variation_count(), 2);
+
+        let data = crate::dump_table(&cvar).unwrap();
+        assert_eq!(&data[..4], &[0, 1, 0, 0]);
+        assert_eq!(read_u16(&data, 4), 2);
+        // two headers, each with an embedded peak and no intermediate region
+        let data_offset = read_u16(&data, 6) as usize;
+        assert_eq!(data_offset, HEADER_LEN + 2 * 6);
+
+        let header = TupleVariationHeader::read_with_args(FontData::new(&data[8..]), &1).unwrap();
+        let peak = header.peak_tuple().unwrap();
+        assert_eq!(peak.get(0), Some(F2Dot14::from_f32(1.0)));
+        assert!(header.intermediate_start_tuple().is_none());
+        assert!(header.tuple_index().private_point_numbers());
+
+        // only the first value has a delta
+        let serialized = &data[data_offset..];
+        let (points, rest) = PackedPointNumbers::split_off_front(FontData::new(serialized));
+        assert_eq!(points.iter().collect::<Vec<_>>(), [0]);
+        let mut deltas = Vec::new();
+        encode_deltas([300].into_iter(), &mut deltas);
+        assert_eq!(&rest.as_ref()[..deltas.len()], deltas);
+        assert_eq!(header.variation_data_size() as usize, 3 + deltas.len());
+
+        // every value of the second variation has a delta
+        let (points, rest) = PackedPointNumbers::split_off_front(FontData::new(
+            &serialized[header.variation_data_size() as usize..],
+        ));
+        assert_eq!(points.count(), 0);
+        let mut deltas = Vec::new();
+        encode_deltas([1; 200].into_iter(), &mut deltas);
+        assert_eq!(rest.as_ref(), deltas);
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = CvarBuilder::new();
+        builder
+            .add_master(loc(&[1.0, 0.0]), vec![110, 200, 300])
+            .add_master(loc(&[0.0, 0.0]), vec![100, 200, 300])
+            .add_master(loc(&[0.0, 1.0]), vec![100, 150, 300])
+            .add_master(loc(&[1.0, 1.0]), vec![120, 160, 300]);
+        let cvar = builder.build().unwrap();
+        // one variation for each non-default master
+        assert_eq!(cvar.variation_count(), 3);
+
+        let data = crate::dump_table(&cvar).unwrap();
+        let mut offset = 8;
+        let mut peaks = Vec::new();
+        for _ in 0..3 {
+            let header =
+                TupleVariationHeader::read_with_args(FontData::new(&data[offset..]), &2).unwrap();
+            let peak = header.peak_tuple().unwrap();
+            peaks.push([peak.get(0).unwrap().to_f32(), peak.get(1).unwrap().to_f32()]);
+            // the implied intermediate regions are not written
+            assert!(header.intermediate_start_tuple().is_none());
+            offset += 4 + 2 * 2;
+        }
+        assert_eq!(peaks, [[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]]);
+    }
+
+    #[test]
+    fn input_errors() {
+        assert_eq!(
+            Cvar::new(
+                vec![
+                    CvtDeltas::new(tuple(&[1.0]), vec![1, 2], None),
+                    CvtDeltas::new(tuple(&[-1.0]), vec![1], None),
+                ],
+                1
+            )
+            .unwrap_err(),
+            CvarInputError::InconsistentDeltaLength
+        );
+        assert_eq!(
+            Cvar::new(vec![CvtDeltas::new(tuple(&[1.0]), vec![1], None)], 2).unwrap_err(),
+            CvarInputError::InconsistentTupleLengths
+        );
+
+        let mut builder = CvarBuilder::new();
+        builder
+            .add_master(loc(&[0.0]), vec![100, 200])
+            .add_master(loc(&[1.0]), vec![100]);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            CvarInputError::InconsistentCvtLength { master: 1 }
+        );
+
+        let mut builder = CvarBuilder::new();
+        builder
+            .add_master(loc(&[0.0]), vec![-30000])
+            .add_master(loc(&[1.0]), vec![30000]);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            CvarInputError::DeltaOutOfRange { index: 0 }
+        );
+
+        let mut builder = CvarBuilder::new();
+        builder.add_master(loc(&[1.0]), vec![100]);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            CvarInputError::Model(VariationModelError::NoDefaultMaster)
+        );
+    }
+}
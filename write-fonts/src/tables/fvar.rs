@@ -0,0 +1,373 @@
+//! The [fvar (Font Variations)](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar) table
+
+use read_fonts::TopLevelTable;
+use types::{Fixed, MajorMinor, NameId, Tag};
+
+use super::name::NameIdAllocator;
+use crate::{
+    validate::{Validate, ValidationCtx},
+    write::TableType,
+    FontWrite, TableWriter,
+};
+
+/// The size of the fixed portion of the table.
+const HEADER_LEN: u16 = 16;
+/// The size of a [`VariationAxisRecord`].
+const AXIS_RECORD_LEN: u16 = 20;
+/// The name id used for instances that have no PostScript name.
+const NO_NAME_ID: NameId = NameId::new(0xFFFF);
+
+/// The [fvar] table.
+///
+/// [fvar]: https://docs.microsoft.com/en-us/typography/opentype/spec/fvar
+#[derive(Clone, Debug, Default)]
+pub struct Fvar {
+    /// The variation axes.
+    pub axes: Vec<VariationAxisRecord>,
+    /// The named instances.
+    pub instances: Vec<InstanceRecord>,
+}
+
+/// A variation axis.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VariationAxisRecord {
+    /// Tag identifying the design variation for the axis.
+    pub axis_tag: Tag,
+    /// The minimum coordinate value for the axis.
+    pub min_value: Fixed,
+    /// The default coordinate value for the axis.
+    pub default_value: Fixed,
+    /// The maximum coordinate value for the axis.
+    pub max_value: Fixed,
+    /// Axis qualifiers.
+    pub flags: u16,
+    /// The name ID for entries in the 'name' table that provide a display name for this axis.
+    pub axis_name_id: NameId,
+}
+
+/// A named instance.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InstanceRecord {
+    /// The name ID for entries in the 'name' table that provide subfamily names for this instance.
+    pub subfamily_name_id: NameId,
+    /// Reserved for future use — set to 0.
+    pub flags: u16,
+    /// The coordinates of the instance, in the order of the axes.
+    pub coordinates: Vec<Fixed>,
+    /// The name ID for entries in the 'name' table that provide PostScript names for this instance.
+    pub post_script_name_id: Option<NameId>,
+}
+
+impl Fvar {
+    /// Construct a new `Fvar`
+    pub fn new(axes: Vec<VariationAxisRecord>, instances: Vec<InstanceRecord>) -> Self {
+        Fvar { axes, instances }
+    }
+
+    // PostScript names are written for all instances if any instance has one
+    fn has_post_script_names(&self) -> bool {
+        self.instances
+            .iter()
+            .any(|instance| instance.post_script_name_id.is_some())
+    }
+
+    fn compute_instance_size(&self) -> u16 {
+        let ps_name_len = if self.has_post_script_names() { 2 } else { 0 };
+        (self.axes.len() * 4 + 4 + ps_name_len) as u16
+    }
+}
+
+impl VariationAxisRecord {
+    /// The axis should not be exposed directly in user interfaces.
+    pub const HIDDEN_AXIS: u16 = 0x0001;
+
+    /// Construct a new `VariationAxisRecord`
+    pub fn new(
+        axis_tag: Tag,
+        min_value: Fixed,
+        default_value: Fixed,
+        max_value: Fixed,
+        flags: u16,
+        axis_name_id: NameId,
+    ) -> Self {
+        VariationAxisRecord {
+            axis_tag,
+            min_value,
+            default_value,
+            max_value,
+            flags,
+            axis_name_id,
+        }
+    }
+}
+
+impl InstanceRecord {
+    /// Construct a new `InstanceRecord`
+    pub fn new(subfamily_name_id: NameId, coordinates: Vec<Fixed>) -> Self {
+        InstanceRecord {
+            subfamily_name_id,
+            coordinates,
+            ..Default::default()
+        }
+    }
+}
+
+impl TopLevelTable for Fvar {
+    const TAG: Tag = Tag::FVAR;
+}
+
+impl FontWrite for Fvar {
+    fn write_into(&self, writer: &mut TableWriter) {
+        MajorMinor::VERSION_1_0.write_into(writer);
+        HEADER_LEN.write_into(writer);
+        // reserved
+        2u16.write_into(writer);
+        (self.axes.len() as u16).write_into(writer);
+        AXIS_RECORD_LEN.write_into(writer);
+        (self.instances.len() as u16).write_into(writer);
+        self.compute_instance_size().write_into(writer);
+        self.axes.write_into(writer);
+        let has_post_script_names = self.has_post_script_names();
+        for instance in &self.instances {
+            instance.subfamily_name_id.write_into(writer);
+            instance.flags.write_into(writer);
+            instance.coordinates.write_into(writer);
+            if has_post_script_names {
+                instance
+                    .post_script_name_id
+                    .unwrap_or(NO_NAME_ID)
+                    .write_into(writer);
+            }
+        }
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::TopLevel(Self::TAG)
+    }
+}
+
+impl FontWrite for VariationAxisRecord {
+    fn write_into(&self, writer: &mut TableWriter) {
+        self.axis_tag.write_into(writer);
+        self.min_value.write_into(writer);
+        self.default_value.write_into(writer);
+        self.max_value.write_into(writer);
+        self.flags.write_into(writer);
+        self.axis_name_id.write_into(writer);
+    }
+}
+
+impl Validate for Fvar {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        ctx.in_table("fvar", |ctx| {
+            ctx.in_field("axes", |ctx| {
+                if self.axes.len() > u16::MAX as usize {
+                    ctx.report("too many axes");
+                }
+                ctx.in_array(|ctx| {
+                    for axis in &self.axes {
+                        ctx.array_item(|ctx| {
+                            if axis.min_value > axis.default_value
+                                || axis.default_value > axis.max_value
+                            {
+                                ctx.report(format!(
+                                    "axis '{}' must have min <= default <= max",
+                                    axis.axis_tag
+                                ));
+                            }
+                        })
+                    }
+                })
+            });
+            ctx.in_field("instances", |ctx| {
+                if self.instances.len() > u16::MAX as usize {
+                    ctx.report("too many instances");
+                }
+                ctx.in_array(|ctx| {
+                    for instance in &self.instances {
+                        ctx.array_item(|ctx| {
+                            if instance.coordinates.len() != self.axes.len() {
+                                ctx.report(format!(
+                                    "instance has {} coordinates, but there are {} axes",
+                                    instance.coordinates.len(),
+                                    self.axes.len()
+                                ));
+                                return;
+                            }
+                            for (coord, axis) in instance.coordinates.iter().zip(&self.axes) {
+                                if *coord < axis.min_value || *coord > axis.max_value {
+                                    ctx.report(format!(
+                                        "coordinate {coord} is outside the range of axis '{}'",
+                                        axis.axis_tag
+                                    ));
+                                }
+                            }
+                        })
+                    }
+                })
+            });
+        })
+    }
+}
+
+/// A builder for the `fvar` table.
+///
+/// Axes and instances are described with their English names; ids for these
+/// names are assigned when the table is built.
+#[derive(Clone, Debug, Default)]
+pub struct FvarBuilder {
+    axes: Vec<(VariationAxisRecord, String)>,
+    instances: Vec<(Vec<Fixed>, String, Option<String>)>,
+}
+
+impl FvarBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an axis, with its range in user coordinates.
+    pub fn add_axis(
+        &mut self,
+        tag: Tag,
+        min: f64,
+        default: f64,
+        max: f64,
+        name: impl Into<String>,
+    ) -> &mut Self {
+        let record = VariationAxisRecord::new(
+            tag,
+            Fixed::from_f64(min),
+            Fixed::from_f64(default),
+            Fixed::from_f64(max),
+            0,
+            NameId::default(),
+        );
+        self.axes.push((record, name.into()));
+        self
+    }
+
+    /// Mark the most recently added axis as hidden.
+    ///
+    /// Hidden axes are not intended to be exposed directly in user interfaces.
+    pub fn hidden(&mut self) -> &mut Self {
+        if let Some((axis, _)) = self.axes.last_mut() {
+            axis.flags |= VariationAxisRecord::HIDDEN_AXIS;
+        }
+        self
+    }
+
+    /// Add a named instance.
+    ///
+    /// The coordinates are in user space, in the order that the axes are
+    /// added.
+    pub fn add_instance(
+        &mut self,
+        subfamily_name: impl Into<String>,
+        coordinates: &[f64],
+    ) -> &mut Self {
+        let coordinates = coordinates.iter().copied().map(Fixed::from_f64).collect();
+        self.instances
+            .push((coordinates, subfamily_name.into(), None));
+        self
+    }
+
+    /// Set the PostScript name of the most recently added instance.
+    pub fn post_script_name(&mut self, name: impl Into<String>) -> &mut Self {
+        if let Some((_, _, post_script_name)) = self.instances.last_mut() {
+            *post_script_name = Some(name.into());
+        }
+        self
+    }
+
+    /// Build the table, using `names` to assign name ids.
+    pub fn build(self, names: &mut impl NameIdAllocator) -> Fvar {
+        let axes = self
+            .axes
+            .into_iter()
+            .map(|(mut axis, name)| {
+                axis.axis_name_id = names.name_id(&name);
+                axis
+            })
+            .collect();
+        let instances = self
+            .instances
+            .into_iter()
+            .map(|(coordinates, name, post_script_name)| InstanceRecord {
+                subfamily_name_id: names.name_id(&name),
+                flags: 0,
+                coordinates,
+                post_script_name_id: post_script_name.map(|name| names.name_id(&name)),
+            })
+            .collect();
+        Fvar::new(axes, instances)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::{FontData, FontRead};
+
+    use super::*;
+    use crate::tables::name::NameBuilder;
+
+    #[test]
+    fn builder() {
+        let mut names = NameBuilder::new();
+        names.add(NameId::SUBFAMILY_NAME, 0x409, "Regular");
+        let mut builder = FvarBuilder::new();
+        builder
+            .add_axis(Tag::new(b"wght"), 100.0, 400.0, 900.0, "Weight")
+            .add_axis(Tag::new(b"XOPQ"), 0.0, 50.0, 100.0, "Thin stroke")
+            .hidden()
+            .add_instance("Regular", &[400.0, 50.0])
+            .add_instance("Bold", &[700.0, 50.0])
+            .post_script_name("Family-Bold");
+        let fvar = builder.build(&mut names);
+        assert_eq!(fvar.axes[0].axis_name_id, 256);
+        assert_eq!(fvar.axes[1].flags, VariationAxisRecord::HIDDEN_AXIS);
+
+        let bytes = crate::dump_table(&fvar).unwrap();
+        let read = read_fonts::tables::fvar::Fvar::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read.instance_size(), 14);
+        let axes = read.axes().unwrap();
+        assert_eq!(axes.len(), 2);
+        assert_eq!(axes[1].axis_tag(), Tag::new(b"XOPQ"));
+        assert_eq!(axes[1].default_value(), Fixed::from_f64(50.0));
+        assert_eq!(axes[1].axis_name_id(), 257);
+        let instances = read.instances().unwrap();
+        let regular = instances.get(0).unwrap();
+        // 'Regular' was added with a font-specific id
+        assert_eq!(regular.subfamily_name_id, 258);
+        assert_eq!(regular.post_script_name_id, Some(NameId::new(0xFFFF)));
+        let bold = instances.get(1).unwrap();
+        assert_eq!(bold.coordinates[0].get(), Fixed::from_f64(700.0));
+        let ps_name = bold.post_script_name_id.unwrap();
+        assert_eq!(names.get(ps_name, 0x409), Some("Family-Bold"));
+    }
+
+    #[test]
+    fn validation() {
+        let mut fvar = Fvar::new(
+            vec![VariationAxisRecord::new(
+                Tag::new(b"wght"),
+                Fixed::from_f64(100.0),
+                Fixed::from_f64(400.0),
+                Fixed::from_f64(900.0),
+                0,
+                NameId::new(256),
+            )],
+            vec![InstanceRecord::new(
+                NameId::new(257),
+                vec![Fixed::from_f64(1000.0)],
+            )],
+        );
+        assert!(crate::dump_table(&fvar).is_err());
+        fvar.instances[0].coordinates = vec![];
+        assert!(crate::dump_table(&fvar).is_err());
+        fvar.instances[0].coordinates = vec![Fixed::from_f64(700.0)];
+        assert!(crate::dump_table(&fvar).is_ok());
+        fvar.axes[0].default_value = Fixed::from_f64(50.0);
+        assert!(crate::dump_table(&fvar).is_err());
+    }
+}
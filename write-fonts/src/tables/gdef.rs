@@ -2,10 +2,12 @@
 //!
 //! [GDEF]: https://docs.microsoft.com/en-us/typography/opentype/spec/gdef
 
+use std::collections::BTreeMap;
+
 use types::MajorMinor;
 
 use super::{
-    layout::{ClassDef, CoverageTable, Device},
+    layout::{ClassDef, ClassDefBuilder, CoverageTable, CoverageTableBuilder, DeltaFormat, Device},
     variations::ItemVariationStore,
 };
 
@@ -23,6 +25,256 @@ impl Gdef {
     }
 }
 
+/// A builder for the GDEF table.
+///
+/// Glyph data is collected by glyph id, and checked against the number of
+/// glyphs in the font when the table is built.
+#[derive(Clone, Debug, Default)]
+pub struct GdefBuilder {
+    glyph_classes: BTreeMap<GlyphId, GlyphClassDef>,
+    attach_points: BTreeMap<GlyphId, Vec<u16>>,
+    ligature_carets: BTreeMap<GlyphId, Vec<CaretValue>>,
+    mark_attach_classes: BTreeMap<GlyphId, u16>,
+    mark_glyph_sets: Vec<BTreeSet<GlyphId>>,
+    var_store: Option<ItemVariationStore>,
+}
+
+/// An error in the input to [`GdefBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GdefInputError {
+    /// A glyph id is not less than the number of glyphs in the font.
+    GlyphOutOfRange { glyph: GlyphId, num_glyphs: u16 },
+    /// A glyph has a mark attachment class, but is not classified as a mark.
+    MarkAttachClassForNonMark(GlyphId),
+    /// A glyph has ligature carets, but is not classified as a ligature.
+    CaretsForNonLigature(GlyphId),
+    /// A caret refers to variation data that is not in the variation store.
+    MissingVariationData {
+        glyph: GlyphId,
+        outer: u16,
+        inner: u16,
+    },
+}
+
+impl GdefBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the class of a glyph.
+    pub fn set_glyph_class(&mut self, glyph: GlyphId, class: GlyphClassDef) {
+        self.glyph_classes.insert(glyph, class);
+    }
+
+    /// Set the contour point indices that are used as attachment points for
+    /// a glyph.
+    pub fn set_attach_points(&mut self, glyph: GlyphId, mut points: Vec<u16>) {
+        points.sort_unstable();
+        points.dedup();
+        self.attach_points.insert(glyph, points);
+    }
+
+    /// Set the caret positions of a ligature glyph.
+    ///
+    /// Carets are sorted by their coordinates, or by point index for carets
+    /// that are placed on a contour point.
+    pub fn set_ligature_carets(&mut self, glyph: GlyphId, carets: Vec<CaretValue>) {
+        self.ligature_carets.insert(glyph, carets);
+    }
+
+    /// Set the mark attachment class of a mark glyph.
+    pub fn set_mark_attach_class(&mut self, glyph: GlyphId, class: u16) {
+        self.mark_attach_classes.insert(glyph, class);
+    }
+
+    /// Add a set of mark glyphs, returning the index of the set.
+    ///
+    /// If an identical set has already been added, its index is returned.
+    pub fn add_mark_glyph_set(&mut self, glyphs: BTreeSet<GlyphId>) -> u16 {
+        let index = match self.mark_glyph_sets.iter().position(|set| *set == glyphs) {
+            Some(index) => index,
+            None => {
+                self.mark_glyph_sets.push(glyphs);
+                self.mark_glyph_sets.len() - 1
+            }
+        };
+        // if we're over u16::MAX sets, crash
+        index.try_into().unwrap()
+    }
+
+    /// Set the item variation store, for variable caret positions.
+    pub fn set_var_store(&mut self, var_store: ItemVariationStore) {
+        self.var_store = Some(var_store);
+    }
+
+    /// Build the table, checking the input against the number of glyphs in
+    /// the font.
+    pub fn build(self, num_glyphs: u16) -> Result<Gdef, GdefInputError> {
+        self.validate(num_glyphs)?;
+        let glyph_class_def = (!self.glyph_classes.is_empty()).then(|| {
+            self.glyph_classes
+                .iter()
+                .map(|(glyph, class)| (*glyph, *class as u16))
+                .collect::<ClassDefBuilder>()
+                .build()
+        });
+        let attach_list = (!self.attach_points.is_empty()).then(|| {
+            AttachList::new(
+                coverage(self.attach_points.keys()),
+                self.attach_points
+                    .into_values()
+                    .map(AttachPoint::new)
+                    .collect(),
+            )
+        });
+        let lig_caret_list = (!self.ligature_carets.is_empty()).then(|| {
+            LigCaretList::new(
+                coverage(self.ligature_carets.keys()),
+                self.ligature_carets
+                    .into_values()
+                    .map(|mut carets| {
+                        carets.sort_by_key(CaretValue::sort_key);
+                        LigGlyph::new(carets)
+                    })
+                    .collect(),
+            )
+        });
+        let mark_attach_class_def = (!self.mark_attach_classes.is_empty()).then(|| {
+            self.mark_attach_classes
+                .into_iter()
+                .collect::<ClassDefBuilder>()
+                .build()
+        });
+        let mut gdef = Gdef::new(
+            glyph_class_def,
+            attach_list,
+            lig_caret_list,
+            mark_attach_class_def,
+        );
+        if !self.mark_glyph_sets.is_empty() {
+            gdef.mark_glyph_sets_def = MarkGlyphSets::new(
+                self.mark_glyph_sets
+                    .iter()
+                    .map(|set| coverage(set.iter()))
+                    .collect(),
+            )
+            .into();
+        }
+        gdef.item_var_store = self.var_store.into();
+        Ok(gdef)
+    }
+
+    fn validate(&self, num_glyphs: u16) -> Result<(), GdefInputError> {
+        let all_glyphs = self
+            .glyph_classes
+            .keys()
+            .chain(self.attach_points.keys())
+            .chain(self.ligature_carets.keys())
+            .chain(self.mark_attach_classes.keys())
+            .chain(self.mark_glyph_sets.iter().flatten());
+        if let Some(glyph) = all_glyphs.max() {
+            if glyph.to_u16() >= num_glyphs {
+                return Err(GdefInputError::GlyphOutOfRange {
+                    glyph: *glyph,
+                    num_glyphs,
+                });
+            }
+        }
+        // class-based checks only apply if glyphs have been classified
+        if !self.glyph_classes.is_empty() {
+            let has_class = |glyph, class| self.glyph_classes.get(glyph) == Some(&class);
+            if let Some(glyph) = self
+                .mark_attach_classes
+                .keys()
+                .find(|glyph| !has_class(*glyph, GlyphClassDef::Mark))
+            {
+                return Err(GdefInputError::MarkAttachClassForNonMark(*glyph));
+            }
+            if let Some(glyph) = self
+                .ligature_carets
+                .keys()
+                .find(|glyph| !has_class(*glyph, GlyphClassDef::Ligature))
+            {
+                return Err(GdefInputError::CaretsForNonLigature(*glyph));
+            }
+        }
+        for (glyph, carets) in &self.ligature_carets {
+            for caret in carets {
+                let CaretValue::Format3(caret) = caret else {
+                    continue;
+                };
+                if caret.device.delta_format != DeltaFormat::VariationIndex {
+                    continue;
+                }
+                // a VariationIndex table has the same layout as a Device
+                let (outer, inner) = (caret.device.start_size, caret.device.end_size);
+                let exists = self
+                    .var_store
+                    .as_ref()
+                    .and_then(|store| store.item_variation_datas.get(outer as usize))
+                    .and_then(|data| data.as_ref())
+                    .is_some_and(|data| inner < data.item_count);
+                if !exists {
+                    return Err(GdefInputError::MissingVariationData {
+                        glyph: *glyph,
+                        outer,
+                        inner,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CaretValue {
+    fn sort_key(&self) -> i32 {
+        match self {
+            CaretValue::Format1(caret) => caret.coordinate as i32,
+            CaretValue::Format2(caret) => caret.caret_value_point_index as i32,
+            CaretValue::Format3(caret) => caret.coordinate as i32,
+        }
+    }
+}
+
+fn coverage<'a>(glyphs: impl Iterator<Item = &'a GlyphId>) -> CoverageTable {
+    glyphs.copied().collect::<CoverageTableBuilder>().build()
+}
+
+impl std::fmt::Display for GdefInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GdefInputError::GlyphOutOfRange { glyph, num_glyphs } => write!(
+                f,
+                "glyph {} is out of range for a font with {num_glyphs} glyphs",
+                glyph.to_u16()
+            ),
+            GdefInputError::MarkAttachClassForNonMark(glyph) => write!(
+                f,
+                "glyph {} has a mark attachment class but is not a mark",
+                glyph.to_u16()
+            ),
+            GdefInputError::CaretsForNonLigature(glyph) => write!(
+                f,
+                "glyph {} has ligature carets but is not a ligature",
+                glyph.to_u16()
+            ),
+            GdefInputError::MissingVariationData {
+                glyph,
+                outer,
+                inner,
+            } => write!(
+                f,
+                "caret of glyph {} uses variation data {outer}/{inner}, which is not in the variation store",
+                glyph.to_u16()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GdefInputError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,4 +295,132 @@ mod tests {
         assert_eq!(loaded.version(), MajorMinor::VERSION_1_3);
         assert!(!loaded.item_var_store_offset().unwrap().is_null());
     }
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = GdefBuilder::new();
+        builder.set_glyph_class(gid(1), GlyphClassDef::Base);
+        builder.set_glyph_class(gid(2), GlyphClassDef::Ligature);
+        builder.set_glyph_class(gid(3), GlyphClassDef::Mark);
+        builder.set_glyph_class(gid(4), GlyphClassDef::Mark);
+        builder.set_mark_attach_class(gid(3), 1);
+        builder.set_mark_attach_class(gid(4), 2);
+        builder.set_attach_points(gid(1), vec![5, 2, 5]);
+        builder.set_ligature_carets(
+            gid(2),
+            vec![CaretValue::format_1(600), CaretValue::format_1(300)],
+        );
+        let marks = [gid(3), gid(4)].into_iter().collect::<BTreeSet<_>>();
+        assert_eq!(builder.add_mark_glyph_set(marks.clone()), 0);
+        assert_eq!(builder.add_mark_glyph_set([gid(4)].into()), 1);
+        assert_eq!(builder.add_mark_glyph_set(marks), 0);
+
+        let gdef = builder.build(5).unwrap();
+        let bytes = crate::dump_table(&gdef).unwrap();
+        let read = read_fonts::tables::gdef::Gdef::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read.version(), MajorMinor::VERSION_1_2);
+
+        let classes = read.glyph_class_def().unwrap().unwrap();
+        assert_eq!(classes.get(gid(2)), GlyphClassDef::Ligature as u16);
+        assert_eq!(classes.get(gid(4)), GlyphClassDef::Mark as u16);
+        let mark_classes = read.mark_attach_class_def().unwrap().unwrap();
+        assert_eq!(mark_classes.get(gid(4)), 2);
+
+        let attach_list = read.attach_list().unwrap().unwrap();
+        let points = attach_list.attach_points().next().unwrap().unwrap();
+        let points = points
+            .point_indices()
+            .iter()
+            .map(|idx| idx.get())
+            .collect::<Vec<_>>();
+        assert_eq!(points, [2, 5]);
+
+        let lig_carets = read.lig_caret_list().unwrap().unwrap();
+        let carets = lig_carets.lig_glyphs().next().unwrap().unwrap();
+        let coords = carets
+            .caret_values()
+            .map(|caret| match caret.unwrap() {
+                read_fonts::tables::gdef::CaretValue::Format1(caret) => caret.coordinate(),
+                _ => panic!("unexpected caret format"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(coords, [300, 600]);
+
+        let sets = read.mark_glyph_sets_def().unwrap().unwrap();
+        assert_eq!(sets.mark_glyph_set_count(), 2);
+        let second = sets.coverages().nth(1).unwrap().unwrap();
+        assert_eq!(second.iter().collect::<Vec<_>>(), [gid(4)]);
+    }
+
+    #[test]
+    fn builder_validation() {
+        let mut builder = GdefBuilder::new();
+        builder.set_glyph_class(gid(1), GlyphClassDef::Base);
+        builder.set_glyph_class(gid(2), GlyphClassDef::Mark);
+        assert!(builder.clone().build(3).is_ok());
+        assert_eq!(
+            builder.clone().build(2).unwrap_err(),
+            GdefInputError::GlyphOutOfRange {
+                glyph: gid(2),
+                num_glyphs: 2
+            }
+        );
+
+        let mut non_mark = builder.clone();
+        non_mark.set_mark_attach_class(gid(1), 1);
+        assert_eq!(
+            non_mark.build(3).unwrap_err(),
+            GdefInputError::MarkAttachClassForNonMark(gid(1))
+        );
+
+        let mut non_ligature = builder.clone();
+        non_ligature.set_ligature_carets(gid(1), vec![CaretValue::format_1(100)]);
+        assert_eq!(
+            non_ligature.build(3).unwrap_err(),
+            GdefInputError::CaretsForNonLigature(gid(1))
+        );
+
+        // without glyph classes, nothing is known about glyph types
+        let mut unclassified = GdefBuilder::new();
+        unclassified.set_mark_attach_class(gid(1), 1);
+        unclassified.set_ligature_carets(gid(1), vec![CaretValue::format_1(100)]);
+        assert!(unclassified.build(3).is_ok());
+    }
+
+    #[test]
+    fn builder_variable_carets() {
+        let variable_caret = CaretValue::format_3(
+            100,
+            Device {
+                start_size: 0,
+                end_size: 1,
+                delta_format: DeltaFormat::VariationIndex,
+                delta_value: Vec::new(),
+            },
+        );
+        let mut builder = GdefBuilder::new();
+        builder.set_ligature_carets(gid(1), vec![variable_caret]);
+        assert_eq!(
+            builder.clone().build(2).unwrap_err(),
+            GdefInputError::MissingVariationData {
+                glyph: gid(1),
+                outer: 0,
+                inner: 1
+            }
+        );
+
+        let mut var_store = ItemVariationStore::default();
+        let data = crate::tables::variations::ItemVariationData {
+            item_count: 2,
+            ..Default::default()
+        };
+        var_store.item_variation_datas.push(data.into());
+        builder.set_var_store(var_store);
+        let gdef = builder.build(2).unwrap();
+        assert_eq!(gdef.compute_version(), MajorMinor::VERSION_1_3);
+    }
 }
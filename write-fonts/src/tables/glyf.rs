@@ -1,28 +1,43 @@
 //! The [glyf (Glyph Data)](https://docs.microsoft.com/en-us/typography/opentype/spec/glyf) table
 
+use std::collections::HashMap;
+
 use kurbo::{BezPath, Rect, Shape};
 
-use read_fonts::tables::glyf::{CurvePoint, SimpleGlyphFlags};
+use read_fonts::{tables::glyf::SimpleGlyphFlags, FontData, TopLevelTable};
+use types::{F2Dot14, GlyphId, Tag};
+
+use crate::{
+    from_obj::{FromObjRef, FromTableRef, ToOwnedObj},
+    tables::loca::{Loca, LocaFormat},
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+pub use read_fonts::tables::glyf::{Anchor, CompositeGlyphFlags, CurvePoint, Transform};
 
-use crate::FontWrite;
+#[path = "./cu2qu.rs"]
+pub mod cu2qu;
 
 /// A single contour, comprising only line and quadratic bezier segments
 #[derive(Clone, Debug)]
 pub struct Contour(Vec<CurvePoint>);
 
+/// The bounding box of a glyph.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
-struct Bbox {
-    x_min: i16,
-    y_min: i16,
-    x_max: i16,
-    y_max: i16,
+pub struct Bbox {
+    pub x_min: i16,
+    pub y_min: i16,
+    pub x_max: i16,
+    pub y_max: i16,
 }
 
 /// A simple (without components) glyph
+#[derive(Clone, Debug)]
 pub struct SimpleGlyph {
     bbox: Bbox,
     contours: Vec<Contour>,
-    _instructions: Vec<u8>,
+    instructions: Vec<u8>,
 }
 
 /// An error if an input curve is malformed
@@ -31,6 +46,24 @@ pub enum BadKurbo {
     HasCubic,
     TooSmall,
     MissingMove,
+    /// The paths of an interpolatable glyph have different structures
+    Incompatible,
+    /// A cubic could not be approximated within the maximum error
+    ApproximationFailed,
+}
+
+/// Options for converting cubic curves to quadratics, when building glyphs
+/// from paths with cubic segments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicConversion {
+    /// The maximum distance between a curve and its approximation, in font
+    /// units.
+    pub max_error: f64,
+    /// Reverse the direction of each contour.
+    ///
+    /// Cubic outlines conventionally run counter-clockwise, and TrueType
+    /// outlines clockwise.
+    pub reverse_direction: bool,
 }
 
 /// A helper trait for converting other point types to open-type compatible reprs
@@ -88,12 +121,78 @@ impl Contour {
     }
 }
 
+impl Contour {
+    /// Add a quadratic spline, with implied on-curve points between
+    /// consecutive off-curve points.
+    fn spline_to(&mut self, off_curve: &[kurbo::Point], end: kurbo::Point) {
+        for pt in off_curve {
+            let (x, y) = pt.get();
+            self.0.push(CurvePoint::off_curve(x, y));
+        }
+        self.line_to(end);
+    }
+
+    /// Reverse the direction of the contour, keeping the same start point.
+    fn reverse(&mut self) {
+        if let Some(rest) = self.0.get_mut(1..) {
+            rest.reverse();
+        }
+    }
+}
+
+impl CubicConversion {
+    /// Convert with the given maximum error, keeping the direction of
+    /// contours.
+    pub fn new(max_error: f64) -> Self {
+        CubicConversion {
+            max_error,
+            reverse_direction: false,
+        }
+    }
+
+    /// The options used by default when compiling sources with fontmake's
+    /// cu2qu filter: a maximum error of 1/1000 em, and reversed contours.
+    pub fn cu2qu_compatible(units_per_em: u16) -> Self {
+        CubicConversion {
+            max_error: units_per_em as f64 * 0.001,
+            reverse_direction: true,
+        }
+    }
+}
+
 impl SimpleGlyph {
+    /// Create a new simple glyph from a set of contours.
+    ///
+    /// The bounding box is computed from the points of the contours.
+    pub fn new(contours: Vec<Contour>) -> Self {
+        let bbox = Bbox::from_points(contours.iter().flatten()).unwrap_or_default();
+        SimpleGlyph {
+            bbox,
+            contours,
+            instructions: Default::default(),
+        }
+    }
+
+    /// The bounding box of this glyph.
+    pub fn bbox(&self) -> Bbox {
+        self.bbox
+    }
+
+    /// The contours of this glyph.
+    pub fn contours(&self) -> &[Contour] {
+        &self.contours
+    }
+
+    /// Set the TrueType instructions for this glyph.
+    pub fn set_instructions(&mut self, instructions: Vec<u8>) {
+        self.instructions = instructions;
+    }
+
     /// Attempt to create a simple glyph from a kurbo `BezPath`
     ///
-    /// The path may contain only line and quadratic bezier segments. The caller
-    /// is responsible for converting any cubic segments to quadratics before
-    /// calling.
+    /// The path may contain only line and quadratic bezier segments. Use
+    /// [`from_kurbo_cubic`](Self::from_kurbo_cubic) for paths with cubic
+    /// segments.
     ///
     /// Returns an error if the input path is malformed; that is, if it is empty,
     /// contains cubic segments, or does not begin with a 'move' instruction.
@@ -141,10 +240,129 @@ impl SimpleGlyph {
         Ok(SimpleGlyph {
             bbox: bbox.into(),
             contours,
-            _instructions: Default::default(),
+            instructions: Default::default(),
         })
     }
 
+    /// Create a simple glyph from a kurbo `BezPath` that may contain cubic
+    /// segments.
+    ///
+    /// Cubic segments are converted to quadratic splines with
+    /// [cu2qu](cu2qu::cubic_to_quadratic); other segments are kept as they are.
+    pub fn from_kurbo_cubic(path: &BezPath, conversion: CubicConversion) -> Result<Self, BadKurbo> {
+        Self::interpolatable_from_kurbo(std::slice::from_ref(path), conversion)
+            .map(|mut glyphs| glyphs.pop().unwrap())
+    }
+
+    /// Create simple glyphs from the paths of the same glyph in each master of
+    /// a variable font.
+    ///
+    /// Cubic segments are converted so that the glyphs have the same number of
+    /// points, as required for interpolation. Returns an error if the paths do
+    /// not have the same structure.
+    pub fn interpolatable_from_kurbo(
+        paths: &[BezPath],
+        conversion: CubicConversion,
+    ) -> Result<Vec<Self>, BadKurbo> {
+        use kurbo::PathEl;
+
+        let Some(first) = paths.first() else {
+            return Ok(Vec::new());
+        };
+        let num_elements = first.elements().len();
+        if paths
+            .iter()
+            .any(|path| path.elements().len() != num_elements)
+        {
+            return Err(BadKurbo::Incompatible);
+        }
+
+        let mut contours = vec![Vec::<Contour>::new(); paths.len()];
+        let mut current = vec![None::<Contour>; paths.len()];
+        // the unrounded end of the last segment in each path
+        let mut last = vec![kurbo::Point::ZERO; paths.len()];
+        for i in 0..num_elements {
+            let elements = paths
+                .iter()
+                .map(|path| path.elements()[i])
+                .collect::<Vec<_>>();
+            let kind = std::mem::discriminant(&elements[0]);
+            if elements.iter().any(|el| std::mem::discriminant(el) != kind) {
+                return Err(BadKurbo::Incompatible);
+            }
+            // cubics are converted together, so that they stay compatible
+            if let PathEl::CurveTo(..) = elements[0] {
+                let cubics = elements
+                    .iter()
+                    .zip(&last)
+                    .map(|(el, start)| match el {
+                        PathEl::CurveTo(p1, p2, p3) => kurbo::CubicBez::new(*start, *p1, *p2, *p3),
+                        _ => unreachable!("elements have the same kind"),
+                    })
+                    .collect::<Vec<_>>();
+                let splines = cu2qu::cubics_to_quadratic(&cubics, conversion.max_error)
+                    .ok_or(BadKurbo::ApproximationFailed)?;
+                for (j, spline) in splines.iter().enumerate() {
+                    let end = spline[spline.len() - 1];
+                    current[j]
+                        .as_mut()
+                        .ok_or(BadKurbo::MissingMove)?
+                        .spline_to(&spline[1..spline.len() - 1], end);
+                    last[j] = end;
+                }
+                continue;
+            }
+            for (j, el) in elements.into_iter().enumerate() {
+                match el {
+                    PathEl::MoveTo(pt) => {
+                        contours[j].extend(current[j].replace(Contour::new(pt)));
+                        last[j] = pt;
+                    }
+                    PathEl::LineTo(pt) => {
+                        current[j]
+                            .as_mut()
+                            .ok_or(BadKurbo::MissingMove)?
+                            .line_to(pt);
+                        last[j] = pt;
+                    }
+                    PathEl::QuadTo(p0, p1) => {
+                        current[j]
+                            .as_mut()
+                            .ok_or(BadKurbo::MissingMove)?
+                            .quad_to(p0, p1);
+                        last[j] = p1;
+                    }
+                    PathEl::CurveTo(..) => unreachable!("cubics are converted above"),
+                    PathEl::ClosePath => (),
+                }
+            }
+        }
+        for (contours, current) in contours.iter_mut().zip(current) {
+            contours.extend(current);
+        }
+
+        for i in 0..contours[0].len() {
+            if contours.iter().any(|contours| contours[i].len() < 2) {
+                return Err(BadKurbo::TooSmall);
+            }
+            // the closing point is only dropped if it can be in every master
+            if contours
+                .iter()
+                .all(|contours| contours[i].0.first() == contours[i].0.last())
+            {
+                contours.iter_mut().for_each(|contours| {
+                    contours[i].0.pop();
+                });
+            }
+            if conversion.reverse_direction {
+                contours
+                    .iter_mut()
+                    .for_each(|contours| contours[i].reverse());
+            }
+        }
+        Ok(contours.into_iter().map(SimpleGlyph::new).collect())
+    }
+
     /// Compute the flags and deltas for this glyph's points.
     ///
     /// This does not do the final binary encoding, and it also does not handle
@@ -296,6 +514,12 @@ impl RepeatableFlag {
     }
 }
 
+impl From<Vec<CurvePoint>> for Contour {
+    fn from(points: Vec<CurvePoint>) -> Self {
+        Contour(points)
+    }
+}
+
 impl<'a> IntoIterator for &'a Contour {
     type Item = &'a CurvePoint;
 
@@ -309,7 +533,7 @@ impl<'a> IntoIterator for &'a Contour {
 impl FontWrite for SimpleGlyph {
     fn write_into(&self, writer: &mut crate::TableWriter) {
         assert!(self.contours.len() < i16::MAX as usize);
-        assert!(self._instructions.len() < u16::MAX as usize);
+        assert!(self.instructions.len() < u16::MAX as usize);
         let n_contours = self.contours.len() as i16;
         n_contours.write_into(writer);
         self.bbox.write_into(writer);
@@ -319,8 +543,8 @@ impl FontWrite for SimpleGlyph {
             cur += contour.len();
             (cur as u16 - 1).write_into(writer);
         }
-        (self._instructions.len() as u16).write_into(writer);
-        self._instructions.write_into(writer);
+        (self.instructions.len() as u16).write_into(writer);
+        self.instructions.write_into(writer);
 
         let deltas = self.compute_point_deltas().collect::<Vec<_>>();
         RepeatableFlag::iter_from_flags(deltas.iter().map(|(flag, _, _)| *flag))
@@ -336,6 +560,474 @@ impl crate::validate::Validate for SimpleGlyph {
     }
 }
 
+/// A glyph composed of other glyphs.
+#[derive(Clone, Debug)]
+pub struct CompositeGlyph {
+    bbox: Bbox,
+    components: Vec<Component>,
+    instructions: Vec<u8>,
+}
+
+/// A reference to another glyph, as part of a [CompositeGlyph].
+#[derive(Clone, Debug)]
+pub struct Component {
+    /// The id of the component glyph.
+    pub glyph: GlyphId,
+    /// The position of the component.
+    pub anchor: Anchor,
+    /// Flags that control how the component is used.
+    pub flags: ComponentFlags,
+    /// A scale or 2x2 transformation applied to the component.
+    pub transform: Transform,
+}
+
+/// The flags of a [Component] that are set by the user.
+///
+/// The remaining [CompositeGlyphFlags] are determined by the component's
+/// anchor and transform when it is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComponentFlags {
+    /// Round the x and y offsets to the grid when hinting.
+    pub round_xy_to_grid: bool,
+    /// Use the metrics of this component for the composite glyph.
+    pub use_my_metrics: bool,
+    /// The components of the composite glyph overlap; only meaningful
+    /// for the first component.
+    pub overlap_compound: bool,
+    /// The component offset should be scaled along with the component.
+    pub scaled_component_offset: bool,
+    /// The component offset should not be scaled.
+    pub unscaled_component_offset: bool,
+}
+
+impl CompositeGlyph {
+    /// Create a new composite glyph from a single component.
+    ///
+    /// The `bbox` is the bounding box of the component, after it has been
+    /// positioned and transformed.
+    pub fn new(component: Component, bbox: Bbox) -> Self {
+        CompositeGlyph {
+            bbox,
+            components: vec![component],
+            instructions: Vec::new(),
+        }
+    }
+
+    /// Add another component to this glyph.
+    ///
+    /// The `bbox` is the bounding box of the component, after it has been
+    /// positioned and transformed; the bounding box of the glyph is
+    /// expanded to include it.
+    pub fn add_component(&mut self, component: Component, bbox: Bbox) {
+        self.bbox = self.bbox.union(bbox);
+        self.components.push(component);
+    }
+
+    /// The bounding box of this glyph.
+    pub fn bbox(&self) -> Bbox {
+        self.bbox
+    }
+
+    /// The components of this glyph.
+    pub fn components(&self) -> &[Component] {
+        &self.components
+    }
+
+    /// Set the TrueType instructions for this glyph.
+    pub fn set_instructions(&mut self, instructions: Vec<u8>) {
+        self.instructions = instructions;
+    }
+}
+
+impl Component {
+    /// Create a new component.
+    pub fn new(
+        glyph: GlyphId,
+        anchor: Anchor,
+        transform: Transform,
+        flags: impl Into<ComponentFlags>,
+    ) -> Self {
+        Component {
+            glyph,
+            anchor,
+            flags: flags.into(),
+            transform,
+        }
+    }
+
+    fn compute_flags(&self, more_components: bool) -> CompositeGlyphFlags {
+        let mut flags = CompositeGlyphFlags::empty();
+        let user_flags = [
+            (
+                self.flags.round_xy_to_grid,
+                CompositeGlyphFlags::ROUND_XY_TO_GRID,
+            ),
+            (
+                self.flags.use_my_metrics,
+                CompositeGlyphFlags::USE_MY_METRICS,
+            ),
+            (
+                self.flags.overlap_compound,
+                CompositeGlyphFlags::OVERLAP_COMPOUND,
+            ),
+            (
+                self.flags.scaled_component_offset,
+                CompositeGlyphFlags::SCALED_COMPONENT_OFFSET,
+            ),
+            (
+                self.flags.unscaled_component_offset,
+                CompositeGlyphFlags::UNSCALED_COMPONENT_OFFSET,
+            ),
+        ];
+        for (is_set, flag) in user_flags {
+            if is_set {
+                flags |= flag;
+            }
+        }
+        if more_components {
+            flags |= CompositeGlyphFlags::MORE_COMPONENTS;
+        }
+
+        let args_are_words = match self.anchor {
+            Anchor::Offset { x, y } => {
+                flags |= CompositeGlyphFlags::ARGS_ARE_XY_VALUES;
+                i8::try_from(x).is_err() || i8::try_from(y).is_err()
+            }
+            Anchor::Point { base, component } => {
+                u8::try_from(base).is_err() || u8::try_from(component).is_err()
+            }
+        };
+        if args_are_words {
+            flags |= CompositeGlyphFlags::ARG_1_AND_2_ARE_WORDS;
+        }
+
+        let Transform { xx, yx, xy, yy } = self.transform;
+        if yx != F2Dot14::ZERO || xy != F2Dot14::ZERO {
+            flags |= CompositeGlyphFlags::WE_HAVE_A_TWO_BY_TWO;
+        } else if xx != yy {
+            flags |= CompositeGlyphFlags::WE_HAVE_AN_X_AND_Y_SCALE;
+        } else if xx != F2Dot14::ONE {
+            flags |= CompositeGlyphFlags::WE_HAVE_A_SCALE;
+        }
+        flags
+    }
+
+    fn write_with_flags(&self, flags: CompositeGlyphFlags, writer: &mut TableWriter) {
+        flags.bits().write_into(writer);
+        self.glyph.write_into(writer);
+        let args_are_words = flags.contains(CompositeGlyphFlags::ARG_1_AND_2_ARE_WORDS);
+        match (self.anchor, args_are_words) {
+            (Anchor::Offset { x, y }, true) => [x, y].write_into(writer),
+            (Anchor::Offset { x, y }, false) => [x as i8, y as i8].write_into(writer),
+            (Anchor::Point { base, component }, true) => [base, component].write_into(writer),
+            (Anchor::Point { base, component }, false) => {
+                [base as u8, component as u8].write_into(writer)
+            }
+        }
+        let Transform { xx, yx, xy, yy } = self.transform;
+        if flags.contains(CompositeGlyphFlags::WE_HAVE_A_SCALE) {
+            xx.write_into(writer);
+        } else if flags.contains(CompositeGlyphFlags::WE_HAVE_AN_X_AND_Y_SCALE) {
+            [xx, yy].write_into(writer);
+        } else if flags.contains(CompositeGlyphFlags::WE_HAVE_A_TWO_BY_TWO) {
+            [xx, yx, xy, yy].write_into(writer);
+        }
+    }
+}
+
+impl From<CompositeGlyphFlags> for ComponentFlags {
+    fn from(flags: CompositeGlyphFlags) -> Self {
+        ComponentFlags {
+            round_xy_to_grid: flags.contains(CompositeGlyphFlags::ROUND_XY_TO_GRID),
+            use_my_metrics: flags.contains(CompositeGlyphFlags::USE_MY_METRICS),
+            overlap_compound: flags.contains(CompositeGlyphFlags::OVERLAP_COMPOUND),
+            scaled_component_offset: flags.contains(CompositeGlyphFlags::SCALED_COMPONENT_OFFSET),
+            unscaled_component_offset: flags
+                .contains(CompositeGlyphFlags::UNSCALED_COMPONENT_OFFSET),
+        }
+    }
+}
+
+impl FontWrite for CompositeGlyph {
+    fn write_into(&self, writer: &mut TableWriter) {
+        // composite glyphs are identified by a negative number of contours
+        (-1i16).write_into(writer);
+        self.bbox.write_into(writer);
+        let last = self.components.len() - 1;
+        for (i, component) in self.components.iter().enumerate() {
+            let mut flags = component.compute_flags(i < last);
+            if i == last && !self.instructions.is_empty() {
+                flags |= CompositeGlyphFlags::WE_HAVE_INSTRUCTIONS;
+            }
+            component.write_with_flags(flags, writer);
+        }
+        if !self.instructions.is_empty() {
+            assert!(self.instructions.len() < u16::MAX as usize);
+            (self.instructions.len() as u16).write_into(writer);
+            self.instructions.write_into(writer);
+        }
+    }
+}
+
+impl Validate for CompositeGlyph {
+    fn validate_impl(&self, _ctx: &mut ValidationCtx) {}
+}
+
+/// A glyph in the `glyf` table.
+#[derive(Clone, Debug, Default)]
+pub enum Glyph {
+    /// A glyph with no outline, such as a space.
+    #[default]
+    Empty,
+    Simple(SimpleGlyph),
+    Composite(CompositeGlyph),
+}
+
+impl Glyph {
+    /// The bounding box of this glyph, if it is not empty.
+    pub fn bbox(&self) -> Option<Bbox> {
+        match self {
+            Glyph::Empty => None,
+            Glyph::Simple(glyph) => Some(glyph.bbox()),
+            Glyph::Composite(glyph) => Some(glyph.bbox()),
+        }
+    }
+}
+
+impl<'a> FromObjRef<read_fonts::tables::glyf::SimpleGlyph<'a>> for SimpleGlyph {
+    fn from_obj_ref(from: &read_fonts::tables::glyf::SimpleGlyph<'a>, _: FontData) -> Self {
+        let mut points = from.points();
+        let mut start = 0;
+        let contours = from
+            .end_pts_of_contours()
+            .iter()
+            .map(|end| {
+                let end = end.get() as usize + 1;
+                let len = end.saturating_sub(start);
+                start = end;
+                Contour(points.by_ref().take(len).collect())
+            })
+            .collect();
+        SimpleGlyph {
+            bbox: Bbox {
+                x_min: from.x_min(),
+                y_min: from.y_min(),
+                x_max: from.x_max(),
+                y_max: from.y_max(),
+            },
+            contours,
+            instructions: from.instructions().to_owned(),
+        }
+    }
+}
+
+impl<'a> FromTableRef<read_fonts::tables::glyf::SimpleGlyph<'a>> for SimpleGlyph {}
+
+impl<'a> FromObjRef<read_fonts::tables::glyf::CompositeGlyph<'a>> for CompositeGlyph {
+    fn from_obj_ref(from: &read_fonts::tables::glyf::CompositeGlyph<'a>, _: FontData) -> Self {
+        let components = from
+            .components()
+            .map(|component| {
+                Component::new(
+                    component.glyph,
+                    component.anchor,
+                    component.transform,
+                    component.flags,
+                )
+            })
+            .collect();
+        CompositeGlyph {
+            bbox: Bbox {
+                x_min: from.x_min(),
+                y_min: from.y_min(),
+                x_max: from.x_max(),
+                y_max: from.y_max(),
+            },
+            components,
+            instructions: from.instructions().unwrap_or_default().to_owned(),
+        }
+    }
+}
+
+impl<'a> FromTableRef<read_fonts::tables::glyf::CompositeGlyph<'a>> for CompositeGlyph {}
+
+impl<'a> FromObjRef<read_fonts::tables::glyf::Glyph<'a>> for Glyph {
+    fn from_obj_ref(from: &read_fonts::tables::glyf::Glyph<'a>, data: FontData) -> Self {
+        match from {
+            read_fonts::tables::glyf::Glyph::Simple(glyph) => {
+                Glyph::Simple(glyph.to_owned_obj(data))
+            }
+            read_fonts::tables::glyf::Glyph::Composite(glyph) => {
+                Glyph::Composite(glyph.to_owned_obj(data))
+            }
+        }
+    }
+}
+
+impl<'a> FromTableRef<read_fonts::tables::glyf::Glyph<'a>> for Glyph {}
+
+impl From<SimpleGlyph> for Glyph {
+    fn from(glyph: SimpleGlyph) -> Self {
+        Glyph::Simple(glyph)
+    }
+}
+
+impl From<CompositeGlyph> for Glyph {
+    fn from(glyph: CompositeGlyph) -> Self {
+        Glyph::Composite(glyph)
+    }
+}
+
+impl FontWrite for Glyph {
+    fn write_into(&self, writer: &mut TableWriter) {
+        match self {
+            Glyph::Empty => (),
+            Glyph::Simple(glyph) => glyph.write_into(writer),
+            Glyph::Composite(glyph) => glyph.write_into(writer),
+        }
+    }
+}
+
+impl Validate for Glyph {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        match self {
+            Glyph::Empty => (),
+            Glyph::Simple(glyph) => glyph.validate_impl(ctx),
+            Glyph::Composite(glyph) => glyph.validate_impl(ctx),
+        }
+    }
+}
+
+/// The [glyf (Glyph Data)](https://docs.microsoft.com/en-us/typography/opentype/spec/glyf) table
+///
+/// This is constructed with a [GlyfLocaBuilder].
+#[derive(Clone, Debug, Default)]
+pub struct Glyf(Vec<u8>);
+
+impl TopLevelTable for Glyf {
+    const TAG: Tag = Tag::GLYF;
+}
+
+impl FontWrite for Glyf {
+    fn write_into(&self, writer: &mut TableWriter) {
+        writer.write_slice(&self.0)
+    }
+}
+
+impl Validate for Glyf {
+    fn validate_impl(&self, _ctx: &mut ValidationCtx) {}
+}
+
+/// A builder for the `glyf` and `loca` tables.
+///
+/// Glyphs are added in glyph id order. Each glyph is padded to a four-byte
+/// boundary, and the `loca` format is chosen based on the final size of the
+/// `glyf` table.
+#[derive(Clone, Debug)]
+pub struct GlyfLocaBuilder {
+    glyph_data: Vec<u8>,
+    offsets: Vec<u32>,
+    // the serialized simple glyphs seen so far, if deduplicating
+    seen: Option<HashMap<Vec<u8>, GlyphId>>,
+    duplicates: Vec<(GlyphId, GlyphId)>,
+}
+
+impl GlyfLocaBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        GlyfLocaBuilder {
+            glyph_data: Vec::new(),
+            offsets: vec![0],
+            seen: None,
+            duplicates: Vec::new(),
+        }
+    }
+
+    /// Write simple glyphs that are identical to an earlier glyph as a
+    /// reference to that glyph.
+    ///
+    /// The offsets in `loca` must be in ascending order, so two glyphs cannot
+    /// share the same data. Instead, a repeated glyph is written as a
+    /// composite glyph with a single component, the earlier glyph, at the
+    /// origin. This is only done if the composite is smaller than the
+    /// glyph it replaces.
+    ///
+    /// This should not be used for variable fonts, since the `gvar` deltas
+    /// for a composite glyph apply to its components and not to its points.
+    pub fn deduplicate_glyphs(&mut self) -> &mut Self {
+        self.seen.get_or_insert_with(Default::default);
+        self
+    }
+
+    /// The glyphs that were written as a reference to an identical glyph.
+    ///
+    /// Each item is the id of a repeated glyph and the id of the glyph it
+    /// refers to.
+    pub fn duplicates(&self) -> &[(GlyphId, GlyphId)] {
+        &self.duplicates
+    }
+
+    /// Add a glyph; its id is the number of glyphs previously added.
+    pub fn add_glyph(&mut self, glyph: &Glyph) -> &mut Self {
+        let gid = GlyphId::new((self.offsets.len() - 1) as u16);
+        let mut data = write_glyph(glyph);
+        if let (Some(seen), Glyph::Simple(simple)) = (self.seen.as_mut(), glyph) {
+            match seen.get(&data) {
+                Some(original) => {
+                    let component = Component::new(
+                        *original,
+                        Anchor::Offset { x: 0, y: 0 },
+                        Transform::default(),
+                        ComponentFlags::default(),
+                    );
+                    let reference =
+                        write_glyph(&CompositeGlyph::new(component, simple.bbox()).into());
+                    if reference.len() < data.len() {
+                        self.duplicates.push((gid, *original));
+                        data = reference;
+                    }
+                }
+                None => {
+                    seen.insert(data.clone(), gid);
+                }
+            }
+        }
+        self.add_glyph_data(&data)
+    }
+
+    /// Add the data of a glyph that has already been compiled, such as a
+    /// glyph copied from an existing font.
+    pub(crate) fn add_glyph_data(&mut self, data: &[u8]) -> &mut Self {
+        self.glyph_data.extend_from_slice(data);
+        let padding = (4 - self.glyph_data.len() % 4) % 4;
+        self.glyph_data.resize(self.glyph_data.len() + padding, 0);
+        self.offsets.push(self.glyph_data.len() as u32);
+        self
+    }
+
+    /// Build the tables.
+    ///
+    /// Also returns the format of the `loca` table, which must be recorded in
+    /// the `head` table; see [Head::set_loca_format](crate::tables::head::Head::set_loca_format).
+    pub fn build(self) -> (Glyf, Loca, LocaFormat) {
+        let loca = Loca::new(self.offsets);
+        let format = loca.format();
+        (Glyf(self.glyph_data), loca, format)
+    }
+}
+
+fn write_glyph(glyph: &Glyph) -> Vec<u8> {
+    let mut writer = TableWriter::default();
+    glyph.write_into(&mut writer);
+    writer.into_data()
+}
+
+impl Default for GlyfLocaBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl From<Rect> for Bbox {
     fn from(value: Rect) -> Self {
         Bbox {
@@ -347,6 +1039,30 @@ impl From<Rect> for Bbox {
     }
 }
 
+impl Bbox {
+    /// Return the smallest bounding box that contains both `self` and `other`.
+    pub fn union(self, other: Bbox) -> Bbox {
+        Bbox {
+            x_min: self.x_min.min(other.x_min),
+            y_min: self.y_min.min(other.y_min),
+            x_max: self.x_max.max(other.x_max),
+            y_max: self.y_max.max(other.y_max),
+        }
+    }
+
+    fn from_points<'a>(points: impl IntoIterator<Item = &'a CurvePoint>) -> Option<Bbox> {
+        points
+            .into_iter()
+            .map(|pt| Bbox {
+                x_min: pt.x,
+                y_min: pt.y,
+                x_max: pt.x,
+                y_max: pt.y,
+            })
+            .reduce(Bbox::union)
+    }
+}
+
 impl FontWrite for Bbox {
     fn write_into(&self, writer: &mut crate::TableWriter) {
         let Bbox {
@@ -366,7 +1082,7 @@ mod tests {
     };
 
     use super::*;
-    use crate::read::test_data;
+    use crate::{from_obj::ToOwnedTable, read::test_data};
 
     #[test]
     #[should_panic(expected = "HasCubic")]
@@ -478,6 +1194,43 @@ mod tests {
         assert_eq!(orig_bytes.as_ref(), bytes);
     }
 
+    #[test]
+    fn round_trip_to_owned() {
+        let font = FontRef::new(test_data::test_fonts::VAZIRMATN_VAR).unwrap();
+        let loca = font.loca(None).unwrap();
+        let glyf = font.glyf().unwrap();
+        for gid in (0..font.maxp().unwrap().num_glyphs()).map(GlyphId::new) {
+            let Some(orig) = loca.get_glyf(gid, &glyf).unwrap() else {
+                continue;
+            };
+            let ours: Glyph = orig.to_owned_table();
+            let bytes = crate::dump_table(&ours).unwrap();
+            let read = read_glyf::Glyph::read(FontData::new(&bytes)).unwrap();
+            match (&orig, &read) {
+                (read_glyf::Glyph::Simple(orig), read_glyf::Glyph::Simple(read)) => {
+                    assert_eq!(
+                        orig.points().collect::<Vec<_>>(),
+                        read.points().collect::<Vec<_>>()
+                    );
+                    assert_eq!(orig.end_pts_of_contours(), read.end_pts_of_contours());
+                    assert_eq!(orig.instructions(), read.instructions());
+                }
+                (read_glyf::Glyph::Composite(orig), read_glyf::Glyph::Composite(read)) => {
+                    let components = |glyph: &read_glyf::CompositeGlyph| {
+                        glyph
+                            .components()
+                            .map(|c| (c.glyph, format!("{:?}", c.anchor)))
+                            .collect::<Vec<_>>()
+                    };
+                    assert_eq!(components(orig), components(read));
+                }
+                _ => panic!("glyph type changed for {gid}"),
+            }
+            assert_eq!(ours.bbox().unwrap().x_min, orig.x_min());
+            assert_eq!(ours.bbox().unwrap().y_max, orig.y_max());
+        }
+    }
+
     #[test]
     fn very_simple_glyph() {
         let mut path = BezPath::new();
@@ -501,6 +1254,96 @@ mod tests {
         assert!(points[4].on_curve);
     }
 
+    /// A counter-clockwise "O", with cubic curves.
+    fn cubic_o(width: f64, height: f64) -> BezPath {
+        let (w, h) = (width, height);
+        let mut path = BezPath::new();
+        path.move_to((w / 2., 0.));
+        path.curve_to((w * 0.78, 0.), (w, h * 0.22), (w, h / 2.));
+        path.curve_to((w, h * 0.78), (w * 0.78, h), (w / 2., h));
+        path.curve_to((w * 0.22, h), (0., h * 0.78), (0., h / 2.));
+        path.curve_to((0., h * 0.22), (w * 0.22, 0.), (w / 2., 0.));
+        path.close_path();
+        path
+    }
+
+    #[test]
+    fn cubic_glyph() {
+        let path = cubic_o(600., 700.);
+        assert!(matches!(
+            SimpleGlyph::from_kurbo(&path),
+            Err(BadKurbo::HasCubic)
+        ));
+        let glyph = SimpleGlyph::from_kurbo_cubic(&path, CubicConversion::new(1.0)).unwrap();
+        let bytes = crate::dump_table(&glyph).unwrap();
+        let read = read_glyf::SimpleGlyph::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read.number_of_contours(), 1);
+        let points = read.points().collect::<Vec<_>>();
+        // the closing point is dropped
+        assert_eq!((points[0].x, points[0].y), (300, 0));
+        assert!(points[0].on_curve);
+        assert_ne!(points.last(), points.first());
+        // consecutive off-curve points have implied on-curve points between
+        // them, so there are more off-curve points than quarters of the "O"
+        let off_curve = points.iter().filter(|pt| !pt.on_curve).count();
+        assert!(off_curve > 4);
+        assert_eq!(points.iter().filter(|pt| pt.on_curve).count(), 4);
+        assert_eq!(glyph.bbox().x_max, 600);
+        assert_eq!(glyph.bbox().y_max, 700);
+
+        let reversed =
+            SimpleGlyph::from_kurbo_cubic(&path, CubicConversion::cu2qu_compatible(1000)).unwrap();
+        let bytes = crate::dump_table(&reversed).unwrap();
+        let read = read_glyf::SimpleGlyph::read(FontData::new(&bytes)).unwrap();
+        let reversed = read.points().collect::<Vec<_>>();
+        assert_eq!(reversed.len(), points.len());
+        assert_eq!(reversed[0], points[0]);
+        assert_eq!(reversed[1], points[points.len() - 1]);
+    }
+
+    #[test]
+    fn interpolatable_cubic_glyphs() {
+        // the bold master is more distorted, and needs more segments alone
+        let masters = [cubic_o(100., 100.), cubic_o(1000., 400.)];
+        let conversion = CubicConversion::new(1.0);
+        let alone = masters
+            .iter()
+            .map(|path| {
+                SimpleGlyph::from_kurbo_cubic(path, conversion)
+                    .unwrap()
+                    .contours[0]
+                    .len()
+            })
+            .collect::<Vec<_>>();
+        assert_ne!(alone[0], alone[1]);
+        let glyphs = SimpleGlyph::interpolatable_from_kurbo(&masters, conversion).unwrap();
+        let lens = glyphs
+            .iter()
+            .map(|glyph| glyph.contours[0].len())
+            .collect::<Vec<_>>();
+        assert_eq!(lens[0], lens[1]);
+        assert_eq!(lens[0], alone[0].max(alone[1]));
+        let on_curve = |glyph: &SimpleGlyph| {
+            glyph.contours[0]
+                .0
+                .iter()
+                .map(|pt| pt.on_curve)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(on_curve(&glyphs[0]), on_curve(&glyphs[1]));
+
+        let mut other = BezPath::new();
+        other.move_to((0., 0.));
+        other.line_to((10., 0.));
+        other.line_to((10., 10.));
+        other.line_to((0., 10.));
+        other.close_path();
+        assert!(matches!(
+            SimpleGlyph::interpolatable_from_kurbo(&[masters[0].clone(), other], conversion),
+            Err(BadKurbo::Incompatible)
+        ));
+    }
+
     #[test]
     fn compile_repeatable_flags() {
         let mut path = BezPath::new();
@@ -533,6 +1376,180 @@ mod tests {
         assert_eq!(points[3].y, -20);
     }
 
+    fn simple_square(size: i16) -> SimpleGlyph {
+        let contour = Contour::from(vec![
+            CurvePoint::on_curve(0, 0),
+            CurvePoint::on_curve(0, size),
+            CurvePoint::on_curve(size, size),
+            CurvePoint::on_curve(size, 0),
+        ]);
+        SimpleGlyph::new(vec![contour])
+    }
+
+    #[test]
+    fn simple_glyph_bbox() {
+        let contour = Contour::from(vec![
+            CurvePoint::on_curve(-10, 5),
+            CurvePoint::off_curve(40, 300),
+            CurvePoint::on_curve(20, -7),
+        ]);
+        let glyph = SimpleGlyph::new(vec![contour]);
+        assert_eq!(
+            glyph.bbox(),
+            Bbox {
+                x_min: -10,
+                y_min: -7,
+                x_max: 40,
+                y_max: 300
+            }
+        );
+        let bytes = crate::dump_table(&glyph).unwrap();
+        let read = read_glyf::SimpleGlyph::read(FontData::new(&bytes)).unwrap();
+        assert_eq!((read.x_min(), read.y_max()), (-10, 300));
+        assert_eq!(read.num_points(), 3);
+    }
+
+    #[test]
+    fn composite_round_trip() {
+        let bbox = simple_square(100).bbox();
+        let mut glyph = CompositeGlyph::new(
+            Component::new(
+                GlyphId::new(1),
+                Anchor::Offset { x: 10, y: -20 },
+                Transform::default(),
+                ComponentFlags {
+                    use_my_metrics: true,
+                    ..Default::default()
+                },
+            ),
+            bbox,
+        );
+        glyph.add_component(
+            Component::new(
+                GlyphId::new(2),
+                Anchor::Offset { x: 500, y: 0 },
+                Transform {
+                    xx: F2Dot14::from_f32(0.5),
+                    yy: F2Dot14::from_f32(0.5),
+                    ..Default::default()
+                },
+                ComponentFlags::default(),
+            ),
+            Bbox {
+                x_min: 500,
+                y_min: 0,
+                x_max: 550,
+                y_max: 50,
+            },
+        );
+        glyph.add_component(
+            Component::new(
+                GlyphId::new(300),
+                Anchor::Point {
+                    base: 3,
+                    component: 400,
+                },
+                Transform {
+                    yx: F2Dot14::from_f32(0.25),
+                    ..Default::default()
+                },
+                ComponentFlags::default(),
+            ),
+            bbox,
+        );
+        assert_eq!(glyph.bbox().x_max, 550);
+
+        let bytes = crate::dump_table(&glyph).unwrap();
+        let read = read_glyf::CompositeGlyph::read(FontData::new(&bytes)).unwrap();
+        assert_eq!(read.number_of_contours(), -1);
+        assert_eq!((read.x_min(), read.x_max()), (0, 550));
+        let components = read.components().collect::<Vec<_>>();
+        assert_eq!(components.len(), 3);
+
+        assert_eq!(components[0].glyph, GlyphId::new(1));
+        assert!(matches!(
+            components[0].anchor,
+            Anchor::Offset { x: 10, y: -20 }
+        ));
+        assert!(components[0]
+            .flags
+            .contains(CompositeGlyphFlags::USE_MY_METRICS));
+        assert!(!components[0]
+            .flags
+            .contains(CompositeGlyphFlags::ARG_1_AND_2_ARE_WORDS));
+        assert!(components[0].transform.is_identity());
+
+        assert!(matches!(
+            components[1].anchor,
+            Anchor::Offset { x: 500, y: 0 }
+        ));
+        assert!(components[1]
+            .flags
+            .contains(CompositeGlyphFlags::WE_HAVE_A_SCALE));
+        assert_eq!(components[1].transform.yy, F2Dot14::from_f32(0.5));
+
+        assert_eq!(components[2].glyph, GlyphId::new(300));
+        assert!(matches!(
+            components[2].anchor,
+            Anchor::Point {
+                base: 3,
+                component: 400
+            }
+        ));
+        assert!(components[2]
+            .flags
+            .contains(CompositeGlyphFlags::WE_HAVE_A_TWO_BY_TWO));
+        assert_eq!(components[2].transform.yx, F2Dot14::from_f32(0.25));
+        assert!(!components[2]
+            .flags
+            .contains(CompositeGlyphFlags::MORE_COMPONENTS));
+    }
+
+    #[test]
+    fn build_glyf_and_loca() {
+        let square = simple_square(100);
+        let composite = CompositeGlyph::new(
+            Component::new(
+                GlyphId::new(1),
+                Anchor::Offset { x: 0, y: 0 },
+                Transform::default(),
+                ComponentFlags::default(),
+            ),
+            square.bbox(),
+        );
+        let mut builder = GlyfLocaBuilder::new();
+        builder
+            .add_glyph(&Glyph::Empty)
+            .add_glyph(&square.into())
+            .add_glyph(&composite.into());
+        let (glyf, loca, format) = builder.build();
+        assert_eq!(format, LocaFormat::Short);
+
+        let mut head = crate::tables::head::Head::default();
+        head.set_loca_format(format);
+        let font_data = crate::FontBuilder::default()
+            .add_table(Tag::new(b"head"), crate::dump_table(&head).unwrap())
+            .add_table(Glyf::TAG, crate::dump_table(&glyf).unwrap())
+            .add_table(Loca::TAG, crate::dump_table(&loca).unwrap())
+            .build();
+
+        let font = FontRef::new(&font_data).unwrap();
+        let glyf = font.glyf().unwrap();
+        let loca = font.loca(None).unwrap();
+        assert_eq!(loca.len(), 3);
+        assert!(loca.get_glyf(GlyphId::new(0), &glyf).unwrap().is_none());
+        assert!(matches!(
+            loca.get_glyf(GlyphId::new(1), &glyf).unwrap(),
+            Some(read_glyf::Glyph::Simple(_))
+        ));
+        assert!(matches!(
+            loca.get_glyf(GlyphId::new(2), &glyf).unwrap(),
+            Some(read_glyf::Glyph::Composite(_))
+        ));
+        // every glyph is padded to four bytes
+        assert!((0..4).all(|i| loca.get_raw(i).unwrap() % 4 == 0));
+    }
+
     #[test]
     fn repeatable_flags_basic() {
         let flags = [
@@ -4,12 +4,12 @@
 
 include!("../../generated/generated_gpos.rs");
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 //use super::layout::value_record::ValueRecord;
 use super::layout::{
-    ChainedSequenceContext, ClassDef, CoverageTable, Device, FeatureList, FeatureVariations,
-    Lookup, LookupList, LookupType, ScriptList, SequenceContext,
+    ChainedSequenceContext, ClassDef, ClassDefBuilder, CoverageTable, CoverageTableBuilder, Device,
+    FeatureList, FeatureVariations, Lookup, LookupList, LookupType, ScriptList, SequenceContext,
 };
 
 #[cfg(test)]
@@ -20,6 +20,10 @@ mod spec_tests;
 mod value_record;
 pub use value_record::ValueRecord;
 
+#[path = "./gpos_builders.rs"]
+mod builders;
+pub use builders::{GposBuilder, MarkBaseBuilder, MarkMarkBuilder, SinglePosBuilder};
+
 /// A GPOS lookup list table.
 type PositionLookupList = LookupList<PositionLookup>;
 
@@ -163,6 +167,405 @@ impl MarkArray {
     }
 }
 
+/// The maximum size of a class-based pair subtable.
+///
+/// This ensures that the offsets from the subtable to its coverage and class
+/// definition tables fit in 16 bits. (Large format 1 subtables are split when
+/// the font is compiled, but format 2 subtables are not.)
+const MAX_PAIR_POS_FORMAT2_LEN: usize = u16::MAX as usize;
+
+/// The value formats that use device tables.
+const DEVICE_FORMATS: ValueFormat = ValueFormat::from_bits_truncate(
+    ValueFormat::X_PLACEMENT_DEVICE.bits()
+        | ValueFormat::Y_PLACEMENT_DEVICE.bits()
+        | ValueFormat::X_ADVANCE_DEVICE.bits()
+        | ValueFormat::Y_ADVANCE_DEVICE.bits(),
+);
+
+/// The adjustments for a first glyph, keyed by second glyph.
+type PairRow = BTreeMap<GlyphId, (ValueRecord, ValueRecord)>;
+
+/// A builder for [PairPos] subtables.
+///
+/// Pairs are added individually. When building, first glyphs that are
+/// adjusted identically are grouped into classes, as are second glyphs, and
+/// the pairs are written as class-based (format 2) subtables, except where
+/// listing the pairs individually (format 1) would be smaller.
+#[derive(Clone, Debug, Default)]
+pub struct PairPosBuilder {
+    pairs: BTreeMap<GlyphId, PairRow>,
+}
+
+impl PairPosBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the adjustments for a pair of glyphs.
+    ///
+    /// If the pair has already been added, its adjustments are replaced.
+    pub fn insert(
+        &mut self,
+        first: GlyphId,
+        second: GlyphId,
+        value1: ValueRecord,
+        value2: ValueRecord,
+    ) {
+        self.pairs
+            .entry(first)
+            .or_default()
+            .insert(second, (value1, value2));
+    }
+
+    /// Add a kerning pair, which adjusts the advance of the first glyph.
+    pub fn insert_kern(&mut self, first: GlyphId, second: GlyphId, x_advance: i16) {
+        let value = ValueRecord {
+            x_advance: Some(x_advance),
+            ..Default::default()
+        };
+        self.insert(first, second, value, ValueRecord::default())
+    }
+
+    /// The number of pairs in the builder.
+    pub fn len(&self) -> usize {
+        self.pairs.values().map(BTreeMap::len).sum()
+    }
+
+    /// Returns `true` if no pairs have been added.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Build the subtables for these pairs.
+    ///
+    /// Each first glyph appears in at most one of the returned subtables,
+    /// except for pairs that use device tables; these are written to format 1
+    /// subtables that come first in the list. A format 1 subtable only matches
+    /// the pairs that it lists, so the remaining pairs of those first glyphs
+    /// are still found in the later subtables.
+    pub fn build(self) -> Vec<PairPos> {
+        let mut device_rows = BTreeMap::<_, BTreeMap<_, PairRow>>::new();
+        let mut rows = BTreeMap::<GlyphId, PairRow>::new();
+        for (first, row) in self.pairs {
+            for (second, values) in row {
+                let device_formats = (
+                    (values.0.format() & DEVICE_FORMATS).bits(),
+                    (values.1.format() & DEVICE_FORMATS).bits(),
+                );
+                let rows = match device_formats {
+                    (0, 0) => &mut rows,
+                    _ => device_rows.entry(device_formats).or_default(),
+                };
+                rows.entry(first).or_default().insert(second, values);
+            }
+        }
+        let mut subtables = device_rows
+            .values()
+            .map(|rows| pair_pos_format1(rows.iter()))
+            .collect::<Vec<_>>();
+
+        // group the first glyphs that have identical rows
+        let mut classes: Vec<(Vec<GlyphId>, &PairRow)> = Vec::new();
+        let mut class_ids = HashMap::new();
+        for (first, row) in &rows {
+            let id = *class_ids.entry(row).or_insert_with(|| {
+                classes.push((Vec::new(), row));
+                classes.len() - 1
+            });
+            classes[id].0.push(*first);
+        }
+
+        let record_len = value_formats(rows.values().flat_map(|row| row.values())).record_len();
+        let mut current = PairClasses::default();
+        for class in &classes {
+            let (mut second, mut n_second_classes) = current.second_classes_with(class.1);
+            let len = estimate_pair_pos_format2_len(
+                current.n_first_glyphs() + class.0.len(),
+                current.first.len() + 1,
+                second.len(),
+                n_second_classes,
+                record_len,
+            );
+            // if this class doesn't fit, start a new subtable
+            if len > MAX_PAIR_POS_FORMAT2_LEN && !current.first.is_empty() {
+                subtables.push(std::mem::take(&mut current).build());
+                (second, n_second_classes) = current.second_classes_with(class.1);
+            }
+            current.second = second;
+            current.n_second_classes = n_second_classes;
+            current.first.push(class);
+        }
+        if !current.first.is_empty() {
+            subtables.push(current.build());
+        }
+        subtables
+    }
+}
+
+/// A set of classes of first glyphs that are written to the same subtable,
+/// along with the classes of the second glyphs that they are paired with.
+#[derive(Default)]
+struct PairClasses<'a> {
+    first: Vec<&'a (Vec<GlyphId>, &'a PairRow)>,
+    second: BTreeMap<GlyphId, u16>,
+    // including class 0, for second glyphs that are not paired with any
+    // of the first glyphs.
+    n_second_classes: u16,
+}
+
+impl<'a> PairClasses<'a> {
+    fn n_first_glyphs(&self) -> usize {
+        self.first.iter().map(|(glyphs, _)| glyphs.len()).sum()
+    }
+
+    /// Compute the classes of the second glyphs, if a class of first glyphs
+    /// with this row were added.
+    ///
+    /// Second glyphs are in the same class if they have the same adjustments
+    /// for every class of first glyphs, so the new classes are found by
+    /// splitting the existing classes according to the new row.
+    fn second_classes_with(&self, row: &PairRow) -> (BTreeMap<GlyphId, u16>, u16) {
+        let mut new_ids = HashMap::new();
+        new_ids.insert((0, None), 0u16);
+        let mut classes = BTreeMap::new();
+        // visit the glyphs in order, so that classes are numbered consistently
+        let seconds = self
+            .second
+            .keys()
+            .chain(row.keys())
+            .collect::<BTreeSet<_>>();
+        for second in seconds {
+            let key = (
+                self.second.get(second).copied().unwrap_or(0),
+                row.get(second),
+            );
+            let next_id = new_ids.len() as u16;
+            let id = *new_ids.entry(key).or_insert(next_id);
+            classes.insert(*second, id);
+        }
+        let n_classes = new_ids.len() as u16;
+        (classes, n_classes)
+    }
+
+    /// Build the smaller of a format 1 or format 2 subtable for these pairs.
+    fn build(self) -> PairPos {
+        let format1 = pair_pos_format1(
+            self.first
+                .iter()
+                .flat_map(|(glyphs, row)| glyphs.iter().map(move |glyph| (glyph, *row))),
+        );
+        let format2 = self.build_format2();
+        if pair_pos_len(&format1) <= pair_pos_len(&format2) {
+            format1
+        } else {
+            format2
+        }
+    }
+
+    fn build_format2(&self) -> PairPos {
+        // the largest class of first glyphs is class 0, so that it does not
+        // need any entries in the class definition table.
+        let zero = self
+            .first
+            .iter()
+            .enumerate()
+            .max_by_key(|(i, (glyphs, _))| (glyphs.len(), std::cmp::Reverse(*i)))
+            .map(|(i, _)| i)
+            .unwrap_or_default();
+        let mut first_classes = Vec::with_capacity(self.first.len());
+        first_classes.push(self.first[zero]);
+        first_classes.extend(
+            self.first
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != zero)
+                .map(|(_, class)| *class),
+        );
+
+        let coverage = first_classes
+            .iter()
+            .flat_map(|(glyphs, _)| glyphs.iter().copied())
+            .collect::<CoverageTableBuilder>()
+            .build();
+        let class_def1 = first_classes
+            .iter()
+            .enumerate()
+            .flat_map(|(class, (glyphs, _))| glyphs.iter().map(move |glyph| (*glyph, class as u16)))
+            .collect::<ClassDefBuilder>()
+            .build();
+        let class_def2 = self
+            .second
+            .iter()
+            .map(|(glyph, class)| (*glyph, *class))
+            .collect::<ClassDefBuilder>()
+            .build();
+
+        // every glyph in a second class has the same adjustments, so we can
+        // look up the values of any one of them.
+        let mut second_glyphs = vec![None; self.n_second_classes as usize];
+        for (glyph, class) in &self.second {
+            let slot = &mut second_glyphs[*class as usize];
+            if slot.is_none_or(|prev| prev > *glyph) {
+                *slot = Some(*glyph);
+            }
+        }
+        let formats = value_formats(first_classes.iter().flat_map(|(_, row)| row.values()));
+        let class1_records = first_classes
+            .iter()
+            .map(|(_, row)| {
+                Class1Record::new(
+                    second_glyphs
+                        .iter()
+                        .map(|second| {
+                            let (value1, value2) = second
+                                .and_then(|glyph| row.get(&glyph))
+                                .map(|(value1, value2)| (value1.clone(), value2.clone()))
+                                .unwrap_or_default();
+                            Class2Record::new(formats.pad(value1, 0), formats.pad(value2, 1))
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+        PairPos::format_2(coverage, class_def1, class_def2, class1_records)
+    }
+}
+
+/// Build a format 1 subtable for the rows of these first glyphs.
+fn pair_pos_format1<'a>(rows: impl Iterator<Item = (&'a GlyphId, &'a PairRow)>) -> PairPos {
+    let mut rows = rows.collect::<Vec<_>>();
+    rows.sort_unstable_by_key(|(first, _)| **first);
+    let formats = value_formats(rows.iter().flat_map(|(_, row)| row.values()));
+    let coverage =
+        CoverageTableBuilder::from_glyphs(rows.iter().map(|(first, _)| **first).collect()).build();
+    let pair_sets = rows
+        .iter()
+        .map(|(_, row)| {
+            PairSet::new(
+                row.iter()
+                    .map(|(second, (value1, value2))| {
+                        PairValueRecord::new(
+                            *second,
+                            formats.pad(value1.clone(), 0),
+                            formats.pad(value2.clone(), 1),
+                        )
+                    })
+                    .collect(),
+            )
+        })
+        .collect();
+    PairPos::format_1(coverage, pair_sets)
+}
+
+/// The value formats of the first and second value records of a subtable.
+#[derive(Clone, Copy)]
+struct PairValueFormats([ValueFormat; 2]);
+
+/// The union of the formats of these value records.
+fn value_formats<'a>(
+    values: impl Iterator<Item = &'a (ValueRecord, ValueRecord)>,
+) -> PairValueFormats {
+    let mut formats = [ValueFormat::empty(); 2];
+    for (value1, value2) in values {
+        formats[0] |= value1.format();
+        formats[1] |= value2.format();
+    }
+    PairValueFormats(formats)
+}
+
+impl PairValueFormats {
+    /// The length of the two value records.
+    fn record_len(&self) -> usize {
+        self.0
+            .iter()
+            .map(|format| format.bits().count_ones() as usize * 2)
+            .sum()
+    }
+
+    /// Set any missing (non-device) fields in this value record to zero, so
+    /// that it has the format of the subtable.
+    fn pad(&self, value: ValueRecord, index: usize) -> ValueRecord {
+        pad_value_record(value, self.0[index])
+    }
+}
+
+/// Set any missing (non-device) fields in this value record to zero, so that
+/// it has the given format.
+fn pad_value_record(mut value: ValueRecord, format: ValueFormat) -> ValueRecord {
+    for (flag, field) in [
+        (ValueFormat::X_PLACEMENT, &mut value.x_placement),
+        (ValueFormat::Y_PLACEMENT, &mut value.y_placement),
+        (ValueFormat::X_ADVANCE, &mut value.x_advance),
+        (ValueFormat::Y_ADVANCE, &mut value.y_advance),
+    ] {
+        if format.contains(flag) && field.is_none() {
+            *field = Some(0);
+        }
+    }
+    value
+}
+
+/// An upper bound on the length of a format 2 subtable, including its
+/// coverage and class definition tables.
+fn estimate_pair_pos_format2_len(
+    n_first_glyphs: usize,
+    n_first_classes: usize,
+    n_second_glyphs: usize,
+    n_second_classes: u16,
+    record_len: usize,
+) -> usize {
+    16 + n_first_classes * n_second_classes as usize * record_len
+        + (4 + 2 * n_first_glyphs)
+        + (4 + 6 * n_first_glyphs)
+        + (4 + 6 * n_second_glyphs)
+}
+
+/// The length of a subtable without device tables, including its coverage
+/// and class definition tables.
+fn pair_pos_len(subtable: &PairPos) -> usize {
+    fn coverage_len(coverage: &CoverageTable) -> usize {
+        match coverage {
+            CoverageTable::Format1(table) => 4 + 2 * table.glyph_array.len(),
+            CoverageTable::Format2(table) => 4 + 6 * table.range_records.len(),
+        }
+    }
+    fn class_def_len(class_def: &ClassDef) -> usize {
+        match class_def {
+            ClassDef::Format1(table) => 6 + 2 * table.class_value_array.len(),
+            ClassDef::Format2(table) => 4 + 6 * table.class_range_records.len(),
+        }
+    }
+    match subtable {
+        PairPos::Format1(table) => {
+            let record_len = 2
+                + table.compute_value_format1().bits().count_ones() as usize * 2
+                + table.compute_value_format2().bits().count_ones() as usize * 2;
+            10 + 2 * table.pair_sets.len()
+                + coverage_len(&table.coverage)
+                + table
+                    .pair_sets
+                    .iter()
+                    .map(|set| 2 + set.pair_value_records.len() * record_len)
+                    .sum::<usize>()
+        }
+        PairPos::Format2(table) => {
+            let record_len = table.compute_value_format1().bits().count_ones() as usize * 2
+                + table.compute_value_format2().bits().count_ones() as usize * 2;
+            16 + table.class1_records.len()
+                * table
+                    .class1_records
+                    .first()
+                    .map(|rec| rec.class2_records.len())
+                    .unwrap_or_default()
+                * record_len
+                + coverage_len(&table.coverage)
+                + class_def_len(&table.class_def1)
+                + class_def_len(&table.class_def2)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -212,4 +615,376 @@ mod tests {
             }
         );
     }
+
+    /// Find the adjustment of the first glyph's advance for this pair, as a
+    /// shaper would.
+    fn kern_value(subtables: &[PairPos], first: u16, second: u16) -> Option<i16> {
+        let (first, second) = (GlyphId::new(first), GlyphId::new(second));
+        for subtable in subtables {
+            match subtable {
+                PairPos::Format1(table) => {
+                    let Some(idx) = table.coverage.iter().position(|gid| gid == first) else {
+                        continue;
+                    };
+                    if let Some(record) = table.pair_sets[idx]
+                        .pair_value_records
+                        .iter()
+                        .find(|rec| rec.second_glyph == second)
+                    {
+                        return record.value_record1.x_advance;
+                    }
+                }
+                PairPos::Format2(table) => {
+                    if table.coverage.iter().any(|gid| gid == first) {
+                        let class1 = table.class_def1.get(first) as usize;
+                        let class2 = table.class_def2.get(second) as usize;
+                        return table.class1_records[class1].class2_records[class2]
+                            .value_record1
+                            .x_advance;
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn round_trip(subtables: Vec<PairPos>) -> Vec<PairPos> {
+        subtables
+            .iter()
+            .map(|subtable| {
+                let bytes = crate::dump_table(subtable).unwrap();
+                PairPos::read(FontData::new(&bytes)).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pair_pos_builder_classes() {
+        let mut builder = PairPosBuilder::new();
+        // accented forms of 'A' kern identically against forms of 'V'
+        for first in 1..=8 {
+            for second in 20..=27 {
+                builder.insert_kern(GlyphId::new(first), GlyphId::new(second), -50);
+            }
+            builder.insert_kern(GlyphId::new(first), GlyphId::new(40), -20);
+        }
+        // 'T' kerns with 'o' and 'e'
+        for second in [30, 31] {
+            builder.insert_kern(GlyphId::new(10), GlyphId::new(second), -80);
+        }
+        builder.insert_kern(GlyphId::new(11), GlyphId::new(30), -80);
+        builder.insert_kern(GlyphId::new(11), GlyphId::new(31), -80);
+        builder.insert_kern(GlyphId::new(11), GlyphId::new(20), 10);
+        assert_eq!(builder.len(), 77);
+
+        let subtables = round_trip(builder.build());
+        assert_eq!(subtables.len(), 1);
+        let PairPos::Format2(table) = &subtables[0] else {
+            panic!("expected format 2");
+        };
+        assert_eq!(table.compute_class1_count(), 3);
+        // class 0, {20}, {21..=27}, {30, 31}, {40}
+        assert_eq!(table.compute_class2_count(), 5);
+        assert_eq!(table.compute_value_format1(), ValueFormat::X_ADVANCE);
+        assert_eq!(table.compute_value_format2(), ValueFormat::empty());
+
+        assert_eq!(kern_value(&subtables, 2, 21), Some(-50));
+        assert_eq!(kern_value(&subtables, 3, 40), Some(-20));
+        assert_eq!(kern_value(&subtables, 10, 31), Some(-80));
+        assert_eq!(kern_value(&subtables, 11, 20), Some(10));
+        assert_eq!(kern_value(&subtables, 10, 20), Some(0));
+        assert_eq!(kern_value(&subtables, 1, 99), Some(0));
+        assert_eq!(kern_value(&subtables, 99, 20), None);
+    }
+
+    #[test]
+    fn pair_pos_builder_sparse_pairs() {
+        // unrelated pairs are smaller as format 1
+        let mut builder = PairPosBuilder::new();
+        builder.insert_kern(GlyphId::new(1), GlyphId::new(100), -10);
+        builder.insert_kern(GlyphId::new(2), GlyphId::new(200), -20);
+        builder.insert_kern(GlyphId::new(3), GlyphId::new(300), -30);
+        let subtables = round_trip(builder.build());
+        assert_eq!(subtables.len(), 1);
+        assert!(matches!(subtables[0], PairPos::Format1(_)));
+        assert_eq!(kern_value(&subtables, 2, 200), Some(-20));
+        assert_eq!(kern_value(&subtables, 2, 100), None);
+    }
+
+    #[test]
+    fn pair_pos_builder_mixed_formats() {
+        let mut builder = PairPosBuilder::new();
+        builder.insert_kern(GlyphId::new(1), GlyphId::new(2), -10);
+        builder.insert(
+            GlyphId::new(1),
+            GlyphId::new(3),
+            ValueRecord {
+                y_placement: Some(5),
+                ..Default::default()
+            },
+            ValueRecord {
+                x_placement: Some(7),
+                ..Default::default()
+            },
+        );
+        builder.insert(
+            GlyphId::new(1),
+            GlyphId::new(4),
+            ValueRecord {
+                x_advance: Some(-40),
+                x_advance_device: Device::new(10, 11, &[1, -1]).into(),
+                ..Default::default()
+            },
+            ValueRecord::default(),
+        );
+        let subtables = round_trip(builder.build());
+        assert_eq!(subtables.len(), 2);
+
+        // the pair with a device table comes first, in its own subtable
+        let PairPos::Format1(table) = &subtables[0] else {
+            panic!("expected format 1");
+        };
+        assert_eq!(table.pair_sets[0].pair_value_records.len(), 1);
+        assert_eq!(kern_value(&subtables, 1, 4), Some(-40));
+
+        // the other records are padded to a common format
+        let PairPos::Format1(table) = &subtables[1] else {
+            panic!("expected format 1");
+        };
+        let records = &table.pair_sets[0].pair_value_records;
+        assert_eq!(
+            records[0].value_record1.format(),
+            ValueFormat::Y_PLACEMENT | ValueFormat::X_ADVANCE
+        );
+        assert_eq!(records[0].value_record1.y_placement, Some(0));
+        assert_eq!(records[0].value_record2.x_placement, Some(0));
+        assert_eq!(records[1].value_record1.x_advance, Some(0));
+        assert_eq!(kern_value(&subtables, 1, 2), Some(-10));
+    }
+
+    #[test]
+    fn pair_pos_builder_splits_large_class_tables() {
+        // 400 first classes * 100 second classes * 2 bytes is too large for
+        // a single subtable
+        let mut builder = PairPosBuilder::new();
+        for first in 0..400u16 {
+            for second in 0..100u16 {
+                let value = (first as i16 % 7 + 1) * (second as i16 + 1) + first as i16;
+                builder.insert_kern(GlyphId::new(first), GlyphId::new(second + 1000), value);
+            }
+        }
+        let subtables = round_trip(builder.build());
+        assert!(subtables.len() > 1);
+        for subtable in &subtables {
+            assert!(matches!(subtable, PairPos::Format2(_)));
+            assert!(pair_pos_len(subtable) <= MAX_PAIR_POS_FORMAT2_LEN);
+        }
+        for first in (0..400u16).step_by(13) {
+            for second in (0..100u16).step_by(7) {
+                let value = (first as i16 % 7 + 1) * (second as i16 + 1) + first as i16;
+                assert_eq!(
+                    kern_value(&subtables, first, second + 1000),
+                    Some(value),
+                    "{first} {second}"
+                );
+            }
+        }
+
+        let lookup = Lookup::new(LookupFlag::default(), subtables, 0);
+        let gpos = Gpos::new(
+            Default::default(),
+            Default::default(),
+            LookupList::new(vec![PositionLookup::Pair(lookup)]),
+        );
+        assert!(crate::dump_table(&gpos).is_ok());
+    }
+
+    #[test]
+    fn validation_error_path() {
+        let good = SinglePos::format_1(
+            CoverageTable::format_1(vec![GlyphId::new(2)]),
+            ValueRecord::default(),
+        );
+        let unsorted = SinglePos::format_1(
+            CoverageTable::format_1(vec![GlyphId::new(5), GlyphId::new(3)]),
+            ValueRecord::default(),
+        );
+        let lookups = vec![
+            PositionLookup::Single(Lookup::new(LookupFlag::default(), vec![good.clone()], 0)),
+            PositionLookup::Single(Lookup::new(LookupFlag::default(), vec![good, unsorted], 0)),
+        ];
+        let gpos = Gpos::new(
+            Default::default(),
+            Default::default(),
+            LookupList::new(lookups),
+        );
+        let report = gpos.validate().unwrap_err();
+        let errors = report.errors().collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "Gpos.lookup_list.lookups[1].subtables[1].coverage.glyph_array: \
+             coverage glyphs unsorted or duplicated at index 1"
+        );
+    }
+
+    #[test]
+    fn split_large_pair_pos() {
+        // a thousand pair sets of 162 bytes each won't fit in one subtable
+        let first_glyphs = (0..1000u16).map(GlyphId::new).collect::<Vec<_>>();
+        let pair_sets = first_glyphs
+            .iter()
+            .map(|first| {
+                let records = (0..40u16)
+                    .map(|second| {
+                        let value = ValueRecord {
+                            x_advance: Some(first.to_u16() as i16 - second as i16),
+                            ..Default::default()
+                        };
+                        PairValueRecord::new(
+                            GlyphId::new(second + 1000),
+                            value,
+                            ValueRecord::default(),
+                        )
+                    })
+                    .collect();
+                PairSet::new(records)
+            })
+            .collect();
+        let coverage = CoverageTable::format_1(first_glyphs);
+        let subtable = PairPos::format_1(coverage, pair_sets);
+        let lookup = Lookup::new(LookupFlag::default(), vec![subtable], 0);
+        let gpos = Gpos::new(
+            Default::default(),
+            Default::default(),
+            LookupList::new(vec![PositionLookup::Pair(lookup)]),
+        );
+        let bytes = crate::dump_table(&gpos).unwrap();
+
+        let gpos = read_gpos::Gpos::read(FontData::new(&bytes)).unwrap();
+        let lookup_list = gpos.lookup_list().unwrap();
+        assert_eq!(lookup_list.lookup_count(), 1);
+        let subtables = match lookup_list.lookups().next().unwrap().unwrap() {
+            read_gpos::PositionLookup::Pair(lookup) => {
+                lookup.subtables().collect::<Result<Vec<_>, _>>()
+            }
+            read_gpos::PositionLookup::Extension(lookup) => lookup
+                .subtables()
+                .map(|ext| match ext.unwrap() {
+                    read_gpos::ExtensionSubtable::Pair(ext) => ext.extension(),
+                    _ => panic!("wrong extension type"),
+                })
+                .collect(),
+            _ => panic!("wrong lookup type"),
+        }
+        .unwrap();
+        assert!(subtables.len() > 1);
+
+        let mut n_pair_sets = 0;
+        for subtable in subtables {
+            let read_gpos::PairPos::Format1(subtable) = subtable else {
+                panic!("wrong subtable format");
+            };
+            let coverage = subtable.coverage().unwrap();
+            assert_eq!(coverage.iter().count(), subtable.pair_set_count() as usize);
+            for (first, pair_set) in coverage.iter().zip(subtable.pair_sets()) {
+                let record = pair_set.unwrap().pair_value_records().get(3).unwrap();
+                assert_eq!(record.second_glyph().to_u16(), 1003);
+                assert_eq!(
+                    record.value_record1().x_advance.map(|x| x.get()),
+                    Some(first.to_u16() as i16 - 3)
+                );
+                n_pair_sets += 1;
+            }
+        }
+        assert_eq!(n_pair_sets, 1000);
+    }
+
+    #[test]
+    fn single_pos_builder() {
+        let advance = |x_advance| ValueRecord {
+            x_advance: Some(x_advance),
+            ..Default::default()
+        };
+        let mut builder = SinglePosBuilder::new();
+        builder.insert(GlyphId::new(3), advance(50));
+        builder.insert(GlyphId::new(7), advance(50));
+        let SinglePos::Format1(table) = builder.clone().build() else {
+            panic!("expected format 1");
+        };
+        assert_eq!(table.coverage.iter().count(), 2);
+
+        builder.insert(
+            GlyphId::new(5),
+            ValueRecord {
+                y_placement: Some(-20),
+                ..Default::default()
+            },
+        );
+        let SinglePos::Format2(table) = builder.build() else {
+            panic!("expected format 2");
+        };
+        // all records are padded to the same format
+        let format = ValueFormat::Y_PLACEMENT | ValueFormat::X_ADVANCE;
+        assert!(table
+            .value_records
+            .iter()
+            .all(|value| value.format() == format));
+        assert_eq!(table.value_records[1].y_placement, Some(-20));
+        assert_eq!(table.value_records[1].x_advance, Some(0));
+    }
+
+    #[test]
+    fn mark_base_builder() {
+        let mut builder = MarkBaseBuilder::new();
+        // classes need not be contiguous
+        builder.add_mark(GlyphId::new(10), 4, AnchorTable::format_1(100, 500));
+        builder.add_mark(GlyphId::new(11), 2, AnchorTable::format_1(80, -20));
+        builder.add_base(GlyphId::new(1), 4, AnchorTable::format_1(250, 600));
+        builder.add_base(GlyphId::new(2), 2, AnchorTable::format_1(300, 0));
+        builder.add_base(GlyphId::new(2), 4, AnchorTable::format_1(300, 700));
+        // no marks are in class 9
+        builder.add_base(GlyphId::new(2), 9, AnchorTable::format_1(0, 0));
+
+        let mut gpos = GposBuilder::new();
+        let lookup = gpos.add_mark_base(LookupFlag::empty(), builder);
+        gpos.add_feature(
+            Tag::new(b"latn"),
+            Tag::new(b"dflt"),
+            Tag::new(b"mark"),
+            &[lookup],
+        );
+        let bytes = crate::dump_table(&gpos.build()).unwrap();
+        let gpos = read_gpos::Gpos::read(FontData::new(&bytes)).unwrap();
+        let read_gpos::PositionLookup::MarkToBase(lookup) = gpos
+            .lookup_list()
+            .unwrap()
+            .lookups()
+            .next()
+            .unwrap()
+            .unwrap()
+        else {
+            panic!("wrong lookup type");
+        };
+        let subtable = lookup.subtables().next().unwrap().unwrap();
+        assert_eq!(subtable.mark_class_count(), 2);
+        let marks = subtable.mark_array().unwrap();
+        let classes = marks
+            .mark_records()
+            .iter()
+            .map(|rec| rec.mark_class())
+            .collect::<Vec<_>>();
+        assert_eq!(classes, [1, 0]);
+
+        let bases = subtable.base_array().unwrap();
+        let base_records = bases.base_records();
+        let first = base_records.get(0).unwrap();
+        let mut anchors = first.base_anchors(bases.offset_data());
+        assert!(anchors.next().unwrap().is_none());
+        let read_gpos::AnchorTable::Format1(anchor) = anchors.next().unwrap().unwrap().unwrap()
+        else {
+            panic!("wrong anchor format");
+        };
+        assert_eq!((anchor.x_coordinate(), anchor.y_coordinate()), (250, 600));
+    }
 }
@@ -0,0 +1,313 @@
+//! Builders for GPOS lookups.
+//!
+//! These complement the [PairPosBuilder](super::PairPosBuilder): each builder
+//! collects the rules of one kind of lookup, and the [GposBuilder] collects
+//! lookups and features into a complete table.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use read_fonts::tables::layout::LookupFlag;
+use types::{GlyphId, Tag};
+
+use super::{
+    pad_value_record, AnchorTable, BaseArray, BaseRecord, Gpos, Mark2Array, Mark2Record, MarkArray,
+    MarkBasePosFormat1, MarkMarkPosFormat1, MarkRecord, PairPosBuilder, PositionLookup, SinglePos,
+    ValueFormat, ValueRecord,
+};
+use crate::tables::layout::{
+    CoverageTable, CoverageTableBuilder, FeatureListBuilder, Lookup, LookupList,
+};
+
+/// A builder for a GPOS table.
+///
+/// ```
+/// # use write_fonts::tables::gpos::{GposBuilder, PairPosBuilder};
+/// # use write_fonts::tables::layout::LookupFlag;
+/// # use write_fonts::types::{GlyphId, Tag};
+/// let mut kerning = PairPosBuilder::new();
+/// kerning.insert_kern(GlyphId::new(5), GlyphId::new(8), -40);
+///
+/// let mut builder = GposBuilder::new();
+/// let lookup = builder.add_pair(LookupFlag::empty(), kerning);
+/// builder.add_feature(Tag::new(b"latn"), Tag::new(b"dflt"), Tag::new(b"kern"), &[lookup]);
+/// let gpos = builder.build();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GposBuilder {
+    lookups: Vec<PositionLookup>,
+    features: FeatureListBuilder,
+}
+
+/// A builder for single adjustment subtables.
+#[derive(Clone, Debug, Default)]
+pub struct SinglePosBuilder {
+    items: BTreeMap<GlyphId, ValueRecord>,
+}
+
+/// A builder for mark-to-base attachment subtables.
+///
+/// Marks are assigned to classes, and each base glyph has an anchor for some
+/// or all of the mark classes.
+#[derive(Clone, Debug, Default)]
+pub struct MarkBaseBuilder {
+    attachments: MarkAttachments,
+}
+
+/// A builder for mark-to-mark attachment subtables.
+///
+/// This is the same as a [MarkBaseBuilder], except that the glyphs that marks
+/// attach to are also marks.
+#[derive(Clone, Debug, Default)]
+pub struct MarkMarkBuilder {
+    attachments: MarkAttachments,
+}
+
+/// Marks, and the anchors of the glyphs that they attach to.
+#[derive(Clone, Debug, Default)]
+struct MarkAttachments {
+    marks: BTreeMap<GlyphId, (u16, AnchorTable)>,
+    bases: BTreeMap<GlyphId, BTreeMap<u16, AnchorTable>>,
+}
+
+impl GposBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a lookup, returning its index in the lookup list.
+    pub fn add_lookup(&mut self, lookup: PositionLookup) -> u16 {
+        self.lookups.push(lookup);
+        // if we're over u16::MAX lookups, crash
+        (self.lookups.len() - 1).try_into().unwrap()
+    }
+
+    /// Add a single adjustment lookup, returning its index.
+    pub fn add_single(&mut self, flags: LookupFlag, builder: SinglePosBuilder) -> u16 {
+        self.add_lookup(PositionLookup::Single(Lookup::new(
+            flags,
+            vec![builder.build()],
+            0,
+        )))
+    }
+
+    /// Add a pair adjustment lookup, returning its index.
+    pub fn add_pair(&mut self, flags: LookupFlag, builder: PairPosBuilder) -> u16 {
+        self.add_lookup(PositionLookup::Pair(Lookup::new(flags, builder.build(), 0)))
+    }
+
+    /// Add a mark-to-base attachment lookup, returning its index.
+    pub fn add_mark_base(&mut self, flags: LookupFlag, builder: MarkBaseBuilder) -> u16 {
+        self.add_lookup(PositionLookup::MarkToBase(Lookup::new(
+            flags,
+            vec![builder.build()],
+            0,
+        )))
+    }
+
+    /// Add a mark-to-mark attachment lookup, returning its index.
+    pub fn add_mark_mark(&mut self, flags: LookupFlag, builder: MarkMarkBuilder) -> u16 {
+        self.add_lookup(PositionLookup::MarkToMark(Lookup::new(
+            flags,
+            vec![builder.build()],
+            0,
+        )))
+    }
+
+    /// Restrict the marks that a lookup considers to a mark glyph set.
+    ///
+    /// This sets the `USE_MARK_FILTERING_SET` flag of the lookup; `set` is the
+    /// index of a mark glyph set in the GDEF table.
+    pub fn set_mark_filtering_set(&mut self, lookup: u16, set: u16) {
+        fn set_on<T>(lookup: &mut Lookup<T>, set: u16) {
+            lookup.lookup_flag.set_use_mark_filtering_set(true);
+            lookup.mark_filtering_set = set;
+        }
+        match &mut self.lookups[lookup as usize] {
+            PositionLookup::Single(lookup) => set_on(lookup, set),
+            PositionLookup::Pair(lookup) => set_on(lookup, set),
+            PositionLookup::Cursive(lookup) => set_on(lookup, set),
+            PositionLookup::MarkToBase(lookup) => set_on(lookup, set),
+            PositionLookup::MarkToLig(lookup) => set_on(lookup, set),
+            PositionLookup::MarkToMark(lookup) => set_on(lookup, set),
+            PositionLookup::Contextual(lookup) => set_on(lookup, set),
+            PositionLookup::ChainContextual(lookup) => set_on(lookup, set),
+            PositionLookup::Extension(lookup) => set_on(lookup, set),
+        }
+    }
+
+    /// Add lookups to a feature, for a script and language system.
+    ///
+    /// The language `dflt` is the script's default language system.
+    pub fn add_feature(&mut self, script: Tag, language: Tag, feature: Tag, lookups: &[u16]) {
+        self.features
+            .add_feature(script, language, feature, lookups)
+    }
+
+    /// Build the GPOS table.
+    pub fn build(self) -> Gpos {
+        let (script_list, feature_list) = self.features.build();
+        Gpos::new(script_list, feature_list, LookupList::new(self.lookups))
+    }
+}
+
+impl SinglePosBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the adjustment for a glyph, replacing any previous adjustment.
+    pub fn insert(&mut self, glyph: GlyphId, value: ValueRecord) {
+        self.items.insert(glyph, value);
+    }
+
+    /// Returns `true` if no glyphs have been added.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Build the subtable.
+    ///
+    /// If all glyphs have the same adjustment this is a format 1 subtable;
+    /// otherwise it is a format 2 subtable, with the value records padded to
+    /// a common format.
+    pub fn build(self) -> SinglePos {
+        let coverage = self.items.keys().copied().collect::<CoverageTableBuilder>();
+        let values = self.items.into_values().collect::<Vec<_>>();
+        let first = values.first().cloned().unwrap_or_default();
+        if values.iter().all(|value| *value == first) {
+            return SinglePos::format_1(coverage.build(), first);
+        }
+        let format = values.iter().fold(ValueFormat::empty(), |format, value| {
+            format | value.format()
+        });
+        SinglePos::format_2(
+            coverage.build(),
+            values
+                .into_iter()
+                .map(|value| pad_value_record(value, format))
+                .collect(),
+        )
+    }
+}
+
+impl MarkBaseBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a mark glyph, with its class and anchor.
+    pub fn add_mark(&mut self, glyph: GlyphId, class: u16, anchor: AnchorTable) {
+        self.attachments.marks.insert(glyph, (class, anchor));
+    }
+
+    /// Add the anchor of a base glyph for a class of marks.
+    pub fn add_base(&mut self, glyph: GlyphId, class: u16, anchor: AnchorTable) {
+        self.attachments.add_base(glyph, class, anchor)
+    }
+
+    /// Build the subtable.
+    ///
+    /// Mark classes are renumbered so that they are contiguous; anchors for
+    /// classes that have no marks are dropped.
+    pub fn build(self) -> MarkBasePosFormat1 {
+        let (mark_coverage, base_coverage, mark_array, anchors) = self.attachments.build();
+        MarkBasePosFormat1::new(
+            mark_coverage,
+            base_coverage,
+            mark_array,
+            BaseArray::new(anchors.into_iter().map(BaseRecord::new).collect()),
+        )
+    }
+}
+
+impl MarkMarkBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an attaching mark glyph, with its class and anchor.
+    pub fn add_mark(&mut self, glyph: GlyphId, class: u16, anchor: AnchorTable) {
+        self.attachments.marks.insert(glyph, (class, anchor));
+    }
+
+    /// Add the anchor of a base mark glyph for a class of marks.
+    pub fn add_base_mark(&mut self, glyph: GlyphId, class: u16, anchor: AnchorTable) {
+        self.attachments.add_base(glyph, class, anchor)
+    }
+
+    /// Build the subtable.
+    ///
+    /// Mark classes are renumbered so that they are contiguous; anchors for
+    /// classes that have no marks are dropped.
+    pub fn build(self) -> MarkMarkPosFormat1 {
+        let (mark1_coverage, mark2_coverage, mark1_array, anchors) = self.attachments.build();
+        MarkMarkPosFormat1::new(
+            mark1_coverage,
+            mark2_coverage,
+            mark1_array,
+            Mark2Array::new(anchors.into_iter().map(Mark2Record::new).collect()),
+        )
+    }
+}
+
+impl MarkAttachments {
+    fn add_base(&mut self, glyph: GlyphId, class: u16, anchor: AnchorTable) {
+        self.bases.entry(glyph).or_default().insert(class, anchor);
+    }
+
+    /// Returns the mark and base coverage, the mark array, and the anchors of
+    /// each base glyph, indexed by class.
+    #[allow(clippy::type_complexity)]
+    fn build(
+        self,
+    ) -> (
+        CoverageTable,
+        CoverageTable,
+        MarkArray,
+        Vec<Vec<Option<AnchorTable>>>,
+    ) {
+        let classes = self
+            .marks
+            .values()
+            .map(|(class, _)| *class)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .enumerate()
+            .map(|(i, class)| (class, i as u16))
+            .collect::<HashMap<_, _>>();
+        let mark_coverage = self
+            .marks
+            .keys()
+            .copied()
+            .collect::<CoverageTableBuilder>()
+            .build();
+        let mark_array = MarkArray::new(
+            self.marks
+                .into_values()
+                .map(|(class, anchor)| MarkRecord::new(classes[&class], anchor))
+                .collect(),
+        );
+        let base_coverage = self
+            .bases
+            .keys()
+            .copied()
+            .collect::<CoverageTableBuilder>()
+            .build();
+        let anchors = self
+            .bases
+            .into_values()
+            .map(|mut anchors| {
+                let mut record = vec![None; classes.len()];
+                for (class, index) in &classes {
+                    record[*index as usize] = anchors.remove(class);
+                }
+                record
+            })
+            .collect();
+        (mark_coverage, base_coverage, mark_array, anchors)
+    }
+}
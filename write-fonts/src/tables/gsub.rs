@@ -13,6 +13,13 @@ use super::layout::{
 #[path = "../tests/test_gsub.rs"]
 mod tests;
 
+#[path = "./gsub_builders.rs"]
+mod builders;
+pub use builders::{
+    AlternateSubBuilder, ChainContextBuilder, ChainRule, GsubBuilder, LigatureSubBuilder,
+    MultipleSubBuilder, SingleSubBuilder,
+};
+
 /// A GSUB lookup list table.
 type SubstitutionLookupList = LookupList<SubstitutionLookup>;
 
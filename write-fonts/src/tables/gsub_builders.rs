@@ -0,0 +1,448 @@
+//! Builders for GSUB lookups.
+//!
+//! Each builder collects the rules of one kind of lookup and chooses how to
+//! encode them. The [GsubBuilder] collects lookups and features into a
+//! complete table.
+//!
+//! Lookups that are too large for 16-bit offsets are promoted to extension
+//! lookups when the table is compiled, so the builders do not need to handle
+//! this case.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use read_fonts::tables::layout::LookupFlag;
+use types::{GlyphId, Tag};
+
+use super::{
+    AlternateSet, AlternateSubstFormat1, Gsub, Ligature, LigatureSet, LigatureSubstFormat1,
+    MultipleSubstFormat1, Sequence, SingleSubst, SubstitutionChainContext, SubstitutionLookup,
+};
+use crate::tables::layout::{
+    ChainedSequenceContext, ChainedSequenceRule, ChainedSequenceRuleSet, CoverageTable,
+    CoverageTableBuilder, FeatureListBuilder, Lookup, LookupList, SequenceLookupRecord,
+};
+
+/// A builder for a GSUB table.
+///
+/// ```
+/// # use write_fonts::tables::gsub::{GsubBuilder, LigatureSubBuilder};
+/// # use write_fonts::tables::layout::LookupFlag;
+/// # use write_fonts::types::{GlyphId, Tag};
+/// let mut ligatures = LigatureSubBuilder::new();
+/// ligatures.insert(&[GlyphId::new(5), GlyphId::new(8)], GlyphId::new(20));
+///
+/// let mut builder = GsubBuilder::new();
+/// let lookup = builder.add_ligature(LookupFlag::empty(), ligatures);
+/// builder.add_feature(Tag::new(b"latn"), Tag::new(b"dflt"), Tag::new(b"liga"), &[lookup]);
+/// let gsub = builder.build();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GsubBuilder {
+    lookups: Vec<SubstitutionLookup>,
+    features: FeatureListBuilder,
+}
+
+/// A builder for single substitution subtables.
+#[derive(Clone, Debug, Default)]
+pub struct SingleSubBuilder {
+    items: BTreeMap<GlyphId, GlyphId>,
+}
+
+/// A builder for multiple substitution subtables.
+#[derive(Clone, Debug, Default)]
+pub struct MultipleSubBuilder {
+    items: BTreeMap<GlyphId, Vec<GlyphId>>,
+}
+
+/// A builder for alternate substitution subtables.
+#[derive(Clone, Debug, Default)]
+pub struct AlternateSubBuilder {
+    items: BTreeMap<GlyphId, Vec<GlyphId>>,
+}
+
+/// A builder for ligature substitution subtables.
+#[derive(Clone, Debug, Default)]
+pub struct LigatureSubBuilder {
+    // first glyph -> (remaining components, ligature), in insertion order
+    items: BTreeMap<GlyphId, Vec<(Vec<GlyphId>, GlyphId)>>,
+}
+
+/// A builder for chained contextual substitution subtables.
+///
+/// Rules are tried in the order that they are added.
+#[derive(Clone, Debug, Default)]
+pub struct ChainContextBuilder {
+    rules: Vec<ChainRule>,
+}
+
+/// A rule in a chained contextual lookup.
+///
+/// Each position in the backtrack, input and lookahead sequences matches a set
+/// of glyphs. All sequences are in logical order; the backtrack sequence is
+/// reversed when it is compiled.
+#[derive(Clone, Debug, Default)]
+pub struct ChainRule {
+    backtrack: Vec<BTreeSet<GlyphId>>,
+    input: Vec<BTreeSet<GlyphId>>,
+    lookahead: Vec<BTreeSet<GlyphId>>,
+    lookups: Vec<SequenceLookupRecord>,
+}
+
+impl GsubBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a lookup, returning its index in the lookup list.
+    pub fn add_lookup(&mut self, lookup: SubstitutionLookup) -> u16 {
+        self.lookups.push(lookup);
+        // if we're over u16::MAX lookups, crash
+        (self.lookups.len() - 1).try_into().unwrap()
+    }
+
+    /// Add a single substitution lookup, returning its index.
+    pub fn add_single(&mut self, flags: LookupFlag, builder: SingleSubBuilder) -> u16 {
+        self.add_lookup(SubstitutionLookup::Single(Lookup::new(
+            flags,
+            builder.build(),
+            0,
+        )))
+    }
+
+    /// Add a multiple substitution lookup, returning its index.
+    pub fn add_multiple(&mut self, flags: LookupFlag, builder: MultipleSubBuilder) -> u16 {
+        self.add_lookup(SubstitutionLookup::Multiple(Lookup::new(
+            flags,
+            vec![builder.build()],
+            0,
+        )))
+    }
+
+    /// Add an alternate substitution lookup, returning its index.
+    pub fn add_alternate(&mut self, flags: LookupFlag, builder: AlternateSubBuilder) -> u16 {
+        self.add_lookup(SubstitutionLookup::Alternate(Lookup::new(
+            flags,
+            vec![builder.build()],
+            0,
+        )))
+    }
+
+    /// Add a ligature substitution lookup, returning its index.
+    pub fn add_ligature(&mut self, flags: LookupFlag, builder: LigatureSubBuilder) -> u16 {
+        self.add_lookup(SubstitutionLookup::Ligature(Lookup::new(
+            flags,
+            vec![builder.build()],
+            0,
+        )))
+    }
+
+    /// Add a chained contextual substitution lookup, returning its index.
+    ///
+    /// The lookups referenced by the rules must also be added to this
+    /// builder.
+    pub fn add_chain(&mut self, flags: LookupFlag, builder: ChainContextBuilder) -> u16 {
+        self.add_lookup(SubstitutionLookup::ChainContextual(Lookup::new(
+            flags,
+            builder.build(),
+            0,
+        )))
+    }
+
+    /// Restrict the marks that a lookup considers to a mark glyph set.
+    ///
+    /// This sets the `USE_MARK_FILTERING_SET` flag of the lookup; `set` is the
+    /// index of a mark glyph set in the GDEF table.
+    pub fn set_mark_filtering_set(&mut self, lookup: u16, set: u16) {
+        fn set_on<T>(lookup: &mut Lookup<T>, set: u16) {
+            lookup.lookup_flag.set_use_mark_filtering_set(true);
+            lookup.mark_filtering_set = set;
+        }
+        match &mut self.lookups[lookup as usize] {
+            SubstitutionLookup::Single(lookup) => set_on(lookup, set),
+            SubstitutionLookup::Multiple(lookup) => set_on(lookup, set),
+            SubstitutionLookup::Alternate(lookup) => set_on(lookup, set),
+            SubstitutionLookup::Ligature(lookup) => set_on(lookup, set),
+            SubstitutionLookup::Contextual(lookup) => set_on(lookup, set),
+            SubstitutionLookup::ChainContextual(lookup) => set_on(lookup, set),
+            SubstitutionLookup::Extension(lookup) => set_on(lookup, set),
+            SubstitutionLookup::Reverse(lookup) => set_on(lookup, set),
+        }
+    }
+
+    /// Add lookups to a feature, for a script and language system.
+    ///
+    /// The language `dflt` is the script's default language system.
+    pub fn add_feature(&mut self, script: Tag, language: Tag, feature: Tag, lookups: &[u16]) {
+        self.features
+            .add_feature(script, language, feature, lookups)
+    }
+
+    /// Build the GSUB table.
+    pub fn build(self) -> Gsub {
+        let (script_list, feature_list) = self.features.build();
+        Gsub::new(script_list, feature_list, LookupList::new(self.lookups))
+    }
+}
+
+impl SingleSubBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Substitute `target` with `replacement`.
+    ///
+    /// If there is already a substitution for `target`, it is replaced.
+    pub fn insert(&mut self, target: GlyphId, replacement: GlyphId) {
+        self.items.insert(target, replacement);
+    }
+
+    /// Returns `true` if no substitutions have been added.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Build the subtables for these substitutions.
+    ///
+    /// If every substitution adds the same delta to the glyph id, they are
+    /// written as a format 1 subtable; otherwise they are written as format 2.
+    pub fn build(self) -> Vec<SingleSubst> {
+        let delta = |(target, replacement): (&GlyphId, &GlyphId)| {
+            replacement.to_u16().wrapping_sub(target.to_u16()) as i16
+        };
+        let first_delta = self.items.iter().next().map(delta);
+        let coverage = self.items.keys().copied().collect::<CoverageTableBuilder>();
+        let subtable = match first_delta {
+            Some(first) if self.items.iter().all(|item| delta(item) == first) => {
+                SingleSubst::format_1(coverage.build(), first)
+            }
+            _ => SingleSubst::format_2(coverage.build(), self.items.into_values().collect()),
+        };
+        vec![subtable]
+    }
+}
+
+impl MultipleSubBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Substitute `target` with a sequence of glyphs.
+    ///
+    /// If there is already a substitution for `target`, it is replaced.
+    pub fn insert(&mut self, target: GlyphId, replacement: Vec<GlyphId>) {
+        self.items.insert(target, replacement);
+    }
+
+    /// Build the subtable for these substitutions.
+    pub fn build(self) -> MultipleSubstFormat1 {
+        let coverage = self.items.keys().copied().collect::<CoverageTableBuilder>();
+        let sequences = self.items.into_values().map(Sequence::new).collect();
+        MultipleSubstFormat1::new(coverage.build(), sequences)
+    }
+}
+
+impl AlternateSubBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add alternates for `target`.
+    ///
+    /// If `target` already has alternates, these are added to the end.
+    pub fn insert(&mut self, target: GlyphId, alternates: &[GlyphId]) {
+        self.items
+            .entry(target)
+            .or_default()
+            .extend_from_slice(alternates);
+    }
+
+    /// Build the subtable for these substitutions.
+    pub fn build(self) -> AlternateSubstFormat1 {
+        let coverage = self.items.keys().copied().collect::<CoverageTableBuilder>();
+        let alternate_sets = self.items.into_values().map(AlternateSet::new).collect();
+        AlternateSubstFormat1::new(coverage.build(), alternate_sets)
+    }
+}
+
+impl LigatureSubBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Substitute a sequence of glyphs with a ligature.
+    ///
+    /// If there is already a substitution for this sequence, it is replaced.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `sequence` is empty.
+    pub fn insert(&mut self, sequence: &[GlyphId], ligature: GlyphId) {
+        let (first, rest) = sequence
+            .split_first()
+            .expect("ligature sequence must not be empty");
+        let ligatures = self.items.entry(*first).or_default();
+        match ligatures
+            .iter_mut()
+            .find(|(components, _)| components == rest)
+        {
+            Some(existing) => existing.1 = ligature,
+            None => ligatures.push((rest.to_vec(), ligature)),
+        }
+    }
+
+    /// Build the subtable for these substitutions.
+    ///
+    /// The ligatures for each first glyph are ordered from longest to
+    /// shortest, so that longer sequences take precedence.
+    pub fn build(self) -> LigatureSubstFormat1 {
+        let coverage = self.items.keys().copied().collect::<CoverageTableBuilder>();
+        let ligature_sets = self
+            .items
+            .into_values()
+            .map(|mut ligatures| {
+                // stable, so equal lengths keep the order they were added in
+                ligatures.sort_by_key(|(components, _)| std::cmp::Reverse(components.len()));
+                LigatureSet::new(
+                    ligatures
+                        .into_iter()
+                        .map(|(components, ligature)| Ligature::new(ligature, components))
+                        .collect(),
+                )
+            })
+            .collect();
+        LigatureSubstFormat1::new(coverage.build(), ligature_sets)
+    }
+}
+
+impl ChainContextBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule.
+    pub fn add_rule(&mut self, rule: ChainRule) {
+        self.rules.push(rule);
+    }
+
+    /// Build the subtables for these rules.
+    ///
+    /// Consecutive rules that only match single glyphs are written together
+    /// as a glyph-based (format 1) subtable; other rules are written as
+    /// coverage-based (format 3) subtables. This preserves the order of the
+    /// rules.
+    pub fn build(self) -> Vec<SubstitutionChainContext> {
+        let mut subtables = Vec::new();
+        let mut glyph_rules = Vec::new();
+        for rule in self.rules {
+            if rule.is_glyph_based() {
+                glyph_rules.push(rule);
+                continue;
+            }
+            if !glyph_rules.is_empty() {
+                subtables.push(build_chain_format1(std::mem::take(&mut glyph_rules)));
+            }
+            subtables.push(rule.build_format3());
+        }
+        if !glyph_rules.is_empty() {
+            subtables.push(build_chain_format1(glyph_rules));
+        }
+        subtables
+            .into_iter()
+            .map(SubstitutionChainContext::from)
+            .collect()
+    }
+}
+
+impl ChainRule {
+    /// Create a rule that matches this input sequence.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `input` is empty.
+    pub fn new(input: Vec<BTreeSet<GlyphId>>) -> Self {
+        assert!(!input.is_empty(), "chain rule input must not be empty");
+        ChainRule {
+            input,
+            ..Default::default()
+        }
+    }
+
+    /// Set the glyphs that must precede the input, in logical order.
+    pub fn backtrack(mut self, backtrack: Vec<BTreeSet<GlyphId>>) -> Self {
+        self.backtrack = backtrack;
+        self
+    }
+
+    /// Set the glyphs that must follow the input.
+    pub fn lookahead(mut self, lookahead: Vec<BTreeSet<GlyphId>>) -> Self {
+        self.lookahead = lookahead;
+        self
+    }
+
+    /// Apply a lookup at a position in the input sequence.
+    pub fn lookup(mut self, sequence_index: u16, lookup_index: u16) -> Self {
+        self.lookups
+            .push(SequenceLookupRecord::new(sequence_index, lookup_index));
+        self
+    }
+
+    fn is_glyph_based(&self) -> bool {
+        [&self.backtrack, &self.input, &self.lookahead]
+            .into_iter()
+            .flatten()
+            .all(|glyphs| glyphs.len() == 1)
+    }
+
+    fn build_format3(self) -> ChainedSequenceContext {
+        let coverages = |sets: Vec<BTreeSet<GlyphId>>| -> Vec<CoverageTable> {
+            sets.into_iter()
+                .map(|glyphs| glyphs.into_iter().collect::<CoverageTableBuilder>().build())
+                .collect()
+        };
+        let mut backtrack = self.backtrack;
+        backtrack.reverse();
+        ChainedSequenceContext::format_3(
+            coverages(backtrack),
+            coverages(self.input),
+            coverages(self.lookahead),
+            self.lookups,
+        )
+    }
+}
+
+/// Build a format 1 subtable from rules where every position is a single
+/// glyph.
+fn build_chain_format1(rules: Vec<ChainRule>) -> ChainedSequenceContext {
+    let glyphs = |sets: &[BTreeSet<GlyphId>]| -> Vec<GlyphId> {
+        sets.iter().map(|glyphs| *glyphs.first().unwrap()).collect()
+    };
+    let mut rule_sets = BTreeMap::<GlyphId, Vec<ChainedSequenceRule>>::new();
+    for rule in rules {
+        let input = glyphs(&rule.input);
+        let mut backtrack = glyphs(&rule.backtrack);
+        backtrack.reverse();
+        rule_sets
+            .entry(input[0])
+            .or_default()
+            .push(ChainedSequenceRule::new(
+                backtrack,
+                input[1..].to_vec(),
+                glyphs(&rule.lookahead),
+                rule.lookups,
+            ));
+    }
+    let coverage = rule_sets.keys().copied().collect::<CoverageTableBuilder>();
+    ChainedSequenceContext::format_1(
+        coverage.build(),
+        rule_sets
+            .into_values()
+            .map(|rules| Some(ChainedSequenceRuleSet::new(rules)))
+            .collect(),
+    )
+}
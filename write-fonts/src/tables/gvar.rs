@@ -0,0 +1,739 @@
+//! The [gvar (Glyph Variations)](https://learn.microsoft.com/en-us/typography/opentype/spec/gvar) table
+
+use std::collections::BTreeMap;
+
+use read_fonts::TopLevelTable;
+use types::{F2Dot14, GlyphId, MajorMinor, Point, Tag};
+
+use crate::{
+    tables::variations::{
+        encode_deltas, encode_point_numbers, explicit_intermediate_region, PackedTupleVariation,
+        Tuple, TupleVariationStore,
+    },
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+/// The shared tuple index is stored in the low 12 bits of the tuple index.
+const MAX_SHARED_TUPLES: usize = 0x1000;
+
+/// The [gvar] table.
+///
+/// This is compiled from the deltas of each glyph with [`Gvar::new`].
+///
+/// [gvar]: https://learn.microsoft.com/en-us/typography/opentype/spec/gvar
+#[derive(Clone, Debug, Default)]
+pub struct Gvar {
+    axis_count: u16,
+    shared_tuples: Vec<Tuple>,
+    // the compiled GlyphVariationData for each glyph; empty if a glyph has
+    // no variations. Each entry has an even length.
+    glyph_variation_data: Vec<Vec<u8>>,
+}
+
+/// The variation data for a single glyph.
+#[derive(Clone, Debug)]
+pub struct GlyphVariations {
+    gid: GlyphId,
+    variations: Vec<GlyphDeltas>,
+}
+
+/// The deltas for every point of a glyph at a single region of the design
+/// space.
+///
+/// For a simple glyph there is one delta per point, followed by one for each
+/// of the four phantom points; for a composite glyph there is one delta per
+/// component, followed by the phantom points.
+#[derive(Clone, Debug)]
+pub struct GlyphDeltas {
+    peak_tuple: Tuple,
+    intermediate_region: Option<(Tuple, Tuple)>,
+    deltas: Vec<GlyphDelta>,
+}
+
+/// The delta for a single point or component.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GlyphDelta {
+    pub x: i16,
+    pub y: i16,
+    /// If `false`, this delta can be inferred by interpolating the deltas of
+    /// neighbouring points (IUP) and does not need to be written.
+    pub required: bool,
+}
+
+/// An error in the input to [`Gvar::new`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GvarInputError {
+    /// Every glyph must have an entry, even if it has no variations.
+    GlyphMissing(GlyphId),
+    /// A glyph has more than one entry.
+    DuplicateGlyph(GlyphId),
+    /// The variations of a glyph do not all have the same number of deltas.
+    InconsistentDeltaLength(GlyphId),
+    /// A tuple of a glyph does not have one value per axis.
+    InconsistentTupleLengths(GlyphId),
+    /// A glyph has too many variations, or too much data, to be encoded.
+    GlyphDataTooLarge(GlyphId),
+    /// There are more glyphs than can be stored in the table.
+    TooManyGlyphs,
+}
+
+/// An error in the input to [`iup_delta_optimize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IupError {
+    /// There must be one delta for each point.
+    DeltaCountMismatch { deltas: usize, points: usize },
+    /// A contour end point is out of order or out of bounds.
+    InvalidContourEnd(usize),
+}
+
+impl Gvar {
+    /// Compile a new table.
+    ///
+    /// `variations` must contain an entry for each glyph in the font, in any
+    /// order; glyphs without variations should have an empty entry.
+    ///
+    /// Peak tuples used by more than one glyph are stored in the shared tuple
+    /// array, and within each glyph the most profitable set of point numbers
+    /// is shared between variations.
+    pub fn new(
+        mut variations: Vec<GlyphVariations>,
+        axis_count: u16,
+    ) -> Result<Self, GvarInputError> {
+        if variations.len() > u16::MAX as usize {
+            return Err(GvarInputError::TooManyGlyphs);
+        }
+        variations.sort_by_key(|var| var.gid);
+        for (i, var) in variations.iter().enumerate() {
+            let expected = GlyphId::new(i as u16);
+            if var.gid > expected {
+                return Err(GvarInputError::GlyphMissing(expected));
+            }
+            if var.gid < expected {
+                return Err(GvarInputError::DuplicateGlyph(var.gid));
+            }
+            var.validate(axis_count)?;
+        }
+
+        let shared_tuples = compute_shared_tuples(&variations);
+        let shared_indices = shared_tuples
+            .iter()
+            .enumerate()
+            .map(|(i, tuple)| (tuple.as_slice(), i as u16))
+            .collect::<BTreeMap<_, _>>();
+        let glyph_variation_data = variations
+            .iter()
+            .map(|var| var.compile(&shared_indices))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Gvar {
+            axis_count,
+            shared_tuples: shared_tuples.into_iter().map(Tuple::new).collect(),
+            glyph_variation_data,
+        })
+    }
+
+    /// The number of glyphs in the table.
+    pub fn glyph_count(&self) -> u16 {
+        self.glyph_variation_data.len() as u16
+    }
+
+    /// The tuples shared between glyphs.
+    pub fn shared_tuples(&self) -> &[Tuple] {
+        &self.shared_tuples
+    }
+
+    fn data_len(&self) -> usize {
+        self.glyph_variation_data.iter().map(Vec::len).sum()
+    }
+
+    fn use_long_offsets(&self) -> bool {
+        // short offsets are stored divided by two; each entry has even length
+        self.data_len() / 2 > u16::MAX as usize
+    }
+}
+
+impl GlyphVariations {
+    /// Create the variations for a glyph.
+    pub fn new(gid: GlyphId, variations: Vec<GlyphDeltas>) -> Self {
+        GlyphVariations { gid, variations }
+    }
+
+    fn validate(&self, axis_count: u16) -> Result<(), GvarInputError> {
+        let axis_count = axis_count as usize;
+        let Some(first) = self.variations.first() else {
+            return Ok(());
+        };
+        if self
+            .variations
+            .iter()
+            .any(|var| var.deltas.len() != first.deltas.len())
+        {
+            return Err(GvarInputError::InconsistentDeltaLength(self.gid));
+        }
+        if self.variations.iter().any(|var| {
+            var.peak_tuple.values.len() != axis_count
+                || var
+                    .intermediate_region
+                    .as_ref()
+                    .is_some_and(|(start, end)| {
+                        start.values.len() != axis_count || end.values.len() != axis_count
+                    })
+        }) {
+            return Err(GvarInputError::InconsistentTupleLengths(self.gid));
+        }
+        Ok(())
+    }
+
+    /// The variations that have an effect; the others are not written.
+    fn active_variations(&self) -> impl Iterator<Item = &GlyphDeltas> {
+        self.variations.iter().filter(|var| !var.is_noop())
+    }
+
+    /// Compile the GlyphVariationData table for this glyph.
+    fn compile(
+        &self,
+        shared_tuples: &BTreeMap<&[F2Dot14], u16>,
+    ) -> Result<Vec<u8>, GvarInputError> {
+        let too_large = || GvarInputError::GlyphDataTooLarge(self.gid);
+        let variations = self
+            .active_variations()
+            .map(|var| {
+                let all_points = var.all_required();
+                let deltas = var
+                    .deltas
+                    .iter()
+                    .filter(|delta| all_points || delta.required);
+                let mut packed = Vec::new();
+                encode_deltas(deltas.clone().map(|delta| delta.x), &mut packed);
+                encode_deltas(deltas.map(|delta| delta.y), &mut packed);
+                PackedTupleVariation {
+                    peak_tuple: &var.peak_tuple.values,
+                    intermediate_region: var
+                        .explicit_intermediate_region()
+                        .map(|(start, end)| (start.values.as_slice(), end.values.as_slice())),
+                    point_numbers: encode_point_numbers(var.point_numbers().as_deref()),
+                    deltas: packed,
+                }
+            })
+            .collect::<Vec<_>>();
+        if variations.is_empty() {
+            return Ok(Vec::new());
+        }
+        let store =
+            TupleVariationStore::compile(&variations, shared_tuples).ok_or_else(too_large)?;
+        // tupleVariationCount + dataOffset + headers
+        let data_offset: u16 = (4 + store.headers.len())
+            .try_into()
+            .map_err(|_| too_large())?;
+
+        let mut result = Vec::with_capacity(data_offset as usize + store.serialized.len() + 1);
+        result.extend_from_slice(&store.count.to_be_bytes());
+        result.extend_from_slice(&data_offset.to_be_bytes());
+        result.extend(store.headers);
+        result.extend(store.serialized);
+        if result.len() % 2 != 0 {
+            result.push(0);
+        }
+        Ok(result)
+    }
+}
+
+impl GlyphDeltas {
+    /// Create a new set of deltas.
+    ///
+    /// If `intermediate_region` is `None`, the region is inferred from the
+    /// peak tuple, spanning from zero to the peak on each axis.
+    pub fn new(
+        peak_tuple: Tuple,
+        deltas: Vec<GlyphDelta>,
+        intermediate_region: Option<(Tuple, Tuple)>,
+    ) -> Self {
+        GlyphDeltas {
+            peak_tuple,
+            intermediate_region,
+            deltas,
+        }
+    }
+
+    fn all_required(&self) -> bool {
+        self.deltas.iter().all(|delta| delta.required)
+    }
+
+    /// `true` if applying these deltas would not change the glyph.
+    ///
+    /// If no non-zero delta is required, every delta is interpolated from
+    /// zeros and the whole variation can be dropped.
+    fn is_noop(&self) -> bool {
+        self.deltas
+            .iter()
+            .all(|delta| !delta.required || (delta.x == 0 && delta.y == 0))
+    }
+
+    /// The point numbers to write, or `None` if all points are included.
+    fn point_numbers(&self) -> Option<Vec<u16>> {
+        (!self.all_required()).then(|| {
+            self.deltas
+                .iter()
+                .enumerate()
+                .filter_map(|(i, delta)| delta.required.then_some(i as u16))
+                .collect()
+        })
+    }
+
+    /// The intermediate region, if it differs from the one implied by the
+    /// peak tuple.
+    fn explicit_intermediate_region(&self) -> Option<&(Tuple, Tuple)> {
+        explicit_intermediate_region(&self.peak_tuple, self.intermediate_region.as_ref())
+    }
+}
+
+impl GlyphDelta {
+    /// Create a new delta.
+    pub fn new(x: i16, y: i16, required: bool) -> Self {
+        GlyphDelta { x, y, required }
+    }
+
+    /// A delta that must be written.
+    pub fn required(x: i16, y: i16) -> Self {
+        Self::new(x, y, true)
+    }
+
+    /// A delta that can be inferred by interpolation, and may be omitted.
+    pub fn optional(x: i16, y: i16) -> Self {
+        Self::new(x, y, false)
+    }
+}
+
+/// Peak tuples used by more than one glyph, most frequently used first.
+fn compute_shared_tuples(glyphs: &[GlyphVariations]) -> Vec<Vec<F2Dot14>> {
+    let mut counts = BTreeMap::<&[F2Dot14], usize>::new();
+    for var in glyphs.iter().flat_map(GlyphVariations::active_variations) {
+        *counts.entry(var.peak_tuple.values.as_slice()).or_default() += 1;
+    }
+    let mut shared = counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .collect::<Vec<_>>();
+    // stable sort, so ties are ordered by tuple value
+    shared.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    shared
+        .into_iter()
+        .take(MAX_SHARED_TUPLES)
+        .map(|(tuple, _)| tuple.to_vec())
+        .collect()
+}
+
+/// Determine which deltas can be inferred by [interpolation][iup], and so
+/// do not need to be written.
+///
+/// `points` are the original coordinates of a simple glyph, including the four
+/// phantom points, `deltas` contains one delta for each of them and
+/// `contour_ends` contains the index of the last point of each contour.
+///
+/// A delta is marked as optional if the delta inferred for it, after omitting
+/// it, is within `tolerance` of its actual value. Deltas for points that are
+/// not part of a contour (such as the phantom points) are always required.
+///
+/// [iup]: https://learn.microsoft.com/en-us/typography/opentype/spec/gvar#inferred-deltas-for-un-referenced-point-numbers
+pub fn iup_delta_optimize(
+    deltas: &[Point<i16>],
+    points: &[Point<i16>],
+    contour_ends: &[usize],
+    tolerance: f64,
+) -> Result<Vec<GlyphDelta>, IupError> {
+    if deltas.len() != points.len() {
+        return Err(IupError::DeltaCountMismatch {
+            deltas: deltas.len(),
+            points: points.len(),
+        });
+    }
+    let mut result = deltas
+        .iter()
+        .map(|delta| GlyphDelta::required(delta.x, delta.y))
+        .collect::<Vec<_>>();
+    let mut start = 0;
+    for &end in contour_ends {
+        if end < start || end >= points.len() {
+            return Err(IupError::InvalidContourEnd(end));
+        }
+        let contour = start..end + 1;
+        start = end + 1;
+        optimize_contour(
+            &deltas[contour.clone()],
+            &points[contour.clone()],
+            &mut result[contour],
+            tolerance,
+        );
+    }
+    Ok(result)
+}
+
+/// Greedily mark deltas as optional, as long as every omitted delta can still
+/// be inferred from its nearest required neighbours.
+fn optimize_contour(
+    deltas: &[Point<i16>],
+    points: &[Point<i16>],
+    result: &mut [GlyphDelta],
+    tolerance: f64,
+) {
+    if deltas.iter().all(|delta| delta.x == 0 && delta.y == 0) {
+        result.iter_mut().for_each(|delta| delta.required = false);
+        return;
+    }
+    let n = deltas.len();
+    for i in 0..n {
+        let required = |ix: &usize| result[*ix].required;
+        // the nearest required points before and after this one, wrapping
+        let Some(prev) = (1..n).map(|k| (i + n - k) % n).find(required) else {
+            // this is the only required point left
+            break;
+        };
+        let next = (1..n).map(|k| (i + k) % n).find(required).unwrap();
+        let mut ix = (prev + 1) % n;
+        let mut can_infer = true;
+        while ix != next {
+            let inferred = infer_delta(points, deltas, prev, next, ix);
+            let actual = deltas[ix];
+            if (inferred.x - actual.x as f64).abs() > tolerance
+                || (inferred.y - actual.y as f64).abs() > tolerance
+            {
+                can_infer = false;
+                break;
+            }
+            ix = (ix + 1) % n;
+        }
+        if can_infer {
+            result[i].required = false;
+        }
+    }
+}
+
+/// Infer the delta of point `ix` from the reference points `p1` and `p2`.
+///
+/// This matches the interpolation performed when reading.
+fn infer_delta(
+    points: &[Point<i16>],
+    deltas: &[Point<i16>],
+    p1: usize,
+    p2: usize,
+    ix: usize,
+) -> Point<f64> {
+    fn interpolate(coord: i16, ref1: (i16, i16), ref2: (i16, i16)) -> f64 {
+        let ((in1, d1), (in2, d2)) = if ref1.0 <= ref2.0 {
+            (ref1, ref2)
+        } else {
+            (ref2, ref1)
+        };
+        if coord <= in1 {
+            d1 as f64
+        } else if coord >= in2 {
+            d2 as f64
+        } else {
+            let t = (coord - in1) as f64 / (in2 as f64 - in1 as f64);
+            d1 as f64 + t * (d2 as f64 - d1 as f64)
+        }
+    }
+    let (ref1, ref2, point) = (points[p1], points[p2], points[ix]);
+    let (d1, d2) = (deltas[p1], deltas[p2]);
+    Point::new(
+        interpolate(point.x, (ref1.x, d1.x), (ref2.x, d2.x)),
+        interpolate(point.y, (ref1.y, d1.y), (ref2.y, d2.y)),
+    )
+}
+
+impl TopLevelTable for Gvar {
+    const TAG: Tag = Tag::GVAR;
+}
+
+impl FontWrite for Gvar {
+    fn write_into(&self, writer: &mut TableWriter) {
+        // the size of the fields before the offsets array
+        const HEADER_LEN: usize = 20;
+        let long_offsets = self.use_long_offsets();
+        let offset_len = if long_offsets { 4 } else { 2 };
+        let shared_tuples_offset = HEADER_LEN + offset_len * (self.glyph_variation_data.len() + 1);
+        let shared_tuples_len = self.shared_tuples.len() * self.axis_count as usize * 2;
+        let data_offset = shared_tuples_offset + shared_tuples_len;
+
+        MajorMinor::VERSION_1_0.write_into(writer);
+        self.axis_count.write_into(writer);
+        (self.shared_tuples.len() as u16).write_into(writer);
+        (shared_tuples_offset as u32).write_into(writer);
+        self.glyph_count().write_into(writer);
+        (long_offsets as u16).write_into(writer);
+        (data_offset as u32).write_into(writer);
+
+        let mut offset = 0u32;
+        let mut write_offset = |offset: u32| {
+            if long_offsets {
+                offset.write_into(writer);
+            } else {
+                ((offset / 2) as u16).write_into(writer);
+            }
+        };
+        write_offset(offset);
+        for data in &self.glyph_variation_data {
+            offset += data.len() as u32;
+            write_offset(offset);
+        }
+        self.shared_tuples.write_into(writer);
+        for data in &self.glyph_variation_data {
+            writer.write_slice(data);
+        }
+    }
+}
+
+impl Validate for Gvar {
+    // the input is checked when the table is constructed
+    fn validate_impl(&self, _ctx: &mut ValidationCtx) {}
+}
+
+impl std::fmt::Display for GvarInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GvarInputError::GlyphMissing(gid) => write!(f, "no variations for glyph {gid}"),
+            GvarInputError::DuplicateGlyph(gid) => {
+                write!(f, "multiple variations for glyph {gid}")
+            }
+            GvarInputError::InconsistentDeltaLength(gid) => {
+                write!(f, "inconsistent number of deltas for glyph {gid}")
+            }
+            GvarInputError::InconsistentTupleLengths(gid) => {
+                write!(f, "tuple length does not match axis count for glyph {gid}")
+            }
+            GvarInputError::GlyphDataTooLarge(gid) => {
+                write!(f, "variation data for glyph {gid} is too large")
+            }
+            GvarInputError::TooManyGlyphs => f.write_str("too many glyphs"),
+        }
+    }
+}
+
+impl std::fmt::Display for IupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IupError::DeltaCountMismatch { deltas, points } => {
+                write!(f, "{deltas} deltas for {points} points")
+            }
+            IupError::InvalidContourEnd(end) => write!(f, "invalid contour end point {end}"),
+        }
+    }
+}
+
+impl std::error::Error for GvarInputError {}
+impl std::error::Error for IupError {}
+
+#[cfg(test)]
+mod tests {
+    use read::{
+        tables::{glyf::PointFlags, gvar as read_gvar, variations::Tuple as ReadTuple},
+        types::Fixed,
+        FontData, FontRead,
+    };
+
+    use super::*;
+
+    fn tuple(values: &[f32]) -> Tuple {
+        Tuple::new(values.iter().copied().map(F2Dot14::from_f32).collect())
+    }
+
+    fn required(deltas: &[(i16, i16)]) -> Vec<GlyphDelta> {
+        deltas
+            .iter()
+            .map(|(x, y)| GlyphDelta::required(*x, *y))
+            .collect()
+    }
+
+    fn read_tuple(tuple: ReadTuple) -> Vec<f32> {
+        (0..tuple.len())
+            .map(|i| tuple.get(i).unwrap().to_f32())
+            .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let variations = vec![
+            GlyphVariations::new(GlyphId::new(0), vec![]),
+            GlyphVariations::new(
+                GlyphId::new(2),
+                vec![GlyphDeltas::new(
+                    tuple(&[1.0, 0.0]),
+                    required(&[(5, 6), (0, 0), (300, -300)]),
+                    Some((tuple(&[0.5, 0.0]), tuple(&[1.0, 0.0]))),
+                )],
+            ),
+            GlyphVariations::new(
+                GlyphId::new(1),
+                vec![
+                    GlyphDeltas::new(
+                        tuple(&[1.0, 0.0]),
+                        vec![
+                            GlyphDelta::required(10, 20),
+                            GlyphDelta::optional(5, 5),
+                            GlyphDelta::required(0, 0),
+                        ],
+                        None,
+                    ),
+                    GlyphDeltas::new(
+                        tuple(&[0.0, -1.0]),
+                        vec![
+                            GlyphDelta::required(-1, -2),
+                            GlyphDelta::optional(5, 5),
+                            GlyphDelta::required(0, 0),
+                        ],
+                        // the same as the implicit region; not written
+                        Some((tuple(&[0.0, -1.0]), tuple(&[0.0, 0.0]))),
+                    ),
+                    // no effect; dropped
+                    GlyphDeltas::new(tuple(&[1.0, 1.0]), required(&[(0, 0); 3]), None),
+                ],
+            ),
+        ];
+        let gvar = Gvar::new(variations, 2).unwrap();
+        let bytes = crate::dump_table(&gvar).unwrap();
+        let read = read_gvar::Gvar::read(FontData::new(&bytes)).unwrap();
+
+        assert_eq!(read.axis_count(), 2);
+        assert_eq!(read.glyph_count(), 3);
+        assert_eq!(read.shared_tuple_count(), 1);
+        assert!(read
+            .glyph_variation_data(GlyphId::new(0))
+            .unwrap()
+            .is_none());
+
+        let glyph1 = read.glyph_variation_data(GlyphId::new(1)).unwrap().unwrap();
+        let tuples = glyph1.tuples().collect::<Vec<_>>();
+        assert_eq!(tuples.len(), 2);
+        assert_eq!(read_tuple(tuples[0].peak()), [1.0, 0.0]);
+        assert_eq!(read_tuple(tuples[1].peak()), [0.0, -1.0]);
+        let deltas = tuples[1]
+            .deltas()
+            .map(|d| (d.position, d.x_delta, d.y_delta))
+            .collect::<Vec<_>>();
+        assert_eq!(deltas, [(0, -1, -2), (2, 0, 0)]);
+
+        let glyph2 = read.glyph_variation_data(GlyphId::new(2)).unwrap().unwrap();
+        let tuple = glyph2.tuples().next().unwrap();
+        assert!(tuple.all_points());
+        assert_eq!(read_tuple(tuple.peak()), [1.0, 0.0]);
+        // the explicit intermediate region makes this apply only above 0.5
+        let coords = [F2Dot14::from_f32(0.25), F2Dot14::ZERO];
+        assert_eq!(tuple.compute_scalar(&coords), None);
+        let coords = [F2Dot14::from_f32(0.75), F2Dot14::ZERO];
+        assert_eq!(tuple.compute_scalar(&coords), Some(Fixed::from_f64(0.5)));
+        let deltas = tuple
+            .deltas()
+            .map(|d| (d.x_delta, d.y_delta))
+            .collect::<Vec<_>>();
+        assert_eq!(deltas, [(5, 6), (0, 0), (300, -300)]);
+    }
+
+    #[test]
+    fn input_errors() {
+        let deltas = |n| GlyphDeltas::new(tuple(&[1.0]), required(&vec![(1, 1); n]), None);
+        assert_eq!(
+            Gvar::new(vec![GlyphVariations::new(GlyphId::new(1), vec![])], 1).unwrap_err(),
+            GvarInputError::GlyphMissing(GlyphId::new(0))
+        );
+        assert_eq!(
+            Gvar::new(
+                vec![GlyphVariations::new(
+                    GlyphId::new(0),
+                    vec![deltas(3), deltas(4)]
+                )],
+                1
+            )
+            .unwrap_err(),
+            GvarInputError::InconsistentDeltaLength(GlyphId::new(0))
+        );
+        assert_eq!(
+            Gvar::new(
+                vec![GlyphVariations::new(GlyphId::new(0), vec![deltas(3)])],
+                2
+            )
+            .unwrap_err(),
+            GvarInputError::InconsistentTupleLengths(GlyphId::new(0))
+        );
+    }
+
+    #[test]
+    fn iup_optimize() {
+        // a square, plus four phantom points
+        let points = [
+            (0, 0),
+            (0, 100),
+            (100, 100),
+            (100, 0),
+            (0, 0),
+            (0, 0),
+            (0, 0),
+            (0, 0),
+        ]
+        .map(|(x, y)| Point::new(x, y));
+        // stretched by ten units on each axis
+        let deltas = [
+            (0, 0),
+            (0, 10),
+            (10, 10),
+            (10, 0),
+            (0, 0),
+            (10, 0),
+            (0, 0),
+            (0, 0),
+        ]
+        .map(|(x, y)| Point::new(x, y));
+        let optimized = iup_delta_optimize(&deltas, &points, &[3], 0.5).unwrap();
+        let required = optimized
+            .iter()
+            .enumerate()
+            .filter_map(|(i, delta)| delta.required.then_some(i))
+            .collect::<Vec<_>>();
+        assert_eq!(required, [1, 3, 4, 5, 6, 7]);
+
+        // the omitted deltas are recovered when reading
+        let gvar = Gvar::new(
+            vec![GlyphVariations::new(
+                GlyphId::new(0),
+                vec![GlyphDeltas::new(tuple(&[1.0]), optimized, None)],
+            )],
+            1,
+        )
+        .unwrap();
+        let bytes = crate::dump_table(&gvar).unwrap();
+        let read = read_gvar::Gvar::read(FontData::new(&bytes)).unwrap();
+        let data = read.glyph_variation_data(GlyphId::new(0)).unwrap().unwrap();
+        let points = points.map(|p| Point::new(p.x as i32, p.y as i32));
+        let mut flags = [PointFlags::default(); 8];
+        let mut scratch = [Point::default(); 8];
+        let mut computed = [Point::default(); 8];
+        data.compute_deltas(
+            &[F2Dot14::ONE],
+            &points,
+            &[3],
+            &mut flags,
+            &mut scratch,
+            &mut computed,
+        )
+        .unwrap();
+        let computed = computed.map(|p| Point::new(p.x.to_i32() as i16, p.y.to_i32() as i16));
+        assert_eq!(computed, deltas);
+    }
+
+    #[test]
+    fn iup_all_zero_or_uniform() {
+        let points = [(0, 0), (50, 80), (100, 0)].map(|(x, y)| Point::new(x, y));
+        let zeros = [Point::new(0, 0); 3];
+        let optimized = iup_delta_optimize(&zeros, &points, &[2], 0.5).unwrap();
+        assert!(optimized.iter().all(|delta| !delta.required));
+
+        let shifted = [Point::new(7, -3); 3];
+        let optimized = iup_delta_optimize(&shifted, &points, &[2], 0.5).unwrap();
+        assert_eq!(optimized.iter().filter(|delta| delta.required).count(), 1);
+
+        assert_eq!(
+            iup_delta_optimize(&shifted, &points, &[3], 0.5),
+            Err(IupError::InvalidContourEnd(3))
+        );
+    }
+}
@@ -1,3 +1,12 @@
 //! The head table
 
 include!("../../generated/generated_head.rs");
+
+use super::loca::LocaFormat;
+
+impl Head {
+    /// Set `index_to_loc_format` to match the format of a compiled loca table.
+    pub fn set_loca_format(&mut self, format: LocaFormat) {
+        self.index_to_loc_format = format.index_to_loc_format();
+    }
+}
@@ -2,6 +2,91 @@
 
 include!("../../generated/generated_hmtx.rs");
 
+use super::{glyf::Bbox, hhea::Hhea};
+
+/// A builder for the `hmtx` table.
+///
+/// Glyphs are added in glyph id order. When the table is built, the trailing
+/// run of glyphs that share the same advance width is stored as bare side
+/// bearings, and the metrics in the `hhea` table are recomputed.
+#[derive(Clone, Debug, Default)]
+pub struct HmtxBuilder {
+    glyphs: Vec<(u16, Option<Bbox>)>,
+}
+
+impl HmtxBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a glyph; its id is the number of glyphs previously added.
+    ///
+    /// The bounding box should be `None` for glyphs with no contours. The left
+    /// side bearing of the glyph is the minimum x coordinate of its bounding
+    /// box, as is expected for fonts with TrueType outlines.
+    pub fn add_glyph(&mut self, advance: u16, bbox: Option<Bbox>) -> &mut Self {
+        self.glyphs.push((advance, bbox));
+        self
+    }
+
+    /// Build the table, updating the metrics in `hhea` to match.
+    ///
+    /// This sets `number_of_long_metrics`, `advance_width_max`,
+    /// `min_left_side_bearing`, `min_right_side_bearing` and `x_max_extent`.
+    pub fn build(self, hhea: &mut Hhea) -> Hmtx {
+        let num_long_metrics = match self.glyphs.last() {
+            Some((last_advance, _)) => {
+                let run = self
+                    .glyphs
+                    .iter()
+                    .rev()
+                    .take_while(|(advance, _)| advance == last_advance)
+                    .count();
+                self.glyphs.len() - run + 1
+            }
+            None => 0,
+        };
+
+        let mut advance_width_max = 0u16;
+        let mut extents: Option<(i16, i16, i16)> = None;
+        for (advance, bbox) in &self.glyphs {
+            advance_width_max = advance_width_max.max(*advance);
+            let Some(bbox) = bbox else {
+                continue;
+            };
+            let lsb = bbox.x_min as i32;
+            let extent = bbox.x_max as i32;
+            let rsb = *advance as i32 - extent;
+            let clamp = |value: i32| value.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+            let (lsb, rsb, extent) = (clamp(lsb), clamp(rsb), clamp(extent));
+            extents = Some(match extents {
+                Some((min_lsb, min_rsb, max_extent)) => {
+                    (min_lsb.min(lsb), min_rsb.min(rsb), max_extent.max(extent))
+                }
+                None => (lsb, rsb, extent),
+            });
+        }
+        let (min_lsb, min_rsb, max_extent) = extents.unwrap_or_default();
+        hhea.number_of_long_metrics = num_long_metrics as u16;
+        hhea.advance_width_max = UfWord::new(advance_width_max);
+        hhea.min_left_side_bearing = FWord::new(min_lsb);
+        hhea.min_right_side_bearing = FWord::new(min_rsb);
+        hhea.x_max_extent = FWord::new(max_extent);
+
+        let side_bearing = |bbox: &Option<Bbox>| bbox.map(|bbox| bbox.x_min).unwrap_or(0);
+        let h_metrics = self.glyphs[..num_long_metrics]
+            .iter()
+            .map(|(advance, bbox)| LongMetric::new(*advance, side_bearing(bbox)))
+            .collect();
+        let left_side_bearings = self.glyphs[num_long_metrics..]
+            .iter()
+            .map(|(_, bbox)| side_bearing(bbox))
+            .collect();
+        Hmtx::new(h_metrics, left_side_bearings)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -24,4 +109,43 @@ mod tests {
         assert_eq!(loaded.h_metrics()[0].side_bearing(), -214);
         assert_eq!(loaded.left_side_bearings(), &hmtx.left_side_bearings);
     }
+
+    #[test]
+    fn builder() {
+        let bbox = |x_min, x_max| {
+            Some(Bbox {
+                x_min,
+                y_min: 0,
+                x_max,
+                y_max: 100,
+            })
+        };
+        let mut builder = HmtxBuilder::new();
+        builder
+            .add_glyph(500, None)
+            .add_glyph(600, bbox(-10, 550))
+            .add_glyph(400, bbox(20, 420))
+            .add_glyph(400, None)
+            .add_glyph(400, bbox(5, 300));
+        let mut hhea = Hhea::default();
+        let hmtx = builder.build(&mut hhea);
+
+        assert_eq!(hhea.number_of_long_metrics, 3);
+        assert_eq!(hhea.advance_width_max, UfWord::new(600));
+        assert_eq!(hhea.min_left_side_bearing, FWord::new(-10));
+        assert_eq!(hhea.min_right_side_bearing, FWord::new(-20));
+        assert_eq!(hhea.x_max_extent, FWord::new(550));
+        let h_metrics = hmtx
+            .h_metrics
+            .iter()
+            .map(|metric| (metric.advance, metric.side_bearing))
+            .collect::<Vec<_>>();
+        assert_eq!(h_metrics, [(500, 0), (600, -10), (400, 20)]);
+        assert_eq!(hmtx.left_side_bearings, [0, 5]);
+
+        let mut hhea = Hhea::default();
+        let hmtx = HmtxBuilder::new().build(&mut hhea);
+        assert_eq!(hhea.number_of_long_metrics, 0);
+        assert!(hmtx.h_metrics.is_empty());
+    }
 }
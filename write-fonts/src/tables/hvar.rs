@@ -0,0 +1,141 @@
+//! The [HVAR (Horizontal Metrics Variations)](https://docs.microsoft.com/en-us/typography/opentype/spec/hvar) table
+
+include!("../../generated/generated_hvar.rs");
+
+use super::variations::{
+    DeltaSetIndexMap, ItemVariationStore, VariationRegion, VariationStoreBuilder,
+};
+
+/// A builder for the `HVAR` table.
+///
+/// Glyphs are added in glyph id order, with the deltas of their advance
+/// widths. When the table is built, the advance widths are either looked up
+/// directly by glyph id, or through an index map into a store where glyphs
+/// with the same deltas share a row, whichever is smaller.
+#[derive(Clone, Debug, Default)]
+pub struct HvarBuilder {
+    advance_deltas: Vec<Vec<(VariationRegion, i32)>>,
+}
+
+impl HvarBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a glyph; its id is the number of glyphs previously added.
+    ///
+    /// Each delta is the change of the glyph's advance width in the region it
+    /// is paired with. Glyphs whose advance does not vary have no deltas.
+    pub fn add_glyph(&mut self, advance_deltas: Vec<(VariationRegion, i32)>) -> &mut Self {
+        self.advance_deltas.push(advance_deltas);
+        self
+    }
+
+    /// Build the table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than 65535 glyphs have been added.
+    pub fn build(self) -> Hvar {
+        assert!(
+            self.advance_deltas.len() <= u16::MAX as usize,
+            "too many glyphs"
+        );
+        let mut store = VariationStoreBuilder::new();
+        let ids = self
+            .advance_deltas
+            .into_iter()
+            .map(|deltas| store.add_deltas(deltas))
+            .collect::<Vec<_>>();
+
+        let direct = store.clone().build_direct(&ids);
+        let (indirect, remapping) = store.build();
+        let entries = ids
+            .iter()
+            .map(|id| remapping.get_raw(*id).unwrap())
+            .collect::<Vec<_>>();
+        let advance_map = DeltaSetIndexMap::from_entries(&entries);
+
+        let size = |store: &ItemVariationStore| crate::dump_table(store).unwrap().len();
+        let indirect_size = size(&indirect) + crate::dump_table(&advance_map).unwrap().len();
+        if size(&direct) <= indirect_size {
+            Hvar::new(direct, None, None, None)
+        } else {
+            Hvar::new(indirect, Some(advance_map), None, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::FontRead;
+    use types::{F2Dot14, Fixed, GlyphId};
+
+    use super::*;
+    use crate::tables::variations::RegionAxisCoordinates;
+
+    fn wght_max() -> VariationRegion {
+        VariationRegion::new(vec![RegionAxisCoordinates::new(
+            F2Dot14::ZERO,
+            F2Dot14::from_f32(1.0),
+            F2Dot14::from_f32(1.0),
+        )])
+    }
+
+    fn check_deltas(hvar: &Hvar, expected: &[i32]) {
+        let bytes = crate::dump_table(hvar).unwrap();
+        let hvar = read_fonts::tables::hvar::Hvar::read(FontData::new(&bytes)).unwrap();
+        let coords = [F2Dot14::from_f32(1.0)];
+        for (gid, expected) in expected.iter().enumerate() {
+            let delta = hvar
+                .advance_width_delta(GlyphId::new(gid as u16), &coords)
+                .unwrap();
+            assert_eq!(delta, Fixed::from_i32(*expected), "glyph {gid}");
+        }
+    }
+
+    #[test]
+    fn direct() {
+        let expected = [0, 10, 20, 30];
+        let mut builder = HvarBuilder::new();
+        for delta in expected {
+            builder.add_glyph(vec![(wght_max(), delta)]);
+        }
+        let hvar = builder.build();
+        assert!(hvar.advance_width_mapping.is_none());
+        check_deltas(&hvar, &expected);
+    }
+
+    #[test]
+    fn mapped() {
+        // the glyphs share a few wide rows, so an index map is smaller than a
+        // row per glyph
+        let regions = [-1.0, 0.5, 1.0].map(|peak| {
+            let (start, end) = if peak < 0.0 { (-1.0, 0.0) } else { (0.0, 1.0) };
+            VariationRegion::new(vec![RegionAxisCoordinates::new(
+                F2Dot14::from_f32(start),
+                F2Dot14::from_f32(peak),
+                F2Dot14::from_f32(end),
+            )])
+        });
+        let expected = (0..100).map(|gid| [300, -400][gid % 2]).collect::<Vec<_>>();
+        let mut builder = HvarBuilder::new();
+        for delta in &expected {
+            let deltas = regions.iter().map(|region| (region.clone(), *delta));
+            builder.add_glyph(deltas.collect());
+        }
+        let hvar = builder.build();
+        let map = hvar.advance_width_mapping.as_ref().unwrap();
+        assert!(matches!(map, DeltaSetIndexMap::Format0(map) if map.map_count == 100));
+        assert_eq!(
+            hvar.item_variation_store.item_variation_datas[0]
+                .as_ref()
+                .unwrap()
+                .item_count,
+            2
+        );
+        // only the region peaking at the maximum applies there
+        check_deltas(&hvar, &expected);
+    }
+}
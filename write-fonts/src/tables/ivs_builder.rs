@@ -0,0 +1,528 @@
+//! Building the ItemVariationStore
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use super::{ItemVariationData, ItemVariationStore, VariationRegion, VariationRegionList};
+use crate::tables::layout::VariationIndex;
+
+/// The maximum number of rows in an ItemVariationData subtable.
+const MAX_ROWS: usize = u16::MAX as usize;
+/// The flag in `word_delta_count` for 32-bit word deltas.
+const LONG_WORDS: u16 = 0x8000;
+/// The delta format of a [VariationIndex] table.
+const VARIATION_INDEX_FORMAT: u16 = 0x8000;
+
+/// A builder for an [ItemVariationStore].
+///
+/// Each set of deltas that is added is given a temporary id. Identical sets of
+/// deltas share a single row of the store, and rows that need the same
+/// regions and the same sizes of deltas are grouped into ItemVariationData
+/// subtables, which are merged while that makes the store smaller. Once the
+/// store is built, the temporary ids are mapped to their final
+/// (outer, inner) indices.
+///
+/// The same builder is shared by all the tables that contain an
+/// ItemVariationStore.
+#[derive(Clone, Debug, Default)]
+pub struct VariationStoreBuilder {
+    // regions, and their index in the region list
+    regions: Vec<VariationRegion>,
+    region_indices: HashMap<RegionKey, u16>,
+    // the unique delta sets, in the order they were added
+    delta_sets: Vec<DeltaSet>,
+    delta_set_ids: HashMap<DeltaSet, TemporaryDeltaSetId>,
+}
+
+/// The id of a set of deltas added to a [VariationStoreBuilder].
+///
+/// This is mapped to a final (outer, inner) index when the store is built;
+/// see [VariationIndexRemapping].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TemporaryDeltaSetId(u32);
+
+/// The final indices of the delta sets added to a [VariationStoreBuilder].
+#[derive(Clone, Debug, Default)]
+pub struct VariationIndexRemapping {
+    map: HashMap<TemporaryDeltaSetId, (u16, u16)>,
+}
+
+/// The (start, peak, end) bits of each axis of a region.
+type RegionKey = Vec<[i16; 3]>;
+
+/// The non-zero deltas of a row, sorted by region index.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+struct DeltaSet(Vec<(u16, i32)>);
+
+/// The regions of a group of rows, and the number of bytes needed to encode
+/// each region's deltas, sorted by region index.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Encoding(Vec<(u16, u8)>);
+
+/// A group of rows that will be written as one ItemVariationData.
+#[derive(Clone, Debug)]
+struct RowGroup {
+    encoding: Encoding,
+    rows: Vec<TemporaryDeltaSetId>,
+}
+
+impl VariationStoreBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a set of deltas, returning its temporary id.
+    ///
+    /// Each delta applies in the region it is paired with; deltas for the same
+    /// region are added together. Adding the same deltas more than once
+    /// returns the same id.
+    pub fn add_deltas<T: Into<i32>>(
+        &mut self,
+        deltas: Vec<(VariationRegion, T)>,
+    ) -> TemporaryDeltaSetId {
+        let mut by_region = HashMap::<u16, i32>::new();
+        for (region, delta) in deltas {
+            let delta = delta.into();
+            if delta == 0 {
+                continue;
+            }
+            let index = self.intern_region(region);
+            *by_region.entry(index).or_default() += delta;
+        }
+        let mut delta_set = by_region
+            .into_iter()
+            .filter(|(_, delta)| *delta != 0)
+            .collect::<Vec<_>>();
+        delta_set.sort_unstable();
+        let delta_set = DeltaSet(delta_set);
+
+        if let Some(id) = self.delta_set_ids.get(&delta_set) {
+            return *id;
+        }
+        let id = TemporaryDeltaSetId(self.delta_sets.len() as u32);
+        self.delta_sets.push(delta_set.clone());
+        self.delta_set_ids.insert(delta_set, id);
+        id
+    }
+
+    /// The number of distinct delta sets that have been added.
+    pub fn len(&self) -> usize {
+        self.delta_sets.len()
+    }
+
+    /// Returns `true` if no deltas have been added.
+    pub fn is_empty(&self) -> bool {
+        self.delta_sets.is_empty()
+    }
+
+    fn intern_region(&mut self, region: VariationRegion) -> u16 {
+        let key = region
+            .region_axes
+            .iter()
+            .map(|axis| {
+                [
+                    axis.start_coord.to_bits(),
+                    axis.peak_coord.to_bits(),
+                    axis.end_coord.to_bits(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let next_index = self.regions.len() as u16;
+        *self.region_indices.entry(key).or_insert_with(|| {
+            self.regions.push(region);
+            next_index
+        })
+    }
+
+    /// Build the store, and the map from temporary ids to final indices.
+    pub fn build(self) -> (ItemVariationStore, VariationIndexRemapping) {
+        let groups = self.group_rows();
+        let mut remapping = VariationIndexRemapping::default();
+        let mut subtables = Vec::with_capacity(groups.len());
+        for group in groups {
+            for chunk in group.rows.chunks(MAX_ROWS) {
+                let outer = subtables.len() as u16;
+                for (inner, id) in chunk.iter().enumerate() {
+                    remapping.map.insert(*id, (outer, inner as u16));
+                }
+                let rows = chunk.iter().map(|id| &self.delta_sets[id.0 as usize]);
+                subtables.push(Some(group.encoding.encode(rows)));
+            }
+        }
+        let store = ItemVariationStore::new(1, VariationRegionList::new(self.regions), subtables);
+        (store, remapping)
+    }
+
+    /// Build a store with a single subtable, with a row for each id in order.
+    ///
+    /// This is for tables such as HVAR, where items can be looked up in the
+    /// store directly, without an index map. Rows are not shared, and are
+    /// all encoded with the same regions and delta sizes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more rows than fit in a subtable.
+    pub(crate) fn build_direct(self, rows: &[TemporaryDeltaSetId]) -> ItemVariationStore {
+        assert!(rows.len() <= MAX_ROWS, "too many rows for one subtable");
+        let encoding = self
+            .delta_sets
+            .iter()
+            .map(Encoding::for_delta_set)
+            .fold(Encoding::default(), |acc, encoding| acc.merge(&encoding));
+        let rows = rows.iter().map(|id| &self.delta_sets[id.0 as usize]);
+        let subtable = encoding.encode(rows);
+        ItemVariationStore::new(
+            1,
+            VariationRegionList::new(self.regions),
+            vec![Some(subtable)],
+        )
+    }
+
+    /// Group rows by encoding, and then merge groups while that saves space.
+    fn group_rows(&self) -> Vec<RowGroup> {
+        let mut groups = Vec::<RowGroup>::new();
+        let mut by_encoding = HashMap::<Vec<(u16, u8)>, usize>::new();
+        for (i, delta_set) in self.delta_sets.iter().enumerate() {
+            let encoding = Encoding::for_delta_set(delta_set);
+            let id = TemporaryDeltaSetId(i as u32);
+            match by_encoding.get(&encoding.0) {
+                Some(group) => groups[*group].rows.push(id),
+                None => {
+                    by_encoding.insert(encoding.0.clone(), groups.len());
+                    groups.push(RowGroup {
+                        encoding,
+                        rows: vec![id],
+                    });
+                }
+            }
+        }
+
+        // merge the pair of groups that saves the most space, until no merge
+        // saves any. Merged groups go at the end; gains for pairs that
+        // include a group that has since been merged are skipped.
+        let mut groups = groups.into_iter().map(Some).collect::<Vec<_>>();
+        let mut gains = BinaryHeap::new();
+        for i in 0..groups.len() {
+            for j in i + 1..groups.len() {
+                let gain = merge_gain(groups[i].as_ref().unwrap(), groups[j].as_ref().unwrap());
+                if gain > 0 {
+                    gains.push((gain, Reverse(i), Reverse(j)));
+                }
+            }
+        }
+        while let Some((_, Reverse(i), Reverse(j))) = gains.pop() {
+            if groups[i].is_none() || groups[j].is_none() {
+                continue;
+            }
+            let (a, b) = (groups[i].take().unwrap(), groups[j].take().unwrap());
+            let mut rows = a.rows;
+            rows.extend(b.rows);
+            rows.sort_unstable();
+            let merged = RowGroup {
+                encoding: a.encoding.merge(&b.encoding),
+                rows,
+            };
+            let k = groups.len();
+            for (other_i, other) in groups.iter().enumerate() {
+                if let Some(other) = other {
+                    let gain = merge_gain(other, &merged);
+                    if gain > 0 {
+                        gains.push((gain, Reverse(other_i), Reverse(k)));
+                    }
+                }
+            }
+            groups.push(Some(merged));
+        }
+
+        let mut groups = groups.into_iter().flatten().collect::<Vec<_>>();
+        groups.sort_unstable_by_key(|group| group.rows[0]);
+        groups
+    }
+}
+
+/// The number of bytes saved by storing the rows of both groups in a single
+/// subtable.
+fn merge_gain(a: &RowGroup, b: &RowGroup) -> i64 {
+    let merged = a.encoding.merge(&b.encoding);
+    a.encoding.cost(a.rows.len()) + b.encoding.cost(b.rows.len())
+        - merged.cost(a.rows.len() + b.rows.len())
+}
+
+impl Encoding {
+    fn for_delta_set(delta_set: &DeltaSet) -> Self {
+        Encoding(
+            delta_set
+                .0
+                .iter()
+                .map(|(region, delta)| {
+                    let width = match *delta {
+                        -128..=127 => 1,
+                        -32768..=32767 => 2,
+                        _ => 4,
+                    };
+                    (*region, width)
+                })
+                .collect(),
+        )
+    }
+
+    /// The union of the regions of both encodings, with the larger width of
+    /// each region.
+    fn merge(&self, other: &Encoding) -> Encoding {
+        let mut columns = self.0.clone();
+        for (region, width) in &other.0 {
+            match columns.binary_search_by_key(region, |(region, _)| *region) {
+                Ok(i) => columns[i].1 = columns[i].1.max(*width),
+                Err(i) => columns.insert(i, (*region, *width)),
+            }
+        }
+        Encoding(columns)
+    }
+
+    fn long_words(&self) -> bool {
+        self.0.iter().any(|(_, width)| *width == 4)
+    }
+
+    /// Whether the column is stored as a word, rather than a short delta.
+    fn is_word(&self, width: u8, long_words: bool) -> bool {
+        if long_words {
+            width == 4
+        } else {
+            width == 2
+        }
+    }
+
+    fn row_size(&self) -> usize {
+        let long_words = self.long_words();
+        let (word_size, short_size) = if long_words { (4, 2) } else { (2, 1) };
+        self.0
+            .iter()
+            .map(|(_, width)| match self.is_word(*width, long_words) {
+                true => word_size,
+                false => short_size,
+            })
+            .sum()
+    }
+
+    /// The size of a subtable with this encoding, and its offset.
+    fn cost(&self, row_count: usize) -> i64 {
+        // offset, item count, word count, region count, region indices
+        let overhead = 4 + 6 + 2 * self.0.len();
+        (overhead + row_count * self.row_size()) as i64
+    }
+
+    fn encode<'a>(&self, rows: impl Iterator<Item = &'a DeltaSet>) -> ItemVariationData {
+        let long_words = self.long_words();
+        // word columns come first
+        let mut columns = self.0.clone();
+        columns.sort_by_key(|(region, width)| (!self.is_word(*width, long_words), *region));
+        let word_count = columns
+            .iter()
+            .filter(|(_, width)| self.is_word(*width, long_words))
+            .count() as u16;
+
+        let mut item_count = 0u16;
+        let mut delta_sets = Vec::new();
+        for row in rows {
+            item_count += 1;
+            for (region, width) in &columns {
+                let delta = row
+                    .0
+                    .binary_search_by_key(region, |(region, _)| *region)
+                    .map(|i| row.0[i].1)
+                    .unwrap_or_default();
+                match (self.is_word(*width, long_words), long_words) {
+                    (true, true) => delta_sets.extend(delta.to_be_bytes()),
+                    (false, false) => delta_sets.extend((delta as i8).to_be_bytes()),
+                    _ => delta_sets.extend((delta as i16).to_be_bytes()),
+                }
+            }
+        }
+        ItemVariationData::new(
+            item_count,
+            word_count | if long_words { LONG_WORDS } else { 0 },
+            columns.iter().map(|(region, _)| *region).collect(),
+            delta_sets,
+        )
+    }
+}
+
+impl VariationIndexRemapping {
+    /// The (outer, inner) index of a delta set in the built store.
+    pub fn get_raw(&self, id: TemporaryDeltaSetId) -> Option<(u16, u16)> {
+        self.map.get(&id).copied()
+    }
+
+    /// A [VariationIndex] table referencing a delta set in the built store.
+    pub fn get(&self, id: TemporaryDeltaSetId) -> Option<VariationIndex> {
+        self.get_raw(id)
+            .map(|(outer, inner)| VariationIndex::new(outer, inner, VARIATION_INDEX_FORMAT))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::{
+        tables::variations::{DeltaSetIndex, ItemVariationStore as ReadItemVariationStore},
+        FontData, FontRead,
+    };
+
+    use types::F2Dot14;
+
+    use super::*;
+    use crate::tables::variations::{RegionAxisCoordinates, VariationModel};
+
+    // a region that peaks at a location, and extends to the default location
+    fn region(peak: &[f32]) -> VariationRegion {
+        VariationRegion::new(
+            peak.iter()
+                .map(|peak| {
+                    let (start, end) = match *peak {
+                        peak if peak > 0.0 => (0.0, 1.0),
+                        peak if peak < 0.0 => (-1.0, 0.0),
+                        _ => (0.0, 0.0),
+                    };
+                    RegionAxisCoordinates::new(
+                        F2Dot14::from_f32(start),
+                        F2Dot14::from_f32(*peak),
+                        F2Dot14::from_f32(end),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn dedupe() {
+        let mut builder = VariationStoreBuilder::new();
+        let a = builder.add_deltas(vec![(region(&[1.0]), 10), (region(&[-1.0]), -5)]);
+        let b = builder.add_deltas(vec![(region(&[-1.0]), -5), (region(&[1.0]), 10)]);
+        // deltas for the same region are summed
+        let c = builder.add_deltas(vec![
+            (region(&[1.0]), 4),
+            (region(&[-1.0]), -5),
+            (region(&[1.0]), 6),
+        ]);
+        let d = builder.add_deltas(vec![(region(&[1.0]), 10)]);
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        assert_ne!(a, d);
+        assert_eq!(builder.len(), 2);
+
+        let (store, remap) = builder.build();
+        assert_eq!(store.variation_region_list.variation_regions.len(), 2);
+        assert_eq!(store.item_variation_datas.len(), 1);
+        assert_eq!(remap.get_raw(a), Some((0, 0)));
+        assert_eq!(remap.get_raw(d), Some((0, 1)));
+        let index = remap.get(d).unwrap();
+        assert_eq!(index.delta_set_inner_index, 1);
+        assert_eq!(index.delta_format, 0x8000);
+    }
+
+    #[test]
+    fn encodings() {
+        let mut builder = VariationStoreBuilder::new();
+        let wght = region(&[1.0, 0.0]);
+        let wdth = region(&[0.0, 1.0]);
+        let small = builder.add_deltas(vec![(wght.clone(), 5), (wdth.clone(), -7)]);
+        let word = builder.add_deltas(vec![(wght.clone(), 300), (wdth.clone(), 1)]);
+        let long = builder.add_deltas(vec![(wght.clone(), 1), (wdth.clone(), 100_000)]);
+        let zero = builder.add_deltas(vec![(wght, 0)]);
+
+        let (store, remap) = builder.build();
+        let bytes = crate::dump_table(&store).unwrap();
+        let read = ReadItemVariationStore::read(FontData::new(&bytes)).unwrap();
+        let deltas = |id| {
+            let (outer, inner) = remap.get_raw(id).unwrap();
+            let data = read.item_variation_datas().nth(outer as usize).unwrap();
+            let data = data.unwrap().unwrap();
+            let regions = data
+                .region_indexes()
+                .iter()
+                .map(|index| index.get())
+                .collect::<Vec<_>>();
+            let mut deltas = data
+                .delta_set(inner)
+                .zip(regions)
+                .map(|(delta, region)| (region, delta.to_i32()))
+                .collect::<Vec<_>>();
+            deltas.sort_unstable();
+            deltas
+        };
+        assert_eq!(deltas(small), [(0, 5), (1, -7)]);
+        assert_eq!(deltas(word), [(0, 300), (1, 1)]);
+        assert_eq!(deltas(zero), [(0, 0), (1, 0)]);
+
+        // with so few rows, they all share one subtable with 32-bit words
+        assert_eq!(store.item_variation_datas.len(), 1);
+        let data = store.item_variation_datas[0].as_ref().unwrap();
+        assert_eq!(data.word_delta_count, 0x8001);
+        assert_eq!(data.region_indexes, [1, 0]);
+        // read-fonts reads deltas as Fixed, which can't represent this one
+        let (_, inner) = remap.get_raw(long).unwrap();
+        let row = &data.delta_sets[inner as usize * 6..][..6];
+        assert_eq!(row, [0, 1, 0x86, 0xa0, 0, 1]);
+    }
+
+    #[test]
+    fn merge_groups() {
+        // a few rows that only vary on one axis, and one that varies on both:
+        // it is cheaper to store them all in one subtable.
+        let mut builder = VariationStoreBuilder::new();
+        let wght = region(&[1.0, 0.0]);
+        let wdth = region(&[0.0, 1.0]);
+        for delta in 1..4 {
+            builder.add_deltas(vec![(wght.clone(), delta)]);
+        }
+        builder.add_deltas(vec![(wght, 1), (wdth, 1)]);
+        let (store, _) = builder.build();
+        assert_eq!(store.item_variation_datas.len(), 1);
+        let data = store.item_variation_datas[0].as_ref().unwrap();
+        assert_eq!(data.item_count, 4);
+        assert_eq!(data.word_delta_count, 0);
+    }
+
+    #[test]
+    fn round_trip_model() {
+        let loc = |coords: &[f32]| {
+            coords
+                .iter()
+                .map(|c| F2Dot14::from_f32(*c))
+                .collect::<Vec<_>>()
+        };
+        let locations = vec![
+            loc(&[0.0, 0.0]),
+            loc(&[1.0, 0.0]),
+            loc(&[0.0, 1.0]),
+            loc(&[1.0, 1.0]),
+            loc(&[0.5, 0.0]),
+        ];
+        let model = VariationModel::new(locations.clone()).unwrap();
+        let values = [[100, 180, 120, 300, 130], [-20, 40, -20, 40, 0]];
+
+        let mut builder = VariationStoreBuilder::new();
+        let ids = values
+            .iter()
+            .map(|values| {
+                let deltas = model.deltas(values);
+                builder.add_deltas(model.regions().into_iter().zip(deltas).collect())
+            })
+            .collect::<Vec<_>>();
+        let (store, remap) = builder.build();
+        let bytes = crate::dump_table(&store).unwrap();
+        let read = ReadItemVariationStore::read(FontData::new(&bytes)).unwrap();
+
+        for (values, id) in values.iter().zip(ids) {
+            let (outer, inner) = remap.get_raw(id).unwrap();
+            for (location, value) in locations.iter().zip(values) {
+                let delta = read
+                    .compute_delta(DeltaSetIndex { outer, inner }, location)
+                    .unwrap();
+                assert_eq!(values[0] + delta.to_i32(), *value, "at {location:?}");
+            }
+        }
+    }
+}
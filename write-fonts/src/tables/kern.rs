@@ -0,0 +1,239 @@
+//! The [kern (Kerning)](https://learn.microsoft.com/en-us/typography/opentype/spec/kern) table
+
+use std::collections::BTreeMap;
+
+use read_fonts::TopLevelTable;
+use types::{FWord, GlyphId, Tag};
+
+use crate::{
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+/// The size of a format 0 subtable with no pairs.
+const SUBTABLE_HEADER_LEN: usize = 14;
+/// The size of a single kerning pair.
+const PAIR_LEN: usize = 6;
+
+/// The [kern] table.
+///
+/// Only the OpenType version of the table, containing format 0 subtables, is
+/// supported; this is the version understood by Windows and by most other
+/// consumers. New fonts should put their kerning in `GPOS`, but a `kern`
+/// table is still useful for older applications.
+///
+/// [kern]: https://learn.microsoft.com/en-us/typography/opentype/spec/kern
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Kern {
+    /// The subtables, in the order they are applied.
+    pub subtables: Vec<Kern0>,
+}
+
+/// A format 0 `kern` subtable: a sorted list of kerning pairs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Kern0 {
+    /// The subtable contains horizontal kerning, rather than vertical.
+    pub horizontal: bool,
+    /// The values are minimum values, rather than kerning values.
+    pub minimum: bool,
+    /// The values adjust glyphs perpendicular to the flow of text.
+    pub cross_stream: bool,
+    /// The values replace the values accumulated so far, rather than
+    /// being added to them.
+    pub overrides: bool,
+    /// The kerning value of each pair of glyphs, keyed by the left and
+    /// right glyph.
+    pub pairs: BTreeMap<(GlyphId, GlyphId), FWord>,
+}
+
+/// A builder for a `kern` table with a single horizontal subtable.
+#[derive(Clone, Debug, Default)]
+pub struct KernBuilder {
+    pairs: BTreeMap<(GlyphId, GlyphId), FWord>,
+}
+
+impl Kern {
+    /// Create a new table from its subtables.
+    pub fn new(subtables: Vec<Kern0>) -> Self {
+        Kern { subtables }
+    }
+}
+
+impl Kern0 {
+    /// Create a new horizontal subtable from a map of kerning pairs.
+    pub fn new(pairs: BTreeMap<(GlyphId, GlyphId), FWord>) -> Self {
+        Kern0 {
+            horizontal: true,
+            pairs,
+            ..Default::default()
+        }
+    }
+
+    fn coverage(&self) -> u16 {
+        // the format is in the high byte, and is always zero here
+        [
+            self.horizontal,
+            self.minimum,
+            self.cross_stream,
+            self.overrides,
+        ]
+        .into_iter()
+        .enumerate()
+        .filter(|(_, set)| *set)
+        .fold(0, |coverage, (bit, _)| coverage | 1 << bit)
+    }
+
+    fn len(&self) -> usize {
+        SUBTABLE_HEADER_LEN + self.pairs.len() * PAIR_LEN
+    }
+}
+
+impl KernBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the kerning value for a pair of glyphs, replacing any existing
+    /// value.
+    pub fn insert(&mut self, left: GlyphId, right: GlyphId, value: impl Into<FWord>) {
+        self.pairs.insert((left, right), value.into());
+    }
+
+    /// Build the table.
+    pub fn build(self) -> Kern {
+        Kern::new(vec![Kern0::new(self.pairs)])
+    }
+}
+
+impl TopLevelTable for Kern {
+    const TAG: Tag = Tag::KERN;
+}
+
+impl FontWrite for Kern {
+    fn write_into(&self, writer: &mut TableWriter) {
+        0u16.write_into(writer);
+        (self.subtables.len() as u16).write_into(writer);
+        self.subtables.write_into(writer);
+    }
+}
+
+impl FontWrite for Kern0 {
+    fn write_into(&self, writer: &mut TableWriter) {
+        let num_pairs = self.pairs.len();
+        let (search_range, entry_selector, range_shift) = match num_pairs {
+            0 => (0, 0, 0),
+            _ => {
+                let entry_selector = num_pairs.ilog2();
+                let search_range = (1usize << entry_selector) * PAIR_LEN;
+                (
+                    search_range,
+                    entry_selector as usize,
+                    num_pairs * PAIR_LEN - search_range,
+                )
+            }
+        };
+        0u16.write_into(writer);
+        // a long final subtable has its length truncated; consumers compute
+        // its real length from the number of pairs, as fontTools does
+        (self.len() as u16).write_into(writer);
+        self.coverage().write_into(writer);
+        for value in [num_pairs, search_range, entry_selector, range_shift] {
+            (value as u16).write_into(writer);
+        }
+        for ((left, right), value) in &self.pairs {
+            left.write_into(writer);
+            right.write_into(writer);
+            value.write_into(writer);
+        }
+    }
+}
+
+impl Validate for Kern {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        ctx.in_table("Kern", |ctx| {
+            ctx.in_field("subtables", |ctx| {
+                if self.subtables.len() > u16::MAX as usize {
+                    ctx.report("too many subtables");
+                }
+                let last = self.subtables.len().saturating_sub(1);
+                ctx.in_array(|ctx| {
+                    for (i, subtable) in self.subtables.iter().enumerate() {
+                        ctx.array_item(|ctx| {
+                            if subtable.pairs.len() > u16::MAX as usize {
+                                ctx.report("too many kerning pairs");
+                            } else if i != last && subtable.len() > u16::MAX as usize {
+                                ctx.report("only the last subtable can be longer than 65535 bytes");
+                            }
+                        })
+                    }
+                })
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    fn read_u16s(data: &[u8]) -> Vec<u16> {
+        data.chunks(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect()
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = KernBuilder::new();
+        builder.insert(gid(5), gid(2), -20);
+        builder.insert(gid(1), gid(9), 30);
+        builder.insert(gid(1), gid(3), -40);
+        let dumped = crate::dump_table(&builder.build()).unwrap();
+        #[rustfmt::skip]
+        let expected: &[u16] = &[
+            0, 1, // version, nTables
+            0, 14 + 3 * 6, 0x0001, // version, length, coverage
+            3, 12, 1, 6, // nPairs, searchRange, entrySelector, rangeShift
+            1, 3, -40i16 as u16,
+            1, 9, 30,
+            5, 2, -20i16 as u16,
+        ];
+        assert_eq!(read_u16s(&dumped), expected);
+    }
+
+    #[test]
+    fn coverage() {
+        let subtable = Kern0 {
+            horizontal: true,
+            cross_stream: true,
+            ..Default::default()
+        };
+        assert_eq!(subtable.coverage(), 0b101);
+        let subtable = Kern0 {
+            minimum: true,
+            overrides: true,
+            ..Default::default()
+        };
+        assert_eq!(subtable.coverage(), 0b1010);
+    }
+
+    #[test]
+    fn long_subtables() {
+        let pairs = (0..11_000u16)
+            .map(|i| ((gid(i), gid(1)), FWord::new(-1)))
+            .collect::<BTreeMap<_, _>>();
+        let kern = Kern::new(vec![Kern0::new(pairs.clone())]);
+        let dumped = crate::dump_table(&kern).unwrap();
+        assert_eq!(dumped.len(), 4 + 14 + 11_000 * 6);
+
+        let kern = Kern::new(vec![Kern0::new(pairs.clone()), Kern0::new(pairs)]);
+        let report = kern.validate().unwrap_err();
+        let error = report.errors().next().unwrap();
+        assert_eq!(error.path(), "Kern.subtables[0]");
+    }
+}
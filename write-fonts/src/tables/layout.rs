@@ -1,7 +1,7 @@
 //! OpenType layout.
 
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     hash::Hash,
 };
 
@@ -31,6 +31,7 @@ macro_rules! lookup_type {
 macro_rules! table_newtype {
     ($name:ident, $inner:ident, $read_type:path) => {
         #[derive(Clone, Debug, Default)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
         pub struct $name($inner);
 
         impl std::ops::Deref for $name {
@@ -91,7 +92,9 @@ impl<T: LookupType + FontWrite> FontWrite for Lookup<T> {
             .unwrap()
             .write_into(writer);
         self.subtables.write_into(writer);
-        self.mark_filtering_set.write_into(writer);
+        if self.lookup_flag.use_mark_filtering_set() {
+            self.mark_filtering_set.write_into(writer);
+        }
     }
 }
 
@@ -145,6 +148,7 @@ pub trait LookupType {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum FeatureParams {
     StylisticSet(StylisticSetParams),
     Size(SizeParams),
@@ -199,6 +203,26 @@ impl ClassDefFormat1 {
     }
 }
 
+impl FeatureRecord {
+    fn validate_feature_params(&self, ctx: &mut ValidationCtx) {
+        // the type of the params table is determined by the feature tag
+        let tag = self.feature_tag.to_raw();
+        let expected = match self.feature.feature_params.as_ref() {
+            None => true,
+            Some(FeatureParams::Size(_)) => &tag == b"size",
+            Some(FeatureParams::StylisticSet(_)) => tag.starts_with(b"ss"),
+            Some(FeatureParams::CharacterVariant(_)) => tag.starts_with(b"cv"),
+        };
+        if !expected {
+            ctx.report(format!(
+                "feature params not valid for feature '{}'",
+                self.feature_tag
+            ));
+        }
+        self.feature.validate_impl(ctx);
+    }
+}
+
 impl ClassRangeRecord {
     fn validate_glyph_range(&self, ctx: &mut ValidationCtx) {
         if self.start_glyph_id > self.end_glyph_id {
@@ -211,6 +235,23 @@ impl ClassRangeRecord {
 }
 
 impl ClassDefFormat2 {
+    fn validate_class_range_records(&self, ctx: &mut ValidationCtx) {
+        if self.class_range_records.len() > (u16::MAX as usize) {
+            ctx.report("array exceeds max length");
+        }
+        if let Some(pos) = self
+            .class_range_records
+            .windows(2)
+            .position(|w| w[1].start_glyph_id <= w[0].end_glyph_id)
+        {
+            ctx.report(format!(
+                "class ranges unsorted or overlapping at index {}",
+                pos + 1
+            ));
+        }
+        self.class_range_records.validate_impl(ctx);
+    }
+
     fn iter(&self) -> impl Iterator<Item = (GlyphId, u16)> + '_ {
         self.class_range_records.iter().flat_map(|rcd| {
             (rcd.start_glyph_id.to_u16()..=rcd.end_glyph_id.to_u16())
@@ -245,7 +286,7 @@ impl ClassDef {
                 .and_then(|idx| table.class_value_array.get(idx as usize))
                 .copied(),
             ClassDef::Format2(table) => table.class_range_records.iter().find_map(|rec| {
-                (rec.start_glyph_id <= glyph && rec.end_glyph_id <= glyph).then_some(rec.class)
+                (rec.start_glyph_id <= glyph && glyph <= rec.end_glyph_id).then_some(rec.class)
             }),
         }
     }
@@ -263,6 +304,18 @@ impl ClassDef {
 }
 
 impl CoverageFormat1 {
+    fn validate_glyph_array(&self, ctx: &mut ValidationCtx) {
+        if self.glyph_array.len() > (u16::MAX as usize) {
+            ctx.report("array exceeds max length");
+        }
+        if let Some(pos) = self.glyph_array.windows(2).position(|w| w[0] >= w[1]) {
+            ctx.report(format!(
+                "coverage glyphs unsorted or duplicated at index {}",
+                pos + 1
+            ));
+        }
+    }
+
     fn iter(&self) -> impl Iterator<Item = GlyphId> + '_ {
         self.glyph_array.iter().copied()
     }
@@ -273,6 +326,38 @@ impl CoverageFormat1 {
 }
 
 impl CoverageFormat2 {
+    fn validate_range_records(&self, ctx: &mut ValidationCtx) {
+        if self.range_records.len() > (u16::MAX as usize) {
+            ctx.report("array exceeds max length");
+        }
+        let mut coverage_index = 0usize;
+        ctx.in_array(|ctx| {
+            let mut prev_end = None;
+            for record in &self.range_records {
+                ctx.array_item(|ctx| {
+                    if record.start_glyph_id > record.end_glyph_id {
+                        ctx.report(format!(
+                            "start_glyph_id {} larger than end_glyph_id {}",
+                            record.start_glyph_id, record.end_glyph_id
+                        ));
+                    }
+                    if prev_end.is_some_and(|end| record.start_glyph_id <= end) {
+                        ctx.report("coverage ranges unsorted or overlapping");
+                    }
+                    if record.start_coverage_index as usize != coverage_index {
+                        ctx.report(format!(
+                            "start_coverage_index is {}, expected {coverage_index}",
+                            record.start_coverage_index
+                        ));
+                    }
+                });
+                prev_end = Some(record.end_glyph_id);
+                coverage_index += (record.end_glyph_id.to_u16() as usize + 1)
+                    .saturating_sub(record.start_glyph_id.to_u16() as usize);
+            }
+        });
+    }
+
     fn iter(&self) -> impl Iterator<Item = GlyphId> + '_ {
         self.range_records
             .iter()
@@ -455,6 +540,90 @@ fn should_choose_coverage_format_2(glyphs: &[GlyphId]) -> bool {
     format2_len < format1_len
 }
 
+/// A builder for the [ScriptList] and [FeatureList] of a GSUB or GPOS table.
+///
+/// Features are added for a script and language system, along with the
+/// indices of their lookups. A feature with the same lookups in multiple
+/// language systems is only written once.
+#[derive(Clone, Debug, Default)]
+pub struct FeatureListBuilder {
+    // (script, language) -> feature -> lookup indices
+    lang_systems: BTreeMap<(Tag, Tag), BTreeMap<Tag, Vec<u16>>>,
+}
+
+impl FeatureListBuilder {
+    /// The tag of a script's default language system.
+    pub const DEFAULT_LANGUAGE: Tag = Tag::new(b"dflt");
+
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add lookups to a feature, for a script and language system.
+    ///
+    /// Lookups are always applied in the order of the lookup list, so the
+    /// order of the indices is not significant.
+    pub fn add_feature(&mut self, script: Tag, language: Tag, feature: Tag, lookups: &[u16]) {
+        let indices = self
+            .lang_systems
+            .entry((script, language))
+            .or_default()
+            .entry(feature)
+            .or_default();
+        indices.extend_from_slice(lookups);
+        indices.sort_unstable();
+        indices.dedup();
+    }
+
+    /// Build the script and feature lists.
+    pub fn build(&self) -> (ScriptList, FeatureList) {
+        let features = self
+            .lang_systems
+            .values()
+            .flat_map(|features| features.iter())
+            .collect::<BTreeSet<_>>();
+        let feature_indices = features
+            .iter()
+            .enumerate()
+            .map(|(i, feature)| (*feature, i as u16))
+            .collect::<HashMap<_, _>>();
+        let feature_list = FeatureList::new(
+            features
+                .iter()
+                .map(|(tag, lookups)| {
+                    FeatureRecord::new(**tag, Feature::new(None, lookups.to_vec()))
+                })
+                .collect(),
+        );
+
+        let mut scripts = BTreeMap::<Tag, (Option<LangSys>, Vec<LangSysRecord>)>::new();
+        for ((script, language), features) in &self.lang_systems {
+            let lang_sys = LangSys::new(
+                features
+                    .iter()
+                    .map(|feature| feature_indices[&feature])
+                    .collect(),
+            );
+            let script = scripts.entry(*script).or_default();
+            if *language == Self::DEFAULT_LANGUAGE {
+                script.0 = Some(lang_sys);
+            } else {
+                script.1.push(LangSysRecord::new(*language, lang_sys));
+            }
+        }
+        let script_list = ScriptList::new(
+            scripts
+                .into_iter()
+                .map(|(tag, (default, records))| {
+                    ScriptRecord::new(tag, Script::new(default, records))
+                })
+                .collect(),
+        );
+        (script_list, feature_list)
+    }
+}
+
 impl RangeRecord {
     /// An iterator over records for this array of glyphs.
     ///
@@ -530,6 +699,19 @@ impl Device {
     }
 }
 
+impl From<VariationIndex> for Device {
+    /// A `VariationIndex` table has the same layout as a `Device` table, so
+    /// it can be stored in the same offset fields.
+    fn from(src: VariationIndex) -> Self {
+        Device {
+            start_size: src.delta_set_outer_index,
+            end_size: src.delta_set_inner_index,
+            delta_format: DeltaFormat::VariationIndex,
+            delta_value: Vec::new(),
+        }
+    }
+}
+
 fn encode_delta(format: DeltaFormat, values: &[i8]) -> Vec<u16> {
     let (chunk_size, mask, bits) = match format {
         DeltaFormat::Local2BitDeltas => (8, 0b11, 2),
@@ -611,6 +793,63 @@ mod tests {
         classdef.validate().unwrap();
     }
 
+    #[test]
+    fn validate_feature_params() {
+        let feature = |params| Feature::new(Some(params), vec![0]);
+        let stylistic_set = || FeatureParams::StylisticSet(StylisticSetParams::default());
+        let record = FeatureRecord::new(Tag::new(b"ss01"), feature(stylistic_set()));
+        assert!(record.validate().is_ok());
+
+        let record = FeatureRecord::new(Tag::new(b"liga"), feature(stylistic_set()));
+        let report = record.validate().unwrap_err();
+        assert_eq!(
+            report.errors().next().unwrap().message(),
+            "feature params not valid for feature 'liga'"
+        );
+    }
+
+    #[test]
+    fn validate_coverage() {
+        let messages = |table: CoverageTable| match table.validate() {
+            Ok(()) => Vec::new(),
+            Err(report) => report.errors().map(|e| e.message().to_owned()).collect(),
+        };
+        let gids = |ids: &[u16]| ids.iter().copied().map(GlyphId::new).collect::<Vec<_>>();
+        assert!(messages(CoverageTable::format_1(gids(&[1, 2, 5]))).is_empty());
+        assert_eq!(
+            messages(CoverageTable::format_1(gids(&[1, 2, 2]))),
+            ["coverage glyphs unsorted or duplicated at index 2"]
+        );
+
+        let range =
+            |start, end, index| RangeRecord::new(GlyphId::new(start), GlyphId::new(end), index);
+        assert!(messages(CoverageTable::format_2(vec![
+            range(1, 4, 0),
+            range(6, 6, 4)
+        ]))
+        .is_empty());
+        assert_eq!(
+            messages(CoverageTable::format_2(vec![
+                range(1, 4, 0),
+                range(4, 6, 3)
+            ])),
+            [
+                "coverage ranges unsorted or overlapping",
+                "start_coverage_index is 3, expected 4"
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "class ranges unsorted or overlapping at index 1")]
+    fn validate_classdef_order() {
+        let classdef = ClassDefFormat2::new(vec![
+            ClassRangeRecord::new(GlyphId::new(5), GlyphId::new(8), 1),
+            ClassRangeRecord::new(GlyphId::new(2), GlyphId::new(3), 2),
+        ]);
+        classdef.validate().unwrap();
+    }
+
     #[test]
     fn classdef_format() {
         let builder: ClassDefBuilder = [(3u16, 4u16), (4, 6), (5, 1), (9, 5), (10, 2), (11, 3)]
@@ -674,5 +913,11 @@ mod tests {
         assert!(class.get_raw(GlyphId::new(4)).is_none());
         assert_eq!(class.get_raw(GlyphId::new(5)), Some(1));
         assert!(class.get_raw(GlyphId::new(100)).is_none());
+
+        let class = make_class([(4, 1), (5, 1), (6, 1), (200, 2)]);
+        assert!(matches!(class, ClassDef::Format2(_)));
+        assert_eq!(class.get(GlyphId::new(5)), 1);
+        assert_eq!(class.get(GlyphId::new(7)), 0);
+        assert_eq!(class.get(GlyphId::new(200)), 2);
     }
 }
@@ -0,0 +1,105 @@
+//! The [loca (Index to Location)](https://docs.microsoft.com/en-us/typography/opentype/spec/loca) table
+
+use read_fonts::TopLevelTable;
+use types::Tag;
+
+use crate::{
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+/// The format of the `loca` table, as stored in `head.index_to_loc_format`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i16)]
+pub enum LocaFormat {
+    /// Offsets are stored as `u16`, divided by two.
+    Short = 0,
+    /// Offsets are stored as `u32`.
+    Long = 1,
+}
+
+/// The [loca] table.
+///
+/// [loca]: https://docs.microsoft.com/en-us/typography/opentype/spec/loca
+#[derive(Clone, Debug, Default)]
+pub struct Loca {
+    // one more offset than there are glyphs
+    offsets: Vec<u32>,
+}
+
+impl Loca {
+    /// Create a new table from glyph offsets.
+    ///
+    /// There must be one more offset than there are glyphs; the final offset
+    /// is the length of the `glyf` table.
+    pub fn new(offsets: Vec<u32>) -> Self {
+        Loca { offsets }
+    }
+
+    /// The format that will be used when this table is written.
+    ///
+    /// The short format is used when all offsets are even and small enough
+    /// to fit.
+    pub fn format(&self) -> LocaFormat {
+        let fits_short = self
+            .offsets
+            .iter()
+            .all(|off| off % 2 == 0 && off / 2 <= u16::MAX as u32);
+        if fits_short {
+            LocaFormat::Short
+        } else {
+            LocaFormat::Long
+        }
+    }
+}
+
+impl LocaFormat {
+    /// The value to store in `head.index_to_loc_format` for this format.
+    pub fn index_to_loc_format(self) -> i16 {
+        self as i16
+    }
+}
+
+impl TopLevelTable for Loca {
+    const TAG: Tag = Tag::LOCA;
+}
+
+impl FontWrite for Loca {
+    fn write_into(&self, writer: &mut TableWriter) {
+        match self.format() {
+            LocaFormat::Short => self
+                .offsets
+                .iter()
+                .for_each(|off| ((off / 2) as u16).write_into(writer)),
+            LocaFormat::Long => self.offsets.write_into(writer),
+        }
+    }
+}
+
+impl Validate for Loca {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        ctx.in_table("loca", |ctx| {
+            if self.offsets.windows(2).any(|pair| pair[0] > pair[1]) {
+                ctx.report("offsets must be in ascending order");
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choose_format() {
+        let loca = Loca::new(vec![0, 4, 4, 20]);
+        assert_eq!(loca.format(), LocaFormat::Short);
+        assert_eq!(crate::dump_table(&loca).unwrap(), [0, 0, 0, 2, 0, 2, 0, 10]);
+
+        assert_eq!(Loca::new(vec![0, 3]).format(), LocaFormat::Long);
+        let loca = Loca::new(vec![0, 0x20000]);
+        assert_eq!(loca.format(), LocaFormat::Long);
+        assert_eq!(crate::dump_table(&loca).unwrap(), [0, 0, 0, 0, 0, 2, 0, 0]);
+        assert!(crate::dump_table(&Loca::new(vec![4, 0])).is_err());
+    }
+}
@@ -0,0 +1,350 @@
+//! The [MATH](https://learn.microsoft.com/en-us/typography/opentype/spec/math) table
+
+use std::collections::BTreeMap;
+
+use super::layout::{CoverageTable, CoverageTableBuilder, Device};
+
+include!("../../generated/generated_math.rs");
+
+/// One of the four corners of a glyph that can have a [`MathKern`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MathKernCorner {
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
+/// A builder for the MATH table.
+///
+/// Glyph data is collected by glyph id, and checked against the number of
+/// glyphs in the font when the table is built.
+#[derive(Clone, Debug, Default)]
+pub struct MathBuilder {
+    constants: MathConstants,
+    italics_corrections: BTreeMap<GlyphId, MathValueRecord>,
+    top_accent_attachments: BTreeMap<GlyphId, MathValueRecord>,
+    extended_shapes: BTreeSet<GlyphId>,
+    math_kerns: BTreeMap<GlyphId, [Option<MathKern>; 4]>,
+    min_connector_overlap: UfWord,
+    vertical_constructions: BTreeMap<GlyphId, MathGlyphConstruction>,
+    horizontal_constructions: BTreeMap<GlyphId, MathGlyphConstruction>,
+}
+
+/// An error in the input to [`MathBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MathInputError {
+    /// A glyph id is not less than the number of glyphs in the font.
+    GlyphOutOfRange { glyph: GlyphId, num_glyphs: u16 },
+    /// A kern of a glyph does not have exactly one more kern value than
+    /// correction heights.
+    InvalidMathKern(GlyphId),
+}
+
+impl MathBuilder {
+    /// Create a new builder with the given constants.
+    pub fn new(constants: MathConstants) -> Self {
+        MathBuilder {
+            constants,
+            ..Default::default()
+        }
+    }
+
+    /// Set the italics correction of a glyph.
+    pub fn set_italics_correction(&mut self, glyph: GlyphId, value: MathValueRecord) {
+        self.italics_corrections.insert(glyph, value);
+    }
+
+    /// Set the horizontal position at which accents are attached to a glyph.
+    pub fn set_top_accent_attachment(&mut self, glyph: GlyphId, value: MathValueRecord) {
+        self.top_accent_attachments.insert(glyph, value);
+    }
+
+    /// Mark a glyph as an extended shape.
+    pub fn add_extended_shape(&mut self, glyph: GlyphId) {
+        self.extended_shapes.insert(glyph);
+    }
+
+    /// Set the kern at one corner of a glyph.
+    ///
+    /// A kern must have one more value in `kern_values` than in
+    /// `correction_height`.
+    pub fn set_math_kern(&mut self, glyph: GlyphId, corner: MathKernCorner, kern: MathKern) {
+        self.math_kerns.entry(glyph).or_default()[corner as usize] = Some(kern);
+    }
+
+    /// Set the minimum overlap of connecting glyphs in glyph assemblies.
+    pub fn set_min_connector_overlap(&mut self, overlap: UfWord) {
+        self.min_connector_overlap = overlap;
+    }
+
+    /// Set the variants and assembly used to grow a glyph vertically.
+    pub fn set_vertical_construction(
+        &mut self,
+        glyph: GlyphId,
+        construction: MathGlyphConstruction,
+    ) {
+        self.vertical_constructions.insert(glyph, construction);
+    }
+
+    /// Set the variants and assembly used to grow a glyph horizontally.
+    pub fn set_horizontal_construction(
+        &mut self,
+        glyph: GlyphId,
+        construction: MathGlyphConstruction,
+    ) {
+        self.horizontal_constructions.insert(glyph, construction);
+    }
+
+    /// Build the table, checking the input against the number of glyphs in
+    /// the font.
+    pub fn build(self, num_glyphs: u16) -> Result<Math, MathInputError> {
+        self.validate(num_glyphs)?;
+        let italics_correction_info = (!self.italics_corrections.is_empty()).then(|| {
+            MathItalicsCorrectionInfo::new(
+                coverage(self.italics_corrections.keys()),
+                self.italics_corrections.into_values().collect(),
+            )
+        });
+        let top_accent_attachment = (!self.top_accent_attachments.is_empty()).then(|| {
+            MathTopAccentAttachment::new(
+                coverage(self.top_accent_attachments.keys()),
+                self.top_accent_attachments.into_values().collect(),
+            )
+        });
+        let extended_shape_coverage =
+            (!self.extended_shapes.is_empty()).then(|| coverage(self.extended_shapes.iter()));
+        let math_kern_info = (!self.math_kerns.is_empty()).then(|| {
+            MathKernInfo::new(
+                coverage(self.math_kerns.keys()),
+                self.math_kerns
+                    .into_values()
+                    .map(|[top_right, top_left, bottom_right, bottom_left]| {
+                        MathKernInfoRecord::new(top_right, top_left, bottom_right, bottom_left)
+                    })
+                    .collect(),
+            )
+        });
+        let glyph_info = MathGlyphInfo::new(
+            italics_correction_info,
+            top_accent_attachment,
+            extended_shape_coverage,
+            math_kern_info,
+        );
+        let variants = MathVariants::new(
+            self.min_connector_overlap,
+            (!self.vertical_constructions.is_empty())
+                .then(|| coverage(self.vertical_constructions.keys())),
+            (!self.horizontal_constructions.is_empty())
+                .then(|| coverage(self.horizontal_constructions.keys())),
+            self.vertical_constructions.into_values().collect(),
+            self.horizontal_constructions.into_values().collect(),
+        );
+        Ok(Math::new(self.constants, glyph_info, variants))
+    }
+
+    fn validate(&self, num_glyphs: u16) -> Result<(), MathInputError> {
+        let constructions = self
+            .vertical_constructions
+            .iter()
+            .chain(&self.horizontal_constructions);
+        let construction_glyphs = constructions.clone().flat_map(|(_, construction)| {
+            let variants = construction
+                .math_glyph_variant_records
+                .iter()
+                .map(|record| record.variant_glyph);
+            let parts = construction
+                .glyph_assembly
+                .as_ref()
+                .into_iter()
+                .flat_map(|assembly| assembly.part_records.iter().map(|part| part.glyph_id));
+            variants.chain(parts)
+        });
+        let all_glyphs = self
+            .italics_corrections
+            .keys()
+            .chain(self.top_accent_attachments.keys())
+            .chain(&self.extended_shapes)
+            .chain(self.math_kerns.keys())
+            .chain(constructions.map(|(glyph, _)| glyph))
+            .copied()
+            .chain(construction_glyphs);
+        if let Some(glyph) = all_glyphs.max() {
+            if glyph.to_u16() >= num_glyphs {
+                return Err(MathInputError::GlyphOutOfRange { glyph, num_glyphs });
+            }
+        }
+        for (glyph, kerns) in &self.math_kerns {
+            if kerns
+                .iter()
+                .flatten()
+                .any(|kern| kern.kern_values.len() != kern.correction_height.len() + 1)
+            {
+                return Err(MathInputError::InvalidMathKern(*glyph));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn coverage<'a>(glyphs: impl Iterator<Item = &'a GlyphId>) -> CoverageTable {
+    glyphs.copied().collect::<CoverageTableBuilder>().build()
+}
+
+impl std::fmt::Display for MathInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MathInputError::GlyphOutOfRange { glyph, num_glyphs } => write!(
+                f,
+                "glyph {} is out of range for a font with {num_glyphs} glyphs",
+                glyph.to_u16()
+            ),
+            MathInputError::InvalidMathKern(glyph) => write!(
+                f,
+                "a kern of glyph {} must have one more kern value than correction heights",
+                glyph.to_u16()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MathInputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    fn value(value: i16) -> MathValueRecord {
+        MathValueRecord::new(value.into(), None)
+    }
+
+    fn constants() -> MathConstants {
+        MathConstants {
+            script_percent_scale_down: 70,
+            script_script_percent_scale_down: 50,
+            axis_height: value(250),
+            fraction_rule_thickness: MathValueRecord::new(
+                FWord::new(40),
+                Some(Device::new(12, 13, &[1, -1])),
+            ),
+            radical_degree_bottom_raise_percent: 60,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = MathBuilder::new(constants());
+        builder.set_italics_correction(gid(5), value(30));
+        builder.set_italics_correction(gid(2), value(10));
+        builder.set_top_accent_attachment(gid(2), value(200));
+        builder.add_extended_shape(gid(7));
+        builder.set_math_kern(
+            gid(2),
+            MathKernCorner::BottomLeft,
+            MathKern::new(vec![value(100)], vec![value(-10), value(-20)]),
+        );
+        builder.set_min_connector_overlap(UfWord::new(20));
+        let part =
+            |glyph, flags| GlyphPart::new(gid(glyph), 10.into(), 10.into(), 300.into(), flags);
+        builder.set_vertical_construction(
+            gid(3),
+            MathGlyphConstruction::new(
+                Some(GlyphAssembly::new(
+                    value(0),
+                    vec![
+                        part(8, GlyphPartFlags::empty()),
+                        part(9, GlyphPartFlags::EXTENDER_FLAG),
+                    ],
+                )),
+                vec![MathGlyphVariantRecord::new(gid(3), 500.into())],
+            ),
+        );
+        let math = builder.build(10).unwrap();
+
+        let dumped = crate::dump_table(&math).unwrap();
+        let loaded = read_fonts::tables::math::Math::read(FontData::new(&dumped)).unwrap();
+        let constants = loaded.math_constants().unwrap();
+        assert_eq!(constants.script_percent_scale_down(), 70);
+        assert_eq!(constants.axis_height().value(), FWord::new(250));
+        assert_eq!(constants.radical_degree_bottom_raise_percent(), 60);
+        let thickness = constants.fraction_rule_thickness();
+        assert_eq!(thickness.value(), FWord::new(40));
+        let device = thickness.device(constants.offset_data()).unwrap().unwrap();
+        assert_eq!((device.start_size(), device.end_size()), (12, 13));
+        assert!(constants
+            .math_leading()
+            .device(constants.offset_data())
+            .is_none());
+
+        let glyph_info = loaded.math_glyph_info().unwrap();
+        let italics = glyph_info.math_italics_correction_info().unwrap().unwrap();
+        let coverage = italics.italics_correction_coverage().unwrap();
+        assert_eq!(coverage.iter().collect::<Vec<_>>(), [gid(2), gid(5)]);
+        let values = italics
+            .italics_correction()
+            .iter()
+            .map(|record| record.value().to_i16())
+            .collect::<Vec<_>>();
+        assert_eq!(values, [10, 30]);
+        assert!(glyph_info.math_top_accent_attachment().is_some());
+        let extended = glyph_info.extended_shape_coverage().unwrap().unwrap();
+        assert_eq!(extended.get(gid(7)), Some(0));
+
+        let kern_info = glyph_info.math_kern_info().unwrap().unwrap();
+        let record = &kern_info.math_kern_info_records()[0];
+        assert!(record
+            .top_right_math_kern(kern_info.offset_data())
+            .is_none());
+        let kern = record
+            .bottom_left_math_kern(kern_info.offset_data())
+            .unwrap()
+            .unwrap();
+        assert_eq!(kern.height_count(), 1);
+        assert_eq!(kern.kern_values()[1].value(), FWord::new(-20));
+
+        let variants = loaded.math_variants().unwrap();
+        assert_eq!(variants.min_connector_overlap(), UfWord::new(20));
+        assert!(variants.horiz_glyph_coverage().is_none());
+        assert_eq!(variants.horiz_glyph_count(), 0);
+        let construction = variants.vert_glyph_constructions().next().unwrap().unwrap();
+        let variant = &construction.math_glyph_variant_records()[0];
+        assert_eq!(variant.advance_measurement(), UfWord::new(500));
+        let assembly = construction.glyph_assembly().unwrap().unwrap();
+        let parts = assembly.part_records();
+        assert_eq!(parts[1].glyph_id(), gid(9));
+        assert_eq!(parts[1].part_flags(), GlyphPartFlags::EXTENDER_FLAG);
+    }
+
+    #[test]
+    fn builder_validation() {
+        let mut builder = MathBuilder::new(constants());
+        builder.set_vertical_construction(
+            gid(3),
+            MathGlyphConstruction::new(None, vec![MathGlyphVariantRecord::new(gid(12), 0.into())]),
+        );
+        assert_eq!(
+            builder.clone().build(10).unwrap_err(),
+            MathInputError::GlyphOutOfRange {
+                glyph: gid(12),
+                num_glyphs: 10
+            }
+        );
+        assert!(builder.build(13).is_ok());
+
+        let mut builder = MathBuilder::new(constants());
+        builder.set_math_kern(
+            gid(1),
+            MathKernCorner::TopRight,
+            MathKern::new(vec![value(100)], vec![value(-10)]),
+        );
+        assert_eq!(
+            builder.build(10).unwrap_err(),
+            MathInputError::InvalidMathKern(gid(1))
+        );
+    }
+}
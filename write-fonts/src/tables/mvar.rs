@@ -0,0 +1,117 @@
+//! The [MVAR (Metrics Variations)](https://docs.microsoft.com/en-us/typography/opentype/spec/mvar) table
+
+include!("../../generated/generated_mvar.rs");
+
+use std::collections::BTreeMap;
+
+use super::variations::{ItemVariationStore, VariationRegion, VariationStoreBuilder};
+
+pub use read_fonts::tables::mvar::tags;
+
+/// A builder for the `MVAR` table.
+///
+/// Deltas are collected for each metric, identified by one of the [tags].
+/// Metrics whose deltas are all zero are omitted from the table.
+#[derive(Clone, Debug, Default)]
+pub struct MvarBuilder {
+    metrics: BTreeMap<Tag, Vec<(VariationRegion, i32)>>,
+}
+
+impl MvarBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the deltas of a metric.
+    ///
+    /// Each delta is the change of the metric in the region it is paired
+    /// with.
+    pub fn set_metric_deltas(&mut self, tag: Tag, deltas: Vec<(VariationRegion, i32)>) {
+        self.metrics.insert(tag, deltas);
+    }
+
+    /// Build the table.
+    ///
+    /// Value records are sorted by tag, as required.
+    pub fn build(self) -> Mvar {
+        let mut store = VariationStoreBuilder::new();
+        let ids = self
+            .metrics
+            .into_iter()
+            .filter(|(_, deltas)| deltas.iter().any(|(_, delta)| *delta != 0))
+            .map(|(tag, deltas)| (tag, store.add_deltas(deltas)))
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            return Mvar::new(None, Vec::new());
+        }
+        let (store, remapping) = store.build();
+        let value_records = ids
+            .into_iter()
+            .map(|(tag, id)| {
+                let (outer, inner) = remapping.get_raw(id).unwrap();
+                ValueRecord::new(tag, outer, inner)
+            })
+            .collect();
+        Mvar::new(Some(store), value_records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::FontRead;
+    use types::{F2Dot14, Fixed};
+
+    use super::*;
+    use crate::tables::variations::RegionAxisCoordinates;
+
+    fn wght_max() -> VariationRegion {
+        VariationRegion::new(vec![RegionAxisCoordinates::new(
+            F2Dot14::ZERO,
+            F2Dot14::from_f32(1.0),
+            F2Dot14::from_f32(1.0),
+        )])
+    }
+
+    #[test]
+    fn sorted_records() {
+        let mut builder = MvarBuilder::new();
+        builder.set_metric_deltas(tags::XHGT, vec![(wght_max(), 12)]);
+        builder.set_metric_deltas(tags::HASC, vec![(wght_max(), 50)]);
+        builder.set_metric_deltas(tags::CPHT, vec![(wght_max(), 12)]);
+        builder.set_metric_deltas(tags::HDSC, vec![(wght_max(), 0)]);
+        let mvar = builder.build();
+        let record_tags = mvar
+            .value_records
+            .iter()
+            .map(|record| record.value_tag)
+            .collect::<Vec<_>>();
+        assert_eq!(record_tags, [tags::CPHT, tags::HASC, tags::XHGT]);
+        // identical deltas share a row
+        assert_eq!(
+            mvar.value_records[0].delta_set_inner_index,
+            mvar.value_records[2].delta_set_inner_index
+        );
+
+        let bytes = crate::dump_table(&mvar).unwrap();
+        let mvar = read_fonts::tables::mvar::Mvar::read(FontData::new(&bytes)).unwrap();
+        let coords = [F2Dot14::from_f32(0.5)];
+        assert_eq!(
+            mvar.metric_delta(tags::HASC, &coords).unwrap(),
+            Fixed::from_i32(25)
+        );
+        assert_eq!(
+            mvar.metric_delta(tags::XHGT, &coords).unwrap(),
+            Fixed::from_i32(6)
+        );
+        assert!(mvar.metric_delta(tags::HDSC, &coords).is_err());
+    }
+
+    #[test]
+    fn empty() {
+        let mvar = MvarBuilder::new().build();
+        assert!(mvar.item_variation_store.is_none());
+        let bytes = crate::dump_table(&mvar).unwrap();
+        assert_eq!(bytes.len(), 14);
+    }
+}
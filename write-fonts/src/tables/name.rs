@@ -1,6 +1,8 @@
 //! The name table
 
 include!("../../generated/generated_name.rs");
+use std::collections::BTreeMap;
+
 use read_fonts::tables::name::{Encoding, MacRomanMapping};
 
 impl Name {
@@ -169,6 +171,166 @@ impl PartialOrd for NameRecord {
     }
 }
 
+/// The Windows platform id.
+const WINDOWS_PLATFORM: u16 = 3;
+/// The Unicode BMP encoding on the Windows platform.
+const WINDOWS_UNICODE_BMP: u16 = 1;
+/// The Macintosh platform id.
+const MAC_PLATFORM: u16 = 1;
+/// The Roman encoding on the Macintosh platform.
+const MAC_ROMAN: u16 = 0;
+/// The Windows language id for English (United States).
+const WINDOWS_ENGLISH: u16 = 0x409;
+/// The Macintosh language id for English.
+const MAC_ENGLISH: u16 = 0;
+
+/// A builder for the `name` table.
+///
+/// Names are added with a name id and a Windows language id, and are encoded
+/// as UTF-16BE on the Windows platform. English names can optionally also be
+/// added on the Macintosh platform, encoded as Mac Roman.
+///
+/// Records that have identical string data share the same storage in the
+/// compiled table.
+#[derive(Clone, Debug, Default)]
+pub struct NameBuilder {
+    // (platform_id, encoding_id, language_id, name_id)
+    names: BTreeMap<(u16, u16, u16, NameId), String>,
+    mac_names: bool,
+}
+
+impl NameBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// If `true`, English names are also added on the Macintosh platform.
+    ///
+    /// This only affects names added after it is set, and names that cannot be
+    /// represented in Mac Roman are only added on the Windows platform.
+    pub fn with_mac_names(&mut self, mac_names: bool) -> &mut Self {
+        self.mac_names = mac_names;
+        self
+    }
+
+    /// Add a name, replacing any existing name with this id and language.
+    pub fn add(&mut self, name_id: NameId, language: u16, string: impl Into<String>) -> &mut Self {
+        let string = string.into();
+        if self.mac_names
+            && language == WINDOWS_ENGLISH
+            && string.chars().all(|c| MacRomanMapping.encode(c).is_some())
+        {
+            self.names.insert(
+                (MAC_PLATFORM, MAC_ROMAN, MAC_ENGLISH, name_id),
+                string.clone(),
+            );
+        }
+        self.names.insert(
+            (WINDOWS_PLATFORM, WINDOWS_UNICODE_BMP, language, name_id),
+            string,
+        );
+        self
+    }
+
+    /// Return a font-specific name id (256 or greater) for this string.
+    ///
+    /// If the string has already been added with a font-specific id in this
+    /// language then that id is reused; otherwise, the string is added with the
+    /// lowest unused id. This is intended for tables such as `fvar` and `STAT`,
+    /// which refer to names by id.
+    pub fn add_name(&mut self, language: u16, string: impl Into<String>) -> NameId {
+        let string = string.into();
+        let existing = self
+            .names
+            .iter()
+            .find(|((platform, _, lang, name_id), value)| {
+                *platform == WINDOWS_PLATFORM
+                    && *lang == language
+                    && name_id.is_font_specific()
+                    && **value == string
+            });
+        if let Some(((.., name_id), _)) = existing {
+            return *name_id;
+        }
+        let name_id = self
+            .names
+            .keys()
+            .map(|(.., name_id)| *name_id)
+            .max()
+            .map(|max| max.checked_next().unwrap_or(max))
+            .unwrap_or_default()
+            .max(NameId::FIRST_FONT_SPECIFIC);
+        self.add(name_id, language, string);
+        name_id
+    }
+
+    /// Return the Windows name with this id and language, if one exists.
+    pub fn get(&self, name_id: NameId, language: u16) -> Option<&str> {
+        self.names
+            .get(&(WINDOWS_PLATFORM, WINDOWS_UNICODE_BMP, language, name_id))
+            .map(String::as_str)
+    }
+
+    /// Build the table.
+    pub fn build(self) -> Name {
+        Name::new(
+            self.names
+                .into_iter()
+                .map(
+                    |((platform_id, encoding_id, language_id, name_id), string)| {
+                        NameRecord::new(
+                            platform_id,
+                            encoding_id,
+                            language_id,
+                            name_id,
+                            OffsetMarker::new(string),
+                        )
+                    },
+                )
+                .collect(),
+        )
+    }
+}
+
+/// A source of name ids, for tables that refer to entries in the `name` table.
+///
+/// Builders for tables such as `fvar` and `STAT` use this to register the
+/// names they refer to.
+pub trait NameIdAllocator {
+    /// Return the id of a name with this English string, adding it if needed.
+    fn name_id(&mut self, name: &str) -> NameId;
+}
+
+impl NameIdAllocator for NameBuilder {
+    fn name_id(&mut self, name: &str) -> NameId {
+        self.add_name(WINDOWS_ENGLISH, name)
+    }
+}
+
+impl From<Name> for NameBuilder {
+    fn from(name: Name) -> Self {
+        NameBuilder {
+            names: name
+                .name_record
+                .into_iter()
+                .map(|record| {
+                    (
+                        (
+                            record.platform_id,
+                            record.encoding_id,
+                            record.language_id,
+                            record.name_id,
+                        ),
+                        record.string.into_inner(),
+                    )
+                })
+                .collect(),
+            mac_names: false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,35 +359,35 @@ mod tests {
             platform_id: 3,
             encoding_id: 1,
             language_id: 0,
-            name_id: 1030,
+            name_id: NameId::new(1030),
             string: OffsetMarker::new("Ordinær".into()),
         });
         table.name_record.insert(NameRecord {
             platform_id: 0,
             encoding_id: 4,
             language_id: 0,
-            name_id: 4,
+            name_id: NameId::FULL_NAME,
             string: OffsetMarker::new("oh".into()),
         });
         table.name_record.insert(NameRecord {
             platform_id: 3,
             encoding_id: 1,
             language_id: 0,
-            name_id: 1029,
+            name_id: NameId::new(1029),
             string: OffsetMarker::new("Regular".into()),
         });
 
         let _dumped = crate::dump_table(&table).unwrap();
         let loaded = read_fonts::tables::name::Name::read(FontData::new(&_dumped)).unwrap();
         assert_eq!(loaded.name_record()[0].encoding_id, 4);
-        assert_eq!(loaded.name_record()[1].name_id, 1029);
-        assert_eq!(loaded.name_record()[2].name_id, 1030);
+        assert_eq!(loaded.name_record()[1].name_id(), 1029);
+        assert_eq!(loaded.name_record()[2].name_id(), 1030);
     }
 
     /// ensure we are counting characters and not bytes
     #[test]
     fn mac_str_length() {
-        let name = NameRecord::new(1, 0, 0, 9, String::from("cé").into());
+        let name = NameRecord::new(1, 0, 0, NameId::DESIGNER, String::from("cé").into());
         let mut table = Name::default();
         table.name_record.insert(name);
         let bytes = crate::dump_table(&table).unwrap();
@@ -235,6 +397,74 @@ mod tests {
         assert_eq!(data.chars().collect::<String>(), "cé");
     }
 
+    #[test]
+    fn builder() {
+        let mut builder = NameBuilder::new();
+        builder
+            .with_mac_names(true)
+            .add(NameId::FAMILY_NAME, WINDOWS_ENGLISH, "Family")
+            .add(NameId::SUBFAMILY_NAME, WINDOWS_ENGLISH, "Regular")
+            .add(NameId::FULL_NAME, WINDOWS_ENGLISH, "Family ☃")
+            .add(NameId::SUBFAMILY_NAME, 0x407, "Standard");
+        let weight = builder.add_name(WINDOWS_ENGLISH, "Weight");
+        assert_eq!(weight, 256);
+        assert_eq!(builder.add_name(WINDOWS_ENGLISH, "Weight"), 256);
+        assert_eq!(builder.add_name(0x407, "Weight"), 257);
+        assert_eq!(builder.add_name(WINDOWS_ENGLISH, "Regular"), 258);
+        assert_eq!(builder.get(NameId::SUBFAMILY_NAME, 0x407), Some("Standard"));
+
+        let bytes = crate::dump_table(&builder.build()).unwrap();
+        let name = read_fonts::tables::name::Name::read(FontData::new(&bytes)).unwrap();
+        let records = name
+            .name_record()
+            .iter()
+            .map(|record| {
+                (
+                    record.platform_id(),
+                    record.language_id(),
+                    record.name_id(),
+                    record
+                        .string(name.string_data())
+                        .unwrap()
+                        .chars()
+                        .collect::<String>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let expected = [
+            (1, 0, 1, "Family"),
+            (1, 0, 2, "Regular"),
+            (1, 0, 256, "Weight"),
+            (1, 0, 258, "Regular"),
+            (3, 0x407, 2, "Standard"),
+            (3, 0x407, 257, "Weight"),
+            (3, 0x409, 1, "Family"),
+            (3, 0x409, 2, "Regular"),
+            (3, 0x409, 4, "Family ☃"),
+            (3, 0x409, 256, "Weight"),
+            (3, 0x409, 258, "Regular"),
+        ]
+        .map(|(platform, language, name_id, string)| {
+            (platform, language, NameId::new(name_id), string.to_string())
+        });
+        assert_eq!(records, expected);
+
+        // identical strings share storage
+        let offset = |platform_id, language_id, name_id| {
+            name.name_record()
+                .iter()
+                .find(|record| {
+                    (record.platform_id(), record.language_id(), record.name_id())
+                        == (platform_id, language_id, NameId::new(name_id))
+                })
+                .unwrap()
+                .string_offset()
+        };
+        assert_eq!(offset(3, 0x409, 2), offset(3, 0x409, 258));
+        assert_eq!(offset(1, 0, 2), offset(1, 0, 258));
+        assert_ne!(offset(1, 0, 2), offset(3, 0x409, 2));
+    }
+
     #[test]
     fn roundtrip() {
         #[rustfmt::skip]
@@ -2,7 +2,195 @@
 
 include!("../../generated/generated_os2.rs");
 
+use std::collections::BTreeSet;
+
+use super::hmtx::Hmtx;
+
+/// The bit in `ul_unicode_range_*` that is set for characters outside the
+/// Basic Multilingual Plane.
+const NON_PLANE_0_BIT: u8 = 57;
+
+/// The Unicode blocks assigned to each bit of `ul_unicode_range_*`, as
+/// `(first, last, bit)`, sorted by codepoint.
+///
+/// See <https://learn.microsoft.com/en-us/typography/opentype/spec/os2#ur>.
+#[rustfmt::skip]
+static UNICODE_RANGES: &[(u32, u32, u8)] = &[
+    (0x0000, 0x007F, 0),
+    (0x0080, 0x00FF, 1),
+    (0x0100, 0x017F, 2),
+    (0x0180, 0x024F, 3),
+    (0x0250, 0x02AF, 4),
+    (0x02B0, 0x02FF, 5),
+    (0x0300, 0x036F, 6),
+    (0x0370, 0x03FF, 7),
+    (0x0400, 0x04FF, 9),
+    (0x0500, 0x052F, 9),
+    (0x0530, 0x058F, 10),
+    (0x0590, 0x05FF, 11),
+    (0x0600, 0x06FF, 13),
+    (0x0700, 0x074F, 71),
+    (0x0750, 0x077F, 13),
+    (0x0780, 0x07BF, 72),
+    (0x07C0, 0x07FF, 14),
+    (0x0900, 0x097F, 15),
+    (0x0980, 0x09FF, 16),
+    (0x0A00, 0x0A7F, 17),
+    (0x0A80, 0x0AFF, 18),
+    (0x0B00, 0x0B7F, 19),
+    (0x0B80, 0x0BFF, 20),
+    (0x0C00, 0x0C7F, 21),
+    (0x0C80, 0x0CFF, 22),
+    (0x0D00, 0x0D7F, 23),
+    (0x0D80, 0x0DFF, 73),
+    (0x0E00, 0x0E7F, 24),
+    (0x0E80, 0x0EFF, 25),
+    (0x0F00, 0x0FFF, 70),
+    (0x1000, 0x109F, 74),
+    (0x10A0, 0x10FF, 26),
+    (0x1100, 0x11FF, 28),
+    (0x1200, 0x137F, 75),
+    (0x1380, 0x139F, 75),
+    (0x13A0, 0x13FF, 76),
+    (0x1400, 0x167F, 77),
+    (0x1680, 0x169F, 78),
+    (0x16A0, 0x16FF, 79),
+    (0x1700, 0x171F, 84),
+    (0x1720, 0x173F, 84),
+    (0x1740, 0x175F, 84),
+    (0x1760, 0x177F, 84),
+    (0x1780, 0x17FF, 80),
+    (0x1800, 0x18AF, 81),
+    (0x1900, 0x194F, 93),
+    (0x1950, 0x197F, 94),
+    (0x1980, 0x19DF, 95),
+    (0x19E0, 0x19FF, 80),
+    (0x1A00, 0x1A1F, 96),
+    (0x1B00, 0x1B7F, 27),
+    (0x1B80, 0x1BBF, 112),
+    (0x1C00, 0x1C4F, 113),
+    (0x1C50, 0x1C7F, 114),
+    (0x1D00, 0x1D7F, 4),
+    (0x1D80, 0x1DBF, 4),
+    (0x1DC0, 0x1DFF, 6),
+    (0x1E00, 0x1EFF, 29),
+    (0x1F00, 0x1FFF, 30),
+    (0x2000, 0x206F, 31),
+    (0x2070, 0x209F, 32),
+    (0x20A0, 0x20CF, 33),
+    (0x20D0, 0x20FF, 34),
+    (0x2100, 0x214F, 35),
+    (0x2150, 0x218F, 36),
+    (0x2190, 0x21FF, 37),
+    (0x2200, 0x22FF, 38),
+    (0x2300, 0x23FF, 39),
+    (0x2400, 0x243F, 40),
+    (0x2440, 0x245F, 41),
+    (0x2460, 0x24FF, 42),
+    (0x2500, 0x257F, 43),
+    (0x2580, 0x259F, 44),
+    (0x25A0, 0x25FF, 45),
+    (0x2600, 0x26FF, 46),
+    (0x2700, 0x27BF, 47),
+    (0x27C0, 0x27EF, 38),
+    (0x27F0, 0x27FF, 37),
+    (0x2800, 0x28FF, 82),
+    (0x2900, 0x297F, 37),
+    (0x2980, 0x29FF, 38),
+    (0x2A00, 0x2AFF, 38),
+    (0x2B00, 0x2BFF, 37),
+    (0x2C00, 0x2C5F, 97),
+    (0x2C60, 0x2C7F, 29),
+    (0x2C80, 0x2CFF, 8),
+    (0x2D00, 0x2D2F, 26),
+    (0x2D30, 0x2D7F, 98),
+    (0x2D80, 0x2DDF, 75),
+    (0x2DE0, 0x2DFF, 9),
+    (0x2E00, 0x2E7F, 31),
+    (0x2E80, 0x2EFF, 59),
+    (0x2F00, 0x2FDF, 59),
+    (0x2FF0, 0x2FFF, 59),
+    (0x3000, 0x303F, 48),
+    (0x3040, 0x309F, 49),
+    (0x30A0, 0x30FF, 50),
+    (0x3100, 0x312F, 51),
+    (0x3130, 0x318F, 52),
+    (0x3190, 0x319F, 59),
+    (0x31A0, 0x31BF, 51),
+    (0x31C0, 0x31EF, 61),
+    (0x31F0, 0x31FF, 50),
+    (0x3200, 0x32FF, 54),
+    (0x3300, 0x33FF, 55),
+    (0x3400, 0x4DBF, 59),
+    (0x4DC0, 0x4DFF, 99),
+    (0x4E00, 0x9FFF, 59),
+    (0xA000, 0xA48F, 83),
+    (0xA490, 0xA4CF, 83),
+    (0xA500, 0xA63F, 12),
+    (0xA640, 0xA69F, 9),
+    (0xA700, 0xA71F, 5),
+    (0xA720, 0xA7FF, 29),
+    (0xA800, 0xA82F, 100),
+    (0xA840, 0xA87F, 53),
+    (0xA880, 0xA8DF, 115),
+    (0xA900, 0xA92F, 116),
+    (0xA930, 0xA95F, 117),
+    (0xAA00, 0xAA5F, 118),
+    (0xAC00, 0xD7AF, 56),
+    (0xD800, 0xDFFF, 57),
+    (0xE000, 0xF8FF, 60),
+    (0xF900, 0xFAFF, 61),
+    (0xFB00, 0xFB4F, 62),
+    (0xFB50, 0xFDFF, 63),
+    (0xFE00, 0xFE0F, 91),
+    (0xFE10, 0xFE1F, 65),
+    (0xFE20, 0xFE2F, 64),
+    (0xFE30, 0xFE4F, 65),
+    (0xFE50, 0xFE6F, 66),
+    (0xFE70, 0xFEFF, 67),
+    (0xFF00, 0xFFEF, 68),
+    (0xFFF0, 0xFFFF, 69),
+    (0x10000, 0x1007F, 101),
+    (0x10080, 0x100FF, 101),
+    (0x10100, 0x1013F, 101),
+    (0x10140, 0x1018F, 102),
+    (0x10190, 0x101CF, 119),
+    (0x101D0, 0x101FF, 120),
+    (0x10280, 0x1029F, 121),
+    (0x102A0, 0x102DF, 121),
+    (0x10300, 0x1032F, 85),
+    (0x10330, 0x1034F, 86),
+    (0x10380, 0x1039F, 103),
+    (0x103A0, 0x103DF, 104),
+    (0x10400, 0x1044F, 87),
+    (0x10450, 0x1047F, 105),
+    (0x10480, 0x104AF, 106),
+    (0x10800, 0x1083F, 107),
+    (0x10900, 0x1091F, 58),
+    (0x10920, 0x1093F, 121),
+    (0x10A00, 0x10A5F, 108),
+    (0x12000, 0x123FF, 110),
+    (0x12400, 0x1247F, 110),
+    (0x1D000, 0x1D0FF, 88),
+    (0x1D100, 0x1D1FF, 88),
+    (0x1D200, 0x1D24F, 88),
+    (0x1D300, 0x1D35F, 109),
+    (0x1D360, 0x1D37F, 111),
+    (0x1D400, 0x1D7FF, 89),
+    (0x1F000, 0x1F02F, 122),
+    (0x1F030, 0x1F09F, 122),
+    (0x20000, 0x2A6DF, 59),
+    (0x2F800, 0x2FA1F, 61),
+    (0xE0000, 0xE007F, 92),
+    (0xE0100, 0xE01EF, 91),
+    (0xF0000, 0xFFFFD, 90),
+    (0x100000, 0x10FFFD, 90),
+];
+
 impl Os2 {
+    /// The version is the lowest that has all of the fields that are set,
+    /// except that versions 2 through 4 have the same fields, and we use 4,
+    /// which defines the most `fs_selection` bits.
     fn compute_version(&self) -> u16 {
         if self.us_lower_optical_point_size.is_some() || self.us_upper_optical_point_size.is_some()
         {
@@ -14,7 +202,7 @@ impl Os2 {
                 .or(self.us_max_context)
                 .is_some()
         {
-            2
+            4
         } else {
             u16::from(
                 self.ul_code_page_range_1
@@ -25,6 +213,243 @@ impl Os2 {
     }
 }
 
+impl Os2 {
+    /// Set the fields that describe the characters supported by the font.
+    ///
+    /// This computes `ul_unicode_range_1` through `ul_unicode_range_4`,
+    /// `ul_code_page_range_1` and `ul_code_page_range_2`, and
+    /// `us_first_char_index` and `us_last_char_index` from the codepoints
+    /// mapped in the `cmap` table.
+    pub fn set_codepoints(&mut self, codepoints: &BTreeSet<u32>) {
+        let [range_1, range_2, range_3, range_4] = unicode_range_bits(codepoints);
+        self.ul_unicode_range_1 = range_1;
+        self.ul_unicode_range_2 = range_2;
+        self.ul_unicode_range_3 = range_3;
+        self.ul_unicode_range_4 = range_4;
+        let [code_page_1, code_page_2] = code_page_range_bits(codepoints);
+        self.ul_code_page_range_1 = Some(code_page_1);
+        self.ul_code_page_range_2 = Some(code_page_2);
+        let clamp = |codepoint: &u32| (*codepoint).min(0xFFFF) as u16;
+        self.us_first_char_index = codepoints.first().map(clamp).unwrap_or(0);
+        self.us_last_char_index = codepoints.last().map(clamp).unwrap_or(0);
+    }
+
+    /// Set `x_avg_char_width` to the average advance of all glyphs with a
+    /// non-zero advance.
+    pub fn set_avg_char_width(&mut self, hmtx: &Hmtx) {
+        let last_advance = hmtx
+            .h_metrics
+            .last()
+            .map(|metric| metric.advance)
+            .unwrap_or(0);
+        let advances = hmtx
+            .h_metrics
+            .iter()
+            .map(|metric| metric.advance)
+            .chain(hmtx.left_side_bearings.iter().map(|_| last_advance))
+            .filter(|advance| *advance != 0);
+        let (count, total) = advances.fold((0u32, 0u32), |(count, total), advance| {
+            (count + 1, total + advance as u32)
+        });
+        self.x_avg_char_width = if count == 0 {
+            0
+        } else {
+            ((total as f64 / count as f64).round() as u32).min(i16::MAX as u32) as i16
+        };
+    }
+}
+
+/// Compute the bits of `ul_unicode_range_1` through `ul_unicode_range_4`.
+fn unicode_range_bits(codepoints: &BTreeSet<u32>) -> [u32; 4] {
+    let mut bits = [0u32; 4];
+    let mut set = |bit: u8| bits[bit as usize / 32] |= 1 << (bit % 32);
+    for codepoint in codepoints {
+        if *codepoint > 0xFFFF {
+            set(NON_PLANE_0_BIT);
+        }
+        let idx = UNICODE_RANGES.partition_point(|(_, last, _)| last < codepoint);
+        if let Some((first, _, bit)) = UNICODE_RANGES.get(idx) {
+            if first <= codepoint {
+                set(*bit);
+            }
+        }
+    }
+    bits
+}
+
+/// Compute the bits of `ul_code_page_range_1` and `ul_code_page_range_2`.
+///
+/// A code page is considered supported if the font includes characters that
+/// are distinctive of that code page; this is the same heuristic used by
+/// fontTools.
+fn code_page_range_bits(codepoints: &BTreeSet<u32>) -> [u32; 2] {
+    let has = |c: char| codepoints.contains(&(c as u32));
+    let has_ascii = (0x20..0x7E).all(|c| codepoints.contains(&c));
+    let has_lineart = has('┤');
+    let has_sqrt = has('√');
+
+    let mut bits = [0u32; 2];
+    let mut set = |bit: u8| bits[bit as usize / 32] |= 1 << (bit % 32);
+    if has_ascii && has('Þ') {
+        set(0); // Latin 1
+    }
+    if has_ascii && has('Ľ') {
+        set(1); // Latin 2: Eastern Europe
+        if has_lineart {
+            set(58); // Latin 2
+        }
+    }
+    if has('Б') {
+        set(2); // Cyrillic
+        if has('Ѕ') && has_lineart {
+            set(57); // IBM Cyrillic
+        }
+        if has('╜') && has_lineart {
+            set(49); // MS-DOS Russian
+        }
+    }
+    if has('Ά') {
+        set(3); // Greek
+        if has_lineart && has('½') {
+            set(48); // IBM Greek
+        }
+        if has_lineart && has_sqrt {
+            set(60); // Greek, former 437 G
+        }
+    }
+    if has_ascii && has('İ') {
+        set(4); // Turkish
+        if has_lineart {
+            set(56); // IBM Turkish
+        }
+    }
+    if has('א') {
+        set(5); // Hebrew
+        if has_lineart && has_sqrt {
+            set(53); // Hebrew
+        }
+    }
+    if has('ر') {
+        set(6); // Arabic
+        if has_sqrt {
+            set(51); // Arabic
+        }
+        if has_lineart {
+            set(61); // Arabic; ASMO 708
+        }
+    }
+    if has_ascii && has('ŗ') {
+        set(7); // Windows Baltic
+        if has_lineart {
+            set(59); // MS-DOS Baltic
+        }
+    }
+    if has_ascii && has('₫') {
+        set(8); // Vietnamese
+    }
+    if has('ๅ') {
+        set(16); // Thai
+    }
+    if has('エ') {
+        set(17); // JIS/Japan
+    }
+    if has('ㄅ') {
+        set(18); // Chinese: Simplified
+    }
+    if has('ㄱ') {
+        set(19); // Korean Wansung
+    }
+    if has('央') {
+        set(20); // Chinese: Traditional
+    }
+    if has('곴') {
+        set(21); // Korean Johab
+    }
+    if has_ascii && has('‰') && has('∑') {
+        set(29); // Macintosh Character Set (US Roman)
+    }
+    if has_ascii && has('♥') {
+        set(30); // OEM Character Set
+    }
+    if has_ascii && has_lineart && has('þ') {
+        set(54); // MS-DOS Icelandic
+    }
+    if has_ascii && has('╚') {
+        set(62); // WE/Latin 1
+        set(63); // US
+    }
+    if has_ascii && has_lineart && has_sqrt {
+        if has('Þ') {
+            set(52); // MS-DOS Nordic
+        }
+        if has('é') {
+            set(50); // MS-DOS Canadian French
+        }
+        if has('õ') {
+            set(55); // MS-DOS Portuguese
+        }
+    }
+    // if nothing else matches, claim Latin 1 so that the font is usable in
+    // applications that require some code page to be set
+    if bits == [0, 0] {
+        bits[0] = 1;
+    }
+    bits
+}
+
 fn convert_panose(raw: &[u8]) -> [u8; 10] {
     raw.try_into().unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::hmtx::LongMetric;
+
+    fn chars(s: &str) -> BTreeSet<u32> {
+        s.chars().map(|c| c as u32).collect()
+    }
+
+    #[test]
+    fn unicode_ranges() {
+        let mut os2 = Os2::default();
+        os2.set_codepoints(&chars("Aé\u{0416}\u{4E2D}\u{1F600}\u{1D400}"));
+        // basic latin, latin-1, cyrillic
+        assert_eq!(os2.ul_unicode_range_1, 1 << 0 | 1 << 1 | 1 << 9);
+        // non-plane 0, CJK
+        assert_eq!(os2.ul_unicode_range_2, 1 << (57 - 32) | 1 << (59 - 32));
+        // mathematical alphanumeric symbols
+        assert_eq!(os2.ul_unicode_range_3, 1 << (89 - 64));
+        assert_eq!(os2.ul_unicode_range_4, 0);
+        assert_eq!(os2.us_first_char_index, 'A' as u16);
+        assert_eq!(os2.us_last_char_index, 0xFFFF);
+        // no code page can be identified, so we claim latin 1
+        assert_eq!(os2.ul_code_page_range_1, Some(1));
+        assert_eq!(os2.ul_code_page_range_2, Some(0));
+    }
+
+    #[test]
+    fn code_page_ranges() {
+        let mut codepoints = (0x20..0x7F).collect::<BTreeSet<_>>();
+        codepoints.extend(chars("ÞБЅ┤╚"));
+        let [range_1, range_2] = code_page_range_bits(&codepoints);
+        assert_eq!(range_1, 1 << 0 | 1 << 2);
+        assert_eq!(range_2, 1 << (57 - 32) | 1 << (62 - 32) | 1 << (63 - 32));
+    }
+
+    #[test]
+    fn avg_char_width() {
+        let hmtx = Hmtx::new(
+            vec![
+                LongMetric::new(0, 0),
+                LongMetric::new(500, 0),
+                LongMetric::new(601, 0),
+            ],
+            vec![0, 0],
+        );
+        let mut os2 = Os2::default();
+        os2.set_avg_char_width(&hmtx);
+        // (500 + 601 * 3) / 4
+        assert_eq!(os2.x_avg_char_width, 576);
+    }
+}
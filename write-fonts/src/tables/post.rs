@@ -4,29 +4,119 @@ use std::collections::HashMap;
 
 include!("../../generated/generated_post.rs");
 
-//TODO: I imagine we're going to need a builder for this
-
 /// A string in the post table.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PString(String);
 
+/// A builder for the [`Post`] table.
+///
+/// Glyph names are added in glyph id order. The most compact version of the
+/// table that can represent them is chosen when the table is built:
+///
+/// - version 1.0, if the names are exactly the standard Macintosh glyph names,
+/// - version 3.0, if there are no names or they have been dropped with
+///   [`drop_glyph_names`][Self::drop_glyph_names],
+/// - version 2.0 otherwise; names in the standard set are stored as an index
+///   into that set, and other names are stored once, however many glyphs
+///   share them.
+#[derive(Clone, Debug, Default)]
+pub struct PostBuilder {
+    italic_angle: Fixed,
+    underline_position: FWord,
+    underline_thickness: FWord,
+    is_fixed_pitch: bool,
+    glyph_names: Vec<String>,
+    drop_glyph_names: bool,
+}
+
+impl PostBuilder {
+    /// Create a new builder with no glyph names.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add the name of the next glyph.
+    pub fn add_glyph_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.glyph_names.push(name.into());
+        self
+    }
+
+    /// Set the italic angle, in counter-clockwise degrees from the vertical.
+    pub fn italic_angle(&mut self, angle: Fixed) -> &mut Self {
+        self.italic_angle = angle;
+        self
+    }
+
+    /// Set the suggested position and thickness of the underline.
+    pub fn underline(&mut self, position: FWord, thickness: FWord) -> &mut Self {
+        self.underline_position = position;
+        self.underline_thickness = thickness;
+        self
+    }
+
+    /// Set whether the font is monospaced.
+    pub fn fixed_pitch(&mut self, is_fixed_pitch: bool) -> &mut Self {
+        self.is_fixed_pitch = is_fixed_pitch;
+        self
+    }
+
+    /// Omit the glyph names, writing a version 3.0 table.
+    ///
+    /// This makes the font smaller, at the cost of the names no longer being
+    /// available to tools such as PDF text extraction.
+    pub fn drop_glyph_names(&mut self, drop: bool) -> &mut Self {
+        self.drop_glyph_names = drop;
+        self
+    }
+
+    /// Build the table.
+    pub fn build(self) -> Post {
+        let standard_names = crate::read::tables::post::DEFAULT_GLYPH_NAMES;
+        let mut post = if self.drop_glyph_names || self.glyph_names.is_empty() {
+            Post {
+                version: Version16Dot16::VERSION_3_0,
+                ..Default::default()
+            }
+        } else if self.glyph_names.iter().eq(standard_names.iter()) {
+            Post {
+                version: Version16Dot16::VERSION_1_0,
+                ..Default::default()
+            }
+        } else {
+            Post::new_v2(self.glyph_names.iter().map(String::as_str))
+        };
+        post.italic_angle = self.italic_angle;
+        post.underline_position = self.underline_position;
+        post.underline_thickness = self.underline_thickness;
+        post.is_fixed_pitch = self.is_fixed_pitch as u32;
+        post
+    }
+}
+
 impl Post {
     /// Construct a new version 2.0 table from a glyph order.
+    ///
+    /// Names in the standard Macintosh set are stored as an index into that
+    /// set; other names are stored once, even if they are used by more than
+    /// one glyph.
     pub fn new_v2<'a>(order: impl IntoIterator<Item = &'a str>) -> Self {
         let known_glyphs = crate::read::tables::post::DEFAULT_GLYPH_NAMES
             .iter()
             .enumerate()
             .map(|(i, name)| (*name, i as u16))
             .collect::<HashMap<_, _>>();
+        let mut custom_glyphs = HashMap::new();
         let mut name_index = Vec::new();
         let mut storage = Vec::new();
 
         for name in order {
-            match known_glyphs.get(name) {
+            match known_glyphs.get(name).or_else(|| custom_glyphs.get(name)) {
                 Some(i) => name_index.push(*i),
                 None => {
                     let idx = (known_glyphs.len() + storage.len()).try_into().unwrap();
                     name_index.push(idx);
+                    custom_glyphs.insert(name, idx);
                     storage.push(PString(name.into()));
                 }
             }
@@ -98,4 +188,55 @@ mod tests {
         assert_eq!(loaded.glyph_name(GlyphId::new(4)), Some("flarb"));
         assert_eq!(loaded.glyph_name(GlyphId::new(5)), Some("C"));
     }
+
+    #[test]
+    fn compilev2_shared_names() {
+        let post = Post::new_v2([".notdef", "flarb", "A", "flarb", "blah", "flarb"]);
+        assert_eq!(
+            post.glyph_name_index.as_deref(),
+            Some([0, 258, 36, 258, 259, 258].as_slice())
+        );
+        assert_eq!(post.string_data.as_ref().unwrap().len(), 2);
+        let dumped = crate::dump_table(&post).unwrap();
+        let loaded = read::tables::post::Post::read(FontData::new(&dumped)).unwrap();
+        assert_eq!(loaded.glyph_name(GlyphId::new(5)), Some("flarb"));
+        assert_eq!(loaded.glyph_name(GlyphId::new(4)), Some("blah"));
+    }
+
+    #[test]
+    fn builder_versions() {
+        let mut builder = PostBuilder::new();
+        for name in read::tables::post::DEFAULT_GLYPH_NAMES {
+            builder.add_glyph_name(name);
+        }
+        let mut dropped = builder.clone();
+        assert_eq!(builder.clone().build().version, Version16Dot16::VERSION_1_0);
+        dropped.drop_glyph_names(true);
+        assert_eq!(dropped.build().version, Version16Dot16::VERSION_3_0);
+        assert_eq!(
+            PostBuilder::new().build().version,
+            Version16Dot16::VERSION_3_0
+        );
+        builder.add_glyph_name("flarb");
+        assert_eq!(builder.build().version, Version16Dot16::VERSION_2_0);
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = PostBuilder::new();
+        builder
+            .add_glyph_name(".notdef")
+            .add_glyph_name("uni0041.alt")
+            .italic_angle(Fixed::from_f64(-12.5))
+            .underline(FWord::new(-100), FWord::new(50))
+            .fixed_pitch(true);
+        let dumped = crate::dump_table(&builder.build()).unwrap();
+        let loaded = read::tables::post::Post::read(FontData::new(&dumped)).unwrap();
+        assert_eq!(loaded.version(), Version16Dot16::VERSION_2_0);
+        assert_eq!(loaded.glyph_name(GlyphId::new(1)), Some("uni0041.alt"));
+        assert_eq!(loaded.italic_angle(), Fixed::from_f64(-12.5));
+        assert_eq!(loaded.underline_position(), FWord::new(-100));
+        assert_eq!(loaded.underline_thickness(), FWord::new(50));
+        assert_eq!(loaded.is_fixed_pitch(), 1);
+    }
 }
@@ -0,0 +1,355 @@
+//! The [sbix (Standard Bitmap Graphics)](https://learn.microsoft.com/en-us/typography/opentype/spec/sbix) table
+
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
+
+use read_fonts::TopLevelTable;
+use types::{GlyphId, Tag};
+
+use super::cbdt::png_size;
+use crate::{
+    validate::{Validate, ValidationCtx},
+    FontWrite, TableWriter,
+};
+
+/// The size of the table header, without the strike offsets.
+const HEADER_LEN: usize = 8;
+/// The size of a strike header, without the glyph data offsets.
+const STRIKE_HEADER_LEN: usize = 4;
+/// The size of the header of each glyph's data.
+const GLYPH_HEADER_LEN: usize = 8;
+/// The graphic type of PNG data.
+const PNG: Tag = Tag::new(b"png ");
+/// The graphic type of a reference to the data of another glyph.
+pub(crate) const DUPE: Tag = Tag::new(b"dupe");
+/// The default resolution of a strike, in pixels per inch.
+const DEFAULT_PPI: u16 = 72;
+
+/// The [sbix] table.
+///
+/// [sbix]: https://learn.microsoft.com/en-us/typography/opentype/spec/sbix
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sbix {
+    /// Draw the outlines of each glyph in addition to its bitmap.
+    pub draw_outlines: bool,
+    /// The strikes, one for each size of bitmaps.
+    pub strikes: Vec<Strike>,
+}
+
+/// The bitmaps of all glyphs at a single size.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Strike {
+    /// The size in pixels per em for which the bitmaps were designed.
+    pub ppem: u16,
+    /// The resolution in pixels per inch for which the bitmaps were designed.
+    pub ppi: u16,
+    /// The data of each glyph in the font, indexed by glyph id.
+    pub glyphs: Vec<Option<GlyphData>>,
+}
+
+/// The bitmap of a single glyph.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GlyphData {
+    /// The horizontal position of the bitmap relative to the glyph origin.
+    pub origin_offset_x: i16,
+    /// The vertical position of the bitmap relative to the glyph origin.
+    pub origin_offset_y: i16,
+    /// The format of the data, such as `png `.
+    pub graphic_type: Tag,
+    pub data: Vec<u8>,
+}
+
+/// A builder for the `sbix` table.
+///
+/// Glyph bitmaps are PNG data, added for each strike by its size in pixels
+/// per em. A bitmap that is identical to the bitmap of an earlier glyph in
+/// the same strike is stored as a reference to that glyph.
+#[derive(Clone, Debug, Default)]
+pub struct SbixBuilder {
+    draw_outlines: bool,
+    ppis: BTreeMap<u16, u16>,
+    strikes: BTreeMap<u16, BTreeMap<GlyphId, GlyphData>>,
+}
+
+/// An error in the input to [`SbixBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SbixInputError {
+    /// A glyph id is not less than the number of glyphs in the font.
+    GlyphOutOfRange { glyph: GlyphId, num_glyphs: u16 },
+    /// The bitmap of a glyph is not PNG data.
+    InvalidPng { ppem: u16, glyph: GlyphId },
+}
+
+impl Sbix {
+    /// Create a new table from its strikes.
+    pub fn new(strikes: Vec<Strike>) -> Self {
+        Sbix {
+            draw_outlines: false,
+            strikes,
+        }
+    }
+}
+
+impl Strike {
+    fn len(&self) -> usize {
+        let data_len = self
+            .glyphs
+            .iter()
+            .flatten()
+            .map(|glyph| GLYPH_HEADER_LEN + glyph.data.len())
+            .sum::<usize>();
+        STRIKE_HEADER_LEN + (self.glyphs.len() + 1) * 4 + data_len
+    }
+
+    /// Replace the data of each glyph that repeats the data of an earlier
+    /// glyph with a reference to that glyph.
+    pub(crate) fn share_duplicates(&mut self) {
+        let mut seen = HashMap::<(Tag, Vec<u8>), u16>::new();
+        for (glyph, data) in self.glyphs.iter_mut().enumerate() {
+            let Some(data) = data.as_mut().filter(|data| data.graphic_type != DUPE) else {
+                continue;
+            };
+            match seen.entry((data.graphic_type, data.data.clone())) {
+                Entry::Occupied(original) => {
+                    data.graphic_type = DUPE;
+                    data.data = original.get().to_be_bytes().to_vec();
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(glyph as u16);
+                }
+            }
+        }
+    }
+}
+
+impl SbixBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draw the outlines of each glyph in addition to its bitmap.
+    pub fn set_draw_outlines(&mut self, draw_outlines: bool) {
+        self.draw_outlines = draw_outlines;
+    }
+
+    /// Set the resolution of the strike with the given size.
+    ///
+    /// The default is 72 pixels per inch.
+    pub fn set_ppi(&mut self, ppem: u16, ppi: u16) {
+        self.ppis.insert(ppem, ppi);
+    }
+
+    /// Add the bitmap of a glyph in the strike with the given size, replacing
+    /// any existing bitmap.
+    ///
+    /// The origin offset is the position of the bottom left corner of the
+    /// bitmap relative to the glyph origin, in pixels.
+    pub fn add_png(&mut self, ppem: u16, glyph: GlyphId, png: Vec<u8>, origin_offset: (i16, i16)) {
+        let (origin_offset_x, origin_offset_y) = origin_offset;
+        self.strikes.entry(ppem).or_default().insert(
+            glyph,
+            GlyphData {
+                origin_offset_x,
+                origin_offset_y,
+                graphic_type: PNG,
+                data: png,
+            },
+        );
+    }
+
+    /// Build the table, checking the input against the number of glyphs in
+    /// the font.
+    pub fn build(self, num_glyphs: u16) -> Result<Sbix, SbixInputError> {
+        let mut strikes = Vec::with_capacity(self.strikes.len());
+        for (ppem, bitmaps) in self.strikes {
+            let mut glyphs = vec![None; num_glyphs as usize];
+            for (glyph, data) in bitmaps {
+                if glyph.to_u16() >= num_glyphs {
+                    return Err(SbixInputError::GlyphOutOfRange { glyph, num_glyphs });
+                }
+                if png_size(&data.data).is_none() {
+                    return Err(SbixInputError::InvalidPng { ppem, glyph });
+                }
+                glyphs[glyph.to_u16() as usize] = Some(data);
+            }
+            let mut strike = Strike {
+                ppem,
+                ppi: self.ppis.get(&ppem).copied().unwrap_or(DEFAULT_PPI),
+                glyphs,
+            };
+            strike.share_duplicates();
+            strikes.push(strike);
+        }
+        Ok(Sbix {
+            draw_outlines: self.draw_outlines,
+            strikes,
+        })
+    }
+}
+
+impl TopLevelTable for Sbix {
+    const TAG: Tag = Tag::SBIX;
+}
+
+impl FontWrite for Sbix {
+    fn write_into(&self, writer: &mut TableWriter) {
+        1u16.write_into(writer);
+        // bit 0 must always be set
+        let flags = if self.draw_outlines { 0b11u16 } else { 0b01 };
+        flags.write_into(writer);
+        (self.strikes.len() as u32).write_into(writer);
+        // the strikes follow the header, in the same order
+        let mut offset = HEADER_LEN + self.strikes.len() * 4;
+        for strike in &self.strikes {
+            (offset as u32).write_into(writer);
+            offset += strike.len();
+        }
+        self.strikes.write_into(writer);
+    }
+}
+
+impl FontWrite for Strike {
+    fn write_into(&self, writer: &mut TableWriter) {
+        self.ppem.write_into(writer);
+        self.ppi.write_into(writer);
+        let mut offset = STRIKE_HEADER_LEN + (self.glyphs.len() + 1) * 4;
+        (offset as u32).write_into(writer);
+        for glyph in &self.glyphs {
+            if let Some(glyph) = glyph {
+                offset += GLYPH_HEADER_LEN + glyph.data.len();
+            }
+            (offset as u32).write_into(writer);
+        }
+        for glyph in self.glyphs.iter().flatten() {
+            glyph.origin_offset_x.write_into(writer);
+            glyph.origin_offset_y.write_into(writer);
+            glyph.graphic_type.write_into(writer);
+            writer.write_slice(&glyph.data);
+        }
+    }
+}
+
+impl Validate for Sbix {
+    fn validate_impl(&self, ctx: &mut ValidationCtx) {
+        ctx.in_table("Sbix", |ctx| {
+            ctx.in_field("strikes", |ctx| {
+                let num_glyphs = self.strikes.first().map(|strike| strike.glyphs.len());
+                ctx.in_array(|ctx| {
+                    for strike in &self.strikes {
+                        ctx.array_item(|ctx| {
+                            if Some(strike.glyphs.len()) != num_glyphs {
+                                ctx.report("all strikes must have the same number of glyphs");
+                            }
+                        })
+                    }
+                })
+            })
+        })
+    }
+}
+
+impl std::fmt::Display for SbixInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SbixInputError::GlyphOutOfRange { glyph, num_glyphs } => write!(
+                f,
+                "glyph {} is out of range for a font with {num_glyphs} glyphs",
+                glyph.to_u16()
+            ),
+            SbixInputError::InvalidPng { ppem, glyph } => write!(
+                f,
+                "bitmap of glyph {} at {ppem} ppem is not a PNG",
+                glyph.to_u16()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SbixInputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gid(id: u16) -> GlyphId {
+        GlyphId::new(id)
+    }
+
+    /// The start of a PNG file, with some distinguishing pixel data.
+    fn png(width: u32, height: u32, pixels: u8) -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend(width.to_be_bytes());
+        png.extend(height.to_be_bytes());
+        png.push(pixels);
+        png
+    }
+
+    fn read_u32(data: &[u8], pos: usize) -> u32 {
+        u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = SbixBuilder::new();
+        builder.add_png(20, gid(1), png(20, 20, 1), (0, -4));
+        builder.add_png(20, gid(3), png(20, 20, 1), (1, -4));
+        builder.add_png(40, gid(1), png(40, 40, 1), (0, -8));
+        builder.set_ppi(40, 144);
+        let sbix = builder.build(4).unwrap();
+        assert_eq!(sbix.strikes.len(), 2);
+        assert_eq!(sbix.strikes[1].ppi, 144);
+        let dupe = sbix.strikes[0].glyphs[3].as_ref().unwrap();
+        assert_eq!(dupe.graphic_type, DUPE);
+        assert_eq!(dupe.data, [0, 1]);
+
+        let dumped = crate::dump_table(&sbix).unwrap();
+        assert_eq!(&dumped[..4], &[0, 1, 0, 1]);
+        assert_eq!(read_u32(&dumped, 4), 2);
+        let strike = read_u32(&dumped, 8) as usize;
+        assert_eq!(strike, 16);
+        assert_eq!(
+            read_u32(&dumped, 12) as usize,
+            strike + sbix.strikes[0].len()
+        );
+
+        // ppem, ppi, then five offsets
+        let offsets = (0..5)
+            .map(|i| read_u32(&dumped, strike + 4 + i * 4) as usize)
+            .collect::<Vec<_>>();
+        let png_len = png(20, 20, 1).len();
+        assert_eq!(
+            offsets,
+            [
+                24,
+                24,
+                24 + 8 + png_len,
+                24 + 8 + png_len,
+                24 + 8 + png_len + 10
+            ]
+        );
+        let glyph = strike + offsets[1];
+        assert_eq!(&dumped[glyph + 4..glyph + 8], b"png ");
+        assert_eq!(&dumped[glyph + 8..glyph + 8 + png_len], png(20, 20, 1));
+    }
+
+    #[test]
+    fn builder_errors() {
+        let mut builder = SbixBuilder::new();
+        builder.add_png(20, gid(4), png(20, 20, 1), (0, 0));
+        assert_eq!(
+            builder.clone().build(4).unwrap_err(),
+            SbixInputError::GlyphOutOfRange {
+                glyph: gid(4),
+                num_glyphs: 4
+            }
+        );
+        builder.add_png(20, gid(1), b"GIF89a".to_vec(), (0, 0));
+        assert_eq!(
+            builder.build(5).unwrap_err(),
+            SbixInputError::InvalidPng {
+                ppem: 20,
+                glyph: gid(1)
+            }
+        );
+    }
+}
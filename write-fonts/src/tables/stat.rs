@@ -2,12 +2,16 @@
 
 include!("../../generated/generated_stat.rs");
 
+use std::collections::HashSet;
+
+use super::name::NameIdAllocator;
+
 impl Stat {
-    /// Create a new STAT 1.2 table
+    /// Create a new STAT table
     pub fn new(
         design_axes: Vec<AxisRecord>,
         axis_values: Vec<AxisValue>,
-        elided_fallback_name_id: u16,
+        elided_fallback_name_id: NameId,
     ) -> Self {
         Stat {
             design_axes: design_axes.into(),
@@ -19,6 +23,269 @@ impl Stat {
     }
 }
 
+impl Stat {
+    /// The lowest version that can represent this table: 1.2 if there are
+    /// format 4 axis values, otherwise 1.1 if there is an elided fallback
+    /// name.
+    fn compute_version(&self) -> MajorMinor {
+        if self
+            .offset_to_axis_values
+            .iter()
+            .any(|value| matches!(value.as_ref(), AxisValue::Format4(_)))
+        {
+            MajorMinor::VERSION_1_2
+        } else if self.elided_fallback_name_id.is_some() {
+            MajorMinor::VERSION_1_1
+        } else {
+            MajorMinor::VERSION_1_0
+        }
+    }
+
+    fn validate_design_axes(&self, ctx: &mut ValidationCtx) {
+        let mut tags = HashSet::new();
+        let mut orderings = HashSet::new();
+        for axis in self.design_axes.iter() {
+            if !tags.insert(axis.axis_tag) {
+                ctx.report(format!("duplicate axis '{}'", axis.axis_tag));
+            }
+            if !orderings.insert(axis.axis_ordering) {
+                ctx.report(format!(
+                    "axis '{}' has the same ordering as another axis ({})",
+                    axis.axis_tag, axis.axis_ordering
+                ));
+            }
+        }
+        self.design_axes.validate_impl(ctx);
+    }
+
+    fn validate_axis_values(&self, ctx: &mut ValidationCtx) {
+        let axis_count = self.design_axes.len();
+        let check_index = |ctx: &mut ValidationCtx, axis_index: u16| {
+            if axis_index as usize >= axis_count {
+                ctx.report(format!(
+                    "axis index {axis_index} is out of range for {axis_count} axes"
+                ));
+            }
+        };
+        ctx.in_array(|ctx| {
+            for value in self.offset_to_axis_values.iter() {
+                ctx.array_item(|ctx| match value.as_ref() {
+                    AxisValue::Format1(value) => check_index(ctx, value.axis_index),
+                    AxisValue::Format2(value) => {
+                        check_index(ctx, value.axis_index);
+                        if value.range_min_value > value.nominal_value
+                            || value.nominal_value > value.range_max_value
+                        {
+                            ctx.report("range must satisfy min <= nominal <= max");
+                        }
+                    }
+                    AxisValue::Format3(value) => check_index(ctx, value.axis_index),
+                    AxisValue::Format4(value) => {
+                        let mut seen = HashSet::new();
+                        for record in &value.axis_values {
+                            check_index(ctx, record.axis_index);
+                            if !seen.insert(record.axis_index) {
+                                ctx.report(format!(
+                                    "multiple values for axis index {}",
+                                    record.axis_index
+                                ));
+                            }
+                        }
+                    }
+                })
+            }
+        });
+        self.offset_to_axis_values.validate_impl(ctx);
+    }
+}
+
+/// An error in the input to [`StatBuilder::build`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StatInputError {
+    /// An axis value refers to an axis that was not added to the builder.
+    UnknownAxis(Tag),
+}
+
+impl std::fmt::Display for StatInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatInputError::UnknownAxis(tag) => write!(f, "no design axis with tag '{tag}'"),
+        }
+    }
+}
+
+impl std::error::Error for StatInputError {}
+
+/// The location of an axis value, in user coordinates.
+#[derive(Clone, Debug)]
+enum ValueLocation {
+    Single(Tag, Fixed),
+    Range {
+        axis: Tag,
+        nominal: Fixed,
+        min: Fixed,
+        max: Fixed,
+    },
+    Linked {
+        axis: Tag,
+        value: Fixed,
+        linked: Fixed,
+    },
+    Multi(Vec<(Tag, Fixed)>),
+}
+
+/// A builder for the `STAT` table.
+///
+/// Axes, axis values, and the elided fallback name are described with their
+/// English names; ids for these names are assigned when the table is built.
+/// Axis values refer to axes by tag.
+#[derive(Clone, Debug, Default)]
+pub struct StatBuilder {
+    axes: Vec<(Tag, String, u16)>,
+    values: Vec<(ValueLocation, String, AxisValueTableFlags)>,
+    elided_fallback_name: Option<String>,
+}
+
+impl StatBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a design axis.
+    ///
+    /// The ordering is used by applications to sort the names of axis values
+    /// when composing style names; each axis should have a distinct ordering.
+    pub fn add_axis(&mut self, tag: Tag, name: impl Into<String>, ordering: u16) -> &mut Self {
+        self.axes.push((tag, name.into(), ordering));
+        self
+    }
+
+    /// Add a value for a single axis (format 1).
+    pub fn add_value(
+        &mut self,
+        axis: Tag,
+        value: f64,
+        name: impl Into<String>,
+        flags: AxisValueTableFlags,
+    ) -> &mut Self {
+        let location = ValueLocation::Single(axis, Fixed::from_f64(value));
+        self.values.push((location, name.into(), flags));
+        self
+    }
+
+    /// Add a value that applies to a range of an axis (format 2).
+    pub fn add_range_value(
+        &mut self,
+        axis: Tag,
+        nominal: f64,
+        (min, max): (f64, f64),
+        name: impl Into<String>,
+        flags: AxisValueTableFlags,
+    ) -> &mut Self {
+        let location = ValueLocation::Range {
+            axis,
+            nominal: Fixed::from_f64(nominal),
+            min: Fixed::from_f64(min),
+            max: Fixed::from_f64(max),
+        };
+        self.values.push((location, name.into(), flags));
+        self
+    }
+
+    /// Add a value with a linked value, such as Regular and Bold (format 3).
+    pub fn add_linked_value(
+        &mut self,
+        axis: Tag,
+        value: f64,
+        linked_value: f64,
+        name: impl Into<String>,
+        flags: AxisValueTableFlags,
+    ) -> &mut Self {
+        let location = ValueLocation::Linked {
+            axis,
+            value: Fixed::from_f64(value),
+            linked: Fixed::from_f64(linked_value),
+        };
+        self.values.push((location, name.into(), flags));
+        self
+    }
+
+    /// Add a value for a combination of axes (format 4).
+    pub fn add_multi_axis_value(
+        &mut self,
+        values: &[(Tag, f64)],
+        name: impl Into<String>,
+        flags: AxisValueTableFlags,
+    ) -> &mut Self {
+        let location = ValueLocation::Multi(
+            values
+                .iter()
+                .map(|(tag, value)| (*tag, Fixed::from_f64(*value)))
+                .collect(),
+        );
+        self.values.push((location, name.into(), flags));
+        self
+    }
+
+    /// Set the name used when all of the axis values of a font are elided.
+    ///
+    /// If this is not set, the font subfamily name (name id 2) is used.
+    pub fn elided_fallback_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.elided_fallback_name = Some(name.into());
+        self
+    }
+
+    /// Build the table, using `names` to assign name ids.
+    pub fn build(self, names: &mut impl NameIdAllocator) -> Result<Stat, StatInputError> {
+        let axis_index = |tag: Tag| {
+            self.axes
+                .iter()
+                .position(|(axis_tag, ..)| *axis_tag == tag)
+                .map(|index| index as u16)
+                .ok_or(StatInputError::UnknownAxis(tag))
+        };
+        let mut values = Vec::with_capacity(self.values.len());
+        for (location, name, flags) in &self.values {
+            let name_id = names.name_id(name);
+            let flags = *flags;
+            values.push(match location {
+                ValueLocation::Single(axis, value) => {
+                    AxisValue::format_1(axis_index(*axis)?, flags, name_id, *value)
+                }
+                ValueLocation::Range {
+                    axis,
+                    nominal,
+                    min,
+                    max,
+                } => AxisValue::format_2(axis_index(*axis)?, flags, name_id, *nominal, *min, *max),
+                ValueLocation::Linked {
+                    axis,
+                    value,
+                    linked,
+                } => AxisValue::format_3(axis_index(*axis)?, flags, name_id, *value, *linked),
+                ValueLocation::Multi(values) => {
+                    let records = values
+                        .iter()
+                        .map(|(axis, value)| Ok(AxisValueRecord::new(axis_index(*axis)?, *value)))
+                        .collect::<Result<_, _>>()?;
+                    AxisValue::format_4(flags, name_id, records)
+                }
+            });
+        }
+        let axes = self
+            .axes
+            .iter()
+            .map(|(tag, name, ordering)| AxisRecord::new(*tag, names.name_id(name), *ordering))
+            .collect();
+        let elided_fallback_name_id = match &self.elided_fallback_name {
+            Some(name) => names.name_id(name),
+            None => NameId::SUBFAMILY_NAME,
+        };
+        Ok(Stat::new(axes, values, elided_fallback_name_id))
+    }
+}
+
 // we use a custom conversion here because we use a shim table in read-fonts
 // (required because it is an offset to an array of offsets, which is too recursive for us)
 // but in write-fonts we want to skip the shim table and just use a vec.
@@ -46,12 +313,22 @@ mod tests {
     #[test]
     fn smoke_test() {
         let table = Stat::new(
-            vec![AxisRecord::new(Tag::new(b"wght"), 257, 1)],
+            vec![AxisRecord::new(Tag::new(b"wght"), NameId::new(257), 1)],
             vec![
-                AxisValue::format_1(0, AxisValueTableFlags::empty(), 258, Fixed::from_f64(100.)),
-                AxisValue::format_1(0, AxisValueTableFlags::empty(), 261, Fixed::from_f64(400.)),
+                AxisValue::format_1(
+                    0,
+                    AxisValueTableFlags::empty(),
+                    NameId::new(258),
+                    Fixed::from_f64(100.),
+                ),
+                AxisValue::format_1(
+                    0,
+                    AxisValueTableFlags::empty(),
+                    NameId::new(261),
+                    Fixed::from_f64(400.),
+                ),
             ],
-            0,
+            NameId::COPYRIGHT_NOTICE,
         );
 
         let bytes = dump_table(&table).unwrap();
@@ -65,4 +342,106 @@ mod tests {
         let read_stat::AxisValue::Format1(value2) = value2 else { panic!("wrong format"); };
         assert_eq!(value2.value_name_id(), 261);
     }
+
+    #[test]
+    fn builder() {
+        let wght = Tag::new(b"wght");
+        let ital = Tag::new(b"ital");
+        let mut names = crate::tables::name::NameBuilder::new();
+        let mut builder = StatBuilder::new();
+        builder
+            .add_axis(wght, "Weight", 0)
+            .add_axis(ital, "Italic", 1)
+            .add_value(wght, 300.0, "Light", AxisValueTableFlags::empty())
+            .add_range_value(
+                wght,
+                500.0,
+                (450.0, 550.0),
+                "Medium",
+                AxisValueTableFlags::empty(),
+            )
+            .add_linked_value(
+                wght,
+                400.0,
+                700.0,
+                "Regular",
+                AxisValueTableFlags::ELIDABLE_AXIS_VALUE_NAME,
+            )
+            .add_multi_axis_value(
+                &[(wght, 700.0), (ital, 1.0)],
+                "Bold Italic",
+                AxisValueTableFlags::empty(),
+            )
+            .elided_fallback_name("Regular");
+        let table = builder.build(&mut names).unwrap();
+        assert_eq!(table.elided_fallback_name_id, Some(NameId::new(258)));
+
+        let bytes = dump_table(&table).unwrap();
+        let read = read_stat::Stat::read(FontData::new(&bytes)).unwrap();
+        let axes = read.design_axes().unwrap();
+        assert_eq!(axes[1].axis_tag(), ital);
+        assert_eq!(names.get(axes[1].axis_name_id(), 0x409), Some("Italic"));
+        let values = read
+            .offset_to_axis_values()
+            .unwrap()
+            .axis_values()
+            .map(|value| value.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values.len(), 4);
+        let read_stat::AxisValue::Format2(medium) = &values[1] else {
+            panic!("wrong format");
+        };
+        assert_eq!(medium.range_max_value(), Fixed::from_f64(550.));
+        let read_stat::AxisValue::Format3(regular) = &values[2] else {
+            panic!("wrong format");
+        };
+        assert_eq!(regular.linked_value(), Fixed::from_f64(700.));
+        assert_eq!(regular.value_name_id(), 258);
+        let read_stat::AxisValue::Format4(bold_italic) = &values[3] else {
+            panic!("wrong format");
+        };
+        assert_eq!(bold_italic.axis_values()[1].axis_index(), 1);
+
+        let mut builder = StatBuilder::new();
+        builder.add_value(wght, 400.0, "Regular", AxisValueTableFlags::empty());
+        assert_eq!(
+            builder.build(&mut names).unwrap_err(),
+            StatInputError::UnknownAxis(wght)
+        );
+    }
+
+    #[test]
+    fn validation() {
+        let wght = Tag::new(b"wght");
+        let mut table = Stat::new(
+            vec![
+                AxisRecord::new(wght, NameId::new(256), 0),
+                AxisRecord::new(Tag::new(b"wdth"), NameId::new(257), 0),
+            ],
+            vec![],
+            NameId::SUBFAMILY_NAME,
+        );
+        assert!(dump_table(&table).is_err());
+        table.design_axes[1].axis_ordering = 1;
+        assert!(dump_table(&table).is_ok());
+
+        table.offset_to_axis_values = OffsetMarker::new(vec![AxisValue::format_1(
+            2,
+            AxisValueTableFlags::empty(),
+            NameId::new(258),
+            Fixed::from_f64(400.),
+        )
+        .into()]);
+        assert!(dump_table(&table).is_err());
+        table.offset_to_axis_values = OffsetMarker::new(vec![AxisValue::format_2(
+            0,
+            AxisValueTableFlags::empty(),
+            NameId::new(258),
+            Fixed::from_f64(400.),
+            Fixed::from_f64(500.),
+            Fixed::from_f64(600.),
+        )
+        .into()]);
+        assert!(dump_table(&table).is_err());
+    }
 }
@@ -0,0 +1,452 @@
+//! Computing the regions and deltas of values that vary between masters.
+
+use types::F2Dot14;
+
+use super::{RegionAxisCoordinates, VariationRegion};
+
+/// A model of how values vary between the masters of a variable font.
+///
+/// Given the normalized location of each master, this computes the region in
+/// which each master's delta applies, and how the deltas of earlier masters
+/// contribute at each location. This is the same model as fontTools'
+/// `VariationModel`, with axes in the order of the fvar table, so the regions
+/// and deltas are the same as those compiled by fontTools.
+#[derive(Clone, Debug)]
+pub struct VariationModel {
+    axis_count: usize,
+    // the index of each master, in the order of the model
+    master_order: Vec<usize>,
+    // the region of each master, in model order; the default master's region
+    // is empty
+    supports: Vec<Vec<Support>>,
+    // the contributions of earlier masters at each master's location, in
+    // model order
+    delta_weights: Vec<Vec<(usize, f64)>>,
+}
+
+/// An error in the locations of a [VariationModel]'s masters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VariationModelError {
+    /// No master is at the default location.
+    NoDefaultMaster,
+    /// A location does not have a coordinate for each axis.
+    AxisCountMismatch { expected: usize, found: usize },
+    /// More than one master is at the same location.
+    DuplicateLocation(Vec<F2Dot14>),
+}
+
+/// The (start, peak, end) coordinates of a region on one axis.
+///
+/// Axes that a region does not depend on have a peak of zero.
+type Support = (f64, f64, f64);
+
+impl VariationModel {
+    /// Create a model from the normalized location of each master.
+    ///
+    /// Each location has a coordinate for every axis, and exactly one of them
+    /// must be the default location, where every coordinate is zero.
+    pub fn new(locations: Vec<Vec<F2Dot14>>) -> Result<Self, VariationModelError> {
+        let axis_count = locations.first().map(Vec::len).unwrap_or_default();
+        if let Some(loc) = locations.iter().find(|loc| loc.len() != axis_count) {
+            return Err(VariationModelError::AxisCountMismatch {
+                expected: axis_count,
+                found: loc.len(),
+            });
+        }
+        for (i, loc) in locations.iter().enumerate() {
+            if locations[..i].contains(loc) {
+                return Err(VariationModelError::DuplicateLocation(loc.clone()));
+            }
+        }
+        if !locations
+            .iter()
+            .any(|loc| loc.iter().all(|coord| *coord == F2Dot14::ZERO))
+        {
+            return Err(VariationModelError::NoDefaultMaster);
+        }
+
+        let master_order = master_order(&locations);
+        let sorted = master_order
+            .iter()
+            .map(|i| {
+                locations[*i]
+                    .iter()
+                    .map(|coord| coord.to_f32() as f64)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let supports = compute_supports(&sorted);
+        let delta_weights = sorted
+            .iter()
+            .enumerate()
+            .map(|(i, loc)| {
+                supports[..i]
+                    .iter()
+                    .enumerate()
+                    .map(|(j, support)| (j, support_scalar(loc, support)))
+                    .filter(|(_, scalar)| *scalar != 0.0)
+                    .collect()
+            })
+            .collect();
+        Ok(VariationModel {
+            axis_count,
+            master_order,
+            supports,
+            delta_weights,
+        })
+    }
+
+    /// The regions of the non-default masters, in the order of the model.
+    pub fn regions(&self) -> Vec<VariationRegion> {
+        self.supports[1..]
+            .iter()
+            .map(|support| {
+                VariationRegion::new(
+                    support
+                        .iter()
+                        .map(|(start, peak, end)| {
+                            RegionAxisCoordinates::new(
+                                F2Dot14::from_f32(*start as f32),
+                                F2Dot14::from_f32(*peak as f32),
+                                F2Dot14::from_f32(*end as f32),
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Split the values of each master into deltas.
+    ///
+    /// `values` are in the same order as the locations the model was created
+    /// with. The result has the delta of each of the model's
+    /// [regions](Self::regions), in order; the default value is not included.
+    /// Deltas are rounded as they are computed, so that the rounding errors do
+    /// not accumulate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not a value for each master.
+    pub fn deltas(&self, values: &[i32]) -> Vec<i32> {
+        assert_eq!(
+            values.len(),
+            self.master_order.len(),
+            "one value per master"
+        );
+        let mut deltas = Vec::<i32>::with_capacity(values.len());
+        for (master, weights) in self.master_order.iter().zip(&self.delta_weights) {
+            let mut delta = values[*master] as f64;
+            for (j, weight) in weights {
+                delta -= deltas[*j] as f64 * weight;
+            }
+            deltas.push(ot_round(delta));
+        }
+        deltas.remove(0);
+        deltas
+    }
+
+    /// The number of axes of the master locations.
+    pub fn axis_count(&self) -> usize {
+        self.axis_count
+    }
+
+    /// The number of masters in the model.
+    pub fn master_count(&self) -> usize {
+        self.master_order.len()
+    }
+
+    /// The index of the default master, in the order the locations were given.
+    pub fn default_master(&self) -> usize {
+        self.master_order[0]
+    }
+}
+
+/// The order of masters in the model.
+///
+/// Masters are ordered by the number of axes they are off-default on, then
+/// by how many of those coordinates are also the location of a master that
+/// is only off-default on that axis, and then by their coordinates.
+fn master_order(locations: &[Vec<F2Dot14>]) -> Vec<usize> {
+    let axis_count = locations.first().map(Vec::len).unwrap_or_default();
+    let mut axis_points = vec![vec![F2Dot14::ZERO]; axis_count];
+    for loc in locations {
+        let mut off_default = loc
+            .iter()
+            .enumerate()
+            .filter(|(_, coord)| **coord != F2Dot14::ZERO);
+        if let (Some((axis, coord)), None) = (off_default.next(), off_default.next()) {
+            axis_points[axis].push(*coord);
+        }
+    }
+    let sort_key = |loc: &[F2Dot14]| {
+        let axes = (0..loc.len())
+            .filter(|i| loc[*i] != F2Dot14::ZERO)
+            .collect::<Vec<_>>();
+        let on_point = axes
+            .iter()
+            .filter(|i| axis_points[**i].contains(&loc[**i]))
+            .count();
+        let signs = axes
+            .iter()
+            .map(|i| loc[*i].to_bits().signum())
+            .collect::<Vec<_>>();
+        let magnitudes = axes
+            .iter()
+            .map(|i| (loc[*i].to_bits() as i32).abs())
+            .collect::<Vec<_>>();
+        (
+            axes.len(),
+            std::cmp::Reverse(on_point),
+            axes,
+            signs,
+            magnitudes,
+        )
+    };
+    let mut order = (0..locations.len()).collect::<Vec<_>>();
+    order.sort_by_cached_key(|i| sort_key(&locations[*i]));
+    order
+}
+
+/// The region of each master: the box around its location in which its
+/// delta applies, shrunk so that it does not overlap the locations of
+/// earlier masters.
+fn compute_supports(locations: &[Vec<f64>]) -> Vec<Vec<Support>> {
+    let axis_count = locations.first().map(Vec::len).unwrap_or_default();
+    let (mut min, mut max) = (vec![0.0f64; axis_count], vec![0.0f64; axis_count]);
+    for loc in locations {
+        for (i, coord) in loc.iter().enumerate() {
+            min[i] = min[i].min(*coord);
+            max[i] = max[i].max(*coord);
+        }
+    }
+    let regions = locations
+        .iter()
+        .map(|loc| {
+            loc.iter()
+                .enumerate()
+                .map(|(i, coord)| match *coord {
+                    coord if coord > 0.0 => (0.0, coord, max[i]),
+                    coord if coord < 0.0 => (min[i], coord, 0.0),
+                    _ => (0.0, 0.0, 0.0),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let axes = |region: &[Support]| {
+        region
+            .iter()
+            .map(|(_, peak, _)| *peak != 0.0)
+            .collect::<Vec<_>>()
+    };
+
+    let mut supports = Vec::<Vec<Support>>::with_capacity(regions.len());
+    for (i, region) in regions.iter().enumerate() {
+        let mut region = region.clone();
+        let region_axes = axes(&region);
+        for prev in &regions[..i] {
+            // masters that are off-default on other axes do not participate
+            if axes(prev) != region_axes {
+                continue;
+            }
+            // nor do masters outside the current box
+            let relevant = region.iter().zip(prev).all(|((lower, peak, upper), prev)| {
+                *peak == 0.0 || prev.1 == *peak || (*lower < prev.1 && prev.1 < *upper)
+            });
+            if !relevant {
+                continue;
+            }
+            // split the box on the axes where that keeps the largest ratio of
+            // the range
+            let mut best_ratio = -1.0;
+            let mut best_axes = Vec::new();
+            for (axis, (lower, peak, upper)) in region.iter().enumerate() {
+                let value = prev[axis].1;
+                if *peak == 0.0 || value == *peak {
+                    continue;
+                }
+                let (new_lower, new_upper, ratio) = if value < *peak {
+                    (value, *upper, (value - peak) / (lower - peak))
+                } else {
+                    (*lower, value, (value - peak) / (upper - peak))
+                };
+                if ratio > best_ratio {
+                    best_ratio = ratio;
+                    best_axes.clear();
+                }
+                if ratio == best_ratio {
+                    best_axes.push((axis, (new_lower, *peak, new_upper)));
+                }
+            }
+            for (axis, support) in best_axes {
+                region[axis] = support;
+            }
+        }
+        supports.push(region);
+    }
+    supports
+}
+
+/// The scalar of a region's delta at a location.
+fn support_scalar(location: &[f64], support: &[Support]) -> f64 {
+    let mut scalar = 1.0;
+    for (coord, (lower, peak, upper)) in location.iter().zip(support) {
+        if *peak == 0.0 || lower > peak || peak > upper || (*lower < 0.0 && *upper > 0.0) {
+            continue;
+        }
+        if coord == peak {
+            continue;
+        }
+        if coord <= lower || upper <= coord {
+            return 0.0;
+        }
+        if coord < peak {
+            scalar *= (coord - lower) / (peak - lower);
+        } else {
+            scalar *= (coord - upper) / (peak - upper);
+        }
+    }
+    scalar
+}
+
+fn ot_round(value: f64) -> i32 {
+    (value + 0.5).floor() as i32
+}
+
+impl std::fmt::Display for VariationModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariationModelError::NoDefaultMaster => {
+                f.write_str("no master is at the default location")
+            }
+            VariationModelError::AxisCountMismatch { expected, found } => write!(
+                f,
+                "expected a location with {expected} axes, found {found} axes"
+            ),
+            VariationModelError::DuplicateLocation(loc) => {
+                write!(f, "more than one master is at location {loc:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VariationModelError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loc(coords: &[f32]) -> Vec<F2Dot14> {
+        coords.iter().copied().map(F2Dot14::from_f32).collect()
+    }
+
+    #[test]
+    fn corner_master() {
+        // wght, wdth
+        let model = VariationModel::new(vec![
+            loc(&[0.0, 0.0]),
+            loc(&[1.0, 1.0]),
+            loc(&[1.0, 0.0]),
+            loc(&[0.0, 1.0]),
+        ])
+        .unwrap();
+        assert_eq!(model.master_order, [0, 2, 3, 1]);
+        let regions = model.regions();
+        assert_eq!(regions.len(), 3);
+        let peaks = regions
+            .iter()
+            .map(|region| {
+                region
+                    .region_axes
+                    .iter()
+                    .map(|axis| axis.peak_coord.to_f32())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(peaks, [[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]]);
+        // default 100, wght 150, wdth 120, both 200
+        assert_eq!(model.deltas(&[100, 200, 150, 120]), [50, 20, 30]);
+    }
+
+    #[test]
+    fn intermediate_masters() {
+        let model =
+            VariationModel::new(vec![loc(&[0.0]), loc(&[1.0]), loc(&[0.5]), loc(&[-1.0])]).unwrap();
+        let supports = model.supports[1..]
+            .iter()
+            .map(|support| support[0])
+            .collect::<Vec<_>>();
+        // the intermediate master splits the region of the maximum
+        assert_eq!(
+            supports,
+            [(-1.0, -1.0, 0.0), (0.0, 0.5, 1.0), (0.5, 1.0, 1.0)]
+        );
+        // a linear axis needs no correction at the intermediate master
+        assert_eq!(model.deltas(&[400, 700, 550, 100]), [-300, 150, 300]);
+        assert_eq!(model.deltas(&[400, 700, 500, 100]), [-300, 100, 300]);
+    }
+
+    #[test]
+    fn fonttools_example() {
+        // the example from the documentation of fontTools' VariationModel,
+        // with wght normalized to 180
+        let wght = |value: f32| value / 180.0;
+        let model = VariationModel::new(vec![
+            loc(&[wght(100.0), 0.0]),
+            loc(&[wght(-100.0), 0.0]),
+            loc(&[wght(-180.0), 0.0]),
+            loc(&[0.0, 0.3]),
+            loc(&[wght(120.0), 0.3]),
+            loc(&[wght(120.0), 0.2]),
+            loc(&[0.0, 0.0]),
+            loc(&[wght(180.0), 0.3]),
+            loc(&[wght(180.0), 0.0]),
+        ])
+        .unwrap();
+        assert_eq!(model.master_order, [6, 1, 2, 0, 8, 3, 7, 4, 5]);
+        let expected: [&[(usize, f64)]; 9] = [
+            &[],
+            &[(0, 1.0)],
+            &[(0, 1.0)],
+            &[(0, 1.0)],
+            &[(0, 1.0)],
+            &[(0, 1.0)],
+            &[(0, 1.0), (4, 1.0), (5, 1.0)],
+            &[(0, 1.0), (3, 0.75), (4, 0.25), (5, 1.0), (6, 0.6666666)],
+            &[
+                (0, 1.0),
+                (3, 0.75),
+                (4, 0.25),
+                (5, 0.6666666),
+                (6, 0.4444444),
+                (7, 0.6666666),
+            ],
+        ];
+        for (weights, expected) in model.delta_weights.iter().zip(expected) {
+            assert_eq!(weights.len(), expected.len(), "{weights:?}");
+            for ((j, weight), (expected_j, expected_weight)) in weights.iter().zip(expected) {
+                assert_eq!(j, expected_j);
+                assert!((weight - expected_weight).abs() < 1e-3, "{weights:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn bad_locations() {
+        assert_eq!(
+            VariationModel::new(vec![loc(&[1.0]), loc(&[-1.0])]).unwrap_err(),
+            VariationModelError::NoDefaultMaster
+        );
+        assert_eq!(
+            VariationModel::new(vec![loc(&[0.0]), loc(&[1.0, 0.0])]).unwrap_err(),
+            VariationModelError::AxisCountMismatch {
+                expected: 1,
+                found: 2
+            }
+        );
+        assert!(matches!(
+            VariationModel::new(vec![loc(&[0.0]), loc(&[1.0]), loc(&[1.0])]),
+            Err(VariationModelError::DuplicateLocation(_))
+        ));
+    }
+}
@@ -2,7 +2,18 @@
 
 include!("../../generated/generated_variations.rs");
 
+use std::collections::BTreeMap;
+
 pub use read_fonts::tables::variations::TupleIndex;
+use read_fonts::tables::variations::TupleVariationCount;
+
+#[path = "./variation_model.rs"]
+mod model;
+pub use model::{VariationModel, VariationModelError};
+
+#[path = "./ivs_builder.rs"]
+mod ivs_builder;
+pub use ivs_builder::{TemporaryDeltaSetId, VariationIndexRemapping, VariationStoreBuilder};
 
 impl VariationRegionList {
     fn compute_axis_count(&self) -> usize {
@@ -11,14 +22,78 @@ impl VariationRegionList {
             .first()
             .map(|reg| reg.region_axes.len())
             .unwrap_or(0);
-        //TODO: check this at validation time
-        debug_assert!(self
-            .variation_regions
-            .iter()
-            .map(|reg| reg.region_axes.len())
-            .all(|n| n == count));
         count
     }
+
+    fn validate_variation_regions(&self, ctx: &mut ValidationCtx) {
+        let axis_count = self.compute_axis_count();
+        if !self.variation_regions.is_empty()
+            && (axis_count == 0
+                || self
+                    .variation_regions
+                    .iter()
+                    .any(|reg| reg.region_axes.len() != axis_count))
+        {
+            ctx.report("all regions must have the same non-zero number of axes");
+        }
+        self.variation_regions.validate_impl(ctx);
+    }
+}
+
+impl ItemVariationData {
+    fn validate_delta_sets(&self, ctx: &mut ValidationCtx) {
+        let long_words = self.word_delta_count & 0x8000 != 0;
+        let (word_size, small_size) = if long_words { (4, 2) } else { (2, 1) };
+        let word_delta_count = (self.word_delta_count & 0x7FFF) as usize;
+        let region_count = self.region_indexes.len();
+        if word_delta_count > region_count {
+            ctx.report(format!(
+                "word_delta_count {word_delta_count} is larger than the region count {region_count}"
+            ));
+        }
+        let row_size = word_delta_count * word_size
+            + region_count.saturating_sub(word_delta_count) * small_size;
+        let expected = self.item_count as usize * row_size;
+        if self.delta_sets.len() != expected {
+            ctx.report(format!(
+                "expected {expected} bytes of deltas for {} items, found {}",
+                self.item_count,
+                self.delta_sets.len()
+            ));
+        }
+    }
+}
+
+impl DeltaSetIndexMap {
+    /// Create a map from the (outer, inner) delta-set index of each item.
+    ///
+    /// Entries use the fewest bits needed for the largest indices, and format 1
+    /// is only used if there are more entries than fit in format 0.
+    pub fn from_entries(entries: &[(u16, u16)]) -> Self {
+        let bit_len = |value: u16| (u16::BITS - value.leading_zeros()) as u8;
+        let max_outer = entries.iter().map(|(outer, _)| *outer).max();
+        let max_inner = entries.iter().map(|(_, inner)| *inner).max();
+        let inner_bits = bit_len(max_inner.unwrap_or_default()).max(1);
+        let outer_bits = bit_len(max_outer.unwrap_or_default());
+        let entry_size = (outer_bits + inner_bits).div_ceil(8);
+        let entry_format =
+            EntryFormat::from_bits_truncate(((entry_size - 1) << 4) | (inner_bits - 1));
+
+        let map_data = entries
+            .iter()
+            .flat_map(|(outer, inner)| {
+                let entry = ((*outer as u32) << inner_bits) | *inner as u32;
+                entry
+                    .to_be_bytes()
+                    .into_iter()
+                    .skip(4 - entry_size as usize)
+            })
+            .collect();
+        match u16::try_from(entries.len()) {
+            Ok(map_count) => DeltaSetIndexMap::format_0(entry_format, map_count, map_data),
+            Err(_) => DeltaSetIndexMap::format_1(entry_format, entries.len() as u32, map_data),
+        }
+    }
 }
 
 impl FontWrite for TupleIndex {
@@ -27,6 +102,239 @@ impl FontWrite for TupleIndex {
     }
 }
 
+/// The tuple variation count is stored in the low 12 bits of its field.
+const MAX_TUPLE_VARIATIONS: usize = 0x0FFF;
+
+/// A single variation, with its point numbers and deltas already packed,
+/// ready to be written in a [tuple variation store].
+///
+/// [tuple variation store]: https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#tuple-variation-store
+pub(crate) struct PackedTupleVariation<'a> {
+    pub(crate) peak_tuple: &'a [F2Dot14],
+    /// The intermediate region, if it is not the one implied by the peak.
+    pub(crate) intermediate_region: Option<(&'a [F2Dot14], &'a [F2Dot14])>,
+    pub(crate) point_numbers: Vec<u8>,
+    pub(crate) deltas: Vec<u8>,
+}
+
+/// The parts of a tuple variation store, as shared by `gvar` and `cvar`.
+///
+/// The tables differ only in what comes before the tuple variation count
+/// and the offset of the serialized data, which is relative to the start of
+/// the table (in `cvar`) or of the glyph's data (in `gvar`).
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TupleVariationStore {
+    /// The tuple variation count, including the shared point numbers flag.
+    pub(crate) count: u16,
+    pub(crate) headers: Vec<u8>,
+    pub(crate) serialized: Vec<u8>,
+}
+
+impl TupleVariationStore {
+    /// Serialize a set of variations.
+    ///
+    /// Peak tuples that are in `shared_tuples` are written as an index into
+    /// the shared tuples; the others are embedded in their headers. The most
+    /// profitable set of point numbers is shared between variations.
+    ///
+    /// Returns `None` if there are too many variations, or a variation has
+    /// too much data, to be encoded.
+    pub(crate) fn compile(
+        variations: &[PackedTupleVariation],
+        shared_tuples: &BTreeMap<&[F2Dot14], u16>,
+    ) -> Option<Self> {
+        if variations.len() > MAX_TUPLE_VARIATIONS {
+            return None;
+        }
+        let point_numbers = variations
+            .iter()
+            .map(|var| var.point_numbers.clone())
+            .collect::<Vec<_>>();
+        let shared_points = compute_shared_points(&point_numbers);
+
+        let mut headers = Vec::new();
+        let mut serialized = Vec::new();
+        if let Some(shared) = shared_points {
+            serialized.extend_from_slice(shared);
+        }
+        for var in variations {
+            let start = serialized.len();
+            let mut tuple_index = 0u16;
+            if shared_points != Some(var.point_numbers.as_slice()) {
+                tuple_index |= TupleIndex::PRIVATE_POINT_NUMBERS;
+                serialized.extend_from_slice(&var.point_numbers);
+            }
+            serialized.extend_from_slice(&var.deltas);
+            let data_size: u16 = (serialized.len() - start).try_into().ok()?;
+
+            let shared_index = shared_tuples.get(var.peak_tuple);
+            match shared_index {
+                Some(index) => tuple_index |= *index,
+                None => tuple_index |= TupleIndex::EMBEDDED_PEAK_TUPLE,
+            }
+            if var.intermediate_region.is_some() {
+                tuple_index |= TupleIndex::INTERMEDIATE_REGION;
+            }
+
+            headers.extend_from_slice(&data_size.to_be_bytes());
+            headers.extend_from_slice(&tuple_index.to_be_bytes());
+            if shared_index.is_none() {
+                write_tuple(var.peak_tuple, &mut headers);
+            }
+            if let Some((start, end)) = var.intermediate_region {
+                write_tuple(start, &mut headers);
+                write_tuple(end, &mut headers);
+            }
+        }
+
+        let mut count = variations.len() as u16;
+        if shared_points.is_some() {
+            count |= TupleVariationCount::SHARED_POINT_NUMBERS;
+        }
+        Some(TupleVariationStore {
+            count,
+            headers,
+            serialized,
+        })
+    }
+}
+
+/// Choose the packed point numbers that save the most bytes if shared, if any.
+fn compute_shared_points(point_numbers: &[Vec<u8>]) -> Option<&[u8]> {
+    let mut counts = BTreeMap::<&[u8], usize>::new();
+    for points in point_numbers {
+        *counts.entry(points.as_slice()).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .max_by_key(|(points, count)| (count - 1) * points.len())
+        .map(|(points, _)| points)
+}
+
+/// The intermediate region, if it differs from the one implied by the peak
+/// tuple, spanning from zero to the peak on each axis.
+pub(crate) fn explicit_intermediate_region<'a>(
+    peak_tuple: &Tuple,
+    intermediate_region: Option<&'a (Tuple, Tuple)>,
+) -> Option<&'a (Tuple, Tuple)> {
+    intermediate_region.filter(|(start, end)| {
+        let zero = F2Dot14::ZERO;
+        peak_tuple
+            .values
+            .iter()
+            .zip(start.values.iter().zip(&end.values))
+            .any(|(peak, (start, end))| *start != zero.min(*peak) || *end != zero.max(*peak))
+    })
+}
+
+pub(crate) fn write_tuple(tuple: &[F2Dot14], out: &mut Vec<u8>) {
+    for value in tuple {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+/// Encode [packed point numbers]; `None` means all points.
+///
+/// [packed point numbers]: https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#packed-point-numbers
+pub(crate) fn encode_point_numbers(points: Option<&[u16]>) -> Vec<u8> {
+    const POINTS_ARE_WORDS: u8 = 0x80;
+    const MAX_RUN_LEN: usize = 128;
+
+    let Some(points) = points else {
+        return vec![0];
+    };
+    let mut out = Vec::new();
+    // point numbers are 16 bits, so the count always fits in 15
+    if points.len() < 0x80 {
+        out.push(points.len() as u8);
+    } else {
+        out.extend_from_slice(&(points.len() as u16 | 0x8000).to_be_bytes());
+    }
+
+    let mut last = 0;
+    let diffs = points
+        .iter()
+        .map(|point| {
+            let diff = point - last;
+            last = *point;
+            diff
+        })
+        .collect::<Vec<_>>();
+    let mut remaining = diffs.as_slice();
+    while let Some(first) = remaining.first() {
+        let words = *first > u8::MAX as u16;
+        let run_len = remaining
+            .iter()
+            .take(MAX_RUN_LEN)
+            .take_while(|diff| (**diff > u8::MAX as u16) == words)
+            .count();
+        let (run, rest) = remaining.split_at(run_len);
+        if words {
+            out.push((run_len - 1) as u8 | POINTS_ARE_WORDS);
+            run.iter()
+                .for_each(|diff| out.extend_from_slice(&diff.to_be_bytes()));
+        } else {
+            out.push((run_len - 1) as u8);
+            out.extend(run.iter().map(|diff| *diff as u8));
+        }
+        remaining = rest;
+    }
+    out
+}
+
+/// Encode [packed deltas].
+///
+/// [packed deltas]: https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#packed-deltas
+pub(crate) fn encode_deltas(deltas: impl Iterator<Item = i16>, out: &mut Vec<u8>) {
+    const DELTAS_ARE_ZERO: u8 = 0x80;
+    const DELTAS_ARE_WORDS: u8 = 0x40;
+    const MAX_RUN_LEN: usize = 64;
+
+    let fits_byte = |val: i16| i8::try_from(val).is_ok();
+    let deltas = deltas.collect::<Vec<_>>();
+    let mut remaining = deltas.as_slice();
+    while let Some(first) = remaining.first().copied() {
+        let run_len = if first == 0 {
+            remaining
+                .iter()
+                .take(MAX_RUN_LEN)
+                .take_while(|val| **val == 0)
+                .count()
+        } else if fits_byte(first) {
+            // a single zero is cheaper inside a byte run than as its own run
+            (0..remaining.len().min(MAX_RUN_LEN))
+                .take_while(|i| {
+                    let val = remaining[*i];
+                    fits_byte(val) && !(val == 0 && remaining.get(i + 1) == Some(&0))
+                })
+                .count()
+        } else {
+            // stay in words until a zero, or two values that fit in bytes
+            (0..remaining.len().min(MAX_RUN_LEN))
+                .take_while(|i| {
+                    let val = remaining[*i];
+                    val != 0
+                        && !(fits_byte(val) && remaining.get(i + 1).copied().is_some_and(fits_byte))
+                })
+                .count()
+        };
+        let (run, rest) = remaining.split_at(run_len);
+        let count_bits = (run_len - 1) as u8;
+        if first == 0 {
+            out.push(count_bits | DELTAS_ARE_ZERO);
+        } else if fits_byte(first) {
+            out.push(count_bits);
+            out.extend(run.iter().map(|val| *val as i8 as u8));
+        } else {
+            out.push(count_bits | DELTAS_ARE_WORDS);
+            run.iter()
+                .for_each(|val| out.extend_from_slice(&val.to_be_bytes()));
+        }
+        remaining = rest;
+    }
+}
+
 //hack: unclear if we're even going to do any codegen for writing, but
 //for the time being this lets us compile
 impl<'a> FromObjRef<Option<read_fonts::tables::variations::Tuple<'a>>> for Vec<F2Dot14> {
@@ -39,3 +347,67 @@ impl<'a> FromObjRef<Option<read_fonts::tables::variations::Tuple<'a>>> for Vec<F
             .unwrap_or_default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::tables::variations::PackedPointNumbers;
+
+    use super::*;
+
+    #[test]
+    fn validate_delta_sets() {
+        // two items, with one word delta and one byte delta each
+        let data = ItemVariationData::new(2, 1, vec![0, 1], vec![0; 6]);
+        assert!(data.validate().is_ok());
+
+        let data = ItemVariationData::new(3, 1, vec![0, 1], vec![0; 6]);
+        let report = data.validate().unwrap_err();
+        assert_eq!(
+            report.errors().next().unwrap().to_string(),
+            "ItemVariationData.delta_sets: expected 9 bytes of deltas for 3 items, found 6"
+        );
+    }
+
+    #[test]
+    fn validate_region_axis_counts() {
+        let region = |n_axes| {
+            let coords = RegionAxisCoordinates::new(F2Dot14::ZERO, F2Dot14::ONE, F2Dot14::ONE);
+            VariationRegion::new(vec![coords; n_axes])
+        };
+        let regions = VariationRegionList::new(vec![region(2), region(2)]);
+        assert!(regions.validate().is_ok());
+
+        let regions = VariationRegionList::new(vec![region(2), region(1)]);
+        let report = regions.validate().unwrap_err();
+        assert_eq!(
+            report.errors().next().unwrap().message(),
+            "all regions must have the same non-zero number of axes"
+        );
+    }
+
+    #[test]
+    fn packed_deltas_spec_example() {
+        // https://learn.microsoft.com/en-us/typography/opentype/spec/otvarcommonformats#packed-deltas
+        let deltas = [10, -105, 0, -58, 0, 0, 0, 0, 0, 0, 0, 0, 4130, -1228];
+        let mut out = Vec::new();
+        encode_deltas(deltas.into_iter(), &mut out);
+        assert_eq!(
+            out,
+            [0x03, 0x0A, 0x97, 0x00, 0xC6, 0x87, 0x41, 0x10, 0x22, 0xFB, 0x34]
+        );
+    }
+
+    #[test]
+    fn packed_point_numbers_round_trip() {
+        let points = (0..200u16)
+            .map(|i| i * 3)
+            .chain([1000, 2000, 2001])
+            .collect::<Vec<_>>();
+        let encoded = encode_point_numbers(Some(&points));
+        let (read, rest) = PackedPointNumbers::split_off_front(FontData::new(&encoded));
+        assert!(rest.is_empty());
+        assert_eq!(read.iter().collect::<Vec<_>>(), points);
+
+        assert_eq!(encode_point_numbers(None), [0]);
+    }
+}
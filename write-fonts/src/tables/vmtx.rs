@@ -3,3 +3,144 @@
 pub use super::hmtx::LongMetric;
 
 include!("../../generated/generated_vmtx.rs");
+
+use super::{glyf::Bbox, vhea::Vhea};
+
+/// A builder for the `vmtx` table.
+///
+/// Glyphs are added in glyph id order. When the table is built, the trailing
+/// run of glyphs that share the same advance height is stored as bare side
+/// bearings, and the metrics in the `vhea` table are recomputed.
+#[derive(Clone, Debug, Default)]
+pub struct VmtxBuilder {
+    glyphs: Vec<(u16, i16, Option<Bbox>)>,
+}
+
+impl VmtxBuilder {
+    /// Create a new, empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a glyph; its id is the number of glyphs previously added.
+    ///
+    /// `vertical_origin` is the y coordinate of the glyph's vertical origin,
+    /// often the ascender of the font. The top side bearing is the distance
+    /// from the vertical origin to the top of the bounding box, which should
+    /// be `None` for glyphs with no contours.
+    pub fn add_glyph(
+        &mut self,
+        advance: u16,
+        vertical_origin: i16,
+        bbox: Option<Bbox>,
+    ) -> &mut Self {
+        self.glyphs.push((advance, vertical_origin, bbox));
+        self
+    }
+
+    /// Build the table, updating the metrics in `vhea` to match.
+    ///
+    /// This sets `number_of_long_ver_metrics`, `advance_height_max`,
+    /// `min_top_side_bearing`, `min_bottom_side_bearing` and `y_max_extent`.
+    pub fn build(self, vhea: &mut Vhea) -> Vmtx {
+        let num_long_metrics = match self.glyphs.last() {
+            Some((last_advance, ..)) => {
+                let run = self
+                    .glyphs
+                    .iter()
+                    .rev()
+                    .take_while(|(advance, ..)| advance == last_advance)
+                    .count();
+                self.glyphs.len() - run + 1
+            }
+            None => 0,
+        };
+
+        let clamp = |value: i32| value.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        let side_bearing = |(_, origin, bbox): &(u16, i16, Option<Bbox>)| {
+            bbox.map(|bbox| clamp(*origin as i32 - bbox.y_max as i32))
+                .unwrap_or(0)
+        };
+        let mut advance_height_max = 0u16;
+        let mut extents: Option<(i16, i16, i16)> = None;
+        for glyph in &self.glyphs {
+            let (advance, _, bbox) = glyph;
+            advance_height_max = advance_height_max.max(*advance);
+            let Some(bbox) = bbox else {
+                continue;
+            };
+            let tsb = side_bearing(glyph) as i32;
+            let extent = tsb + (bbox.y_max as i32 - bbox.y_min as i32);
+            let bsb = *advance as i32 - extent;
+            let (tsb, bsb, extent) = (clamp(tsb), clamp(bsb), clamp(extent));
+            extents = Some(match extents {
+                Some((min_tsb, min_bsb, max_extent)) => {
+                    (min_tsb.min(tsb), min_bsb.min(bsb), max_extent.max(extent))
+                }
+                None => (tsb, bsb, extent),
+            });
+        }
+        let (min_tsb, min_bsb, max_extent) = extents.unwrap_or_default();
+        vhea.number_of_long_ver_metrics = num_long_metrics as u16;
+        vhea.advance_height_max = UfWord::new(advance_height_max);
+        vhea.min_top_side_bearing = FWord::new(min_tsb);
+        vhea.min_bottom_side_bearing = FWord::new(min_bsb);
+        vhea.y_max_extent = FWord::new(max_extent);
+
+        let v_metrics = self.glyphs[..num_long_metrics]
+            .iter()
+            .map(|glyph| LongMetric::new(glyph.0, side_bearing(glyph)))
+            .collect();
+        let top_side_bearings = self.glyphs[num_long_metrics..]
+            .iter()
+            .map(side_bearing)
+            .collect();
+        Vmtx::new(v_metrics, top_side_bearings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder() {
+        let bbox = |y_min, y_max| {
+            Some(Bbox {
+                x_min: 0,
+                y_min,
+                x_max: 100,
+                y_max,
+            })
+        };
+        let mut builder = VmtxBuilder::new();
+        builder
+            .add_glyph(1000, 880, None)
+            .add_glyph(1200, 880, bbox(-150, 900))
+            .add_glyph(1000, 880, bbox(0, 700))
+            .add_glyph(1000, 880, None)
+            .add_glyph(1000, 800, bbox(-100, 750));
+        let mut vhea = Vhea::default();
+        let vmtx = builder.build(&mut vhea);
+
+        assert_eq!(vhea.number_of_long_ver_metrics, 3);
+        assert_eq!(vhea.advance_height_max, UfWord::new(1200));
+        assert_eq!(vhea.min_top_side_bearing, FWord::new(-20));
+        // glyph 4: tsb 50, height 850
+        assert_eq!(vhea.min_bottom_side_bearing, FWord::new(100));
+        assert_eq!(vhea.y_max_extent, FWord::new(1030));
+        let v_metrics = vmtx
+            .v_metrics
+            .iter()
+            .map(|metric| (metric.advance, metric.side_bearing))
+            .collect::<Vec<_>>();
+        assert_eq!(v_metrics, [(1000, 0), (1200, -20), (1000, 180)]);
+        assert_eq!(vmtx.top_side_bearings, [0, 50]);
+
+        let dumped = crate::dump_table(&vmtx).unwrap();
+        let loaded =
+            read_fonts::tables::vmtx::Vmtx::read_with_args(FontData::new(&dumped), &(3, 5))
+                .unwrap();
+        assert_eq!(loaded.top_side_bearings(), &[0, 50]);
+    }
+}
@@ -0,0 +1,80 @@
+//! Property tests for compile types, using `arbitrary` generated tables.
+//!
+//! Each generated table that passes validation should be accepted by the
+//! parser, and should still be valid when it is read back and compiled again.
+
+use arbitrary::{Arbitrary, Unstructured};
+use read_fonts::{FontData, FontRead};
+
+use crate::{from_obj::ToOwnedTable, tables, validate::Validate, write::dump_table, FontWrite};
+
+const N_ITERATIONS: usize = 200;
+const INPUT_LEN: usize = 512;
+
+/// A small deterministic PRNG (xorshift) so failures are reproducible.
+struct ByteSource(u64);
+
+impl ByteSource {
+    fn fill(&mut self, buf: &mut [u8]) {
+        for byte in buf {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            *byte = self.0 as u8;
+        }
+    }
+}
+
+/// Generate tables from a fixed seed, returning the compiled bytes of those
+/// that pass validation.
+fn valid_tables<T>() -> Vec<Vec<u8>>
+where
+    T: for<'a> Arbitrary<'a> + FontWrite + Validate,
+{
+    let mut source = ByteSource(0x5EED_CAFE_F00D_D00D);
+    let mut input = vec![0u8; INPUT_LEN];
+    let mut result = Vec::new();
+    for _ in 0..N_ITERATIONS {
+        source.fill(&mut input);
+        if let Ok(bytes) = T::arbitrary(&mut Unstructured::new(&input))
+            .map_err(|_| ())
+            .and_then(|table| dump_table(&table).map_err(|_| ()))
+        {
+            result.push(bytes);
+        }
+    }
+    result
+}
+
+macro_rules! round_trip_test {
+    ($test_name:ident, $module:ident :: $typ:ident) => {
+        #[test]
+        fn $test_name() {
+            let tables = valid_tables::<tables::$module::$typ>();
+            assert!(
+                !tables.is_empty(),
+                "no valid {} generated",
+                stringify!($typ)
+            );
+            for (i, bytes) in tables.iter().enumerate() {
+                let parsed = read_fonts::tables::$module::$typ::read(FontData::new(bytes))
+                    .unwrap_or_else(|e| panic!("{} #{i} failed to parse: {e}", stringify!($typ)));
+                let owned: tables::$module::$typ = parsed.to_owned_table();
+                if let Err(e) = dump_table(&owned) {
+                    panic!("{} #{i} invalid after round-trip: {e:?}", stringify!($typ));
+                }
+            }
+        }
+    };
+}
+
+round_trip_test!(head, head::Head);
+round_trip_test!(hhea, hhea::Hhea);
+round_trip_test!(maxp, maxp::Maxp);
+round_trip_test!(os2, os2::Os2);
+round_trip_test!(name, name::Name);
+round_trip_test!(stat, stat::Stat);
+round_trip_test!(gdef, gdef::Gdef);
+round_trip_test!(gpos, gpos::Gpos);
+round_trip_test!(gsub, gsub::Gsub);
+round_trip_test!(base, base::Base);
@@ -41,3 +41,255 @@ fn ligaturesubstformat1() {
     let dumped = crate::write::dump_table(&table).unwrap();
     assert_hex_eq!(test_data::LIGATURESUBSTFORMAT1_TABLE.as_ref(), &dumped);
 }
+
+#[test]
+fn promote_lookups_to_extension_on_overflow() {
+    use read_fonts::tables::{gsub as read_gsub, layout::LookupFlag};
+
+    // twenty lookups of ~8k each can't all be reached with 16-bit offsets
+    let lookups = (0..20u16)
+        .map(|i| {
+            let glyphs = (0..2000u16)
+                .map(|gid| GlyphId::new(gid * 2 + i))
+                .collect::<Vec<_>>();
+            let substitutes = glyphs
+                .iter()
+                .map(|gid| GlyphId::new(gid.to_u16() + 1))
+                .collect();
+            let coverage = CoverageTable::format_1(glyphs);
+            let subtable = SingleSubst::format_2(coverage, substitutes);
+            SubstitutionLookup::Single(Lookup::new(LookupFlag::default(), vec![subtable], 0))
+        })
+        .collect();
+    let gsub = Gsub::new(
+        Default::default(),
+        Default::default(),
+        LookupList::new(lookups),
+    );
+    let dumped = crate::dump_table(&gsub).unwrap();
+    assert!(dumped.len() > u16::MAX as usize);
+
+    let gsub = read_gsub::Gsub::read(FontData::new(&dumped)).unwrap();
+    let lookup_list = gsub.lookup_list().unwrap();
+    assert_eq!(lookup_list.lookup_count(), 20);
+    let mut n_extensions = 0;
+    for (i, lookup) in lookup_list.lookups().enumerate() {
+        let subtable = match lookup.unwrap() {
+            read_gsub::SubstitutionLookup::Single(lookup) => lookup.subtables().next(),
+            read_gsub::SubstitutionLookup::Extension(lookup) => {
+                n_extensions += 1;
+                match lookup.subtables().next().unwrap().unwrap() {
+                    read_gsub::ExtensionSubtable::Single(ext) => Some(ext.extension()),
+                    _ => panic!("wrong extension type"),
+                }
+            }
+            _ => panic!("wrong lookup type"),
+        };
+        let read_gsub::SingleSubst::Format2(subtable) = subtable.unwrap().unwrap() else {
+            panic!("wrong subtable format");
+        };
+        let coverage = subtable.coverage().unwrap();
+        let last = coverage.iter().last().unwrap();
+        assert_eq!(last.to_u16(), 3998 + i as u16);
+        assert_eq!(
+            subtable
+                .substitute_glyph_ids()
+                .last()
+                .unwrap()
+                .get()
+                .to_u16(),
+            last.to_u16() + 1
+        );
+    }
+    assert!(n_extensions > 0);
+}
+
+fn gids<const N: usize>(ids: [u16; N]) -> Vec<GlyphId> {
+    ids.into_iter().map(GlyphId::new).collect()
+}
+
+fn glyph_sets<const N: usize>(ids: [&[u16]; N]) -> Vec<BTreeSet<GlyphId>> {
+    ids.into_iter()
+        .map(|set| set.iter().copied().map(GlyphId::new).collect())
+        .collect()
+}
+
+#[test]
+fn single_sub_builder() {
+    let mut builder = SingleSubBuilder::new();
+    for (target, replacement) in [(5, 105), (6, 106), (9, 109)] {
+        builder.insert(GlyphId::new(target), GlyphId::new(replacement));
+    }
+    let subtables = builder.build();
+    let [SingleSubst::Format1(table)] = subtables.as_slice() else {
+        panic!("expected a single format 1 subtable");
+    };
+    assert_eq!(table.delta_glyph_id, 100);
+
+    // a negative delta is fine too
+    let mut builder = SingleSubBuilder::new();
+    builder.insert(GlyphId::new(105), GlyphId::new(5));
+    let SingleSubst::Format1(table) = &builder.build()[0] else {
+        panic!("expected format 1");
+    };
+    assert_eq!(table.delta_glyph_id, -100);
+
+    let mut builder = SingleSubBuilder::new();
+    for (target, replacement) in [(5, 105), (6, 7), (9, 109)] {
+        builder.insert(GlyphId::new(target), GlyphId::new(replacement));
+    }
+    let SingleSubst::Format2(table) = &builder.build()[0] else {
+        panic!("expected format 2");
+    };
+    assert_eq!(table.substitute_glyph_ids, gids([105, 7, 109]));
+}
+
+#[test]
+fn ligature_sub_builder() {
+    let mut builder = LigatureSubBuilder::new();
+    builder.insert(&gids([1, 2]), GlyphId::new(100));
+    builder.insert(&gids([1, 2, 3]), GlyphId::new(101));
+    builder.insert(&gids([1, 4]), GlyphId::new(102));
+    builder.insert(&gids([5, 6]), GlyphId::new(103));
+    // replaces the existing ligature
+    builder.insert(&gids([1, 4]), GlyphId::new(104));
+
+    let table = builder.build();
+    assert_eq!(table.coverage.iter().collect::<Vec<_>>(), gids([1, 5]));
+    let ligatures = table.ligature_sets[0]
+        .ligatures
+        .iter()
+        .map(|lig| (lig.ligature_glyph.to_u16(), lig.component_glyph_ids.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ligatures,
+        [(101, gids([2, 3])), (100, gids([2])), (104, gids([4]))]
+    );
+}
+
+#[test]
+fn chain_context_builder() {
+    let mut builder = ChainContextBuilder::new();
+    builder.add_rule(
+        ChainRule::new(glyph_sets([&[10], &[11]]))
+            .backtrack(glyph_sets([&[1], &[2]]))
+            .lookup(0, 3),
+    );
+    builder.add_rule(ChainRule::new(glyph_sets([&[12]])).lookup(0, 4));
+    builder.add_rule(
+        ChainRule::new(glyph_sets([&[20, 21]]))
+            .lookahead(glyph_sets([&[30], &[31, 32]]))
+            .lookup(0, 5),
+    );
+    builder.add_rule(ChainRule::new(glyph_sets([&[10]])).lookup(0, 6));
+
+    let subtables = builder.build();
+    assert_eq!(subtables.len(), 3);
+    let ChainedSequenceContext::Format1(first) = &*subtables[0] else {
+        panic!("expected format 1");
+    };
+    assert_eq!(first.coverage.iter().collect::<Vec<_>>(), gids([10, 12]));
+    let rule = &first.chained_seq_rule_sets[0]
+        .as_ref()
+        .unwrap()
+        .chained_seq_rules[0];
+    // the backtrack sequence is stored in reverse order
+    assert_eq!(rule.backtrack_sequence, gids([2, 1]));
+    assert_eq!(rule.input_sequence, gids([11]));
+
+    let ChainedSequenceContext::Format3(second) = &*subtables[1] else {
+        panic!("expected format 3");
+    };
+    assert_eq!(second.input_coverages[0].len(), 2);
+    assert_eq!(second.lookahead_coverages.len(), 2);
+    assert_eq!(second.seq_lookup_records[0].lookup_list_index, 5);
+
+    assert!(matches!(&*subtables[2], ChainedSequenceContext::Format1(_)));
+}
+
+#[test]
+fn gsub_builder() {
+    use read_fonts::tables::{gsub as read_gsub, layout::LookupFlag};
+
+    let latn = Tag::new(b"latn");
+    let dflt = Tag::new(b"dflt");
+    let trk = Tag::new(b"TRK ");
+
+    let mut builder = GsubBuilder::new();
+    let mut single = SingleSubBuilder::new();
+    single.insert(GlyphId::new(3), GlyphId::new(30));
+    let single = builder.add_single(LookupFlag::empty(), single);
+    let mut ligatures = LigatureSubBuilder::new();
+    ligatures.insert(&gids([5, 6]), GlyphId::new(56));
+    let mut flags = LookupFlag::empty();
+    flags.set_ignore_marks(true);
+    let liga = builder.add_ligature(flags, ligatures);
+    let mut chain = ChainContextBuilder::new();
+    chain.add_rule(
+        ChainRule::new(glyph_sets([&[3]]))
+            .lookahead(glyph_sets([&[7, 8]]))
+            .lookup(0, single),
+    );
+    let calt = builder.add_chain(LookupFlag::empty(), chain);
+    builder.set_mark_filtering_set(calt, 2);
+
+    builder.add_feature(latn, dflt, Tag::new(b"liga"), &[liga]);
+    builder.add_feature(latn, trk, Tag::new(b"liga"), &[liga]);
+    builder.add_feature(latn, dflt, Tag::new(b"calt"), &[calt]);
+    builder.add_feature(latn, trk, Tag::new(b"calt"), &[calt, single]);
+
+    let bytes = crate::dump_table(&builder.build()).unwrap();
+    let gsub = read_gsub::Gsub::read(FontData::new(&bytes)).unwrap();
+
+    let features = gsub.feature_list().unwrap();
+    let feature_records = features.feature_records();
+    // 'liga' is shared between the language systems, but 'calt' is not
+    assert_eq!(feature_records.len(), 3);
+    let tags = feature_records
+        .iter()
+        .map(|rec| rec.feature_tag())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tags,
+        [Tag::new(b"calt"), Tag::new(b"calt"), Tag::new(b"liga")]
+    );
+    let calt_trk = feature_records[0].feature(features.offset_data()).unwrap();
+    assert_eq!(
+        calt_trk
+            .lookup_list_indices()
+            .iter()
+            .map(|idx| idx.get())
+            .collect::<Vec<_>>(),
+        [single, calt]
+    );
+
+    let scripts = gsub.script_list().unwrap();
+    let script_records = scripts.script_records();
+    assert_eq!(script_records.len(), 1);
+    assert_eq!(script_records[0].script_tag(), latn);
+    let script = script_records[0].script(scripts.offset_data()).unwrap();
+    let default = script.default_lang_sys().unwrap().unwrap();
+    assert_eq!(default.feature_index_count(), 2);
+    assert_eq!(default.required_feature_index(), 0xFFFF);
+    assert_eq!(script.lang_sys_records()[0].lang_sys_tag(), trk);
+
+    let lookups = gsub.lookup_list().unwrap();
+    let lookup_flags = lookups
+        .lookups()
+        .map(|lookup| match lookup.unwrap() {
+            read_gsub::SubstitutionLookup::Single(lookup) => {
+                (lookup.lookup_flag(), lookup.mark_filtering_set())
+            }
+            read_gsub::SubstitutionLookup::Ligature(lookup) => {
+                (lookup.lookup_flag(), lookup.mark_filtering_set())
+            }
+            read_gsub::SubstitutionLookup::ChainContextual(lookup) => {
+                (lookup.lookup_flag(), lookup.mark_filtering_set())
+            }
+            _ => panic!("unexpected lookup type"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(lookup_flags[1].0, flags);
+    assert!(lookup_flags[2].0.use_mark_filtering_set());
+    assert_eq!(lookup_flags[2].1, 2);
+}
@@ -1,4 +1,4 @@
-//! The pre-compilation validation pass
+//! The pre-compilation validation pass, and validation of compiled fonts
 
 use std::{
     collections::BTreeSet,
@@ -6,7 +6,14 @@ use std::{
     ops::Deref,
 };
 
-use crate::offsets::{NullableOffsetMarker, OffsetMarker};
+use read_fonts::{tables::cmap::CmapSubtable, FontRef, ReadError, TableProvider};
+use types::{GlyphId, Tag};
+
+use crate::{
+    font_builder::checksum_and_padding,
+    from_obj::ToOwnedTable,
+    offsets::{NullableOffsetMarker, OffsetMarker},
+};
 
 /// Pre-compilation validation of tables.
 ///
@@ -70,8 +77,9 @@ pub struct ValidationCtx {
     errors: Vec<ValidationError>,
 }
 
+/// A single validation error, and the location in the table where it occurred.
 #[derive(Debug, Clone)]
-struct ValidationError {
+pub struct ValidationError {
     error: String,
     location: Vec<LocationElem>,
 }
@@ -139,15 +147,51 @@ impl ValidationCtx {
     }
 }
 
+impl ValidationReport {
+    /// The errors in this report, in the order they were found.
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationError> {
+        self.errors.iter()
+    }
+}
+
+impl ValidationError {
+    /// The error message.
+    pub fn message(&self) -> &str {
+        &self.error
+    }
+
+    /// The location of the error, as a path from the top-level table.
+    ///
+    /// This is the name of the table, followed by the field names and array
+    /// indices that lead to the error, such as
+    /// `Gpos.lookup_list.lookups[4].subtables[0].coverage`.
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        for (i, elem) in self.location.iter().enumerate() {
+            match elem {
+                LocationElem::Table(name) if i == 0 => path.push_str(name),
+                // the names of subtables are implied by the fields that reference them
+                LocationElem::Table(_) => (),
+                LocationElem::Field(name) => {
+                    path.push('.');
+                    path.push_str(name);
+                }
+                LocationElem::Index(idx) => path.push_str(&format!("[{idx}]")),
+            }
+        }
+        path
+    }
+}
+
 impl Display for ValidationReport {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.errors.len() == 1 {
-            return writeln!(f, "Validation error:\n{}", self.errors.first().unwrap());
+            return writeln!(f, "Validation error: {}", self.errors[0]);
         }
 
         writeln!(f, "{} validation errors:", self.errors.len())?;
-        for (i, error) in self.errors.iter().enumerate() {
-            writeln!(f, "#{}\n{error}", i + 1)?;
+        for error in &self.errors {
+            writeln!(f, "{error}")?;
         }
         Ok(())
     }
@@ -159,35 +203,9 @@ impl Debug for ValidationReport {
     }
 }
 
-static MANY_SPACES: &str = "                                                                                                        ";
-
 impl Display for ValidationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "\"{}\"", self.error)?;
-        let mut indent = 0;
-
-        for (i, window) in self.location.windows(2).enumerate() {
-            let prev = &window[0];
-            let current = &window[1];
-            if i == 0 {
-                if let LocationElem::Table(name) = prev {
-                    write!(f, "in: {name}")?;
-                } else {
-                    panic!("first item always table");
-                }
-            }
-
-            match current {
-                LocationElem::Table(name) => {
-                    indent += 1;
-                    let indent_str = &MANY_SPACES[..indent * 2];
-                    write!(f, "\n{indent_str}{name}")
-                }
-                LocationElem::Field(name) => write!(f, ".{name}"),
-                LocationElem::Index(idx) => write!(f, "[{idx}]"),
-            }?;
-        }
-        writeln!(f)
+        write!(f, "{}: {}", self.path(), self.error)
     }
 }
 
@@ -235,3 +253,447 @@ impl<T: Validate> Validate for BTreeSet<T> {
         });
     }
 }
+
+/// How serious an [`Issue`] found by [`validate_font`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Something unusual that most consumers will tolerate.
+    Warning,
+    /// A violation of the spec that may cause the font to be rejected or
+    /// rendered incorrectly.
+    Error,
+}
+
+/// A problem found by [`validate_font`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Issue {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// Where the problem is: a table tag, or a path into a table such as
+    /// `Gpos.lookup_list.lookups[4]` or `cmap.encoding_records[1]`.
+    pub path: String,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl Issue {
+    fn new(severity: Severity, path: impl Display, message: impl Display) -> Self {
+        Issue {
+            severity,
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.path, self.message)
+    }
+}
+
+/// Check a compiled font for problems.
+///
+/// This is a lightweight alternative to a full sanitizer such as OTS, meant
+/// for catching mistakes in font production. Each supported table is parsed
+/// and run through the [`Validate`] pass, and the tables are checked for
+/// consistency with each other:
+///
+/// - required tables are present
+/// - `loca` has an entry for each glyph in `maxp`, and points into `glyf`
+/// - each glyph in `glyf` can be parsed
+/// - `hmtx` and `vmtx` have the length implied by `maxp`, `hhea` and `vhea`
+/// - `cmap` only maps to glyphs that exist
+/// - table checksums match the table directory
+///
+/// The issues are returned in the order they were found.
+pub fn validate_font(font: &FontRef) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for tag in [Tag::CMAP, Tag::HEAD, Tag::HHEA, Tag::HMTX, Tag::MAXP] {
+        if font.data_for_tag(tag).is_none() {
+            issues.push(Issue::new(Severity::Error, tag, "missing required table"));
+        }
+    }
+    for tag in [Tag::NAME, Tag::OS2, Tag::POST] {
+        if font.data_for_tag(tag).is_none() {
+            issues.push(Issue::new(Severity::Warning, tag, "missing table"));
+        }
+    }
+    check_checksums(font, &mut issues);
+    for record in font.table_directory.table_records() {
+        let tag = record.tag();
+        let result = match validate_table(font, tag) {
+            Some(result) => result,
+            None => continue,
+        };
+        match result {
+            Ok(errors) => issues.extend(
+                errors
+                    .into_iter()
+                    .map(|error| Issue::new(Severity::Error, error.path(), error.message())),
+            ),
+            Err(e) => issues.push(Issue::new(Severity::Error, tag, e)),
+        }
+    }
+    if let Ok(maxp) = font.maxp() {
+        let num_glyphs = maxp.num_glyphs();
+        check_glyf(font, num_glyphs, &mut issues);
+        check_metrics(font, Tag::HMTX, num_glyphs, &mut issues);
+        check_metrics(font, Tag::VMTX, num_glyphs, &mut issues);
+        check_cmap(font, num_glyphs, &mut issues);
+    }
+    issues
+}
+
+/// Run the [`Validate`] pass on a table, if it is supported.
+fn validate_table(font: &FontRef, tag: Tag) -> Option<Result<Vec<ValidationError>, ReadError>> {
+    use crate::tables;
+    use read_fonts::tables as read;
+
+    fn errors<T: Validate>(table: T) -> Vec<ValidationError> {
+        table
+            .validate()
+            .err()
+            .map(|report| report.errors)
+            .unwrap_or_default()
+    }
+
+    fn owned<R, T>(table: Result<R, ReadError>) -> Result<Vec<ValidationError>, ReadError>
+    where
+        R: ToOwnedTable<T>,
+        T: Validate,
+    {
+        table.map(|table| errors(table.to_owned_table()))
+    }
+
+    Some(match tag {
+        Tag::BASE => owned::<_, tables::base::Base>(font.expect_table::<read::base::Base>()),
+        Tag::CMAP => owned::<_, tables::cmap::Cmap>(font.cmap()),
+        Tag::COLR => owned::<_, tables::colr::Colr>(font.colr()),
+        Tag::CPAL => owned::<_, tables::cpal::Cpal>(font.cpal()),
+        Tag::GDEF => owned::<_, tables::gdef::Gdef>(font.gdef()),
+        Tag::GPOS => owned::<_, tables::gpos::Gpos>(font.gpos()),
+        Tag::GSUB => owned::<_, tables::gsub::Gsub>(font.gsub()),
+        Tag::HEAD => owned::<_, tables::head::Head>(font.head()),
+        Tag::HHEA => owned::<_, tables::hhea::Hhea>(font.hhea()),
+        Tag::HVAR => owned::<_, tables::hvar::Hvar>(font.hvar()),
+        Tag::MATH => owned::<_, tables::math::Math>(font.math()),
+        Tag::MAXP => owned::<_, tables::maxp::Maxp>(font.maxp()),
+        Tag::MVAR => owned::<_, tables::mvar::Mvar>(font.mvar()),
+        Tag::NAME => owned::<_, tables::name::Name>(font.name()),
+        Tag::OS2 => owned::<_, tables::os2::Os2>(font.os2()),
+        Tag::POST => owned::<_, tables::post::Post>(font.post()),
+        Tag::STAT => owned::<_, tables::stat::Stat>(font.stat()),
+        Tag::VHEA => owned::<_, tables::vhea::Vhea>(font.vhea()),
+        _ => return None,
+    })
+}
+
+fn check_checksums(font: &FontRef, issues: &mut Vec<Issue>) {
+    for record in font.table_directory.table_records() {
+        let tag = record.tag();
+        let Some(data) = font.data_for_tag(tag) else {
+            issues.push(Issue::new(
+                Severity::Error,
+                tag,
+                "table extends beyond the end of the file",
+            ));
+            continue;
+        };
+        let mut bytes = data.as_ref().to_vec();
+        if tag == Tag::HEAD && bytes.len() >= 12 {
+            // the checksum adjustment is treated as zero
+            bytes[8..12].fill(0);
+        }
+        let (checksum, _) = checksum_and_padding(&bytes);
+        if checksum != record.checksum() {
+            issues.push(Issue::new(
+                Severity::Warning,
+                tag,
+                format!(
+                    "checksum is 0x{:08X}, expected 0x{checksum:08X}",
+                    record.checksum()
+                ),
+            ));
+        }
+    }
+}
+
+fn check_glyf(font: &FontRef, num_glyphs: u16, issues: &mut Vec<Issue>) {
+    let (glyf, loca) = match (font.glyf(), font.loca(None)) {
+        (Ok(glyf), Ok(loca)) => (glyf, loca),
+        (Err(ReadError::TableIsMissing(_)), Err(ReadError::TableIsMissing(_))) => return,
+        (Err(e), _) => return issues.push(Issue::new(Severity::Error, Tag::GLYF, e)),
+        (_, Err(e)) => return issues.push(Issue::new(Severity::Error, Tag::LOCA, e)),
+    };
+    // even a font with no glyphs has the offset of the end of the last glyph
+    if loca.get_raw(0).is_none() {
+        return issues.push(Issue::new(Severity::Error, Tag::LOCA, "has no entries"));
+    }
+    if loca.len() != num_glyphs as usize {
+        issues.push(Issue::new(
+            Severity::Error,
+            Tag::LOCA,
+            format!(
+                "has {} entries, but maxp.num_glyphs is {num_glyphs} (expected {})",
+                loca.len() + 1,
+                num_glyphs as u32 + 1
+            ),
+        ));
+    }
+    let glyf_len = glyf.offset_data().len() as u32;
+    let mut offsets = vec![0; loca.len() + 1];
+    loca.copy_offsets(&mut offsets);
+    let mut prev = 0;
+    for (i, offset) in offsets.into_iter().enumerate() {
+        if offset < prev || offset > glyf_len {
+            return issues.push(Issue::new(
+                Severity::Error,
+                format!("loca[{i}]"),
+                format!(
+                    "offset {offset} is out of order or beyond the end of glyf ({glyf_len} bytes)"
+                ),
+            ));
+        }
+        prev = offset;
+    }
+    for gid in 0..num_glyphs.min(loca.len() as u16) {
+        if let Err(e) = loca.get_glyf(GlyphId::new(gid), &glyf) {
+            issues.push(Issue::new(Severity::Error, format!("glyf[{gid}]"), e));
+        }
+    }
+}
+
+/// Check the length of `hmtx` or `vmtx`.
+fn check_metrics(font: &FontRef, tag: Tag, num_glyphs: u16, issues: &mut Vec<Issue>) {
+    let Some(data) = font.data_for_tag(tag) else {
+        return;
+    };
+    let (header, num_long_metrics) = if tag == Tag::HMTX {
+        let Ok(hhea) = font.hhea() else { return };
+        ("hhea.number_of_long_metrics", hhea.number_of_long_metrics())
+    } else {
+        let Ok(vhea) = font.vhea() else {
+            return issues.push(Issue::new(Severity::Error, tag, "vmtx requires vhea"));
+        };
+        (
+            "vhea.number_of_long_ver_metrics",
+            vhea.number_of_long_ver_metrics(),
+        )
+    };
+    if num_long_metrics == 0 || num_long_metrics > num_glyphs {
+        return issues.push(Issue::new(
+            Severity::Error,
+            header,
+            format!(
+                "is {num_long_metrics}, but must be between 1 and maxp.num_glyphs ({num_glyphs})"
+            ),
+        ));
+    }
+    let expected = num_long_metrics as usize * 4 + (num_glyphs - num_long_metrics) as usize * 2;
+    let len = data.len();
+    if len < expected {
+        issues.push(Issue::new(
+            Severity::Error,
+            tag,
+            format!("is {len} bytes, but {header} and maxp.num_glyphs require {expected}"),
+        ));
+    } else if len > expected {
+        issues.push(Issue::new(
+            Severity::Warning,
+            tag,
+            format!("has {} bytes of unused data", len - expected),
+        ));
+    }
+}
+
+/// Check that the format 4 and 12 subtables only map to glyphs in the font.
+fn check_cmap(font: &FontRef, num_glyphs: u16, issues: &mut Vec<Issue>) {
+    let Ok(cmap) = font.cmap() else { return };
+    for (i, record) in cmap.encoding_records().iter().enumerate() {
+        let mappings: Box<dyn Iterator<Item = (u32, GlyphId)>> =
+            match record.subtable(cmap.offset_data()) {
+                Ok(CmapSubtable::Format4(subtable)) => Box::new(subtable.iter()),
+                Ok(CmapSubtable::Format12(subtable)) => Box::new(subtable.iter()),
+                // other formats are not checked, and read errors are
+                // reported by the validation pass
+                _ => continue,
+            };
+        let mut bad = mappings.filter(|(_, gid)| gid.to_u16() >= num_glyphs);
+        if let Some((codepoint, gid)) = bad.next() {
+            let more = bad.count();
+            let mut message = format!(
+                "U+{codepoint:04X} maps to glyph {}, but maxp.num_glyphs is {num_glyphs}",
+                gid.to_u16()
+            );
+            if more > 0 {
+                message.push_str(&format!(" ({more} more)"));
+            }
+            issues.push(Issue::new(
+                Severity::Error,
+                format!("cmap.encoding_records[{i}]"),
+                message,
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        dump_table,
+        tables::{
+            cmap::Cmap,
+            glyf::{GlyfLocaBuilder, Glyph},
+            head::Head,
+            hhea::Hhea,
+            hmtx::{Hmtx, LongMetric},
+            maxp::Maxp,
+        },
+        FontBuilder,
+    };
+
+    /// A font with two empty glyphs, where 'a' maps to `cmap_gid`.
+    fn make_font(num_glyphs: u16, cmap_gid: u16) -> FontBuilder<'static> {
+        let mut builder = GlyfLocaBuilder::new();
+        builder.add_glyph(&Glyph::Empty).add_glyph(&Glyph::Empty);
+        let (glyf, loca, format) = builder.build();
+        let mut head = Head {
+            units_per_em: 1000,
+            ..Default::default()
+        };
+        head.set_loca_format(format);
+        let hhea = Hhea {
+            number_of_long_metrics: 2,
+            ..Default::default()
+        };
+        let hmtx = Hmtx::new(vec![LongMetric::new(500, 0); 2], Vec::new());
+        let cmap = Cmap::from_mappings(BTreeMap::from([('a', GlyphId::new(cmap_gid))]));
+
+        let mut builder = FontBuilder::default();
+        builder
+            .add_table(Tag::GLYF, dump_table(&glyf).unwrap())
+            .add_table(Tag::LOCA, dump_table(&loca).unwrap())
+            .add_table(Tag::HEAD, dump_table(&head).unwrap())
+            .add_table(Tag::MAXP, dump_table(&Maxp::new(num_glyphs)).unwrap())
+            .add_table(Tag::HHEA, dump_table(&hhea).unwrap())
+            .add_table(Tag::HMTX, dump_table(&hmtx).unwrap())
+            .add_table(Tag::CMAP, dump_table(&cmap).unwrap());
+        builder
+    }
+
+    fn errors(issues: &[Issue]) -> Vec<&str> {
+        issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .map(|issue| issue.path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn valid_font() {
+        let data = make_font(2, 1).build();
+        let issues = validate_font(&FontRef::new(&data).unwrap());
+        assert!(errors(&issues).is_empty(), "{issues:?}");
+        let warnings = issues
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            [
+                "warning: name: missing table",
+                "warning: OS/2: missing table",
+                "warning: post: missing table"
+            ]
+        );
+    }
+
+    #[test]
+    fn glyph_count_mismatch() {
+        let data = make_font(3, 1).build();
+        let issues = validate_font(&FontRef::new(&data).unwrap());
+        assert_eq!(errors(&issues), ["loca", "hmtx"]);
+        assert_eq!(
+            issues[3].message,
+            "has 3 entries, but maxp.num_glyphs is 3 (expected 4)"
+        );
+    }
+
+    #[test]
+    fn empty_loca() {
+        let mut builder = make_font(2, 1);
+        builder.add_table(Tag::LOCA, Vec::new());
+        let data = builder.build();
+        let issues = validate_font(&FontRef::new(&data).unwrap());
+        let loca_issues = issues
+            .iter()
+            .filter(|issue| issue.path == "loca")
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(loca_issues, ["error: loca: has no entries"]);
+    }
+
+    #[test]
+    fn cmap_glyph_out_of_range() {
+        let data = make_font(2, 5).build();
+        let issues = validate_font(&FontRef::new(&data).unwrap());
+        let cmap_issues = issues
+            .iter()
+            .filter(|issue| issue.path.starts_with("cmap"))
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cmap_issues,
+            [
+                "error: cmap.encoding_records[0]: U+0061 maps to glyph 5, but maxp.num_glyphs is 2",
+                "error: cmap.encoding_records[1]: U+0061 maps to glyph 5, but maxp.num_glyphs is 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn unused_metrics() {
+        let mut builder = make_font(2, 1);
+        let hmtx = Hmtx::new(vec![LongMetric::new(500, 0); 2], vec![0; 3]);
+        builder.add_table(Tag::HMTX, dump_table(&hmtx).unwrap());
+        let data = builder.build();
+        let issues = validate_font(&FontRef::new(&data).unwrap());
+        assert!(errors(&issues).is_empty(), "{issues:?}");
+        assert!(issues
+            .iter()
+            .any(|issue| issue.path == "hmtx" && issue.message == "has 6 bytes of unused data"));
+    }
+
+    #[test]
+    fn bad_checksum() {
+        let mut data = make_font(2, 1).build();
+        let font = FontRef::new(&data).unwrap();
+        let record = font
+            .table_directory
+            .table_records()
+            .iter()
+            .find(|record| record.tag() == Tag::HHEA)
+            .unwrap();
+        // ascender
+        let offset = record.offset().to_u32() as usize + 4;
+        data[offset] ^= 1;
+        let issues = validate_font(&FontRef::new(&data).unwrap());
+        assert!(errors(&issues).is_empty(), "{issues:?}");
+        assert!(issues
+            .iter()
+            .any(|issue| issue.path == "hhea" && issue.message.starts_with("checksum")));
+    }
+}
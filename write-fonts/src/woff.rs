@@ -0,0 +1,889 @@
+//! Encoding fonts as [WOFF] and [WOFF2].
+//!
+//! A [`WoffEncoder`] takes a compiled font and produces the compressed web
+//! font formats. WOFF compresses each table with zlib; WOFF2 compresses all of
+//! the tables together with Brotli, after applying the `glyf`/`loca`
+//! transform described in the specification.
+//!
+//! Both formats can carry an extended metadata block (an XML document) and a
+//! block of private data.
+//!
+//! [WOFF]: https://www.w3.org/TR/WOFF/
+//! [WOFF2]: https://www.w3.org/TR/WOFF2/
+
+use std::fmt::Display;
+
+use read_fonts::{
+    tables::glyf::{CompositeGlyph, Glyph, SimpleGlyph},
+    FontRef, ReadError, TableProvider,
+};
+use types::{GlyphId, Tag};
+
+const WOFF_SIGNATURE: Tag = Tag::new(b"wOFF");
+const WOFF2_SIGNATURE: Tag = Tag::new(b"wOF2");
+
+const WOFF_HEADER_LEN: usize = 44;
+const WOFF_TABLE_ENTRY_LEN: usize = 20;
+const WOFF2_HEADER_LEN: usize = 48;
+const SFNT_HEADER_LEN: usize = 12;
+const SFNT_TABLE_RECORD_LEN: usize = 16;
+
+/// The offset of the `flags` field in the `head` table.
+const HEAD_FLAGS_OFFSET: usize = 16;
+/// The `head` flag indicating that the font has been losslessly transformed.
+const HEAD_FLAG_TRANSFORMED: u16 = 1 << 11;
+
+/// The zlib compression level used for WOFF tables and metadata.
+const ZLIB_LEVEL: u8 = 9;
+/// The Brotli quality used for WOFF2.
+const BROTLI_QUALITY: i32 = 11;
+/// The Brotli window size used for WOFF2.
+const BROTLI_WINDOW_BITS: i32 = 22;
+
+/// Tags that can be stored as an index in a WOFF2 table directory entry.
+const WOFF2_KNOWN_TAGS: [&[u8; 4]; 63] = [
+    b"cmap", b"head", b"hhea", b"hmtx", b"maxp", b"name", b"OS/2", b"post", b"cvt ", b"fpgm",
+    b"glyf", b"loca", b"prep", b"CFF ", b"VORG", b"EBDT", b"EBLC", b"gasp", b"hdmx", b"kern",
+    b"LTSH", b"PCLT", b"VDMX", b"vhea", b"vmtx", b"BASE", b"GDEF", b"GPOS", b"GSUB", b"EBSC",
+    b"JSTF", b"MATH", b"CBDT", b"CBLC", b"COLR", b"CPAL", b"SVG ", b"sbix", b"acnt", b"avar",
+    b"bdat", b"bloc", b"bsln", b"cvar", b"fdsc", b"feat", b"fmtx", b"fvar", b"gvar", b"hsty",
+    b"just", b"lcar", b"mort", b"morx", b"opbd", b"prop", b"trak", b"Zapf", b"Silf", b"Glat",
+    b"Gloc", b"Feat", b"Sill",
+];
+/// The flag value indicating that an arbitrary tag follows the flags byte.
+const WOFF2_ARBITRARY_TAG: u8 = 63;
+
+/// Encodes a font as WOFF or WOFF2.
+///
+/// ```no_run
+/// # let bytes: &[u8] = &[];
+/// use write_fonts::{read::FontRef, woff::WoffEncoder};
+///
+/// let font = FontRef::new(bytes).unwrap();
+/// let woff2 = WoffEncoder::new(font)
+///     .metadata("<?xml version=\"1.0\"?><metadata version=\"1.0\"/>")
+///     .encode_woff2()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct WoffEncoder<'a> {
+    font: FontRef<'a>,
+    metadata: Option<String>,
+    private_data: Option<Vec<u8>>,
+}
+
+/// An error that occurs while encoding a font.
+#[derive(Debug)]
+pub enum WoffError {
+    /// The input font could not be read.
+    Read(ReadError),
+    /// Compression failed.
+    Compression(std::io::Error),
+}
+
+impl<'a> WoffEncoder<'a> {
+    /// Create an encoder for the provided font.
+    pub fn new(font: FontRef<'a>) -> Self {
+        WoffEncoder {
+            font,
+            metadata: None,
+            private_data: None,
+        }
+    }
+
+    /// Set the extended metadata, an XML document describing the font.
+    pub fn metadata(&mut self, xml: impl Into<String>) -> &mut Self {
+        self.metadata = Some(xml.into());
+        self
+    }
+
+    /// Set the private data block.
+    pub fn private_data(&mut self, data: Vec<u8>) -> &mut Self {
+        self.private_data = Some(data);
+        self
+    }
+
+    /// Encode the font as WOFF.
+    pub fn encode_woff(&self) -> Result<Vec<u8>, WoffError> {
+        let (major_version, minor_version) = self.font_version()?;
+        let mut tables = Vec::new();
+        for record in self.font.table_records() {
+            let tag = record.tag();
+            let data = self
+                .font
+                .data_for_record(record)
+                .ok_or(ReadError::TableIsMissing(tag))?;
+            let data = data.as_ref();
+            let compressed = miniz_oxide::deflate::compress_to_vec_zlib(data, ZLIB_LEVEL);
+            // tables that do not get smaller are stored uncompressed
+            let stored = if compressed.len() < data.len() {
+                compressed
+            } else {
+                data.to_vec()
+            };
+            tables.push((tag, record.checksum(), data.len(), stored));
+        }
+        tables.sort_by_key(|(tag, ..)| *tag);
+
+        let header_and_dir = WOFF_HEADER_LEN + WOFF_TABLE_ENTRY_LEN * tables.len();
+        let mut directory = Vec::new();
+        let mut blocks = Vec::new();
+        for (tag, checksum, orig_len, stored) in &tables {
+            directory.extend_from_slice(&tag.to_be_bytes());
+            push_u32(&mut directory, (header_and_dir + blocks.len()) as u32);
+            push_u32(&mut directory, stored.len() as u32);
+            push_u32(&mut directory, *orig_len as u32);
+            push_u32(&mut directory, *checksum);
+            blocks.extend_from_slice(stored);
+            pad_to_4(&mut blocks);
+        }
+
+        let metadata = self.metadata.as_ref().map(|xml| {
+            let compressed = miniz_oxide::deflate::compress_to_vec_zlib(xml.as_bytes(), ZLIB_LEVEL);
+            (compressed, xml.len())
+        });
+        let total_sfnt_size = sfnt_size(tables.iter().map(|(_, _, len, _)| *len));
+
+        let (meta_offset, meta_len, meta_orig_len) =
+            self.append_metadata(&mut blocks, header_and_dir, metadata);
+        let (priv_offset, priv_len) = self.append_private_data(&mut blocks, header_and_dir);
+
+        let total_len = header_and_dir + blocks.len();
+        let mut out = Vec::with_capacity(total_len);
+        out.extend_from_slice(&WOFF_SIGNATURE.to_be_bytes());
+        push_u32(&mut out, self.font.table_directory.sfnt_version());
+        push_u32(&mut out, total_len as u32);
+        push_u16(&mut out, tables.len() as u16);
+        // reserved
+        push_u16(&mut out, 0);
+        push_u32(&mut out, total_sfnt_size as u32);
+        push_u16(&mut out, major_version);
+        push_u16(&mut out, minor_version);
+        push_u32(&mut out, meta_offset);
+        push_u32(&mut out, meta_len);
+        push_u32(&mut out, meta_orig_len);
+        push_u32(&mut out, priv_offset);
+        push_u32(&mut out, priv_len);
+        out.extend_from_slice(&directory);
+        out.extend_from_slice(&blocks);
+        Ok(out)
+    }
+
+    /// Encode the font as WOFF2.
+    ///
+    /// If the font has `glyf` and `loca` tables, they are transformed as
+    /// described in section 5.1 of the specification.
+    pub fn encode_woff2(&self) -> Result<Vec<u8>, WoffError> {
+        let (major_version, minor_version) = self.font_version()?;
+        let mut tags = self
+            .font
+            .table_records()
+            .map(|record| record.tag())
+            .collect::<Vec<_>>();
+        tags.sort();
+        // the transformed loca table must immediately follow glyf
+        let transform_glyf = tags.contains(&Tag::GLYF) && tags.contains(&Tag::LOCA);
+        if transform_glyf {
+            tags.retain(|tag| *tag != Tag::LOCA);
+            let glyf_pos = tags.iter().position(|tag| *tag == Tag::GLYF).unwrap();
+            tags.insert(glyf_pos + 1, Tag::LOCA);
+        }
+
+        let mut directory = Vec::new();
+        let mut stream = Vec::new();
+        let mut orig_lens = Vec::new();
+        for tag in &tags {
+            let data = self
+                .font
+                .table_data(*tag)
+                .ok_or(ReadError::TableIsMissing(*tag))?;
+            let data = data.as_ref();
+            orig_lens.push(data.len());
+            push_woff2_tag(&mut directory, *tag);
+            push_base128(&mut directory, data.len() as u32);
+            match *tag {
+                Tag::GLYF if transform_glyf => {
+                    let transformed = transform_glyf_table(&self.font)?;
+                    push_base128(&mut directory, transformed.len() as u32);
+                    stream.extend_from_slice(&transformed);
+                }
+                // loca is rebuilt from the transformed glyf table
+                Tag::LOCA if transform_glyf => push_base128(&mut directory, 0),
+                Tag::HEAD => {
+                    let mut head = data.to_vec();
+                    if let Some(flags) = head.get_mut(HEAD_FLAGS_OFFSET..HEAD_FLAGS_OFFSET + 2) {
+                        let value = u16::from_be_bytes([flags[0], flags[1]]);
+                        flags.copy_from_slice(&(value | HEAD_FLAG_TRANSFORMED).to_be_bytes());
+                    }
+                    stream.extend_from_slice(&head);
+                }
+                _ => stream.extend_from_slice(data),
+            }
+        }
+
+        let compressed = brotli_compress(&stream, true)?;
+        let metadata = self
+            .metadata
+            .as_ref()
+            .map(|xml| brotli_compress(xml.as_bytes(), false).map(|data| (data, xml.len())))
+            .transpose()?;
+
+        let header_and_dir = WOFF2_HEADER_LEN + directory.len();
+        let compressed_len = compressed.len();
+        let mut blocks = compressed;
+        let total_sfnt_size = sfnt_size(orig_lens.iter().copied());
+        let (meta_offset, meta_len, meta_orig_len) =
+            self.append_metadata(&mut blocks, header_and_dir, metadata);
+        let (priv_offset, priv_len) = self.append_private_data(&mut blocks, header_and_dir);
+        pad_to_4(&mut blocks);
+
+        let total_len = header_and_dir + blocks.len();
+        let mut out = Vec::with_capacity(total_len);
+        out.extend_from_slice(&WOFF2_SIGNATURE.to_be_bytes());
+        push_u32(&mut out, self.font.table_directory.sfnt_version());
+        push_u32(&mut out, total_len as u32);
+        push_u16(&mut out, tags.len() as u16);
+        // reserved
+        push_u16(&mut out, 0);
+        push_u32(&mut out, total_sfnt_size as u32);
+        push_u32(&mut out, compressed_len as u32);
+        push_u16(&mut out, major_version);
+        push_u16(&mut out, minor_version);
+        push_u32(&mut out, meta_offset);
+        push_u32(&mut out, meta_len);
+        push_u32(&mut out, meta_orig_len);
+        push_u32(&mut out, priv_offset);
+        push_u32(&mut out, priv_len);
+        out.extend_from_slice(&directory);
+        out.extend_from_slice(&blocks);
+        Ok(out)
+    }
+
+    /// The version of the WOFF file, taken from the font revision in `head`.
+    fn font_version(&self) -> Result<(u16, u16), ReadError> {
+        let revision = self.font.head()?.font_revision().to_bits() as u32;
+        Ok(((revision >> 16) as u16, revision as u16))
+    }
+
+    /// Append the compressed metadata, returning its offset, compressed length
+    /// and original length.
+    fn append_metadata(
+        &self,
+        blocks: &mut Vec<u8>,
+        blocks_start: usize,
+        metadata: Option<(Vec<u8>, usize)>,
+    ) -> (u32, u32, u32) {
+        let Some((compressed, orig_len)) = metadata else {
+            return (0, 0, 0);
+        };
+        pad_to_4(blocks);
+        let offset = blocks_start + blocks.len();
+        blocks.extend_from_slice(&compressed);
+        (offset as u32, compressed.len() as u32, orig_len as u32)
+    }
+
+    /// Append the private data, returning its offset and length.
+    fn append_private_data(&self, blocks: &mut Vec<u8>, blocks_start: usize) -> (u32, u32) {
+        let Some(data) = self.private_data.as_ref() else {
+            return (0, 0);
+        };
+        pad_to_4(blocks);
+        let offset = blocks_start + blocks.len();
+        blocks.extend_from_slice(data);
+        (offset as u32, data.len() as u32)
+    }
+}
+
+/// The size of the uncompressed font, with tables of the given lengths.
+fn sfnt_size(table_lens: impl Iterator<Item = usize>) -> usize {
+    let mut num_tables = 0;
+    let mut size = SFNT_HEADER_LEN;
+    for len in table_lens {
+        num_tables += 1;
+        size += (len + 3) & !3;
+    }
+    size + SFNT_TABLE_RECORD_LEN * num_tables
+}
+
+fn brotli_compress(data: &[u8], is_font: bool) -> Result<Vec<u8>, WoffError> {
+    use brotli::enc::{backward_references::BrotliEncoderMode, BrotliEncoderParams};
+    let params = BrotliEncoderParams {
+        quality: BROTLI_QUALITY,
+        lgwin: BROTLI_WINDOW_BITS,
+        size_hint: data.len(),
+        mode: if is_font {
+            BrotliEncoderMode::BROTLI_MODE_FONT
+        } else {
+            BrotliEncoderMode::BROTLI_MODE_GENERIC
+        },
+        ..Default::default()
+    };
+    let mut out = Vec::new();
+    brotli::BrotliCompress(&mut &data[..], &mut out, &params)?;
+    Ok(out)
+}
+
+/// Write the flags byte, and the tag if it is not a known tag.
+fn push_woff2_tag(out: &mut Vec<u8>, tag: Tag) {
+    match WOFF2_KNOWN_TAGS
+        .iter()
+        .position(|known| Tag::new(*known) == tag)
+    {
+        Some(index) => out.push(index as u8),
+        None => {
+            out.push(WOFF2_ARBITRARY_TAG);
+            out.extend_from_slice(&tag.to_be_bytes());
+        }
+    }
+}
+
+/// The streams of the transformed `glyf` table.
+#[derive(Default)]
+struct GlyfStreams {
+    n_contours: Vec<u8>,
+    n_points: Vec<u8>,
+    flags: Vec<u8>,
+    glyphs: Vec<u8>,
+    composites: Vec<u8>,
+    bbox_bitmap: Vec<u8>,
+    bboxes: Vec<u8>,
+    instructions: Vec<u8>,
+    overlap_bitmap: Vec<u8>,
+    has_overlaps: bool,
+}
+
+/// Apply the WOFF2 `glyf` table transform.
+fn transform_glyf_table(font: &FontRef) -> Result<Vec<u8>, ReadError> {
+    let num_glyphs = font.maxp()?.num_glyphs();
+    let index_format = font.head()?.index_to_loc_format();
+    let loca = font.loca(None)?;
+    let glyf = font.glyf()?;
+
+    let bitmap_len = 4 * (num_glyphs as usize).div_ceil(32);
+    let mut streams = GlyfStreams {
+        bbox_bitmap: vec![0; bitmap_len],
+        overlap_bitmap: vec![0; (num_glyphs as usize).div_ceil(8)],
+        ..Default::default()
+    };
+    for gid in 0..num_glyphs {
+        match loca.get_glyf(GlyphId::new(gid), &glyf)? {
+            None => streams.n_contours.extend_from_slice(&0i16.to_be_bytes()),
+            Some(Glyph::Simple(glyph)) => streams.add_simple(gid, &glyph)?,
+            Some(Glyph::Composite(glyph)) => streams.add_composite(gid, &glyph),
+        }
+    }
+
+    let mut bbox_stream = streams.bbox_bitmap;
+    bbox_stream.extend_from_slice(&streams.bboxes);
+    // the overlap bitmap is only written if some glyph has overlaps
+    let option_flags = streams.has_overlaps as u16;
+    let mut out = Vec::new();
+    // reserved
+    push_u16(&mut out, 0);
+    push_u16(&mut out, option_flags);
+    push_u16(&mut out, num_glyphs);
+    push_u16(&mut out, index_format as u16);
+    for stream in [
+        &streams.n_contours,
+        &streams.n_points,
+        &streams.flags,
+        &streams.glyphs,
+        &streams.composites,
+        &bbox_stream,
+        &streams.instructions,
+    ] {
+        push_u32(&mut out, stream.len() as u32);
+    }
+    for stream in [
+        &streams.n_contours,
+        &streams.n_points,
+        &streams.flags,
+        &streams.glyphs,
+        &streams.composites,
+        &bbox_stream,
+        &streams.instructions,
+    ] {
+        out.extend_from_slice(stream);
+    }
+    if streams.has_overlaps {
+        out.extend_from_slice(&streams.overlap_bitmap);
+    }
+    Ok(out)
+}
+
+impl GlyfStreams {
+    fn add_simple(&mut self, gid: u16, glyph: &SimpleGlyph) -> Result<(), ReadError> {
+        let end_pts = glyph.end_pts_of_contours();
+        let n_points = end_pts.last().map(|pt| pt.get() as usize + 1).unwrap_or(0);
+        let points = glyph.points().collect::<Vec<_>>();
+        if points.len() != n_points {
+            return Err(ReadError::MalformedData("bad point data in simple glyph"));
+        }
+
+        self.n_contours
+            .extend_from_slice(&(end_pts.len() as i16).to_be_bytes());
+        let mut prev_end = -1i32;
+        for end in end_pts {
+            let end = end.get() as i32;
+            push_255_u16(&mut self.n_points, (end - prev_end) as u16);
+            prev_end = end;
+        }
+
+        let (mut x, mut y) = (0i32, 0i32);
+        let mut bbox = None;
+        for point in &points {
+            let (px, py) = (point.x as i32, point.y as i32);
+            self.push_triplet(px - x, py - y, point.on_curve);
+            (x, y) = (px, py);
+            let (x_min, y_min, x_max, y_max) = bbox.get_or_insert((px, py, px, py));
+            *x_min = px.min(*x_min);
+            *y_min = py.min(*y_min);
+            *x_max = px.max(*x_max);
+            *y_max = py.max(*y_max);
+        }
+
+        let instructions = glyph.instructions();
+        push_255_u16(&mut self.glyphs, instructions.len() as u16);
+        self.instructions.extend_from_slice(instructions);
+
+        // the bbox is only stored if it can't be computed from the points
+        let header_bbox = (
+            glyph.x_min() as i32,
+            glyph.y_min() as i32,
+            glyph.x_max() as i32,
+            glyph.y_max() as i32,
+        );
+        if bbox != Some(header_bbox) {
+            self.push_bbox(gid, header_bbox);
+        }
+        // the first flag of the original glyph data carries OVERLAP_SIMPLE
+        let overlaps = glyph
+            .glyph_data()
+            .first()
+            .map(|flag| flag & OVERLAP_SIMPLE != 0)
+            .unwrap_or(false);
+        if overlaps {
+            self.has_overlaps = true;
+            self.overlap_bitmap[gid as usize / 8] |= 0x80 >> (gid % 8);
+        }
+        Ok(())
+    }
+
+    fn add_composite(&mut self, gid: u16, glyph: &CompositeGlyph) {
+        self.n_contours.extend_from_slice(&(-1i16).to_be_bytes());
+        let data = glyph.component_data();
+        let components_len = match glyph.instructions() {
+            Some(instructions) => {
+                push_255_u16(&mut self.glyphs, instructions.len() as u16);
+                self.instructions.extend_from_slice(instructions);
+                data.len() - instructions.len() - 2
+            }
+            None => data.len(),
+        };
+        self.composites.extend_from_slice(&data[..components_len]);
+        // composite glyphs always have an explicit bbox
+        self.push_bbox(
+            gid,
+            (
+                glyph.x_min() as i32,
+                glyph.y_min() as i32,
+                glyph.x_max() as i32,
+                glyph.y_max() as i32,
+            ),
+        );
+    }
+
+    fn push_bbox(&mut self, gid: u16, (x_min, y_min, x_max, y_max): (i32, i32, i32, i32)) {
+        self.bbox_bitmap[gid as usize / 8] |= 0x80 >> (gid % 8);
+        for value in [x_min, y_min, x_max, y_max] {
+            self.bboxes.extend_from_slice(&(value as i16).to_be_bytes());
+        }
+    }
+
+    /// Encode a point delta using the triplet encoding from section 5.2 of
+    /// the specification.
+    fn push_triplet(&mut self, dx: i32, dy: i32, on_curve: bool) {
+        let (abs_x, abs_y) = (dx.unsigned_abs(), dy.unsigned_abs());
+        let on_bit = if on_curve { 0 } else { 128 };
+        let x_sign = if dx < 0 { 0 } else { 1 };
+        let y_sign = if dy < 0 { 0 } else { 1 };
+        let xy_sign = x_sign + 2 * y_sign;
+        let glyphs = &mut self.glyphs;
+        let flag = if dx == 0 && abs_y < 1280 {
+            glyphs.push(abs_y as u8);
+            on_bit + ((abs_y & 0xf00) >> 7) + y_sign
+        } else if dy == 0 && abs_x < 1280 {
+            glyphs.push(abs_x as u8);
+            on_bit + 10 + ((abs_x & 0xf00) >> 7) + x_sign
+        } else if abs_x < 65 && abs_y < 65 {
+            glyphs.push(((((abs_x - 1) & 0xf) << 4) | ((abs_y - 1) & 0xf)) as u8);
+            on_bit + 20 + ((abs_x - 1) & 0x30) + (((abs_y - 1) & 0x30) >> 2) + xy_sign
+        } else if abs_x < 769 && abs_y < 769 {
+            glyphs.push((abs_x - 1) as u8);
+            glyphs.push((abs_y - 1) as u8);
+            on_bit + 84 + 12 * (((abs_x - 1) & 0x300) >> 8) + (((abs_y - 1) & 0x300) >> 6) + xy_sign
+        } else if abs_x < 4096 && abs_y < 4096 {
+            glyphs.push((abs_x >> 4) as u8);
+            glyphs.push((((abs_x & 0xf) << 4) | (abs_y >> 8)) as u8);
+            glyphs.push(abs_y as u8);
+            on_bit + 120 + xy_sign
+        } else {
+            glyphs.extend_from_slice(&(abs_x as u16).to_be_bytes());
+            glyphs.extend_from_slice(&(abs_y as u16).to_be_bytes());
+            on_bit + 124 + xy_sign
+        };
+        self.flags.push(flag as u8);
+    }
+}
+
+/// The `OVERLAP_SIMPLE` flag of a simple glyph.
+const OVERLAP_SIMPLE: u8 = 0x40;
+
+fn push_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+fn push_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+fn pad_to_4(out: &mut Vec<u8>) {
+    out.resize((out.len() + 3) & !3, 0);
+}
+
+/// Write a value in the `UIntBase128` encoding.
+fn push_base128(out: &mut Vec<u8>, value: u32) {
+    let n_bytes = (1..5).find(|n| value >> (7 * n) == 0).unwrap_or(5);
+    for i in (0..n_bytes).rev() {
+        let byte = ((value >> (7 * i)) & 0x7f) as u8;
+        out.push(if i == 0 { byte } else { byte | 0x80 });
+    }
+}
+
+/// Write a value in the `255UInt16` encoding.
+fn push_255_u16(out: &mut Vec<u8>, value: u16) {
+    const ONE_MORE_BYTE_CODE1: u8 = 255;
+    const ONE_MORE_BYTE_CODE2: u8 = 254;
+    const WORD_CODE: u8 = 253;
+    const LOWEST_U_CODE: u16 = 253;
+    match value {
+        0..=252 => out.push(value as u8),
+        253..=505 => out.extend([ONE_MORE_BYTE_CODE1, (value - LOWEST_U_CODE) as u8]),
+        506..=761 => out.extend([ONE_MORE_BYTE_CODE2, (value - 2 * LOWEST_U_CODE) as u8]),
+        _ => {
+            out.push(WORD_CODE);
+            push_u16(out, value);
+        }
+    }
+}
+
+impl From<ReadError> for WoffError {
+    fn from(src: ReadError) -> WoffError {
+        WoffError::Read(src)
+    }
+}
+
+impl From<std::io::Error> for WoffError {
+    fn from(src: std::io::Error) -> WoffError {
+        WoffError::Compression(src)
+    }
+}
+
+impl Display for WoffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WoffError::Read(error) => write!(f, "failed to read font: {error}"),
+            WoffError::Compression(error) => write!(f, "compression failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for WoffError {}
+
+#[cfg(test)]
+mod tests {
+    use read_fonts::test_data::test_fonts;
+
+    use super::*;
+
+    fn read_u16(data: &[u8], pos: usize) -> u16 {
+        u16::from_be_bytes(data[pos..pos + 2].try_into().unwrap())
+    }
+
+    fn read_u32(data: &[u8], pos: usize) -> u32 {
+        u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap())
+    }
+
+    fn read_base128(data: &[u8], pos: &mut usize) -> u32 {
+        let mut value = 0;
+        loop {
+            let byte = data[*pos];
+            *pos += 1;
+            value = (value << 7) | (byte & 0x7f) as u32;
+            if byte & 0x80 == 0 {
+                return value;
+            }
+        }
+    }
+
+    #[test]
+    fn base128_and_255_u16() {
+        let mut out = Vec::new();
+        push_base128(&mut out, 63);
+        push_base128(&mut out, 0x3fff);
+        push_base128(&mut out, 0x4000);
+        assert_eq!(out, [0x3f, 0xff, 0x7f, 0x81, 0x80, 0x00]);
+        let mut out = Vec::new();
+        for value in [252, 253, 506, 762] {
+            push_255_u16(&mut out, value);
+        }
+        assert_eq!(out, [252, 255, 0, 254, 0, 253, 0x02, 0xfa]);
+    }
+
+    fn read_255_u16(data: &[u8], pos: &mut usize) -> u16 {
+        let code = data[*pos];
+        *pos += 1;
+        let (value, len) = match code {
+            253 => (read_u16(data, *pos), 2),
+            254 => (data[*pos] as u16 + 506, 1),
+            255 => (data[*pos] as u16 + 253, 1),
+            _ => (code as u16, 0),
+        };
+        *pos += len;
+        value
+    }
+
+    /// Decode a triplet-encoded point delta, returning (dx, dy, on_curve).
+    fn read_triplet(flag: u8, data: &[u8], pos: &mut usize) -> (i32, i32, bool) {
+        let on_curve = flag & 0x80 == 0;
+        let flag = (flag & 0x7f) as i32;
+        let with_sign = |flag: i32, value: i32| if flag & 1 != 0 { value } else { -value };
+        let len = match flag {
+            0..=83 => 1,
+            84..=119 => 2,
+            120..=123 => 3,
+            _ => 4,
+        };
+        let b = |i: usize| data[*pos + i] as i32;
+        let (dx, dy) = match flag {
+            0..=9 => (0, with_sign(flag, ((flag & 14) << 7) + b(0))),
+            10..=19 => (with_sign(flag, (((flag - 10) & 14) << 7) + b(0)), 0),
+            20..=83 => {
+                let b0 = flag - 20;
+                (
+                    with_sign(flag, 1 + (b0 & 0x30) + (b(0) >> 4)),
+                    with_sign(flag >> 1, 1 + ((b0 & 0x0c) << 2) + (b(0) & 0x0f)),
+                )
+            }
+            84..=119 => {
+                let b0 = flag - 84;
+                (
+                    with_sign(flag, 1 + ((b0 / 12) << 8) + b(0)),
+                    with_sign(flag >> 1, 1 + (((b0 % 12) >> 2) << 8) + b(1)),
+                )
+            }
+            120..=123 => (
+                with_sign(flag, (b(0) << 4) + (b(1) >> 4)),
+                with_sign(flag >> 1, ((b(1) & 0xf) << 8) + b(2)),
+            ),
+            _ => (
+                with_sign(flag, (b(0) << 8) + b(1)),
+                with_sign(flag >> 1, (b(2) << 8) + b(3)),
+            ),
+        };
+        *pos += len;
+        (dx, dy, on_curve)
+    }
+
+    #[test]
+    fn triplets() {
+        let deltas = [
+            (0, 0),
+            (0, -1279),
+            (1279, 0),
+            (-64, 64),
+            (1, -1),
+            (768, -700),
+            (-65, 3),
+            (4095, -4095),
+            (-4096, 20000),
+        ];
+        let mut streams = GlyfStreams::default();
+        for (i, (dx, dy)) in deltas.iter().enumerate() {
+            streams.push_triplet(*dx, *dy, i % 2 == 0);
+        }
+        let mut pos = 0;
+        for (i, (flag, delta)) in streams.flags.iter().zip(deltas).enumerate() {
+            let decoded = read_triplet(*flag, &streams.glyphs, &mut pos);
+            assert_eq!(decoded, (delta.0, delta.1, i % 2 == 0));
+        }
+        assert_eq!(pos, streams.glyphs.len());
+    }
+
+    #[test]
+    fn woff() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let woff = WoffEncoder::new(font.clone())
+            .metadata("<metadata version=\"1.0\"/>")
+            .private_data(vec![1, 2, 3])
+            .encode_woff()
+            .unwrap();
+        assert_eq!(&woff[..4], b"wOFF");
+        assert_eq!(read_u32(&woff, 8) as usize, woff.len());
+        let num_tables = read_u16(&woff, 12) as usize;
+        assert_eq!(num_tables, font.table_records().count());
+        assert_eq!(read_u32(&woff, 16) as usize, {
+            let records = font.table_records();
+            12 + 16 * num_tables
+                + records
+                    .map(|rec| (rec.length() as usize + 3) & !3)
+                    .sum::<usize>()
+        });
+
+        for i in 0..num_tables {
+            let entry = WOFF_HEADER_LEN + i * WOFF_TABLE_ENTRY_LEN;
+            let tag = Tag::new(&woff[entry..entry + 4]);
+            let offset = read_u32(&woff, entry + 4) as usize;
+            let comp_len = read_u32(&woff, entry + 8) as usize;
+            let orig_len = read_u32(&woff, entry + 12) as usize;
+            assert_eq!(offset % 4, 0);
+            let stored = &woff[offset..offset + comp_len];
+            let data = if comp_len < orig_len {
+                miniz_oxide::inflate::decompress_to_vec_zlib(stored).unwrap()
+            } else {
+                stored.to_vec()
+            };
+            assert_eq!(data, font.table_data(tag).unwrap().as_ref(), "{tag}");
+        }
+
+        let meta_offset = read_u32(&woff, 24) as usize;
+        let meta_len = read_u32(&woff, 28) as usize;
+        assert_eq!(read_u32(&woff, 32), 25);
+        let meta =
+            miniz_oxide::inflate::decompress_to_vec_zlib(&woff[meta_offset..][..meta_len]).unwrap();
+        assert_eq!(meta, b"<metadata version=\"1.0\"/>");
+        let priv_offset = read_u32(&woff, 36) as usize;
+        assert_eq!(priv_offset % 4, 0);
+        assert_eq!(&woff[priv_offset..], [1, 2, 3]);
+    }
+
+    #[test]
+    fn woff2() {
+        let font = FontRef::new(test_fonts::VAZIRMATN_VAR).unwrap();
+        let woff2 = WoffEncoder::new(font.clone()).encode_woff2().unwrap();
+        assert_eq!(&woff2[..4], b"wOF2");
+        assert_eq!(read_u32(&woff2, 8) as usize, woff2.len());
+        let num_tables = read_u16(&woff2, 12) as usize;
+        assert_eq!(num_tables, font.table_records().count());
+        let compressed_len = read_u32(&woff2, 20) as usize;
+
+        // read the directory
+        let mut pos = WOFF2_HEADER_LEN;
+        let mut entries = Vec::new();
+        for _ in 0..num_tables {
+            let flags = woff2[pos];
+            pos += 1;
+            let tag = match flags & 0x3f {
+                WOFF2_ARBITRARY_TAG => {
+                    pos += 4;
+                    Tag::new(&woff2[pos - 4..pos])
+                }
+                index => Tag::new(WOFF2_KNOWN_TAGS[index as usize]),
+            };
+            let orig_len = read_base128(&woff2, &mut pos);
+            let transformed = flags >> 6 == 0 && (tag == Tag::GLYF || tag == Tag::LOCA);
+            let len = if transformed {
+                read_base128(&woff2, &mut pos)
+            } else {
+                orig_len
+            };
+            entries.push((tag, orig_len, len));
+        }
+        let glyf_pos = entries.iter().position(|e| e.0 == Tag::GLYF).unwrap();
+        assert_eq!(entries[glyf_pos + 1].0, Tag::LOCA);
+        assert_eq!(entries[glyf_pos + 1].2, 0);
+
+        let mut stream = Vec::new();
+        let mut compressed = &woff2[pos..pos + compressed_len];
+        brotli::BrotliDecompress(&mut compressed, &mut stream).unwrap();
+        assert_eq!(
+            stream.len(),
+            entries.iter().map(|e| e.2 as usize).sum::<usize>()
+        );
+
+        let mut offset = 0;
+        for (tag, orig_len, len) in entries {
+            let data = &stream[offset..offset + len as usize];
+            offset += len as usize;
+            let orig = font.table_data(tag).unwrap();
+            assert_eq!(orig_len as usize, orig.len());
+            match tag {
+                Tag::GLYF => {
+                    let num_glyphs = font.maxp().unwrap().num_glyphs();
+                    let index_format = font.head().unwrap().index_to_loc_format();
+                    assert_eq!(read_u16(data, 4), num_glyphs);
+                    assert_eq!(read_u16(data, 6), index_format as u16);
+                    let mut starts = vec![36];
+                    for i in 0..7 {
+                        starts.push(starts[i] + read_u32(data, 8 + 4 * i) as usize);
+                    }
+                    assert_eq!(starts[7], data.len());
+                    check_glyph_streams(&font, data, &starts);
+                }
+                Tag::LOCA => (),
+                Tag::HEAD => {
+                    assert_eq!(read_u16(data, 16), read_u16(orig.as_ref(), 16) | 1 << 11);
+                }
+                _ => assert_eq!(data, orig.as_ref(), "{tag}"),
+            }
+        }
+    }
+
+    /// Decode the contour, point and glyph streams of a transformed `glyf`
+    /// table, and compare them with the glyphs in the font.
+    fn check_glyph_streams(font: &FontRef, data: &[u8], starts: &[usize]) {
+        let loca = font.loca(None).unwrap();
+        let glyf = font.glyf().unwrap();
+        let (mut n_points_pos, mut flag_pos, mut glyph_pos) = (starts[1], starts[2], starts[3]);
+        let mut n_simple = 0;
+        for gid in 0..font.maxp().unwrap().num_glyphs() {
+            let n_contours = read_u16(data, starts[0] + 2 * gid as usize) as i16;
+            match loca.get_glyf(GlyphId::new(gid), &glyf).unwrap() {
+                None => assert_eq!(n_contours, 0),
+                Some(Glyph::Composite(glyph)) => {
+                    assert_eq!(n_contours, -1);
+                    if let Some(instructions) = glyph.instructions() {
+                        let len = read_255_u16(data, &mut glyph_pos);
+                        assert_eq!(len as usize, instructions.len());
+                    }
+                }
+                Some(Glyph::Simple(glyph)) => {
+                    n_simple += 1;
+                    assert_eq!(n_contours as usize, glyph.end_pts_of_contours().len());
+                    let n_points = (0..n_contours)
+                        .map(|_| read_255_u16(data, &mut n_points_pos) as usize)
+                        .sum::<usize>();
+                    let (mut x, mut y) = (0, 0);
+                    let mut points = Vec::new();
+                    for _ in 0..n_points {
+                        let (dx, dy, on_curve) = read_triplet(data[flag_pos], data, &mut glyph_pos);
+                        flag_pos += 1;
+                        (x, y) = (x + dx, y + dy);
+                        points.push((x, y, on_curve));
+                    }
+                    let expected = glyph
+                        .points()
+                        .map(|pt| (pt.x as i32, pt.y as i32, pt.on_curve))
+                        .collect::<Vec<_>>();
+                    assert_eq!(points, expected, "glyph {gid}");
+                    let len = read_255_u16(data, &mut glyph_pos);
+                    assert_eq!(len as usize, glyph.instructions().len());
+                }
+            }
+        }
+        assert!(n_simple > 0);
+        assert_eq!(n_points_pos, starts[2]);
+        assert_eq!(flag_pos, starts[3]);
+        assert_eq!(glyph_pos, starts[4]);
+    }
+}
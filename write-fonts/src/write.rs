@@ -1,8 +1,9 @@
 use std::collections::{BTreeSet, HashMap};
 
-use crate::graph::{Graph, ObjectId, ObjectStore, OffsetLen};
-use crate::validate::{Validate, ValidationReport};
-use types::Uint24;
+use crate::error::{Error, PackingError};
+use crate::graph::{Graph, GraphDump, ObjectId, ObjectStore, OffsetLen};
+use crate::validate::Validate;
+use types::{Tag, Uint24};
 
 /// A type that that can be written out as part of a font file.
 ///
@@ -11,6 +12,25 @@ use types::Uint24;
 pub trait FontWrite {
     /// Write our data and information about offsets into this [TableWriter].
     fn write_into(&self, writer: &mut TableWriter);
+
+    /// The type of this table, if it is relevant to serialization.
+    ///
+    /// This is used when resolving offset overflows, where knowing what a
+    /// table is allows us to make structural changes such as promoting
+    /// lookups to extension lookups.
+    fn table_type(&self) -> TableType {
+        TableType::Unknown
+    }
+}
+
+/// The type of a table, as understood by the serializer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TableType {
+    /// A top-level table, identified by its tag.
+    TopLevel(Tag),
+    /// Any other table.
+    #[default]
+    Unknown,
 }
 
 /// An object that manages a collection of serialized tables.
@@ -36,17 +56,42 @@ pub struct TableWriter {
 
 /// Attempt to serialize a table.
 ///
-/// If the table is malformed, this will return an Err([`ValidationReport`]),
-/// otherwise it will return the bytes encoding the table.
-pub fn dump_table<T: FontWrite + Validate>(table: &T) -> Result<Vec<u8>, ValidationReport> {
+/// If the table is malformed, this will return [`Error::ValidationFailed`];
+/// if it contains offsets that overflow even after we have tried to
+/// reorganize it, this will return [`Error::PackingFailed`]. Otherwise it
+/// will return the bytes encoding the table.
+pub fn dump_table<T: FontWrite + Validate>(table: &T) -> Result<Vec<u8>, Error> {
     table.validate()?;
     let mut writer = TableWriter::default();
+    writer.stack.last_mut().unwrap().type_ = table.table_type();
     table.write_into(&mut writer);
     let mut graph = writer.finish();
-    graph.topological_sort();
+    if !graph.pack_objects() {
+        return Err(Error::PackingFailed(PackingError {
+            table_type: table.table_type(),
+        }));
+    }
     Ok(dump_impl(&graph.order, &graph.objects))
 }
 
+/// Describe the object graph of a table, as it is packed by [`dump_table`].
+///
+/// This is intended for debugging serialization: the result records the size
+/// and position of each subtable and the offsets between them, and can be
+/// written as Graphviz or JSON. Unlike [`dump_table`], this succeeds if some
+/// offsets overflow; they are marked in the result.
+///
+/// If the table is malformed, this will return [`Error::ValidationFailed`].
+pub fn dump_table_graph<T: FontWrite + Validate>(table: &T) -> Result<GraphDump, Error> {
+    table.validate()?;
+    let mut writer = TableWriter::default();
+    writer.stack.last_mut().unwrap().type_ = table.table_type();
+    table.write_into(&mut writer);
+    let mut graph = writer.finish();
+    graph.pack_objects();
+    Ok(GraphDump::new(&graph))
+}
+
 fn dump_impl(order: &[ObjectId], nodes: &HashMap<ObjectId, TableData>) -> Vec<u8> {
     let mut offsets = HashMap::new();
     let mut out = Vec::new();
@@ -69,6 +114,7 @@ fn dump_impl(order: &[ObjectId], nodes: &HashMap<ObjectId, TableData>) -> Vec<u8
             let rel_off = abs_off - (table_head + offset.adjustment);
             let buffer_pos = table_head + offset.pos;
             let write_over = out.get_mut(buffer_pos as usize..).unwrap();
+            // packing has already ensured that all offsets fit
             write_offset(write_over, offset.len, rel_off).unwrap();
         }
         table_head += node.bytes.len() as u32;
@@ -99,7 +145,7 @@ fn write_offset(at: &mut [u8], len: OffsetLen, resolved: u32) -> Result<(), ()>
 
 impl TableWriter {
     fn add_table(&mut self, table: &dyn FontWrite) -> ObjectId {
-        self.stack.push(TableData::default());
+        self.stack.push(TableData::new(table.table_type()));
         table.write_into(self);
         self.tables.add(self.stack.pop().unwrap())
     }
@@ -167,6 +213,7 @@ impl Default for TableWriter {
 /// The encoded data for a given table, along with info on included offsets
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
 pub(crate) struct TableData {
+    pub(crate) type_: TableType,
     pub(crate) bytes: Vec<u8>,
     pub(crate) offsets: Vec<OffsetRecord>,
 }
@@ -175,7 +222,7 @@ pub(crate) struct TableData {
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub(crate) struct OffsetRecord {
     /// the position of the offset within the parent table
-    pos: u32,
+    pub(crate) pos: u32,
     /// the offset length in bytes
     pub(crate) len: OffsetLen,
     /// The object pointed to by the offset
@@ -189,8 +236,15 @@ pub(crate) struct OffsetRecord {
 }
 
 impl TableData {
+    pub(crate) fn new(type_: TableType) -> Self {
+        TableData {
+            type_,
+            ..Default::default()
+        }
+    }
+
     /// the 'adjustment' param is used to modify the written position.
-    fn add_offset(&mut self, object: ObjectId, width: usize, adjustment: u32) {
+    pub(crate) fn add_offset(&mut self, object: ObjectId, width: usize, adjustment: u32) {
         self.offsets.push(OffsetRecord {
             pos: self.bytes.len() as u32,
             len: match width {
@@ -213,6 +267,7 @@ impl TableData {
     #[cfg(test)]
     pub fn make_mock(size: usize) -> Self {
         TableData {
+            type_: TableType::Unknown,
             bytes: vec![0xca; size], // has no special meaning
             offsets: Vec::new(),
         }
@@ -250,6 +305,7 @@ write_be_bytes!(u32);
 write_be_bytes!(i32);
 write_be_bytes!(i64);
 write_be_bytes!(types::Uint24);
+write_be_bytes!(types::Int24);
 write_be_bytes!(types::F2Dot14);
 write_be_bytes!(types::Fixed);
 write_be_bytes!(types::FWord);
@@ -259,6 +315,8 @@ write_be_bytes!(types::Tag);
 write_be_bytes!(types::Version16Dot16);
 write_be_bytes!(types::MajorMinor);
 write_be_bytes!(types::GlyphId);
+write_be_bytes!(types::GlyphId24);
+write_be_bytes!(types::NameId);
 
 impl<T: FontWrite> FontWrite for [T] {
     fn write_into(&self, writer: &mut TableWriter) {